@@ -0,0 +1,38 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tari_dan_app_utilities::template_manager::implementation::{TemplateConfig, TemplateManager};
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_dan_engine::wasm::WasmModule;
+use tari_template_builtin::{get_template_builtin, ACCOUNT_TEMPLATE_ADDRESS};
+use tari_template_lib::models::TemplateAddress;
+
+#[path = "../src/template_manager/implementation/test_fixtures.rs"]
+mod test_fixtures;
+
+use test_fixtures::create_manager_with_template;
+
+fn template_cache(c: &mut Criterion) {
+    let address = TemplateAddress::from_array([1u8; 32]);
+    let code = get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec();
+    let (manager, _temp) = create_manager_with_template(address, code.clone());
+
+    let mut group = c.benchmark_group("template_cache");
+
+    // Compiling the module is what the cache avoids on every execution
+    group.bench_function("compile", |b| {
+        b.iter(|| WasmModule::from_code(code.clone()).load_template().unwrap())
+    });
+
+    group.bench_function("cached", |b| {
+        // Warm the cache
+        manager.get_template_module(&address).unwrap().unwrap();
+        b.iter(|| manager.get_template_module(&address).unwrap().unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, template_cache);
+criterion_main!(benches);
@@ -44,9 +44,9 @@ use tari_crypto::{
     ristretto::RistrettoPublicKey,
     tari_utilities::{hex::Hex, ByteArray},
 };
-use tari_dan_common_types::{optional::Optional, Epoch, NodeAddressable, NodeHeight};
+use tari_dan_common_types::{optional::Optional, NodeAddressable, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{Block, SubstateRecord},
+    consensus_models::{Block, GenesisConfig, SubstateRecord},
     global::{GlobalDb, MetadataKey},
     StateStore,
     StorageError,
@@ -71,6 +71,7 @@ const LOG_TARGET: &str = "tari::dan::base_layer_scanner";
 
 pub fn spawn<TAddr: NodeAddressable + 'static>(
     network: Network,
+    genesis: GenesisConfig,
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
     base_node_client: GrpcBaseNodeClient,
     epoch_manager: EpochManagerHandle<TAddr>,
@@ -87,6 +88,7 @@ pub fn spawn<TAddr: NodeAddressable + 'static>(
     task::spawn(async move {
         let base_layer_scanner = BaseLayerScanner::new(
             network,
+            genesis,
             global_db,
             base_node_client,
             epoch_manager,
@@ -108,6 +110,7 @@ pub fn spawn<TAddr: NodeAddressable + 'static>(
 
 pub struct BaseLayerScanner<TAddr> {
     network: Network,
+    genesis: GenesisConfig,
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
     last_scanned_height: u64,
     last_scanned_tip: Option<FixedHash>,
@@ -130,6 +133,7 @@ pub struct BaseLayerScanner<TAddr> {
 impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
     pub fn new(
         network: Network,
+        genesis: GenesisConfig,
         global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
         base_node_client: GrpcBaseNodeClient,
         epoch_manager: EpochManagerHandle<TAddr>,
@@ -145,6 +149,7 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
     ) -> Self {
         Self {
             network,
+            genesis,
             global_db,
             last_scanned_tip: None,
             last_scanned_height: 0,
@@ -451,7 +456,8 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
         });
         self.state_store
             .with_write_tx(|tx| {
-                let genesis = Block::genesis(self.network);
+                // The genesis block must be the same on all nodes
+                let genesis = Block::genesis(self.network, &self.genesis, 0);
 
                 // TODO: This should be proposed in a block...
                 SubstateRecord {
@@ -463,7 +469,7 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
                     created_justify: *genesis.justify().id(),
                     created_block: *genesis.id(),
                     created_height: NodeHeight::zero(),
-                    created_at_epoch: Epoch(0),
+                    created_at_epoch: genesis.epoch(),
                     destroyed: None,
                 }
                 .create(tx)
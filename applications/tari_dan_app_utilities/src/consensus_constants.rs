@@ -20,12 +20,71 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::time::Duration;
+
+use tari_common::configuration::Network;
+use tari_dan_common_types::NodeHeight;
+use tari_dan_engine::{limits::SubstateSizeLimits, transaction::DEFAULT_EXECUTION_BUDGET};
+
 #[derive(Clone, Debug)]
 pub struct ConsensusConstants {
     pub base_layer_confirmations: u64,
     pub committee_size: u32,
     pub max_base_layer_blocks_ahead: u64,
     pub max_base_layer_blocks_behind: u64,
+    /// The maximum encoded size in bytes of a single substate written by a transaction
+    pub max_substate_size_bytes: usize,
+    /// The maximum combined encoded size in bytes of all substates written by a transaction
+    pub max_transaction_substates_size_bytes: usize,
+    /// The number of blocks after a foreign proposal is proposed after which its transactions that have not been
+    /// prepared locally are aborted
+    pub foreign_proposal_timeout: NodeHeight,
+    /// The maximum number of dummy blocks that are created to fill the gap left by failed leaders before a proposal
+    pub max_dummy_blocks: u64,
+    /// The maximum estimated size in bytes of the commands in a proposed block. This must leave room for the block
+    /// header within the network message size limit.
+    pub max_block_size_bytes: usize,
+    /// The maximum number of transactions that are taken from the transaction pool for a proposed block
+    pub max_block_transactions: usize,
+    /// The number of epochs for which rejected proposals are kept for inspection
+    pub block_rejection_retention_epochs: u64,
+    /// The number of epochs for which invalid and expired foreign proposals are kept for inspection
+    pub foreign_proposal_retention_epochs: u64,
+    /// If true, proposals with a state merkle root that does not match the locally calculated root are recorded as
+    /// rejected. Otherwise, validators only abstain from voting for them.
+    pub reject_state_merkle_root_mismatch: bool,
+    /// The maximum number of verified QCs that are cached to avoid verifying the signatures of the same QC again
+    pub verified_qc_cache_size: usize,
+    /// If true, the next leader executes the deferred transactions for its proposal while the current block is being
+    /// voted on
+    pub enable_proposal_pipelining: bool,
+    /// The leader timeout when the previous view did not fail
+    pub pacemaker_base_timeout: Duration,
+    /// The leader timeout is multiplied by this factor for each consecutive failed view
+    pub pacemaker_timeout_multiplier: f64,
+    /// The maximum leader timeout after repeated failed views
+    pub pacemaker_max_timeout: Duration,
+    /// The number of times that a deferred transaction may fail to execute when it is proposed before it is aborted
+    pub max_deferred_execution_attempts: u32,
+    /// A deferred transaction that fails to execute is aborted if it was added to the pool at least this many blocks
+    /// ago
+    pub max_deferred_execution_age: NodeHeight,
+    /// If true, a proposed block never contains two transactions that lock the same substate unless both only read
+    /// it. Otherwise, conflicting transactions may be proposed together and all but one of them will abort.
+    pub exclude_conflicting_transactions: bool,
+    /// The number of metering points that a transaction may use when it is executed in a block. Transactions that
+    /// exceed it are aborted.
+    pub transaction_execution_budget: u64,
+    /// A peer is banned once its misbehavior score reaches this value. Each invalid proposal or vote adds one.
+    pub peer_ban_threshold: u64,
+    /// Consensus messages from a banned peer are dropped for this long
+    pub peer_ban_duration: Duration,
+    /// The misbehavior score of a peer is reduced by one for each interval without an offence
+    pub peer_score_decay_interval: Duration,
+    /// The maximum number of times that sending a vote to the leader is retried after the first attempt fails
+    pub max_vote_send_retries: u32,
+    /// The delay before the first vote retry, which is doubled for each subsequent retry
+    pub vote_send_retry_backoff: Duration,
 }
 
 impl ConsensusConstants {
@@ -35,6 +94,47 @@ impl ConsensusConstants {
             committee_size: 7,
             max_base_layer_blocks_ahead: 5,
             max_base_layer_blocks_behind: 5,
+            max_substate_size_bytes: 512 * 1024,
+            max_transaction_substates_size_bytes: 2 * 1024 * 1024,
+            foreign_proposal_timeout: NodeHeight(1000),
+            max_dummy_blocks: 1000,
+            max_block_size_bytes: 3 * 1024 * 1024,
+            max_block_transactions: 1000,
+            block_rejection_retention_epochs: 10,
+            foreign_proposal_retention_epochs: 10,
+            reject_state_merkle_root_mismatch: false,
+            verified_qc_cache_size: 1024,
+            enable_proposal_pipelining: true,
+            pacemaker_base_timeout: Duration::from_secs(14),
+            pacemaker_timeout_multiplier: 2.0,
+            pacemaker_max_timeout: Duration::from_secs(300),
+            max_deferred_execution_attempts: 5,
+            max_deferred_execution_age: NodeHeight(100),
+            exclude_conflicting_transactions: true,
+            transaction_execution_budget: DEFAULT_EXECUTION_BUDGET,
+            peer_ban_threshold: 10,
+            peer_ban_duration: Duration::from_secs(60 * 60),
+            peer_score_decay_interval: Duration::from_secs(10 * 60),
+            max_vote_send_retries: 3,
+            vote_send_retry_backoff: Duration::from_millis(500),
+        }
+    }
+
+    pub const fn for_network(network: Network) -> Self {
+        match network {
+            // Local networks run with few validators and are restarted often, so give up on foreign proposals sooner
+            Network::LocalNet => Self {
+                foreign_proposal_timeout: NodeHeight(100),
+                ..Self::devnet()
+            },
+            _ => Self::devnet(),
+        }
+    }
+
+    pub fn substate_size_limits(&self) -> SubstateSizeLimits {
+        SubstateSizeLimits {
+            max_substate_size: self.max_substate_size_bytes,
+            max_transaction_size: self.max_transaction_substates_size_bytes,
         }
     }
 }
@@ -2,12 +2,14 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use serde_json as json;
+use tari_dan_engine::abi::{ComponentSchema, StructDef, Type};
 use tari_engine_types::{
     commit_result::FinalizeResult,
     component::ComponentHeader,
     non_fungible::NonFungibleContainer,
     substate::{Substate, SubstateValue},
 };
+use tari_validator_node_client::types::{DecodedField, DecodedValue};
 use tari_validator_node_rpc::client::FinalizedResult;
 
 type JsonObject = json::Map<String, json::Value>;
@@ -44,17 +46,100 @@ pub fn encode_substate_into_json(substate: &Substate) -> Result<json::Value, Jso
     let mut result = json::to_value(substate_cbor)?;
 
     let substate_field = get_mut_json_field(&mut result, "substate")?;
-    match substate.substate_value() {
+    fix_substate_value_for_json(substate.substate_value(), substate_field)?;
+
+    Ok(result)
+}
+
+pub fn encode_substate_value_into_json(value: &SubstateValue) -> Result<json::Value, JsonEncodingError> {
+    let value_cbor = tari_bor::to_value(value)?;
+    let value_cbor = fix_invalid_object_keys(&value_cbor);
+    let mut result = json::to_value(value_cbor)?;
+    fix_substate_value_for_json(value, &mut result)?;
+
+    Ok(result)
+}
+
+/// Decodes the substate value into typed fields using the component schema of its template. Substates that are not
+/// components, or components whose state does not match the schema, are returned as raw JSON.
+pub fn decode_substate_value(
+    value: &SubstateValue,
+    schema: Option<&ComponentSchema>,
+) -> Result<DecodedValue, JsonEncodingError> {
+    if let (SubstateValue::Component(header), Some(schema)) = (value, schema) {
+        if let Some(decoded) = decode_struct(&schema.component, header.state(), schema)? {
+            return Ok(decoded);
+        }
+    }
+
+    Ok(DecodedValue::Raw(encode_substate_value_into_json(value)?))
+}
+
+fn decode_struct(
+    def: &StructDef,
+    value: &CborValue,
+    schema: &ComponentSchema,
+) -> Result<Option<DecodedValue>, JsonEncodingError> {
+    let is_newtype = def.fields.len() == 1 && def.fields[0].name == "0";
+    let mut fields = Vec::with_capacity(def.fields.len());
+    for (i, field) in def.fields.iter().enumerate() {
+        let field_value = match value {
+            // Newtype structs are encoded as their inner value
+            _ if is_newtype => Some(value),
+            CborValue::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k.as_text() == Some(field.name.as_str()))
+                .map(|(_, v)| v),
+            CborValue::Array(items) => items.get(i),
+            _ => None,
+        };
+        let Some(field_value) = field_value else {
+            return Ok(None);
+        };
+
+        fields.push(DecodedField {
+            name: field.name.clone(),
+            field_type: field.field_type.to_string(),
+            value: decode_field_value(&field.field_type, field_value, schema)?,
+        });
+    }
+
+    Ok(Some(DecodedValue::Struct {
+        name: def.name.clone(),
+        fields,
+    }))
+}
+
+fn decode_field_value(
+    field_type: &Type,
+    value: &CborValue,
+    schema: &ComponentSchema,
+) -> Result<DecodedValue, JsonEncodingError> {
+    if let Some(def) = field_type.other().and_then(|name| schema.get_type(name)) {
+        if let Some(decoded) = decode_struct(def, value, schema)? {
+            return Ok(decoded);
+        }
+    }
+
+    let value = fix_invalid_object_keys(value);
+    Ok(DecodedValue::Raw(json::to_value(value)?))
+}
+
+fn fix_substate_value_for_json(
+    value: &SubstateValue,
+    substate_json_field: &mut json::Value,
+) -> Result<(), JsonEncodingError> {
+    match value {
         SubstateValue::NonFungible(nf_container) => {
-            encode_non_fungible_into_json(nf_container, substate_field)?;
+            encode_non_fungible_into_json(nf_container, substate_json_field)?;
         },
         SubstateValue::Component(header) => {
-            encode_component_into_json(header, substate_field)?;
+            encode_component_into_json(header, substate_json_field)?;
         },
         _ => {},
     }
 
-    Ok(result)
+    Ok(())
 }
 
 fn get_mut_json_field<'a>(
@@ -159,9 +244,20 @@ fn fix_invalid_object_keys(value: &CborValue) -> CborValue {
 
 #[cfg(test)]
 mod tests {
+    use serde::Serialize;
     use tari_common_types::types::Commitment;
-    use tari_engine_types::{confidential::ConfidentialOutput, resource_container::ResourceContainer, vault::Vault};
-    use tari_template_lib::models::{Amount, ResourceAddress};
+    use tari_dan_engine::abi::FieldDef;
+    use tari_engine_types::{
+        component::ComponentBody,
+        confidential::ConfidentialOutput,
+        resource_container::ResourceContainer,
+        vault::Vault,
+    };
+    use tari_template_lib::{
+        auth::OwnerRule,
+        models::{Amount, EntityId, ResourceAddress, TemplateAddress},
+        prelude::ComponentAccessRules,
+    };
 
     use super::*;
 
@@ -189,4 +285,126 @@ mod tests {
 
         assert!(encode_substate_into_json(&substate).is_ok());
     }
+
+    #[derive(Serialize)]
+    struct NestedState {
+        name: String,
+        settings: Settings,
+        history: Vec<u64>,
+    }
+
+    #[derive(Serialize)]
+    struct Settings {
+        enabled: bool,
+        limits: Limits,
+    }
+
+    #[derive(Serialize)]
+    struct Limits(u32, u64);
+
+    fn field(name: &str, field_type: Type) -> FieldDef {
+        FieldDef {
+            name: name.to_string(),
+            field_type,
+        }
+    }
+
+    fn other(name: &str) -> Type {
+        Type::Other { name: name.to_string() }
+    }
+
+    fn nested_state_schema() -> ComponentSchema {
+        ComponentSchema {
+            component: StructDef {
+                name: "NestedState".to_string(),
+                fields: vec![
+                    field("name", Type::String),
+                    field("settings", other("Settings")),
+                    field("history", Type::Vec(Box::new(Type::U64))),
+                ],
+            },
+            types: vec![
+                StructDef {
+                    name: "Settings".to_string(),
+                    fields: vec![field("enabled", Type::Bool), field("limits", other("Limits"))],
+                },
+                StructDef {
+                    name: "Limits".to_string(),
+                    fields: vec![field("0", Type::U32), field("1", Type::U64)],
+                },
+            ],
+        }
+    }
+
+    fn create_component<T: Serialize>(state: &T) -> SubstateValue {
+        SubstateValue::Component(ComponentHeader {
+            template_address: TemplateAddress::from_array([1u8; 32]),
+            module_name: "NestedState".to_string(),
+            owner_key: None,
+            owner_rule: OwnerRule::None,
+            access_rules: ComponentAccessRules::allow_all(),
+            entity_id: EntityId::default(),
+            body: ComponentBody {
+                state: tari_bor::to_value(state).unwrap(),
+            },
+        })
+    }
+
+    #[test]
+    fn it_decodes_nested_component_state_using_the_schema() {
+        let value = create_component(&NestedState {
+            name: "test".to_string(),
+            settings: Settings {
+                enabled: true,
+                limits: Limits(10, 1000),
+            },
+            history: vec![1, 2],
+        });
+        let schema = nested_state_schema();
+
+        let decoded = decode_substate_value(&value, Some(&schema)).unwrap();
+        let DecodedValue::Struct { name, .. } = &decoded else {
+            panic!("Expected struct but got {:?}", decoded);
+        };
+        assert_eq!(name, "NestedState");
+
+        let name_field = decoded.get_field("name").unwrap();
+        assert_eq!(name_field.field_type, "String");
+        assert_eq!(name_field.value, DecodedValue::Raw(json::json!("test")));
+        let history = decoded.get_field("history").unwrap();
+        assert_eq!(history.field_type, "Vec<U64>");
+        assert_eq!(history.value, DecodedValue::Raw(json::json!([1, 2])));
+
+        let settings = &decoded.get_field("settings").unwrap().value;
+        assert_eq!(settings.get_field("enabled").unwrap().value, DecodedValue::Raw(json::json!(true)));
+        let limits = &settings.get_field("limits").unwrap().value;
+        assert_eq!(limits.get_field("0").unwrap().value, DecodedValue::Raw(json::json!(10)));
+        assert_eq!(limits.get_field("1").unwrap().value, DecodedValue::Raw(json::json!(1000)));
+    }
+
+    #[test]
+    fn it_falls_back_to_raw_json_if_the_template_has_no_schema() {
+        let value = create_component(&Settings {
+            enabled: false,
+            limits: Limits(1, 2),
+        });
+
+        let decoded = decode_substate_value(&value, None).unwrap();
+        assert_eq!(decoded, DecodedValue::Raw(encode_substate_value_into_json(&value).unwrap()));
+        let DecodedValue::Raw(json) = decoded else {
+            unreachable!()
+        };
+        assert_eq!(json["Component"]["state"]["enabled"], json::json!(false));
+    }
+
+    #[test]
+    fn it_falls_back_to_raw_json_if_the_state_does_not_match_the_schema() {
+        let value = create_component(&Settings {
+            enabled: false,
+            limits: Limits(1, 2),
+        });
+
+        let decoded = decode_substate_value(&value, Some(&nested_state_schema())).unwrap();
+        assert!(decoded.is_raw());
+    }
 }
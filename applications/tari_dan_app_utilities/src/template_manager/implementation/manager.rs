@@ -24,20 +24,33 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     fs,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 use chrono::Utc;
 use log::*;
+use tari_common_types::types::FixedHash;
 use tari_core::transactions::transaction_components::TemplateType;
 use tari_dan_common_types::{optional::Optional, services::template_provider::TemplateProvider, NodeAddressable};
 use tari_dan_engine::{
+    abi::ComponentSchema,
     flow::FlowFactory,
     function_definitions::FlowFunctionDefinition,
     template::{LoadedTemplate, TemplateModuleLoader},
     wasm::WasmModule,
 };
-use tari_dan_storage::global::{DbTemplate, DbTemplateType, DbTemplateUpdate, GlobalDb, TemplateStatus};
+use tari_dan_storage::global::{
+    DbTemplate,
+    DbTemplateType,
+    DbTemplateUpdate,
+    DbTemplateUpload,
+    DbTemplateUploadChunk,
+    GlobalDb,
+    TemplateStatus,
+};
 use tari_dan_storage_sqlite::global::SqliteGlobalDbAdapter;
 use tari_engine_types::calculate_template_binary_hash;
 use tari_template_builtin::{get_template_builtin, ACCOUNT_NFT_TEMPLATE_ADDRESS, ACCOUNT_TEMPLATE_ADDRESS};
@@ -53,12 +66,31 @@ const LOG_TARGET: &str = "tari::validator_node::template_manager";
 
 const CONCURRENT_ACCESS_LIMIT: isize = 100;
 
+/// A snapshot of the loaded template cache counters
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TemplateCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub entry_count: u64,
+    /// The combined code size in bytes of all cached templates
+    pub weighted_size: u64,
+}
+
+#[derive(Debug, Default)]
+struct TemplateCacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
 #[derive(Debug)]
 pub struct TemplateManager<TAddr> {
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
     config: TemplateConfig,
     builtin_templates: Arc<HashMap<TemplateAddress, Template>>,
     cache: mini_moka::sync::Cache<TemplateAddress, LoadedTemplate>,
+    cache_counters: Arc<TemplateCacheCounters>,
     cmap_semaphore: cmap_semaphore::ConcurrentMapSemaphore<TemplateAddress>,
 }
 
@@ -83,11 +115,29 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
             global_db,
             builtin_templates: Arc::new(builtin_templates),
             cache,
+            cache_counters: Arc::new(TemplateCacheCounters::default()),
             config,
             cmap_semaphore: cmap_semaphore::ConcurrentMapSemaphore::new(CONCURRENT_ACCESS_LIMIT),
         })
     }
 
+    pub fn cache_stats(&self) -> TemplateCacheStats {
+        TemplateCacheStats {
+            hits: self.cache_counters.hits.load(Ordering::Relaxed),
+            misses: self.cache_counters.misses.load(Ordering::Relaxed),
+            invalidations: self.cache_counters.invalidations.load(Ordering::Relaxed),
+            entry_count: self.cache.entry_count(),
+            weighted_size: self.cache.weighted_size(),
+        }
+    }
+
+    fn get_cached(&self, address: &TemplateAddress) -> Option<LoadedTemplate> {
+        let template = self.cache.get(address)?;
+        debug!(target: LOG_TARGET, "CACHE HIT: Template {}", address);
+        self.cache_counters.hits.fetch_add(1, Ordering::Relaxed);
+        Some(template)
+    }
+
     fn load_builtin_templates() -> HashMap<TemplateAddress, Template> {
         // for now, we only load the "account" template
         let mut builtin_templates = HashMap::new();
@@ -186,6 +236,30 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         Ok(templates)
     }
 
+    pub fn fetch_component_schema(
+        &self,
+        address: &TemplateAddress,
+    ) -> Result<Option<ComponentSchema>, TemplateManagerError> {
+        if !self.builtin_templates.contains_key(address) {
+            let mut tx = self.global_db.create_transaction()?;
+            let template = self
+                .global_db
+                .templates(&mut tx)
+                .get_template(address)?
+                .ok_or(TemplateManagerError::TemplateNotFound { address: *address })?;
+            if let Some(schema) = template.component_schema {
+                return Ok(Some(serde_json::from_str(&schema)?));
+            }
+        }
+
+        // Builtin templates and templates that were downloaded before the schema was stored are loaded to read the
+        // schema from the ABI
+        let template = self
+            .get_template_module(address)?
+            .ok_or(TemplateManagerError::TemplateNotFound { address: *address })?;
+        Ok(template.template_def().component_schema().cloned())
+    }
+
     pub(super) fn add_template(&self, template: TemplateRegistration) -> Result<(), TemplateManagerError> {
         let template = DbTemplate {
             template_name: template.template_name,
@@ -203,6 +277,8 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
             },
             flow_json: None,
             manifest: None,
+            component_schema: None,
+            previous_template_address: None,
         };
 
         let mut tx = self.global_db.create_transaction()?;
@@ -221,11 +297,23 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         address: TemplateAddress,
         update: DbTemplateUpdate,
     ) -> Result<(), TemplateManagerError> {
+        // Hold the template's access guard so that the template cannot be loaded from the old record and cached after
+        // it has been invalidated
+        let guard = self.cmap_semaphore.acquire(address);
+        let _access = guard.access();
+
         let mut tx = self.global_db.create_transaction()?;
         let mut template_db = self.global_db.templates(&mut tx);
         template_db.update_template(&address, update)?;
         tx.commit()?;
 
+        // The status or code of the template may have changed, so the loaded template must be reloaded on next use
+        if self.cache.contains_key(&address) {
+            self.cache.invalidate(&address);
+            self.cache_counters.invalidations.fetch_add(1, Ordering::Relaxed);
+            debug!(target: LOG_TARGET, "CACHE INVALIDATED: Template {}", address);
+        }
+
         Ok(())
     }
 
@@ -234,6 +322,191 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         let templates = self.global_db.templates(&mut tx).get_pending_templates(1000)?;
         Ok(templates)
     }
+
+    /// Starts a chunked upload of a template binary, returning the upload id that chunks are submitted against.
+    pub fn begin_upload(
+        &self,
+        template_name: String,
+        total_size: u64,
+        expected_hash: FixedHash,
+    ) -> Result<FixedHash, TemplateManagerError> {
+        let max = self.config.max_upload_size_bytes();
+        if total_size == 0 || total_size > max {
+            return Err(TemplateManagerError::InvalidTemplateUploadSize { size: total_size, max });
+        }
+        self.delete_expired_uploads()?;
+
+        let upload_id = FixedHash::from(rand::random::<[u8; 32]>());
+        let mut tx = self.global_db.create_transaction()?;
+        self.global_db.templates(&mut tx).insert_upload(DbTemplateUpload {
+            upload_id,
+            template_name,
+            total_size,
+            expected_hash,
+            started_at: Utc::now().naive_utc(),
+        })?;
+        tx.commit()?;
+
+        Ok(upload_id)
+    }
+
+    /// Stores a chunk of the upload. Chunks may be submitted in any order.
+    pub fn upload_chunk(&self, upload_id: &FixedHash, offset: u64, data: Vec<u8>) -> Result<(), TemplateManagerError> {
+        self.delete_expired_uploads()?;
+
+        let mut tx = self.global_db.create_transaction()?;
+        let mut templates_db = self.global_db.templates(&mut tx);
+        let upload = templates_db
+            .get_upload(upload_id)?
+            .ok_or(TemplateManagerError::TemplateUploadNotFound { upload_id: *upload_id })?;
+
+        let len = data.len() as u64;
+        if len == 0 || offset.checked_add(len).map_or(true, |end| end > upload.total_size) {
+            return Err(TemplateManagerError::InvalidTemplateUploadChunk {
+                offset,
+                len,
+                total_size: upload.total_size,
+            });
+        }
+        templates_db.insert_upload_chunk(upload_id, DbTemplateUploadChunk { offset, data })?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Assembles and verifies the uploaded binary and stores it until the template is registered on the base layer.
+    /// The returned address is the binary hash of the template.
+    pub fn finish_upload(&self, upload_id: &FixedHash) -> Result<TemplateAddress, TemplateManagerError> {
+        self.delete_expired_uploads()?;
+
+        let mut tx = self.global_db.create_transaction()?;
+        let mut templates_db = self.global_db.templates(&mut tx);
+        let upload = templates_db
+            .get_upload(upload_id)?
+            .ok_or(TemplateManagerError::TemplateUploadNotFound { upload_id: *upload_id })?;
+        let chunks = templates_db.get_upload_chunks(upload_id)?;
+        // Missing chunks may still be submitted, so the upload is kept if it is incomplete
+        let binary = assemble_upload(&upload, chunks)?;
+
+        // The upload is discarded from here whether or not the binary is valid
+        templates_db.delete_upload(upload_id)?;
+        let binary_hash = calculate_template_binary_hash(&binary);
+        let result = if binary_hash == upload.expected_hash {
+            WasmModule::load_template_from_code(&binary)
+                .map_err(TemplateManagerError::from)
+                .and_then(|template| {
+                    let component_schema = encode_component_schema(&template)?;
+                    Ok((component_schema, previous_template_address(&template)))
+                })
+        } else {
+            Err(TemplateManagerError::TemplateUploadHashMismatch {
+                expected: upload.expected_hash,
+                actual: binary_hash,
+            })
+        };
+        let (component_schema, previous_template_address) = match result {
+            Ok(abi_data) => abi_data,
+            Err(err) => {
+                tx.commit()?;
+                return Err(err);
+            },
+        };
+
+        if templates_db.get_template(binary_hash.as_slice())?.is_none() {
+            templates_db.insert_template(DbTemplate {
+                template_name: upload.template_name,
+                template_address: binary_hash,
+                expected_hash: binary_hash,
+                url: "".to_string(),
+                height: 0,
+                template_type: DbTemplateType::Wasm,
+                compiled_code: Some(binary),
+                flow_json: None,
+                manifest: None,
+                component_schema,
+                previous_template_address,
+                status: TemplateStatus::Uploaded,
+                added_at: Utc::now().naive_utc(),
+            })?;
+        }
+        tx.commit()?;
+
+        info!(target: LOG_TARGET, "⬆️ Template upload {} complete: {}", upload_id, binary_hash);
+        Ok(TemplateAddress::from_array(binary_hash.into_array()))
+    }
+
+    /// Returns the binary of a template that was uploaded with the given binary hash, if any.
+    pub(super) fn fetch_uploaded_binary(
+        &self,
+        binary_hash: &FixedHash,
+    ) -> Result<Option<Vec<u8>>, TemplateManagerError> {
+        let mut tx = self.global_db.create_transaction()?;
+        let template = self.global_db.templates(&mut tx).get_template(binary_hash.as_slice())?;
+        Ok(template
+            .filter(|t| t.status == TemplateStatus::Uploaded)
+            .and_then(|t| t.compiled_code))
+    }
+
+    fn delete_expired_uploads(&self) -> Result<(), TemplateManagerError> {
+        let ttl = chrono::Duration::from_std(self.config.upload_ttl()).unwrap_or(chrono::Duration::max_value());
+        let Some(started_before) = Utc::now().naive_utc().checked_sub_signed(ttl) else {
+            return Ok(());
+        };
+        let mut tx = self.global_db.create_transaction()?;
+        let num_deleted = self
+            .global_db
+            .templates(&mut tx)
+            .delete_uploads_started_before(started_before)?;
+        tx.commit()?;
+        if num_deleted > 0 {
+            debug!(target: LOG_TARGET, "Deleted {} expired template upload(s)", num_deleted);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the JSON encoded component schema from the template ABI, if the template defines one.
+pub(super) fn encode_component_schema(template: &LoadedTemplate) -> Result<Option<String>, serde_json::Error> {
+    template
+        .template_def()
+        .component_schema()
+        .map(serde_json::to_string)
+        .transpose()
+}
+
+/// Returns the address of the template that the given template upgrades, if it declares one in its ABI.
+pub(super) fn previous_template_address(template: &LoadedTemplate) -> Option<FixedHash> {
+    template
+        .template_def()
+        .upgrades_from()
+        .map(|address| FixedHash::from(*address))
+}
+
+/// Assembles the uploaded binary from its chunks, which must be ordered by offset. Chunks may overlap if they were
+/// submitted more than once.
+fn assemble_upload(
+    upload: &DbTemplateUpload,
+    chunks: Vec<DbTemplateUploadChunk>,
+) -> Result<Vec<u8>, TemplateManagerError> {
+    let mut binary = vec![0u8; upload.total_size as usize];
+    let mut filled = 0u64;
+    for chunk in chunks {
+        if chunk.offset > filled {
+            break;
+        }
+        let start = chunk.offset as usize;
+        binary[start..start + chunk.data.len()].copy_from_slice(&chunk.data);
+        filled = filled.max(chunk.offset + chunk.data.len() as u64);
+    }
+
+    if filled < upload.total_size {
+        return Err(TemplateManagerError::TemplateUploadIncomplete {
+            upload_id: upload.upload_id,
+            missing_offset: filled,
+        });
+    }
+
+    Ok(binary)
 }
 
 impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for TemplateManager<TAddr> {
@@ -241,8 +514,7 @@ impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for Templa
     type Template = LoadedTemplate;
 
     fn get_template_module(&self, address: &TemplateAddress) -> Result<Option<Self::Template>, Self::Error> {
-        if let Some(template) = self.cache.get(address) {
-            debug!(target: LOG_TARGET, "CACHE HIT: Template {}", address);
+        if let Some(template) = self.get_cached(address) {
             return Ok(Some(template));
         }
 
@@ -255,8 +527,7 @@ impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for Templa
         let guard = self.cmap_semaphore.acquire(*address);
         let _access = guard.access();
 
-        if let Some(template) = self.cache.get(address) {
-            debug!(target: LOG_TARGET, "CACHE HIT: Template {}", address);
+        if let Some(template) = self.get_cached(address) {
             return Ok(Some(template));
         }
 
@@ -264,6 +535,7 @@ impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for Templa
             return Ok(None);
         };
         debug!(target: LOG_TARGET, "CACHE MISS: Template {}", address);
+        self.cache_counters.misses.fetch_add(1, Ordering::Relaxed);
         let loaded = match template.executable {
             TemplateExecutable::CompiledWasm(wasm) => {
                 let module = WasmModule::from_code(wasm);
@@ -290,7 +562,206 @@ impl<TAddr> Clone for TemplateManager<TAddr> {
             config: self.config.clone(),
             builtin_templates: self.builtin_templates.clone(),
             cache: self.cache.clone(),
+            cache_counters: self.cache_counters.clone(),
             cmap_semaphore: self.cmap_semaphore.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tari_dan_common_types::PeerAddress;
+    use tari_dan_storage::global::DbFactory;
+    use tari_dan_storage_sqlite::SqliteDbFactory;
+
+    use super::*;
+    use crate::template_manager::implementation::test_fixtures::create_manager_with_template;
+
+    #[test]
+    fn it_caches_loaded_templates() {
+        let address = TemplateAddress::from_array([1u8; 32]);
+        let (manager, _temp) =
+            create_manager_with_template(address, get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec());
+
+        manager.get_template_module(&address).unwrap().unwrap();
+        manager.get_template_module(&address).unwrap().unwrap();
+        manager.get_template_module(&address).unwrap().unwrap();
+
+        let stats = manager.cache_stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 2);
+    }
+
+    #[test]
+    fn it_invalidates_the_cached_template_when_it_is_updated() {
+        let address = TemplateAddress::from_array([1u8; 32]);
+        let (manager, _temp) =
+            create_manager_with_template(address, get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec());
+
+        manager.get_template_module(&address).unwrap().unwrap();
+        manager
+            .update_template(address, DbTemplateUpdate {
+                status: Some(TemplateStatus::Deprecated),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(manager.cache_stats().invalidations, 1);
+
+        // Reloaded from the database
+        manager.get_template_module(&address).unwrap().unwrap();
+        assert_eq!(manager.cache_stats().misses, 2);
+
+        manager
+            .update_template(address, DbTemplateUpdate {
+                status: Some(TemplateStatus::Invalid),
+                ..Default::default()
+            })
+            .unwrap();
+        let err = manager.get_template_module(&address).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUnavailable));
+        assert_eq!(manager.cache_stats().invalidations, 2);
+    }
+
+    fn create_manager(config: TemplateConfig) -> (TemplateManager<PeerAddress>, tempfile::TempDir) {
+        let temp = tempfile::tempdir().unwrap();
+        let db_factory = SqliteDbFactory::new(temp.path().to_path_buf());
+        db_factory.migrate().unwrap();
+        let global_db = db_factory.get_or_create_global_db().unwrap();
+        let manager = TemplateManager::initialize(global_db, config).unwrap();
+        (manager, temp)
+    }
+
+    fn leb128(mut n: usize) -> Vec<u8> {
+        let mut buf = Vec::new();
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                buf.push(byte);
+                return buf;
+            }
+            buf.push(byte | 0x80);
+        }
+    }
+
+    /// Returns a valid template binary that is padded with a custom section to the given minimum size
+    fn large_template_binary(min_size: usize) -> Vec<u8> {
+        let mut code = get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec();
+        let name = b"padding";
+        let mut section = leb128(name.len());
+        section.extend_from_slice(name);
+        section.resize(min_size.saturating_sub(code.len()).max(section.len()), 0);
+        code.push(0);
+        code.extend(leb128(section.len()));
+        code.extend(section);
+        code
+    }
+
+    fn upload_in_chunks(manager: &TemplateManager<PeerAddress>, binary: &[u8], chunk_size: usize) -> FixedHash {
+        let upload_id = manager
+            .begin_upload(
+                "test".to_string(),
+                binary.len() as u64,
+                calculate_template_binary_hash(binary),
+            )
+            .unwrap();
+        // Submit the chunks in reverse order
+        let chunks = binary.chunks(chunk_size).enumerate().collect::<Vec<_>>();
+        for (i, chunk) in chunks.into_iter().rev() {
+            manager
+                .upload_chunk(&upload_id, (i * chunk_size) as u64, chunk.to_vec())
+                .unwrap();
+        }
+        upload_id
+    }
+
+    #[test]
+    fn it_uploads_a_template_in_chunks() {
+        let (manager, _temp) = create_manager(TemplateConfig::default());
+        let binary = large_template_binary(3 * 1024 * 1024);
+        let binary_hash = calculate_template_binary_hash(&binary);
+
+        let upload_id = upload_in_chunks(&manager, &binary, 256 * 1024);
+        let address = manager.finish_upload(&upload_id).unwrap();
+        assert_eq!(address.into_array(), binary_hash.into_array());
+
+        // Not executable until it is registered
+        let err = manager.fetch_template(&address).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUnavailable));
+        assert_eq!(manager.fetch_uploaded_binary(&binary_hash).unwrap(), Some(binary));
+
+        let err = manager.finish_upload(&upload_id).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUploadNotFound { .. }));
+    }
+
+    #[test]
+    fn it_rejects_an_upload_with_a_hash_mismatch() {
+        let (manager, _temp) = create_manager(TemplateConfig::default());
+        let binary = get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec();
+
+        let upload_id = manager
+            .begin_upload("test".to_string(), binary.len() as u64, FixedHash::zero())
+            .unwrap();
+        manager.upload_chunk(&upload_id, 0, binary.clone()).unwrap();
+        let err = manager.finish_upload(&upload_id).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUploadHashMismatch { .. }));
+        assert_eq!(
+            manager
+                .fetch_uploaded_binary(&calculate_template_binary_hash(&binary))
+                .unwrap(),
+            None
+        );
+
+        // The upload is discarded
+        let err = manager.finish_upload(&upload_id).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUploadNotFound { .. }));
+    }
+
+    #[test]
+    fn it_rejects_invalid_upload_chunks() {
+        let (manager, _temp) = create_manager(TemplateConfig::default());
+        let binary = get_template_builtin(&ACCOUNT_TEMPLATE_ADDRESS).to_vec();
+        let len = binary.len() as u64;
+
+        let upload_id = manager
+            .begin_upload("test".to_string(), len, calculate_template_binary_hash(&binary))
+            .unwrap();
+        let err = manager.upload_chunk(&upload_id, len - 1, vec![0, 0]).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::InvalidTemplateUploadChunk { .. }));
+        let err = manager.upload_chunk(&upload_id, u64::MAX, vec![0]).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::InvalidTemplateUploadChunk { .. }));
+
+        // Missing the first byte
+        manager.upload_chunk(&upload_id, 1, binary[1..].to_vec()).unwrap();
+        let err = manager.finish_upload(&upload_id).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUploadIncomplete {
+            missing_offset: 0,
+            ..
+        }));
+
+        // The upload can be completed after the missing chunk is submitted
+        manager.upload_chunk(&upload_id, 0, binary[..1].to_vec()).unwrap();
+        manager.finish_upload(&upload_id).unwrap();
+
+        let err = manager
+            .begin_upload(
+                "test".to_string(),
+                TemplateConfig::default().max_upload_size_bytes() + 1,
+                FixedHash::zero(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, TemplateManagerError::InvalidTemplateUploadSize { .. }));
+    }
+
+    #[test]
+    fn it_expires_partial_uploads() {
+        let (manager, _temp) = create_manager(TemplateConfig::default().with_upload_ttl(Duration::ZERO));
+        let upload_id = manager.begin_upload("test".to_string(), 10, FixedHash::zero()).unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        let err = manager.upload_chunk(&upload_id, 0, vec![0; 10]).unwrap_err();
+        assert!(matches!(err, TemplateManagerError::TemplateUploadNotFound { .. }));
+    }
+}
@@ -26,10 +26,12 @@ mod initializer;
 pub use initializer::spawn;
 
 mod manager;
-pub use manager::TemplateManager;
+pub use manager::{TemplateCacheStats, TemplateManager};
 mod service;
 
 mod cmap_semaphore;
 mod template_config;
+#[cfg(test)]
+mod test_fixtures;
 
 pub use template_config::TemplateConfig;
@@ -22,11 +22,12 @@
 
 use std::convert::TryFrom;
 
+use bytes::Bytes;
 use log::*;
 use tari_common_types::types::FixedHash;
 use tari_core::transactions::transaction_components::TemplateType;
 use tari_dan_common_types::{services::template_provider::TemplateProvider, NodeAddressable};
-use tari_dan_engine::function_definitions::FlowFunctionDefinition;
+use tari_dan_engine::{function_definitions::FlowFunctionDefinition, wasm::WasmModule};
 use tari_dan_storage::global::{DbTemplateType, DbTemplateUpdate, TemplateStatus};
 use tari_engine_types::calculate_template_binary_hash;
 use tari_shutdown::ShutdownSignal;
@@ -39,6 +40,7 @@ use tokio::{
 
 use super::{
     downloader::{DownloadRequest, DownloadResult},
+    manager::{encode_component_schema, previous_template_address},
     TemplateManager,
 };
 use crate::template_manager::interface::{TemplateManagerError, TemplateManagerRequest, TemplateRegistration};
@@ -127,6 +129,23 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
             },
             GetTemplates { limit, reply } => handle(reply, self.manager.fetch_template_metadata(limit)),
             LoadTemplateAbi { address, reply } => handle(reply, self.handle_load_template_abi(address)),
+            GetComponentSchema { address, reply } => handle(reply, self.manager.fetch_component_schema(&address)),
+            BeginTemplateUpload {
+                template_name,
+                total_size,
+                expected_hash,
+                reply,
+            } => handle(
+                reply,
+                self.manager.begin_upload(template_name, total_size, expected_hash),
+            ),
+            UploadTemplateChunk {
+                upload_id,
+                offset,
+                data,
+                reply,
+            } => handle(reply, self.manager.upload_chunk(&upload_id, offset, data)),
+            FinishTemplateUpload { upload_id, reply } => handle(reply, self.manager.finish_upload(&upload_id)),
         }
     }
 
@@ -187,10 +206,30 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
                 };
 
                 let update = match download.template_type {
-                    DbTemplateType::Wasm => DbTemplateUpdate {
-                        compiled_code: Some(bytes.to_vec()),
-                        status: Some(template_status),
-                        ..Default::default()
+                    DbTemplateType::Wasm => {
+                        // Store the component schema so that substates can be decoded without loading the template
+                        let (component_schema, previous_template_address) =
+                            match WasmModule::load_template_from_code(&bytes) {
+                                Ok(template) => (
+                                    encode_component_schema(&template)?,
+                                    previous_template_address(&template),
+                                ),
+                                Err(e) => {
+                                    warn!(
+                                        target: LOG_TARGET,
+                                        "⚠️ Failed to load the ABI for template {}: {}", download.template_address, e
+                                    );
+                                    (None, None)
+                                },
+                            };
+
+                        DbTemplateUpdate {
+                            compiled_code: Some(bytes.to_vec()),
+                            component_schema,
+                            previous_template_address,
+                            status: Some(template_status),
+                            ..Default::default()
+                        }
                     },
                     DbTemplateType::Flow => {
                         // make sure it deserializes correctly
@@ -238,6 +277,20 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
         let expected_binary_hash = FixedHash::try_from(template.registration.binary_sha.clone().into_vec())
             .map_err(|_| TemplateManagerError::InvalidBaseLayerTemplate)?;
         self.manager.add_template(template)?;
+
+        // The binary may have been uploaded directly to this node ahead of the registration
+        if matches!(template_type, DbTemplateType::Wasm) {
+            if let Some(binary) = self.manager.fetch_uploaded_binary(&expected_binary_hash)? {
+                info!(target: LOG_TARGET, "⬆️ Using uploaded binary for template {}", address);
+                return self.handle_completed_download(DownloadResult {
+                    template_address: address,
+                    template_type,
+                    expected_binary_hash,
+                    result: Ok(Bytes::from(binary)),
+                });
+            }
+        }
+
         // We could queue this up much later, at which point we'd update to pending
         self.manager.update_template(address, DbTemplateUpdate {
             status: Some(TemplateStatus::Pending),
@@ -20,15 +20,22 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
 use tari_engine_types::TemplateAddress;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct TemplateConfig {
     max_cache_size_bytes: u64,
     debug_replacements: Vec<String>,
+    /// The maximum size of a template binary that may be uploaded in chunks
+    max_upload_size_bytes: u64,
+    /// How long a chunked upload may take before the partially uploaded binary is discarded
+    #[serde(with = "serializers::seconds")]
+    upload_ttl: Duration,
 }
 
 impl Default for TemplateConfig {
@@ -36,6 +43,8 @@ impl Default for TemplateConfig {
         Self {
             max_cache_size_bytes: 200 * 1024 * 1024,
             debug_replacements: Vec::new(),
+            max_upload_size_bytes: 10 * 1024 * 1024,
+            upload_ttl: Duration::from_secs(60 * 60),
         }
     }
 }
@@ -54,4 +63,22 @@ impl TemplateConfig {
     pub fn max_cache_size_bytes(&self) -> u64 {
         self.max_cache_size_bytes
     }
+
+    pub fn max_upload_size_bytes(&self) -> u64 {
+        self.max_upload_size_bytes
+    }
+
+    pub fn upload_ttl(&self) -> Duration {
+        self.upload_ttl
+    }
+
+    pub fn with_max_upload_size_bytes(mut self, max_upload_size_bytes: u64) -> Self {
+        self.max_upload_size_bytes = max_upload_size_bytes;
+        self
+    }
+
+    pub fn with_upload_ttl(mut self, upload_ttl: Duration) -> Self {
+        self.upload_ttl = upload_ttl;
+        self
+    }
 }
@@ -0,0 +1,49 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Fixtures shared by the template manager tests and the template cache benchmark. The benchmark includes this file by
+//! path, so the template manager types are imported from the including module.
+
+use chrono::Utc;
+use tari_dan_common_types::PeerAddress;
+use tari_dan_storage::global::{DbFactory, DbTemplate, DbTemplateType, TemplateStatus};
+use tari_dan_storage_sqlite::SqliteDbFactory;
+use tari_engine_types::calculate_template_binary_hash;
+use tari_template_lib::models::TemplateAddress;
+
+use super::{TemplateConfig, TemplateManager};
+
+/// Creates a template manager with a new database that contains an active WASM template with the given code
+pub fn create_manager_with_template(
+    address: TemplateAddress,
+    code: Vec<u8>,
+) -> (TemplateManager<PeerAddress>, tempfile::TempDir) {
+    let temp = tempfile::tempdir().unwrap();
+    let db_factory = SqliteDbFactory::new(temp.path().to_path_buf());
+    db_factory.migrate().unwrap();
+    let global_db = db_factory.get_or_create_global_db().unwrap();
+
+    let mut tx = global_db.create_transaction().unwrap();
+    global_db
+        .templates(&mut tx)
+        .insert_template(DbTemplate {
+            template_name: "test".to_string(),
+            template_address: address.into_array().into(),
+            expected_hash: calculate_template_binary_hash(&code),
+            url: "".to_string(),
+            height: 0,
+            template_type: DbTemplateType::Wasm,
+            compiled_code: Some(code),
+            flow_json: None,
+            manifest: None,
+            component_schema: None,
+            previous_template_address: None,
+            status: TemplateStatus::Active,
+            added_at: Utc::now().naive_utc(),
+        })
+        .unwrap();
+    tx.commit().unwrap();
+
+    let manager = TemplateManager::initialize(global_db, TemplateConfig::default()).unwrap();
+    (manager, temp)
+}
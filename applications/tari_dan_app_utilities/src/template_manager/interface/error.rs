@@ -23,7 +23,7 @@
 use std::string::FromUtf8Error;
 
 use serde_json;
-use tari_common_types::types::FixedHashSizeError;
+use tari_common_types::types::{FixedHash, FixedHashSizeError};
 use tari_dan_common_types::optional::IsNotFoundError;
 use tari_dan_engine::template::TemplateLoaderError;
 use tari_dan_storage::StorageError;
@@ -57,10 +57,20 @@ pub enum TemplateManagerError {
     FlowEngineError(#[from] tari_dan_engine::flow::FlowEngineError),
     #[error("FixedHashSizeError: {0}")]
     FixedHashSizeError(#[from] FixedHashSizeError),
+    #[error("Template upload {upload_id} not found or expired")]
+    TemplateUploadNotFound { upload_id: FixedHash },
+    #[error("Invalid template upload size {size}. The size must be between 1 and {max} bytes")]
+    InvalidTemplateUploadSize { size: u64, max: u64 },
+    #[error("Chunk at offset {offset} with length {len} is invalid for an upload of {total_size} bytes")]
+    InvalidTemplateUploadChunk { offset: u64, len: u64, total_size: u64 },
+    #[error("Template upload {upload_id} is incomplete. No data has been received from offset {missing_offset}")]
+    TemplateUploadIncomplete { upload_id: FixedHash, missing_offset: u64 },
+    #[error("Template upload hash mismatch. Expected {expected} but the uploaded binary has hash {actual}")]
+    TemplateUploadHashMismatch { expected: FixedHash, actual: FixedHash },
 }
 
 impl IsNotFoundError for TemplateManagerError {
     fn is_not_found_error(&self) -> bool {
-        matches!(self, Self::TemplateNotFound { .. })
+        matches!(self, Self::TemplateNotFound { .. } | Self::TemplateUploadNotFound { .. })
     }
 }
@@ -22,6 +22,7 @@
 
 use tari_common_types::types::FixedHash;
 use tari_core::transactions::transaction_components::CodeTemplateRegistration;
+use tari_dan_engine::abi::ComponentSchema;
 use tari_template_lib::models::TemplateAddress;
 use tari_validator_node_client::types::TemplateAbi;
 use tokio::sync::{mpsc, oneshot};
@@ -68,6 +69,65 @@ impl TemplateManagerHandle {
         rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
     }
 
+    pub async fn get_component_schema(
+        &self,
+        address: TemplateAddress,
+    ) -> Result<Option<ComponentSchema>, TemplateManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.request_tx
+            .send(TemplateManagerRequest::GetComponentSchema { address, reply: tx })
+            .await
+            .map_err(|_| TemplateManagerError::ChannelClosed)?;
+        rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
+    }
+
+    pub async fn begin_template_upload(
+        &self,
+        template_name: String,
+        total_size: u64,
+        expected_hash: FixedHash,
+    ) -> Result<FixedHash, TemplateManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.request_tx
+            .send(TemplateManagerRequest::BeginTemplateUpload {
+                template_name,
+                total_size,
+                expected_hash,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| TemplateManagerError::ChannelClosed)?;
+        rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
+    }
+
+    pub async fn upload_template_chunk(
+        &self,
+        upload_id: FixedHash,
+        offset: u64,
+        data: Vec<u8>,
+    ) -> Result<(), TemplateManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.request_tx
+            .send(TemplateManagerRequest::UploadTemplateChunk {
+                upload_id,
+                offset,
+                data,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| TemplateManagerError::ChannelClosed)?;
+        rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
+    }
+
+    pub async fn finish_template_upload(&self, upload_id: FixedHash) -> Result<TemplateAddress, TemplateManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.request_tx
+            .send(TemplateManagerRequest::FinishTemplateUpload { upload_id, reply: tx })
+            .await
+            .map_err(|_| TemplateManagerError::ChannelClosed)?;
+        rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
+    }
+
     pub async fn get_templates(&self, limit: usize) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
         let (tx, rx) = oneshot::channel();
         self.request_tx
@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use tari_common_types::types::FixedHash;
+use tari_dan_engine::abi::ComponentSchema;
 use tari_dan_storage::global::{DbTemplate, DbTemplateType};
 use tari_template_lib::models::TemplateAddress;
 use tari_validator_node_client::types::TemplateAbi;
@@ -117,4 +119,24 @@ pub enum TemplateManagerRequest {
         address: TemplateAddress,
         reply: oneshot::Sender<Result<TemplateAbi, TemplateManagerError>>,
     },
+    GetComponentSchema {
+        address: TemplateAddress,
+        reply: oneshot::Sender<Result<Option<ComponentSchema>, TemplateManagerError>>,
+    },
+    BeginTemplateUpload {
+        template_name: String,
+        total_size: u64,
+        expected_hash: FixedHash,
+        reply: oneshot::Sender<Result<FixedHash, TemplateManagerError>>,
+    },
+    UploadTemplateChunk {
+        upload_id: FixedHash,
+        offset: u64,
+        data: Vec<u8>,
+        reply: oneshot::Sender<Result<(), TemplateManagerError>>,
+    },
+    FinishTemplateUpload {
+        upload_id: FixedHash,
+        reply: oneshot::Sender<Result<TemplateAddress, TemplateManagerError>>,
+    },
 }
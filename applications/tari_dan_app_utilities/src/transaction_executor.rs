@@ -7,17 +7,17 @@ use std::{
 };
 
 use indexmap::{IndexMap, IndexSet};
-use log::*;
 use tari_common::configuration::Network;
 use tari_common_types::types::PublicKey;
 use tari_crypto::tari_utilities::ByteArray;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 use tari_dan_engine::{
     fees::{FeeModule, FeeTable},
+    limits::{SubstateSizeLimitModule, SubstateSizeLimits},
     runtime::{AuthParams, RuntimeModule},
     state_store::{memory::MemoryStateStore, StateStoreError},
     template::LoadedTemplate,
-    transaction::{TransactionError, TransactionProcessor},
+    transaction::{TransactionError, TransactionProcessor, DEFAULT_EXECUTION_BUDGET},
 };
 use tari_dan_storage::consensus_models::{SubstateLockFlag, VersionedSubstateIdLockIntent};
 use tari_engine_types::{
@@ -28,8 +28,6 @@ use tari_engine_types::{
 use tari_template_lib::{crypto::RistrettoPublicKeyBytes, prelude::NonFungibleAddress};
 use tari_transaction::{Transaction, VersionedSubstateId};
 
-const _LOG_TARGET: &str = "tari::dan::transaction_executor";
-
 pub trait TransactionExecutor {
     type Error: std::error::Error + Send + Sync + 'static;
 
@@ -83,17 +81,32 @@ impl ExecutionOutput {
 pub struct TariDanTransactionProcessor<TTemplateProvider> {
     template_provider: Arc<TTemplateProvider>,
     fee_table: FeeTable,
+    substate_size_limits: SubstateSizeLimits,
     network: Network,
+    execution_budget: u64,
 }
 
 impl<TTemplateProvider> TariDanTransactionProcessor<TTemplateProvider> {
-    pub fn new(network: Network, template_provider: TTemplateProvider, fee_table: FeeTable) -> Self {
+    pub fn new(
+        network: Network,
+        template_provider: TTemplateProvider,
+        fee_table: FeeTable,
+        substate_size_limits: SubstateSizeLimits,
+    ) -> Self {
         Self {
             template_provider: Arc::new(template_provider),
             fee_table,
+            substate_size_limits,
             network,
+            execution_budget: DEFAULT_EXECUTION_BUDGET,
         }
     }
+
+    /// Sets the number of metering points that a transaction may use. Transactions that exceed it are rejected.
+    pub fn with_execution_budget(mut self, execution_budget: u64) -> Self {
+        self.execution_budget = execution_budget;
+        self
+    }
 }
 
 impl<TTemplateProvider> TransactionExecutor for TariDanTransactionProcessor<TTemplateProvider>
@@ -115,7 +128,13 @@ where TTemplateProvider: TemplateProvider<Template = LoadedTemplate>
         };
 
         let initial_cost = 0;
-        let modules: Vec<Arc<dyn RuntimeModule>> = vec![Arc::new(FeeModule::new(initial_cost, self.fee_table.clone()))];
+        // The size limits are checked before the fee module charges for storage
+        let modules: Vec<Arc<dyn RuntimeModule>> = vec![
+            Arc::new(SubstateSizeLimitModule::new(self.substate_size_limits)),
+            Arc::new(
+                FeeModule::new(initial_cost, self.fee_table.clone()).with_priority_fee(transaction.priority_fee()),
+            ),
+        ];
 
         let processor = TransactionProcessor::new(
             self.template_provider.clone(),
@@ -124,7 +143,8 @@ where TTemplateProvider: TemplateProvider<Template = LoadedTemplate>
             virtual_substates,
             modules,
             self.network,
-        );
+        )
+        .with_execution_budget(self.execution_budget);
         let tx_id = transaction.hash();
         let result = match processor.execute(transaction.clone()) {
             Ok(result) => result,
@@ -54,7 +54,7 @@ use tari_template_lib::{
     prelude::ResourceAddress,
 };
 use tari_transaction::{SubstateRequirement, TransactionId};
-use tari_transaction_manifest::{parse_manifest, ManifestValue};
+use tari_transaction_manifest::{check_manifest, parse_manifest, ManifestValue};
 use tari_utilities::{hex::to_hex, ByteArray};
 use tari_wallet_daemon_client::{
     types::{
@@ -139,6 +139,9 @@ pub struct SendArgs {
     #[clap(flatten)]
     common: CommonSubmitArgs,
     source_account_name: Option<ComponentAddressOrName>,
+    /// Abort the transaction if more than the amount is taken from the source account
+    #[clap(long)]
+    assert_balances: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -274,6 +277,7 @@ pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) ->
         proof_ids: vec![],
         min_epoch: common.min_epoch.map(Epoch),
         max_epoch: common.max_epoch.map(Epoch),
+        deferred_signing: false,
     };
     submit_transaction(request, client).await?;
     Ok(())
@@ -284,7 +288,18 @@ async fn handle_submit_manifest(
     client: &mut WalletDaemonClient,
 ) -> Result<(), anyhow::Error> {
     let contents = fs::read_to_string(&args.manifest).map_err(|e| anyhow!("Failed to read manifest: {}", e))?;
-    let instructions = parse_manifest(&contents, parse_globals(args.input_variables)?, Default::default())?;
+    let globals = parse_globals(args.input_variables)?;
+    let diagnostics = check_manifest(&contents, &globals)?;
+    if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprintln!("{}:{}", args.manifest.display(), diagnostic);
+        }
+        return Err(anyhow!(
+            "Manifest failed static checks with {} error(s). Nothing was submitted.",
+            diagnostics.len()
+        ));
+    }
+    let instructions = parse_manifest(&contents, globals, Default::default())?;
     let common = args.common;
 
     let fee_account;
@@ -313,6 +328,7 @@ async fn handle_submit_manifest(
         proof_ids: vec![],
         min_epoch: common.min_epoch.map(Epoch),
         max_epoch: common.max_epoch.map(Epoch),
+        deferred_signing: false,
     };
 
     submit_transaction(request, client).await?;
@@ -326,6 +342,7 @@ pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Res
         resource_address,
         destination_public_key,
         common,
+        assert_balances,
     } = args;
 
     let destination_public_key =
@@ -341,6 +358,8 @@ pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Res
             max_fee: fee,
             proof_from_badge_resource: None,
             dry_run: false,
+            priority_fee: 0,
+            assert_balances,
         })
         .await?;
 
@@ -42,6 +42,9 @@ pub struct Cli {
     pub indexer_node_json_rpc_url: Option<String>,
     #[clap(long)]
     pub derive_secret: Option<u64>,
+    /// The profile used by requests that do not specify a profile
+    #[clap(long)]
+    pub profile: Option<String>,
 }
 
 impl Cli {
@@ -77,6 +80,9 @@ impl ConfigOverrideProvider for Cli {
                 indexer_node_json_rpc_url.clone(),
             ));
         }
+        if let Some(ref profile) = self.profile {
+            overrides.push(("dan_wallet_daemon.active_profile".to_string(), profile.clone()));
+        }
         overrides
     }
 }
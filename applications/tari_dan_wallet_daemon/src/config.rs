@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{net::SocketAddr, path::PathBuf, time::Duration};
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, time::Duration};
 
 use config::Config;
 use serde::{Deserialize, Serialize};
@@ -70,6 +70,17 @@ pub struct WalletDaemonConfig {
     /// utility. If this is not set, the value lookup table will be generated on the fly which will have a large
     /// performance cost when brute forcing high-value outputs.
     pub value_lookup_table_file: Option<PathBuf>,
+    /// How long to wait for the signature of a transaction submitted with deferred signing before the transaction is
+    /// marked as invalid
+    #[serde(with = "humantime_serde")]
+    pub external_signing_timeout: Duration,
+    /// The profile used by requests that do not specify a profile. Defaults to the first configured profile.
+    pub active_profile: Option<String>,
+    /// Wallet profiles keyed by network id (e.g. "localnet" or "esmeralda"). Each profile has its own database, indexer
+    /// and key derivation domain. If no profiles are configured, a single "default" profile is used that stores its
+    /// data in data/wallet.sqlite and connects to `indexer_node_json_rpc_url`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, WalletProfileConfig>,
 }
 
 impl Default for WalletDaemonConfig {
@@ -85,10 +96,22 @@ impl Default for WalletDaemonConfig {
             jwt_secret_key: Some(create_secret()),
             http_ui_address: Some("127.0.0.1:5100".parse().unwrap()),
             value_lookup_table_file: None,
+            external_signing_timeout: Duration::from_secs(5 * 60),
+            active_profile: None,
+            profiles: BTreeMap::new(),
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WalletProfileConfig {
+    /// The indexer jrpc endpoint url used by this profile
+    pub indexer_node_json_rpc_url: String,
+    /// The domain tag included in the derivation of every key in this profile. Defaults to the profile name.
+    pub key_domain: Option<String>,
+}
+
 impl SubConfigPath for WalletDaemonConfig {
     fn main_key_prefix() -> &'static str {
         "dan_wallet_daemon"
@@ -1,9 +1,13 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
-use std::convert::TryFrom;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
 
 use anyhow::anyhow;
 use base64;
+use futures::{stream, StreamExt, TryStreamExt};
 use log::*;
 use rand::rngs::OsRng;
 use tari_common_types::types::{PrivateKey, PublicKey};
@@ -26,7 +30,7 @@ use tari_engine_types::{
     component::new_account_address_from_parts,
     confidential::ConfidentialClaim,
     instruction::Instruction,
-    substate::{Substate, SubstateId},
+    substate::{Substate, SubstateId, SubstateValue},
 };
 use tari_key_manager::key_manager::DerivedKey;
 use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
@@ -37,6 +41,7 @@ use tari_template_lib::{
 };
 use tari_transaction::{SubstateRequirement, Transaction};
 use tari_wallet_daemon_client::{
+    messages::WalletMessage,
     types::{
         AccountGetDefaultRequest,
         AccountGetRequest,
@@ -44,19 +49,32 @@ use tari_wallet_daemon_client::{
         AccountInfo,
         AccountSetDefaultRequest,
         AccountSetDefaultResponse,
+        AccountsBulkTransferRequest,
+        AccountsBulkTransferResponse,
         AccountsCreateFreeTestCoinsRequest,
         AccountsCreateFreeTestCoinsResponse,
         AccountsCreateRequest,
         AccountsCreateResponse,
         AccountsGetBalancesRequest,
         AccountsGetBalancesResponse,
+        AccountsGetQueueRequest,
+        AccountsGetQueueResponse,
+        AccountsGetVaultsRequest,
+        AccountsGetVaultsResponse,
         AccountsInvokeRequest,
         AccountsInvokeResponse,
         AccountsListRequest,
         AccountsListResponse,
+        AccountsSetQueueEnabledRequest,
+        AccountsSetQueueEnabledResponse,
+        AccountsSkipQueuedRequest,
+        AccountsSkipQueuedResponse,
         AccountsTransferRequest,
         AccountsTransferResponse,
+        AccountVaultEntry,
         BalanceEntry,
+        BulkTransferOutcome,
+        BulkTransferSummary,
         ClaimBurnRequest,
         ClaimBurnResponse,
         ConfidentialTransferRequest,
@@ -70,13 +88,17 @@ use tokio::task;
 
 use super::context::HandlerContext;
 use crate::{
-    handlers::helpers::{
-        get_account,
-        get_account_or_default,
-        get_account_with_inputs,
-        invalid_params,
-        wait_for_result,
-        wait_for_result_and_account,
+    handlers::{
+        bulk_transfer,
+        bulk_transfer::{BatchFailure, BatchSuccess},
+        helpers::{
+            get_account,
+            get_account_or_default,
+            get_account_with_inputs,
+            invalid_params,
+            wait_for_result,
+            wait_for_result_and_account,
+        },
     },
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     services::TransactionSubmittedEvent,
@@ -85,6 +107,9 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::transaction";
 
+const DEFAULT_BULK_TRANSFER_MAX_INSTRUCTIONS: u32 = 100;
+const DEFAULT_BULK_TRANSFER_MAX_CONCURRENT_TRANSACTIONS: u32 = 4;
+
 pub async fn handle_create(
     context: &HandlerContext,
     token: Option<String>,
@@ -92,11 +117,11 @@ pub async fn handle_create(
 ) -> Result<AccountsCreateResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
     let key_manager_api = sdk.key_manager_api();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     if let Some(name) = req.account_name.as_ref() {
         if sdk.accounts_api().get_account_by_name(name).optional()?.is_some() {
-            return Err(anyhow!("Account name '{}' already exists", name));
+            return Err(WalletMessage::AccountNameAlreadyExists { name: name.clone() }.into());
         }
     }
 
@@ -144,11 +169,17 @@ pub async fn handle_create(
 
     let event = wait_for_result(&mut events, tx_id).await?;
     if let Some(reject) = event.finalize.result.reject() {
-        return Err(anyhow!("Create account transaction rejected: {}", reject));
+        return Err(WalletMessage::TransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
 
     if let Some(reason) = event.finalize.reject() {
-        return Err(anyhow!("Create account transaction failed: {}", reason));
+        return Err(WalletMessage::TransactionFailed {
+            reason: reason.to_string(),
+        }
+        .into());
     }
 
     let address = event
@@ -174,19 +205,60 @@ pub async fn handle_set_default(
     req: AccountSetDefaultRequest,
 ) -> Result<AccountSetDefaultResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let account = get_account(&req.account, &sdk.accounts_api())?;
     sdk.accounts_api().set_default_account(&account.address)?;
     Ok(AccountSetDefaultResponse {})
 }
 
+pub async fn handle_get_queue(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsGetQueueRequest,
+) -> Result<AccountsGetQueueResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let account = get_account(&req.account, &sdk.accounts_api())?;
+    let queue = sdk.transaction_queue_api().get(&account.address).optional()?;
+    Ok(AccountsGetQueueResponse { queue })
+}
+
+pub async fn handle_set_queue_enabled(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsSetQueueEnabledRequest,
+) -> Result<AccountsSetQueueEnabledResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let account = get_account(&req.account, &sdk.accounts_api())?;
+    if req.enabled {
+        sdk.transaction_queue_api().enable(&account.address)?;
+    } else {
+        sdk.transaction_queue_api().disable(&account.address)?;
+    }
+    Ok(AccountsSetQueueEnabledResponse {})
+}
+
+pub async fn handle_skip_queued(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsSkipQueuedRequest,
+) -> Result<AccountsSkipQueuedResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let account = get_account(&req.account, &sdk.accounts_api())?;
+    // The next transaction in the queue is released by the transaction service
+    let transaction_id = sdk.transaction_queue_api().skip(&account.address)?;
+    Ok(AccountsSkipQueuedResponse { transaction_id })
+}
+
 pub async fn handle_list(
     context: &HandlerContext,
     token: Option<String>,
     req: AccountsListRequest,
 ) -> Result<AccountsListResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let accounts = sdk.accounts_api().get_many(req.offset, req.limit)?;
     let total = sdk.accounts_api().count()?;
     let km = sdk.key_manager_api();
@@ -211,7 +283,7 @@ pub async fn handle_invoke(
     req: AccountsInvokeRequest,
 ) -> Result<AccountsInvokeResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let account = get_account_or_default(req.account, &sdk.accounts_api())?;
 
@@ -241,10 +313,16 @@ pub async fn handle_invoke(
 
     let mut finalized = wait_for_result(&mut events, tx_id).await?;
     if let Some(reject) = finalized.finalize.result.reject() {
-        return Err(anyhow!("Fee transaction rejected: {}", reject));
+        return Err(WalletMessage::FeeTransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
     if let Some(reject) = finalized.finalize.reject() {
-        return Err(anyhow!("Transaction rejected: {}", reject));
+        return Err(WalletMessage::TransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
 
     Ok(AccountsInvokeResponse {
@@ -259,7 +337,8 @@ pub async fn handle_get_balances(
 ) -> Result<AccountsGetBalancesResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
     let account = get_account_or_default(req.account, &sdk.accounts_api())?;
-    sdk.jwt_api()
+    context
+        .jwt_api()
         .check_auth(token, &[JrpcPermission::AccountBalance(account.clone().address)])?;
     if req.refresh {
         context
@@ -287,13 +366,68 @@ pub async fn handle_get_balances(
     })
 }
 
+pub async fn handle_get_vaults(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsGetVaultsRequest,
+) -> Result<AccountsGetVaultsResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    context
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::AccountBalance(account.clone().address)])?;
+
+    let substate_api = sdk.substate_api();
+    let vaults = sdk.accounts_api().get_vaults_by_account(&account.address)?;
+    let mut latest_vaults = HashMap::with_capacity(vaults.len());
+    for vault in vaults {
+        match substate_api.scan_for_substate(&vault.address, None).await {
+            Ok(ValidatorScanResult {
+                substate: SubstateValue::Vault(latest),
+                created_by_tx,
+                ..
+            }) => {
+                latest_vaults.insert(vault.address, (latest, created_by_tx));
+            },
+            Ok(_) => {
+                warn!(target: LOG_TARGET, "Substate {} is not a vault", vault.address);
+            },
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to scan vault {}, falling back to cached state: {}", vault.address, err
+                );
+            },
+        }
+    }
+
+    let vaults = sdk
+        .accounts_api()
+        .get_vault_breakdown(&account.address, &latest_vaults)?
+        .into_iter()
+        .map(|vault| AccountVaultEntry {
+            vault_address: vault.vault_address,
+            resource_address: vault.resource_address,
+            resource_type: vault.resource_type,
+            revealed_balance: vault.revealed_balance,
+            confidential_commitment_count: vault.confidential_commitment_count,
+            last_updated_transaction: vault.last_updated_transaction,
+        })
+        .collect();
+
+    Ok(AccountsGetVaultsResponse {
+        address: account.address,
+        vaults,
+    })
+}
+
 pub async fn handle_get(
     context: &HandlerContext,
     token: Option<String>,
     req: AccountGetRequest,
 ) -> Result<AccountGetResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let account = get_account(&req.name_or_address, &sdk.accounts_api())?;
     let km = sdk.key_manager_api();
     let key = km.derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
@@ -307,7 +441,7 @@ pub async fn handle_get_default(
     _req: AccountGetDefaultRequest,
 ) -> Result<AccountGetResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::AccountInfo])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::AccountInfo])?;
     let account = get_account_or_default(None, &sdk.accounts_api())?;
     let km = sdk.key_manager_api();
     let key = km.derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
@@ -322,7 +456,7 @@ pub async fn handle_reveal_funds(
     req: RevealFundsRequest,
 ) -> Result<RevealFundsResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let notifier = context.notifier().clone();
     let transaction_service = context.transaction_service().clone();
 
@@ -442,7 +576,10 @@ pub async fn handle_reveal_funds(
 
         let finalized = wait_for_result(&mut events, tx_id).await?;
         if let Some(reason) = finalized.finalize.reject() {
-            return Err(anyhow::anyhow!("Transaction failed: {}", reason));
+            return Err(WalletMessage::TransactionRejected {
+                reason: reason.to_string(),
+            }
+            .into());
         }
 
         Ok(RevealFundsResponse {
@@ -461,7 +598,7 @@ pub async fn handle_claim_burn(
     req: ClaimBurnRequest,
 ) -> Result<ClaimBurnResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let ClaimBurnRequest {
         account,
@@ -577,11 +714,11 @@ pub async fn handle_claim_burn(
 
     let final_amount = Amount::try_from(unmasked_output.value)? - max_fee;
     if final_amount.is_negative() {
-        return Err(anyhow::anyhow!(
-            "Fee ({}) is greater than the claimed output amount ({})",
-            max_fee,
-            unmasked_output.value
-        ));
+        return Err(WalletMessage::FeeExceedsClaimedAmount {
+            fee: max_fee.value(),
+            amount: unmasked_output.value,
+        }
+        .into());
     }
 
     let encrypted_data = sdk.confidential_crypto_api().encrypt_value_and_mask(
@@ -666,7 +803,9 @@ async fn finish_claiming<T: WalletStore>(
     });
     let account_component_address = account_address
         .as_component_address()
-        .ok_or_else(|| anyhow!("Invalid account address"))?;
+        .ok_or_else(|| WalletMessage::InvalidAccountAddress {
+            address: account_address.to_string(),
+        })?;
     if new_account_name.is_none() {
         // Add all versioned account child addresses as inputs unless the account is new
         let child_addresses = sdk.substate_api().load_dependent_substates(&[&account_address])?;
@@ -714,13 +853,16 @@ async fn finish_claiming<T: WalletStore>(
     let (finalized, _) = wait_for_result_and_account(&mut events, &tx_id, &account_address).await?;
     // let finalized = wait_for_result(&mut events, tx_id).await?;
     if let Some(reject) = finalized.finalize.reject() {
-        return Err(anyhow::anyhow!("Fee transaction rejected: {}", reject));
+        return Err(WalletMessage::FeeTransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
     if let Some(reason) = finalized.finalize.full_reject() {
-        return Err(anyhow::anyhow!(
-            "Fee transaction succeeded (fees charged) however the transaction failed: {}",
-            reason
-        ));
+        return Err(WalletMessage::TransactionFailed {
+            reason: reason.to_string(),
+        }
+        .into());
     }
 
     Ok((tx_id, finalized))
@@ -734,7 +876,7 @@ pub async fn handle_create_free_test_coins(
     req: AccountsCreateFreeTestCoinsRequest,
 ) -> Result<AccountsCreateFreeTestCoinsResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let AccountsCreateFreeTestCoinsRequest {
         account,
@@ -807,7 +949,7 @@ fn get_or_create_account<T: WalletStore>(
             let account = accounts_api
                 .get_default()
                 .optional()?
-                .ok_or_else(|| anyhow::anyhow!("No default account found. Please set a default account."))?;
+                .ok_or(WalletMessage::NoDefaultAccount)?;
 
             Some(account)
         },
@@ -828,7 +970,7 @@ fn get_or_create_account<T: WalletStore>(
                 .as_ref()
                 .unwrap()
                 .name()
-                .ok_or_else(|| anyhow!("Account name must be provided when creating a new account"))?;
+                .ok_or(WalletMessage::AccountNameRequired)?;
             let account_secret_key = key_id
                 .map(|idx| sdk.key_manager_api().derive_key(key_manager::TRANSACTION_BRANCH, idx))
                 .unwrap_or_else(|| sdk.key_manager_api().next_key(key_manager::TRANSACTION_BRANCH))?;
@@ -850,7 +992,7 @@ pub async fn handle_transfer(
     req: AccountsTransferRequest,
 ) -> Result<AccountsTransferResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let (account, mut inputs) = get_account_with_inputs(req.account, &sdk)?;
 
@@ -858,7 +1000,9 @@ pub async fn handle_transfer(
     let source_account_address = account
         .address
         .as_component_address()
-        .ok_or_else(|| anyhow!("Invalid account address"))?;
+        .ok_or_else(|| WalletMessage::InvalidAccountAddress {
+            address: account.address.to_string(),
+        })?;
 
     // add the input for the source account vault substate
     let src_vault = sdk
@@ -920,12 +1064,35 @@ pub async fn handle_transfer(
         Instruction::PutLastInstructionOutputOnWorkspace {
             key: b"bucket".to_vec(),
         },
-        Instruction::CallMethod {
-            component_address: destination_account_address,
-            method: "deposit".to_string(),
-            args: args![Workspace("bucket")],
-        },
     ]);
+    if req.assert_balances {
+        instructions.push(Instruction::AssertBucketContains {
+            workspace_bucket: "bucket".to_string(),
+            resource_address: req.resource_address,
+            min_amount: req.amount,
+        });
+    }
+    instructions.push(Instruction::CallMethod {
+        component_address: destination_account_address,
+        method: "deposit".to_string(),
+        args: args![Workspace("bucket")],
+    });
+    if req.assert_balances {
+        let vault_id = src_vault
+            .address
+            .as_vault_id()
+            .ok_or_else(|| anyhow!("Source vault address {} is not a vault", src_vault.address))?;
+        // The fee instructions are executed first, so the maximum fee has already been taken from the XTR vault
+        let fee = if req.resource_address == CONFIDENTIAL_TARI_RESOURCE_ADDRESS {
+            max_fee
+        } else {
+            Amount::zero()
+        };
+        instructions.push(Instruction::AssertVaultBalanceAtLeast {
+            vault_id,
+            min_amount: (src_vault.revealed_balance - req.amount - fee).max(Amount::zero()),
+        });
+    }
 
     if req.proof_from_badge_resource.is_some() {
         instructions.push(Instruction::DropAllProofsInWorkspace);
@@ -945,6 +1112,7 @@ pub async fn handle_transfer(
         .with_fee_instructions(fee_instructions)
         .with_instructions(instructions)
         .with_inputs(vec![resource_substate_address])
+        .with_priority_fee(req.priority_fee)
         .sign(&account_secret_key.key)
         .build();
 
@@ -974,13 +1142,16 @@ pub async fn handle_transfer(
     let finalized = wait_for_result(&mut events, tx_id).await?;
 
     if let Some(reject) = finalized.finalize.result.reject() {
-        return Err(anyhow::anyhow!("Fee transaction rejected: {}", reject));
+        return Err(WalletMessage::FeeTransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
     if let Some(reason) = finalized.finalize.reject() {
-        return Err(anyhow::anyhow!(
-            "Fee transaction succeeded (fees charged) however the transaction failed: {}",
-            reason
-        ));
+        return Err(WalletMessage::TransactionFailed {
+            reason: reason.to_string(),
+        }
+        .into());
     }
     info!(
         target: LOG_TARGET,
@@ -997,13 +1168,234 @@ pub async fn handle_transfer(
     })
 }
 
+pub async fn handle_bulk_transfer(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsBulkTransferRequest,
+) -> Result<AccountsBulkTransferResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    if req.recipients.is_empty() {
+        return Err(invalid_params("recipients", Some("must not be empty")));
+    }
+    let mut destination_keys = HashSet::with_capacity(req.recipients.len());
+    for recipient in &req.recipients {
+        if !recipient.amount.is_positive() {
+            return Err(invalid_params(
+                "recipients",
+                Some(format!(
+                    "amount for {} must be positive",
+                    recipient.destination_public_key
+                )),
+            ));
+        }
+        if !destination_keys.insert(&recipient.destination_public_key) {
+            return Err(invalid_params(
+                "recipients",
+                Some(format!("duplicate recipient {}", recipient.destination_public_key)),
+            ));
+        }
+    }
+
+    let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    let source_account_address = account
+        .address
+        .as_component_address()
+        .ok_or_else(|| WalletMessage::InvalidAccountAddress {
+            address: account.address.to_string(),
+        })?;
+
+    let src_vault = sdk
+        .accounts_api()
+        .get_vault_by_resource(&account.address, &req.resource_address)?;
+    let total_amount = req.recipients.iter().map(|recipient| recipient.amount).sum::<Amount>();
+    let available = src_vault.available_revealed_balance();
+    if available < total_amount {
+        return Err(WalletMessage::InsufficientFunds {
+            resource_address: req.resource_address.to_string(),
+            required: total_amount.value(),
+            available: available.value(),
+        }
+        .into());
+    }
+
+    let max_concurrency = req
+        .max_concurrent_transactions
+        .unwrap_or(DEFAULT_BULK_TRANSFER_MAX_CONCURRENT_TRANSACTIONS)
+        .max(1) as usize;
+
+    // Find out which recipients already have an account, the others need one to be created
+    let destinations = stream::iter(&req.recipients)
+        .map(|recipient| {
+            let sdk = &sdk;
+            async move {
+                let address =
+                    new_account_address_from_parts(&ACCOUNT_TEMPLATE_ADDRESS, &recipient.destination_public_key);
+                let existing_account = sdk
+                    .substate_api()
+                    .scan_for_substate(&SubstateId::Component(address), None)
+                    .await
+                    .optional()?;
+                Ok::<_, anyhow::Error>((address, existing_account.is_some()))
+            }
+        })
+        .buffered(max_concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let instruction_counts = destinations
+        .iter()
+        .map(|(_, exists)| if *exists { 3 } else { 4 })
+        .collect::<Vec<_>>();
+    let max_instructions = req
+        .max_instructions_per_transaction
+        .unwrap_or(DEFAULT_BULK_TRANSFER_MAX_INSTRUCTIONS) as usize;
+    let batches = bulk_transfer::pack_recipients(&instruction_counts, max_instructions);
+
+    let account_secret_key = sdk
+        .key_manager_api()
+        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+    let max_fee = req.max_fee_per_transaction.unwrap_or(DEFAULT_FEE);
+
+    info!(
+        target: LOG_TARGET,
+        "Bulk transfer of {} {} to {} recipient(s) in {} transaction(s)",
+        total_amount,
+        req.resource_address,
+        req.recipients.len(),
+        batches.len()
+    );
+
+    let run = bulk_transfer::run_batches(req.recipients.len(), batches, max_concurrency, |batch| {
+        let mut instructions = vec![];
+        // The versions are resolved when the transaction is submitted, since the transactions for other batches may
+        // change the source account at the same time
+        let mut inputs = vec![
+            SubstateRequirement::new(account.address.clone(), None),
+            SubstateRequirement::new(src_vault.address.clone(), None),
+            SubstateRequirement::new(SubstateId::Resource(req.resource_address), None),
+        ];
+        for i in batch {
+            let recipient = &req.recipients[i];
+            let (destination_address, exists) = destinations[i];
+            if exists {
+                inputs.push(SubstateRequirement::new(destination_address.into(), None));
+            } else {
+                instructions.push(Instruction::CreateAccount {
+                    owner_public_key: recipient.destination_public_key.clone(),
+                    workspace_bucket: None,
+                });
+            }
+            instructions.extend([
+                Instruction::CallMethod {
+                    component_address: source_account_address,
+                    method: "withdraw".to_string(),
+                    args: args![req.resource_address, recipient.amount],
+                },
+                Instruction::PutLastInstructionOutputOnWorkspace {
+                    key: b"bucket".to_vec(),
+                },
+                Instruction::CallMethod {
+                    component_address: destination_address,
+                    method: "deposit".to_string(),
+                    args: args![Workspace("bucket")],
+                },
+            ]);
+        }
+
+        let transaction = Transaction::builder()
+            .fee_transaction_pay_from_component(source_account_address, max_fee)
+            .with_instructions(instructions)
+            .with_priority_fee(req.priority_fee)
+            .sign(&account_secret_key.key)
+            .build();
+
+        async move {
+            let mut events = context.notifier().subscribe();
+            let transaction_id = context
+                .transaction_service()
+                .submit_transaction(transaction, inputs)
+                .await
+                .map_err(|err| BatchFailure {
+                    transaction_id: None,
+                    fee: Amount::zero(),
+                    reason: err.to_string(),
+                    can_split: false,
+                })?;
+
+            // The transaction may have been finalized even if waiting for it failed, so it is not retried
+            let finalized = wait_for_result(&mut events, transaction_id)
+                .await
+                .map_err(|err| BatchFailure {
+                    transaction_id: Some(transaction_id),
+                    fee: Amount::zero(),
+                    reason: err.to_string(),
+                    can_split: false,
+                })?;
+            if let Some(reason) = finalized.finalize.full_reject() {
+                warn!(
+                    target: LOG_TARGET,
+                    "Bulk transfer transaction {} failed: {}", transaction_id, reason
+                );
+                return Err(BatchFailure {
+                    transaction_id: Some(transaction_id),
+                    fee: finalized.final_fee,
+                    reason: reason.to_string(),
+                    can_split: true,
+                });
+            }
+
+            Ok(BatchSuccess {
+                transaction_id,
+                fee: finalized.final_fee,
+            })
+        }
+    })
+    .await;
+
+    let outcomes = req
+        .recipients
+        .into_iter()
+        .zip(run.results)
+        .map(|(recipient, result)| BulkTransferOutcome {
+            destination_public_key: recipient.destination_public_key,
+            amount: recipient.amount,
+            transaction_id: result.transaction_id,
+            failure_reason: result.failure_reason,
+        })
+        .collect::<Vec<_>>();
+    let num_succeeded = outcomes.iter().filter(|outcome| outcome.is_success()).count();
+    let summary = BulkTransferSummary {
+        num_succeeded: num_succeeded as u32,
+        num_failed: (outcomes.len() - num_succeeded) as u32,
+        num_transactions: run.num_transactions as u32,
+        total_amount_transferred: outcomes
+            .iter()
+            .filter(|outcome| outcome.is_success())
+            .map(|outcome| outcome.amount)
+            .sum(),
+        total_fees: run.total_fees,
+    };
+    info!(
+        target: LOG_TARGET,
+        "✅ Bulk transfer finished: {} succeeded, {} failed, {} transaction(s), fees: {}",
+        summary.num_succeeded,
+        summary.num_failed,
+        summary.num_transactions,
+        summary.total_fees
+    );
+
+    Ok(AccountsBulkTransferResponse { outcomes, summary })
+}
+
 pub async fn handle_confidential_transfer(
     context: &HandlerContext,
     token: Option<String>,
     req: ConfidentialTransferRequest,
 ) -> Result<ConfidentialTransferResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let notifier = context.notifier().clone();
 
     if req.amount.is_negative() {
@@ -1059,13 +1451,16 @@ pub async fn handle_confidential_transfer(
 
         let finalized = wait_for_result(&mut events, tx_id).await?;
         if let Some(reject) = finalized.finalize.result.reject() {
-            return Err(anyhow::anyhow!("Fee transaction rejected: {}", reject));
+            return Err(WalletMessage::FeeTransactionRejected {
+                reason: reject.to_string(),
+            }
+            .into());
         }
         if let Some(reason) = finalized.finalize.reject() {
-            return Err(anyhow::anyhow!(
-                "Fee transaction succeeded (fees charged) however the transaction failed: {}",
-                reason
-            ));
+            return Err(WalletMessage::TransactionFailed {
+                reason: reason.to_string(),
+            }
+            .into());
         }
 
         Ok(ConfidentialTransferResponse {
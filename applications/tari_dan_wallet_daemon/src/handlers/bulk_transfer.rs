@@ -0,0 +1,214 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::VecDeque, future::Future};
+
+use futures::{stream::FuturesUnordered, StreamExt};
+use tari_template_lib::models::Amount;
+use tari_transaction::TransactionId;
+
+/// A transaction for a batch of recipients that was finalized successfully
+#[derive(Debug, Clone)]
+pub(super) struct BatchSuccess {
+    pub transaction_id: TransactionId,
+    pub fee: Amount,
+}
+
+/// A transaction for a batch of recipients that was not finalized successfully
+#[derive(Debug, Clone)]
+pub(super) struct BatchFailure {
+    /// None if the transaction could not be submitted
+    pub transaction_id: Option<TransactionId>,
+    /// The fee that was charged even though the transaction failed
+    pub fee: Amount,
+    pub reason: String,
+    /// True if the transaction itself was rejected, in which case a smaller batch may succeed. False if the failure
+    /// does not depend on the recipients, e.g. the transaction could not be submitted.
+    pub can_split: bool,
+}
+
+/// The outcome of a bulk transfer for one recipient
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct RecipientResult {
+    pub transaction_id: Option<TransactionId>,
+    pub failure_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct BulkTransferRun {
+    /// The result for each recipient, by index
+    pub results: Vec<RecipientResult>,
+    pub num_transactions: usize,
+    pub total_fees: Amount,
+}
+
+/// Packs recipients, in order, into batches of at most `max_instructions` instructions. `instruction_counts` are the
+/// number of instructions required for each recipient. A recipient that requires more than `max_instructions` is put
+/// in a batch of its own.
+pub(super) fn pack_recipients(instruction_counts: &[usize], max_instructions: usize) -> Vec<Vec<usize>> {
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut batch_instructions = 0;
+    for (i, &count) in instruction_counts.iter().enumerate() {
+        if !batch.is_empty() && batch_instructions + count > max_instructions {
+            batches.push(std::mem::take(&mut batch));
+            batch_instructions = 0;
+        }
+        batch.push(i);
+        batch_instructions += count;
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+/// Submits a transaction for each batch of recipient indexes with at most `max_concurrency` transactions in flight. A
+/// batch whose transaction is rejected is split in half and each half is submitted again, until the recipients that
+/// cause the rejection are in a batch of their own. A single rejected recipient therefore does not prevent the others
+/// from receiving their transfer, at the cost of the fees of the rejected transactions.
+pub(super) async fn run_batches<F, Fut>(
+    num_recipients: usize,
+    batches: Vec<Vec<usize>>,
+    max_concurrency: usize,
+    mut submit: F,
+) -> BulkTransferRun
+where
+    F: FnMut(Vec<usize>) -> Fut,
+    Fut: Future<Output = Result<BatchSuccess, BatchFailure>>,
+{
+    let mut run = BulkTransferRun {
+        results: vec![RecipientResult::default(); num_recipients],
+        num_transactions: 0,
+        total_fees: Amount::zero(),
+    };
+    let mut pending = VecDeque::from(batches);
+    let mut in_flight = FuturesUnordered::new();
+
+    loop {
+        while in_flight.len() < max_concurrency.max(1) {
+            let Some(batch) = pending.pop_front() else {
+                break;
+            };
+            let fut = submit(batch.clone());
+            in_flight.push(async move { (batch, fut.await) });
+        }
+
+        let Some((mut batch, result)) = in_flight.next().await else {
+            break;
+        };
+
+        match result {
+            Ok(success) => {
+                run.num_transactions += 1;
+                run.total_fees += success.fee;
+                for i in batch {
+                    run.results[i] = RecipientResult {
+                        transaction_id: Some(success.transaction_id),
+                        failure_reason: None,
+                    };
+                }
+            },
+            Err(failure) => {
+                if failure.transaction_id.is_some() {
+                    run.num_transactions += 1;
+                }
+                run.total_fees += failure.fee;
+                if failure.can_split && batch.len() > 1 {
+                    let second_half = batch.split_off(batch.len() / 2);
+                    pending.push_front(second_half);
+                    pending.push_front(batch);
+                    continue;
+                }
+                for i in batch {
+                    run.results[i] = RecipientResult {
+                        transaction_id: failure.transaction_id,
+                        failure_reason: Some(failure.reason.clone()),
+                    };
+                }
+            },
+        }
+    }
+
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_packs_recipients_up_to_the_instruction_budget() {
+        assert_eq!(pack_recipients(&[3, 4, 4, 3, 3], 10), vec![vec![0, 1], vec![2, 3, 4]]);
+        assert_eq!(pack_recipients(&[3, 3, 3], 9), vec![vec![0, 1, 2]]);
+        // A recipient that does not fit into the budget is put in a batch of its own
+        assert_eq!(pack_recipients(&[3, 4, 3], 2), vec![vec![0], vec![1], vec![2]]);
+        assert!(pack_recipients(&[], 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_isolates_recipients_that_deny_deposits() {
+        const DENIES_DEPOSITS: usize = 3;
+        let mut num_submitted = 0u8;
+        let batches = pack_recipients(&[3; 6], 9);
+        assert_eq!(batches, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+
+        let run = run_batches(6, batches, 2, |batch| {
+            num_submitted += 1;
+            let transaction_id = TransactionId::new([num_submitted; 32]);
+            async move {
+                if batch.contains(&DENIES_DEPOSITS) {
+                    return Err(BatchFailure {
+                        transaction_id: Some(transaction_id),
+                        fee: Amount(10),
+                        reason: "deposit denied".to_string(),
+                        can_split: true,
+                    });
+                }
+                Ok(BatchSuccess {
+                    transaction_id,
+                    fee: Amount(20),
+                })
+            }
+        })
+        .await;
+
+        for (i, result) in run.results.iter().enumerate() {
+            assert!(result.transaction_id.is_some());
+            if i == DENIES_DEPOSITS {
+                assert_eq!(result.failure_reason.as_deref(), Some("deposit denied"));
+            } else {
+                assert_eq!(result.failure_reason, None, "recipient {} failed", i);
+            }
+        }
+        // The first batch succeeds. [3, 4, 5] fails and is split into [3], which fails, and [4, 5], which succeeds.
+        assert_eq!(run.num_transactions, 4);
+        assert_eq!(run.total_fees, Amount(20 + 10 + 10 + 20));
+        assert_eq!(run.results[4].transaction_id, run.results[5].transaction_id);
+        assert_ne!(run.results[0].transaction_id, run.results[4].transaction_id);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_split_batches_that_could_not_be_submitted() {
+        let mut num_submitted = 0;
+        let run = run_batches(4, vec![vec![0, 1, 2, 3]], 1, |_| {
+            num_submitted += 1;
+            async {
+                Err(BatchFailure {
+                    transaction_id: None,
+                    fee: Amount::zero(),
+                    reason: "network error".to_string(),
+                    can_split: false,
+                })
+            }
+        })
+        .await;
+
+        assert_eq!(num_submitted, 1);
+        assert_eq!(run.num_transactions, 0);
+        assert!(run
+            .results
+            .iter()
+            .all(|r| r.transaction_id.is_none() && r.failure_reason.as_deref() == Some("network error")));
+    }
+}
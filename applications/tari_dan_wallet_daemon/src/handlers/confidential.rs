@@ -44,7 +44,7 @@ pub async fn handle_create_transfer_proof(
     req: ProofsGenerateRequest,
 ) -> Result<ProofsGenerateResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     if req.amount.is_negative() || req.reveal_amount.is_negative() {
         return Err(JsonRpcError::new(
@@ -183,7 +183,7 @@ pub async fn handle_finalize_transfer(
     req: ProofsCancelRequest,
 ) -> Result<ProofsCancelResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     sdk.confidential_outputs_api()
         .finalize_locked_revealed_funds(req.proof_id)?;
@@ -198,7 +198,7 @@ pub async fn handle_cancel_transfer(
     req: ProofsCancelRequest,
 ) -> Result<ProofsCancelResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     sdk.confidential_outputs_api().release_proof_outputs(req.proof_id)?;
     Ok(ProofsCancelResponse {})
 }
@@ -209,7 +209,7 @@ pub async fn handle_create_output_proof(
     req: ConfidentialCreateOutputProofRequest,
 ) -> Result<ConfidentialCreateOutputProofResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     if req.amount.is_negative() {
         return Err(invalid_params("amount", Some("must be positive")));
@@ -244,7 +244,7 @@ pub async fn handle_view_vault_balance(
     req: ConfidentialViewVaultBalanceRequest,
 ) -> Result<ConfidentialViewVaultBalanceResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let substate = sdk.substate_api().scan_for_substate(&req.vault_id.into(), None).await?;
     let vault = substate
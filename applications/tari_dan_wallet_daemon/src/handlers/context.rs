@@ -1,59 +1,94 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_wallet_sdk::DanWalletSdk;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use tari_dan_wallet_sdk::{apis::jwt::JwtApi, signer::SigningRequest};
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_transaction::TransactionId;
+use tari_wallet_daemon_client::messages::WalletMessage;
 
 use crate::{
     config::WalletDaemonConfig,
-    indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     notify::Notify,
+    profiles::{WalletProfile, WalletProfiles, WalletSdk},
     services::{AccountMonitorHandle, TransactionServiceHandle, WalletEvent},
 };
 
+/// Signing requests for transactions submitted with deferred signing, keyed by the pending transaction id
+pub type PendingSigningRequests = Arc<Mutex<HashMap<TransactionId, SigningRequest>>>;
+
+/// The context of a request. A context is scoped to a single wallet profile, and all wallet state accessed through it
+/// belongs to that profile.
 #[derive(Debug, Clone)]
 pub struct HandlerContext {
-    wallet_sdk: DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
-    notifier: Notify<WalletEvent>,
-    transaction_service: TransactionServiceHandle,
-    account_monitor: AccountMonitorHandle,
+    profile: WalletProfile,
+    profiles: WalletProfiles,
+    auth_store: SqliteWalletStore,
     config: WalletDaemonConfig,
 }
 
 impl HandlerContext {
-    pub fn new(
-        wallet_sdk: DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
-        notifier: Notify<WalletEvent>,
-        transaction_service: TransactionServiceHandle,
-        account_monitor: AccountMonitorHandle,
-        config: WalletDaemonConfig,
-    ) -> Self {
+    /// Creates a context for the active profile. JWTs are issued and revoked using `auth_store` regardless of the
+    /// profile, so that a token is valid for every profile.
+    pub fn new(profiles: WalletProfiles, auth_store: SqliteWalletStore, config: WalletDaemonConfig) -> Self {
+        let profile = profiles.get(None).expect("active profile always exists").clone();
         Self {
-            wallet_sdk,
-            notifier,
-            transaction_service,
-            account_monitor,
+            profile,
+            profiles,
+            auth_store,
             config,
         }
     }
 
+    /// Returns a context for the given profile, or for the active profile if no profile is given
+    pub fn for_profile(&self, name: Option<&str>) -> Result<Self, WalletMessage> {
+        Ok(Self {
+            profile: self.profiles.get(name)?.clone(),
+            ..self.clone()
+        })
+    }
+
+    pub fn profile(&self) -> &WalletProfile {
+        &self.profile
+    }
+
+    pub fn profiles(&self) -> &WalletProfiles {
+        &self.profiles
+    }
+
+    pub fn jwt_api(&self) -> JwtApi<'_, SqliteWalletStore> {
+        JwtApi::new(
+            &self.auth_store,
+            self.config.jwt_expiry.unwrap(),
+            self.config.jwt_secret_key.clone().unwrap(),
+        )
+    }
+
     pub fn notifier(&self) -> &Notify<WalletEvent> {
-        &self.notifier
+        self.profile.notifier()
     }
 
-    pub fn wallet_sdk(&self) -> &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface> {
-        &self.wallet_sdk
+    pub fn wallet_sdk(&self) -> &WalletSdk {
+        self.profile.wallet_sdk()
     }
 
     pub fn account_monitor(&self) -> &AccountMonitorHandle {
-        &self.account_monitor
+        self.profile.account_monitor()
     }
 
     pub fn transaction_service(&self) -> &TransactionServiceHandle {
-        &self.transaction_service
+        self.profile.transaction_service()
     }
 
     pub fn config(&self) -> &WalletDaemonConfig {
         &self.config
     }
+
+    pub fn pending_signing_requests(&self) -> &PendingSigningRequests {
+        self.profile.pending_signing_requests()
+    }
 }
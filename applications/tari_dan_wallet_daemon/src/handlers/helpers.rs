@@ -12,7 +12,7 @@ use tari_dan_wallet_sdk::{
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_engine_types::substate::SubstateId;
 use tari_transaction::TransactionId;
-use tari_wallet_daemon_client::ComponentAddressOrName;
+use tari_wallet_daemon_client::{messages::WalletMessage, ComponentAddressOrName};
 use tokio::sync::broadcast;
 
 use crate::{
@@ -133,7 +133,7 @@ where
         result = accounts_api
             .get_default()
             .optional()?
-            .ok_or_else(|| anyhow::anyhow!("No default account found. Please set a default account."))?;
+            .ok_or(WalletMessage::NoDefaultAccount)?;
     }
     Ok(result)
 }
@@ -21,7 +21,7 @@ pub async fn handle_create(
     req: KeysCreateRequest,
 ) -> Result<KeysCreateResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let key_manager = sdk.key_manager_api();
     let key = req
         .specific_index
@@ -39,7 +39,7 @@ pub async fn handle_list(
     req: KeysListRequest,
 ) -> Result<KeysListResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::KeyList])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::KeyList])?;
     let keys = sdk.key_manager_api().get_all_keys(req.branch.as_str())?;
     Ok(KeysListResponse { keys })
 }
@@ -50,7 +50,7 @@ pub async fn handle_set_active(
     req: KeysSetActiveRequest,
 ) -> Result<KeysSetActiveResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let km = sdk.key_manager_api();
     km.set_active_key(key_manager::TRANSACTION_BRANCH, req.index)?;
     let (_, key) = km.get_active_key(key_manager::TRANSACTION_BRANCH)?;
@@ -0,0 +1,21 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_wallet_daemon_client::{
+    messages::WalletMessage,
+    types::{GetMessageCatalogueRequest, GetMessageCatalogueResponse},
+};
+
+use crate::handlers::HandlerContext;
+
+/// Returns every message that the wallet daemon may emit so that front-ends can provide translations. This does not
+/// require authentication.
+pub async fn handle_get_message_catalogue(
+    _context: &HandlerContext,
+    _token: Option<String>,
+    _req: GetMessageCatalogueRequest,
+) -> Result<GetMessageCatalogueResponse, anyhow::Error> {
+    Ok(GetMessageCatalogueResponse {
+        messages: WalletMessage::catalogue(),
+    })
+}
@@ -2,12 +2,15 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 pub mod accounts;
+mod bulk_transfer;
 pub mod confidential;
 mod context;
 pub mod error;
 mod helpers;
 pub mod keys;
+pub mod meta;
 pub mod nfts;
+pub mod profiles;
 pub mod rpc;
 pub mod settings;
 pub mod substates;
@@ -19,7 +22,7 @@ pub mod webrtc;
 use std::future::Future;
 
 use axum::async_trait;
-pub use context::HandlerContext;
+pub use context::{HandlerContext, PendingSigningRequests};
 use error::HandlerError;
 
 #[async_trait]
@@ -44,7 +44,7 @@ pub async fn handle_get_nft(
     req: GetAccountNftRequest,
 ) -> Result<GetAccountNftResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let non_fungible_api = sdk.non_fungible_api();
 
@@ -64,7 +64,7 @@ pub async fn handle_list_nfts(
     let sdk = context.wallet_sdk();
     let account = get_account_or_default(account, &sdk.accounts_api())?;
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let non_fungible_api = sdk.non_fungible_api();
 
@@ -81,7 +81,7 @@ pub async fn handle_mint_account_nft(
 ) -> Result<MintAccountNftResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
     let key_manager_api = sdk.key_manager_api();
-    sdk.jwt_api().check_auth(token.clone(), &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token.clone(), &[JrpcPermission::Admin])?;
 
     let account = get_account(&req.account, &sdk.accounts_api())?;
 
@@ -178,7 +178,7 @@ async fn mint_account_nft(
     metadata: Metadata,
 ) -> Result<TransactionFinalizedEvent, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let inputs = sdk
         .substate_api()
@@ -246,7 +246,7 @@ async fn create_account_nft(
     token: Option<String>,
 ) -> Result<TransactionFinalizedEvent, anyhow::Error> {
     let sdk = context.wallet_sdk();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let inputs = sdk
         .substate_api()
@@ -0,0 +1,50 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_wallet_sdk::apis::jwt::JrpcPermission;
+use tari_wallet_daemon_client::types::{
+    ProfilesListRequest,
+    ProfilesListResponse,
+    ProfilesSwitchRequest,
+    ProfilesSwitchResponse,
+    WalletProfileInfo,
+};
+
+use crate::handlers::HandlerContext;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::profiles";
+
+pub async fn handle_list(
+    context: &HandlerContext,
+    token: Option<String>,
+    _req: ProfilesListRequest,
+) -> Result<ProfilesListResponse, anyhow::Error> {
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let profiles = context
+        .profiles()
+        .iter()
+        .map(|profile| WalletProfileInfo {
+            name: profile.name().to_string(),
+            indexer_url: profile.wallet_sdk().get_network_interface().get_endpoint().to_string(),
+            key_domain: profile.settings().key_domain.clone(),
+        })
+        .collect();
+
+    Ok(ProfilesListResponse {
+        profiles,
+        active_profile: context.profiles().active_profile_name(),
+    })
+}
+
+/// Sets the profile that is used by requests that do not specify a profile
+pub async fn handle_switch(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: ProfilesSwitchRequest,
+) -> Result<ProfilesSwitchResponse, anyhow::Error> {
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.profiles().switch(&req.name)?;
+    info!(target: LOG_TARGET, "Switched active profile to '{}'", req.name);
+    Ok(ProfilesSwitchResponse {})
+}
@@ -30,7 +30,7 @@ pub async fn handle_login_request(
     _token: Option<String>,
     auth_request: AuthLoginRequest,
 ) -> Result<AuthLoginResponse, anyhow::Error> {
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
 
     let (auth_token, valid_till) =
         jwt.generate_auth_token(auth_request.permissions.as_slice().try_into()?, auth_request.duration)?;
@@ -46,7 +46,7 @@ pub async fn handle_login_accept(
     _token: Option<String>,
     auth_accept_request: AuthLoginAcceptRequest,
 ) -> Result<AuthLoginAcceptResponse, anyhow::Error> {
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
     let permissions_token = jwt.grant(auth_accept_request.name, auth_accept_request.auth_token)?;
     Ok(AuthLoginAcceptResponse { permissions_token })
 }
@@ -56,7 +56,7 @@ pub async fn handle_login_deny(
     _token: Option<String>,
     auth_deny_request: AuthLoginDenyRequest,
 ) -> Result<AuthLoginDenyResponse, anyhow::Error> {
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
     jwt.deny(auth_deny_request.auth_token)?;
     Ok(AuthLoginDenyResponse {})
 }
@@ -66,7 +66,7 @@ pub async fn handle_revoke(
     token: Option<String>,
     revoke_request: AuthRevokeTokenRequest,
 ) -> Result<AuthRevokeTokenResponse, anyhow::Error> {
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
     jwt.check_auth(token, &[JrpcPermission::Admin])?;
     jwt.revoke(revoke_request.permission_token_id)?;
     Ok(AuthRevokeTokenResponse {})
@@ -77,7 +77,7 @@ pub async fn handle_get_all_jwt(
     token: Option<String>,
     _request: AuthGetAllJwtRequest,
 ) -> Result<AuthGetAllJwtResponse, anyhow::Error> {
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
     jwt.check_auth(token, &[JrpcPermission::Admin])?;
     let tokens = jwt.get_tokens()?;
     Ok(AuthGetAllJwtResponse { jwt: tokens })
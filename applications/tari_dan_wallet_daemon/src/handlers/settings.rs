@@ -13,7 +13,7 @@ pub async fn handle_get(
     _value: serde_json::Value,
 ) -> Result<SettingsGetResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let indexer_url = sdk
         .config_api()
         .get(ConfigKey::IndexerUrl)
@@ -29,7 +29,7 @@ pub async fn handle_set(
     req: SettingsSetRequest,
 ) -> Result<SettingsSetResponse, anyhow::Error> {
     let mut sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     sdk.get_network_interface_mut().set_endpoint(&req.indexer_url)?;
     sdk.config_api().set(ConfigKey::IndexerUrl, &req.indexer_url, false)?;
     Ok(SettingsSetResponse {})
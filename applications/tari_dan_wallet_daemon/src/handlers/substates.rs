@@ -18,7 +18,7 @@ pub async fn handle_get(
     req: SubstatesGetRequest,
 ) -> Result<SubstatesGetResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::SubstatesRead])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::SubstatesRead])?;
 
     let record = sdk.substate_api().get_substate(&req.substate_id)?;
 
@@ -45,7 +45,7 @@ pub async fn handle_list(
     req: SubstatesListRequest,
 ) -> Result<SubstatesListResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::SubstatesRead])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::SubstatesRead])?;
 
     // TODO: pagination
     let substates =
@@ -12,7 +12,7 @@ pub async fn handle_get(
     req: TemplatesGetRequest,
 ) -> Result<TemplatesGetResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::TemplatesRead])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::TemplatesRead])?;
 
     let template_definition = sdk
         .get_network_interface()
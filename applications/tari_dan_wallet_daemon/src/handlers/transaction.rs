@@ -7,7 +7,13 @@ use futures::{future, future::Either};
 use log::*;
 use tari_dan_app_utilities::json_encoding;
 use tari_dan_common_types::{optional::Optional, Epoch};
-use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_common_types::types::PublicKey;
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_wallet_sdk::{
+    apis::{jwt::JrpcPermission, key_manager},
+    models::ConfidentialProofId,
+    signer::{ExternalTransactionSigner, InMemoryTransactionSigner, SigningRequest, TransactionSigner},
+};
 use tari_engine_types::{
     commit_result::ExecuteResult,
     indexed_value::IndexedValue,
@@ -15,26 +21,39 @@ use tari_engine_types::{
     substate::SubstateId,
 };
 use tari_template_lib::{args, args::Arg, models::Amount};
-use tari_transaction::Transaction;
-use tari_wallet_daemon_client::types::{
-    AccountGetRequest,
-    AccountGetResponse,
-    CallInstructionRequest,
-    TransactionGetAllRequest,
-    TransactionGetAllResponse,
-    TransactionGetRequest,
-    TransactionGetResponse,
-    TransactionGetResultRequest,
-    TransactionGetResultResponse,
-    TransactionSubmitRequest,
-    TransactionSubmitResponse,
-    TransactionWaitResultRequest,
-    TransactionWaitResultResponse,
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, TransactionSignature, UnsignedTransaction};
+use tari_wallet_daemon_client::{
+    messages::WalletMessage,
+    types::{
+        AccountGetRequest,
+        AccountGetResponse,
+        CallInstructionRequest,
+        TransactionCancelScheduledRequest,
+        TransactionCancelScheduledResponse,
+        TransactionGetAllRequest,
+        TransactionGetAllResponse,
+        TransactionGetRequest,
+        TransactionGetResponse,
+        TransactionGetResultRequest,
+        TransactionGetResultResponse,
+        TransactionGetSigningRequestRequest,
+        TransactionGetSigningRequestResponse,
+        TransactionListScheduledRequest,
+        TransactionListScheduledResponse,
+        TransactionScheduleRequest,
+        TransactionScheduleResponse,
+        TransactionSubmitRequest,
+        TransactionSubmitResponse,
+        TransactionSubmitSignatureRequest,
+        TransactionSubmitSignatureResponse,
+        TransactionWaitResultRequest,
+        TransactionWaitResultResponse,
+    },
 };
-use tokio::time;
+use tokio::{sync::mpsc, time};
 
 use super::{accounts, context::HandlerContext};
-use crate::{handlers::HandlerError, services::WalletEvent};
+use crate::{handlers::HandlerError, profiles::WalletSdk, services::WalletEvent};
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::transaction";
 
@@ -73,6 +92,7 @@ pub async fn handle_submit_instruction(
         )
         .with_min_epoch(req.min_epoch.map(Epoch))
         .with_max_epoch(req.max_epoch.map(Epoch))
+        .with_priority_fee(req.priority_fee)
         .build_unsigned_transaction();
 
     let request = TransactionSubmitRequest {
@@ -86,6 +106,7 @@ pub async fn handle_submit_instruction(
         proof_ids: vec![],
         min_epoch: None,
         max_epoch: None,
+        deferred_signing: false,
     };
     handle_submit(context, token, request).await
 }
@@ -97,55 +118,93 @@ pub async fn handle_submit(
 ) -> Result<TransactionSubmitResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
     // TODO: fine-grained checks of individual addresses involved (resources, components, etc)
-    sdk.jwt_api()
+    context
+        .jwt_api()
         .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
     let key_api = sdk.key_manager_api();
     // Fetch the key to sign the transaction
     // TODO: Ideally the SDK should take care of signing the transaction internally
-    let (_, key) = key_api.get_key_or_active(key_manager::TRANSACTION_BRANCH, req.signing_key_index)?;
+    let (key_index, key) = key_api.get_key_or_active(key_manager::TRANSACTION_BRANCH, req.signing_key_index)?;
 
     let inputs = if req.override_inputs {
         req.inputs
     } else {
         // If we are not overriding inputs, we will use inputs that we know about in the local substate id db
-        let mut substates = get_referenced_substate_addresses(
+        let loaded_dependent_substates = locate_referenced_substates(
+            sdk,
             req.transaction
                 .as_ref()
                 .map(|t| &t.instructions)
                 .unwrap_or(&req.instructions),
-        )?;
-        substates.extend(get_referenced_substate_addresses(
             req.transaction
                 .as_ref()
                 .map(|t| &t.fee_instructions)
                 .unwrap_or(&req.fee_instructions),
-        )?);
-        let substates = substates.into_iter().collect::<Vec<_>>();
-        let loaded_dependent_substates = sdk
-            .substate_api()
-            .locate_dependent_substates(&substates)
-            .await?
-            .into_iter()
-            .map(Into::into)
-            .collect();
+        )
+        .await?;
         [req.inputs, loaded_dependent_substates].concat()
     };
 
-    let transaction = if let Some(transaction) = req.transaction {
-        Transaction::builder()
-            .with_unsigned_transaction(transaction)
-            .sign(&key.key)
-            .build()
+    let unsigned_transaction = if let Some(transaction) = req.transaction {
+        transaction
     } else {
         Transaction::builder()
             .with_instructions(req.instructions)
             .with_fee_instructions(req.fee_instructions)
             .with_min_epoch(req.min_epoch)
             .with_max_epoch(req.max_epoch)
-            .sign(&key.key)
-            .build()
+            .build_unsigned_transaction()
     };
 
+    let key_id = format!("{}/{}", key_manager::TRANSACTION_BRANCH, key_index);
+    if req.deferred_signing {
+        if req.is_dry_run {
+            return Err(WalletMessage::DeferredSigningNotSupportedForDryRun.into());
+        }
+
+        let (tx_requests, rx_requests) = mpsc::channel(1);
+        let signer = ExternalTransactionSigner::new(
+            key_id,
+            PublicKey::from_secret_key(&key.key),
+            tx_requests,
+            context.config().external_signing_timeout,
+        );
+        let transaction_id = sdk.transaction_api().insert_pending_signature(
+            unsigned_transaction.clone(),
+            signer.public_key().clone(),
+            inputs.clone(),
+            None,
+            false,
+        )?;
+        info!(
+            target: LOG_TARGET,
+            "Transaction {} is waiting for an external signature",
+            transaction_id
+        );
+        spawn_deferred_signing(
+            context.clone(),
+            transaction_id,
+            unsigned_transaction,
+            signer,
+            rx_requests,
+            req.proof_ids,
+        );
+
+        return Ok(TransactionSubmitResponse {
+            transaction_id,
+            inputs,
+            result: None,
+            json_result: None,
+        });
+    }
+
+    let signer = InMemoryTransactionSigner::new(key_id, key.key);
+    let signature = signer.sign_transaction(&unsigned_transaction).await?;
+    let transaction = Transaction::builder()
+        .with_unsigned_transaction(unsigned_transaction)
+        .with_signature(signature)
+        .build();
+
     for proof_id in req.proof_ids {
         // update the proofs table with the corresponding transaction hash
         sdk.confidential_outputs_api()
@@ -186,15 +245,185 @@ pub async fn handle_submit(
     }
 }
 
+fn spawn_deferred_signing(
+    context: HandlerContext,
+    pending_transaction_id: TransactionId,
+    transaction: UnsignedTransaction,
+    signer: ExternalTransactionSigner,
+    mut rx_requests: mpsc::Receiver<SigningRequest>,
+    proof_ids: Vec<ConfidentialProofId>,
+) {
+    tokio::spawn(async move {
+        let pending_signing_requests = context.pending_signing_requests().clone();
+        let publish_request = async {
+            if let Some(request) = rx_requests.recv().await {
+                pending_signing_requests
+                    .lock()
+                    .unwrap()
+                    .insert(pending_transaction_id, request);
+            }
+        };
+        let (_, result) = tokio::join!(publish_request, signer.sign_transaction(&transaction));
+        pending_signing_requests.lock().unwrap().remove(&pending_transaction_id);
+
+        let sdk = context.wallet_sdk();
+        let signature = match result {
+            Ok(signature) => signature,
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to get signature for transaction {}: {}", pending_transaction_id, err
+                );
+                if let Err(err) = sdk.transaction_api().abandon_pending_signature(pending_transaction_id) {
+                    error!(target: LOG_TARGET, "Failed to abandon transaction {}: {}", pending_transaction_id, err);
+                }
+                return;
+            },
+        };
+
+        match sdk.transaction_api().apply_signature(pending_transaction_id, signature) {
+            Ok(transaction) => {
+                for proof_id in proof_ids {
+                    if let Err(err) = sdk
+                        .confidential_outputs_api()
+                        .proofs_set_transaction_hash(proof_id, *transaction.id())
+                    {
+                        error!(target: LOG_TARGET, "Failed to set transaction for proof {}: {}", proof_id, err);
+                    }
+                }
+                // The transaction service submits transactions in the New status
+                info!(
+                    target: LOG_TARGET,
+                    "Transaction {} signed externally as {}", pending_transaction_id, transaction.id()
+                );
+            },
+            Err(err) => {
+                error!(
+                    target: LOG_TARGET,
+                    "Failed to apply signature to transaction {}: {}", pending_transaction_id, err
+                );
+            },
+        }
+    });
+}
+
+pub async fn handle_get_signing_request(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionGetSigningRequestRequest,
+) -> Result<TransactionGetSigningRequestResponse, anyhow::Error> {
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
+    let payload = context
+        .pending_signing_requests()
+        .lock()
+        .unwrap()
+        .get(&req.transaction_id)
+        .map(|request| request.payload().clone())
+        .ok_or(HandlerError::NotFound)?;
+
+    Ok(TransactionGetSigningRequestResponse {
+        key_id: payload.key_id,
+        public_key: payload.public_key,
+        challenge: payload.challenge,
+        transaction: payload.transaction,
+    })
+}
+
+pub async fn handle_submit_signature(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionSubmitSignatureRequest,
+) -> Result<TransactionSubmitSignatureResponse, anyhow::Error> {
+    context
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let mut pending_signing_requests = context.pending_signing_requests().lock().unwrap();
+    let payload = pending_signing_requests
+        .get(&req.transaction_id)
+        .map(|request| request.payload())
+        .ok_or(HandlerError::NotFound)?;
+
+    let signature = TransactionSignature::new(payload.public_key.clone(), req.signature.clone());
+    if !signature.verify(&payload.transaction) {
+        return Err(WalletMessage::InvalidTransactionSignature {
+            transaction_id: req.transaction_id.to_string(),
+        }
+        .into());
+    }
+    let transaction = Transaction::builder()
+        .with_unsigned_transaction(payload.transaction.clone())
+        .with_signature(signature)
+        .build();
+
+    let request = pending_signing_requests
+        .remove(&req.transaction_id)
+        .expect("request checked above");
+    request.respond(req.signature);
+
+    Ok(TransactionSubmitSignatureResponse {
+        transaction_id: *transaction.id(),
+    })
+}
+
+pub async fn handle_schedule(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionScheduleRequest,
+) -> Result<TransactionScheduleResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    context
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    // The transaction is signed with this key when it is released
+    let (key_index, _) = sdk
+        .key_manager_api()
+        .get_key_or_active(key_manager::TRANSACTION_BRANCH, req.signing_key_index)?;
+
+    let inputs = if req.override_inputs {
+        req.inputs
+    } else {
+        let loaded_dependent_substates =
+            locate_referenced_substates(sdk, &req.transaction.instructions, &req.transaction.fee_instructions).await?;
+        [req.inputs, loaded_dependent_substates].concat()
+    };
+
+    let id = sdk
+        .scheduled_transaction_api()
+        .schedule(&req.transaction, &inputs, key_index, req.trigger)?;
+
+    Ok(TransactionScheduleResponse { id, inputs })
+}
+
+pub async fn handle_list_scheduled(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionListScheduledRequest,
+) -> Result<TransactionListScheduledResponse, anyhow::Error> {
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
+    let scheduled_transactions = context.wallet_sdk().scheduled_transaction_api().list(req.status)?;
+    Ok(TransactionListScheduledResponse { scheduled_transactions })
+}
+
+pub async fn handle_cancel_scheduled(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionCancelScheduledRequest,
+) -> Result<TransactionCancelScheduledResponse, anyhow::Error> {
+    context
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let scheduled_api = context.wallet_sdk().scheduled_transaction_api();
+    scheduled_api.get(req.id).optional()?.ok_or(HandlerError::NotFound)?;
+    scheduled_api.cancel(req.id)?;
+    Ok(TransactionCancelScheduledResponse {})
+}
+
 pub async fn handle_get(
     context: &HandlerContext,
     token: Option<String>,
     req: TransactionGetRequest,
 ) -> Result<TransactionGetResponse, anyhow::Error> {
-    context
-        .wallet_sdk()
-        .jwt_api()
-        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
     let transaction = context
         .wallet_sdk()
         .transaction_api()
@@ -215,10 +444,7 @@ pub async fn handle_get_all(
     token: Option<String>,
     req: TransactionGetAllRequest,
 ) -> Result<TransactionGetAllResponse, anyhow::Error> {
-    context
-        .wallet_sdk()
-        .jwt_api()
-        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
     let transactions = context
         .wallet_sdk()
         .transaction_api()
@@ -236,10 +462,7 @@ pub async fn handle_get_result(
     token: Option<String>,
     req: TransactionGetResultRequest,
 ) -> Result<TransactionGetResultResponse, anyhow::Error> {
-    context
-        .wallet_sdk()
-        .jwt_api()
-        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
     let transaction = context
         .wallet_sdk()
         .transaction_api()
@@ -266,10 +489,7 @@ pub async fn handle_wait_result(
     token: Option<String>,
     req: TransactionWaitResultRequest,
 ) -> Result<TransactionWaitResultResponse, anyhow::Error> {
-    context
-        .wallet_sdk()
-        .jwt_api()
-        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
     let mut events = context.notifier().subscribe();
     let transaction = context
         .wallet_sdk()
@@ -345,6 +565,26 @@ pub async fn handle_wait_result(
     }
 }
 
+/// Returns the substates referenced by the instructions, along with their dependent substates, at the versions known to
+/// the wallet
+async fn locate_referenced_substates(
+    sdk: &WalletSdk,
+    instructions: &[Instruction],
+    fee_instructions: &[Instruction],
+) -> anyhow::Result<Vec<SubstateRequirement>> {
+    let mut substates = get_referenced_substate_addresses(instructions)?;
+    substates.extend(get_referenced_substate_addresses(fee_instructions)?);
+    let substates = substates.into_iter().collect::<Vec<_>>();
+    let loaded_dependent_substates = sdk
+        .substate_api()
+        .locate_dependent_substates(&substates)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    Ok(loaded_dependent_substates)
+}
+
 fn get_referenced_substate_addresses(instructions: &[Instruction]) -> anyhow::Result<HashSet<SubstateId>> {
     let mut substates = HashSet::new();
     for instruction in instructions {
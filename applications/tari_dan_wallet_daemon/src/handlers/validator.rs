@@ -7,11 +7,14 @@ use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
 use tari_engine_types::instruction::Instruction;
 use tari_template_lib::args;
 use tari_transaction::Transaction;
-use tari_wallet_daemon_client::types::{
-    ClaimValidatorFeesRequest,
-    ClaimValidatorFeesResponse,
-    GetValidatorFeesRequest,
-    GetValidatorFeesResponse,
+use tari_wallet_daemon_client::{
+    messages::WalletMessage,
+    types::{
+        ClaimValidatorFeesRequest,
+        ClaimValidatorFeesResponse,
+        GetValidatorFeesRequest,
+        GetValidatorFeesResponse,
+    },
 };
 
 use crate::{
@@ -39,7 +42,7 @@ pub async fn handle_claim_validator_fees(
     req: ClaimValidatorFeesRequest,
 ) -> Result<ClaimValidatorFeesResponse, anyhow::Error> {
     let sdk = context.wallet_sdk().clone();
-    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    context.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let mut fee_instructions = vec![];
 
@@ -109,13 +112,16 @@ pub async fn handle_claim_validator_fees(
     let finalized = wait_for_result(&mut events, tx_id).await?;
 
     if let Some(reject) = finalized.finalize.result.reject() {
-        return Err(anyhow::anyhow!("Fee transaction rejected: {}", reject));
+        return Err(WalletMessage::FeeTransactionRejected {
+            reason: reject.to_string(),
+        }
+        .into());
     }
     if let Some(reason) = finalized.finalize.reject() {
-        return Err(anyhow::anyhow!(
-            "Fee transaction succeeded (fees charged) however the transaction failed: {}",
-            reason
-        ));
+        return Err(WalletMessage::TransactionFailed {
+            reason: reason.to_string(),
+        }
+        .into());
     }
     info!(
         target: LOG_TARGET,
@@ -28,7 +28,6 @@ pub fn handle_start(
 ) -> JrpcResult {
     let answer_id = value.get_answer_id();
     context
-        .wallet_sdk()
         .jwt_api()
         .check_auth(token, &[JrpcPermission::StartWebrtc])
         .map_err(|e| {
@@ -53,7 +52,7 @@ pub fn handle_start(
             ),
         )
     })?;
-    let jwt = context.wallet_sdk().jwt_api();
+    let jwt = context.jwt_api();
     let auth_token = jwt.generate_auth_token(permissions, None).map_err(|e| {
         JsonRpcResponse::error(
             answer_id,
@@ -5,7 +5,7 @@ use std::sync::{Arc, Mutex};
 
 use axum::async_trait;
 use reqwest::{IntoUrl, Url};
-use tari_dan_common_types::optional::IsNotFoundError;
+use tari_dan_common_types::{optional::IsNotFoundError, Epoch};
 use tari_dan_wallet_sdk::network::{
     SubstateQueryResult,
     TransactionFinalizedResult,
@@ -146,6 +146,12 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
 
         Ok(resp.definition)
     }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        let mut client = self.get_client()?;
+        let resp = client.get_epoch_manager_stats().await?;
+        Ok(resp.current_epoch)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +23,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 use tari_dan_wallet_sdk::apis::jwt::JwtApiError;
 use tari_shutdown::ShutdownSignal;
+use tari_wallet_daemon_client::messages::WalletMessage;
 use tokio::task;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
@@ -32,7 +33,9 @@ use crate::handlers::{
     confidential,
     error::HandlerError,
     keys,
+    meta,
     nfts,
+    profiles,
     rpc,
     settings,
     transaction,
@@ -94,10 +97,20 @@ async fn handler(
     Extension(addresses): Extension<(SocketAddr, SocketAddr)>,
     Extension(shutdown_signal): Extension<Arc<ShutdownSignal>>,
     Extension(token): Extension<Option<String>>,
-    value: JsonRpcExtractor,
+    mut value: JsonRpcExtractor,
 ) -> JrpcResult {
     info!(target: LOG_TARGET, "🌐 JSON-RPC request: {}", value.method);
     debug!(target: LOG_TARGET, "🌐 JSON-RPC request: {:?}", value);
+    // Account and transaction methods may specify a profile, all other methods use the active profile
+    let profile = match value.method.split_once('.') {
+        Some(("accounts" | "transactions", _)) => take_profile_param(&mut value)?,
+        _ => None,
+    };
+    let context = context
+        .for_profile(profile.as_deref())
+        .map(Arc::new)
+        .map_err(|e| resolve_wallet_message(value.get_answer_id(), &e))?;
+
     match value.method.as_str().split_once('.') {
         Some(("auth", method)) => match method {
             "request" => call_handler(context, value, token, rpc::handle_login_request).await,
@@ -113,6 +126,14 @@ async fn handler(
             _ => Ok(value.method_not_found(&value.method)),
         },
         Some(("webrtc", "start")) => webrtc::handle_start(context, value, token, shutdown_signal, addresses),
+        Some(("meta", "get_message_catalogue")) => {
+            call_handler(context, value, token, meta::handle_get_message_catalogue).await
+        },
+        Some(("profiles", method)) => match method {
+            "list" => call_handler(context, value, token, profiles::handle_list).await,
+            "switch" => call_handler(context, value, token, profiles::handle_switch).await,
+            _ => Ok(value.method_not_found(&value.method)),
+        },
         Some(("rpc", "discover")) => call_handler(context, value, token, rpc::handle_discover).await,
         Some(("keys", method)) => match method {
             "create" => call_handler(context, value, token, keys::handle_create).await,
@@ -123,6 +144,13 @@ async fn handler(
         Some(("transactions", method)) => match method {
             "submit_instruction" => call_handler(context, value, token, transaction::handle_submit_instruction).await,
             "submit" => call_handler(context, value, token, transaction::handle_submit).await,
+            "get_signing_request" => {
+                call_handler(context, value, token, transaction::handle_get_signing_request).await
+            },
+            "submit_signature" => call_handler(context, value, token, transaction::handle_submit_signature).await,
+            "schedule" => call_handler(context, value, token, transaction::handle_schedule).await,
+            "list_scheduled" => call_handler(context, value, token, transaction::handle_list_scheduled).await,
+            "cancel_scheduled" => call_handler(context, value, token, transaction::handle_cancel_scheduled).await,
             "get" => call_handler(context, value, token, transaction::handle_get).await,
             "get_result" => call_handler(context, value, token, transaction::handle_get_result).await,
             "wait_result" => call_handler(context, value, token, transaction::handle_wait_result).await,
@@ -135,14 +163,19 @@ async fn handler(
             "create" => call_handler(context, value, token, accounts::handle_create).await,
             "list" => call_handler(context, value, token, accounts::handle_list).await,
             "get_balances" => call_handler(context, value, token, accounts::handle_get_balances).await,
+            "get_vaults" => call_handler(context, value, token, accounts::handle_get_vaults).await,
             "invoke" => call_handler(context, value, token, accounts::handle_invoke).await,
             "get" => call_handler(context, value, token, accounts::handle_get).await,
             "get_default" => call_handler(context, value, token, accounts::handle_get_default).await,
             "transfer" => call_handler(context, value, token, accounts::handle_transfer).await,
+            "bulk_transfer" => call_handler(context, value, token, accounts::handle_bulk_transfer).await,
             "confidential_transfer" => {
                 call_handler(context, value, token, accounts::handle_confidential_transfer).await
             },
             "set_default" => call_handler(context, value, token, accounts::handle_set_default).await,
+            "get_queue" => call_handler(context, value, token, accounts::handle_get_queue).await,
+            "set_queue_enabled" => call_handler(context, value, token, accounts::handle_set_queue_enabled).await,
+            "skip_queued" => call_handler(context, value, token, accounts::handle_skip_queued).await,
             "create_free_test_coins" => {
                 call_handler(context, value, token, accounts::handle_create_free_test_coins).await
             },
@@ -181,6 +214,25 @@ async fn handler(
     }
 }
 
+/// Removes the `profile` parameter from the request params, so that the remaining params can be parsed as the request
+fn take_profile_param(value: &mut JsonRpcExtractor) -> Result<Option<String>, JsonRpcResponse> {
+    let Some(profile) = value.parsed.as_object_mut().and_then(|params| params.remove("profile")) else {
+        return Ok(None);
+    };
+    match profile {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(profile) => Ok(Some(profile)),
+        _ => Err(JsonRpcResponse::error(
+            value.get_answer_id(),
+            JsonRpcError::new(
+                JsonRpcErrorReason::InvalidParams,
+                "The profile parameter must be a string".to_string(),
+                serde_json::Value::Null,
+            ),
+        )),
+    }
+}
+
 async fn call_handler<H, TReq, TResp>(
     context: Arc<HandlerContext>,
     value: JsonRpcExtractor,
@@ -217,10 +269,7 @@ where
 fn resolve_handler_error(answer_id: i64, e: &HandlerError) -> JsonRpcResponse {
     match e {
         HandlerError::Anyhow(e) => resolve_any_error(answer_id, e),
-        HandlerError::NotFound => JsonRpcResponse::error(
-            answer_id,
-            JsonRpcError::new(JsonRpcErrorReason::ApplicationError(404), e.to_string(), json!({})),
-        ),
+        HandlerError::NotFound => resolve_wallet_message(answer_id, &WalletMessage::NotFound),
     }
 }
 
@@ -230,19 +279,99 @@ fn resolve_any_error(answer_id: i64, e: &anyhow::Error) -> JsonRpcResponse {
         return resolve_handler_error(answer_id, handler_err);
     }
 
+    if let Some(message) = e.downcast_ref::<WalletMessage>() {
+        return resolve_wallet_message(answer_id, message);
+    }
+
     if let Some(error) = e.downcast_ref::<JwtApiError>() {
-        JsonRpcResponse::error(
-            answer_id,
-            JsonRpcError::new(
-                JsonRpcErrorReason::ApplicationError(401),
-                error.to_string(),
-                serde_json::Value::Null,
-            ),
-        )
+        resolve_wallet_message(answer_id, &WalletMessage::Unauthorized {
+            reason: error.to_string(),
+        })
     } else {
-        JsonRpcResponse::error(
-            answer_id,
-            JsonRpcError::new(JsonRpcErrorReason::ApplicationError(500), e.to_string(), json!({})),
-        )
+        resolve_wallet_message(answer_id, &WalletMessage::InternalError {
+            details: format!("{:#}", e),
+        })
+    }
+}
+
+/// Returns an error response with the default message, and with the code and params of the message as the error data
+fn resolve_wallet_message(answer_id: i64, message: &WalletMessage) -> JsonRpcResponse {
+    let status = match message {
+        WalletMessage::NotFound => 404,
+        WalletMessage::Unauthorized { .. } => 401,
+        WalletMessage::InternalError { .. } => 500,
+        _ => 400,
+    };
+    JsonRpcResponse::error(
+        answer_id,
+        JsonRpcError::new(
+            JsonRpcErrorReason::ApplicationError(status),
+            message.default_message(),
+            message.to_data(),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Returns the message and data of the error response
+    fn get_error(response: JsonRpcResponse) -> (String, Value) {
+        let mut response = serde_json::to_value(response).unwrap();
+        let error = response["error"].take();
+        (error["message"].as_str().unwrap().to_string(), error["data"].clone())
+    }
+
+    #[test]
+    fn it_includes_the_message_code_and_params_in_the_error_data() {
+        let err = anyhow::Error::from(WalletMessage::InvalidAccountAddress {
+            address: "component_abc".to_string(),
+        });
+        let (message, data) = get_error(resolve_any_error(1, &err));
+        assert_eq!(message, "Invalid account address component_abc");
+        assert_eq!(
+            data,
+            json!({ "code": "invalid_account_address", "params": { "address": "component_abc" } })
+        );
+
+        let err = anyhow::Error::from(WalletMessage::NoDefaultAccount).context("Failed to get account");
+        let (_, data) = get_error(resolve_any_error(1, &err));
+        assert_eq!(data["code"], "no_default_account");
+    }
+
+    #[test]
+    fn it_resolves_not_found_unauthorized_and_other_errors_to_message_codes() {
+        let (_, data) = get_error(resolve_handler_error(1, &HandlerError::NotFound));
+        assert_eq!(data, json!({ "code": "not_found", "params": {} }));
+
+        let err = anyhow::Error::from(JwtApiError::TokenMissing);
+        let (_, data) = get_error(resolve_any_error(1, &err));
+        assert_eq!(data["code"], "unauthorized");
+
+        let err = anyhow::anyhow!("Something went wrong");
+        let (message, data) = get_error(resolve_any_error(1, &err));
+        assert_eq!(message, "Error: Something went wrong");
+        assert_eq!(
+            data,
+            json!({ "code": "internal_error", "params": { "details": "Something went wrong" } })
+        );
+    }
+
+    #[test]
+    fn it_removes_the_profile_from_the_request_params() {
+        let mut value = JsonRpcExtractor {
+            parsed: json!({ "profile": "esmeralda", "limit": 10 }),
+            method: "accounts.list".to_string(),
+            id: 1,
+        };
+        assert_eq!(take_profile_param(&mut value).unwrap().as_deref(), Some("esmeralda"));
+        assert_eq!(value.parsed, json!({ "limit": 10 }));
+        assert_eq!(take_profile_param(&mut value).unwrap(), None);
+
+        value.parsed = json!({ "profile": 1 });
+        assert!(take_profile_param(&mut value).is_err());
     }
 }
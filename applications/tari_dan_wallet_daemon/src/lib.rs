@@ -27,21 +27,15 @@ mod http_ui;
 pub mod indexer_jrpc_impl;
 mod jrpc_server;
 mod notify;
+mod profiles;
 mod services;
 mod webrtc;
 
 use std::{fs, panic, process};
 
+use futures::future;
 use log::*;
-use tari_dan_common_types::optional::Optional;
-use tari_dan_wallet_sdk::{
-    apis::{
-        config::{ConfigApi, ConfigKey},
-        key_manager,
-    },
-    DanWalletSdk,
-    WalletSdkConfig,
-};
+use tari_dan_wallet_sdk::DanWalletSdk;
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_shutdown::ShutdownSignal;
 use tari_template_lib::models::Amount;
@@ -52,8 +46,7 @@ use crate::{
     handlers::HandlerContext,
     http_ui::server::run_http_ui_server,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
-    notify::Notify,
-    services::spawn_services,
+    profiles::{ProfileSettings, WalletProfile, WalletProfiles},
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon";
@@ -67,23 +60,32 @@ pub async fn run_tari_dan_wallet_daemon(
     // Uncomment to enable tokio tracing via tokio-console
     // console_subscriber::init();
 
-    let wallet_sdk = initialize_wallet_sdk(&config)?;
-    wallet_sdk
-        .key_manager_api()
-        .get_or_create_initial(key_manager::TRANSACTION_BRANCH)?;
-    let notify = Notify::new(100);
+    // JWTs are kept in the default wallet database regardless of the profile, so that a token is valid for every
+    // profile and tokens revoked before profiles were introduced remain revoked
+    let auth_store_path = config.common.base_path.join("data/wallet.sqlite");
+    let auth_store = SqliteWalletStore::try_open(&auth_store_path)?;
+    auth_store.run_migrations()?;
 
-    let services = spawn_services(shutdown_signal.clone(), notify.clone(), wallet_sdk.clone());
+    let (profile_settings, active_profile) = ProfileSettings::resolve_all(&config)?;
+    let mut profiles = Vec::with_capacity(profile_settings.len());
+    let mut services_futs = Vec::with_capacity(profile_settings.len());
+    for settings in profile_settings {
+        info!(target: LOG_TARGET, "👛 Starting wallet profile '{}'", settings.name);
+        let store = if settings.database_path == auth_store_path {
+            auth_store.clone()
+        } else {
+            settings.open_store()?
+        };
+        let (profile, services_fut) =
+            WalletProfile::start(settings, store, &config.dan_wallet_daemon, shutdown_signal.clone())?;
+        profiles.push(profile);
+        services_futs.push(services_fut);
+    }
+    let profiles = WalletProfiles::new(profiles, active_profile)?;
 
     let jrpc_address = config.dan_wallet_daemon.json_rpc_address.unwrap();
     let signaling_server_address = config.dan_wallet_daemon.signaling_server_address.unwrap();
-    let handlers = HandlerContext::new(
-        wallet_sdk.clone(),
-        notify,
-        services.transaction_service_handle.clone(),
-        services.account_monitor_handle.clone(),
-        config.dan_wallet_daemon.clone(),
-    );
+    let handlers = HandlerContext::new(profiles, auth_store, config.dan_wallet_daemon.clone());
     let (jrpc_address, listen_fut) =
         jrpc_server::spawn_listener(jrpc_address, signaling_server_address, handlers, shutdown_signal)?;
 
@@ -114,32 +116,22 @@ pub async fn run_tari_dan_wallet_daemon(
         res = listen_fut => {
             res??;
         },
-        res = services.services_fut => {
+        (res, _, _) = future::select_all(services_futs) => {
             res?;
         },
     }
     Ok(())
 }
 
+/// Initializes the wallet SDK of the active profile
 pub fn initialize_wallet_sdk(
     config: &ApplicationConfig,
 ) -> anyhow::Result<DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>> {
-    let store = SqliteWalletStore::try_open(config.common.base_path.join("data/wallet.sqlite"))?;
-    store.run_migrations()?;
-
-    let sdk_config = WalletSdkConfig {
-        // TODO: Configure
-        password: None,
-        jwt_expiry: config.dan_wallet_daemon.jwt_expiry.unwrap(),
-        jwt_secret_key: config.dan_wallet_daemon.jwt_secret_key.clone().unwrap(),
-    };
-    let config_api = ConfigApi::new(&store);
-    let indexer_jrpc_endpoint = if let Some(indexer_url) = config_api.get(ConfigKey::IndexerUrl).optional()? {
-        indexer_url
-    } else {
-        config.dan_wallet_daemon.indexer_node_json_rpc_url.clone()
-    };
-    let indexer = IndexerJsonRpcNetworkInterface::new(indexer_jrpc_endpoint);
-    let wallet_sdk = DanWalletSdk::initialize(store, indexer, sdk_config)?;
-    Ok(wallet_sdk)
+    let (profile_settings, active_profile) = ProfileSettings::resolve_all(config)?;
+    let settings = profile_settings
+        .into_iter()
+        .find(|settings| settings.name == active_profile)
+        .expect("active profile is always resolved");
+    let store = settings.open_store()?;
+    settings.initialize_sdk(store, &config.dan_wallet_daemon)
 }
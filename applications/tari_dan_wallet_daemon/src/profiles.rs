@@ -0,0 +1,392 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use anyhow::bail;
+use futures::future::BoxFuture;
+use log::*;
+use tari_dan_common_types::optional::Optional;
+use tari_dan_wallet_sdk::{
+    apis::{
+        config::{ConfigApi, ConfigKey},
+        key_manager,
+    },
+    DanWalletSdk,
+    WalletSdkConfig,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_shutdown::ShutdownSignal;
+use tari_wallet_daemon_client::messages::WalletMessage;
+
+use crate::{
+    config::{ApplicationConfig, WalletDaemonConfig},
+    handlers::PendingSigningRequests,
+    indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
+    notify::Notify,
+    services::{spawn_services, AccountMonitorHandle, TransactionServiceHandle, WalletEvent},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::profiles";
+
+pub type WalletSdk = DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>;
+
+/// The name of the profile that is used when no profiles are configured
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// The settings of a wallet profile, resolved from the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSettings {
+    pub name: String,
+    pub database_path: PathBuf,
+    pub indexer_url: String,
+    pub key_domain: Option<String>,
+}
+
+impl ProfileSettings {
+    /// Returns the settings of every configured profile and the name of the active profile
+    pub fn resolve_all(config: &ApplicationConfig) -> anyhow::Result<(Vec<Self>, String)> {
+        let daemon_config = &config.dan_wallet_daemon;
+        let data_path = config.common.base_path.join("data");
+
+        let profiles = if daemon_config.profiles.is_empty() {
+            // Use the same database and keys as wallets created before profiles were introduced
+            vec![Self {
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                database_path: data_path.join("wallet.sqlite"),
+                indexer_url: daemon_config.indexer_node_json_rpc_url.clone(),
+                key_domain: None,
+            }]
+        } else {
+            daemon_config
+                .profiles
+                .iter()
+                .map(|(name, profile)| {
+                    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                        bail!(
+                            "Invalid profile name '{}'. Profile names may only contain alphanumeric characters, '-' \
+                             and '_'",
+                            name
+                        );
+                    }
+                    Ok(Self {
+                        name: name.clone(),
+                        database_path: data_path.join("profiles").join(name).join("wallet.sqlite"),
+                        indexer_url: profile.indexer_node_json_rpc_url.clone(),
+                        key_domain: Some(profile.key_domain.clone().unwrap_or_else(|| name.clone())),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+
+        let active_profile = match daemon_config.active_profile {
+            Some(ref name) => {
+                if profiles.iter().all(|p| p.name != *name) {
+                    bail!("Active profile '{}' is not configured", name);
+                }
+                name.clone()
+            },
+            None => profiles[0].name.clone(),
+        };
+
+        Ok((profiles, active_profile))
+    }
+
+    /// Opens the database of the profile, creating it if it does not exist
+    pub fn open_store(&self) -> anyhow::Result<SqliteWalletStore> {
+        let store = SqliteWalletStore::try_open(&self.database_path)?;
+        store.run_migrations()?;
+        Ok(store)
+    }
+
+    /// Initializes a wallet SDK for the profile that only has access to the given profile database
+    pub fn initialize_sdk(&self, store: SqliteWalletStore, config: &WalletDaemonConfig) -> anyhow::Result<WalletSdk> {
+        let sdk_config = WalletSdkConfig {
+            // TODO: Configure
+            password: None,
+            jwt_expiry: config.jwt_expiry.unwrap(),
+            jwt_secret_key: config.jwt_secret_key.clone().unwrap(),
+            key_domain: self.key_domain.clone(),
+        };
+        let config_api = ConfigApi::new(&store);
+        let indexer_jrpc_endpoint = if let Some(indexer_url) = config_api.get(ConfigKey::IndexerUrl).optional()? {
+            indexer_url
+        } else {
+            self.indexer_url.clone()
+        };
+        let indexer = IndexerJsonRpcNetworkInterface::new(indexer_jrpc_endpoint);
+        let wallet_sdk = DanWalletSdk::initialize(store, indexer, sdk_config)?;
+        Ok(wallet_sdk)
+    }
+}
+
+/// A wallet for a single network. A profile has its own database, wallet services and event notifier, so anything that
+/// is given a profile cannot access the keys, accounts or transactions of another profile.
+#[derive(Debug, Clone)]
+pub struct WalletProfile {
+    settings: ProfileSettings,
+    wallet_sdk: WalletSdk,
+    notifier: Notify<WalletEvent>,
+    transaction_service: TransactionServiceHandle,
+    account_monitor: AccountMonitorHandle,
+    pending_signing_requests: PendingSigningRequests,
+}
+
+impl WalletProfile {
+    /// Initializes the profile and spawns its wallet services. Returns the profile and a future that resolves when any
+    /// of the services exit.
+    pub fn start(
+        settings: ProfileSettings,
+        store: SqliteWalletStore,
+        config: &WalletDaemonConfig,
+        shutdown_signal: ShutdownSignal,
+    ) -> anyhow::Result<(Self, BoxFuture<'static, anyhow::Result<()>>)> {
+        let wallet_sdk = settings.initialize_sdk(store, config)?;
+        wallet_sdk
+            .key_manager_api()
+            .get_or_create_initial(key_manager::TRANSACTION_BRANCH)?;
+        // Signing requests are only held in memory, so transactions that were waiting for an external signature when
+        // the daemon stopped can never be signed
+        let abandoned = wallet_sdk.transaction_api().abandon_all_pending_signatures()?;
+        if !abandoned.is_empty() {
+            warn!(
+                target: LOG_TARGET,
+                "Profile {}: marked {} transaction(s) that were pending an external signature as invalid",
+                settings.name,
+                abandoned.len()
+            );
+        }
+        let notifier = Notify::new(100);
+        let services = spawn_services(shutdown_signal, notifier.clone(), wallet_sdk.clone());
+
+        let profile = Self {
+            settings,
+            wallet_sdk,
+            notifier,
+            transaction_service: services.transaction_service_handle,
+            account_monitor: services.account_monitor_handle,
+            pending_signing_requests: PendingSigningRequests::default(),
+        };
+        Ok((profile, services.services_fut))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.settings.name
+    }
+
+    pub fn settings(&self) -> &ProfileSettings {
+        &self.settings
+    }
+
+    pub fn wallet_sdk(&self) -> &WalletSdk {
+        &self.wallet_sdk
+    }
+
+    pub fn notifier(&self) -> &Notify<WalletEvent> {
+        &self.notifier
+    }
+
+    pub fn transaction_service(&self) -> &TransactionServiceHandle {
+        &self.transaction_service
+    }
+
+    pub fn account_monitor(&self) -> &AccountMonitorHandle {
+        &self.account_monitor
+    }
+
+    pub fn pending_signing_requests(&self) -> &PendingSigningRequests {
+        &self.pending_signing_requests
+    }
+}
+
+/// All profiles of the wallet daemon and the name of the active profile, which is used when a request does not specify
+/// a profile. Clones share the same active profile.
+#[derive(Debug, Clone)]
+pub struct WalletProfiles {
+    profiles: Arc<BTreeMap<String, WalletProfile>>,
+    active_profile: Arc<RwLock<String>>,
+}
+
+impl WalletProfiles {
+    pub fn new<I: IntoIterator<Item = WalletProfile>>(profiles: I, active_profile: String) -> anyhow::Result<Self> {
+        let profiles = profiles
+            .into_iter()
+            .map(|profile| (profile.name().to_string(), profile))
+            .collect::<BTreeMap<_, _>>();
+        if !profiles.contains_key(&active_profile) {
+            bail!("Active profile '{}' does not exist", active_profile);
+        }
+
+        Ok(Self {
+            profiles: Arc::new(profiles),
+            active_profile: Arc::new(RwLock::new(active_profile)),
+        })
+    }
+
+    /// Returns the given profile, or the active profile if no profile is given
+    pub fn get(&self, name: Option<&str>) -> Result<&WalletProfile, WalletMessage> {
+        match name {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .ok_or_else(|| WalletMessage::ProfileNotFound { name: name.to_string() }),
+            None => {
+                let active_profile = self.active_profile.read().unwrap();
+                Ok(self
+                    .profiles
+                    .get(active_profile.as_str())
+                    .expect("active profile always exists"))
+            },
+        }
+    }
+
+    pub fn active_profile_name(&self) -> String {
+        self.active_profile.read().unwrap().clone()
+    }
+
+    /// Sets the profile used by requests that do not specify a profile
+    pub fn switch(&self, name: &str) -> Result<(), WalletMessage> {
+        let profile = self.get(Some(name))?;
+        *self.active_profile.write().unwrap() = profile.name().to_string();
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WalletProfile> + '_ {
+        self.profiles.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_dan_wallet_sdk::apis::key_manager::TRANSACTION_BRANCH;
+    use tari_engine_types::substate::SubstateId;
+    use tari_shutdown::Shutdown;
+    use tari_template_lib::models::ComponentAddress;
+    use tari_transaction::Transaction;
+
+    use super::*;
+    use crate::config::WalletProfileConfig;
+
+    fn create_config(base_path: PathBuf) -> ApplicationConfig {
+        let mut config = ApplicationConfig {
+            common: Default::default(),
+            dan_wallet_daemon: Default::default(),
+        };
+        config.common.base_path = base_path;
+        for (name, port) in [("localnet", 18300), ("esmeralda", 18301)] {
+            config.dan_wallet_daemon.profiles.insert(name.to_string(), WalletProfileConfig {
+                indexer_node_json_rpc_url: format!("http://127.0.0.1:{}/json_rpc", port),
+                key_domain: None,
+            });
+        }
+        config.dan_wallet_daemon.active_profile = Some("localnet".to_string());
+        config
+    }
+
+    #[tokio::test]
+    async fn it_isolates_keys_accounts_and_transactions_between_profiles() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = create_config(temp.path().to_path_buf());
+        let (settings, active_profile) = ProfileSettings::resolve_all(&config).unwrap();
+        assert_eq!(active_profile, "localnet");
+        let [esmeralda, localnet] = [&settings[0], &settings[1]].map(|settings| {
+            let store = settings.open_store().unwrap();
+            settings.initialize_sdk(store, &config.dan_wallet_daemon).unwrap()
+        });
+
+        // Keys
+        let esmeralda_key = esmeralda.key_manager_api().derive_key(TRANSACTION_BRANCH, 0).unwrap();
+        let localnet_key = localnet.key_manager_api().derive_key(TRANSACTION_BRANCH, 0).unwrap();
+        assert_ne!(esmeralda_key.key, localnet_key.key);
+        esmeralda.key_manager_api().next_key(TRANSACTION_BRANCH).unwrap();
+        let esmeralda_keys = esmeralda.key_manager_api().get_all_keys(TRANSACTION_BRANCH).unwrap();
+        let localnet_keys = localnet.key_manager_api().get_all_keys(TRANSACTION_BRANCH).unwrap();
+        assert_eq!(esmeralda_keys.len(), 2);
+        assert_eq!(localnet_keys.len(), 1);
+
+        // Accounts
+        let account = SubstateId::Component(ComponentAddress::from_array([1u8; 32]));
+        localnet
+            .accounts_api()
+            .add_account(Some("alice"), &account, 0, true)
+            .unwrap();
+        assert!(localnet.accounts_api().has_account(&account).unwrap());
+        assert!(!esmeralda.accounts_api().has_account(&account).unwrap());
+        assert_eq!(esmeralda.accounts_api().count().unwrap(), 0);
+        // The same name can be used in each profile
+        let other_account = SubstateId::Component(ComponentAddress::from_array([2u8; 32]));
+        esmeralda
+            .accounts_api()
+            .add_account(Some("alice"), &other_account, 0, true)
+            .unwrap();
+        assert_eq!(localnet.accounts_api().get_default().unwrap().address, account);
+
+        // Transactions
+        let transaction = Transaction::builder()
+            .call_method(account.as_component_address().unwrap(), "withdraw", vec![])
+            .sign(&localnet_key.key)
+            .build();
+        let transaction_id = localnet
+            .transaction_api()
+            .insert_new_transaction(transaction, vec![], None, false)
+            .await
+            .unwrap();
+        assert!(localnet.transaction_api().get(transaction_id).is_ok());
+        assert!(esmeralda.transaction_api().get(transaction_id).is_err());
+        assert!(esmeralda.transaction_api().fetch_all(None, None).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_resolves_the_requested_or_active_profile() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = create_config(temp.path().to_path_buf());
+        let shutdown = Shutdown::new();
+        let (settings, active_profile) = ProfileSettings::resolve_all(&config).unwrap();
+        let profiles = settings
+            .into_iter()
+            .map(|settings| {
+                let store = settings.open_store().unwrap();
+                let (profile, _) =
+                    WalletProfile::start(settings, store, &config.dan_wallet_daemon, shutdown.to_signal()).unwrap();
+                profile
+            })
+            .collect::<Vec<_>>();
+        let profiles = WalletProfiles::new(profiles, active_profile).unwrap();
+
+        assert_eq!(profiles.get(None).unwrap().name(), "localnet");
+        let esmeralda = profiles.get(Some("esmeralda")).unwrap();
+        assert_eq!(esmeralda.name(), "esmeralda");
+        assert_eq!(esmeralda.settings().key_domain.as_deref(), Some("esmeralda"));
+        assert_eq!(
+            profiles.get(Some("mainnet")).unwrap_err(),
+            WalletMessage::ProfileNotFound {
+                name: "mainnet".to_string()
+            }
+        );
+
+        profiles.switch("esmeralda").unwrap();
+        assert_eq!(profiles.active_profile_name(), "esmeralda");
+        assert_eq!(profiles.get(None).unwrap().name(), "esmeralda");
+        assert!(profiles.switch("mainnet").is_err());
+        assert_eq!(profiles.active_profile_name(), "esmeralda");
+    }
+
+    #[test]
+    fn it_uses_a_single_default_profile_if_no_profiles_are_configured() {
+        let mut config = create_config(PathBuf::from("/wallet"));
+        config.dan_wallet_daemon.profiles.clear();
+        config.dan_wallet_daemon.active_profile = None;
+        let (settings, active_profile) = ProfileSettings::resolve_all(&config).unwrap();
+        assert_eq!(active_profile, DEFAULT_PROFILE_NAME);
+        assert_eq!(settings, vec![ProfileSettings {
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            database_path: PathBuf::from("/wallet/data/wallet.sqlite"),
+            indexer_url: config.dan_wallet_daemon.indexer_node_json_rpc_url.clone(),
+            key_domain: None,
+        }]);
+    }
+}
@@ -551,7 +551,11 @@ where
             WalletEvent::TransactionInvalid(event) => {
                 self.pending_accounts.remove(&event.transaction_id);
             },
-            WalletEvent::AccountCreated(_) | WalletEvent::AccountChanged(_) | WalletEvent::AuthLoginRequest(_) => {},
+            WalletEvent::AccountCreated(_) |
+            WalletEvent::AccountChanged(_) |
+            WalletEvent::AuthLoginRequest(_) |
+            WalletEvent::TransactionQueuePaused(_) |
+            WalletEvent::EpochChanged(_) => {},
         }
         Ok(())
     }
@@ -3,10 +3,12 @@
 
 use std::time::SystemTime;
 
+use tari_dan_common_types::Epoch;
 use tari_dan_wallet_sdk::models::{Account, NewAccountInfo, TransactionStatus};
 use tari_engine_types::{commit_result::FinalizeResult, substate::SubstateId};
 use tari_template_lib::models::Amount;
 use tari_transaction::TransactionId;
+use tari_wallet_daemon_client::messages::WalletMessage;
 
 #[derive(Debug, Clone)]
 pub enum WalletEvent {
@@ -16,6 +18,41 @@ pub enum WalletEvent {
     AccountCreated(AccountCreatedEvent),
     AccountChanged(AccountChangedEvent),
     AuthLoginRequest(AuthLoginRequestEvent),
+    TransactionQueuePaused(TransactionQueuePausedEvent),
+    EpochChanged(EpochChangedEvent),
+}
+
+impl WalletEvent {
+    /// Returns the localizable status message for this event
+    pub fn message(&self) -> WalletMessage {
+        match self {
+            Self::TransactionSubmitted(event) => WalletMessage::TransactionSubmitted {
+                transaction_id: event.transaction_id.to_string(),
+            },
+            Self::TransactionFinalized(event) => WalletMessage::TransactionFinalized {
+                transaction_id: event.transaction_id.to_string(),
+                status: event.status.as_key_str().to_string(),
+                final_fee: event.final_fee.value(),
+            },
+            Self::TransactionInvalid(event) => WalletMessage::TransactionInvalid {
+                transaction_id: event.transaction_id.to_string(),
+                status: event.status.as_key_str().to_string(),
+            },
+            Self::AccountCreated(event) => WalletMessage::AccountCreated {
+                account_address: event.account.address.to_string(),
+                transaction_id: event.created_by_tx.to_string(),
+            },
+            Self::AccountChanged(event) => WalletMessage::AccountChanged {
+                account_address: event.account_address.to_string(),
+            },
+            Self::AuthLoginRequest(_) => WalletMessage::AuthLoginRequested,
+            Self::TransactionQueuePaused(event) => WalletMessage::TransactionQueuePaused {
+                account_address: event.account_address.to_string(),
+                transaction_id: event.transaction_id.to_string(),
+                reason: event.reason.clone(),
+            },
+        }
+    }
 }
 
 impl From<TransactionSubmittedEvent> for WalletEvent {
@@ -54,6 +91,18 @@ impl From<AccountCreatedEvent> for WalletEvent {
     }
 }
 
+impl From<TransactionQueuePausedEvent> for WalletEvent {
+    fn from(value: TransactionQueuePausedEvent) -> Self {
+        Self::TransactionQueuePaused(value)
+    }
+}
+
+impl From<EpochChangedEvent> for WalletEvent {
+    fn from(value: EpochChangedEvent) -> Self {
+        Self::EpochChanged(value)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TransactionSubmittedEvent {
     pub transaction_id: TransactionId,
@@ -94,3 +143,55 @@ pub struct AuthLoginRequestEvent {
     pub auth_token: String,
     pub valid_till: SystemTime,
 }
+
+#[derive(Debug, Clone)]
+pub struct TransactionQueuePausedEvent {
+    pub account_address: SubstateId,
+    /// The queued transaction that failed and is blocking the queue
+    pub transaction_id: TransactionId,
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tari_template_lib::models::{ComponentAddress, ObjectKey};
+
+    use super::*;
+
+    #[test]
+    fn it_maps_events_to_message_codes_and_params() {
+        let transaction_id = TransactionId::new([1u8; 32]);
+        let account_address = SubstateId::Component(ComponentAddress::from_array([2u8; ObjectKey::LENGTH]));
+
+        let event = WalletEvent::from(TransactionInvalidEvent {
+            transaction_id,
+            status: TransactionStatus::Rejected,
+            finalize: None,
+            final_fee: None,
+            is_dry_run: false,
+        });
+        assert_eq!(
+            event.message().to_data(),
+            json!({
+                "code": "transaction_invalid",
+                "params": { "transaction_id": transaction_id.to_string(), "status": "Rejected" },
+            })
+        );
+
+        let event = WalletEvent::from(TransactionQueuePausedEvent {
+            account_address: account_address.clone(),
+            transaction_id,
+            reason: "Insufficient funds".to_string(),
+        });
+        let message = event.message();
+        assert_eq!(message.code(), "transaction_queue_paused");
+        assert_eq!(message.params()["account_address"], account_address.to_string());
+        assert_eq!(message.params()["reason"], "Insufficient funds");
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EpochChangedEvent {
+    pub epoch: Epoch,
+}
@@ -7,6 +7,8 @@ pub use events::*;
 mod account_monitor;
 pub use account_monitor::AccountMonitorHandle;
 
+mod transaction_scheduler;
+
 mod transaction_service;
 // -------------------------------- Spawn -------------------------------- //
 use anyhow::anyhow;
@@ -15,6 +17,7 @@ use tari_dan_common_types::optional::IsNotFoundError;
 use tari_dan_wallet_sdk::{network::WalletNetworkInterface, storage::WalletStore, DanWalletSdk};
 use tari_shutdown::ShutdownSignal;
 use tokio::{sync::oneshot, task::JoinHandle};
+use transaction_scheduler::TransactionScheduler;
 use transaction_service::TransactionService;
 pub use transaction_service::TransactionServiceHandle;
 
@@ -35,13 +38,25 @@ where
     let (transaction_service, transaction_service_handle) =
         TransactionService::new(notify.clone(), wallet_sdk.clone(), shutdown_signal.clone());
     let transaction_service_join_handle = tokio::spawn(transaction_service.run());
+    let transaction_scheduler = TransactionScheduler::new(
+        notify.clone(),
+        wallet_sdk.clone(),
+        transaction_service_handle.clone(),
+        shutdown_signal.clone(),
+    );
+    let transaction_scheduler_join_handle = tokio::spawn(transaction_scheduler.run());
     let (account_monitor, account_monitor_handle) = AccountMonitor::new(notify, wallet_sdk, shutdown_signal);
     let account_monitor_join_handle = tokio::spawn(account_monitor.run());
 
     Services {
         account_monitor_handle,
         transaction_service_handle,
-        services_fut: try_select_any([transaction_service_join_handle, account_monitor_join_handle]).boxed(),
+        services_fut: try_select_any([
+            transaction_service_join_handle,
+            transaction_scheduler_join_handle,
+            account_monitor_join_handle,
+        ])
+        .boxed(),
     }
 }
 
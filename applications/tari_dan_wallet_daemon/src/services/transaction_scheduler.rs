@@ -0,0 +1,150 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::*;
+use tari_dan_common_types::{optional::IsNotFoundError, Epoch};
+use tari_dan_wallet_sdk::{
+    apis::scheduled_transaction::ScheduledTransactionApiError,
+    models::ScheduledTransaction,
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::{
+    notify::Notify,
+    services::{EpochChangedEvent, TransactionServiceHandle, WalletEvent},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::transaction_scheduler";
+
+/// Releases scheduled transactions once their trigger fires. Time triggers are checked on a short interval. Epoch
+/// triggers are checked when an EpochChanged event is received. This service polls the network for the current epoch
+/// and publishes the EpochChanged events.
+pub struct TransactionScheduler<TStore, TNetworkInterface> {
+    notify: Notify<WalletEvent>,
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    transaction_service: TransactionServiceHandle,
+    current_epoch: Option<Epoch>,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> TransactionScheduler<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        notify: Notify<WalletEvent>,
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        transaction_service: TransactionServiceHandle,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            notify,
+            wallet_sdk,
+            transaction_service,
+            current_epoch: None,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let mut events_subscription = self.notify.subscribe();
+        let mut trigger_interval = time::interval(Duration::from_secs(1));
+        trigger_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut epoch_poll_interval = time::interval(Duration::from_secs(30));
+        epoch_poll_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = trigger_interval.tick() => {
+                    self.release_due_transactions().await;
+                }
+
+                _ = epoch_poll_interval.tick() => {
+                    self.poll_current_epoch().await;
+                }
+
+                Ok(event) = events_subscription.recv() => {
+                    if let WalletEvent::EpochChanged(event) = event {
+                        debug!(target: LOG_TARGET, "Epoch changed to {}", event.epoch);
+                        self.current_epoch = Some(event.epoch);
+                        self.release_due_transactions().await;
+                    }
+                },
+            }
+        }
+    }
+
+    async fn poll_current_epoch(&self) {
+        match self.wallet_sdk.get_network_interface().get_current_epoch().await {
+            Ok(epoch) => {
+                if self.current_epoch != Some(epoch) {
+                    self.notify.notify(EpochChangedEvent { epoch });
+                }
+            },
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Failed to get the current epoch: {}", err);
+            },
+        }
+    }
+
+    async fn release_due_transactions(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let due = match self
+            .wallet_sdk
+            .scheduled_transaction_api()
+            .get_due(now, self.current_epoch)
+        {
+            Ok(due) => due,
+            Err(err) => {
+                error!(target: LOG_TARGET, "Failed to get due scheduled transactions: {}", err);
+                return;
+            },
+        };
+
+        for scheduled in due {
+            if let Err(err) = self.release(&scheduled).await {
+                error!(
+                    target: LOG_TARGET,
+                    "Failed to record the outcome of scheduled transaction {}: {}", scheduled.id, err
+                );
+            }
+        }
+    }
+
+    /// Signs and submits a scheduled transaction, recording the submitted transaction id or the reason that it failed
+    async fn release(&self, scheduled: &ScheduledTransaction) -> Result<(), ScheduledTransactionApiError> {
+        let scheduled_api = self.wallet_sdk.scheduled_transaction_api();
+        info!(
+            target: LOG_TARGET,
+            "Releasing scheduled transaction {} ({})", scheduled.id, scheduled.trigger
+        );
+        let (transaction, required_substates) = match scheduled_api.prepare_release(scheduled) {
+            Ok(prepared) => prepared,
+            Err(err) => return scheduled_api.mark_failed(scheduled.id, &err.to_string()),
+        };
+
+        match self
+            .transaction_service
+            .submit_transaction(transaction, required_substates)
+            .await
+        {
+            Ok(transaction_id) => scheduled_api.mark_submitted(scheduled.id, transaction_id),
+            Err(err) => scheduled_api.mark_failed(scheduled.id, &err.to_string()),
+        }
+    }
+}
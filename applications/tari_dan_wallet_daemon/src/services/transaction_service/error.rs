@@ -1,7 +1,7 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_wallet_sdk::apis::transaction::TransactionApiError;
+use tari_dan_wallet_sdk::apis::{transaction::TransactionApiError, transaction_queue::TransactionQueueApiError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionServiceError {
@@ -9,6 +9,8 @@ pub enum TransactionServiceError {
     ServiceShutdown,
     #[error("Transaction API error: {0}")]
     TransactionApiError(#[from] TransactionApiError),
+    #[error("Transaction queue API error: {0}")]
+    TransactionQueueApiError(#[from] TransactionQueueApiError),
     #[error("Dry run transaction failed: {details}")]
     DryRunTransactionFailed { details: String },
 }
@@ -11,6 +11,7 @@ use tari_dan_wallet_sdk::{
     storage::WalletStore,
     DanWalletSdk,
 };
+use tari_engine_types::substate::SubstateId;
 use tari_shutdown::ShutdownSignal;
 use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
 use tokio::{
@@ -25,7 +26,13 @@ use super::{
 };
 use crate::{
     notify::Notify,
-    services::{TransactionFinalizedEvent, TransactionInvalidEvent, TransactionSubmittedEvent, WalletEvent},
+    services::{
+        TransactionFinalizedEvent,
+        TransactionInvalidEvent,
+        TransactionQueuePausedEvent,
+        TransactionSubmittedEvent,
+        WalletEvent,
+    },
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::transaction_service";
@@ -160,9 +167,24 @@ where
         new_account_info: Option<NewAccountInfo>,
     ) -> Result<TransactionId, TransactionServiceError> {
         let transaction_api = self.wallet_sdk.transaction_api();
+        let queue_api = self.wallet_sdk.transaction_queue_api();
+        let maybe_queue_account = queue_api.find_queue_account(&transaction)?;
         let transaction_id = transaction_api
             .insert_new_transaction(transaction, required_substates, new_account_info.clone(), false)
             .await?;
+
+        // Transactions from accounts with ordered transactions enabled are submitted once all prior transactions have
+        // been finalized
+        if let Some(account) = maybe_queue_account {
+            let sequence = queue_api.enqueue(&account, transaction_id)?;
+            info!(
+                target: LOG_TARGET,
+                "Transaction {} added to the queue for account {} with sequence {}", transaction_id, account, sequence
+            );
+            Self::release_next_queued(&self.wallet_sdk, &self.notify, &account).await?;
+            return Ok(transaction_id);
+        }
+
         transaction_api.submit_transaction(transaction_id).await?;
         self.notify.notify(TransactionSubmittedEvent {
             transaction_id,
@@ -183,6 +205,9 @@ where
         let wallet_sdk = self.wallet_sdk.clone();
         let notify = self.notify.clone();
         tokio::spawn(async move {
+            if let Err(err) = Self::release_queued_transactions(&wallet_sdk, &notify).await {
+                error!(target: LOG_TARGET, "Error releasing queued transactions: {}", err);
+            }
             if let Err(err) = Self::resubmit_new_transactions(&wallet_sdk, &notify).await {
                 error!(target: LOG_TARGET, "Error re-submitting new transactions: {}", err);
             }
@@ -195,6 +220,68 @@ where
         Ok(())
     }
 
+    async fn release_queued_transactions(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+    ) -> Result<(), TransactionServiceError> {
+        let accounts = wallet_sdk.transaction_queue_api().get_enabled_accounts()?;
+        for account in accounts {
+            Self::release_next_queued(wallet_sdk, notify, &account).await?;
+        }
+        Ok(())
+    }
+
+    /// Submits the transaction at the front of the account queue if the previous transaction has been finalized
+    async fn release_next_queued(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+        account: &SubstateId,
+    ) -> Result<(), TransactionServiceError> {
+        let Some(transaction_id) = wallet_sdk.transaction_queue_api().release_next(account)? else {
+            return Ok(());
+        };
+        info!(
+            target: LOG_TARGET,
+            "Releasing queued transaction {} for account {}", transaction_id, account
+        );
+        let transaction_api = wallet_sdk.transaction_api();
+        let transaction = transaction_api.get(transaction_id)?;
+        transaction_api.submit_transaction(transaction_id).await?;
+        notify.notify(TransactionSubmittedEvent {
+            transaction_id,
+            new_account: transaction.new_account_info,
+        });
+        Ok(())
+    }
+
+    async fn on_transaction_finalized(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+    ) -> Result<(), TransactionServiceError> {
+        let Some(queue) = wallet_sdk
+            .transaction_queue_api()
+            .on_transaction_finalized(transaction_id, status)?
+        else {
+            return Ok(());
+        };
+
+        match queue.paused_reason {
+            Some(reason) => {
+                notify.notify(TransactionQueuePausedEvent {
+                    account_address: queue.account_address,
+                    transaction_id,
+                    reason,
+                });
+            },
+            None => {
+                Self::release_next_queued(wallet_sdk, notify, &queue.account_address).await?;
+            },
+        }
+        Ok(())
+    }
+
     async fn resubmit_new_transactions(
         wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
         notify: &Notify<WalletEvent>,
@@ -263,6 +350,13 @@ where
                         transaction.transaction.id(),
                         transaction.status,
                     );
+                    Self::on_transaction_finalized(
+                        wallet_sdk,
+                        notify,
+                        *transaction.transaction.id(),
+                        transaction.status,
+                    )
+                    .await?;
                     match transaction.finalize {
                         Some(finalize) => {
                             notify.notify(TransactionFinalizedEvent {
@@ -302,7 +396,9 @@ where
             WalletEvent::TransactionFinalized(_) |
             WalletEvent::AccountChanged(_) |
             WalletEvent::AuthLoginRequest(_) |
-            WalletEvent::AccountCreated(_) => {},
+            WalletEvent::AccountCreated(_) |
+            WalletEvent::TransactionQueuePaused(_) |
+            WalletEvent::EpochChanged(_) => {},
         }
         Ok(())
     }
@@ -35,6 +35,8 @@ const colorList: Record<string, string> = {
   Pending: "#ECA86A",
   DryRun: "#318EFA",
   New: "#9D5CF9",
+  PendingSignature: "#ECA86A",
+  Queued: "#ECA86A",
   Rejected: "#DB7E7E",
   InvalidTransaction: "#DB7E7E",
   OnlyFeeAccepted: "#FFA500",
@@ -48,6 +50,10 @@ export default function StatusChip({ status, showTitle = true }: StatusChipProps
     Pending: <IoHourglassOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     DryRun: <IoReload style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     New: <IoDiamondOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
+    PendingSignature: (
+      <IoHourglassOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />
+    ),
+    Queued: <IoHourglassOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     Rejected: <IoCloseOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     InvalidTransaction: <IoCloseOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     OnlyFeeAccepted: (
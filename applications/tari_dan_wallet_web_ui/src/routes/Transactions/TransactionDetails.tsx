@@ -130,6 +130,10 @@ export default function TransactionDetails() {
           return `ShardRejected: ${x["ShardRejected"]}`;
         } else if ("FeesNotPaid" in x) {
           return `FeesNotPaid: ${x["FeesNotPaid"]}`;
+        } else if ("EpochExpired" in x) {
+          return `EpochExpired: ${x["EpochExpired"]}`;
+        } else if ("ExecutionBudgetExceeded" in x) {
+          return `ExecutionBudgetExceeded: ${x["ExecutionBudgetExceeded"]}`;
         }
         return "Unknown reason";
       };
@@ -138,6 +138,7 @@ pub async fn spawn_services(
     // Base Node scanner
     base_layer_scanner::spawn(
         config.network,
+        config.indexer.genesis,
         global_db,
         base_node_client.clone(),
         epoch_manager.clone(),
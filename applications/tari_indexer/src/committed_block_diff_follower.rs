@@ -0,0 +1,115 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use log::*;
+use tari_dan_common_types::PeerAddress;
+use tari_dan_p2p::proto::rpc::StreamCommittedBlockDiffsRequest;
+use tari_dan_storage::consensus_models::{CommittedBlockDiff, CommittedBlockDiffCursor};
+use tari_rpc_framework::RpcError;
+use tari_shutdown::ShutdownSignal;
+use tari_validator_node_rpc::client::{TariValidatorNodeRpcClientFactory, ValidatorNodeClientFactory};
+use tokio::time;
+
+const LOG_TARGET: &str = "tari::indexer::committed_block_diff_follower";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Follows the committed block diff stream of a validator node. The cursor of the last diff received is kept so that
+/// the stream resumes without gaps when the connection is lost, provided that the validator node still retains that
+/// diff.
+pub struct CommittedBlockDiffFollower {
+    client_factory: TariValidatorNodeRpcClientFactory,
+    validator_node: PeerAddress,
+    cursor: Option<CommittedBlockDiffCursor>,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl CommittedBlockDiffFollower {
+    pub fn new(
+        client_factory: TariValidatorNodeRpcClientFactory,
+        validator_node: PeerAddress,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            client_factory,
+            validator_node,
+            cursor: None,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) {
+        let mut shutdown_signal = self.shutdown_signal.clone();
+        loop {
+            tokio::select! {
+                _ = shutdown_signal.wait() => break,
+                result = self.follow() => {
+                    if let Err(err) = result {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Committed block diff stream from {} failed: {}", self.validator_node, err
+                        );
+                    }
+                },
+            }
+
+            // The stream also ends if no block is committed within the RPC deadline, so we always reconnect
+            tokio::select! {
+                _ = shutdown_signal.wait() => break,
+                _ = time::sleep(RECONNECT_DELAY) => {},
+            }
+        }
+    }
+
+    async fn follow(&mut self) -> Result<(), anyhow::Error> {
+        let mut rpc_client = self.client_factory.create_client(&self.validator_node);
+        let mut client = rpc_client.client_connection().await?;
+
+        let request = StreamCommittedBlockDiffsRequest {
+            cursor: self.cursor.map(Into::into),
+        };
+        let mut stream = match client.stream_committed_block_diffs(request).await {
+            Ok(stream) => stream,
+            Err(err) => {
+                if let (RpcError::RequestFailed(status), Some(cursor)) = (&err, self.cursor) {
+                    if status.is_not_found() {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Cursor {} is no longer retained by {}. Blocks were missed, restarting from the oldest \
+                             retained diff",
+                            cursor,
+                            self.validator_node
+                        );
+                        self.cursor = None;
+                        return Ok(());
+                    }
+                }
+                return Err(err.into());
+            },
+        };
+
+        while let Some(resp) = stream.next().await {
+            let diff = CommittedBlockDiff::try_from(resp?)?;
+            self.handle_diff(&diff);
+            self.cursor = Some(diff.cursor());
+        }
+
+        Ok(())
+    }
+
+    fn handle_diff(&self, diff: &CommittedBlockDiff) {
+        let num_up = diff.changes.iter().filter(|change| change.is_up()).count();
+        info!(
+            target: LOG_TARGET,
+            "Committed block diff #{}: {} ({} command(s), {} up, {} down)",
+            diff.sequence,
+            diff.block,
+            diff.block.commands().len(),
+            num_up,
+            diff.changes.len() - num_up,
+        );
+    }
+}
@@ -39,6 +39,7 @@ use tari_dan_app_utilities::{
     p2p_config::{P2pConfig, PeerSeedsConfig},
     template_manager::implementation::TemplateConfig,
 };
+use tari_dan_storage::consensus_models::GenesisConfig;
 use tari_engine_types::substate::SubstateId;
 
 #[derive(Debug, Clone)]
@@ -102,6 +103,11 @@ pub struct IndexerConfig {
     pub templates_sidechain_id: Option<RistrettoPublicKey>,
     /// the burnt utxos sidechain id
     pub burnt_utxo_sidechain_id: Option<RistrettoPublicKey>,
+    /// The public key of a validator node to follow committed block diffs from. The validator node must have committed
+    /// block diff streaming enabled.
+    pub committed_block_diff_source: Option<RistrettoPublicKey>,
+    /// Consensus genesis parameters of the network. These must match the validator nodes' genesis config.
+    pub genesis: GenesisConfig,
 }
 
 impl IndexerConfig {
@@ -142,6 +148,8 @@ impl Default for IndexerConfig {
             sidechain_id: None,
             templates_sidechain_id: None,
             burnt_utxo_sidechain_id: None,
+            committed_block_diff_source: None,
+            genesis: GenesisConfig::default(),
         }
     }
 }
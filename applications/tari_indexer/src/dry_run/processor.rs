@@ -25,6 +25,7 @@ use std::{collections::HashMap, sync::Arc};
 use log::info;
 use tari_common::configuration::Network;
 use tari_dan_app_utilities::{
+    consensus_constants::ConsensusConstants,
     template_manager::implementation::TemplateManager,
     transaction_executor::{TariDanTransactionProcessor, TransactionExecutor as _},
 };
@@ -68,6 +69,7 @@ pub struct DryRunTransactionProcessor<TSubstateCache> {
     substate_scanner:
         Arc<SubstateScanner<EpochManagerHandle<PeerAddress>, TariValidatorNodeRpcClientFactory, TSubstateCache>>,
     network: Network,
+    consensus_constants: ConsensusConstants,
 }
 
 impl<TSubstateCache> DryRunTransactionProcessor<TSubstateCache>
@@ -81,6 +83,7 @@ where TSubstateCache: SubstateCache + 'static
         >,
         template_manager: TemplateManager<PeerAddress>,
         network: Network,
+        consensus_constants: ConsensusConstants,
     ) -> Self {
         let transaction_autofiller = TransactionAutofiller::new(substate_scanner.clone());
 
@@ -91,6 +94,7 @@ where TSubstateCache: SubstateCache + 'static
             template_manager,
             substate_scanner,
             network,
+            consensus_constants,
         }
     }
 
@@ -142,7 +146,12 @@ where TSubstateCache: SubstateCache + 'static
             FeeTable::zero_rated()
         };
 
-        TariDanTransactionProcessor::new(self.network, self.template_manager.clone(), fee_table)
+        TariDanTransactionProcessor::new(
+            self.network,
+            self.template_manager.clone(),
+            fee_table,
+            self.consensus_constants.substate_size_limits(),
+        )
     }
 
     fn transaction_includes_fees(transaction: &Transaction) -> bool {
@@ -33,7 +33,7 @@ use tari_common::configuration::Network;
 use tari_crypto::tari_utilities::message_format::MessageFormat;
 use tari_dan_common_types::{committee::Committee, shard::Shard, Epoch, PeerAddress};
 use tari_dan_p2p::proto::rpc::{GetTransactionResultRequest, PayloadResultStatus, SyncBlocksRequest};
-use tari_dan_storage::consensus_models::{Block, BlockId, Command, Decision, TransactionRecord};
+use tari_dan_storage::consensus_models::{Block, BlockId, Command, Decision, GenesisConfig, TransactionRecord};
 use tari_engine_types::{commit_result::ExecuteResult, events::Event, substate::SubstateId};
 use tari_epoch_manager::EpochManagerReader;
 use tari_template_lib::{models::Metadata, Hash};
@@ -54,6 +54,7 @@ const LOG_TARGET: &str = "tari::indexer::event_manager";
 
 pub struct EventManager {
     network: Network,
+    genesis: GenesisConfig,
     epoch_manager: Box<dyn EpochManagerReader<Addr = PeerAddress>>,
     client_factory: TariValidatorNodeRpcClientFactory,
     substate_store: SqliteSubstateStore,
@@ -62,12 +63,14 @@ pub struct EventManager {
 impl EventManager {
     pub fn new(
         network: Network,
+        genesis: GenesisConfig,
         epoch_manager: Box<dyn EpochManagerReader<Addr = PeerAddress>>,
         client_factory: TariValidatorNodeRpcClientFactory,
         substate_store: SqliteSubstateStore,
     ) -> Self {
         Self {
             network,
+            genesis,
             epoch_manager,
             client_factory,
             substate_store,
@@ -263,7 +266,7 @@ impl EventManager {
     }
 
     fn build_genesis_block_id(&self) -> BlockId {
-        let start_block = Block::zero_block(self.network);
+        let start_block = Block::zero_block_with_genesis(self.network, &self.genesis);
         *start_block.id()
     }
 
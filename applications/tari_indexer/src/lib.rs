@@ -27,6 +27,7 @@ extern crate diesel_migrations;
 
 mod bootstrap;
 pub mod cli;
+mod committed_block_diff_follower;
 pub mod config;
 mod dry_run;
 pub mod graphql;
@@ -53,6 +54,7 @@ use tari_dan_app_utilities::{
     keypair::setup_keypair_prompt,
     substate_file_cache::SubstateFileCache,
 };
+use tari_dan_common_types::PeerAddress;
 use tari_dan_storage::global::DbFactory;
 use tari_dan_storage_sqlite::SqliteDbFactory;
 use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
@@ -63,6 +65,7 @@ use tokio::{task, time};
 
 use crate::{
     bootstrap::{spawn_services, Services},
+    committed_block_diff_follower::CommittedBlockDiffFollower,
     config::ApplicationConfig,
     dry_run::processor::DryRunTransactionProcessor,
     event_manager::EventManager,
@@ -129,6 +132,7 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
         dan_layer_scanner,
         services.template_manager.clone(),
         config.network,
+        ConsensusConstants::for_network(config.network),
     );
 
     // Run the JSON-RPC API
@@ -165,6 +169,7 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
     // Run the event manager
     let event_manager = Arc::new(EventManager::new(
         config.network,
+        config.indexer.genesis,
         Box::new(services.epoch_manager.clone()),
         services.validator_node_client_factory.clone(),
         services.substate_store.clone(),
@@ -177,6 +182,17 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
         task::spawn(run_graphql(address, substate_manager.clone(), event_manager.clone()));
     }
 
+    if let Some(public_key) = config.indexer.committed_block_diff_source.clone() {
+        task::spawn(
+            CommittedBlockDiffFollower::new(
+                services.validator_node_client_factory.clone(),
+                PeerAddress::from(public_key),
+                shutdown_signal.clone(),
+            )
+            .run(),
+        );
+    }
+
     // Create pid to allow watchers to know that the process has started
     fs::write(config.common.base_path.join("pid"), std::process::id().to_string())
         .map_err(|e| ExitError::new(ExitCode::IOError, e))?;
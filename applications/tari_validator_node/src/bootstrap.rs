@@ -25,7 +25,7 @@ use std::{fs, io, ops::Deref, str::FromStr};
 use anyhow::{anyhow, Context};
 use futures::{future, FutureExt};
 use libp2p::identity;
-use log::info;
+use log::{info, warn};
 use minotari_app_utilities::identity_management;
 use serde::Serialize;
 use sqlite_message_logger::SqliteMessageLogger;
@@ -34,6 +34,7 @@ use tari_common::{
     configuration::Network,
     exit_codes::{ExitCode, ExitError},
 };
+use tari_consensus::journal::JournalConfig;
 #[cfg(not(feature = "metrics"))]
 use tari_consensus::traits::hooks::NoopHooks;
 use tari_core::transactions::transaction_components::ValidatorNodeSignature;
@@ -48,11 +49,11 @@ use tari_dan_app_utilities::{
     template_manager::{implementation::TemplateManager, interface::TemplateManagerHandle},
     transaction_executor::TariDanTransactionProcessor,
 };
-use tari_dan_common_types::{Epoch, NodeAddressable, NodeHeight, PeerAddress, SubstateAddress};
+use tari_dan_common_types::{NodeAddressable, NodeHeight, PeerAddress, SubstateAddress};
 use tari_dan_engine::fees::FeeTable;
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, ExecutedTransaction, SubstateRecord},
+    consensus_models::{Block, BlockId, ExecutedTransaction, GenesisConfig, SubstateRecord},
     global::GlobalDb,
     StateStore,
     StateStoreReadTransaction,
@@ -66,7 +67,7 @@ use tari_indexer_lib::substate_scanner::SubstateScanner;
 use tari_networking::{MessagingMode, NetworkingHandle, RelayCircuitLimits, RelayReservationLimits, SwarmConfig};
 use tari_rpc_framework::RpcServer;
 use tari_shutdown::ShutdownSignal;
-use tari_state_store_sqlite::SqliteStateStore;
+use tari_state_store_sqlite::{RecoveryPolicy, SqliteStateStore};
 use tari_template_lib::{
     auth::ResourceAccessRules,
     constants::{CONFIDENTIAL_TARI_RESOURCE_ADDRESS, PUBLIC_IDENTITY_RESOURCE_ADDRESS},
@@ -79,9 +80,15 @@ use tari_validator_node_rpc::client::TariValidatorNodeRpcClientFactory;
 use tokio::{sync::mpsc, task::JoinHandle};
 
 #[cfg(feature = "metrics")]
-use crate::consensus::metrics::PrometheusConsensusMetrics;
 use crate::{
+    consensus::metrics::PrometheusConsensusMetrics,
+    shard_growth_metrics::ShardGrowthMetrics,
+    template_cache_metrics::TemplateCacheMetrics,
+};
+use crate::{
+    config_reload::{ConfigLoader, ConfigReloadHandle},
     consensus::{self, ConsensusHandle, TariDanBlockTransactionExecutor},
+    db_maintenance::{self, DbMaintenanceStatus},
     dry_run_transaction_processor::DryRunTransactionProcessor,
     p2p::{
         create_tari_validator_node_rpc_service,
@@ -103,9 +110,11 @@ use crate::{
             messaging::{ConsensusInboundMessaging, ConsensusOutboundMessaging, Gossip},
         },
     },
+    shard_growth::{self, ShardGrowthStatus},
     substate_resolver::TariSubstateResolver,
     validator_registration_file::ValidatorRegistrationFile,
     virtual_substate::VirtualSubstateManager,
+    write_queue_watchdog,
     ApplicationConfig,
     ValidatorNodeConfig,
 };
@@ -120,6 +129,7 @@ pub async fn spawn_services(
     global_db: GlobalDb<SqliteGlobalDbAdapter<PeerAddress>>,
     consensus_constants: ConsensusConstants,
     base_node_client: GrpcBaseNodeClient,
+    config_loader: Option<ConfigLoader>,
     #[cfg(feature = "metrics")] metrics_registry: &prometheus::Registry,
 ) -> Result<Services, anyhow::Error> {
     let mut handles = Vec::with_capacity(8);
@@ -181,9 +191,27 @@ pub async fn spawn_services(
 
     info!(target: LOG_TARGET, "State store initializing");
     // Connect to shard db
-    let state_store =
-        SqliteStateStore::connect(&format!("sqlite://{}", config.validator_node.state_db_path().display()))?;
-    state_store.with_write_tx(|tx| bootstrap_state(tx, config.network))?;
+    let recovery_policy = if config.validator_node.auto_recover_state_store {
+        RecoveryPolicy::RestoreFromBackup {
+            backup_dir: config.validator_node.state_db_backup_dir(),
+        }
+    } else {
+        RecoveryPolicy::Refuse
+    };
+    let (state_store, recovery) =
+        SqliteStateStore::try_open_with_recovery(config.validator_node.state_db_path(), &recovery_policy)?;
+    let state_store = state_store.with_write_queue_config((&config.validator_node.write_queue).into());
+    if let Some(recovery) = recovery {
+        warn!(
+            target: LOG_TARGET,
+            "⚠️ The state store was corrupt ({}). It was moved to {} and restored from backup {}. Blocks committed \
+             since the backup will be synced from peers before this node participates in consensus.",
+            recovery.corruption,
+            recovery.corrupt_file.display(),
+            recovery.restored_from.display()
+        );
+    }
+    state_store.with_write_tx(|tx| bootstrap_state(tx, config.network, &config.validator_node.genesis))?;
 
     info!(target: LOG_TARGET, "Epoch manager initializing");
     // Epoch manager
@@ -211,6 +239,8 @@ pub async fn spawn_services(
     info!(target: LOG_TARGET, "Template manager initializing");
     // Template manager
     let template_manager = TemplateManager::initialize(global_db.clone(), config.validator_node.templates.clone())?;
+    #[cfg(feature = "metrics")]
+    TemplateCacheMetrics::register(template_manager.clone(), metrics_registry);
     let (template_manager_service, join_handle) =
         template_manager::implementation::spawn(template_manager.clone(), shutdown.clone());
     handles.push(join_handle);
@@ -227,7 +257,13 @@ pub async fn spawn_services(
             per_log_cost: 1,
         }
     };
-    let payload_processor = TariDanTransactionProcessor::new(config.network, template_manager.clone(), fee_table);
+    let payload_processor = TariDanTransactionProcessor::new(
+        config.network,
+        template_manager.clone(),
+        fee_table,
+        consensus_constants.substate_size_limits(),
+    )
+    .with_execution_budget(consensus_constants.transaction_execution_budget);
 
     let validator_node_client_factory = TariValidatorNodeRpcClientFactory::new(networking.clone());
 
@@ -242,18 +278,27 @@ pub async fn spawn_services(
         loopback_receiver,
         message_logger.clone(),
     );
-    let outbound_messaging =
-        ConsensusOutboundMessaging::new(loopback_sender, networking.clone(), message_logger.clone());
-
-    let transaction_executor = TariDanBlockTransactionExecutor::new(epoch_manager.clone(), payload_processor.clone());
 
     #[cfg(feature = "metrics")]
     let metrics = PrometheusConsensusMetrics::new(state_store.clone(), metrics_registry);
     #[cfg(not(feature = "metrics"))]
     let metrics = NoopHooks;
 
+    let outbound_messaging = ConsensusOutboundMessaging::new(
+        loopback_sender,
+        networking.clone(),
+        message_logger.clone(),
+        metrics.clone(),
+    );
+
+    let transaction_executor = TariDanBlockTransactionExecutor::new(epoch_manager.clone(), payload_processor.clone());
+
+    let join_handle = write_queue_watchdog::spawn(state_store.write_queue().clone(), metrics.clone(), shutdown.clone());
+    handles.push(join_handle);
+
     let (consensus_join_handle, consensus_handle, rx_consensus_to_mempool) = consensus::spawn(
         config.network,
+        config.validator_node.genesis,
         state_store.clone(),
         keypair.clone(),
         epoch_manager.clone(),
@@ -265,6 +310,9 @@ pub async fn spawn_services(
         shutdown.clone(),
         transaction_executor,
         consensus_constants.clone(),
+        config.validator_node.maintenance_mode,
+        consensus_journal_config(&config.validator_node),
+        committed_block_diff_retention(&config.validator_node),
     )
     .await;
     handles.push(consensus_join_handle);
@@ -313,6 +361,7 @@ pub async fn spawn_services(
     // Base Node scanner
     let join_handle = base_layer_scanner::spawn(
         config.network,
+        config.validator_node.genesis,
         global_db.clone(),
         base_node_client.clone(),
         epoch_manager.clone(),
@@ -328,12 +377,45 @@ pub async fn spawn_services(
     );
     handles.push(join_handle);
 
+    let (config_reload, rx_db_maintenance_config, rx_shard_growth_config) =
+        ConfigReloadHandle::new(config_loader, &config.validator_node);
+    #[cfg(unix)]
+    handles.push(tokio::spawn(crate::config_reload::reload_on_sighup(
+        config_reload.clone(),
+        shutdown.clone(),
+    )));
+
+    let db_maintenance_status = DbMaintenanceStatus::default();
+    let join_handle = db_maintenance::spawn(
+        rx_db_maintenance_config,
+        state_store.clone(),
+        config.validator_node.state_db_backup_dir(),
+        global_db.clone(),
+        epoch_manager.clone(),
+        db_maintenance_status.clone(),
+        shutdown.clone(),
+    );
+    handles.push(join_handle);
+
+    let shard_growth_status = ShardGrowthStatus::default();
+    #[cfg(feature = "metrics")]
+    ShardGrowthMetrics::register(shard_growth_status.clone(), metrics_registry);
+    let join_handle = shard_growth::spawn(
+        rx_shard_growth_config,
+        state_store.clone(),
+        epoch_manager.clone(),
+        shard_growth_status.clone(),
+        shutdown.clone(),
+    );
+    handles.push(join_handle);
+
     spawn_p2p_rpc(
         config,
         &mut networking,
         state_store.clone(),
         mempool.clone(),
         virtual_substate_manager,
+        consensus_handle.clone(),
     )
     .await?;
     // Save final node identity after comms has initialized. This is required because the public_address can be
@@ -353,6 +435,9 @@ pub async fn spawn_services(
         global_db,
         state_store,
         dry_run_transaction_processor,
+        db_maintenance_status,
+        shard_growth_status,
+        config_reload,
         handles,
         validator_node_client_factory,
     })
@@ -406,6 +491,9 @@ pub struct Services {
     pub dry_run_transaction_processor: DryRunTransactionProcessor,
     pub validator_node_client_factory: TariValidatorNodeRpcClientFactory,
     pub state_store: SqliteStateStore<PeerAddress>,
+    pub db_maintenance_status: DbMaintenanceStatus,
+    pub shard_growth_status: ShardGrowthStatus,
+    pub config_reload: ConfigReloadHandle,
 
     pub handles: Vec<JoinHandle<Result<(), anyhow::Error>>>,
 }
@@ -425,6 +513,7 @@ async fn spawn_p2p_rpc(
     shard_store_store: SqliteStateStore<PeerAddress>,
     mempool: MempoolHandle,
     virtual_substate_manager: VirtualSubstateManager<SqliteStateStore<PeerAddress>, EpochManagerHandle<PeerAddress>>,
+    consensus_handle: ConsensusHandle,
 ) -> anyhow::Result<()> {
     let rpc_server = RpcServer::builder()
         .with_maximum_simultaneous_sessions(config.validator_node.rpc.max_simultaneous_sessions)
@@ -434,6 +523,8 @@ async fn spawn_p2p_rpc(
             shard_store_store,
             mempool,
             virtual_substate_manager,
+            consensus_handle,
+            config.validator_node.committed_block_diff_stream.enabled,
         ));
 
     let (notify_tx, notify_rx) = mpsc::unbounded_channel();
@@ -445,13 +536,14 @@ async fn spawn_p2p_rpc(
 }
 
 // TODO: Figure out the best way to have the engine shard store mirror these bootstrapped states.
-fn bootstrap_state<TTx>(tx: &mut TTx, network: Network) -> Result<(), StorageError>
+fn bootstrap_state<TTx>(tx: &mut TTx, network: Network, genesis: &GenesisConfig) -> Result<(), StorageError>
 where
     TTx: StateStoreWriteTransaction + Deref,
     TTx::Target: StateStoreReadTransaction,
     TTx::Addr: NodeAddressable + Serialize,
 {
-    let genesis_block = Block::genesis(network);
+    // The genesis block must be the same on all nodes
+    let genesis_block = Block::genesis(network, genesis, 0);
     let substate_id = SubstateId::Resource(PUBLIC_IDENTITY_RESOURCE_ADDRESS);
     let substate_address = SubstateAddress::from_substate_id(&substate_id, 0);
     let mut metadata: Metadata = Default::default();
@@ -476,7 +568,7 @@ where
             created_justify: *genesis_block.justify().id(),
             created_block: BlockId::genesis(),
             created_height: NodeHeight(0),
-            created_at_epoch: Epoch(0),
+            created_at_epoch: genesis_block.epoch(),
             destroyed: None,
         }
         .create(tx)?;
@@ -505,7 +597,7 @@ where
             created_justify: *genesis_block.justify().id(),
             created_block: BlockId::genesis(),
             created_height: NodeHeight(0),
-            created_at_epoch: Epoch(0),
+            created_at_epoch: genesis_block.epoch(),
             destroyed: None,
         }
         .create(tx)?;
@@ -514,6 +606,22 @@ where
     Ok(())
 }
 
+fn consensus_journal_config(config: &ValidatorNodeConfig) -> Option<JournalConfig> {
+    if !config.consensus_journal.enabled {
+        return None;
+    }
+    Some(JournalConfig {
+        directory: config.consensus_journal_dir(),
+        max_file_size: config.consensus_journal.max_file_size,
+        max_rotated_files: config.consensus_journal.max_rotated_files,
+    })
+}
+
+fn committed_block_diff_retention(config: &ValidatorNodeConfig) -> Option<u64> {
+    let stream_config = &config.committed_block_diff_stream;
+    stream_config.enabled.then_some(stream_config.retention.max(1))
+}
+
 fn create_mempool_before_execute_validator(
     config: &ValidatorNodeConfig,
     template_manager: TemplateManager<PeerAddress>,
@@ -28,6 +28,7 @@ use std::{
 
 use config::Config;
 use libp2p::Multiaddr;
+use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 use tari_common::{
     configuration::{serializers, CommonConfig, Network},
@@ -40,6 +41,7 @@ use tari_dan_app_utilities::{
     p2p_config::{P2pConfig, PeerSeedsConfig, RpcConfig},
     template_manager::implementation::TemplateConfig,
 };
+use tari_dan_storage::consensus_models::GenesisConfig;
 
 #[derive(Debug, Clone)]
 pub struct ApplicationConfig {
@@ -108,6 +110,32 @@ pub struct ValidatorNodeConfig {
     pub template_sidechain_id: Option<RistrettoPublicKey>,
     /// The burnt utxo sidechain id
     pub burnt_utxo_sidechain_id: Option<RistrettoPublicKey>,
+    /// Database maintenance settings
+    pub db_maintenance: DbMaintenanceConfig,
+    /// Shard growth monitoring settings
+    pub shard_growth: ShardGrowthConfig,
+    /// State store write queue monitoring and load shedding settings
+    pub write_queue: WriteQueueConfig,
+    /// If the state store is corrupt at startup, move it aside and restore the most recent backup. The node then syncs
+    /// the blocks committed since the backup from peers. If false, the node refuses to start with a corrupt state store.
+    pub auto_recover_state_store: bool,
+    /// Consensus genesis parameters. These must be the same for every validator node on the network.
+    pub genesis: GenesisConfig,
+    /// Start in maintenance mode. The node stays in sync but does not propose or vote until it is resumed.
+    pub maintenance_mode: bool,
+    /// Consensus decision journal settings
+    pub consensus_journal: ConsensusJournalConfig,
+    /// Committed block diff streaming settings
+    pub committed_block_diff_stream: CommittedBlockDiffStreamConfig,
+    /// The number of blocks after a foreign proposal is proposed after which its transactions that have not been
+    /// prepared locally are aborted. If not set, the consensus constant for the network is used.
+    pub foreign_proposal_timeout: Option<u64>,
+    /// The maximum estimated size in bytes of the commands in a proposed block. If not set, the consensus constant for
+    /// the network is used.
+    pub max_block_size_bytes: Option<usize>,
+    /// Caps the level of all log targets, e.g. "debug". If not set, the levels in the log config are used. This can be
+    /// changed without a restart by reloading the config.
+    pub max_log_level: Option<LevelFilter>,
 }
 
 impl ValidatorNodeConfig {
@@ -115,6 +143,14 @@ impl ValidatorNodeConfig {
         self.data_dir.join("state.db")
     }
 
+    pub fn state_db_backup_dir(&self) -> PathBuf {
+        self.data_dir.join("state_backups")
+    }
+
+    pub fn consensus_journal_dir(&self) -> PathBuf {
+        self.data_dir.join("consensus_journal")
+    }
+
     pub fn set_base_path<P: AsRef<Path>>(&mut self, base_path: P) {
         if !self.shard_key_file.is_absolute() {
             self.shard_key_file = base_path.as_ref().join(&self.shard_key_file);
@@ -152,6 +188,149 @@ impl Default for ValidatorNodeConfig {
             validator_node_sidechain_id: None,
             template_sidechain_id: None,
             burnt_utxo_sidechain_id: None,
+            db_maintenance: DbMaintenanceConfig::default(),
+            shard_growth: ShardGrowthConfig::default(),
+            write_queue: WriteQueueConfig::default(),
+            auto_recover_state_store: false,
+            genesis: GenesisConfig::default(),
+            maintenance_mode: false,
+            consensus_journal: ConsensusJournalConfig::default(),
+            committed_block_diff_stream: CommittedBlockDiffStreamConfig::default(),
+            foreign_proposal_timeout: None,
+            max_block_size_bytes: None,
+            max_log_level: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DbMaintenanceConfig {
+    /// If set to false, sqlite maintenance is never run
+    pub enabled: bool,
+    /// The minimum time between maintenance runs. Maintenance is run on epoch change if at least this much time has
+    /// passed since the last run.
+    #[serde(with = "serializers::seconds")]
+    pub min_interval: Duration,
+    /// Also run `PRAGMA incremental_vacuum` to release free pages. This only has an effect on databases created with
+    /// `auto_vacuum = INCREMENTAL`.
+    pub incremental_vacuum: bool,
+    /// The number of state store backups to keep. A backup is written after each maintenance run. Set to 0 to disable
+    /// backups.
+    pub num_state_store_backups: usize,
+}
+
+impl Default for DbMaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_interval: Duration::from_secs(60 * 60),
+            incremental_vacuum: false,
+            num_state_store_backups: 2,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShardGrowthConfig {
+    /// If set to false, the substates in this node's shard are not sampled
+    pub enabled: bool,
+    /// The time between samples of the number and size of the substates in this node's shard
+    #[serde(with = "serializers::seconds")]
+    pub sample_interval: Duration,
+    /// The number of most recent samples to keep. At least one sample is always kept.
+    pub max_samples: usize,
+    /// A warning is logged when the stored size of the substates in this node's shard grows faster than this many
+    /// bytes per hour. Set to 0 to disable the warning.
+    pub warn_bytes_per_hour: u64,
+}
+
+impl Default for ShardGrowthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval: Duration::from_secs(10 * 60),
+            max_samples: 144,
+            warn_bytes_per_hour: 100 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WriteQueueConfig {
+    /// A warning is logged when a state store write waits longer than this many milliseconds
+    pub warn_wait_ms: u64,
+    /// New transaction submissions are refused when a state store write waits longer than this many milliseconds.
+    /// Consensus writes continue to be processed.
+    pub shed_wait_ms: u64,
+    /// Submissions are accepted again after `recover_count` consecutive writes wait less than this many milliseconds
+    pub recover_wait_ms: u64,
+    /// The number of consecutive short waits required to accept submissions again
+    pub recover_count: usize,
+    /// Submissions are accepted again once no write has waited longer than `recover_wait_ms` for this long
+    #[serde(with = "serializers::seconds")]
+    pub max_shed_duration: Duration,
+}
+
+impl Default for WriteQueueConfig {
+    fn default() -> Self {
+        let defaults = tari_state_store_sqlite::WriteQueueConfig::default();
+        Self {
+            warn_wait_ms: defaults.warn_wait.as_millis() as u64,
+            shed_wait_ms: defaults.shed_wait.as_millis() as u64,
+            recover_wait_ms: defaults.recover_wait.as_millis() as u64,
+            recover_count: defaults.recover_count,
+            max_shed_duration: defaults.max_shed_duration,
+        }
+    }
+}
+
+impl From<&WriteQueueConfig> for tari_state_store_sqlite::WriteQueueConfig {
+    fn from(config: &WriteQueueConfig) -> Self {
+        Self {
+            warn_wait: Duration::from_millis(config.warn_wait_ms),
+            shed_wait: Duration::from_millis(config.shed_wait_ms),
+            recover_wait: Duration::from_millis(config.recover_wait_ms),
+            recover_count: config.recover_count,
+            max_shed_duration: config.max_shed_duration,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConsensusJournalConfig {
+    /// If set to true, consensus decisions are written to an append-only journal in the data directory for post-mortem
+    /// analysis
+    pub enabled: bool,
+    /// The journal file is rotated once it reaches this size in bytes
+    pub max_file_size: u64,
+    /// The number of rotated journal files to keep
+    pub max_rotated_files: usize,
+}
+
+impl Default for ConsensusJournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_file_size: 64 * 1024 * 1024,
+            max_rotated_files: 4,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommittedBlockDiffStreamConfig {
+    /// If set to true, the diffs of committed blocks are kept so that they can be streamed to consumers over p2p RPC
+    pub enabled: bool,
+    /// The number of most recently committed blocks for which diffs are kept. A consumer that falls further behind than
+    /// this cannot resume from its cursor.
+    pub retention: u64,
+}
+
+impl Default for CommittedBlockDiffStreamConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention: 1000,
         }
     }
 }
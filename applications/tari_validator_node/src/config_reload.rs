@@ -0,0 +1,215 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Reloading of the validator node config at runtime.
+//!
+//! The reloaded config is diffed against the config currently in effect. Changes to the keys in [RELOADABLE_KEYS] are
+//! applied to the running services, all other changes are reported and only take effect after a restart.
+
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+use log::*;
+use serde_json::Value;
+use tokio::sync::watch;
+
+use crate::config::{ApplicationConfig, DbMaintenanceConfig, ShardGrowthConfig, ValidatorNodeConfig};
+
+const LOG_TARGET: &str = "tari::validator_node::config_reload";
+
+/// The config keys that are applied to the running node when the config is reloaded
+pub const RELOADABLE_KEYS: &[&str] = &[
+    "validator_node.max_log_level",
+    "validator_node.db_maintenance.enabled",
+    "validator_node.db_maintenance.min_interval",
+    "validator_node.db_maintenance.incremental_vacuum",
+    "validator_node.db_maintenance.num_state_store_backups",
+    "validator_node.shard_growth.max_samples",
+    "validator_node.shard_growth.warn_bytes_per_hour",
+];
+
+/// Loads the application config from the source that the node was started with
+pub type ConfigLoader = Box<dyn Fn() -> Result<ApplicationConfig, anyhow::Error> + Send + Sync>;
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReloadResult {
+    /// The changed keys that were applied
+    pub applied: Vec<String>,
+    /// The changed keys that require a restart to take effect
+    pub requires_restart: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct ConfigReloadHandle {
+    inner: Arc<ConfigReloader>,
+}
+
+struct ConfigReloader {
+    loader: Option<ConfigLoader>,
+    /// The config currently in effect
+    current: Mutex<ValidatorNodeConfig>,
+    /// The max log level set by the log config, used if max_log_level is not set
+    default_max_log_level: LevelFilter,
+    db_maintenance: watch::Sender<DbMaintenanceConfig>,
+    shard_growth: watch::Sender<ShardGrowthConfig>,
+}
+
+impl ConfigReloadHandle {
+    pub fn new(
+        loader: Option<ConfigLoader>,
+        config: &ValidatorNodeConfig,
+    ) -> (
+        Self,
+        watch::Receiver<DbMaintenanceConfig>,
+        watch::Receiver<ShardGrowthConfig>,
+    ) {
+        let default_max_log_level = log::max_level();
+        if let Some(level) = config.max_log_level {
+            log::set_max_level(level);
+        }
+        let (db_maintenance, rx_db_maintenance) = watch::channel(config.db_maintenance.clone());
+        let (shard_growth, rx_shard_growth) = watch::channel(config.shard_growth.clone());
+        let handle = Self {
+            inner: Arc::new(ConfigReloader {
+                loader,
+                current: Mutex::new(config.clone()),
+                default_max_log_level,
+                db_maintenance,
+                shard_growth,
+            }),
+        };
+        (handle, rx_db_maintenance, rx_shard_growth)
+    }
+
+    /// Re-reads the config and applies the changed reloadable keys. If the config cannot be loaded, nothing is applied.
+    pub fn reload(&self) -> Result<ConfigReloadResult, anyhow::Error> {
+        let Some(loader) = self.inner.loader.as_ref() else {
+            return Err(anyhow!(
+                "Config reload is not supported, the node was not started from a config file"
+            ));
+        };
+        let config = loader()?;
+        self.apply(config.validator_node)
+    }
+
+    fn apply(&self, new_config: ValidatorNodeConfig) -> Result<ConfigReloadResult, anyhow::Error> {
+        let mut current = self.inner.current.lock().unwrap();
+        let mut changed = vec![];
+        diff_keys(
+            "validator_node",
+            &serde_json::to_value(&*current)?,
+            &serde_json::to_value(&new_config)?,
+            &mut changed,
+        );
+        let (applied, requires_restart) = changed
+            .into_iter()
+            .partition::<Vec<_>, _>(|key| RELOADABLE_KEYS.contains(&key.as_str()));
+
+        if !applied.is_empty() {
+            current.max_log_level = new_config.max_log_level;
+            current.db_maintenance = new_config.db_maintenance;
+            current.shard_growth.max_samples = new_config.shard_growth.max_samples;
+            current.shard_growth.warn_bytes_per_hour = new_config.shard_growth.warn_bytes_per_hour;
+
+            log::set_max_level(current.max_log_level.unwrap_or(self.inner.default_max_log_level));
+            self.inner.db_maintenance.send_replace(current.db_maintenance.clone());
+            self.inner.shard_growth.send_replace(current.shard_growth.clone());
+            info!(target: LOG_TARGET, "🔄 Applied config changes: {}", applied.join(", "));
+        }
+        if !requires_restart.is_empty() {
+            warn!(
+                target: LOG_TARGET,
+                "Config changes to {} require a restart and were not applied",
+                requires_restart.join(", ")
+            );
+        }
+
+        Ok(ConfigReloadResult {
+            applied,
+            requires_restart,
+        })
+    }
+}
+
+/// Appends the dotted keys of all leaf values that differ between `old` and `new`
+fn diff_keys(prefix: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                diff_keys(
+                    &format!("{}.{}", prefix, key),
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changed,
+                );
+            }
+        },
+        (old, new) if old != new => changed.push(prefix.to_string()),
+        _ => {},
+    }
+}
+
+#[cfg(unix)]
+pub async fn reload_on_sighup(
+    handle: ConfigReloadHandle,
+    mut shutdown: tari_shutdown::ShutdownSignal,
+) -> Result<(), anyhow::Error> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    loop {
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            Some(_) = hangup.recv() => {
+                info!(target: LOG_TARGET, "Received SIGHUP, reloading config");
+                if let Err(err) = handle.reload() {
+                    error!(target: LOG_TARGET, "Failed to reload config: {}", err);
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn it_applies_reloadable_keys_and_reports_the_rest() {
+        let config = ValidatorNodeConfig::default();
+        let (handle, rx_db_maintenance, _rx_shard_growth) = ConfigReloadHandle::new(None, &config);
+
+        let mut new_config = config.clone();
+        new_config.max_log_level = Some(LevelFilter::Trace);
+        new_config.db_maintenance.min_interval = Duration::from_secs(10);
+        new_config.no_fees = !config.no_fees;
+        let result = handle.apply(new_config).unwrap();
+
+        assert_eq!(result.applied, vec![
+            "validator_node.db_maintenance.min_interval",
+            "validator_node.max_log_level"
+        ]);
+        assert_eq!(result.requires_restart, vec!["validator_node.no_fees"]);
+        assert_eq!(log::max_level(), LevelFilter::Trace);
+        assert_eq!(rx_db_maintenance.borrow().min_interval, Duration::from_secs(10));
+
+        // The restart-required change is not in effect, so it is reported again
+        let mut new_config = config.clone();
+        new_config.no_fees = !config.no_fees;
+        let result = handle.apply(new_config).unwrap();
+        assert_eq!(result.applied, vec![
+            "validator_node.db_maintenance.min_interval",
+            "validator_node.max_log_level"
+        ]);
+        assert_eq!(result.requires_restart, vec!["validator_node.no_fees"]);
+        assert_eq!(log::max_level(), LevelFilter::Off);
+    }
+}
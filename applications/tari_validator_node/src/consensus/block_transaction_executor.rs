@@ -4,8 +4,8 @@
 use indexmap::IndexMap;
 use log::info;
 use tari_consensus::{
-    hotstuff::substate_store::PendingSubstateStore,
-    traits::{BlockTransactionExecutor, BlockTransactionExecutorError, ReadableSubstateStore},
+    hotstuff::substate_store::{PendingSubstateStore, SubstateSnapshotView},
+    traits::{BlockTransactionExecutor, BlockTransactionExecutorError, InputSubstateStore},
 };
 use tari_dan_app_utilities::transaction_executor::TransactionExecutor;
 use tari_dan_common_types::optional::Optional;
@@ -34,17 +34,38 @@ impl<TEpochManager, TExecutor, TStateStore> BlockTransactionExecutor<TStateStore
     for TariDanBlockTransactionExecutor<TEpochManager, TExecutor>
 where
     TStateStore: StateStore,
-    TExecutor: TransactionExecutor,
+    TEpochManager: Sync,
+    TExecutor: TransactionExecutor + Sync,
 {
     fn execute(
         &self,
         transaction: Transaction,
         store: &PendingSubstateStore<TStateStore>,
     ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
-        let id: tari_transaction::TransactionId = *transaction.id();
-
         // Get the latest input substates
-        let inputs = self.resolve_substates::<TStateStore>(&transaction, store)?;
+        let inputs = self.resolve_substates(&transaction, store)?;
+        self.execute_with_inputs(transaction, inputs)
+    }
+
+    fn execute_with_snapshot(
+        &self,
+        transaction: Transaction,
+        snapshot: &SubstateSnapshotView<'_>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        let inputs = self.resolve_substates(&transaction, snapshot)?;
+        self.execute_with_inputs(transaction, inputs)
+    }
+}
+
+impl<TEpochManager, TExecutor> TariDanBlockTransactionExecutor<TEpochManager, TExecutor>
+where TExecutor: TransactionExecutor
+{
+    fn execute_with_inputs(
+        &self,
+        transaction: Transaction,
+        inputs: IndexMap<VersionedSubstateId, Substate>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        let id = *transaction.id();
         info!(target: LOG_TARGET, "Transaction {} executing. Inputs: {:?}", id, inputs);
 
         // Create a memory db with all the input substates, needed for the transaction execution
@@ -54,7 +75,8 @@ where
         // TODO: create the virtual substates for execution
         let virtual_substates = VirtualSubstates::new();
 
-        // Execute the transaction and get the result
+        // Execute the transaction and get the result. A transaction that exceeds the execution budget is rejected, so
+        // that it is aborted and execution continues with the next transaction.
         let exec_output = self
             .executor
             .execute(transaction, state_db, virtual_substates)
@@ -84,10 +106,10 @@ impl<TEpochManager, TExecutor> TariDanBlockTransactionExecutor<TEpochManager, TE
         }
     }
 
-    fn resolve_substates<TStateStore: StateStore>(
+    fn resolve_substates<TStore: InputSubstateStore>(
         &self,
         transaction: &Transaction,
-        store: &PendingSubstateStore<TStateStore>,
+        store: &TStore,
     ) -> Result<IndexMap<VersionedSubstateId, Substate>, BlockTransactionExecutorError> {
         let mut resolved_substates = IndexMap::with_capacity(transaction.num_unique_inputs());
 
@@ -100,7 +122,7 @@ impl<TEpochManager, TExecutor> TariDanBlockTransactionExecutor<TEpochManager, TE
                     resolved_substates.insert(id, substate);
                 },
                 None => {
-                    let (id, substate) = self.resolve_local_substate::<TStateStore>(input.substate_id, store)?;
+                    let (id, substate) = self.resolve_local_substate(input.substate_id, store)?;
                     info!(target: LOG_TARGET, "Resolved unversioned substate: {id}");
                     resolved_substates.insert(id, substate);
                 },
@@ -112,10 +134,10 @@ impl<TEpochManager, TExecutor> TariDanBlockTransactionExecutor<TEpochManager, TE
         Ok(resolved_substates)
     }
 
-    fn resolve_local_substate<TStateStore: StateStore>(
+    fn resolve_local_substate<TStore: InputSubstateStore>(
         &self,
         id: SubstateId,
-        store: &PendingSubstateStore<TStateStore>,
+        store: &TStore,
     ) -> Result<(VersionedSubstateId, Substate), BlockTransactionExecutorError> {
         let substate = store.get_latest(&id).optional()?.ok_or_else(|| {
             BlockTransactionExecutorError::UnableToResolveSubstateId {
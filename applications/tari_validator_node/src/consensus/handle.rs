@@ -1,25 +1,44 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_consensus::hotstuff::{ConsensusCurrentState, HotstuffEvent};
+use tari_consensus::{
+    hotstuff::{
+        ConsensusCurrentState,
+        ConsensusStatus,
+        ConsensusStatusHandle,
+        HotStuffError,
+        HotstuffEvent,
+        MaintenanceMode,
+    },
+    traits::SyncProgress,
+};
 use tokio::sync::{broadcast, watch};
 
-use crate::event_subscription::EventSubscription;
+use crate::{consensus::spec::TariConsensusSpec, event_subscription::EventSubscription};
 
 #[derive(Debug, Clone)]
 pub struct ConsensusHandle {
     rx_current_state: watch::Receiver<ConsensusCurrentState>,
+    rx_sync_progress: watch::Receiver<Option<SyncProgress>>,
     events_subscription: EventSubscription<HotstuffEvent>,
+    maintenance_mode: MaintenanceMode,
+    status: ConsensusStatusHandle<TariConsensusSpec>,
 }
 
 impl ConsensusHandle {
     pub(super) fn new(
         rx_current_state: watch::Receiver<ConsensusCurrentState>,
+        rx_sync_progress: watch::Receiver<Option<SyncProgress>>,
         events_subscription: EventSubscription<HotstuffEvent>,
+        maintenance_mode: MaintenanceMode,
+        status: ConsensusStatusHandle<TariConsensusSpec>,
     ) -> Self {
         Self {
             rx_current_state,
+            rx_sync_progress,
             events_subscription,
+            maintenance_mode,
+            status,
         }
     }
 
@@ -30,4 +49,30 @@ impl ConsensusHandle {
     pub fn get_current_state(&self) -> ConsensusCurrentState {
         *self.rx_current_state.borrow()
     }
+
+    /// Returns a snapshot of the progress of consensus, assembled from the state store and the pacemaker
+    pub async fn get_consensus_status(&self) -> Result<ConsensusStatus, HotStuffError> {
+        self.status.get_status().await
+    }
+
+    /// Returns the progress of the current or most recent block sync, or None if the node has not synced since it
+    /// started
+    pub fn get_sync_progress(&self) -> Option<SyncProgress> {
+        *self.rx_sync_progress.borrow()
+    }
+
+    /// Stops this node from proposing and voting. Blocks continue to be processed so that the node stays in sync.
+    /// Returns false if the node was already in maintenance mode.
+    pub fn enter_maintenance_mode(&self) -> bool {
+        self.maintenance_mode.enter()
+    }
+
+    /// Resumes proposing and voting. Returns false if the node was not in maintenance mode.
+    pub fn resume(&self) -> bool {
+        self.maintenance_mode.resume()
+    }
+
+    pub fn is_in_maintenance_mode(&self) -> bool {
+        self.maintenance_mode.is_enabled()
+    }
 }
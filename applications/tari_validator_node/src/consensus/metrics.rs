@@ -1,13 +1,31 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
-use prometheus::{core::Collector, IntCounter, IntGauge, IntGaugeVec, Opts, Registry};
+use prometheus::{core::Collector, Histogram, HistogramOpts, IntCounter, IntGauge, IntGaugeVec, Opts, Registry};
 use tari_consensus::{hotstuff::HotStuffError, messages::HotstuffMessage, traits::hooks::ConsensusHooks};
-use tari_dan_common_types::{NodeHeight, PeerAddress};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight, PeerAddress};
 use tari_dan_storage::{
-    consensus_models::{Decision, QuorumDecision, TransactionAtom, TransactionPool, ValidBlock},
+    consensus_models::{
+        Block,
+        Decision,
+        ForeignProposalOutboxEntry,
+        ProposerEquivocation,
+        QcTiming,
+        QuorumDecision,
+        TransactionAtom,
+        TransactionPool,
+        TransactionPoolError,
+        ValidBlock,
+        VoteTiming,
+    },
     StateStore,
 };
 use tari_state_store_sqlite::SqliteStateStore;
@@ -22,6 +40,8 @@ pub struct PrometheusConsensusMetrics<S = SqliteStateStore<PeerAddress>> {
     blocks_accepted: IntCounter,
     blocks_rejected: IntCounter,
     blocks_validation_failed: IntCounter,
+    blocks_committed: IntCounter,
+    committed_height: IntGauge,
 
     commands_count: IntGaugeVec,
 
@@ -31,12 +51,38 @@ pub struct PrometheusConsensusMetrics<S = SqliteStateStore<PeerAddress>> {
 
     pacemaker_height: IntGauge,
     pacemaker_leader_failures: IntCounter,
+    leader_timeout_rounds: Histogram,
+    dummy_blocks_created: IntCounter,
     needs_sync: IntCounter,
+    maintenance_mode: IntGauge,
+    load_shedding: IntGauge,
+    proposer_equivocations: IntCounter,
+    verified_qc_cache_hits: IntCounter,
+    verified_qc_cache_misses: IntCounter,
+    banned_peer_messages_dropped: IntCounter,
+    pipelined_proposals_used: IntCounter,
+    pipelined_proposals_discarded: IntCounter,
+    vote_send_retries_succeeded: IntCounter,
+    vote_send_retries_failed: IntCounter,
 
     transactions_pool_size: IntGauge,
     transactions_ready_for_consensus: IntCounter,
     transactions_finalized_committed: IntCounter,
     transactions_finalized_aborted: IntCounter,
+
+    foreign_proposal_outbox_depth: IntGauge,
+    outbound_queue_depth: IntGaugeVec,
+
+    qc_formation_latency: Histogram,
+    vote_lateness: IntGaugeVec,
+    vote_lateness_totals: Arc<Mutex<VoteLatenessTotals>>,
+}
+
+/// Running totals of vote lateness per validator for the current epoch
+#[derive(Debug, Default)]
+struct VoteLatenessTotals {
+    epoch: Option<Epoch>,
+    totals: HashMap<PublicKey, (u64, u64)>,
 }
 
 impl<S: StateStore> PrometheusConsensusMetrics<S> {
@@ -67,6 +113,21 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             pacemaker_leader_failures: IntCounter::new("consensus_leader_failures", "Number of leader failures")
                 .unwrap()
                 .register_at(registry),
+            leader_timeout_rounds: Histogram::with_opts(
+                HistogramOpts::new(
+                    "consensus_leader_timeout_rounds",
+                    "Number of consecutive failed views at each leader timeout",
+                )
+                .buckets(vec![1.0, 2.0, 3.0, 4.0, 5.0, 7.0, 10.0, 15.0, 20.0]),
+            )
+            .unwrap()
+            .register_at(registry),
+            dummy_blocks_created: IntCounter::new(
+                "consensus_dummy_blocks_created",
+                "Number of dummy blocks created for heights where the leader failed to propose",
+            )
+            .unwrap()
+            .register_at(registry),
             blocks_validation_failed: IntCounter::new(
                 "consensus_block_validation_failed",
                 "Number of block validation failures",
@@ -76,9 +137,76 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             blocks_rejected: IntCounter::new("consensus_blocks_rejected", "Number of blocks rejected")
                 .unwrap()
                 .register_at(registry),
+            blocks_committed: IntCounter::new("consensus_blocks_committed", "Number of blocks committed")
+                .unwrap()
+                .register_at(registry),
+            committed_height: IntGauge::new("consensus_committed_height", "Height of the last committed block")
+                .unwrap()
+                .register_at(registry),
             needs_sync: IntCounter::new("consensus_needs_sync", "Number of times consensus needs to sync")
                 .unwrap()
                 .register_at(registry),
+            maintenance_mode: IntGauge::new(
+                "consensus_maintenance_mode",
+                "1 if the validator is in maintenance mode and is not proposing or voting, otherwise 0",
+            )
+            .unwrap()
+            .register_at(registry),
+            load_shedding: IntGauge::new(
+                "state_store_load_shedding",
+                "1 if new transaction submissions are refused because the state store write queue is backed up, \
+                 otherwise 0",
+            )
+            .unwrap()
+            .register_at(registry),
+            proposer_equivocations: IntCounter::new(
+                "consensus_proposer_equivocations",
+                "Number of leaders detected proposing two different blocks for the same height",
+            )
+            .unwrap()
+            .register_at(registry),
+            verified_qc_cache_hits: IntCounter::new(
+                "consensus_verified_qc_cache_hits",
+                "Number of justify QCs that did not need to be verified again because they were cached",
+            )
+            .unwrap()
+            .register_at(registry),
+            verified_qc_cache_misses: IntCounter::new(
+                "consensus_verified_qc_cache_misses",
+                "Number of justify QCs that were verified because they were not cached",
+            )
+            .unwrap()
+            .register_at(registry),
+            banned_peer_messages_dropped: IntCounter::new(
+                "consensus_banned_peer_messages_dropped",
+                "Number of consensus messages dropped because the sending peer is banned for misbehaving",
+            )
+            .unwrap()
+            .register_at(registry),
+            pipelined_proposals_used: IntCounter::new(
+                "consensus_pipelined_proposals_used",
+                "Number of proposals that used transaction executions prepared while the parent block was voted on",
+            )
+            .unwrap()
+            .register_at(registry),
+            pipelined_proposals_discarded: IntCounter::new(
+                "consensus_pipelined_proposals_discarded",
+                "Number of prepared proposals that were discarded because the leaf block changed or preparation failed",
+            )
+            .unwrap()
+            .register_at(registry),
+            vote_send_retries_succeeded: IntCounter::new(
+                "consensus_vote_send_retries_succeeded",
+                "Number of retries that sent a vote to the leader after the first attempt failed",
+            )
+            .unwrap()
+            .register_at(registry),
+            vote_send_retries_failed: IntCounter::new(
+                "consensus_vote_send_retries_failed",
+                "Number of retries of sending a vote to the leader that failed",
+            )
+            .unwrap()
+            .register_at(registry),
             transactions_ready_for_consensus: IntCounter::new(
                 "consensus_transaction_ready_for_consensus",
                 "Number of transactions ready for consensus",
@@ -100,6 +228,37 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             transactions_pool_size: IntGauge::new("consensus_transactions_pool_size", "Number of transactions in pool")
                 .unwrap()
                 .register_at(registry),
+            foreign_proposal_outbox_depth: IntGauge::new(
+                "consensus_foreign_proposal_outbox_depth",
+                "Number of foreign proposals awaiting acknowledgement",
+            )
+            .unwrap()
+            .register_at(registry),
+            outbound_queue_depth: IntGaugeVec::new(
+                Opts::new(
+                    "consensus_outbound_queue_depth",
+                    "Number of consensus messages waiting to be sent to a peer",
+                ),
+                &["peer"],
+            )
+            .unwrap()
+            .register_at(registry),
+            qc_formation_latency: Histogram::with_opts(HistogramOpts::new(
+                "consensus_qc_formation_latency",
+                "Time in seconds from receiving a proposal to receiving a quorum of votes for it",
+            ))
+            .unwrap()
+            .register_at(registry),
+            vote_lateness: IntGaugeVec::new(
+                Opts::new(
+                    "consensus_vote_lateness_ms",
+                    "Average time in milliseconds for a validator's votes to arrive in the current epoch",
+                ),
+                &["validator"],
+            )
+            .unwrap()
+            .register_at(registry),
+            vote_lateness_totals: Arc::new(Mutex::new(VoteLatenessTotals::default())),
         }
     }
 
@@ -164,20 +323,30 @@ impl<S: StateStore> ConsensusHooks for PrometheusConsensusMetrics<S> {
         self.pacemaker_height.set(height.as_u64() as i64);
     }
 
-    fn on_leader_timeout(&mut self, _new_height: NodeHeight) {
-        self.pacemaker_leader_failures.inc()
+    fn on_leader_timeout(&mut self, _new_height: NodeHeight, round: u32) {
+        self.pacemaker_leader_failures.inc();
+        self.leader_timeout_rounds.observe(f64::from(round));
+    }
+
+    fn on_dummy_block_created(&mut self, _height: NodeHeight) {
+        self.dummy_blocks_created.inc();
     }
 
     fn on_beat(&mut self) {
-        let Some(count) = self
+        let Some((count, outbox_depth)) = self
             .state_store
-            .with_read_tx(|tx| TransactionPool::<S>::new().count(tx))
+            .with_read_tx(|tx| {
+                let count = TransactionPool::<S>::new().count(tx)?;
+                let outbox_depth = ForeignProposalOutboxEntry::count(tx)?;
+                Ok::<_, TransactionPoolError>((count, outbox_depth))
+            })
             .ok()
         else {
             return;
         };
 
         self.transactions_pool_size.set(count as i64);
+        self.foreign_proposal_outbox_depth.set(outbox_depth as i64);
     }
 
     fn on_needs_sync(&mut self, _local_height: NodeHeight, _remote_qc_height: NodeHeight) {
@@ -199,4 +368,74 @@ impl<S: StateStore> ConsensusHooks for PrometheusConsensusMetrics<S> {
             Decision::Deferred => {},
         }
     }
+
+    fn on_block_committed(&mut self, block: &Block) {
+        self.blocks_committed.inc();
+        self.committed_height.set(block.height().as_u64() as i64);
+    }
+
+    fn on_vote_received(&mut self, epoch: Epoch, vote: &VoteTiming) {
+        let mut lateness = self.vote_lateness_totals.lock().unwrap();
+        if lateness.epoch != Some(epoch) {
+            lateness.epoch = Some(epoch);
+            lateness.totals.clear();
+            self.vote_lateness.reset();
+        }
+        let (num_votes, total_ms) = lateness.totals.entry(vote.public_key.clone()).or_default();
+        *num_votes += 1;
+        *total_ms += vote.arrived_after_ms;
+        self.vote_lateness
+            .with_label(&vote.public_key)
+            .set((*total_ms / *num_votes) as i64);
+    }
+
+    fn on_qc_formed(&mut self, timing: &QcTiming) {
+        if let Some(ms) = timing.quorum_reached_ms {
+            self.qc_formation_latency.observe(ms as f64 / 1000.0);
+        }
+    }
+
+    fn on_maintenance_mode_changed(&mut self, is_enabled: bool) {
+        self.maintenance_mode.set(i64::from(is_enabled));
+    }
+
+    fn on_proposer_equivocation(&mut self, _equivocation: &ProposerEquivocation) {
+        self.proposer_equivocations.inc();
+    }
+
+    fn on_load_shedding_changed(&mut self, is_shedding: bool) {
+        self.load_shedding.set(i64::from(is_shedding));
+    }
+
+    fn on_verified_qc_cache_lookup(&mut self, is_hit: bool) {
+        if is_hit {
+            self.verified_qc_cache_hits.inc();
+        } else {
+            self.verified_qc_cache_misses.inc();
+        }
+    }
+
+    fn on_outbound_queue_depth_changed<TAddr: Display>(&mut self, peer: &TAddr, depth: usize) {
+        self.outbound_queue_depth.with_label(peer).set(depth as i64);
+    }
+
+    fn on_banned_peer_message_dropped<TAddr: Display>(&mut self, _peer: &TAddr, _message: &HotstuffMessage) {
+        self.banned_peer_messages_dropped.inc();
+    }
+
+    fn on_pipelined_proposal(&mut self, is_used: bool) {
+        if is_used {
+            self.pipelined_proposals_used.inc();
+        } else {
+            self.pipelined_proposals_discarded.inc();
+        }
+    }
+
+    fn on_vote_send_retried(&mut self, is_sent: bool) {
+        if is_sent {
+            self.vote_send_retries_succeeded.inc();
+        } else {
+            self.vote_send_retries_failed.inc();
+        }
+    }
 }
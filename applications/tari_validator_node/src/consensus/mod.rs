@@ -2,8 +2,22 @@
 //    SPDX-License-Identifier: BSD-3-Clause
 
 use tari_common::configuration::Network;
-use tari_consensus::hotstuff::{ConsensusWorker, ConsensusWorkerContext, HotstuffConfig, HotstuffWorker};
-use tari_dan_storage::consensus_models::TransactionPool;
+use tari_consensus::{
+    hotstuff::{
+        ConsensusWorker,
+        ConsensusWorkerContext,
+        DeferredExecutionConfig,
+        HotstuffConfig,
+        HotstuffWorker,
+        MaintenanceMode,
+        PacemakerConfig,
+        PeerBanConfig,
+        VoteRetryConfig,
+    },
+    journal::JournalConfig,
+    traits::SystemClock,
+};
+use tari_dan_storage::consensus_models::{GenesisConfig, TransactionPool, TransactionPoolOrdering};
 use tari_epoch_manager::base_layer::EpochManagerHandle;
 use tari_shutdown::ShutdownSignal;
 use tari_state_store_sqlite::SqliteStateStore;
@@ -48,12 +62,13 @@ use crate::p2p::services::messaging::{ConsensusInboundMessaging, ConsensusOutbou
 
 pub async fn spawn(
     network: Network,
+    genesis: GenesisConfig,
     store: SqliteStateStore<PeerAddress>,
     keypair: RistrettoKeypair,
     epoch_manager: EpochManagerHandle<PeerAddress>,
     rx_new_transactions: mpsc::Receiver<(TransactionId, usize)>,
     inbound_messaging: ConsensusInboundMessaging<SqliteMessageLogger>,
-    outbound_messaging: ConsensusOutboundMessaging<SqliteMessageLogger>,
+    outbound_messaging: ConsensusOutboundMessaging<SqliteMessageLogger, <TariConsensusSpec as ConsensusSpec>::Hooks>,
     client_factory: TariValidatorNodeRpcClientFactory,
     hooks: <TariConsensusSpec as ConsensusSpec>::Hooks,
     shutdown_signal: ShutdownSignal,
@@ -62,6 +77,9 @@ pub async fn spawn(
         TariDanTransactionProcessor<TemplateManager<PeerAddress>>,
     >,
     consensus_constants: ConsensusConstants,
+    maintenance_mode: bool,
+    journal: Option<JournalConfig>,
+    committed_block_diff_retention: Option<u64>,
 ) -> (
     JoinHandle<Result<(), anyhow::Error>>,
     ConsensusHandle,
@@ -72,8 +90,11 @@ pub async fn spawn(
     let validator_addr = PeerAddress::from(keypair.public_key().clone());
     let signing_service = TariSignatureService::new(keypair);
     let leader_strategy = RoundRobinLeaderStrategy::new();
-    let transaction_pool = TransactionPool::new();
-    let (tx_hotstuff_events, _) = broadcast::channel(100);
+    let transaction_pool = TransactionPool::with_ordering(TransactionPoolOrdering::FeePriority)
+        .with_exclude_conflicting(consensus_constants.exclude_conflicting_transactions);
+    // A Decided event is published for each transaction in a committed block, so this allows for a few full blocks
+    let (tx_hotstuff_events, _) = broadcast::channel(1000);
+    let maintenance_mode = MaintenanceMode::new(maintenance_mode);
 
     let hotstuff_worker = HotstuffWorker::<TariConsensusSpec>::new(
         validator_addr,
@@ -84,24 +105,67 @@ pub async fn spawn(
         store.clone(),
         epoch_manager.clone(),
         leader_strategy,
-        signing_service,
+        signing_service.clone(),
         transaction_pool,
         transaction_executor,
         tx_hotstuff_events.clone(),
         tx_mempool,
         hooks,
+        SystemClock,
+        maintenance_mode.clone(),
         shutdown_signal.clone(),
         HotstuffConfig {
             max_base_layer_blocks_behind: consensus_constants.max_base_layer_blocks_behind,
             max_base_layer_blocks_ahead: consensus_constants.max_base_layer_blocks_ahead,
+            genesis,
+            journal,
+            committed_block_diff_retention,
+            foreign_proposal_timeout: consensus_constants.foreign_proposal_timeout,
+            max_dummy_blocks: consensus_constants.max_dummy_blocks,
+            max_block_size_bytes: consensus_constants.max_block_size_bytes,
+            max_block_transactions: consensus_constants.max_block_transactions,
+            block_rejection_retention_epochs: consensus_constants.block_rejection_retention_epochs,
+            foreign_proposal_retention_epochs: consensus_constants.foreign_proposal_retention_epochs,
+            reject_state_merkle_root_mismatch: consensus_constants.reject_state_merkle_root_mismatch,
+            verified_qc_cache_size: consensus_constants.verified_qc_cache_size,
+            enable_proposal_pipelining: consensus_constants.enable_proposal_pipelining,
+            pacemaker: PacemakerConfig {
+                base_timeout: consensus_constants.pacemaker_base_timeout,
+                timeout_multiplier: consensus_constants.pacemaker_timeout_multiplier,
+                max_timeout: consensus_constants.pacemaker_max_timeout,
+            },
+            deferred_execution: DeferredExecutionConfig {
+                max_attempts: consensus_constants.max_deferred_execution_attempts,
+                max_age: consensus_constants.max_deferred_execution_age,
+            },
+            peer_bans: PeerBanConfig {
+                ban_threshold: consensus_constants.peer_ban_threshold,
+                ban_duration: consensus_constants.peer_ban_duration,
+                score_decay_interval: consensus_constants.peer_score_decay_interval,
+            },
+            vote_retry: VoteRetryConfig {
+                max_retries: consensus_constants.max_vote_send_retries,
+                initial_backoff: consensus_constants.vote_send_retry_backoff,
+            },
         },
     );
 
     let (tx_current_state, rx_current_state) = watch::channel(Default::default());
+    let state_sync = RpcStateSyncManager::new(
+        network,
+        genesis,
+        epoch_manager.clone(),
+        store,
+        leader_strategy,
+        signing_service,
+        client_factory,
+    );
+    let rx_sync_progress = state_sync.subscribe_to_progress();
+    let status = hotstuff_worker.status_handle();
     let context = ConsensusWorkerContext {
-        epoch_manager: epoch_manager.clone(),
+        epoch_manager,
         hotstuff: hotstuff_worker,
-        state_sync: RpcStateSyncManager::new(network, epoch_manager, store, leader_strategy, client_factory),
+        state_sync,
         tx_current_state,
     };
 
@@ -109,7 +173,13 @@ pub async fn spawn(
 
     (
         handle,
-        ConsensusHandle::new(rx_current_state, EventSubscription::new(tx_hotstuff_events)),
+        ConsensusHandle::new(
+            rx_current_state,
+            rx_sync_progress,
+            EventSubscription::new(tx_hotstuff_events),
+            maintenance_mode,
+            status,
+        ),
         rx_mempool,
     )
 }
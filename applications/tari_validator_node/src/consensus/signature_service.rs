@@ -5,7 +5,13 @@ use rand::rngs::OsRng;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_consensus::traits::{ValidatorSignatureService, VoteSignatureService};
 use tari_dan_app_utilities::keypair::RistrettoKeypair;
-use tari_dan_storage::consensus_models::{BlockId, QuorumDecision, ValidatorSchnorrSignature, ValidatorSignature};
+use tari_dan_storage::consensus_models::{
+    verify_validator_signatures_batch,
+    BlockId,
+    QuorumDecision,
+    ValidatorSchnorrSignature,
+    ValidatorSignature,
+};
 
 #[derive(Debug, Clone)]
 pub struct TariSignatureService {
@@ -39,4 +45,8 @@ impl VoteSignatureService for TariSignatureService {
         let challenge = self.create_challenge(leaf_hash, block_id, decision);
         signature.verify(challenge)
     }
+
+    fn verify_batch(&self, challenges_and_signatures: &[(FixedHash, &ValidatorSignature)]) -> bool {
+        verify_validator_signatures_batch(challenges_and_signatures)
+    }
 }
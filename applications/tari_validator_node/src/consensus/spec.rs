@@ -4,7 +4,7 @@
 use sqlite_message_logger::SqliteMessageLogger;
 #[cfg(not(feature = "metrics"))]
 use tari_consensus::traits::hooks::NoopHooks;
-use tari_consensus::traits::ConsensusSpec;
+use tari_consensus::traits::{ConsensusSpec, SystemClock};
 use tari_dan_app_utilities::{
     template_manager::implementation::TemplateManager,
     transaction_executor::TariDanTransactionProcessor,
@@ -30,6 +30,7 @@ pub struct TariConsensusSpec;
 
 impl ConsensusSpec for TariConsensusSpec {
     type Addr = PeerAddress;
+    type Clock = SystemClock;
     type EpochManager = EpochManagerHandle<Self::Addr>;
     #[cfg(not(feature = "metrics"))]
     type Hooks = NoopHooks;
@@ -37,7 +38,7 @@ impl ConsensusSpec for TariConsensusSpec {
     type Hooks = PrometheusConsensusMetrics;
     type InboundMessaging = ConsensusInboundMessaging<SqliteMessageLogger>;
     type LeaderStrategy = RoundRobinLeaderStrategy;
-    type OutboundMessaging = ConsensusOutboundMessaging<SqliteMessageLogger>;
+    type OutboundMessaging = ConsensusOutboundMessaging<SqliteMessageLogger, Self::Hooks>;
     type SignatureService = TariSignatureService;
     type StateStore = SqliteStateStore<Self::Addr>;
     type SyncManager = RpcStateSyncManager<Self>;
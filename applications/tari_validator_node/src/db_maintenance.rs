@@ -0,0 +1,228 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant, SystemTime},
+};
+
+use log::*;
+use tari_dan_common_types::PeerAddress;
+use tari_dan_storage::global::GlobalDb;
+use tari_dan_storage_sqlite::global::SqliteGlobalDbAdapter;
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerEvent, EpochManagerReader};
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tokio::{
+    sync::watch,
+    task::{self, JoinHandle},
+};
+
+use crate::config::DbMaintenanceConfig;
+
+const LOG_TARGET: &str = "tari::validator_node::db_maintenance";
+
+#[derive(Debug, Clone, Copy)]
+pub struct MaintenanceRun {
+    pub completed_at: SystemTime,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DbMaintenanceStats {
+    pub state_store: Option<MaintenanceRun>,
+    pub global_db: Option<MaintenanceRun>,
+    pub state_store_backup: Option<MaintenanceRun>,
+}
+
+/// Shared view of the most recent maintenance runs
+#[derive(Debug, Clone, Default)]
+pub struct DbMaintenanceStatus {
+    stats: Arc<Mutex<DbMaintenanceStats>>,
+}
+
+impl DbMaintenanceStatus {
+    // The stats are plain values that are always left in a valid state, so a poisoned mutex is recovered
+    pub fn get(&self) -> DbMaintenanceStats {
+        *self.stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn update<F: FnOnce(&mut DbMaintenanceStats)>(&self, f: F) {
+        f(&mut self.stats.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+}
+
+pub fn spawn(
+    config: watch::Receiver<DbMaintenanceConfig>,
+    state_store: SqliteStateStore<PeerAddress>,
+    state_store_backup_dir: PathBuf,
+    global_db: GlobalDb<SqliteGlobalDbAdapter<PeerAddress>>,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    status: DbMaintenanceStatus,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<Result<(), anyhow::Error>> {
+    task::spawn(async move {
+        DbMaintenance {
+            config,
+            state_store,
+            state_store_backup_dir,
+            global_db,
+            status,
+            last_run: None,
+        }
+        .run(epoch_manager, shutdown)
+        .await
+    })
+}
+
+struct DbMaintenance {
+    config: watch::Receiver<DbMaintenanceConfig>,
+    state_store: SqliteStateStore<PeerAddress>,
+    state_store_backup_dir: PathBuf,
+    global_db: GlobalDb<SqliteGlobalDbAdapter<PeerAddress>>,
+    status: DbMaintenanceStatus,
+    last_run: Option<Instant>,
+}
+
+impl DbMaintenance {
+    async fn run(
+        mut self,
+        epoch_manager: EpochManagerHandle<PeerAddress>,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), anyhow::Error> {
+        // The config may be reloaded at runtime, so maintenance may be enabled later
+        if !self.config.borrow().enabled {
+            info!(target: LOG_TARGET, "Database maintenance is disabled");
+        }
+
+        let mut epoch_events = epoch_manager.subscribe().await?;
+        loop {
+            tokio::select! {
+                _ = shutdown.wait() => break,
+                Ok(event) = epoch_events.recv() => {
+                    // Epoch changes are a natural low-activity window, consensus is waiting for the new committee
+                    if let EpochManagerEvent::EpochChanged(epoch) = event {
+                        debug!(target: LOG_TARGET, "Epoch changed to {}, checking if maintenance is due", epoch);
+                        self.run_maintenance_if_due().await;
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs maintenance if it is enabled and the minimum interval has elapsed since the last successful run. If any
+    /// step is skipped or fails, maintenance is attempted again on the next epoch change.
+    async fn run_maintenance_if_due(&mut self) {
+        let config = self.config.borrow().clone();
+        let is_due = self
+            .last_run
+            .map_or(true, |last_run| last_run.elapsed() >= config.min_interval);
+        if !config.enabled || !is_due {
+            return;
+        }
+        let started_at = Instant::now();
+
+        let incremental_vacuum = config.incremental_vacuum;
+        let state_store = self.state_store.clone();
+        let result = task::spawn_blocking(move || state_store.maintenance(incremental_vacuum)).await;
+        let is_state_store_done = match result {
+            Ok(Ok(Some(duration))) => {
+                info!(target: LOG_TARGET, "🧹 State store maintenance completed in {:.2?}", duration);
+                self.status.update(|stats| {
+                    stats.state_store = Some(MaintenanceRun {
+                        completed_at: SystemTime::now(),
+                        duration,
+                    })
+                });
+                true
+            },
+            Ok(Ok(None)) => {
+                info!(target: LOG_TARGET, "State store busy, skipping maintenance");
+                false
+            },
+            Ok(Err(err)) => {
+                error!(target: LOG_TARGET, "State store maintenance failed: {}", err);
+                false
+            },
+            Err(err) => {
+                error!(target: LOG_TARGET, "State store maintenance task panicked: {}", err);
+                false
+            },
+        };
+
+        let global_db = self.global_db.clone();
+        let result = task::spawn_blocking(move || global_db.adapter().maintenance(incremental_vacuum)).await;
+        let is_global_db_done = match result {
+            Ok(Ok(Some(duration))) => {
+                info!(target: LOG_TARGET, "🧹 Global db maintenance completed in {:.2?}", duration);
+                self.status.update(|stats| {
+                    stats.global_db = Some(MaintenanceRun {
+                        completed_at: SystemTime::now(),
+                        duration,
+                    })
+                });
+                true
+            },
+            Ok(Ok(None)) => {
+                info!(target: LOG_TARGET, "Global db busy, skipping maintenance");
+                false
+            },
+            Ok(Err(err)) => {
+                error!(target: LOG_TARGET, "Global db maintenance failed: {}", err);
+                false
+            },
+            Err(err) => {
+                error!(target: LOG_TARGET, "Global db maintenance task panicked: {}", err);
+                false
+            },
+        };
+
+        let is_backup_done =
+            config.num_state_store_backups == 0 || self.backup_state_store(config.num_state_store_backups).await;
+
+        if is_state_store_done && is_global_db_done && is_backup_done {
+            self.last_run = Some(started_at);
+        } else {
+            info!(
+                target: LOG_TARGET,
+                "Database maintenance did not complete, retrying on the next epoch change"
+            );
+        }
+    }
+
+    /// Returns true if the backup was created
+    async fn backup_state_store(&mut self, keep: usize) -> bool {
+        let backup_dir = self.state_store_backup_dir.clone();
+        let state_store = self.state_store.clone();
+        let timer = Instant::now();
+        match task::spawn_blocking(move || state_store.create_backup(backup_dir, keep)).await {
+            Ok(Ok(path)) => {
+                let duration = timer.elapsed();
+                info!(
+                    target: LOG_TARGET,
+                    "💾 State store backed up to {} in {:.2?}",
+                    path.display(),
+                    duration
+                );
+                self.status.update(|stats| {
+                    stats.state_store_backup = Some(MaintenanceRun {
+                        completed_at: SystemTime::now(),
+                        duration,
+                    })
+                });
+                true
+            },
+            Ok(Err(err)) => {
+                error!(target: LOG_TARGET, "State store backup failed: {}", err);
+                false
+            },
+            Err(err) => {
+                error!(target: LOG_TARGET, "State store backup task panicked: {}", err);
+                false
+            },
+        }
+    }
+}
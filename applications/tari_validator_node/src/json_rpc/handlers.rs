@@ -20,6 +20,8 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::{collections::HashSet, time::SystemTime};
+
 use axum_jrpc::{
     error::{JsonRpcError, JsonRpcErrorReason},
     JrpcResult,
@@ -30,75 +32,152 @@ use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
 use log::*;
 use serde_json::{self as json, json};
 use tari_base_node_client::{grpc::GrpcBaseNodeClient, BaseNodeClient};
-use tari_dan_app_utilities::{keypair::RistrettoKeypair, template_manager::interface::TemplateManagerHandle};
+use tari_consensus::{
+    hotstuff::HotstuffEvent,
+    traits::{Clock, SystemClock},
+};
+use tari_dan_app_utilities::{
+    json_encoding::decode_substate_value,
+    keypair::RistrettoKeypair,
+    template_manager::interface::{TemplateManagerError, TemplateManagerHandle},
+};
 use tari_dan_common_types::{optional::Optional, public_key_to_peer_id, PeerAddress, SubstateAddress};
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::{
-    consensus_models::{Block, ExecutedTransaction, LeafBlock, QuorumDecision, SubstateRecord, TransactionRecord},
-    Ordering,
+    consensus_models::{
+        Block,
+        BlockRejection,
+        EquivocationEvidence,
+        ExecutedTransaction,
+        LeaderFailureStats,
+        LeafBlock,
+        NftOwnership,
+        PeerMisbehavior,
+        ProposerEquivocation,
+        QcTiming,
+        QuorumDecision,
+        RecentTransaction,
+        RecentTransactionFilter,
+        ShardGrowthSample,
+        SubstateRecord,
+        SyncCheckpoint,
+        TransactionRecord,
+    },
     StateStore,
     StateStoreReadTransaction,
+    StorageError,
 };
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
 use tari_networking::{is_supported_multiaddr, NetworkingHandle, NetworkingService};
 use tari_state_store_sqlite::SqliteStateStore;
-use tari_validator_node_client::types::{
-    self,
-    AddPeerRequest,
-    AddPeerResponse,
-    ConnectionDirection,
-    DryRunTransactionFinalizeResult,
-    GetAllVnsRequest,
-    GetAllVnsResponse,
-    GetBlockRequest,
-    GetBlockResponse,
-    GetBlocksCountResponse,
-    GetBlocksRequest,
-    GetBlocksResponse,
-    GetCommitteeRequest,
-    GetCommitteeResponse,
-    GetCommsStatsResponse,
-    GetConnectionsResponse,
-    GetEpochManagerStatsResponse,
-    GetFilteredBlocksCountRequest,
-    GetIdentityResponse,
-    GetMempoolStatsResponse,
-    GetRecentTransactionsResponse,
-    GetShardKeyRequest,
-    GetShardKeyResponse,
-    GetStateRequest,
-    GetStateResponse,
-    GetSubstateRequest,
-    GetSubstateResponse,
-    GetSubstatesByTransactionRequest,
-    GetSubstatesByTransactionResponse,
-    GetTemplateRequest,
-    GetTemplateResponse,
-    GetTemplatesRequest,
-    GetTemplatesResponse,
-    GetTransactionRequest,
-    GetTransactionResponse,
-    GetTransactionResultRequest,
-    GetTransactionResultResponse,
-    GetValidatorFeesRequest,
-    GetValidatorFeesResponse,
-    ListBlocksRequest,
-    ListBlocksResponse,
-    SubmitTransactionRequest,
-    SubmitTransactionResponse,
-    SubstateStatus,
-    TemplateMetadata,
+use tari_validator_node_client::{
+    types::{
+        self,
+        AddPeerRequest,
+        AddPeerResponse,
+        ConnectionDirection,
+        DbMaintenanceInfo,
+        DryRunTransactionFinalizeResult,
+        GetAllVnsRequest,
+        GetAllVnsResponse,
+        GetBlockRejectionsRequest,
+        GetBlockRejectionsResponse,
+        GetBlockRequest,
+        GetBlockResponse,
+        GetBlocksAfterRequest,
+        GetBlocksAfterResponse,
+        GetBlocksCountResponse,
+        GetBlocksRequest,
+        GetBlocksResponse,
+        GetCommitCertificateRequest,
+        GetCommitCertificateResponse,
+        GetCommitteeRequest,
+        GetCommitteeResponse,
+        GetCommsStatsResponse,
+        GetConnectionsResponse,
+        GetConsensusStatusResponse,
+        GetDbStatsResponse,
+        GetEpochManagerStatsResponse,
+        GetEquivocationEvidenceResponse,
+        GetFilteredBlocksCountRequest,
+        GetIdentityResponse,
+        GetMempoolStatsResponse,
+        GetMisbehavingPeersResponse,
+        GetNftOwnerRequest,
+        GetNftOwnerResponse,
+        GetNftsByOwnerRequest,
+        GetNftsByOwnerResponse,
+        GetQcTimingsRequest,
+        GetQcTimingsResponse,
+        GetRecentTransactionsRequest,
+        GetRecentTransactionsResponse,
+        GetShardGrowthRequest,
+        GetShardGrowthResponse,
+        GetShardKeyRequest,
+        GetShardKeyResponse,
+        GetStateRequest,
+        GetStateResponse,
+        GetSubstateDecodedRequest,
+        GetSubstateDecodedResponse,
+        GetSubstateRequest,
+        GetSubstateResponse,
+        GetSubstatesByTransactionRequest,
+        GetSubstatesByTransactionResponse,
+        GetSyncStatusResponse,
+        GetTemplateRequest,
+        GetTemplateResponse,
+        GetTemplatesRequest,
+        GetTemplatesResponse,
+        GetTransactionDagRequest,
+        GetTransactionDagResponse,
+        GetTransactionRequest,
+        GetTransactionResponse,
+        GetTransactionResultRequest,
+        GetTransactionResultResponse,
+        GetTransactionsAfterRequest,
+        GetTransactionsAfterResponse,
+        GetValidatorFeesRequest,
+        GetValidatorFeesResponse,
+        ListBlocksRequest,
+        ListBlocksResponse,
+        MaintenanceModeResponse,
+        MisbehavingPeer,
+        ReloadConfigResponse,
+        SubmitTransactionRequest,
+        SubmitTransactionResponse,
+        SubstateStatus,
+        TemplateMetadata,
+        TransactionDagNode,
+        UnbanPeerRequest,
+        UnbanPeerResponse,
+        UploadTemplateBeginRequest,
+        UploadTemplateBeginResponse,
+        UploadTemplateChunkRequest,
+        UploadTemplateChunkResponse,
+        UploadTemplateFinishRequest,
+        UploadTemplateFinishResponse,
+    },
+    CommitCertificate,
+    CommitteeMemberKey,
 };
+use tokio::sync::broadcast;
 
 use crate::{
+    config_reload::ConfigReloadHandle,
+    consensus::ConsensusHandle,
+    db_maintenance::{DbMaintenanceStatus, MaintenanceRun},
     dry_run_transaction_processor::DryRunTransactionProcessor,
     json_rpc::jrpc_errors::{internal_error, not_found},
     p2p::services::mempool::MempoolHandle,
+    shard_growth::ShardGrowthStatus,
     Services,
 };
 
 const LOG_TARGET: &str = "tari::validator_node::json_rpc::handlers";
 
+/// The maximum number of blocks in the chain returned by `get_commit_certificate`
+const MAX_COMMIT_CHAIN_LENGTH: usize = 100;
+
 pub struct JsonRpcHandlers {
     keypair: RistrettoKeypair,
     mempool: MempoolHandle,
@@ -108,6 +187,10 @@ pub struct JsonRpcHandlers {
     base_node_client: GrpcBaseNodeClient,
     state_store: SqliteStateStore<PeerAddress>,
     dry_run_transaction_processor: DryRunTransactionProcessor,
+    db_maintenance_status: DbMaintenanceStatus,
+    shard_growth_status: ShardGrowthStatus,
+    consensus_handle: ConsensusHandle,
+    config_reload: ConfigReloadHandle,
 }
 
 impl JsonRpcHandlers {
@@ -121,6 +204,10 @@ impl JsonRpcHandlers {
             base_node_client,
             state_store: services.state_store.clone(),
             dry_run_transaction_processor: services.dry_run_transaction_processor.clone(),
+            db_maintenance_status: services.db_maintenance_status.clone(),
+            shard_growth_status: services.shard_growth_status.clone(),
+            consensus_handle: services.consensus_handle.clone(),
+            config_reload: services.config_reload.clone(),
         }
     }
 
@@ -249,23 +336,41 @@ impl JsonRpcHandlers {
 
     pub async fn get_recent_transactions(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
+        // No params is equivalent to the default request, i.e. the 1000 most recent transactions
+        let req = if value.parsed.is_null() {
+            GetRecentTransactionsRequest::default()
+        } else {
+            value.parse_params::<GetRecentTransactionsRequest>()?
+        };
+        let filter = RecentTransactionFilter {
+            decision: req.decision,
+            from_epoch: req.from_epoch,
+            to_epoch: req.to_epoch,
+            involved_substate_id: req.involved_substate_id,
+        };
         let tx = self.state_store.create_read_tx().map_err(internal_error(answer_id))?;
-        match TransactionRecord::get_paginated(&tx, 1000, 0, Some(Ordering::Descending)) {
-            Ok(recent_transactions) => {
-                let res = GetRecentTransactionsResponse {
-                    transactions: recent_transactions.into_iter().map(|t| t.transaction).collect(),
-                };
-                Ok(JsonRpcResponse::success(answer_id, res))
-            },
-            Err(e) => Err(JsonRpcResponse::error(
-                answer_id,
-                JsonRpcError::new(
-                    JsonRpcErrorReason::InvalidParams,
-                    format!("Something went wrong: {}", e),
-                    json::Value::Null,
-                ),
-            )),
-        }
+        let (transactions, next_cursor) =
+            RecentTransaction::get_filtered(&tx, &filter, req.cursor.as_ref(), req.limit.unwrap_or(1000))
+                .map_err(internal_error(answer_id))?;
+        let res = GetRecentTransactionsResponse {
+            transactions,
+            next_cursor,
+        };
+        Ok(JsonRpcResponse::success(answer_id, res))
+    }
+
+    pub async fn get_transactions_after(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetTransactionsAfterRequest = value.parse_params()?;
+        let tx = self.state_store.create_read_tx().map_err(internal_error(answer_id))?;
+        let (transactions, next_cursor) =
+            TransactionRecord::get_after(&tx, req.cursor.as_ref(), req.limit, req.ordering)
+                .map_err(internal_error(answer_id))?;
+        let res = GetTransactionsAfterResponse {
+            transactions: transactions.into_iter().map(|t| t.transaction).collect(),
+            next_cursor,
+        };
+        Ok(JsonRpcResponse::success(answer_id, res))
     }
 
     pub async fn list_blocks(&self, value: JsonRpcExtractor) -> JrpcResult {
@@ -302,6 +407,41 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
+    pub async fn get_transaction_dag(&self, value: JsonRpcExtractor) -> JrpcResult {
+        const DEFAULT_EDGE_LIMIT: usize = 1000;
+
+        let answer_id = value.get_answer_id();
+        let request: GetTransactionDagRequest = value.parse_params()?;
+
+        let (pool, edges) = self
+            .state_store
+            .with_read_tx(|tx| {
+                let pool = tx.transaction_pool_get_all()?;
+                let edges = tx.transaction_pool_get_conflict_edges(
+                    request.substate_id.as_ref(),
+                    request.limit.unwrap_or(DEFAULT_EDGE_LIMIT),
+                )?;
+                Ok::<_, StorageError>((pool, edges))
+            })
+            .map_err(internal_error(answer_id))?;
+
+        let nodes = pool
+            .into_iter()
+            .filter(|rec| request.substate_id.is_none() || edges.iter().any(|e| e.involves(rec.transaction_id())))
+            .map(|rec| TransactionDagNode {
+                transaction_id: *rec.transaction_id(),
+                stage: rec.current_stage(),
+                decision: rec.current_decision(),
+                is_ready: rec.is_ready(),
+            })
+            .collect();
+
+        Ok(JsonRpcResponse::success(answer_id, GetTransactionDagResponse {
+            nodes,
+            edges,
+        }))
+    }
+
     pub async fn get_transaction_result(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request: GetTransactionResultRequest = value.parse_params()?;
@@ -367,6 +507,62 @@ impl JsonRpcHandlers {
         }
     }
 
+    pub async fn get_substate_decoded(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let data: GetSubstateDecodedRequest = value.parse_params()?;
+
+        let maybe_substate = self
+            .state_store
+            .with_read_tx(|tx| {
+                let address = SubstateAddress::from_substate_id(&data.address, data.version);
+                SubstateRecord::get(tx, &address).optional()
+            })
+            .map_err(internal_error(answer_id))?;
+
+        let substate = match maybe_substate {
+            Some(substate) if substate.is_destroyed() => {
+                return Ok(JsonRpcResponse::success(answer_id, GetSubstateDecodedResponse {
+                    status: SubstateStatus::Down,
+                    created_by_tx: Some(substate.created_by_transaction),
+                    value: None,
+                }));
+            },
+            Some(substate) => substate,
+            None => {
+                return Ok(JsonRpcResponse::success(answer_id, GetSubstateDecodedResponse {
+                    status: SubstateStatus::DoesNotExist,
+                    created_by_tx: None,
+                    value: None,
+                }));
+            },
+        };
+
+        let created_by_tx = substate.created_by_transaction;
+        let substate_value = substate.into_substate_value();
+        let schema = match substate_value.component() {
+            Some(component) => self
+                .template_manager
+                .get_component_schema(component.template_address)
+                .await
+                .unwrap_or_else(|err| {
+                    // The value is still returned as raw JSON
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to get component schema for template {}: {}", component.template_address, err
+                    );
+                    None
+                }),
+            None => None,
+        };
+        let decoded = decode_substate_value(&substate_value, schema.as_ref()).map_err(internal_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetSubstateDecodedResponse {
+            status: SubstateStatus::Up,
+            created_by_tx: Some(created_by_tx),
+            value: Some(decoded),
+        }))
+    }
+
     pub async fn get_substates_created_by_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let data: GetSubstatesByTransactionRequest = value.parse_params()?;
@@ -393,6 +589,35 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_nfts_by_owner(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetNftsByOwnerRequest = value.parse_params()?;
+        let nfts = self
+            .state_store
+            .with_read_tx(|tx| {
+                NftOwnership::get_by_owner(
+                    tx,
+                    &req.component_address,
+                    req.limit.unwrap_or(usize::MAX),
+                    req.offset.unwrap_or(0),
+                )
+            })
+            .map_err(internal_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetNftsByOwnerResponse { nfts }))
+    }
+
+    pub async fn get_nft_owner(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetNftOwnerRequest = value.parse_params()?;
+        let ownership = self
+            .state_store
+            .with_read_tx(|tx| NftOwnership::get(tx, &req.resource_address, &req.nft_id).optional())
+            .map_err(internal_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetNftOwnerResponse { ownership }))
+    }
+
     pub async fn get_block(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let data: GetBlockRequest = value.parse_params()?;
@@ -413,6 +638,56 @@ impl JsonRpcHandlers {
         }
     }
 
+    pub async fn get_commit_certificate(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let GetCommitCertificateRequest { block_id } = value.parse_params()?;
+        let Some((blocks, commit_qc)) = self
+            .state_store
+            .with_read_tx(|tx| {
+                let block = Block::get(tx, &block_id)?;
+                block.get_commit_chain(tx, MAX_COMMIT_CHAIN_LENGTH)
+            })
+            .optional()
+            .map_err(internal_error(answer_id))?
+        else {
+            return Err(not_found(
+                answer_id,
+                format!("No commit certificate found for block {}", block_id),
+            ));
+        };
+
+        let epoch = commit_qc.epoch();
+        let committees = self
+            .epoch_manager
+            .get_committees_by_shards(epoch, HashSet::from([commit_qc.shard()]))
+            .await
+            .map_err(internal_error(answer_id))?;
+        let members = committees
+            .into_values()
+            .flat_map(|committee| committee.members)
+            .map(|(_, public_key)| (epoch, public_key))
+            .collect();
+        let validators = self
+            .epoch_manager
+            .get_many_validator_nodes(members)
+            .await
+            .map_err(internal_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetCommitCertificateResponse {
+            certificate: CommitCertificate {
+                blocks,
+                commit_qc,
+                committee: validators
+                    .into_values()
+                    .map(|vn| CommitteeMemberKey {
+                        public_key: vn.public_key,
+                        shard_key: vn.shard_key,
+                    })
+                    .collect(),
+            },
+        }))
+    }
+
     pub async fn get_blocks_count(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let tx = self.state_store.create_read_tx().map_err(internal_error(answer_id))?;
@@ -443,6 +718,44 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
+    pub async fn get_qc_timings(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetQcTimingsRequest = value.parse_params()?;
+        let timings = self
+            .state_store
+            .with_read_tx(|tx| QcTiming::get_by_epoch(tx, req.epoch))
+            .map_err(internal_error(answer_id))?;
+        let validators = QcTiming::summarize_by_validator(&timings);
+        let res = GetQcTimingsResponse { timings, validators };
+        Ok(JsonRpcResponse::success(answer_id, res))
+    }
+
+    pub async fn get_block_rejections(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetBlockRejectionsRequest = value.parse_params()?;
+        let (rejections, total) = self
+            .state_store
+            .with_read_tx(|tx| {
+                let rejections = BlockRejection::get_paginated(tx, req.limit, req.offset)?;
+                let total = BlockRejection::count(tx)?;
+                Ok::<_, StorageError>((rejections, total))
+            })
+            .map_err(internal_error(answer_id))?;
+        let res = GetBlockRejectionsResponse { rejections, total };
+        Ok(JsonRpcResponse::success(answer_id, res))
+    }
+
+    pub async fn get_equivocation_evidence(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let evidence = self
+            .state_store
+            .with_read_tx(|tx| EquivocationEvidence::get_all(tx))
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(answer_id, GetEquivocationEvidenceResponse {
+            evidence,
+        }))
+    }
+
     pub async fn get_blocks(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let req: GetBlocksRequest = value.parse_params()?;
@@ -463,6 +776,17 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
+    pub async fn get_blocks_after(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetBlocksAfterRequest = value.parse_params()?;
+        let (blocks, next_cursor) = self
+            .state_store
+            .with_read_tx(|tx| tx.blocks_get_after(req.cursor.as_ref(), req.limit, req.ordering))
+            .map_err(internal_error(answer_id))?;
+        let res = GetBlocksAfterResponse { blocks, next_cursor };
+        Ok(JsonRpcResponse::success(answer_id, res))
+    }
+
     pub async fn get_templates(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let req: GetTemplatesRequest = value.parse_params()?;
@@ -515,6 +839,46 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn upload_template_begin(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: UploadTemplateBeginRequest = value.parse_params()?;
+
+        let upload_id = self
+            .template_manager
+            .begin_template_upload(req.template_name, req.total_size, req.expected_hash)
+            .await
+            .map_err(template_upload_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, UploadTemplateBeginResponse { upload_id }))
+    }
+
+    pub async fn upload_template_chunk(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: UploadTemplateChunkRequest = value.parse_params()?;
+
+        self.template_manager
+            .upload_template_chunk(req.upload_id, req.offset, req.data)
+            .await
+            .map_err(template_upload_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, UploadTemplateChunkResponse {}))
+    }
+
+    pub async fn upload_template_finish(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: UploadTemplateFinishRequest = value.parse_params()?;
+
+        let template_address = self
+            .template_manager
+            .finish_template_upload(req.upload_id)
+            .await
+            .map_err(template_upload_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, UploadTemplateFinishResponse {
+            template_address,
+        }))
+    }
+
     pub async fn get_connections(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let active_connections = self
@@ -545,6 +909,42 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_misbehaving_peers(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let misbehaviors = self
+            .state_store
+            .with_read_tx(|tx| PeerMisbehavior::get_all(tx))
+            .map_err(internal_error(answer_id))?;
+        let now = SystemClock.now();
+        let peers = misbehaviors
+            .into_iter()
+            .map(|m| MisbehavingPeer {
+                is_banned: m.is_banned(now),
+                address: m.address,
+                score: m.score,
+                last_offence: m.last_offence,
+                reason: m.reason,
+                banned_until: m.banned_until,
+            })
+            .collect();
+        Ok(JsonRpcResponse::success(answer_id, GetMisbehavingPeersResponse {
+            peers,
+        }))
+    }
+
+    pub async fn unban_peer(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: UnbanPeerRequest = value.parse_params()?;
+        let is_removed = self
+            .state_store
+            .with_write_tx(|tx| PeerMisbehavior::remove(tx, &req.address))
+            .map_err(internal_error(answer_id))?;
+        if is_removed {
+            info!(target: LOG_TARGET, "Peer {} was unbanned", req.address);
+        }
+        Ok(JsonRpcResponse::success(answer_id, UnbanPeerResponse { is_removed }))
+    }
+
     pub async fn get_mempool_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let size = self.mempool.get_mempool_size().await.map_err(|err| {
@@ -561,6 +961,149 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, GetMempoolStatsResponse { size }))
     }
 
+    pub async fn get_db_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let stats = self.db_maintenance_status.get();
+
+        fn to_info(run: Option<MaintenanceRun>) -> DbMaintenanceInfo {
+            run.map(|run| DbMaintenanceInfo {
+                last_maintenance_at: run
+                    .completed_at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_secs()),
+                last_maintenance_duration_ms: Some(run.duration.as_millis() as u64),
+            })
+            .unwrap_or_default()
+        }
+
+        Ok(JsonRpcResponse::success(answer_id, GetDbStatsResponse {
+            state_store: to_info(stats.state_store),
+            global_db: to_info(stats.global_db),
+            state_store_backup: to_info(stats.state_store_backup),
+        }))
+    }
+
+    pub async fn get_shard_growth(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetShardGrowthRequest = value.parse_params()?;
+        let Some(latest) = self.shard_growth_status.latest() else {
+            return Ok(JsonRpcResponse::success(answer_id, GetShardGrowthResponse {
+                samples: vec![],
+            }));
+        };
+        let samples = self
+            .state_store
+            .with_read_tx(|tx| ShardGrowthSample::get_recent(tx, latest.shard, req.limit.unwrap_or(usize::MAX)))
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(answer_id, GetShardGrowthResponse { samples }))
+    }
+
+    pub fn subscribe_to_consensus_events(&self) -> broadcast::Receiver<HotstuffEvent> {
+        self.consensus_handle.clone().subscribe_to_hotstuff_events()
+    }
+
+    pub async fn get_consensus_status(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let status = self
+            .consensus_handle
+            .get_consensus_status()
+            .await
+            .map_err(internal_error(answer_id))?;
+        let (proposer_equivocation_count, leader_failures) = self
+            .state_store
+            .with_read_tx(|tx| {
+                Ok::<_, StorageError>((
+                    ProposerEquivocation::count(tx)?,
+                    LeaderFailureStats::get(tx, status.epoch)?,
+                ))
+            })
+            .map_err(internal_error(answer_id))?;
+        let write_queue = self.state_store.write_queue().stats();
+        Ok(JsonRpcResponse::success(answer_id, GetConsensusStatusResponse {
+            state: format!("{:?}", self.consensus_handle.get_current_state()),
+            is_in_maintenance_mode: self.consensus_handle.is_in_maintenance_mode(),
+            epoch: status.epoch,
+            leaf_block_id: status.leaf_block_id,
+            leaf_block_height: status.leaf_block_height,
+            locked_block_id: status.locked_block_id,
+            locked_block_height: status.locked_block_height,
+            high_qc_block_id: status.high_qc_block_id,
+            high_qc_block_height: status.high_qc_block_height,
+            last_voted_height: status.last_voted_height,
+            current_view_height: status.current_view_height,
+            current_view_timeout_ms: status.current_view_timeout.as_millis() as u64,
+            is_leader: status.is_leader,
+            transaction_pool_stage_counts: status
+                .transaction_pool_stage_counts
+                .into_iter()
+                .map(|(stage, count)| (stage.to_string(), count))
+                .collect(),
+            proposer_equivocation_count,
+            dummy_block_count: leader_failures.dummy_block_count,
+            leader_timeouts_last_epoch: leader_failures.leader_timeouts_last_epoch,
+            is_shedding_load: write_queue.is_shedding_load,
+            write_queue_last_wait_ms: write_queue.last_wait.as_millis() as u64,
+            write_queue_max_wait_ms: write_queue.max_wait.as_millis() as u64,
+        }))
+    }
+
+    pub async fn get_sync_status(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let checkpoint = self
+            .state_store
+            .with_read_tx(|tx| SyncCheckpoint::get(tx).optional())
+            .map_err(internal_error(answer_id))?;
+        let progress = self.consensus_handle.get_sync_progress();
+        Ok(JsonRpcResponse::success(answer_id, GetSyncStatusResponse {
+            checkpoint,
+            blocks_synced: progress.map_or(0, |p| p.blocks_synced),
+            estimated_remaining: progress.map(|p| p.estimated_remaining),
+        }))
+    }
+
+    pub async fn enter_maintenance_mode(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let changed = self.consensus_handle.enter_maintenance_mode();
+        if changed {
+            info!(target: LOG_TARGET, "🔧 Entering maintenance mode");
+        }
+        Ok(JsonRpcResponse::success(answer_id, MaintenanceModeResponse {
+            is_in_maintenance_mode: true,
+            changed,
+        }))
+    }
+
+    pub async fn resume(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let changed = self.consensus_handle.resume();
+        if changed {
+            info!(target: LOG_TARGET, "🔧 Resuming from maintenance mode");
+        }
+        Ok(JsonRpcResponse::success(answer_id, MaintenanceModeResponse {
+            is_in_maintenance_mode: false,
+            changed,
+        }))
+    }
+
+    pub async fn reload_config(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let result = self.config_reload.reload().map_err(|e| {
+            JsonRpcResponse::error(
+                answer_id,
+                JsonRpcError::new(
+                    JsonRpcErrorReason::InternalError,
+                    format!("Failed to reload config: {}", e),
+                    json::Value::Null,
+                ),
+            )
+        })?;
+        Ok(JsonRpcResponse::success(answer_id, ReloadConfigResponse {
+            applied: result.applied,
+            requires_restart: result.requires_restart,
+        }))
+    }
+
     pub async fn get_epoch_manager_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let current_epoch = self.epoch_manager.current_epoch().await.map_err(|e| {
@@ -748,3 +1291,22 @@ impl JsonRpcHandlers {
         }))
     }
 }
+
+fn template_upload_error(answer_id: i64) -> impl Fn(TemplateManagerError) -> JsonRpcResponse {
+    move |err| {
+        if err.is_not_found_error() {
+            return not_found(answer_id, err.to_string());
+        }
+        match err {
+            TemplateManagerError::InvalidTemplateUploadSize { .. } |
+            TemplateManagerError::InvalidTemplateUploadChunk { .. } |
+            TemplateManagerError::TemplateUploadIncomplete { .. } |
+            TemplateManagerError::TemplateUploadHashMismatch { .. } |
+            TemplateManagerError::TemplateLoaderError(_) => JsonRpcResponse::error(
+                answer_id,
+                JsonRpcError::new(JsonRpcErrorReason::InvalidParams, err.to_string(), json::Value::Null),
+            ),
+            err => internal_error(answer_id)(err),
+        }
+    }
+}
@@ -22,9 +22,16 @@
 
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::{extract::Extension, routing::post, Router};
+use axum::{
+    extract::Extension,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Router,
+};
 use axum_jrpc::{JrpcResult, JsonRpcAnswer, JsonRpcExtractor};
+use futures::{stream, Stream};
 use log::*;
+use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
 
 use super::handlers::JsonRpcHandlers;
@@ -38,7 +45,8 @@ pub fn spawn_json_rpc(
 ) -> Result<SocketAddr, anyhow::Error> {
     let router = Router::new()
         .route("/", post(handler))
-        .route("/json_rpc", post(handler));
+        .route("/json_rpc", post(handler))
+        .route("/events", get(consensus_events_handler));
     #[cfg(feature = "metrics")]
     let router = router.route("/_metrics", axum::routing::get(metrics::MetricsHandler(registry)));
     let router = router
@@ -68,26 +76,46 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         // "get_transaction_status" => handlers.get_transaction_status(value).await,
         "submit_transaction" => handlers.submit_transaction(value).await,
         "get_recent_transactions" => handlers.get_recent_transactions(value).await,
+        "get_transactions_after" => handlers.get_transactions_after(value).await,
         "get_transaction" => handlers.get_transaction(value).await,
         "get_transaction_result" => handlers.get_transaction_result(value).await,
         "get_state" => handlers.get_state(value).await,
         "get_substate" => handlers.get_substate(value).await,
+        "get_substate_decoded" => handlers.get_substate_decoded(value).await,
         "get_substates_created_by_transaction" => handlers.get_substates_created_by_transaction(value).await,
         "get_substates_destroyed_by_transaction" => handlers.get_substates_destroyed_by_transaction(value).await,
+        "get_nfts_by_owner" => handlers.get_nfts_by_owner(value).await,
+        "get_nft_owner" => handlers.get_nft_owner(value).await,
         "list_blocks" => handlers.list_blocks(value).await,
         "get_tx_pool" => handlers.get_tx_pool(value).await,
+        "get_transaction_dag" => handlers.get_transaction_dag(value).await,
         // Blocks
         "get_block" => handlers.get_block(value).await,
+        "get_commit_certificate" => handlers.get_commit_certificate(value).await,
         "get_blocks_count" => handlers.get_blocks_count(value).await,
         "get_blocks" => handlers.get_blocks(value).await,
+        "get_blocks_after" => handlers.get_blocks_after(value).await,
         "get_filtered_blocks_count" => handlers.get_filtered_blocks_count(value).await,
+        "get_qc_timings" => handlers.get_qc_timings(value).await,
+        "get_block_rejections" => handlers.get_block_rejections(value).await,
+        "get_equivocation_evidence" => handlers.get_equivocation_evidence(value).await,
         // Template
         "get_template" => handlers.get_template(value).await,
         "get_templates" => handlers.get_templates(value).await,
+        "templates.upload_begin" => handlers.upload_template_begin(value).await,
+        "templates.upload_chunk" => handlers.upload_template_chunk(value).await,
+        "templates.upload_finish" => handlers.upload_template_finish(value).await,
         // Validator Node
         "get_identity" => handlers.get_identity(value).await,
         "get_mempool_stats" => handlers.get_mempool_stats(value).await,
         "get_epoch_manager_stats" => handlers.get_epoch_manager_stats(value).await,
+        "get_db_stats" => handlers.get_db_stats(value).await,
+        "get_shard_growth" => handlers.get_shard_growth(value).await,
+        "get_consensus_status" => handlers.get_consensus_status(value).await,
+        "get_sync_status" => handlers.get_sync_status(value).await,
+        "maintenance" => handlers.enter_maintenance_mode(value).await,
+        "resume" => handlers.resume(value).await,
+        "config.reload" => handlers.reload_config(value).await,
         "get_shard_key" => handlers.get_shard_key(value).await,
         "get_committee" => handlers.get_committee(value).await,
         "get_all_vns" => handlers.get_all_vns(value).await,
@@ -97,6 +125,8 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         "add_peer" => handlers.add_peer(value).await,
         "get_comms_stats" => handlers.get_comms_stats(value).await,
         "get_connections" => handlers.get_connections(value).await,
+        "get_misbehaving_peers" => handlers.get_misbehaving_peers(value).await,
+        "unban_peer" => handlers.unban_peer(value).await,
         method => Ok(value.method_not_found(method)),
     };
 
@@ -117,6 +147,25 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
     result
 }
 
+/// Streams consensus events to the client as server-sent events. Each event is the JSON-serialized `HotstuffEvent`.
+async fn consensus_events_handler(
+    Extension(handlers): Extension<Arc<JsonRpcHandlers>>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = handlers.subscribe_to_consensus_events();
+    let events = stream::unfold(events, |mut events| async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => return Some((Event::default().json_data(&event), events)),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!(target: LOG_TARGET, "🌐 Consensus event stream lagged. {} event(s) were dropped", n);
+                },
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
 #[cfg(feature = "metrics")]
 mod metrics {
     use std::future;
@@ -23,8 +23,10 @@
 mod bootstrap;
 pub mod cli;
 mod config;
+mod config_reload;
 mod consensus;
 mod dan_node;
+mod db_maintenance;
 mod dry_run_transaction_processor;
 mod event_subscription;
 mod http_ui;
@@ -32,8 +34,14 @@ mod json_rpc;
 #[cfg(feature = "metrics")]
 mod metrics;
 mod p2p;
+mod shard_growth;
+#[cfg(feature = "metrics")]
+mod shard_growth_metrics;
 mod substate_resolver;
+#[cfg(feature = "metrics")]
+mod template_cache_metrics;
 mod virtual_substate;
+mod write_queue_watchdog;
 
 mod validator_registration_file;
 use std::{fs, io, process};
@@ -46,14 +54,17 @@ use tari_common::{
     exit_codes::{ExitCode, ExitError},
 };
 use tari_dan_app_utilities::{consensus_constants::ConsensusConstants, keypair::setup_keypair_prompt};
-use tari_dan_common_types::SubstateAddress;
+use tari_dan_common_types::{NodeHeight, SubstateAddress};
 use tari_dan_storage::global::DbFactory;
 use tari_dan_storage_sqlite::SqliteDbFactory;
 use tari_shutdown::ShutdownSignal;
 use tokio::task;
 pub use validator_registration_file::ValidatorRegistrationFile;
 
-pub use crate::config::{ApplicationConfig, ValidatorNodeConfig};
+pub use crate::{
+    config::{ApplicationConfig, ValidatorNodeConfig},
+    config_reload::ConfigLoader,
+};
 use crate::{
     bootstrap::{spawn_services, Services},
     dan_node::DanNode,
@@ -87,8 +98,10 @@ pub struct ShardKey {
     substate_address: Option<SubstateAddress>,
 }
 
+/// Runs the validator node until shutdown. If a `config_loader` is given, the config can be reloaded at runtime.
 pub async fn run_validator_node(
     config: &ApplicationConfig,
+    config_loader: Option<ConfigLoader>,
     shutdown_signal: ShutdownSignal,
 ) -> Result<(), anyhow::Error> {
     let keypair = setup_keypair_prompt(
@@ -113,14 +126,23 @@ pub async fn run_validator_node(
     #[cfg(feature = "metrics")]
     let metrics_registry = create_metrics_registry(keypair.public_key());
 
+    let mut consensus_constants = ConsensusConstants::for_network(config.network);
+    if let Some(timeout) = config.validator_node.foreign_proposal_timeout {
+        consensus_constants.foreign_proposal_timeout = NodeHeight(timeout);
+    }
+    if let Some(max_block_size_bytes) = config.validator_node.max_block_size_bytes {
+        consensus_constants.max_block_size_bytes = max_block_size_bytes;
+    }
+
     let base_node_client = create_base_layer_client(config).await?;
     let services = spawn_services(
         config,
         shutdown_signal.clone(),
         keypair.clone(),
         global_db,
-        ConsensusConstants::devnet(), // TODO: change this eventually
+        consensus_constants,
         base_node_client.clone(),
+        config_loader,
         #[cfg(feature = "metrics")]
         &metrics_registry,
     )
@@ -32,7 +32,7 @@ use tari_common::{
 };
 use tari_dan_app_utilities::configuration::load_configuration;
 use tari_shutdown::Shutdown;
-use tari_validator_node::{cli::Cli, run_validator_node, ApplicationConfig};
+use tari_validator_node::{cli::Cli, run_validator_node, ApplicationConfig, ConfigLoader};
 
 const LOG_TARGET: &str = "tari::validator_node::app";
 
@@ -66,7 +66,7 @@ async fn main() {
 async fn main_inner() -> Result<(), ExitError> {
     let cli = Cli::parse();
     let config_path = cli.common.config_path();
-    let cfg = load_configuration(config_path, true, &cli).map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+    let cfg = load_configuration(&config_path, true, &cli).map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
     let config = ApplicationConfig::load_from(&cfg)?;
 
     // Remove the pid file if it exists
@@ -80,8 +80,14 @@ async fn main_inner() -> Result<(), ExitError> {
         eprintln!("{}", e);
     }
 
+    // Re-reads the config file with the same command line overrides when the config is reloaded
+    let config_loader: ConfigLoader = Box::new(move || {
+        let cfg = load_configuration(&config_path, false, &cli)?;
+        Ok(ApplicationConfig::load_from(&cfg)?)
+    });
+
     info!(target: LOG_TARGET, "Starting validator node on network {}", config.network);
-    match run_validator_node(&config, shutdown.to_signal()).await {
+    match run_validator_node(&config, Some(config_loader), shutdown.to_signal()).await {
         Ok(_) => info!(target: LOG_TARGET, "Validator node shutdown successfully"),
         Err(e) => {
             error!(target: LOG_TARGET, "Validator node shutdown with an error: {:?}", e);
@@ -0,0 +1,106 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_consensus::hotstuff::HotstuffEvent;
+use tari_dan_p2p::proto::rpc as proto;
+use tari_dan_storage::{consensus_models::CommittedBlockDiff, StateStore};
+use tari_rpc_framework::RpcStatus;
+use tokio::sync::{broadcast, mpsc};
+
+const LOG_TARGET: &str = "tari::dan::rpc::committed_block_diff_task";
+
+const BATCH_SIZE: usize = 15;
+
+pub struct CommittedBlockDiffStreamTask<TStateStore: StateStore> {
+    store: TStateStore,
+    after_sequence: Option<u64>,
+    events: broadcast::Receiver<HotstuffEvent>,
+    sender: mpsc::Sender<Result<proto::CommittedBlockDiff, RpcStatus>>,
+}
+
+impl<TStateStore: StateStore> CommittedBlockDiffStreamTask<TStateStore> {
+    pub fn new(
+        store: TStateStore,
+        after_sequence: Option<u64>,
+        events: broadcast::Receiver<HotstuffEvent>,
+        sender: mpsc::Sender<Result<proto::CommittedBlockDiff, RpcStatus>>,
+    ) -> Self {
+        Self {
+            store,
+            after_sequence,
+            events,
+            sender,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), ()> {
+        loop {
+            let diffs = match self
+                .store
+                .with_read_tx(|tx| CommittedBlockDiff::get_after(tx, self.after_sequence, BATCH_SIZE))
+            {
+                Ok(diffs) => diffs,
+                Err(err) => {
+                    self.send(Err(RpcStatus::log_internal_error(LOG_TARGET)(err))).await?;
+                    return Err(());
+                },
+            };
+
+            // The client fell out of the retention window while it was catching up
+            if let Some((after_sequence, first)) = self.after_sequence.zip(diffs.first()) {
+                if first.sequence != after_sequence + 1 {
+                    self.send(Err(RpcStatus::not_found(&format!(
+                        "Committed block diffs after #{} were pruned before they could be streamed",
+                        after_sequence
+                    ))))
+                    .await?;
+                    return Err(());
+                }
+            }
+
+            let is_caught_up = diffs.len() < BATCH_SIZE;
+            for diff in diffs {
+                debug!(target: LOG_TARGET, "Streaming {}", diff);
+                self.after_sequence = Some(diff.sequence);
+                self.send(Ok((&diff).into())).await?;
+            }
+
+            if is_caught_up {
+                self.wait_for_next_commit().await?;
+            }
+        }
+    }
+
+    async fn wait_for_next_commit(&mut self) -> Result<(), ()> {
+        loop {
+            tokio::select! {
+                _ = self.sender.closed() => {
+                    debug!(target: LOG_TARGET, "Peer stream closed by client. Aborting");
+                    return Err(());
+                },
+                event = self.events.recv() => match event {
+                    Ok(HotstuffEvent::BlockCommitted { .. }) => return Ok(()),
+                    Ok(_) => {},
+                    // Missed events are not a problem because the buffer is read from the last streamed sequence
+                    Err(broadcast::error::RecvError::Lagged(_)) => return Ok(()),
+                    Err(broadcast::error::RecvError::Closed) => {
+                        debug!(target: LOG_TARGET, "Consensus has shut down. Ending stream");
+                        return Err(());
+                    },
+                },
+            }
+        }
+    }
+
+    async fn send(&mut self, result: Result<proto::CommittedBlockDiff, RpcStatus>) -> Result<(), ()> {
+        if self.sender.send(result).await.is_err() {
+            debug!(
+                target: LOG_TARGET,
+                "Peer stream closed by client before completing. Aborting"
+            );
+            return Err(());
+        }
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod committed_block_diff_task;
 mod service_impl;
 mod sync_task;
 
@@ -29,16 +30,20 @@ use tari_epoch_manager::base_layer::EpochManagerHandle;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_validator_node_rpc::rpc_service::ValidatorNodeRpcServer;
 
-use crate::{p2p::services::mempool::MempoolHandle, virtual_substate::VirtualSubstateManager};
+use crate::{consensus::ConsensusHandle, p2p::services::mempool::MempoolHandle, virtual_substate::VirtualSubstateManager};
 
 pub fn create_tari_validator_node_rpc_service(
     shard_store_store: SqliteStateStore<PeerAddress>,
     mempool: MempoolHandle,
     virtual_substate_manager: VirtualSubstateManager<SqliteStateStore<PeerAddress>, EpochManagerHandle<PeerAddress>>,
+    consensus_handle: ConsensusHandle,
+    is_committed_block_diff_stream_enabled: bool,
 ) -> ValidatorNodeRpcServer<ValidatorNodeRpcServiceImpl> {
     ValidatorNodeRpcServer::new(ValidatorNodeRpcServiceImpl::new(
         shard_store_store,
         mempool,
         virtual_substate_manager,
+        consensus_handle,
+        is_committed_block_diff_stream_enabled,
     ))
 }
@@ -35,13 +35,24 @@ use tari_dan_p2p::{
         GetTransactionResultRequest,
         GetTransactionResultResponse,
         PayloadResultStatus,
+        StreamCommittedBlockDiffsRequest,
         SubstateStatus,
         SyncBlocksRequest,
         SyncBlocksResponse,
     },
 };
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, HighQc, LockedBlock, QuorumCertificate, SubstateRecord, TransactionRecord},
+    consensus_models::{
+        Block,
+        BlockId,
+        CommittedBlockDiff,
+        CommittedBlockDiffCursor,
+        HighQc,
+        LockedBlock,
+        QuorumCertificate,
+        SubstateRecord,
+        TransactionRecord,
+    },
     StateStore,
 };
 use tari_engine_types::virtual_substate::VirtualSubstateId;
@@ -53,7 +64,11 @@ use tari_validator_node_rpc::rpc_service::ValidatorNodeRpcService;
 use tokio::{sync::mpsc, task};
 
 use crate::{
-    p2p::{rpc::sync_task::BlockSyncTask, services::mempool::MempoolHandle},
+    consensus::ConsensusHandle,
+    p2p::{
+        rpc::{committed_block_diff_task::CommittedBlockDiffStreamTask, sync_task::BlockSyncTask},
+        services::mempool::MempoolHandle,
+    },
     virtual_substate::VirtualSubstateManager,
 };
 
@@ -63,6 +78,8 @@ pub struct ValidatorNodeRpcServiceImpl {
     shard_state_store: SqliteStateStore<PeerAddress>,
     mempool: MempoolHandle,
     virtual_substate_manager: VirtualSubstateManager<SqliteStateStore<PeerAddress>, EpochManagerHandle<PeerAddress>>,
+    consensus_handle: ConsensusHandle,
+    is_committed_block_diff_stream_enabled: bool,
 }
 
 impl ValidatorNodeRpcServiceImpl {
@@ -73,11 +90,15 @@ impl ValidatorNodeRpcServiceImpl {
             SqliteStateStore<PeerAddress>,
             EpochManagerHandle<PeerAddress>,
         >,
+        consensus_handle: ConsensusHandle,
+        is_committed_block_diff_stream_enabled: bool,
     ) -> Self {
         Self {
             shard_state_store,
             mempool,
             virtual_substate_manager,
+            consensus_handle,
+            is_committed_block_diff_stream_enabled,
         }
     }
 }
@@ -302,4 +323,51 @@ impl ValidatorNodeRpcService for ValidatorNodeRpcServiceImpl {
             high_qc: Some((&high_qc).into()),
         }))
     }
+
+    async fn stream_committed_block_diffs(
+        &self,
+        request: Request<StreamCommittedBlockDiffsRequest>,
+    ) -> Result<Streaming<proto::rpc::CommittedBlockDiff>, RpcStatus> {
+        if !self.is_committed_block_diff_stream_enabled {
+            return Err(RpcStatus::not_found(
+                "Committed block diff streaming is not enabled on this node",
+            ));
+        }
+
+        let req = request.into_message();
+        let cursor = req
+            .cursor
+            .map(CommittedBlockDiffCursor::try_from)
+            .transpose()
+            .map_err(|e| RpcStatus::bad_request(&format!("Invalid cursor: {}", e)))?;
+
+        // Subscribe before the buffer is read so that no commit is missed between reading and following
+        let events = self.consensus_handle.clone().subscribe_to_hotstuff_events();
+
+        if let Some(cursor) = cursor {
+            let is_retained = self
+                .shard_state_store
+                .with_read_tx(|tx| CommittedBlockDiff::is_retained(tx, &cursor))
+                .map_err(RpcStatus::log_internal_error(LOG_TARGET))?;
+            if !is_retained {
+                return Err(RpcStatus::not_found(&format!(
+                    "Cursor {} is not within the retention window of this node",
+                    cursor
+                )));
+            }
+        }
+
+        let (sender, receiver) = mpsc::channel(10);
+        task::spawn(
+            CommittedBlockDiffStreamTask::new(
+                self.shard_state_store.clone(),
+                cursor.map(|c| c.sequence),
+                events,
+                sender,
+            )
+            .run(),
+        );
+
+        Ok(Streaming::new(receiver))
+    }
 }
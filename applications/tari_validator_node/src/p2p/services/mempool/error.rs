@@ -75,6 +75,8 @@ pub enum MempoolError {
     InvalidSignature,
     #[error("Network error: {0}")]
     NetworkingError(#[from] NetworkingError),
+    #[error("The node is overloaded and is not accepting new transactions. Try again later")]
+    QueueFull,
 }
 
 impl From<mpsc::error::SendError<MempoolRequest>> for MempoolError {
@@ -20,7 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashSet, fmt, fmt::Display, iter};
+use std::{collections::HashMap, fmt, fmt::Display, iter};
 
 use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
 use log::*;
@@ -95,7 +95,8 @@ impl Display for TransactionExecution {
 
 #[derive(Debug)]
 pub struct MempoolService<TValidator, TExecutedValidator, TExecutor, TSubstateResolver> {
-    transactions: HashSet<TransactionId>,
+    /// The transactions that are being executed by the mempool with their max epoch
+    transactions: HashMap<TransactionId, Option<Epoch>>,
     pending_executions: FuturesUnordered<BoxFuture<'static, MempoolTransactionExecution>>,
     mempool_requests: mpsc::Receiver<MempoolRequest>,
     tx_executed_transactions: mpsc::Sender<(TransactionId, usize)>,
@@ -176,6 +177,7 @@ where
                 }
                 Ok(event) = events.recv() => {
                     if let EpochManagerEvent::EpochChanged(epoch) = event {
+                        self.evict_expired_transactions(epoch);
                         if self.epoch_manager.is_this_validator_registered_for_epoch(epoch).await?{
                             info!(target: LOG_TARGET, "Mempool service subscribing transaction messages for epoch {}", epoch);
                             self.gossip.subscribe(epoch).await?;
@@ -202,6 +204,12 @@ where
                 should_propagate,
                 reply,
             } => {
+                // Consensus writes take priority over new submissions while the state store is backed up
+                if self.state_store.write_queue().is_shedding_load() {
+                    debug!(target: LOG_TARGET, "Refusing transaction {} while shedding load", transaction.id());
+                    let _ignore = reply.send(Err(MempoolError::QueueFull));
+                    return;
+                }
                 handle(
                     reply,
                     self.handle_new_transaction_from_local(*transaction, should_propagate)
@@ -221,13 +229,27 @@ where
     fn remove_transactions(&mut self, ids: &[TransactionId]) -> usize {
         let mut num_found = 0;
         for id in ids {
-            if self.transactions.remove(id) {
+            if self.transactions.remove(id).is_some() {
                 num_found += 1;
             }
         }
         num_found
     }
 
+    /// Removes the transactions whose max epoch has passed. Their execution results are not passed on to consensus.
+    fn evict_expired_transactions(&mut self, epoch: Epoch) {
+        let num_transactions = self.transactions.len();
+        self.transactions
+            .retain(|_, max_epoch| max_epoch.map_or(true, |max_epoch| epoch <= max_epoch));
+        let num_evicted = num_transactions - self.transactions.len();
+        if num_evicted > 0 {
+            info!(
+                target: LOG_TARGET,
+                "🎱 Evicted {} transaction(s) that expired before epoch {}", num_evicted, epoch
+            );
+        }
+    }
+
     async fn handle_new_transaction_from_local(
         &mut self,
         transaction: Transaction,
@@ -378,7 +400,7 @@ where
             let transaction = TransactionRecord::new(transaction);
             self.state_store.with_write_tx(|tx| transaction.insert(tx))?;
             let transaction = transaction.into_transaction();
-            self.transactions.insert(*transaction.id());
+            self.transactions.insert(*transaction.id(), transaction.max_epoch());
 
             self.queue_transaction_for_execution(transaction.clone(), current_epoch, should_propagate, sender_shard);
 
@@ -517,6 +539,14 @@ where
         } = result;
 
         info!(target: LOG_TARGET, "🎱 Transaction {transaction_id} execution: {execution}");
+        if !self.transactions.contains_key(&transaction_id) {
+            info!(
+                target: LOG_TARGET,
+                "🎱 Transaction {transaction_id} was removed from the mempool during execution (e.g. it expired). Ignoring"
+            );
+            return Ok(());
+        }
+
         match execution {
             TransactionExecution::Executed { result } => {
                 self.handle_execution_complete(transaction_id, result, should_propagate, sender_shard)
@@ -758,7 +788,7 @@ where
     }
 
     fn transaction_exists(&self, id: &TransactionId) -> Result<bool, MempoolError> {
-        if self.transactions.contains(id) {
+        if self.transactions.contains_key(id) {
             debug!(
                 target: LOG_TARGET,
                 "🎱 Transaction {} already in mempool",
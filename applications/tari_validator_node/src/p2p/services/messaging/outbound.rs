@@ -24,7 +24,12 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use async_trait::async_trait;
-use tari_consensus::{messages::HotstuffMessage, traits::OutboundMessagingError};
+use log::*;
+use tari_consensus::{
+    messages::HotstuffMessage,
+    outbound_queue::{OutboundQueues, PeerMessageSender},
+    traits::{hooks::ConsensusHooks, OutboundMessagingError},
+};
 use tari_dan_common_types::PeerAddress;
 use tari_dan_p2p::{proto, TariMessagingSpec};
 use tari_networking::{NetworkingHandle, NetworkingService};
@@ -32,34 +37,45 @@ use tokio::sync::mpsc;
 
 use crate::p2p::logging::MessageLogger;
 
-const _LOG_TARGET: &str = "tari::dan::messages::outbound::validator_node";
+const LOG_TARGET: &str = "tari::dan::messages::outbound::validator_node";
+
+/// The maximum number of messages waiting to be sent to a single peer. Older proposals are dropped when the queue is
+/// full.
+const PEER_QUEUE_CAPACITY: usize = 100;
 
 #[derive(Debug, Clone)]
-pub struct ConsensusOutboundMessaging<TMsgLogger> {
+pub struct ConsensusOutboundMessaging<TMsgLogger, THooks> {
     our_node_addr: PeerAddress,
     loopback_sender: mpsc::UnboundedSender<HotstuffMessage>,
-    networking: NetworkingHandle<TariMessagingSpec>,
+    queues: OutboundQueues<PeerAddress, NetworkingPeerSender, THooks>,
     msg_logger: TMsgLogger,
 }
 
-impl<TMsgLogger: MessageLogger> ConsensusOutboundMessaging<TMsgLogger> {
+impl<TMsgLogger, THooks> ConsensusOutboundMessaging<TMsgLogger, THooks>
+where
+    TMsgLogger: MessageLogger,
+    THooks: ConsensusHooks + Clone + Send + 'static,
+{
     pub fn new(
         loopback_sender: mpsc::UnboundedSender<HotstuffMessage>,
         networking: NetworkingHandle<TariMessagingSpec>,
         msg_logger: TMsgLogger,
+        hooks: THooks,
     ) -> Self {
         Self {
             our_node_addr: (*networking.local_peer_id()).into(),
             loopback_sender,
-            networking,
+            queues: OutboundQueues::new(PEER_QUEUE_CAPACITY, NetworkingPeerSender { networking }, hooks),
             msg_logger,
         }
     }
 }
 
 #[async_trait]
-impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
-    for ConsensusOutboundMessaging<TMsgLogger>
+impl<TMsgLogger, THooks> tari_consensus::traits::OutboundMessaging for ConsensusOutboundMessaging<TMsgLogger, THooks>
+where
+    TMsgLogger: MessageLogger + Send,
+    THooks: ConsensusHooks + Clone + Send + 'static,
 {
     type Addr = PeerAddress;
 
@@ -90,15 +106,35 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
         }
 
         let msg = message.into();
+        let msg_type = msg.as_type_str();
+        if !self.try_send(to, msg)? {
+            warn!(
+                target: LOG_TARGET,
+                "Outbound queue for {} is full. {} message not sent.", to, msg_type
+            );
+        }
+
+        Ok(())
+    }
+
+    fn try_send<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+    ) -> Result<bool, OutboundMessagingError> {
+        let msg = message.into();
+        if to == self.our_node_addr {
+            self.loopback_sender
+                .send(msg)
+                .map_err(|_| OutboundMessagingError::FailedToEnqueueMessage {
+                    reason: "loopback sender closed".to_string(),
+                })?;
+            return Ok(true);
+        }
 
         self.msg_logger
             .log_outbound_message("send", &to.to_string(), msg.as_type_str(), "", &msg);
-        self.networking
-            .send_message(to.as_peer_id(), proto::consensus::HotStuffMessage::from(&msg))
-            .await
-            .map_err(OutboundMessagingError::from_error)?;
-
-        Ok(())
+        Ok(self.queues.enqueue(to, msg))
     }
 
     async fn multicast<'a, I, T>(&mut self, committee: I, message: T) -> Result<(), OutboundMessagingError>
@@ -122,19 +158,29 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
             self.send_self(message.clone()).await?;
         }
 
-        for to in &theirs {
+        // Each peer has its own queue so that a slow peer does not hold up the message for the rest of the committee
+        for to in theirs {
             self.msg_logger
                 .log_outbound_message("broadcast", &to.to_string(), message.as_type_str(), "", &message);
+            self.queues.enqueue(*to, message.clone());
         }
 
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NetworkingPeerSender {
+    networking: NetworkingHandle<TariMessagingSpec>,
+}
+
+#[async_trait]
+impl PeerMessageSender<PeerAddress> for NetworkingPeerSender {
+    async fn send_to_peer(&self, to: &PeerAddress, message: HotstuffMessage) -> Result<(), OutboundMessagingError> {
         self.networking
-            .send_multicast(
-                theirs.into_iter().map(|a| a.as_peer_id()).collect::<Vec<_>>(),
-                proto::consensus::HotStuffMessage::from(&message),
-            )
+            .clone()
+            .send_message(to.as_peer_id(), proto::consensus::HotStuffMessage::from(&message))
             .await
-            .map_err(OutboundMessagingError::from_error)?;
-
-        Ok(())
+            .map_err(OutboundMessagingError::from_error)
     }
 }
@@ -0,0 +1,151 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::*;
+use tari_dan_common_types::PeerAddress;
+use tari_dan_storage::{consensus_models::ShardGrowthSample, StateStore};
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tokio::{
+    sync::watch,
+    task::{self, JoinHandle},
+    time::{self, MissedTickBehavior},
+};
+
+use crate::config::ShardGrowthConfig;
+
+const LOG_TARGET: &str = "tari::validator_node::shard_growth";
+
+/// Shared view of the most recent growth sample of this node's shard
+#[derive(Debug, Clone, Default)]
+pub struct ShardGrowthStatus {
+    latest: Arc<Mutex<Option<ShardGrowthSample>>>,
+}
+
+impl ShardGrowthStatus {
+    pub fn latest(&self) -> Option<ShardGrowthSample> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    fn set(&self, sample: ShardGrowthSample) {
+        *self.latest.lock().unwrap() = Some(sample);
+    }
+}
+
+pub fn spawn(
+    config: watch::Receiver<ShardGrowthConfig>,
+    state_store: SqliteStateStore<PeerAddress>,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    status: ShardGrowthStatus,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<Result<(), anyhow::Error>> {
+    task::spawn(async move {
+        ShardGrowthMonitor {
+            config,
+            state_store,
+            epoch_manager,
+            status,
+        }
+        .run(shutdown)
+        .await
+    })
+}
+
+struct ShardGrowthMonitor {
+    /// Only max_samples and warn_bytes_per_hour are reloaded at runtime
+    config: watch::Receiver<ShardGrowthConfig>,
+    state_store: SqliteStateStore<PeerAddress>,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    status: ShardGrowthStatus,
+}
+
+impl ShardGrowthMonitor {
+    async fn run(self, mut shutdown: ShutdownSignal) -> Result<(), anyhow::Error> {
+        let (enabled, sample_interval) = {
+            let config = self.config.borrow();
+            (config.enabled, config.sample_interval)
+        };
+        if !enabled {
+            info!(target: LOG_TARGET, "Shard growth sampling is disabled");
+            return Ok(());
+        }
+
+        let mut interval = time::interval(sample_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = shutdown.wait() => break,
+                _ = interval.tick() => {
+                    if let Err(err) = self.sample().await {
+                        warn!(target: LOG_TARGET, "Failed to sample shard growth: {}", err);
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn sample(&self) -> Result<(), anyhow::Error> {
+        let epoch = self.epoch_manager.current_epoch().await?;
+        if !self.epoch_manager.is_this_validator_registered_for_epoch(epoch).await? {
+            debug!(
+                target: LOG_TARGET,
+                "Not registered for epoch {}, skipping shard growth sample", epoch
+            );
+            return Ok(());
+        }
+        let committee_info = self.epoch_manager.get_local_committee_info(epoch).await?;
+        let sampled_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let state_store = self.state_store.clone();
+        let (max_samples, threshold) = {
+            let config = self.config.borrow();
+            (config.max_samples, config.warn_bytes_per_hour)
+        };
+        let sample = task::spawn_blocking(move || {
+            state_store.with_write_tx(|tx| {
+                ShardGrowthSample::record(
+                    tx,
+                    epoch,
+                    committee_info.shard(),
+                    committee_info.num_committees(),
+                    sampled_at,
+                    max_samples,
+                )
+            })
+        })
+        .await??;
+
+        debug!(
+            target: LOG_TARGET,
+            "Shard {} has {} substate(s) ({:+}) using {} bytes ({:+})",
+            sample.shard,
+            sample.substate_count,
+            sample.count_delta,
+            sample.total_bytes,
+            sample.bytes_delta
+        );
+        if let Some(bytes_per_hour) = sample.bytes_per_hour() {
+            if threshold > 0 && bytes_per_hour > i64::try_from(threshold).unwrap_or(i64::MAX) {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Substates in shard {} are growing at {} bytes/hour, above the configured threshold of {} \
+                     bytes/hour",
+                    sample.shard,
+                    bytes_per_hour,
+                    threshold
+                );
+            }
+        }
+        self.status.set(sample);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    IntGauge,
+    Registry,
+};
+
+use crate::{metrics::CollectorRegister, shard_growth::ShardGrowthStatus};
+
+/// Exposes the most recent growth sample of this node's shard. The values are read from the shared status when
+/// scraped.
+#[derive(Debug, Clone)]
+pub struct ShardGrowthMetrics {
+    status: ShardGrowthStatus,
+    substate_count: IntGauge,
+    size_bytes: IntGauge,
+    growth_bytes_per_hour: IntGauge,
+}
+
+impl ShardGrowthMetrics {
+    pub fn register(status: ShardGrowthStatus, registry: &Registry) -> Self {
+        Self {
+            status,
+            substate_count: IntGauge::new("shard_substate_count", "Number of live substates in this node's shard")
+                .unwrap(),
+            size_bytes: IntGauge::new(
+                "shard_substates_size_bytes",
+                "Stored size of the live substates in this node's shard",
+            )
+            .unwrap(),
+            growth_bytes_per_hour: IntGauge::new(
+                "shard_substates_growth_bytes_per_hour",
+                "Growth of the stored size of the substates in this node's shard since the previous sample",
+            )
+            .unwrap(),
+        }
+        .register_at(registry)
+    }
+}
+
+impl Collector for ShardGrowthMetrics {
+    fn desc(&self) -> Vec<&Desc> {
+        self.substate_count
+            .desc()
+            .into_iter()
+            .chain(self.size_bytes.desc())
+            .chain(self.growth_bytes_per_hour.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        if let Some(sample) = self.status.latest() {
+            self.substate_count
+                .set(i64::try_from(sample.substate_count).unwrap_or(i64::MAX));
+            self.size_bytes
+                .set(i64::try_from(sample.total_bytes).unwrap_or(i64::MAX));
+            self.growth_bytes_per_hour.set(sample.bytes_per_hour().unwrap_or(0));
+        }
+
+        self.substate_count
+            .collect()
+            .into_iter()
+            .chain(self.size_bytes.collect())
+            .chain(self.growth_bytes_per_hour.collect())
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    IntCounter,
+    IntGauge,
+    Registry,
+};
+use tari_dan_app_utilities::template_manager::implementation::TemplateManager;
+use tari_dan_common_types::PeerAddress;
+
+use crate::metrics::CollectorRegister;
+
+/// Exposes the loaded template cache counters. The values are read from the template manager when scraped.
+#[derive(Debug, Clone)]
+pub struct TemplateCacheMetrics {
+    template_manager: TemplateManager<PeerAddress>,
+    hits: IntCounter,
+    misses: IntCounter,
+    invalidations: IntCounter,
+    entries: IntGauge,
+    size_bytes: IntGauge,
+}
+
+impl TemplateCacheMetrics {
+    pub fn register(template_manager: TemplateManager<PeerAddress>, registry: &Registry) -> Self {
+        Self {
+            template_manager,
+            hits: IntCounter::new("template_cache_hits", "Number of loaded template cache hits").unwrap(),
+            misses: IntCounter::new("template_cache_misses", "Number of loaded template cache misses").unwrap(),
+            invalidations: IntCounter::new(
+                "template_cache_invalidations",
+                "Number of cached templates invalidated by a template update",
+            )
+            .unwrap(),
+            entries: IntGauge::new("template_cache_entries", "Number of loaded templates in the cache").unwrap(),
+            size_bytes: IntGauge::new("template_cache_size_bytes", "Code size of all loaded templates in the cache")
+                .unwrap(),
+        }
+        .register_at(registry)
+    }
+}
+
+impl Collector for TemplateCacheMetrics {
+    fn desc(&self) -> Vec<&Desc> {
+        self.hits
+            .desc()
+            .into_iter()
+            .chain(self.misses.desc())
+            .chain(self.invalidations.desc())
+            .chain(self.entries.desc())
+            .chain(self.size_bytes.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let stats = self.template_manager.cache_stats();
+        set_counter(&self.hits, stats.hits);
+        set_counter(&self.misses, stats.misses);
+        set_counter(&self.invalidations, stats.invalidations);
+        self.entries.set(i64::try_from(stats.entry_count).unwrap_or(i64::MAX));
+        self.size_bytes.set(i64::try_from(stats.weighted_size).unwrap_or(i64::MAX));
+
+        self.hits
+            .collect()
+            .into_iter()
+            .chain(self.misses.collect())
+            .chain(self.invalidations.collect())
+            .chain(self.entries.collect())
+            .chain(self.size_bytes.collect())
+            .collect()
+    }
+}
+
+fn set_counter(counter: &IntCounter, value: u64) {
+    counter.reset();
+    counter.inc_by(value);
+}
@@ -0,0 +1,62 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use log::*;
+use tari_consensus::traits::hooks::ConsensusHooks;
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::WriteQueueMonitor;
+use tokio::{
+    task::{self, JoinHandle},
+    time::{self, MissedTickBehavior},
+};
+
+const LOG_TARGET: &str = "tari::validator_node::write_queue_watchdog";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reports changes to the load shedding state of the state store write queue to the consensus hooks
+pub fn spawn<THooks>(
+    write_queue: WriteQueueMonitor,
+    hooks: THooks,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<Result<(), anyhow::Error>>
+where
+    THooks: ConsensusHooks + Send + 'static,
+{
+    task::spawn(run(write_queue, hooks, shutdown))
+}
+
+async fn run<THooks: ConsensusHooks>(
+    write_queue: WriteQueueMonitor,
+    mut hooks: THooks,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), anyhow::Error> {
+    let mut is_shedding = false;
+    hooks.on_load_shedding_changed(is_shedding);
+
+    let mut interval = time::interval(CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = interval.tick() => {
+                let stats = write_queue.stats();
+                if stats.is_shedding_load != is_shedding {
+                    is_shedding = stats.is_shedding_load;
+                    debug!(
+                        target: LOG_TARGET,
+                        "Load shedding {} (last wait: {:.2?}, last write: {:.2?})",
+                        if is_shedding { "engaged" } else { "disengaged" },
+                        stats.last_wait,
+                        stats.last_duration,
+                    );
+                    hooks.on_load_shedding_changed(is_shedding);
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -35,6 +35,7 @@ function Transaction({ transaction }: { transaction: TransactionAtom }) {
         <StatusChip status={transaction.decision} />
       </TableCell>
       <TableCell>{transaction.leader_fee?.fee}</TableCell>
+      <TableCell>{transaction.priority_fee}</TableCell>
       <TableCell>{transaction.transaction_fee}</TableCell>
     </TableRow>
   );
@@ -49,6 +50,7 @@ export default function Transactions({ transactions }: { transactions: Transacti
             <TableCell>Transaction ID</TableCell>
             <TableCell>Decision</TableCell>
             <TableCell>Leader fee</TableCell>
+            <TableCell>Priority fee</TableCell>
             <TableCell>Transaction fee</TableCell>
           </TableRow>
         </TableHead>
@@ -25,8 +25,12 @@ import type {
   AddPeerRequest,
   GetAllVnsRequest,
   GetAllVnsResponse,
+  GetBlockRejectionsRequest,
+  GetBlockRejectionsResponse,
   GetBlockRequest,
   GetBlockResponse,
+  GetBlocksAfterRequest,
+  GetBlocksAfterResponse,
   GetBlocksCountResponse,
   GetBlocksRequest,
   GetBlocksResponse,
@@ -35,18 +39,24 @@ import type {
   GetCommsStatsResponse,
   GetConnectionsResponse,
   GetEpochManagerStatsResponse,
+  GetEquivocationEvidenceResponse,
   GetIdentityResponse,
   GetMempoolStatsResponse,
+  GetMisbehavingPeersResponse,
   GetNetworkCommitteeResponse,
+  GetRecentTransactionsRequest,
   GetRecentTransactionsResponse,
   GetShardKeyRequest,
   GetShardKeyResponse,
   GetStateRequest,
   GetStateResponse,
+  GetSubstateDecodedRequest,
+  GetSubstateDecodedResponse,
   GetSubstateRequest,
   GetSubstateResponse,
   GetSubstatesByTransactionRequest,
   GetSubstatesByTransactionResponse,
+  GetSyncStatusResponse,
   GetTemplateRequest,
   GetTemplateResponse,
   GetTemplatesRequest,
@@ -55,11 +65,15 @@ import type {
   GetTransactionResponse,
   GetTransactionResultRequest,
   GetTransactionResultResponse,
+  GetTransactionsAfterRequest,
+  GetTransactionsAfterResponse,
   GetTxPoolResponse,
   ListBlocksRequest,
   ListBlocksResponse,
   SubmitTransactionRequest,
   SubmitTransactionResponse,
+  UnbanPeerRequest,
+  UnbanPeerResponse,
   VNGetValidatorFeesRequest,
   VNGetValidatorFeesResponse,
 } from "@tariproject/typescript-bindings/validator-node-client";
@@ -109,7 +123,10 @@ async function jsonRpc(method: string, params: any = null) {
 // Transaction
 export const submitTransaction = (request: SubmitTransactionRequest): Promise<SubmitTransactionResponse> =>
   jsonRpc("submit_transaction", request);
-export const getRecentTransactions = (): Promise<GetRecentTransactionsResponse> => jsonRpc("get_recent_transactions");
+export const getRecentTransactions = (request?: GetRecentTransactionsRequest): Promise<GetRecentTransactionsResponse> =>
+  jsonRpc("get_recent_transactions", request);
+export const getTransactionsAfter = (request: GetTransactionsAfterRequest): Promise<GetTransactionsAfterResponse> =>
+  jsonRpc("get_transactions_after", request);
 export const getTransaction = (request: GetTransactionRequest): Promise<GetTransactionResponse> =>
   jsonRpc("get_transaction", request);
 export const getTransactionResult = (request: GetTransactionResultRequest): Promise<GetTransactionResultResponse> =>
@@ -117,6 +134,8 @@ export const getTransactionResult = (request: GetTransactionResultRequest): Prom
 export const getState = (request: GetStateRequest): Promise<GetStateResponse> => jsonRpc("get_state", request);
 export const getSubstate = (request: GetSubstateRequest): Promise<GetSubstateResponse> =>
   jsonRpc("get_substate", request);
+export const getSubstateDecoded = (request: GetSubstateDecodedRequest): Promise<GetSubstateDecodedResponse> =>
+  jsonRpc("get_substate_decoded", request);
 export const getUpSubstates = (request: GetSubstatesByTransactionRequest): Promise<GetSubstatesByTransactionResponse> =>
   jsonRpc("get_substates_created_by_transaction", request);
 export const getDownSubstates = (
@@ -129,6 +148,12 @@ export const getTxPool = (): Promise<GetTxPoolResponse> => jsonRpc("get_tx_pool"
 export const getBlock = (request: GetBlockRequest): Promise<GetBlockResponse> => jsonRpc("get_block", request);
 export const getBlocksCount = (): Promise<GetBlocksCountResponse> => jsonRpc("get_blocks_count");
 export const getBlocks = (request: GetBlocksRequest): Promise<GetBlocksResponse> => jsonRpc("get_blocks", request);
+export const getBlocksAfter = (request: GetBlocksAfterRequest): Promise<GetBlocksAfterResponse> =>
+  jsonRpc("get_blocks_after", request);
+export const getBlockRejections = (request: GetBlockRejectionsRequest): Promise<GetBlockRejectionsResponse> =>
+  jsonRpc("get_block_rejections", request);
+export const getEquivocationEvidence = (): Promise<GetEquivocationEvidenceResponse> =>
+  jsonRpc("get_equivocation_evidence");
 export const getFilteredBlocksCount = (request: GetFilteredBlocksCountRequest): Promise<GetBlocksCountResponse> =>
   jsonRpc("get_filtered_blocks_count", request);
 
@@ -143,6 +168,7 @@ export const getIdentity = (): Promise<GetIdentityResponse> => jsonRpc("get_iden
 
 export const getMempoolStats = (): Promise<GetMempoolStatsResponse> => jsonRpc("get_mempool_stats");
 export const getEpochManagerStats = (): Promise<GetEpochManagerStatsResponse> => jsonRpc("get_epoch_manager_stats");
+export const getSyncStatus = (): Promise<GetSyncStatusResponse> => jsonRpc("get_sync_status");
 export const getShardKey = (request: GetShardKeyRequest): Promise<GetShardKeyResponse> =>
   jsonRpc("get_shard_key", request);
 export const getCommittee = (request: GetCommitteeRequest): Promise<GetCommitteeResponse> =>
@@ -156,3 +182,5 @@ export const getFees = (request: VNGetValidatorFeesRequest): Promise<VNGetValida
 export const addPeer = (request: AddPeerRequest) => jsonRpc("add_peer", request);
 export const getCommsStats = (): Promise<GetCommsStatsResponse> => jsonRpc("get_comms_stats");
 export const getConnections = (): Promise<GetConnectionsResponse> => jsonRpc("get_connections");
+export const getMisbehavingPeers = (): Promise<GetMisbehavingPeersResponse> => jsonRpc("get_misbehaving_peers");
+export const unbanPeer = (request: UnbanPeerRequest): Promise<UnbanPeerResponse> => jsonRpc("unban_peer", request);
@@ -3,11 +3,14 @@
 
 export * from "./src/types/AccessRule";
 export * from "./src/types/Account";
+export * from "./src/types/AccountTransactionQueue";
 export * from "./src/types/Amount";
 export * from "./src/types/Arg";
 export * from "./src/types/ArgDef";
 export * from "./src/types/AuthHook";
 export * from "./src/types/Block";
+export * from "./src/types/BlockCursor";
+export * from "./src/types/BlockRejection";
 export * from "./src/types/BucketId";
 export * from "./src/types/Claims";
 export * from "./src/types/Command";
@@ -19,6 +22,7 @@ export * from "./src/types/ComponentAddress";
 export * from "./src/types/ComponentBody";
 export * from "./src/types/ComponentHeader";
 export * from "./src/types/ComponentKey";
+export * from "./src/types/ComponentSchema";
 export * from "./src/types/ConfidentialClaim";
 export * from "./src/types/ConfidentialOutput";
 export * from "./src/types/ConfidentialOutputStatement";
@@ -29,7 +33,9 @@ export * from "./src/types/Decision";
 export * from "./src/types/ElgamalVerifiableBalance";
 export * from "./src/types/EntityId";
 export * from "./src/types/Epoch";
+export * from "./src/types/EpochCheckpoint";
 export * from "./src/types/EpochEvent";
+export * from "./src/types/EquivocationEvidence";
 export * from "./src/types/Event";
 export * from "./src/types/Evidence";
 export * from "./src/types/ExecutedTransaction";
@@ -40,6 +46,7 @@ export * from "./src/types/FeeClaimAddress";
 export * from "./src/types/FeeCostBreakdown";
 export * from "./src/types/FeeReceipt";
 export * from "./src/types/FeeSource";
+export * from "./src/types/FieldDef";
 export * from "./src/types/FinalizeResult";
 export * from "./src/types/ForeignProposal";
 export * from "./src/types/ForeignProposalState";
@@ -56,6 +63,7 @@ export * from "./src/types/LogEntry";
 export * from "./src/types/LogLevel";
 export * from "./src/types/Metadata";
 export * from "./src/types/NetworkCommitteeInfo";
+export * from "./src/types/NftOwnership";
 export * from "./src/types/NodeHeight";
 export * from "./src/types/NonFungible";
 export * from "./src/types/NonFungibleAddress";
@@ -69,8 +77,12 @@ export * from "./src/types/Ordering";
 export * from "./src/types/OwnerRule";
 export * from "./src/types/PeerAddress";
 export * from "./src/types/ProofId";
+export * from "./src/types/QcTiming";
+export * from "./src/types/QueuedTransaction";
+export * from "./src/types/QueuedTransactionStatus";
 export * from "./src/types/QuorumCertificate";
 export * from "./src/types/QuorumDecision";
+export * from "./src/types/RecentTransaction";
 export * from "./src/types/RejectReason";
 export * from "./src/types/RequireRule";
 export * from "./src/types/Resource";
@@ -79,9 +91,15 @@ export * from "./src/types/ResourceAddress";
 export * from "./src/types/ResourceContainer";
 export * from "./src/types/ResourceType";
 export * from "./src/types/RestrictedAccessRule";
+export * from "./src/types/RoyaltyConfig";
 export * from "./src/types/RuleRequirement";
+export * from "./src/types/ScheduleTrigger";
+export * from "./src/types/ScheduledTransaction";
+export * from "./src/types/ScheduledTransactionStatus";
 export * from "./src/types/Shard";
 export * from "./src/types/ShardEvidence";
+export * from "./src/types/ShardGrowthSample";
+export * from "./src/types/StructDef";
 export * from "./src/types/Substate";
 export * from "./src/types/SubstateAddress";
 export * from "./src/types/SubstateDestroyed";
@@ -90,12 +108,15 @@ export * from "./src/types/SubstateId";
 export * from "./src/types/SubstateLockFlag";
 export * from "./src/types/SubstateRecord";
 export * from "./src/types/SubstateRequirement";
+export * from "./src/types/SubstateSize";
 export * from "./src/types/SubstateType";
 export * from "./src/types/SubstateValue";
+export * from "./src/types/SyncCheckpoint";
 export * from "./src/types/TemplateDef";
 export * from "./src/types/TemplateDefV1";
 export * from "./src/types/Transaction";
 export * from "./src/types/TransactionAtom";
+export * from "./src/types/TransactionCursor";
 export * from "./src/types/TransactionPoolRecord";
 export * from "./src/types/TransactionPoolStage";
 export * from "./src/types/TransactionReceipt";
@@ -107,9 +128,12 @@ export * from "./src/types/Type";
 export * from "./src/types/UnclaimedConfidentialOutput";
 export * from "./src/types/UnsignedTransaction";
 export * from "./src/types/ValidatorSignature";
+export * from "./src/types/ValidatorVoteLateness";
 export * from "./src/types/Vault";
 export * from "./src/types/VaultId";
 export * from "./src/types/VersionedSubstateId";
 export * from "./src/types/VersionedSubstateIdLockIntent";
 export * from "./src/types/ViewableBalanceProof";
+export * from "./src/types/Vote";
+export * from "./src/types/VoteTiming";
 export * from "./src/helpers/helpers";
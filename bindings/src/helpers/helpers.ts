@@ -89,6 +89,12 @@ export function rejectReasonToString(reason: RejectReason | null): string {
   if ("FeesNotPaid" in reason) {
     return `FeesNotPaid(${reason.FeesNotPaid})`;
   }
+  if ("EpochExpired" in reason) {
+    return `EpochExpired(${reason.EpochExpired})`;
+  }
+  if ("ExecutionBudgetExceeded" in reason) {
+    return `ExecutionBudgetExceeded(${reason.ExecutionBudgetExceeded})`;
+  }
   console.error("Unknown reason", reason);
   return "Unknown";
 }
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueuedTransaction } from "./QueuedTransaction";
+import type { SubstateId } from "./SubstateId";
+
+export interface AccountTransactionQueue {
+  account_address: SubstateId;
+  next_sequence: number;
+  paused_reason: string | null;
+  entries: Array<QueuedTransaction>;
+}
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { NodeHeight } from "./NodeHeight";
+
+export interface BlockRejection {
+  block_id: string;
+  epoch: Epoch;
+  height: NodeHeight;
+  proposed_by: string;
+  reason: string;
+}
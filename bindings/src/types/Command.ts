@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EpochCheckpoint } from "./EpochCheckpoint";
 import type { EpochEvent } from "./EpochEvent";
 import type { ForeignProposal } from "./ForeignProposal";
 import type { TransactionAtom } from "./TransactionAtom";
@@ -9,4 +10,5 @@ export type Command =
   | { Accept: TransactionAtom }
   | { ForeignProposal: ForeignProposal }
   | { LocalOnly: TransactionAtom }
-  | { EpochEvent: EpochEvent };
+  | { EpochEvent: EpochEvent }
+  | { EpochCheckpoint: EpochCheckpoint };
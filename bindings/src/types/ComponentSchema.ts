@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StructDef } from "./StructDef";
+
+export interface ComponentSchema {
+  component: StructDef;
+  types: Array<StructDef>;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+
+export interface EpochCheckpoint {
+  epoch: Epoch;
+  state_merkle_root: string;
+  validator_set_hash: string;
+}
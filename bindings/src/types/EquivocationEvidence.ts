@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Vote } from "./Vote";
+
+export interface EquivocationEvidence {
+  first_vote: Vote;
+  second_vote: Vote;
+}
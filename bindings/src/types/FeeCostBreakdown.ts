@@ -5,4 +5,5 @@ import type { FeeBreakdown } from "./FeeBreakdown";
 export interface FeeCostBreakdown {
   total_fees_charged: Amount;
   breakdown: Array<FeeBreakdown>;
+  total_storage_bytes: number;
 }
@@ -6,4 +6,5 @@ export interface FeeReceipt {
   total_fee_payment: Amount;
   total_fees_paid: Amount;
   cost_breakdown: Array<FeeBreakdown>;
+  total_storage_bytes: number;
 }
@@ -15,4 +15,8 @@ export type Instruction =
   | { ClaimBurn: { claim: ConfidentialClaim } }
   | { ClaimValidatorFees: { epoch: number; validator_public_key: string } }
   | "DropAllProofsInWorkspace"
+  | { DeclareRoyaltyPayment: { resource_address: string; workspace_bucket: string } }
+  | { MigrateComponent: { component_address: string; template_address: Uint8Array } }
+  | { AssertBucketContains: { workspace_bucket: string; resource_address: string; min_amount: Amount } }
+  | { AssertVaultBalanceAtLeast: { vault_id: string; min_amount: Amount } }
   | { CreateFreeTestCoins: { revealed_amount: Amount; output: ConfidentialOutput | null } };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddress } from "./ComponentAddress";
+import type { NonFungibleId } from "./NonFungibleId";
+import type { ResourceAddress } from "./ResourceAddress";
+import type { VaultId } from "./VaultId";
+
+export interface NftOwnership {
+  resource_address: ResourceAddress;
+  nft_id: NonFungibleId;
+  vault_id: VaultId;
+  component_address: ComponentAddress | null;
+}
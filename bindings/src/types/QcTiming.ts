@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { NodeHeight } from "./NodeHeight";
+import type { VoteTiming } from "./VoteTiming";
+
+export interface QcTiming {
+  block_id: string;
+  epoch: Epoch;
+  height: NodeHeight;
+  first_vote_ms: number;
+  quorum_reached_ms: number | null;
+  last_vote_ms: number;
+  votes: Array<VoteTiming>;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueuedTransactionStatus } from "./QueuedTransactionStatus";
+
+export interface QueuedTransaction {
+  sequence: number;
+  transaction_id: string;
+  status: QueuedTransactionStatus;
+}
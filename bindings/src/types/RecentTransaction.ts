@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Decision } from "./Decision";
+
+export interface RecentTransaction {
+  transaction_id: string;
+  decision: Decision | null;
+  abort_reason: string | null;
+  fee: number | null;
+  block_id: string | null;
+  timestamp: number;
+}
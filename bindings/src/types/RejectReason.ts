@@ -7,4 +7,6 @@ export type RejectReason =
   | { ShardPledgedToAnotherPayload: string }
   | { ShardRejected: string }
   | "FeeTransactionFailed"
-  | { FeesNotPaid: string };
+  | { FeesNotPaid: string }
+  | { EpochExpired: string }
+  | { ExecutionBudgetExceeded: string };
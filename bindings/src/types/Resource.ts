@@ -5,6 +5,7 @@ import type { Metadata } from "./Metadata";
 import type { OwnerRule } from "./OwnerRule";
 import type { ResourceAccessRules } from "./ResourceAccessRules";
 import type { ResourceType } from "./ResourceType";
+import type { RoyaltyConfig } from "./RoyaltyConfig";
 
 export interface Resource {
   resource_type: ResourceType;
@@ -15,4 +16,5 @@ export interface Resource {
   total_supply: Amount;
   view_key: string | null;
   auth_hook: AuthHook | null;
+  royalty: RoyaltyConfig | null;
 }
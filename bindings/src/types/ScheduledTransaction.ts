@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScheduleTrigger } from "./ScheduleTrigger";
+import type { ScheduledTransactionStatus } from "./ScheduledTransactionStatus";
+import type { SubstateRequirement } from "./SubstateRequirement";
+import type { UnsignedTransaction } from "./UnsignedTransaction";
+
+export interface ScheduledTransaction {
+  id: number;
+  trigger: ScheduleTrigger;
+  transaction: UnsignedTransaction;
+  required_substates: Array<SubstateRequirement>;
+  signing_key_index: number;
+  status: ScheduledTransactionStatus;
+  transaction_id: string | null;
+  failure_reason: string | null;
+}
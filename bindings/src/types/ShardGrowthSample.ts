@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { Shard } from "./Shard";
+
+export interface ShardGrowthSample {
+  epoch: Epoch;
+  shard: Shard;
+  substate_count: number;
+  total_bytes: number;
+  count_delta: number;
+  bytes_delta: number;
+  interval_secs: number;
+  sampled_at: number;
+}
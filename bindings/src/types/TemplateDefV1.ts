@@ -1,8 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentSchema } from "./ComponentSchema";
 import type { FunctionDef } from "./FunctionDef";
 
 export interface TemplateDefV1 {
   template_name: string;
   tari_version: string;
   functions: Array<FunctionDef>;
+  component_schema: ComponentSchema | null;
+  is_reentrant: boolean;
+  upgrades_from: Uint8Array | null;
 }
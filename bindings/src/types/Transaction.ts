@@ -14,4 +14,5 @@ export interface Transaction {
   filled_inputs: Array<VersionedSubstateId>;
   min_epoch: Epoch | null;
   max_epoch: Epoch | null;
+  priority_fee: number;
 }
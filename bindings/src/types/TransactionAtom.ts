@@ -9,4 +9,5 @@ export interface TransactionAtom {
   evidence: Evidence;
   transaction_fee: number;
   leader_fee: LeaderFee | null;
+  priority_fee: number;
 }
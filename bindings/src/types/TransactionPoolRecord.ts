@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Decision } from "./Decision";
+import type { NodeHeight } from "./NodeHeight";
 import type { TransactionAtom } from "./TransactionAtom";
 import type { TransactionPoolStage } from "./TransactionPoolStage";
 
@@ -10,4 +11,6 @@ export interface TransactionPoolRecord {
   local_decision: Decision | null;
   remote_decision: Decision | null;
   is_ready: boolean;
+  deferred_execution_attempts: number;
+  first_seen_height: NodeHeight;
 }
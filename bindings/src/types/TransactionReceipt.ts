@@ -2,10 +2,12 @@
 import type { Event } from "./Event";
 import type { FeeReceipt } from "./FeeReceipt";
 import type { LogEntry } from "./LogEntry";
+import type { SubstateSize } from "./SubstateSize";
 
 export interface TransactionReceipt {
   transaction_hash: Uint8Array;
   events: Array<Event>;
   logs: Array<LogEntry>;
   fee_receipt: FeeReceipt;
+  substate_sizes: Array<SubstateSize>;
 }
@@ -2,6 +2,8 @@
 
 export type TransactionStatus =
   | "New"
+  | "PendingSignature"
+  | "Queued"
   | "DryRun"
   | "Pending"
   | "Accepted"
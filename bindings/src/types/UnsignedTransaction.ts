@@ -11,4 +11,5 @@ export interface UnsignedTransaction {
   filled_inputs: Array<VersionedSubstateId>;
   min_epoch: Epoch | null;
   max_epoch: Epoch | null;
+  priority_fee: number;
 }
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ValidatorVoteLateness {
+  public_key: string;
+  num_votes: number;
+  average_lateness_ms: number;
+  average_rank: number;
+}
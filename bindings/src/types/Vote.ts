@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { NodeHeight } from "./NodeHeight";
+import type { QuorumDecision } from "./QuorumDecision";
+import type { ValidatorSignature } from "./ValidatorSignature";
+
+export interface Vote {
+  epoch: Epoch;
+  block_id: string;
+  height: NodeHeight;
+  decision: QuorumDecision;
+  sender_leaf_hash: string;
+  signature: ValidatorSignature;
+}
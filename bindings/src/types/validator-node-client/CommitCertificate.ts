@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "../Block";
+import type { CommitteeMemberKey } from "./CommitteeMemberKey";
+import type { QuorumCertificate } from "../QuorumCertificate";
+
+export interface CommitCertificate {
+  blocks: Array<Block>;
+  commit_qc: QuorumCertificate;
+  committee: Array<CommitteeMemberKey>;
+}
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateAddress } from "../SubstateAddress";
+
+export interface CommitteeMemberKey {
+  public_key: string;
+  shard_key: SubstateAddress;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DecodedValue } from "./DecodedValue";
+
+export interface DecodedField {
+  name: string;
+  field_type: string;
+  value: DecodedValue;
+}
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DecodedField } from "./DecodedField";
+
+export type DecodedValue = { Struct: { name: string; fields: Array<DecodedField> } } | { Raw: any };
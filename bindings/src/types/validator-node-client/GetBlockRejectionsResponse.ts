@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockRejection } from "../BlockRejection";
+
+export interface GetBlockRejectionsResponse {
+  rejections: Array<BlockRejection>;
+  total: number;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockCursor } from "../BlockCursor";
+import type { Ordering } from "../Ordering";
+
+export interface GetBlocksAfterRequest {
+  cursor: BlockCursor | null;
+  limit: number;
+  ordering: Ordering;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "../Block";
+import type { BlockCursor } from "../BlockCursor";
+
+export interface GetBlocksAfterResponse {
+  blocks: Array<Block>;
+  next_cursor: BlockCursor | null;
+}
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+import type { NodeHeight } from "../NodeHeight";
+
+export interface GetConsensusStatusResponse {
+  state: string;
+  is_in_maintenance_mode: boolean;
+  epoch: Epoch;
+  leaf_block_id: string;
+  leaf_block_height: NodeHeight;
+  locked_block_id: string;
+  locked_block_height: NodeHeight;
+  high_qc_block_id: string;
+  high_qc_block_height: NodeHeight;
+  last_voted_height: NodeHeight | null;
+  current_view_height: NodeHeight;
+  current_view_timeout_ms: number;
+  is_leader: boolean;
+  transaction_pool_stage_counts: Record<string, number>;
+  proposer_equivocation_count: number;
+  dummy_block_count: number;
+  leader_timeouts_last_epoch: number;
+  is_shedding_load: boolean;
+  write_queue_last_wait_ms: number;
+  write_queue_max_wait_ms: number;
+}
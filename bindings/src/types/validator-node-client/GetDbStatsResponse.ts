@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DbMaintenanceInfo } from "./DbMaintenanceInfo";
+
+export interface GetDbStatsResponse {
+  state_store: DbMaintenanceInfo;
+  global_db: DbMaintenanceInfo;
+  state_store_backup: DbMaintenanceInfo;
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NftOwnership } from "../NftOwnership";
+
+export interface GetNftOwnerResponse {
+  ownership: NftOwnership | null;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QcTiming } from "../QcTiming";
+import type { ValidatorVoteLateness } from "../ValidatorVoteLateness";
+
+export interface GetQcTimingsResponse {
+  timings: Array<QcTiming>;
+  validators: Array<ValidatorVoteLateness>;
+}
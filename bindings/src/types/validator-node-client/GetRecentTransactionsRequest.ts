@@ -1,3 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Decision } from "../Decision";
+import type { Epoch } from "../Epoch";
+import type { TransactionCursor } from "../TransactionCursor";
 
-export type GetRecentTransactionsRequest = Record<string, never>;
+export interface GetRecentTransactionsRequest {
+  limit: number | null;
+  cursor: TransactionCursor | null;
+  decision: Decision | null;
+  from_epoch: Epoch | null;
+  to_epoch: Epoch | null;
+  involved_substate_id: string | null;
+}
@@ -1,6 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { Transaction } from "../Transaction";
+import type { RecentTransaction } from "../RecentTransaction";
+import type { TransactionCursor } from "../TransactionCursor";
 
 export interface GetRecentTransactionsResponse {
-  transactions: Array<Transaction>;
+  transactions: Array<RecentTransaction>;
+  next_cursor: TransactionCursor | null;
 }
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "../SubstateId";
+
+export interface GetSubstateDecodedRequest {
+  address: SubstateId;
+  version: number;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DecodedValue } from "./DecodedValue";
+import type { SubstateStatus } from "./SubstateStatus";
+
+export interface GetSubstateDecodedResponse {
+  value: DecodedValue | null;
+  created_by_tx: string | null;
+  status: SubstateStatus;
+}
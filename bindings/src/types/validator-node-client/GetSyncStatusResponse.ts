@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncCheckpoint } from "../SyncCheckpoint";
+
+export interface GetSyncStatusResponse {
+  checkpoint: SyncCheckpoint | null;
+  blocks_synced: number;
+  estimated_remaining: number | null;
+}
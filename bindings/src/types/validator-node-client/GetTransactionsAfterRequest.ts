@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ordering } from "../Ordering";
+import type { TransactionCursor } from "../TransactionCursor";
+
+export interface GetTransactionsAfterRequest {
+  cursor: TransactionCursor | null;
+  limit: number;
+  ordering: Ordering;
+}
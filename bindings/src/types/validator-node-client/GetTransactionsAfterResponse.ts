@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Transaction } from "../Transaction";
+import type { TransactionCursor } from "../TransactionCursor";
+
+export interface GetTransactionsAfterResponse {
+  transactions: Array<Transaction>;
+  next_cursor: TransactionCursor | null;
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface MaintenanceModeResponse {
+  is_in_maintenance_mode: boolean;
+  changed: boolean;
+}
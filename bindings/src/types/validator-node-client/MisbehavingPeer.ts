@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface MisbehavingPeer {
+  address: string;
+  score: number;
+  last_offence: number;
+  reason: string;
+  banned_until: number | null;
+  is_banned: boolean;
+}
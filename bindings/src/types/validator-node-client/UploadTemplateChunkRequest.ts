@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface UploadTemplateChunkRequest {
+  upload_id: string;
+  offset: number;
+  data: string;
+}
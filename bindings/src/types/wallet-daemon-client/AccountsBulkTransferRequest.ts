@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BulkTransferRecipient } from "./BulkTransferRecipient";
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+import type { Amount } from "../Amount";
+import type { ResourceAddress } from "../ResourceAddress";
+
+export interface AccountsBulkTransferRequest {
+  account: ComponentAddressOrName | null;
+  resource_address: ResourceAddress;
+  recipients: Array<BulkTransferRecipient>;
+  max_instructions_per_transaction: number | null;
+  max_fee_per_transaction: Amount | null;
+  max_concurrent_transactions: number | null;
+  priority_fee: number;
+}
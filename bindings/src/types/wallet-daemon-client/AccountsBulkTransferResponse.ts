@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BulkTransferOutcome } from "./BulkTransferOutcome";
+import type { BulkTransferSummary } from "./BulkTransferSummary";
+
+export interface AccountsBulkTransferResponse {
+  outcomes: Array<BulkTransferOutcome>;
+  summary: BulkTransferSummary;
+}
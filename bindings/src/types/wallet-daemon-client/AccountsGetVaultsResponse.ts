@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountVaultEntry } from "./AccountVaultEntry";
+import type { SubstateId } from "../SubstateId";
+
+export interface AccountsGetVaultsResponse {
+  address: SubstateId;
+  vaults: Array<AccountVaultEntry>;
+}
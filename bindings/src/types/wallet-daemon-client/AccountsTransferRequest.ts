@@ -11,4 +11,6 @@ export interface AccountsTransferRequest {
   max_fee: Amount | null;
   proof_from_badge_resource: string | null;
   dry_run: boolean;
+  priority_fee: number;
+  assert_balances: boolean;
 }
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+
+export interface BulkTransferOutcome {
+  destination_public_key: string;
+  amount: Amount;
+  transaction_id: string | null;
+  failure_reason: string | null;
+}
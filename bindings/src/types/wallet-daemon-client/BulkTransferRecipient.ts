@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+
+export interface BulkTransferRecipient {
+  destination_public_key: string;
+  amount: Amount;
+}
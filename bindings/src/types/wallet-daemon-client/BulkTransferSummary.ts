@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+
+export interface BulkTransferSummary {
+  num_succeeded: number;
+  num_failed: number;
+  num_transactions: number;
+  total_amount_transferred: Amount;
+  total_fees: Amount;
+}
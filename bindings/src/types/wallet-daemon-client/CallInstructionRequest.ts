@@ -15,4 +15,5 @@ export interface CallInstructionRequest {
   proof_ids: Array<number>;
   min_epoch: number | null;
   max_epoch: number | null;
+  priority_fee: number;
 }
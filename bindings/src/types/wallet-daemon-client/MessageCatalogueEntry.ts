@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MessageParamSchema } from "./MessageParamSchema";
+
+export interface MessageCatalogueEntry {
+  code: string;
+  params: Array<MessageParamSchema>;
+  default_message: string;
+}
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WalletProfileInfo } from "./WalletProfileInfo";
+
+export interface ProfilesListResponse {
+  profiles: Array<WalletProfileInfo>;
+  active_profile: string;
+}
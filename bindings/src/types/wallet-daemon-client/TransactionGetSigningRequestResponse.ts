@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UnsignedTransaction } from "../UnsignedTransaction";
+
+export interface TransactionGetSigningRequestResponse {
+  key_id: string;
+  public_key: string;
+  challenge: string;
+  transaction: UnsignedTransaction;
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScheduledTransaction } from "../ScheduledTransaction";
+
+export interface TransactionListScheduledResponse {
+  scheduled_transactions: Array<ScheduledTransaction>;
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScheduleTrigger } from "../ScheduleTrigger";
+import type { SubstateRequirement } from "../SubstateRequirement";
+import type { UnsignedTransaction } from "../UnsignedTransaction";
+
+export interface TransactionScheduleRequest {
+  transaction: UnsignedTransaction;
+  signing_key_index: number | null;
+  inputs: Array<SubstateRequirement>;
+  override_inputs: boolean;
+  trigger: ScheduleTrigger;
+}
@@ -15,4 +15,5 @@ export interface TransactionSubmitRequest {
   instructions: Array<Instruction>;
   min_epoch: Epoch | null;
   max_epoch: Epoch | null;
+  deferred_signing: boolean;
 }
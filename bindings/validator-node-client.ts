@@ -4,37 +4,63 @@
 export * from "./src/types/validator-node-client/AddPeerRequest";
 export * from "./src/types/validator-node-client/AddPeerResponse";
 export * from "./src/types/validator-node-client/ArgDef";
+export * from "./src/types/validator-node-client/CommitCertificate";
+export * from "./src/types/validator-node-client/CommitteeMemberKey";
 export * from "./src/types/validator-node-client/CommitteeShardInfo";
 export * from "./src/types/validator-node-client/Connection";
 export * from "./src/types/validator-node-client/ConnectionDirection";
+export * from "./src/types/validator-node-client/DbMaintenanceInfo";
+export * from "./src/types/validator-node-client/DecodedField";
+export * from "./src/types/validator-node-client/DecodedValue";
 export * from "./src/types/validator-node-client/DryRunTransactionFinalizeResult";
 export * from "./src/types/validator-node-client/FunctionDef";
 export * from "./src/types/validator-node-client/GetAllVnsRequest";
 export * from "./src/types/validator-node-client/GetAllVnsResponse";
+export * from "./src/types/validator-node-client/GetBlockRejectionsRequest";
+export * from "./src/types/validator-node-client/GetBlockRejectionsResponse";
 export * from "./src/types/validator-node-client/GetBlockRequest";
 export * from "./src/types/validator-node-client/GetBlockResponse";
+export * from "./src/types/validator-node-client/GetBlocksAfterRequest";
+export * from "./src/types/validator-node-client/GetBlocksAfterResponse";
 export * from "./src/types/validator-node-client/GetBlocksCountResponse";
 export * from "./src/types/validator-node-client/GetBlocksRequest";
 export * from "./src/types/validator-node-client/GetBlocksResponse";
+export * from "./src/types/validator-node-client/GetCommitCertificateRequest";
+export * from "./src/types/validator-node-client/GetCommitCertificateResponse";
 export * from "./src/types/validator-node-client/GetCommitteeRequest";
 export * from "./src/types/validator-node-client/GetCommitteeResponse";
 export * from "./src/types/validator-node-client/GetCommsStatsResponse";
 export * from "./src/types/validator-node-client/GetConnectionsResponse";
+export * from "./src/types/validator-node-client/GetConsensusStatusResponse";
+export * from "./src/types/validator-node-client/GetDbStatsResponse";
 export * from "./src/types/validator-node-client/GetEpochManagerStatsResponse";
+export * from "./src/types/validator-node-client/GetEquivocationEvidenceResponse";
 export * from "./src/types/validator-node-client/GetFilteredBlocksCountRequest";
 export * from "./src/types/validator-node-client/GetIdentityResponse";
 export * from "./src/types/validator-node-client/GetMempoolStatsResponse";
+export * from "./src/types/validator-node-client/GetMisbehavingPeersResponse";
 export * from "./src/types/validator-node-client/GetNetworkCommitteeResponse";
+export * from "./src/types/validator-node-client/GetNftOwnerRequest";
+export * from "./src/types/validator-node-client/GetNftOwnerResponse";
+export * from "./src/types/validator-node-client/GetNftsByOwnerRequest";
+export * from "./src/types/validator-node-client/GetNftsByOwnerResponse";
+export * from "./src/types/validator-node-client/GetQcTimingsRequest";
+export * from "./src/types/validator-node-client/GetQcTimingsResponse";
 export * from "./src/types/validator-node-client/GetRecentTransactionsRequest";
 export * from "./src/types/validator-node-client/GetRecentTransactionsResponse";
+export * from "./src/types/validator-node-client/GetShardGrowthRequest";
+export * from "./src/types/validator-node-client/GetShardGrowthResponse";
 export * from "./src/types/validator-node-client/GetShardKeyRequest";
 export * from "./src/types/validator-node-client/GetShardKeyResponse";
 export * from "./src/types/validator-node-client/GetStateRequest";
 export * from "./src/types/validator-node-client/GetStateResponse";
+export * from "./src/types/validator-node-client/GetSubstateDecodedRequest";
+export * from "./src/types/validator-node-client/GetSubstateDecodedResponse";
 export * from "./src/types/validator-node-client/GetSubstateRequest";
 export * from "./src/types/validator-node-client/GetSubstateResponse";
 export * from "./src/types/validator-node-client/GetSubstatesByTransactionRequest";
 export * from "./src/types/validator-node-client/GetSubstatesByTransactionResponse";
+export * from "./src/types/validator-node-client/GetSyncStatusResponse";
 export * from "./src/types/validator-node-client/GetTemplateRequest";
 export * from "./src/types/validator-node-client/GetTemplateResponse";
 export * from "./src/types/validator-node-client/GetTemplatesRequest";
@@ -43,16 +69,29 @@ export * from "./src/types/validator-node-client/GetTransactionRequest";
 export * from "./src/types/validator-node-client/GetTransactionResponse";
 export * from "./src/types/validator-node-client/GetTransactionResultRequest";
 export * from "./src/types/validator-node-client/GetTransactionResultResponse";
+export * from "./src/types/validator-node-client/GetTransactionsAfterRequest";
+export * from "./src/types/validator-node-client/GetTransactionsAfterResponse";
 export * from "./src/types/validator-node-client/GetTxPoolResponse";
 export * from "./src/types/validator-node-client/ListBlocksRequest";
 export * from "./src/types/validator-node-client/ListBlocksResponse";
 export * from "./src/types/validator-node-client/LogEntry";
 export * from "./src/types/validator-node-client/LogLevel";
+export * from "./src/types/validator-node-client/MaintenanceModeResponse";
+export * from "./src/types/validator-node-client/MisbehavingPeer";
+export * from "./src/types/validator-node-client/ReloadConfigResponse";
 export * from "./src/types/validator-node-client/SubmitTransactionRequest";
 export * from "./src/types/validator-node-client/SubmitTransactionResponse";
 export * from "./src/types/validator-node-client/SubstateStatus";
 export * from "./src/types/validator-node-client/TemplateAbi";
 export * from "./src/types/validator-node-client/TemplateMetadata";
+export * from "./src/types/validator-node-client/UnbanPeerRequest";
+export * from "./src/types/validator-node-client/UnbanPeerResponse";
+export * from "./src/types/validator-node-client/UploadTemplateBeginRequest";
+export * from "./src/types/validator-node-client/UploadTemplateBeginResponse";
+export * from "./src/types/validator-node-client/UploadTemplateChunkRequest";
+export * from "./src/types/validator-node-client/UploadTemplateChunkResponse";
+export * from "./src/types/validator-node-client/UploadTemplateFinishRequest";
+export * from "./src/types/validator-node-client/UploadTemplateFinishResponse";
 export * from "./src/types/validator-node-client/ValidatorFee";
 export * from "./src/types/validator-node-client/ValidatorNode";
 export * from "./src/types/validator-node-client/VNGetValidatorFeesRequest";
@@ -5,6 +5,8 @@ export * from "./src/types/wallet-daemon-client/AccountGetDefaultRequest";
 export * from "./src/types/wallet-daemon-client/AccountGetRequest";
 export * from "./src/types/wallet-daemon-client/AccountGetResponse";
 export * from "./src/types/wallet-daemon-client/AccountInfo";
+export * from "./src/types/wallet-daemon-client/AccountsBulkTransferRequest";
+export * from "./src/types/wallet-daemon-client/AccountsBulkTransferResponse";
 export * from "./src/types/wallet-daemon-client/AccountsCreateFreeTestCoinsRequest";
 export * from "./src/types/wallet-daemon-client/AccountsCreateFreeTestCoinsResponse";
 export * from "./src/types/wallet-daemon-client/AccountsCreateRequest";
@@ -13,12 +15,21 @@ export * from "./src/types/wallet-daemon-client/AccountSetDefaultRequest";
 export * from "./src/types/wallet-daemon-client/AccountSetDefaultResponse";
 export * from "./src/types/wallet-daemon-client/AccountsGetBalancesRequest";
 export * from "./src/types/wallet-daemon-client/AccountsGetBalancesResponse";
+export * from "./src/types/wallet-daemon-client/AccountsGetQueueRequest";
+export * from "./src/types/wallet-daemon-client/AccountsGetQueueResponse";
+export * from "./src/types/wallet-daemon-client/AccountsGetVaultsRequest";
+export * from "./src/types/wallet-daemon-client/AccountsGetVaultsResponse";
 export * from "./src/types/wallet-daemon-client/AccountsInvokeRequest";
 export * from "./src/types/wallet-daemon-client/AccountsInvokeResponse";
 export * from "./src/types/wallet-daemon-client/AccountsListRequest";
 export * from "./src/types/wallet-daemon-client/AccountsListResponse";
+export * from "./src/types/wallet-daemon-client/AccountsSetQueueEnabledRequest";
+export * from "./src/types/wallet-daemon-client/AccountsSetQueueEnabledResponse";
+export * from "./src/types/wallet-daemon-client/AccountsSkipQueuedRequest";
+export * from "./src/types/wallet-daemon-client/AccountsSkipQueuedResponse";
 export * from "./src/types/wallet-daemon-client/AccountsTransferRequest";
 export * from "./src/types/wallet-daemon-client/AccountsTransferResponse";
+export * from "./src/types/wallet-daemon-client/AccountVaultEntry";
 export * from "./src/types/wallet-daemon-client/AuthGetAllJwtRequest";
 export * from "./src/types/wallet-daemon-client/AuthGetAllJwtResponse";
 export * from "./src/types/wallet-daemon-client/AuthLoginAcceptRequest";
@@ -30,6 +41,9 @@ export * from "./src/types/wallet-daemon-client/AuthLoginResponse";
 export * from "./src/types/wallet-daemon-client/AuthRevokeTokenRequest";
 export * from "./src/types/wallet-daemon-client/AuthRevokeTokenResponse";
 export * from "./src/types/wallet-daemon-client/BalanceEntry";
+export * from "./src/types/wallet-daemon-client/BulkTransferOutcome";
+export * from "./src/types/wallet-daemon-client/BulkTransferRecipient";
+export * from "./src/types/wallet-daemon-client/BulkTransferSummary";
 export * from "./src/types/wallet-daemon-client/CallInstructionRequest";
 export * from "./src/types/wallet-daemon-client/ClaimBurnRequest";
 export * from "./src/types/wallet-daemon-client/ClaimBurnResponse";
@@ -43,6 +57,8 @@ export * from "./src/types/wallet-daemon-client/ConfidentialTransferResponse";
 export * from "./src/types/wallet-daemon-client/ConfidentialViewVaultBalanceRequest";
 export * from "./src/types/wallet-daemon-client/ConfidentialViewVaultBalanceResponse";
 export * from "./src/types/wallet-daemon-client/GetAccountNftRequest";
+export * from "./src/types/wallet-daemon-client/GetMessageCatalogueRequest";
+export * from "./src/types/wallet-daemon-client/GetMessageCatalogueResponse";
 export * from "./src/types/wallet-daemon-client/GetValidatorFeesRequest";
 export * from "./src/types/wallet-daemon-client/GetValidatorFeesResponse";
 export * from "./src/types/wallet-daemon-client/KeyBranch";
@@ -54,8 +70,14 @@ export * from "./src/types/wallet-daemon-client/KeysSetActiveRequest";
 export * from "./src/types/wallet-daemon-client/KeysSetActiveResponse";
 export * from "./src/types/wallet-daemon-client/ListAccountNftRequest";
 export * from "./src/types/wallet-daemon-client/ListAccountNftResponse";
+export * from "./src/types/wallet-daemon-client/MessageCatalogueEntry";
+export * from "./src/types/wallet-daemon-client/MessageParamSchema";
 export * from "./src/types/wallet-daemon-client/MintAccountNftRequest";
 export * from "./src/types/wallet-daemon-client/MintAccountNftResponse";
+export * from "./src/types/wallet-daemon-client/ProfilesListRequest";
+export * from "./src/types/wallet-daemon-client/ProfilesListResponse";
+export * from "./src/types/wallet-daemon-client/ProfilesSwitchRequest";
+export * from "./src/types/wallet-daemon-client/ProfilesSwitchResponse";
 export * from "./src/types/wallet-daemon-client/ProofsCancelRequest";
 export * from "./src/types/wallet-daemon-client/ProofsCancelResponse";
 export * from "./src/types/wallet-daemon-client/ProofsFinalizeRequest";
@@ -73,6 +95,8 @@ export * from "./src/types/wallet-daemon-client/SubstatesListRequest";
 export * from "./src/types/wallet-daemon-client/SubstatesListResponse";
 export * from "./src/types/wallet-daemon-client/TemplatesGetRequest";
 export * from "./src/types/wallet-daemon-client/TemplatesGetResponse";
+export * from "./src/types/wallet-daemon-client/TransactionCancelScheduledRequest";
+export * from "./src/types/wallet-daemon-client/TransactionCancelScheduledResponse";
 export * from "./src/types/wallet-daemon-client/TransactionClaimBurnResponse";
 export * from "./src/types/wallet-daemon-client/TransactionGetAllRequest";
 export * from "./src/types/wallet-daemon-client/TransactionGetAllResponse";
@@ -80,10 +104,19 @@ export * from "./src/types/wallet-daemon-client/TransactionGetRequest";
 export * from "./src/types/wallet-daemon-client/TransactionGetResponse";
 export * from "./src/types/wallet-daemon-client/TransactionGetResultRequest";
 export * from "./src/types/wallet-daemon-client/TransactionGetResultResponse";
+export * from "./src/types/wallet-daemon-client/TransactionGetSigningRequestRequest";
+export * from "./src/types/wallet-daemon-client/TransactionGetSigningRequestResponse";
+export * from "./src/types/wallet-daemon-client/TransactionListScheduledRequest";
+export * from "./src/types/wallet-daemon-client/TransactionListScheduledResponse";
+export * from "./src/types/wallet-daemon-client/TransactionScheduleRequest";
+export * from "./src/types/wallet-daemon-client/TransactionScheduleResponse";
 export * from "./src/types/wallet-daemon-client/TransactionSubmitRequest";
 export * from "./src/types/wallet-daemon-client/TransactionSubmitResponse";
+export * from "./src/types/wallet-daemon-client/TransactionSubmitSignatureRequest";
+export * from "./src/types/wallet-daemon-client/TransactionSubmitSignatureResponse";
 export * from "./src/types/wallet-daemon-client/TransactionWaitResultRequest";
 export * from "./src/types/wallet-daemon-client/TransactionWaitResultResponse";
+export * from "./src/types/wallet-daemon-client/WalletProfileInfo";
 export * from "./src/types/wallet-daemon-client/WalletSubstateRecord";
 export * from "./src/types/wallet-daemon-client/WebRtcStart";
 export * from "./src/types/wallet-daemon-client/WebRtcStartRequest";
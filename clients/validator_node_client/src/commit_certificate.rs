@@ -0,0 +1,198 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{vn_node_hash, SubstateAddress};
+use tari_dan_storage::consensus_models::{
+    vote_signature_challenge,
+    Block,
+    BlockId,
+    QcId,
+    QuorumCertificate,
+    QuorumDecision,
+};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+/// Proof that a block is committed. The proof is a chain of blocks that starts with the committed block and ends with
+/// a 3-chain b, b', b'' together with the QC for b''. A block that was committed as an ancestor of the 3-chain is
+/// linked to it by its committed descendants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct CommitCertificate {
+    /// The committed block followed by its descendants up to and including the block certified by `commit_qc`
+    pub blocks: Vec<Block>,
+    /// The QC for the last block in `blocks`
+    pub commit_qc: QuorumCertificate,
+    /// The committee that signed the QCs of the 3-chain
+    pub committee: Vec<CommitteeMemberKey>,
+}
+
+/// The keys of a committee member that are needed to verify its vote signatures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct CommitteeMemberKey {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    pub shard_key: SubstateAddress,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommitCertificateError {
+    #[error("Commit certificate must contain at least 3 blocks but contains {len}")]
+    ChainTooShort { len: usize },
+    #[error("Block {block_id} does not match its hash")]
+    InvalidBlockId { block_id: BlockId },
+    #[error("Block {block_id} is not a child of block {parent_id}")]
+    BrokenChain { block_id: BlockId, parent_id: BlockId },
+    #[error("QC {qc_id} does not certify block {block_id}")]
+    QcBlockMismatch { qc_id: QcId, block_id: BlockId },
+    #[error("QC {qc_id} is invalid: {details}")]
+    InvalidQc { qc_id: QcId, details: String },
+    #[error("QC {qc_id} has {count} valid signatures but requires {threshold}")]
+    InsufficientSignatures {
+        qc_id: QcId,
+        count: usize,
+        threshold: usize,
+    },
+}
+
+impl CommitCertificate {
+    /// Returns the block that this certificate proves is committed
+    pub fn block(&self) -> Option<&Block> {
+        self.blocks.first()
+    }
+
+    /// Verifies that the block chain is linked, that it ends with a 3-chain and that each QC of the 3-chain is signed
+    /// by a quorum of the committee. This does not check that the committee is the registered committee for the
+    /// epoch; callers should check the committee keys against an independent source such as the base layer.
+    pub fn verify(&self) -> Result<(), CommitCertificateError> {
+        verify_commit_certificate(self)
+    }
+}
+
+/// Verifies a commit certificate without requiring access to a state store. See [CommitCertificate::verify].
+pub fn verify_commit_certificate(certificate: &CommitCertificate) -> Result<(), CommitCertificateError> {
+    let blocks = &certificate.blocks;
+    if blocks.len() < 3 {
+        return Err(CommitCertificateError::ChainTooShort { len: blocks.len() });
+    }
+
+    for block in blocks {
+        if *block.id().hash() != block.calculate_hash() {
+            return Err(CommitCertificateError::InvalidBlockId { block_id: *block.id() });
+        }
+    }
+
+    for pair in blocks.windows(2) {
+        if pair[1].parent() != pair[0].id() {
+            return Err(CommitCertificateError::BrokenChain {
+                block_id: *pair[1].id(),
+                parent_id: *pair[0].id(),
+            });
+        }
+    }
+
+    // b <- b'.justify, b' <- b''.justify, b'' <- commit QC
+    let three_chain = &blocks[blocks.len() - 3..];
+    let qcs = [
+        three_chain[1].justify(),
+        three_chain[2].justify(),
+        &certificate.commit_qc,
+    ];
+    for (block, qc) in three_chain.iter().zip(qcs) {
+        verify_qc(certificate, block, qc)?;
+    }
+
+    Ok(())
+}
+
+fn verify_qc(
+    certificate: &CommitCertificate,
+    block: &Block,
+    qc: &QuorumCertificate,
+) -> Result<(), CommitCertificateError> {
+    if qc.block_id() != block.id() || qc.block_height() != block.height() {
+        return Err(CommitCertificateError::QcBlockMismatch {
+            qc_id: *qc.id(),
+            block_id: *block.id(),
+        });
+    }
+
+    let invalid_qc = |details: String| CommitCertificateError::InvalidQc {
+        qc_id: *qc.id(),
+        details,
+    };
+
+    if qc.calculate_id() != *qc.id() {
+        return Err(invalid_qc("QC id does not match its hash".to_string()));
+    }
+    if qc.decision() != QuorumDecision::Accept {
+        return Err(invalid_qc(format!("QC decision is {}", qc.decision())));
+    }
+    if qc.epoch() != certificate.commit_qc.epoch() || qc.shard() != certificate.commit_qc.shard() {
+        return Err(invalid_qc(format!(
+            "QC is for epoch {} and shard {} but the commit QC is for epoch {} and shard {}",
+            qc.epoch(),
+            qc.shard(),
+            certificate.commit_qc.epoch(),
+            certificate.commit_qc.shard()
+        )));
+    }
+
+    let mut signers = HashSet::with_capacity(qc.signatures().len());
+    for signature in qc.signatures() {
+        let member = certificate
+            .committee
+            .iter()
+            .find(|m| m.public_key == signature.public_key)
+            .ok_or_else(|| invalid_qc(format!("{} is not a committee member", signature.public_key)))?;
+
+        let leaf_hash = vn_node_hash(block.network(), &member.public_key, &member.shard_key);
+        if !qc.leaf_hashes().contains(&leaf_hash) {
+            return Err(invalid_qc(format!(
+                "QC does not contain the leaf hash of {}",
+                member.public_key
+            )));
+        }
+
+        let challenge = vote_signature_challenge(&leaf_hash, qc.block_id(), &qc.decision());
+        if !signature.verify(challenge) {
+            return Err(invalid_qc(format!("Invalid signature from {}", member.public_key)));
+        }
+
+        if !signers.insert(&member.public_key) {
+            return Err(invalid_qc(format!("Duplicate signature from {}", member.public_key)));
+        }
+    }
+
+    let threshold = quorum_threshold(certificate.committee.len());
+    if signers.len() < threshold {
+        return Err(CommitCertificateError::InsufficientSignatures {
+            qc_id: *qc.id(),
+            count: signers.len(),
+            threshold,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns $n - f$ where n is the number of committee members and f is the tolerated failure nodes
+fn quorum_threshold(num_members: usize) -> usize {
+    if num_members == 0 {
+        return 1;
+    }
+    num_members - (num_members - 1) / 3
+}
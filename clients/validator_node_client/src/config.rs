@@ -0,0 +1,65 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct ValidatorNodeClientConfig {
+    /// The maximum time to wait for a response to a request
+    pub request_timeout: Duration,
+    /// The maximum time to wait for a connection to the validator node to be established
+    pub connect_timeout: Duration,
+    /// How long an idle pooled connection is kept open
+    pub pool_idle_timeout: Duration,
+    /// The retry policy for requests that only read from the validator node. Requests that change state on the node
+    /// are never retried.
+    pub retry_policy: RetryPolicy,
+}
+
+impl Default for ValidatorNodeClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            pool_idle_timeout: Duration::from_secs(90),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// The number of times a failed request is retried
+    pub max_retries: usize,
+    /// The delay before the first retry. The delay doubles on each subsequent retry up to `max_backoff`.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Returns the delay before the given retry attempt, starting at 1
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX).min(16);
+        self.initial_backoff
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
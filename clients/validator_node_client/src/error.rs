@@ -20,6 +20,17 @@ pub enum ValidatorNodeClientError {
     InvalidResponse { message: String },
 }
 
+impl ValidatorNodeClientError {
+    /// Returns true if the request may succeed if it is sent again i.e. the validator node could not be reached or did
+    /// not respond in time
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RequestFailed { source } => source.is_connect() || source.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
 impl IsNotFoundError for ValidatorNodeClientError {
     fn is_not_found_error(&self) -> bool {
         match self {
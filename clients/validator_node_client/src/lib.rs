@@ -19,11 +19,23 @@
 //   SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+mod commit_certificate;
+pub use commit_certificate::{
+    verify_commit_certificate,
+    CommitCertificate,
+    CommitCertificateError,
+    CommitteeMemberKey,
+};
+
+mod config;
+pub use config::{RetryPolicy, ValidatorNodeClientConfig};
+
 mod error;
 pub use error::ValidatorNodeClientError;
 
 pub mod types;
 
+use log::*;
 use reqwest::{header, header::HeaderMap, IntoUrl, Url};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json as json;
@@ -31,96 +43,195 @@ use serde_json::json;
 
 use crate::types::*;
 
+const LOG_TARGET: &str = "tari::dan::validator_node_client";
+
 #[derive(Debug, Clone)]
 pub struct ValidatorNodeClient {
     client: reqwest::Client,
     endpoint: Url,
     request_id: i64,
+    retry_policy: RetryPolicy,
 }
 
 impl ValidatorNodeClient {
     pub fn connect<T: IntoUrl>(endpoint: T) -> Result<Self, ValidatorNodeClientError> {
+        Self::connect_with_config(endpoint, ValidatorNodeClientConfig::default())
+    }
+
+    /// Creates a client for the given endpoint. Connections to the endpoint are pooled and reused across requests and
+    /// clones of the client.
+    pub fn connect_with_config<T: IntoUrl>(
+        endpoint: T,
+        config: ValidatorNodeClientConfig,
+    ) -> Result<Self, ValidatorNodeClientError> {
         let client = reqwest::Client::builder()
             .default_headers({
                 let mut headers = HeaderMap::with_capacity(1);
                 headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
                 headers
             })
+            .timeout(config.request_timeout)
+            .connect_timeout(config.connect_timeout)
+            .pool_idle_timeout(config.pool_idle_timeout)
             .build()?;
 
         Ok(Self {
             client,
             endpoint: endpoint.into_url()?,
             request_id: 0,
+            retry_policy: config.retry_policy,
         })
     }
 
     pub async fn get_identity(&mut self) -> Result<GetIdentityResponse, ValidatorNodeClientError> {
-        self.send_request("get_identity", json!({})).await
+        self.send_read_request("get_identity", json!({})).await
     }
 
     pub async fn get_epoch_manager_stats(&mut self) -> Result<GetEpochManagerStatsResponse, ValidatorNodeClientError> {
-        self.send_request("get_epoch_manager_stats", json!({})).await
+        self.send_read_request("get_epoch_manager_stats", json!({})).await
     }
 
     pub async fn get_active_templates(
         &mut self,
         request: GetTemplatesRequest,
     ) -> Result<GetTemplatesResponse, ValidatorNodeClientError> {
-        self.send_request("get_templates", request).await
+        self.send_read_request("get_templates", request).await
     }
 
     pub async fn get_state(&mut self, request: GetStateRequest) -> Result<GetStateResponse, ValidatorNodeClientError> {
-        self.send_request("get_state", request).await
+        self.send_read_request("get_state", request).await
     }
 
     pub async fn get_substate(
         &mut self,
         request: GetSubstateRequest,
     ) -> Result<GetSubstateResponse, ValidatorNodeClientError> {
-        self.send_request("get_substate", request).await
+        self.send_read_request("get_substate", request).await
+    }
+
+    pub async fn get_substate_decoded(
+        &mut self,
+        request: GetSubstateDecodedRequest,
+    ) -> Result<GetSubstateDecodedResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_substate_decoded", request).await
+    }
+
+    pub async fn get_substates_created_by_transaction(
+        &mut self,
+        request: GetSubstatesByTransactionRequest,
+    ) -> Result<GetSubstatesByTransactionResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_substates_created_by_transaction", request)
+            .await
+    }
+
+    pub async fn get_substates_destroyed_by_transaction(
+        &mut self,
+        request: GetSubstatesByTransactionRequest,
+    ) -> Result<GetSubstatesByTransactionResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_substates_destroyed_by_transaction", request)
+            .await
     }
 
     pub async fn get_fees(
         &mut self,
         request: GetValidatorFeesRequest,
     ) -> Result<GetValidatorFeesResponse, ValidatorNodeClientError> {
-        self.send_request("get_fees", request).await
+        self.send_read_request("get_fees", request).await
+    }
+
+    pub async fn get_shard_key(
+        &mut self,
+        request: GetShardKeyRequest,
+    ) -> Result<GetShardKeyResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_shard_key", request).await
+    }
+
+    pub async fn get_committee(
+        &mut self,
+        request: GetCommitteeRequest,
+    ) -> Result<GetCommitteeResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_committee", request).await
+    }
+
+    pub async fn get_all_vns(
+        &mut self,
+        request: GetAllVnsRequest,
+    ) -> Result<GetAllVnsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_all_vns", request).await
     }
 
     pub async fn get_template(
         &mut self,
         request: GetTemplateRequest,
     ) -> Result<GetTemplateResponse, ValidatorNodeClientError> {
-        self.send_request("get_template", request).await
+        self.send_read_request("get_template", request).await
+    }
+
+    pub async fn upload_template_begin(
+        &mut self,
+        request: UploadTemplateBeginRequest,
+    ) -> Result<UploadTemplateBeginResponse, ValidatorNodeClientError> {
+        self.send_request("templates.upload_begin", request).await
+    }
+
+    pub async fn upload_template_chunk(
+        &mut self,
+        request: UploadTemplateChunkRequest,
+    ) -> Result<UploadTemplateChunkResponse, ValidatorNodeClientError> {
+        self.send_request("templates.upload_chunk", request).await
+    }
+
+    pub async fn upload_template_finish(
+        &mut self,
+        request: UploadTemplateFinishRequest,
+    ) -> Result<UploadTemplateFinishResponse, ValidatorNodeClientError> {
+        self.send_request("templates.upload_finish", request).await
     }
 
     pub async fn get_transaction(
         &mut self,
         request: GetTransactionRequest,
     ) -> Result<GetTransactionResponse, ValidatorNodeClientError> {
-        self.send_request("get_transaction", request).await
+        self.send_read_request("get_transaction", request).await
     }
 
     pub async fn get_transaction_result(
         &mut self,
         request: GetTransactionResultRequest,
     ) -> Result<GetTransactionResultResponse, ValidatorNodeClientError> {
-        self.send_request("get_transaction_result", request).await
+        self.send_read_request("get_transaction_result", request).await
     }
 
     pub async fn get_recent_transactions(
         &mut self,
         request: GetRecentTransactionsRequest,
     ) -> Result<GetRecentTransactionsResponse, ValidatorNodeClientError> {
-        self.send_request("get_recent_transactions", request).await
+        self.send_read_request("get_recent_transactions", request).await
+    }
+
+    pub async fn get_transactions_after(
+        &mut self,
+        request: GetTransactionsAfterRequest,
+    ) -> Result<GetTransactionsAfterResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_transactions_after", request).await
+    }
+
+    pub async fn get_tx_pool(&mut self) -> Result<GetTxPoolResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_tx_pool", json!({})).await
+    }
+
+    pub async fn get_transaction_dag(
+        &mut self,
+        request: GetTransactionDagRequest,
+    ) -> Result<GetTransactionDagResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_transaction_dag", request).await
     }
 
     pub async fn list_blocks(
         &mut self,
         request: ListBlocksRequest,
     ) -> Result<ListBlocksResponse, ValidatorNodeClientError> {
-        self.send_request("list_blocks", request).await
+        self.send_read_request("list_blocks", request).await
     }
 
     pub async fn submit_transaction(
@@ -134,12 +245,130 @@ impl ValidatorNodeClient {
         self.send_request("add_peer", request).await
     }
 
+    pub async fn get_comms_stats(&mut self) -> Result<GetCommsStatsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_comms_stats", json!({})).await
+    }
+
+    pub async fn get_connections(&mut self) -> Result<GetConnectionsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_connections", json!({})).await
+    }
+
     pub async fn get_blocks_count(&mut self) -> Result<GetBlocksCountResponse, ValidatorNodeClientError> {
-        self.send_request("get_blocks_count", json!({})).await
+        self.send_read_request("get_blocks_count", json!({})).await
     }
 
     pub async fn get_block(&mut self, request: GetBlockRequest) -> Result<GetBlockResponse, ValidatorNodeClientError> {
-        self.send_request("get_block", request).await
+        self.send_read_request("get_block", request).await
+    }
+
+    pub async fn get_commit_certificate(
+        &mut self,
+        request: GetCommitCertificateRequest,
+    ) -> Result<GetCommitCertificateResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_commit_certificate", request).await
+    }
+
+    pub async fn get_blocks(
+        &mut self,
+        request: GetBlocksRequest,
+    ) -> Result<GetBlocksResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_blocks", request).await
+    }
+
+    pub async fn get_filtered_blocks_count(
+        &mut self,
+        request: GetFilteredBlocksCountRequest,
+    ) -> Result<GetBlocksCountResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_filtered_blocks_count", request).await
+    }
+
+    pub async fn get_blocks_after(
+        &mut self,
+        request: GetBlocksAfterRequest,
+    ) -> Result<GetBlocksAfterResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_blocks_after", request).await
+    }
+
+    pub async fn get_block_rejections(
+        &mut self,
+        request: GetBlockRejectionsRequest,
+    ) -> Result<GetBlockRejectionsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_block_rejections", request).await
+    }
+
+    pub async fn get_equivocation_evidence(
+        &mut self,
+    ) -> Result<GetEquivocationEvidenceResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_equivocation_evidence", json!({})).await
+    }
+
+    pub async fn get_misbehaving_peers(&mut self) -> Result<GetMisbehavingPeersResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_misbehaving_peers", json!({})).await
+    }
+
+    /// Lifts the ban on the peer and resets its misbehavior score
+    pub async fn unban_peer(
+        &mut self,
+        request: UnbanPeerRequest,
+    ) -> Result<UnbanPeerResponse, ValidatorNodeClientError> {
+        self.send_request("unban_peer", request).await
+    }
+
+    pub async fn get_qc_timings(
+        &mut self,
+        request: GetQcTimingsRequest,
+    ) -> Result<GetQcTimingsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_qc_timings", request).await
+    }
+
+    pub async fn get_shard_growth(
+        &mut self,
+        request: GetShardGrowthRequest,
+    ) -> Result<GetShardGrowthResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_shard_growth", request).await
+    }
+
+    pub async fn get_nfts_by_owner(
+        &mut self,
+        request: GetNftsByOwnerRequest,
+    ) -> Result<GetNftsByOwnerResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_nfts_by_owner", request).await
+    }
+
+    pub async fn get_nft_owner(
+        &mut self,
+        request: GetNftOwnerRequest,
+    ) -> Result<GetNftOwnerResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_nft_owner", request).await
+    }
+
+    pub async fn get_consensus_status(&mut self) -> Result<GetConsensusStatusResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_consensus_status", json!({})).await
+    }
+
+    pub async fn get_sync_status(&mut self) -> Result<GetSyncStatusResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_sync_status", json!({})).await
+    }
+
+    pub async fn get_mempool_stats(&mut self) -> Result<GetMempoolStatsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_mempool_stats", json!({})).await
+    }
+
+    pub async fn get_db_stats(&mut self) -> Result<GetDbStatsResponse, ValidatorNodeClientError> {
+        self.send_read_request("get_db_stats", json!({})).await
+    }
+
+    /// Puts the node into maintenance mode. The node keeps in sync but no longer proposes or votes.
+    pub async fn enter_maintenance_mode(&mut self) -> Result<MaintenanceModeResponse, ValidatorNodeClientError> {
+        self.send_request("maintenance", json!({})).await
+    }
+
+    pub async fn resume(&mut self) -> Result<MaintenanceModeResponse, ValidatorNodeClientError> {
+        self.send_request("resume", json!({})).await
+    }
+
+    pub async fn reload_config(&mut self) -> Result<ReloadConfigResponse, ValidatorNodeClientError> {
+        self.send_request("config.reload", json!({})).await
     }
 
     fn next_request_id(&mut self) -> i64 {
@@ -147,6 +376,39 @@ impl ValidatorNodeClient {
         self.request_id
     }
 
+    /// Sends a request that only reads from the validator node. The request is retried according to the retry policy if
+    /// the validator node could not be reached.
+    async fn send_read_request<T: Serialize, R: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: T,
+    ) -> Result<R, ValidatorNodeClientError> {
+        let params = json::to_value(params).map_err(|e| ValidatorNodeClientError::SerializeRequest {
+            source: e,
+            method: method.to_string(),
+        })?;
+        let mut attempt = 0;
+        loop {
+            match self.send_request(method, &params).await {
+                Err(err) if err.is_retryable() && attempt < self.retry_policy.max_retries => {
+                    attempt += 1;
+                    let backoff = self.retry_policy.backoff(attempt);
+                    warn!(
+                        target: LOG_TARGET,
+                        "Request {} failed ({}). Retrying in {:.2?} (attempt {}/{})",
+                        method,
+                        err,
+                        backoff,
+                        attempt,
+                        self.retry_policy.max_retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                },
+                result => break result,
+            }
+        }
+    }
+
     async fn send_request<T: Serialize, R: DeserializeOwned>(
         &mut self,
         method: &str,
@@ -20,7 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{ops::RangeInclusive, sync::Arc, time::Duration};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc, time::Duration};
 
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -30,18 +30,31 @@ use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
     shard::Shard,
     Epoch,
+    NodeHeight,
     PeerAddress,
     SubstateAddress,
 };
 use tari_dan_storage::{
     consensus_models::{
         Block,
+        BlockCursor,
         BlockId,
+        BlockRejection,
         Decision,
+        EquivocationEvidence,
         ExecutedTransaction,
+        NftOwnership,
+        QcTiming,
         QuorumDecision,
+        RecentTransaction,
+        ShardGrowthSample,
         SubstateRecord,
+        SyncCheckpoint,
+        TransactionConflictEdge,
+        TransactionCursor,
         TransactionPoolRecord,
+        TransactionPoolStage,
+        ValidatorVoteLateness,
     },
     global::models,
     Ordering,
@@ -53,10 +66,13 @@ use tari_engine_types::{
     substate::{SubstateId, SubstateValue},
     TemplateAddress,
 };
+use tari_template_lib::models::{ComponentAddress, NonFungibleId, ResourceAddress};
 use tari_transaction::{Transaction, TransactionId};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
+use crate::CommitCertificate;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -154,6 +170,80 @@ pub struct GetTemplatesResponse {
     pub templates: Vec<TemplateMetadata>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateBeginRequest {
+    pub template_name: String,
+    /// The size in bytes of the complete template binary
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_size: u64,
+    /// The template binary hash, which becomes the address of the uploaded template
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub expected_hash: FixedHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateBeginResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub upload_id: FixedHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateChunkRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub upload_id: FixedHash,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub offset: u64,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::base64")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateChunkResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateFinishRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub upload_id: FixedHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UploadTemplateFinishResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -298,13 +388,31 @@ pub struct GetTransactionResultResponse {
     pub execution_time: Option<Duration>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/validator-node-client/")
 )]
-pub struct GetRecentTransactionsRequest {}
+pub struct GetRecentTransactionsRequest {
+    /// The maximum number of transactions to return. Defaults to 1000.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+    /// The cursor returned by the previous page, or None to fetch the most recent transactions
+    #[serde(default)]
+    pub cursor: Option<TransactionCursor>,
+    #[serde(default)]
+    pub decision: Option<Decision>,
+    #[serde(default)]
+    pub from_epoch: Option<Epoch>,
+    #[serde(default)]
+    pub to_epoch: Option<Epoch>,
+    /// If provided, only transactions that have this substate as an input or output are returned
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub involved_substate_id: Option<SubstateId>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
@@ -313,7 +421,33 @@ pub struct GetRecentTransactionsRequest {}
     ts(export, export_to = "../../bindings/src/types/validator-node-client/")
 )]
 pub struct GetRecentTransactionsResponse {
+    pub transactions: Vec<RecentTransaction>,
+    pub next_cursor: Option<TransactionCursor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTransactionsAfterRequest {
+    /// The cursor returned by the previous page, or None to fetch the first page
+    pub cursor: Option<TransactionCursor>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub limit: u64,
+    pub ordering: Ordering,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTransactionsAfterResponse {
     pub transactions: Vec<Transaction>,
+    pub next_cursor: Option<TransactionCursor>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -360,6 +494,27 @@ pub struct GetBlockResponse {
     pub block: Block,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetCommitCertificateRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetCommitCertificateResponse {
+    pub certificate: CommitCertificate,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -370,6 +525,31 @@ pub struct GetBlocksResponse {
     pub blocks: Vec<Block>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetBlocksAfterRequest {
+    /// The cursor returned by the previous page, or None to fetch the first page
+    pub cursor: Option<BlockCursor>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub limit: u64,
+    pub ordering: Ordering,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetBlocksAfterResponse {
+    pub blocks: Vec<Block>,
+    pub next_cursor: Option<BlockCursor>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -562,6 +742,70 @@ pub enum SubstateStatus {
     DoesNotExist,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSubstateDecodedRequest {
+    pub address: SubstateId,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSubstateDecodedResponse {
+    pub value: Option<DecodedValue>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub created_by_tx: Option<TransactionId>,
+    pub status: SubstateStatus,
+}
+
+/// A substate value decoded using the component schema of its template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub enum DecodedValue {
+    /// A struct that is described by the schema
+    Struct { name: String, fields: Vec<DecodedField> },
+    /// A value that could not be decoded using the schema, encoded as JSON
+    Raw(#[cfg_attr(feature = "ts", ts(type = "any"))] serde_json::Value),
+}
+
+impl DecodedValue {
+    pub fn is_raw(&self) -> bool {
+        matches!(self, DecodedValue::Raw(_))
+    }
+
+    pub fn get_field(&self, name: &str) -> Option<&DecodedField> {
+        match self {
+            DecodedValue::Struct { fields, .. } => fields.iter().find(|f| f.name == name),
+            DecodedValue::Raw(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct DecodedField {
+    pub name: String,
+    /// The field type as declared in the template
+    pub field_type: String,
+    pub value: DecodedValue,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -695,6 +939,8 @@ pub struct GetBlockRequest {
 pub struct GetBlocksRequest {
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub limit: u64,
+    /// Deprecated: offset pagination can skip or repeat blocks that are inserted between page fetches. Use
+    /// `get_blocks_after` instead.
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub offset: u64,
     pub ordering_index: Option<usize>,
@@ -703,6 +949,119 @@ pub struct GetBlocksRequest {
     pub filter: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetBlockRejectionsRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub limit: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub offset: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetBlockRejectionsResponse {
+    /// The rejected proposals, most recent first
+    pub rejections: Vec<BlockRejection>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetEquivocationEvidenceResponse {
+    /// Evidence of validators that voted for two different blocks at the same height, most recent first
+    pub evidence: Vec<EquivocationEvidence>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct MisbehavingPeer {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub address: PeerAddress,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub score: u64,
+    /// The time of the most recent offence in seconds since the unix epoch
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub last_offence: u64,
+    pub reason: String,
+    /// Consensus messages from the peer are dropped until this time in seconds since the unix epoch
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub banned_until: Option<u64>,
+    pub is_banned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetMisbehavingPeersResponse {
+    /// Peers that sent invalid proposals or votes, highest score first
+    pub peers: Vec<MisbehavingPeer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UnbanPeerRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub address: PeerAddress,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct UnbanPeerResponse {
+    /// False if the peer had no misbehavior record
+    pub is_removed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetQcTimingsRequest {
+    pub epoch: Epoch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetQcTimingsResponse {
+    pub timings: Vec<QcTiming>,
+    /// The average vote lateness of each validator, ordered from the earliest to the latest voter
+    pub validators: Vec<ValidatorVoteLateness>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -763,3 +1122,243 @@ pub struct GetConnectionsResponse {
 pub struct GetMempoolStatsResponse {
     pub size: usize,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetDbStatsResponse {
+    pub state_store: DbMaintenanceInfo,
+    pub global_db: DbMaintenanceInfo,
+    /// The most recent state store backup
+    #[serde(default)]
+    pub state_store_backup: DbMaintenanceInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetConsensusStatusResponse {
+    /// The state of the consensus state machine e.g. Running or Syncing
+    pub state: String,
+    /// True if the node is in maintenance mode and is not proposing or voting
+    pub is_in_maintenance_mode: bool,
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub leaf_block_id: BlockId,
+    pub leaf_block_height: NodeHeight,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub locked_block_id: BlockId,
+    pub locked_block_height: NodeHeight,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub high_qc_block_id: BlockId,
+    pub high_qc_block_height: NodeHeight,
+    /// The height of the last block that the node voted for, or None if it has not voted
+    pub last_voted_height: Option<NodeHeight>,
+    /// The height of the view that the pacemaker is currently waiting on
+    pub current_view_height: NodeHeight,
+    /// The leader timeout of the current view in milliseconds
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub current_view_timeout_ms: u64,
+    /// True if the node is the leader for the block after the leaf block
+    pub is_leader: bool,
+    /// The number of transactions in the pool for each stage
+    pub transaction_pool_stage_counts: HashMap<String, usize>,
+    /// The number of times a leader was detected proposing two different blocks for the same height
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub proposer_equivocation_count: u64,
+    /// The number of dummy blocks created for heights where the leader failed to propose
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub dummy_block_count: u64,
+    /// The number of leader timeouts in the epoch before the current epoch
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub leader_timeouts_last_epoch: u64,
+    /// True if new transaction submissions are refused because state store writes are waiting too long
+    #[serde(default)]
+    pub is_shedding_load: bool,
+    /// The time in milliseconds that the most recent state store write waited for the writer
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub write_queue_last_wait_ms: u64,
+    /// The longest time in milliseconds that a state store write has waited for the writer
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub write_queue_max_wait_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSyncStatusResponse {
+    /// The checkpoint that block sync resumes from, or None if the node has not synced any blocks
+    pub checkpoint: Option<SyncCheckpoint>,
+    /// The number of blocks synced by the current or most recent sync since the node started
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub blocks_synced: u64,
+    /// The estimated number of blocks remaining, or None if the node has not synced since it started
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub estimated_remaining: Option<u64>,
+}
+
+/// The response to the maintenance and resume requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct MaintenanceModeResponse {
+    pub is_in_maintenance_mode: bool,
+    /// False if the node was already in the requested mode
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct ReloadConfigResponse {
+    /// The changed config keys that were applied to the running node
+    pub applied: Vec<String>,
+    /// The changed config keys that only take effect after a restart
+    pub requires_restart: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct DbMaintenanceInfo {
+    /// Unix timestamp in seconds of the last completed maintenance run
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub last_maintenance_at: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub last_maintenance_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetShardGrowthRequest {
+    /// The maximum number of most recent samples to return. Defaults to all stored samples.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetShardGrowthResponse {
+    /// The growth samples of this node's shard, oldest first. Empty if no sample has been taken since the node started.
+    pub samples: Vec<ShardGrowthSample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetNftsByOwnerRequest {
+    pub component_address: ComponentAddress,
+    /// The maximum number of NFTs to return. Defaults to all NFTs owned by the component.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetNftsByOwnerResponse {
+    /// The NFTs held in the component's vaults in this node's shard, ordered by resource address and token id
+    pub nfts: Vec<NftOwnership>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetNftOwnerRequest {
+    pub resource_address: ResourceAddress,
+    pub nft_id: NonFungibleId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetNftOwnerResponse {
+    /// None if the NFT is not held in a vault in this node's shard
+    pub ownership: Option<NftOwnership>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTransactionDagRequest {
+    /// If provided, only transactions that lock this substate are returned
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub substate_id: Option<SubstateId>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTransactionDagResponse {
+    pub nodes: Vec<TransactionDagNode>,
+    pub edges: Vec<TransactionConflictEdge>,
+}
+
+#[derive(Serialize, Debug)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct TransactionDagNode {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub stage: TransactionPoolStage,
+    pub decision: Decision,
+    pub is_ready: bool,
+}
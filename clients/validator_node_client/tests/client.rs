@@ -0,0 +1,177 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use httpmock::{Method::POST, MockServer};
+use serde_json::json;
+use tari_dan_common_types::{optional::IsNotFoundError, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, Decision};
+use tari_engine_types::TemplateAddress;
+use tari_transaction::TransactionId;
+use tari_validator_node_client::{
+    types::{GetBlockRequest, GetTemplateRequest, GetTransactionResultRequest},
+    RetryPolicy,
+    ValidatorNodeClient,
+    ValidatorNodeClientConfig,
+    ValidatorNodeClientError,
+};
+
+fn connect(server: &MockServer) -> ValidatorNodeClient {
+    ValidatorNodeClient::connect(server.url("/json_rpc")).unwrap()
+}
+
+fn success(result: serde_json::Value) -> serde_json::Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+}
+
+#[tokio::test]
+async fn it_gets_a_template() {
+    let server = MockServer::start_async().await;
+    let template_address = TemplateAddress::from_array([1u8; 32]);
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/json_rpc").json_body_partial(
+                json!({
+                    "method": "get_template",
+                    "params": { "template_address": template_address.to_string() },
+                })
+                .to_string(),
+            );
+            then.status(200).json_body(success(json!({
+                "registration_metadata": {
+                    "name": "Counter",
+                    "address": template_address.to_string(),
+                    "url": "http://localhost/counter.wasm",
+                    "binary_sha": [1, 2, 3],
+                    "height": 10,
+                },
+                "abi": { "template_name": "Counter", "functions": [], "version": "0.1.0" },
+            })));
+        })
+        .await;
+
+    let response = connect(&server)
+        .get_template(GetTemplateRequest { template_address })
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.registration_metadata.address, template_address);
+    assert_eq!(response.registration_metadata.height, 10);
+    assert_eq!(response.abi.template_name, "Counter");
+}
+
+#[tokio::test]
+async fn it_maps_a_json_rpc_error_to_a_not_found_error() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/json_rpc")
+                .json_body_partial(json!({ "method": "get_block" }).to_string());
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 404, "message": "Block not found" },
+            }));
+        })
+        .await;
+
+    let err = connect(&server)
+        .get_block(GetBlockRequest {
+            block_id: BlockId::genesis(),
+        })
+        .await
+        .unwrap_err();
+
+    assert!(err.is_not_found_error());
+    assert!(!err.is_retryable());
+    // JSON-RPC errors are returned by the node so the request is not retried
+    assert_eq!(mock.hits_async().await, 1);
+    match err {
+        ValidatorNodeClientError::RequestFailedWithStatus { code, message } => {
+            assert_eq!(code, 404);
+            assert_eq!(message, "Block not found");
+        },
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[tokio::test]
+async fn it_gets_a_transaction_result() {
+    let server = MockServer::start_async().await;
+    let transaction_id = TransactionId::new([2u8; 32]);
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/json_rpc").json_body_partial(
+                json!({
+                    "method": "get_transaction_result",
+                    "params": { "transaction_id": transaction_id.to_string() },
+                })
+                .to_string(),
+            );
+            then.status(200).json_body(success(json!({
+                "result": null,
+                "final_decision": "Commit",
+                "finalized_time": { "secs": 1, "nanos": 0 },
+                "execution_time": null,
+            })));
+        })
+        .await;
+
+    let response = connect(&server)
+        .get_transaction_result(GetTransactionResultRequest { transaction_id })
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.final_decision, Some(Decision::Commit));
+    assert_eq!(response.finalized_time, Some(Duration::from_secs(1)));
+    assert!(response.result.is_none());
+}
+
+#[tokio::test]
+async fn it_gets_the_consensus_status() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/json_rpc")
+                .json_body_partial(json!({ "method": "get_consensus_status", "params": {} }).to_string());
+            then.status(200).json_body(success(json!({
+                "state": "Running",
+                "is_in_maintenance_mode": false,
+                "leaf_block_id": BlockId::genesis().to_string(),
+                "leaf_block_height": 5,
+                "proposer_equivocation_count": 1,
+            })));
+        })
+        .await;
+
+    let response = connect(&server).get_consensus_status().await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.state, "Running");
+    assert!(!response.is_in_maintenance_mode);
+    assert_eq!(response.leaf_block_id, BlockId::genesis());
+    assert_eq!(response.leaf_block_height, NodeHeight(5));
+    assert_eq!(response.proposer_equivocation_count, 1);
+}
+
+#[tokio::test]
+async fn it_returns_a_retryable_error_when_the_node_cannot_be_reached() {
+    // Nothing is listening on the discard port
+    let config = ValidatorNodeClientConfig {
+        retry_policy: RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        },
+        ..Default::default()
+    };
+    let mut client = ValidatorNodeClient::connect_with_config("http://127.0.0.1:9/json_rpc", config).unwrap();
+
+    let err = client.get_blocks_count().await.unwrap_err();
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,169 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PrivateKey, PublicKey};
+use tari_dan_common_types::{
+    crypto::create_key_pair_from_seed,
+    shard::Shard,
+    vn_node_hash,
+    Epoch,
+    NodeHeight,
+    SubstateAddress,
+};
+use tari_dan_storage::consensus_models::{
+    vote_signature_challenge,
+    Block,
+    BlockId,
+    QuorumCertificate,
+    QuorumDecision,
+    ValidatorSignature,
+};
+use tari_validator_node_client::{CommitCertificate, CommitCertificateError, CommitteeMemberKey};
+
+const NETWORK: Network = Network::LocalNet;
+
+struct TestCommittee {
+    members: Vec<(PrivateKey, CommitteeMemberKey)>,
+}
+
+impl TestCommittee {
+    fn new(size: u8) -> Self {
+        let members = (1..=size)
+            .map(|seed| {
+                let (secret_key, public_key) = create_key_pair_from_seed(seed);
+                let key = CommitteeMemberKey {
+                    public_key,
+                    shard_key: SubstateAddress::from([seed; 32]),
+                };
+                (secret_key, key)
+            })
+            .collect();
+        Self { members }
+    }
+
+    fn keys(&self) -> Vec<CommitteeMemberKey> {
+        self.members.iter().map(|(_, key)| key.clone()).collect()
+    }
+
+    /// Returns a QC for the block signed by the first `num_signers` members
+    fn certify(&self, block: &Block, num_signers: usize) -> QuorumCertificate {
+        let (mut signatures, leaf_hashes): (Vec<_>, Vec<_>) = self
+            .members
+            .iter()
+            .take(num_signers)
+            .map(|(secret_key, key)| {
+                let leaf_hash = vn_node_hash(NETWORK, &key.public_key, &key.shard_key);
+                let challenge = vote_signature_challenge(&leaf_hash, block.id(), &QuorumDecision::Accept);
+                (ValidatorSignature::sign(secret_key, challenge), leaf_hash)
+            })
+            .unzip();
+        signatures.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+
+        QuorumCertificate::new(
+            *block.id(),
+            block.height(),
+            block.epoch(),
+            block.shard(),
+            signatures,
+            leaf_hashes,
+            QuorumDecision::Accept,
+        )
+    }
+}
+
+fn create_block(parent_id: BlockId, height: NodeHeight, justify: QuorumCertificate, timestamp: u64) -> Block {
+    Block::new(
+        NETWORK,
+        parent_id,
+        justify,
+        height,
+        Epoch(1),
+        Shard::from(0),
+        PublicKey::default(),
+        Default::default(),
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        timestamp,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+fn create_child(committee: &TestCommittee, parent: &Block, timestamp: u64) -> Block {
+    let justify = committee.certify(parent, committee.members.len());
+    create_block(*parent.id(), parent.height() + NodeHeight(1), justify, timestamp)
+}
+
+/// Creates the chain b0 <- b1 <- b2 <- b3 where each block justifies its parent
+fn create_chain(committee: &TestCommittee) -> Vec<Block> {
+    let b0 = create_block(BlockId::genesis(), NodeHeight(1), QuorumCertificate::genesis(), 0);
+    let b1 = create_child(committee, &b0, 1);
+    let b2 = create_child(committee, &b1, 2);
+    let b3 = create_child(committee, &b2, 3);
+    vec![b0, b1, b2, b3]
+}
+
+#[test]
+fn it_verifies_the_certificate_of_a_committed_block() {
+    let committee = TestCommittee::new(4);
+    let chain = create_chain(&committee);
+
+    // b0 is linked to the 3-chain b1, b2, b3 through its committed child
+    for start in 0..2 {
+        let certificate = CommitCertificate {
+            blocks: chain[start..].to_vec(),
+            commit_qc: committee.certify(&chain[3], 3),
+            committee: committee.keys(),
+        };
+        certificate.verify().unwrap();
+        assert_eq!(certificate.block().unwrap().id(), chain[start].id());
+    }
+}
+
+#[test]
+fn it_rejects_a_certificate_for_a_fork_block() {
+    let committee = TestCommittee::new(4);
+    let chain = create_chain(&committee);
+    // A sibling of b1 that was never extended
+    let fork = create_child(&committee, &chain[0], 100);
+
+    let certificate = CommitCertificate {
+        blocks: vec![fork.clone(), chain[2].clone(), chain[3].clone()],
+        commit_qc: committee.certify(&chain[3], 4),
+        committee: committee.keys(),
+    };
+    let err = certificate.verify().unwrap_err();
+    assert!(
+        matches!(err, CommitCertificateError::BrokenChain { parent_id, .. } if parent_id == *fork.id()),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn it_rejects_a_certificate_for_a_fork_without_a_quorum() {
+    let committee = TestCommittee::new(4);
+    let chain = create_chain(&committee);
+
+    // A fork of b0 that only one member voted for
+    let f1 = create_child(&committee, &chain[0], 100);
+    let f2 = create_block(*f1.id(), f1.height() + NodeHeight(1), committee.certify(&f1, 1), 101);
+    let f3 = create_block(*f2.id(), f2.height() + NodeHeight(1), committee.certify(&f2, 1), 102);
+
+    let certificate = CommitCertificate {
+        blocks: vec![f1.clone(), f2, f3.clone()],
+        commit_qc: committee.certify(&f3, 1),
+        committee: committee.keys(),
+    };
+    let err = certificate.verify().unwrap_err();
+    assert!(
+        matches!(err, CommitCertificateError::InsufficientSignatures {
+            count: 1,
+            threshold: 3,
+            ..
+        }),
+        "unexpected error: {err}"
+    );
+}
@@ -20,6 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 pub mod error;
+pub mod messages;
 pub mod serialize;
 pub mod types;
 
@@ -43,6 +44,8 @@ use tari_template_lib::models::ComponentAddress;
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 use types::{
+    AccountsBulkTransferRequest,
+    AccountsBulkTransferResponse,
     AccountsCreateFreeTestCoinsRequest,
     AccountsCreateFreeTestCoinsResponse,
     AccountsTransferRequest,
@@ -62,6 +65,10 @@ use types::{
     ListAccountNftResponse,
     MintAccountNftRequest,
     MintAccountNftResponse,
+    ProfilesListRequest,
+    ProfilesListResponse,
+    ProfilesSwitchRequest,
+    ProfilesSwitchResponse,
     ProofsCancelRequest,
     ProofsCancelResponse,
     ProofsFinalizeRequest,
@@ -84,10 +91,18 @@ use crate::{
         AccountsCreateResponse,
         AccountsGetBalancesRequest,
         AccountsGetBalancesResponse,
+        AccountsGetQueueRequest,
+        AccountsGetQueueResponse,
+        AccountsGetVaultsRequest,
+        AccountsGetVaultsResponse,
         AccountsInvokeRequest,
         AccountsInvokeResponse,
         AccountsListRequest,
         AccountsListResponse,
+        AccountsSetQueueEnabledRequest,
+        AccountsSetQueueEnabledResponse,
+        AccountsSkipQueuedRequest,
+        AccountsSkipQueuedResponse,
         AuthGetAllJwtRequest,
         AuthGetAllJwtResponse,
         AuthRevokeTokenRequest,
@@ -100,6 +115,8 @@ use crate::{
         ConfidentialTransferResponse,
         ConfidentialViewVaultBalanceRequest,
         ConfidentialViewVaultBalanceResponse,
+        GetMessageCatalogueRequest,
+        GetMessageCatalogueResponse,
         GetValidatorFeesRequest,
         GetValidatorFeesResponse,
         KeyBranch,
@@ -111,12 +128,22 @@ use crate::{
         KeysSetActiveResponse,
         RevealFundsRequest,
         RevealFundsResponse,
+        TransactionCancelScheduledRequest,
+        TransactionCancelScheduledResponse,
         TransactionGetRequest,
         TransactionGetResponse,
         TransactionGetResultRequest,
         TransactionGetResultResponse,
+        TransactionGetSigningRequestRequest,
+        TransactionGetSigningRequestResponse,
+        TransactionListScheduledRequest,
+        TransactionListScheduledResponse,
+        TransactionScheduleRequest,
+        TransactionScheduleResponse,
         TransactionSubmitRequest,
         TransactionSubmitResponse,
+        TransactionSubmitSignatureRequest,
+        TransactionSubmitSignatureResponse,
         TransactionWaitResultRequest,
         TransactionWaitResultResponse,
     },
@@ -188,6 +215,7 @@ pub struct WalletDaemonClient {
     endpoint: Url,
     request_id: i64,
     token: Option<String>,
+    profile: Option<String>,
 }
 
 impl WalletDaemonClient {
@@ -205,6 +233,7 @@ impl WalletDaemonClient {
             endpoint: endpoint.into_url()?,
             request_id: 0,
             token,
+            profile: None,
         })
     }
 
@@ -213,6 +242,13 @@ impl WalletDaemonClient {
         self
     }
 
+    /// Sets the profile used for account and transaction requests. If None, the active profile of the wallet daemon
+    /// is used.
+    pub fn set_profile(&mut self, profile: Option<String>) -> &mut Self {
+        self.profile = profile;
+        self
+    }
+
     // pub async fn get_identity(&mut self) -> Result<GetIdentityResponse, WalletDaemonClientError> {
     //     self.send_request("identities.get", json!({})).await
     // }
@@ -274,6 +310,41 @@ impl WalletDaemonClient {
         self.send_request("transactions.submit", request.borrow()).await
     }
 
+    pub async fn get_transaction_signing_request<T: Borrow<TransactionGetSigningRequestRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionGetSigningRequestResponse, WalletDaemonClientError> {
+        self.send_request("transactions.get_signing_request", request.borrow()).await
+    }
+
+    pub async fn submit_transaction_signature<T: Borrow<TransactionSubmitSignatureRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionSubmitSignatureResponse, WalletDaemonClientError> {
+        self.send_request("transactions.submit_signature", request.borrow()).await
+    }
+
+    pub async fn schedule_transaction<T: Borrow<TransactionScheduleRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionScheduleResponse, WalletDaemonClientError> {
+        self.send_request("transactions.schedule", request.borrow()).await
+    }
+
+    pub async fn list_scheduled_transactions<T: Borrow<TransactionListScheduledRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionListScheduledResponse, WalletDaemonClientError> {
+        self.send_request("transactions.list_scheduled", request.borrow()).await
+    }
+
+    pub async fn cancel_scheduled_transaction<T: Borrow<TransactionCancelScheduledRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionCancelScheduledResponse, WalletDaemonClientError> {
+        self.send_request("transactions.cancel_scheduled", request.borrow()).await
+    }
+
     pub async fn submit_instruction<T: Borrow<CallInstructionRequest>>(
         &mut self,
         request: T,
@@ -303,6 +374,13 @@ impl WalletDaemonClient {
         self.send_request("accounts.get_balances", request.borrow()).await
     }
 
+    pub async fn get_account_vaults<T: Borrow<AccountsGetVaultsRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<AccountsGetVaultsResponse, WalletDaemonClientError> {
+        self.send_request("accounts.get_vaults", request.borrow()).await
+    }
+
     pub async fn get_validator_fee_summary<T: Borrow<GetValidatorFeesRequest>>(
         &mut self,
         request: T,
@@ -347,6 +425,34 @@ impl WalletDaemonClient {
             .await
     }
 
+    pub async fn accounts_get_queue(
+        &mut self,
+        account: ComponentAddressOrName,
+    ) -> Result<AccountsGetQueueResponse, WalletDaemonClientError> {
+        self.send_request("accounts.get_queue", &AccountsGetQueueRequest { account })
+            .await
+    }
+
+    pub async fn accounts_set_queue_enabled(
+        &mut self,
+        account: ComponentAddressOrName,
+        enabled: bool,
+    ) -> Result<AccountsSetQueueEnabledResponse, WalletDaemonClientError> {
+        self.send_request(
+            "accounts.set_queue_enabled",
+            &AccountsSetQueueEnabledRequest { account, enabled },
+        )
+        .await
+    }
+
+    pub async fn accounts_skip_queued(
+        &mut self,
+        account: ComponentAddressOrName,
+    ) -> Result<AccountsSkipQueuedResponse, WalletDaemonClientError> {
+        self.send_request("accounts.skip_queued", &AccountsSkipQueuedRequest { account })
+            .await
+    }
+
     pub async fn accounts_transfer<T: Borrow<AccountsTransferRequest>>(
         &mut self,
         req: T,
@@ -354,6 +460,13 @@ impl WalletDaemonClient {
         self.send_request("accounts.transfer", req.borrow()).await
     }
 
+    pub async fn accounts_bulk_transfer<T: Borrow<AccountsBulkTransferRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<AccountsBulkTransferResponse, WalletDaemonClientError> {
+        self.send_request("accounts.bulk_transfer", req.borrow()).await
+    }
+
     pub async fn accounts_confidential_transfer<T: Borrow<ConfidentialTransferRequest>>(
         &mut self,
         req: T,
@@ -482,6 +595,23 @@ impl WalletDaemonClient {
         self.send_request("webrtc.start", req.borrow()).await
     }
 
+    pub async fn get_message_catalogue(&mut self) -> Result<GetMessageCatalogueResponse, WalletDaemonClientError> {
+        self.send_request("meta.get_message_catalogue", &GetMessageCatalogueRequest {})
+            .await
+    }
+
+    pub async fn list_profiles(&mut self) -> Result<ProfilesListResponse, WalletDaemonClientError> {
+        self.send_request("profiles.list", &ProfilesListRequest {}).await
+    }
+
+    pub async fn switch_profile<T: Into<String>>(
+        &mut self,
+        name: T,
+    ) -> Result<ProfilesSwitchResponse, WalletDaemonClientError> {
+        self.send_request("profiles.switch", &ProfilesSwitchRequest { name: name.into() })
+            .await
+    }
+
     fn next_request_id(&mut self) -> i64 {
         self.request_id += 1;
         self.request_id
@@ -511,10 +641,18 @@ impl WalletDaemonClient {
         method: &str,
         params: &T,
     ) -> Result<R, WalletDaemonClientError> {
-        let params = json::to_value(params).map_err(|e| WalletDaemonClientError::SerializeRequest {
+        let mut params = json::to_value(params).map_err(|e| WalletDaemonClientError::SerializeRequest {
             source: e,
             method: method.to_string(),
         })?;
+        // Only account and transaction methods are scoped to a profile
+        if let Some(profile) = &self.profile {
+            if method.starts_with("accounts.") || method.starts_with("transactions.") {
+                if let Some(params) = params.as_object_mut() {
+                    params.insert("profile".to_string(), Value::String(profile.clone()));
+                }
+            }
+        }
         let resp = self.jrpc_call(method, &params).await?;
         match serde_json::from_value(resp) {
             Ok(r) => Ok(r),
@@ -0,0 +1,237 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! # Wallet message catalogue
+//!
+//! Every user-facing error and status emitted by the wallet daemon is a [WalletMessage]. Each message has a stable
+//! `code` and named `params` that front-ends use to localize it, along with a default English rendering. The enum, its
+//! codes and the catalogue returned by `meta.get_message_catalogue` are all generated from the single list in
+//! `wallet_messages!` below, so a message cannot be added without a code, params schema and default message.
+
+use std::fmt::{Display, Formatter};
+
+use serde_json::{json, Map, Value};
+
+use crate::types::{MessageCatalogueEntry, MessageParamSchema};
+
+/// A type that may be used as a message parameter
+pub trait MessageParam {
+    /// The JSON type of the parameter as listed in the catalogue
+    const PARAM_TYPE: &'static str;
+
+    fn to_value(&self) -> Value;
+}
+
+impl MessageParam for String {
+    const PARAM_TYPE: &'static str = "string";
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl MessageParam for u64 {
+    const PARAM_TYPE: &'static str = "number";
+
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl MessageParam for i64 {
+    const PARAM_TYPE: &'static str = "number";
+
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+macro_rules! wallet_messages {
+    ($(
+        $(#[doc = $doc:literal])*
+        $variant:ident $({ $($field:ident: $ty:ty),+ $(,)? })? => $code:literal, $message:literal;
+    )+) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum WalletMessage {
+            $(
+                $(#[doc = $doc])*
+                $variant $({ $($field: $ty),+ })?,
+            )+
+        }
+
+        impl WalletMessage {
+            /// The stable identifier of the message
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $code,)+
+                }
+            }
+
+            /// The named parameters of the message
+            pub fn params(&self) -> Map<String, Value> {
+                match self {
+                    $(
+                        Self::$variant $({ $($field),+ })? => {
+                            #[allow(unused_mut)]
+                            let mut params = Map::new();
+                            $($(params.insert(stringify!($field).to_string(), MessageParam::to_value($field));)+)?
+                            params
+                        },
+                    )+
+                }
+            }
+
+            /// The message rendered in English
+            pub fn default_message(&self) -> String {
+                match self {
+                    $(Self::$variant $({ $($field),+ })? => format!($message $(, $($field = $field),+)?),)+
+                }
+            }
+
+            /// Returns the code, parameter schema and default message template of every message
+            pub fn catalogue() -> Vec<MessageCatalogueEntry> {
+                vec![
+                    $(
+                        MessageCatalogueEntry {
+                            code: $code.to_string(),
+                            params: vec![
+                                $($(MessageParamSchema {
+                                    name: stringify!($field).to_string(),
+                                    param_type: <$ty as MessageParam>::PARAM_TYPE.to_string(),
+                                }),+)?
+                            ],
+                            default_message: $message.to_string(),
+                        },
+                    )+
+                ]
+            }
+
+            /// Returns one instance of every message with default parameters
+            #[cfg(test)]
+            fn all_with_default_params() -> Vec<Self> {
+                vec![$(Self::$variant $({ $($field: <$ty>::default()),+ })?,)+]
+            }
+        }
+    };
+}
+
+wallet_messages! {
+    // Errors
+    /// The requested item does not exist
+    NotFound => "not_found", "Not found";
+    /// The request was not authorized by the provided token
+    Unauthorized { reason: String } => "unauthorized", "Unauthorized: {reason}";
+    InternalError { details: String } => "internal_error", "Error: {details}";
+    AccountNameAlreadyExists { name: String } => "account_name_already_exists", "Account name '{name}' already exists";
+    AccountNameRequired => "account_name_required", "Account name must be provided when creating a new account";
+    NoDefaultAccount => "no_default_account", "No default account found. Please set a default account.";
+    InvalidAccountAddress { address: String } => "invalid_account_address", "Invalid account address {address}";
+    FeeExceedsClaimedAmount { fee: i64, amount: u64 } =>
+        "fee_exceeds_claimed_amount", "Fee ({fee}) is greater than the claimed output amount ({amount})";
+    /// The fee transaction was rejected, so no fees were charged
+    FeeTransactionRejected { reason: String } => "fee_transaction_rejected", "Fee transaction rejected: {reason}";
+    TransactionRejected { reason: String } => "transaction_rejected", "Transaction rejected: {reason}";
+    /// The fee transaction succeeded and fees were charged, but the main transaction failed
+    TransactionFailed { reason: String } =>
+        "transaction_failed", "Fee transaction succeeded (fees charged) however the transaction failed: {reason}";
+    InvalidTransactionSignature { transaction_id: String } =>
+        "invalid_transaction_signature", "Invalid signature for transaction {transaction_id}";
+    DeferredSigningNotSupportedForDryRun =>
+        "deferred_signing_not_supported_for_dry_run", "Deferred signing is not supported for dry run transactions";
+    ProfileNotFound { name: String } => "profile_not_found", "Profile '{name}' not found";
+    InsufficientFunds { resource_address: String, required: i64, available: i64 } =>
+        "insufficient_funds",
+        "Insufficient funds of resource {resource_address}: {required} required, {available} available";
+    // Statuses
+    TransactionSubmitted { transaction_id: String } =>
+        "transaction_submitted", "Transaction {transaction_id} submitted";
+    TransactionFinalized { transaction_id: String, status: String, final_fee: i64 } =>
+        "transaction_finalized", "Transaction {transaction_id} finalized with status {status}. Fee: {final_fee}";
+    TransactionInvalid { transaction_id: String, status: String } =>
+        "transaction_invalid", "Transaction {transaction_id} is invalid with status {status}";
+    AccountCreated { account_address: String, transaction_id: String } =>
+        "account_created", "Account {account_address} created by transaction {transaction_id}";
+    AccountChanged { account_address: String } => "account_changed", "Account {account_address} changed";
+    AuthLoginRequested => "auth_login_requested", "A new login was requested";
+    /// A queued transaction failed and is blocking the transaction queue of an account
+    TransactionQueuePaused { account_address: String, transaction_id: String, reason: String } =>
+        "transaction_queue_paused",
+        "Transaction queue for account {account_address} is paused because transaction {transaction_id} failed: \
+         {reason}";
+}
+
+impl WalletMessage {
+    /// Returns the code and params of the message as included in JSON-RPC error data
+    pub fn to_data(&self) -> Value {
+        json!({
+            "code": self.code(),
+            "params": self.params(),
+        })
+    }
+}
+
+impl Display for WalletMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.default_message())
+    }
+}
+
+impl std::error::Error for WalletMessage {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn it_lists_every_message_in_the_catalogue() {
+        let catalogue = WalletMessage::catalogue();
+        let messages = WalletMessage::all_with_default_params();
+        assert_eq!(catalogue.len(), messages.len());
+
+        let codes = catalogue
+            .iter()
+            .map(|entry| entry.code.as_str())
+            .collect::<HashSet<_>>();
+        assert_eq!(codes.len(), catalogue.len(), "Message codes must be unique");
+
+        for message in messages {
+            let entry = catalogue
+                .iter()
+                .find(|entry| entry.code == message.code())
+                .unwrap_or_else(|| panic!("{} is not in the catalogue", message.code()));
+            let params = message.params();
+            assert_eq!(entry.params.len(), params.len());
+            for param in &entry.params {
+                assert!(
+                    params.contains_key(&param.name),
+                    "{} is missing param {}",
+                    entry.code,
+                    param.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_renders_the_default_message_and_params() {
+        let message = WalletMessage::FeeExceedsClaimedAmount { fee: 1000, amount: 10 };
+        assert_eq!(message.code(), "fee_exceeds_claimed_amount");
+        assert_eq!(
+            message.default_message(),
+            "Fee (1000) is greater than the claimed output amount (10)"
+        );
+        assert_eq!(
+            message.to_data(),
+            json!({
+                "code": "fee_exceeds_claimed_amount",
+                "params": { "fee": 1000, "amount": 10 },
+            })
+        );
+        assert_eq!(
+            WalletMessage::NoDefaultAccount.to_data(),
+            json!({ "code": "no_default_account", "params": {} })
+        );
+    }
+}
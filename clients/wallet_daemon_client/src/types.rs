@@ -28,11 +28,21 @@ use std::{collections::HashMap, time::Duration};
 
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::PublicKey;
+use tari_common_types::types::{PublicKey, Signature};
 use tari_dan_common_types::{Epoch, SubstateAddress};
 use tari_dan_wallet_sdk::{
     apis::{confidential_transfer::ConfidentialTransferInputSelection, jwt::Claims, key_manager},
-    models::{Account, ConfidentialProofId, NonFungibleToken, SubstateType, TransactionStatus},
+    models::{
+        Account,
+        AccountTransactionQueue,
+        ConfidentialProofId,
+        NonFungibleToken,
+        ScheduleTrigger,
+        ScheduledTransaction,
+        ScheduledTransactionStatus,
+        SubstateType,
+        TransactionStatus,
+    },
 };
 use tari_engine_types::{
     commit_result::{ExecuteResult, FinalizeResult},
@@ -89,6 +99,10 @@ pub struct CallInstructionRequest {
     #[serde(default)]
     #[cfg_attr(feature = "ts", ts(type = "number | null"))]
     pub max_epoch: Option<u64>,
+    /// An additional fee paid to the validators to prioritise the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub priority_fee: u64,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -112,6 +126,10 @@ pub struct TransactionSubmitRequest {
     pub instructions: Vec<Instruction>,
     pub min_epoch: Option<Epoch>,
     pub max_epoch: Option<Epoch>,
+    /// If true, the transaction is not signed by the wallet. It is held in the PendingSignature status until the
+    /// signature is provided with `transactions.submit_signature`, after which it is submitted.
+    #[serde(default)]
+    pub deferred_signing: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -129,6 +147,128 @@ pub struct TransactionSubmitResponse {
     pub json_result: Option<Vec<serde_json::Value>>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetSigningRequestRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetSigningRequestResponse {
+    pub key_id: String,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    /// The challenge to sign with the key identified by `key_id`
+    #[serde(with = "serde_with::hex")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub challenge: [u8; 64],
+    pub transaction: UnsignedTransaction,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionSubmitSignatureRequest {
+    /// The id returned from a `transactions.submit` call with deferred signing
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionSubmitSignatureResponse {
+    /// The id of the signed transaction
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionScheduleRequest {
+    pub transaction: UnsignedTransaction,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub signing_key_index: Option<u64>,
+    pub inputs: Vec<SubstateRequirement>,
+    pub override_inputs: bool,
+    pub trigger: ScheduleTrigger,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionScheduleResponse {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    /// The inputs as resolved when the transaction was scheduled. The versions are resolved again when the
+    /// transaction is released.
+    pub inputs: Vec<SubstateRequirement>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionListScheduledRequest {
+    pub status: Option<ScheduledTransactionStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionListScheduledResponse {
+    pub scheduled_transactions: Vec<ScheduledTransaction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionCancelScheduledRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionCancelScheduledResponse {}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -462,6 +602,45 @@ pub struct BalanceEntry {
     pub token_symbol: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetVaultsRequest {
+    #[serde(deserialize_with = "opt_string_or_struct")]
+    pub account: Option<ComponentAddressOrName>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetVaultsResponse {
+    pub address: SubstateId,
+    pub vaults: Vec<AccountVaultEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountVaultEntry {
+    pub vault_address: SubstateId,
+    #[serde(with = "serde_with::string")]
+    pub resource_address: ResourceAddress,
+    pub resource_type: ResourceType,
+    pub revealed_balance: Amount,
+    pub confidential_commitment_count: u32,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub last_updated_transaction: Option<TransactionId>,
+}
+
 impl BalanceEntry {
     pub fn to_balance_string(&self) -> String {
         let symbol = self.token_symbol.as_deref().unwrap_or_default();
@@ -537,6 +716,70 @@ pub struct AccountSetDefaultRequest {
 )]
 pub struct AccountSetDefaultResponse {}
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetQueueRequest {
+    #[serde(deserialize_with = "string_or_struct")]
+    pub account: ComponentAddressOrName,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetQueueResponse {
+    /// None if ordered transactions are not enabled for the account
+    pub queue: Option<AccountTransactionQueue>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsSetQueueEnabledRequest {
+    #[serde(deserialize_with = "string_or_struct")]
+    pub account: ComponentAddressOrName,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsSetQueueEnabledResponse {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsSkipQueuedRequest {
+    #[serde(deserialize_with = "string_or_struct")]
+    pub account: ComponentAddressOrName,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsSkipQueuedResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -554,6 +797,14 @@ pub struct AccountsTransferRequest {
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub proof_from_badge_resource: Option<ResourceAddress>,
     pub dry_run: bool,
+    /// An additional fee paid to the validators to prioritise the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub priority_fee: u64,
+    /// If true, the transaction asserts that the withdrawn bucket contains the amount and that no more than the
+    /// amount (and the fee) is taken from the source vault. The transaction is aborted if either does not hold.
+    #[serde(default)]
+    pub assert_balances: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -570,6 +821,97 @@ pub struct AccountsTransferResponse {
     pub result: FinalizeResult,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsBulkTransferRequest {
+    #[serde(deserialize_with = "opt_string_or_struct")]
+    pub account: Option<ComponentAddressOrName>,
+    pub resource_address: ResourceAddress,
+    pub recipients: Vec<BulkTransferRecipient>,
+    /// The maximum number of instructions in each transaction. Each recipient requires three instructions, and one
+    /// more if their account does not exist yet.
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub max_instructions_per_transaction: Option<u32>,
+    /// The maximum fee paid by each transaction. A transaction that runs out of fees is split and retried.
+    pub max_fee_per_transaction: Option<Amount>,
+    /// The maximum number of transactions that are submitted at the same time
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub max_concurrent_transactions: Option<u32>,
+    /// An additional fee paid to the validators to prioritise each transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub priority_fee: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BulkTransferRecipient {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub destination_public_key: PublicKey,
+    pub amount: Amount,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsBulkTransferResponse {
+    /// The outcome for each recipient, in the order of the request
+    pub outcomes: Vec<BulkTransferOutcome>,
+    pub summary: BulkTransferSummary,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BulkTransferOutcome {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub destination_public_key: PublicKey,
+    pub amount: Amount,
+    /// The transaction that transferred the amount, or the last transaction that failed to transfer it
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub transaction_id: Option<TransactionId>,
+    /// Why the transfer to this recipient failed. None if it succeeded.
+    pub failure_reason: Option<String>,
+}
+
+impl BulkTransferOutcome {
+    pub fn is_success(&self) -> bool {
+        self.failure_reason.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BulkTransferSummary {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_succeeded: u32,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_failed: u32,
+    /// The number of transactions that were submitted, including those that failed and were split
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_transactions: u32,
+    pub total_amount_transferred: Amount,
+    pub total_fees: Amount,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -1056,6 +1398,55 @@ pub struct ClaimValidatorFeesResponse {
     pub result: FinalizeResult,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ProfilesListRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ProfilesListResponse {
+    pub profiles: Vec<WalletProfileInfo>,
+    pub active_profile: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct WalletProfileInfo {
+    pub name: String,
+    pub indexer_url: String,
+    pub key_domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ProfilesSwitchRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ProfilesSwitchResponse {}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -1165,3 +1556,47 @@ pub struct TemplatesGetRequest {
 pub struct TemplatesGetResponse {
     pub template_definition: TemplateDef,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct GetMessageCatalogueRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct GetMessageCatalogueResponse {
+    pub messages: Vec<MessageCatalogueEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct MessageCatalogueEntry {
+    /// The stable identifier of the message, included as `code` in JSON-RPC error data and events
+    pub code: String,
+    pub params: Vec<MessageParamSchema>,
+    /// The English message template. Parameters are referenced by name e.g. `{transaction_id}`.
+    pub default_message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct MessageParamSchema {
+    pub name: String,
+    /// The JSON type of the parameter value i.e. "string" or "number"
+    pub param_type: String,
+}
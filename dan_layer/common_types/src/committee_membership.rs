@@ -0,0 +1,84 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{FixedHash, PublicKey};
+
+use crate::{
+    hashing::{validator_node_bmt_leaf_hasher, ValidatorNodeBalancedMerkleTree, ValidatorNodeMerkleProof},
+    shard::Shard,
+    Epoch,
+};
+
+/// Returns the hash of the leaf for a validator in the validator node BMT of an epoch
+pub fn validator_node_bmt_leaf_hash(public_key: &PublicKey, shard: Shard) -> Vec<u8> {
+    validator_node_bmt_leaf_hasher()
+        .chain(public_key)
+        .chain(&shard)
+        .result()
+        .to_vec()
+}
+
+/// Builds the validator node BMT for an epoch. Validators must be given in the same order (by shard key) by every node
+/// so that all nodes arrive at the same root.
+pub fn build_validator_node_bmt<'a, I>(validators: I) -> ValidatorNodeBalancedMerkleTree
+where I: IntoIterator<Item = (&'a PublicKey, Shard)> {
+    let leaves = validators
+        .into_iter()
+        .map(|(public_key, shard)| validator_node_bmt_leaf_hash(public_key, shard))
+        .collect();
+    ValidatorNodeBalancedMerkleTree::create(leaves)
+}
+
+/// A compact proof that a validator is a member of the committee for a shard in an epoch. The proof is verified
+/// against the root of the validator node BMT for the epoch, so the committee does not have to be fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeMembershipProof {
+    epoch: Epoch,
+    shard: Shard,
+    public_key: PublicKey,
+    merkle_proof: ValidatorNodeMerkleProof,
+}
+
+impl CommitteeMembershipProof {
+    /// Generates a membership proof from the validator node BMT of the epoch. Returns None if the validator is not in
+    /// the tree for the given shard.
+    pub fn generate(
+        bmt: &ValidatorNodeBalancedMerkleTree,
+        epoch: Epoch,
+        shard: Shard,
+        public_key: PublicKey,
+    ) -> Option<Self> {
+        let leaf_hash = validator_node_bmt_leaf_hash(&public_key, shard);
+        let leaf_index = bmt.find_leaf_index_for_hash(&leaf_hash).ok()?;
+        let merkle_proof = ValidatorNodeMerkleProof::generate_proof(bmt, leaf_index as usize).ok()?;
+        Some(Self {
+            epoch,
+            shard,
+            public_key,
+            merkle_proof,
+        })
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn shard(&self) -> Shard {
+        self.shard
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Returns true if the proof is for the given epoch and proves that the public key and shard are included in the
+    /// validator node BMT with the given root.
+    pub fn verify(&self, epoch: Epoch, bmt_root: &FixedHash) -> bool {
+        if self.epoch != epoch {
+            return false;
+        }
+        let leaf_hash = validator_node_bmt_leaf_hash(&self.public_key, self.shard);
+        self.merkle_proof.verify(&bmt_root.to_vec(), leaf_hash)
+    }
+}
@@ -40,6 +40,10 @@ pub fn quorum_certificate_hasher() -> TariHasher {
     dan_hasher("QuorumCertificate")
 }
 
+pub fn genesis_hasher() -> TariHasher {
+    dan_hasher("Genesis")
+}
+
 pub fn pledge_hasher() -> TariHasher {
     dan_hasher("Pledges")
 }
@@ -52,6 +56,18 @@ pub fn vote_signature_hasher() -> TariHasher {
     dan_hasher("VoteSignature")
 }
 
+pub fn validator_node_bmt_leaf_hasher() -> TariHasher {
+    dan_hasher("ValidatorNodeBmtLeaf")
+}
+
+pub fn epoch_seed_hasher() -> TariHasher {
+    dan_hasher("EpochSeed")
+}
+
+pub fn leader_selection_hasher() -> TariHasher {
+    dan_hasher("LeaderSelection")
+}
+
 fn dan_hasher(label: &'static str) -> TariHasher {
     tari_hasher::<TariDanConsensusHashDomain>(label)
 }
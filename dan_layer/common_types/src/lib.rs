@@ -8,6 +8,7 @@ mod epoch;
 pub use epoch::Epoch;
 
 pub mod committee;
+pub mod committee_membership;
 pub mod hasher;
 pub mod hashing;
 pub mod optional;
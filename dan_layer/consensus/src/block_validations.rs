@@ -1,16 +1,30 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::collections::HashSet;
+
 use tari_common::configuration::Network;
-use tari_dan_common_types::{committee::Committee, DerivableFromPublicKey};
-use tari_dan_storage::consensus_models::Block;
+use tari_common_types::types::FixedHash;
+use tari_dan_common_types::{
+    committee::{Committee, CommitteeInfo},
+    committee_membership::CommitteeMembershipProof,
+    DerivableFromPublicKey,
+    Epoch,
+};
+use tari_dan_storage::{
+    consensus_models::{Block, Command, ForeignSendCounters, QuorumCertificate, TransactionRecord, ValidatorSignature},
+    StateStoreReadTransaction,
+};
 use tari_epoch_manager::EpochManagerReader;
 
 use crate::{
-    hotstuff::{HotStuffError, HotstuffConfig, ProposalValidationError},
-    traits::{ConsensusSpec, LeaderStrategy, VoteSignatureService},
+    hotstuff::{get_foreign_index_shards, HotStuffError, HotstuffConfig, ProposalValidationError, VerifiedQcCache},
+    traits::{Clock, ConsensusSpec, LeaderStrategy, ValidatorSignatureService, VoteSignatureService},
 };
 
+/// The maximum number of seconds that a block timestamp may be ahead of the local clock
+pub const MAX_BLOCK_TIMESTAMP_DRIFT_SECS: u64 = 5 * 60;
+
 pub fn check_network(candidate_block: &Block, network: Network) -> Result<(), ProposalValidationError> {
     if candidate_block.network() != network {
         return Err(ProposalValidationError::InvalidNetwork {
@@ -68,6 +82,65 @@ pub async fn check_base_layer_block_hash<TConsensusSpec: ConsensusSpec>(
     Ok(())
 }
 
+/// Checks that a block that ends an epoch carries the checkpoint of the epoch, containing the state merkle root of the
+/// block and the validator node merkle root of the epoch. No other block may carry a checkpoint.
+pub async fn check_epoch_checkpoint<TConsensusSpec: ConsensusSpec>(
+    block: &Block,
+    epoch_manager: &TConsensusSpec::EpochManager,
+) -> Result<(), HotStuffError> {
+    let num_checkpoints = block
+        .commands()
+        .iter()
+        .filter(|command| command.epoch_checkpoint().is_some())
+        .count();
+    if !block.is_epoch_end() {
+        if num_checkpoints > 0 {
+            return Err(ProposalValidationError::UnexpectedEpochCheckpoint { block_id: *block.id() }.into());
+        }
+        return Ok(());
+    }
+
+    let checkpoint = block
+        .epoch_checkpoint()
+        .ok_or_else(|| ProposalValidationError::MissingEpochCheckpoint {
+            block_id: *block.id(),
+            epoch: block.epoch(),
+        })?;
+    let invalid = |details: String| ProposalValidationError::InvalidEpochCheckpoint {
+        block_id: *block.id(),
+        details,
+    };
+    if num_checkpoints > 1 {
+        return Err(invalid(format!("block has {} checkpoints", num_checkpoints)).into());
+    }
+    if checkpoint.epoch() != block.epoch() {
+        return Err(invalid(format!(
+            "checkpoint is for epoch {} but the block is in epoch {}",
+            checkpoint.epoch(),
+            block.epoch()
+        ))
+        .into());
+    }
+    if checkpoint.state_merkle_root() != block.merkle_root() {
+        return Err(invalid(format!(
+            "checkpoint state merkle root {} does not match the block merkle root {}",
+            checkpoint.state_merkle_root(),
+            block.merkle_root()
+        ))
+        .into());
+    }
+    let validator_set_hash = epoch_manager.get_validator_node_bmt_root(block.epoch()).await?;
+    if *checkpoint.validator_set_hash() != validator_set_hash {
+        return Err(invalid(format!(
+            "checkpoint validator set hash {} does not match the validator set hash {} of the epoch",
+            checkpoint.validator_set_hash(),
+            validator_set_hash
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 pub fn check_hash_and_height(candidate_block: &Block) -> Result<(), ProposalValidationError> {
     if candidate_block.height().is_zero() || candidate_block.is_genesis() {
         return Err(ProposalValidationError::ProposingGenesisBlock {
@@ -88,12 +161,187 @@ pub fn check_hash_and_height(candidate_block: &Block) -> Result<(), ProposalVali
     Ok(())
 }
 
+pub fn check_timestamp<TClock: Clock>(
+    candidate_block: &Block,
+    justify_block: &Block,
+    clock: &TClock,
+) -> Result<(), ProposalValidationError> {
+    if candidate_block.timestamp() < justify_block.timestamp() {
+        return Err(ProposalValidationError::TimestampBeforeJustify {
+            block_id: *candidate_block.id(),
+            timestamp: candidate_block.timestamp(),
+            justify_timestamp: justify_block.timestamp(),
+        });
+    }
+
+    let max_timestamp = clock.now().saturating_add(MAX_BLOCK_TIMESTAMP_DRIFT_SECS);
+    if candidate_block.timestamp() > max_timestamp {
+        return Err(ProposalValidationError::TimestampTooFarInFuture {
+            block_id: *candidate_block.id(),
+            timestamp: candidate_block.timestamp(),
+            max_timestamp,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the candidate block's epoch continues the epoch of its justify block. A block may only change the epoch
+/// if it is an epoch start block that extends a block that ended the previous epoch. Whether the epoch has been reached
+/// on the base layer is checked by the epoch manager before the block is processed, and the epoch end block was checked
+/// to be at the last base layer block of its epoch when it was received.
+pub fn check_epoch_continuity(candidate_block: &Block, justify_block: &Block) -> Result<(), ProposalValidationError> {
+    let epoch = candidate_block.epoch();
+    let justify_epoch = justify_block.epoch();
+    if epoch < justify_epoch {
+        return Err(ProposalValidationError::EpochRegression {
+            block_id: *candidate_block.id(),
+            epoch,
+            justify_epoch,
+        });
+    }
+
+    if epoch == justify_epoch {
+        if candidate_block.is_epoch_start() {
+            return Err(ProposalValidationError::UnexpectedEpochStart {
+                block_id: *candidate_block.id(),
+                epoch,
+            });
+        }
+        return Ok(());
+    }
+
+    if !candidate_block.is_epoch_start() {
+        return Err(ProposalValidationError::EpochChangeWithoutEpochStart {
+            block_id: *candidate_block.id(),
+            epoch,
+            justify_epoch,
+        });
+    }
+
+    // There is no epoch to end before the first epoch after genesis
+    if !justify_block.is_epoch_end() && !justify_block.is_genesis() {
+        return Err(ProposalValidationError::EpochChangeBeforeEpochEnd {
+            block_id: *candidate_block.id(),
+            epoch,
+            justify_block: *justify_block.id(),
+            justify_epoch,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the foreign indexes of the candidate block are sorted by shard, cover exactly the non-local shards that
+/// are involved in its locally prepared transactions, and that each counter is one more than the counter for that shard
+/// at the justify block.
+pub fn check_foreign_indexes<TTx: StateStoreReadTransaction + ?Sized>(
+    tx: &TTx,
+    candidate_block: &Block,
+    local_committee_info: &CommitteeInfo,
+) -> Result<(), HotStuffError> {
+    let invalid_foreign_counters = |details: String| ProposalValidationError::InvalidForeignCounters {
+        proposed_by: candidate_block.proposed_by().to_string(),
+        hash: *candidate_block.id(),
+        details,
+    };
+
+    let foreign_indexes = candidate_block.foreign_indexes();
+    if let Some((shard, next_shard)) = foreign_indexes
+        .keys()
+        .zip(foreign_indexes.keys().skip(1))
+        .find(|(shard, next_shard)| shard > next_shard)
+    {
+        return Err(invalid_foreign_counters(format!(
+            "Foreign indexes are not sorted by shard. Shard {} is followed by shard {}",
+            shard, next_shard
+        ))
+        .into());
+    }
+
+    let expected_shards = get_foreign_index_shards(candidate_block.commands(), local_committee_info);
+    if let Some(shard) = expected_shards
+        .iter()
+        .find(|shard| !foreign_indexes.contains_key(*shard))
+    {
+        return Err(invalid_foreign_counters(format!(
+            "Missing foreign index for shard {}. Expected foreign indexes for shards [{}]",
+            shard,
+            expected_shards
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .into());
+    }
+    if let Some(shard) = foreign_indexes.keys().find(|shard| !expected_shards.contains(*shard)) {
+        return Err(invalid_foreign_counters(format!(
+            "Shard {} is not involved in any locally prepared transaction in the block",
+            shard
+        ))
+        .into());
+    }
+
+    let foreign_counters = ForeignSendCounters::get_or_default(tx, candidate_block.justify().block_id())?;
+    for (shard, count) in foreign_indexes {
+        let expected_count = foreign_counters.get_count(*shard) + 1;
+        if *count != expected_count {
+            return Err(invalid_foreign_counters(format!(
+                "Foreign counter for shard {} is incorrect. Expected {}, got {}",
+                shard, expected_count, count
+            ))
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the candidate block's epoch is within the min/max epoch bounds of each transaction that it prepares.
+/// The only command allowed outside of the bounds is an ABORT of an expired transaction. Accept and LocalPrepared are
+/// exempt because the transaction was prepared within its bounds in an earlier block.
+pub fn check_transaction_epoch_bounds<TTx: StateStoreReadTransaction>(
+    tx: &TTx,
+    candidate_block: &Block,
+) -> Result<(), HotStuffError> {
+    let epoch = candidate_block.epoch();
+    for command in candidate_block.commands() {
+        let (Command::LocalOnly(atom) | Command::Prepare(atom)) = command else {
+            continue;
+        };
+        let transaction = TransactionRecord::get(tx, &atom.id)?;
+        let transaction = transaction.transaction();
+        if transaction.is_valid_in_epoch(epoch) {
+            continue;
+        }
+        if !atom.decision.is_commit() && transaction.is_expired_in_epoch(epoch) {
+            continue;
+        }
+
+        let fmt_epoch = |epoch: Option<Epoch>| epoch.map(|e| e.to_string()).unwrap_or_else(|| "<none>".to_string());
+        return Err(ProposalValidationError::TransactionOutsideEpochBounds {
+            block_id: *candidate_block.id(),
+            transaction_id: atom.id,
+            details: format!(
+                "{} command in epoch {} for transaction with min epoch {} and max epoch {}",
+                command,
+                epoch,
+                fmt_epoch(transaction.min_epoch()),
+                fmt_epoch(transaction.max_epoch()),
+            ),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
 pub fn check_proposed_by_leader<TAddr: DerivableFromPublicKey, TLeaderStrategy: LeaderStrategy<TAddr>>(
     leader_strategy: &TLeaderStrategy,
     local_committee: &Committee<TAddr>,
     candidate_block: &Block,
 ) -> Result<(), ProposalValidationError> {
-    let leader = leader_strategy.get_leader(local_committee, candidate_block.height());
+    let leader = leader_strategy.get_leader(local_committee, candidate_block.epoch(), candidate_block.height());
     if !leader.eq_to_public_key(candidate_block.proposed_by()) {
         return Err(ProposalValidationError::NotLeader {
             proposed_by: candidate_block.proposed_by().to_string(),
@@ -104,6 +352,41 @@ pub fn check_proposed_by_leader<TAddr: DerivableFromPublicKey, TLeaderStrategy:
     Ok(())
 }
 
+/// Checks that the sender of a cross-committee message is a member of the committee that it claims to be in for the
+/// epoch, by verifying its membership proof against the root of the validator node BMT for the epoch.
+pub fn check_membership_proof<TAddr: DerivableFromPublicKey>(
+    from: &TAddr,
+    epoch: Epoch,
+    membership_proof: &CommitteeMembershipProof,
+    validator_node_bmt_root: &FixedHash,
+) -> Result<(), ProposalValidationError> {
+    if !from.eq_to_public_key(membership_proof.public_key()) {
+        return Err(ProposalValidationError::InvalidMembershipProof {
+            sender: from.to_string(),
+            epoch,
+            details: format!("proof is for public key {}", membership_proof.public_key()),
+        });
+    }
+    if membership_proof.epoch() != epoch {
+        return Err(ProposalValidationError::InvalidMembershipProof {
+            sender: from.to_string(),
+            epoch,
+            details: format!("proof is for epoch {}", membership_proof.epoch()),
+        });
+    }
+    if !membership_proof.verify(epoch, validator_node_bmt_root) {
+        return Err(ProposalValidationError::InvalidMembershipProof {
+            sender: from.to_string(),
+            epoch,
+            details: format!(
+                "merkle proof for shard {} does not match the validator node BMT root",
+                membership_proof.shard()
+            ),
+        });
+    }
+    Ok(())
+}
+
 pub fn check_signature(candidate_block: &Block) -> Result<(), ProposalValidationError> {
     if candidate_block.is_dummy() {
         // Dummy blocks don't have signatures
@@ -128,6 +411,117 @@ pub fn check_signature(candidate_block: &Block) -> Result<(), ProposalValidation
     Ok(())
 }
 
+/// Checks that the state merkle root of the block matches the root calculated from the pending state tree diffs and the
+/// changes made by the block. Dummy blocks copy the merkle root of their parent and are exempt.
+pub fn check_state_merkle_root(
+    candidate_block: &Block,
+    calculated_merkle_root: &FixedHash,
+) -> Result<(), ProposalValidationError> {
+    if candidate_block.is_dummy() {
+        return Ok(());
+    }
+    if candidate_block.merkle_root() != calculated_merkle_root {
+        return Err(ProposalValidationError::StateMerkleRootMismatch {
+            proposed_by: candidate_block.proposed_by().to_string(),
+            block_id: *candidate_block.id(),
+            calculated: *calculated_merkle_root,
+            from_block: *candidate_block.merkle_root(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the block is signed by the leader for its epoch and height in the local committee. Dummy blocks are not
+/// signed and are exempt.
+pub fn check_proposer_signature<TAddr, TLeaderStrategy, TSignatureService>(
+    leader_strategy: &TLeaderStrategy,
+    signing_service: &TSignatureService,
+    local_committee: &Committee<TAddr>,
+    candidate_block: &Block,
+) -> Result<(), ProposalValidationError>
+where
+    TLeaderStrategy: LeaderStrategy<TAddr>,
+    TSignatureService: ValidatorSignatureService,
+{
+    if candidate_block.is_dummy() {
+        return Ok(());
+    }
+
+    let leader =
+        leader_strategy.get_leader_public_key(local_committee, candidate_block.epoch(), candidate_block.height());
+    let invalid_signature = |details: &str| ProposalValidationError::InvalidProposerSignature {
+        block_id: *candidate_block.id(),
+        height: candidate_block.height(),
+        expected_leader: leader.to_string(),
+        details: details.to_string(),
+    };
+
+    let signature = candidate_block
+        .get_signature()
+        .ok_or_else(|| invalid_signature("block is not signed"))?;
+    if !signing_service.verify_signature(leader, signature, candidate_block.id()) {
+        return Err(invalid_signature("signature is not valid for the leader"));
+    }
+    Ok(())
+}
+
+/// Verifies the signatures of a QC as a batch. If the batch is invalid, each signature is verified individually to
+/// identify the offending validator.
+pub fn check_qc_signatures<TSignatureService: VoteSignatureService>(
+    vote_signing_service: &TSignatureService,
+    qc: &QuorumCertificate,
+    challenges_and_signatures: &[(FixedHash, &ValidatorSignature)],
+) -> Result<(), ProposalValidationError> {
+    if vote_signing_service.verify_batch(challenges_and_signatures) {
+        return Ok(());
+    }
+
+    let invalid = challenges_and_signatures
+        .iter()
+        .find(|(challenge, signature)| !signature.verify(challenge));
+    match invalid {
+        Some((_, signature)) => Err(ProposalValidationError::QCInvalidSignature {
+            qc: qc.clone(),
+            validator: signature.public_key().to_string(),
+        }),
+        // Individual verification is authoritative
+        None => Ok(()),
+    }
+}
+
+fn check_justify_height(candidate_block: &Block) -> Result<(), ProposalValidationError> {
+    let qc = candidate_block.justify();
+    if candidate_block.height() <= qc.block_height() {
+        return Err(ProposalValidationError::CandidateBlockNotHigherThanJustify {
+            justify_block_height: qc.block_height(),
+            candidate_block_height: candidate_block.height(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks the justify QC of the candidate block like [check_quorum_certificate], except that the QC signatures are
+/// not verified again if the QC is in the cache of verified QCs. The QC is added to the cache once it is verified.
+/// Returns true if the QC was found in the cache.
+pub async fn check_quorum_certificate_cached<TConsensusSpec: ConsensusSpec>(
+    candidate_block: &Block,
+    vote_signing_service: &TConsensusSpec::SignatureService,
+    epoch_manager: &TConsensusSpec::EpochManager,
+    verified_qcs: &mut VerifiedQcCache,
+) -> Result<bool, HotStuffError> {
+    let qc = candidate_block.justify();
+    if !qc.is_genesis() && verified_qcs.contains(qc.epoch(), qc.id()) {
+        check_justify_height(candidate_block)?;
+        return Ok(true);
+    }
+
+    check_quorum_certificate::<TConsensusSpec>(candidate_block, vote_signing_service, epoch_manager).await?;
+    if !qc.is_genesis() {
+        verified_qcs.insert(qc.epoch(), *qc.id());
+    }
+    Ok(false)
+}
+
 pub async fn check_quorum_certificate<TConsensusSpec: ConsensusSpec>(
     candidate_block: &Block,
     vote_signing_service: &TConsensusSpec::SignatureService,
@@ -140,16 +534,18 @@ pub async fn check_quorum_certificate<TConsensusSpec: ConsensusSpec>(
 
         return Ok(());
     }
-    if candidate_block.height() <= qc.block_height() {
-        return Err(ProposalValidationError::CandidateBlockNotHigherThanJustify {
-            justify_block_height: qc.block_height(),
-            candidate_block_height: candidate_block.height(),
-        }
-        .into());
-    }
+    check_justify_height(candidate_block)?;
 
     let mut vns = vec![];
+    let mut signers = HashSet::with_capacity(qc.signatures().len());
     for signature in qc.signatures() {
+        if !signers.insert(signature.public_key()) {
+            return Err(ProposalValidationError::QCDuplicateSignature {
+                qc: qc.clone(),
+                validator: signature.public_key().to_string(),
+            }
+            .into());
+        }
         let vn = epoch_manager
             .get_validator_node_by_public_key(qc.epoch(), signature.public_key())
             .await?;
@@ -168,12 +564,16 @@ pub async fn check_quorum_certificate<TConsensusSpec: ConsensusSpec>(
         vns.push(vn.get_node_hash(candidate_block.network()));
     }
 
-    for (sign, leaf) in qc.signatures().iter().zip(vns.iter()) {
-        let challenge = vote_signing_service.create_challenge(leaf, qc.block_id(), &qc.decision());
-        if !sign.verify(challenge) {
-            return Err(ProposalValidationError::QCInvalidSignature { qc: qc.clone() }.into());
-        }
-    }
+    let challenges_and_signatures = qc
+        .signatures()
+        .iter()
+        .zip(vns.iter())
+        .map(|(signature, leaf)| {
+            let challenge = vote_signing_service.create_challenge(leaf, qc.block_id(), &qc.decision());
+            (challenge, signature)
+        })
+        .collect::<Vec<_>>();
+    check_qc_signatures(vote_signing_service, qc, &challenges_and_signatures)?;
     let committee_shard = epoch_manager
         .get_committee_info_by_validator_public_key(
             qc.epoch(),
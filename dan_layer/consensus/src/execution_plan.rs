@@ -0,0 +1,190 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tari_dan_storage::consensus_models::TransactionRecord;
+use tari_engine_types::substate::SubstateId;
+
+/// Partitions the transactions of a block into groups that can be executed independently of each other. Two
+/// transactions are in the same group if they (transitively) share an input or output substate. Groups are ordered by
+/// their first transaction and transactions within a group keep their block order, so the plan for a given list of
+/// transactions is always the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionPlan {
+    num_transactions: usize,
+    groups: Vec<Vec<usize>>,
+}
+
+impl ExecutionPlan {
+    /// Creates a plan from the substates involved in each transaction, in block order
+    pub fn new<I, S>(substates_per_transaction: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: IntoIterator<Item = SubstateId>,
+    {
+        // Union-find over the transaction indexes. The root of each set is its lowest index.
+        let mut parents = Vec::new();
+        let mut owners = HashMap::new();
+        for (index, substates) in substates_per_transaction.into_iter().enumerate() {
+            parents.push(index);
+            for substate_id in substates {
+                match owners.entry(substate_id) {
+                    Entry::Occupied(entry) => union(&mut parents, *entry.get(), index),
+                    Entry::Vacant(entry) => {
+                        entry.insert(index);
+                    },
+                }
+            }
+        }
+
+        let mut groups = Vec::<Vec<usize>>::new();
+        let mut group_of_root = HashMap::new();
+        for index in 0..parents.len() {
+            let root = find(&mut parents, index);
+            let group = *group_of_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(index);
+        }
+
+        Self {
+            num_transactions: parents.len(),
+            groups,
+        }
+    }
+
+    /// Creates a plan from the declared inputs of each transaction as well as any inputs and outputs that are known
+    /// from a previous execution
+    pub fn for_transactions<'a, I: IntoIterator<Item = &'a TransactionRecord>>(transactions: I) -> Self {
+        Self::new(transactions.into_iter().map(involved_substate_ids))
+    }
+
+    pub fn num_transactions(&self) -> usize {
+        self.num_transactions
+    }
+
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    /// Calls `execute` for every item on the rayon thread pool, one task per group. Items within a group are executed
+    /// one after the other in block order. The results are returned in the order of the given items and the first
+    /// error (in item order) is returned if any execution fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of items does not match the number of transactions in the plan
+    pub fn execute<T, R, E, F>(&self, items: Vec<T>, execute: F) -> Result<Vec<R>, E>
+    where
+        T: Send,
+        R: Send,
+        E: Send,
+        F: Fn(T) -> Result<R, E> + Sync,
+    {
+        self.execute_with_state(items, || (), |_, item| execute(item))
+            .into_iter()
+            .collect()
+    }
+
+    /// Like [ExecutionPlan::execute], but each group has its own state that is created by `new_state` and passed to
+    /// every execution in the group, so that an execution can see the effects of the executions before it. All
+    /// results are returned in the order of the given items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of items does not match the number of transactions in the plan
+    pub fn execute_with_state<T, R, S, N, F>(&self, items: Vec<T>, new_state: N, execute: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        N: Fn() -> S + Sync,
+        F: Fn(&mut S, T) -> R + Sync,
+    {
+        assert_eq!(
+            items.len(),
+            self.num_transactions,
+            "ExecutionPlan::execute: number of items does not match the plan"
+        );
+
+        let mut items = items.into_iter().map(Some).collect::<Vec<_>>();
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    // unwrap: each index appears in exactly one group
+                    .map(|index| (*index, items[*index].take().unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let executed = groups
+            .into_par_iter()
+            .map(|group| {
+                let mut state = new_state();
+                group
+                    .into_iter()
+                    .map(|(index, item)| (index, execute(&mut state, item)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = (0..self.num_transactions).map(|_| None).collect::<Vec<_>>();
+        for (index, result) in executed.into_iter().flatten() {
+            results[index] = Some(result);
+        }
+        // unwrap: every item was executed
+        results.into_iter().map(|result| result.unwrap()).collect()
+    }
+}
+
+/// Returns the substates that the transaction declares as inputs and, if it has been executed before, the inputs and
+/// outputs of that execution
+pub fn involved_substate_ids(transaction: &TransactionRecord) -> impl Iterator<Item = SubstateId> + '_ {
+    transaction
+        .transaction()
+        .all_inputs_substate_ids_iter()
+        .cloned()
+        .chain(
+            transaction
+                .resolved_inputs()
+                .into_iter()
+                .flatten()
+                .map(|input| input.versioned_substate_id().substate_id().clone()),
+        )
+        .chain(
+            transaction
+                .resulting_outputs()
+                .iter()
+                .map(|output| output.substate_id().clone()),
+        )
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    // Path compression
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let root_a = find(parents, a);
+    let root_b = find(parents, b);
+    if root_a < root_b {
+        parents[root_b] = root_a;
+    } else {
+        parents[root_a] = root_b;
+    }
+}
@@ -9,6 +9,7 @@ use tari_dan_storage::{
         Block,
         BlockDiff,
         LeafBlock,
+        LockedBlock,
         LockedSubstate,
         PendingStateTreeDiff,
         QuorumDecision,
@@ -32,7 +33,9 @@ use tari_transaction::TransactionId;
 pub struct BlockDecision {
     pub quorum_decision: Option<QuorumDecision>,
     pub locked_blocks: Vec<Block>,
-    pub finalized_transactions: Vec<Vec<TransactionAtom>>,
+    /// The committed blocks and the transactions that each finalized
+    pub committed_blocks: Vec<(Block, Vec<TransactionAtom>)>,
+    pub locked_block: LockedBlock,
 }
 
 #[derive(Debug, Clone)]
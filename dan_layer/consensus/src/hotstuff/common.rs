@@ -1,12 +1,45 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    time::Duration,
+};
+
 use log::*;
 use tari_common::configuration::Network;
 use tari_common_types::types::FixedHash;
-use tari_dan_common_types::{committee::Committee, shard::Shard, Epoch, NodeAddressable, NodeHeight};
-use tari_dan_storage::consensus_models::{Block, LeafBlock, PendingStateTreeDiff, QuorumCertificate};
-use tari_engine_types::substate::SubstateDiff;
+use tari_dan_common_types::{
+    committee::{Committee, CommitteeInfo},
+    optional::Optional,
+    shard::Shard,
+    Epoch,
+    NodeAddressable,
+    NodeHeight,
+};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        Command,
+        Decision,
+        ExecutedTransaction,
+        ForeignProposal,
+        LeafBlock,
+        PendingStateTreeDiff,
+        QuorumCertificate,
+        SubstateLockFlag,
+        TransactionPool,
+        TransactionPoolStage,
+        TransactionRecord,
+        VersionedSubstateIdLockIntent,
+    },
+    StateStore,
+};
+use tari_engine_types::{
+    commit_result::{ExecuteResult, FinalizeResult, RejectReason},
+    substate::SubstateDiff,
+};
 use tari_state_tree::{
     Hash,
     StagedTreeStore,
@@ -16,8 +49,15 @@ use tari_state_tree::{
     TreeStoreReader,
     Version,
 };
+use tari_transaction::TransactionId;
 
-use crate::traits::LeaderStrategy;
+use crate::{
+    hotstuff::{
+        substate_store::{PendingSubstateStore, SubstateSnapshot},
+        HotStuffError,
+    },
+    traits::{BlockTransactionExecutor, ConsensusSpec, LeaderStrategy},
+};
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::common";
 
@@ -59,7 +99,7 @@ pub fn calculate_last_dummy_block<TAddr: NodeAddressable, TLeaderStrategy: Leade
         new_height,
     );
     loop {
-        let leader = leader_strategy.get_leader_public_key(local_committee, current_height);
+        let leader = leader_strategy.get_leader_public_key(local_committee, epoch, current_height);
         let dummy_block = Block::dummy_block(
             network,
             *parent_block.block_id(),
@@ -89,6 +129,176 @@ pub fn calculate_last_dummy_block<TAddr: NodeAddressable, TLeaderStrategy: Leade
     Some(parent_block)
 }
 
+/// Returns the execution for a transaction whose max epoch has passed. The transaction is not executed, it is rejected
+/// with an EpochExpired reason. The involved shards are those of the mempool execution if the transaction was executed,
+/// otherwise those of its declared inputs, so that all involved shards ABORT it.
+pub fn create_epoch_expired_execution(transaction: TransactionRecord, epoch: Epoch) -> ExecutedTransaction {
+    let reason = RejectReason::EpochExpired(format!(
+        "Transaction max epoch {} is before the current epoch {}",
+        transaction
+            .transaction()
+            .max_epoch()
+            .map(|max_epoch| max_epoch.to_string())
+            .unwrap_or_else(|| "<none>".to_string()),
+        epoch
+    ));
+    create_rejected_execution(transaction, reason)
+}
+
+/// Returns the execution for a deferred transaction that could not be executed, e.g. because an input substate does
+/// not exist. The transaction is rejected with an ExecutionFailure reason and, like an expired transaction, the
+/// involved shards are those of its declared inputs.
+pub fn create_deferred_execution_failed_execution<T: Display>(
+    transaction: TransactionRecord,
+    error: T,
+) -> ExecutedTransaction {
+    let reason = RejectReason::ExecutionFailure(format!("Deferred execution failed: {error}"));
+    create_rejected_execution(transaction, reason)
+}
+
+fn create_rejected_execution(transaction: TransactionRecord, reason: RejectReason) -> ExecutedTransaction {
+    let resolved_inputs = transaction.resolved_inputs.unwrap_or_else(|| {
+        transaction
+            .transaction
+            .all_inputs_iter()
+            .map(|input| VersionedSubstateIdLockIntent::new(input.or_zero_version(), SubstateLockFlag::Read))
+            .collect()
+    });
+    let result = ExecuteResult {
+        finalize: FinalizeResult::new_rejected(transaction.transaction.id().into_array().into(), reason),
+    };
+
+    ExecutedTransaction::new(
+        transaction.transaction,
+        result,
+        resolved_inputs,
+        transaction.resulting_outputs,
+        Duration::ZERO,
+    )
+}
+
+/// Executes the transactions against a snapshot of their inputs (see [BlockTransactionExecutor::execute_block]) and
+/// returns the executions that succeeded. A transaction that failed is executed again when it is sequenced, which
+/// records the failure.
+pub fn execute_transactions_with_snapshot<TConsensusSpec: ConsensusSpec>(
+    transaction_executor: &TConsensusSpec::TransactionExecutor,
+    transactions: Vec<TransactionRecord>,
+    snapshot: &SubstateSnapshot,
+) -> HashMap<TransactionId, ExecutedTransaction> {
+    if transactions.is_empty() {
+        return HashMap::new();
+    }
+
+    let ids = transactions
+        .iter()
+        .map(|transaction| *transaction.id())
+        .collect::<Vec<_>>();
+    transaction_executor
+        .execute_block(transactions, snapshot)
+        .into_iter()
+        .zip(ids)
+        .filter_map(|(result, id)| match result {
+            Ok(executed) => Some((id, executed)),
+            Err(err) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Transaction {} failed to execute ahead of being sequenced: {}", id, err
+                );
+                None
+            },
+        })
+        .collect()
+}
+
+/// Returns the execution of the transaction that was executed ahead of being sequenced if every input that it read is
+/// still the latest version in the given store. Otherwise, the transaction must be executed again.
+pub fn take_execution_if_current<TStateStore: StateStore>(
+    store: &PendingSubstateStore<TStateStore>,
+    executions: &mut HashMap<TransactionId, ExecutedTransaction>,
+    transaction_id: &TransactionId,
+) -> Result<Option<ExecutedTransaction>, HotStuffError> {
+    let Some(executed) = executions.remove(transaction_id) else {
+        return Ok(None);
+    };
+
+    for input in executed.resolved_inputs() {
+        let id = input.versioned_substate_id();
+        let latest = store.get_latest(&id.substate_id).optional()?;
+        if latest.map_or(true, |substate| substate.version() != id.version()) {
+            debug!(
+                target: LOG_TARGET,
+                "Execution of transaction {} read input {} which is not the latest version. Executing again.",
+                transaction_id,
+                id
+            );
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(executed))
+}
+
+/// Aborts the transactions of foreign proposals that were proposed `timeout` or more blocks before the given block and
+/// have not been prepared locally. Timed out proposals without any such transactions transition to the Expired state
+/// at the height of the given block.
+pub fn abort_timed_out_foreign_proposal_transactions<TStateStore: StateStore>(
+    tx: &mut TStateStore::WriteTransaction<'_>,
+    transaction_pool: &TransactionPool<TStateStore>,
+    block: &Block,
+    timeout: NodeHeight,
+) -> Result<(), HotStuffError> {
+    let all_proposed = ForeignProposal::get_all_proposed(&**tx, block.height().saturating_sub(timeout))?;
+    for mut proposal in all_proposed {
+        let mut has_unresolved_transactions = false;
+
+        let (transactions, _missing) = TransactionRecord::get_any(&**tx, &proposal.transactions)?;
+        for transaction in transactions {
+            // We know the transaction but it's not finalised.
+            if !transaction.is_finalized() && transaction_pool.exists(&**tx, transaction.id())? {
+                let mut tx_rec = transaction_pool.get(&**tx, block.as_leaf_block(), transaction.id())?;
+                // If the transaction is still in the pool we have to check if it was at least locally prepared,
+                // otherwise abort it.
+                if tx_rec.stage() == TransactionPoolStage::New || tx_rec.stage() == TransactionPoolStage::Prepared {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Foreign proposal {} timed out. Aborting transaction {}",
+                        proposal.block_id,
+                        transaction.id(),
+                    );
+                    tx_rec.update_local_decision(tx, Decision::Abort)?;
+                    has_unresolved_transactions = true;
+                }
+            }
+        }
+        if !has_unresolved_transactions {
+            debug!(
+                target: LOG_TARGET,
+                "Foreign proposal {} expired at height {}",
+                proposal.block_id,
+                block.height(),
+            );
+            proposal.expire(tx, block.height(), block.epoch())?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the non-local shards involved in the locally prepared transactions of a block, in shard order. The block
+/// has a foreign index for each of these shards. Only the block contents are used, so proposers and voters compute the
+/// same shards before the block is executed.
+pub fn get_foreign_index_shards<'a, I: IntoIterator<Item = &'a Command>>(
+    commands: I,
+    local_committee_info: &CommitteeInfo,
+) -> BTreeSet<Shard> {
+    commands
+        .into_iter()
+        .filter(|command| command.local_prepared().is_some())
+        .flat_map(|command| command.evidence().substate_addresses_iter())
+        .map(|address| address.to_shard(local_committee_info.num_committees()))
+        .filter(|shard| *shard != local_committee_info.shard())
+        .collect()
+}
+
 pub fn diff_to_substate_changes(diff: &SubstateDiff) -> impl Iterator<Item = SubstateTreeChange> + '_ {
     diff.down_iter()
         .map(|(substate_id, _version)| SubstateTreeChange::Down {
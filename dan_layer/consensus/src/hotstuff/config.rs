@@ -1,8 +1,139 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::time::Duration;
+
+use tari_dan_common_types::NodeHeight;
+use tari_dan_storage::consensus_models::GenesisConfig;
+
+use crate::journal::JournalConfig;
+
 #[derive(Debug, Clone)]
 pub struct HotstuffConfig {
     pub max_base_layer_blocks_ahead: u64,
     pub max_base_layer_blocks_behind: u64,
+    /// The genesis parameters that the chain is started with. This must be the same for all committee members.
+    pub genesis: GenesisConfig,
+    /// If set, consensus decisions are written to an on-disk journal
+    pub journal: Option<JournalConfig>,
+    /// If set, the diffs of this many of the most recently committed blocks are kept so that they can be streamed to
+    /// consumers
+    pub committed_block_diff_retention: Option<u64>,
+    /// The number of blocks after a foreign proposal is proposed after which its transactions that have not been
+    /// prepared locally are aborted
+    pub foreign_proposal_timeout: NodeHeight,
+    /// The maximum number of dummy blocks that are created to fill the gap left by failed leaders before a proposal.
+    /// Proposals that would require more dummy blocks are rejected.
+    pub max_dummy_blocks: u64,
+    /// The maximum estimated size in bytes of the commands in a proposed block. A transaction that exceeds this size
+    /// on its own is proposed alone.
+    pub max_block_size_bytes: usize,
+    /// The maximum number of transactions that are taken from the transaction pool for a proposed block
+    pub max_block_transactions: usize,
+    /// Rejected proposals are kept for this many epochs so that operators can see why proposals were rejected
+    pub block_rejection_retention_epochs: u64,
+    /// Foreign proposals that are invalid or expired are kept for this many epochs before they are pruned
+    pub foreign_proposal_retention_epochs: u64,
+    /// If true, a proposal with a state merkle root that does not match the locally calculated root is rejected and
+    /// recorded as a block rejection. Otherwise, the local validator only abstains from voting for the proposal.
+    pub reject_state_merkle_root_mismatch: bool,
+    /// The maximum number of verified QCs that are cached so that the signatures of the same justify QC are not
+    /// verified again for each proposal
+    pub verified_qc_cache_size: usize,
+    /// If true, the leader of the next block executes the deferred transactions for its proposal in the background
+    /// while the votes for the current leaf block are collected, so that the proposal can be broadcast soon after the
+    /// QC is formed
+    pub enable_proposal_pipelining: bool,
+    pub pacemaker: PacemakerConfig,
+    pub deferred_execution: DeferredExecutionConfig,
+    pub peer_bans: PeerBanConfig,
+    pub vote_retry: VoteRetryConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct PacemakerConfig {
+    /// The leader timeout when the previous view did not fail
+    pub base_timeout: Duration,
+    /// The leader timeout is multiplied by this factor for each consecutive failed view
+    pub timeout_multiplier: f64,
+    /// The leader timeout never exceeds this value, not including jitter
+    pub max_timeout: Duration,
+}
+
+impl Default for PacemakerConfig {
+    fn default() -> Self {
+        Self {
+            base_timeout: Duration::from_secs(14),
+            timeout_multiplier: 2.0,
+            max_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Limits for retrying the execution of deferred transactions (transactions that were not executed by the mempool) that
+/// fail to execute, e.g. because an input substate does not exist
+#[derive(Debug, Clone)]
+pub struct DeferredExecutionConfig {
+    /// The number of times that execution may fail when proposing the transaction before it is proposed to ABORT
+    pub max_attempts: u32,
+    /// A transaction that fails to execute is proposed to ABORT if it was added to the pool at least this many blocks
+    /// ago, regardless of the number of attempts
+    pub max_age: NodeHeight,
+}
+
+impl DeferredExecutionConfig {
+    /// Returns true if a transaction whose execution has failed `num_attempts` times (including the current attempt)
+    /// should be aborted
+    pub fn is_exhausted(&self, num_attempts: u32, first_seen_height: NodeHeight, current_height: NodeHeight) -> bool {
+        num_attempts >= self.max_attempts || current_height.saturating_sub(first_seen_height) >= self.max_age
+    }
+}
+
+impl Default for DeferredExecutionConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            max_age: NodeHeight(100),
+        }
+    }
+}
+
+/// Peers that repeatedly send invalid proposals or votes are banned and their messages are dropped
+#[derive(Debug, Clone)]
+pub struct PeerBanConfig {
+    /// A peer is banned once its misbehavior score reaches this value
+    pub ban_threshold: u64,
+    /// Messages from a banned peer are dropped for this long
+    pub ban_duration: Duration,
+    /// The misbehavior score of a peer is reduced by one for each interval without an offence
+    pub score_decay_interval: Duration,
+}
+
+impl Default for PeerBanConfig {
+    fn default() -> Self {
+        Self {
+            ban_threshold: 10,
+            ban_duration: Duration::from_secs(60 * 60),
+            score_decay_interval: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+/// Votes that cannot be sent to the leader of the next block are retried with exponential backoff, for no longer than
+/// the leader timeout of the view
+#[derive(Debug, Clone)]
+pub struct VoteRetryConfig {
+    /// The maximum number of times that sending a vote is retried after the first attempt fails
+    pub max_retries: u32,
+    /// The delay before the first retry. The delay is doubled for each subsequent retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for VoteRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
 }
@@ -0,0 +1,125 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Debug, Formatter},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tari_dan_common_types::{optional::Optional, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{BlockId, HighQc, LastVoted, LeafBlock, LockedBlock, TransactionPoolStage},
+    StateStore,
+    StateStoreReadTransaction,
+};
+use tari_epoch_manager::EpochManagerReader;
+
+use crate::{
+    hotstuff::{pacemaker_handle::PaceMakerHandle, HotStuffError},
+    traits::{ConsensusSpec, LeaderStrategy},
+};
+
+/// A snapshot of the progress of consensus on this node
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsensusStatus {
+    pub epoch: Epoch,
+    pub leaf_block_id: BlockId,
+    pub leaf_block_height: NodeHeight,
+    pub locked_block_id: BlockId,
+    pub locked_block_height: NodeHeight,
+    pub high_qc_block_id: BlockId,
+    pub high_qc_block_height: NodeHeight,
+    /// The height of the last block that this node voted for, or None if it has not voted
+    pub last_voted_height: Option<NodeHeight>,
+    /// The height of the view that the pacemaker is currently waiting on
+    pub current_view_height: NodeHeight,
+    /// The leader timeout of the current view. This is zero until the pacemaker has started.
+    pub current_view_timeout: Duration,
+    /// True if this node is the leader for the block after the leaf block
+    pub is_leader: bool,
+    /// The number of transactions in the pool for each stage
+    pub transaction_pool_stage_counts: Vec<(TransactionPoolStage, usize)>,
+}
+
+/// Assembles [ConsensusStatus] snapshots on request. This can be used while the hotstuff worker is running.
+#[derive(Clone)]
+pub struct ConsensusStatusHandle<TConsensusSpec: ConsensusSpec> {
+    validator_addr: TConsensusSpec::Addr,
+    store: TConsensusSpec::StateStore,
+    epoch_manager: TConsensusSpec::EpochManager,
+    leader_strategy: TConsensusSpec::LeaderStrategy,
+    pacemaker: PaceMakerHandle,
+}
+
+impl<TConsensusSpec: ConsensusSpec> ConsensusStatusHandle<TConsensusSpec> {
+    pub(super) fn new(
+        validator_addr: TConsensusSpec::Addr,
+        store: TConsensusSpec::StateStore,
+        epoch_manager: TConsensusSpec::EpochManager,
+        leader_strategy: TConsensusSpec::LeaderStrategy,
+        pacemaker: PaceMakerHandle,
+    ) -> Self {
+        Self {
+            validator_addr,
+            store,
+            epoch_manager,
+            leader_strategy,
+            pacemaker,
+        }
+    }
+
+    pub async fn get_status(&self) -> Result<ConsensusStatus, HotStuffError> {
+        let epoch = self.epoch_manager.current_epoch().await?;
+        let (leaf_block, locked_block, high_qc, last_voted, transaction_pool_stage_counts) =
+            self.store.with_read_tx(|tx| {
+                let transaction_pool_stage_counts = TransactionPoolStage::all()
+                    .into_iter()
+                    .map(|stage| Ok((stage, tx.transaction_pool_count(Some(stage), None, None)?)))
+                    .collect::<Result<Vec<_>, HotStuffError>>()?;
+                Ok::<_, HotStuffError>((
+                    LeafBlock::get(tx)?,
+                    LockedBlock::get(tx)?,
+                    HighQc::get(tx)?,
+                    LastVoted::get(tx).optional()?,
+                    transaction_pool_stage_counts,
+                ))
+            })?;
+
+        let is_leader = if self.epoch_manager.is_this_validator_registered_for_epoch(epoch).await? {
+            let local_committee = self.epoch_manager.get_local_committee(epoch).await?;
+            self.leader_strategy.is_leader_for_next_block(
+                &self.validator_addr,
+                &local_committee,
+                epoch,
+                leaf_block.height(),
+            )
+        } else {
+            false
+        };
+
+        Ok(ConsensusStatus {
+            epoch,
+            leaf_block_id: *leaf_block.block_id(),
+            leaf_block_height: leaf_block.height(),
+            locked_block_id: *locked_block.block_id(),
+            locked_block_height: locked_block.height(),
+            high_qc_block_id: *high_qc.block_id(),
+            high_qc_block_height: high_qc.block_height(),
+            last_voted_height: last_voted.map(|last_voted| last_voted.height()),
+            current_view_height: self.pacemaker.current_height(),
+            current_view_timeout: self.pacemaker.current_timeout(),
+            is_leader,
+            transaction_pool_stage_counts,
+        })
+    }
+}
+
+impl<TConsensusSpec: ConsensusSpec> Debug for ConsensusStatusHandle<TConsensusSpec> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsensusStatusHandle")
+            .field("validator_addr", &self.validator_addr)
+            .field("pacemaker", &self.pacemaker)
+            .finish()
+    }
+}
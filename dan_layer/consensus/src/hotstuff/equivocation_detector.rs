@@ -0,0 +1,204 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use log::*;
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, ProposerEquivocation},
+    StateStore,
+};
+use tari_epoch_manager::EpochManagerReader;
+use tokio::sync::broadcast;
+
+use crate::{
+    block_validations::{check_hash_and_height, check_signature},
+    hotstuff::{error::HotStuffError, HotstuffEvent},
+    messages::{HotstuffMessage, ProposerEquivocationMessage},
+    traits::{hooks::ConsensusHooks, ConsensusSpec, OutboundMessaging},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::equivocation_detector";
+
+/// The number of heights below the current height for which proposals are kept in memory. Equivocation at older heights
+/// is only detected if the first proposal was persisted.
+const MAX_TRACKED_HEIGHTS: u64 = 32;
+
+/// Detects leaders that propose two different blocks for the same height and epoch. The first valid proposal from each
+/// proposer is kept in memory for recent heights, since the conflicting proposal may arrive before the first has been
+/// persisted.
+pub struct EquivocationDetector<TConsensusSpec: ConsensusSpec> {
+    store: TConsensusSpec::StateStore,
+    epoch_manager: TConsensusSpec::EpochManager,
+    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    tx_events: broadcast::Sender<HotstuffEvent>,
+    hooks: TConsensusSpec::Hooks,
+    proposals: HashMap<(Epoch, NodeHeight, PublicKey), Block>,
+}
+
+impl<TConsensusSpec: ConsensusSpec> EquivocationDetector<TConsensusSpec> {
+    pub fn new(
+        store: TConsensusSpec::StateStore,
+        epoch_manager: TConsensusSpec::EpochManager,
+        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        tx_events: broadcast::Sender<HotstuffEvent>,
+        hooks: TConsensusSpec::Hooks,
+    ) -> Self {
+        Self {
+            store,
+            epoch_manager,
+            outbound_messaging,
+            tx_events,
+            hooks,
+            proposals: HashMap::new(),
+        }
+    }
+
+    /// Returns a proposal that was previously received from the same proposer for the same height and epoch but that
+    /// is a different block, if any.
+    pub fn find_conflicting_proposal(&self, block: &Block) -> Result<Option<Block>, HotStuffError> {
+        let key = (block.epoch(), block.height(), block.proposed_by().clone());
+        if let Some(seen) = self.proposals.get(&key) {
+            if seen.id() == block.id() {
+                return Ok(None);
+            }
+            return Ok(Some(seen.clone()));
+        }
+
+        let conflicting = self
+            .store
+            .with_read_tx(|tx| {
+                Block::get_all_by_proposer_at_height(tx, block.epoch(), block.height(), block.proposed_by())
+            })?
+            .into_iter()
+            .find(|stored| stored.id() != block.id());
+        Ok(conflicting)
+    }
+
+    /// Keeps the block as the first proposal from its proposer at its height, and forgets proposals that are too old to
+    /// be tracked
+    pub fn track_proposal(&mut self, block: &Block, current_height: NodeHeight) {
+        self.proposals
+            .entry((block.epoch(), block.height(), block.proposed_by().clone()))
+            .or_insert_with(|| block.clone());
+        self.proposals
+            .retain(|(_, height, _), _| height.as_u64() + MAX_TRACKED_HEIGHTS >= current_height.as_u64());
+    }
+
+    /// Records an equivocation that this node detected and gossips the evidence to the local committee so that members
+    /// that only received one of the blocks can record it too. The second block must already have passed header
+    /// validation.
+    pub async fn on_equivocation_detected(
+        &mut self,
+        first_block: Block,
+        second_block: Block,
+    ) -> Result<(), HotStuffError> {
+        let equivocation = ProposerEquivocation::new(first_block, second_block);
+        warn!(
+            target: LOG_TARGET,
+            "🚨 Leader {} proposed two different blocks for height {}: {} and {}. Refusing to vote for the second block.",
+            equivocation.proposed_by(),
+            equivocation.height(),
+            equivocation.first_block_id(),
+            equivocation.second_block_id()
+        );
+
+        // If the equivocation is already recorded, the evidence has already been gossiped to the committee
+        if !self.record(&equivocation)? {
+            return Ok(());
+        }
+
+        let local_committee = self.epoch_manager.get_local_committee(equivocation.epoch()).await?;
+        self.outbound_messaging
+            .multicast(
+                local_committee.iter().map(|(addr, _)| addr),
+                HotstuffMessage::ProposerEquivocation(ProposerEquivocationMessage {
+                    first_block: equivocation.first_block().clone(),
+                    second_block: equivocation.second_block().clone(),
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Verifies and records equivocation evidence that was gossiped by another committee member. Gossiped evidence is
+    /// not gossiped again.
+    pub async fn handle_gossip(
+        &mut self,
+        from: TConsensusSpec::Addr,
+        msg: ProposerEquivocationMessage,
+    ) -> Result<(), HotStuffError> {
+        let ProposerEquivocationMessage {
+            first_block,
+            second_block,
+        } = msg;
+        let equivocation = ProposerEquivocation::new(first_block, second_block);
+
+        if !equivocation.is_conflicting() {
+            return Err(HotStuffError::InvalidEquivocationEvidence {
+                sender: from.to_string(),
+                details: format!(
+                    "blocks {} and {} are not different proposals from the same proposer at the same height",
+                    equivocation.first_block_id(),
+                    equivocation.second_block_id()
+                ),
+            });
+        }
+
+        for block in [equivocation.first_block(), equivocation.second_block()] {
+            check_hash_and_height(block)
+                .and_then(|_| check_signature(block))
+                .map_err(|err| HotStuffError::InvalidEquivocationEvidence {
+                    sender: from.to_string(),
+                    details: err.to_string(),
+                })?;
+        }
+
+        // Errors if the proposer is not a registered validator in the epoch
+        self.epoch_manager
+            .get_validator_node_by_public_key(equivocation.epoch(), equivocation.proposed_by())
+            .await?;
+
+        if self.record(&equivocation)? {
+            info!(
+                target: LOG_TARGET,
+                "🚨 Recorded {} gossiped by {}", equivocation, from
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Persists the equivocation and notifies subscribers. Returns false if an equivocation was already recorded for
+    /// the proposer at the same height and epoch.
+    fn record(&mut self, equivocation: &ProposerEquivocation) -> Result<bool, HotStuffError> {
+        let is_new = self.store.with_write_tx(|tx| {
+            if ProposerEquivocation::exists(
+                &**tx,
+                equivocation.epoch(),
+                equivocation.height(),
+                equivocation.proposed_by(),
+            )? {
+                return Ok::<_, HotStuffError>(false);
+            }
+            equivocation.insert(tx)?;
+            Ok(true)
+        })?;
+
+        if is_new {
+            self.hooks.on_proposer_equivocation(equivocation);
+            let _ignore = self.tx_events.send(HotstuffEvent::ProposerEquivocation {
+                proposed_by: equivocation.proposed_by().clone(),
+                epoch: equivocation.epoch(),
+                height: equivocation.height(),
+                first_block_id: *equivocation.first_block_id(),
+                second_block_id: *equivocation.second_block_id(),
+            });
+        }
+
+        Ok(is_new)
+    }
+}
@@ -4,7 +4,7 @@
 use tari_common_types::types::FixedHash;
 use tari_dan_common_types::{Epoch, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{BlockId, LeafBlock, LockedBlock, QuorumCertificate, TransactionPoolError},
+    consensus_models::{BlockId, LeafBlock, LockedBlock, QcId, QuorumCertificate, TransactionPoolError},
     StorageError,
 };
 use tari_epoch_manager::EpochManagerError;
@@ -91,6 +91,8 @@ pub enum HotStuffError {
     TransactionExecutorError(String),
     #[error("Invalid sync request: {details}")]
     InvalidSyncRequest { details: String },
+    #[error("Invalid proposer equivocation evidence from {sender}: {details}")]
+    InvalidEquivocationEvidence { sender: String, details: String },
     #[error("Some input versions were not resolved at execution time: {0}")]
     VersionedSubstateIdError(#[from] VersionedSubstateIdError),
     #[error("Substate store error: {0}")]
@@ -137,6 +139,16 @@ pub enum ProposalValidationError {
         block_id: BlockId,
         details: String,
     },
+    #[error(
+        "Block {block_id} proposed by {proposed_by} is justified by genesis QC {qc_id} but the local genesis QC is \
+         {expected_qc_id}. The proposer may be using a different genesis config."
+    )]
+    GenesisMismatch {
+        proposed_by: String,
+        block_id: BlockId,
+        qc_id: QcId,
+        expected_qc_id: QcId,
+    },
     #[error("Candidate block {candidate_block_height} is not higher than justify {justify_block_height}")]
     CandidateBlockNotHigherThanJustify {
         justify_block_height: NodeHeight,
@@ -157,6 +169,16 @@ pub enum ProposalValidationError {
         justify_block_height: NodeHeight,
         candidate_block_height: NodeHeight,
     },
+    #[error(
+        "Block {block_id} proposed by {proposed_by} requires {num_dummy_blocks} dummy blocks but the maximum is \
+         {max_dummy_blocks}"
+    )]
+    TooManyDummyBlocks {
+        proposed_by: String,
+        block_id: BlockId,
+        num_dummy_blocks: u64,
+        max_dummy_blocks: u64,
+    },
     #[error("Block {block_id} proposed by {proposed_by} is not the leader. Expect {expected_leader}")]
     NotLeader {
         proposed_by: String,
@@ -177,12 +199,21 @@ pub enum ProposalValidationError {
     MissingSignature { block_id: BlockId, height: NodeHeight },
     #[error("Proposed block {block_id} {height} has invalid signature")]
     InvalidSignature { block_id: BlockId, height: NodeHeight },
+    #[error("Proposed block {block_id} {height} is not signed by the leader {expected_leader}: {details}")]
+    InvalidProposerSignature {
+        block_id: BlockId,
+        height: NodeHeight,
+        expected_leader: String,
+        details: String,
+    },
     #[error("QC is not valid: {qc}")]
     QCisNotValid { qc: QuorumCertificate },
-    #[error("QC has invalid signature: {qc}")]
-    QCInvalidSignature { qc: QuorumCertificate },
+    #[error("QC has invalid signature from validator {validator}: {qc}")]
+    QCInvalidSignature { qc: QuorumCertificate, validator: String },
     #[error("Quorum was not reached: {qc}")]
     QuorumWasNotReached { qc: QuorumCertificate },
+    #[error("QC has more than one signature from validator {validator}: {qc}")]
+    QCDuplicateSignature { qc: QuorumCertificate, validator: String },
     #[error("Merkle proof error: {0}")]
     BalancedBinaryMerkleProofError(#[from] BalancedBinaryMerkleProofError),
     #[error("Invalid network in block {block_id}: expected {expected_network}, given {block_network}")]
@@ -191,14 +222,24 @@ pub enum ProposalValidationError {
         block_network: String,
         block_id: BlockId,
     },
-    #[error("Invalid state merkle root for block {block_id}: calculated {calculated} but block has {from_block}")]
-    InvalidStateMerkleRoot {
+    #[error(
+        "State merkle root mismatch for block {block_id} proposed by {proposed_by}: calculated {calculated} but block \
+         has {from_block}"
+    )]
+    StateMerkleRootMismatch {
+        proposed_by: String,
         block_id: BlockId,
         calculated: FixedHash,
         from_block: FixedHash,
     },
     #[error("Problem converting values")]
     QCConversionError,
+    #[error("Invalid committee membership proof from {sender} for epoch {epoch}: {details}")]
+    InvalidMembershipProof {
+        sender: String,
+        epoch: Epoch,
+        details: String,
+    },
     #[error("Validator {validator} is not in committee for shard {expected_shard}. Actual shard: {actual_shard}")]
     ValidatorNotInCommittee {
         validator: String,
@@ -218,4 +259,87 @@ pub enum ProposalValidationError {
         block_id: BlockId,
         base_layer_block_height: u64,
     },
+    #[error("Block {block_id} timestamp {timestamp} is before the justify block timestamp {justify_timestamp}")]
+    TimestampBeforeJustify {
+        block_id: BlockId,
+        timestamp: u64,
+        justify_timestamp: u64,
+    },
+    #[error("Block {block_id} timestamp {timestamp} is too far in the future (max {max_timestamp})")]
+    TimestampTooFarInFuture {
+        block_id: BlockId,
+        timestamp: u64,
+        max_timestamp: u64,
+    },
+    #[error("Block {block_id} has epoch {epoch} which is before the epoch {justify_epoch} of its justify block")]
+    EpochRegression {
+        block_id: BlockId,
+        epoch: Epoch,
+        justify_epoch: Epoch,
+    },
+    #[error(
+        "Block {block_id} starts epoch {epoch} but its justify block {justify_block} in epoch {justify_epoch} does \
+         not end the epoch"
+    )]
+    EpochChangeBeforeEpochEnd {
+        block_id: BlockId,
+        epoch: Epoch,
+        justify_block: BlockId,
+        justify_epoch: Epoch,
+    },
+    #[error("Block {block_id} changes the epoch from {justify_epoch} to {epoch} but is not an epoch start block")]
+    EpochChangeWithoutEpochStart {
+        block_id: BlockId,
+        epoch: Epoch,
+        justify_epoch: Epoch,
+    },
+    #[error("Block {block_id} is an epoch start block but does not change the epoch {epoch}")]
+    UnexpectedEpochStart { block_id: BlockId, epoch: Epoch },
+    #[error("Block {block_id} ends epoch {epoch} but does not carry an epoch checkpoint")]
+    MissingEpochCheckpoint { block_id: BlockId, epoch: Epoch },
+    #[error("Block {block_id} carries an epoch checkpoint but does not end the epoch")]
+    UnexpectedEpochCheckpoint { block_id: BlockId },
+    #[error("Block {block_id} has an invalid epoch checkpoint: {details}")]
+    InvalidEpochCheckpoint { block_id: BlockId, details: String },
+    #[error("Block {block_id} contains transaction {transaction_id} outside of its epoch bounds: {details}")]
+    TransactionOutsideEpochBounds {
+        block_id: BlockId,
+        transaction_id: TransactionId,
+        details: String,
+    },
+}
+
+impl ProposalValidationError {
+    /// Returns true if the error proves that the proposer created an invalid block, regardless of the state of the
+    /// local node. Only these errors count towards the misbehavior score of the proposer. Errors that may be caused by
+    /// the local node being behind, by clock skew or by a different view of the committee or of the base layer are
+    /// excluded.
+    pub fn is_peer_misbehavior(&self) -> bool {
+        matches!(
+            self,
+            Self::NodeHashMismatch { .. } |
+                Self::MissingForeignCounters { .. } |
+                Self::ProposingGenesisBlock { .. } |
+                Self::JustifyBlockInvalid { .. } |
+                Self::CandidateBlockNotHigherThanJustify { .. } |
+                Self::CandidateBlockHigherThanMaxFailures { .. } |
+                Self::CandidateBlockDoesNotExtendJustify { .. } |
+                Self::TooManyDummyBlocks { .. } |
+                Self::MissingSignature { .. } |
+                Self::InvalidSignature { .. } |
+                Self::InvalidProposerSignature { .. } |
+                Self::QCisNotValid { .. } |
+                Self::QCInvalidSignature { .. } |
+                Self::QuorumWasNotReached { .. } |
+                Self::QCDuplicateSignature { .. } |
+                Self::InvalidNetwork { .. } |
+                Self::TimestampBeforeJustify { .. } |
+                Self::EpochRegression { .. } |
+                Self::EpochChangeBeforeEpochEnd { .. } |
+                Self::EpochChangeWithoutEpochStart { .. } |
+                Self::UnexpectedEpochStart { .. } |
+                Self::MissingEpochCheckpoint { .. } |
+                Self::UnexpectedEpochCheckpoint { .. }
+        )
+    }
 }
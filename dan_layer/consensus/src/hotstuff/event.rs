@@ -1,10 +1,16 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_common_types::NodeHeight;
-use tari_dan_storage::consensus_models::BlockId;
+use serde::Serialize;
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, Decision};
+use tari_transaction::TransactionId;
 
-#[derive(Debug, Clone)]
+/// Events emitted by consensus. The serialized form is tagged with `type` and is consumed by external clients, so the
+/// variant and field names should not be changed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum HotstuffEvent {
     /// A block has been committed
     BlockCommitted { block_id: BlockId, height: NodeHeight },
@@ -12,4 +18,21 @@ pub enum HotstuffEvent {
     Failure { message: String },
     /// A leader has timed out
     LeaderTimeout { new_height: NodeHeight },
+    /// A leader proposed two different blocks for the same height and the equivocation was recorded
+    ProposerEquivocation {
+        proposed_by: PublicKey,
+        epoch: Epoch,
+        height: NodeHeight,
+        first_block_id: BlockId,
+        second_block_id: BlockId,
+    },
+    /// A proposed block was parked. `missing_tx_count` includes transactions that are known but still awaiting
+    /// execution.
+    BlockParked { block_id: BlockId, missing_tx_count: usize },
+    /// A foreign proposal from the given shard was applied
+    ForeignProposalReceived { shard: Shard, block_id: BlockId },
+    /// Consensus moved to a new epoch
+    EpochChanged { epoch: Epoch, committee_size: usize },
+    /// The final decision for a transaction was committed
+    Decided { tx_id: TransactionId, decision: Decision },
 }
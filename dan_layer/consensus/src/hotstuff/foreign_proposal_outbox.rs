@@ -0,0 +1,226 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+};
+
+use log::*;
+use tari_dan_common_types::{committee::Committee, optional::Optional, shard::Shard};
+use tari_dan_storage::{
+    consensus_models::{Block, ForeignProposalOutboxEntry},
+    StateStore,
+};
+use tari_epoch_manager::EpochManagerReader;
+
+use crate::{
+    hotstuff::HotStuffError,
+    messages::{ForeignProposalAckMessage, ForeignProposalMessage, HotstuffMessage},
+    traits::{Clock, ConsensusSpec, OutboundMessaging},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::foreign_proposal_outbox";
+
+/// Delay before the first retry. Subsequent retries double the delay up to `MAX_RETRY_DELAY_SECS`.
+const BASE_RETRY_DELAY_SECS: u64 = 2;
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+/// Entries that have not been acknowledged within this time are dropped
+const OUTBOX_EXPIRY_SECS: u64 = 30 * 60;
+
+/// Delivers foreign proposals to foreign committees, retrying until a member of the destination committee acknowledges
+/// the proposal.
+#[derive(Clone)]
+pub struct ForeignProposalOutbox<TConsensusSpec: ConsensusSpec> {
+    store: TConsensusSpec::StateStore,
+    epoch_manager: TConsensusSpec::EpochManager,
+    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    clock: TConsensusSpec::Clock,
+}
+
+impl<TConsensusSpec> ForeignProposalOutbox<TConsensusSpec>
+where TConsensusSpec: ConsensusSpec
+{
+    pub fn new(
+        store: TConsensusSpec::StateStore,
+        epoch_manager: TConsensusSpec::EpochManager,
+        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        clock: TConsensusSpec::Clock,
+    ) -> Self {
+        Self {
+            store,
+            epoch_manager,
+            outbound_messaging,
+            clock,
+        }
+    }
+
+    /// Adds the block to the outbox for each foreign committee and makes the first delivery attempt.
+    pub async fn send(
+        &mut self,
+        block: Block,
+        foreign_committees: HashMap<Shard, Committee<TConsensusSpec::Addr>>,
+    ) -> Result<(), HotStuffError> {
+        let now = self.clock.now();
+        let mut entries = self.store.with_write_tx(|tx| {
+            foreign_committees
+                .keys()
+                .map(|bucket| {
+                    let entry = ForeignProposalOutboxEntry::new(*bucket, *block.id(), now);
+                    entry.insert_if_not_exists(tx)?;
+                    Ok(entry)
+                })
+                .collect::<Result<Vec<_>, HotStuffError>>()
+        })?;
+
+        for entry in &mut entries {
+            let committee = &foreign_committees[&entry.bucket];
+            self.deliver(entry, committee, block.clone()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Retries delivery of all entries that are due and drops entries that have expired.
+    pub async fn on_tick(&mut self) -> Result<(), HotStuffError> {
+        let now = self.clock.now();
+        let expired_before = now.saturating_sub(OUTBOX_EXPIRY_SECS);
+        let entries = self.store.with_read_tx(|tx| ForeignProposalOutboxEntry::get_all(tx))?;
+        let (expired, entries) = entries
+            .into_iter()
+            .partition::<Vec<_>, _>(|entry| entry.created_at < expired_before);
+
+        // Only open a write transaction if there is something to remove
+        if !expired.is_empty() {
+            let num_expired = self
+                .store
+                .with_write_tx(|tx| ForeignProposalOutboxEntry::remove_created_before(tx, expired_before))?;
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ Dropped {} expired foreign proposal(s) that were never acknowledged", num_expired
+            );
+        }
+
+        for mut entry in entries.into_iter().filter(|entry| next_attempt_at(entry) <= now) {
+            let Some(block) = self.store.with_read_tx(|tx| Block::get(tx, &entry.block_id).optional())? else {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Block {} for outbox entry {} not found. Removing entry.", entry.block_id, entry
+                );
+                self.store.with_write_tx(|tx| {
+                    ForeignProposalOutboxEntry::remove(tx, entry.bucket, &entry.block_id)
+                })?;
+                continue;
+            };
+
+            let committee = match self
+                .epoch_manager
+                .get_committees_by_shards(block.epoch(), HashSet::from([entry.bucket]))
+                .await
+            {
+                Ok(mut committees) => committees.remove(&entry.bucket).unwrap_or_default(),
+                Err(err) => {
+                    self.record_attempt(&mut entry, Some(err.to_string()))?;
+                    continue;
+                },
+            };
+
+            info!(
+                target: LOG_TARGET,
+                "🔁 Retrying foreign proposal for block {} to bucket {} (attempt {})",
+                block,
+                entry.bucket,
+                entry.attempts + 1
+            );
+            self.deliver(&mut entry, &committee, block).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the outbox entry acknowledged by a member of the destination committee.
+    pub async fn on_ack(
+        &mut self,
+        from: TConsensusSpec::Addr,
+        message: ForeignProposalAckMessage,
+    ) -> Result<(), HotStuffError> {
+        let vn = self.epoch_manager.get_validator_node(message.epoch, &from).await?;
+        let committee_info = self
+            .epoch_manager
+            .get_committee_info_for_substate(message.epoch, vn.shard_key)
+            .await?;
+
+        let is_removed = self.store.with_write_tx(|tx| {
+            ForeignProposalOutboxEntry::remove(tx, committee_info.shard(), &message.block_id)
+        })?;
+
+        if is_removed {
+            debug!(
+                target: LOG_TARGET,
+                "✅ Foreign proposal for block {} acknowledged by {} (bucket {})",
+                message.block_id,
+                from,
+                committee_info.shard()
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn deliver(
+        &mut self,
+        entry: &mut ForeignProposalOutboxEntry,
+        committee: &Committee<TConsensusSpec::Addr>,
+        block: Block,
+    ) -> Result<(), HotStuffError> {
+        let membership_proof = match self
+            .epoch_manager
+            .get_our_committee_membership_proof(block.epoch())
+            .await
+        {
+            Ok(proof) => proof,
+            Err(err) => return self.record_attempt(entry, Some(err.to_string())),
+        };
+
+        let result = self
+            .outbound_messaging
+            .multicast(
+                committee.iter().map(|(addr, _)| addr),
+                HotstuffMessage::ForeignProposal(ForeignProposalMessage {
+                    block,
+                    membership_proof,
+                }),
+            )
+            .await;
+
+        if let Err(ref err) = result {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ Failed to send foreign proposal for {}: {}. Will retry.", entry, err
+            );
+        }
+
+        self.record_attempt(entry, result.err().map(|e| e.to_string()))
+    }
+
+    fn record_attempt(
+        &self,
+        entry: &mut ForeignProposalOutboxEntry,
+        error: Option<String>,
+    ) -> Result<(), HotStuffError> {
+        entry.record_attempt(self.clock.now(), error);
+        self.store.with_write_tx(|tx| entry.update(tx))?;
+        Ok(())
+    }
+}
+
+fn next_attempt_at(entry: &ForeignProposalOutboxEntry) -> u64 {
+    match entry.last_attempt_at {
+        Some(last_attempt_at) => {
+            let exp = entry.attempts.saturating_sub(1).min(16);
+            let delay = cmp::min(BASE_RETRY_DELAY_SECS << exp, MAX_RETRY_DELAY_SECS);
+            last_attempt_at.saturating_add(delay)
+        },
+        None => entry.created_at,
+    }
+}
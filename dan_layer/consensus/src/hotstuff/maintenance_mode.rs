@@ -0,0 +1,64 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Controls whether this validator takes part in consensus. While in maintenance mode, the validator keeps receiving,
+/// validating and persisting proposals so that it stays in sync, but it does not propose, vote or send new views.
+/// Clones share the same state.
+#[derive(Debug, Clone)]
+pub struct MaintenanceMode {
+    sender: Arc<watch::Sender<bool>>,
+    receiver: watch::Receiver<bool>,
+}
+
+impl MaintenanceMode {
+    pub fn new(is_enabled: bool) -> Self {
+        let (sender, receiver) = watch::channel(is_enabled);
+        Self {
+            sender: Arc::new(sender),
+            receiver,
+        }
+    }
+
+    /// Enters maintenance mode. Returns false if the validator was already in maintenance mode.
+    pub fn enter(&self) -> bool {
+        self.set(true)
+    }
+
+    /// Leaves maintenance mode. Returns false if the validator was not in maintenance mode.
+    pub fn resume(&self) -> bool {
+        self.set(false)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Waits until maintenance mode is entered or left and returns the new state
+    pub(crate) async fn changed(&mut self) -> bool {
+        if self.receiver.changed().await.is_err() {
+            // The sender is held by this instance, so this cannot happen
+            return std::future::pending().await;
+        }
+        *self.receiver.borrow_and_update()
+    }
+
+    fn set(&self, is_enabled: bool) -> bool {
+        self.sender.send_if_modified(|current| {
+            if *current == is_enabled {
+                return false;
+            }
+            *current = is_enabled;
+            true
+        })
+    }
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
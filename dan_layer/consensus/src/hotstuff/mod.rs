@@ -2,9 +2,13 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 mod common;
 mod config;
+mod consensus_status;
 mod current_height;
+mod equivocation_detector;
 mod error;
 mod event;
+mod foreign_proposal_outbox;
+mod maintenance_mode;
 mod on_beat;
 mod on_force_beat;
 mod on_inbound_message;
@@ -23,15 +27,25 @@ mod on_sync_request;
 mod block_change_set;
 mod pacemaker;
 mod pacemaker_handle;
+mod peer_bans;
 mod proposer;
+mod qc_timing_tracker;
 mod state_machine;
 pub mod substate_store;
+mod verified_qc_cache;
+mod view_timeout;
 mod vote_receiver;
+mod vote_sender;
 mod worker;
 
 pub use common::*;
-pub use config::HotstuffConfig;
+pub use config::{DeferredExecutionConfig, HotstuffConfig, PacemakerConfig, PeerBanConfig, VoteRetryConfig};
+pub use consensus_status::{ConsensusStatus, ConsensusStatusHandle};
 pub use error::*;
 pub use event::*;
+pub use maintenance_mode::MaintenanceMode;
+pub use peer_bans::PeerBanTracker;
 pub use state_machine::*;
+pub use verified_qc_cache::VerifiedQcCache;
+pub use view_timeout::*;
 pub use worker::*;
@@ -5,11 +5,14 @@ use std::collections::{BTreeMap, HashSet, VecDeque};
 
 use log::*;
 use tari_common::configuration::Network;
-use tari_dan_common_types::{optional::Optional, NodeAddressable, NodeHeight};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{optional::Optional, Epoch, NodeAddressable, NodeHeight};
 use tari_dan_storage::{
     consensus_models::{
         Block,
+        BlockId,
         ExecutedTransaction,
+        MissingTransactionsRequest,
         TransactionAtom,
         TransactionPool,
         TransactionPoolRecord,
@@ -20,25 +23,34 @@ use tari_dan_storage::{
 };
 use tari_epoch_manager::EpochManagerReader;
 use tari_transaction::TransactionId;
-use tokio::{sync::mpsc, time};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 
-use super::config::HotstuffConfig;
+use super::{config::HotstuffConfig, equivocation_detector::EquivocationDetector, peer_bans::PeerBanTracker};
 use crate::{
     block_validations::{
         check_base_layer_block_hash,
+        check_epoch_checkpoint,
         check_hash_and_height,
         check_network,
         check_proposed_by_leader,
-        check_quorum_certificate,
+        check_quorum_certificate_cached,
         check_signature,
     },
-    hotstuff::error::HotStuffError,
+    hotstuff::{error::HotStuffError, HotstuffEvent, VerifiedQcCache},
     messages::{HotstuffMessage, ProposalMessage, RequestMissingTransactionsMessage},
-    traits::{ConsensusSpec, OutboundMessaging},
+    traits::{Clock, ConsensusHooks, ConsensusSpec, OutboundMessaging},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::inbound_messages";
 
+/// Missing transactions are requested from the proposer again if they have not all been received within this time
+const MISSING_TRANSACTIONS_REQUEST_TIMEOUT_SECS: u64 = 5;
+/// The proposer is not asked again for the transactions of a parked block after this many requests
+const MAX_MISSING_TRANSACTIONS_REQUEST_ATTEMPTS: u32 = 10;
+
 pub type IncomingMessageResult<TAddr> = Result<Option<(TAddr, HotstuffMessage)>, NeedsSync<TAddr>>;
 
 pub struct OnInboundMessage<TConsensusSpec: ConsensusSpec> {
@@ -52,11 +64,18 @@ pub struct OnInboundMessage<TConsensusSpec: ConsensusSpec> {
     tx_msg_ready: mpsc::UnboundedSender<(TConsensusSpec::Addr, HotstuffMessage)>,
     message_buffer: MessageBuffer<TConsensusSpec::Addr>,
     transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
+    equivocation_detector: EquivocationDetector<TConsensusSpec>,
+    verified_qcs: VerifiedQcCache,
+    tx_events: broadcast::Sender<HotstuffEvent>,
+    hooks: TConsensusSpec::Hooks,
+    clock: TConsensusSpec::Clock,
+    peer_bans: PeerBanTracker<TConsensusSpec>,
 }
 
 impl<TConsensusSpec> OnInboundMessage<TConsensusSpec>
 where TConsensusSpec: ConsensusSpec
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         network: Network,
         config: HotstuffConfig,
@@ -66,11 +85,23 @@ where TConsensusSpec: ConsensusSpec
         vote_signing_service: TConsensusSpec::SignatureService,
         outbound_messaging: TConsensusSpec::OutboundMessaging,
         transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
+        tx_events: broadcast::Sender<HotstuffEvent>,
+        hooks: TConsensusSpec::Hooks,
+        clock: TConsensusSpec::Clock,
+        peer_bans: PeerBanTracker<TConsensusSpec>,
     ) -> Self {
         let (tx_msg_ready, rx_msg_ready) = mpsc::unbounded_channel();
         Self {
             network,
+            verified_qcs: VerifiedQcCache::new(config.verified_qc_cache_size),
             config,
+            equivocation_detector: EquivocationDetector::new(
+                store.clone(),
+                epoch_manager.clone(),
+                outbound_messaging.clone(),
+                tx_events.clone(),
+                hooks.clone(),
+            ),
             store,
             epoch_manager,
             leader_strategy,
@@ -79,6 +110,10 @@ where TConsensusSpec: ConsensusSpec
             tx_msg_ready,
             message_buffer: MessageBuffer::new(rx_msg_ready),
             transaction_pool,
+            tx_events,
+            hooks,
+            clock,
+            peer_bans,
         }
     }
 
@@ -88,14 +123,33 @@ where TConsensusSpec: ConsensusSpec
         from: TConsensusSpec::Addr,
         msg: HotstuffMessage,
     ) -> Result<(), HotStuffError> {
+        if self.peer_bans.is_banned(&from)? {
+            debug!(
+                target: LOG_TARGET,
+                "🚫 Dropping {} message from banned peer {}",
+                msg,
+                from
+            );
+            self.hooks.on_banned_peer_message_dropped(&from, &msg);
+            return Ok(());
+        }
+
         match msg {
             HotstuffMessage::Proposal(msg) => {
-                self.process_local_proposal(current_height, msg).await?;
+                let epoch = msg.block.epoch();
+                let proposed_by = msg.block.proposed_by().clone();
+                let result = self.process_local_proposal(current_height, msg).await;
+                self.record_invalid_proposal(epoch, &proposed_by, result).await?;
             },
             HotstuffMessage::ForeignProposal(ref proposal) => {
-                self.check_proposal(&proposal.block).await?;
+                let result = self.check_proposal(&proposal.block).await;
+                self.record_invalid_proposal(proposal.block.epoch(), proposal.block.proposed_by(), result)
+                    .await?;
                 self.report_message_ready(from, msg)?;
             },
+            HotstuffMessage::ProposerEquivocation(msg) => {
+                self.equivocation_detector.handle_gossip(from, msg).await?;
+            },
             msg => {
                 self.report_message_ready(from, msg)?;
             },
@@ -118,8 +172,39 @@ where TConsensusSpec: ConsensusSpec
         self.message_buffer.clear_buffer();
     }
 
-    async fn check_proposal(&self, block: &Block) -> Result<(), HotStuffError> {
+    /// Clears the cache of verified QCs. This must be called when the epoch changes because committee membership may
+    /// have changed.
+    pub fn clear_verified_qc_cache(&mut self) {
+        self.verified_qcs.clear();
+    }
+
+    /// Adds an offence to the misbehavior score of the proposer if the proposal is invalid. The proposer is charged
+    /// rather than the sender, because proposals are forwarded by peers that did not create them. The error is
+    /// returned as is.
+    async fn record_invalid_proposal(
+        &self,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        result: Result<(), HotStuffError>,
+    ) -> Result<(), HotStuffError> {
+        if let Err(HotStuffError::ProposalValidationError(ref err)) = result {
+            if err.is_peer_misbehavior() {
+                let maybe_proposer = self
+                    .epoch_manager
+                    .get_validator_node_by_public_key(epoch, proposed_by)
+                    .await
+                    .optional()?;
+                if let Some(proposer) = maybe_proposer {
+                    self.peer_bans.record_offence(&proposer.address, err)?;
+                }
+            }
+        }
+        result
+    }
+
+    async fn check_proposal(&mut self, block: &Block) -> Result<(), HotStuffError> {
         check_base_layer_block_hash::<TConsensusSpec>(block, &self.epoch_manager, &self.config).await?;
+        check_epoch_checkpoint::<TConsensusSpec>(block, &self.epoch_manager).await?;
         check_network(block, self.network)?;
         check_hash_and_height(block)?;
         let committee_for_block = self
@@ -128,7 +213,17 @@ where TConsensusSpec: ConsensusSpec
             .await?;
         check_proposed_by_leader(&self.leader_strategy, &committee_for_block, block)?;
         check_signature(block)?;
-        check_quorum_certificate::<TConsensusSpec>(block, &self.vote_signing_service, &self.epoch_manager).await?;
+        let is_hit = check_quorum_certificate_cached::<TConsensusSpec>(
+            block,
+            &self.vote_signing_service,
+            &self.epoch_manager,
+            &mut self.verified_qcs,
+        )
+        .await?;
+        // The genesis QC is never verified or cached
+        if !block.justify().is_genesis() {
+            self.hooks.on_verified_qc_cache_lookup(is_hit);
+        }
         Ok(())
     }
 
@@ -147,6 +242,16 @@ where TConsensusSpec: ConsensusSpec
             current_height,
         );
 
+        // Equivocation is checked before stale proposals are ignored, because the conflicting proposal may only arrive
+        // after this node has moved past its height. The conflicting proposal is never voted for.
+        if let Some(first_block) = self.equivocation_detector.find_conflicting_proposal(&block)? {
+            self.check_proposal(&block).await?;
+            self.equivocation_detector
+                .on_equivocation_detected(first_block, block)
+                .await?;
+            return Ok(());
+        }
+
         if block.height() < current_height {
             info!(
                 target: LOG_TARGET,
@@ -158,6 +263,7 @@ where TConsensusSpec: ConsensusSpec
         }
 
         self.check_proposal(&block).await?;
+        self.equivocation_detector.track_proposal(&block, current_height);
         let Some(ready_block) = self.handle_missing_transactions(block).await? else {
             // Block not ready
             return Ok(());
@@ -207,11 +313,14 @@ where TConsensusSpec: ConsensusSpec
                     if transaction.is_executed() {
                         // This should never fail
                         let executed = ExecutedTransaction::try_from(transaction)?;
-                        self.transaction_pool.insert(tx, executed.to_atom())?;
+                        self.transaction_pool.insert(tx, executed.to_atom(), current_height)?;
                     } else {
                         // Deferred execution
-                        self.transaction_pool
-                            .insert(tx, TransactionAtom::deferred(*transaction.id()))?;
+                        self.transaction_pool.insert(
+                            tx,
+                            TransactionAtom::deferred(*transaction.id()),
+                            current_height,
+                        )?;
                     }
                 }
 
@@ -231,6 +340,83 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 
+    /// Requests the transactions that are still missing for parked blocks from their proposers if the previous request
+    /// was not fully answered within the retry timeout. A proposer that only responded with some of the transactions
+    /// is asked for the remainder.
+    pub async fn retry_missing_transactions_requests(
+        &mut self,
+        current_height: NodeHeight,
+    ) -> Result<(), HotStuffError> {
+        let requested_before = self
+            .clock
+            .now()
+            .saturating_sub(MISSING_TRANSACTIONS_REQUEST_TIMEOUT_SECS);
+        let requests = self
+            .store
+            .with_read_tx(|tx| MissingTransactionsRequest::get_all_due(tx, current_height, requested_before))?;
+
+        for request in requests {
+            if request.attempts >= MAX_MISSING_TRANSACTIONS_REQUEST_ATTEMPTS {
+                debug!(
+                    target: LOG_TARGET,
+                    "Not requesting missing transactions for parked block {} after {} attempts",
+                    request.block_id,
+                    request.attempts
+                );
+                continue;
+            }
+
+            info!(
+                target: LOG_TARGET,
+                "🔁 Requesting {} missing transaction(s) for parked block {} from {} again (attempt {})",
+                request.transaction_ids.len(),
+                request.block_id,
+                request.proposed_by,
+                request.attempts + 1
+            );
+            self.request_missing_transactions(
+                request.epoch,
+                request.block_id,
+                &request.proposed_by,
+                request.transaction_ids,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn request_missing_transactions(
+        &mut self,
+        epoch: Epoch,
+        block_id: BlockId,
+        proposed_by: &PublicKey,
+        transaction_ids: HashSet<TransactionId>,
+    ) -> Result<(), HotStuffError> {
+        // The attempt is recorded before sending so that a proposer that cannot be reached is not asked again until
+        // the retry timeout has elapsed
+        self.store
+            .with_write_tx(|tx| MissingTransactionsRequest::record_attempt(tx, &block_id, self.clock.now()))?;
+
+        let vn = self
+            .epoch_manager
+            .get_validator_node_by_public_key(epoch, proposed_by)
+            .await?;
+
+        self.outbound_messaging
+            .send(
+                vn.address,
+                HotstuffMessage::RequestMissingTransactions(RequestMissingTransactionsMessage {
+                    block_id,
+                    epoch,
+                    transactions: transaction_ids,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
     fn report_message_ready(&self, from: TConsensusSpec::Addr, msg: HotstuffMessage) -> Result<(), HotStuffError> {
         self.tx_msg_ready
             .send((from, msg))
@@ -249,26 +435,13 @@ where TConsensusSpec: ConsensusSpec
                 target: LOG_TARGET,
                 "🔥 Block {} has {} missing transactions and {} awaiting execution", block, missing_tx_ids.len(), awaiting_execution.len(),
             );
+            let _ignore = self.tx_events.send(HotstuffEvent::BlockParked {
+                block_id: *block.id(),
+                missing_tx_count: missing_tx_ids.len() + awaiting_execution.len(),
+            });
 
             if !missing_tx_ids.is_empty() {
-                let block_id = *block.id();
-                let epoch = block.epoch();
-                let block_proposed_by = block.proposed_by().clone();
-
-                let vn = self
-                    .epoch_manager
-                    .get_validator_node_by_public_key(epoch, &block_proposed_by)
-                    .await?;
-
-                self.outbound_messaging
-                    .send(
-                        vn.address,
-                        HotstuffMessage::RequestMissingTransactions(RequestMissingTransactionsMessage {
-                            block_id,
-                            epoch,
-                            transactions: missing_tx_ids,
-                        }),
-                    )
+                self.request_missing_transactions(block.epoch(), *block.id(), block.proposed_by(), missing_tx_ids)
                     .await?;
             }
 
@@ -6,29 +6,40 @@ use std::sync::Arc;
 use tari_dan_common_types::NodeHeight;
 use tokio::sync::watch;
 
+/// A leader failure detected by the pacemaker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeaderTimeout {
+    /// The height that the pacemaker moved to after the leader failed to propose
+    pub new_height: NodeHeight,
+    /// The number of consecutive views that have failed, including this one
+    pub round: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct OnLeaderTimeout {
     // todo: consider using a different sync construct, like an mpsc channel
-    receiver: watch::Receiver<NodeHeight>,
-    sender: Arc<watch::Sender<NodeHeight>>,
+    receiver: watch::Receiver<LeaderTimeout>,
+    sender: Arc<watch::Sender<LeaderTimeout>>,
 }
 
 impl OnLeaderTimeout {
     pub fn new() -> Self {
-        let (sender, receiver) = watch::channel(NodeHeight::zero());
+        let (sender, receiver) = watch::channel(LeaderTimeout::default());
         Self {
             receiver,
             sender: Arc::new(sender),
         }
     }
 
-    pub async fn wait(&mut self) -> NodeHeight {
+    pub async fn wait(&mut self) -> LeaderTimeout {
         self.receiver.changed().await.expect("sender can never be dropped");
         // This could lead to a more recent value being seen. Idk if that is ok...
         *self.receiver.borrow()
     }
 
-    pub fn leader_timed_out(&self, new_height: NodeHeight) {
-        self.sender.send(new_height).expect("receiver can never be dropped")
+    pub fn leader_timed_out(&self, new_height: NodeHeight, round: u32) {
+        self.sender
+            .send(LeaderTimeout { new_height, round })
+            .expect("receiver can never be dropped")
     }
 }
@@ -4,7 +4,7 @@
 use log::*;
 use tari_dan_common_types::{optional::Optional, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{HighQc, LastSentVote},
+    consensus_models::{HighQc, LastSentVote, LastVoted},
     StateStore,
 };
 use tari_epoch_manager::EpochManagerReader;
@@ -51,24 +51,28 @@ impl<TConsensusSpec: ConsensusSpec> OnNextSyncViewHandler<TConsensusSpec> {
             return Ok(());
         }
 
-        let (high_qc, last_sent_vote) = self.store.with_read_tx(|tx| {
+        let (high_qc, last_voted_height, last_sent_vote) = self.store.with_read_tx(|tx| {
             let high_qc = HighQc::get(tx)?.get_quorum_certificate(tx)?;
+            let last_voted_height = LastVoted::get(tx)
+                .optional()?
+                .map_or_else(NodeHeight::zero, |last_voted| last_voted.height());
             let last_sent_vote = LastSentVote::get(tx)
                 .optional()?
                 .filter(|vote| high_qc.block_height() < vote.block_height);
-            Ok::<_, HotStuffError>((high_qc, last_sent_vote))
+            Ok::<_, HotStuffError>((high_qc, last_voted_height, last_sent_vote))
         })?;
 
         let local_committee = self.epoch_manager.get_local_committee(current_epoch).await?;
         let next_leader = self
             .leader_strategy
-            .get_leader_for_next_block(&local_committee, new_height);
+            .get_leader_for_next_block(&local_committee, current_epoch, new_height);
 
         info!(target: LOG_TARGET, "🌟 Send NEWVIEW {new_height} HighQC: {} to {next_leader}", high_qc);
         let message = NewViewMessage {
             high_qc,
             new_height,
             epoch: current_epoch,
+            last_voted_height,
             last_vote: last_sent_vote.map(VoteMessage::from),
         };
 
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     num::NonZeroU64,
 };
 
@@ -10,11 +10,9 @@ use indexmap::IndexMap;
 use log::*;
 use tari_common::configuration::Network;
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_crypto::tari_utilities::epoch_time::EpochTime;
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
     optional::Optional,
-    shard::Shard,
     Epoch,
     NodeHeight,
 };
@@ -22,6 +20,7 @@ use tari_dan_storage::{
     consensus_models::{
         Block,
         Command,
+        EpochCheckpoint,
         EpochEvent,
         ExecutedTransaction,
         ForeignProposal,
@@ -32,8 +31,8 @@ use tari_dan_storage::{
         LockedBlock,
         PendingStateTreeDiff,
         QuorumCertificate,
-        SubstateChange,
         SubstateLockFlag,
+        TransactionAtom,
         TransactionPool,
         TransactionPoolRecord,
         TransactionPoolStage,
@@ -44,17 +43,25 @@ use tari_dan_storage::{
 };
 use tari_epoch_manager::EpochManagerReader;
 use tari_transaction::TransactionId;
+use tokio::task;
 
 use crate::{
     hotstuff::{
         calculate_state_merkle_diff,
+        create_deferred_execution_failed_execution,
+        create_epoch_expired_execution,
         error::HotStuffError,
-        substate_store::PendingSubstateStore,
+        execute_transactions_with_snapshot,
+        get_foreign_index_shards,
+        substate_store::{PendingSubstateStore, SubstateSnapshot},
+        take_execution_if_current,
+        HotstuffConfig,
         EXHAUST_DIVISOR,
     },
     messages::{HotstuffMessage, ProposalMessage},
     traits::{
         BlockTransactionExecutor,
+        ConsensusHooks,
         ConsensusSpec,
         OutboundMessaging,
         ValidatorSignatureService,
@@ -64,6 +71,11 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_propose_locally";
 
+/// Estimated size in bytes of a block without its commands and the signatures of its justify QC
+const BLOCK_HEADER_SIZE_ESTIMATE: usize = 1024;
+/// Estimated size in bytes of each signature and leaf hash in the justify QC
+const QC_SIGNATURE_SIZE_ESTIMATE: usize = 160;
+
 pub struct OnPropose<TConsensusSpec: ConsensusSpec> {
     network: Network,
     store: TConsensusSpec::StateStore,
@@ -72,6 +84,10 @@ pub struct OnPropose<TConsensusSpec: ConsensusSpec> {
     transaction_executor: TConsensusSpec::TransactionExecutor,
     signing_service: TConsensusSpec::SignatureService,
     outbound_messaging: TConsensusSpec::OutboundMessaging,
+    clock: TConsensusSpec::Clock,
+    config: HotstuffConfig,
+    hooks: TConsensusSpec::Hooks,
+    prepared_proposal: Option<PreparedProposal>,
 }
 
 impl<TConsensusSpec> OnPropose<TConsensusSpec>
@@ -85,6 +101,9 @@ where TConsensusSpec: ConsensusSpec
         transaction_executor: TConsensusSpec::TransactionExecutor,
         signing_service: TConsensusSpec::SignatureService,
         outbound_messaging: TConsensusSpec::OutboundMessaging,
+        clock: TConsensusSpec::Clock,
+        config: HotstuffConfig,
+        hooks: TConsensusSpec::Hooks,
     ) -> Self {
         Self {
             network,
@@ -94,6 +113,122 @@ where TConsensusSpec: ConsensusSpec
             transaction_executor,
             signing_service,
             outbound_messaging,
+            clock,
+            config,
+            hooks,
+            prepared_proposal: None,
+        }
+    }
+
+    /// Starts executing the deferred transactions for the block after the given leaf block in the background, against
+    /// the speculative state of the leaf block. [OnPropose::handle] uses the executions if the next block is proposed
+    /// on top of the same leaf block and discards them otherwise.
+    pub fn prepare_next_proposal(&mut self, epoch: Epoch, leaf_block: LeafBlock) -> Result<(), HotStuffError> {
+        if !self.config.enable_proposal_pipelining {
+            return Ok(());
+        }
+        if self
+            .prepared_proposal
+            .as_ref()
+            .is_some_and(|prepared| prepared.epoch == epoch && prepared.parent == leaf_block)
+        {
+            return Ok(());
+        }
+        if let Some(last_proposed) = self.store.with_read_tx(|tx| LastProposed::get(tx)).optional()? {
+            if last_proposed.height > leaf_block.height {
+                return Ok(());
+            }
+        }
+
+        if let Some(prepared) = self.prepared_proposal.take() {
+            prepared.task.abort();
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "⏩ Preparing the next proposal on top of leaf {} in epoch {}", leaf_block, epoch
+        );
+        let store = self.store.clone();
+        let transaction_pool = self.transaction_pool.clone();
+        let transaction_executor = self.transaction_executor.clone();
+        let max_block_size_bytes = self.config.max_block_size_bytes;
+        let max_block_transactions = self.config.max_block_transactions;
+        let task = task::spawn_blocking(move || {
+            prepare_deferred_executions::<TConsensusSpec>(
+                &store,
+                &transaction_pool,
+                &transaction_executor,
+                epoch,
+                &leaf_block,
+                max_block_size_bytes,
+                max_block_transactions,
+            )
+        });
+        self.prepared_proposal = Some(PreparedProposal {
+            epoch,
+            parent: leaf_block,
+            task,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the executions that were prepared for a block on top of the given leaf block, waiting for them if they
+    /// are still being prepared. Executions that were prepared on top of a different leaf block are discarded.
+    async fn take_prepared_executions(
+        &mut self,
+        epoch: Epoch,
+        leaf_block: &LeafBlock,
+    ) -> HashMap<TransactionId, ExecutedTransaction> {
+        let Some(prepared) = self.prepared_proposal.take() else {
+            return HashMap::new();
+        };
+
+        if prepared.epoch != epoch || prepared.parent != *leaf_block {
+            info!(
+                target: LOG_TARGET,
+                "🗑️ Discarding the proposal prepared on top of leaf {} because the leaf block is now {}",
+                prepared.parent,
+                leaf_block
+            );
+            // A blocking task cannot be cancelled once it has started, in which case the result is ignored
+            prepared.task.abort();
+            self.hooks.on_pipelined_proposal(false);
+            return HashMap::new();
+        }
+
+        match prepared.task.await {
+            Ok(Ok(executions)) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "⏩ Using {} prepared execution(s) for the proposal on top of leaf {}",
+                    executions.len(),
+                    leaf_block
+                );
+                self.hooks.on_pipelined_proposal(true);
+                executions
+            },
+            Ok(Err(err)) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Failed to prepare the proposal on top of leaf {}: {}. Executing transactions when proposing.",
+                    leaf_block,
+                    err
+                );
+                self.hooks.on_pipelined_proposal(false);
+                HashMap::new()
+            },
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Task preparing the proposal on top of leaf {} did not complete: {}. Executing transactions when \
+                     proposing.",
+                    leaf_block,
+                    err
+                );
+                self.hooks.on_pipelined_proposal(false);
+                HashMap::new()
+            },
         }
     }
 
@@ -180,12 +315,21 @@ where TConsensusSpec: ConsensusSpec
             .get_base_layer_block_height(base_layer_block_hash)
             .await?
             .unwrap();
+        // The epoch end block carries a checkpoint of the validator set of the epoch that it ends
+        let epoch_end_validator_set_hash = if propose_epoch_end {
+            Some(self.epoch_manager.get_validator_node_bmt_root(epoch).await?)
+        } else {
+            None
+        };
         // The epoch is greater only when the EpochEnd event is locked.
         let propose_epoch_start = qc_block.epoch() < epoch;
+        // Block timestamps must never go backwards, even if our clock is behind the justify block proposer's clock
+        let timestamp = self.clock.now().max(qc_block.timestamp());
+        let mut prepared_executions = self.take_prepared_executions(epoch, &leaf_block).await;
 
         let next_block = self.store.with_write_tx(|tx| {
             let high_qc = high_qc.get_quorum_certificate(&**tx)?;
-            let (next_block, executed_transactions) = self.build_next_block(
+            let (next_block, executed_transactions, deferred_execution_failures) = self.build_next_block(
                 tx,
                 epoch,
                 &leaf_block,
@@ -199,6 +343,9 @@ where TConsensusSpec: ConsensusSpec
                 base_layer_block_hash,
                 propose_epoch_start,
                 propose_epoch_end,
+                epoch_end_validator_set_hash,
+                timestamp,
+                &mut prepared_executions,
             )?;
 
             // Add executions for this block
@@ -224,6 +371,12 @@ where TConsensusSpec: ConsensusSpec
                 }
             }
 
+            // Failed deferred transactions are retried the next time they are proposed
+            for transaction_id in &deferred_execution_failures {
+                self.transaction_pool
+                    .record_deferred_execution_failure(tx, transaction_id)?;
+            }
+
             next_block.as_last_proposed().set(tx)?;
             Ok::<_, HotStuffError>(next_block)
         })?;
@@ -285,15 +438,21 @@ where TConsensusSpec: ConsensusSpec
         Ok(executed)
     }
 
-    /// Returns Ok(None) if the command cannot be sequenced yet due to lock conflicts.
+    /// Returns Ok(None) if the transaction has no involved shards, if its command does not fit in the block size
+    /// budget or if it is a deferred transaction that failed to execute and should be retried later. Space in the block
+    /// is only reserved for a command that is returned.
     #[allow(clippy::too_many_lines)]
     fn transaction_pool_record_to_command(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
         mut tx_rec: TransactionPoolRecord,
         local_committee_info: &CommitteeInfo,
+        next_height: NodeHeight,
         substate_store: &mut PendingSubstateStore<TConsensusSpec::StateStore>,
         executed_transactions: &mut HashMap<TransactionId, ExecutedTransaction>,
+        deferred_execution_failures: &mut Vec<TransactionId>,
+        block_size: &mut BlockSizeBudget,
+        prepared_executions: &mut HashMap<TransactionId, ExecutedTransaction>,
     ) -> Result<Option<Command>, HotStuffError> {
         // Execute deferred transaction
         if tx_rec.is_deferred() {
@@ -303,7 +462,45 @@ where TConsensusSpec: ConsensusSpec
                 tx_rec.transaction_id(),
             );
 
-            let executed = self.execute_transaction(substate_store, tx_rec.transaction_id())?;
+            let prepared = take_execution_if_current(substate_store, prepared_executions, tx_rec.transaction_id())?;
+            let result = match prepared {
+                Some(executed) => Ok(executed),
+                None => self.execute_transaction(substate_store, tx_rec.transaction_id()),
+            };
+            let executed = match result {
+                Ok(executed) => executed,
+                Err(HotStuffError::TransactionExecutorError(err)) => {
+                    deferred_execution_failures.push(*tx_rec.transaction_id());
+                    let num_attempts = tx_rec.deferred_execution_attempts() + 1;
+                    if !self.config.deferred_execution.is_exhausted(
+                        num_attempts,
+                        tx_rec.first_seen_height(),
+                        next_height,
+                    ) {
+                        warn!(
+                            target: LOG_TARGET,
+                            "⚠️ Deferred transaction {} failed to execute (attempt {}): {}. Retrying later.",
+                            tx_rec.transaction_id(),
+                            num_attempts,
+                            err,
+                        );
+                        return Ok(None);
+                    }
+
+                    warn!(
+                        target: LOG_TARGET,
+                        "⚠️ Deferred transaction {} failed to execute (attempt {}, first seen at height {}): {}. \
+                         Proposing to ABORT...",
+                        tx_rec.transaction_id(),
+                        num_attempts,
+                        tx_rec.first_seen_height(),
+                        err,
+                    );
+                    let transaction = TransactionRecord::get(tx, tx_rec.transaction_id())?;
+                    create_deferred_execution_failed_execution(transaction, err)
+                },
+                Err(err) => return Err(err),
+            };
             // Update the decision so that we can propose it
             tx_rec.set_local_decision(executed.decision());
             tx_rec.set_initial_evidence(executed.to_initial_evidence());
@@ -354,6 +551,9 @@ where TConsensusSpec: ConsensusSpec
             let involved = NonZeroU64::new(num_involved_shards as u64).expect("involved is 1");
             let leader_fee = tx_rec.calculate_leader_fee(involved, EXHAUST_DIVISOR);
             let tx_atom = tx_rec.get_final_transaction_atom(leader_fee);
+            if !try_reserve_command(block_size, executed_transactions, &tx_atom) {
+                return Ok(None);
+            }
             if tx_atom.decision.is_commit() {
                 let transaction = executed_transactions.get(tx_rec.transaction_id()).ok_or_else(|| {
                     HotStuffError::InvariantError(format!(
@@ -394,6 +594,10 @@ where TConsensusSpec: ConsensusSpec
         match tx_rec.current_stage() {
             // If the transaction is New, propose to Prepare it
             TransactionPoolStage::New => {
+                let tx_atom = tx_rec.get_local_transaction_atom();
+                if !try_reserve_command(block_size, executed_transactions, &tx_atom) {
+                    return Ok(None);
+                }
                 if tx_rec.current_local_decision().is_commit() {
                     let transaction = executed_transactions.get(tx_rec.transaction_id()).ok_or_else(|| {
                         HotStuffError::InvariantError(format!(
@@ -418,14 +622,20 @@ where TConsensusSpec: ConsensusSpec
                         // Only error if it is not related to lock errors
                         let _err = err.ok_or_storage_error()?;
                         // If the transaction does not lock, we should propose to abort it
-                        return Ok(Some(Command::Prepare(tx_rec.get_local_transaction_atom().abort())));
+                        return Ok(Some(Command::Prepare(tx_atom.abort())));
                     }
                 }
-                Ok(Some(Command::Prepare(tx_rec.get_local_transaction_atom())))
+                Ok(Some(Command::Prepare(tx_atom)))
             },
             // The transaction is Prepared, this stage is only _ready_ once we know that all local nodes
             // accepted Prepared so we propose LocalPrepared
-            TransactionPoolStage::Prepared => Ok(Some(Command::LocalPrepared(tx_rec.get_local_transaction_atom()))),
+            TransactionPoolStage::Prepared => {
+                let tx_atom = tx_rec.get_local_transaction_atom();
+                if !try_reserve_command(block_size, executed_transactions, &tx_atom) {
+                    return Ok(None);
+                }
+                Ok(Some(Command::LocalPrepared(tx_atom)))
+            },
             // The transaction is LocalPrepared, meaning that we know that all foreign and local nodes have
             // prepared. We can now propose to Accept it. We also propose the decision change which everyone
             // should agree with if they received the same foreign LocalPrepare.
@@ -438,6 +648,9 @@ where TConsensusSpec: ConsensusSpec
                 })?;
                 let leader_fee = tx_rec.calculate_leader_fee(involved, EXHAUST_DIVISOR);
                 let tx_atom = tx_rec.get_final_transaction_atom(leader_fee);
+                if !try_reserve_command(block_size, executed_transactions, &tx_atom) {
+                    return Ok(None);
+                }
                 if tx_atom.decision.is_commit() {
                     let transaction = tx_rec.get_transaction(tx)?;
                     let result = transaction.result().ok_or_else(|| {
@@ -471,7 +684,82 @@ where TConsensusSpec: ConsensusSpec
         }
     }
 
-    #[allow(clippy::too_many_lines)]
+    /// Returns an ABORT command for a new transaction whose max epoch has passed. All nodes create the same rejected
+    /// execution for the transaction so that it is removed from the pool deterministically.
+    fn expired_transaction_to_command(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+        mut tx_rec: TransactionPoolRecord,
+        epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        executed_transactions: &mut HashMap<TransactionId, ExecutedTransaction>,
+    ) -> Result<Option<Command>, HotStuffError> {
+        let transaction = TransactionRecord::get(tx, tx_rec.transaction_id())?;
+        let executed = create_epoch_expired_execution(transaction, epoch);
+        tx_rec.set_local_decision(executed.decision());
+        tx_rec.set_initial_evidence(executed.to_initial_evidence());
+        tx_rec.set_transaction_fee(executed.transaction_fee());
+        executed_transactions.insert(*executed.id(), executed);
+
+        let num_involved_shards =
+            local_committee_info.count_distinct_shards(tx_rec.atom().evidence.substate_addresses_iter());
+        if num_involved_shards == 0 {
+            warn!(
+                target: LOG_TARGET,
+                "Expired transaction {} has no involved shards, skipping...",
+                tx_rec.transaction_id(),
+            );
+            return Ok(None);
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "⌛️ Transaction {} has expired in epoch {}. Proposing to ABORT...",
+            tx_rec.transaction_id(),
+            epoch,
+        );
+        let tx_atom = tx_rec.get_local_transaction_atom().abort();
+        if num_involved_shards == 1 {
+            Ok(Some(Command::LocalOnly(tx_atom)))
+        } else {
+            Ok(Some(Command::Prepare(tx_atom)))
+        }
+    }
+
+    /// Executes the deferred transactions that are expected to fit in the block and were not prepared in advance. The
+    /// transactions are executed in parallel where they do not share substates and, like prepared executions, an
+    /// execution is only used if the inputs that it read are still the latest versions when the transaction is
+    /// sequenced.
+    fn execute_deferred_transactions(
+        &self,
+        substate_store: &PendingSubstateStore<TConsensusSpec::StateStore>,
+        batch: &[TransactionPoolRecord],
+        remaining_block_size: usize,
+        executions: &mut HashMap<TransactionId, ExecutedTransaction>,
+    ) -> Result<(), HotStuffError> {
+        let transactions = select_deferred_transactions(batch, remaining_block_size)
+            .filter(|id| !executions.contains_key(*id))
+            .map(|id| TransactionRecord::get(substate_store.read_transaction(), id))
+            .collect::<Result<Vec<_>, _>>()?;
+        if transactions.is_empty() {
+            return Ok(());
+        }
+
+        let snapshot = SubstateSnapshot::load(substate_store, transactions.iter().map(|t| t.transaction()))?;
+        info!(
+            target: LOG_TARGET,
+            "👨‍🔧 PROPOSE: Executing {} deferred transaction(s)",
+            transactions.len(),
+        );
+        executions.extend(execute_transactions_with_snapshot::<TConsensusSpec>(
+            &self.transaction_executor,
+            transactions,
+            &snapshot,
+        ));
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
     fn build_next_block(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
@@ -485,13 +773,19 @@ where TConsensusSpec: ConsensusSpec
         base_layer_block_hash: FixedHash,
         propose_epoch_start: bool,
         propose_epoch_end: bool,
-    ) -> Result<(Block, HashMap<TransactionId, ExecutedTransaction>), HotStuffError> {
-        // TODO: Configure
-        const TARGET_BLOCK_SIZE: usize = 1000;
-        let batch = if empty_block || propose_epoch_end || propose_epoch_start {
-            vec![]
+        epoch_end_validator_set_hash: Option<FixedHash>,
+        timestamp: u64,
+        prepared_executions: &mut HashMap<TransactionId, ExecutedTransaction>,
+    ) -> Result<(Block, HashMap<TransactionId, ExecutedTransaction>, Vec<TransactionId>), HotStuffError> {
+        let (batch, expired) = if empty_block || propose_epoch_end || propose_epoch_start {
+            (vec![], vec![])
         } else {
-            self.transaction_pool.get_batch_for_next_block(tx, TARGET_BLOCK_SIZE)?
+            (
+                self.transaction_pool
+                    .get_batch_for_next_block(tx, self.config.max_block_transactions, epoch)?,
+                self.transaction_pool
+                    .get_expired_for_next_block(tx, self.config.max_block_transactions, epoch)?,
+            )
         };
         let current_version = high_qc.block_height().as_u64();
         let next_height = parent_block.height() + NodeHeight(1);
@@ -522,16 +816,33 @@ where TConsensusSpec: ConsensusSpec
                 .collect()
         };
 
+        let mut block_size = BlockSizeBudget::new(
+            self.config
+                .max_block_size_bytes
+                .saturating_sub(BLOCK_HEADER_SIZE_ESTIMATE)
+                .saturating_sub(high_qc.signatures().len() * QC_SIGNATURE_SIZE_ESTIMATE)
+                .saturating_sub(commands.iter().map(Command::estimated_size).sum()),
+        );
+
         // batch is empty for is_empty, is_epoch_end and is_epoch_start blocks
         let mut substate_store = PendingSubstateStore::new(tx);
+        self.execute_deferred_transactions(&substate_store, &batch, block_size.remaining(), prepared_executions)?;
         let mut executed_transactions = HashMap::new();
+        let mut deferred_execution_failures = Vec::new();
         for transaction in batch {
+            if block_size.is_full() {
+                break;
+            }
             if let Some(command) = self.transaction_pool_record_to_command(
                 tx,
                 transaction,
                 local_committee_info,
+                next_height,
                 &mut substate_store,
                 &mut executed_transactions,
+                &mut deferred_execution_failures,
+                &mut block_size,
+                prepared_executions,
             )? {
                 total_leader_fee += command
                     .committing()
@@ -541,6 +852,26 @@ where TConsensusSpec: ConsensusSpec
                 commands.insert(command);
             }
         }
+        for transaction in expired {
+            if block_size.is_full() {
+                break;
+            }
+            if let Some(command) = self.expired_transaction_to_command(
+                tx,
+                transaction,
+                epoch,
+                local_committee_info,
+                &mut executed_transactions,
+            )? {
+                if !block_size.try_reserve(command.estimated_size()) {
+                    if let Some(atom) = command.transaction() {
+                        executed_transactions.remove(atom.id());
+                    }
+                    break;
+                }
+                commands.insert(command);
+            }
+        }
 
         debug!(
             target: LOG_TARGET,
@@ -558,17 +889,23 @@ where TConsensusSpec: ConsensusSpec
             substate_store.diff().iter().map(|ch| ch.into()),
         )?;
 
-        let non_local_shards = get_non_local_shards(substate_store.diff(), local_committee_info);
+        if let Some(validator_set_hash) = epoch_end_validator_set_hash {
+            commands.insert(Command::EpochCheckpoint(EpochCheckpoint::new(
+                epoch,
+                state_root,
+                validator_set_hash,
+            )));
+        }
 
-        let foreign_counters = ForeignSendCounters::get_or_default(tx, parent_block.block_id())?;
-        let mut foreign_indexes = non_local_shards
-            .iter()
-            .map(|shard| (*shard, foreign_counters.get_count(*shard) + 1))
+        // Voters check the foreign indexes against the counters of the justify block, which do not include any dummy
+        // blocks between it and the parent block
+        let foreign_counters = ForeignSendCounters::get_or_default(tx, high_qc.block_id())?;
+        // The shards are returned in order, so the foreign indexes are canonically ordered
+        let foreign_indexes = get_foreign_index_shards(&commands, local_committee_info)
+            .into_iter()
+            .map(|shard| (shard, foreign_counters.get_count(shard) + 1))
             .collect::<IndexMap<_, _>>();
 
-        // Ensure that foreign indexes are canonically ordered
-        foreign_indexes.sort_keys();
-
         let mut next_block = Block::new(
             self.network,
             *parent_block.block_id(),
@@ -582,7 +919,7 @@ where TConsensusSpec: ConsensusSpec
             total_leader_fee,
             foreign_indexes,
             None,
-            EpochTime::now().as_u64(),
+            timestamp,
             base_layer_block_height,
             base_layer_block_hash,
         );
@@ -590,17 +927,139 @@ where TConsensusSpec: ConsensusSpec
         let signature = self.signing_service.sign(next_block.id());
         next_block.set_signature(signature);
 
-        Ok((next_block, executed_transactions))
+        Ok((next_block, executed_transactions, deferred_execution_failures))
     }
 }
 
-pub fn get_non_local_shards(diff: &[SubstateChange], local_committee_info: &CommitteeInfo) -> HashSet<Shard> {
-    diff.iter()
-        .map(|ch| {
-            ch.versioned_substate_id()
-                .to_substate_address()
-                .to_shard(local_committee_info.num_committees())
+/// The executions of the deferred transactions for the block after `parent` that are prepared in the background while
+/// the parent block is voted on
+struct PreparedProposal {
+    epoch: Epoch,
+    parent: LeafBlock,
+    task: task::JoinHandle<Result<HashMap<TransactionId, ExecutedTransaction>, HotStuffError>>,
+}
+
+/// Executes the deferred transactions that would be selected for the block after the parent block against the
+/// speculative state of the parent block. The executions are only used if the inputs that they read are still the
+/// latest versions when the transactions are sequenced.
+fn prepare_deferred_executions<TConsensusSpec: ConsensusSpec>(
+    store: &TConsensusSpec::StateStore,
+    transaction_pool: &TransactionPool<TConsensusSpec::StateStore>,
+    transaction_executor: &TConsensusSpec::TransactionExecutor,
+    epoch: Epoch,
+    parent_block: &LeafBlock,
+    max_block_size_bytes: usize,
+    max_block_transactions: usize,
+) -> Result<HashMap<TransactionId, ExecutedTransaction>, HotStuffError> {
+    // The inputs are loaded up front so that the read transaction, which blocks other access to the store, is not held
+    // while the transactions are executed
+    let (transactions, snapshot) = store.with_read_tx(|tx| {
+        let batch = transaction_pool.get_batch_for_next_block(tx, max_block_transactions, epoch)?;
+        let transactions =
+            select_deferred_transactions(&batch, max_block_size_bytes.saturating_sub(BLOCK_HEADER_SIZE_ESTIMATE))
+                .map(|id| TransactionRecord::get(tx, id))
+                .collect::<Result<Vec<_>, _>>()?;
+        let substate_store =
+            PendingSubstateStore::<TConsensusSpec::StateStore>::new_speculative(tx, parent_block.block_id())?;
+        let snapshot = SubstateSnapshot::load(&substate_store, transactions.iter().map(|t| t.transaction()))?;
+        Ok::<_, HotStuffError>((transactions, snapshot))
+    })?;
+
+    Ok(execute_transactions_with_snapshot::<TConsensusSpec>(
+        transaction_executor,
+        transactions,
+        &snapshot,
+    ))
+}
+
+/// Returns the ids of the deferred transactions in the batch that are expected to fit in a block with the given size
+/// budget. The evidence of a deferred transaction is only known once it has been executed, so the sizes are estimated
+/// without it.
+fn select_deferred_transactions(
+    batch: &[TransactionPoolRecord],
+    remaining_block_size: usize,
+) -> impl Iterator<Item = &TransactionId> {
+    let mut remaining = remaining_block_size;
+    let mut is_first = true;
+    batch
+        .iter()
+        .take_while(move |tx_rec| {
+            let size = tx_rec.atom().estimated_size();
+            // Like BlockSizeBudget, an oversized transaction is proposed alone
+            let fits = is_first || size <= remaining;
+            remaining = remaining.saturating_sub(size);
+            is_first = false;
+            fits
         })
-        .filter(|shard| *shard != local_committee_info.shard())
-        .collect()
+        .filter(|tx_rec| tx_rec.is_deferred())
+        .map(|tx_rec| tx_rec.transaction_id())
+}
+
+/// Reserves space in the block for the command of the transaction atom. This is done once the atom of the command is
+/// known, but before any substates are locked for the transaction, because locks cannot be released once taken. If
+/// the command does not fit, the execution of the transaction is discarded so that it is not added to the block.
+/// Proposing ABORT instead of the atom (e.g. because of a lock conflict) does not change the size of the command.
+fn try_reserve_command(
+    block_size: &mut BlockSizeBudget,
+    executed_transactions: &mut HashMap<TransactionId, ExecutedTransaction>,
+    tx_atom: &TransactionAtom,
+) -> bool {
+    if block_size.try_reserve(Command::estimated_size_of_transaction(tx_atom)) {
+        return true;
+    }
+    executed_transactions.remove(&tx_atom.id);
+    false
+}
+
+/// Tracks the remaining size budget for the transaction commands of a block that is being proposed
+struct BlockSizeBudget {
+    remaining: usize,
+    num_reserved: usize,
+    is_full: bool,
+}
+
+impl BlockSizeBudget {
+    fn new(remaining: usize) -> Self {
+        Self {
+            remaining,
+            num_reserved: 0,
+            is_full: false,
+        }
+    }
+
+    /// Reserves space for a command of the given size, returning false and marking the budget as full if it does not
+    /// fit. A command that does not fit on its own is always accepted as the only command so that oversized
+    /// transactions are proposed alone rather than never being proposed.
+    fn try_reserve(&mut self, size: usize) -> bool {
+        if self.is_full {
+            return false;
+        }
+        if size <= self.remaining {
+            self.remaining -= size;
+            self.num_reserved += 1;
+            return true;
+        }
+
+        self.is_full = true;
+        if self.num_reserved == 0 {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ Transaction command with an estimated size of {} bytes exceeds the block size budget of {} bytes. \
+                 Proposing it alone.",
+                size,
+                self.remaining
+            );
+            self.num_reserved += 1;
+            return true;
+        }
+        false
+    }
+
+    fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn is_full(&self) -> bool {
+        self.is_full
+    }
 }
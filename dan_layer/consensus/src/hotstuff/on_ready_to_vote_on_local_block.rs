@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 #![allow(dead_code)]
-use std::num::NonZeroU64;
+use std::{collections::HashMap, num::NonZeroU64};
 
 use log::*;
 use tari_common::configuration::Network;
@@ -16,6 +16,7 @@ use tari_dan_storage::{
         BlockDiff,
         BlockId,
         Command,
+        CommittedBlockDiff,
         Decision,
         EpochEvent,
         ExecutedTransaction,
@@ -24,9 +25,11 @@ use tari_dan_storage::{
         LastSentVote,
         LastVoted,
         LockedBlock,
+        NftOwnership,
         PendingStateTreeDiff,
         QuorumDecision,
         SubstateLockFlag,
+        SubstateRecord,
         TransactionAtom,
         TransactionExecution,
         TransactionPool,
@@ -36,7 +39,6 @@ use tari_dan_storage::{
         VersionedSubstateIdLockIntent,
     },
     StateStore,
-    StateStoreWriteTransaction,
 };
 use tari_epoch_manager::EpochManagerReader;
 use tari_transaction::TransactionId;
@@ -44,21 +46,28 @@ use tokio::sync::broadcast;
 
 use super::proposer::Proposer;
 use crate::{
+    block_validations::check_state_merkle_root,
     hotstuff::{
         block_change_set::{BlockDecision, ProposedBlockChangeSet},
+        create_deferred_execution_failed_execution,
+        create_epoch_expired_execution,
         error::HotStuffError,
         event::HotstuffEvent,
-        substate_store::PendingSubstateStore,
+        execute_transactions_with_snapshot,
+        substate_store::{PendingSubstateStore, SubstateSnapshot},
+        take_execution_if_current,
+        vote_sender::VoteSender,
+        MaintenanceMode,
         ProposalValidationError,
         EXHAUST_DIVISOR,
     },
-    messages::{HotstuffMessage, VoteMessage},
+    journal::{ConsensusJournal, JournalEntry, JournalTransaction, VoteBasis},
+    messages::VoteMessage,
     traits::{
         hooks::ConsensusHooks,
         BlockTransactionExecutor,
         ConsensusSpec,
         LeaderStrategy,
-        OutboundMessaging,
         VoteSignatureService,
         WriteableSubstateStore,
     },
@@ -73,12 +82,16 @@ pub struct OnReadyToVoteOnLocalBlock<TConsensusSpec: ConsensusSpec> {
     vote_signing_service: TConsensusSpec::SignatureService,
     leader_strategy: TConsensusSpec::LeaderStrategy,
     transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
-    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    vote_sender: VoteSender<TConsensusSpec>,
     tx_events: broadcast::Sender<HotstuffEvent>,
     proposer: Proposer<TConsensusSpec>,
     transaction_executor: TConsensusSpec::TransactionExecutor,
     network: Network,
     hooks: TConsensusSpec::Hooks,
+    maintenance_mode: MaintenanceMode,
+    journal: ConsensusJournal,
+    committed_block_diff_retention: Option<u64>,
+    reject_state_merkle_root_mismatch: bool,
 }
 
 impl<TConsensusSpec> OnReadyToVoteOnLocalBlock<TConsensusSpec>
@@ -92,12 +105,16 @@ where TConsensusSpec: ConsensusSpec
         vote_signing_service: TConsensusSpec::SignatureService,
         leader_strategy: TConsensusSpec::LeaderStrategy,
         transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
-        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        vote_sender: VoteSender<TConsensusSpec>,
         tx_events: broadcast::Sender<HotstuffEvent>,
         proposer: Proposer<TConsensusSpec>,
         transaction_executor: TConsensusSpec::TransactionExecutor,
         network: Network,
         hooks: TConsensusSpec::Hooks,
+        maintenance_mode: MaintenanceMode,
+        journal: ConsensusJournal,
+        committed_block_diff_retention: Option<u64>,
+        reject_state_merkle_root_mismatch: bool,
     ) -> Self {
         Self {
             local_validator_addr: validator_addr,
@@ -106,12 +123,16 @@ where TConsensusSpec: ConsensusSpec
             vote_signing_service,
             leader_strategy,
             transaction_pool,
-            outbound_messaging,
+            vote_sender,
             tx_events,
             proposer,
             transaction_executor,
             network,
             hooks,
+            maintenance_mode,
+            journal,
+            committed_block_diff_retention,
+            reject_state_merkle_root_mismatch,
         }
     }
 
@@ -126,11 +147,24 @@ where TConsensusSpec: ConsensusSpec
             .epoch_manager
             .get_committee_info_by_validator_public_key(valid_block.epoch(), valid_block.proposed_by())
             .await?;
-        let block_decision = self.store.with_write_tx(|tx| {
-            let change_set = self.decide_on_block(&**tx, &local_committee_shard, &valid_block)?;
+        let (block_decision, validation_error) = self.store.with_write_tx(|tx| {
+            // A block that fails validation is not voted for, but the (empty) change set is still saved so that the
+            // block can be extended
+            let decision = self.decide_on_block(&**tx, &local_committee_shard, &valid_block);
+            let (change_set, validation_error) = match decision {
+                Ok(change_set) => (change_set, None),
+                Err(HotStuffError::ProposalValidationError(err)) => {
+                    self.record_vote_withheld(&valid_block, err.to_string());
+                    (
+                        ProposedBlockChangeSet::new(valid_block.block().as_leaf_block()).no_vote(),
+                        Some(err),
+                    )
+                },
+                Err(err) => return Err(err),
+            };
 
             let mut locked_blocks = Vec::new();
-            let mut finalized_transactions = Vec::new();
+            let mut committed_blocks = Vec::new();
 
             if change_set.is_accept() {
                 // Update nodes
@@ -142,14 +176,14 @@ where TConsensusSpec: ConsensusSpec
                     },
                     |tx, last_exec, commit_block| {
                         let committed = self.on_commit(tx, last_exec, commit_block, &local_committee_shard)?;
-                        if !committed.is_empty() {
-                            finalized_transactions.push(committed);
-                        }
+                        committed_blocks.push((commit_block.clone(), committed));
                         Ok(())
                     },
                 )?;
             }
 
+            let locked_block = LockedBlock::get(&**tx)?;
+
             if change_set.is_accept() {
                 valid_block.block().as_last_voted().set(tx)?;
             }
@@ -157,17 +191,29 @@ where TConsensusSpec: ConsensusSpec
             let quorum_decision = change_set.quorum_decision();
             change_set.save(tx)?;
 
-            Ok::<_, HotStuffError>(BlockDecision {
-                quorum_decision,
-                locked_blocks,
-                finalized_transactions,
-            })
+            Ok::<_, HotStuffError>((
+                BlockDecision {
+                    quorum_decision,
+                    locked_blocks,
+                    committed_blocks,
+                    locked_block,
+                },
+                validation_error,
+            ))
         })?;
 
+        if let Some(err) = validation_error {
+            self.hooks.on_local_block_decide(&valid_block, None);
+            return Err(err.into());
+        }
+
         self.hooks
             .on_local_block_decide(&valid_block, block_decision.quorum_decision);
-        for t in block_decision.finalized_transactions.into_iter().flatten() {
-            self.hooks.on_transaction_finalized(&t);
+        for (block, finalized_transactions) in &block_decision.committed_blocks {
+            self.hooks.on_block_committed(block);
+            for t in finalized_transactions {
+                self.hooks.on_transaction_finalized(t);
+            }
         }
         self.propose_newly_locked_blocks(block_decision.locked_blocks).await?;
 
@@ -177,7 +223,14 @@ where TConsensusSpec: ConsensusSpec
                 .is_this_validator_registered_for_epoch(valid_block.epoch())
                 .await?;
 
-            if is_registered {
+            if self.maintenance_mode.is_enabled() {
+                info!(
+                    target: LOG_TARGET,
+                    "🔧 Local validator is in maintenance mode. Not voting on block {}",
+                    valid_block,
+                );
+                self.record_vote_withheld(&valid_block, "Local validator is in maintenance mode");
+            } else if is_registered {
                 debug!(
                     target: LOG_TARGET,
                     "🔥 LOCAL PROPOSAL {} DECIDED {:?}",
@@ -186,6 +239,24 @@ where TConsensusSpec: ConsensusSpec
                 );
                 let local_committee = self.epoch_manager.get_local_committee(valid_block.epoch()).await?;
 
+                self.journal.record(JournalEntry::VoteCast {
+                    block_id: *valid_block.id(),
+                    epoch: valid_block.epoch(),
+                    height: valid_block.height(),
+                    decision,
+                    basis: VoteBasis {
+                        justify_block_id: *valid_block.block().justify().block_id(),
+                        justify_height: valid_block.block().justify().block_height(),
+                        locked_height: block_decision.locked_block.height,
+                        transactions: valid_block
+                            .block()
+                            .commands()
+                            .iter()
+                            .filter_map(|cmd| cmd.transaction())
+                            .map(JournalTransaction::from)
+                            .collect(),
+                    },
+                });
                 let vote = self.generate_vote_message(valid_block.block(), decision).await?;
                 self.send_vote_to_leader(&local_committee, vote, valid_block.block())
                     .await?;
@@ -196,12 +267,25 @@ where TConsensusSpec: ConsensusSpec
                     valid_block.epoch(),
                     valid_block,
                 );
+                self.record_vote_withheld(
+                    &valid_block,
+                    format!("Local validator not registered for epoch {}", valid_block.epoch()),
+                );
             }
         }
 
         Ok(())
     }
 
+    fn record_vote_withheld<T: Into<String>>(&self, valid_block: &ValidBlock, reason: T) {
+        self.journal.record(JournalEntry::VoteWithheld {
+            block_id: *valid_block.id(),
+            epoch: valid_block.epoch(),
+            height: valid_block.height(),
+            reason: reason.into(),
+        });
+    }
+
     fn decide_on_block(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
@@ -225,16 +309,21 @@ where TConsensusSpec: ConsensusSpec
                 !qc_block.is_genesis();
 
         if !self.should_vote(tx, valid_block.block())? {
+            self.record_vote_withheld(valid_block, "Block height is not greater than last voted height");
             return Ok(ProposedBlockChangeSet::new(valid_block.block().as_leaf_block()).no_vote());
         }
 
-        self.decide_what_to_vote(
+        let change_set = self.decide_what_to_vote(
             tx,
             valid_block.block(),
             local_committee_info,
             epoch_should_start,
             epoch_should_end,
-        )
+        )?;
+        if change_set.quorum_decision().is_none() {
+            self.record_vote_withheld(valid_block, "Block commands were rejected by local validation");
+        }
+        Ok(change_set)
     }
 
     /// if b_new .height > vheight && (b_new extends b_lock || b_new .justify.node.height > b_lock .height)
@@ -272,7 +361,8 @@ where TConsensusSpec: ConsensusSpec
     ) -> Result<(), HotStuffError> {
         let leader = self
             .leader_strategy
-            .get_leader_for_next_block(local_committee, block.height());
+            .get_leader_for_next_block(local_committee, block.epoch(), block.height())
+            .clone();
         info!(
             target: LOG_TARGET,
             "🔥 VOTE {:?} for block {} proposed by {} to next leader {:.4}",
@@ -281,19 +371,47 @@ where TConsensusSpec: ConsensusSpec
             block.proposed_by(),
             leader,
         );
-        self.outbound_messaging
-            .send(leader.clone(), HotstuffMessage::Vote(vote.clone()))
-            .await?;
+        // The vote is persisted before it is sent so that it can be sent again if sending fails
         self.store.with_write_tx(|tx| {
             let last_sent_vote = LastSentVote {
                 epoch: vote.epoch,
                 block_id: vote.block_id,
                 block_height: vote.block_height,
                 decision: vote.decision,
-                signature: vote.signature,
+                signature: vote.signature.clone(),
             };
             last_sent_vote.set(tx)
         })?;
+        self.vote_sender.send(leader, vote).await;
+        Ok(())
+    }
+
+    /// Sends the last sent vote to the leader again if it was a vote for the given block. This is done when a proposal
+    /// that has already been processed is received again, since the leader may not have received the vote.
+    pub async fn resend_last_sent_vote(
+        &mut self,
+        local_committee: &Committee<TConsensusSpec::Addr>,
+        block: &Block,
+    ) -> Result<(), HotStuffError> {
+        let Some(last_sent_vote) = self.store.with_read_tx(|tx| LastSentVote::get(tx).optional())? else {
+            return Ok(());
+        };
+        if last_sent_vote.block_id != *block.id() {
+            return Ok(());
+        }
+
+        let leader = self
+            .leader_strategy
+            .get_leader_for_next_block(local_committee, block.epoch(), block.height())
+            .clone();
+        info!(
+            target: LOG_TARGET,
+            "🔁 Re-sending VOTE {:?} for block {} to next leader {:.4}",
+            last_sent_vote.decision,
+            block,
+            leader,
+        );
+        self.vote_sender.send(leader, last_sent_vote.into()).await;
         Ok(())
     }
 
@@ -331,6 +449,8 @@ where TConsensusSpec: ConsensusSpec
             return Ok(proposed_block_change_set.no_vote());
         }
 
+        let mut executions = self.execute_deferred_transactions(&substate_store, block)?;
+
         for cmd in block.commands() {
             if let Some(foreign_proposal) = cmd.foreign_proposal() {
                 if !ForeignProposal::exists(tx, foreign_proposal)? {
@@ -359,6 +479,10 @@ where TConsensusSpec: ConsensusSpec
                 }
                 continue;
             }
+            // The checkpoint is checked against the block when the proposal is received
+            if cmd.epoch_checkpoint().is_some() {
+                continue;
+            }
 
             let atom = cmd
                 .transaction()
@@ -390,6 +514,49 @@ where TConsensusSpec: ConsensusSpec
                 cmd,
                 block,
             );
+
+            // Accept is exempt because foreign shards may already have prepared the transaction within its bounds
+            if let Command::LocalOnly(t) | Command::Prepare(t) = cmd {
+                let transaction = TransactionRecord::get(tx, &t.id)?;
+                if !transaction.transaction().is_valid_in_epoch(block.epoch()) {
+                    // The only valid command outside of the epoch bounds is to ABORT a new transaction that has expired
+                    if t.decision.is_commit() ||
+                        !tx_rec.current_stage().is_new() ||
+                        !transaction.transaction().is_expired_in_epoch(block.epoch())
+                    {
+                        warn!(
+                            target: LOG_TARGET,
+                            "❌ Command {} in block {} is for a transaction that is not valid in epoch {}",
+                            cmd,
+                            block,
+                            block.epoch(),
+                        );
+                        return Ok(proposed_block_change_set.no_vote());
+                    }
+
+                    let execution = create_epoch_expired_execution(transaction, block.epoch())
+                        .into_execution_for_block(*block.id());
+                    tx_rec.set_local_decision(execution.decision());
+                    tx_rec.set_initial_evidence(execution.to_initial_evidence());
+                    tx_rec.set_transaction_fee(execution.transaction_fee());
+                    proposed_block_change_set.add_transaction_execution(execution);
+                    if cmd.local_only().is_some() {
+                        proposed_block_change_set.set_next_transaction_update(
+                            &tx_rec,
+                            TransactionPoolStage::LocalOnly,
+                            false,
+                        );
+                    } else {
+                        proposed_block_change_set.set_next_transaction_update(
+                            &tx_rec,
+                            TransactionPoolStage::Prepared,
+                            true,
+                        );
+                    }
+                    continue;
+                }
+            }
+
             match cmd {
                 Command::LocalOnly(t) => {
                     if tx_rec.is_deferred() {
@@ -400,7 +567,8 @@ where TConsensusSpec: ConsensusSpec
                             block,
                         );
 
-                        let executed = self.execute_transaction_if_required(&substate_store, &atom.id, block.id())?;
+                        let executed =
+                            self.execute_deferred_transaction(&substate_store, &mut executions, atom, block.id())?;
                         tx_rec.set_local_decision(executed.decision());
                         tx_rec.set_initial_evidence(executed.to_initial_evidence());
                         tx_rec.set_transaction_fee(executed.transaction_fee());
@@ -446,6 +614,18 @@ where TConsensusSpec: ConsensusSpec
                         return Ok(proposed_block_change_set.no_vote());
                     }
 
+                    if tx_rec.atom().priority_fee != t.priority_fee {
+                        warn!(
+                            target: LOG_TARGET,
+                            "❌ LocalOnly priority fee disagreement tx {} in block {}. Leader proposed {}, we have {}",
+                            tx_rec.transaction_id(),
+                            block,
+                            t.priority_fee,
+                            tx_rec.atom().priority_fee
+                        );
+                        return Ok(proposed_block_change_set.no_vote());
+                    }
+
                     // If the leader proposed to commit a transaction that we want to abort, we abstain from voting
                     // If the leader proposed to abort a transaction that we want to commit, perhaps the transaction has
                     // a lock conflict, so we'll need to check this.
@@ -582,7 +762,8 @@ where TConsensusSpec: ConsensusSpec
                             block,
                         );
 
-                        let executed = self.execute_transaction_if_required(&substate_store, &atom.id, block.id())?;
+                        let executed =
+                            self.execute_deferred_transaction(&substate_store, &mut executions, atom, block.id())?;
                         tx_rec.set_local_decision(executed.decision());
                         tx_rec.set_initial_evidence(executed.to_initial_evidence());
                         tx_rec.set_transaction_fee(executed.transaction_fee());
@@ -617,6 +798,18 @@ where TConsensusSpec: ConsensusSpec
                         return Ok(proposed_block_change_set.no_vote());
                     }
 
+                    if tx_rec.atom().priority_fee != t.priority_fee {
+                        warn!(
+                            target: LOG_TARGET,
+                            "❌ Prepare priority fee disagreement tx {} in block {}. Leader proposed {}, we have {}",
+                            tx_rec.transaction_id(),
+                            block,
+                            t.priority_fee,
+                            tx_rec.atom().priority_fee
+                        );
+                        return Ok(proposed_block_change_set.no_vote());
+                    }
+
                     if tx_rec.current_decision().is_abort() && t.decision.is_commit() {
                         // If we disagree with any local decision we abstain from voting
                         warn!(
@@ -711,6 +904,18 @@ where TConsensusSpec: ConsensusSpec
                         return Ok(proposed_block_change_set.no_vote());
                     }
 
+                    if tx_rec.atom().priority_fee != t.priority_fee {
+                        warn!(
+                            target: LOG_TARGET,
+                            "❌ LocalPrepared priority fee disagreement tx {} in block {}. Leader proposed {}, we have {}",
+                            tx_rec.transaction_id(),
+                            block,
+                            t.priority_fee,
+                            tx_rec.atom().priority_fee
+                        );
+                        return Ok(proposed_block_change_set.no_vote());
+                    }
+
                     proposed_block_change_set.set_next_transaction_update(
                         &tx_rec,
                         TransactionPoolStage::LocalPrepared,
@@ -768,6 +973,18 @@ where TConsensusSpec: ConsensusSpec
                         return Ok(proposed_block_change_set.no_vote());
                     }
 
+                    if tx_rec.atom().priority_fee != t.priority_fee {
+                        warn!(
+                            target: LOG_TARGET,
+                            "❌ Accept priority fee disagreement tx {} in block {}. Leader proposed {}, we have {}",
+                            tx_rec.transaction_id(),
+                            block,
+                            t.priority_fee,
+                            tx_rec.atom().priority_fee
+                        );
+                        return Ok(proposed_block_change_set.no_vote());
+                    }
+
                     // Check if we have LocalPrepared ready i.e. LocalPrepared from all shards
                     // It is possible that the transaction was not marked as ready yet because of the order we
                     // received messages, but if we are in LocalPrepared and we have all the
@@ -857,8 +1074,8 @@ where TConsensusSpec: ConsensusSpec
                         block,
                     );
                 },
-                // This was already handled above
-                Command::EpochEvent(_) => {},
+                // These were already handled above
+                Command::EpochEvent(_) | Command::EpochCheckpoint(_) => {},
             }
         }
 
@@ -875,7 +1092,7 @@ where TConsensusSpec: ConsensusSpec
         }
 
         let (expected_merkle_root, tree_diff) = substate_store.calculate_jmt_diff_for_block(block)?;
-        if expected_merkle_root != *block.merkle_root() {
+        if let Err(err) = check_state_merkle_root(block, &expected_merkle_root) {
             warn!(
                 target: LOG_TARGET,
                 "❌ Merkle root disagreement for block {}. Leader proposed {}, we calculated {}",
@@ -883,6 +1100,9 @@ where TConsensusSpec: ConsensusSpec
                 block.merkle_root(),
                 expected_merkle_root
             );
+            if self.reject_state_merkle_root_mismatch {
+                return Err(err.into());
+            }
             return Ok(proposed_block_change_set.no_vote());
         }
 
@@ -896,11 +1116,64 @@ where TConsensusSpec: ConsensusSpec
         Ok(proposed_block_change_set)
     }
 
+    /// Executes the deferred transactions of the block that have not been executed for it yet. The transactions are
+    /// executed in parallel where they do not share substates and an execution is only used if the inputs that it read
+    /// are still the latest versions when the transaction is reached in the block.
+    fn execute_deferred_transactions(
+        &self,
+        store: &PendingSubstateStore<TConsensusSpec::StateStore>,
+        block: &Block,
+    ) -> Result<HashMap<TransactionId, ExecutedTransaction>, HotStuffError> {
+        let tx = store.read_transaction();
+        let mut transactions = Vec::new();
+        for atom in block
+            .commands()
+            .iter()
+            .filter_map(|cmd| cmd.local_only().or_else(|| cmd.prepare()))
+        {
+            let Some(tx_rec) = self
+                .transaction_pool
+                .get(tx, block.as_leaf_block(), &atom.id)
+                .optional()?
+            else {
+                continue;
+            };
+            if !tx_rec.is_deferred() ||
+                TransactionExecution::get_by_block(tx, &atom.id, block.id())
+                    .optional()?
+                    .is_some()
+            {
+                continue;
+            }
+            let transaction = TransactionRecord::get(tx, &atom.id)?;
+            if transaction.transaction().is_valid_in_epoch(block.epoch()) {
+                transactions.push(transaction);
+            }
+        }
+        if transactions.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let snapshot = SubstateSnapshot::load(store, transactions.iter().map(|t| t.transaction()))?;
+        info!(
+            target: LOG_TARGET,
+            "👨‍🔧 Executing {} deferred transaction(s) in block {}",
+            transactions.len(),
+            block,
+        );
+        Ok(execute_transactions_with_snapshot::<TConsensusSpec>(
+            &self.transaction_executor,
+            transactions,
+            &snapshot,
+        ))
+    }
+
     /// Executes the given transaction. If the transaction has already been executed for this block (on propose) then we
     /// load without re-executing.
     fn execute_transaction_if_required(
         &self,
         store: &PendingSubstateStore<TConsensusSpec::StateStore>,
+        executions: &mut HashMap<TransactionId, ExecutedTransaction>,
         transaction_id: &TransactionId,
         block_id: &BlockId,
     ) -> Result<TransactionExecution, HotStuffError> {
@@ -911,6 +1184,10 @@ where TConsensusSpec: ConsensusSpec
             return Ok(execution);
         }
 
+        if let Some(executed) = take_execution_if_current(store, executions, transaction_id)? {
+            return Ok(executed.into_execution_for_block(*block_id));
+        }
+
         let transaction = TransactionRecord::get(store.read_transaction(), transaction_id)?;
 
         info!(
@@ -927,6 +1204,31 @@ where TConsensusSpec: ConsensusSpec
         Ok(executed.into_execution_for_block(*block_id))
     }
 
+    /// Executes a deferred transaction. If the transaction fails to execute and the leader proposed to ABORT it, the
+    /// leader has given up retrying the execution and we ABORT it too.
+    fn execute_deferred_transaction(
+        &self,
+        store: &PendingSubstateStore<TConsensusSpec::StateStore>,
+        executions: &mut HashMap<TransactionId, ExecutedTransaction>,
+        atom: &TransactionAtom,
+        block_id: &BlockId,
+    ) -> Result<TransactionExecution, HotStuffError> {
+        match self.execute_transaction_if_required(store, executions, &atom.id, block_id) {
+            Ok(execution) => Ok(execution),
+            Err(HotStuffError::TransactionExecutorError(err)) if atom.decision.is_abort() => {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Deferred transaction {} failed to execute: {}. The leader proposed to ABORT.",
+                    atom.id,
+                    err,
+                );
+                let transaction = TransactionRecord::get(store.read_transaction(), &atom.id)?;
+                Ok(create_deferred_execution_failed_execution(transaction, err).into_execution_for_block(*block_id))
+            },
+            Err(err) => Err(err),
+        }
+    }
+
     fn try_obtain_locks(
         &self,
         transaction_execution: &TransactionExecution,
@@ -999,6 +1301,22 @@ where TConsensusSpec: ConsensusSpec
             block,
             last_executed.height
         );
+        if let Some(checkpoint) = block.epoch_checkpoint() {
+            info!(target: LOG_TARGET, "🏁 Committed {} in block {}", checkpoint, block);
+            checkpoint.insert(tx, block.id())?;
+        }
+        self.journal.record(JournalEntry::BlockCommitted {
+            block_id: *block.id(),
+            epoch: block.epoch(),
+            height: block.height(),
+            transactions: committed_transactions.iter().map(JournalTransaction::from).collect(),
+        });
+        for atom in &committed_transactions {
+            self.publish_event(HotstuffEvent::Decided {
+                tx_id: atom.id,
+                decision: atom.decision,
+            });
+        }
         self.publish_event(HotstuffEvent::BlockCommitted {
             block_id: *block.id(),
             height: block.height(),
@@ -1017,6 +1335,12 @@ where TConsensusSpec: ConsensusSpec
             "🔒️ LOCKED BLOCK: {}",
             block,
         );
+        self.journal.record(JournalEntry::LockAdvanced {
+            block_id: *block.id(),
+            height: block.height(),
+            previous_block_id: locked.block_id,
+            previous_height: locked.height,
+        });
 
         for foreign_proposal in block.all_foreign_proposals() {
             foreign_proposal.upsert(tx)?;
@@ -1053,7 +1377,9 @@ where TConsensusSpec: ConsensusSpec
                 );
                 continue;
             };
-            let leader_index = self.leader_strategy.calculate_leader(&local_committee, block.height());
+            let leader_index = self
+                .leader_strategy
+                .calculate_leader(&local_committee, block.epoch(), block.height());
             let my_index = local_committee
                 .addresses()
                 .position(|addr| *addr == our_addr.address)
@@ -1076,6 +1402,18 @@ where TConsensusSpec: ConsensusSpec
         let _ignore = self.tx_events.send(event);
     }
 
+    fn record_committed_diff(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        block: &Block,
+        diff: &BlockDiff,
+    ) -> Result<(), HotStuffError> {
+        if let Some(retain) = self.committed_block_diff_retention {
+            CommittedBlockDiff::record(tx, block, diff, retain)?;
+        }
+        Ok(())
+    }
+
     fn execute(
         &self,
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
@@ -1084,7 +1422,9 @@ where TConsensusSpec: ConsensusSpec
     ) -> Result<Vec<TransactionAtom>, HotStuffError> {
         // Nothing to do here for empty dummy blocks
         if block.is_dummy() {
-            block.commit_diff(tx, BlockDiff::empty(*block.id()))?;
+            let diff = BlockDiff::empty(*block.id());
+            self.record_committed_diff(tx, block, &diff)?;
+            block.commit_diff(tx, diff)?;
             return Ok(vec![]);
         }
 
@@ -1095,6 +1435,8 @@ where TConsensusSpec: ConsensusSpec
         );
 
         let local_diff = diff.into_filtered(local_committee_info);
+        self.record_committed_diff(tx, block, &local_diff)?;
+        NftOwnership::update_from_diff(tx, &local_diff)?;
         block.commit_diff(tx, local_diff)?;
 
         let finalized_transactions = self
@@ -1111,7 +1453,7 @@ where TConsensusSpec: ConsensusSpec
         }
 
         // Remove locks for finalized transactions
-        tx.substate_locks_remove_many_for_transactions(block.all_accepted_transactions_ids())?;
+        SubstateRecord::remove_locks_for_transactions(tx, block.all_accepted_transactions_ids())?;
 
         let pending = PendingStateTreeDiff::remove_by_block(tx, block.id())?;
         let mut state_tree = tari_state_tree::SpreadPrefixStateTree::new(tx);
@@ -16,11 +16,13 @@ use tari_dan_storage::{
 };
 use tari_epoch_manager::EpochManagerReader;
 use tari_transaction::TransactionId;
+use tokio::sync::broadcast;
 
 use crate::{
-    hotstuff::{error::HotStuffError, pacemaker_handle::PaceMakerHandle, ProposalValidationError},
-    messages::ProposalMessage,
-    traits::ConsensusSpec,
+    block_validations::check_membership_proof,
+    hotstuff::{error::HotStuffError, pacemaker_handle::PaceMakerHandle, HotstuffEvent, ProposalValidationError},
+    messages::{ForeignProposalAckMessage, ForeignProposalMessage, HotstuffMessage},
+    traits::{ConsensusSpec, OutboundMessaging},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_foreign_proposal";
@@ -30,6 +32,8 @@ pub struct OnReceiveForeignProposalHandler<TConsensusSpec: ConsensusSpec> {
     epoch_manager: TConsensusSpec::EpochManager,
     transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
     pacemaker: PaceMakerHandle,
+    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    tx_events: broadcast::Sender<HotstuffEvent>,
 }
 
 impl<TConsensusSpec> OnReceiveForeignProposalHandler<TConsensusSpec>
@@ -40,17 +44,28 @@ where TConsensusSpec: ConsensusSpec
         epoch_manager: TConsensusSpec::EpochManager,
         transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
         pacemaker: PaceMakerHandle,
+        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        tx_events: broadcast::Sender<HotstuffEvent>,
     ) -> Self {
         Self {
             store,
             epoch_manager,
             transaction_pool,
             pacemaker,
+            outbound_messaging,
+            tx_events,
         }
     }
 
-    pub async fn handle(&mut self, from: TConsensusSpec::Addr, message: ProposalMessage) -> Result<(), HotStuffError> {
-        let ProposalMessage { block } = message;
+    pub async fn handle(
+        &mut self,
+        from: TConsensusSpec::Addr,
+        message: ForeignProposalMessage,
+    ) -> Result<(), HotStuffError> {
+        let ForeignProposalMessage {
+            block,
+            membership_proof,
+        } = message;
 
         debug!(
             target: LOG_TARGET,
@@ -61,17 +76,35 @@ where TConsensusSpec: ConsensusSpec
             from,
         );
 
-        let mut foreign_receive_counter = self
+        let foreign_receive_counter = self
             .store
             .with_read_tx(|tx| ForeignReceiveCounters::get_or_default(tx))?;
 
-        let vn = self.epoch_manager.get_validator_node(block.epoch(), &from).await?;
+        // The sender's committee is taken from its membership proof, so the foreign committee does not need to be
+        // fetched
+        let bmt_root = self.epoch_manager.get_validator_node_bmt_root(block.epoch()).await?;
+        if let Err(err) = check_membership_proof(&from, block.epoch(), &membership_proof, &bmt_root) {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ FOREIGN PROPOSAL: Rejecting proposal for block {} from {}: {}",
+                block.id(),
+                from,
+                err
+            );
+            return Ok(());
+        }
+
+        let local_shard = self.epoch_manager.get_local_committee_info(block.epoch()).await?;
         let committee_shard = self
             .epoch_manager
-            .get_committee_info_for_substate(block.epoch(), vn.shard_key)
+            .get_committee_info_for_substate(
+                block.epoch(),
+                *membership_proof
+                    .shard()
+                    .to_substate_address_range(local_shard.num_committees())
+                    .start(),
+            )
             .await?;
-
-        let local_shard = self.epoch_manager.get_local_committee_info(block.epoch()).await?;
         if let Err(err) = self.validate_proposed_block(
             &from,
             &block,
@@ -85,12 +118,22 @@ where TConsensusSpec: ConsensusSpec
                 from,
                 err
             );
+            // Keep a record of the invalid proposal for inspection. Its transactions are not applied.
+            let current_epoch = self.epoch_manager.current_epoch().await?;
+            let invalid_proposal = ForeignProposal::new(
+                committee_shard.shard(),
+                *block.id(),
+                vec![],
+                block.base_layer_block_height(),
+            );
+            self.store.with_write_tx(|tx| {
+                let leaf = LeafBlock::get(&**tx)?;
+                invalid_proposal.record_invalid(tx, leaf.height(), current_epoch)
+            })?;
             // Invalid blocks should not cause the state machine to transition to Error
             return Ok(());
         }
 
-        foreign_receive_counter.increment(&committee_shard.shard());
-
         let tx_ids = block
             .commands()
             .iter()
@@ -115,23 +158,37 @@ where TConsensusSpec: ConsensusSpec
             tx_ids,
             block.base_layer_block_height(),
         );
-        if self
-            .store
-            .with_read_tx(|tx| ForeignProposal::exists(tx, &foreign_proposal))?
-        {
-            warn!(
+
+        // The proposal is recorded as applied in the same transaction that applies it, so a proposal that is delivered
+        // again, e.g. by a retrying sender or after a sync, never updates the counters or evidence a second time
+        let is_applied = self.store.with_write_tx(|tx| {
+            if !ForeignProposal::record_applied(tx, committee_shard.shard(), block.id())? {
+                return Ok::<_, HotStuffError>(false);
+            }
+            let mut foreign_receive_counter = ForeignReceiveCounters::get_or_default(&**tx)?;
+            foreign_receive_counter.increment(&committee_shard.shard());
+            foreign_receive_counter.save(tx)?;
+            foreign_proposal.upsert(tx)?;
+            self.on_receive_foreign_block(tx, &block, &committee_shard)?;
+            Ok(true)
+        })?;
+
+        // The sender retries until it receives our ack, so duplicates are acknowledged too
+        self.send_ack(from, &block).await?;
+
+        if !is_applied {
+            info!(
                 target: LOG_TARGET,
-                "🔥 FOREIGN PROPOSAL: Already received proposal for block {}",
+                "🔥 FOREIGN PROPOSAL: Proposal for block {} has already been applied. Ignoring.",
                 block.id(),
             );
             return Ok(());
         }
 
-        self.store.with_write_tx(|tx| {
-            foreign_receive_counter.save(tx)?;
-            foreign_proposal.upsert(tx)?;
-            self.on_receive_foreign_block(tx, &block, &committee_shard)
-        })?;
+        let _ignore = self.tx_events.send(HotstuffEvent::ForeignProposalReceived {
+            shard: committee_shard.shard(),
+            block_id: *block.id(),
+        });
 
         // We could have ready transactions at this point, so if we're the leader for the next block we can propose
         self.pacemaker.beat();
@@ -139,6 +196,19 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 
+    async fn send_ack(&mut self, to: TConsensusSpec::Addr, block: &Block) -> Result<(), HotStuffError> {
+        self.outbound_messaging
+            .send(
+                to,
+                HotstuffMessage::ForeignProposalAck(ForeignProposalAckMessage {
+                    epoch: block.epoch(),
+                    block_id: *block.id(),
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
     fn on_receive_foreign_block(
         &self,
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
@@ -7,38 +7,50 @@
 
 use log::*;
 use tari_common::configuration::Network;
+use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
     optional::Optional,
+    Epoch,
     NodeHeight,
 };
 use tari_dan_storage::{
     consensus_models::{
         Block,
-        Decision,
+        BlockRejection,
         ExecutedTransaction,
         ForeignProposal,
         HighQc,
+        LeafBlock,
+        SubstateRecord,
         TransactionAtom,
         TransactionPool,
-        TransactionPoolStage,
         TransactionRecord,
         ValidBlock,
     },
     StateStore,
+    StateStoreReadTransaction,
 };
 use tari_epoch_manager::EpochManagerReader;
 use tokio::sync::broadcast;
 
 use super::proposer::Proposer;
 use crate::{
+    block_validations,
     hotstuff::{
+        abort_timed_out_foreign_proposal_transactions,
         error::HotStuffError,
         on_ready_to_vote_on_local_block::OnReadyToVoteOnLocalBlock,
         pacemaker_handle::PaceMakerHandle,
+        peer_bans::PeerBanTracker,
+        qc_timing_tracker::QcTimingTracker,
+        vote_sender::VoteSender,
         HotstuffEvent,
+        MaintenanceMode,
         ProposalValidationError,
+        VoteRetryConfig,
     },
+    journal::{ConsensusJournal, JournalEntry},
     messages::ProposalMessage,
     traits::{hooks::ConsensusHooks, ConsensusSpec, LeaderStrategy},
 };
@@ -50,10 +62,19 @@ pub struct OnReceiveLocalProposalHandler<TConsensusSpec: ConsensusSpec> {
     store: TConsensusSpec::StateStore,
     epoch_manager: TConsensusSpec::EpochManager,
     leader_strategy: TConsensusSpec::LeaderStrategy,
+    signing_service: TConsensusSpec::SignatureService,
     pacemaker: PaceMakerHandle,
     transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
     on_ready_to_vote_on_local_block: OnReadyToVoteOnLocalBlock<TConsensusSpec>,
     hooks: TConsensusSpec::Hooks,
+    clock: TConsensusSpec::Clock,
+    qc_timings: QcTimingTracker,
+    journal: ConsensusJournal,
+    foreign_proposal_timeout: NodeHeight,
+    max_dummy_blocks: u64,
+    block_rejection_retention_epochs: u64,
+    foreign_proposal_retention_epochs: u64,
+    peer_bans: PeerBanTracker<TConsensusSpec>,
 }
 
 impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec> {
@@ -72,13 +93,34 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         transaction_executor: TConsensusSpec::TransactionExecutor,
         network: Network,
         hooks: TConsensusSpec::Hooks,
+        clock: TConsensusSpec::Clock,
+        qc_timings: QcTimingTracker,
+        maintenance_mode: MaintenanceMode,
+        journal: ConsensusJournal,
+        committed_block_diff_retention: Option<u64>,
+        foreign_proposal_timeout: NodeHeight,
+        max_dummy_blocks: u64,
+        block_rejection_retention_epochs: u64,
+        foreign_proposal_retention_epochs: u64,
+        reject_state_merkle_root_mismatch: bool,
+        peer_bans: PeerBanTracker<TConsensusSpec>,
+        vote_retry: VoteRetryConfig,
     ) -> Self {
         Self {
             network,
+            peer_bans,
+            foreign_proposal_timeout,
+            max_dummy_blocks,
+            block_rejection_retention_epochs,
+            foreign_proposal_retention_epochs,
+            clock,
+            qc_timings,
+            journal: journal.clone(),
             store: store.clone(),
             epoch_manager: epoch_manager.clone(),
             leader_strategy: leader_strategy.clone(),
-            pacemaker,
+            signing_service: vote_signing_service.clone(),
+            pacemaker: pacemaker.clone(),
             transaction_pool: transaction_pool.clone(),
             hooks: hooks.clone(),
             on_ready_to_vote_on_local_block: OnReadyToVoteOnLocalBlock::new(
@@ -88,12 +130,16 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 vote_signing_service,
                 leader_strategy,
                 transaction_pool,
-                outbound_messaging,
+                VoteSender::new(outbound_messaging, pacemaker, hooks.clone(), vote_retry),
                 tx_events,
                 proposer,
                 transaction_executor,
                 network,
                 hooks,
+                maintenance_mode,
+                journal,
+                committed_block_diff_retention,
+                reject_state_merkle_root_mismatch,
             ),
         }
     }
@@ -108,6 +154,10 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             block.proposed_by()
         );
 
+        // Votes for this block are timed from when the proposal arrived, in case we are the leader that collects them
+        self.qc_timings
+            .on_proposal_received(*block.id(), block.epoch(), block.height());
+
         match self.process_block(block).await {
             Ok(()) => Ok(()),
             Err(err @ HotStuffError::ProposalValidationError(_)) => {
@@ -135,15 +185,15 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .get_committee_info_by_validator_public_key(block.epoch(), block.proposed_by())
             .await?;
 
-        let maybe_high_qc_and_block = self.store.with_write_tx(|tx| {
+        let outcome = self.store.with_write_tx(|tx| {
             if block.exists(&**tx)? {
                 info!(target: LOG_TARGET, "🧊 Block {} already exists", block);
-                return Ok(None);
+                return Ok(ProposalOutcome::AlreadyExists(block));
             }
 
             let Some(valid_block) = self.validate_block_header(tx, block, &local_committee, &local_committee_shard)?
             else {
-                return Ok(None);
+                return Ok(ProposalOutcome::Rejected);
             };
 
             // Ensure all transactions are inserted in the pool
@@ -158,26 +208,76 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 if transaction.is_executed() {
                     // This should never fail
                     let executed = ExecutedTransaction::try_from(transaction)?;
-                    self.transaction_pool.insert(tx, executed.to_atom())?;
+                    self.transaction_pool
+                        .insert(tx, executed.to_atom(), valid_block.height())?;
                 } else {
                     // Deferred execution
-                    self.transaction_pool
-                        .insert(tx, TransactionAtom::deferred(*transaction.id()))?;
+                    self.transaction_pool.insert(
+                        tx,
+                        TransactionAtom::deferred(*transaction.id()),
+                        valid_block.height(),
+                    )?;
                 }
             }
 
+            self.journal.record(JournalEntry::ProposalAccepted {
+                block_id: *valid_block.id(),
+                epoch: valid_block.epoch(),
+                height: valid_block.height(),
+                proposed_by: valid_block.proposed_by().to_string(),
+                justify_block_id: *valid_block.block().justify().block_id(),
+                justify_height: valid_block.block().justify().block_height(),
+            });
+
             // Save the block as soon as it is valid to ensure we have a valid pacemaker height.
             let high_qc = self.save_block(tx, &valid_block)?;
             info!(target: LOG_TARGET, "✅ Block {} is valid and persisted. HighQc({})", valid_block, high_qc);
-            Ok::<_, HotStuffError>(Some((high_qc, valid_block)))
+            Ok::<_, HotStuffError>(ProposalOutcome::Persisted { high_qc, valid_block })
         })?;
 
-        if let Some((high_qc, valid_block)) = maybe_high_qc_and_block {
-            self.pacemaker
-                .update_view(valid_block.height(), high_qc.block_height())
-                .await?;
+        match outcome {
+            ProposalOutcome::AlreadyExists(block) => {
+                // The proposal is received again, e.g. because the leader did not get enough votes, so the vote for it
+                // is sent again in case it did not reach the leader
+                self.on_ready_to_vote_on_local_block
+                    .resend_last_sent_vote(&local_committee, &block)
+                    .await?;
+            },
+            ProposalOutcome::Rejected => {},
+            ProposalOutcome::Persisted { high_qc, valid_block } => {
+                // The dummy blocks are created in validate_local_proposed_block but are only reported once they are
+                // persisted. The first block in the dummy chain is the justify block, which is not a dummy.
+                for dummy_block in valid_block.dummy_blocks().iter().filter(|b| b.is_dummy()) {
+                    self.hooks.on_dummy_block_created(dummy_block.height());
+                }
 
-            self.on_ready_to_vote_on_local_block.handle(valid_block).await?;
+                self.pacemaker
+                    .update_view(valid_block.height(), high_qc.block_height())
+                    .await?;
+
+                let (block_id, epoch, height) = (*valid_block.id(), valid_block.epoch(), valid_block.height());
+                let proposed_by = valid_block.proposed_by().clone();
+                // Some validation, e.g. of the state merkle root, is only possible when deciding on the block. The
+                // block is already persisted by then, so the rejection is recorded here.
+                match self.on_ready_to_vote_on_local_block.handle(valid_block).await {
+                    Ok(()) => {},
+                    Err(HotStuffError::ProposalValidationError(err)) => {
+                        warn!(target: LOG_TARGET, "❌ Block failed validation: {}", err);
+                        self.store.with_write_tx(|tx| {
+                            self.record_proposer_offence(tx, &local_committee, &proposed_by, &err)?;
+                            self.record_block_rejection(tx, BlockRejection {
+                                block_id,
+                                epoch,
+                                height,
+                                proposed_by,
+                                reason: err.to_string(),
+                            })
+                        })?;
+                        return Err(err.into());
+                    },
+                    Err(err) => return Err(err),
+                }
+            },
         }
 
         Ok(())
@@ -193,7 +293,29 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         valid_block.save_all_dummy_blocks(tx)?;
         valid_block.block().save(tx)?;
 
+        let prev_leaf = LeafBlock::get(&**tx)?;
         let high_qc = valid_block.block().justify().update_high_qc(tx)?;
+        let leaf = LeafBlock::get(&**tx)?;
+        let is_fork_switch = leaf != prev_leaf &&
+            !prev_leaf.is_genesis() &&
+            !tx.blocks_is_ancestor(leaf.block_id(), prev_leaf.block_id())?;
+        if is_fork_switch {
+            // Locks acquired by blocks on the abandoned fork will never be released by a commit
+            let abandoned = SubstateRecord::remove_locks_for_abandoned_fork(tx, prev_leaf.block_id(), leaf.block_id())?;
+            info!(
+                target: LOG_TARGET,
+                "🔀 Fork switch from {} to {}. Released substate locks for {} abandoned block(s)",
+                prev_leaf,
+                leaf,
+                abandoned.len()
+            );
+            self.journal.record(JournalEntry::ForkSwitched {
+                from_block_id: *prev_leaf.block_id(),
+                from_height: prev_leaf.height(),
+                to_block_id: *leaf.block_id(),
+                to_height: leaf.height(),
+            });
+        }
         Ok(high_qc)
     }
 
@@ -204,6 +326,8 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         local_committee: &Committee<TConsensusSpec::Addr>,
         local_committee_info: &CommitteeInfo,
     ) -> Result<Option<ValidBlock>, HotStuffError> {
+        let (block_id, epoch, height) = (*block.id(), block.epoch(), block.height());
+        let proposed_by = block.proposed_by().clone();
         let result = self
             .validate_local_proposed_block(&**tx, block, local_committee, local_committee_info)
             .and_then(|valid_block| {
@@ -217,11 +341,25 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             // Propagate this error out as sync is needed in the case where we have a valid QC but do not know the
             // block
             Err(err @ HotStuffError::ProposalValidationError(ProposalValidationError::JustifyBlockNotFound { .. })) => {
+                self.journal.record(JournalEntry::ProposalRejected {
+                    block_id,
+                    epoch,
+                    height,
+                    reason: err.to_string(),
+                });
                 Err(err)
             },
             // Validation errors should not cause a FAILURE state transition
             Err(HotStuffError::ProposalValidationError(err)) => {
                 warn!(target: LOG_TARGET, "❌ Block failed validation: {}", err);
+                self.record_proposer_offence(tx, local_committee, &proposed_by, &err)?;
+                self.record_block_rejection(tx, BlockRejection {
+                    block_id,
+                    epoch,
+                    height,
+                    proposed_by,
+                    reason: err.to_string(),
+                })?;
                 // A bad block should not cause a FAILURE state transition
                 Ok(None)
             },
@@ -229,112 +367,65 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         }
     }
 
-    fn update_foreign_proposal_transactions(
+    fn record_block_rejection(
         &self,
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
-        block: &Block,
+        rejection: BlockRejection,
     ) -> Result<(), HotStuffError> {
-        // TODO: Move this to consensus constants
-        const FOREIGN_PROPOSAL_TIMEOUT: u64 = 1000;
-        let all_proposed = ForeignProposal::get_all_proposed(
-            &**tx,
-            block.height().saturating_sub(NodeHeight(FOREIGN_PROPOSAL_TIMEOUT)),
+        rejection.insert(tx)?;
+        BlockRejection::prune_before(
+            tx,
+            rejection
+                .epoch
+                .saturating_sub(Epoch(self.block_rejection_retention_epochs)),
         )?;
-        for proposal in all_proposed {
-            let mut has_unresolved_transactions = false;
-
-            let (transactions, _missing) = TransactionRecord::get_any(&**tx, &proposal.transactions)?;
-            for transaction in transactions {
-                if transaction.is_finalized() {
-                    // We don't know the transaction at all, or we know it but it's not finalised.
-                    let mut tx_rec = self
-                        .transaction_pool
-                        .get(&**tx, block.as_leaf_block(), transaction.id())?;
-                    // If the transaction is still in the pool we have to check if it was at least locally prepared,
-                    // otherwise abort it.
-                    if tx_rec.stage() == TransactionPoolStage::New || tx_rec.stage() == TransactionPoolStage::Prepared {
-                        tx_rec.update_local_decision(tx, Decision::Abort)?;
-                        has_unresolved_transactions = true;
-                    }
-                }
-            }
-            if !has_unresolved_transactions {
-                proposal.delete(tx)?;
-            }
+        self.journal.record(JournalEntry::ProposalRejected {
+            block_id: rejection.block_id,
+            epoch: rejection.epoch,
+            height: rejection.height,
+            reason: rejection.reason,
+        });
+        Ok(())
+    }
+
+    /// Adds an offence to the misbehavior score of the proposer of an invalid block, if the proposer is a member of the
+    /// local committee
+    fn record_proposer_offence(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        local_committee: &Committee<TConsensusSpec::Addr>,
+        proposed_by: &PublicKey,
+        err: &ProposalValidationError,
+    ) -> Result<(), HotStuffError> {
+        if !err.is_peer_misbehavior() {
+            return Ok(());
         }
+        let Some((proposer, _)) = local_committee.iter().find(|(_, pk)| pk == proposed_by) else {
+            return Ok(());
+        };
+        self.peer_bans.record_offence_with_tx(tx, proposer, err)?;
         Ok(())
     }
 
-    // TODO: fix
-    // fn check_foreign_indexes(
-    //     &self,
-    //     tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
-    //     num_committees: u32,
-    //     local_shard: Shard,
-    //     block: &Block,
-    //     justify_block: &BlockId,
-    // ) -> Result<(), HotStuffError> {
-    //     let non_local_shards = proposer::get_non_local_shards(tx, block, num_committees, local_shard)?;
-    //     let block_foreign_indexes = block.foreign_indexes();
-    //     if block_foreign_indexes.len() != non_local_shards.len() {
-    //         return Err(ProposalValidationError::InvalidForeignCounters {
-    //             proposed_by: block.proposed_by().to_string(),
-    //             hash: *block.id(),
-    //             details: format!(
-    //                 "Foreign indexes length ({}) does not match non-local shards length ({})",
-    //                 block_foreign_indexes.len(),
-    //                 non_local_shards.len()
-    //             ),
-    //         }
-    //         .into());
-    //     }
-    //
-    //     let mut foreign_counters = ForeignSendCounters::get_or_default(tx, justify_block)?;
-    //     let mut current_shard = None;
-    //     for (shard, foreign_count) in block_foreign_indexes {
-    //         if let Some(current_shard) = current_shard {
-    //             // Check ordering
-    //             if current_shard > shard {
-    //                 return Err(ProposalValidationError::InvalidForeignCounters {
-    //                     proposed_by: block.proposed_by().to_string(),
-    //                     hash: *block.id(),
-    //                     details: format!(
-    //                         "Foreign indexes are not sorted by shard. Current shard: {}, shard: {}",
-    //                         current_shard, shard
-    //                     ),
-    //                 }
-    //                 .into());
-    //             }
-    //         }
-    //
-    //         current_shard = Some(shard);
-    //         // Check that each shard is correct
-    //         if !non_local_shards.contains(shard) {
-    //             return Err(ProposalValidationError::InvalidForeignCounters {
-    //                 proposed_by: block.proposed_by().to_string(),
-    //                 hash: *block.id(),
-    //                 details: format!("Shard {} is not a non-local shard", shard),
-    //             }
-    //             .into());
-    //         }
-    //
-    //         // Check that foreign counters are correct
-    //         let expected_count = foreign_counters.increment_counter(*shard);
-    //         if *foreign_count != expected_count {
-    //             return Err(ProposalValidationError::InvalidForeignCounters {
-    //                 proposed_by: block.proposed_by().to_string(),
-    //                 hash: *block.id(),
-    //                 details: format!(
-    //                     "Foreign counter for shard {} is incorrect. Expected {}, got {}",
-    //                     shard, expected_count, foreign_count
-    //                 ),
-    //             }
-    //             .into());
-    //         }
-    //     }
-    //
-    //     Ok(())
-    // }
+    fn update_foreign_proposal_transactions(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        block: &Block,
+    ) -> Result<(), HotStuffError> {
+        abort_timed_out_foreign_proposal_transactions(
+            tx,
+            &self.transaction_pool,
+            block,
+            self.foreign_proposal_timeout,
+        )?;
+        ForeignProposal::prune_terminal_before(
+            tx,
+            block
+                .epoch()
+                .saturating_sub(Epoch(self.foreign_proposal_retention_epochs)),
+        )?;
+        Ok(())
+    }
 
     /// Perform final block validations (TODO: implement all validations)
     /// We assume at this point that initial stateless validations have been done (in inbound messages)
@@ -354,6 +445,13 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .into());
         }
 
+        block_validations::check_proposer_signature(
+            &self.leader_strategy,
+            &self.signing_service,
+            local_committee,
+            &candidate_block,
+        )?;
+
         // Check that details included in the justify match previously added blocks
         let Some(justify_block) = candidate_block.justify().get_block(tx).optional()? else {
             // This will trigger a sync
@@ -380,6 +478,16 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
 
         // Special case for genesis block
         if candidate_block.parent().is_genesis() && candidate_block.justify().is_genesis() {
+            // The zero block is justified by the local genesis QC, which commits to the local genesis config
+            if candidate_block.justify().id() != justify_block.justify().id() {
+                return Err(ProposalValidationError::GenesisMismatch {
+                    proposed_by: candidate_block.proposed_by().to_string(),
+                    block_id: *candidate_block.id(),
+                    qc_id: *candidate_block.justify().id(),
+                    expected_qc_id: *justify_block.justify().id(),
+                }
+                .into());
+            }
             return Ok(ValidBlock::new(candidate_block));
         }
 
@@ -391,21 +499,31 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .into());
         }
 
-        // TODO: this is broken
-        // self.check_foreign_indexes(
-        //     tx,
-        //     local_committee_info.num_committees(),
-        //     local_committee_info.shard(),
-        //     &candidate_block,
-        //     justify_block.id(),
-        // )?;
+        block_validations::check_timestamp(&candidate_block, &justify_block, &self.clock)?;
+        block_validations::check_epoch_continuity(&candidate_block, &justify_block)?;
+
+        block_validations::check_foreign_indexes(tx, &candidate_block, local_committee_info)?;
+        block_validations::check_transaction_epoch_bounds(tx, &candidate_block)?;
 
         let justify_block_height = justify_block.height();
         // if the block parent is not the justify parent, then we have experienced a leader failure
         // and should make dummy blocks to fill in the gaps.
         if justify_block.id() != candidate_block.parent() {
-            let mut dummy_blocks =
-                Vec::with_capacity((candidate_block.height().as_u64() - justify_block_height.as_u64() - 1) as usize);
+            let num_dummy_blocks = candidate_block
+                .height()
+                .as_u64()
+                .saturating_sub(justify_block_height.as_u64())
+                .saturating_sub(1);
+            if num_dummy_blocks > self.max_dummy_blocks {
+                return Err(ProposalValidationError::TooManyDummyBlocks {
+                    proposed_by: candidate_block.proposed_by().to_string(),
+                    block_id: *candidate_block.id(),
+                    num_dummy_blocks,
+                    max_dummy_blocks: self.max_dummy_blocks,
+                }
+                .into());
+            }
+            let mut dummy_blocks = Vec::with_capacity(num_dummy_blocks as usize);
             let timestamp = justify_block.timestamp();
             let base_layer_block_height = justify_block.base_layer_block_height();
             let base_layer_block_hash = *justify_block.base_layer_block_hash();
@@ -423,7 +541,9 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 }
 
                 let next_height = last_dummy_block.height() + NodeHeight(1);
-                let leader = self.leader_strategy.get_leader_public_key(local_committee, next_height);
+                let leader =
+                    self.leader_strategy
+                        .get_leader_public_key(local_committee, candidate_block.epoch(), next_height);
 
                 // TODO: replace with actual leader's propose
                 dummy_blocks.push(Block::dummy_block(
@@ -465,3 +585,13 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         Ok(ValidBlock::new(candidate_block))
     }
 }
+
+/// The result of validating and persisting a proposed block in a single write transaction
+enum ProposalOutcome {
+    /// The block was persisted previously, so the proposal is received again
+    AlreadyExists(Block),
+    /// The block failed validation and the rejection was recorded
+    Rejected,
+    /// The block is valid and was persisted
+    Persisted { high_qc: HighQc, valid_block: ValidBlock },
+}
@@ -1,13 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::{HashMap, HashSet};
-
 use log::*;
 use tari_common::configuration::Network;
-use tari_dan_common_types::NodeHeight;
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, LeafBlock, LockedBlock, QuorumCertificate},
+    consensus_models::{Block, LeafBlock, LockedBlock, NewViewRecord, QuorumCertificate},
     StateStore,
 };
 use tari_epoch_manager::EpochManagerReader;
@@ -26,7 +23,6 @@ pub struct OnReceiveNewViewHandler<TConsensusSpec: ConsensusSpec> {
     store: TConsensusSpec::StateStore,
     leader_strategy: TConsensusSpec::LeaderStrategy,
     epoch_manager: TConsensusSpec::EpochManager,
-    newview_message_counts: HashMap<(NodeHeight, BlockId), HashSet<TConsensusSpec::Addr>>,
     pacemaker: PaceMakerHandle,
     vote_receiver: VoteReceiver<TConsensusSpec>,
 }
@@ -47,43 +43,26 @@ where TConsensusSpec: ConsensusSpec
             store,
             leader_strategy,
             epoch_manager,
-            newview_message_counts: HashMap::default(),
             pacemaker,
             vote_receiver,
         }
     }
 
-    pub(super) fn clear_new_views(&mut self) {
-        self.newview_message_counts.clear();
-    }
-
-    fn collect_new_views(
-        &mut self,
-        from: TConsensusSpec::Addr,
-        new_height: NodeHeight,
-        high_qc: &QuorumCertificate,
-    ) -> usize {
-        let entry = self
-            .newview_message_counts
-            .entry((new_height, *high_qc.block_id()))
-            .or_default();
-        entry.insert(from);
-        entry.len()
-    }
-
     #[allow(clippy::too_many_lines)]
     pub async fn handle(&mut self, from: TConsensusSpec::Addr, message: NewViewMessage) -> Result<(), HotStuffError> {
         let NewViewMessage {
             high_qc,
             new_height,
             epoch,
+            last_voted_height,
             last_vote,
         } = message;
         debug!(
             target: LOG_TARGET,
-            "🌟 Received NEWVIEW for qc {} new height {} from {}",
+            "🌟 Received NEWVIEW for qc {} new height {} (last voted height: {}) from {}",
             high_qc,
             new_height,
+            last_voted_height,
             from
         );
 
@@ -130,7 +109,7 @@ where TConsensusSpec: ConsensusSpec
         let local_committee_shard = self.epoch_manager.get_local_committee_info(epoch).await?;
         let leader = self
             .leader_strategy
-            .get_leader_for_next_block(&local_committee, new_height);
+            .get_leader_for_next_block(&local_committee, epoch, new_height);
         let our_node = self.epoch_manager.get_our_validator_node(epoch).await?;
 
         if *leader != our_node.address {
@@ -160,21 +139,32 @@ where TConsensusSpec: ConsensusSpec
             });
         }
 
-        // Take note of unique NEWVIEWs so that we can count them
-        let newview_count = self.collect_new_views(from, new_height, &high_qc);
+        // Take note of unique NEWVIEWs so that we can count them. NEWVIEWs are persisted so that a leader that restarts
+        // does not have to wait for replicas to time out again.
+        let new_view = NewViewRecord {
+            epoch,
+            new_height,
+            sender: from,
+            high_qc_id: *high_qc.id(),
+            high_qc_height: high_qc.block_height(),
+        };
+        let (is_new, newview_count, high_qc) = self.store.with_write_tx(|tx| {
+            let is_new = new_view.insert(tx)?;
+            if !is_new {
+                debug!(target: LOG_TARGET, "Ignoring duplicate {}", new_view);
+            }
+            NewViewRecord::<TConsensusSpec::Addr>::prune_before(tx, epoch, locked.height())?;
+            let newview_count = NewViewRecord::count_for_view(&**tx, epoch, new_height)?;
 
-        let high_qc = self.store.with_write_tx(|tx| {
+            // Propose using the highest QC that we know of, including those received in NEWVIEWs
             high_qc.save(tx)?;
             let high_qc = high_qc.update_high_qc(tx)?;
-            high_qc.get_quorum_certificate(&**tx)
+            let high_qc = high_qc.get_quorum_certificate(&**tx)?;
+            Ok::<_, HotStuffError>((is_new, newview_count, high_qc))
         })?;
 
-        // if checked_high_qc.block_height() > high_qc.block_height() {
-        //     warn!(target: LOG_TARGET, "❌ Ignoring NEWVIEW for because high QC is not higher than previous high QC,
-        // given high QC: {} current high QC: {}", high_qc.as_high_qc(), checked_high_qc);     return Ok(());
-        // }
-
-        let threshold = self.epoch_manager.get_local_threshold_for_epoch(epoch).await?;
+        // Only f + 1 NEWVIEWs are required, since at least one of them is from an honest replica that has timed out
+        let threshold = local_committee.max_failures() as u64 + 1;
 
         info!(
             target: LOG_TARGET,
@@ -184,9 +174,9 @@ where TConsensusSpec: ConsensusSpec
             newview_count,
             threshold,
         );
-        // Once we have received enough (quorum) NEWVIEWS, we can create the dummy block(s) and propose the next block.
-        // Any subsequent NEWVIEWs for this height/view are ignored.
-        if newview_count == threshold {
+        // Once we have received enough (f + 1) NEWVIEWS, we can create the dummy block(s) and propose the next block
+        // without waiting for our own timer. Any subsequent NEWVIEWs for this height/view are ignored.
+        if is_new && newview_count == threshold {
             info!(target: LOG_TARGET, "🌟✅ NEWVIEW for block {} (high_qc: {}) has reached quorum ({}/{})", new_height, high_qc.as_high_qc(), newview_count, threshold);
 
             let high_qc_block = self.store.with_read_tx(|tx| high_qc.get_block(tx))?;
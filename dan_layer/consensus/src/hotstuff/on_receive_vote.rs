@@ -19,7 +19,7 @@ where TConsensusSpec: ConsensusSpec
         Self { vote_receiver }
     }
 
-    pub async fn handle(&self, from: TConsensusSpec::Addr, message: VoteMessage) -> Result<(), HotStuffError> {
+    pub async fn handle(&mut self, from: TConsensusSpec::Addr, message: VoteMessage) -> Result<(), HotStuffError> {
         debug!(
             target: LOG_TARGET,
             "🔥 Receive VOTE for node {} from {}", message.block_id, message.signature.public_key,
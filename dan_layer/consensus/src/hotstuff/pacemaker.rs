@@ -1,42 +1,52 @@
 //  Copyright 2022 The Tari Project
 //  SPDX-License-Identifier: BSD-3-Clause
 use std::{
-    cmp,
+    sync::{atomic, atomic::AtomicU64, Arc},
     time::{Duration, Instant},
 };
 
 use log::*;
 use tari_dan_common_types::NodeHeight;
-use tokio::sync::mpsc;
-
-use crate::hotstuff::{
-    current_height::CurrentHeight,
-    on_beat::OnBeat,
-    on_force_beat::OnForceBeat,
-    on_leader_timeout::OnLeaderTimeout,
-    pacemaker_handle::{PaceMakerHandle, PacemakerRequest},
-    HotStuffError,
+use tokio::{sync::mpsc, time::MissedTickBehavior};
+
+use crate::{
+    hotstuff::{
+        current_height::CurrentHeight,
+        on_beat::OnBeat,
+        on_force_beat::OnForceBeat,
+        on_leader_timeout::OnLeaderTimeout,
+        pacemaker_handle::{PaceMakerHandle, PacemakerRequest},
+        HotStuffError,
+        PacemakerConfig,
+        ViewTimeoutBackoff,
+    },
+    traits::Clock,
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::pacemaker";
-const MAX_DELTA: Duration = Duration::from_secs(300);
 const BLOCK_TIME: Duration = Duration::from_secs(10);
+/// How often the leader timeout deadline is checked against the consensus clock
+const LEADER_TIMEOUT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
-pub struct PaceMaker {
+pub struct PaceMaker<TClock> {
     pace_maker_handle: PaceMakerHandle,
     handle_receiver: mpsc::Receiver<PacemakerRequest>,
     current_height: CurrentHeight,
     current_high_qc_height: NodeHeight,
+    backoff: ViewTimeoutBackoff,
+    current_timeout_ms: Arc<AtomicU64>,
+    clock: TClock,
 }
 
-impl PaceMaker {
-    pub fn new() -> Self {
+impl<TClock: Clock + Send + 'static> PaceMaker<TClock> {
+    pub fn new(config: PacemakerConfig, clock: TClock) -> Self {
         let (sender, receiver) = mpsc::channel(100);
 
         let on_beat = OnBeat::new();
         let on_force_beat = OnForceBeat::new();
         let on_leader_timeout = OnLeaderTimeout::new();
         let current_height = CurrentHeight::new();
+        let current_timeout_ms = Arc::new(AtomicU64::new(0));
 
         Self {
             handle_receiver: receiver,
@@ -46,9 +56,13 @@ impl PaceMaker {
                 on_force_beat,
                 on_leader_timeout,
                 current_height.clone(),
+                current_timeout_ms.clone(),
             ),
             current_height,
             current_high_qc_height: NodeHeight(0),
+            backoff: ViewTimeoutBackoff::new(config),
+            current_timeout_ms,
+            clock,
         }
     }
 
@@ -75,11 +89,13 @@ impl PaceMaker {
         on_force_beat: OnForceBeat,
         on_leader_timeout: OnLeaderTimeout,
     ) -> Result<(), HotStuffError> {
-        // Don't start the timer until we start the pacemaker
-        let leader_timeout = tokio::time::sleep(Duration::MAX);
+        // Don't start the timers until we start the pacemaker
         let block_timer = tokio::time::sleep(Duration::MAX);
-        tokio::pin!(leader_timeout);
         tokio::pin!(block_timer);
+        // The leader timeout is measured on the consensus clock, in milliseconds since the unix epoch
+        let mut leader_timeout_at = None;
+        let mut leader_timeout_check = tokio::time::interval(LEADER_TIMEOUT_CHECK_INTERVAL);
+        leader_timeout_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         let mut started = false;
 
@@ -94,10 +110,14 @@ impl PaceMaker {
                                     continue;
                                 }
 
+                                // A new QC means that the view made progress
+                                if high_qc_height > self.current_high_qc_height {
+                                    self.backoff.reset();
+                                }
                                 self.current_high_qc_height = high_qc_height;
                                 let delta = self.delta_time();
                                 info!(target: LOG_TARGET, "Reset! Current height: {}, Delta: {:.2?}", self.current_height, delta);
-                                leader_timeout.as_mut().reset(tokio::time::Instant::now() + delta);
+                                leader_timeout_at = Some(self.deadline_after(delta));
                                 // set a timer for when we must send a block...
                                 block_timer.as_mut().reset(tokio::time::Instant::now() + BLOCK_TIME);
                            },
@@ -109,7 +129,7 @@ impl PaceMaker {
                                 self.current_high_qc_height = high_qc_height;
                                 let delta = self.delta_time();
                                 info!(target: LOG_TARGET, "Reset! Current height: {}, Delta: {:.2?}", self.current_height, delta);
-                                leader_timeout.as_mut().reset(tokio::time::Instant::now() + delta);
+                                leader_timeout_at = Some(self.deadline_after(delta));
                                 block_timer.as_mut().reset(tokio::time::Instant::now() + BLOCK_TIME);
                                 on_beat.beat();
                                 started = true;
@@ -117,8 +137,7 @@ impl PaceMaker {
                             PacemakerRequest::Stop => {
                                 info!(target: LOG_TARGET, "💤 Stopping pacemaker");
                                 started = false;
-                                // TODO: we could use futures-rs Either
-                                leader_timeout.as_mut().reset(far_future());
+                                leader_timeout_at = None;
                                 block_timer.as_mut().reset(far_future());
                             }
                         }
@@ -131,14 +150,24 @@ impl PaceMaker {
                     block_timer.as_mut().reset(tokio::time::Instant::now() + BLOCK_TIME);
                     on_force_beat.beat(None);
                 }
-                () = &mut leader_timeout => {
+                _ = leader_timeout_check.tick() => {
+                    if !leader_timeout_at.is_some_and(|at| self.clock.now_millis() >= at) {
+                        continue;
+                    }
                     block_timer.as_mut().reset(tokio::time::Instant::now() + BLOCK_TIME);
 
+                    self.backoff.on_view_failed();
                     let delta = self.delta_time();
-                    leader_timeout.as_mut().reset(tokio::time::Instant::now() + delta);
-                    info!(target: LOG_TARGET, "⚠️ Leader timeout! Current height: {}, Delta: {:.2?}", self.current_height, delta);
+                    leader_timeout_at = Some(self.deadline_after(delta));
+                    info!(
+                        target: LOG_TARGET,
+                        "⚠️ Leader timeout! Current height: {}, Delta: {:.2?}, consecutive failures: {}",
+                        self.current_height,
+                        delta,
+                        self.backoff.consecutive_failures()
+                    );
                     self.current_height.next_height();
-                    on_leader_timeout.leader_timed_out(self.current_height.get());
+                    on_leader_timeout.leader_timed_out(self.current_height.get(), self.backoff.consecutive_failures());
                 },
 
             }
@@ -147,27 +176,23 @@ impl PaceMaker {
         Ok(())
     }
 
-    /// Current delta time defined as 2^n where n is the difference in height between the last seen block height and the
-    /// high QC height. This is always greater than the block time.
-    /// Ensure that current_height and current_high_qc_height are set before calling this function.
+    /// Returns the leader timeout for the current view, backed off exponentially for each consecutive failed view and
+    /// randomly jittered so that nodes do not time out in lockstep. The timeout is published to the pacemaker handles.
     fn delta_time(&self) -> Duration {
-        let current_height = self.current_height.get();
-        if current_height.is_zero() {
+        let delta = if self.current_height.get().is_zero() {
             // Allow extra time for the first block
-            return BLOCK_TIME * 2;
-        }
-        let exp = u32::try_from(cmp::min(
-            u64::from(u32::MAX),
-            cmp::max(1, current_height.saturating_sub(self.current_high_qc_height).as_u64()),
-        ))
-        .unwrap_or(u32::MAX);
-        let delta = cmp::min(
-            MAX_DELTA,
-            2u64.checked_pow(exp).map(Duration::from_secs).unwrap_or(MAX_DELTA),
-        );
-        // TODO: get real avg latency
-        let avg_latency = Duration::from_secs(2);
-        BLOCK_TIME + delta + avg_latency
+            BLOCK_TIME * 2
+        } else {
+            self.backoff.timeout_with_jitter(&mut rand::thread_rng())
+        };
+        self.current_timeout_ms
+            .store(delta.as_millis() as u64, atomic::Ordering::SeqCst);
+        delta
+    }
+
+    /// Returns the time on the consensus clock, in milliseconds, at which the given duration has elapsed
+    fn deadline_after(&self, delta: Duration) -> u64 {
+        self.clock.now_millis() + delta.as_millis() as u64
     }
 }
 
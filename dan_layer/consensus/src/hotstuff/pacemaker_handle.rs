@@ -1,6 +1,11 @@
 //  Copyright 2022 The Tari Project
 //  SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    sync::{atomic, atomic::AtomicU64, Arc},
+    time::Duration,
+};
+
 use tari_dan_common_types::NodeHeight;
 use tari_dan_storage::consensus_models::LeafBlock;
 use tokio::sync::mpsc;
@@ -26,6 +31,7 @@ pub struct PaceMakerHandle {
     on_force_beat: OnForceBeat,
     on_leader_timeout: OnLeaderTimeout,
     current_height: CurrentHeight,
+    current_timeout_ms: Arc<AtomicU64>,
 }
 
 impl PaceMakerHandle {
@@ -35,6 +41,7 @@ impl PaceMakerHandle {
         on_force_beat: OnForceBeat,
         on_leader_timeout: OnLeaderTimeout,
         current_height: CurrentHeight,
+        current_timeout_ms: Arc<AtomicU64>,
     ) -> Self {
         Self {
             sender,
@@ -42,6 +49,7 @@ impl PaceMakerHandle {
             on_force_beat,
             on_leader_timeout,
             current_height,
+            current_timeout_ms,
         }
     }
 
@@ -101,4 +109,10 @@ impl PaceMakerHandle {
     pub fn current_height(&self) -> NodeHeight {
         self.current_height.get()
     }
+
+    /// Returns the leader timeout of the current view, including backoff and jitter. This is zero until the pacemaker
+    /// has started.
+    pub fn current_timeout(&self) -> Duration {
+        Duration::from_millis(self.current_timeout_ms.load(atomic::Ordering::SeqCst))
+    }
 }
@@ -0,0 +1,79 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::optional::Optional;
+use tari_dan_storage::{consensus_models::PeerMisbehavior, StateStore};
+
+use crate::{
+    hotstuff::{HotStuffError, PeerBanConfig},
+    traits::{Clock, ConsensusSpec},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::peer_bans";
+
+/// Keeps the misbehavior score of peers that send invalid proposals or votes and bans a peer once its score reaches the
+/// configured threshold. Messages from banned peers are dropped until the ban expires or the peer is unbanned.
+#[derive(Clone)]
+pub struct PeerBanTracker<TConsensusSpec: ConsensusSpec> {
+    store: TConsensusSpec::StateStore,
+    clock: TConsensusSpec::Clock,
+    config: PeerBanConfig,
+}
+
+impl<TConsensusSpec> PeerBanTracker<TConsensusSpec>
+where TConsensusSpec: ConsensusSpec
+{
+    pub fn new(store: TConsensusSpec::StateStore, clock: TConsensusSpec::Clock, config: PeerBanConfig) -> Self {
+        Self { store, clock, config }
+    }
+
+    /// Adds an offence to the score of the peer and bans the peer if the score reaches the threshold. Returns the
+    /// updated record.
+    pub fn record_offence<T: ToString>(
+        &self,
+        address: &TConsensusSpec::Addr,
+        reason: &T,
+    ) -> Result<PeerMisbehavior<TConsensusSpec::Addr>, HotStuffError> {
+        self.store
+            .with_write_tx(|tx| self.record_offence_with_tx(tx, address, reason))
+    }
+
+    /// Same as [PeerBanTracker::record_offence] but within an existing write transaction
+    pub fn record_offence_with_tx<T: ToString>(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        address: &TConsensusSpec::Addr,
+        reason: &T,
+    ) -> Result<PeerMisbehavior<TConsensusSpec::Addr>, HotStuffError> {
+        let now = self.clock.now();
+        let mut misbehavior = PeerMisbehavior::get(&**tx, address)
+            .optional()?
+            .unwrap_or_else(|| PeerMisbehavior::new(address.clone()));
+        misbehavior.record_offence(now, reason.to_string(), self.config.score_decay_interval);
+        if misbehavior.score >= self.config.ban_threshold && !misbehavior.is_banned(now) {
+            misbehavior.banned_until = Some(now + self.config.ban_duration.as_secs());
+            warn!(
+                target: LOG_TARGET,
+                "🚫 Banning peer {} for {:.2?} after {} offences. Last offence: {}",
+                address,
+                self.config.ban_duration,
+                misbehavior.score,
+                misbehavior.reason
+            );
+        } else {
+            debug!(target: LOG_TARGET, "⚠️ Recorded offence for peer: {}", misbehavior);
+        }
+        misbehavior.upsert(tx)?;
+
+        Ok(misbehavior)
+    }
+
+    pub fn is_banned(&self, address: &TConsensusSpec::Addr) -> Result<bool, HotStuffError> {
+        let now = self.clock.now();
+        let maybe_misbehavior = self
+            .store
+            .with_read_tx(|tx| PeerMisbehavior::get(tx, address).optional())?;
+        Ok(maybe_misbehavior.is_some_and(|m| m.is_banned(now)))
+    }
+}
@@ -12,17 +12,14 @@ use tari_dan_storage::{
 };
 use tari_epoch_manager::EpochManagerReader;
 
-use super::HotStuffError;
-use crate::{
-    messages::{HotstuffMessage, ProposalMessage},
-    traits::{ConsensusSpec, OutboundMessaging},
-};
+use super::{foreign_proposal_outbox::ForeignProposalOutbox, HotStuffError};
+use crate::traits::ConsensusSpec;
 
 #[derive(Clone)]
 pub struct Proposer<TConsensusSpec: ConsensusSpec> {
     store: TConsensusSpec::StateStore,
     epoch_manager: TConsensusSpec::EpochManager,
-    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    outbox: ForeignProposalOutbox<TConsensusSpec>,
 }
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_propose_foreignly";
@@ -33,12 +30,12 @@ where TConsensusSpec: ConsensusSpec
     pub fn new(
         store: TConsensusSpec::StateStore,
         epoch_manager: TConsensusSpec::EpochManager,
-        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        outbox: ForeignProposalOutbox<TConsensusSpec>,
     ) -> Self {
         Self {
             store,
             epoch_manager,
-            outbound_messaging,
+            outbox,
         }
     }
 
@@ -77,14 +74,7 @@ where TConsensusSpec: ConsensusSpec
             block,
             non_local_committees.len(),
         );
-        self.outbound_messaging
-            .multicast(
-                non_local_committees
-                    .values()
-                    .flat_map(|c| c.iter().map(|(addr, _)| addr)),
-                HotstuffMessage::ForeignProposal(ProposalMessage { block }),
-            )
-            .await?;
+        self.outbox.send(block, non_local_committees).await?;
         Ok(())
     }
 }
@@ -0,0 +1,121 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, QcTiming, VoteTiming};
+
+/// The number of blocks below the highest tracked block for which votes are still timed. Votes for older blocks are
+/// not recorded.
+const MAX_TRACKED_HEIGHTS: u64 = 32;
+
+/// Tracks the arrival times of votes for blocks in memory so that a [QcTiming] summary can be persisted as each vote
+/// arrives. Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct QcTimingTracker {
+    blocks: Arc<Mutex<HashMap<BlockId, PendingQcTiming>>>,
+}
+
+#[derive(Debug)]
+struct PendingQcTiming {
+    epoch: Epoch,
+    height: NodeHeight,
+    started_at: Instant,
+    quorum_reached_after: Option<Duration>,
+    votes: Vec<(PublicKey, Duration)>,
+}
+
+impl QcTimingTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts timing votes for a block from the time that its proposal is received
+    pub fn on_proposal_received(&self, block_id: BlockId, epoch: Epoch, height: NodeHeight) {
+        let mut blocks = self.blocks.lock().unwrap();
+        blocks.entry(block_id).or_insert_with(|| PendingQcTiming::new(epoch, height));
+        Self::prune(&mut blocks, height);
+    }
+
+    /// Records the arrival of a vote and returns the updated timing summary for the block. None is returned if the
+    /// validator has already voted on the block or the block is too old to be tracked.
+    pub fn on_vote_received(
+        &self,
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        voter: &PublicKey,
+        is_quorum_reached: bool,
+    ) -> Option<QcTiming> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let max_height = blocks.values().map(|b| b.height).max().unwrap_or(height);
+        if height.as_u64() + MAX_TRACKED_HEIGHTS < max_height.as_u64() {
+            return None;
+        }
+
+        let pending = blocks
+            .entry(block_id)
+            .or_insert_with(|| PendingQcTiming::new(epoch, height));
+        if pending.votes.iter().any(|(pk, _)| pk == voter) {
+            return None;
+        }
+
+        let arrived_after = pending.started_at.elapsed();
+        pending.votes.push((voter.clone(), arrived_after));
+        if is_quorum_reached && pending.quorum_reached_after.is_none() {
+            pending.quorum_reached_after = Some(arrived_after);
+        }
+        let timing = pending.to_qc_timing(block_id);
+        Self::prune(&mut blocks, height);
+        Some(timing)
+    }
+
+    fn prune(blocks: &mut HashMap<BlockId, PendingQcTiming>, height: NodeHeight) {
+        blocks.retain(|_, b| b.height.as_u64() + MAX_TRACKED_HEIGHTS >= height.as_u64());
+    }
+}
+
+impl PendingQcTiming {
+    fn new(epoch: Epoch, height: NodeHeight) -> Self {
+        Self {
+            epoch,
+            height,
+            started_at: Instant::now(),
+            quorum_reached_after: None,
+            votes: Vec::new(),
+        }
+    }
+
+    fn to_qc_timing(&self, block_id: BlockId) -> QcTiming {
+        let votes = self
+            .votes
+            .iter()
+            .enumerate()
+            .map(|(rank, (public_key, arrived_after))| VoteTiming {
+                public_key: public_key.clone(),
+                arrived_after_ms: as_millis(*arrived_after),
+                rank: rank as u32,
+            })
+            .collect::<Vec<_>>();
+
+        QcTiming {
+            block_id,
+            epoch: self.epoch,
+            height: self.height,
+            first_vote_ms: votes.first().map(|v| v.arrived_after_ms).unwrap_or_default(),
+            quorum_reached_ms: self.quorum_reached_after.map(as_millis),
+            last_vote_ms: votes.last().map(|v| v.arrived_after_ms).unwrap_or_default(),
+            votes,
+        }
+    }
+}
+
+fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
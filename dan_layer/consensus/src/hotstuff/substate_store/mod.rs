@@ -3,6 +3,8 @@
 
 mod error;
 mod pending_store;
+mod snapshot;
 
 pub use error::*;
 pub use pending_store::*;
+pub use snapshot::*;
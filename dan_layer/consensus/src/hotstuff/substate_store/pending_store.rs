@@ -10,6 +10,7 @@ use tari_dan_common_types::{optional::Optional, SubstateAddress};
 use tari_dan_storage::{
     consensus_models::{
         Block,
+        BlockId,
         LockedSubstate,
         PendingStateTreeDiff,
         SubstateChange,
@@ -27,7 +28,7 @@ use tari_transaction::{TransactionId, VersionedSubstateId};
 use super::error::SubstateStoreError;
 use crate::{
     hotstuff::calculate_state_merkle_diff,
-    traits::{ReadableSubstateStore, WriteableSubstateStore},
+    traits::{InputSubstateStore, ReadableSubstateStore, WriteableSubstateStore},
 };
 
 const LOG_TARGET: &str = "tari::dan::hotstuff::substate_store::pending_store";
@@ -39,6 +40,9 @@ pub struct PendingSubstateStore<'a, 'tx, TStore: StateStore + 'a + 'tx> {
     /// Append only list of changes ordered oldest to newest
     diff: Vec<SubstateChange>,
     new_locks: IndexMap<SubstateId, Vec<LockedSubstate>>,
+    /// Changes of uncommitted blocks ordered oldest to newest that are read as if they were committed. These are not
+    /// part of the diff.
+    speculative: Vec<SubstateChange>,
 }
 
 impl<'a, 'tx, TStore: StateStore + 'a> PendingSubstateStore<'a, 'tx, TStore> {
@@ -48,9 +52,28 @@ impl<'a, 'tx, TStore: StateStore + 'a> PendingSubstateStore<'a, 'tx, TStore> {
             pending: HashMap::new(),
             diff: Vec::new(),
             new_locks: IndexMap::new(),
+            speculative: Vec::new(),
         }
     }
 
+    /// Creates a store that reads the substates of the given uncommitted block and its uncommitted ancestors as if
+    /// they were committed. This allows transactions to be executed against the speculative state of a block that has
+    /// not been committed yet.
+    pub fn new_speculative(
+        tx: &'a TStore::ReadTransaction<'tx>,
+        block_id: &BlockId,
+    ) -> Result<Self, SubstateStoreError> {
+        let mut speculative = Vec::new();
+        for pending in PendingStateTreeDiff::get_all_up_to_commit_block(tx, block_id)? {
+            speculative.extend(tx.block_diffs_get(&pending.block_id)?.into_changes());
+        }
+
+        Ok(Self {
+            speculative,
+            ..Self::new(tx)
+        })
+    }
+
     pub fn read_transaction(&self) -> &'a TStore::ReadTransaction<'tx> {
         self.store
     }
@@ -66,6 +89,12 @@ impl<'a, 'tx, TStore: StateStore + 'a + 'tx> ReadableSubstateStore for PendingSu
             });
         }
 
+        if let Some(change) = self.get_speculative(key) {
+            return change.up().cloned().ok_or_else(|| SubstateStoreError::SubstateIsDown {
+                id: change.versioned_substate_id().clone(),
+            });
+        }
+
         let Some(substate) = SubstateRecord::get(self.store, key).optional()? else {
             return Err(SubstateStoreError::SubstateNotFound { address: *key });
         };
@@ -94,6 +123,12 @@ impl<'a, 'tx, TStore: StateStore + 'a + 'tx> WriteableSubstateStore for PendingS
     }
 }
 
+impl<'a, 'tx, TStore: StateStore + 'a + 'tx> InputSubstateStore for PendingSubstateStore<'a, 'tx, TStore> {
+    fn get_latest(&self, id: &SubstateId) -> Result<Substate, SubstateStoreError> {
+        PendingSubstateStore::get_latest(self, id)
+    }
+}
+
 impl<'a, 'tx, TStore: StateStore + 'a + 'tx> PendingSubstateStore<'a, 'tx, TStore> {
     pub fn get_latest(&self, id: &SubstateId) -> Result<Substate, SubstateStoreError> {
         // TODO: This returns the pledged inputs (local or foreign)
@@ -109,6 +144,16 @@ impl<'a, 'tx, TStore: StateStore + 'a + 'tx> PendingSubstateStore<'a, 'tx, TStor
             return Ok(substate.clone());
         }
 
+        if let Some(substate) = self
+            .speculative
+            .iter()
+            .rev()
+            .find(|change| change.versioned_substate_id().substate_id == *id)
+            .and_then(|ch| ch.up())
+        {
+            return Ok(substate.clone());
+        }
+
         let substate = SubstateRecord::get_latest(self.store, id)?;
         Ok(substate.into_substate())
     }
@@ -319,6 +364,13 @@ impl<'a, 'tx, TStore: StateStore + 'a + 'tx> PendingSubstateStore<'a, 'tx, TStor
             .map(|&pos| self.diff.get(pos).expect("Index map and diff are out of sync"))
     }
 
+    fn get_speculative(&self, key: &SubstateAddress) -> Option<&SubstateChange> {
+        self.speculative
+            .iter()
+            .rev()
+            .find(|change| change.to_substate_address() == *key)
+    }
+
     fn insert(&mut self, change: SubstateChange) {
         self.pending.insert(change.to_substate_address(), self.diff.len());
         self.diff.push(change)
@@ -0,0 +1,134 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    SubstateAddress,
+};
+use tari_dan_storage::{consensus_models::ExecutedTransaction, StateStore, StorageError};
+use tari_engine_types::substate::{Substate, SubstateId};
+use tari_transaction::{Transaction, VersionedSubstateId};
+
+use super::{error::SubstateStoreError, PendingSubstateStore};
+use crate::traits::{InputSubstateStore, ReadableSubstateStore};
+
+/// The input substates of a set of transactions, loaded from a [PendingSubstateStore] so that the transactions can be
+/// executed without holding a read transaction on the state store.
+#[derive(Debug, Clone, Default)]
+pub struct SubstateSnapshot {
+    /// Substates by address. None if the substate is DOWN.
+    substates: HashMap<SubstateAddress, (VersionedSubstateId, Option<Substate>)>,
+    latest: HashMap<SubstateId, Substate>,
+}
+
+impl SubstateSnapshot {
+    /// Loads the latest version of every input of the given transactions as well as the specific versions of any
+    /// versioned inputs
+    pub fn load<'a, TStore, I>(
+        store: &PendingSubstateStore<TStore>,
+        transactions: I,
+    ) -> Result<Self, SubstateStoreError>
+    where
+        TStore: StateStore,
+        I: IntoIterator<Item = &'a Transaction>,
+    {
+        let mut snapshot = Self::default();
+        for input in transactions
+            .into_iter()
+            .flat_map(|transaction| transaction.all_inputs_iter())
+        {
+            if let Some(version) = input.version() {
+                let id = VersionedSubstateId::new(input.substate_id().clone(), version);
+                let address = id.to_substate_address();
+                match store.get(&address) {
+                    Ok(substate) => {
+                        snapshot.substates.insert(address, (id, Some(substate)));
+                    },
+                    Err(SubstateStoreError::SubstateIsDown { .. }) => {
+                        snapshot.substates.insert(address, (id, None));
+                    },
+                    Err(err) if err.is_not_found_error() => {},
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if let Some(substate) = store.get_latest(input.substate_id()).optional()? {
+                snapshot.latest.insert(input.substate_id, substate);
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Returns a view of the snapshot that transactions can be executed against
+    pub fn view(&self) -> SubstateSnapshotView<'_> {
+        SubstateSnapshotView {
+            snapshot: self,
+            changes: HashMap::new(),
+            latest: HashMap::new(),
+        }
+    }
+}
+
+/// A view of a [SubstateSnapshot] that includes the outputs of the transactions that were executed against it. Reads
+/// resolve the same way as they would in a [PendingSubstateStore] that the same transaction diffs were put into.
+#[derive(Debug, Clone)]
+pub struct SubstateSnapshotView<'a> {
+    snapshot: &'a SubstateSnapshot,
+    changes: HashMap<SubstateAddress, (VersionedSubstateId, Option<Substate>)>,
+    latest: HashMap<SubstateId, Substate>,
+}
+
+impl SubstateSnapshotView<'_> {
+    /// Applies the substate diff of the executed transaction, if it was accepted
+    pub fn apply(&mut self, executed: &ExecutedTransaction) {
+        let Some(diff) = executed.result().finalize.accept() else {
+            return;
+        };
+
+        for (substate_id, version) in diff.down_iter() {
+            let id = VersionedSubstateId::new(substate_id.clone(), *version);
+            self.changes.insert(id.to_substate_address(), (id, None));
+            self.latest.remove(substate_id);
+        }
+
+        for (substate_id, substate) in diff.up_iter() {
+            let id = VersionedSubstateId::new(substate_id.clone(), substate.version());
+            self.changes
+                .insert(id.to_substate_address(), (id, Some(substate.clone())));
+            self.latest.insert(substate_id.clone(), substate.clone());
+        }
+    }
+}
+
+impl ReadableSubstateStore for SubstateSnapshotView<'_> {
+    type Error = SubstateStoreError;
+
+    fn get(&self, key: &SubstateAddress) -> Result<Substate, Self::Error> {
+        let (id, substate) = self
+            .changes
+            .get(key)
+            .or_else(|| self.snapshot.substates.get(key))
+            .ok_or(SubstateStoreError::SubstateNotFound { address: *key })?;
+        substate
+            .clone()
+            .ok_or_else(|| SubstateStoreError::SubstateIsDown { id: id.clone() })
+    }
+}
+
+impl InputSubstateStore for SubstateSnapshotView<'_> {
+    fn get_latest(&self, id: &SubstateId) -> Result<Substate, SubstateStoreError> {
+        self.latest
+            .get(id)
+            .or_else(|| self.snapshot.latest.get(id))
+            .cloned()
+            .ok_or_else(|| {
+                SubstateStoreError::StoreError(StorageError::NotFound {
+                    item: "Substate".to_string(),
+                    key: id.to_string(),
+                })
+            })
+    }
+}
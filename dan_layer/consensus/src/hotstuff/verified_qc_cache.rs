@@ -0,0 +1,72 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use indexmap::IndexSet;
+use tari_dan_common_types::Epoch;
+use tari_dan_storage::consensus_models::QcId;
+
+/// A least-recently-used set of the QCs whose signatures have already been verified. The same high QC is the justify
+/// of many successive proposals, so this avoids verifying its signatures each time. The cache only holds QCs that
+/// were verified in the current epoch because committee membership can change between epochs.
+#[derive(Debug)]
+pub struct VerifiedQcCache {
+    epoch: Option<Epoch>,
+    capacity: usize,
+    /// Ordered from least to most recently used
+    qc_ids: IndexSet<QcId>,
+}
+
+impl VerifiedQcCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            epoch: None,
+            capacity,
+            qc_ids: IndexSet::with_capacity(capacity),
+        }
+    }
+
+    /// Returns true if the QC has been verified in the given epoch, marking it as the most recently used
+    pub fn contains(&mut self, epoch: Epoch, qc_id: &QcId) -> bool {
+        if self.epoch != Some(epoch) {
+            return false;
+        }
+        let Some(index) = self.qc_ids.get_index_of(qc_id) else {
+            return false;
+        };
+        self.qc_ids.move_index(index, self.qc_ids.len() - 1);
+        true
+    }
+
+    /// Records that the QC has been verified in the given epoch. If the epoch differs from the epoch of the cached
+    /// QCs, the cache is cleared first.
+    pub fn insert(&mut self, epoch: Epoch, qc_id: QcId) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.epoch != Some(epoch) {
+            self.clear();
+            self.epoch = Some(epoch);
+        }
+        let (index, is_new) = self.qc_ids.insert_full(qc_id);
+        if !is_new {
+            self.qc_ids.move_index(index, self.qc_ids.len() - 1);
+            return;
+        }
+        if self.qc_ids.len() > self.capacity {
+            self.qc_ids.shift_remove_index(0);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.qc_ids.clear();
+        self.epoch = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.qc_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.qc_ids.is_empty()
+    }
+}
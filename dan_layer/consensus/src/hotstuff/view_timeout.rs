@@ -0,0 +1,57 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::hotstuff::PacemakerConfig;
+
+/// The maximum fraction by which a view timeout is randomly lengthened or shortened
+pub const VIEW_TIMEOUT_JITTER: f64 = 0.1;
+
+/// Exponential backoff for the leader timeout. Each consecutive failed view multiplies the timeout until the maximum is
+/// reached. The backoff is reset once the view makes progress again.
+#[derive(Debug, Clone)]
+pub struct ViewTimeoutBackoff {
+    config: PacemakerConfig,
+    consecutive_failures: u32,
+}
+
+impl ViewTimeoutBackoff {
+    pub fn new(config: PacemakerConfig) -> Self {
+        Self {
+            config,
+            consecutive_failures: 0,
+        }
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    pub fn on_view_failed(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+
+    pub fn reset(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    /// Returns the timeout for the current view without jitter
+    pub fn timeout(&self) -> Duration {
+        let exp = i32::try_from(self.consecutive_failures).unwrap_or(i32::MAX);
+        let secs = self.config.base_timeout.as_secs_f64() * self.config.timeout_multiplier.powi(exp);
+        if !secs.is_finite() || secs >= self.config.max_timeout.as_secs_f64() {
+            return self.config.max_timeout;
+        }
+        Duration::from_secs_f64(secs)
+    }
+
+    /// Returns the timeout for the current view, randomly adjusted by up to [VIEW_TIMEOUT_JITTER] so that nodes that
+    /// failed the same views do not time out in lockstep
+    pub fn timeout_with_jitter<R: Rng>(&self, rng: &mut R) -> Duration {
+        let factor = rng.gen_range(1.0 - VIEW_TIMEOUT_JITTER..=1.0 + VIEW_TIMEOUT_JITTER);
+        self.timeout().mul_f64(factor)
+    }
+}
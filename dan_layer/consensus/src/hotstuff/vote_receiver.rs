@@ -3,18 +3,32 @@
 
 use log::*;
 use tari_common::configuration::Network;
-use tari_common_types::types::FixedHash;
-use tari_dan_common_types::{committee::CommitteeInfo, optional::Optional};
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_dan_common_types::{committee::CommitteeInfo, optional::Optional, Epoch, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{Block, QuorumCertificate, QuorumDecision, ValidatorSignature, Vote},
+    consensus_models::{
+        Block,
+        BlockId,
+        EquivocationEvidence,
+        QcTiming,
+        QuorumCertificate,
+        QuorumDecision,
+        ValidatorSignature,
+        Vote,
+    },
     StateStore,
 };
 use tari_epoch_manager::EpochManagerReader;
 
 use crate::{
-    hotstuff::{error::HotStuffError, pacemaker_handle::PaceMakerHandle},
+    hotstuff::{
+        error::HotStuffError,
+        pacemaker_handle::PaceMakerHandle,
+        peer_bans::PeerBanTracker,
+        qc_timing_tracker::QcTimingTracker,
+    },
     messages::VoteMessage,
-    traits::{ConsensusSpec, LeaderStrategy, VoteSignatureService},
+    traits::{hooks::ConsensusHooks, ConsensusSpec, LeaderStrategy, VoteSignatureService},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_vote";
@@ -27,11 +41,15 @@ pub struct VoteReceiver<TConsensusSpec: ConsensusSpec> {
     epoch_manager: TConsensusSpec::EpochManager,
     vote_signature_service: TConsensusSpec::SignatureService,
     pacemaker: PaceMakerHandle,
+    qc_timings: QcTimingTracker,
+    hooks: TConsensusSpec::Hooks,
+    peer_bans: PeerBanTracker<TConsensusSpec>,
 }
 
 impl<TConsensusSpec> VoteReceiver<TConsensusSpec>
 where TConsensusSpec: ConsensusSpec
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         network: Network,
         store: TConsensusSpec::StateStore,
@@ -39,6 +57,9 @@ where TConsensusSpec: ConsensusSpec
         epoch_manager: TConsensusSpec::EpochManager,
         vote_signature_service: TConsensusSpec::SignatureService,
         pacemaker: PaceMakerHandle,
+        qc_timings: QcTimingTracker,
+        hooks: TConsensusSpec::Hooks,
+        peer_bans: PeerBanTracker<TConsensusSpec>,
     ) -> Self {
         Self {
             network,
@@ -47,21 +68,30 @@ where TConsensusSpec: ConsensusSpec
             epoch_manager,
             pacemaker,
             vote_signature_service,
+            qc_timings,
+            hooks,
+            peer_bans,
         }
     }
 
     pub async fn handle(
-        &self,
+        &mut self,
         from: TConsensusSpec::Addr,
         message: VoteMessage,
         check_leadership: bool,
     ) -> Result<(), HotStuffError> {
-        match self.handle_vote(from, message, check_leadership).await {
+        match self.handle_vote(from.clone(), message, check_leadership).await {
             Ok(true) => {
                 // If we reached quorum, trigger a check to see if we should propose
                 self.pacemaker.beat();
             },
             Ok(false) => {},
+            Err(
+                err @ (HotStuffError::InvalidVoteSignature { .. } | HotStuffError::RejectingVoteNotSentBySigner { .. }),
+            ) => {
+                warn!(target: LOG_TARGET, "❌ Invalid vote from {}: {}", from, err);
+                self.peer_bans.record_offence(&from, &err)?;
+            },
             Err(err) => {
                 // We dont want bad vote messages to kick us out of running mode
                 warn!(target: LOG_TARGET, "❌ Error handling vote: {}", err);
@@ -73,7 +103,7 @@ where TConsensusSpec: ConsensusSpec
     /// Returns true if quorum is reached
     #[allow(clippy::too_many_lines)]
     pub async fn handle_vote(
-        &self,
+        &mut self,
         from: TConsensusSpec::Addr,
         message: VoteMessage,
         check_leadership: bool,
@@ -116,19 +146,40 @@ where TConsensusSpec: ConsensusSpec
 
         let from = message.signature.public_key.clone();
 
-        let count = self.store.with_write_tx(|tx| {
-            Vote {
+        let quorum_threshold = local_committee_shard.quorum_threshold() as usize;
+        let (count, qc_timing, equivocation) = self.store.with_write_tx(|tx| {
+            let vote = Vote {
                 epoch: message.epoch,
                 block_id: message.block_id,
+                height: message.block_height,
                 decision: message.decision,
                 sender_leaf_hash,
                 signature: message.signature,
-            }
-            .save(tx)?;
+            };
+            let equivocation = self.record_equivocation(tx, &vote)?;
+            let exists = vote.save(tx)?;
 
             let count = Vote::count_for_block(&**tx, &message.block_id)?;
-            Ok::<_, HotStuffError>(count)
+            let qc_timing = if exists {
+                None
+            } else {
+                self.record_vote_timing(
+                    tx,
+                    &message.block_id,
+                    message.epoch,
+                    message.block_height,
+                    &from,
+                    count >= quorum_threshold,
+                )?
+            };
+            Ok::<_, HotStuffError>((count, qc_timing, equivocation))
         })?;
+        if let Some(evidence) = equivocation {
+            warn!(target: LOG_TARGET, "🚨 Recorded {}", evidence);
+        }
+        if let Some(vote) = qc_timing.as_ref().and_then(|t| t.votes.last()) {
+            self.hooks.on_vote_received(message.epoch, vote);
+        }
 
         // We only generate the next high qc once when we have a quorum of votes. Any subsequent votes are not included
         // in the QC.
@@ -142,7 +193,7 @@ where TConsensusSpec: ConsensusSpec
             count,
             local_committee_shard.quorum_threshold()
         );
-        if count < local_committee_shard.quorum_threshold() as usize {
+        if count < quorum_threshold {
             return Ok(false);
         }
 
@@ -158,9 +209,12 @@ where TConsensusSpec: ConsensusSpec
             };
 
             if check_leadership &&
-                !self
-                    .leader_strategy
-                    .is_leader_for_next_block(&our_vn.address, &committee, block.height())
+                !self.leader_strategy.is_leader_for_next_block(
+                    &our_vn.address,
+                    &committee,
+                    block.epoch(),
+                    block.height(),
+                )
             {
                 return Err(HotStuffError::NotTheLeader {
                     details: format!(
@@ -226,6 +280,10 @@ where TConsensusSpec: ConsensusSpec
         let block_height = vote_data.block.height();
         let qc = create_qc(vote_data);
         info!(target: LOG_TARGET, "🔥 New QC {}", qc);
+        if let Some(timing) = qc_timing {
+            debug!(target: LOG_TARGET, "⏱️ {}", timing);
+            self.hooks.on_qc_formed(&timing);
+        }
         let high_qc = self.store.with_write_tx(|tx| qc.update_high_qc(tx))?;
 
         self.pacemaker.update_view(block_height, high_qc.block_height).await?;
@@ -233,6 +291,45 @@ where TConsensusSpec: ConsensusSpec
         Ok(true)
     }
 
+    /// Persists evidence if the sender has already voted for a different block at the same height. Returns the evidence
+    /// if it was recorded. Only the first equivocation of a sender at a height is recorded.
+    fn record_equivocation(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        vote: &Vote,
+    ) -> Result<Option<EquivocationEvidence>, HotStuffError> {
+        let Some(first_vote) = vote.get_conflicting(&**tx)? else {
+            return Ok(None);
+        };
+        if EquivocationEvidence::exists(&**tx, vote.epoch, vote.height, &vote.sender_leaf_hash)? {
+            return Ok(None);
+        }
+
+        let evidence = EquivocationEvidence::new(first_vote, vote.clone());
+        evidence.insert(tx)?;
+        Ok(Some(evidence))
+    }
+
+    /// Records the arrival time of a new vote and persists the updated timing summary for the block
+    fn record_vote_timing(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        block_id: &BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        voter: &PublicKey,
+        is_quorum_reached: bool,
+    ) -> Result<Option<QcTiming>, HotStuffError> {
+        let Some(timing) = self
+            .qc_timings
+            .on_vote_received(*block_id, epoch, height, voter, is_quorum_reached)
+        else {
+            return Ok(None);
+        };
+        timing.upsert(tx)?;
+        Ok(Some(timing))
+    }
+
     fn calculate_threshold_decision(votes: &[Vote], local_committee_info: &CommitteeInfo) -> Option<QuorumDecision> {
         let mut count_accept = 0;
         let mut count_reject = 0;
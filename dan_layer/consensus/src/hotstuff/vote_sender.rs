@@ -0,0 +1,177 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tokio::{
+    task,
+    task::JoinHandle,
+    time::{self, Instant},
+};
+
+use crate::{
+    hotstuff::{pacemaker_handle::PaceMakerHandle, VoteRetryConfig},
+    messages::{HotstuffMessage, VoteMessage},
+    traits::{hooks::ConsensusHooks, ConsensusSpec, OutboundMessaging},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::vote_sender";
+
+/// Sends votes to the leader of the next block. If a vote cannot be sent, sending is retried in the background with
+/// exponential backoff until the vote is sent, the retries are exhausted, the leader timeout of the view elapses or the
+/// view advances past the voted block.
+pub struct VoteSender<TConsensusSpec: ConsensusSpec> {
+    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    pacemaker: PaceMakerHandle,
+    hooks: TConsensusSpec::Hooks,
+    config: VoteRetryConfig,
+    retry_task: Option<JoinHandle<()>>,
+}
+
+impl<TConsensusSpec> VoteSender<TConsensusSpec>
+where TConsensusSpec: ConsensusSpec
+{
+    pub fn new(
+        outbound_messaging: TConsensusSpec::OutboundMessaging,
+        pacemaker: PaceMakerHandle,
+        hooks: TConsensusSpec::Hooks,
+        config: VoteRetryConfig,
+    ) -> Self {
+        Self {
+            outbound_messaging,
+            pacemaker,
+            hooks,
+            config,
+            retry_task: None,
+        }
+    }
+
+    /// Sends the vote to the leader. Any vote that is still being retried is abandoned. If sending fails, the vote is
+    /// retried in the background, so the failure is not returned.
+    pub async fn send(&mut self, leader: TConsensusSpec::Addr, vote: VoteMessage) {
+        self.cancel_retries();
+
+        let err = match self
+            .outbound_messaging
+            .send(leader.clone(), HotstuffMessage::Vote(vote.clone()))
+            .await
+        {
+            Ok(()) => return,
+            Err(err) => err,
+        };
+
+        if self.config.max_retries == 0 {
+            warn!(
+                target: LOG_TARGET,
+                "❌ Failed to send vote for block {} to leader {}: {}",
+                vote.block_id,
+                leader,
+                err
+            );
+            return;
+        }
+
+        warn!(
+            target: LOG_TARGET,
+            "⚠️ Failed to send vote for block {} to leader {}: {}. Retrying up to {} time(s)",
+            vote.block_id,
+            leader,
+            err,
+            self.config.max_retries
+        );
+        let deadline = Instant::now() + self.pacemaker.current_timeout();
+        self.retry_task = Some(task::spawn(retry_send_vote::<TConsensusSpec>(
+            self.outbound_messaging.clone(),
+            self.pacemaker.clone(),
+            self.hooks.clone(),
+            self.config.clone(),
+            leader,
+            vote,
+            deadline,
+        )));
+    }
+
+    pub fn cancel_retries(&mut self) {
+        if let Some(retry_task) = self.retry_task.take() {
+            retry_task.abort();
+        }
+    }
+}
+
+impl<TConsensusSpec: ConsensusSpec> Drop for VoteSender<TConsensusSpec> {
+    fn drop(&mut self) {
+        self.cancel_retries();
+    }
+}
+
+async fn retry_send_vote<TConsensusSpec: ConsensusSpec>(
+    mut outbound_messaging: TConsensusSpec::OutboundMessaging,
+    pacemaker: PaceMakerHandle,
+    mut hooks: TConsensusSpec::Hooks,
+    config: VoteRetryConfig,
+    leader: TConsensusSpec::Addr,
+    vote: VoteMessage,
+    deadline: Instant,
+) {
+    let mut backoff = config.initial_backoff;
+    for attempt in 1..=config.max_retries {
+        if Instant::now() + backoff > deadline {
+            warn!(
+                target: LOG_TARGET,
+                "❌ Giving up sending vote for block {} to leader {} because the view times out before the next retry",
+                vote.block_id,
+                leader,
+            );
+            return;
+        }
+        time::sleep(backoff).await;
+        backoff *= 2;
+
+        // The leader has no use for the vote once this node has moved on to a later view
+        let current_height = pacemaker.current_height();
+        if current_height > vote.block_height {
+            debug!(
+                target: LOG_TARGET,
+                "Not retrying vote for block {} because the view advanced to height {}",
+                vote.block_id,
+                current_height,
+            );
+            return;
+        }
+
+        match outbound_messaging
+            .send(leader.clone(), HotstuffMessage::Vote(vote.clone()))
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    target: LOG_TARGET,
+                    "🔁 Sent vote for block {} to leader {} on retry {}",
+                    vote.block_id,
+                    leader,
+                    attempt
+                );
+                hooks.on_vote_send_retried(true);
+                return;
+            },
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Retry {} of sending vote for block {} to leader {} failed: {}",
+                    attempt,
+                    vote.block_id,
+                    leader,
+                    err
+                );
+                hooks.on_vote_send_retried(false);
+            },
+        }
+    }
+
+    warn!(
+        target: LOG_TARGET,
+        "❌ Giving up sending vote for block {} to leader {} after {} retries",
+        vote.block_id,
+        leader,
+        config.max_retries
+    );
+}
@@ -4,16 +4,18 @@
 use std::{
     cmp,
     fmt::{Debug, Formatter},
+    time::Duration,
 };
 
 use log::*;
 use tari_common::configuration::Network;
-use tari_dan_common_types::NodeHeight;
+use tari_dan_common_types::{committee::Committee, Epoch, NodeHeight};
 use tari_dan_storage::{
     consensus_models::{
         Block,
         BlockDiff,
         ExecutedTransaction,
+        GenesisConfig,
         HighQc,
         LastVoted,
         LeafBlock,
@@ -27,7 +29,10 @@ use tari_dan_storage::{
 use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
 use tari_shutdown::ShutdownSignal;
 use tari_transaction::{Transaction, TransactionId};
-use tokio::sync::{broadcast, mpsc};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 
 use super::{
     config::HotstuffConfig,
@@ -36,8 +41,11 @@ use super::{
 };
 use crate::{
     hotstuff::{
+        consensus_status::ConsensusStatusHandle,
         error::HotStuffError,
         event::HotstuffEvent,
+        foreign_proposal_outbox::ForeignProposalOutbox,
+        maintenance_mode::MaintenanceMode,
         on_inbound_message::{IncomingMessageResult, NeedsSync, OnInboundMessage},
         on_next_sync_view::OnNextSyncViewHandler,
         on_propose::OnPropose,
@@ -49,17 +57,23 @@ use crate::{
         on_sync_request::{OnSyncRequest, MAX_BLOCKS_PER_SYNC},
         pacemaker::PaceMaker,
         pacemaker_handle::PaceMakerHandle,
+        peer_bans::PeerBanTracker,
+        qc_timing_tracker::QcTimingTracker,
         vote_receiver::VoteReceiver,
     },
+    journal::ConsensusJournal,
     messages::{HotstuffMessage, SyncRequestMessage},
     traits::{hooks::ConsensusHooks, ConsensusSpec, InboundMessaging, LeaderStrategy, OutboundMessaging},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::worker";
+const FOREIGN_PROPOSAL_OUTBOX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const MISSING_TRANSACTIONS_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct HotstuffWorker<TConsensusSpec: ConsensusSpec> {
     validator_addr: TConsensusSpec::Addr,
     network: Network,
+    genesis: GenesisConfig,
     hooks: TConsensusSpec::Hooks,
 
     tx_events: broadcast::Sender<HotstuffEvent>,
@@ -77,14 +91,16 @@ pub struct HotstuffWorker<TConsensusSpec: ConsensusSpec> {
     on_receive_requested_txs: OnReceiveRequestedTransactions<TConsensusSpec>,
     on_propose: OnPropose<TConsensusSpec>,
     on_sync_request: OnSyncRequest<TConsensusSpec>,
+    foreign_proposal_outbox: ForeignProposalOutbox<TConsensusSpec>,
 
     state_store: TConsensusSpec::StateStore,
     leader_strategy: TConsensusSpec::LeaderStrategy,
     transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
 
     epoch_manager: TConsensusSpec::EpochManager,
-    pacemaker_worker: Option<PaceMaker>,
+    pacemaker_worker: Option<PaceMaker<TConsensusSpec::Clock>>,
     pacemaker: PaceMakerHandle,
+    maintenance_mode: MaintenanceMode,
     shutdown: ShutdownSignal,
 }
 impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
@@ -104,10 +120,22 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         tx_events: broadcast::Sender<HotstuffEvent>,
         tx_mempool: mpsc::UnboundedSender<Transaction>,
         hooks: TConsensusSpec::Hooks,
+        clock: TConsensusSpec::Clock,
+        maintenance_mode: MaintenanceMode,
         shutdown: ShutdownSignal,
         config: HotstuffConfig,
     ) -> Self {
-        let pacemaker = PaceMaker::new();
+        let pacemaker = PaceMaker::new(config.pacemaker.clone(), clock.clone());
+        let qc_timings = QcTimingTracker::new();
+        let journal = ConsensusJournal::new(config.journal.clone());
+        let committed_block_diff_retention = config.committed_block_diff_retention;
+        let foreign_proposal_timeout = config.foreign_proposal_timeout;
+        let max_dummy_blocks = config.max_dummy_blocks;
+        let block_rejection_retention_epochs = config.block_rejection_retention_epochs;
+        let foreign_proposal_retention_epochs = config.foreign_proposal_retention_epochs;
+        let reject_state_merkle_root_mismatch = config.reject_state_merkle_root_mismatch;
+        let vote_retry = config.vote_retry.clone();
+        let peer_bans = PeerBanTracker::new(state_store.clone(), clock.clone(), config.peer_bans.clone());
         let vote_receiver = VoteReceiver::new(
             network,
             state_store.clone(),
@@ -115,12 +143,25 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             epoch_manager.clone(),
             signing_service.clone(),
             pacemaker.clone_handle(),
+            qc_timings.clone(),
+            hooks.clone(),
+            peer_bans.clone(),
+        );
+        let foreign_proposal_outbox = ForeignProposalOutbox::new(
+            state_store.clone(),
+            epoch_manager.clone(),
+            outbound_messaging.clone(),
+            clock.clone(),
+        );
+        let proposer = Proposer::<TConsensusSpec>::new(
+            state_store.clone(),
+            epoch_manager.clone(),
+            foreign_proposal_outbox.clone(),
         );
-        let proposer =
-            Proposer::<TConsensusSpec>::new(state_store.clone(), epoch_manager.clone(), outbound_messaging.clone());
         Self {
             validator_addr: validator_addr.clone(),
             network,
+            genesis: config.genesis,
             tx_events: tx_events.clone(),
             outbound_messaging: outbound_messaging.clone(),
             inbound_messaging,
@@ -128,13 +169,17 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
 
             on_inbound_message: OnInboundMessage::new(
                 network,
-                config,
+                config.clone(),
                 state_store.clone(),
                 epoch_manager.clone(),
                 leader_strategy.clone(),
                 signing_service.clone(),
                 outbound_messaging.clone(),
                 transaction_pool.clone(),
+                tx_events.clone(),
+                hooks.clone(),
+                clock.clone(),
+                peer_bans.clone(),
             ),
 
             on_next_sync_view: OnNextSyncViewHandler::new(
@@ -152,17 +197,31 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 outbound_messaging.clone(),
                 signing_service.clone(),
                 transaction_pool.clone(),
-                tx_events,
+                tx_events.clone(),
                 proposer.clone(),
                 transaction_executor.clone(),
                 network,
                 hooks.clone(),
+                clock.clone(),
+                qc_timings,
+                maintenance_mode.clone(),
+                journal,
+                committed_block_diff_retention,
+                foreign_proposal_timeout,
+                max_dummy_blocks,
+                block_rejection_retention_epochs,
+                foreign_proposal_retention_epochs,
+                reject_state_merkle_root_mismatch,
+                peer_bans,
+                vote_retry,
             ),
             on_receive_foreign_proposal: OnReceiveForeignProposalHandler::new(
                 state_store.clone(),
                 epoch_manager.clone(),
                 transaction_pool.clone(),
                 pacemaker.clone_handle(),
+                outbound_messaging.clone(),
+                tx_events,
             ),
             on_receive_vote: OnReceiveVoteHandler::new(vote_receiver.clone()),
             on_receive_new_view: OnReceiveNewViewHandler::new(
@@ -186,9 +245,13 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 transaction_executor,
                 signing_service,
                 outbound_messaging.clone(),
+                clock,
+                config,
+                hooks.clone(),
             ),
 
             on_sync_request: OnSyncRequest::new(state_store.clone(), outbound_messaging),
+            foreign_proposal_outbox,
 
             state_store,
             leader_strategy,
@@ -197,18 +260,26 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
 
             pacemaker: pacemaker.clone_handle(),
             pacemaker_worker: Some(pacemaker),
+            maintenance_mode,
             hooks,
             shutdown,
         }
     }
 
+    /// Returns a handle that assembles a snapshot of the consensus status of this node on request
+    pub fn status_handle(&self) -> ConsensusStatusHandle<TConsensusSpec> {
+        ConsensusStatusHandle::new(
+            self.validator_addr.clone(),
+            self.state_store.clone(),
+            self.epoch_manager.clone(),
+            self.leader_strategy.clone(),
+            self.pacemaker.clone(),
+        )
+    }
+
     pub async fn start(&mut self) -> Result<(), HotStuffError> {
         self.create_zero_block_if_required()?;
-        let (current_height, high_qc) = self.state_store.with_read_tx(|tx| {
-            let leaf = LeafBlock::get(tx)?;
-            let last_voted = LastVoted::get(tx)?;
-            Ok::<_, HotStuffError>((cmp::max(leaf.height(), last_voted.height()), HighQc::get(tx)?))
-        })?;
+        let (current_height, high_qc) = self.get_current_height_and_high_qc()?;
         info!(
             target: LOG_TARGET,
             "🚀 Pacemaker starting leaf_block: {}, high_qc: {}",
@@ -217,11 +288,21 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         );
 
         self.pacemaker.start(current_height, high_qc.block_height()).await?;
+        self.hooks
+            .on_maintenance_mode_changed(self.maintenance_mode.is_enabled());
 
         self.run().await?;
         Ok(())
     }
 
+    fn get_current_height_and_high_qc(&self) -> Result<(NodeHeight, HighQc), HotStuffError> {
+        self.state_store.with_read_tx(|tx| {
+            let leaf = LeafBlock::get(tx)?;
+            let last_voted = LastVoted::get(tx)?;
+            Ok::<_, HotStuffError>((cmp::max(leaf.height(), last_voted.height()), HighQc::get(tx)?))
+        })
+    }
+
     async fn run(&mut self) -> Result<(), HotStuffError> {
         // Spawn pacemaker if not spawned already
         if let Some(pm) = self.pacemaker_worker.take() {
@@ -237,6 +318,8 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         self.request_initial_catch_up_sync().await?;
 
         let mut prev_height = self.pacemaker.current_height();
+        let mut outbox_retry_interval = time::interval(FOREIGN_PROPOSAL_OUTBOX_RETRY_INTERVAL);
+        let mut missing_transactions_retry_interval = time::interval(MISSING_TRANSACTIONS_RETRY_INTERVAL);
         loop {
             let current_height = self.pacemaker.current_height() + NodeHeight(1);
 
@@ -279,6 +362,20 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     self.handle_epoch_manager_event(event).await?;
                 },
 
+                _ = outbox_retry_interval.tick() => {
+                    if let Err(err) = self.foreign_proposal_outbox.on_tick().await {
+                        self.hooks.on_error(&err);
+                        error!(target: LOG_TARGET, "Error retrying foreign proposals: {}", err);
+                    }
+                },
+
+                _ = missing_transactions_retry_interval.tick() => {
+                    if let Err(err) = self.on_inbound_message.retry_missing_transactions_requests(current_height).await {
+                        self.hooks.on_error(&err);
+                        error!(target: LOG_TARGET, "Error requesting missing transactions: {}", err);
+                    }
+                },
+
                 _ = on_beat.wait() => {
                     if let Err(e) = self.on_beat().await {
                         self.on_failure("on_beat", &e).await;
@@ -294,13 +391,20 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     }
                 },
 
-                new_height = on_leader_timeout.wait() => {
-                    if let Err(e) = self.on_leader_timeout(new_height).await {
+                timeout = on_leader_timeout.wait() => {
+                    if let Err(e) = self.on_leader_timeout(timeout.new_height, timeout.round).await {
                         self.on_failure("on_leader_timeout", &e).await;
                         return Err(e);
                     }
                 },
 
+                is_enabled = self.maintenance_mode.changed() => {
+                    if let Err(e) = self.on_maintenance_mode_changed(is_enabled).await {
+                        self.on_failure("on_maintenance_mode_changed", &e).await;
+                        return Err(e);
+                    }
+                },
+
                 _ = self.shutdown.wait() => {
                     info!(target: LOG_TARGET, "💤 Shutting down");
                     break;
@@ -308,7 +412,6 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             }
         }
 
-        self.on_receive_new_view.clear_new_views();
         self.on_inbound_message.clear_buffer();
         // This only happens if we're shutting down.
         if let Err(err) = self.pacemaker.stop().await {
@@ -333,11 +436,11 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             if transaction.is_executed() {
                 // This should never fail
                 let executed = ExecutedTransaction::try_from(transaction)?;
-                self.transaction_pool.insert(tx, executed.to_atom())?;
+                self.transaction_pool.insert(tx, executed.to_atom(), current_height)?;
             } else {
                 // Deferred execution
                 self.transaction_pool
-                    .insert(tx, TransactionAtom::deferred(*transaction.id()))?;
+                    .insert(tx, TransactionAtom::deferred(*transaction.id()), current_height)?;
             }
             Ok::<_, HotStuffError>(false)
         })?;
@@ -382,6 +485,15 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     return Err(HotStuffError::NotRegisteredForCurrentEpoch { epoch });
                 }
 
+                // QCs that were verified in the previous epoch are verified again against the new committees
+                self.on_inbound_message.clear_verified_qc_cache();
+
+                let local_committee = self.epoch_manager.get_local_committee(epoch).await?;
+                self.publish_event(HotstuffEvent::EpochChanged {
+                    epoch,
+                    committee_size: local_committee.len(),
+                });
+
                 // TODO: This is breaking my testing right now (division by zero, from time to time)
                 // Send the last vote to the leader at the next epoch so that they can justify the current tip.
                 // if let Some(last_voted) = self.state_store.with_read_tx(|tx| LastSentVote::get(tx)).optional()? {
@@ -427,7 +539,6 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         if let Err(e) = self.pacemaker.stop().await {
             error!(target: LOG_TARGET, "Error while stopping pacemaker: {}", e);
         }
-        self.on_receive_new_view.clear_new_views();
         self.on_inbound_message.clear_buffer();
     }
 
@@ -446,13 +557,42 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         }
     }
 
-    async fn on_leader_timeout(&mut self, new_height: NodeHeight) -> Result<(), HotStuffError> {
-        self.hooks.on_leader_timeout(new_height);
-        self.on_next_sync_view.handle(new_height).await?;
+    async fn on_leader_timeout(&mut self, new_height: NodeHeight, round: u32) -> Result<(), HotStuffError> {
+        self.hooks.on_leader_timeout(new_height, round);
+        if self.maintenance_mode.is_enabled() {
+            debug!(
+                target: LOG_TARGET,
+                "🔧 [on_leader_timeout] In maintenance mode. Not sending NEWVIEW for height {}", new_height
+            );
+        } else {
+            self.on_next_sync_view.handle(new_height).await?;
+        }
         self.publish_event(HotstuffEvent::LeaderTimeout { new_height });
         Ok(())
     }
 
+    async fn on_maintenance_mode_changed(&mut self, is_enabled: bool) -> Result<(), HotStuffError> {
+        self.hooks.on_maintenance_mode_changed(is_enabled);
+        if is_enabled {
+            info!(target: LOG_TARGET, "🔧 Entered maintenance mode. No longer proposing or voting.");
+            return Ok(());
+        }
+
+        // Blocks have been processed while in maintenance mode, so we pick up the view from the current leaf
+        let (current_height, high_qc) = self.get_current_height_and_high_qc()?;
+        info!(
+            target: LOG_TARGET,
+            "🔧 Resuming from maintenance mode. leaf_block: {}, high_qc: {}",
+            current_height,
+            high_qc
+        );
+        self.pacemaker
+            .update_view(current_height, high_qc.block_height())
+            .await?;
+        self.pacemaker.beat();
+        Ok(())
+    }
+
     async fn on_beat(&mut self) -> Result<(), HotStuffError> {
         self.hooks.on_beat();
         if !self
@@ -469,25 +609,24 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
     }
 
     async fn propose_if_leader(&mut self, leaf_block: Option<LeafBlock>) -> Result<(), HotStuffError> {
+        if self.maintenance_mode.is_enabled() {
+            debug!(target: LOG_TARGET, "🔧 [on_beat] In maintenance mode. Not proposing.");
+            return Ok(());
+        }
+
         let is_newview_propose = leaf_block.is_some();
         let leaf_block = match leaf_block {
             Some(leaf_block) => leaf_block,
             None => self.state_store.with_read_tx(|tx| LeafBlock::get(tx))?,
         };
-        let locked_block = self
-            .state_store
-            .with_read_tx(|tx| LockedBlock::get(tx)?.get_block(tx))?;
-        let current_epoch = self.epoch_manager.current_epoch().await?;
-        let epoch = if locked_block.is_epoch_end() || locked_block.is_genesis() {
-            current_epoch
-        } else {
-            locked_block.epoch()
-        };
-        let local_committee = self.epoch_manager.get_local_committee(epoch).await?;
+        let (current_epoch, epoch, local_committee) = self.get_proposing_committee().await?;
 
-        let is_leader =
-            self.leader_strategy
-                .is_leader_for_next_block(&self.validator_addr, &local_committee, leaf_block.height);
+        let is_leader = self.leader_strategy.is_leader_for_next_block(
+            &self.validator_addr,
+            &local_committee,
+            epoch,
+            leaf_block.height,
+        );
         info!(
             target: LOG_TARGET,
             "🔥 [on_beat{}] {} Is leader: {:?}, leaf_block: {}, local_committee: {}",
@@ -511,6 +650,42 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         Ok(())
     }
 
+    /// Starts preparing the next proposal in the background if this node is the leader of the block after the current
+    /// leaf block
+    async fn prepare_next_proposal_if_leader(&mut self) -> Result<(), HotStuffError> {
+        if self.maintenance_mode.is_enabled() {
+            return Ok(());
+        }
+
+        let leaf_block = self.state_store.with_read_tx(|tx| LeafBlock::get(tx))?;
+        let (current_epoch, epoch, local_committee) = self.get_proposing_committee().await?;
+        if self.leader_strategy.is_leader_for_next_block(
+            &self.validator_addr,
+            &local_committee,
+            epoch,
+            leaf_block.height,
+        ) {
+            self.on_propose.prepare_next_proposal(current_epoch, leaf_block)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current epoch, the epoch of the next block and the committee that proposes it. The committee of the
+    /// locked block's epoch proposes until that epoch has ended.
+    async fn get_proposing_committee(&self) -> Result<(Epoch, Epoch, Committee<TConsensusSpec::Addr>), HotStuffError> {
+        let locked_block = self
+            .state_store
+            .with_read_tx(|tx| LockedBlock::get(tx)?.get_block(tx))?;
+        let current_epoch = self.epoch_manager.current_epoch().await?;
+        let epoch = if locked_block.is_epoch_end() || locked_block.is_genesis() {
+            current_epoch
+        } else {
+            locked_block.epoch()
+        };
+        let local_committee = self.epoch_manager.get_local_committee(epoch).await?;
+        Ok((current_epoch, epoch, local_committee))
+    }
+
     async fn dispatch_hotstuff_message(
         &mut self,
         result: IncomingMessageResult<TConsensusSpec::Addr>,
@@ -561,14 +736,21 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 "on_receive_new_view",
                 self.on_receive_new_view.handle(from, message).await,
             ),
-            HotstuffMessage::Proposal(msg) => log_err(
-                "on_receive_local_proposal",
-                self.on_receive_local_proposal.handle(msg).await,
-            ),
+            HotstuffMessage::Proposal(msg) => {
+                log_err(
+                    "on_receive_local_proposal",
+                    self.on_receive_local_proposal.handle(msg).await,
+                )?;
+                log_err("prepare_next_proposal", self.prepare_next_proposal_if_leader().await)
+            },
             HotstuffMessage::ForeignProposal(msg) => log_err(
                 "on_receive_foreign_proposal",
                 self.on_receive_foreign_proposal.handle(from, msg).await,
             ),
+            HotstuffMessage::ForeignProposalAck(msg) => log_err(
+                "on_receive_foreign_proposal_ack",
+                self.foreign_proposal_outbox.on_ack(from, msg).await,
+            ),
             HotstuffMessage::Vote(msg) => log_err("on_receive_vote", self.on_receive_vote.handle(from, msg).await),
             HotstuffMessage::RequestMissingTransactions(msg) => log_err(
                 "on_receive_request_missing_transactions",
@@ -589,6 +771,8 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 );
                 Ok(())
             },
+            // Equivocation evidence is handled when it is received and is never queued for consensus
+            HotstuffMessage::ProposerEquivocation(_) => Ok(()),
         }
     }
 
@@ -626,7 +810,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
     fn create_zero_block_if_required(&self) -> Result<(), HotStuffError> {
         self.state_store.with_write_tx(|tx| {
             // The parent for genesis blocks refer to this zero block
-            let zero_block = Block::zero_block(self.network);
+            let zero_block = Block::zero_block_with_genesis(self.network, &self.genesis);
             if !zero_block.exists(&**tx)? {
                 debug!(target: LOG_TARGET, "Creating zero block");
                 zero_block.justify().insert(tx)?;
@@ -655,6 +839,7 @@ impl<TConsensusSpec: ConsensusSpec> Debug for HotstuffWorker<TConsensusSpec> {
             .field("epoch_manager", &"EpochManager")
             .field("pacemaker_handle", &self.pacemaker)
             .field("pacemaker", &"Pacemaker")
+            .field("maintenance_mode", &self.maintenance_mode)
             .field("shutdown", &self.shutdown)
             .finish()
     }
@@ -0,0 +1,20 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::io;
+
+use tari_bor::BorError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] BorError),
+    #[error("Journal record at offset {offset} is truncated. Expected {expected} bytes but {remaining} remain")]
+    TruncatedRecord {
+        offset: u64,
+        expected: usize,
+        remaining: usize,
+    },
+}
@@ -0,0 +1,41 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! An append-only journal of the consensus decisions made by the local node, kept for post-mortem analysis. The state
+//! store only holds the outcome of consensus, whereas the journal records the sequence of decisions that led to it.
+//!
+//! Each record is a CBOR encoded [JournalRecord] prefixed with its length as a little-endian u32. The current journal
+//! file is `consensus.journal`, rotated files are `consensus.journal.1` (newest) to `consensus.journal.N` (oldest).
+
+mod error;
+mod reader;
+mod record;
+mod writer;
+
+use std::path::{Path, PathBuf};
+
+pub use error::JournalError;
+pub use reader::{journal_files, read_journal, JournalReader};
+pub use record::{JournalEntry, JournalRecord, JournalTransaction, VoteBasis};
+pub use writer::{ConsensusJournal, JournalWriter};
+
+const JOURNAL_FILE_NAME: &str = "consensus.journal";
+
+#[derive(Debug, Clone)]
+pub struct JournalConfig {
+    /// The directory that the journal files are written to
+    pub directory: PathBuf,
+    /// The journal file is rotated when appending a record would take it over this size in bytes
+    pub max_file_size: u64,
+    /// The number of rotated journal files to keep. The oldest file is deleted when this is exceeded.
+    pub max_rotated_files: usize,
+}
+
+/// Returns the path of the journal file with the given rotation index, where 0 is the current journal file
+pub fn journal_file_path<P: AsRef<Path>>(directory: P, index: usize) -> PathBuf {
+    if index == 0 {
+        directory.as_ref().join(JOURNAL_FILE_NAME)
+    } else {
+        directory.as_ref().join(format!("{}.{}", JOURNAL_FILE_NAME, index))
+    }
+}
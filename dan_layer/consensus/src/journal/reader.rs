@@ -0,0 +1,105 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fs::File,
+    io,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::journal::{journal_file_path, JournalError, JournalRecord};
+
+/// Reads the records of a single journal file in the order that they were written
+pub struct JournalReader<R> {
+    reader: R,
+    offset: u64,
+}
+
+impl JournalReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, JournalError> {
+        let file = File::open(path)?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: Read> JournalReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Reads the next record. Returns None at the end of the journal.
+    pub fn read_record(&mut self) -> Result<Option<JournalRecord>, JournalError> {
+        let mut len_bytes = [0u8; 4];
+        let n = read_fully(&mut self.reader, &mut len_bytes)?;
+        if n == 0 {
+            return Ok(None);
+        }
+        if n < len_bytes.len() {
+            return Err(JournalError::TruncatedRecord {
+                offset: self.offset,
+                expected: len_bytes.len(),
+                remaining: n,
+            });
+        }
+
+        let len = tari_bor::decode_len(&len_bytes)?;
+        let mut buf = vec![0u8; len];
+        let n = read_fully(&mut self.reader, &mut buf)?;
+        if n < len {
+            return Err(JournalError::TruncatedRecord {
+                offset: self.offset,
+                expected: len,
+                remaining: n,
+            });
+        }
+
+        self.offset += (len_bytes.len() + len) as u64;
+        let record = tari_bor::decode_exact(&buf)?;
+        Ok(Some(record))
+    }
+}
+
+impl<R: Read> Iterator for JournalReader<R> {
+    type Item = Result<JournalRecord, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Returns the journal files in the directory from oldest to newest
+pub fn journal_files<P: AsRef<Path>>(directory: P) -> Vec<PathBuf> {
+    let directory = directory.as_ref();
+    let mut files = (0..)
+        .map(|index| journal_file_path(directory, index))
+        .take_while(|path| path.exists())
+        .collect::<Vec<_>>();
+    files.reverse();
+    files
+}
+
+/// Reads every record in the journal directory, including rotated files, from oldest to newest
+pub fn read_journal<P: AsRef<Path>>(directory: P) -> Result<Vec<JournalRecord>, JournalError> {
+    let mut records = Vec::new();
+    for path in journal_files(directory) {
+        for record in JournalReader::open(path)? {
+            records.push(record?);
+        }
+    }
+    Ok(records)
+}
+
+/// Reads until the buffer is full or the end of the reader is reached, returning the number of bytes read
+fn read_fully<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(total)
+}
@@ -0,0 +1,258 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, Decision, QuorumDecision, TransactionAtom};
+use tari_transaction::TransactionId;
+
+/// A single entry in the consensus journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    /// Unix timestamp in milliseconds of when the record was written
+    pub timestamp: u64,
+    pub entry: JournalEntry,
+}
+
+impl JournalRecord {
+    pub fn new(entry: JournalEntry) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        Self { timestamp, entry }
+    }
+}
+
+impl Display for JournalRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.timestamp, self.entry)
+    }
+}
+
+/// A consensus decision made by the local node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalEntry {
+    /// A local proposal passed validation and was persisted
+    ProposalAccepted {
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: String,
+        justify_block_id: BlockId,
+        justify_height: NodeHeight,
+    },
+    /// A local proposal failed validation
+    ProposalRejected {
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        reason: String,
+    },
+    /// A vote was sent for a block
+    VoteCast {
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        decision: QuorumDecision,
+        basis: VoteBasis,
+    },
+    /// The node decided not to vote for a valid block
+    VoteWithheld {
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        reason: String,
+    },
+    /// The locked block moved up the chain
+    LockAdvanced {
+        block_id: BlockId,
+        height: NodeHeight,
+        previous_block_id: BlockId,
+        previous_height: NodeHeight,
+    },
+    /// A block was committed along with the transactions that it finalized
+    BlockCommitted {
+        block_id: BlockId,
+        epoch: Epoch,
+        height: NodeHeight,
+        transactions: Vec<JournalTransaction>,
+    },
+    /// The leaf block moved to a block that does not extend the previous leaf block
+    ForkSwitched {
+        from_block_id: BlockId,
+        from_height: NodeHeight,
+        to_block_id: BlockId,
+        to_height: NodeHeight,
+    },
+}
+
+impl JournalEntry {
+    /// Returns true if the entry refers to the given block
+    pub fn involves_block(&self, id: &BlockId) -> bool {
+        match self {
+            Self::ProposalAccepted {
+                block_id,
+                justify_block_id,
+                ..
+            } => block_id == id || justify_block_id == id,
+            Self::ProposalRejected { block_id, .. } |
+            Self::VoteWithheld { block_id, .. } |
+            Self::BlockCommitted { block_id, .. } => block_id == id,
+            Self::VoteCast { block_id, basis, .. } => block_id == id || basis.justify_block_id == *id,
+            Self::LockAdvanced {
+                block_id,
+                previous_block_id,
+                ..
+            } => block_id == id || previous_block_id == id,
+            Self::ForkSwitched {
+                from_block_id,
+                to_block_id,
+                ..
+            } => from_block_id == id || to_block_id == id,
+        }
+    }
+
+    /// Returns true if the entry refers to the given transaction
+    pub fn involves_transaction(&self, id: &TransactionId) -> bool {
+        match self {
+            Self::VoteCast { basis, .. } => basis.transactions.iter().any(|t| t.id == *id),
+            Self::BlockCommitted { transactions, .. } => transactions.iter().any(|t| t.id == *id),
+            _ => false,
+        }
+    }
+
+    /// The name of the entry, used to compare decision sequences
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ProposalAccepted { .. } => "ProposalAccepted",
+            Self::ProposalRejected { .. } => "ProposalRejected",
+            Self::VoteCast { .. } => "VoteCast",
+            Self::VoteWithheld { .. } => "VoteWithheld",
+            Self::LockAdvanced { .. } => "LockAdvanced",
+            Self::BlockCommitted { .. } => "BlockCommitted",
+            Self::ForkSwitched { .. } => "ForkSwitched",
+        }
+    }
+}
+
+impl Display for JournalEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ProposalAccepted {
+                block_id,
+                epoch,
+                height,
+                proposed_by,
+                justify_block_id,
+                justify_height,
+            } => write!(
+                f,
+                "PROPOSAL ACCEPTED {} {} {} proposed by {} justify {} {}",
+                epoch, height, block_id, proposed_by, justify_height, justify_block_id
+            ),
+            Self::ProposalRejected {
+                block_id,
+                epoch,
+                height,
+                reason,
+            } => write!(f, "PROPOSAL REJECTED {} {} {}: {}", epoch, height, block_id, reason),
+            Self::VoteCast {
+                block_id,
+                epoch,
+                height,
+                decision,
+                basis,
+            } => write!(f, "VOTE {:?} {} {} {} ({})", decision, epoch, height, block_id, basis),
+            Self::VoteWithheld {
+                block_id,
+                epoch,
+                height,
+                reason,
+            } => write!(f, "NO VOTE {} {} {}: {}", epoch, height, block_id, reason),
+            Self::LockAdvanced {
+                block_id,
+                height,
+                previous_block_id,
+                previous_height,
+            } => write!(
+                f,
+                "LOCKED {} {} (previous {} {})",
+                height, block_id, previous_height, previous_block_id
+            ),
+            Self::BlockCommitted {
+                block_id,
+                epoch,
+                height,
+                transactions,
+            } => {
+                write!(f, "COMMITTED {} {} {}", epoch, height, block_id)?;
+                for transaction in transactions {
+                    write!(f, "\n    {}", transaction)?;
+                }
+                Ok(())
+            },
+            Self::ForkSwitched {
+                from_block_id,
+                from_height,
+                to_block_id,
+                to_height,
+            } => write!(
+                f,
+                "FORK SWITCHED from {} {} to {} {}",
+                from_height, from_block_id, to_height, to_block_id
+            ),
+        }
+    }
+}
+
+/// The state that a vote was decided on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteBasis {
+    /// The block certified by the QC that justifies the voted block
+    pub justify_block_id: BlockId,
+    pub justify_height: NodeHeight,
+    /// The height of the locked block after the voted block was processed
+    pub locked_height: NodeHeight,
+    /// The transaction decisions contained in the voted block
+    pub transactions: Vec<JournalTransaction>,
+}
+
+impl Display for VoteBasis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "justify: {} {}, locked: {}, transactions: {}",
+            self.justify_height,
+            self.justify_block_id,
+            self.locked_height,
+            self.transactions.len()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalTransaction {
+    pub id: TransactionId,
+    pub decision: Decision,
+}
+
+impl From<&TransactionAtom> for JournalTransaction {
+    fn from(atom: &TransactionAtom) -> Self {
+        Self {
+            id: atom.id,
+            decision: atom.decision,
+        }
+    }
+}
+
+impl Display for JournalTransaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.id, self.decision)
+    }
+}
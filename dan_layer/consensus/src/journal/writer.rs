@@ -0,0 +1,117 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fs,
+    fs::{File, OpenOptions},
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use log::*;
+
+use crate::journal::{journal_file_path, JournalConfig, JournalEntry, JournalError, JournalRecord};
+
+const LOG_TARGET: &str = "tari::dan::consensus::journal";
+
+/// Append-only journal of the decisions made by the local node. Journalling never fails consensus: write errors are
+/// logged and the record is dropped. Clones share the same journal file.
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusJournal {
+    writer: Option<Arc<Mutex<JournalWriter>>>,
+}
+
+impl ConsensusJournal {
+    pub fn new(config: Option<JournalConfig>) -> Self {
+        Self {
+            writer: config.map(|config| Arc::new(Mutex::new(JournalWriter::new(config)))),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { writer: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    pub fn record(&self, entry: JournalEntry) {
+        let Some(writer) = self.writer.as_ref() else {
+            return;
+        };
+
+        let record = JournalRecord::new(entry);
+        let mut writer = writer.lock().expect("journal writer lock poisoned");
+        if let Err(err) = writer.append(&record) {
+            warn!(target: LOG_TARGET, "⚠️ Failed to write consensus journal record ({}): {}", record.entry.kind(), err);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JournalWriter {
+    config: JournalConfig,
+    file: Option<File>,
+    file_size: u64,
+}
+
+impl JournalWriter {
+    pub fn new(config: JournalConfig) -> Self {
+        Self {
+            config,
+            file: None,
+            file_size: 0,
+        }
+    }
+
+    /// Appends a length-prefixed record to the journal, rotating the journal file first if the record would take it
+    /// over the configured maximum size.
+    pub fn append(&mut self, record: &JournalRecord) -> Result<(), JournalError> {
+        let buf = tari_bor::encode_with_len(record);
+        if self.file_size > 0 && self.file_size + buf.len() as u64 > self.config.max_file_size {
+            self.rotate()?;
+        }
+
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => self.open()?,
+        };
+        // The record is written in a single call so that a crash leaves at most one truncated record at the end of the
+        // file
+        file.write_all(&buf)?;
+        file.flush()?;
+        self.file_size += buf.len() as u64;
+        Ok(())
+    }
+
+    fn open(&mut self) -> Result<&mut File, JournalError> {
+        fs::create_dir_all(&self.config.directory)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(journal_file_path(&self.config.directory, 0))?;
+        self.file_size = file.metadata()?.len();
+        Ok(self.file.insert(file))
+    }
+
+    /// Shifts every journal file up by one index, deleting the oldest file if the maximum number of rotated files is
+    /// reached. The next append starts a new journal file.
+    fn rotate(&mut self) -> Result<(), JournalError> {
+        self.file = None;
+        self.file_size = 0;
+
+        let oldest = journal_file_path(&self.config.directory, self.config.max_rotated_files);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (0..self.config.max_rotated_files).rev() {
+            let path = journal_file_path(&self.config.directory, index);
+            if path.exists() {
+                fs::rename(path, journal_file_path(&self.config.directory, index + 1))?;
+            }
+        }
+        debug!(target: LOG_TARGET, "Rotated consensus journal in {}", self.config.directory.display());
+        Ok(())
+    }
+}
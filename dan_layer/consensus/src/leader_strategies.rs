@@ -0,0 +1,122 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, RwLock},
+};
+
+use log::*;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_dan_common_types::{committee::Committee, hashing::leader_selection_hasher, Epoch, NodeHeight};
+use tari_epoch_manager::{EpochManagerError, EpochManagerReader};
+
+use crate::traits::LeaderStrategy;
+
+const LOG_TARGET: &str = "tari::dan::consensus::leader_strategies";
+
+/// Selects leaders in committee order, starting at an offset derived from a per-epoch seed. Unlike plain round robin,
+/// the validator that leads at a given height differs between epochs.
+///
+/// The seed for an epoch must be loaded with [RoundRobinOffsetLeaderStrategy::load_epoch_seed] before the strategy is
+/// used for that epoch. Clones share the loaded seeds.
+#[derive(Debug, Clone, Default)]
+pub struct RoundRobinOffsetLeaderStrategy {
+    seeds: Arc<RwLock<HashMap<Epoch, u64>>>,
+}
+
+impl RoundRobinOffsetLeaderStrategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the seed for the epoch from the epoch manager
+    pub async fn load_epoch_seed<TEpochManager: EpochManagerReader>(
+        &self,
+        epoch_manager: &TEpochManager,
+        epoch: Epoch,
+    ) -> Result<(), EpochManagerError> {
+        let seed = epoch_manager.get_epoch_seed(epoch).await?;
+        self.set_epoch_seed(epoch, &seed);
+        Ok(())
+    }
+
+    pub fn set_epoch_seed(&self, epoch: Epoch, seed: &FixedHash) {
+        self.seeds.write().unwrap().insert(epoch, hash_to_u64(seed));
+    }
+
+    fn get_epoch_seed(&self, epoch: Epoch) -> u64 {
+        self.seeds.read().unwrap().get(&epoch).copied().unwrap_or_else(|| {
+            warn!(
+                target: LOG_TARGET,
+                "No leader seed loaded for epoch {}. Falling back to round robin without an offset", epoch
+            );
+            0
+        })
+    }
+}
+
+impl<TAddr> LeaderStrategy<TAddr> for RoundRobinOffsetLeaderStrategy {
+    fn calculate_leader(&self, committee: &Committee<TAddr>, epoch: Epoch, height: NodeHeight) -> u32 {
+        let len = committee.members.len() as u64;
+        let offset = self.get_epoch_seed(epoch) % len;
+        ((height.as_u64() % len + offset) % len) as u32
+    }
+}
+
+/// Selects the leader with a probability proportional to its weight (e.g. derived from its registration stake). The
+/// selection is a deterministic function of the epoch and height so every node selects the same leader. Validators
+/// without a weight are never selected unless no committee member has a weight, in which case the strategy falls back
+/// to round robin.
+#[derive(Debug, Clone, Default)]
+pub struct WeightedLeaderStrategy {
+    weights: Arc<BTreeMap<PublicKey, u64>>,
+}
+
+impl WeightedLeaderStrategy {
+    pub fn new(weights: BTreeMap<PublicKey, u64>) -> Self {
+        Self {
+            weights: Arc::new(weights),
+        }
+    }
+
+    pub fn weight_of(&self, public_key: &PublicKey) -> u64 {
+        self.weights.get(public_key).copied().unwrap_or(0)
+    }
+}
+
+impl<TAddr: PartialEq> LeaderStrategy<TAddr> for WeightedLeaderStrategy {
+    fn calculate_leader(&self, committee: &Committee<TAddr>, epoch: Epoch, height: NodeHeight) -> u32 {
+        let total_weight = committee
+            .public_keys()
+            .map(|pk| u128::from(self.weight_of(pk)))
+            .sum::<u128>();
+        if total_weight == 0 {
+            return (height.as_u64() % committee.members.len() as u64) as u32;
+        }
+
+        let hash = leader_selection_hasher().chain(&epoch).chain(&height).result();
+        let mut target = hash_to_u128(&hash) % total_weight;
+        for (index, public_key) in committee.public_keys().enumerate() {
+            let weight = u128::from(self.weight_of(public_key));
+            if target < weight {
+                return index as u32;
+            }
+            target -= weight;
+        }
+
+        unreachable!("target is less than the total weight of the committee")
+    }
+}
+
+fn hash_to_u64(hash: &FixedHash) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_slice()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn hash_to_u128(hash: &FixedHash) -> u128 {
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash.as_slice()[..16]);
+    u128::from_le_bytes(bytes)
+}
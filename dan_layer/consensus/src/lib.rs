@@ -1,7 +1,11 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-mod block_validations;
+pub mod block_validations;
+pub mod execution_plan;
 pub mod hotstuff;
+pub mod journal;
+pub mod leader_strategies;
 pub mod messages;
+pub mod outbound_queue;
 pub mod traits;
@@ -0,0 +1,13 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::Serialize;
+use tari_dan_common_types::Epoch;
+use tari_dan_storage::consensus_models::BlockId;
+
+/// Sent by a member of a foreign committee to confirm that it has received the foreign proposal for `block_id`
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeignProposalAckMessage {
+    pub epoch: Epoch,
+    pub block_id: BlockId,
+}
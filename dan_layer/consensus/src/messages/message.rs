@@ -6,7 +6,15 @@ use std::fmt::Display;
 use serde::Serialize;
 use tari_dan_common_types::Epoch;
 
-use super::{NewViewMessage, ProposalMessage, RequestedTransactionMessage, VoteMessage};
+use super::{
+    ForeignProposalAckMessage,
+    ForeignProposalMessage,
+    NewViewMessage,
+    ProposalMessage,
+    ProposerEquivocationMessage,
+    RequestedTransactionMessage,
+    VoteMessage,
+};
 use crate::messages::{RequestMissingTransactionsMessage, SyncRequestMessage, SyncResponseMessage};
 
 // Serialize is implemented for the message logger
@@ -14,12 +22,14 @@ use crate::messages::{RequestMissingTransactionsMessage, SyncRequestMessage, Syn
 pub enum HotstuffMessage {
     NewView(NewViewMessage),
     Proposal(ProposalMessage),
-    ForeignProposal(ProposalMessage),
+    ForeignProposal(ForeignProposalMessage),
+    ForeignProposalAck(ForeignProposalAckMessage),
     Vote(VoteMessage),
     RequestMissingTransactions(RequestMissingTransactionsMessage),
     RequestedTransaction(RequestedTransactionMessage),
     SyncRequest(SyncRequestMessage),
     SyncResponse(SyncResponseMessage),
+    ProposerEquivocation(ProposerEquivocationMessage),
 }
 
 impl HotstuffMessage {
@@ -28,11 +38,13 @@ impl HotstuffMessage {
             HotstuffMessage::NewView(_) => "NewView",
             HotstuffMessage::Proposal(_) => "Proposal",
             HotstuffMessage::ForeignProposal(_) => "ForeignProposal",
+            HotstuffMessage::ForeignProposalAck(_) => "ForeignProposalAck",
             HotstuffMessage::Vote(_) => "Vote",
             HotstuffMessage::RequestMissingTransactions(_) => "RequestMissingTransactions",
             HotstuffMessage::RequestedTransaction(_) => "RequestedTransaction",
             HotstuffMessage::SyncRequest(_) => "SyncRequest",
             HotstuffMessage::SyncResponse(_) => "SyncResponse",
+            HotstuffMessage::ProposerEquivocation(_) => "ProposerEquivocation",
         }
     }
 
@@ -41,11 +53,13 @@ impl HotstuffMessage {
             Self::NewView(msg) => msg.epoch,
             Self::Proposal(msg) => msg.block.epoch(),
             Self::ForeignProposal(msg) => msg.block.epoch(),
+            Self::ForeignProposalAck(msg) => msg.epoch,
             Self::Vote(msg) => msg.epoch,
             Self::RequestMissingTransactions(msg) => msg.epoch,
             Self::RequestedTransaction(msg) => msg.epoch,
             Self::SyncRequest(msg) => msg.epoch,
             Self::SyncResponse(msg) => msg.epoch,
+            Self::ProposerEquivocation(msg) => msg.first_block.epoch(),
         }
     }
 
@@ -63,6 +77,7 @@ impl Display for HotstuffMessage {
             HotstuffMessage::NewView(msg) => write!(f, "NewView({})", msg.new_height),
             HotstuffMessage::Proposal(msg) => write!(f, "Proposal({})", msg.block.height()),
             HotstuffMessage::ForeignProposal(msg) => write!(f, "ForeignProposal({})", msg.block.height()),
+            HotstuffMessage::ForeignProposalAck(msg) => write!(f, "ForeignProposalAck({})", msg.block_id),
             HotstuffMessage::Vote(msg) => write!(f, "Vote({}, {}, {})", msg.block_height, msg.block_id, msg.decision),
             HotstuffMessage::RequestMissingTransactions(msg) => {
                 write!(
@@ -82,6 +97,13 @@ impl Display for HotstuffMessage {
             ),
             HotstuffMessage::SyncRequest(msg) => write!(f, "SyncRequest({})", msg.high_qc),
             HotstuffMessage::SyncResponse(msg) => write!(f, "SyncResponse({} block(s))", msg.blocks.len()),
+            HotstuffMessage::ProposerEquivocation(msg) => write!(
+                f,
+                "ProposerEquivocation({}, {} and {})",
+                msg.first_block.height(),
+                msg.first_block.id(),
+                msg.second_block.id()
+            ),
         }
     }
 }
@@ -9,6 +9,9 @@ pub use new_view::*;
 mod proposal;
 pub use proposal::*;
 
+mod foreign_proposal_ack;
+pub use foreign_proposal_ack::*;
+
 mod vote;
 pub use vote::*;
 
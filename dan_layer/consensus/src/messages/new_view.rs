@@ -12,5 +12,7 @@ pub struct NewViewMessage {
     pub high_qc: QuorumCertificate,
     pub epoch: Epoch,
     pub new_height: NodeHeight,
+    /// The height of the last block that the sender voted for
+    pub last_voted_height: NodeHeight,
     pub last_vote: Option<VoteMessage>,
 }
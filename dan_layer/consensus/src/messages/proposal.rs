@@ -2,9 +2,25 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use serde::Serialize;
+use tari_dan_common_types::committee_membership::CommitteeMembershipProof;
 use tari_dan_storage::consensus_models::Block;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ProposalMessage {
     pub block: Block,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeignProposalMessage {
+    pub block: Block,
+    /// Proves that the sender is a member of the committee that proposed the block
+    pub membership_proof: CommitteeMembershipProof,
+}
+
+/// Evidence that the proposer of both blocks proposed two different blocks for the same height. Gossiped to the local
+/// committee by the validators that detect the equivocation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposerEquivocationMessage {
+    pub first_block: Block,
+    pub second_block: Block,
+}
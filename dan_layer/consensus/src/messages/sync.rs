@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use serde::Serialize;
-use tari_dan_common_types::Epoch;
+use tari_dan_common_types::{committee_membership::CommitteeMembershipProof, Epoch};
 use tari_dan_storage::consensus_models::{Block, HighQc, QuorumCertificate};
 use tari_transaction::Transaction;
 
@@ -16,6 +16,8 @@ pub struct SyncRequestMessage {
 pub struct SyncResponseMessage {
     pub epoch: Epoch,
     pub blocks: Vec<FullBlock>,
+    /// Proves that the sender is a member of the committee that it is syncing blocks for
+    pub membership_proof: CommitteeMembershipProof,
 }
 
 #[derive(Debug, Clone, Serialize)]
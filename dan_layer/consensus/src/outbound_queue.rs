@@ -0,0 +1,193 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use log::*;
+use tari_dan_common_types::NodeAddressable;
+use tokio::sync::Notify;
+
+use crate::{
+    messages::HotstuffMessage,
+    traits::{hooks::ConsensusHooks, OutboundMessagingError},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::outbound_queue";
+
+/// Delivers a message to a single peer. Delivery may take arbitrarily long, e.g. if the peer is slow to accept
+/// messages.
+#[async_trait]
+pub trait PeerMessageSender<TAddr>: Send + Sync + 'static {
+    async fn send_to_peer(&self, to: &TAddr, message: HotstuffMessage) -> Result<(), OutboundMessagingError>;
+}
+
+/// Bounded outbound message queues, one per peer. Each queue is drained by its own task, so a peer that is slow to
+/// accept messages only delays the messages destined for that peer.
+///
+/// When the queue for a peer is full, the oldest local proposal in the queue is dropped to make room for the new
+/// message. Votes are never dropped, because the leader cannot form a QC without them. Any other message is not queued
+/// if the queue is full and contains no proposals.
+pub struct OutboundQueues<TAddr, TSender, THooks> {
+    capacity: usize,
+    sender: Arc<TSender>,
+    hooks: THooks,
+    queues: Arc<Mutex<HashMap<TAddr, PeerQueue>>>,
+}
+
+impl<TAddr, TSender, THooks> OutboundQueues<TAddr, TSender, THooks>
+where
+    TAddr: NodeAddressable + 'static,
+    TSender: PeerMessageSender<TAddr>,
+    THooks: ConsensusHooks + Clone + Send + 'static,
+{
+    pub fn new(capacity: usize, sender: TSender, hooks: THooks) -> Self {
+        Self {
+            capacity,
+            sender: Arc::new(sender),
+            hooks,
+            queues: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Queues the message for the peer without waiting for it to be sent. Returns true if the message was queued.
+    pub fn enqueue(&mut self, to: TAddr, message: HotstuffMessage) -> bool {
+        let queue = self.get_or_create_queue(&to);
+        let (is_queued, depth) = queue.push(self.capacity, message);
+        if !is_queued {
+            warn!(
+                target: LOG_TARGET,
+                "Outbound queue for {} is full ({} messages). Message not queued.", to, depth
+            );
+        }
+        self.hooks.on_outbound_queue_depth_changed(&to, depth);
+        is_queued
+    }
+
+    /// Returns the number of messages waiting to be sent to the peer
+    pub fn queue_depth(&self, peer: &TAddr) -> usize {
+        self.queues
+            .lock()
+            .unwrap()
+            .get(peer)
+            .map(|queue| queue.len())
+            .unwrap_or(0)
+    }
+
+    fn get_or_create_queue(&self, to: &TAddr) -> PeerQueue {
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(queue) = queues.get(to) {
+            return queue.clone();
+        }
+
+        let queue = PeerQueue::new();
+        queues.insert(to.clone(), queue.clone());
+        // The task runs for as long as the runtime, waiting for messages for the peer
+        tokio::spawn(drain_queue(
+            to.clone(),
+            queue.clone(),
+            self.sender.clone(),
+            self.hooks.clone(),
+        ));
+        queue
+    }
+}
+
+impl<TAddr, TSender, THooks: Clone> Clone for OutboundQueues<TAddr, TSender, THooks> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            sender: self.sender.clone(),
+            hooks: self.hooks.clone(),
+            queues: self.queues.clone(),
+        }
+    }
+}
+
+impl<TAddr, TSender, THooks> Debug for OutboundQueues<TAddr, TSender, THooks> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutboundQueues")
+            .field("capacity", &self.capacity)
+            .field("num_peers", &self.queues.lock().unwrap().len())
+            .finish()
+    }
+}
+
+async fn drain_queue<TAddr, TSender, THooks>(to: TAddr, queue: PeerQueue, sender: Arc<TSender>, mut hooks: THooks)
+where
+    TAddr: NodeAddressable,
+    TSender: PeerMessageSender<TAddr>,
+    THooks: ConsensusHooks,
+{
+    loop {
+        let (message, depth) = queue.pop().await;
+        hooks.on_outbound_queue_depth_changed(&to, depth);
+        if let Err(err) = sender.send_to_peer(&to, message).await {
+            warn!(target: LOG_TARGET, "Failed to send message to {}: {}", to, err);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PeerQueue {
+    messages: Arc<Mutex<VecDeque<HotstuffMessage>>>,
+    notify: Arc<Notify>,
+}
+
+impl PeerQueue {
+    fn new() -> Self {
+        Self {
+            messages: Arc::new(Mutex::new(VecDeque::new())),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Pushes the message onto the queue and returns whether it was queued and the resulting queue depth
+    fn push(&self, capacity: usize, message: HotstuffMessage) -> (bool, usize) {
+        let mut messages = self.messages.lock().unwrap();
+        if messages.len() >= capacity {
+            if let Some(index) = messages.iter().position(is_droppable) {
+                let dropped = messages.remove(index).expect("index is in bounds");
+                debug!(
+                    target: LOG_TARGET,
+                    "Outbound queue is full. Dropped the oldest {} message", dropped.as_type_str()
+                );
+            }
+        }
+        if messages.len() >= capacity && !matches!(message, HotstuffMessage::Vote(_)) {
+            return (false, messages.len());
+        }
+
+        messages.push_back(message);
+        let depth = messages.len();
+        drop(messages);
+        self.notify.notify_one();
+        (true, depth)
+    }
+
+    /// Waits for the next message and returns it with the remaining queue depth
+    async fn pop(&self) -> (HotstuffMessage, usize) {
+        loop {
+            {
+                let mut messages = self.messages.lock().unwrap();
+                if let Some(message) = messages.pop_front() {
+                    return (message, messages.len());
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+}
+
+/// A local proposal is superseded by the next proposal, which the peer can sync from if it missed the previous one
+fn is_droppable(message: &HotstuffMessage) -> bool {
+    matches!(message, HotstuffMessage::Proposal(_))
+}
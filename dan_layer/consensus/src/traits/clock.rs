@@ -0,0 +1,33 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tari_crypto::tari_utilities::epoch_time::EpochTime;
+
+/// Source of the current time used by consensus for block timestamps and leader timeouts.
+pub trait Clock {
+    /// Returns the current time in seconds since the unix epoch
+    fn now(&self) -> u64;
+
+    /// Returns the current time in milliseconds since the unix epoch
+    fn now_millis(&self) -> u64 {
+        self.now() * 1000
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        EpochTime::now().as_u64()
+    }
+
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
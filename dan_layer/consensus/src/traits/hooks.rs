@@ -1,8 +1,18 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_common_types::NodeHeight;
-use tari_dan_storage::consensus_models::{QuorumDecision, TransactionAtom, ValidBlock};
+use std::fmt::Display;
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{
+    Block,
+    ProposerEquivocation,
+    QcTiming,
+    QuorumDecision,
+    TransactionAtom,
+    ValidBlock,
+    VoteTiming,
+};
 use tari_transaction::TransactionId;
 
 use crate::{hotstuff::HotStuffError, messages::HotstuffMessage};
@@ -14,13 +24,46 @@ pub trait ConsensusHooks {
     fn on_message_received(&mut self, message: &HotstuffMessage);
     fn on_error(&mut self, err: &HotStuffError);
     fn on_pacemaker_height_changed(&mut self, height: NodeHeight);
-    fn on_leader_timeout(&mut self, new_height: NodeHeight);
+    /// Called when the leader for the current view fails to propose in time. `new_height` is the height that the
+    /// pacemaker moved to and `round` is the number of consecutive views that have failed, including this one.
+    fn on_leader_timeout(&mut self, new_height: NodeHeight, round: u32);
+    /// Called once for each dummy block that this node creates to fill in the heights of failed leaders, after the
+    /// dummy block is persisted
+    fn on_dummy_block_created(&mut self, height: NodeHeight);
     fn on_beat(&mut self);
 
     fn on_needs_sync(&mut self, local_height: NodeHeight, remote_qc_height: NodeHeight);
 
     fn on_transaction_ready(&mut self, tx_id: &TransactionId);
     fn on_transaction_finalized(&mut self, transaction: &TransactionAtom);
+    /// Called once for each block that this node commits, after the commit is persisted and before
+    /// [ConsensusHooks::on_transaction_finalized] is called for the transactions finalized by the block
+    fn on_block_committed(&mut self, _block: &Block) {}
+
+    /// Called when a new vote is received for a block whose votes this node collects
+    fn on_vote_received(&mut self, epoch: Epoch, vote: &VoteTiming);
+    /// Called when this node forms a QC from the votes that it received for a block
+    fn on_qc_formed(&mut self, timing: &QcTiming);
+    /// Called when consensus starts and whenever this node enters or leaves maintenance mode
+    fn on_maintenance_mode_changed(&mut self, is_enabled: bool);
+    /// Called when a leader is found to have proposed two different blocks for the same height, either by this node or
+    /// by another committee member that gossiped the evidence
+    fn on_proposer_equivocation(&mut self, equivocation: &ProposerEquivocation);
+    /// Called when the node starts or stops refusing new transaction submissions because write transactions are
+    /// waiting too long for the state store
+    fn on_load_shedding_changed(&mut self, _is_shedding: bool) {}
+    /// Called each time the cache of verified QCs is consulted while validating the justify QC of a proposal
+    fn on_verified_qc_cache_lookup(&mut self, _is_hit: bool) {}
+    /// Called when a message is added to or taken from the outbound message queue for a peer
+    fn on_outbound_queue_depth_changed<TAddr: Display>(&mut self, _peer: &TAddr, _depth: usize) {}
+    /// Called when a message from a peer is dropped because the peer is banned for misbehaving
+    fn on_banned_peer_message_dropped<TAddr: Display>(&mut self, _peer: &TAddr, _message: &HotstuffMessage) {}
+    /// Called when this node proposes a block for which it prepared the transaction executions while the parent block
+    /// was voted on. `is_used` is false if the prepared executions were discarded, e.g. because the leaf block changed.
+    fn on_pipelined_proposal(&mut self, _is_used: bool) {}
+    /// Called for each retry of sending a vote to the leader after the first attempt failed. `is_sent` is false if the
+    /// retry also failed.
+    fn on_vote_send_retried(&mut self, _is_sent: bool) {}
 }
 
 #[derive(Debug, Clone)]
@@ -69,9 +112,15 @@ impl<T: ConsensusHooks> ConsensusHooks for OptionalHooks<T> {
         }
     }
 
-    fn on_leader_timeout(&mut self, new_height: NodeHeight) {
+    fn on_leader_timeout(&mut self, new_height: NodeHeight, round: u32) {
         if let Some(inner) = self.inner.as_mut() {
-            inner.on_leader_timeout(new_height);
+            inner.on_leader_timeout(new_height, round);
+        }
+    }
+
+    fn on_dummy_block_created(&mut self, height: NodeHeight) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_dummy_block_created(height);
         }
     }
 
@@ -98,6 +147,72 @@ impl<T: ConsensusHooks> ConsensusHooks for OptionalHooks<T> {
             inner.on_transaction_finalized(transaction);
         }
     }
+
+    fn on_block_committed(&mut self, block: &Block) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_block_committed(block);
+        }
+    }
+
+    fn on_vote_received(&mut self, epoch: Epoch, vote: &VoteTiming) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_vote_received(epoch, vote);
+        }
+    }
+
+    fn on_qc_formed(&mut self, timing: &QcTiming) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_qc_formed(timing);
+        }
+    }
+
+    fn on_maintenance_mode_changed(&mut self, is_enabled: bool) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_maintenance_mode_changed(is_enabled);
+        }
+    }
+
+    fn on_proposer_equivocation(&mut self, equivocation: &ProposerEquivocation) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_proposer_equivocation(equivocation);
+        }
+    }
+
+    fn on_load_shedding_changed(&mut self, is_shedding: bool) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_load_shedding_changed(is_shedding);
+        }
+    }
+
+    fn on_verified_qc_cache_lookup(&mut self, is_hit: bool) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_verified_qc_cache_lookup(is_hit);
+        }
+    }
+
+    fn on_outbound_queue_depth_changed<TAddr: Display>(&mut self, peer: &TAddr, depth: usize) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_outbound_queue_depth_changed(peer, depth);
+        }
+    }
+
+    fn on_banned_peer_message_dropped<TAddr: Display>(&mut self, peer: &TAddr, message: &HotstuffMessage) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_banned_peer_message_dropped(peer, message);
+        }
+    }
+
+    fn on_pipelined_proposal(&mut self, is_used: bool) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_pipelined_proposal(is_used);
+        }
+    }
+
+    fn on_vote_send_retried(&mut self, is_sent: bool) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_vote_send_retried(is_sent);
+        }
+    }
 }
 
 impl<T> From<T> for OptionalHooks<T> {
@@ -120,7 +235,9 @@ impl ConsensusHooks for NoopHooks {
 
     fn on_pacemaker_height_changed(&mut self, _: NodeHeight) {}
 
-    fn on_leader_timeout(&mut self, _new_height: NodeHeight) {}
+    fn on_leader_timeout(&mut self, _new_height: NodeHeight, _round: u32) {}
+
+    fn on_dummy_block_created(&mut self, _height: NodeHeight) {}
 
     fn on_beat(&mut self) {}
 
@@ -129,4 +246,12 @@ impl ConsensusHooks for NoopHooks {
     fn on_transaction_ready(&mut self, _tx_id: &TransactionId) {}
 
     fn on_transaction_finalized(&mut self, _transaction: &TransactionAtom) {}
+
+    fn on_vote_received(&mut self, _epoch: Epoch, _vote: &VoteTiming) {}
+
+    fn on_qc_formed(&mut self, _timing: &QcTiming) {}
+
+    fn on_maintenance_mode_changed(&mut self, _is_enabled: bool) {}
+
+    fn on_proposer_equivocation(&mut self, _equivocation: &ProposerEquivocation) {}
 }
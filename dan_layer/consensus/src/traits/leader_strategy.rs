@@ -21,14 +21,24 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use tari_common_types::types::PublicKey;
-use tari_dan_common_types::{committee::Committee, NodeHeight};
+use tari_dan_common_types::{committee::Committee, Epoch, NodeHeight};
 
 pub trait LeaderStrategy<TAddr> {
-    fn calculate_leader(&self, committee: &Committee<TAddr>, height: NodeHeight) -> u32;
+    /// Returns the index of the leader in the committee for the given epoch and height. Every honest node must
+    /// calculate the same leader for the same committee, epoch and height.
+    fn calculate_leader(&self, committee: &Committee<TAddr>, epoch: Epoch, height: NodeHeight) -> u32;
 
-    fn is_leader(&self, validator_addr: &TAddr, committee: &Committee<TAddr>, height: NodeHeight) -> bool
-    where TAddr: PartialEq {
-        let position = self.calculate_leader(committee, height);
+    fn is_leader(
+        &self,
+        validator_addr: &TAddr,
+        committee: &Committee<TAddr>,
+        epoch: Epoch,
+        height: NodeHeight,
+    ) -> bool
+    where
+        TAddr: PartialEq,
+    {
+        let position = self.calculate_leader(committee, epoch, height);
         if let Some((addr, _)) = committee.members.get(position as usize) {
             addr == validator_addr
         } else {
@@ -40,28 +50,39 @@ pub trait LeaderStrategy<TAddr> {
         &self,
         validator_addr: &TAddr,
         committee: &Committee<TAddr>,
+        epoch: Epoch,
         // block: &BlockId,
         height: NodeHeight,
     ) -> bool
     where
         TAddr: PartialEq,
     {
-        self.is_leader(validator_addr, committee, height + NodeHeight(1))
+        self.is_leader(validator_addr, committee, epoch, height + NodeHeight(1))
     }
 
-    fn get_leader<'b>(&self, committee: &'b Committee<TAddr>, height: NodeHeight) -> &'b TAddr {
-        let index = self.calculate_leader(committee, height);
+    fn get_leader<'b>(&self, committee: &'b Committee<TAddr>, epoch: Epoch, height: NodeHeight) -> &'b TAddr {
+        let index = self.calculate_leader(committee, epoch, height);
         let (addr, _) = committee.members.get(index as usize).unwrap();
         addr
     }
 
-    fn get_leader_public_key<'b>(&self, committee: &'b Committee<TAddr>, height: NodeHeight) -> &'b PublicKey {
-        let index = self.calculate_leader(committee, height);
+    fn get_leader_public_key<'b>(
+        &self,
+        committee: &'b Committee<TAddr>,
+        epoch: Epoch,
+        height: NodeHeight,
+    ) -> &'b PublicKey {
+        let index = self.calculate_leader(committee, epoch, height);
         let (_, public_key) = committee.members.get(index as usize).unwrap();
         public_key
     }
 
-    fn get_leader_for_next_block<'b>(&self, committee: &'b Committee<TAddr>, height: NodeHeight) -> &'b TAddr {
-        self.get_leader(committee, height + NodeHeight(1))
+    fn get_leader_for_next_block<'b>(
+        &self,
+        committee: &'b Committee<TAddr>,
+        epoch: Epoch,
+        height: NodeHeight,
+    ) -> &'b TAddr {
+        self.get_leader(committee, epoch, height + NodeHeight(1))
     }
 }
@@ -37,6 +37,14 @@ pub trait OutboundMessaging {
         message: T,
     ) -> Result<(), OutboundMessagingError>;
 
+    /// Queues the message to be sent to the peer without waiting for it to be sent. Returns false if the message was
+    /// not queued, e.g. because the queue for a slow peer is full.
+    fn try_send<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+    ) -> Result<bool, OutboundMessagingError>;
+
     async fn multicast<'a, I, T>(&mut self, committee: I, message: T) -> Result<(), OutboundMessagingError>
     where
         Self::Addr: 'a,
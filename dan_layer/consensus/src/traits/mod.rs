@@ -1,6 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+mod clock;
 pub mod hooks;
 mod leader_strategy;
 mod messaging;
@@ -9,6 +10,7 @@ mod substate_store;
 mod sync;
 mod transaction_executor;
 
+pub use clock::*;
 pub use leader_strategy::*;
 pub use messaging::*;
 pub use substate_store::*;
@@ -33,4 +35,5 @@ pub trait ConsensusSpec: Send + Sync + Clone + 'static {
     type InboundMessaging: InboundMessaging<Addr = Self::Addr> + Send + Sync + 'static;
     type OutboundMessaging: OutboundMessaging<Addr = Self::Addr> + Clone + Send + Sync + 'static;
     type Hooks: ConsensusHooks + Clone + Send + Sync + 'static;
+    type Clock: Clock + Clone + Send + Sync + 'static;
 }
@@ -2,13 +2,28 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_common_types::hashing::vote_signature_hasher;
-use tari_dan_storage::consensus_models::{BlockId, QuorumDecision, ValidatorSchnorrSignature, ValidatorSignature};
+use tari_dan_storage::consensus_models::{
+    vote_signature_challenge,
+    BlockId,
+    QuorumDecision,
+    ValidatorSchnorrSignature,
+    ValidatorSignature,
+};
 
 pub trait ValidatorSignatureService {
     fn sign<M: AsRef<[u8]>>(&self, message: M) -> ValidatorSchnorrSignature;
 
     fn public_key(&self) -> &PublicKey;
+
+    /// Verifies that the signature over the message was created by the given public key
+    fn verify_signature<M: AsRef<[u8]>>(
+        &self,
+        public_key: &PublicKey,
+        signature: &ValidatorSchnorrSignature,
+        message: M,
+    ) -> bool {
+        signature.verify(public_key, message)
+    }
 }
 
 pub trait VoteSignatureService: ValidatorSignatureService {
@@ -18,11 +33,7 @@ pub trait VoteSignatureService: ValidatorSignatureService {
         block_id: &BlockId,
         decision: &QuorumDecision,
     ) -> FixedHash {
-        vote_signature_hasher()
-            .chain(voter_leaf_hash)
-            .chain(block_id)
-            .chain(decision)
-            .result()
+        vote_signature_challenge(voter_leaf_hash, block_id, decision)
     }
 
     fn sign_vote(&self, leaf_hash: &FixedHash, block_id: &BlockId, decision: &QuorumDecision) -> ValidatorSignature {
@@ -38,4 +49,11 @@ pub trait VoteSignatureService: ValidatorSignatureService {
         block_id: &BlockId,
         decision: &QuorumDecision,
     ) -> bool;
+
+    /// Verifies that each signature is valid for its vote challenge. Returns true only if all signatures are valid.
+    fn verify_batch(&self, challenges_and_signatures: &[(FixedHash, &ValidatorSignature)]) -> bool {
+        challenges_and_signatures
+            .iter()
+            .all(|(challenge, signature)| signature.verify(challenge))
+    }
 }
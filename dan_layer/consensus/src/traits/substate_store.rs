@@ -7,15 +7,22 @@ use tari_dan_storage::{
     StateStoreReadTransaction,
     StorageError,
 };
-use tari_engine_types::substate::{Substate, SubstateDiff};
+use tari_engine_types::substate::{Substate, SubstateDiff, SubstateId};
 use tari_transaction::{TransactionId, VersionedSubstateId};
 
+use crate::hotstuff::substate_store::SubstateStoreError;
+
 pub trait ReadableSubstateStore {
     type Error;
 
     fn get(&self, key: &SubstateAddress) -> Result<Substate, Self::Error>;
 }
 
+/// A store that the input substates of a transaction are resolved from
+pub trait InputSubstateStore: ReadableSubstateStore<Error = SubstateStoreError> {
+    fn get_latest(&self, id: &SubstateId) -> Result<Substate, SubstateStoreError>;
+}
+
 pub trait WriteableSubstateStore: ReadableSubstateStore {
     fn put(&mut self, change: SubstateChange) -> Result<(), Self::Error>;
 
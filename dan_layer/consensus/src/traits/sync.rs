@@ -2,6 +2,12 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use tari_dan_storage::consensus_models::SyncCheckpoint;
+
+/// Progress events from a checkpointed sync. Sync only makes progress while the stream is polled and the stream ends
+/// once the node has caught up with its sync peer.
+pub type SyncProgressStream<'a, E> = BoxStream<'a, Result<SyncProgress, E>>;
 
 #[async_trait]
 pub trait SyncManager {
@@ -10,6 +16,10 @@ pub trait SyncManager {
     async fn check_sync(&self) -> Result<SyncStatus, Self::Error>;
 
     async fn sync(&mut self) -> Result<(), Self::Error>;
+
+    /// Syncs the blocks after the checkpoint. A new checkpoint is stored after each committed batch of blocks, so if
+    /// the stream returns an error or is dropped, sync can be resumed from the stored checkpoint.
+    fn sync_from_checkpoint(&mut self, checkpoint: SyncCheckpoint) -> SyncProgressStream<'_, Self::Error>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -17,3 +27,13 @@ pub enum SyncStatus {
     UpToDate,
     Behind,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncProgress {
+    /// The checkpoint that was stored after the most recently committed batch
+    pub checkpoint: SyncCheckpoint,
+    /// The number of blocks synced since sync was started from the checkpoint
+    pub blocks_synced: u64,
+    /// The estimated number of blocks remaining, based on the height of the sync peer's high QC when sync started
+    pub estimated_remaining: u64,
+}
@@ -1,11 +1,18 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_storage::{consensus_models::ExecutedTransaction, StateStore, StorageError};
+use tari_dan_storage::{
+    consensus_models::{ExecutedTransaction, TransactionRecord},
+    StateStore,
+    StorageError,
+};
 use tari_engine_types::substate::SubstateId;
 use tari_transaction::Transaction;
 
-use crate::hotstuff::substate_store::{PendingSubstateStore, SubstateStoreError};
+use crate::{
+    execution_plan::ExecutionPlan,
+    hotstuff::substate_store::{PendingSubstateStore, SubstateSnapshot, SubstateSnapshotView, SubstateStoreError},
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum BlockTransactionExecutorError {
@@ -30,4 +37,39 @@ pub trait BlockTransactionExecutor<TStateStore: StateStore> {
         transaction: Transaction,
         store: &PendingSubstateStore<TStateStore>,
     ) -> Result<ExecutedTransaction, BlockTransactionExecutorError>;
+
+    /// Executes the transaction, resolving its inputs from a snapshot of the store instead of the store itself
+    fn execute_with_snapshot(
+        &self,
+        transaction: Transaction,
+        snapshot: &SubstateSnapshotView<'_>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError>;
+
+    /// Executes the transactions of a block against a snapshot of their inputs and returns the result of each
+    /// execution in the order of the given transactions. Groups of transactions that do not share substates (see
+    /// [ExecutionPlan]) are executed in parallel. Within a group, each transaction sees the outputs of the accepted
+    /// transactions before it, so the results are the same as executing the transactions one after the other.
+    fn execute_block(
+        &self,
+        transactions: Vec<TransactionRecord>,
+        snapshot: &SubstateSnapshot,
+    ) -> Vec<Result<ExecutedTransaction, BlockTransactionExecutorError>>
+    where
+        Self: Sync,
+    {
+        let plan = ExecutionPlan::for_transactions(&transactions);
+        let transactions = transactions
+            .into_iter()
+            .map(TransactionRecord::into_transaction)
+            .collect();
+        plan.execute_with_state(
+            transactions,
+            || snapshot.view(),
+            |view, transaction| {
+                let executed = self.execute_with_snapshot(transaction, view)?;
+                view.apply(&executed);
+                Ok(executed)
+            },
+        )
+    }
 }
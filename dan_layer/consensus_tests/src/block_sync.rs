@@ -0,0 +1,371 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashSet, time::Duration};
+
+use async_trait::async_trait;
+use futures::{future, stream, StreamExt, TryStreamExt};
+use tari_common::configuration::Network;
+use tari_consensus::traits::{SyncManager, SyncProgressStream, SyncStatus};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, Decision, GenesisConfig, QuorumCertificate, SyncCheckpoint},
+    StateStore,
+    StateStoreReadTransaction,
+    StorageError,
+};
+use tari_epoch_manager::EpochManagerReader;
+use tari_rpc_state_sync::{
+    create_zero_block_if_required,
+    load_sync_checkpoint,
+    sync_progress_stream,
+    BlockSyncProcessor,
+    CommsRpcConsensusSyncError,
+    SyncFailureCounter,
+    SyncedBlock,
+};
+use tari_state_store_sqlite::SqliteStateStore;
+
+use crate::support::{
+    logging::setup_logger,
+    RoundRobinLeaderStrategy,
+    Test,
+    TestAddress,
+    TestConsensusSpec,
+    TestEpochManager,
+    TestVoteSignatureService,
+};
+
+const BATCH_SIZE: usize = 2;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn it_rejects_a_tampered_batch_and_completes_sync_from_another_peer() {
+    setup_logger();
+    let honest_peer = TestAddress::new("1");
+    let malicious_peer = TestAddress::new("2");
+    let (blocks, epoch_manager, signing_service) = commit_blocks_on_network(&honest_peer).await;
+
+    let tampered_index = BATCH_SIZE * 2 + 1;
+    assert!(
+        blocks.len() > tampered_index + BATCH_SIZE,
+        "Not enough blocks to sync: {}",
+        blocks.len()
+    );
+
+    let store = SqliteStateStore::<TestAddress>::connect(":memory:").unwrap();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+    let mut sync_failures = SyncFailureCounter::new();
+
+    // The malicious peer changes the leader fee of a block in the third batch without being able to re-sign it
+    let mut tampered_blocks = blocks.clone();
+    let tampered = &blocks[tampered_index].block;
+    tampered_blocks[tampered_index].block = rebuild_block(tampered, tampered.total_leader_fee() + 1);
+    let tampered_block_id = *tampered_blocks[tampered_index].block.id();
+
+    let err = sync_blocks(&store, &epoch_manager, &signing_service, tampered_blocks)
+        .await
+        .unwrap_err();
+    match err {
+        CommsRpcConsensusSyncError::InvalidBlock { block_id, .. } => assert_eq!(block_id, tampered_block_id),
+        err => panic!("Expected InvalidBlock error, got {}", err),
+    }
+    assert_eq!(sync_failures.penalize(&malicious_peer), 1);
+
+    store
+        .with_read_tx(|tx| {
+            // Validated batches before the tampered block are kept
+            for synced in &blocks[..BATCH_SIZE * 2] {
+                assert!(tx.blocks_exists(synced.block.id())?);
+            }
+            // The batch that contained the tampered block is discarded
+            for synced in &blocks[BATCH_SIZE * 2..] {
+                assert!(!tx.blocks_exists(synced.block.id())?);
+            }
+            assert!(!tx.blocks_exists(&tampered_block_id)?);
+            Ok::<_, StorageError>(())
+        })
+        .unwrap();
+
+    let mut candidates = vec![malicious_peer.clone(), honest_peer.clone()];
+    sync_failures.order_candidates(&mut candidates);
+    assert_eq!(candidates, vec![honest_peer.clone(), malicious_peer]);
+
+    // Sync resumes from the honest peer
+    let num_committed = sync_blocks(&store, &epoch_manager, &signing_service, blocks.clone())
+        .await
+        .unwrap();
+    assert!(num_committed > 0);
+
+    store
+        .with_read_tx(|tx| {
+            for synced in &blocks {
+                assert!(tx.blocks_exists(synced.block.id())?);
+            }
+            assert!(tx.substates_count()? > 0);
+            Ok::<_, StorageError>(())
+        })
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn it_resumes_an_interrupted_sync_from_the_stored_checkpoint() {
+    setup_logger();
+    let peer = TestAddress::new("1");
+    let (blocks, epoch_manager, signing_service) = commit_blocks_on_network(&peer).await;
+    assert!(
+        blocks.len() > BATCH_SIZE * 3,
+        "Not enough blocks to sync: {}",
+        blocks.len()
+    );
+
+    let store = SqliteStateStore::<TestAddress>::connect(":memory:").unwrap();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+    let mut sync_manager = PeerBlocksSyncManager {
+        peer_blocks: blocks.clone(),
+        store: store.clone(),
+        epoch_manager: epoch_manager.clone(),
+        signing_service: signing_service.clone(),
+    };
+    assert_eq!(sync_manager.check_sync().await.unwrap(), SyncStatus::Behind);
+
+    // Sync two batches and then stop, as if the node was shut down mid-sync
+    let checkpoint = load_sync_checkpoint(&store).unwrap();
+    assert_eq!(checkpoint.height(), NodeHeight(0));
+    let mut progress_stream = sync_manager.sync_from_checkpoint(checkpoint);
+    let first = progress_stream.try_next().await.unwrap().unwrap();
+    let second = progress_stream.try_next().await.unwrap().unwrap();
+    drop(progress_stream);
+    drop(sync_manager);
+
+    assert_eq!(first.blocks_synced, BATCH_SIZE as u64);
+    assert_eq!(second.blocks_synced, BATCH_SIZE as u64 * 2);
+    assert!(second.estimated_remaining < first.estimated_remaining);
+    assert!(second.checkpoint.height() > NodeHeight(0));
+    assert!(second.checkpoint.height() >= first.checkpoint.height());
+    let stored_checkpoint = store.with_read_tx(|tx| SyncCheckpoint::get(tx)).unwrap();
+    assert_eq!(stored_checkpoint, second.checkpoint);
+
+    // The restarted node resumes from the stored checkpoint rather than from genesis
+    let mut sync_manager = PeerBlocksSyncManager {
+        peer_blocks: blocks.clone(),
+        store: store.clone(),
+        epoch_manager,
+        signing_service,
+    };
+    let checkpoint = load_sync_checkpoint(&store).unwrap();
+    assert_eq!(checkpoint, stored_checkpoint);
+    let progress = sync_manager
+        .sync_from_checkpoint(checkpoint)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    let num_blocks_after_checkpoint = blocks
+        .iter()
+        .filter(|synced| synced.block.height() > checkpoint.height())
+        .count();
+    assert!(num_blocks_after_checkpoint < blocks.len());
+    let last = progress.last().unwrap();
+    assert_eq!(last.blocks_synced, num_blocks_after_checkpoint as u64);
+    assert_eq!(last.estimated_remaining, 0);
+    assert!(last.checkpoint.height() > checkpoint.height());
+    assert_eq!(sync_manager.check_sync().await.unwrap(), SyncStatus::UpToDate);
+
+    store
+        .with_read_tx(|tx| {
+            for synced in &blocks {
+                assert!(tx.blocks_exists(synced.block.id())?);
+            }
+            assert_eq!(SyncCheckpoint::get(tx)?, last.checkpoint);
+            assert!(tx.substates_count()? > 0);
+            Ok::<_, StorageError>(())
+        })
+        .unwrap();
+}
+
+/// Syncs from the committed blocks of another validator. Like the sync RPC, the blocks after the requested checkpoint
+/// are sent.
+struct PeerBlocksSyncManager {
+    peer_blocks: Vec<SyncedBlock>,
+    store: SqliteStateStore<TestAddress>,
+    epoch_manager: TestEpochManager,
+    signing_service: TestVoteSignatureService,
+}
+
+#[async_trait]
+impl SyncManager for PeerBlocksSyncManager {
+    type Error = CommsRpcConsensusSyncError;
+
+    async fn check_sync(&self) -> Result<SyncStatus, Self::Error> {
+        let Some(tip) = self.peer_blocks.last() else {
+            return Ok(SyncStatus::UpToDate);
+        };
+        if self.store.with_read_tx(|tx| tx.blocks_exists(tip.block.id()))? {
+            Ok(SyncStatus::UpToDate)
+        } else {
+            Ok(SyncStatus::Behind)
+        }
+    }
+
+    async fn sync(&mut self) -> Result<(), Self::Error> {
+        let checkpoint = load_sync_checkpoint(&self.store)?;
+        self.sync_from_checkpoint(checkpoint)
+            .try_for_each(|_| future::ok(()))
+            .await
+    }
+
+    fn sync_from_checkpoint(&mut self, checkpoint: SyncCheckpoint) -> SyncProgressStream<'_, Self::Error> {
+        let processor = match BlockSyncProcessor::<TestConsensusSpec>::new(
+            Network::LocalNet,
+            self.epoch_manager.clone(),
+            self.store.clone(),
+            RoundRobinLeaderStrategy::new(),
+            self.signing_service.clone(),
+            &checkpoint,
+            BATCH_SIZE,
+        ) {
+            Ok(processor) => processor,
+            Err(err) => return stream::once(future::err(err)).boxed(),
+        };
+
+        let blocks = self
+            .peer_blocks
+            .iter()
+            .filter(|synced| synced.block.height() > checkpoint.height())
+            .cloned()
+            .map(Ok)
+            .collect::<Vec<_>>();
+        let target_height = self
+            .peer_blocks
+            .last()
+            .map_or(checkpoint.height(), |synced| synced.block.height());
+
+        sync_progress_stream(processor, stream::iter(blocks), target_height).boxed()
+    }
+}
+
+/// Runs a committee until the peer has committed the blocks that contain a few transactions. Returns the committed
+/// blocks of the peer and the services needed to validate them.
+async fn commit_blocks_on_network(
+    peer: &TestAddress,
+) -> (Vec<SyncedBlock>, TestEpochManager, TestVoteSignatureService) {
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    let mut all_committed_height = None;
+    loop {
+        let (address, _, committed_height) = test.on_block_committed().await;
+        if all_committed_height.is_none() && test.is_transaction_pool_empty() {
+            all_committed_height = Some(committed_height);
+        }
+        // Wait for a few more blocks so that the synced chain commits the blocks that contain the transactions
+        if all_committed_height.is_some_and(|h| address == *peer && committed_height > h + NodeHeight(3)) {
+            break;
+        }
+        if committed_height > NodeHeight(50) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    let validator = test.get_validator(peer);
+    let blocks = get_committed_blocks(validator.state_store());
+    let epoch_manager = validator.epoch_manager.clone();
+    let public_key = epoch_manager.get_our_validator_node(Epoch(0)).await.unwrap().public_key;
+    let signing_service = TestVoteSignatureService::new(public_key, peer.clone());
+    test.assert_clean_shutdown().await;
+
+    (blocks, epoch_manager, signing_service)
+}
+
+/// Feeds the blocks after the sync checkpoint to a sync processor, as a peer would stream them
+async fn sync_blocks(
+    store: &SqliteStateStore<TestAddress>,
+    epoch_manager: &TestEpochManager,
+    signing_service: &TestVoteSignatureService,
+    blocks: Vec<SyncedBlock>,
+) -> Result<usize, CommsRpcConsensusSyncError> {
+    let checkpoint = load_sync_checkpoint(store)?;
+    let mut processor = BlockSyncProcessor::<TestConsensusSpec>::new(
+        Network::LocalNet,
+        epoch_manager.clone(),
+        store.clone(),
+        RoundRobinLeaderStrategy::new(),
+        signing_service.clone(),
+        &checkpoint,
+        BATCH_SIZE,
+    )?;
+
+    for synced in blocks {
+        if synced.block.height() <= checkpoint.height() {
+            continue;
+        }
+        processor.add_block(synced).await?;
+    }
+
+    processor.finish()
+}
+
+/// Returns the committed blocks in the same way that the block sync RPC streams them
+fn get_committed_blocks(store: &SqliteStateStore<TestAddress>) -> Vec<SyncedBlock> {
+    store
+        .with_read_tx(|tx| {
+            let mut blocks = vec![];
+            let mut current_block_id =
+                *Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default()).id();
+            loop {
+                let children = tx.blocks_get_all_by_parent(&current_block_id)?;
+                let Some(child) = children.into_iter().find(|b| b.is_committed()) else {
+                    break;
+                };
+
+                current_block_id = *child.id();
+                if child.is_dummy() {
+                    continue;
+                }
+
+                let all_qcs = child
+                    .commands()
+                    .iter()
+                    .filter_map(|cmd| cmd.transaction())
+                    .flat_map(|transaction| transaction.evidence.qc_ids_iter())
+                    .collect::<HashSet<_>>();
+                blocks.push(SyncedBlock {
+                    qcs: QuorumCertificate::get_all(tx, all_qcs)?,
+                    updates: child.get_substate_updates(tx)?,
+                    transactions: child.get_transactions(tx)?,
+                    block: rebuild_block(&child, child.total_leader_fee()),
+                });
+            }
+            Ok::<_, StorageError>(blocks)
+        })
+        .unwrap()
+}
+
+/// Rebuilds the block as it is received over the wire, keeping the original signature
+fn rebuild_block(block: &Block, total_leader_fee: u64) -> Block {
+    Block::new(
+        block.network(),
+        *block.parent(),
+        block.justify().clone(),
+        block.height(),
+        block.epoch(),
+        block.shard(),
+        block.proposed_by().clone(),
+        block.commands().clone(),
+        *block.merkle_root(),
+        total_leader_fee,
+        block.foreign_indexes().clone(),
+        block.get_signature().cloned(),
+        block.timestamp(),
+        block.base_layer_block_height(),
+        *block.base_layer_block_hash(),
+    )
+}
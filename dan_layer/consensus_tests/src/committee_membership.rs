@@ -0,0 +1,131 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use tari_common_types::types::{FixedHash, PrivateKey, PublicKey};
+use tari_consensus::{block_validations::check_membership_proof, hotstuff::ProposalValidationError};
+use tari_crypto::keys::{PublicKey as _, SecretKey};
+use tari_dan_common_types::{
+    committee::Committee,
+    committee_membership::{build_validator_node_bmt, CommitteeMembershipProof},
+    shard::Shard,
+    Epoch,
+};
+use tari_epoch_manager::EpochManagerReader;
+use tokio::sync::broadcast;
+
+use crate::support::{TestAddress, TestEpochManager};
+
+#[tokio::test]
+async fn it_accepts_a_valid_membership_proof() {
+    let (epoch_manager, committees) = create_epoch_manager().await;
+    let root = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+
+    for (shard, committee) in &committees {
+        for (address, public_key) in committee {
+            let proof = epoch_manager
+                .get_committee_membership_proof(Epoch(1), public_key)
+                .await
+                .unwrap();
+            assert_eq!(proof.epoch(), Epoch(1));
+            assert_eq!(proof.shard(), *shard);
+            assert_eq!(proof.public_key(), public_key);
+            assert!(proof.verify(Epoch(1), &root));
+            check_membership_proof(address, Epoch(1), &proof, &root).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn it_rejects_a_membership_proof_for_the_wrong_epoch() {
+    let (epoch_manager, committees) = create_epoch_manager().await;
+    let root = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+    let (address, public_key) = committees[&Shard::from(0)].iter().next().unwrap();
+
+    // A valid proof for epoch 1 cannot be used for epoch 2
+    let proof = epoch_manager
+        .get_committee_membership_proof(Epoch(1), public_key)
+        .await
+        .unwrap();
+    assert!(!proof.verify(Epoch(2), &root));
+    let err = check_membership_proof(address, Epoch(2), &proof, &root).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidMembershipProof { .. }));
+
+    // A proof from the epoch 1 tree that claims to be for epoch 2 does not match the epoch 2 root, because a validator
+    // joined in epoch 2
+    let mut epoch2_committees = committees.clone();
+    epoch2_committees
+        .get_mut(&Shard::from(1))
+        .unwrap()
+        .members
+        .push(create_member("new"));
+    let epoch2_root = bmt_root(&epoch2_committees);
+    assert_ne!(epoch2_root, root);
+    let epoch1_bmt = build_validator_node_bmt(ordered_leaves(&committees));
+    let relabelled = CommitteeMembershipProof::generate(&epoch1_bmt, Epoch(2), Shard::from(0), public_key.clone())
+        .expect("validator is in the epoch 1 tree");
+    assert!(!relabelled.verify(Epoch(2), &epoch2_root));
+    let err = check_membership_proof(address, Epoch(2), &relabelled, &epoch2_root).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidMembershipProof { .. }));
+}
+
+#[tokio::test]
+async fn it_rejects_a_membership_proof_forged_by_a_non_member() {
+    let (epoch_manager, committees) = create_epoch_manager().await;
+    let root = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+    let (address, public_key) = create_member("forger");
+
+    // The non-member is not in the real tree so cannot generate a proof from it
+    let bmt = build_validator_node_bmt(ordered_leaves(&committees));
+    assert!(CommitteeMembershipProof::generate(&bmt, Epoch(1), Shard::from(0), public_key.clone()).is_none());
+
+    // A member cannot claim to be in another committee
+    let (_, member_public_key) = committees[&Shard::from(0)].iter().next().unwrap();
+    assert!(CommitteeMembershipProof::generate(&bmt, Epoch(1), Shard::from(1), member_public_key.clone()).is_none());
+
+    // A proof from a tree that includes the non-member does not match the real root
+    let mut forged_committees = committees.clone();
+    forged_committees
+        .get_mut(&Shard::from(0))
+        .unwrap()
+        .members
+        .push((address.clone(), public_key.clone()));
+    let forged_bmt = build_validator_node_bmt(ordered_leaves(&forged_committees));
+    let forged = CommitteeMembershipProof::generate(&forged_bmt, Epoch(1), Shard::from(0), public_key).unwrap();
+    assert!(!forged.verify(Epoch(1), &root));
+    let err = check_membership_proof(&address, Epoch(1), &forged, &root).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidMembershipProof { .. }));
+}
+
+async fn create_epoch_manager() -> (TestEpochManager, HashMap<Shard, Committee<TestAddress>>) {
+    let (tx_events, _) = broadcast::channel(10);
+    let epoch_manager = TestEpochManager::new(tx_events);
+    let committee0 = Committee::new(vec![create_member("1"), create_member("2")]);
+    let committee1 = Committee::new(vec![create_member("3"), create_member("4")]);
+    let committees = HashMap::from([(Shard::from(0), committee0), (Shard::from(1), committee1)]);
+    epoch_manager.add_committees(committees.clone()).await;
+    (epoch_manager, committees)
+}
+
+fn create_member(name: &str) -> (TestAddress, PublicKey) {
+    let mut bytes = [0u8; 64];
+    bytes[0..name.len()].copy_from_slice(name.as_bytes());
+    let secret_key = PrivateKey::from_uniform_bytes(&bytes).unwrap();
+    (TestAddress::new(name), PublicKey::from_secret_key(&secret_key))
+}
+
+/// Returns the BMT leaves for the committees in a deterministic order
+fn ordered_leaves(committees: &HashMap<Shard, Committee<TestAddress>>) -> Vec<(&PublicKey, Shard)> {
+    let mut leaves = committees
+        .iter()
+        .flat_map(|(shard, committee)| committee.public_keys().map(move |public_key| (public_key, *shard)))
+        .collect::<Vec<_>>();
+    leaves.sort();
+    leaves
+}
+
+fn bmt_root(committees: &HashMap<Shard, Committee<TestAddress>>) -> FixedHash {
+    let bmt = build_validator_node_bmt(ordered_leaves(committees));
+    FixedHash::try_from(bmt.get_merkle_root()).unwrap()
+}
@@ -8,16 +8,47 @@
 //! Use `Test::builder().debug_sql("/tmp/test{}.db")...` to create a database file for each validator
 //! where {} is replaced with the node address.
 
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use tari_consensus::hotstuff::HotStuffError;
-use tari_dan_common_types::{optional::Optional, Epoch, NodeHeight};
+use tari_common_types::types::FixedHash;
+use tari_consensus::{
+    hotstuff::{HotStuffError, HotstuffEvent},
+    messages::HotstuffMessage,
+    traits::Clock,
+};
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, Command, Decision},
+    consensus_models::{
+        vote_signature_challenge,
+        Block,
+        BlockId,
+        Command,
+        CommittedBlockDiff,
+        Decision,
+        EquivocationEvidence,
+        ForeignProposal,
+        ForeignReceiveCounters,
+        GenesisConfig,
+        ProposerEquivocation,
+        QcTiming,
+        TransactionRecord,
+    },
     StateStore,
     StateStoreReadTransaction,
+    StorageError,
 };
+use tari_engine_types::{commit_result::RejectReason, substate::SubstateId};
+use tari_epoch_manager::EpochManagerReader;
+use tari_template_lib::models::ComponentAddress;
 use tari_transaction::{SubstateRequirement, Transaction};
+use transaction_generator::transaction_builders::synthetic::{TransactionGenerator, TransactionGeneratorConfig};
 
 use crate::support::{
     build_transaction,
@@ -78,6 +109,55 @@ async fn single_transaction() {
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn block_committed_hook_fires_once_per_committed_block() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2", "3"]).start().await;
+    test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(10) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    let validators = test
+        .validators()
+        .map(|v| (v.address.clone(), v.hooks.clone(), v.state_store.clone()))
+        .collect::<Vec<_>>();
+    // Shut down first so that no hooks are called while the blocks are checked
+    test.assert_clean_shutdown().await;
+
+    for (address, hooks, state_store) in validators {
+        let hooked = hooks.committed_blocks();
+        let unique = hooked.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), hooked.len(), "{} called the hook more than once for a block", address);
+
+        let committed = state_store
+            .with_read_tx(|tx| {
+                let mut committed = HashSet::new();
+                let mut block = Block::get_tip(tx)?;
+                while !block.is_genesis() {
+                    if block.is_committed() {
+                        committed.insert(*block.id());
+                    }
+                    block = block.get_parent(tx)?;
+                }
+                Ok::<_, HotStuffError>(committed)
+            })
+            .unwrap();
+        assert!(!committed.is_empty());
+        assert_eq!(unique, committed, "{} hooked blocks do not match the committed blocks", address);
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn propose_blocks_with_queued_up_transactions_until_all_committed() {
     setup_logger();
@@ -191,6 +271,49 @@ async fn node_requests_missing_transaction_from_local_leader() {
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn node_requests_missing_transactions_again_if_the_response_is_lost() {
+    setup_logger();
+    let dropped_response = Arc::new(AtomicBool::new(false));
+    let mut test = Test::builder()
+        .with_message_filter(Box::new({
+            let dropped_response = dropped_response.clone();
+            move |_: &TestAddress, _: &TestAddress, msg: &HotstuffMessage| {
+                // Drop the first response to a missing transactions request
+                !matches!(msg, HotstuffMessage::RequestedTransaction(_)) ||
+                    dropped_response.swap(true, Ordering::SeqCst)
+            }
+        }))
+        .add_committee(0, vec!["1", "2"])
+        .start()
+        .await;
+    for _ in 0..10 {
+        test.send_transaction_to(&TestAddress::new("2"), Decision::Commit, 1, 5)
+            .await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    // Let the retry timeout elapse while node "1" waits for the transactions
+    let advance_clock = test.clock().advance_every(Duration::from_millis(100));
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height >= NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+    advance_clock.abort();
+
+    assert!(dropped_response.load(Ordering::SeqCst));
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn multi_validator_propose_blocks_with_new_transactions_until_all_committed() {
     setup_logger();
@@ -226,6 +349,162 @@ async fn multi_validator_propose_blocks_with_new_transactions_until_all_committe
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn consensus_status_is_self_consistent() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "2", "3"])
+        .start()
+        .await;
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 2).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    let mut statuses = vec![];
+    for v in test.validators() {
+        statuses.push((v.address.clone(), v.get_consensus_status().await));
+    }
+
+    for (address, status) in &statuses {
+        assert_eq!(status.epoch, Epoch(0), "{} is in the wrong epoch", address);
+        assert!(
+            status.leaf_block_height >= status.high_qc_block_height,
+            "{} high QC is above the leaf block: {:?}",
+            address,
+            status
+        );
+        assert!(
+            status.high_qc_block_height >= status.locked_block_height,
+            "{} locked block is above the high QC: {:?}",
+            address,
+            status
+        );
+        let last_voted_height = status.last_voted_height.expect("validator has not voted");
+        assert!(
+            last_voted_height <= status.leaf_block_height,
+            "{} voted above the leaf block: {:?}",
+            address,
+            status
+        );
+        assert!(
+            status.current_view_timeout > Duration::ZERO,
+            "{} pacemaker has not started",
+            address
+        );
+        assert!(
+            status
+                .transaction_pool_stage_counts
+                .iter()
+                .all(|(_, count)| *count == 0),
+            "{} reports transactions in an empty pool: {:?}",
+            address,
+            status.transaction_pool_stage_counts
+        );
+    }
+
+    // At most one of the validators with the same leaf block believes that it is the leader for the next block
+    for (_, status) in &statuses {
+        let num_leaders = statuses
+            .iter()
+            .filter(|(_, s)| s.leaf_block_id == status.leaf_block_id && s.is_leader)
+            .count();
+        assert!(
+            num_leaders <= 1,
+            "{} leaders for the block after {}",
+            num_leaders,
+            status.leaf_block_id
+        );
+    }
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn decided_events_are_published_before_the_block_commit() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1"])
+        .start()
+        .await;
+    let tx1 = build_transaction(Decision::Commit, 1, 1, 1);
+    test.send_transaction_to_destination(TestNetworkDestination::All, tx1.clone())
+        .await;
+    test.start_epoch(Epoch(0)).await;
+
+    let mut events = vec![];
+    let mut is_decided = false;
+    let committed_block_id = loop {
+        let (address, event) = tokio::time::timeout(Duration::from_secs(60), test.on_hotstuff_event())
+            .await
+            .expect("Timeout waiting for Hotstuff event");
+        match &event {
+            HotstuffEvent::Failure { message } => panic!("[{}] Consensus failure: {}", address, message),
+            HotstuffEvent::Decided { tx_id, .. } if tx_id == tx1.id() => is_decided = true,
+            HotstuffEvent::BlockCommitted { block_id, .. } if is_decided => {
+                let block_id = *block_id;
+                events.push(event);
+                break block_id;
+            },
+            _ => {},
+        }
+        events.push(event);
+
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height > NodeHeight(10) {
+            panic!("Transaction not committed after {} blocks", leaf.height);
+        }
+    };
+
+    let decided = events
+        .iter()
+        .filter_map(|e| match e {
+            HotstuffEvent::Decided { tx_id, decision } => Some((*tx_id, *decision)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(decided, vec![(*tx1.id(), Decision::Commit)]);
+
+    // The decision is published immediately before the commit of the block that contains the transaction
+    let n = events.len();
+    assert!(matches!(&events[n - 2], HotstuffEvent::Decided { tx_id, .. } if tx_id == tx1.id()));
+    let committed_block = test
+        .get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| Block::get(tx, &committed_block_id))
+        .unwrap();
+    assert!(committed_block.all_transaction_ids().any(|id| id == tx1.id()));
+
+    let committed_heights = events
+        .iter()
+        .filter_map(|e| match e {
+            HotstuffEvent::BlockCommitted { height, .. } => Some(*height),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        committed_heights.windows(2).all(|w| w[0] < w[1]),
+        "Blocks were not committed in order: {:?}",
+        committed_heights
+    );
+
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn multi_shard_propose_blocks_with_new_transactions_until_all_committed() {
     setup_logger();
@@ -313,6 +592,193 @@ async fn foreign_shard_decides_to_abort() {
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transactions_that_expire_before_they_are_proposed_are_aborted() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "3", "4"])
+        .add_committee(1, vec!["2", "5", "6"])
+        .start()
+        .await;
+
+    // The transactions are in the pool before the epoch changes
+    let unbounded = build_transaction(Decision::Commit, 1, 5, 2);
+    let outputs = build_transaction(Decision::Commit, 1, 5, 2)
+        .resulting_outputs()
+        .to_vec();
+    let tx = Transaction::builder()
+        .with_max_epoch(Some(Epoch(0)))
+        .sign(&Default::default())
+        .build();
+    let expired = build_transaction_from(tx, Decision::Commit, 1, outputs);
+    test.send_transaction_to_destination(TestNetworkDestination::All, unbounded.clone())
+        .await;
+    test.send_transaction_to_destination(TestNetworkDestination::All, expired.clone())
+        .await;
+
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        let leaf2 = test.get_validator(&TestAddress::new("2")).get_leaf_block();
+        if leaf1.height > NodeHeight(40) || leaf2.height > NodeHeight(40) {
+            panic!(
+                "Not all transaction committed after {}/{} blocks",
+                leaf1.height, leaf2.height,
+            );
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_have_decision(unbounded.id(), Decision::Commit)
+        .await;
+    test.assert_all_validators_have_decision(expired.id(), Decision::Abort)
+        .await;
+    test.with_all_validators(|v| {
+        let result = v
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::get(tx, expired.id()))
+            .unwrap()
+            .result
+            .unwrap_or_else(|| panic!("Validator {} has no result for the expired transaction", v.address));
+        assert!(
+            matches!(result.finalize.result.reject(), Some(RejectReason::EpochExpired(_))),
+            "Validator {} did not abort the transaction because it expired: {}",
+            v.address,
+            result.finalize.result
+        );
+    });
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn foreign_proposals_are_delivered_after_committee_comes_back_online() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "3", "4"])
+        .add_committee(1, vec!["2", "5", "6"])
+        .start()
+        .await;
+
+    test.network().go_offline(TestNetworkDestination::Shard(1)).await;
+    let tx1 = build_transaction(Decision::Commit, 1, 5, 2);
+    test.send_transaction_to_destination(TestNetworkDestination::All, tx1.clone())
+        .await;
+    test.start_epoch(Epoch(0)).await;
+
+    // Both committees continue to make progress while foreign proposals are not delivered
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        if committed_height > NodeHeight(5) {
+            break;
+        }
+    }
+    assert!(!test.is_transaction_pool_empty());
+
+    test.network().go_online(&TestNetworkDestination::Shard(1)).await;
+    // Skip the retry backoff
+    test.clock().advance(60);
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        let leaf2 = test.get_validator(&TestAddress::new("2")).get_leaf_block();
+        if leaf1.height > NodeHeight(50) || leaf2.height > NodeHeight(50) {
+            panic!(
+                "Not all transaction committed after {}/{} blocks",
+                leaf1.height, leaf2.height,
+            );
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    log::info!("total messages sent: {}", test.network().total_messages_sent());
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn replayed_foreign_proposals_are_not_applied_twice() {
+    const REPLAY_DELAY: Duration = Duration::from_millis(500);
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "3", "4"])
+        .add_committee(1, vec!["2", "5", "6"])
+        .with_foreign_proposal_replay(REPLAY_DELAY)
+        .start()
+        .await;
+
+    let tx1 = build_transaction(Decision::Commit, 1, 5, 2);
+    test.send_transaction_to_destination(TestNetworkDestination::All, tx1.clone())
+        .await;
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        let leaf2 = test.get_validator(&TestAddress::new("2")).get_leaf_block();
+        if leaf1.height > NodeHeight(40) || leaf2.height > NodeHeight(40) {
+            panic!(
+                "Not all transaction committed after {}/{} blocks",
+                leaf1.height, leaf2.height,
+            );
+        }
+    }
+
+    // Wait for the proposals to be replayed after they have been applied and the transaction has been finalized
+    tokio::time::sleep(REPLAY_DELAY * 2).await;
+
+    test.assert_all_validators_committed();
+    let foreign_shards = [("1", 1), ("3", 1), ("4", 1), ("2", 0), ("5", 0), ("6", 0)];
+    for (address, foreign_shard) in foreign_shards {
+        let foreign_shard = Shard::from(foreign_shard);
+        let (num_applied, counters) = test
+            .get_validator(&TestAddress::new(address))
+            .state_store
+            .with_read_tx(|tx| {
+                Ok::<_, StorageError>((
+                    ForeignProposal::count_applied(tx, foreign_shard)?,
+                    ForeignReceiveCounters::get_or_default(tx)?,
+                ))
+            })
+            .unwrap();
+        assert!(
+            num_applied > 0,
+            "Validator {} did not apply any foreign proposals",
+            address
+        );
+        assert_eq!(
+            counters.get_count(&foreign_shard),
+            num_applied,
+            "Validator {} counted a foreign proposal more than once",
+            address
+        );
+    }
+
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn output_conflict_abort() {
     setup_logger();
@@ -384,6 +850,8 @@ async fn leader_failure_node_goes_down() {
         test.send_transaction_to_all(Decision::Commit, 1, 2).await;
     }
     test.start_epoch(Epoch(0)).await;
+    // Leader timeouts are measured on the consensus clock
+    let advance_clock = test.clock().advance_every(Duration::from_millis(100));
 
     loop {
         let (_, _, committed_height) = test.on_block_committed().await;
@@ -395,10 +863,11 @@ async fn leader_failure_node_goes_down() {
                 .await;
         }
 
+        // Continue until the failed leader has been replaced by a dummy block on every remaining validator
         if test.validators().filter(|vn| vn.address != failure_node).all(|v| {
             let c = v.get_transaction_pool_count();
             log::info!("{} has {} transactions in pool", v.address, c);
-            c == 0
+            c == 0 && !v.hooks.dummy_blocks().is_empty()
         }) {
             break;
         }
@@ -415,30 +884,160 @@ async fn leader_failure_node_goes_down() {
         assert!(v.has_committed_substates(), "Validator {} did not commit", v.address);
     });
 
+    let validators = test
+        .validators()
+        .filter(|vn| vn.address != failure_node)
+        .map(|v| (v.address.clone(), v.hooks.clone(), v.state_store.clone()))
+        .collect::<Vec<_>>();
     log::info!("total messages sent: {}", test.network().total_messages_sent());
+    advance_clock.abort();
+    // Shut down first so that no hooks are called while the blocks are checked
     test.assert_clean_shutdown().await;
+
+    for (address, hooks, state_store) in validators {
+        let leader_timeouts = hooks.leader_timeouts();
+        assert!(!leader_timeouts.is_empty(), "{} did not report a leader timeout", address);
+        for (new_height, round) in leader_timeouts {
+            assert!(new_height > NodeHeight::zero(), "{} reported a leader timeout at height 0", address);
+            assert!(round >= 1, "{} reported a leader timeout in round {}", address, round);
+        }
+
+        let hooked = hooks.dummy_blocks().into_iter().collect::<HashSet<_>>();
+        let (tip_height, dummy_heights) = state_store
+            .with_read_tx(|tx| {
+                let mut dummy_heights = HashSet::new();
+                let tip = Block::get_tip(tx)?;
+                let mut block = tip.clone();
+                while !block.is_genesis() {
+                    if block.is_dummy() {
+                        dummy_heights.insert(block.height());
+                    }
+                    block = block.get_parent(tx)?;
+                }
+                Ok::<_, HotStuffError>((tip.height(), dummy_heights))
+            })
+            .unwrap();
+        assert!(
+            hooked.iter().all(|h| *h <= tip_height),
+            "{} reported a dummy block above the tip",
+            address
+        );
+        assert!(
+            dummy_heights.is_subset(&hooked),
+            "{} did not report all dummy blocks. Stored: {:?}, reported: {:?}",
+            address,
+            dummy_heights,
+            hooked
+        );
+    }
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
-#[ignore = "FIXME: this test is flaky"]
-async fn foreign_block_distribution() {
+async fn leader_failure_recovers_with_a_single_dummy_block_per_failed_leader() {
     setup_logger();
     let mut test = Test::builder()
         .with_test_timeout(Duration::from_secs(60))
-        .with_message_filter(Box::new(move |from: &TestAddress, to: &TestAddress, _| {
-            match from.0.as_str() {
-                // We filter our message from each leader to the foreign committees. So we will rely on other members of
-                // the local committees to send the message to the foreign committee members. And then on
-                // the distribution within the foreign committee.
-                "1" => *to == TestAddress::new("1") || *to == TestAddress::new("2") || *to == TestAddress::new("3"),
-                "4" => *to == TestAddress::new("4") || *to == TestAddress::new("5") || *to == TestAddress::new("6"),
-                "7" => *to == TestAddress::new("7") || *to == TestAddress::new("8") || *to == TestAddress::new("9"),
-                _ => true,
-            }
-        }))
-        .add_committee(0, vec!["1", "2", "3"])
-        .add_committee(1, vec!["4", "5", "6"])
-        .add_committee(2, vec!["7", "8", "9"])
+        .add_committee(0, vec!["1", "2", "3", "4", "5"])
+        .start()
+        .await;
+
+    let failure_node = TestAddress::new("2");
+    let failure_node_public_key = test
+        .get_validator(&failure_node)
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+
+    for _ in 0..10 {
+        test.send_transaction_to_all(Decision::Commit, 1, 2).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+    // Leader timeouts are measured on the consensus clock
+    let advance_clock = test.clock().advance_every(Duration::from_millis(100));
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if committed_height == NodeHeight(1) {
+            log::info!("😴 Node 2 goes offline");
+            test.network()
+                .go_offline(TestNetworkDestination::Address(failure_node.clone()))
+                .await;
+        }
+
+        if test
+            .validators()
+            .filter(|vn| vn.address != failure_node)
+            .all(|v| v.get_transaction_pool_count() == 0 && !v.hooks.dummy_blocks().is_empty())
+        {
+            break;
+        }
+
+        if committed_height > NodeHeight(100) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height_except(&[failure_node.clone()])
+        .await;
+
+    let state_stores = test
+        .validators()
+        .filter(|vn| vn.address != failure_node)
+        .map(|v| (v.address.clone(), v.state_store.clone()))
+        .collect::<Vec<_>>();
+    advance_clock.abort();
+    test.assert_clean_shutdown().await;
+
+    // The next leader proposes as soon as f + 1 NEWVIEWs are received, so only the view of the failed leader is
+    // replaced by a dummy block. A dummy block for any other leader means that the view change itself timed out.
+    for (address, state_store) in state_stores {
+        let dummy_blocks = state_store
+            .with_read_tx(|tx| {
+                let mut dummy_blocks = vec![];
+                let mut block = Block::get_tip(tx)?;
+                while !block.is_genesis() {
+                    if block.is_dummy() {
+                        dummy_blocks.push((block.height(), block.proposed_by().clone()));
+                    }
+                    block = block.get_parent(tx)?;
+                }
+                Ok::<_, HotStuffError>(dummy_blocks)
+            })
+            .unwrap();
+        assert!(!dummy_blocks.is_empty(), "{} has no dummy blocks", address);
+        for (height, proposed_by) in dummy_blocks {
+            assert_eq!(
+                proposed_by, failure_node_public_key,
+                "{} has a dummy block at height {} for a leader that did not fail",
+                address, height
+            );
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "FIXME: this test is flaky"]
+async fn foreign_block_distribution() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_message_filter(Box::new(move |from: &TestAddress, to: &TestAddress, _| {
+            match from.0.as_str() {
+                // We filter our message from each leader to the foreign committees. So we will rely on other members of
+                // the local committees to send the message to the foreign committee members. And then on
+                // the distribution within the foreign committee.
+                "1" => *to == TestAddress::new("1") || *to == TestAddress::new("2") || *to == TestAddress::new("3"),
+                "4" => *to == TestAddress::new("4") || *to == TestAddress::new("5") || *to == TestAddress::new("6"),
+                "7" => *to == TestAddress::new("7") || *to == TestAddress::new("8") || *to == TestAddress::new("9"),
+                _ => true,
+            }
+        }))
+        .add_committee(0, vec!["1", "2", "3"])
+        .add_committee(1, vec!["4", "5", "6"])
+        .add_committee(2, vec!["7", "8", "9"])
         .start()
         .await;
     for _ in 0..20 {
@@ -537,3 +1136,565 @@ async fn deferred_execution() {
 
     test.assert_clean_shutdown().await;
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn deferred_execution_aborts_a_transaction_whose_input_never_exists() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2", "3"]).start().await;
+    // The input is never created so the transaction can never be executed
+    let missing_input = SubstateRequirement::new(SubstateId::Component(ComponentAddress::from_array([0xff; 28])), None);
+    let tx = build_transaction_with_inputs(Decision::Deferred, 1, [missing_input]);
+    let tx_id = *tx.id();
+    test.send_transaction_to_destination(TestNetworkDestination::All, tx)
+        .await;
+
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(30) {
+            panic!("Deferred transaction was not aborted after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_have_decision(&tx_id, Decision::Abort).await;
+    test.with_all_validators(|v| {
+        let result = v
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::get(tx, &tx_id))
+            .unwrap()
+            .result
+            .unwrap_or_else(|| panic!("Validator {} has no result for the deferred transaction", v.address));
+        assert!(
+            matches!(result.finalize.result.reject(), Some(RejectReason::ExecutionFailure(_))),
+            "Validator {} did not abort the transaction because it failed to execute: {}",
+            v.address,
+            result.finalize.result
+        );
+    });
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn generated_transactions() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2", "3"]).start().await;
+    let generator = TransactionGenerator::new(TransactionGeneratorConfig {
+        seed: 1,
+        num_accounts: 20,
+        num_instructions: 1..=3,
+        ..Default::default()
+    })
+    .unwrap();
+    test.send_generated_transactions(&generator, 20).await;
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(30) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn proposed_blocks_stay_within_the_max_block_size() {
+    const MAX_BLOCK_SIZE_BYTES: usize = 32 * 1024;
+
+    setup_logger();
+    let mut test = Test::builder()
+        .with_max_block_size_bytes(MAX_BLOCK_SIZE_BYTES)
+        .add_committee(0, vec!["1", "2"])
+        .start()
+        .await;
+    // Each transaction has evidence for 200 outputs, so only a few fit in a block
+    let mut transactions = (0..10)
+        .map(|_| build_transaction(Decision::Commit, 1, 200, 1))
+        .collect::<Vec<_>>();
+    // This transaction exceeds the block size limit on its own
+    transactions.push(build_transaction(Decision::Commit, 1, 1000, 1));
+    for transaction in &transactions {
+        test.send_transaction_to_destination(TestNetworkDestination::All, transaction.clone())
+            .await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(40) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let mut num_transactions = 0;
+            let mut parents = vec![BlockId::genesis()];
+            while let Some(parent) = parents.pop() {
+                for block in tx.blocks_get_all_by_parent(&parent)? {
+                    if block.id().is_genesis() {
+                        continue;
+                    }
+                    let transaction_commands = block
+                        .commands()
+                        .iter()
+                        .filter(|c| c.transaction().is_some())
+                        .collect::<Vec<_>>();
+                    let size = transaction_commands.iter().map(|c| c.estimated_size()).sum::<usize>();
+                    assert!(
+                        size <= MAX_BLOCK_SIZE_BYTES || transaction_commands.len() == 1,
+                        "Block {} has {} transaction commands with an estimated size of {} bytes",
+                        block,
+                        transaction_commands.len(),
+                        size
+                    );
+                    num_transactions += transaction_commands.len();
+                    parents.push(*block.id());
+                }
+            }
+            // No transaction was dropped
+            assert!(num_transactions >= transactions.len());
+            Ok::<_, StorageError>(())
+        })
+        .unwrap();
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn block_timestamps_follow_the_consensus_clock() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2", "3"]).start().await;
+    let start_time = test.clock().now();
+    test.start_epoch(Epoch(0)).await;
+
+    test.on_block_committed().await;
+    test.clock().advance(60);
+    let advanced_time = test.clock().now();
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        let tip = test
+            .get_validator(&TestAddress::new("1"))
+            .state_store
+            .with_read_tx(|tx| Block::get_tip(tx))
+            .unwrap();
+        if tip.timestamp() == advanced_time {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("No block with the advanced clock timestamp after {} blocks", committed_height);
+        }
+    }
+
+    test.get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let mut block = Block::get_tip(tx)?;
+            while !block.height().is_zero() {
+                let parent = block.get_parent(tx)?;
+                // Dummy blocks inherit the timestamp of their justify block
+                assert!(
+                    block.is_dummy() || (block.timestamp() >= start_time && block.timestamp() <= advanced_time),
+                    "Block {} timestamp {} is not from the test clock",
+                    block,
+                    block.timestamp()
+                );
+                if !parent.height().is_zero() {
+                    assert!(
+                        block.timestamp() >= parent.timestamp(),
+                        "Block {} timestamp is before its parent {}",
+                        block,
+                        parent
+                    );
+                }
+                block = parent;
+            }
+            Ok::<_, HotStuffError>(())
+        })
+        .unwrap();
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn chain_starts_from_genesis_config() {
+    setup_logger();
+    let genesis = GenesisConfig {
+        epoch: Epoch(5),
+        initial_state_root: FixedHash::from([1u8; 32]),
+        ..Default::default()
+    };
+    let mut test = Test::builder()
+        .with_genesis(genesis)
+        .add_committee(0, vec!["1", "2", "3"])
+        .start()
+        .await;
+    test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    test.start_epoch(Epoch(5)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(10) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    test.get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let zero_block = Block::get(tx, &BlockId::genesis())?;
+            assert_eq!(zero_block.epoch(), Epoch(5));
+            assert_eq!(*zero_block.merkle_root(), genesis.initial_state_root);
+
+            // Walk back to the first block, which is justified by the genesis QC
+            let mut block = Block::get_tip(tx)?;
+            while !block.parent().is_genesis() {
+                block = block.get_parent(tx)?;
+            }
+            assert_eq!(block.epoch(), Epoch(5));
+            assert_eq!(block.justify().genesis_hash(), Some(&genesis.calculate_hash(block.network())));
+            assert_eq!(block.justify().id(), zero_block.justify().id());
+            Ok::<_, HotStuffError>(())
+        })
+        .unwrap();
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn qc_timings_rank_a_delayed_voter_last() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_vote_delay("4", Duration::from_millis(300))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    let delayed_voter = test
+        .get_validator(&TestAddress::new("4"))
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+
+    // Votes are collected by the next leader, so the timings are spread over the committee
+    let mut timings = vec![];
+    test.with_all_validators(|v| {
+        timings.extend(
+            v.state_store
+                .with_read_tx(|tx| QcTiming::get_by_epoch(tx, Epoch(0)))
+                .unwrap(),
+        );
+    });
+    assert!(!timings.is_empty());
+    assert!(timings.iter().any(|t| t.quorum_reached_ms.is_some()));
+
+    let validators = QcTiming::summarize_by_validator(&timings);
+    assert_eq!(validators.len(), 4);
+    let latest = validators.last().unwrap();
+    assert_eq!(latest.public_key, delayed_voter);
+    assert!(latest.average_lateness_ms > validators[0].average_lateness_ms);
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn maintenance_mode_keeps_liveness_and_resumes_voting() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(120))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    let maintenance_node = TestAddress::new("4");
+    assert!(test.get_validator(&maintenance_node).maintenance_mode.enter());
+    let maintenance_node_pk = test
+        .get_validator(&maintenance_node)
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+
+    // Votes are collected by the next leader, so the timings recorded by all validators are checked
+    let count_votes_from_maintenance_node = |test: &Test| {
+        let mut count = 0;
+        test.with_all_validators(|v| {
+            count += v
+                .state_store
+                .with_read_tx(|tx| QcTiming::get_by_epoch(tx, Epoch(0)))
+                .unwrap()
+                .iter()
+                .flat_map(|t| &t.votes)
+                .filter(|vote| vote.public_key == maintenance_node_pk)
+                .count();
+        });
+        count
+    };
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        // The transaction pool of the node in maintenance mode only empties if it keeps processing blocks
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(50) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    assert!(test.get_validator(&maintenance_node).has_committed_substates());
+    assert_eq!(count_votes_from_maintenance_node(&test), 0);
+
+    log::info!("🔧 Node 4 resumes");
+    assert!(test.get_validator(&maintenance_node).maintenance_mode.resume());
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(100) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    assert!(count_votes_from_maintenance_node(&test) > 0);
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn committed_block_diffs_are_buffered_in_commit_order() {
+    setup_logger();
+    let mut test = Test::builder()
+        .add_committee(0, vec!["1", "2", "3"])
+        .with_committed_block_diff_retention(100)
+        .start()
+        .await;
+
+    let transaction = build_transaction(Decision::Commit, 1, 1, 1);
+    let transaction_id = *transaction.id();
+    test.send_transaction_to_destination(TestNetworkDestination::All, transaction)
+        .await;
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    test.with_all_validators(|v| {
+        let diffs = v
+            .state_store
+            .with_read_tx(|tx| CommittedBlockDiff::get_after(tx, None, 100))
+            .unwrap();
+        assert!(!diffs.is_empty());
+
+        // Sequence numbers are contiguous and each diff extends the previous committed block
+        for pair in diffs.windows(2) {
+            assert_eq!(pair[1].sequence, pair[0].sequence + 1);
+            assert_eq!(pair[1].block.parent(), pair[0].block.id());
+        }
+
+        let diff = diffs
+            .iter()
+            .find(|diff| {
+                diff.block
+                    .all_accepted_transactions_ids()
+                    .any(|id| *id == transaction_id)
+            })
+            .expect("No committed diff for the block that accepted the transaction");
+        assert!(diff
+            .changes
+            .iter()
+            .any(|change| change.is_up() && change.transaction_id() == transaction_id));
+    });
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn equivocating_leader_is_recorded_by_all_validators() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_equivocating_leader("1")
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    let mut max_height = NodeHeight(0);
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        max_height = max_height.max(committed_height);
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    let leader = test
+        .get_validator(&TestAddress::new("1"))
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+
+    test.with_all_validators(|v| {
+        v.state_store
+            .with_read_tx(|tx| {
+                // The honest validators detect the equivocation and the leader learns of it through gossip
+                assert!(ProposerEquivocation::count(tx)? >= 1);
+
+                // The conflicting proposals are never stored, so they cannot be voted for or committed
+                for height in 1..=max_height.as_u64() {
+                    let blocks = Block::get_all_by_proposer_at_height(tx, Epoch(0), NodeHeight(height), &leader)?;
+                    assert!(blocks.len() <= 1);
+                }
+                Ok::<_, anyhow::Error>(())
+            })
+            .unwrap();
+    });
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn equivocating_voter_is_recorded_by_the_leader() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_equivocating_voter("2")
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    let voter = test
+        .get_validator(&TestAddress::new("2"))
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+
+    let mut num_evidence = 0;
+    test.with_all_validators(|v| {
+        let all_evidence = v
+            .state_store
+            .with_read_tx(|tx| EquivocationEvidence::get_all(tx))
+            .unwrap();
+        for evidence in all_evidence {
+            assert!(evidence.is_conflicting(), "{} is not conflicting", evidence);
+            assert_eq!(*evidence.voter(), voter);
+
+            // The evidence contains both signed votes
+            let first = evidence.first_vote();
+            let second = evidence.second_vote();
+            assert_ne!(first.signature().signature, second.signature().signature);
+            for vote in [first, second] {
+                assert_eq!(vote.signature().public_key, voter);
+                let challenge = vote_signature_challenge(&vote.sender_leaf_hash, &vote.block_id, &vote.decision);
+                assert!(vote.signature().verify(challenge), "Invalid signature in {}", evidence);
+            }
+            num_evidence += 1;
+        }
+    });
+    assert!(num_evidence > 0, "No validator recorded the equivocating voter");
+
+    test.assert_clean_shutdown().await;
+}
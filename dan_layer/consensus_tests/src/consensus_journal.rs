@@ -0,0 +1,231 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, fs::OpenOptions, io::Write};
+
+use tari_common::configuration::Network;
+use tari_consensus::journal::{
+    journal_files,
+    read_journal,
+    JournalConfig,
+    JournalEntry,
+    JournalError,
+    JournalReader,
+    JournalRecord,
+    JournalWriter,
+};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, BlockId, Decision, GenesisConfig},
+    StateStore,
+    StateStoreReadTransaction,
+    StorageError,
+};
+use tari_state_store_sqlite::SqliteStateStore;
+
+use crate::support::{build_transaction, logging::setup_logger, Test, TestAddress, TestNetworkDestination};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn it_journals_the_decision_sequence() {
+    setup_logger();
+    let journal_dir = tempfile::tempdir().unwrap();
+    let mut test = Test::builder()
+        .add_committee(0, vec!["1", "2", "3"])
+        .with_consensus_journal(journal_dir.path())
+        .start()
+        .await;
+
+    let mut transaction_ids = Vec::new();
+    for _ in 0..3 {
+        // Single committee
+        let transaction = build_transaction(Decision::Commit, 1, 1, 1);
+        transaction_ids.push(*transaction.id());
+        test.send_transaction_to_destination(TestNetworkDestination::All, transaction)
+            .await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    let address = TestAddress::new("1");
+    let store = test.get_validator(&address).state_store().clone();
+    test.assert_clean_shutdown().await;
+    let committed_blocks = get_committed_block_ids(&store);
+
+    let records = read_journal(journal_dir.path().join(&address.0))
+        .unwrap()
+        .into_iter()
+        .map(|record| record.entry)
+        .collect::<Vec<_>>();
+    assert!(!records.is_empty());
+
+    // Commits are journalled in the same order as the chain
+    let journalled_commits = records
+        .iter()
+        .filter_map(|entry| match entry {
+            JournalEntry::BlockCommitted { block_id, .. } => Some(*block_id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(journalled_commits, committed_blocks);
+
+    // Every proposal is accepted before it is voted on, and every block is locked before it is committed
+    let mut accepted = HashMap::new();
+    let mut locked = HashMap::new();
+    let mut last_locked_height = NodeHeight(0);
+    let mut num_votes = 0;
+    for entry in &records {
+        match entry {
+            JournalEntry::ProposalAccepted { block_id, height, .. } => {
+                accepted.insert(*block_id, *height);
+            },
+            JournalEntry::ProposalRejected { block_id, reason, .. } => {
+                panic!("Unexpected rejected proposal {}: {}", block_id, reason);
+            },
+            JournalEntry::VoteCast {
+                block_id,
+                height,
+                basis,
+                ..
+            } => {
+                assert_eq!(accepted.get(block_id), Some(height), "Vote cast before acceptance");
+                assert!(basis.justify_height < *height);
+                num_votes += 1;
+            },
+            JournalEntry::LockAdvanced {
+                block_id,
+                height,
+                previous_height,
+                ..
+            } => {
+                assert!(*height > *previous_height);
+                assert!(*height > last_locked_height, "Lock did not advance");
+                last_locked_height = *height;
+                locked.insert(*block_id, *height);
+            },
+            JournalEntry::BlockCommitted { block_id, height, .. } => {
+                assert_eq!(locked.get(block_id), Some(height), "Commit before lock");
+            },
+            JournalEntry::VoteWithheld { .. } | JournalEntry::ForkSwitched { .. } => {},
+        }
+    }
+    assert!(num_votes > 0);
+
+    // Each transaction can be traced to the vote and commit that finalized it
+    for id in &transaction_ids {
+        let entries = records
+            .iter()
+            .filter(|entry| entry.involves_transaction(id))
+            .collect::<Vec<_>>();
+        let is_voted = entries
+            .iter()
+            .any(|entry| matches!(entry, JournalEntry::VoteCast { .. }));
+        assert!(is_voted);
+        let commit = entries
+            .iter()
+            .find_map(|entry| match entry {
+                JournalEntry::BlockCommitted { block_id, .. } => Some(*block_id),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Transaction {} was not committed", id));
+        assert!(records
+            .iter()
+            .filter(|entry| entry.involves_block(&commit))
+            .any(|entry| matches!(entry, JournalEntry::ProposalAccepted { .. })));
+    }
+}
+
+#[test]
+fn it_rotates_the_journal_and_reads_back_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut writer = JournalWriter::new(JournalConfig {
+        directory: dir.path().to_path_buf(),
+        max_file_size: 1024,
+        max_rotated_files: 2,
+    });
+
+    let records = (0..20u64).map(create_record).collect::<Vec<_>>();
+    for record in &records {
+        writer.append(record).unwrap();
+    }
+
+    let files = journal_files(dir.path());
+    assert_eq!(files.len(), 3);
+    for file in &files {
+        assert!(file.metadata().unwrap().len() <= 1024);
+    }
+
+    // The oldest records were deleted with the oldest rotated file
+    let read = read_journal(dir.path()).unwrap();
+    assert!(read.len() < records.len());
+    assert_eq!(read, records[records.len() - read.len()..]);
+}
+
+#[test]
+fn it_reports_a_truncated_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = JournalConfig {
+        directory: dir.path().to_path_buf(),
+        max_file_size: 1024 * 1024,
+        max_rotated_files: 1,
+    };
+    let mut writer = JournalWriter::new(config);
+    writer.append(&create_record(1)).unwrap();
+    writer.append(&create_record(2)).unwrap();
+
+    // Simulate a crash part way through writing a record
+    let path = journal_files(dir.path()).pop().unwrap();
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(&100u32.to_le_bytes()).unwrap();
+    file.write_all(&[1, 2, 3]).unwrap();
+
+    let mut reader = JournalReader::open(&path).unwrap();
+    assert_eq!(reader.read_record().unwrap(), Some(create_record(1)));
+    assert_eq!(reader.read_record().unwrap(), Some(create_record(2)));
+    let err = reader.read_record().unwrap_err();
+    assert!(matches!(err, JournalError::TruncatedRecord {
+        expected: 100,
+        remaining: 3,
+        ..
+    }));
+}
+
+fn create_record(n: u64) -> JournalRecord {
+    JournalRecord {
+        timestamp: n,
+        entry: JournalEntry::LockAdvanced {
+            block_id: BlockId::genesis(),
+            height: NodeHeight(n),
+            previous_block_id: BlockId::genesis(),
+            previous_height: NodeHeight(n.saturating_sub(1)),
+        },
+    }
+}
+
+/// Returns the ids of the committed blocks after the zero block in chain order
+fn get_committed_block_ids(store: &SqliteStateStore<TestAddress>) -> Vec<BlockId> {
+    store
+        .with_read_tx(|tx| {
+            let mut block_ids = vec![];
+            let mut current_block_id =
+                *Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default()).id();
+            loop {
+                let children = tx.blocks_get_all_by_parent(&current_block_id)?;
+                let Some(child) = children.into_iter().find(|b| b.is_committed()) else {
+                    break;
+                };
+                current_block_id = *child.id();
+                block_ids.push(current_block_id);
+            }
+            Ok::<_, StorageError>(block_ids)
+        })
+        .unwrap()
+}
@@ -0,0 +1,214 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::BTreeSet, time::Duration};
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    block_validations::check_epoch_checkpoint,
+    hotstuff::{HotStuffError, ProposalValidationError},
+};
+use tari_dan_common_types::{committee::Committee, optional::Optional, shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, Command, EpochCheckpoint, EpochEvent, GenesisConfig},
+    StateStore,
+};
+use tari_epoch_manager::EpochManagerReader;
+use tokio::sync::broadcast;
+
+use crate::support::{logging::setup_logger, Test, TestAddress, TestConsensusSpec, TestEpochManager};
+
+fn create_block(commands: BTreeSet<Command>, merkle_root: FixedHash) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        NodeHeight(1),
+        Epoch(1),
+        Shard::from(0),
+        Default::default(),
+        commands,
+        merkle_root,
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+fn create_epoch_end_block(checkpoint: Option<EpochCheckpoint>, merkle_root: FixedHash) -> Block {
+    let commands = [Command::EpochEvent(EpochEvent::End)]
+        .into_iter()
+        .chain(checkpoint.map(Command::EpochCheckpoint))
+        .collect();
+    create_block(commands, merkle_root)
+}
+
+async fn create_epoch_manager() -> TestEpochManager {
+    let (tx_events, _) = broadcast::channel(10);
+    let epoch_manager = TestEpochManager::new(tx_events);
+    let committee = Committee::new(
+        ["1", "2", "3", "4"]
+            .into_iter()
+            .map(|addr| (TestAddress::new(addr), PublicKey::default()))
+            .collect(),
+    );
+    epoch_manager
+        .add_committees([(Shard::from(0), committee)].into_iter().collect())
+        .await;
+    epoch_manager
+}
+
+fn assert_invalid(result: Result<(), HotStuffError>) -> ProposalValidationError {
+    match result {
+        Err(HotStuffError::ProposalValidationError(err)) => err,
+        other => panic!("Expected a proposal validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn it_accepts_an_epoch_end_block_with_a_valid_checkpoint() {
+    let epoch_manager = create_epoch_manager().await;
+    let validator_set_hash = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+    let merkle_root = FixedHash::from([1u8; 32]);
+    let block = create_epoch_end_block(
+        Some(EpochCheckpoint::new(Epoch(1), merkle_root, validator_set_hash)),
+        merkle_root,
+    );
+
+    check_epoch_checkpoint::<TestConsensusSpec>(&block, &epoch_manager)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn it_rejects_an_epoch_end_block_without_a_checkpoint() {
+    let epoch_manager = create_epoch_manager().await;
+    let block = create_epoch_end_block(None, FixedHash::zero());
+
+    let err = assert_invalid(check_epoch_checkpoint::<TestConsensusSpec>(&block, &epoch_manager).await);
+    assert!(matches!(err, ProposalValidationError::MissingEpochCheckpoint { .. }));
+}
+
+#[tokio::test]
+async fn it_rejects_a_checkpoint_with_a_different_state_merkle_root() {
+    let epoch_manager = create_epoch_manager().await;
+    let validator_set_hash = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+    let block = create_epoch_end_block(
+        Some(EpochCheckpoint::new(
+            Epoch(1),
+            FixedHash::from([2u8; 32]),
+            validator_set_hash,
+        )),
+        FixedHash::from([1u8; 32]),
+    );
+
+    let err = assert_invalid(check_epoch_checkpoint::<TestConsensusSpec>(&block, &epoch_manager).await);
+    assert!(matches!(err, ProposalValidationError::InvalidEpochCheckpoint { .. }));
+}
+
+#[tokio::test]
+async fn it_rejects_a_checkpoint_with_a_different_validator_set_hash() {
+    let epoch_manager = create_epoch_manager().await;
+    let merkle_root = FixedHash::from([1u8; 32]);
+    let block = create_epoch_end_block(
+        Some(EpochCheckpoint::new(Epoch(1), merkle_root, FixedHash::from([3u8; 32]))),
+        merkle_root,
+    );
+
+    let err = assert_invalid(check_epoch_checkpoint::<TestConsensusSpec>(&block, &epoch_manager).await);
+    assert!(matches!(err, ProposalValidationError::InvalidEpochCheckpoint { .. }));
+}
+
+#[tokio::test]
+async fn it_rejects_a_checkpoint_in_a_block_that_does_not_end_the_epoch() {
+    let epoch_manager = create_epoch_manager().await;
+    let validator_set_hash = epoch_manager.get_validator_node_bmt_root(Epoch(1)).await.unwrap();
+    let merkle_root = FixedHash::from([1u8; 32]);
+    let checkpoint = EpochCheckpoint::new(Epoch(1), merkle_root, validator_set_hash);
+    let block = create_block(
+        [Command::EpochCheckpoint(checkpoint)].into_iter().collect(),
+        merkle_root,
+    );
+
+    let err = assert_invalid(check_epoch_checkpoint::<TestConsensusSpec>(&block, &epoch_manager).await);
+    assert!(matches!(err, ProposalValidationError::UnexpectedEpochCheckpoint { .. }));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn it_commits_a_checkpoint_when_the_epoch_ends() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        let (_, _, height) = test.on_block_committed().await;
+        if height >= NodeHeight(3) {
+            break;
+        }
+    }
+
+    for validator in test.validators() {
+        validator.epoch_manager.set_current_epoch(Epoch(2)).await;
+    }
+
+    loop {
+        test.on_block_committed().await;
+
+        let num_checkpointed = test
+            .validators()
+            .filter(|v| {
+                v.state_store
+                    .with_read_tx(|tx| EpochCheckpoint::get(tx, Epoch(1)).optional())
+                    .unwrap()
+                    .is_some()
+            })
+            .count();
+        if num_checkpointed == test.validators().len() {
+            break;
+        }
+
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height > NodeHeight(40) {
+            panic!(
+                "Not all validators committed an epoch checkpoint after {} blocks",
+                leaf.height
+            );
+        }
+    }
+
+    let validator_set_hash = test
+        .get_validator(&TestAddress::new("1"))
+        .epoch_manager
+        .get_validator_node_bmt_root(Epoch(1))
+        .await
+        .unwrap();
+    let expected = test
+        .get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| EpochCheckpoint::get(tx, Epoch(1)))
+        .unwrap();
+    assert_eq!(expected.epoch(), Epoch(1));
+    assert_eq!(*expected.validator_set_hash(), validator_set_hash);
+    test.with_all_validators(|v| {
+        let checkpoint = v
+            .state_store
+            .with_read_tx(|tx| EpochCheckpoint::get(tx, Epoch(1)))
+            .unwrap();
+        assert_eq!(
+            checkpoint, expected,
+            "Validator {} has a different checkpoint",
+            v.address
+        );
+    });
+
+    test.assert_clean_shutdown().await;
+}
@@ -0,0 +1,97 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeSet;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_consensus::{block_validations::check_epoch_continuity, hotstuff::ProposalValidationError};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Block, Command, EpochEvent, GenesisConfig};
+
+fn create_block(parent: &Block, epoch: Epoch, epoch_event: Option<EpochEvent>) -> Block {
+    let commands = epoch_event
+        .into_iter()
+        .map(Command::EpochEvent)
+        .collect::<BTreeSet<_>>();
+    Block::new(
+        Network::LocalNet,
+        *parent.id(),
+        parent.justify().clone(),
+        parent.height() + NodeHeight(1),
+        epoch,
+        Shard::from(0),
+        Default::default(),
+        commands,
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+fn zero_block() -> Block {
+    Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default())
+}
+
+#[test]
+fn it_accepts_a_block_in_the_same_epoch() {
+    let justify = create_block(&zero_block(), Epoch(1), None);
+    let candidate = create_block(&justify, Epoch(1), None);
+
+    check_epoch_continuity(&candidate, &justify).unwrap();
+}
+
+#[test]
+fn it_accepts_an_epoch_start_block_that_extends_an_epoch_end_block() {
+    let justify = create_block(&zero_block(), Epoch(1), Some(EpochEvent::End));
+    let candidate = create_block(&justify, Epoch(2), Some(EpochEvent::Start));
+
+    check_epoch_continuity(&candidate, &justify).unwrap();
+}
+
+#[test]
+fn it_accepts_the_first_epoch_after_genesis() {
+    let zero_block = zero_block();
+    let candidate = create_block(&zero_block, Epoch(1), Some(EpochEvent::Start));
+
+    check_epoch_continuity(&candidate, &zero_block).unwrap();
+}
+
+#[test]
+fn it_rejects_an_epoch_change_before_the_epoch_has_ended() {
+    let justify = create_block(&zero_block(), Epoch(1), None);
+
+    // A leader claims the next epoch while extending a chain that has not ended the current one
+    let candidate = create_block(&justify, Epoch(2), Some(EpochEvent::Start));
+    let err = check_epoch_continuity(&candidate, &justify).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::EpochChangeBeforeEpochEnd { .. }));
+
+    let candidate = create_block(&justify, Epoch(2), None);
+    let err = check_epoch_continuity(&candidate, &justify).unwrap_err();
+    assert!(matches!(
+        err,
+        ProposalValidationError::EpochChangeWithoutEpochStart { .. }
+    ));
+}
+
+#[test]
+fn it_rejects_an_epoch_start_block_that_does_not_change_the_epoch() {
+    let justify = create_block(&zero_block(), Epoch(1), Some(EpochEvent::End));
+    let candidate = create_block(&justify, Epoch(1), Some(EpochEvent::Start));
+
+    let err = check_epoch_continuity(&candidate, &justify).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::UnexpectedEpochStart { .. }));
+}
+
+#[test]
+fn it_rejects_an_epoch_regression() {
+    let justify = create_block(&zero_block(), Epoch(2), None);
+    let candidate = create_block(&justify, Epoch(1), None);
+
+    let err = check_epoch_continuity(&candidate, &justify).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::EpochRegression { .. }));
+}
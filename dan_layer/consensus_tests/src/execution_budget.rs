@@ -0,0 +1,120 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use rand::rngs::OsRng;
+use tari_common::configuration::Network;
+use tari_common_types::types::PrivateKey;
+use tari_crypto::keys::SecretKey;
+use tari_dan_app_utilities::transaction_executor::TariDanTransactionProcessor;
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_engine::{fees::FeeTable, limits::SubstateSizeLimits};
+use tari_dan_storage::consensus_models::{Decision, TransactionRecord};
+use tari_engine_types::commit_result::RejectReason;
+use tari_template_lib::{args, models::TemplateAddress};
+use tari_template_test_tooling::Package;
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, VersionedSubstateId};
+
+use crate::support::{build_transaction_from, logging::setup_logger, Test, TestNetworkDestination};
+
+/// Enough metering points for a short loop, but far too few for the loop to run to completion
+const EXECUTION_BUDGET: u64 = 10_000_000;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn a_transaction_that_exceeds_the_execution_budget_is_aborted_by_all_validators() {
+    setup_logger();
+    let (processor, template_address) = create_processor();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_transaction_processor(processor)
+        .add_committee(0, vec!["1", "2", "3"])
+        .start()
+        .await;
+
+    let inputs = test.create_substates_on_all_vns(2);
+    let endless_tx_id = send_spin_transaction(&test, template_address, u64::MAX, &inputs[0]).await;
+    let short_tx_id = send_spin_transaction(&test, template_address, 10, &inputs[1]).await;
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(30) {
+            panic!("Not all transaction finalized after {} blocks", committed_height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_have_decision(&short_tx_id, Decision::Commit)
+        .await;
+    test.assert_all_validators_have_decision(&endless_tx_id, Decision::Abort)
+        .await;
+    test.with_all_validators(|v| {
+        let rec = v
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::get(tx, &endless_tx_id))
+            .unwrap();
+        let result = rec
+            .result
+            .as_ref()
+            .unwrap_or_else(|| panic!("Validator {} has no result for the transaction", v.address));
+        assert!(
+            matches!(
+                result.finalize.result.reject(),
+                Some(RejectReason::ExecutionBudgetExceeded(_))
+            ),
+            "Validator {} did not reject the transaction for exceeding the execution budget: {}",
+            v.address,
+            result.finalize.result
+        );
+        let abort_details = rec
+            .abort_details
+            .as_deref()
+            .unwrap_or_else(|| panic!("Validator {} did not record the abort reason", v.address));
+        assert!(
+            abort_details.starts_with("Execution budget exceeded"),
+            "Validator {} recorded an unexpected abort reason: {}",
+            v.address,
+            abort_details
+        );
+    });
+
+    test.assert_clean_shutdown().await;
+}
+
+fn create_processor() -> (TariDanTransactionProcessor<Package>, TemplateAddress) {
+    let package = Package::builder().add_template("templates/long_loop").build();
+    let (template_address, _) = package.iter().next().unwrap();
+    let template_address = *template_address;
+    let substate_size_limits = SubstateSizeLimits {
+        max_substate_size: 512 * 1024,
+        max_transaction_size: 2 * 1024 * 1024,
+    };
+    let processor =
+        TariDanTransactionProcessor::new(Network::LocalNet, package, FeeTable::zero_rated(), substate_size_limits)
+            .with_execution_budget(EXECUTION_BUDGET);
+    (processor, template_address)
+}
+
+/// Sends a deferred transaction that loops for the given number of iterations. The transaction is executed by the
+/// engine of each validator when it is proposed.
+async fn send_spin_transaction(
+    test: &Test,
+    template_address: TemplateAddress,
+    iterations: u64,
+    input: &VersionedSubstateId,
+) -> TransactionId {
+    let transaction = Transaction::builder()
+        .call_function(template_address, "spin", args![iterations])
+        .with_inputs([SubstateRequirement::new(input.substate_id.clone(), None)])
+        .sign(&PrivateKey::random(&mut OsRng))
+        .build();
+    let transaction = build_transaction_from(transaction, Decision::Deferred, 0, vec![]);
+    let id = *transaction.id();
+    test.send_transaction_to_destination(TestNetworkDestination::All, transaction)
+        .await;
+    id
+}
@@ -0,0 +1,131 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use tari_consensus::execution_plan::{involved_substate_ids, ExecutionPlan};
+use tari_dan_storage::consensus_models::TransactionRecord;
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+use transaction_generator::transaction_builders::synthetic::{TransactionGenerator, TransactionGeneratorConfig};
+
+const NUM_ACCOUNTS: usize = 10;
+const NUM_TRANSACTIONS: u64 = 50;
+const EXECUTION_TIME: Duration = Duration::from_millis(20);
+
+fn create_generator() -> TransactionGenerator {
+    TransactionGenerator::new(TransactionGeneratorConfig {
+        num_accounts: NUM_ACCOUNTS,
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn create_transactions() -> Vec<TransactionRecord> {
+    let generator = create_generator();
+    (0..NUM_TRANSACTIONS)
+        .map(|index| TransactionRecord::new(generator.generate(index)))
+        .collect()
+}
+
+/// Simulates an execution that takes some time and whose result depends on the transactions that used the same
+/// substates before it
+fn simulate_execution(
+    nonces: &Mutex<HashMap<SubstateId, u64>>,
+    transaction: &TransactionRecord,
+) -> Result<(TransactionId, Vec<u64>), ()> {
+    thread::sleep(EXECUTION_TIME);
+    let mut nonces = nonces.lock().unwrap();
+    let used_nonces = involved_substate_ids(transaction)
+        .map(|id| {
+            let nonce = nonces.entry(id).or_default();
+            *nonce += 1;
+            *nonce
+        })
+        .collect();
+    Ok((*transaction.id(), used_nonces))
+}
+
+#[test]
+fn it_groups_transactions_that_share_substates() {
+    let transactions = create_transactions();
+    let plan = ExecutionPlan::for_transactions(&transactions);
+
+    assert_eq!(plan.num_transactions(), transactions.len());
+    assert_eq!(plan.groups().len(), NUM_ACCOUNTS);
+    for (account, group) in plan.groups().iter().enumerate() {
+        // Transaction i uses account i % NUM_ACCOUNTS
+        let expected = (account..NUM_TRANSACTIONS as usize)
+            .step_by(NUM_ACCOUNTS)
+            .collect::<Vec<_>>();
+        assert_eq!(*group, expected);
+    }
+}
+
+#[test]
+fn it_groups_transactions_that_transitively_share_substates() {
+    let generator = create_generator();
+    let [a, b, c, d] = [0, 1, 2, 3].map(|i| SubstateId::from(generator.account(i).address));
+
+    // 4 does not share a substate with 0 but is linked to it through 2
+    let substates = vec![vec![a.clone()], vec![b.clone()], vec![a, b.clone()], vec![c], vec![
+        b, d,
+    ]];
+    let plan = ExecutionPlan::new(substates);
+    assert_eq!(plan.groups(), [vec![0, 1, 2, 4], vec![3]]);
+}
+
+#[test]
+fn it_executes_independent_groups_in_parallel_with_the_same_result_as_sequential_execution() {
+    let transactions = create_transactions();
+    let plan = ExecutionPlan::for_transactions(&transactions);
+
+    let nonces = Mutex::new(HashMap::new());
+    let timer = Instant::now();
+    let sequential = transactions
+        .iter()
+        .map(|transaction| simulate_execution(&nonces, transaction))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let sequential_time = timer.elapsed();
+
+    // Use a dedicated pool so that the test does not depend on the number of cores
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(NUM_ACCOUNTS)
+        .build()
+        .unwrap();
+    let nonces = Mutex::new(HashMap::new());
+    let timer = Instant::now();
+    let parallel = pool
+        .install(|| plan.execute(transactions.iter().collect(), |tx| simulate_execution(&nonces, tx)))
+        .unwrap();
+    let parallel_time = timer.elapsed();
+
+    assert_eq!(parallel, sequential);
+    assert!(
+        parallel_time * 2 < sequential_time,
+        "parallel execution took {:.2?}, sequential execution took {:.2?}",
+        parallel_time,
+        sequential_time
+    );
+}
+
+#[test]
+fn it_returns_the_first_error_in_transaction_order() {
+    let transactions = create_transactions();
+    let plan = ExecutionPlan::for_transactions(&transactions);
+
+    let result = plan.execute((0..transactions.len()).collect(), |index| {
+        if index == 7 || index == 23 {
+            Err(index)
+        } else {
+            Ok(index)
+        }
+    });
+    assert_eq!(result, Err(7));
+}
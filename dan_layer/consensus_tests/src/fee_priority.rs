@@ -0,0 +1,97 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{BlockId, Decision, TransactionPoolOrdering},
+    StateStoreReadTransaction,
+    StorageError,
+};
+use tari_transaction::{Transaction, TransactionId};
+
+use crate::support::{
+    build_transaction,
+    build_transaction_from,
+    logging::setup_logger,
+    Test,
+    TestAddress,
+    TestNetworkDestination,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transactions_with_a_higher_priority_fee_are_proposed_first() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_transaction_pool_ordering(TransactionPoolOrdering::FeePriority)
+        // Each transaction exceeds the block size on its own, so each block contains one transaction
+        .with_max_block_size_bytes(0)
+        .add_committee(0, vec!["1", "2"])
+        .start()
+        .await;
+
+    let mut transactions = [3, 10, 1, 5]
+        .into_iter()
+        .map(|priority_fee| {
+            let outputs = build_transaction(Decision::Commit, 1, 1, 1)
+                .resulting_outputs()
+                .to_vec();
+            let tx = Transaction::builder()
+                .with_priority_fee(priority_fee)
+                .sign(&Default::default())
+                .build();
+            build_transaction_from(tx, Decision::Commit, 1, outputs)
+        })
+        .collect::<Vec<_>>();
+    for transaction in &transactions {
+        test.send_transaction_to_destination(TestNetworkDestination::All, transaction.clone())
+            .await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(30) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    let proposal_order = test
+        .get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let mut committed_blocks = vec![];
+            let mut parents = vec![BlockId::genesis()];
+            while let Some(parent) = parents.pop() {
+                for block in tx.blocks_get_all_by_parent(&parent)? {
+                    if block.id().is_genesis() {
+                        continue;
+                    }
+                    parents.push(*block.id());
+                    if block.is_committed() {
+                        committed_blocks.push(block);
+                    }
+                }
+            }
+            committed_blocks.sort_by_key(|b| b.height());
+            Ok::<_, StorageError>(
+                committed_blocks
+                    .iter()
+                    .flat_map(|b| b.commands().iter().filter_map(|c| c.local_only()).map(|t| t.id))
+                    .collect::<Vec<TransactionId>>(),
+            )
+        })
+        .unwrap();
+
+    transactions.sort_by_key(|t| std::cmp::Reverse(t.transaction().priority_fee()));
+    let expected_order = transactions.iter().map(|t| *t.id()).collect::<Vec<_>>();
+    assert_eq!(proposal_order, expected_order);
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+    test.assert_clean_shutdown().await;
+}
@@ -0,0 +1,176 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{BTreeSet, HashMap};
+
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_consensus::{
+    block_validations::check_foreign_indexes,
+    hotstuff::{HotStuffError, ProposalValidationError},
+};
+use tari_dan_common_types::{committee::CommitteeInfo, shard::Shard, Epoch, NodeHeight, SubstateAddress};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        Command,
+        Decision,
+        Evidence,
+        ForeignSendCounters,
+        GenesisConfig,
+        ShardEvidence,
+        SubstateLockFlag,
+        TransactionAtom,
+    },
+    StateStore,
+};
+use tari_rpc_state_sync::create_zero_block_if_required;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_transaction::TransactionId;
+
+use crate::support::TestAddress;
+
+type TestStore = SqliteStateStore<TestAddress>;
+
+const NUM_COMMITTEES: u32 = 4;
+
+fn create_store() -> TestStore {
+    let store = SqliteStateStore::connect(":memory:").unwrap();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+    // The justify block has already sent 4 foreign proposals to shard 1
+    let counters = ForeignSendCounters {
+        counters: HashMap::from([(Shard::from(1), 4)]),
+    };
+    store.with_write_tx(|tx| counters.set(tx, zero_block().id())).unwrap();
+    store
+}
+
+fn zero_block() -> Block {
+    Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default())
+}
+
+fn local_committee_info() -> CommitteeInfo {
+    CommitteeInfo::new(NUM_COMMITTEES, 4, Shard::from(0))
+}
+
+/// Returns an address in the given shard when there are 4 committees
+fn address_in_shard(shard: u8) -> SubstateAddress {
+    let mut address = [0u8; 32];
+    address[0] = shard * 0x40 + 0x10;
+    SubstateAddress::new(address)
+}
+
+fn create_atom(seed: u8, shards: &[u8]) -> TransactionAtom {
+    TransactionAtom {
+        id: TransactionId::new([seed; 32]),
+        decision: Decision::Commit,
+        evidence: shards
+            .iter()
+            .map(|shard| {
+                (address_in_shard(*shard), ShardEvidence {
+                    qc_ids: Default::default(),
+                    lock: SubstateLockFlag::Write,
+                })
+            })
+            .collect::<Evidence>(),
+        transaction_fee: 0,
+        leader_fee: None,
+        priority_fee: 0,
+    }
+}
+
+fn create_block(commands: BTreeSet<Command>, foreign_indexes: &[(u32, u64)]) -> Block {
+    let zero_block = zero_block();
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        NodeHeight(1),
+        Epoch(0),
+        Shard::from(0),
+        Default::default(),
+        commands,
+        FixedHash::zero(),
+        0,
+        foreign_indexes
+            .iter()
+            .map(|(shard, count)| (Shard::from(*shard), *count))
+            .collect(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+/// A block that locally prepares transactions involving the local shard 0 and foreign shards 1 and 3. The prepare
+/// command involving shard 2 does not require a foreign index.
+fn create_commands() -> BTreeSet<Command> {
+    BTreeSet::from([
+        Command::LocalPrepared(create_atom(1, &[0, 1])),
+        Command::LocalPrepared(create_atom(2, &[0, 3])),
+        Command::Prepare(create_atom(3, &[0, 2])),
+    ])
+}
+
+fn check(store: &TestStore, block: &Block) -> Result<(), HotStuffError> {
+    store.with_read_tx(|tx| check_foreign_indexes(tx, block, &local_committee_info()))
+}
+
+fn assert_invalid_foreign_counters(result: Result<(), HotStuffError>, expected_details: &str) {
+    match result {
+        Err(HotStuffError::ProposalValidationError(ProposalValidationError::InvalidForeignCounters {
+            details,
+            ..
+        })) => assert!(
+            details.contains(expected_details),
+            "expected details to contain '{}' but got '{}'",
+            expected_details,
+            details
+        ),
+        other => panic!("expected InvalidForeignCounters but got {:?}", other),
+    }
+}
+
+#[test]
+fn it_accepts_valid_foreign_indexes() {
+    let store = create_store();
+
+    check(&store, &create_block(create_commands(), &[(1, 5), (3, 1)])).unwrap();
+    // A block without locally prepared transactions has no foreign indexes
+    check(&store, &create_block(BTreeSet::new(), &[])).unwrap();
+}
+
+#[test]
+fn it_rejects_unsorted_foreign_indexes() {
+    let store = create_store();
+
+    let result = check(&store, &create_block(create_commands(), &[(3, 1), (1, 5)]));
+    assert_invalid_foreign_counters(result, "not sorted by shard");
+}
+
+#[test]
+fn it_rejects_missing_or_unexpected_shards() {
+    let store = create_store();
+
+    let result = check(&store, &create_block(create_commands(), &[(1, 5)]));
+    assert_invalid_foreign_counters(result, "Missing foreign index for shard 3");
+
+    let result = check(&store, &create_block(create_commands(), &[(1, 5), (2, 1), (3, 1)]));
+    assert_invalid_foreign_counters(result, "Shard 2 is not involved");
+
+    let result = check(&store, &create_block(BTreeSet::new(), &[(1, 5)]));
+    assert_invalid_foreign_counters(result, "Shard 1 is not involved");
+}
+
+#[test]
+fn it_rejects_wrong_counter_values() {
+    let store = create_store();
+
+    // The counter does not include the proposals sent by the justify block
+    let result = check(&store, &create_block(create_commands(), &[(1, 1), (3, 1)]));
+    assert_invalid_foreign_counters(result, "Expected 5, got 1");
+
+    let result = check(&store, &create_block(create_commands(), &[(1, 5), (3, 2)]));
+    assert_invalid_foreign_counters(result, "Expected 1, got 2");
+}
@@ -0,0 +1,154 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_consensus::hotstuff::abort_timed_out_foreign_proposal_transactions;
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        BlockId,
+        Decision,
+        ForeignProposal,
+        ForeignProposalState,
+        GenesisConfig,
+        TransactionAtom,
+        TransactionPool,
+        TransactionPoolRecord,
+    },
+    StateStore,
+};
+use tari_rpc_state_sync::create_zero_block_if_required;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_transaction::TransactionId;
+
+use crate::support::{build_transaction, TestAddress};
+
+type TestStore = SqliteStateStore<TestAddress>;
+
+#[test]
+fn it_aborts_unprepared_transactions_of_timed_out_foreign_proposals() {
+    const TIMEOUT: NodeHeight = NodeHeight(10);
+    let store = create_store();
+    let transaction_pool = TransactionPool::<TestStore>::new();
+
+    let timed_out_tx = insert_pool_transaction(&store, &transaction_pool);
+    let pending_tx = insert_pool_transaction(&store, &transaction_pool);
+    // Proposed exactly TIMEOUT blocks before the current block
+    let timed_out = insert_proposed_foreign_proposal(&store, 1, NodeHeight(10), timed_out_tx);
+    let pending = insert_proposed_foreign_proposal(&store, 2, NodeHeight(11), pending_tx);
+
+    let block = create_block_at_height(NodeHeight(20));
+    store
+        .with_write_tx(|tx| abort_timed_out_foreign_proposal_transactions(tx, &transaction_pool, &block, TIMEOUT))
+        .unwrap();
+
+    let timed_out_rec = get_pool_record(&store, &timed_out_tx);
+    assert_eq!(timed_out_rec.local_decision(), Some(Decision::Abort));
+    let pending_rec = get_pool_record(&store, &pending_tx);
+    assert_eq!(pending_rec.local_decision(), None);
+
+    // Both proposals are kept: the timed out one until its aborted transaction is resolved and the pending one because
+    // it has not timed out
+    let proposals = store
+        .with_read_tx(|tx| ForeignProposal::get_all_proposed(tx, block.height()))
+        .unwrap();
+    assert!(proposals.contains(&timed_out));
+    assert!(proposals.contains(&pending));
+}
+
+#[test]
+fn it_expires_timed_out_foreign_proposals_without_unresolved_transactions() {
+    const TIMEOUT: NodeHeight = NodeHeight(10);
+    let store = create_store();
+    let transaction_pool = TransactionPool::<TestStore>::new();
+
+    // The transaction is not known locally, so there is nothing to abort
+    let mut proposal = insert_proposed_foreign_proposal(&store, 1, NodeHeight(10), TransactionId::new([1u8; 32]));
+
+    let block = create_block_at_height(NodeHeight(20));
+    store
+        .with_write_tx(|tx| abort_timed_out_foreign_proposal_transactions(tx, &transaction_pool, &block, TIMEOUT))
+        .unwrap();
+
+    let proposed = store
+        .with_read_tx(|tx| ForeignProposal::get_all_proposed(tx, block.height()))
+        .unwrap();
+    assert!(proposed.is_empty());
+    let expired = store
+        .with_read_tx(|tx| ForeignProposal::get_all_by_state(tx, ForeignProposalState::Expired, 10, 0))
+        .unwrap();
+    proposal.state = ForeignProposalState::Expired;
+    assert_eq!(expired, vec![proposal]);
+}
+
+fn create_store() -> TestStore {
+    let store = SqliteStateStore::connect(":memory:").unwrap();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+    store
+}
+
+fn create_block_at_height(height: NodeHeight) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        height,
+        Epoch(0),
+        Shard::from(0),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+fn insert_pool_transaction(store: &TestStore, transaction_pool: &TransactionPool<TestStore>) -> TransactionId {
+    let record = build_transaction(Decision::Commit, 1, 1, 2);
+    let id = *record.id();
+    store
+        .with_write_tx(|tx| {
+            record.insert(tx)?;
+            let atom = TransactionAtom {
+                id,
+                decision: Decision::Commit,
+                evidence: Default::default(),
+                transaction_fee: 1,
+                leader_fee: None,
+                priority_fee: 0,
+            };
+            transaction_pool.insert(tx, atom, NodeHeight::zero())
+        })
+        .unwrap();
+    id
+}
+
+fn insert_proposed_foreign_proposal(
+    store: &TestStore,
+    seed: u8,
+    proposed_height: NodeHeight,
+    transaction_id: TransactionId,
+) -> ForeignProposal {
+    let mut proposal = ForeignProposal::new(
+        Shard::from(1),
+        BlockId::from(FixedHash::from([seed; 32])),
+        vec![transaction_id],
+        0,
+    );
+    proposal.set_proposed_height(proposed_height);
+    store.with_write_tx(|tx| proposal.upsert(tx)).unwrap();
+    proposal
+}
+
+fn get_pool_record(store: &TestStore, transaction_id: &TransactionId) -> TransactionPoolRecord {
+    store
+        .with_read_tx(|tx| TransactionPoolRecord::get(tx, transaction_id))
+        .unwrap()
+}
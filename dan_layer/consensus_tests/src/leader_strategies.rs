@@ -0,0 +1,169 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Property tests for the leader strategies. Each test simulates several honest nodes that build their strategy
+//! independently from the same inputs and checks that they agree on the leader for random epochs and heights.
+
+use std::collections::BTreeMap;
+
+use rand::{rngs::OsRng, seq::SliceRandom, Rng};
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    leader_strategies::{RoundRobinOffsetLeaderStrategy, WeightedLeaderStrategy},
+    traits::LeaderStrategy,
+};
+use tari_dan_common_types::{committee::Committee, crypto::create_key_pair_from_seed, Epoch, NodeHeight};
+
+const NUM_NODES: usize = 5;
+const NUM_SAMPLES: usize = 1000;
+
+fn create_committee(size: u8) -> Committee<PublicKey> {
+    Committee::new(
+        (1..=size)
+            .map(|seed| {
+                let (_, public_key) = create_key_pair_from_seed(seed);
+                (public_key.clone(), public_key)
+            })
+            .collect(),
+    )
+}
+
+fn random_epoch_and_height() -> (Epoch, NodeHeight) {
+    let epoch = Epoch(OsRng.gen_range(0..100));
+    // Include the extremes of the height range to catch overflows
+    let height = match OsRng.gen_range(0..10) {
+        0 => NodeHeight(u64::MAX),
+        1 => NodeHeight(0),
+        _ => NodeHeight(OsRng.gen()),
+    };
+    (epoch, height)
+}
+
+fn assert_all_nodes_agree<T: LeaderStrategy<PublicKey>>(nodes: &[T], committee: &Committee<PublicKey>) {
+    for _ in 0..NUM_SAMPLES {
+        let (epoch, height) = random_epoch_and_height();
+        let leader = nodes[0].calculate_leader(committee, epoch, height);
+        assert!((leader as usize) < committee.len());
+        for node in &nodes[1..] {
+            assert_eq!(
+                node.calculate_leader(committee, epoch, height),
+                leader,
+                "nodes disagree on the leader for epoch {epoch} height {height}"
+            );
+        }
+    }
+}
+
+mod round_robin_offset {
+    use super::*;
+
+    fn create_nodes(seeds: &[(Epoch, FixedHash)]) -> Vec<RoundRobinOffsetLeaderStrategy> {
+        (0..NUM_NODES)
+            .map(|_| {
+                let strategy = RoundRobinOffsetLeaderStrategy::new();
+                let mut seeds = seeds.to_vec();
+                seeds.shuffle(&mut OsRng);
+                for (epoch, seed) in seeds {
+                    strategy.set_epoch_seed(epoch, &seed);
+                }
+                strategy
+            })
+            .collect()
+    }
+
+    fn random_seeds() -> Vec<(Epoch, FixedHash)> {
+        (0..100)
+            .map(|epoch| (Epoch(epoch), FixedHash::from(OsRng.gen::<[u8; 32]>())))
+            .collect()
+    }
+
+    #[test]
+    fn all_nodes_select_the_same_leader() {
+        for size in [1, 2, 3, 7, 20] {
+            let committee = create_committee(size);
+            let nodes = create_nodes(&random_seeds());
+            assert_all_nodes_agree(&nodes, &committee);
+        }
+    }
+
+    #[test]
+    fn it_rotates_through_every_member_in_committee_order() {
+        let committee = create_committee(7);
+        let nodes = create_nodes(&random_seeds());
+        for _ in 0..NUM_SAMPLES {
+            let (epoch, height) = random_epoch_and_height();
+            let height = NodeHeight(height.as_u64().min(u64::MAX - 7));
+            let first = nodes[0].calculate_leader(&committee, epoch, height);
+            for i in 1..7u64 {
+                let leader = nodes[0].calculate_leader(&committee, epoch, height + NodeHeight(i));
+                assert_eq!(leader, (first + i as u32) % 7);
+            }
+        }
+    }
+}
+
+mod weighted {
+    use super::*;
+
+    fn create_nodes(weights: &[(PublicKey, u64)]) -> Vec<WeightedLeaderStrategy> {
+        (0..NUM_NODES)
+            .map(|_| {
+                let mut weights = weights.to_vec();
+                weights.shuffle(&mut OsRng);
+                WeightedLeaderStrategy::new(weights.into_iter().collect::<BTreeMap<_, _>>())
+            })
+            .collect()
+    }
+
+    fn random_weights(committee: &Committee<PublicKey>) -> Vec<(PublicKey, u64)> {
+        committee
+            .public_keys()
+            .map(|pk| {
+                // Some validators have no weight and some have the maximum weight
+                let weight = match OsRng.gen_range(0..5) {
+                    0 => 0,
+                    1 => u64::MAX,
+                    _ => OsRng.gen_range(1..1000),
+                };
+                (pk.clone(), weight)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn all_nodes_select_the_same_leader() {
+        for size in [1, 2, 3, 7, 20] {
+            let committee = create_committee(size);
+            let nodes = create_nodes(&random_weights(&committee));
+            assert_all_nodes_agree(&nodes, &committee);
+        }
+    }
+
+    #[test]
+    fn it_never_selects_a_validator_without_weight() {
+        let committee = create_committee(10);
+        let weights = random_weights(&committee);
+        let nodes = create_nodes(&weights);
+        if weights.iter().all(|(_, weight)| *weight == 0) {
+            return;
+        }
+
+        for _ in 0..NUM_SAMPLES {
+            let (epoch, height) = random_epoch_and_height();
+            let leader = nodes[0].calculate_leader(&committee, epoch, height);
+            let (_, public_key) = &committee.members[leader as usize];
+            assert_ne!(nodes[0].weight_of(public_key), 0);
+        }
+    }
+
+    #[test]
+    fn it_falls_back_to_round_robin_if_no_validator_has_weight() {
+        let committee = create_committee(7);
+        let nodes = create_nodes(&[]);
+        for _ in 0..NUM_SAMPLES {
+            let (epoch, height) = random_epoch_and_height();
+            let leader = nodes[0].calculate_leader(&committee, epoch, height);
+            assert_eq!(leader as u64, height.as_u64() % 7);
+        }
+    }
+}
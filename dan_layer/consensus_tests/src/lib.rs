@@ -1,8 +1,50 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 #[cfg(test)]
+mod block_sync;
+#[cfg(test)]
+mod committee_membership;
+#[cfg(test)]
 mod consensus;
 #[cfg(test)]
+mod consensus_journal;
+#[cfg(test)]
+mod epoch_checkpoint;
+#[cfg(test)]
+mod epoch_continuity;
+#[cfg(test)]
+mod execution_budget;
+#[cfg(test)]
+mod execution_plan;
+#[cfg(test)]
+mod fee_priority;
+#[cfg(test)]
+mod foreign_indexes;
+#[cfg(test)]
+mod foreign_proposal_timeout;
+#[cfg(test)]
+mod leader_strategies;
+#[cfg(test)]
+mod outbound_queue;
+#[cfg(test)]
+mod pacemaker;
+#[cfg(test)]
+mod peer_bans;
+#[cfg(test)]
+mod proposal_pipelining;
+#[cfg(test)]
+mod proposer_signature;
+#[cfg(test)]
+mod qc_signatures;
+#[cfg(test)]
+mod state_merkle_root;
+#[cfg(test)]
 mod substate_store;
 #[cfg(test)]
 mod support;
+#[cfg(test)]
+mod transaction_epoch_bounds;
+#[cfg(test)]
+mod verified_qc_cache;
+#[cfg(test)]
+mod vote_retry;
@@ -0,0 +1,128 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    messages::{HotstuffMessage, ProposalMessage, VoteMessage},
+    outbound_queue::{OutboundQueues, PeerMessageSender},
+    traits::{OutboundMessagingError, VoteSignatureService},
+};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Block, BlockId, QuorumDecision};
+
+use crate::support::{TestAddress, TestHooks, TestVoteSignatureService};
+
+const CAPACITY: usize = 10;
+const NUM_PROPOSALS: usize = 50;
+
+/// Records the messages sent to each peer. Sending to the stuck peer never completes.
+#[derive(Clone)]
+struct RecordingSender {
+    stuck_peer: TestAddress,
+    sent: Arc<Mutex<HashMap<TestAddress, Vec<HotstuffMessage>>>>,
+}
+
+impl RecordingSender {
+    fn new(stuck_peer: TestAddress) -> Self {
+        Self {
+            stuck_peer,
+            sent: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn num_sent(&self, peer: &TestAddress) -> usize {
+        self.sent.lock().unwrap().get(peer).map(|msgs| msgs.len()).unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl PeerMessageSender<TestAddress> for RecordingSender {
+    async fn send_to_peer(&self, to: &TestAddress, message: HotstuffMessage) -> Result<(), OutboundMessagingError> {
+        if *to == self.stuck_peer {
+            std::future::pending::<()>().await;
+        }
+        self.sent.lock().unwrap().entry(to.clone()).or_default().push(message);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn a_stuck_peer_does_not_delay_messages_to_other_peers() {
+    let stuck_peer = TestAddress::new("4");
+    let peers = ["1", "2", "3", "4"]
+        .into_iter()
+        .map(TestAddress::new)
+        .collect::<Vec<_>>();
+    let sender = RecordingSender::new(stuck_peer.clone());
+    let hooks = TestHooks::default();
+    let mut queues = OutboundQueues::new(CAPACITY, sender.clone(), hooks.clone());
+
+    for _ in 0..NUM_PROPOSALS {
+        for peer in &peers {
+            queues.enqueue(peer.clone(), create_proposal());
+        }
+        // Give the queues a chance to drain, as they would between rounds
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while peers[..3].iter().any(|peer| sender.num_sent(peer) < NUM_PROPOSALS) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for messages to be sent to the responsive peers");
+
+    assert_eq!(sender.num_sent(&stuck_peer), 0);
+    assert!(queues.queue_depth(&stuck_peer) <= CAPACITY);
+    assert_eq!(
+        hooks.outbound_queue_depth(&stuck_peer),
+        Some(queues.queue_depth(&stuck_peer))
+    );
+}
+
+#[tokio::test]
+async fn votes_are_not_dropped_when_the_queue_is_full() {
+    let stuck_peer = TestAddress::new("1");
+    let sender = RecordingSender::new(stuck_peer.clone());
+    let mut queues = OutboundQueues::new(CAPACITY, sender, TestHooks::default());
+    let signer = TestVoteSignatureService::new(PublicKey::default(), stuck_peer.clone());
+
+    // Fill the queue with proposals
+    for _ in 0..=CAPACITY {
+        queues.enqueue(stuck_peer.clone(), create_proposal());
+    }
+
+    for height in 0..(CAPACITY * 2) {
+        let is_queued = queues.enqueue(stuck_peer.clone(), create_vote(&signer, NodeHeight(height as u64)));
+        assert!(is_queued);
+    }
+    // All proposals have been dropped to make room and the votes exceed the capacity
+    assert_eq!(queues.queue_depth(&stuck_peer), CAPACITY * 2);
+}
+
+fn create_proposal() -> HotstuffMessage {
+    HotstuffMessage::Proposal(ProposalMessage {
+        block: Block::zero_block(Network::LocalNet),
+    })
+}
+
+fn create_vote(signer: &TestVoteSignatureService, block_height: NodeHeight) -> HotstuffMessage {
+    let block_id = BlockId::new(FixedHash::from([1u8; 32]));
+    let decision = QuorumDecision::Accept;
+    HotstuffMessage::Vote(VoteMessage {
+        epoch: Epoch(0),
+        block_id,
+        block_height,
+        decision,
+        signature: signer.sign_vote(&FixedHash::zero(), &block_id, &decision),
+    })
+}
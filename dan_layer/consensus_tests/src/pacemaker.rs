@@ -0,0 +1,71 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use rand::{rngs::StdRng, SeedableRng};
+use tari_consensus::hotstuff::{PacemakerConfig, ViewTimeoutBackoff, VIEW_TIMEOUT_JITTER};
+
+fn create_backoff() -> ViewTimeoutBackoff {
+    ViewTimeoutBackoff::new(PacemakerConfig {
+        base_timeout: Duration::from_secs(10),
+        timeout_multiplier: 2.0,
+        max_timeout: Duration::from_secs(60),
+    })
+}
+
+#[test]
+fn it_backs_off_exponentially_on_consecutive_missed_views() {
+    let mut backoff = create_backoff();
+
+    let mut timeouts = vec![backoff.timeout()];
+    for _ in 0..5 {
+        backoff.on_view_failed();
+        timeouts.push(backoff.timeout());
+    }
+
+    assert_eq!(timeouts, [10, 20, 40, 60, 60, 60].map(Duration::from_secs));
+    assert_eq!(backoff.consecutive_failures(), 5);
+}
+
+#[test]
+fn it_resets_the_timeout_once_the_view_makes_progress() {
+    let mut backoff = create_backoff();
+    for _ in 0..3 {
+        backoff.on_view_failed();
+    }
+    assert_eq!(backoff.timeout(), Duration::from_secs(60));
+
+    backoff.reset();
+    assert_eq!(backoff.consecutive_failures(), 0);
+    assert_eq!(backoff.timeout(), Duration::from_secs(10));
+
+    backoff.on_view_failed();
+    assert_eq!(backoff.timeout(), Duration::from_secs(20));
+}
+
+#[test]
+fn it_caps_the_timeout_after_many_missed_views() {
+    let mut backoff = create_backoff();
+    for _ in 0..10_000 {
+        backoff.on_view_failed();
+    }
+    assert_eq!(backoff.timeout(), Duration::from_secs(60));
+}
+
+#[test]
+fn it_jitters_the_timeout_within_bounds() {
+    let mut backoff = create_backoff();
+    backoff.on_view_failed();
+    let timeout = backoff.timeout();
+    let min = timeout.mul_f64(1.0 - VIEW_TIMEOUT_JITTER);
+    let max = timeout.mul_f64(1.0 + VIEW_TIMEOUT_JITTER);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let jittered = (0..100)
+        .map(|_| backoff.timeout_with_jitter(&mut rng))
+        .collect::<Vec<_>>();
+    assert!(jittered.iter().all(|t| (min..=max).contains(t)));
+    // Nodes that missed the same views should not all time out at the same time
+    assert!(jittered.iter().any(|t| *t != jittered[0]));
+}
@@ -0,0 +1,170 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use tari_consensus::{
+    hotstuff::{PeerBanConfig, PeerBanTracker},
+    traits::Clock,
+};
+use tari_dan_common_types::{optional::Optional, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Decision, PeerMisbehavior},
+    StateStore,
+};
+use tari_state_store_sqlite::SqliteStateStore;
+
+use crate::support::{logging::setup_logger, Test, TestAddress, TestClock, TestConsensusSpec};
+
+const BAN_DURATION_SECS: u64 = 60 * 60;
+const DECAY_INTERVAL_SECS: u64 = 10 * 60;
+
+fn create_tracker(ban_threshold: u64) -> (PeerBanTracker<TestConsensusSpec>, TestClock) {
+    let store = SqliteStateStore::connect(":memory:").unwrap();
+    let clock = TestClock::default();
+    let tracker = PeerBanTracker::new(store, clock.clone(), PeerBanConfig {
+        ban_threshold,
+        ban_duration: Duration::from_secs(BAN_DURATION_SECS),
+        score_decay_interval: Duration::from_secs(DECAY_INTERVAL_SECS),
+    });
+    (tracker, clock)
+}
+
+#[test]
+fn it_bans_a_peer_once_its_score_reaches_the_threshold() {
+    let (tracker, clock) = create_tracker(3);
+    let peer = TestAddress::new("1");
+
+    for _ in 0..2 {
+        let misbehavior = tracker.record_offence(&peer, &"invalid proposal").unwrap();
+        assert_eq!(misbehavior.banned_until, None);
+        assert!(!tracker.is_banned(&peer).unwrap());
+    }
+
+    let misbehavior = tracker.record_offence(&peer, &"invalid vote").unwrap();
+    assert_eq!(misbehavior.score, 3);
+    assert_eq!(misbehavior.reason, "invalid vote");
+    assert_eq!(misbehavior.banned_until, Some(clock.now() + BAN_DURATION_SECS));
+    assert!(tracker.is_banned(&peer).unwrap());
+    assert!(!tracker.is_banned(&TestAddress::new("2")).unwrap());
+}
+
+#[test]
+fn it_lifts_the_ban_after_the_ban_duration() {
+    let (tracker, clock) = create_tracker(1);
+    let peer = TestAddress::new("1");
+    tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    assert!(tracker.is_banned(&peer).unwrap());
+
+    clock.advance(BAN_DURATION_SECS - 1);
+    assert!(tracker.is_banned(&peer).unwrap());
+    clock.advance(1);
+    assert!(!tracker.is_banned(&peer).unwrap());
+}
+
+#[test]
+fn it_decays_the_score_of_a_peer_that_behaves() {
+    let (tracker, clock) = create_tracker(3);
+    let peer = TestAddress::new("1");
+    tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    tracker.record_offence(&peer, &"invalid proposal").unwrap();
+
+    // One point decays per interval, so the next offence brings the score back to 2
+    clock.advance(DECAY_INTERVAL_SECS);
+    let misbehavior = tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    assert_eq!(misbehavior.score, 2);
+    assert!(!tracker.is_banned(&peer).unwrap());
+
+    // The score never decays below zero
+    clock.advance(DECAY_INTERVAL_SECS * 10);
+    let misbehavior = tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    assert_eq!(misbehavior.score, 1);
+    assert!(!tracker.is_banned(&peer).unwrap());
+}
+
+#[test]
+fn it_unbans_a_peer_when_its_record_is_removed() {
+    let store = SqliteStateStore::<TestAddress>::connect(":memory:").unwrap();
+    let clock = TestClock::default();
+    let tracker = PeerBanTracker::<TestConsensusSpec>::new(store.clone(), clock, PeerBanConfig {
+        ban_threshold: 1,
+        ..Default::default()
+    });
+    let peer = TestAddress::new("1");
+    tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    assert!(tracker.is_banned(&peer).unwrap());
+
+    assert!(store.with_write_tx(|tx| PeerMisbehavior::remove(tx, &peer)).unwrap());
+    assert!(!tracker.is_banned(&peer).unwrap());
+    assert!(store
+        .with_read_tx(|tx| PeerMisbehavior::get(tx, &peer).optional())
+        .unwrap()
+        .is_none());
+
+    // The score starts from zero again
+    let misbehavior = tracker.record_offence(&peer, &"invalid proposal").unwrap();
+    assert_eq!(misbehavior.score, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn proposals_from_a_banned_leader_are_ignored() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_wrong_network_leader("1")
+        .with_peer_ban_threshold(1)
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    let banned_leader = TestAddress::new("1");
+    let honest_validators = ["2", "3", "4"].map(TestAddress::new);
+    let get_misbehavior = |test: &Test, address: &TestAddress| {
+        test.get_validator(address)
+            .state_store
+            .with_read_tx(|tx| PeerMisbehavior::get(tx, &banned_leader).optional())
+            .unwrap()
+    };
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        // Keep going until all honest validators have banned the leader and dropped its messages
+        if test.is_transaction_pool_empty() &&
+            honest_validators.iter().all(|address| {
+                test.get_validator(address)
+                    .hooks
+                    .banned_peer_messages_dropped(&banned_leader) >
+                    0
+            })
+        {
+            break;
+        }
+        if committed_height > NodeHeight(50) {
+            panic!(
+                "Honest validators did not ignore the banned leader after {} blocks",
+                committed_height
+            );
+        }
+    }
+
+    let now = test.clock().now();
+    for address in &honest_validators {
+        let misbehavior = get_misbehavior(&test, address)
+            .unwrap_or_else(|| panic!("{} did not record the misbehavior of the leader", address));
+        assert!(misbehavior.is_banned(now), "{} did not ban the leader", address);
+        // Proposals from the banned leader are dropped before they are validated, so the score does not increase
+        assert_eq!(misbehavior.score, 1);
+        assert!(misbehavior.reason.contains("Invalid network"));
+    }
+    // The leader itself did not see any invalid proposals
+    assert!(get_misbehavior(&test, &banned_leader).is_none());
+    test.assert_all_validators_committed();
+
+    test.assert_clean_shutdown().await;
+}
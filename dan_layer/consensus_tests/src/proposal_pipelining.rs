@@ -0,0 +1,133 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{Duration, Instant};
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, Decision};
+use tari_transaction::SubstateRequirement;
+
+use crate::support::{
+    build_transaction_with_inputs,
+    create_execution_result_for_transaction,
+    logging::setup_logger,
+    Test,
+    TestAddress,
+    TestNetworkDestination,
+};
+
+const NUM_TRANSACTIONS: usize = 4;
+const EXECUTION_DELAY: Duration = Duration::from_millis(100);
+const VOTE_DELAY: Duration = Duration::from_millis(200);
+
+struct PipeliningRun {
+    /// The mean time between the blocks committed by one validator
+    mean_block_interval: Duration,
+    /// Whether the prepared executions were used for each pipelined proposal of all validators
+    pipelined_proposals: Vec<bool>,
+}
+
+/// Commits deferred transactions that take EXECUTION_DELAY to execute, one transaction per block. Votes take VOTE_DELAY
+/// to arrive, which is the time that a pipelining leader has to execute the transactions for the next block.
+async fn run_deferred_transactions(enable_pipelining: bool) -> PipeliningRun {
+    let mut builder = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_execution_delay(EXECUTION_DELAY)
+        .with_vote_delay("1", VOTE_DELAY)
+        .with_vote_delay("2", VOTE_DELAY)
+        // Each transaction exceeds the block size on its own, so each block contains one transaction
+        .with_max_block_size_bytes(0)
+        .add_committee(0, vec!["1", "2"]);
+    if !enable_pipelining {
+        builder = builder.disable_proposal_pipelining();
+    }
+    let mut test = builder.start().await;
+
+    for input in test.create_substates_on_all_vns(NUM_TRANSACTIONS) {
+        let tx = build_transaction_with_inputs(Decision::Deferred, 1, [SubstateRequirement::new(
+            input.substate_id.clone(),
+            None,
+        )]);
+        test.transaction_executions()
+            .insert(create_execution_result_for_transaction(
+                BlockId::genesis(),
+                *tx.id(),
+                Decision::Commit,
+                0,
+                vec![input],
+                vec![],
+            ));
+        test.send_transaction_to_destination(TestNetworkDestination::All, tx)
+            .await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    let validator = TestAddress::new("1");
+    let mut commit_times = vec![];
+    loop {
+        let (address, _, committed_height) = test.on_block_committed().await;
+        if address == validator {
+            commit_times.push(Instant::now());
+        }
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(40) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    assert!(
+        commit_times.len() > 1,
+        "Validator {} committed fewer than 2 blocks",
+        validator
+    );
+    let mean_block_interval =
+        (commit_times[commit_times.len() - 1] - commit_times[0]) / (commit_times.len() as u32 - 1);
+    let pipelined_proposals = test.validators().flat_map(|v| v.hooks.pipelined_proposals()).collect();
+
+    test.assert_clean_shutdown().await;
+
+    PipeliningRun {
+        mean_block_interval,
+        pipelined_proposals,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn it_reduces_the_block_interval_by_executing_transactions_while_voting() {
+    setup_logger();
+    let sequential = run_deferred_transactions(false).await;
+    assert!(sequential.pipelined_proposals.is_empty());
+
+    let pipelined = run_deferred_transactions(true).await;
+    assert!(
+        pipelined.pipelined_proposals.iter().any(|is_used| *is_used),
+        "No proposal used the prepared executions"
+    );
+
+    log::info!(
+        "Mean block interval with {:.2?} execution and {:.2?} vote delays: {:.2?} sequential, {:.2?} pipelined ({} \
+         prepared proposals used, {} discarded)",
+        EXECUTION_DELAY,
+        VOTE_DELAY,
+        sequential.mean_block_interval,
+        pipelined.mean_block_interval,
+        pipelined.pipelined_proposals.iter().filter(|is_used| **is_used).count(),
+        pipelined
+            .pipelined_proposals
+            .iter()
+            .filter(|is_used| !**is_used)
+            .count(),
+    );
+    assert!(
+        pipelined.mean_block_interval < sequential.mean_block_interval,
+        "Pipelining did not reduce the mean block interval: {:.2?} sequential, {:.2?} pipelined",
+        sequential.mean_block_interval,
+        pipelined.mean_block_interval
+    );
+}
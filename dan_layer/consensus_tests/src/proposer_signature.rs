@@ -0,0 +1,130 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    block_validations::check_proposer_signature,
+    hotstuff::ProposalValidationError,
+    traits::ValidatorSignatureService,
+};
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::{committee::Committee, shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Block, GenesisConfig};
+
+use crate::support::{RoundRobinLeaderStrategy, TestAddress, TestVoteSignatureService};
+
+const LEADER_HEIGHT: NodeHeight = NodeHeight(4);
+
+#[test]
+fn it_accepts_a_block_signed_by_the_leader() {
+    let (committee, signers) = create_committee();
+    let leader = &signers[leader_index(&committee)];
+    let block = create_signed_block(leader, leader.public_key());
+
+    check_proposer_signature(&RoundRobinLeaderStrategy::new(), leader, &committee, &block).unwrap();
+}
+
+#[test]
+fn it_rejects_a_block_signed_by_a_non_leader() {
+    let (committee, signers) = create_committee();
+    let leader = &signers[leader_index(&committee)];
+    let non_leader = &signers[(leader_index(&committee) + 1) % signers.len()];
+
+    // The non-leader proposes a block in its own name
+    let block = create_signed_block(non_leader, non_leader.public_key());
+    let err = check_proposer_signature(&RoundRobinLeaderStrategy::new(), leader, &committee, &block).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidProposerSignature { .. }));
+
+    // The non-leader proposes a block in the name of the leader
+    let block = create_signed_block(non_leader, leader.public_key());
+    let err = check_proposer_signature(&RoundRobinLeaderStrategy::new(), leader, &committee, &block).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidProposerSignature { .. }));
+}
+
+#[test]
+fn it_rejects_an_unsigned_block() {
+    let (committee, signers) = create_committee();
+    let leader = &signers[leader_index(&committee)];
+    let block = create_block(leader.public_key().clone(), false);
+
+    let err = check_proposer_signature(&RoundRobinLeaderStrategy::new(), leader, &committee, &block).unwrap_err();
+    assert!(matches!(err, ProposalValidationError::InvalidProposerSignature { .. }));
+}
+
+#[test]
+fn it_accepts_an_unsigned_dummy_block() {
+    let (committee, signers) = create_committee();
+    let leader = &signers[leader_index(&committee)];
+    let block = create_block(leader.public_key().clone(), true);
+    assert!(block.is_dummy());
+
+    check_proposer_signature(&RoundRobinLeaderStrategy::new(), leader, &committee, &block).unwrap();
+}
+
+fn create_committee() -> (Committee<TestAddress>, Vec<TestVoteSignatureService>) {
+    let signers = ["1", "2", "3", "4"]
+        .into_iter()
+        .map(|addr| {
+            let addr = TestAddress::new(addr);
+            let mut signer = TestVoteSignatureService::new(PublicKey::default(), addr);
+            signer.public_key = PublicKey::from_secret_key(&signer.secret_key);
+            signer
+        })
+        .collect::<Vec<_>>();
+    let committee = Committee::new(
+        ["1", "2", "3", "4"]
+            .into_iter()
+            .map(TestAddress::new)
+            .zip(signers.iter().map(|s| s.public_key.clone()))
+            .collect(),
+    );
+    (committee, signers)
+}
+
+fn leader_index(committee: &Committee<TestAddress>) -> usize {
+    (LEADER_HEIGHT.as_u64() % committee.len() as u64) as usize
+}
+
+fn create_signed_block(signer: &TestVoteSignatureService, proposed_by: &PublicKey) -> Block {
+    let mut block = create_block(proposed_by.clone(), false);
+    block.set_signature(signer.sign(block.id()));
+    block
+}
+
+fn create_block(proposed_by: PublicKey, is_dummy: bool) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    if is_dummy {
+        return Block::dummy_block(
+            Network::LocalNet,
+            *zero_block.id(),
+            proposed_by,
+            LEADER_HEIGHT,
+            zero_block.justify().clone(),
+            Epoch(0),
+            Shard::from(0),
+            FixedHash::zero(),
+            0,
+            0,
+            FixedHash::zero(),
+        );
+    }
+
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        LEADER_HEIGHT,
+        Epoch(0),
+        Shard::from(0),
+        proposed_by,
+        Default::default(),
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
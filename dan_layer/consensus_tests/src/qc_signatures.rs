@@ -0,0 +1,91 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    block_validations::check_qc_signatures,
+    hotstuff::ProposalValidationError,
+    traits::VoteSignatureService,
+};
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{BlockId, QuorumCertificate, QuorumDecision, ValidatorSignature};
+
+use crate::support::{TestAddress, TestVoteSignatureService};
+
+#[test]
+fn it_accepts_a_qc_with_valid_signatures() {
+    let signers = create_signers();
+    let (qc, challenges_and_signatures) = create_qc(&signers, None);
+
+    check_qc_signatures(&signers[0], &qc, &as_batch(&challenges_and_signatures)).unwrap();
+}
+
+#[test]
+fn it_identifies_the_validator_with_an_invalid_signature() {
+    let signers = create_signers();
+    let (qc, challenges_and_signatures) = create_qc(&signers, Some(2));
+
+    let err = check_qc_signatures(&signers[0], &qc, &as_batch(&challenges_and_signatures)).unwrap_err();
+    match err {
+        ProposalValidationError::QCInvalidSignature { validator, .. } => {
+            assert_eq!(validator, signers[2].public_key.to_string());
+        },
+        err => panic!("Unexpected error: {err}"),
+    }
+}
+
+fn create_signers() -> Vec<TestVoteSignatureService> {
+    ["1", "2", "3", "4"]
+        .into_iter()
+        .map(|addr| {
+            let mut signer = TestVoteSignatureService::new(PublicKey::default(), TestAddress::new(addr));
+            signer.public_key = PublicKey::from_secret_key(&signer.secret_key);
+            signer
+        })
+        .collect()
+}
+
+/// Creates a QC signed by all signers. If `invalid_signer` is set, that signer signs a different block.
+fn create_qc(
+    signers: &[TestVoteSignatureService],
+    invalid_signer: Option<usize>,
+) -> (QuorumCertificate, Vec<(FixedHash, ValidatorSignature)>) {
+    let block_id = BlockId::new(FixedHash::from([1u8; 32]));
+    let other_block_id = BlockId::new(FixedHash::from([2u8; 32]));
+    let decision = QuorumDecision::Accept;
+
+    let challenges_and_signatures = signers
+        .iter()
+        .enumerate()
+        .map(|(i, signer)| {
+            let leaf_hash = FixedHash::from([i as u8 + 1; 32]);
+            let signed_block_id = if invalid_signer == Some(i) {
+                &other_block_id
+            } else {
+                &block_id
+            };
+            let signature = signer.sign_vote(&leaf_hash, signed_block_id, &decision);
+            (signer.create_challenge(&leaf_hash, &block_id, &decision), signature)
+        })
+        .collect::<Vec<_>>();
+
+    let qc = QuorumCertificate::new(
+        block_id,
+        NodeHeight(1),
+        Epoch(0),
+        Shard::from(0),
+        challenges_and_signatures.iter().map(|(_, s)| s.clone()).collect(),
+        (1..=signers.len() as u8).map(|i| FixedHash::from([i; 32])).collect(),
+        decision,
+    );
+
+    (qc, challenges_and_signatures)
+}
+
+fn as_batch(challenges_and_signatures: &[(FixedHash, ValidatorSignature)]) -> Vec<(FixedHash, &ValidatorSignature)> {
+    challenges_and_signatures
+        .iter()
+        .map(|(challenge, signature)| (*challenge, signature))
+        .collect()
+}
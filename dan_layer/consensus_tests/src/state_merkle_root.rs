@@ -0,0 +1,163 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{block_validations::check_state_merkle_root, hotstuff::ProposalValidationError};
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, BlockRejection, Decision, GenesisConfig, PeerMisbehavior},
+    StateStore,
+};
+use tari_epoch_manager::EpochManagerReader;
+
+use crate::support::{logging::setup_logger, Test, TestAddress};
+
+fn state_root() -> FixedHash {
+    FixedHash::from([1u8; 32])
+}
+
+fn corrupted_state_root() -> FixedHash {
+    FixedHash::from([2u8; 32])
+}
+
+#[test]
+fn it_accepts_a_block_with_the_calculated_merkle_root() {
+    let block = create_block(state_root(), false);
+    check_state_merkle_root(&block, &state_root()).unwrap();
+}
+
+#[test]
+fn it_rejects_a_block_with_a_corrupted_merkle_root() {
+    let block = create_block(corrupted_state_root(), false);
+    let err = check_state_merkle_root(&block, &state_root()).unwrap_err();
+    assert!(matches!(
+        err,
+        ProposalValidationError::StateMerkleRootMismatch { calculated, from_block, .. }
+            if calculated == state_root() && from_block == corrupted_state_root()
+    ));
+}
+
+#[test]
+fn a_merkle_root_mismatch_is_not_peer_misbehavior() {
+    let block = create_block(corrupted_state_root(), false);
+    let err = check_state_merkle_root(&block, &state_root()).unwrap_err();
+    assert!(!err.is_peer_misbehavior());
+}
+
+#[test]
+fn it_does_not_check_the_merkle_root_of_a_dummy_block() {
+    let block = create_block(corrupted_state_root(), true);
+    assert!(block.is_dummy());
+    check_state_merkle_root(&block, &state_root()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn proposals_with_a_corrupted_merkle_root_are_rejected() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_merkle_root_corrupting_leader("1")
+        .with_state_merkle_root_mismatch_rejection()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    let corrupting_leader = test
+        .get_validator(&TestAddress::new("1"))
+        .epoch_manager
+        .get_our_validator_node(Epoch(0))
+        .await
+        .unwrap()
+        .public_key;
+    let honest_validators = ["2", "3", "4"].map(TestAddress::new);
+    let get_merkle_root_rejections = |test: &Test, address: &TestAddress| {
+        test.get_validator(address)
+            .state_store
+            .with_read_tx(|tx| BlockRejection::get_paginated(tx, 100, 0))
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.reason.contains("State merkle root mismatch"))
+            .collect::<Vec<_>>()
+    };
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+
+        // Keep going until the corrupting validator has been the leader at least once
+        if test.is_transaction_pool_empty() &&
+            honest_validators
+                .iter()
+                .all(|address| !get_merkle_root_rejections(&test, address).is_empty())
+        {
+            break;
+        }
+        if committed_height > NodeHeight(30) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    for address in &honest_validators {
+        for rejection in get_merkle_root_rejections(&test, address) {
+            assert_eq!(
+                rejection.proposed_by, corrupting_leader,
+                "{} rejected an honest proposal",
+                address
+            );
+        }
+        // The mismatch may be caused by the local state rather than the proposer, so it is not counted as misbehavior
+        let misbehavior = test
+            .get_validator(address)
+            .state_store
+            .with_read_tx(|tx| PeerMisbehavior::get(tx, &TestAddress::new("1")).optional())
+            .unwrap();
+        assert!(misbehavior.is_none(), "{} recorded an offence for the leader", address);
+    }
+    test.assert_all_validators_committed();
+
+    test.assert_clean_shutdown().await;
+}
+
+fn create_block(merkle_root: FixedHash, is_dummy: bool) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    if is_dummy {
+        return Block::dummy_block(
+            Network::LocalNet,
+            *zero_block.id(),
+            PublicKey::default(),
+            NodeHeight(1),
+            zero_block.justify().clone(),
+            Epoch(0),
+            Shard::from(0),
+            merkle_root,
+            0,
+            0,
+            FixedHash::zero(),
+        );
+    }
+
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        NodeHeight(1),
+        Epoch(0),
+        Shard::from(0),
+        PublicKey::default(),
+        Default::default(),
+        merkle_root,
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
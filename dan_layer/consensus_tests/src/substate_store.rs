@@ -1,31 +1,51 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::time::Duration;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
 use tari_consensus::{
-    hotstuff::substate_store::{PendingSubstateStore, SubstateStoreError},
-    traits::{ReadableSubstateStore, WriteableSubstateStore},
+    hotstuff::substate_store::{PendingSubstateStore, SubstateSnapshot, SubstateSnapshotView, SubstateStoreError},
+    traits::{
+        BlockTransactionExecutor,
+        BlockTransactionExecutorError,
+        InputSubstateStore,
+        ReadableSubstateStore,
+        WriteableSubstateStore,
+    },
 };
-use tari_dan_common_types::PeerAddress;
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight, PeerAddress};
 use tari_dan_storage::{
     consensus_models::{
+        Block,
+        BlockDiff,
         BlockId,
+        Decision,
+        ExecutedTransaction,
+        GenesisConfig,
+        PendingStateTreeDiff,
         QcId,
         SubstateChange,
         SubstateLockFlag,
         SubstateRecord,
+        TransactionRecord,
         VersionedSubstateIdLockIntent,
     },
     StateStore,
 };
 use tari_engine_types::{
+    commit_result::{ExecuteResult, FinalizeResult, TransactionResult},
     component::{ComponentBody, ComponentHeader},
-    substate::{Substate, SubstateId, SubstateValue},
+    fees::FeeReceipt,
+    substate::{Substate, SubstateDiff, SubstateId, SubstateValue},
 };
+use tari_rpc_state_sync::create_zero_block_if_required;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_template_lib::models::{ComponentAddress, EntityId, ObjectKey};
-use tari_transaction::VersionedSubstateId;
+use tari_transaction::{SubstateRequirement, Transaction, VersionedSubstateId};
 
-use crate::support::logging::setup_logger;
+use crate::support::{build_transaction_with_inputs, logging::setup_logger};
 
 type TestStore = SqliteStateStore<PeerAddress>;
 
@@ -190,6 +210,187 @@ fn it_allows_locks_within_one_transaction() {
     assert_eq!(n, 2);
 }
 
+#[test]
+fn it_reads_the_changes_of_uncommitted_blocks_speculatively() {
+    let store = create_store();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+
+    let id = add_substate(&store, 0, 0);
+    let block = add_uncommitted_block(&store, vec![
+        SubstateChange::Down {
+            id: id.clone(),
+            transaction_id: tx_id(1),
+        },
+        SubstateChange::Up {
+            id: id.to_next_version(),
+            transaction_id: tx_id(1),
+            substate: new_substate(1, 1),
+        },
+    ]);
+
+    let tx = store.create_read_tx().unwrap();
+    // The changes of the block are not visible until it is committed
+    let pending_store = PendingSubstateStore::<'_, '_, TestStore>::new(&tx);
+    assert_substate_eq(pending_store.get_latest(id.substate_id()).unwrap(), new_substate(0, 0));
+
+    let speculative_store = PendingSubstateStore::<'_, '_, TestStore>::new_speculative(&tx, block.id()).unwrap();
+    assert_substate_eq(
+        speculative_store.get_latest(id.substate_id()).unwrap(),
+        new_substate(1, 1),
+    );
+    let s = speculative_store
+        .get(&id.to_next_version().to_substate_address())
+        .unwrap();
+    assert_substate_eq(s, new_substate(1, 1));
+    let err = speculative_store.get(&id.to_substate_address()).unwrap_err();
+    assert!(matches!(err, SubstateStoreError::SubstateIsDown { .. }));
+    // Speculative changes are not part of the diff of the block being built
+    assert!(speculative_store.diff().is_empty());
+}
+
+#[test]
+fn it_executes_a_block_in_parallel_with_the_same_results_as_sequentially() {
+    let store = create_store();
+    let x = add_substate(&store, 0, 0);
+    let y = add_substate(&store, 1, 0);
+    // A chain of transactions that each read the output of the previous one and a transaction that is independent of
+    // the chain
+    let transactions = [&x, &y, &x, &x]
+        .into_iter()
+        .map(|id| {
+            build_transaction_with_inputs(Decision::Commit, 1, [SubstateRequirement::new(
+                id.substate_id().clone(),
+                None,
+            )])
+        })
+        .collect::<Vec<_>>();
+
+    let tx = store.create_read_tx().unwrap();
+    let mut substate_store = PendingSubstateStore::<'_, '_, TestStore>::new(&tx);
+    let snapshot =
+        SubstateSnapshot::load(&substate_store, transactions.iter().map(TransactionRecord::transaction)).unwrap();
+    let parallel =
+        BlockTransactionExecutor::<TestStore>::execute_block(&IncrementingExecutor, transactions.clone(), &snapshot);
+
+    let sequential = transactions
+        .into_iter()
+        .map(|transaction| {
+            let executed = BlockTransactionExecutor::<TestStore>::execute(
+                &IncrementingExecutor,
+                transaction.into_transaction(),
+                &substate_store,
+            )
+            .unwrap();
+            substate_store
+                .put_diff(*executed.id(), executed.result().finalize.accept().unwrap())
+                .unwrap();
+            executed
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(parallel.len(), sequential.len());
+    for (parallel, sequential) in parallel.into_iter().zip(&sequential) {
+        let parallel = parallel.unwrap();
+        assert_eq!(parallel.id(), sequential.id());
+        assert_eq!(parallel.resolved_inputs(), sequential.resolved_inputs());
+        assert_eq!(parallel.resulting_outputs(), sequential.resulting_outputs());
+    }
+    assert_eq!(sequential[1].resulting_outputs(), &[y.to_next_version()]);
+    assert_eq!(sequential[3].resulting_outputs(), &[VersionedSubstateId::new(
+        x.substate_id().clone(),
+        3
+    )]);
+}
+
+/// Increments the version of every input, so the result of a transaction depends on the input versions that it read
+struct IncrementingExecutor;
+
+impl IncrementingExecutor {
+    fn increment<TStore: InputSubstateStore>(
+        transaction: Transaction,
+        store: &TStore,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        let mut diff = SubstateDiff::new();
+        let mut resolved_inputs = Vec::new();
+        let mut resulting_outputs = Vec::new();
+        for input in transaction.all_inputs_iter() {
+            let substate = store.get_latest(input.substate_id())?;
+            let id = VersionedSubstateId::new(input.substate_id, substate.version());
+            diff.down(id.substate_id().clone(), id.version());
+            diff.up(
+                id.substate_id().clone(),
+                Substate::new(id.version() + 1, substate.into_substate_value()),
+            );
+            resulting_outputs.push(id.to_next_version());
+            resolved_inputs.push(VersionedSubstateIdLockIntent::new(id, SubstateLockFlag::Write));
+        }
+
+        let finalize = FinalizeResult::new(
+            transaction.id().into_array().into(),
+            vec![],
+            vec![],
+            TransactionResult::Accept(diff),
+            FeeReceipt::default(),
+        );
+        Ok(ExecutedTransaction::new(
+            transaction,
+            ExecuteResult { finalize },
+            resolved_inputs.into_iter().collect(),
+            resulting_outputs,
+            Duration::ZERO,
+        ))
+    }
+}
+
+impl<TStateStore: StateStore> BlockTransactionExecutor<TStateStore> for IncrementingExecutor {
+    fn execute(
+        &self,
+        transaction: Transaction,
+        store: &PendingSubstateStore<TStateStore>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        Self::increment(transaction, store)
+    }
+
+    fn execute_with_snapshot(
+        &self,
+        transaction: Transaction,
+        snapshot: &SubstateSnapshotView<'_>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        Self::increment(transaction, snapshot)
+    }
+}
+
+fn add_uncommitted_block(store: &TestStore, changes: Vec<SubstateChange>) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    let block = Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        NodeHeight(1),
+        Epoch(0),
+        Shard::from(0),
+        Default::default(),
+        Default::default(),
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    );
+
+    store
+        .with_write_tx(|tx| {
+            block.insert(tx)?;
+            PendingStateTreeDiff::new(*block.id(), block.height(), Default::default()).save(tx)?;
+            BlockDiff::new(*block.id(), changes).insert(tx)
+        })
+        .unwrap();
+
+    block
+}
+
 fn add_substate(store: &TestStore, seed: u8, version: u32) -> VersionedSubstateId {
     let id = new_substate_id(seed);
     let value = new_substate_value(seed);
@@ -10,6 +10,8 @@ use async_trait::async_trait;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    committee_membership::{build_validator_node_bmt, CommitteeMembershipProof},
+    hashing::ValidatorNodeBalancedMerkleTree,
     shard::Shard,
     Epoch,
     SubstateAddress,
@@ -135,6 +137,19 @@ impl TestEpochManager {
     pub async fn all_committees(&self) -> HashMap<Shard, Committee<TestAddress>> {
         self.state_lock().await.committees.clone()
     }
+
+    /// Builds the validator node BMT. The test validator set is the same for every epoch.
+    pub async fn validator_node_bmt(&self) -> ValidatorNodeBalancedMerkleTree {
+        let state = self.state_lock().await;
+        let mut vns = state
+            .validator_shards
+            .values()
+            .filter(|(_, _, _, sidechain_id, _, _, _)| sidechain_id.is_none())
+            .map(|(shard, shard_key, public_key, _, _, _, _)| (shard_key, public_key, *shard))
+            .collect::<Vec<_>>();
+        vns.sort_by_key(|(shard_key, _, _)| **shard_key);
+        build_validator_node_bmt(vns.into_iter().map(|(_, public_key, shard)| (public_key, shard)))
+    }
 }
 
 #[async_trait]
@@ -179,6 +194,27 @@ impl EpochManagerReader for TestEpochManager {
         })
     }
 
+    async fn get_validator_node_bmt_root(&self, _epoch: Epoch) -> Result<FixedHash, EpochManagerError> {
+        let bmt = self.validator_node_bmt().await;
+        Ok(FixedHash::try_from(bmt.get_merkle_root()).unwrap())
+    }
+
+    async fn get_committee_membership_proof(
+        &self,
+        epoch: Epoch,
+        public_key: &PublicKey,
+    ) -> Result<CommitteeMembershipProof, EpochManagerError> {
+        let vn = self.get_validator_node_by_public_key(epoch, public_key).await?;
+        let shard = self.state_lock().await.validator_shards[&vn.address].0;
+        let bmt = self.validator_node_bmt().await;
+        CommitteeMembershipProof::generate(&bmt, epoch, shard, public_key.clone()).ok_or_else(|| {
+            EpochManagerError::ValidatorNodeNotRegistered {
+                address: vn.address.to_string(),
+                epoch,
+            }
+        })
+    }
+
     async fn get_all_validator_nodes(
         &self,
         _epoch: Epoch,
@@ -207,8 +243,9 @@ impl EpochManagerReader for TestEpochManager {
         Ok(self.inner.lock().await.last_block_of_current_epoch)
     }
 
-    async fn is_last_block_of_epoch(&self, _block_height: u64) -> Result<bool, EpochManagerError> {
-        Ok(false)
+    async fn is_last_block_of_epoch(&self, block_height: u64) -> Result<bool, EpochManagerError> {
+        // The test base layer does not advance, so its current block is the last block of every epoch
+        Ok(block_height == self.inner.lock().await.current_block_info.0)
     }
 
     async fn is_epoch_active(&self, _epoch: Epoch) -> Result<bool, EpochManagerError> {
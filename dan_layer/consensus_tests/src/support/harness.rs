@@ -3,16 +3,28 @@
 
 use std::{
     collections::{hash_map, HashMap, HashSet},
+    path::PathBuf,
     time::Duration,
 };
 
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
+use tari_common::configuration::Network;
 use tari_common_types::types::{PrivateKey, PublicKey};
-use tari_consensus::hotstuff::HotstuffEvent;
+use tari_consensus::{hotstuff::HotstuffEvent, journal::JournalConfig};
 use tari_crypto::keys::{PublicKey as _, SecretKey};
+use tari_dan_app_utilities::transaction_executor::TariDanTransactionProcessor;
 use tari_dan_common_types::{committee::Committee, shard::Shard, Epoch, NodeHeight};
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, Decision, QcId, SubstateRecord, TransactionRecord},
+    consensus_models::{
+        Block,
+        BlockId,
+        Decision,
+        GenesisConfig,
+        QcId,
+        SubstateRecord,
+        TransactionPoolOrdering,
+        TransactionRecord,
+    },
     StateStore,
     StorageError,
 };
@@ -23,16 +35,19 @@ use tari_engine_types::{
 use tari_epoch_manager::EpochManagerReader;
 use tari_shutdown::{Shutdown, ShutdownSignal};
 use tari_template_lib::models::ComponentAddress;
+use tari_template_test_tooling::Package;
 use tari_transaction::{TransactionId, VersionedSubstateId};
 use tokio::{sync::broadcast, task, time::sleep};
+use transaction_generator::transaction_builders::synthetic::TransactionGenerator;
 
 use super::MessageFilter;
 use crate::support::{
     address::TestAddress,
+    clock::TestClock,
     epoch_manager::TestEpochManager,
     executions_store::TestTransactionExecutionsStore,
     network::{spawn_network, TestNetwork, TestNetworkDestination},
-    transaction::build_transaction,
+    transaction::{build_transaction, build_transaction_from, create_execution_result_for_transaction},
     validator::Validator,
     RoundRobinLeaderStrategy,
     ValidatorChannels,
@@ -44,6 +59,7 @@ pub struct Test {
     transaction_executions: TestTransactionExecutionsStore,
     _leader_strategy: RoundRobinLeaderStrategy,
     epoch_manager: TestEpochManager,
+    clock: TestClock,
     shutdown: Shutdown,
     timeout: Option<Duration>,
 }
@@ -87,9 +103,41 @@ impl Test {
             "Creating more than 255 substates is not supported"
         );
 
-        let substates = (0..num)
-            .map(|i| {
-                let id = SubstateId::Component(ComponentAddress::from_array([i as u8; 28]));
+        self.create_components_on_all_vns(
+            (0..num).map(|i| SubstateId::Component(ComponentAddress::from_array([i as u8; 28]))),
+        )
+    }
+
+    /// Creates the synthetic accounts of the generator on all VNs and sends the first `num_transactions` generated
+    /// transactions to all VNs. The execution of each transaction is mocked to commit without outputs.
+    pub async fn send_generated_transactions(&self, generator: &TransactionGenerator, num_transactions: u64) {
+        self.create_components_on_all_vns(generator.accounts().map(|account| account.address.into()));
+        for index in 0..num_transactions {
+            let transaction = build_transaction_from(generator.generate(index), Decision::Commit, 1, vec![]);
+            let resolved_inputs = transaction
+                .transaction()
+                .inputs()
+                .iter()
+                .map(|input| VersionedSubstateId::new(input.substate_id.clone(), 0))
+                .collect();
+            self.transaction_executions
+                .insert(create_execution_result_for_transaction(
+                    BlockId::genesis(),
+                    *transaction.id(),
+                    Decision::Commit,
+                    1,
+                    resolved_inputs,
+                    vec![],
+                ));
+            self.send_transaction_to_destination(TestNetworkDestination::All, transaction)
+                .await;
+        }
+    }
+
+    fn create_components_on_all_vns<I: IntoIterator<Item = SubstateId>>(&self, ids: I) -> Vec<VersionedSubstateId> {
+        let substates = ids
+            .into_iter()
+            .map(|id| {
                 let value = SubstateValue::Component(ComponentHeader {
                     template_address: Default::default(),
                     module_name: "Test".to_string(),
@@ -166,10 +214,24 @@ impl Test {
                     log::info!("[{address}] Leader timeout. New height {new_height}");
                     continue;
                 },
+                HotstuffEvent::ProposerEquivocation {
+                    proposed_by, height, ..
+                } => {
+                    log::info!("[{address}] Leader {proposed_by} equivocated at height {height}");
+                    continue;
+                },
+                HotstuffEvent::BlockParked { .. } |
+                HotstuffEvent::ForeignProposalReceived { .. } |
+                HotstuffEvent::EpochChanged { .. } |
+                HotstuffEvent::Decided { .. } => continue,
             }
         }
     }
 
+    pub fn clock(&self) -> &TestClock {
+        &self.clock
+    }
+
     pub fn network(&mut self) -> &mut TestNetwork {
         &mut self.network
     }
@@ -363,6 +425,24 @@ pub struct TestBuilder {
     timeout: Option<Duration>,
     debug_sql_file: Option<String>,
     message_filter: Option<MessageFilter>,
+    vote_delays: HashMap<TestAddress, Duration>,
+    equivocating_leaders: HashSet<TestAddress>,
+    equivocating_voters: HashSet<TestAddress>,
+    foreign_proposal_replay_delay: Option<Duration>,
+    proposal_replay_delay: Option<Duration>,
+    genesis: GenesisConfig,
+    journal_dir: Option<PathBuf>,
+    committed_block_diff_retention: Option<u64>,
+    max_block_size_bytes: Option<usize>,
+    transaction_pool_ordering: TransactionPoolOrdering,
+    merkle_root_corrupting_leaders: HashSet<TestAddress>,
+    wrong_network_leaders: HashSet<TestAddress>,
+    reject_state_merkle_root_mismatch: bool,
+    peer_ban_threshold: Option<u64>,
+    enable_proposal_pipelining: bool,
+    execution_delay: Option<Duration>,
+    transaction_processor: Option<TariDanTransactionProcessor<Package>>,
+    flaky_voters: HashMap<TestAddress, usize>,
 }
 
 impl TestBuilder {
@@ -373,6 +453,24 @@ impl TestBuilder {
             timeout: Some(Duration::from_secs(10)),
             debug_sql_file: None,
             message_filter: None,
+            vote_delays: HashMap::new(),
+            equivocating_leaders: HashSet::new(),
+            equivocating_voters: HashSet::new(),
+            foreign_proposal_replay_delay: None,
+            proposal_replay_delay: None,
+            genesis: GenesisConfig::default(),
+            journal_dir: None,
+            committed_block_diff_retention: None,
+            max_block_size_bytes: None,
+            transaction_pool_ordering: TransactionPoolOrdering::default(),
+            merkle_root_corrupting_leaders: HashSet::new(),
+            wrong_network_leaders: HashSet::new(),
+            reject_state_merkle_root_mismatch: false,
+            peer_ban_threshold: None,
+            enable_proposal_pipelining: true,
+            execution_delay: None,
+            transaction_processor: None,
+            flaky_voters: HashMap::new(),
         }
     }
 
@@ -421,11 +519,122 @@ impl TestBuilder {
         self
     }
 
+    /// Delays the delivery of all votes sent by the validator
+    pub fn with_vote_delay(mut self, address: &'static str, delay: Duration) -> Self {
+        self.vote_delays.insert(TestAddress::new(address), delay);
+        self
+    }
+
+    /// Makes the validator send a second, conflicting proposal after every proposal that it broadcasts
+    pub fn with_equivocating_leader(mut self, address: &'static str) -> Self {
+        self.equivocating_leaders.insert(TestAddress::new(address));
+        self
+    }
+
+    /// Makes the validator send a second vote for a different block after every vote that it sends
+    pub fn with_equivocating_voter(mut self, address: &'static str) -> Self {
+        self.equivocating_voters.insert(TestAddress::new(address));
+        self
+    }
+
+    /// Delivers every foreign proposal a second time after the given delay, as happens when a proposal is re-delivered
+    /// after a sync
+    pub fn with_foreign_proposal_replay(mut self, delay: Duration) -> Self {
+        self.foreign_proposal_replay_delay = Some(delay);
+        self
+    }
+
+    /// Delivers every local proposal a second time after the given delay, as happens when a proposal is re-delivered
+    /// by the network
+    pub fn with_proposal_replay(mut self, delay: Duration) -> Self {
+        self.proposal_replay_delay = Some(delay);
+        self
+    }
+
+    pub fn with_genesis(mut self, genesis: GenesisConfig) -> Self {
+        self.genesis = genesis;
+        self
+    }
+
+    /// Enables the consensus journal. Each validator writes its journal to a subdirectory named after its address.
+    pub fn with_consensus_journal<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.journal_dir = Some(dir.into());
+        self
+    }
+
+    /// Keeps the diffs of the given number of most recently committed blocks in each validator's state store
+    pub fn with_committed_block_diff_retention(mut self, retain: u64) -> Self {
+        self.committed_block_diff_retention = Some(retain);
+        self
+    }
+
+    /// Limits the estimated size of the commands in blocks proposed by each validator
+    pub fn with_max_block_size_bytes(mut self, max_block_size_bytes: usize) -> Self {
+        self.max_block_size_bytes = Some(max_block_size_bytes);
+        self
+    }
+
+    /// Sets the order in which each validator selects transactions from the pool when proposing
+    pub fn with_transaction_pool_ordering(mut self, ordering: TransactionPoolOrdering) -> Self {
+        self.transaction_pool_ordering = ordering;
+        self
+    }
+
+    /// Makes the validator send its proposals to other validators with a corrupted state merkle root
+    pub fn with_merkle_root_corrupting_leader(mut self, address: &'static str) -> Self {
+        self.merkle_root_corrupting_leaders.insert(TestAddress::new(address));
+        self
+    }
+
+    /// Makes the validator send its proposals to other validators for a different network
+    pub fn with_wrong_network_leader(mut self, address: &'static str) -> Self {
+        self.wrong_network_leaders.insert(TestAddress::new(address));
+        self
+    }
+
+    /// Makes all validators reject proposals with a state merkle root that does not match the calculated root
+    pub fn with_state_merkle_root_mismatch_rejection(mut self) -> Self {
+        self.reject_state_merkle_root_mismatch = true;
+        self
+    }
+
+    /// Makes all validators ban a peer once it has sent this many invalid proposals or votes
+    pub fn with_peer_ban_threshold(mut self, threshold: u64) -> Self {
+        self.peer_ban_threshold = Some(threshold);
+        self
+    }
+
+    /// Makes leaders execute the transactions for their proposals only after the QC for the parent block is formed
+    pub fn disable_proposal_pipelining(mut self) -> Self {
+        self.enable_proposal_pipelining = false;
+        self
+    }
+
+    /// Makes every execution of a deferred transaction take at least this long
+    pub fn with_execution_delay(mut self, delay: Duration) -> Self {
+        self.execution_delay = Some(delay);
+        self
+    }
+
+    /// Executes transactions that do not have a registered execution with the given transaction processor
+    pub fn with_transaction_processor(mut self, transaction_processor: TariDanTransactionProcessor<Package>) -> Self {
+        self.transaction_processor = Some(transaction_processor);
+        self
+    }
+
+    /// Makes the first `num_failures` attempts of the validator to send each vote fail, as if the leader were
+    /// unreachable
+    pub fn with_flaky_voter(mut self, address: &'static str, num_failures: usize) -> Self {
+        self.flaky_voters.insert(TestAddress::new(address), num_failures);
+        self
+    }
+
     async fn build_validators(
         &self,
         leader_strategy: &RoundRobinLeaderStrategy,
         epoch_manager: &TestEpochManager,
         transaction_executions: TestTransactionExecutionsStore,
+        clock: &TestClock,
         shutdown_signal: ShutdownSignal,
     ) -> (Vec<ValidatorChannels>, HashMap<TestAddress, Validator>) {
         epoch_manager
@@ -442,6 +651,22 @@ impl TestBuilder {
                     .with_bucket(bucket)
                     .with_epoch_manager(epoch_manager.clone_for(address.clone(), pk, shard))
                     .with_leader_strategy(*leader_strategy)
+                    .with_clock(clock.clone())
+                    .with_genesis(self.genesis)
+                    .with_journal(self.journal_dir.as_ref().map(|dir| JournalConfig {
+                        directory: dir.join(&address.0),
+                        max_file_size: 1024 * 1024,
+                        max_rotated_files: 1,
+                    }))
+                    .with_committed_block_diff_retention(self.committed_block_diff_retention)
+                    .with_max_block_size_bytes(self.max_block_size_bytes)
+                    .with_transaction_pool_ordering(self.transaction_pool_ordering)
+                    .with_reject_state_merkle_root_mismatch(self.reject_state_merkle_root_mismatch)
+                    .with_peer_ban_threshold(self.peer_ban_threshold)
+                    .with_proposal_pipelining(self.enable_proposal_pipelining)
+                    .with_execution_delay(self.execution_delay)
+                    .with_transaction_processor(self.transaction_processor.clone())
+                    .with_flaky_vote_sends(self.flaky_voters.get(&address).copied())
                     .spawn(shutdown_signal.clone());
                 (channels, (address, validator))
             })
@@ -468,15 +693,33 @@ impl TestBuilder {
         epoch_manager.add_committees(self.committees.clone()).await;
         let shutdown = Shutdown::new();
         let transaction_executions = TestTransactionExecutionsStore::new();
+        let clock = TestClock::default();
         let (channels, validators) = self
             .build_validators(
                 &leader_strategy,
                 &epoch_manager,
                 transaction_executions.clone(),
+                &clock,
                 shutdown.to_signal(),
             )
             .await;
-        let network = spawn_network(channels, shutdown.to_signal(), self.message_filter);
+        let mut equivocating_voters = HashMap::with_capacity(self.equivocating_voters.len());
+        for address in self.equivocating_voters {
+            let vn = epoch_manager.get_validator_node(Epoch(0), &address).await.unwrap();
+            equivocating_voters.insert(address, vn.get_node_hash(Network::LocalNet));
+        }
+        let network = spawn_network(
+            channels,
+            shutdown.to_signal(),
+            self.message_filter,
+            self.vote_delays,
+            self.equivocating_leaders,
+            equivocating_voters,
+            self.merkle_root_corrupting_leaders,
+            self.wrong_network_leaders,
+            self.foreign_proposal_replay_delay,
+            self.proposal_replay_delay,
+        );
 
         Test {
             validators,
@@ -485,6 +728,7 @@ impl TestBuilder {
 
             _leader_strategy: leader_strategy,
             epoch_manager,
+            clock,
             shutdown,
             timeout: self.timeout,
         }
@@ -0,0 +1,145 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{Arc, Mutex},
+};
+
+use tari_consensus::{hotstuff::HotStuffError, messages::HotstuffMessage, traits::hooks::ConsensusHooks};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{
+    Block,
+    BlockId,
+    ProposerEquivocation,
+    QcTiming,
+    QuorumDecision,
+    TransactionAtom,
+    ValidBlock,
+    VoteTiming,
+};
+use tari_transaction::TransactionId;
+
+/// Records the blocks that a validator commits and the leader failures that it observes
+#[derive(Debug, Clone, Default)]
+pub struct TestHooks {
+    committed_blocks: Arc<Mutex<Vec<BlockId>>>,
+    leader_timeouts: Arc<Mutex<Vec<(NodeHeight, u32)>>>,
+    dummy_blocks: Arc<Mutex<Vec<NodeHeight>>>,
+    outbound_queue_depths: Arc<Mutex<HashMap<String, usize>>>,
+    banned_peer_messages_dropped: Arc<Mutex<HashMap<String, usize>>>,
+    pipelined_proposals: Arc<Mutex<Vec<bool>>>,
+    vote_send_retries: Arc<Mutex<Vec<bool>>>,
+}
+
+impl TestHooks {
+    /// Returns the committed block ids in the order that the hook was called
+    pub fn committed_blocks(&self) -> Vec<BlockId> {
+        self.committed_blocks.lock().unwrap().clone()
+    }
+
+    /// Returns the new height and round of each leader timeout in the order that the hook was called
+    pub fn leader_timeouts(&self) -> Vec<(NodeHeight, u32)> {
+        self.leader_timeouts.lock().unwrap().clone()
+    }
+
+    /// Returns the heights of the dummy blocks created in the order that the hook was called
+    pub fn dummy_blocks(&self) -> Vec<NodeHeight> {
+        self.dummy_blocks.lock().unwrap().clone()
+    }
+
+    /// Returns the last reported outbound queue depth for the peer
+    pub fn outbound_queue_depth<TAddr: Display>(&self, peer: &TAddr) -> Option<usize> {
+        self.outbound_queue_depths
+            .lock()
+            .unwrap()
+            .get(&peer.to_string())
+            .copied()
+    }
+
+    /// Returns the number of messages from the peer that were dropped because the peer is banned
+    pub fn banned_peer_messages_dropped<TAddr: Display>(&self, peer: &TAddr) -> usize {
+        self.banned_peer_messages_dropped
+            .lock()
+            .unwrap()
+            .get(&peer.to_string())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns whether the prepared executions were used for each pipelined proposal in the order that the hook was
+    /// called
+    pub fn pipelined_proposals(&self) -> Vec<bool> {
+        self.pipelined_proposals.lock().unwrap().clone()
+    }
+
+    /// Returns whether each retry of sending a vote succeeded in the order that the hook was called
+    pub fn vote_send_retries(&self) -> Vec<bool> {
+        self.vote_send_retries.lock().unwrap().clone()
+    }
+}
+
+impl ConsensusHooks for TestHooks {
+    fn on_local_block_decide(&mut self, _block: &ValidBlock, _decision: Option<QuorumDecision>) {}
+
+    fn on_block_validation_failed<E: ToString>(&mut self, _: &E) {}
+
+    fn on_message_received(&mut self, _message: &HotstuffMessage) {}
+
+    fn on_error(&mut self, _err: &HotStuffError) {}
+
+    fn on_pacemaker_height_changed(&mut self, _: NodeHeight) {}
+
+    fn on_leader_timeout(&mut self, new_height: NodeHeight, round: u32) {
+        self.leader_timeouts.lock().unwrap().push((new_height, round));
+    }
+
+    fn on_dummy_block_created(&mut self, height: NodeHeight) {
+        self.dummy_blocks.lock().unwrap().push(height);
+    }
+
+    fn on_beat(&mut self) {}
+
+    fn on_needs_sync(&mut self, _local_height: NodeHeight, _remote_qc_height: NodeHeight) {}
+
+    fn on_transaction_ready(&mut self, _tx_id: &TransactionId) {}
+
+    fn on_transaction_finalized(&mut self, _transaction: &TransactionAtom) {}
+
+    fn on_block_committed(&mut self, block: &Block) {
+        self.committed_blocks.lock().unwrap().push(*block.id());
+    }
+
+    fn on_vote_received(&mut self, _epoch: Epoch, _vote: &VoteTiming) {}
+
+    fn on_qc_formed(&mut self, _timing: &QcTiming) {}
+
+    fn on_maintenance_mode_changed(&mut self, _is_enabled: bool) {}
+
+    fn on_proposer_equivocation(&mut self, _equivocation: &ProposerEquivocation) {}
+
+    fn on_outbound_queue_depth_changed<TAddr: Display>(&mut self, peer: &TAddr, depth: usize) {
+        self.outbound_queue_depths
+            .lock()
+            .unwrap()
+            .insert(peer.to_string(), depth);
+    }
+
+    fn on_banned_peer_message_dropped<TAddr: Display>(&mut self, peer: &TAddr, _message: &HotstuffMessage) {
+        *self
+            .banned_peer_messages_dropped
+            .lock()
+            .unwrap()
+            .entry(peer.to_string())
+            .or_default() += 1;
+    }
+
+    fn on_pipelined_proposal(&mut self, is_used: bool) {
+        self.pipelined_proposals.lock().unwrap().push(is_used);
+    }
+
+    fn on_vote_send_retried(&mut self, is_sent: bool) {
+        self.vote_send_retries.lock().unwrap().push(is_sent);
+    }
+}
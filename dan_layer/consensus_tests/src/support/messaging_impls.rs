@@ -1,12 +1,18 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
 use async_trait::async_trait;
 use tari_consensus::{
     messages::HotstuffMessage,
     traits::{InboundMessaging, InboundMessagingError, OutboundMessaging, OutboundMessagingError},
 };
-use tokio::sync::mpsc;
+use tari_dan_storage::consensus_models::BlockId;
+use tokio::sync::{mpsc, mpsc::error::TrySendError};
 
 use crate::support::TestAddress;
 
@@ -15,6 +21,7 @@ pub struct TestOutboundMessaging {
     tx_leader: mpsc::Sender<(TestAddress, HotstuffMessage)>,
     tx_broadcast: mpsc::Sender<(Vec<TestAddress>, HotstuffMessage)>,
     loopback_sender: mpsc::Sender<HotstuffMessage>,
+    flaky_vote_sends: Option<FlakyVoteSends>,
 }
 
 impl TestOutboundMessaging {
@@ -28,10 +35,39 @@ impl TestOutboundMessaging {
                 tx_leader,
                 tx_broadcast,
                 loopback_sender,
+                flaky_vote_sends: None,
             },
             loopback_receiver,
         )
     }
+
+    /// Makes the first `num_failures` attempts to send each vote fail, as if the leader were unreachable
+    pub fn with_flaky_vote_sends(mut self, num_failures: Option<usize>) -> Self {
+        self.flaky_vote_sends = num_failures.map(|num_failures| FlakyVoteSends {
+            num_failures,
+            attempts: Arc::new(Mutex::new(HashMap::new())),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FlakyVoteSends {
+    num_failures: usize,
+    attempts: Arc<Mutex<HashMap<BlockId, usize>>>,
+}
+
+impl FlakyVoteSends {
+    /// Records an attempt to send the message and returns true if the attempt should fail
+    fn should_fail(&self, message: &HotstuffMessage) -> bool {
+        let HotstuffMessage::Vote(vote) = message else {
+            return false;
+        };
+        let mut attempts = self.attempts.lock().unwrap();
+        let attempt = attempts.entry(vote.block_id).or_default();
+        *attempt += 1;
+        *attempt <= self.num_failures
+    }
 }
 
 #[async_trait]
@@ -52,14 +88,34 @@ impl OutboundMessaging for TestOutboundMessaging {
         to: Self::Addr,
         message: T,
     ) -> Result<(), OutboundMessagingError> {
+        let message = message.into();
+        if self.flaky_vote_sends.as_ref().is_some_and(|f| f.should_fail(&message)) {
+            return Err(OutboundMessagingError::FailedToEnqueueMessage {
+                reason: format!("leader {} is unreachable", to),
+            });
+        }
         self.tx_leader
-            .send((to, message.into()))
+            .send((to, message))
             .await
             .map_err(|_| OutboundMessagingError::FailedToEnqueueMessage {
                 reason: "leader channel closed".to_string(),
             })
     }
 
+    fn try_send<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+    ) -> Result<bool, OutboundMessagingError> {
+        match self.tx_leader.try_send((to, message.into())) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => Ok(false),
+            Err(TrySendError::Closed(_)) => Err(OutboundMessagingError::FailedToEnqueueMessage {
+                reason: "leader channel closed".to_string(),
+            }),
+        }
+    }
+
     async fn multicast<'a, I, T>(&mut self, committee: I, message: T) -> Result<(), OutboundMessagingError>
     where
         Self::Addr: 'a,
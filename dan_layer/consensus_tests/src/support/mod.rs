@@ -5,10 +5,12 @@
 // #![allow(dead_code)]
 
 mod address;
+mod clock;
 mod epoch_manager;
 mod executions_store;
 mod harness;
 mod helpers;
+mod hooks;
 mod leader_strategy;
 pub mod logging;
 mod messaging_impls;
@@ -21,9 +23,13 @@ mod transaction_executor;
 mod validator;
 
 pub use address::*;
+pub use clock::*;
+pub use epoch_manager::*;
 pub use harness::*;
+pub use hooks::*;
 pub use leader_strategy::*;
 pub use network::*;
+pub use signing_service::*;
 pub use spec::*;
 pub use transaction::*;
 pub use transaction_executor::*;
@@ -2,15 +2,24 @@
 //    SPDX-License-Identifier: BSD-3-Clause
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{atomic::AtomicUsize, Arc},
+    time::Duration,
 };
 
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
 use itertools::Itertools;
-use tari_consensus::messages::HotstuffMessage;
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_consensus::{
+    messages::{HotstuffMessage, ProposalMessage, VoteMessage},
+    traits::{ValidatorSignatureService, VoteSignatureService},
+};
 use tari_dan_common_types::shard::Shard;
-use tari_dan_storage::{consensus_models::TransactionRecord, StateStore};
+use tari_dan_storage::{
+    consensus_models::{Block, BlockId, TransactionRecord},
+    StateStore,
+};
 use tari_shutdown::ShutdownSignal;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_transaction::{Transaction, TransactionId};
@@ -23,7 +32,7 @@ use tokio::{
     task,
 };
 
-use crate::support::{address::TestAddress, ValidatorChannels};
+use crate::support::{address::TestAddress, TestVoteSignatureService, ValidatorChannels};
 
 pub type MessageFilter = Box<dyn Fn(&TestAddress, &TestAddress, &HotstuffMessage) -> bool + Sync + Send + 'static>;
 
@@ -31,6 +40,13 @@ pub fn spawn_network(
     channels: Vec<ValidatorChannels>,
     shutdown_signal: ShutdownSignal,
     message_filter: Option<MessageFilter>,
+    vote_delays: HashMap<TestAddress, Duration>,
+    equivocating_leaders: HashSet<TestAddress>,
+    equivocating_voters: HashMap<TestAddress, FixedHash>,
+    merkle_root_corrupting_leaders: HashSet<TestAddress>,
+    wrong_network_leaders: HashSet<TestAddress>,
+    foreign_proposal_replay_delay: Option<Duration>,
+    proposal_replay_delay: Option<Duration>,
 ) -> TestNetwork {
     let tx_new_transactions = channels
         .iter()
@@ -78,6 +94,13 @@ pub fn spawn_network(
         offline_destinations: offline_destinations.clone(),
         shutdown_signal,
         message_filter,
+        vote_delays,
+        equivocating_leaders,
+        equivocating_voters,
+        merkle_root_corrupting_leaders,
+        wrong_network_leaders,
+        foreign_proposal_replay_delay,
+        proposal_replay_delay,
     }
     .spawn();
 
@@ -123,14 +146,18 @@ impl TestNetwork {
         &self.network_task_handle
     }
 
+    /// Drops messages to the destination. Taking a bucket offline only drops messages between that bucket and other
+    /// buckets, so the committee continues to reach consensus amongst itself.
     pub async fn go_offline(&self, destination: TestNetworkDestination) -> &Self {
-        if destination.is_bucket() {
-            unimplemented!("Sorry :/ taking a bucket offline is not yet supported in the test harness");
-        }
         self.offline_destinations.write().await.push(destination);
         self
     }
 
+    pub async fn go_online(&self, destination: &TestNetworkDestination) -> &Self {
+        self.offline_destinations.write().await.retain(|d| d != destination);
+        self
+    }
+
     #[allow(dead_code)]
     pub async fn on_message(&mut self) -> Option<HotstuffMessage> {
         self._on_message.changed().await.unwrap();
@@ -155,11 +182,10 @@ impl TestNetwork {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TestNetworkDestination {
     All,
     Address(TestAddress),
-    #[allow(dead_code)]
     Shard(u32),
 }
 
@@ -203,6 +229,21 @@ pub struct TestNetworkWorker {
     offline_destinations: Arc<RwLock<Vec<TestNetworkDestination>>>,
     shutdown_signal: ShutdownSignal,
     message_filter: Option<MessageFilter>,
+    /// Votes sent by these validators are delivered after the given delay
+    vote_delays: HashMap<TestAddress, Duration>,
+    /// Every proposal broadcast by these validators is followed by a conflicting proposal for the same height
+    equivocating_leaders: HashSet<TestAddress>,
+    /// Every vote sent by these validators is followed by a conflicting vote for the same height. The value is the
+    /// leaf hash of the validator, which the conflicting vote is signed over.
+    equivocating_voters: HashMap<TestAddress, FixedHash>,
+    /// Other validators receive the proposals of these validators with a corrupted state merkle root
+    merkle_root_corrupting_leaders: HashSet<TestAddress>,
+    /// Other validators receive the proposals of these validators for a different network
+    wrong_network_leaders: HashSet<TestAddress>,
+    /// Every foreign proposal is delivered again after this delay
+    foreign_proposal_replay_delay: Option<Duration>,
+    /// Every local proposal is delivered again after this delay
+    proposal_replay_delay: Option<Duration>,
 }
 
 impl TestNetworkWorker {
@@ -308,6 +349,21 @@ impl TestNetworkWorker {
 
     pub async fn handle_broadcast(&mut self, from: TestAddress, to: Vec<TestAddress>, msg: HotstuffMessage) {
         log::debug!("🌎️ Broadcast {} from {} to {}", msg, from, to.iter().join(", "));
+        let conflicting_proposal = match msg {
+            HotstuffMessage::Proposal(ref proposal) if self.equivocating_leaders.contains(&from) => {
+                Some(forge_conflicting_proposal(&from, &proposal.block))
+            },
+            _ => None,
+        };
+        let corrupted_proposal = match msg {
+            HotstuffMessage::Proposal(ref proposal) if self.merkle_root_corrupting_leaders.contains(&from) => {
+                Some(forge_proposal_with_corrupted_merkle_root(&from, &proposal.block))
+            },
+            HotstuffMessage::Proposal(ref proposal) if self.wrong_network_leaders.contains(&from) => {
+                Some(forge_proposal_for_wrong_network(&from, &proposal.block))
+            },
+            _ => None,
+        };
         for vn in to {
             if let Some(message_filter) = &self.message_filter {
                 if !message_filter(&from, &vn, &msg) {
@@ -316,19 +372,49 @@ impl TestNetworkWorker {
                     continue;
                 }
             }
-            // TODO: support for taking a whole committee bucket offline
             if vn != from && self.is_offline_destination(&vn, u32::MAX.into()).await {
                 continue;
             }
+            if self.is_bucket_offline(&from, &vn).await {
+                continue;
+            }
 
+            let msg_to_send = corrupted_proposal.as_ref().filter(|_| vn != from).unwrap_or(&msg);
             self.tx_hs_message
                 .get(&vn)
                 .unwrap()
-                .send((from.clone(), msg.clone()))
+                .send((from.clone(), msg_to_send.clone()))
                 .await
                 .unwrap();
             self.num_sent_messages
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+            if let Some(conflicting_proposal) = conflicting_proposal.as_ref().filter(|_| vn != from) {
+                log::info!("😈 Sending conflicting proposal from {} to {}", from, vn);
+                self.tx_hs_message
+                    .get(&vn)
+                    .unwrap()
+                    .send((from.clone(), conflicting_proposal.clone()))
+                    .await
+                    .unwrap();
+            }
+
+            let replay_delay = match msg {
+                HotstuffMessage::Proposal(_) => self.proposal_replay_delay,
+                HotstuffMessage::ForeignProposal(_) => self.foreign_proposal_replay_delay,
+                _ => None,
+            };
+            if let Some(delay) = replay_delay {
+                let tx_hs_message = self.tx_hs_message.get(&vn).unwrap().clone();
+                let from = from.clone();
+                let msg = msg.clone();
+                task::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    log::info!("🔁 Replaying {} from {} to {}", msg, from, vn);
+                    // The validator may have shut down by the time the proposal is replayed
+                    let _ignore = tx_hs_message.send((from, msg)).await;
+                });
+            }
         }
         self.on_message.send(Some(msg.clone())).unwrap();
     }
@@ -345,10 +431,36 @@ impl TestNetworkWorker {
         if from != to && self.is_offline_destination(&from, u32::MAX.into()).await {
             return;
         }
+        if self.is_bucket_offline(&from, &to).await {
+            return;
+        }
         self.on_message.send(Some(msg.clone())).unwrap();
         self.num_sent_messages
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        self.tx_hs_message.get(&to).unwrap().send((from, msg)).await.unwrap();
+        let tx_hs_message = self.tx_hs_message.get(&to).unwrap();
+        if let HotstuffMessage::Vote(ref vote) = msg {
+            if let Some(leaf_hash) = self.equivocating_voters.get(&from) {
+                log::info!("😈 Sending conflicting vote from {} to {}", from, to);
+                tx_hs_message
+                    .send((from.clone(), forge_conflicting_vote(&from, leaf_hash, vote)))
+                    .await
+                    .unwrap();
+            }
+        }
+        match self.vote_delays.get(&from) {
+            Some(delay) if matches!(msg, HotstuffMessage::Vote(_)) => {
+                let delay = *delay;
+                let tx_hs_message = tx_hs_message.clone();
+                task::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    // The validator may have shut down by the time the delayed vote is sent
+                    let _ignore = tx_hs_message.send((from, msg)).await;
+                });
+            },
+            _ => {
+                tx_hs_message.send((from, msg)).await.unwrap();
+            },
+        }
     }
 
     async fn is_offline_destination(&self, addr: &TestAddress, bucket: Shard) -> bool {
@@ -356,6 +468,27 @@ impl TestNetworkWorker {
         lock.iter().any(|d| d.is_for(addr, bucket))
     }
 
+    /// Returns true if the message crosses into or out of a bucket that is offline
+    async fn is_bucket_offline(&self, from: &TestAddress, to: &TestAddress) -> bool {
+        let from_bucket = self.get_bucket(from);
+        let to_bucket = self.get_bucket(to);
+        if from_bucket == to_bucket {
+            return false;
+        }
+        let lock = self.offline_destinations.read().await;
+        lock.iter()
+            .filter(|d| d.is_bucket())
+            .any(|d| d.is_for(from, from_bucket) || d.is_for(to, to_bucket))
+    }
+
+    fn get_bucket(&self, addr: &TestAddress) -> Shard {
+        let (bucket, _, _) = self
+            .tx_new_transactions
+            .get(addr)
+            .unwrap_or_else(|| panic!("No validator registered for {}", addr));
+        *bucket
+    }
+
     /// Handles transactions that come in from missing transactions
     async fn handle_mempool(&mut self, from: TestAddress, msg: Transaction) {
         let (_, sender, state_store) = self
@@ -377,3 +510,96 @@ impl TestNetworkWorker {
         sender.send((*existing_tx.id(), 0)).await.unwrap();
     }
 }
+
+/// Creates a different block for the same height and epoch as the given block, signed by the same proposer
+fn forge_conflicting_proposal(proposer: &TestAddress, block: &Block) -> HotstuffMessage {
+    let mut conflicting = Block::new(
+        block.network(),
+        *block.parent(),
+        block.justify().clone(),
+        block.height(),
+        block.epoch(),
+        block.shard(),
+        block.proposed_by().clone(),
+        block.commands().clone(),
+        *block.merkle_root(),
+        block.total_leader_fee(),
+        block.foreign_indexes().clone(),
+        None,
+        // The timestamp is part of the block hash
+        block.timestamp() + 1,
+        block.base_layer_block_height(),
+        *block.base_layer_block_hash(),
+    );
+    let signing_service = TestVoteSignatureService::new(block.proposed_by().clone(), proposer.clone());
+    conflicting.set_signature(signing_service.sign(conflicting.id()));
+    HotstuffMessage::Proposal(ProposalMessage { block: conflicting })
+}
+
+/// Creates a copy of the proposal with a corrupted state merkle root, signed by the same proposer
+fn forge_proposal_with_corrupted_merkle_root(proposer: &TestAddress, block: &Block) -> HotstuffMessage {
+    let mut merkle_root = [0u8; 32];
+    merkle_root.copy_from_slice(block.merkle_root().as_slice());
+    merkle_root[0] ^= 0xff;
+    let mut corrupted = Block::new(
+        block.network(),
+        *block.parent(),
+        block.justify().clone(),
+        block.height(),
+        block.epoch(),
+        block.shard(),
+        block.proposed_by().clone(),
+        block.commands().clone(),
+        FixedHash::from(merkle_root),
+        block.total_leader_fee(),
+        block.foreign_indexes().clone(),
+        None,
+        block.timestamp(),
+        block.base_layer_block_height(),
+        *block.base_layer_block_hash(),
+    );
+    let signing_service = TestVoteSignatureService::new(block.proposed_by().clone(), proposer.clone());
+    corrupted.set_signature(signing_service.sign(corrupted.id()));
+    HotstuffMessage::Proposal(ProposalMessage { block: corrupted })
+}
+
+/// Creates a copy of the proposal for a different network, signed by the same proposer
+fn forge_proposal_for_wrong_network(proposer: &TestAddress, block: &Block) -> HotstuffMessage {
+    let mut forged = Block::new(
+        Network::Igor,
+        *block.parent(),
+        block.justify().clone(),
+        block.height(),
+        block.epoch(),
+        block.shard(),
+        block.proposed_by().clone(),
+        block.commands().clone(),
+        *block.merkle_root(),
+        block.total_leader_fee(),
+        block.foreign_indexes().clone(),
+        None,
+        block.timestamp(),
+        block.base_layer_block_height(),
+        *block.base_layer_block_hash(),
+    );
+    let signing_service = TestVoteSignatureService::new(block.proposed_by().clone(), proposer.clone());
+    forged.set_signature(signing_service.sign(forged.id()));
+    HotstuffMessage::Proposal(ProposalMessage { block: forged })
+}
+
+/// Creates a vote for a different block at the same height as the given vote, signed by the same voter
+fn forge_conflicting_vote(voter: &TestAddress, leaf_hash: &FixedHash, vote: &VoteMessage) -> HotstuffMessage {
+    let mut block_id = [0u8; 32];
+    block_id.copy_from_slice(vote.block_id.as_bytes());
+    block_id[0] ^= 0xff;
+    let block_id = BlockId::new(block_id);
+
+    let signing_service = TestVoteSignatureService::new(vote.signature.public_key.clone(), voter.clone());
+    HotstuffMessage::Vote(VoteMessage {
+        epoch: vote.epoch,
+        block_id,
+        block_height: vote.block_height,
+        decision: vote.decision,
+        signature: signing_service.sign_vote(leaf_hash, &block_id, &vote.decision),
+    })
+}
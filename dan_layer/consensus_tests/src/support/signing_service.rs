@@ -1,11 +1,22 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
 use rand::rngs::OsRng;
 use tari_common_types::types::{FixedHash, PrivateKey, PublicKey};
 use tari_consensus::traits::{ValidatorSignatureService, VoteSignatureService};
 use tari_crypto::keys::SecretKey;
-use tari_dan_storage::consensus_models::{BlockId, QuorumDecision, ValidatorSchnorrSignature, ValidatorSignature};
+use tari_dan_storage::consensus_models::{
+    verify_validator_signatures_batch,
+    BlockId,
+    QuorumDecision,
+    ValidatorSchnorrSignature,
+    ValidatorSignature,
+};
 
 use super::TestAddress;
 
@@ -14,6 +25,7 @@ pub struct TestVoteSignatureService {
     pub public_key: PublicKey,
     pub secret_key: PrivateKey,
     pub is_signature_valid: bool,
+    batch_verifications: Arc<AtomicUsize>,
 }
 
 impl TestVoteSignatureService {
@@ -25,8 +37,14 @@ impl TestVoteSignatureService {
             public_key,
             secret_key,
             is_signature_valid: true,
+            batch_verifications: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Returns the number of times that a batch of vote signatures was verified by this service or its clones
+    pub fn num_batch_verifications(&self) -> usize {
+        self.batch_verifications.load(Ordering::SeqCst)
+    }
 }
 
 impl ValidatorSignatureService for TestVoteSignatureService {
@@ -49,4 +67,9 @@ impl VoteSignatureService for TestVoteSignatureService {
     ) -> bool {
         self.is_signature_valid
     }
+
+    fn verify_batch(&self, challenges_and_signatures: &[(FixedHash, &ValidatorSignature)]) -> bool {
+        self.batch_verifications.fetch_add(1, Ordering::SeqCst);
+        verify_validator_signatures_batch(challenges_and_signatures)
+    }
 }
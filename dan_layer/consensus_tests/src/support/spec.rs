@@ -1,17 +1,19 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_consensus::traits::{hooks::NoopHooks, ConsensusSpec};
+use tari_consensus::traits::ConsensusSpec;
 use tari_state_store_sqlite::SqliteStateStore;
 
 use super::TestBlockTransactionProcessor;
 use crate::support::{
     address::TestAddress,
+    clock::TestClock,
     epoch_manager::TestEpochManager,
     messaging_impls::{TestInboundMessaging, TestOutboundMessaging},
     signing_service::TestVoteSignatureService,
     sync::AlwaysSyncedSyncManager,
     RoundRobinLeaderStrategy,
+    TestHooks,
 };
 
 #[derive(Clone)]
@@ -19,8 +21,9 @@ pub struct TestConsensusSpec;
 
 impl ConsensusSpec for TestConsensusSpec {
     type Addr = TestAddress;
+    type Clock = TestClock;
     type EpochManager = TestEpochManager;
-    type Hooks = NoopHooks;
+    type Hooks = TestHooks;
     type InboundMessaging = TestInboundMessaging;
     type LeaderStrategy = RoundRobinLeaderStrategy;
     type OutboundMessaging = TestOutboundMessaging;
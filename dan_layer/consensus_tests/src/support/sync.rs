@@ -2,10 +2,12 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
 use tari_consensus::{
     hotstuff::HotStuffError,
-    traits::{SyncManager, SyncStatus},
+    traits::{SyncManager, SyncProgressStream, SyncStatus},
 };
+use tari_dan_storage::consensus_models::SyncCheckpoint;
 
 #[derive(Clone)]
 pub struct AlwaysSyncedSyncManager;
@@ -21,4 +23,8 @@ impl SyncManager for AlwaysSyncedSyncManager {
     async fn sync(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    fn sync_from_checkpoint(&mut self, _checkpoint: SyncCheckpoint) -> SyncProgressStream<'_, Self::Error> {
+        stream::empty().boxed()
+    }
 }
@@ -94,6 +94,7 @@ pub fn create_execution_result_for_transaction(
                 total_fee_payment: fee.try_into().unwrap(),
                 total_fees_paid: fee.try_into().unwrap(),
                 cost_breakdown: vec![],
+                total_storage_bytes: 0,
             }),
         },
         resolved_inputs
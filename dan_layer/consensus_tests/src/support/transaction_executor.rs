@@ -1,26 +1,122 @@
 //    Copyright 2024 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+use std::{thread, time::Duration};
+
 use tari_consensus::{
-    hotstuff::substate_store::PendingSubstateStore,
-    traits::{BlockTransactionExecutor, BlockTransactionExecutorError},
+    hotstuff::substate_store::{PendingSubstateStore, SubstateSnapshotView},
+    traits::{BlockTransactionExecutor, BlockTransactionExecutorError, InputSubstateStore},
+};
+use tari_dan_app_utilities::transaction_executor::{TariDanTransactionProcessor, TransactionExecutor};
+use tari_dan_common_types::optional::Optional;
+use tari_dan_engine::{
+    bootstrap_state,
+    state_store::{memory::MemoryStateStore, AtomicDb, StateWriter},
 };
 use tari_dan_storage::{
     consensus_models::{ExecutedTransaction, TransactionRecord},
     StateStore,
 };
-use tari_transaction::Transaction;
+use tari_engine_types::virtual_substate::VirtualSubstates;
+use tari_template_test_tooling::Package;
+use tari_transaction::{Transaction, VersionedSubstateId};
 
 use crate::support::executions_store::TestTransactionExecutionsStore;
 
 #[derive(Debug, Clone)]
 pub struct TestBlockTransactionProcessor {
     store: TestTransactionExecutionsStore,
+    execution_delay: Option<Duration>,
+    engine: Option<TariDanTransactionProcessor<Package>>,
 }
 
 impl TestBlockTransactionProcessor {
     pub fn new(store: TestTransactionExecutionsStore) -> Self {
-        Self { store }
+        Self {
+            store,
+            execution_delay: None,
+            engine: None,
+        }
+    }
+
+    /// Simulates the time that it takes to execute a transaction
+    pub fn with_execution_delay(mut self, delay: Option<Duration>) -> Self {
+        self.execution_delay = delay;
+        self
+    }
+
+    /// Executes transactions that do not have a registered execution with the engine
+    pub fn with_engine(mut self, engine: Option<TariDanTransactionProcessor<Package>>) -> Self {
+        self.engine = engine;
+        self
+    }
+}
+
+impl TestBlockTransactionProcessor {
+    fn get_registered_execution<TStore: InputSubstateStore>(
+        &self,
+        transaction: &Transaction,
+        store: &TStore,
+    ) -> Result<Option<ExecutedTransaction>, BlockTransactionExecutorError> {
+        if let Some(delay) = self.execution_delay {
+            thread::sleep(delay);
+        }
+
+        // Like the real executor, a transaction cannot be executed if one of its inputs does not exist
+        for input in transaction.all_inputs_iter() {
+            if store.get_latest(input.substate_id()).optional()?.is_none() {
+                return Err(BlockTransactionExecutorError::UnableToResolveSubstateId {
+                    substate_id: input.substate_id,
+                });
+            }
+        }
+
+        let Some(execution) = self.store.get(transaction.id()) else {
+            return Ok(None);
+        };
+        let mut rec = TransactionRecord::new(transaction.clone());
+        rec.resolved_inputs = Some(execution.resolved_inputs().clone());
+        rec.result = Some(execution.result().clone());
+        rec.resulting_outputs.clone_from(execution.resulting_outputs());
+        rec.execution_time = Some(execution.execution_time());
+
+        Ok(Some(rec.try_into().unwrap()))
+    }
+
+    fn execute_with_engine<TStore: InputSubstateStore>(
+        &self,
+        transaction: &Transaction,
+        store: &TStore,
+    ) -> Result<Option<ExecutedTransaction>, BlockTransactionExecutorError> {
+        let Some(engine) = self.engine.as_ref() else {
+            return Ok(None);
+        };
+
+        let state_db = MemoryStateStore::new();
+        let mut access = state_db.write_access().unwrap();
+        bootstrap_state(&mut access).unwrap();
+        let mut inputs = Vec::with_capacity(transaction.num_unique_inputs());
+        for input in transaction.all_inputs_iter() {
+            let substate = store.get_latest(input.substate_id())?;
+            access.set_state(input.substate_id(), &substate).unwrap();
+            inputs.push((
+                VersionedSubstateId::new(input.substate_id, substate.version()),
+                substate,
+            ));
+        }
+        access.commit().unwrap();
+
+        let output = engine
+            .execute(transaction.clone(), state_db, VirtualSubstates::new())
+            .map_err(|e| BlockTransactionExecutorError::ExecutionThreadFailure(e.to_string()))?;
+        let resolved_inputs = output.resolve_inputs(inputs.into_iter().collect());
+        Ok(Some(ExecutedTransaction::new(
+            output.transaction,
+            output.result,
+            resolved_inputs,
+            output.outputs,
+            output.execution_time,
+        )))
     }
 }
 
@@ -30,16 +126,31 @@ impl<TStateStore: StateStore> BlockTransactionExecutor<TStateStore> for TestBloc
         transaction: Transaction,
         store: &PendingSubstateStore<TStateStore>,
     ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
-        if let Some(execution) = self.store.get(transaction.id()) {
-            let mut rec = TransactionRecord::new(transaction);
-            rec.resolved_inputs = Some(execution.resolved_inputs().clone());
-            rec.result = Some(execution.result().clone());
-            rec.resulting_outputs.clone_from(execution.resulting_outputs());
-            rec.execution_time = Some(execution.execution_time());
-
-            return Ok(rec.try_into().unwrap());
+        if let Some(executed) = self.get_registered_execution(&transaction, store)? {
+            return Ok(executed);
+        }
+        if let Some(executed) = self.execute_with_engine(&transaction, store)? {
+            return Ok(executed);
         }
         let executed = ExecutedTransaction::get(store.read_transaction(), transaction.id())?;
         Ok(executed)
     }
+
+    fn execute_with_snapshot(
+        &self,
+        transaction: Transaction,
+        snapshot: &SubstateSnapshotView<'_>,
+    ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+        if let Some(executed) = self.get_registered_execution(&transaction, snapshot)? {
+            return Ok(executed);
+        }
+        // There is no store to fall back to, so the transaction is executed again against the store when it is
+        // sequenced
+        self.execute_with_engine(&transaction, snapshot)?.ok_or_else(|| {
+            BlockTransactionExecutorError::StateStoreError(format!(
+                "No execution registered for transaction {}",
+                transaction.id()
+            ))
+        })
+    }
 }
@@ -1,20 +1,36 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::time::Duration;
+
 use tari_common::configuration::Network;
 use tari_common_types::types::PublicKey;
 use tari_consensus::{
-    hotstuff::{ConsensusCurrentState, ConsensusWorker, ConsensusWorkerContext, HotstuffConfig, HotstuffWorker},
-    traits::hooks::NoopHooks,
+    hotstuff::{
+        ConsensusCurrentState,
+        ConsensusWorker,
+        ConsensusWorkerContext,
+        DeferredExecutionConfig,
+        HotstuffConfig,
+        HotstuffWorker,
+        MaintenanceMode,
+        PacemakerConfig,
+        PeerBanConfig,
+        VoteRetryConfig,
+    },
+    journal::JournalConfig,
 };
-use tari_dan_common_types::{shard::Shard, SubstateAddress};
-use tari_dan_storage::consensus_models::TransactionPool;
+use tari_dan_app_utilities::transaction_executor::TariDanTransactionProcessor;
+use tari_dan_common_types::{shard::Shard, NodeHeight, SubstateAddress};
+use tari_dan_storage::consensus_models::{GenesisConfig, TransactionPool, TransactionPoolOrdering};
 use tari_shutdown::ShutdownSignal;
 use tari_state_store_sqlite::SqliteStateStore;
+use tari_template_test_tooling::Package;
 use tokio::sync::{broadcast, mpsc, watch};
 
 use crate::support::{
     address::TestAddress,
+    clock::TestClock,
     epoch_manager::TestEpochManager,
     executions_store::TestTransactionExecutionsStore,
     messaging_impls::{TestInboundMessaging, TestOutboundMessaging},
@@ -23,6 +39,7 @@ use crate::support::{
     RoundRobinLeaderStrategy,
     TestBlockTransactionProcessor,
     TestConsensusSpec,
+    TestHooks,
     Validator,
     ValidatorChannels,
 };
@@ -36,6 +53,18 @@ pub struct ValidatorBuilder {
     pub leader_strategy: RoundRobinLeaderStrategy,
     pub epoch_manager: Option<TestEpochManager>,
     pub transaction_executions: TestTransactionExecutionsStore,
+    pub clock: TestClock,
+    pub genesis: GenesisConfig,
+    pub journal: Option<JournalConfig>,
+    pub committed_block_diff_retention: Option<u64>,
+    pub max_block_size_bytes: Option<usize>,
+    pub transaction_pool_ordering: TransactionPoolOrdering,
+    pub reject_state_merkle_root_mismatch: bool,
+    pub peer_ban_threshold: Option<u64>,
+    pub enable_proposal_pipelining: bool,
+    pub execution_delay: Option<Duration>,
+    pub transaction_processor: Option<TariDanTransactionProcessor<Package>>,
+    pub flaky_vote_sends: Option<usize>,
 }
 
 impl ValidatorBuilder {
@@ -49,6 +78,18 @@ impl ValidatorBuilder {
             leader_strategy: RoundRobinLeaderStrategy::new(),
             epoch_manager: None,
             transaction_executions: TestTransactionExecutionsStore::new(),
+            clock: TestClock::default(),
+            genesis: GenesisConfig::default(),
+            journal: None,
+            committed_block_diff_retention: None,
+            max_block_size_bytes: None,
+            transaction_pool_ordering: TransactionPoolOrdering::default(),
+            reject_state_merkle_root_mismatch: false,
+            peer_ban_threshold: None,
+            enable_proposal_pipelining: true,
+            execution_delay: None,
+            transaction_processor: None,
+            flaky_vote_sends: None,
         }
     }
 
@@ -83,6 +124,69 @@ impl ValidatorBuilder {
         self
     }
 
+    pub fn with_clock(&mut self, clock: TestClock) -> &mut Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_genesis(&mut self, genesis: GenesisConfig) -> &mut Self {
+        self.genesis = genesis;
+        self
+    }
+
+    pub fn with_journal(&mut self, journal: Option<JournalConfig>) -> &mut Self {
+        self.journal = journal;
+        self
+    }
+
+    pub fn with_committed_block_diff_retention(&mut self, retain: Option<u64>) -> &mut Self {
+        self.committed_block_diff_retention = retain;
+        self
+    }
+
+    pub fn with_max_block_size_bytes(&mut self, max_block_size_bytes: Option<usize>) -> &mut Self {
+        self.max_block_size_bytes = max_block_size_bytes;
+        self
+    }
+
+    pub fn with_transaction_pool_ordering(&mut self, ordering: TransactionPoolOrdering) -> &mut Self {
+        self.transaction_pool_ordering = ordering;
+        self
+    }
+
+    pub fn with_reject_state_merkle_root_mismatch(&mut self, reject: bool) -> &mut Self {
+        self.reject_state_merkle_root_mismatch = reject;
+        self
+    }
+
+    pub fn with_peer_ban_threshold(&mut self, peer_ban_threshold: Option<u64>) -> &mut Self {
+        self.peer_ban_threshold = peer_ban_threshold;
+        self
+    }
+
+    pub fn with_proposal_pipelining(&mut self, enable: bool) -> &mut Self {
+        self.enable_proposal_pipelining = enable;
+        self
+    }
+
+    pub fn with_execution_delay(&mut self, delay: Option<Duration>) -> &mut Self {
+        self.execution_delay = delay;
+        self
+    }
+
+    pub fn with_transaction_processor(
+        &mut self,
+        transaction_processor: Option<TariDanTransactionProcessor<Package>>,
+    ) -> &mut Self {
+        self.transaction_processor = transaction_processor;
+        self
+    }
+
+    pub fn with_flaky_vote_sends(&mut self, num_failures: Option<usize>) -> &mut Self {
+        self.flaky_vote_sends = num_failures;
+        self
+    }
+
     pub fn with_leader_strategy(&mut self, leader_strategy: RoundRobinLeaderStrategy) -> &mut Self {
         self.leader_strategy = leader_strategy;
         self
@@ -102,11 +206,12 @@ impl ValidatorBuilder {
         let (tx_mempool, rx_mempool) = mpsc::unbounded_channel();
 
         let (outbound_messaging, rx_loopback) = TestOutboundMessaging::create(tx_leader, tx_broadcast);
+        let outbound_messaging = outbound_messaging.with_flaky_vote_sends(self.flaky_vote_sends);
         let inbound_messaging = TestInboundMessaging::new(self.address.clone(), rx_hs_message, rx_loopback);
 
         let store = SqliteStateStore::connect(&self.sql_url).unwrap();
         let signing_service = TestVoteSignatureService::new(self.public_key.clone(), self.address.clone());
-        let transaction_pool = TransactionPool::new();
+        let transaction_pool = TransactionPool::with_ordering(self.transaction_pool_ordering);
         let (tx_events, _) = broadcast::channel(100);
 
         let epoch_manager =
@@ -115,7 +220,11 @@ impl ValidatorBuilder {
                 .unwrap()
                 .clone_for(self.address.clone(), self.public_key.clone(), self.shard);
 
-        let transaction_executor = TestBlockTransactionProcessor::new(self.transaction_executions.clone());
+        let transaction_executor = TestBlockTransactionProcessor::new(self.transaction_executions.clone())
+            .with_execution_delay(self.execution_delay)
+            .with_engine(self.transaction_processor.clone());
+        let maintenance_mode = MaintenanceMode::default();
+        let hooks = TestHooks::default();
 
         let worker = HotstuffWorker::<TestConsensusSpec>::new(
             self.address.clone(),
@@ -131,14 +240,42 @@ impl ValidatorBuilder {
             transaction_executor,
             tx_events.clone(),
             tx_mempool,
-            NoopHooks,
+            hooks.clone(),
+            self.clock.clone(),
+            maintenance_mode.clone(),
             shutdown_signal.clone(),
             HotstuffConfig {
                 max_base_layer_blocks_ahead: 5,
                 max_base_layer_blocks_behind: 5,
+                genesis: self.genesis,
+                journal: self.journal.clone(),
+                committed_block_diff_retention: self.committed_block_diff_retention,
+                foreign_proposal_timeout: NodeHeight(1000),
+                max_dummy_blocks: 1000,
+                max_block_size_bytes: self.max_block_size_bytes.unwrap_or(3 * 1024 * 1024),
+                max_block_transactions: 1000,
+                block_rejection_retention_epochs: 10,
+                foreign_proposal_retention_epochs: 10,
+                reject_state_merkle_root_mismatch: self.reject_state_merkle_root_mismatch,
+                verified_qc_cache_size: 1024,
+                enable_proposal_pipelining: self.enable_proposal_pipelining,
+                pacemaker: PacemakerConfig::default(),
+                deferred_execution: DeferredExecutionConfig {
+                    max_attempts: 3,
+                    max_age: NodeHeight(1000),
+                },
+                peer_bans: PeerBanConfig {
+                    ban_threshold: self.peer_ban_threshold.unwrap_or(10),
+                    ..Default::default()
+                },
+                vote_retry: VoteRetryConfig {
+                    max_retries: 3,
+                    initial_backoff: Duration::from_millis(50),
+                },
             },
         );
 
+        let status = worker.status_handle();
         let (tx_current_state, rx_current_state) = watch::channel(ConsensusCurrentState::default());
         let context = ConsensusWorkerContext {
             epoch_manager: epoch_manager.clone(),
@@ -169,6 +306,9 @@ impl ValidatorBuilder {
             leader_strategy: self.leader_strategy,
             events: tx_events.subscribe(),
             current_state_machine_state: rx_current_state,
+            maintenance_mode,
+            hooks,
+            status,
             handle,
         };
         (channels, validator)
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use tari_consensus::{
-    hotstuff::{ConsensusCurrentState, HotstuffEvent},
+    hotstuff::{ConsensusCurrentState, ConsensusStatus, ConsensusStatusHandle, HotstuffEvent, MaintenanceMode},
     messages::HotstuffMessage,
 };
 use tari_dan_common_types::{shard::Shard, SubstateAddress};
@@ -18,6 +18,8 @@ use crate::support::{
     address::TestAddress,
     epoch_manager::TestEpochManager,
     RoundRobinLeaderStrategy,
+    TestConsensusSpec,
+    TestHooks,
     ValidatorBuilder,
 };
 
@@ -42,6 +44,9 @@ pub struct Validator {
     pub leader_strategy: RoundRobinLeaderStrategy,
     pub events: broadcast::Receiver<HotstuffEvent>,
     pub current_state_machine_state: watch::Receiver<ConsensusCurrentState>,
+    pub maintenance_mode: MaintenanceMode,
+    pub hooks: TestHooks,
+    pub status: ConsensusStatusHandle<TestConsensusSpec>,
 
     pub handle: JoinHandle<()>,
 }
@@ -70,6 +75,10 @@ impl Validator {
         *self.current_state_machine_state.borrow()
     }
 
+    pub async fn get_consensus_status(&self) -> ConsensusStatus {
+        self.status.get_status().await.unwrap()
+    }
+
     pub fn get_leaf_block(&self) -> LeafBlock {
         self.state_store.with_read_tx(|tx| LeafBlock::get(tx)).unwrap()
     }
@@ -0,0 +1,164 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeSet;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_consensus::{
+    block_validations::check_transaction_epoch_bounds,
+    hotstuff::{HotStuffError, ProposalValidationError},
+};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{Block, Command, Decision, GenesisConfig, TransactionAtom, TransactionRecord},
+    StateStore,
+};
+use tari_rpc_state_sync::create_zero_block_if_required;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_transaction::{Transaction, TransactionId};
+
+use crate::support::TestAddress;
+
+type TestStore = SqliteStateStore<TestAddress>;
+
+/// The epoch of the proposed blocks
+const BLOCK_EPOCH: Epoch = Epoch(5);
+
+fn create_store() -> TestStore {
+    let store = SqliteStateStore::connect(":memory:").unwrap();
+    create_zero_block_if_required(&store, Network::LocalNet, &GenesisConfig::default()).unwrap();
+    store
+}
+
+fn insert_transaction(store: &TestStore, min_epoch: Option<Epoch>, max_epoch: Option<Epoch>) -> TransactionId {
+    let transaction = Transaction::builder()
+        .with_min_epoch(min_epoch)
+        .with_max_epoch(max_epoch)
+        .sign(&Default::default())
+        .build();
+    let record = TransactionRecord::new(transaction);
+    store.with_write_tx(|tx| record.insert(tx)).unwrap();
+    *record.id()
+}
+
+fn create_atom(id: TransactionId, decision: Decision) -> TransactionAtom {
+    TransactionAtom {
+        id,
+        decision,
+        evidence: Default::default(),
+        transaction_fee: 0,
+        leader_fee: None,
+        priority_fee: 0,
+    }
+}
+
+fn create_block(commands: BTreeSet<Command>) -> Block {
+    let zero_block = Block::zero_block_with_genesis(Network::LocalNet, &GenesisConfig::default());
+    Block::new(
+        Network::LocalNet,
+        *zero_block.id(),
+        zero_block.justify().clone(),
+        NodeHeight(1),
+        BLOCK_EPOCH,
+        Shard::from(0),
+        Default::default(),
+        commands,
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
+
+fn check(store: &TestStore, block: &Block) -> Result<(), HotStuffError> {
+    store.with_read_tx(|tx| check_transaction_epoch_bounds(tx, block))
+}
+
+fn assert_outside_epoch_bounds(result: Result<(), HotStuffError>, expected_transaction_id: &TransactionId) {
+    match result {
+        Err(HotStuffError::ProposalValidationError(ProposalValidationError::TransactionOutsideEpochBounds {
+            transaction_id,
+            ..
+        })) => assert_eq!(transaction_id, *expected_transaction_id),
+        other => panic!("expected TransactionOutsideEpochBounds but got {:?}", other),
+    }
+}
+
+#[test]
+fn it_accepts_transactions_within_their_epoch_bounds() {
+    let store = create_store();
+    let unbounded = insert_transaction(&store, None, None);
+    let bounded = insert_transaction(&store, Some(BLOCK_EPOCH), Some(BLOCK_EPOCH));
+
+    let block = create_block(BTreeSet::from([
+        Command::LocalOnly(create_atom(unbounded, Decision::Commit)),
+        Command::Prepare(create_atom(bounded, Decision::Commit)),
+    ]));
+    check(&store, &block).unwrap();
+}
+
+#[test]
+fn it_rejects_a_block_that_commits_an_expired_transaction() {
+    let store = create_store();
+    let expired = insert_transaction(&store, None, Some(Epoch(4)));
+
+    let block = create_block(BTreeSet::from([Command::Prepare(create_atom(
+        expired,
+        Decision::Commit,
+    ))]));
+    assert_outside_epoch_bounds(check(&store, &block), &expired);
+
+    let block = create_block(BTreeSet::from([Command::LocalOnly(create_atom(
+        expired,
+        Decision::Commit,
+    ))]));
+    assert_outside_epoch_bounds(check(&store, &block), &expired);
+}
+
+#[test]
+fn it_accepts_a_block_that_aborts_an_expired_transaction() {
+    let store = create_store();
+    let expired = insert_transaction(&store, None, Some(Epoch(4)));
+
+    let block = create_block(BTreeSet::from([Command::Prepare(create_atom(
+        expired,
+        Decision::Abort,
+    ))]));
+    check(&store, &block).unwrap();
+}
+
+#[test]
+fn it_rejects_a_block_that_includes_a_transaction_that_is_not_yet_valid() {
+    let store = create_store();
+    let not_yet_valid = insert_transaction(&store, Some(Epoch(6)), None);
+
+    let block = create_block(BTreeSet::from([Command::Prepare(create_atom(
+        not_yet_valid,
+        Decision::Commit,
+    ))]));
+    assert_outside_epoch_bounds(check(&store, &block), &not_yet_valid);
+
+    // Only an expired transaction may be aborted outside of its bounds
+    let block = create_block(BTreeSet::from([Command::LocalOnly(create_atom(
+        not_yet_valid,
+        Decision::Abort,
+    ))]));
+    assert_outside_epoch_bounds(check(&store, &block), &not_yet_valid);
+}
+
+#[test]
+fn it_does_not_check_the_bounds_of_accepted_transactions() {
+    let store = create_store();
+    let expired = insert_transaction(&store, None, Some(Epoch(4)));
+
+    // The transaction was prepared within its bounds in an earlier epoch
+    let block = create_block(BTreeSet::from([Command::Accept(create_atom(
+        expired,
+        Decision::Commit,
+    ))]));
+    check(&store, &block).unwrap();
+}
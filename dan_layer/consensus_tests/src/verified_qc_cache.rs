@@ -0,0 +1,167 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::{
+    block_validations::check_quorum_certificate_cached,
+    hotstuff::VerifiedQcCache,
+    traits::VoteSignatureService,
+};
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::{committee::Committee, shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Block, BlockId, QcId, QuorumCertificate, QuorumDecision};
+use tari_epoch_manager::EpochManagerReader;
+use tokio::sync::broadcast;
+
+use crate::support::{TestAddress, TestConsensusSpec, TestEpochManager, TestVoteSignatureService};
+
+#[test]
+fn it_evicts_the_least_recently_used_qc() {
+    let mut cache = VerifiedQcCache::new(2);
+    cache.insert(Epoch(1), qc_id(1));
+    cache.insert(Epoch(1), qc_id(2));
+    assert!(cache.contains(Epoch(1), &qc_id(1)));
+
+    cache.insert(Epoch(1), qc_id(3));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(Epoch(1), &qc_id(1)));
+    assert!(!cache.contains(Epoch(1), &qc_id(2)));
+    assert!(cache.contains(Epoch(1), &qc_id(3)));
+}
+
+#[test]
+fn it_only_holds_qcs_verified_in_the_latest_epoch() {
+    let mut cache = VerifiedQcCache::new(2);
+    cache.insert(Epoch(1), qc_id(1));
+    assert!(!cache.contains(Epoch(2), &qc_id(1)));
+
+    cache.insert(Epoch(2), qc_id(2));
+    assert_eq!(cache.len(), 1);
+    assert!(!cache.contains(Epoch(1), &qc_id(1)));
+    assert!(cache.contains(Epoch(2), &qc_id(2)));
+}
+
+#[tokio::test]
+async fn it_skips_verification_of_a_cached_qc() {
+    let (epoch_manager, signers) = create_epoch_manager().await;
+    let justify = create_qc(&epoch_manager, &signers).await;
+    let signing_service = signers[0].clone();
+    let mut cache = VerifiedQcCache::new(1024);
+
+    let block = create_block(justify.clone(), NodeHeight(2));
+    let is_hit =
+        check_quorum_certificate_cached::<TestConsensusSpec>(&block, &signing_service, &epoch_manager, &mut cache)
+            .await
+            .unwrap();
+    assert!(!is_hit);
+    assert_eq!(signing_service.num_batch_verifications(), 1);
+
+    // A dummy block or later proposal with the same justify
+    let block = create_block(justify, NodeHeight(3));
+    let is_hit =
+        check_quorum_certificate_cached::<TestConsensusSpec>(&block, &signing_service, &epoch_manager, &mut cache)
+            .await
+            .unwrap();
+    assert!(is_hit);
+    assert_eq!(signing_service.num_batch_verifications(), 1);
+}
+
+#[tokio::test]
+async fn it_verifies_a_cached_qc_again_after_the_cache_is_cleared() {
+    let (epoch_manager, signers) = create_epoch_manager().await;
+    let justify = create_qc(&epoch_manager, &signers).await;
+    let signing_service = signers[0].clone();
+    let mut cache = VerifiedQcCache::new(1024);
+    let block = create_block(justify, NodeHeight(2));
+
+    check_quorum_certificate_cached::<TestConsensusSpec>(&block, &signing_service, &epoch_manager, &mut cache)
+        .await
+        .unwrap();
+    // The cache is cleared when the epoch changes
+    cache.clear();
+    let is_hit =
+        check_quorum_certificate_cached::<TestConsensusSpec>(&block, &signing_service, &epoch_manager, &mut cache)
+            .await
+            .unwrap();
+    assert!(!is_hit);
+    assert_eq!(signing_service.num_batch_verifications(), 2);
+}
+
+fn qc_id(n: u8) -> QcId {
+    QcId::new(FixedHash::from([n; 32]))
+}
+
+async fn create_epoch_manager() -> (TestEpochManager, Vec<TestVoteSignatureService>) {
+    let signers = ["1", "2", "3", "4"]
+        .into_iter()
+        .map(|addr| {
+            let mut signer = TestVoteSignatureService::new(PublicKey::default(), TestAddress::new(addr));
+            signer.public_key = PublicKey::from_secret_key(&signer.secret_key);
+            signer
+        })
+        .collect::<Vec<_>>();
+    let committee = Committee::new(
+        ["1", "2", "3", "4"]
+            .into_iter()
+            .map(TestAddress::new)
+            .zip(signers.iter().map(|s| s.public_key.clone()))
+            .collect(),
+    );
+
+    let (tx_events, _) = broadcast::channel(10);
+    let epoch_manager = TestEpochManager::new(tx_events);
+    epoch_manager
+        .add_committees(HashMap::from([(Shard::from(0), committee)]))
+        .await;
+    (epoch_manager, signers)
+}
+
+/// Creates a QC for a block at height 1 that is signed by all signers
+async fn create_qc(epoch_manager: &TestEpochManager, signers: &[TestVoteSignatureService]) -> QuorumCertificate {
+    let block_id = BlockId::new(FixedHash::from([1u8; 32]));
+    let decision = QuorumDecision::Accept;
+    let mut signatures = Vec::with_capacity(signers.len());
+    let mut leaf_hashes = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let leaf_hash = epoch_manager
+            .get_validator_node_by_public_key(Epoch(0), &signer.public_key)
+            .await
+            .unwrap()
+            .get_node_hash(Network::LocalNet);
+        signatures.push(signer.sign_vote(&leaf_hash, &block_id, &decision));
+        leaf_hashes.push(leaf_hash);
+    }
+
+    QuorumCertificate::new(
+        block_id,
+        NodeHeight(1),
+        Epoch(0),
+        Shard::from(0),
+        signatures,
+        leaf_hashes,
+        decision,
+    )
+}
+
+fn create_block(justify: QuorumCertificate, height: NodeHeight) -> Block {
+    Block::new(
+        Network::LocalNet,
+        *justify.block_id(),
+        justify,
+        height,
+        Epoch(0),
+        Shard::from(0),
+        PublicKey::default(),
+        Default::default(),
+        FixedHash::zero(),
+        0,
+        Default::default(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+    )
+}
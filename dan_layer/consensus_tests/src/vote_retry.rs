@@ -0,0 +1,127 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::Decision;
+
+use crate::support::{logging::setup_logger, Test, TestAddress, TestBuilder};
+
+/// The number of vote retries configured for the test validators
+const MAX_VOTE_RETRIES: usize = 3;
+
+async fn run_until_all_committed(builder: TestBuilder) -> Test {
+    let mut test = builder
+        .with_test_timeout(Duration::from_secs(60))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 1).await;
+    }
+    test.start_epoch(Epoch(0)).await;
+
+    loop {
+        let (_, _, committed_height) = test.on_block_committed().await;
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        if committed_height > NodeHeight(40) {
+            panic!("Not all transaction committed after {} blocks", committed_height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+    test
+}
+
+fn assert_no_leader_timeouts(test: &Test) {
+    for validator in test.validators() {
+        let leader_timeouts = validator.hooks.leader_timeouts();
+        assert!(
+            leader_timeouts.is_empty(),
+            "{} reported leader timeouts: {:?}",
+            validator.address,
+            leader_timeouts
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn votes_that_fail_to_send_are_retried() {
+    setup_logger();
+    // Without the votes of both flaky voters, the leader cannot form a QC
+    let test = run_until_all_committed(
+        Test::builder()
+            .with_flaky_voter("2", MAX_VOTE_RETRIES - 1)
+            .with_flaky_voter("3", MAX_VOTE_RETRIES - 1),
+    )
+    .await;
+
+    assert_no_leader_timeouts(&test);
+    for address in ["2", "3"].map(TestAddress::new) {
+        let retries = test.get_validator(&address).hooks.vote_send_retries();
+        assert!(retries.contains(&true), "{} did not send a vote on retry", address);
+    }
+    assert!(test
+        .get_validator(&TestAddress::new("1"))
+        .hooks
+        .vote_send_retries()
+        .is_empty());
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn the_last_sent_vote_is_resent_when_the_proposal_is_received_again() {
+    setup_logger();
+    // Every send and retry of the first vote fails, so the vote only reaches the leader when it is re-sent for the
+    // replayed proposal. The replay arrives well before the leader times out.
+    let test = run_until_all_committed(
+        Test::builder()
+            .with_flaky_voter("2", MAX_VOTE_RETRIES + 1)
+            .with_flaky_voter("3", MAX_VOTE_RETRIES + 1)
+            .with_proposal_replay(Duration::from_secs(1)),
+    )
+    .await;
+
+    assert_no_leader_timeouts(&test);
+    for address in ["2", "3"].map(TestAddress::new) {
+        let retries = test.get_validator(&address).hooks.vote_send_retries();
+        assert!(!retries.is_empty(), "{} did not retry sending a vote", address);
+        assert!(
+            retries.iter().all(|is_sent| !is_sent),
+            "{} sent a vote on retry instead of re-sending it for the replayed proposal",
+            address
+        );
+    }
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn vote_retries_stop_once_the_view_advances() {
+    setup_logger();
+    // The votes of this validator never reach the leader, but the other three validators are enough to form QCs
+    let unreachable = TestAddress::new("4");
+    let test = run_until_all_committed(Test::builder().with_flaky_voter("4", usize::MAX)).await;
+
+    assert_no_leader_timeouts(&test);
+    let hooks = &test.get_validator(&unreachable).hooks;
+    let retries = hooks.vote_send_retries();
+    assert!(retries.iter().all(|is_sent| !is_sent));
+    // The next proposal arrives before most retries are due, which ends the retries for the previous vote
+    let num_votes = hooks.committed_blocks().len();
+    assert!(
+        retries.len() < num_votes * MAX_VOTE_RETRIES,
+        "{} retried {} times for {} votes",
+        unreachable,
+        retries.len(),
+        num_votes
+    );
+
+    test.assert_clean_shutdown().await;
+}
@@ -0,0 +1,23 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod long_loop_template {
+    use super::*;
+
+    pub struct LongLoop {}
+
+    impl LongLoop {
+        /// Loops for the given number of iterations. Each iteration depends on the previous one so that the loop cannot
+        /// be optimised away.
+        pub fn spin(iterations: u64) -> u64 {
+            let mut acc = 0u64;
+            for i in 0..iterations {
+                acc = acc.rotate_left(5) ^ i.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            }
+            acc
+        }
+    }
+}
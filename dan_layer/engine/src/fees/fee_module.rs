@@ -1,9 +1,6 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::io;
-
-use tari_bor::encode_into;
 use tari_engine_types::fees::FeeSource;
 
 use super::FeeTable;
@@ -11,6 +8,7 @@ use crate::runtime::{RuntimeModule, RuntimeModuleError, StateTracker};
 
 pub struct FeeModule {
     initial_cost: u64,
+    priority_fee: u64,
     fee_table: FeeTable,
 }
 
@@ -18,14 +16,24 @@ impl FeeModule {
     pub fn new(initial_cost: u64, fee_table: FeeTable) -> Self {
         Self {
             initial_cost,
+            priority_fee: 0,
             fee_table,
         }
     }
+
+    /// Charges the priority fee offered by the transaction in addition to the execution costs
+    pub fn with_priority_fee(mut self, priority_fee: u64) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
 }
 
 impl RuntimeModule for FeeModule {
     fn on_initialize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
         track.add_fee_charge(FeeSource::Initial, self.initial_cost);
+        if self.priority_fee > 0 {
+            track.add_fee_charge(FeeSource::PriorityFee, self.priority_fee);
+        }
         Ok(())
     }
 
@@ -35,13 +43,7 @@ impl RuntimeModule for FeeModule {
     }
 
     fn on_before_finalize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
-        let total_storage = track.with_substates_to_persist(|changes| {
-            let mut counter = ByteCounter::new();
-            for substate in changes.values() {
-                encode_into(substate, &mut counter)?;
-            }
-            Ok::<_, RuntimeModuleError>(counter.get())
-        })?;
+        let total_storage = track.substate_sizes()?.values().sum::<usize>();
 
         // TODO: Cost per byte of storage is reduced by a pretty arbitrarily chosen factor (floor(cost/0.333...))
         const STORAGE_COST_REDUCTION_DIVISOR: u64 = 3;
@@ -61,31 +63,3 @@ impl RuntimeModule for FeeModule {
         Ok(())
     }
 }
-
-// TODO: This may become available in tari_utilities in future
-#[derive(Debug, Clone, Default)]
-struct ByteCounter {
-    count: usize,
-}
-
-impl ByteCounter {
-    pub fn new() -> Self {
-        Default::default()
-    }
-
-    pub fn get(&self) -> usize {
-        self.count
-    }
-}
-
-impl io::Write for ByteCounter {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let len = buf.len();
-        self.count += len;
-        Ok(len)
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        Ok(())
-    }
-}
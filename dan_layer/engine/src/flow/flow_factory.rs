@@ -45,6 +45,9 @@ impl FlowFactory {
                 output: Type::Unit,
                 is_mut: false,
             }],
+            component_schema: None,
+            is_reentrant: false,
+            upgrades_from: None,
         });
 
         let _test_build = FlowInstance::try_build(
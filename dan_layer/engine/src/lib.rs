@@ -8,6 +8,7 @@ mod bootstrap;
 pub mod fees;
 pub mod flow;
 pub mod function_definitions;
+pub mod limits;
 pub mod runtime;
 pub mod state_store;
 pub mod template;
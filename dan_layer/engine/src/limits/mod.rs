@@ -0,0 +1,5 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+mod substate_size_module;
+pub use substate_size_module::{SubstateSizeLimitModule, SubstateSizeLimits};
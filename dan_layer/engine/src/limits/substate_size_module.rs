@@ -0,0 +1,49 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use crate::runtime::{RuntimeModule, RuntimeModuleError, StateTracker};
+
+#[derive(Debug, Clone, Copy)]
+pub struct SubstateSizeLimits {
+    /// The maximum encoded size in bytes of a single substate written by a transaction
+    pub max_substate_size: usize,
+    /// The maximum combined encoded size in bytes of all substates written by a transaction
+    pub max_transaction_size: usize,
+}
+
+/// Rejects transactions that write substates larger than the configured limits
+pub struct SubstateSizeLimitModule {
+    limits: SubstateSizeLimits,
+}
+
+impl SubstateSizeLimitModule {
+    pub fn new(limits: SubstateSizeLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl RuntimeModule for SubstateSizeLimitModule {
+    fn on_before_finalize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
+        let sizes = track.substate_sizes()?;
+
+        for (substate_id, size) in &sizes {
+            if *size > self.limits.max_substate_size {
+                return Err(RuntimeModuleError::SubstateTooLarge {
+                    substate_id: substate_id.clone(),
+                    size: *size,
+                    max: self.limits.max_substate_size,
+                });
+            }
+        }
+
+        let total_size = sizes.values().sum::<usize>();
+        if total_size > self.limits.max_transaction_size {
+            return Err(RuntimeModuleError::TransactionSubstatesTooLarge {
+                size: total_size,
+                max: self.limits.max_transaction_size,
+            });
+        }
+
+        Ok(())
+    }
+}
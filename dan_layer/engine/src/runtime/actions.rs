@@ -6,7 +6,7 @@ use std::fmt::{Display, Formatter};
 use tari_template_lib::{
     args::{ComponentAction, VaultAction},
     auth::ResourceAuthAction,
-    models::ComponentAddress,
+    models::{ComponentAddress, TemplateAddress},
 };
 
 #[derive(Debug, Clone)]
@@ -16,6 +16,10 @@ pub enum ActionIdent {
         component_address: ComponentAddress,
         method: String,
     },
+    MigrateComponent {
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+    },
 }
 
 impl From<NativeAction> for ActionIdent {
@@ -52,6 +56,15 @@ impl Display for ActionIdent {
             } => {
                 write!(f, "call component method '{method}' on {component_address}")
             },
+            ActionIdent::MigrateComponent {
+                component_address,
+                template_address,
+            } => {
+                write!(
+                    f,
+                    "migrate component {component_address} to template {template_address}"
+                )
+            },
         }
     }
 }
@@ -209,6 +209,13 @@ pub enum RuntimeError {
     AuthScopeStackEmpty,
     #[error("Invalid deposit of bucket {bucket_id} has locked value amounting to {locked_amount}")]
     InvalidOpDepositLockedBucket { bucket_id: BucketId, locked_amount: Amount },
+    #[error("Royalty not paid for transfer of resource {resource_address}: {details}")]
+    RoyaltyNotPaid {
+        resource_address: ResourceAddress,
+        details: String,
+    },
+    #[error("Assertion failed: {details}")]
+    AssertionFailed { details: String },
     #[error("Duplicate substate {address}")]
     DuplicateSubstate { address: SubstateId },
     #[error("Substate {address} is orphaned")]
@@ -228,8 +235,13 @@ pub enum RuntimeError {
     DanglingSubstateLocks { count: usize },
     #[error("No active call frame")]
     NoActiveCallFrame,
-    #[error("Max call depth {max_depth} exceeded")]
-    MaxCallDepthExceeded { max_depth: usize },
+    #[error("Max call depth {max_depth} exceeded. Call chain: {}", .call_chain.join(" -> "))]
+    CallDepthExceeded { max_depth: usize, call_chain: Vec<String> },
+    #[error("Reentrant call to component {component_address} denied. Call chain: {}", .call_chain.join(" -> "))]
+    ReentrancyDenied {
+        component_address: ComponentAddress,
+        call_chain: Vec<String>,
+    },
     #[error("{action} can only be called from within a component context")]
     NotInComponentContext { action: ActionIdent },
     #[error("Duplicate bucket {bucket_id}")]
@@ -241,6 +253,10 @@ pub enum RuntimeError {
     AddressAllocationNotFound { id: u32 },
     #[error("Address allocation type mismatch: {address}")]
     AddressAllocationTypeMismatch { address: SubstateId },
+    #[error("Substate {address} is in the reserved address range and can only be created by a system transaction")]
+    ReservedAddressNotPermitted { address: SubstateId },
+    #[error("Address {address} is not in the reserved address range")]
+    AddressNotReserved { address: SubstateId },
 
     #[error("Invalid event topic {topic}")]
     InvalidEventTopic { topic: String },
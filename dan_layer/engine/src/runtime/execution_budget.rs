@@ -0,0 +1,54 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+
+/// The number of metering points that a transaction may use across all of its template calls. Metering points are
+/// charged per WASM instruction, so the budget is exceeded at the same point on every node that executes the
+/// transaction.
+#[derive(Debug, Clone)]
+pub struct ExecutionBudget {
+    remaining: Arc<AtomicU64>,
+    is_exceeded: Arc<AtomicBool>,
+}
+
+impl ExecutionBudget {
+    pub fn new(points: u64) -> Self {
+        Self {
+            remaining: Arc::new(AtomicU64::new(points)),
+            is_exceeded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.remaining.load(Ordering::SeqCst)
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.is_exceeded.load(Ordering::SeqCst)
+    }
+
+    /// Consumes the given number of points from the budget. Returns false and marks the budget as exceeded if fewer
+    /// than `points` remain.
+    pub fn consume(&self, points: u64) -> bool {
+        let result = self
+            .remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| {
+                remaining.checked_sub(points)
+            });
+        if result.is_err() {
+            self.set_exceeded();
+            return false;
+        }
+        true
+    }
+
+    /// Marks the budget as exceeded and consumes any remaining points
+    pub fn set_exceeded(&self) {
+        self.remaining.store(0, Ordering::SeqCst);
+        self.is_exceeded.store(true, Ordering::SeqCst);
+    }
+}
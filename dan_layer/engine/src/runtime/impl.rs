@@ -25,10 +25,15 @@ use std::sync::Arc;
 use log::{warn, *};
 use tari_common::configuration::Network;
 use tari_common_types::types::PublicKey;
-use tari_crypto::{range_proof::RangeProofService, ristretto::RistrettoPublicKey, tari_utilities::ByteArray};
+use tari_crypto::{
+    range_proof::RangeProofService,
+    ristretto::RistrettoPublicKey,
+    tari_utilities::{hex::Hex, ByteArray},
+};
 use tari_dan_common_types::{services::template_provider::TemplateProvider, Epoch};
 use tari_engine_types::{
     base_layer_hashing::ownership_proof_hasher64,
+    bucket::Bucket,
     commit_result::{FinalizeResult, RejectReason, TransactionResult},
     component::ComponentHeader,
     confidential::{get_commitment_factory, get_range_proof_service, ConfidentialClaim, ConfidentialOutput},
@@ -39,7 +44,7 @@ use tari_engine_types::{
     lock::LockFlag,
     logs::LogEntry,
     resource::Resource,
-    resource_container::ResourceContainer,
+    resource_container::{ConfidentialBurn, ResourceContainer},
     substate::{SubstateId, SubstateValue},
     vault::Vault,
     TemplateAddress,
@@ -59,6 +64,7 @@ use tari_template_lib::{
         CallerContextAction,
         ComponentAction,
         ComponentRef,
+        ConfidentialBurnArg,
         ConfidentialRevealArg,
         ConsensusAction,
         CreateComponentArg,
@@ -94,10 +100,12 @@ use tari_template_lib::{
         NonFungible,
         NonFungibleAddress,
         NotAuthorized,
+        ResourceAddress,
         VaultId,
         VaultRef,
     },
     prelude::ResourceType,
+    resource::MAX_ROYALTY_BASIS_POINTS,
     template::BuiltinTemplate,
 };
 
@@ -109,6 +117,8 @@ use crate::{
         scope::PushCallFrame,
         tracker::StateTracker,
         utils::to_ristretto_public_key_bytes,
+        ActionIdent,
+        ExecutionBudget,
         RuntimeError,
         RuntimeInterface,
         RuntimeModule,
@@ -123,6 +133,7 @@ const LOG_TARGET: &str = "tari::dan::engine::runtime::impl";
 const STANDARD_TOPIC_PREFIX: &str = "std.";
 const VAULT_DEPOSIT_TOPIC: &str = "std.vault.deposit";
 const VAULT_WITHDRAW_TOPIC: &str = "std.vault.withdraw";
+const RESOURCE_BURN_CONFIDENTIAL_TOPIC: &str = "std.resource.burn_confidential";
 
 #[derive(Clone)]
 pub struct RuntimeInterfaceImpl<TTemplateProvider> {
@@ -133,6 +144,7 @@ pub struct RuntimeInterfaceImpl<TTemplateProvider> {
     modules: Vec<Arc<dyn RuntimeModule>>,
     max_call_depth: usize,
     network: Network,
+    execution_budget: ExecutionBudget,
 }
 
 impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInterfaceImpl<TTemplateProvider> {
@@ -144,6 +156,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         modules: Vec<Arc<dyn RuntimeModule>>,
         max_call_depth: usize,
         network: Network,
+        execution_budget: ExecutionBudget,
     ) -> Result<Self, RuntimeError> {
         let runtime = Self {
             tracker,
@@ -153,6 +166,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
             modules,
             max_call_depth,
             network,
+            execution_budget,
         };
         runtime.invoke_modules_on_initialize()?;
         Ok(runtime)
@@ -264,6 +278,44 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn emit_confidential_burn_event(
+        &self,
+        resource_address: ResourceAddress,
+        vault_id: Option<VaultId>,
+        burn: &ConfidentialBurn,
+        state: &mut WorkingState,
+    ) -> Result<(), RuntimeError> {
+        let tx_hash = self.entity_id_provider.transaction_hash();
+        let (template_address, _) = state.current_template()?;
+
+        let mut payload = Metadata::new();
+        if let Some(vault_id) = vault_id {
+            payload.insert("vault_id", vault_id.to_string());
+        }
+        payload.insert("resource_address", resource_address.to_string());
+        payload.insert("amount", burn.amount.to_string());
+        payload.insert(
+            "commitments",
+            burn.destroyed_commitments
+                .iter()
+                .map(|commitment| commitment.as_public_key().to_hex())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+
+        let event = Event::new(
+            Some(SubstateId::Resource(resource_address)),
+            *template_address,
+            tx_hash,
+            RESOURCE_BURN_CONFIDENTIAL_TOPIC.to_string(),
+            payload,
+        );
+        debug!(target: LOG_TARGET, "Emitted confidential burn event {}", event);
+        state.push_event(event);
+
+        Ok(())
+    }
+
     fn invoke_resource_access_hook(
         &self,
         auth_hook: AuthHook,
@@ -321,6 +373,63 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    /// If depositing the bucket completes a transfer of a royalty-bearing resource out of a component, takes the
+    /// royalty from the declared payment bucket and returns the recipient and a bucket containing the royalty. The
+    /// royalty is charged for every such deposit, so a token that changes hands twice in a transaction pays twice.
+    fn take_royalty_for_deposit(
+        state: &mut WorkingState,
+        resource_lock: &LockedSubstate,
+        bucket: &Bucket,
+    ) -> Result<Option<(ComponentAddress, BucketId)>, RuntimeError> {
+        let Some(royalty) = state.get_resource(resource_lock)?.royalty().copied() else {
+            return Ok(None);
+        };
+        let depositing_component = state.current_component()?;
+        let resource_address = *bucket.resource_address();
+        if !state.royalty_state_mut().take_crosses_ownership(
+            resource_address,
+            bucket.non_fungible_ids(),
+            depositing_component.as_ref(),
+        ) {
+            return Ok(None);
+        }
+
+        let payment = state
+            .royalty_state()
+            .get_payment(&resource_address)
+            .copied()
+            .ok_or_else(|| RuntimeError::RoyaltyNotPaid {
+                resource_address,
+                details: "No royalty payment was declared for the transfer".to_string(),
+            })?;
+
+        let royalty_amount =
+            royalty
+                .calculate_royalty(payment.amount)
+                .ok_or_else(|| RuntimeError::NumericConversionError {
+                    details: format!("Royalty overflowed for payment amount {}", payment.amount),
+                })?;
+        if royalty_amount.is_zero() {
+            return Ok(None);
+        }
+
+        let royalty_container = state
+            .get_bucket_mut(payment.bucket_id)
+            .map_err(|e| RuntimeError::RoyaltyNotPaid {
+                resource_address,
+                details: format!("Royalty payment bucket is not available: {}", e),
+            })?
+            .take(royalty_amount)
+            .map_err(|e| RuntimeError::RoyaltyNotPaid {
+                resource_address,
+                details: format!("Royalty payment of {} could not be taken: {}", royalty_amount, e),
+            })?;
+        let royalty_bucket_id = state.new_bucket_id();
+        state.new_bucket(royalty_bucket_id, royalty_container)?;
+
+        Ok(Some((royalty.recipient, royalty_bucket_id)))
+    }
+
     fn invoke_component_method(
         &self,
         component_address: &ComponentAddress,
@@ -497,6 +606,20 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                 let allocation = state.new_address_allocation(address)?;
                 Ok(InvokeResult::encode(&allocation)?)
             }),
+            CallerContextAction::AllocateReservedComponentAddress(address) => self.tracker.write_with(|state| {
+                if !address.as_object_key().is_reserved() {
+                    return Err(RuntimeError::AddressNotReserved {
+                        address: address.into(),
+                    });
+                }
+                if !state.is_system_transaction() {
+                    return Err(RuntimeError::ReservedAddressNotPermitted {
+                        address: address.into(),
+                    });
+                }
+                let allocation = state.new_address_allocation(address)?;
+                Ok(InvokeResult::encode(&allocation)?)
+            }),
         }
     }
 
@@ -684,16 +807,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
 
                 args.assert_no_args("Component::GetTemplateAddress")?;
 
-                // The template can never change so we'll just fetch the component
-                self.tracker.read_with(|state| {
-                    let substate = state.store().get_unmodified_substate(&component_address.into())?;
-                    let component = substate
-                        .substate_value()
-                        .component()
-                        .ok_or(RuntimeError::ComponentNotFound {
-                            address: component_address,
-                        })?;
-
+                // The template changes if the component is migrated, possibly earlier in this transaction, so the current
+                // component is loaded without a lock
+                self.tracker.write_with(|state| {
+                    let component = state.load_component(&component_address)?;
                     Ok(InvokeResult::encode(&component.template_address)?)
                 })
             },
@@ -756,6 +873,24 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         reason: format!("Invalid view key: {}", e),
                     })?;
 
+                if let Some(royalty) = arg.royalty.as_ref() {
+                    if !arg.resource_type.is_non_fungible() {
+                        return Err(RuntimeError::InvalidArgument {
+                            argument: "CreateResourceArg",
+                            reason: "Royalties can only be set for non-fungible resources".to_string(),
+                        });
+                    }
+                    if !royalty.is_valid() {
+                        return Err(RuntimeError::InvalidArgument {
+                            argument: "CreateResourceArg",
+                            reason: format!(
+                                "Royalty basis points must be between 1 and {}, got {}",
+                                MAX_ROYALTY_BASIS_POINTS, royalty.basis_points
+                            ),
+                        });
+                    }
+                }
+
                 // Check that auth hook is valid
                 if let Some(hook) = arg.authorize_hook.as_ref() {
                     self.check_resource_auth_hook(hook)?;
@@ -770,7 +905,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         arg.metadata,
                         maybe_view_key,
                         arg.authorize_hook,
-                    );
+                    )
+                    .with_royalty(arg.royalty);
 
                     let resource_address = state.id_provider()?.new_resource_address()?;
                     state.new_substate(resource_address, resource)?;
@@ -1146,7 +1282,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Deposit)?;
                 }
 
-                self.tracker.write_with(move |state_mut| {
+                let maybe_royalty = self.tracker.write_with(move |state_mut| {
                     let bucket = state_mut.take_bucket(bucket_id)?;
                     // It is invalid to deposit a bucket that has locked funds
                     if !bucket.locked_amount().is_zero() {
@@ -1156,6 +1292,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         });
                     }
 
+                    let maybe_royalty = Self::take_royalty_for_deposit(state_mut, &resource_lock, &bucket)?;
+
                     // Emit a builtin event for the deposit
                     self.emit_vault_events(
                         VAULT_DEPOSIT_TOPIC.to_owned(),
@@ -1172,8 +1310,16 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     state_mut.unlock_substate(resource_lock)?;
                     state_mut.unlock_substate(vault_lock)?;
 
-                    Ok(InvokeResult::unit())
-                })
+                    Ok::<_, RuntimeError>(maybe_royalty)
+                })?;
+
+                if let Some((recipient, royalty_bucket_id)) = maybe_royalty {
+                    self.invoke_component_method(&recipient, "deposit", args![
+                        tari_template_lib::models::Bucket::from_id(royalty_bucket_id)
+                    ])?;
+                }
+
+                Ok(InvokeResult::unit())
             },
             VaultAction::Withdraw => {
                 let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
@@ -1210,6 +1356,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                 self.tracker.write_with(|state| {
                     let resource = state.get_resource(&resource_lock)?;
                     let maybe_view_key = resource.view_key().cloned();
+                    let has_royalty = resource.royalty().is_some();
 
                     let vault_mut = state.get_vault_mut(&vault_lock)?;
                     let (resource_container, amount) = match arg {
@@ -1242,6 +1389,17 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         state,
                     )?;
 
+                    if has_royalty {
+                        if let Some(component) = state.current_component()? {
+                            let resource_address = *resource_container.resource_address();
+                            state.royalty_state_mut().record_withdraw(
+                                resource_address,
+                                resource_container.non_fungible_token_ids(),
+                                component,
+                            );
+                        }
+                    }
+
                     let bucket_id = state.id_provider()?.new_bucket_id();
                     state.new_bucket(bucket_id, resource_container)?;
 
@@ -1373,6 +1531,58 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::encode(&bucket)?)
                 })
             },
+            VaultAction::BurnConfidential => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "Vault::BurnConfidential action requires a vault id".to_string(),
+                })?;
+
+                let arg: ConfidentialBurnArg = args.assert_one_arg()?;
+
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                    self.tracker.write_with(|state_mut| {
+                        let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
+
+                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+
+                        let resource_lock =
+                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Write)?;
+
+                        let resource = state_mut.get_resource(&resource_lock)?;
+
+                        state_mut.authorization().check_resource_access_rules(
+                            ResourceAuthAction::Burn,
+                            resource.as_ownership(),
+                            resource.access_rules(),
+                        )?;
+
+                        let auth_caller = state_mut.get_auth_caller()?;
+                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+                    })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Burn)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    let resource = state.get_resource(&resource_lock)?;
+                    let view_key = resource.view_key().cloned();
+
+                    let vault_mut = state.get_vault_mut(&vault_lock)?;
+                    let resource_address = *vault_mut.resource_address();
+                    let burn = vault_mut.burn_confidential(arg.proof, view_key.as_ref())?;
+
+                    let resource_mut = state.get_resource_mut(&resource_lock)?;
+                    resource_mut.decrease_total_supply_saturating(burn.amount);
+
+                    self.emit_confidential_burn_event(resource_address, Some(vault_id), &burn, state)?;
+
+                    state.unlock_substate(vault_lock)?;
+                    state.unlock_substate(resource_lock)?;
+
+                    Ok(InvokeResult::encode(&burn.amount)?)
+                })
+            },
             VaultAction::PayFee => {
                 let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
                     argument: "vault_ref",
@@ -1736,6 +1946,54 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::unit())
                 })
             },
+            BucketAction::BurnConfidential => {
+                let bucket_id = bucket_ref.bucket_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "bucket_ref",
+                    reason: "BurnConfidential bucket action requires a bucket id".to_string(),
+                })?;
+
+                let arg: ConfidentialBurnArg = args.assert_one_arg()?;
+
+                let (resource_lock, maybe_auth_hook, auth_caller) = self.tracker.write_with(|state_mut| {
+                    let bucket = state_mut.get_bucket(bucket_id)?;
+
+                    let resource_lock =
+                        state_mut.lock_substate(&SubstateId::Resource(*bucket.resource_address()), LockFlag::Write)?;
+
+                    let resource = state_mut.get_resource(&resource_lock)?;
+
+                    state_mut.authorization().check_resource_access_rules(
+                        ResourceAuthAction::Burn,
+                        resource.as_ownership(),
+                        resource.access_rules(),
+                    )?;
+
+                    let auth_caller = state_mut.get_auth_caller()?;
+                    Ok::<_, RuntimeError>((resource_lock, resource.auth_hook().cloned(), auth_caller))
+                })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Burn)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    let resource = state.get_resource(&resource_lock)?;
+                    let view_key = resource.view_key().cloned();
+
+                    let bucket_mut = state.get_bucket_mut(bucket_id)?;
+                    let resource_address = *bucket_mut.resource_address();
+                    let burn = bucket_mut.burn_confidential(arg.proof, view_key.as_ref())?;
+
+                    let resource_mut = state.get_resource_mut(&resource_lock)?;
+                    resource_mut.decrease_total_supply_saturating(burn.amount);
+
+                    self.emit_confidential_burn_event(resource_address, None, &burn, state)?;
+
+                    state.unlock_substate(resource_lock)?;
+
+                    Ok(InvokeResult::encode(&burn.amount)?)
+                })
+            },
             BucketAction::CreateProof => {
                 let bucket_id = bucket_ref.bucket_id().ok_or_else(|| RuntimeError::InvalidArgument {
                     argument: "bucket_ref",
@@ -2176,6 +2434,133 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn declare_royalty_payment(
+        &self,
+        resource_address: ResourceAddress,
+        workspace_bucket: String,
+    ) -> Result<(), RuntimeError> {
+        let value = self.tracker.get_from_workspace(workspace_bucket.as_bytes())?;
+        let bucket_id = match value.bucket_ids() {
+            [bucket_id] => *bucket_id,
+            _ => {
+                return Err(RuntimeError::InvalidArgument {
+                    argument: "workspace_bucket",
+                    reason: format!("Workspace item '{}' must contain exactly one bucket", workspace_bucket),
+                })
+            },
+        };
+
+        self.tracker.write_with(|state| {
+            let resource_lock = state.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
+            if state.get_resource(&resource_lock)?.royalty().is_none() {
+                return Err(RuntimeError::InvalidArgument {
+                    argument: "resource_address",
+                    reason: format!("Resource {} does not have a royalty", resource_address),
+                });
+            }
+            let amount = state.get_bucket(bucket_id)?.amount();
+            state
+                .royalty_state_mut()
+                .declare_payment(resource_address, bucket_id, amount);
+            state.unlock_substate(resource_lock)?;
+            Ok::<_, RuntimeError>(())
+        })
+    }
+
+    fn assert_bucket_contains(
+        &self,
+        workspace_bucket: String,
+        resource_address: ResourceAddress,
+        min_amount: Amount,
+    ) -> Result<(), RuntimeError> {
+        let value = self.tracker.get_from_workspace(workspace_bucket.as_bytes())?;
+        let bucket_id = match value.bucket_ids() {
+            [bucket_id] => *bucket_id,
+            _ => {
+                return Err(RuntimeError::InvalidArgument {
+                    argument: "workspace_bucket",
+                    reason: format!("Workspace item '{}' must contain exactly one bucket", workspace_bucket),
+                })
+            },
+        };
+
+        self.tracker.read_with(|state| {
+            let bucket = state.get_bucket(bucket_id)?;
+            if *bucket.resource_address() != resource_address {
+                return Err(RuntimeError::AssertionFailed {
+                    details: format!(
+                        "bucket '{}' contains resource {} but resource {} was expected",
+                        workspace_bucket,
+                        bucket.resource_address(),
+                        resource_address
+                    ),
+                });
+            }
+            if bucket.amount() < min_amount {
+                return Err(RuntimeError::AssertionFailed {
+                    details: format!(
+                        "bucket '{}' contains {} of resource {} but at least {} is required",
+                        workspace_bucket,
+                        bucket.amount(),
+                        resource_address,
+                        min_amount
+                    ),
+                });
+            }
+            Ok(())
+        })
+    }
+
+    fn assert_vault_balance_at_least(&self, vault_id: VaultId, min_amount: Amount) -> Result<(), RuntimeError> {
+        let balance = self.tracker.write_with(|state| {
+            let vault_lock = state.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Read)?;
+            let balance = state.get_vault(&vault_lock)?.balance();
+            state.unlock_substate(vault_lock)?;
+            Ok::<_, RuntimeError>(balance)
+        })?;
+
+        if balance < min_amount {
+            return Err(RuntimeError::AssertionFailed {
+                details: format!(
+                    "vault {} has a balance of {} but at least {} is required",
+                    vault_id, balance, min_amount
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn migrate_component(
+        &self,
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+        new_state: Option<tari_bor::Value>,
+    ) -> Result<(), RuntimeError> {
+        let template_def = self.get_template_def(&template_address)?;
+
+        self.tracker.write_with(|state| {
+            let component_lock = state.lock_substate(&SubstateId::Component(component_address), LockFlag::Write)?;
+            let component = state.get_component(&component_lock)?;
+            state.authorization().require_ownership(
+                ActionIdent::MigrateComponent {
+                    component_address,
+                    template_address,
+                },
+                component.as_ownership(),
+            )?;
+
+            state.modify_component_with(&component_lock, |component| {
+                component.template_address = template_address;
+                component.module_name = template_def.template_name().to_string();
+                if let Some(new_state) = new_state {
+                    component.body.set(new_state);
+                }
+            })?;
+            state.unlock_substate(component_lock)?;
+            Ok::<_, RuntimeError>(())
+        })
+    }
+
     fn create_free_test_coins(
         &self,
         revealed_amount: Amount,
@@ -2249,6 +2634,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
             .read_with(|state| state.check_all_substates_known(value.well_known_types()))
     }
 
+    fn check_reentrancy(&self, component_address: &ComponentAddress, module_name: &str) -> Result<(), RuntimeError> {
+        self.tracker.check_reentrancy(component_address, module_name)
+    }
+
     fn push_call_frame(&self, frame: PushCallFrame) -> Result<(), RuntimeError> {
         self.tracker.push_call_frame(frame, self.max_call_depth)?;
         Ok(())
@@ -2259,6 +2648,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn execution_budget(&self) -> &ExecutionBudget {
+        &self.execution_budget
+    }
+
     fn builtin_template_invoke(&self, action: BuiltinTemplateAction) -> Result<InvokeResult, RuntimeError> {
         self.invoke_modules_on_runtime_call("builtin_template_invoke")?;
 
@@ -32,6 +32,9 @@ pub use crate::runtime::engine_args::EngineArgs;
 mod error;
 pub use error::{RuntimeError, TransactionCommitError};
 
+mod execution_budget;
+pub use execution_budget::ExecutionBudget;
+
 mod actions;
 pub use actions::*;
 
@@ -39,6 +42,7 @@ mod module;
 pub use module::{RuntimeModule, RuntimeModuleError};
 
 mod fee_state;
+mod royalty_state;
 mod tracker;
 
 mod locking;
@@ -87,7 +91,18 @@ use tari_template_lib::{
         WorkspaceAction,
     },
     invoke_args,
-    models::{Amount, BucketId, ComponentAddress, EntityId, Metadata, NonFungibleAddress, VaultRef},
+    models::{
+        Amount,
+        BucketId,
+        ComponentAddress,
+        EntityId,
+        Metadata,
+        NonFungibleAddress,
+        ResourceAddress,
+        TemplateAddress,
+        VaultId,
+        VaultRef,
+    },
 };
 pub use tracker::StateTracker;
 
@@ -159,6 +174,34 @@ pub trait RuntimeInterface: Send + Sync {
 
     fn claim_validator_fees(&self, epoch: Epoch, validator_public_key: PublicKey) -> Result<(), RuntimeError>;
 
+    fn declare_royalty_payment(
+        &self,
+        resource_address: ResourceAddress,
+        workspace_bucket: String,
+    ) -> Result<(), RuntimeError>;
+
+    /// Fails with `RuntimeError::AssertionFailed` unless the bucket on the workspace contains at least `min_amount` of
+    /// the resource. Only the revealed amount of a confidential bucket is considered.
+    fn assert_bucket_contains(
+        &self,
+        workspace_bucket: String,
+        resource_address: ResourceAddress,
+        min_amount: Amount,
+    ) -> Result<(), RuntimeError>;
+
+    /// Fails with `RuntimeError::AssertionFailed` unless the balance of the vault is at least `min_amount`. Only the
+    /// revealed balance of a confidential vault is considered.
+    fn assert_vault_balance_at_least(&self, vault_id: VaultId, min_amount: Amount) -> Result<(), RuntimeError>;
+
+    /// Points the component at the given template and replaces its state with `new_state` if given. The caller must
+    /// have checked that the template is an upgrade of the component's current template.
+    fn migrate_component(
+        &self,
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+        new_state: Option<tari_bor::Value>,
+    ) -> Result<(), RuntimeError>;
+
     fn create_free_test_coins(
         &self,
         revealed_amount: Amount,
@@ -178,8 +221,14 @@ pub trait RuntimeInterface: Send + Sync {
 
     fn validate_return_value(&self, value: &IndexedValue) -> Result<(), RuntimeError>;
 
+    /// Returns an error if the component is already in the active call stack. This is not called for templates that
+    /// opt into reentrancy.
+    fn check_reentrancy(&self, component_address: &ComponentAddress, module_name: &str) -> Result<(), RuntimeError>;
     fn push_call_frame(&self, frame: PushCallFrame) -> Result<(), RuntimeError>;
     fn pop_call_frame(&self) -> Result<(), RuntimeError>;
+
+    /// The metering budget that is shared by all template calls made by the transaction
+    fn execution_budget(&self) -> &ExecutionBudget;
 }
 
 #[derive(Clone)]
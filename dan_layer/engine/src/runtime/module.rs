@@ -1,6 +1,8 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_engine_types::substate::SubstateId;
+
 use crate::runtime::StateTracker;
 
 pub trait RuntimeModule: Send + Sync {
@@ -21,4 +23,12 @@ pub trait RuntimeModule: Send + Sync {
 pub enum RuntimeModuleError {
     #[error("BOR error: {0}")]
     Bor(#[from] tari_bor::BorError),
+    #[error("Substate {substate_id} is {size} bytes which exceeds the maximum of {max} bytes")]
+    SubstateTooLarge {
+        substate_id: SubstateId,
+        size: usize,
+        max: usize,
+    },
+    #[error("Transaction writes {size} bytes of substates which exceeds the maximum of {max} bytes")]
+    TransactionSubstatesTooLarge { size: usize, max: usize },
 }
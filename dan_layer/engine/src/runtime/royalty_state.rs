@@ -0,0 +1,78 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{BTreeSet, HashMap};
+
+use tari_template_lib::models::{
+    Amount,
+    BucketId,
+    ComponentAddress,
+    NonFungibleAddress,
+    NonFungibleId,
+    ResourceAddress,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RoyaltyPayment {
+    pub bucket_id: BucketId,
+    /// The amount in the payment bucket at the time it was declared. Royalties are calculated on this amount.
+    pub amount: Amount,
+}
+
+/// Tracks royalty-bearing non-fungibles as they move between components within a transaction.
+#[derive(Debug, Clone, Default)]
+pub struct RoyaltyState {
+    payments: HashMap<ResourceAddress, RoyaltyPayment>,
+    /// The component that each royalty-bearing token was withdrawn from
+    origins: HashMap<NonFungibleAddress, ComponentAddress>,
+}
+
+impl RoyaltyState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn declare_payment(&mut self, resource_address: ResourceAddress, bucket_id: BucketId, amount: Amount) {
+        self.payments
+            .insert(resource_address, RoyaltyPayment { bucket_id, amount });
+    }
+
+    pub fn get_payment(&self, resource_address: &ResourceAddress) -> Option<&RoyaltyPayment> {
+        self.payments.get(resource_address)
+    }
+
+    pub fn record_withdraw<'a, I: IntoIterator<Item = &'a NonFungibleId>>(
+        &mut self,
+        resource_address: ResourceAddress,
+        ids: I,
+        component: ComponentAddress,
+    ) {
+        for id in ids {
+            // Keep the original owner if the token is moved through several components in the same transaction
+            self.origins
+                .entry(NonFungibleAddress::new(resource_address, id.clone()))
+                .or_insert(component);
+        }
+    }
+
+    /// Removes the recorded origin of the given tokens, returning true if any of them were withdrawn from a component
+    /// other than `depositing_component`. A deposit made outside of a component (e.g. into the vault of a component
+    /// that is being created) always crosses ownership.
+    pub fn take_crosses_ownership(
+        &mut self,
+        resource_address: ResourceAddress,
+        ids: &BTreeSet<NonFungibleId>,
+        depositing_component: Option<&ComponentAddress>,
+    ) -> bool {
+        let mut crosses_ownership = false;
+        for id in ids {
+            if let Some(origin) = self
+                .origins
+                .remove(&NonFungibleAddress::new(resource_address, id.clone()))
+            {
+                crosses_ownership |= depositing_component != Some(&origin);
+            }
+        }
+        crosses_ownership
+    }
+}
@@ -338,6 +338,15 @@ impl CallFrame {
     }
 }
 
+impl Display for CallFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.scope.get_current_component_lock() {
+            Some(lock) => write!(f, "{}({})", self.current_module, lock.address()),
+            None => write!(f, "{}", self.current_module),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum PushCallFrame {
     ForComponent {
@@ -27,6 +27,7 @@ use std::{
 
 use indexmap::IndexMap;
 use log::*;
+use tari_bor::BorError;
 use tari_dan_common_types::Epoch;
 use tari_engine_types::{
     commit_result::{FinalizeResult, RejectReason, TransactionResult},
@@ -38,6 +39,7 @@ use tari_engine_types::{
     lock::LockFlag,
     logs::LogEntry,
     substate::{SubstateId, SubstateValue},
+    transaction_receipt::SubstateSize,
     virtual_substate::VirtualSubstates,
     TemplateAddress,
 };
@@ -52,6 +54,7 @@ use crate::{
     runtime::{
         locking::LockedSubstate,
         scope::{CallScope, PushCallFrame},
+        utils::encoded_len,
         working_state::WorkingState,
         workspace::Workspace,
         RuntimeError,
@@ -85,6 +88,16 @@ impl StateTracker {
         }
     }
 
+    /// Allows the transaction to create substates in the reserved address range
+    pub fn set_system_transaction(&self, is_system_transaction: bool) {
+        self.write_with(|state| state.set_system_transaction(is_system_transaction));
+    }
+
+    /// Derives new object ids from the given seed instead of the transaction hash
+    pub fn set_id_seed(&self, id_seed: Hash) {
+        self.write_with(|state| state.set_id_seed(id_seed));
+    }
+
     pub fn get_current_epoch(&self) -> Result<Epoch, RuntimeError> {
         self.read_with(|state| state.get_current_epoch())
     }
@@ -235,6 +248,14 @@ impl StateTracker {
         })
     }
 
+    pub fn check_reentrancy(
+        &self,
+        component_address: &ComponentAddress,
+        module_name: &str,
+    ) -> Result<(), RuntimeError> {
+        self.read_with(|state| state.check_reentrancy(component_address, module_name))
+    }
+
     pub fn pop_call_frame(&self) -> Result<(), RuntimeError> {
         self.write_with(|state| state.pop_frame())
     }
@@ -287,7 +308,19 @@ impl StateTracker {
             });
         }
         // Resolve the transfers to the fee pool resource and vault refunds
-        let transaction_receipt = state.finalize_fees(&mut substates_to_persist)?;
+        let mut transaction_receipt = state.finalize_fees(&mut substates_to_persist)?;
+
+        let substate_sizes = substates_to_persist
+            .iter()
+            .map(|(id, value)| {
+                Ok(SubstateSize {
+                    substate_id: id.clone(),
+                    size: encoded_len(value)? as u64,
+                })
+            })
+            .collect::<Result<Vec<_>, RuntimeError>>()?;
+        transaction_receipt.fee_receipt.total_storage_bytes = substate_sizes.iter().map(|s| s.size).sum();
+        transaction_receipt.substate_sizes = substate_sizes;
 
         let fee_receipt = transaction_receipt.fee_receipt.clone();
 
@@ -344,6 +377,16 @@ impl StateTracker {
         self.write_with(|state| state.take_mutated_substates())
     }
 
+    /// Returns the encoded size in bytes of each substate that will be written by the transaction
+    pub fn substate_sizes(&self) -> Result<IndexMap<SubstateId, usize>, BorError> {
+        self.with_substates_to_persist(|changes| {
+            changes
+                .iter()
+                .map(|(id, value)| Ok((id.clone(), encoded_len(value)?)))
+                .collect()
+        })
+    }
+
     pub fn with_substates_to_persist<F: FnMut(&IndexMap<SubstateId, SubstateValue>) -> R, R>(&self, mut f: F) -> R {
         self.write_with(|state| f(state.mutated_substates()))
     }
@@ -1,6 +1,9 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::io;
+
+use tari_bor::{encode_into, BorError, Serialize};
 use tari_common_types::types::PublicKey;
 use tari_crypto::tari_utilities::ByteArray;
 use tari_template_lib::crypto::RistrettoPublicKeyBytes;
@@ -11,3 +14,28 @@ pub fn to_ristretto_public_key_bytes(public_key: &PublicKey) -> RistrettoPublicK
          is not size_of::<RistrettoPublicKeyBytes>() bytes.",
     )
 }
+
+/// Returns the length in bytes of the BOR encoding of the value
+pub fn encoded_len<T: Serialize + ?Sized>(val: &T) -> Result<usize, BorError> {
+    let mut counter = ByteCounter::default();
+    encode_into(val, &mut counter)?;
+    Ok(counter.count)
+}
+
+// TODO: This may become available in tari_utilities in future
+#[derive(Debug, Clone, Default)]
+struct ByteCounter {
+    count: usize,
+}
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len();
+        self.count += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
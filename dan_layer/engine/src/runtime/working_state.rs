@@ -57,6 +57,7 @@ use crate::{
         address_allocation::AllocatedAddress,
         fee_state::FeeState,
         locking::LockedSubstate,
+        royalty_state::RoyaltyState,
         scope::{CallFrame, CallScope},
         state_store::WorkingStateStore,
         tracker_auth::Authorization,
@@ -72,6 +73,7 @@ const LOG_TARGET: &str = "dan::engine::runtime::working_state";
 #[derive(Debug, Clone)]
 pub(super) struct WorkingState {
     transaction_hash: Hash,
+    id_seed: Hash,
     events: Vec<Event>,
     logs: Vec<LogEntry>,
     buckets: HashMap<BucketId, Bucket>,
@@ -91,6 +93,8 @@ pub(super) struct WorkingState {
     initial_call_scope: CallScope,
 
     fee_state: FeeState,
+    royalty_state: RoyaltyState,
+    is_system_transaction: bool,
 }
 
 impl WorkingState {
@@ -102,6 +106,7 @@ impl WorkingState {
     ) -> Self {
         Self {
             transaction_hash,
+            id_seed: transaction_hash,
             events: Vec::new(),
             logs: Vec::new(),
             buckets: HashMap::new(),
@@ -119,7 +124,9 @@ impl WorkingState {
             call_frames: Vec::new(),
             initial_call_scope,
             fee_state: FeeState::new(),
+            royalty_state: RoyaltyState::new(),
             object_ids: ObjectIds::new(1000),
+            is_system_transaction: false,
         }
     }
 
@@ -127,6 +134,18 @@ impl WorkingState {
         self.transaction_hash
     }
 
+    pub fn is_system_transaction(&self) -> bool {
+        self.is_system_transaction
+    }
+
+    pub fn set_system_transaction(&mut self, is_system_transaction: bool) {
+        self.is_system_transaction = is_system_transaction;
+    }
+
+    pub fn set_id_seed(&mut self, id_seed: Hash) {
+        self.id_seed = id_seed;
+    }
+
     pub fn substate_exists(&self, address: &SubstateId) -> Result<bool, RuntimeError> {
         // All public identity resources exist
         if address
@@ -146,6 +165,9 @@ impl WorkingState {
         value: V,
     ) -> Result<(), RuntimeError> {
         let address = address.into();
+        if address.is_reserved() && !self.is_system_transaction {
+            return Err(RuntimeError::ReservedAddressNotPermitted { address });
+        }
         self.current_call_scope_mut()?.add_substate_to_scope(address.clone())?;
         self.store.insert(address, value.into())?;
         Ok(())
@@ -833,6 +855,14 @@ impl WorkingState {
         &mut self.fee_state
     }
 
+    pub fn royalty_state(&self) -> &RoyaltyState {
+        &self.royalty_state
+    }
+
+    pub fn royalty_state_mut(&mut self) -> &mut RoyaltyState {
+        &mut self.royalty_state
+    }
+
     pub fn set_last_instruction_output(&mut self, output: IndexedValue) {
         self.last_instruction_output = Some(output);
     }
@@ -888,7 +918,10 @@ impl WorkingState {
                 total_fee_payment,
                 total_fees_paid: fee_resource.amount(),
                 cost_breakdown: self.fee_state.fee_charges.drain(..).collect(),
+                // Set once the final substate sizes are known
+                total_storage_bytes: 0,
             },
+            substate_sizes: vec![],
         })
     }
 
@@ -923,7 +956,7 @@ impl WorkingState {
     pub fn id_provider(&self) -> Result<IdProvider<'_>, RuntimeError> {
         self.call_frames
             .last()
-            .map(|frame| IdProvider::new(frame.entity_id(), self.transaction_hash, &self.object_ids))
+            .map(|frame| IdProvider::new(frame.entity_id(), self.id_seed, &self.object_ids))
             .ok_or(RuntimeError::NoActiveCallFrame)
     }
 
@@ -951,10 +984,43 @@ impl WorkingState {
         Ok(AuthHookCaller::new(*template, component))
     }
 
+    /// Returns a description of each active call frame, from the outermost to the innermost call
+    pub fn call_chain(&self) -> Vec<String> {
+        self.call_frames.iter().map(|frame| frame.to_string()).collect()
+    }
+
+    /// Returns an error if the component is already in the active call stack
+    pub fn check_reentrancy(
+        &self,
+        component_address: &ComponentAddress,
+        module_name: &str,
+    ) -> Result<(), RuntimeError> {
+        let is_active = self.call_frames.iter().any(|frame| {
+            frame
+                .scope()
+                .get_current_component_lock()
+                .is_some_and(|lock| lock.address().as_component_address() == Some(*component_address))
+        });
+
+        if is_active {
+            let mut call_chain = self.call_chain();
+            call_chain.push(format!("{}({})", module_name, component_address));
+            return Err(RuntimeError::ReentrancyDenied {
+                component_address: *component_address,
+                call_chain,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn push_frame(&mut self, mut new_frame: CallFrame, max_call_depth: usize) -> Result<(), RuntimeError> {
         if self.call_frame_depth() + 1 > max_call_depth {
-            return Err(RuntimeError::MaxCallDepthExceeded {
+            let mut call_chain = self.call_chain();
+            call_chain.push(new_frame.to_string());
+            return Err(RuntimeError::CallDepthExceeded {
                 max_depth: max_call_depth,
+                call_chain,
             });
         }
 
@@ -21,7 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use tari_engine_types::indexed_value::IndexedValueError;
-use tari_template_lib::models::TemplateAddress;
+use tari_template_lib::models::{ComponentAddress, TemplateAddress};
 
 use crate::{runtime::RuntimeError, wasm::WasmExecutionError};
 
@@ -43,6 +43,15 @@ pub enum TransactionError {
     ValueVisitorError(#[from] IndexedValueError),
     #[error("Function {name} not found")]
     FunctionNotFound { name: String },
+    #[error(
+        "Template {template_address} is not an upgrade of template {current_template_address} used by component \
+         {component_address}"
+    )]
+    IncompatibleTemplateUpgrade {
+        component_address: ComponentAddress,
+        current_template_address: TemplateAddress,
+        template_address: TemplateAddress,
+    },
     #[error("Invariant error: {details}")]
     InvariantError { details: String },
 }
@@ -24,4 +24,4 @@ mod error;
 pub use error::TransactionError;
 
 mod processor;
-pub use processor::{TransactionProcessor, MAX_CALL_DEPTH};
+pub use processor::{TransactionProcessor, DEFAULT_EXECUTION_BUDGET, MAX_CALL_DEPTH};
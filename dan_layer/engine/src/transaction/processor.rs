@@ -46,6 +46,7 @@ use tari_template_lib::{
     invoke_args,
     models::{ComponentAddress, NonFungibleAddress},
     prelude::TemplateAddress,
+    Hash,
 };
 use tari_transaction::Transaction;
 use tari_utilities::ByteArray;
@@ -55,6 +56,7 @@ use crate::{
         scope::{CallScope, PushCallFrame},
         AuthParams,
         AuthorizationScope,
+        ExecutionBudget,
         Runtime,
         RuntimeInterfaceImpl,
         RuntimeModule,
@@ -68,7 +70,12 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "tari::dan::engine::instruction_processor";
+/// The default maximum depth of nested template calls, including the call made by the instruction
 pub const MAX_CALL_DEPTH: usize = 10;
+/// The default number of metering points that a transaction may use across all of its template calls
+pub const DEFAULT_EXECUTION_BUDGET: u64 = 1_000_000_000;
+/// The function that a template may export to transform the state of components that are migrated to it
+pub const ON_MIGRATE_FUNCTION: &str = "on_migrate";
 
 pub struct TransactionProcessor<TTemplateProvider> {
     template_provider: Arc<TTemplateProvider>,
@@ -77,6 +84,10 @@ pub struct TransactionProcessor<TTemplateProvider> {
     virtual_substates: VirtualSubstates,
     modules: Vec<Arc<dyn RuntimeModule>>,
     network: Network,
+    max_call_depth: usize,
+    execution_budget: u64,
+    is_system_transaction: bool,
+    id_seed: Option<Hash>,
 }
 
 impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> TransactionProcessor<TTemplateProvider> {
@@ -95,11 +106,43 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             virtual_substates,
             modules,
             network,
+            max_call_depth: MAX_CALL_DEPTH,
+            execution_budget: DEFAULT_EXECUTION_BUDGET,
+            is_system_transaction: false,
+            id_seed: None,
         }
     }
 
+    /// Sets the maximum depth of nested template calls. Transactions that exceed it fail with `CallDepthExceeded`.
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Sets the number of metering points that the transaction may use across all of its template calls. Transactions
+    /// that exceed it are rejected with `ExecutionBudgetExceeded`.
+    pub fn with_execution_budget(mut self, execution_budget: u64) -> Self {
+        self.execution_budget = execution_budget;
+        self
+    }
+
+    /// Executes the transaction as a system (e.g. genesis) transaction. System transactions may create substates in
+    /// the reserved address range. This must never be set for user-submitted transactions.
+    pub fn with_system_transaction(mut self, is_system_transaction: bool) -> Self {
+        self.is_system_transaction = is_system_transaction;
+        self
+    }
+
+    /// Derives the ids of new entities, components, resources and vaults from the given seed instead of the
+    /// transaction hash, so that the same transactions allocate the same addresses on every run. This is intended for
+    /// tests and must never be set when executing transactions for consensus.
+    pub fn with_id_seed(mut self, id_seed: Hash) -> Self {
+        self.id_seed = Some(id_seed);
+        self
+    }
+
     pub fn execute(self, transaction: Transaction) -> Result<ExecuteResult, TransactionError> {
-        let entity_id_provider = EntityIdProvider::new(transaction.hash(), 1000);
+        let mut entity_id_provider = EntityIdProvider::new(transaction.hash(), 1000);
         let Self {
             template_provider,
             state_db,
@@ -107,6 +150,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             virtual_substates,
             modules,
             network,
+            max_call_depth,
+            execution_budget,
+            is_system_transaction,
+            id_seed,
         } = self;
 
         let initial_auth_scope = AuthorizationScope::new(auth_params.initial_ownership_proofs);
@@ -117,6 +164,11 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         }
 
         let tracker = StateTracker::new(state_db, virtual_substates, initial_call_scope, transaction.hash());
+        tracker.set_system_transaction(is_system_transaction);
+        if let Some(id_seed) = id_seed {
+            entity_id_provider = entity_id_provider.with_id_seed(id_seed);
+            tracker.set_id_seed(id_seed);
+        }
 
         let runtime_interface = RuntimeInterfaceImpl::initialize(
             tracker,
@@ -124,8 +176,9 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             transaction.signer_public_key().clone(),
             entity_id_provider,
             modules,
-            MAX_CALL_DEPTH,
+            max_call_depth,
             network,
+            ExecutionBudget::new(execution_budget),
         )?;
 
         let runtime = Runtime::new(Arc::new(runtime_interface));
@@ -149,19 +202,18 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             },
             Err(err) => {
                 return Ok(ExecuteResult {
-                    finalize: FinalizeResult::new_rejected(
-                        transaction_hash,
-                        RejectReason::ExecutionFailure(err.to_string()),
-                    ),
+                    finalize: FinalizeResult::new_rejected(transaction_hash, Self::reject_reason(&runtime, &err)),
                 });
             },
         };
 
-        let instruction_result = Self::process_instructions(&*template_provider, &runtime, instructions);
+        // Finalize errors (e.g. substates exceeding the size limits) are treated like instruction failures so that
+        // fees are still charged
+        let instruction_result = Self::process_instructions(&*template_provider, &runtime, instructions)
+            .and_then(|execution_results| Ok((runtime.interface().finalize()?, execution_results)));
 
         match instruction_result {
-            Ok(execution_results) => {
-                let mut finalize = runtime.interface().finalize()?;
+            Ok((mut finalize, execution_results)) => {
                 if finalize.fee_receipt.is_paid_in_full() {
                     finalize.execution_results = execution_results;
                 } else {
@@ -169,6 +221,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                 }
                 Ok(ExecuteResult { finalize })
             },
+            // A transaction that exceeds its execution budget is rejected outright so that no partial state is kept
+            Err(err) if runtime.interface().execution_budget().is_exceeded() => Ok(ExecuteResult {
+                finalize: FinalizeResult::new_rejected(transaction_hash, Self::reject_reason(&runtime, &err)),
+            }),
             // This can happen e.g if you have dangling buckets after running the instructions
             Err(err) => {
                 // Reset the state to when the state at the end of the fee instructions. The fee charges for the
@@ -190,6 +246,14 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         }
     }
 
+    fn reject_reason(runtime: &Runtime, err: &TransactionError) -> RejectReason {
+        if runtime.interface().execution_budget().is_exceeded() {
+            RejectReason::ExecutionBudgetExceeded(err.to_string())
+        } else {
+            RejectReason::ExecutionFailure(err.to_string())
+        }
+    }
+
     fn process_instructions(
         template_provider: &TTemplateProvider,
         runtime: &Runtime,
@@ -250,6 +314,35 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                     .claim_validator_fees(Epoch(epoch), validator_public_key)?;
                 Ok(InstructionResult::empty())
             },
+            Instruction::DeclareRoyaltyPayment {
+                resource_address,
+                workspace_bucket,
+            } => {
+                runtime
+                    .interface()
+                    .declare_royalty_payment(resource_address, workspace_bucket)?;
+                Ok(InstructionResult::empty())
+            },
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => Self::migrate_component(template_provider, runtime, &component_address, &template_address),
+            Instruction::AssertBucketContains {
+                workspace_bucket,
+                resource_address,
+                min_amount,
+            } => {
+                runtime
+                    .interface()
+                    .assert_bucket_contains(workspace_bucket, resource_address, min_amount)?;
+                Ok(InstructionResult::empty())
+            },
+            Instruction::AssertVaultBalanceAtLeast { vault_id, min_amount } => {
+                runtime
+                    .interface()
+                    .assert_vault_balance_at_least(vault_id, min_amount)?;
+                Ok(InstructionResult::empty())
+            },
             Instruction::CreateFreeTestCoins {
                 revealed_amount: amount,
                 output,
@@ -407,6 +500,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             }
         })?;
 
+        if !template.template_def().is_reentrant() {
+            runtime
+                .interface()
+                .check_reentrancy(component_address, template.template_name())?;
+        }
+
         let lock_flag = if function_def.is_mut {
             LockFlag::Write
         } else {
@@ -449,6 +548,67 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         Ok(result)
     }
 
+    /// Migrates the component to the given template, which must declare that it upgrades the component's current
+    /// template. If the new template exports an `on_migrate` function, it is called with the current component state
+    /// and the returned value becomes the new component state.
+    pub fn migrate_component(
+        template_provider: &TTemplateProvider,
+        runtime: &Runtime,
+        component_address: &ComponentAddress,
+        template_address: &TemplateAddress,
+    ) -> Result<InstructionResult, TransactionError> {
+        let component = runtime.interface().load_component(component_address)?;
+
+        let template = template_provider
+            .get_template_module(template_address)
+            .map_err(|e| TransactionError::FailedToLoadTemplate {
+                address: *template_address,
+                details: e.to_string(),
+            })?
+            .ok_or(TransactionError::TemplateNotFound {
+                address: *template_address,
+            })?;
+
+        if template.template_def().upgrades_from() != Some(&component.template_address.into_array()) {
+            return Err(TransactionError::IncompatibleTemplateUpgrade {
+                component_address: *component_address,
+                current_template_address: component.template_address,
+                template_address: *template_address,
+            });
+        }
+
+        let new_state = match template.template_def().get_function(ON_MIGRATE_FUNCTION).cloned() {
+            Some(function_def) => {
+                let old_state = component.state().clone();
+                runtime.interface().push_call_frame(PushCallFrame::Static {
+                    template_address: *template_address,
+                    module_name: template.template_name().to_string(),
+                    arg_scope: IndexedWellKnownTypes::from_value(&old_state)?,
+                    entity_id: component.entity_id,
+                })?;
+
+                let result = Self::invoke_template(
+                    template,
+                    template_provider,
+                    runtime.clone(),
+                    function_def,
+                    vec![old_state],
+                )?;
+
+                runtime.interface().validate_return_value(&result.indexed)?;
+                runtime.interface().pop_call_frame()?;
+                Some(result.indexed.into_value())
+            },
+            None => None,
+        };
+
+        runtime
+            .interface()
+            .migrate_component(*component_address, *template_address, new_state)?;
+
+        Ok(InstructionResult::empty())
+    }
+
     fn invoke_template(
         module: LoadedTemplate,
         template_provider: &TTemplateProvider,
@@ -44,6 +44,8 @@ pub enum WasmExecutionError {
     EngineArgDecodeFailed(BorError),
     #[error("maximum module memory size exceeded")]
     MaxMemorySizeExceeded,
+    #[error("Execution budget exceeded")]
+    ExecutionBudgetExceeded,
     #[error("Failed to decode ABI: {0:?}")]
     AbiDecodeError(BorError),
     #[error("Unexpected ABI function {name}")]
@@ -78,7 +78,8 @@ impl WasmModule {
     fn create_store() -> Store {
         let mut cranelift = Cranelift::new();
         cranelift.opt_level(CraneliftOptLevel::Speed).canonicalize_nans(true);
-        // TODO: Configure metering limit
+        // This limit only applies when loading the template. Calls made by a transaction are limited to the remaining
+        // execution budget of the transaction (see WasmProcess::invoke).
         cranelift.push_middleware(Arc::new(metering::middleware(100_000_000)));
         let engine = Universal::new(cranelift).engine();
         let tunables = BaseTunables::for_target(engine.target());
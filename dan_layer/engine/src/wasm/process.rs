@@ -45,6 +45,7 @@ use tari_template_lib::{
     AbiContext,
 };
 use wasmer::{Function, Instance, Module, Val, WasmerEnv};
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 
 use super::version::are_versions_compatible;
 use crate::{
@@ -246,7 +247,22 @@ impl Invokable for WasmProcess {
         let func = self.instance.exports.get_function(&main_name)?;
 
         let call_info_ptr = self.alloc_and_write(&call_info)?;
+        // The call may only use the points that remain in the budget of the transaction. Nested template calls run in
+        // their own instance and consume from the same budget.
+        let budget = self.env.state().interface().execution_budget();
+        let initial_points = budget.remaining();
+        set_remaining_points(&self.instance, initial_points);
         let res = func.call(&[Val::I32(call_info_ptr.as_i32()), Val::I32(call_info_ptr.len() as i32)]);
+        let used_points = match get_remaining_points(&self.instance) {
+            MeteringPoints::Remaining(remaining) => initial_points - remaining,
+            MeteringPoints::Exhausted => {
+                budget.set_exceeded();
+                return Err(WasmExecutionError::ExecutionBudgetExceeded);
+            },
+        };
+        if !budget.consume(used_points) || budget.is_exceeded() {
+            return Err(WasmExecutionError::ExecutionBudgetExceeded);
+        }
         self.env.free(call_info_ptr)?;
 
         let val = match res {
@@ -1,8 +1,13 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_engine::runtime::TransactionCommitError;
-use tari_template_lib::{args, models::ComponentAddress};
+use tari_dan_engine::runtime::{RuntimeError, TransactionCommitError};
+use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{
+    args,
+    constants::{FEE_POOL_COMPONENT_ADDRESS, NETWORK_CONFIG_COMPONENT_ADDRESS},
+    models::{ComponentAddress, ObjectKey},
+};
 use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
 use tari_transaction::Transaction;
 
@@ -50,3 +55,92 @@ fn it_fails_if_allocation_is_not_used() {
 
     assert_reject_reason(reason, TransactionCommitError::DanglingAddressAllocations { count: 1 });
 }
+
+#[test]
+fn it_rejects_user_transactions_that_create_components_at_reserved_addresses() {
+    let mut test = TemplateTest::new(["tests/templates/address_allocation"]);
+    let template_addr = test.get_template_address("AddressAllocationTest");
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(template_addr, "create_at_reserved", args![FEE_POOL_COMPONENT_ADDRESS])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert_reject_reason(reason, RuntimeError::ReservedAddressNotPermitted {
+        address: FEE_POOL_COMPONENT_ADDRESS.into(),
+    });
+}
+
+#[test]
+fn it_creates_components_at_reserved_addresses_in_system_transactions() {
+    let mut test = TemplateTest::new(["tests/templates/address_allocation"]);
+    let template_addr = test.get_template_address("AddressAllocationTest");
+
+    let result = test.execute_system_expect_success(
+        Transaction::builder()
+            .call_function(template_addr, "create_at_reserved", args![FEE_POOL_COMPONENT_ADDRESS])
+            .call_function(
+                template_addr,
+                "create_at_reserved",
+                args![NETWORK_CONFIG_COMPONENT_ADDRESS],
+            )
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    let diff = result.finalize.result.accept().unwrap();
+    assert!(diff
+        .up_iter()
+        .any(|(id, _)| *id == SubstateId::Component(FEE_POOL_COMPONENT_ADDRESS)));
+    assert!(diff
+        .up_iter()
+        .any(|(id, _)| *id == SubstateId::Component(NETWORK_CONFIG_COMPONENT_ADDRESS)));
+}
+
+#[test]
+fn it_rejects_system_transactions_that_allocate_outside_the_reserved_range() {
+    let mut test = TemplateTest::new(["tests/templates/address_allocation"]);
+    let template_addr = test.get_template_address("AddressAllocationTest");
+    let address = ComponentAddress::from_array([1u8; ObjectKey::LENGTH]);
+
+    let result = test
+        .try_execute_system(
+            Transaction::builder()
+                .call_function(template_addr, "create_at_reserved", args![address])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        )
+        .unwrap();
+
+    assert_reject_reason(result.expect_failure(), RuntimeError::AddressNotReserved {
+        address: address.into(),
+    });
+}
+
+#[test]
+fn it_allocates_the_same_addresses_with_the_same_id_seed() {
+    fn run(seed: u64) -> Vec<Vec<SubstateId>> {
+        let mut test = TemplateTest::new(["tests/templates/address_allocation"]).with_deterministic_ids(seed);
+        test.create_funded_account();
+        test.execute_expect_success(
+            Transaction::builder()
+                .call_function(test.get_template_address("AddressAllocationTest"), "create", args![])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        );
+        assert!(test
+            .last_allocated_addresses()
+            .iter()
+            .any(|addr| addr.as_component_address().is_some()));
+        test.allocated_addresses().to_vec()
+    }
+
+    assert_eq!(run(1), run(1));
+    assert_ne!(run(1), run(2));
+}
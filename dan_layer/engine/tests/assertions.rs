@@ -0,0 +1,140 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use tari_dan_engine::runtime::RuntimeError;
+use tari_template_lib::{
+    args,
+    constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+    models::{Amount, ComponentAddress, ResourceAddress, VaultId},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+fn get_vault(test: &TemplateTest, account: ComponentAddress, resource_address: ResourceAddress) -> VaultId {
+    let vaults: BTreeMap<ResourceAddress, VaultId> = test.extract_component_value(account, "$.vaults");
+    vaults[&resource_address]
+}
+
+#[test]
+fn it_commits_transactions_whose_assertions_hold() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+    let (other_account, _, _) = test.create_empty_account();
+    let vault_id = get_vault(&test, account, CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
+    let orig_balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(account, "withdraw", args![
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                Amount(100)
+            ])
+            .put_last_instruction_output_on_workspace("bucket")
+            .assert_bucket_contains("bucket", CONFIDENTIAL_TARI_RESOURCE_ADDRESS, Amount(100))
+            .call_method(other_account, "deposit", args![Workspace("bucket")])
+            .assert_vault_balance_at_least(vault_id, orig_balance - Amount(100))
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    let balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+    assert_eq!(balance, orig_balance - Amount(100));
+}
+
+#[test]
+fn it_rejects_transactions_whose_bucket_assertion_fails() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(account, "withdraw", args![
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                Amount(100)
+            ])
+            .put_last_instruction_output_on_workspace("bucket")
+            .assert_bucket_contains("bucket", CONFIDENTIAL_TARI_RESOURCE_ADDRESS, Amount(101))
+            .call_method(account, "deposit", args![Workspace("bucket")])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    assert_reject_reason(reason, RuntimeError::AssertionFailed {
+        details: format!(
+            "bucket 'bucket' contains 100 of resource {} but at least 101 is required",
+            CONFIDENTIAL_TARI_RESOURCE_ADDRESS
+        ),
+    });
+}
+
+#[test]
+fn it_rejects_transactions_whose_vault_assertion_fails() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+    let (other_account, _, _) = test.create_empty_account();
+    let vault_id = get_vault(&test, account, CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
+    let orig_balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(account, "withdraw", args![
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                Amount(100)
+            ])
+            .put_last_instruction_output_on_workspace("bucket")
+            .call_method(other_account, "deposit", args![Workspace("bucket")])
+            .assert_vault_balance_at_least(vault_id, orig_balance - Amount(99))
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    assert_reject_reason(reason, RuntimeError::AssertionFailed {
+        details: format!(
+            "vault {} has a balance of {} but at least {} is required",
+            vault_id,
+            orig_balance - Amount(100),
+            orig_balance - Amount(99)
+        ),
+    });
+}
+
+#[test]
+fn it_charges_fees_when_an_assertion_fails() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+    let (other_account, _, _) = test.create_empty_account();
+    let vault_id = get_vault(&test, account, CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
+    let orig_balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+
+    test.enable_fees();
+    let result = test.execute_expect_commit(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_method(account, "withdraw", args![
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                Amount(100)
+            ])
+            .put_last_instruction_output_on_workspace("bucket")
+            .call_method(other_account, "deposit", args![Workspace("bucket")])
+            // The maximum fee has already been taken from the vault by the fee instructions, so this does not hold
+            .assert_vault_balance_at_least(vault_id, orig_balance - Amount(100))
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+    test.disable_fees();
+
+    let reason = result.expect_transaction_failure();
+    assert_reject_reason(reason, "Assertion failed");
+
+    // Only the fee is taken from the account, the transfer is not applied
+    let payment = &result.finalize.fee_receipt;
+    assert!(!payment.total_fees_paid().is_zero());
+    let balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+    assert_eq!(balance, orig_balance - payment.total_fees_paid());
+}
@@ -0,0 +1,120 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashSet;
+
+use tari_engine_types::{commit_result::ExecuteResult, component::new_account_address_from_parts, substate::SubstateId};
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_template_lib::{args, models::ComponentAddress};
+use tari_template_test_tooling::{test_faucet_component, ShardedTemplateTest, SubstateType};
+use tari_transaction::Transaction;
+
+const NUM_COMMITTEES: [u32; 5] = [2, 3, 4, 7, 16];
+
+fn create_funded_account(test: &mut ShardedTemplateTest) -> (ComponentAddress, ComponentAddress, ExecuteResult) {
+    let (owner_token, public_key, secret_key) = test.create_owner_proof();
+    let expected = new_account_address_from_parts(&ACCOUNT_TEMPLATE_ADDRESS, &public_key);
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(test_faucet_component(), "take_free_coins", args![])
+            .put_last_instruction_output_on_workspace("bucket")
+            .create_account_with_bucket(public_key, "bucket")
+            .sign(&secret_key)
+            .build(),
+        vec![owner_token],
+    );
+    let account = result.finalize.execution_results[2]
+        .decode::<ComponentAddress>()
+        .unwrap();
+    (account, expected, result)
+}
+
+/// Returns the substates that were created (not updated) by the transaction
+fn new_substates(result: &ExecuteResult) -> Vec<SubstateId> {
+    let diff = result.finalize.result.accept().unwrap();
+    let downed = diff.down_iter().map(|(id, _)| id).collect::<HashSet<_>>();
+    diff.up_iter()
+        .map(|(id, _)| id)
+        .filter(|id| !downed.contains(id))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn it_creates_account_outputs_in_the_shard_derived_from_the_owner_token() {
+    for num_committees in NUM_COMMITTEES {
+        let mut test = ShardedTemplateTest::new(Vec::<&str>::new(), num_committees);
+        let (account, expected_account, result) = create_funded_account(&mut test);
+        assert_eq!(account, expected_account);
+
+        let expected_shard = test.shard_for(&expected_account.into(), 0);
+        test.assert_in_shard(account, expected_shard);
+
+        let account_outputs = new_substates(&result)
+            .into_iter()
+            .filter(|id| SubstateType::Component.matches(id) || SubstateType::Vault.matches(id))
+            .collect::<Vec<_>>();
+        // The account component and the vault for the deposited funds
+        assert_eq!(account_outputs.len(), 2);
+        for id in account_outputs {
+            test.assert_in_shard(id, expected_shard);
+        }
+    }
+}
+
+#[test]
+fn it_keeps_account_vaults_in_the_account_shard_after_deposits() {
+    for num_committees in NUM_COMMITTEES {
+        let mut test = ShardedTemplateTest::new(Vec::<&str>::new(), num_committees);
+        let (account, _, _) = create_funded_account(&mut test);
+        let (other, _, _) = create_funded_account(&mut test);
+
+        test.execute_expect_success(
+            Transaction::builder()
+                .call_method(test_faucet_component(), "take_free_coins", args![])
+                .put_last_instruction_output_on_workspace("bucket")
+                .call_method(other, "deposit", args![Workspace("bucket")])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        );
+
+        for account in [account, other] {
+            let state = test.read_only_state_store().inspect_component(account).unwrap();
+            assert!(!state.vault_ids().is_empty());
+            for vault_id in state.vault_ids() {
+                test.assert_same_shard(account, *vault_id);
+            }
+        }
+    }
+}
+
+#[test]
+fn it_reports_the_output_distribution_of_a_transaction() {
+    let mut test = ShardedTemplateTest::new(Vec::<&str>::new(), 1);
+    let (_, _, result) = create_funded_account(&mut test);
+    let distribution = test.output_distribution(&result);
+    let num_outputs = result.finalize.result.accept().unwrap().up_len();
+    assert_eq!(distribution.num_committees(), 1);
+    assert!(!distribution.is_cross_shard());
+    assert_eq!(distribution.total_outputs(), num_outputs);
+
+    let mut test = ShardedTemplateTest::new(Vec::<&str>::new(), 16);
+    let (account, _, result) = create_funded_account(&mut test);
+    let distribution = test.output_distribution(&result);
+    assert_eq!(distribution.total_outputs(), num_outputs);
+    assert_eq!(
+        distribution.iter().map(|(_, outputs)| outputs.len()).sum::<usize>(),
+        num_outputs
+    );
+    let account_shard = test.shard_of(&account.into());
+    assert!(distribution.outputs_in(account_shard).contains(&account.into()));
+    assert_eq!(
+        distribution.count_in(account_shard),
+        distribution.outputs_in(account_shard).len()
+    );
+    // Every tracked output is assigned to the shard reported in the distribution
+    for (id, shard) in test.tracked_substates() {
+        assert!(distribution.outputs_in(shard).contains(id));
+    }
+}
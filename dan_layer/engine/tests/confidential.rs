@@ -3,18 +3,24 @@
 
 use rand::rngs::OsRng;
 use tari_common_types::types::PublicKey;
-use tari_crypto::keys::PublicKey as _;
-use tari_engine_types::{resource_container::ResourceError, substate::SubstateId};
+use tari_crypto::{commitment::HomomorphicCommitmentFactory, keys::PublicKey as _};
+use tari_engine_types::{
+    confidential::get_commitment_factory,
+    resource_container::ResourceError,
+    substate::SubstateId,
+};
 use tari_template_lib::{
     args,
+    auth::ResourceAuthAction,
     crypto::RistrettoPublicKeyBytes,
     models::{Amount, ComponentAddress},
     prelude::ConfidentialOutputStatement,
 };
 use tari_template_test_tooling::{
     support::{
-        assert_error::assert_reject_reason,
+        assert_error::{assert_access_denied_for_action, assert_reject_reason},
         confidential::{
+            generate_burn_proof,
             generate_confidential_proof,
             generate_confidential_proof_with_view_key,
             generate_withdraw_proof,
@@ -28,7 +34,7 @@ use tari_template_test_tooling::{
 };
 use tari_transaction::Transaction;
 use tari_transaction_manifest::ManifestValue;
-use tari_utilities::ByteArray;
+use tari_utilities::{hex::Hex, ByteArray};
 
 fn setup(
     initial_supply: ConfidentialOutputStatement,
@@ -515,3 +521,81 @@ fn mint_with_view_key() {
         .unwrap();
     assert_eq!(total_balance, Some(55));
 }
+
+#[test]
+fn burn_part_of_a_confidential_output() {
+    let (confidential_proof, mask, _change) = generate_confidential_proof(Amount(100), None);
+    let mut test = TemplateTest::new(vec!["tests/templates/confidential/faucet"]);
+    let faucet: ComponentAddress =
+        test.call_function("ConfidentialFaucet", "mint_burnable", args![confidential_proof], vec![]);
+    let faucet_resx = test.get_previous_output_address(SubstateType::Resource);
+    test.call_method::<()>(faucet, "mint_revealed", args![Amount(1000)], vec![]);
+
+    let (_account, user_proof, user_key) = test.create_empty_account();
+
+    let burn_proof = generate_burn_proof(&mask, Amount(100), Amount(30));
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(faucet, "burn_coins", args![burn_proof.proof])
+            .call_method(faucet, "total_supply", args![])
+            .call_method(faucet, "vault_balance", args![])
+            .sign(&user_key)
+            .build(),
+        vec![user_proof],
+    );
+
+    let burnt = result.finalize.execution_results[0].decode::<Amount>().unwrap();
+    assert_eq!(burnt, Amount(30));
+    let total_supply = result.finalize.execution_results[1].decode::<Amount>().unwrap();
+    assert_eq!(total_supply, Amount(970));
+    // Only the confidential input was burnt
+    let revealed_balance = result.finalize.execution_results[2].decode::<Amount>().unwrap();
+    assert_eq!(revealed_balance, Amount(1000));
+
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "std.resource.burn_confidential")
+        .unwrap();
+    assert_eq!(
+        *event.payload().get("resource_address").unwrap(),
+        faucet_resx.to_string()
+    );
+    assert_eq!(*event.payload().get("amount").unwrap(), "30");
+    let input_commitment = get_commitment_factory().commit_value(&mask, 100);
+    assert_eq!(
+        *event.payload().get("commitments").unwrap(),
+        input_commitment.as_public_key().to_hex()
+    );
+
+    // The input commitment was destroyed, so it cannot be burnt again
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(faucet, "burn_coins", args![burn_proof.proof])
+            .sign(&user_key)
+            .build(),
+        vec![],
+    );
+    assert_reject_reason(reason, ResourceError::InvalidConfidentialProof {
+        details: String::new(),
+    });
+}
+
+#[test]
+fn burn_confidential_requires_a_burnable_resource() {
+    let (confidential_proof, mask, _change) = generate_confidential_proof(Amount(100), None);
+    let (mut test, faucet, _faucet_resx) = setup(confidential_proof, None);
+
+    let (_account, user_proof, user_key) = test.create_empty_account();
+
+    let burn_proof = generate_burn_proof(&mask, Amount(100), Amount(100));
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(faucet, "burn_coins", args![burn_proof.proof])
+            .sign(&user_key)
+            .build(),
+        vec![user_proof],
+    );
+    assert_access_denied_for_action(reason, ResourceAuthAction::Burn);
+}
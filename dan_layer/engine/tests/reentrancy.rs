@@ -1,7 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_engine::runtime::{LockError, LockState};
+use tari_dan_engine::{
+    runtime::{LockError, LockState, RuntimeError},
+    transaction::MAX_CALL_DEPTH,
+};
 use tari_engine_types::lock::LockFlag;
 use tari_template_lib::{
     args,
@@ -116,3 +119,72 @@ fn it_prevents_multiple_mutable_access_to_component() {
         address: reentrancy.into(),
     });
 }
+
+#[test]
+fn it_denies_reentrant_calls_to_templates_that_have_not_opted_in() {
+    let mut test = TemplateTest::new(["tests/templates/ping", "tests/templates/pong"]);
+
+    let ping: ComponentAddress = test.call_function("Ping", "new", args![], vec![]);
+    let pong: ComponentAddress = test.call_function("Pong", "new", args![], vec![]);
+
+    // Ping -> Pong -> Ping
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(ping, "bounce", args![pong, 2u32])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert_reject_reason(reason, RuntimeError::ReentrancyDenied {
+        component_address: ping,
+        call_chain: vec![
+            format!("Ping({})", ping),
+            format!("Pong({})", pong),
+            format!("Ping({})", ping),
+        ],
+    });
+}
+
+#[test]
+fn it_allows_reentrant_calls_to_templates_that_opt_in() {
+    let mut test = TemplateTest::new(["tests/templates/ping", "tests/templates/pong"]);
+
+    let ping: ComponentAddress = test.call_function("Ping", "new", args![], vec![]);
+    let pong: ComponentAddress = test.call_function("Pong", "new", args![], vec![]);
+
+    // Pong -> Ping -> Pong
+    let calls: u32 = test.call_method(pong, "bounce", args![ping, 2u32], vec![]);
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn it_reports_the_call_chain_when_the_max_call_depth_is_exceeded() {
+    let mut test = TemplateTest::new(["tests/templates/pong"]);
+
+    let pong1: ComponentAddress = test.call_function("Pong", "new", args![], vec![]);
+    let pong2: ComponentAddress = test.call_function("Pong", "new", args![], vec![]);
+
+    // Pong1 -> Pong2 -> Pong1 -> ... without end
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(pong1, "bounce", args![pong2, u32::MAX])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    let call_chain = (0..=MAX_CALL_DEPTH)
+        .map(|i| {
+            if i % 2 == 0 {
+                format!("Pong({})", pong1)
+            } else {
+                format!("Pong({})", pong2)
+            }
+        })
+        .collect();
+    assert_reject_reason(reason, RuntimeError::CallDepthExceeded {
+        max_depth: MAX_CALL_DEPTH,
+        call_chain,
+    });
+}
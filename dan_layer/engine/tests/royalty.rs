@@ -0,0 +1,255 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_crypto::ristretto::RistrettoSecretKey;
+use tari_template_lib::{
+    args,
+    constants::XTR2,
+    models::{Amount, ComponentAddress, NonFungibleAddress, NonFungibleId, ResourceAddress, TemplateAddress},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, SubstateType, TemplateTest};
+use tari_transaction::Transaction;
+
+struct Account {
+    address: ComponentAddress,
+    proof: NonFungibleAddress,
+    secret_key: RistrettoSecretKey,
+}
+
+struct Setup {
+    test: TemplateTest,
+    royalty_template: TemplateAddress,
+    artist: ComponentAddress,
+    seller: Account,
+    buyer: Account,
+    nft_resource: ResourceAddress,
+}
+
+fn setup(basis_points: u16) -> Setup {
+    let mut test = TemplateTest::new(["tests/templates/royalty"]);
+    let royalty_template = test.get_template_address("Royalty");
+
+    let (artist, _, _) = test.create_empty_account();
+    let (address, proof, secret_key) = test.create_empty_account();
+    let seller = Account {
+        address,
+        proof,
+        secret_key,
+    };
+    let (address, proof, secret_key) = test.create_funded_account();
+    let buyer = Account {
+        address,
+        proof,
+        secret_key,
+    };
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_function(royalty_template, "mint", args![artist, basis_points])
+            .put_last_instruction_output_on_workspace("nfts")
+            .call_method(seller.address, "deposit", args![Workspace("nfts")])
+            .sign(&seller.secret_key)
+            .build(),
+        vec![seller.proof.clone()],
+    );
+    let nft_resource = test
+        .get_previous_output_address(SubstateType::Resource)
+        .as_resource_address()
+        .unwrap();
+
+    Setup {
+        test,
+        royalty_template,
+        artist,
+        seller,
+        buyer,
+        nft_resource,
+    }
+}
+
+#[test]
+fn it_pays_royalty_to_recipient_on_transfer() {
+    let Setup {
+        mut test,
+        artist,
+        seller,
+        buyer,
+        nft_resource,
+    } = setup(500);
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(buyer.address, "withdraw", args![XTR2, Amount(1000)])
+            .put_last_instruction_output_on_workspace("payment")
+            .declare_royalty_payment(nft_resource, "payment")
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer.address, "deposit", args![Workspace("nft")])
+            .call_method(seller.address, "deposit", args![Workspace("payment")])
+            .call_method(artist, "balance", args![XTR2])
+            .call_method(seller.address, "balance", args![XTR2])
+            .sign(&buyer.secret_key)
+            .build(),
+        vec![buyer.proof, seller.proof],
+    );
+
+    let artist_balance = result.finalize.execution_results[7].decode::<Amount>().unwrap();
+    assert_eq!(artist_balance, Amount(50));
+    let seller_balance = result.finalize.execution_results[8].decode::<Amount>().unwrap();
+    assert_eq!(seller_balance, Amount(950));
+}
+
+#[test]
+fn it_rejects_transfer_without_royalty_payment() {
+    let Setup {
+        mut test,
+        seller,
+        buyer,
+        nft_resource,
+        ..
+    } = setup(500);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer.address, "deposit", args![Workspace("nft")])
+            .sign(&seller.secret_key)
+            .build(),
+        vec![seller.proof],
+    );
+
+    assert_reject_reason(reason, "Royalty not paid");
+}
+
+#[test]
+fn it_does_not_charge_royalty_when_owner_does_not_change() {
+    let Setup {
+        mut test,
+        seller,
+        nft_resource,
+        ..
+    } = setup(500);
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(seller.address, "deposit", args![Workspace("nft")])
+            .sign(&seller.secret_key)
+            .build(),
+        vec![seller.proof],
+    );
+}
+
+#[test]
+fn it_charges_royalty_when_depositing_into_a_new_component() {
+    let Setup {
+        mut test,
+        royalty_template,
+        artist,
+        seller,
+        buyer,
+        nft_resource,
+    } = setup(500);
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(buyer.address, "withdraw", args![XTR2, Amount(1000)])
+            .put_last_instruction_output_on_workspace("payment")
+            .declare_royalty_payment(nft_resource, "payment")
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            // The NFT is deposited into a vault of a component that is being created, i.e. outside of any component
+            .call_function(royalty_template, "store", args![Workspace("nft")])
+            .call_method(seller.address, "deposit", args![Workspace("payment")])
+            .call_method(artist, "balance", args![XTR2])
+            .sign(&buyer.secret_key)
+            .build(),
+        vec![buyer.proof, seller.proof],
+    );
+
+    let artist_balance = result.finalize.execution_results[7].decode::<Amount>().unwrap();
+    assert_eq!(artist_balance, Amount(50));
+}
+
+#[test]
+fn it_rejects_depositing_into_a_new_component_without_royalty_payment() {
+    let Setup {
+        mut test,
+        royalty_template,
+        seller,
+        nft_resource,
+        ..
+    } = setup(500);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_function(royalty_template, "store", args![Workspace("nft")])
+            .sign(&seller.secret_key)
+            .build(),
+        vec![seller.proof],
+    );
+
+    assert_reject_reason(reason, "Royalty not paid");
+}
+
+#[test]
+fn it_charges_royalty_for_each_transfer_in_a_transaction() {
+    let Setup {
+        mut test,
+        artist,
+        seller,
+        buyer,
+        nft_resource,
+        ..
+    } = setup(500);
+    let (collector, _, _) = test.create_empty_account();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(buyer.address, "withdraw", args![XTR2, Amount(1000)])
+            .put_last_instruction_output_on_workspace("payment")
+            .declare_royalty_payment(nft_resource, "payment")
+            .call_method(seller.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer.address, "deposit", args![Workspace("nft")])
+            .call_method(buyer.address, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft2")
+            .call_method(collector, "deposit", args![Workspace("nft2")])
+            .call_method(seller.address, "deposit", args![Workspace("payment")])
+            .call_method(artist, "balance", args![XTR2])
+            .call_method(seller.address, "balance", args![XTR2])
+            .sign(&buyer.secret_key)
+            .build(),
+        vec![buyer.proof, seller.proof],
+    );
+
+    let artist_balance = result.finalize.execution_results[10].decode::<Amount>().unwrap();
+    assert_eq!(artist_balance, Amount(100));
+    let seller_balance = result.finalize.execution_results[11].decode::<Amount>().unwrap();
+    assert_eq!(seller_balance, Amount(900));
+}
@@ -0,0 +1,152 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_engine::{
+    limits::SubstateSizeLimits,
+    runtime::{RuntimeError, RuntimeModuleError},
+};
+use tari_engine_types::{commit_result::ExecuteResult, substate::SubstateId};
+use tari_template_lib::{
+    args,
+    constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+    models::{Amount, ComponentAddress},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+const DATA_SIZE: u32 = 1000;
+
+fn setup() -> (TemplateTest, ComponentAddress) {
+    let mut test = TemplateTest::new(["tests/templates/large_state"]);
+    let component: ComponentAddress =
+        test.call_function(test.get_template_address("LargeState"), "new", args![], vec![]);
+    (test, component)
+}
+
+fn set_size(test: &mut TemplateTest, component: ComponentAddress, size: u32) -> ExecuteResult {
+    test.execute_and_commit_on_success(
+        Transaction::builder()
+            .call_method(component, "set_size", args![size])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    )
+}
+
+fn recorded_size(result: &ExecuteResult, substate_id: &SubstateId) -> u64 {
+    let receipt = result
+        .finalize
+        .result
+        .accept()
+        .unwrap()
+        .up_iter()
+        .find_map(|(_, substate)| substate.substate_value().as_transaction_receipt())
+        .unwrap();
+    receipt
+        .substate_sizes
+        .iter()
+        .find(|s| s.substate_id == *substate_id)
+        .map(|s| s.size)
+        .unwrap()
+}
+
+fn limits(max_substate_size: u64, max_transaction_size: u64) -> SubstateSizeLimits {
+    SubstateSizeLimits {
+        max_substate_size: max_substate_size as usize,
+        max_transaction_size: max_transaction_size as usize,
+    }
+}
+
+#[test]
+fn it_records_the_size_of_each_written_substate_in_the_receipt() {
+    let (mut test, component) = setup();
+
+    let result = set_size(&mut test, component, DATA_SIZE);
+    result.expect_success();
+
+    let size = recorded_size(&result, &component.into());
+    assert!(size > u64::from(DATA_SIZE));
+
+    let fee_receipt = &result.finalize.fee_receipt;
+    assert!(fee_receipt.total_storage_bytes >= size);
+    assert_eq!(
+        fee_receipt.to_cost_breakdown().total_storage_bytes,
+        fee_receipt.total_storage_bytes
+    );
+}
+
+#[test]
+fn it_accepts_a_substate_at_the_size_limit() {
+    let (mut test, component) = setup();
+    let size = recorded_size(&set_size(&mut test, component, DATA_SIZE), &component.into());
+
+    test.set_substate_size_limits(limits(size, u64::MAX));
+    set_size(&mut test, component, DATA_SIZE).expect_success();
+}
+
+#[test]
+fn it_rejects_a_substate_over_the_size_limit() {
+    let (mut test, component) = setup();
+    let size = recorded_size(&set_size(&mut test, component, DATA_SIZE), &component.into());
+
+    test.set_substate_size_limits(limits(size, u64::MAX));
+    let result = set_size(&mut test, component, DATA_SIZE + 1);
+
+    let reason = result.expect_transaction_failure();
+    assert_reject_reason(
+        reason,
+        RuntimeError::ModuleError(RuntimeModuleError::SubstateTooLarge {
+            substate_id: component.into(),
+            size: size as usize + 1,
+            max: size as usize,
+        }),
+    );
+}
+
+#[test]
+fn it_rejects_a_transaction_over_the_total_size_limit() {
+    let (mut test, _) = setup();
+    let template = test.get_template_address("LargeState");
+
+    test.set_substate_size_limits(limits(u64::from(DATA_SIZE) * 2, u64::from(DATA_SIZE) * 3));
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(template, "new_many", args![4u32, DATA_SIZE])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert!(
+        reason.to_string().contains("bytes of substates which exceeds the maximum"),
+        "Unexpected reject reason: {}",
+        reason
+    );
+}
+
+#[test]
+fn it_charges_fees_when_a_substate_is_too_large() {
+    let (mut test, component) = setup();
+    let (account, owner_token, secret_key) = test.create_funded_account();
+    let orig_balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+
+    test.set_substate_size_limits(limits(u64::from(DATA_SIZE) * 2, u64::MAX));
+    test.enable_fees();
+    let result = test.execute_and_commit_on_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_method(component, "set_size", args![DATA_SIZE * 4])
+            .sign(&secret_key)
+            .build(),
+        vec![owner_token],
+    );
+    test.disable_fees();
+
+    let reason = result.expect_transaction_failure();
+    assert!(reason.to_string().contains(&component.to_string()));
+    result.expect_finalization_success();
+
+    let new_balance: Amount = test.call_method(account, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS], vec![]);
+    assert!(!result.finalize.fee_receipt.total_fees_paid().is_zero());
+    assert_eq!(orig_balance - new_balance, result.finalize.fee_receipt.total_fees_paid());
+}
@@ -0,0 +1,120 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_engine::{
+    runtime::{ActionIdent, RuntimeError},
+    transaction::TransactionError,
+};
+use tari_template_lib::{
+    args,
+    models::{ComponentAddress, TemplateAddress},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, Package, TemplateTest};
+use tari_transaction::Transaction;
+
+/// The address that CounterV2 declares as the template that it upgrades
+fn counter_v1_address() -> TemplateAddress {
+    TemplateAddress::from_array([0xab; 32])
+}
+
+fn setup() -> TemplateTest {
+    let package = Package::builder()
+        .add_template_at_address("tests/templates/upgrades/counter_v1", counter_v1_address())
+        .add_template("tests/templates/upgrades/counter_v2")
+        .build();
+    TemplateTest::from_package(package)
+}
+
+fn create_counter_v1(test: &mut TemplateTest, value: u32) -> ComponentAddress {
+    let counter: ComponentAddress = test.call_function("CounterV1", "new", args![], vec![]);
+    for _ in 0..value {
+        test.call_method::<()>(counter, "increase", args![], vec![]);
+    }
+    counter
+}
+
+#[test]
+fn it_migrates_a_component_and_transforms_its_state() {
+    let mut test = setup();
+    let counter_v2_address = test.get_template_address("CounterV2");
+    let counter = create_counter_v1(&mut test, 2);
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .migrate_component(counter, counter_v2_address)
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![test.get_test_proof()],
+    );
+
+    let component = test.read_only_state_store().get_component(counter).unwrap();
+    assert_eq!(component.template_address, counter_v2_address);
+    assert_eq!(component.module_name, "CounterV2");
+
+    // The state was converted by on_migrate
+    let count: u64 = test.call_method(counter, "count", args![], vec![]);
+    assert_eq!(count, 2);
+    test.call_method::<()>(counter, "set_step", args![5u64], vec![]);
+    test.call_method::<()>(counter, "increment", args![], vec![]);
+    let count: u64 = test.call_method(counter, "count", args![], vec![]);
+    assert_eq!(count, 7);
+
+    // Methods of the previous template can no longer be called
+    for method in ["increase", "value"] {
+        let reason = test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(counter, method, args![])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        );
+        assert_reject_reason(reason, TransactionError::FunctionNotFound {
+            name: method.to_string(),
+        });
+    }
+}
+
+#[test]
+fn it_requires_the_owner_to_migrate_a_component() {
+    let mut test = setup();
+    let counter_v2_address = test.get_template_address("CounterV2");
+    let counter = create_counter_v1(&mut test, 1);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .migrate_component(counter, counter_v2_address)
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert_reject_reason(reason, RuntimeError::AccessDeniedOwnerRequired {
+        action: ActionIdent::MigrateComponent {
+            component_address: counter,
+            template_address: counter_v2_address,
+        },
+    });
+    let value: u32 = test.call_method(counter, "value", args![], vec![]);
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn it_rejects_a_template_that_does_not_declare_the_upgrade() {
+    let mut test = setup();
+    let counter_v2_address = test.get_template_address("CounterV2");
+    let counter: ComponentAddress = test.call_function("CounterV2", "new", args![1u64], vec![]);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .migrate_component(counter, counter_v1_address())
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![test.get_test_proof()],
+    );
+
+    assert_reject_reason(reason, TransactionError::IncompatibleTemplateUpgrade {
+        component_address: counter,
+        current_template_address: counter_v2_address,
+        template_address: counter_v1_address(),
+    });
+}
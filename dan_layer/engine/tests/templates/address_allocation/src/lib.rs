@@ -22,5 +22,10 @@ mod template {
         pub fn drop_allocation() {
             let _allocation = CallerContext::allocate_component_address();
         }
+
+        pub fn create_at_reserved(address: ComponentAddress) -> Component<Self> {
+            let allocation = CallerContext::allocate_reserved_component_address(address);
+            Component::new(Self {}).with_address_allocation(allocation).create()
+        }
     }
 }
@@ -44,6 +44,20 @@ mod faucet_template {
             .create()
         }
 
+        pub fn mint_burnable(confidential_proof: ConfidentialOutputStatement) -> Component<Self> {
+            let coins = ResourceBuilder::confidential()
+                .mintable(AccessRule::AllowAll)
+                .burnable(AccessRule::AllowAll)
+                .initial_supply(confidential_proof)
+                .build_bucket();
+
+            Component::new(Self {
+                vault: Vault::from_bucket(coins),
+            })
+            .with_access_rules(AccessRules::allow_all())
+            .create()
+        }
+
         pub fn mint_with_view_key(
             confidential_proof: ConfidentialOutputStatement,
             view_key: RistrettoPublicKeyBytes,
@@ -88,6 +102,10 @@ mod faucet_template {
             self.vault.withdraw_confidential(proof)
         }
 
+        pub fn burn_coins(&mut self, proof: ConfidentialWithdrawProof) -> Amount {
+            self.vault.burn_confidential(proof)
+        }
+
         pub fn total_supply(&self) -> Amount {
             ResourceManager::get(self.vault.resource_address()).total_supply()
         }
@@ -0,0 +1,35 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod large_state_template {
+    use super::*;
+
+    pub struct LargeState {
+        data: Vec<u8>,
+    }
+
+    impl LargeState {
+        pub fn new() -> Component<Self> {
+            Component::new(Self { data: Vec::new() })
+                .with_access_rules(AccessRules::new().default(AccessRule::AllowAll))
+                .create()
+        }
+
+        pub fn new_many(count: u32, size: u32) {
+            for _ in 0..count {
+                Component::new(Self {
+                    data: vec![0; size as usize],
+                })
+                .with_access_rules(AccessRules::new().default(AccessRule::AllowAll))
+                .create();
+            }
+        }
+
+        pub fn set_size(&mut self, size: u32) {
+            self.data = vec![0; size as usize];
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod nested_state_template {
+    use super::*;
+
+    pub struct NestedState {
+        name: String,
+        settings: Settings,
+        history: Vec<u64>,
+        owner: (String, u32),
+    }
+
+    pub struct Settings {
+        enabled: bool,
+        limits: Limits,
+    }
+
+    pub struct Limits(u32, u64);
+
+    impl NestedState {
+        pub fn new(name: String) -> Component<Self> {
+            Component::new(Self {
+                name,
+                settings: Settings {
+                    enabled: true,
+                    limits: Limits(10, 1000),
+                },
+                history: vec![],
+                owner: ("alice".to_string(), 1),
+            })
+            .with_access_rules(AccessRules::new().default(AccessRule::AllowAll))
+            .create()
+        }
+
+        pub fn set_limits(&mut self, min: u32, max: u64) {
+            self.settings.limits = Limits(min, max);
+            self.history.push(max);
+        }
+    }
+}
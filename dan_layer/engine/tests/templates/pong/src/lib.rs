@@ -0,0 +1,31 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+// Calls back and forth with another component. This template opts into reentrancy.
+#[template(reentrant)]
+mod pong_template {
+    use super::*;
+
+    pub struct Pong {}
+
+    impl Pong {
+        pub fn new() -> Component<Self> {
+            Component::new(Self {})
+                .with_access_rules(AccessRules::allow_all())
+                .create()
+        }
+
+        /// Calls `bounce` on the other component, passing this component as the next target, until `remaining` is 0.
+        /// Returns the number of calls made.
+        pub fn bounce(&self, other: ComponentAddress, remaining: u32) -> u32 {
+            if remaining == 0 {
+                return 0;
+            }
+            let this = ComponentManager::current().component_address();
+            let calls: u32 = ComponentManager::get(other).call("bounce", args![this, remaining - 1]);
+            calls + 1
+        }
+    }
+}
@@ -3,7 +3,8 @@
 
 use tari_template_lib::prelude::*;
 
-#[template]
+// Opts into reentrancy so that the tests exercise the substate locks that guard reentrant access
+#[template(reentrant)]
 mod dangling_template {
     use super::*;
 
@@ -0,0 +1,30 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+use tari_template_lib::prelude::*;
+
+#[template]
+mod template {
+    use super::*;
+
+    pub struct Royalty {
+        vault: Vault,
+    }
+
+    impl Royalty {
+        pub fn mint(royalty_recipient: ComponentAddress, basis_points: u16) -> Bucket {
+            ResourceBuilder::non_fungible()
+                .with_token_symbol("ART")
+                .with_royalty(royalty_recipient, basis_points)
+                .mint_many_with(1..=3, |n| (NonFungibleId::from_u32(n), (&(), &())))
+                .build_bucket()
+        }
+
+        pub fn store(nfts: Bucket) -> Component<Self> {
+            Component::new(Self {
+                vault: Vault::from_bucket(nfts),
+            })
+            .with_access_rules(AccessRules::allow_all())
+            .create()
+        }
+    }
+}
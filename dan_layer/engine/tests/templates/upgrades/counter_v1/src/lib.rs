@@ -0,0 +1,29 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod counter_v1_template {
+    use super::*;
+
+    pub struct CounterV1 {
+        value: u32,
+    }
+
+    impl CounterV1 {
+        pub fn new() -> Component<Self> {
+            Component::new(Self { value: 0 })
+                .with_access_rules(AccessRules::allow_all())
+                .create()
+        }
+
+        pub fn increase(&mut self) {
+            self.value += 1;
+        }
+
+        pub fn value(&self) -> u32 {
+            self.value
+        }
+    }
+}
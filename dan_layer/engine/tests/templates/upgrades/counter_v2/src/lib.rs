@@ -0,0 +1,47 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+// The tests register CounterV1 at this address
+#[template(upgrades_from = "abababababababababababababababababababababababababababababababab")]
+mod counter_v2_template {
+    use super::*;
+
+    pub struct CounterV2 {
+        count: u64,
+        step: u64,
+    }
+
+    /// The state of a CounterV1 component
+    pub struct PreviousState {
+        value: u32,
+    }
+
+    impl CounterV2 {
+        pub fn new(step: u64) -> Component<Self> {
+            Component::new(Self { count: 0, step })
+                .with_access_rules(AccessRules::allow_all())
+                .create()
+        }
+
+        pub fn on_migrate(previous: PreviousState) -> CounterV2 {
+            Self {
+                count: u64::from(previous.value),
+                step: 1,
+            }
+        }
+
+        pub fn set_step(&mut self, step: u64) {
+            self.step = step;
+        }
+
+        pub fn increment(&mut self) {
+            self.count += self.step;
+        }
+
+        pub fn count(&self) -> u64 {
+            self.count
+        }
+    }
+}
@@ -32,6 +32,7 @@ use tari_engine_types::{
     virtual_substate::{VirtualSubstate, VirtualSubstateId},
     TemplateAddress,
 };
+use tari_template_abi::{FieldDef, Type};
 use tari_template_builtin::{ACCOUNT_NFT_TEMPLATE_ADDRESS, ACCOUNT_TEMPLATE_ADDRESS};
 use tari_template_lib::{
     args,
@@ -254,6 +255,66 @@ fn test_tuples() {
     assert_eq!(value, new_value);
 }
 
+#[test]
+fn test_component_schema() {
+    let mut template_test = TemplateTest::new(vec!["tests/templates/nested_state"]);
+
+    let schema = template_test
+        .get_module("NestedState")
+        .template_def()
+        .component_schema()
+        .cloned()
+        .unwrap();
+    assert_eq!(schema.component.name, "NestedState");
+    let field_types = schema
+        .component
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.field_type.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(field_types, [
+        ("name", "String".to_string()),
+        ("settings", "Settings".to_string()),
+        ("history", "Vec<U64>".to_string()),
+        ("owner", "Tuple<String,U32>".to_string()),
+    ]);
+
+    // nested structs are included in the schema
+    let settings = schema.get_type("Settings").unwrap();
+    assert_eq!(settings.get_field("enabled").unwrap().field_type, Type::Bool);
+    assert_eq!(settings.get_field("limits").unwrap().field_type, Type::Other {
+        name: "Limits".to_string()
+    });
+    let limits = schema.get_type("Limits").unwrap();
+    assert_eq!(limits.fields, [
+        FieldDef {
+            name: "0".to_string(),
+            field_type: Type::U32
+        },
+        FieldDef {
+            name: "1".to_string(),
+            field_type: Type::U64
+        },
+    ]);
+
+    // the schema field names match the encoded component state
+    let component_address: ComponentAddress =
+        template_test.call_function("NestedState", "new", args!["test"], vec![]);
+    let component = template_test
+        .read_only_state_store()
+        .get_component(component_address)
+        .unwrap();
+    let state_fields = component
+        .state()
+        .as_map()
+        .unwrap()
+        .iter()
+        .map(|(k, _)| k.as_text().unwrap())
+        .collect::<Vec<_>>();
+    let schema_fields = schema.component.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(state_fields, schema_fields);
+}
+
 #[test]
 fn test_get_template_address() {
     let mut template_test = TemplateTest::new(vec!["tests/templates/component_manager"]);
@@ -307,8 +368,8 @@ fn test_errors_on_infinite_loop() {
             .build(),
         vec![],
     );
-    // Transaction failed: Execution failure: RuntimeError: unreachable\n    at tari_free (<module>[327]:0x2390c)
-    assert_reject_reason(reason, wasmer::RuntimeError::new("unreachable"))
+    assert!(matches!(reason, RejectReason::ExecutionBudgetExceeded(_)));
+    assert_reject_reason(reason, WasmExecutionError::ExecutionBudgetExceeded)
 }
 
 mod errors {
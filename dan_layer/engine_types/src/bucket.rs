@@ -31,7 +31,7 @@ use tari_template_lib::{
 
 use crate::{
     proof::{ContainerRef, LockedResource, Proof},
-    resource_container::{ResourceContainer, ResourceError},
+    resource_container::{ConfidentialBurn, ResourceContainer, ResourceError},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +100,14 @@ impl Bucket {
         self.resource_container.reveal_confidential(proof, view_key)
     }
 
+    pub fn burn_confidential(
+        &mut self,
+        proof: ConfidentialWithdrawProof,
+        view_key: Option<&PublicKey>,
+    ) -> Result<ConfidentialBurn, ResourceError> {
+        self.resource_container.burn_confidential(proof, view_key)
+    }
+
     pub fn lock_all(&mut self) -> Result<LockedResource, ResourceError> {
         let locked_resource = self.resource_container.lock_all()?;
         Ok(LockedResource::new(
@@ -282,6 +282,8 @@ pub enum RejectReason {
     ShardRejected(String),
     FeeTransactionFailed,
     FeesNotPaid(String),
+    EpochExpired(String),
+    ExecutionBudgetExceeded(String),
 }
 
 impl std::fmt::Display for RejectReason {
@@ -294,6 +296,8 @@ impl std::fmt::Display for RejectReason {
             RejectReason::ShardRejected(msg) => write!(f, "Shard was rejected: {}", msg),
             RejectReason::FeeTransactionFailed => write!(f, "Fee transaction failed"),
             RejectReason::FeesNotPaid(msg) => write!(f, "Fee not paid: {}", msg),
+            RejectReason::EpochExpired(msg) => write!(f, "Epoch expired: {}", msg),
+            RejectReason::ExecutionBudgetExceeded(msg) => write!(f, "Execution budget exceeded: {}", msg),
         }
     }
 }
@@ -10,6 +10,7 @@ use crate::hashing::{hasher32, EngineHashDomainLabel};
 #[derive(Debug, Clone)]
 pub struct EntityIdProvider {
     transaction_hash: Hash,
+    id_seed: Hash,
     max_ids: u32,
     current_id: Arc<AtomicU32>,
 }
@@ -26,11 +27,18 @@ impl EntityIdProvider {
     pub fn new(transaction_hash: Hash, max_ids: u32) -> Self {
         Self {
             transaction_hash,
+            id_seed: transaction_hash,
             max_ids,
             current_id: Arc::new(AtomicU32::new(0)),
         }
     }
 
+    /// Derives entity ids from the given seed instead of the transaction hash
+    pub fn with_id_seed(mut self, id_seed: Hash) -> Self {
+        self.id_seed = id_seed;
+        self
+    }
+
     fn next(&self) -> Result<u32, EntityIdProviderError> {
         let id = self.current_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         if id >= self.max_ids {
@@ -43,9 +51,10 @@ impl EntityIdProvider {
         self.transaction_hash
     }
 
-    /// Generates a new entity id trailing_24_bytes(H(tx_hash || n))
+    /// Generates a new entity id trailing_24_bytes(H(tx_hash || n)). If an id seed is set, it is used in place of the
+    /// transaction hash.
     pub fn next_entity_id(&self) -> Result<EntityId, EntityIdProviderError> {
-        let id = generate_entity_id(&self.transaction_hash, self.next()?);
+        let id = generate_entity_id(&self.id_seed, self.next()?);
         Ok(id)
     }
 }
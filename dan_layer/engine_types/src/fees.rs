@@ -19,6 +19,9 @@ pub struct FeeReceipt {
     pub total_fees_paid: Amount,
     /// Breakdown of fee costs
     pub cost_breakdown: Vec<FeeBreakdown>,
+    /// The total encoded size in bytes of all substates written by the transaction
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_storage_bytes: u64,
 }
 
 impl FeeReceipt {
@@ -26,6 +29,7 @@ impl FeeReceipt {
         FeeCostBreakdown {
             total_fees_charged: self.total_fees_charged(),
             breakdown: self.cost_breakdown.clone(),
+            total_storage_bytes: self.total_storage_bytes,
         }
     }
 
@@ -63,6 +67,15 @@ impl FeeReceipt {
             .unwrap_or_default()
     }
 
+    /// The priority fee charged, which is included in the total fees charged
+    pub fn priority_fee_charged(&self) -> u64 {
+        self.cost_breakdown
+            .iter()
+            .filter(|breakdown| matches!(breakdown.source, FeeSource::PriorityFee))
+            .map(|breakdown| breakdown.amount)
+            .sum()
+    }
+
     /// Returns true if the total fees charged is equal to the total fees paid, otherwise false
     pub fn is_paid_in_full(&self) -> bool {
         self.unpaid_debt().is_zero()
@@ -77,6 +90,8 @@ pub enum FeeSource {
     Storage,
     Events,
     Logs,
+    /// The additional fee the transaction offered to be prioritised
+    PriorityFee,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +107,8 @@ pub struct FeeBreakdown {
 pub struct FeeCostBreakdown {
     pub total_fees_charged: Amount,
     pub breakdown: Vec<FeeBreakdown>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_storage_bytes: u64,
 }
 
 #[derive(Debug)]
@@ -8,7 +8,7 @@ use tari_common_types::types::PublicKey;
 use tari_crypto::tari_utilities::hex::Hex;
 use tari_template_lib::{
     args::{Arg, LogLevel},
-    models::{Amount, ComponentAddress, TemplateAddress},
+    models::{Amount, ComponentAddress, ResourceAddress, TemplateAddress, VaultId},
 };
 #[cfg(feature = "ts")]
 use ts_rs::TS;
@@ -61,6 +61,38 @@ pub enum Instruction {
         validator_public_key: PublicKey,
     },
     DropAllProofsInWorkspace,
+    /// Declares the bucket on the workspace as the payment for transfers of the given royalty-bearing resource. Any
+    /// royalties due are taken from this bucket.
+    DeclareRoyaltyPayment {
+        #[serde(with = "serde_with::string")]
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        resource_address: ResourceAddress,
+        workspace_bucket: String,
+    },
+    /// Migrates a component to a new version of its template. The new template must declare that it upgrades the
+    /// component's current template and the component owner must authorize the migration.
+    MigrateComponent {
+        #[serde(with = "serde_with::string")]
+        component_address: ComponentAddress,
+        #[serde(with = "serde_with::hex")]
+        #[cfg_attr(feature = "ts", ts(type = "Uint8Array"))]
+        template_address: TemplateAddress,
+    },
+    /// Aborts the transaction unless the bucket on the workspace contains at least `min_amount` of the resource
+    AssertBucketContains {
+        workspace_bucket: String,
+        #[serde(with = "serde_with::string")]
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        resource_address: ResourceAddress,
+        min_amount: Amount,
+    },
+    /// Aborts the transaction unless the balance of the vault is at least `min_amount`
+    AssertVaultBalanceAtLeast {
+        #[serde(with = "serde_with::string")]
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        vault_id: VaultId,
+        min_amount: Amount,
+    },
     #[cfg(feature = "debugging")]
     CreateFreeTestCoins {
         revealed_amount: Amount,
@@ -132,6 +164,44 @@ impl Display for Instruction {
             Self::DropAllProofsInWorkspace => {
                 write!(f, "DropAllProofsInWorkspace")
             },
+            Self::DeclareRoyaltyPayment {
+                resource_address,
+                workspace_bucket,
+            } => {
+                write!(
+                    f,
+                    "DeclareRoyaltyPayment {{ resource_address: {}, workspace_bucket: {} }}",
+                    resource_address, workspace_bucket
+                )
+            },
+            Self::MigrateComponent {
+                component_address,
+                template_address,
+            } => {
+                write!(
+                    f,
+                    "MigrateComponent {{ component_address: {}, template_address: {} }}",
+                    component_address, template_address
+                )
+            },
+            Self::AssertBucketContains {
+                workspace_bucket,
+                resource_address,
+                min_amount,
+            } => {
+                write!(
+                    f,
+                    "AssertBucketContains {{ workspace_bucket: {}, resource_address: {}, min_amount: {} }}",
+                    workspace_bucket, resource_address, min_amount
+                )
+            },
+            Self::AssertVaultBalanceAtLeast { vault_id, min_amount } => {
+                write!(
+                    f,
+                    "AssertVaultBalanceAtLeast {{ vault_id: {}, min_amount: {} }}",
+                    vault_id, min_amount
+                )
+            },
         }
     }
 }
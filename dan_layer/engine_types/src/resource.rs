@@ -26,7 +26,7 @@ use tari_template_lib::{
     auth::{AuthHook, OwnerRule, Ownership, ResourceAccessRules},
     crypto::RistrettoPublicKeyBytes,
     models::{Amount, Metadata},
-    resource::{ResourceType, TOKEN_SYMBOL},
+    resource::{ResourceType, RoyaltyConfig, TOKEN_SYMBOL},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +46,7 @@ pub struct Resource {
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     view_key: Option<PublicKey>,
     auth_hook: Option<AuthHook>,
+    royalty: Option<RoyaltyConfig>,
 }
 
 impl Resource {
@@ -67,9 +68,15 @@ impl Resource {
             total_supply: 0.into(),
             view_key,
             auth_hook,
+            royalty: None,
         }
     }
 
+    pub fn with_royalty(mut self, royalty: Option<RoyaltyConfig>) -> Self {
+        self.royalty = royalty;
+        self
+    }
+
     pub fn resource_type(&self) -> ResourceType {
         self.resource_type
     }
@@ -97,6 +104,10 @@ impl Resource {
         self.auth_hook.as_ref()
     }
 
+    pub fn royalty(&self) -> Option<&RoyaltyConfig> {
+        self.royalty.as_ref()
+    }
+
     pub fn access_rules(&self) -> &ResourceAccessRules {
         &self.access_rules
     }
@@ -132,6 +143,15 @@ impl Resource {
         self.total_supply -= amount;
     }
 
+    /// Decreases the total supply by the amount or to zero, whichever is less. The hidden value of confidential mints
+    /// is not included in the total supply, so burning confidential funds may destroy more than the total supply.
+    pub fn decrease_total_supply_saturating(&mut self, amount: Amount) {
+        let amount = amount.min(self.total_supply);
+        if amount.is_positive() {
+            self.decrease_total_supply(amount);
+        }
+    }
+
     pub fn total_supply(&self) -> Amount {
         self.total_supply
     }
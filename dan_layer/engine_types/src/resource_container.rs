@@ -510,6 +510,44 @@ impl ResourceContainer {
         self.withdraw_confidential(proof, view_key)
     }
 
+    /// Destroys the revealed output amount of a withdraw proof that has no confidential output. The input commitments
+    /// of the proof are removed and any change is returned to this container.
+    pub fn burn_confidential(
+        &mut self,
+        proof: ConfidentialWithdrawProof,
+        view_key: Option<&PublicKey>,
+    ) -> Result<ConfidentialBurn, ResourceError> {
+        if proof.output_proof.output_statement.is_some() {
+            return Err(ResourceError::InvalidConfidentialProof {
+                details: "Confidential burn proof must not contain a confidential output".to_string(),
+            });
+        }
+        if proof.output_proof.output_revealed_amount.is_zero() {
+            return Err(ResourceError::InvalidConfidentialProof {
+                details: "Confidential burn proof must burn a non-zero revealed amount".to_string(),
+            });
+        }
+
+        let destroyed_commitments = proof
+            .inputs
+            .iter()
+            .map(|input| {
+                Commitment::from_canonical_bytes(input.as_bytes()).map_err(|_| {
+                    ResourceError::InvalidConfidentialProof {
+                        details: "Invalid input commitment".to_string(),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The withdrawn container only holds the revealed output amount, which is dropped
+        let burnt = self.withdraw_confidential(proof, view_key)?;
+        Ok(ConfidentialBurn {
+            amount: burnt.amount(),
+            destroyed_commitments,
+        })
+    }
+
     /// Returns all confidential commitments. If the resource is not confidential, None is returned.
     pub fn get_confidential_commitments(&self) -> Option<&BTreeMap<Commitment, ConfidentialOutput>> {
         match self {
@@ -787,6 +825,15 @@ impl ResourceContainer {
     }
 }
 
+/// The result of burning confidential funds
+#[derive(Debug, Clone)]
+pub struct ConfidentialBurn {
+    /// The revealed amount that was burnt
+    pub amount: Amount,
+    /// The input commitments that were destroyed by the burn
+    pub destroyed_commitments: Vec<Commitment>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ResourceError {
     #[error("Attempted to {operate} a {expected} resource, but the resource type is {given}")]
@@ -230,6 +230,17 @@ impl SubstateId {
     pub fn is_transaction_receipt(&self) -> bool {
         matches!(self, Self::TransactionReceipt(_))
     }
+
+    /// Returns true if the substate is in the reserved address range. Only system transactions may create these
+    /// substates.
+    pub fn is_reserved(&self) -> bool {
+        match self {
+            Self::Component(addr) => addr.as_object_key().is_reserved(),
+            Self::Resource(addr) => addr.as_object_key().is_reserved(),
+            Self::Vault(id) => id.as_object_key().is_reserved(),
+            _ => false,
+        }
+    }
 }
 
 impl From<ComponentAddress> for SubstateId {
@@ -12,7 +12,7 @@ use tari_template_lib::{models::BinaryTag, Hash, HashParseError};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
-use crate::{events::Event, fees::FeeReceipt, logs::LogEntry};
+use crate::{events::Event, fees::FeeReceipt, logs::LogEntry, substate::SubstateId};
 
 const TAG: u64 = BinaryTag::TransactionReceipt.as_u64();
 
@@ -55,4 +55,14 @@ pub struct TransactionReceipt {
     pub events: Vec<Event>,
     pub logs: Vec<LogEntry>,
     pub fee_receipt: FeeReceipt,
+    /// The encoded size in bytes of each substate written by the transaction
+    pub substate_sizes: Vec<SubstateSize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SubstateSize {
+    pub substate_id: SubstateId,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub size: u64,
 }
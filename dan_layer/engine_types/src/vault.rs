@@ -36,7 +36,7 @@ use crate::{
     bucket::Bucket,
     confidential::{ConfidentialOutput, ElgamalVerifiableBalance, ValueLookupTable},
     proof::{ContainerRef, LockedResource, Proof},
-    resource_container::{ResourceContainer, ResourceError},
+    resource_container::{ConfidentialBurn, ResourceContainer, ResourceError},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +150,14 @@ impl Vault {
         self.resource_container.reveal_confidential(proof, view_key)
     }
 
+    pub fn burn_confidential(
+        &mut self,
+        proof: ConfidentialWithdrawProof,
+        view_key: Option<&PublicKey>,
+    ) -> Result<ConfidentialBurn, ResourceError> {
+        self.resource_container.burn_confidential(proof, view_key)
+    }
+
     pub fn resource_container_mut(&mut self) -> &mut ResourceContainer {
         &mut self.resource_container
     }
@@ -32,6 +32,8 @@ use tari_common_types::types::{FixedHash, PublicKey};
 use tari_core::{blocks::BlockHeader, transactions::transaction_components::ValidatorNodeRegistration};
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    committee_membership::{build_validator_node_bmt, CommitteeMembershipProof},
+    hashing::ValidatorNodeBalancedMerkleTree,
     optional::Optional,
     shard::Shard,
     DerivableFromPublicKey,
@@ -61,6 +63,7 @@ pub struct BaseLayerEpochManager<TGlobalStore, TBaseNodeClient> {
     current_shard_key: Option<SubstateAddress>,
     base_layer_consensus_constants: Option<BaseLayerConsensusConstants>,
     is_initial_base_layer_sync_complete: bool,
+    validator_node_bmt_roots: HashMap<Epoch, FixedHash>,
 }
 
 impl<TAddr: NodeAddressable + DerivableFromPublicKey>
@@ -85,6 +88,7 @@ impl<TAddr: NodeAddressable + DerivableFromPublicKey>
             current_shard_key: None,
             base_layer_consensus_constants: None,
             is_initial_base_layer_sync_complete: false,
+            validator_node_bmt_roots: HashMap::new(),
         }
     }
 
@@ -155,6 +159,15 @@ impl<TAddr: NodeAddressable + DerivableFromPublicKey>
             )?;
         }
         tx.commit()?;
+
+        if !vns.is_empty() {
+            // Build the BMT for the new epoch so that membership proofs can be generated and verified
+            let root = self.get_validator_node_bmt_root(epoch)?;
+            debug!(target: LOG_TARGET, "Validator node BMT root for epoch {} is {}", epoch, root);
+        }
+        self.validator_node_bmt_roots
+            .retain(|cached_epoch, _| cached_epoch.as_u64() >= epoch.as_u64().saturating_sub(10));
+
         if let Some(vn) = vns.iter().find(|vn| vn.public_key == self.node_public_key) {
             self.publish_event(EpochManagerEvent::ThisValidatorIsRegistered {
                 epoch,
@@ -504,6 +517,84 @@ impl<TAddr: NodeAddressable + DerivableFromPublicKey>
         Ok(vns)
     }
 
+    /// Returns the root of the validator node BMT for the epoch. Roots are cached so that verifying membership proofs
+    /// does not require a database lookup.
+    pub fn get_validator_node_bmt_root(&mut self, epoch: Epoch) -> Result<FixedHash, EpochManagerError> {
+        if let Some(root) = self.validator_node_bmt_roots.get(&epoch) {
+            return Ok(*root);
+        }
+
+        let bmt = self.get_validator_node_bmt(epoch)?;
+        let root = FixedHash::try_from(bmt.get_merkle_root())
+            .map_err(|_| EpochManagerError::InvalidValidatorNodeBmtRoot { epoch })?;
+        self.validator_node_bmt_roots.insert(epoch, root);
+        Ok(root)
+    }
+
+    pub fn get_committee_membership_proof(
+        &self,
+        epoch: Epoch,
+        public_key: PublicKey,
+    ) -> Result<CommitteeMembershipProof, EpochManagerError> {
+        let vn = self
+            .get_validator_node_by_public_key(epoch, &public_key)?
+            .ok_or_else(|| EpochManagerError::ValidatorNodeNotRegistered {
+                address: public_key.to_string(),
+                epoch,
+            })?;
+        let shard = vn.shard_key.to_shard(self.get_num_committees(epoch)?);
+        let bmt = self.get_validator_node_bmt(epoch)?;
+        CommitteeMembershipProof::generate(&bmt, epoch, shard, public_key).ok_or_else(|| {
+            EpochManagerError::ValidatorNodeNotRegistered {
+                address: vn.public_key.to_string(),
+                epoch,
+            }
+        })
+    }
+
+    /// Returns the validator node BMT for the epoch, building and storing it if it has not been built yet
+    fn get_validator_node_bmt(&self, epoch: Epoch) -> Result<ValidatorNodeBalancedMerkleTree, EpochManagerError> {
+        let mut tx = self.global_db.create_transaction()?;
+        if let Some(bmt) = self.global_db.bmt(&mut tx).get_bmt(epoch)? {
+            return Ok(bmt);
+        }
+        drop(tx);
+
+        self.build_validator_node_bmt(epoch)?;
+        let mut tx = self.global_db.create_transaction()?;
+        self.global_db
+            .bmt(&mut tx)
+            .get_bmt(epoch)?
+            .ok_or(EpochManagerError::NoValidatorNodesForEpoch { epoch })
+    }
+
+    fn build_validator_node_bmt(&self, epoch: Epoch) -> Result<(), EpochManagerError> {
+        // The validator set for future epochs can still change
+        if epoch > self.current_epoch {
+            return Err(EpochManagerError::InvalidEpoch { epoch });
+        }
+
+        let mut tx = self.global_db.create_transaction()?;
+        let mut vns = self
+            .global_db
+            .validator_nodes(&mut tx)
+            .get_all_within_epoch(epoch, self.config.validator_node_sidechain_id.as_ref())?;
+        if vns.is_empty() {
+            return Err(EpochManagerError::NoValidatorNodesForEpoch { epoch });
+        }
+        // Every node must build the tree with the same leaf order
+        vns.sort_by_key(|vn| vn.shard_key);
+
+        let num_committees = calculate_num_committees(vns.len() as u64, self.config.committee_size);
+        let bmt = build_validator_node_bmt(
+            vns.iter()
+                .map(|vn| (&vn.public_key, vn.shard_key.to_shard(num_committees))),
+        );
+        self.global_db.bmt(&mut tx).insert_bmt(epoch.as_u64(), bmt)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub async fn on_scanning_complete(&mut self) -> Result<(), EpochManagerError> {
         self.refresh_base_layer_consensus_constants().await?;
 
@@ -195,6 +195,18 @@ impl<TAddr: NodeAddressable + DerivableFromPublicKey + 'static>
             EpochManagerRequest::GetValidatorNodesPerEpoch { epoch, reply } => {
                 handle(reply, self.inner.get_validator_nodes_per_epoch(epoch), context)
             },
+            EpochManagerRequest::GetValidatorNodeBmtRoot { epoch, reply } => {
+                handle(reply, self.inner.get_validator_node_bmt_root(epoch), context)
+            },
+            EpochManagerRequest::GetCommitteeMembershipProof {
+                epoch,
+                public_key,
+                reply,
+            } => handle(
+                reply,
+                self.inner.get_committee_membership_proof(epoch, public_key),
+                context,
+            ),
             EpochManagerRequest::AddValidatorNodeRegistration {
                 block_height,
                 registration,
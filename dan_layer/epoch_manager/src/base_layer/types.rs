@@ -8,6 +8,7 @@ use tari_common_types::types::{FixedHash, PublicKey};
 use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::ValidatorNodeRegistration};
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    committee_membership::CommitteeMembershipProof,
     shard::Shard,
     Epoch,
     SubstateAddress,
@@ -93,6 +94,15 @@ pub enum EpochManagerRequest<TAddr> {
         epoch: Epoch,
         reply: Reply<Vec<ValidatorNode<TAddr>>>,
     },
+    GetValidatorNodeBmtRoot {
+        epoch: Epoch,
+        reply: Reply<FixedHash>,
+    },
+    GetCommitteeMembershipProof {
+        epoch: Epoch,
+        public_key: PublicKey,
+        reply: Reply<CommitteeMembershipProof>,
+    },
     Subscribe {
         reply: Reply<broadcast::Receiver<EpochManagerEvent>>,
     },
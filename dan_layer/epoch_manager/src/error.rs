@@ -39,6 +39,10 @@ pub enum EpochManagerError {
     IntegerOverflow { func: &'static str },
     #[error("Invalid epoch: {epoch}")]
     InvalidEpoch { epoch: Epoch },
+    #[error("No validator nodes found for epoch {epoch}")]
+    NoValidatorNodesForEpoch { epoch: Epoch },
+    #[error("Validator node BMT root for epoch {epoch} is invalid")]
+    InvalidValidatorNodeBmtRoot { epoch: Epoch },
     #[error("Validator node registration sidechain id mismatch. Actual: {actual:?}, Expected: {expected:?}")]
     ValidatorNodeRegistrationSidechainIdMismatch {
         actual: Option<String>,
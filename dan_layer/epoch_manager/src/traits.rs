@@ -26,6 +26,8 @@ use async_trait::async_trait;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    committee_membership::CommitteeMembershipProof,
+    hashing::epoch_seed_hasher,
     shard::Shard,
     Epoch,
     NodeAddressable,
@@ -44,6 +46,24 @@ pub trait EpochManagerReader: Send + Sync {
 
     async fn get_all_validator_nodes(&self, epoch: Epoch) -> Result<Vec<ValidatorNode<Self::Addr>>, EpochManagerError>;
 
+    /// Returns the root of the validator node BMT for the epoch. Committee membership proofs for the epoch are verified
+    /// against this root.
+    async fn get_validator_node_bmt_root(&self, epoch: Epoch) -> Result<FixedHash, EpochManagerError>;
+
+    /// Returns a seed that every validator node derives identically for the epoch. The seed commits to the validator
+    /// node BMT root of the epoch, so it is only known once the epoch's registrations are final.
+    async fn get_epoch_seed(&self, epoch: Epoch) -> Result<FixedHash, EpochManagerError> {
+        let bmt_root = self.get_validator_node_bmt_root(epoch).await?;
+        Ok(epoch_seed_hasher().chain(&epoch).chain(&bmt_root).result())
+    }
+
+    /// Returns a proof that the validator with the given public key is a member of its committee in the epoch
+    async fn get_committee_membership_proof(
+        &self,
+        epoch: Epoch,
+        public_key: &PublicKey,
+    ) -> Result<CommitteeMembershipProof, EpochManagerError>;
+
     async fn get_committees(&self, epoch: Epoch) -> Result<HashMap<Shard, Committee<Self::Addr>>, EpochManagerError>;
     async fn get_committee_info_by_validator_address(
         &self,
@@ -84,6 +104,13 @@ pub trait EpochManagerReader: Send + Sync {
     }
 
     async fn get_our_validator_node(&self, epoch: Epoch) -> Result<ValidatorNode<Self::Addr>, EpochManagerError>;
+    async fn get_our_committee_membership_proof(
+        &self,
+        epoch: Epoch,
+    ) -> Result<CommitteeMembershipProof, EpochManagerError> {
+        let validator = self.get_our_validator_node(epoch).await?;
+        self.get_committee_membership_proof(epoch, &validator.public_key).await
+    }
     async fn get_local_committee_info(&self, epoch: Epoch) -> Result<CommitteeInfo, EpochManagerError>;
     async fn get_committee_info_for_substate(
         &self,
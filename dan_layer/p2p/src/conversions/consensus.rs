@@ -26,10 +26,13 @@ use anyhow::anyhow;
 use tari_bor::{decode_exact, encode};
 use tari_common_types::types::PublicKey;
 use tari_consensus::messages::{
+    ForeignProposalAckMessage,
+    ForeignProposalMessage,
     FullBlock,
     HotstuffMessage,
     NewViewMessage,
     ProposalMessage,
+    ProposerEquivocationMessage,
     RequestMissingTransactionsMessage,
     RequestedTransactionMessage,
     SyncRequestMessage,
@@ -37,11 +40,18 @@ use tari_consensus::messages::{
     VoteMessage,
 };
 use tari_crypto::tari_utilities::ByteArray;
-use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight, ValidatorMetadata};
+use tari_dan_common_types::{
+    committee_membership::CommitteeMembershipProof,
+    shard::Shard,
+    Epoch,
+    NodeHeight,
+    ValidatorMetadata,
+};
 use tari_dan_storage::consensus_models::{
     BlockId,
     Command,
     Decision,
+    EpochCheckpoint,
     EpochEvent,
     Evidence,
     ForeignProposal,
@@ -69,6 +79,9 @@ impl From<&HotstuffMessage> for proto::consensus::HotStuffMessage {
             HotstuffMessage::ForeignProposal(msg) => {
                 proto::consensus::hot_stuff_message::Message::ForeignProposal(msg.into())
             },
+            HotstuffMessage::ForeignProposalAck(msg) => {
+                proto::consensus::hot_stuff_message::Message::ForeignProposalAck(msg.into())
+            },
             HotstuffMessage::Vote(msg) => proto::consensus::hot_stuff_message::Message::Vote(msg.into()),
             HotstuffMessage::RequestMissingTransactions(msg) => {
                 proto::consensus::hot_stuff_message::Message::RequestMissingTransactions(msg.into())
@@ -80,6 +93,9 @@ impl From<&HotstuffMessage> for proto::consensus::HotStuffMessage {
             HotstuffMessage::SyncResponse(msg) => {
                 proto::consensus::hot_stuff_message::Message::SyncResponse(msg.into())
             },
+            HotstuffMessage::ProposerEquivocation(msg) => {
+                proto::consensus::hot_stuff_message::Message::ProposerEquivocation(msg.into())
+            },
         };
         Self { message: Some(message) }
     }
@@ -96,6 +112,9 @@ impl TryFrom<proto::consensus::HotStuffMessage> for HotstuffMessage {
             proto::consensus::hot_stuff_message::Message::ForeignProposal(msg) => {
                 HotstuffMessage::ForeignProposal(msg.try_into()?)
             },
+            proto::consensus::hot_stuff_message::Message::ForeignProposalAck(msg) => {
+                HotstuffMessage::ForeignProposalAck(msg.try_into()?)
+            },
             proto::consensus::hot_stuff_message::Message::Vote(msg) => HotstuffMessage::Vote(msg.try_into()?),
             proto::consensus::hot_stuff_message::Message::RequestMissingTransactions(msg) => {
                 HotstuffMessage::RequestMissingTransactions(msg.try_into()?)
@@ -109,6 +128,9 @@ impl TryFrom<proto::consensus::HotStuffMessage> for HotstuffMessage {
             proto::consensus::hot_stuff_message::Message::SyncResponse(msg) => {
                 HotstuffMessage::SyncResponse(msg.try_into()?)
             },
+            proto::consensus::hot_stuff_message::Message::ProposerEquivocation(msg) => {
+                HotstuffMessage::ProposerEquivocation(msg.try_into()?)
+            },
         })
     }
 }
@@ -122,6 +144,7 @@ impl From<&NewViewMessage> for proto::consensus::NewViewMessage {
             new_height: value.new_height.0,
             epoch: value.epoch.as_u64(),
             last_vote: value.last_vote.as_ref().map(|a| a.into()),
+            last_voted_height: value.last_voted_height.as_u64(),
         }
     }
 }
@@ -134,6 +157,7 @@ impl TryFrom<proto::consensus::NewViewMessage> for NewViewMessage {
             high_qc: value.high_qc.ok_or_else(|| anyhow!("High QC is missing"))?.try_into()?,
             new_height: value.new_height.into(),
             epoch: Epoch(value.epoch),
+            last_voted_height: NodeHeight(value.last_voted_height),
             last_vote: value
                 .last_vote
                 .map(|a: proto::consensus::VoteMessage| a.try_into())
@@ -162,6 +186,77 @@ impl TryFrom<proto::consensus::ProposalMessage> for ProposalMessage {
     }
 }
 
+//---------------------------------- ProposerEquivocationMessage --------------------------------------------//
+
+impl From<&ProposerEquivocationMessage> for proto::consensus::ProposerEquivocationMessage {
+    fn from(value: &ProposerEquivocationMessage) -> Self {
+        Self {
+            first_block: Some((&value.first_block).into()),
+            second_block: Some((&value.second_block).into()),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::ProposerEquivocationMessage> for ProposerEquivocationMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::ProposerEquivocationMessage) -> Result<Self, Self::Error> {
+        Ok(ProposerEquivocationMessage {
+            first_block: value
+                .first_block
+                .ok_or_else(|| anyhow!("First block is missing"))?
+                .try_into()?,
+            second_block: value
+                .second_block
+                .ok_or_else(|| anyhow!("Second block is missing"))?
+                .try_into()?,
+        })
+    }
+}
+
+//---------------------------------- ForeignProposalMessage --------------------------------------------//
+
+impl From<&ForeignProposalMessage> for proto::consensus::ForeignProposalMessage {
+    fn from(value: &ForeignProposalMessage) -> Self {
+        Self {
+            block: Some((&value.block).into()),
+            membership_proof: Some((&value.membership_proof).into()),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::ForeignProposalMessage> for ForeignProposalMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::ForeignProposalMessage) -> Result<Self, Self::Error> {
+        Ok(ForeignProposalMessage {
+            block: value.block.ok_or_else(|| anyhow!("Block is missing"))?.try_into()?,
+            membership_proof: value
+                .membership_proof
+                .ok_or_else(|| anyhow!("Membership proof is missing"))?
+                .try_into()?,
+        })
+    }
+}
+
+//---------------------------------- CommitteeMembershipProof --------------------------------------------//
+
+impl From<&CommitteeMembershipProof> for proto::consensus::CommitteeMembershipProof {
+    fn from(value: &CommitteeMembershipProof) -> Self {
+        Self {
+            encoded_proof: encode(value).unwrap(),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::CommitteeMembershipProof> for CommitteeMembershipProof {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::CommitteeMembershipProof) -> Result<Self, Self::Error> {
+        Ok(decode_exact(&value.encoded_proof)?)
+    }
+}
+
 // -------------------------------- VoteMessage -------------------------------- //
 
 impl From<&VoteMessage> for proto::consensus::VoteMessage {
@@ -220,6 +315,28 @@ impl TryFrom<proto::consensus::RequestMissingTransactionsMessage> for RequestMis
         })
     }
 }
+//---------------------------------- ForeignProposalAck --------------------------------------------//
+
+impl From<&ForeignProposalAckMessage> for proto::consensus::ForeignProposalAck {
+    fn from(msg: &ForeignProposalAckMessage) -> Self {
+        Self {
+            epoch: msg.epoch.as_u64(),
+            block_id: msg.block_id.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::ForeignProposalAck> for ForeignProposalAckMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::ForeignProposalAck) -> Result<Self, Self::Error> {
+        Ok(ForeignProposalAckMessage {
+            epoch: Epoch(value.epoch),
+            block_id: BlockId::try_from(value.block_id)?,
+        })
+    }
+}
+
 //---------------------------------- RequestedTransactionMessage --------------------------------------------//
 
 impl From<&RequestedTransactionMessage> for proto::consensus::RequestedTransactionMessage {
@@ -322,6 +439,9 @@ impl From<&Command> for proto::consensus::Command {
             Command::EpochEvent(event) => {
                 proto::consensus::command::Command::EpochEvent(proto::consensus::EpochEvent::from(event).into())
             },
+            Command::EpochCheckpoint(checkpoint) => {
+                proto::consensus::command::Command::EpochCheckpoint(checkpoint.into())
+            },
         };
 
         Self { command: Some(command) }
@@ -346,10 +466,37 @@ impl TryFrom<proto::consensus::Command> for Command {
                     .map_err(|_| anyhow!("Invalid epoch event value {}", event))?
                     .try_into()?,
             ),
+            proto::consensus::command::Command::EpochCheckpoint(checkpoint) => {
+                Command::EpochCheckpoint(checkpoint.try_into()?)
+            },
         })
     }
 }
 
+//---------------------------------- EpochCheckpoint --------------------------------------------//
+
+impl From<&EpochCheckpoint> for proto::consensus::EpochCheckpoint {
+    fn from(value: &EpochCheckpoint) -> Self {
+        Self {
+            epoch: value.epoch().as_u64(),
+            state_merkle_root: value.state_merkle_root().as_bytes().to_vec(),
+            validator_set_hash: value.validator_set_hash().as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::EpochCheckpoint> for EpochCheckpoint {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::EpochCheckpoint) -> Result<Self, Self::Error> {
+        Ok(EpochCheckpoint::new(
+            Epoch(value.epoch),
+            value.state_merkle_root.try_into()?,
+            value.validator_set_hash.try_into()?,
+        ))
+    }
+}
+
 //---------------------------------- TransactionAtom --------------------------------------------//
 
 impl From<&TransactionAtom> for proto::consensus::TransactionAtom {
@@ -360,6 +507,7 @@ impl From<&TransactionAtom> for proto::consensus::TransactionAtom {
             evidence: Some((&value.evidence).into()),
             fee: value.transaction_fee,
             leader_fee: value.leader_fee.as_ref().map(|a| a.into()),
+            priority_fee: value.priority_fee,
         }
     }
 }
@@ -379,6 +527,7 @@ impl TryFrom<proto::consensus::TransactionAtom> for TransactionAtom {
                 .try_into()?,
             transaction_fee: value.fee,
             leader_fee: value.leader_fee.map(TryInto::try_into).transpose()?,
+            priority_fee: value.priority_fee,
         })
     }
 }
@@ -413,6 +562,8 @@ impl From<ForeignProposalState> for proto::consensus::ForeignProposalState {
             ForeignProposalState::New => proto::consensus::ForeignProposalState::New,
             ForeignProposalState::Proposed => proto::consensus::ForeignProposalState::Mined,
             ForeignProposalState::Deleted => proto::consensus::ForeignProposalState::Deleted,
+            ForeignProposalState::Invalid => proto::consensus::ForeignProposalState::Invalid,
+            ForeignProposalState::Expired => proto::consensus::ForeignProposalState::Expired,
         }
     }
 }
@@ -425,6 +576,8 @@ impl TryFrom<proto::consensus::ForeignProposalState> for ForeignProposalState {
             proto::consensus::ForeignProposalState::New => Ok(ForeignProposalState::New),
             proto::consensus::ForeignProposalState::Mined => Ok(ForeignProposalState::Proposed),
             proto::consensus::ForeignProposalState::Deleted => Ok(ForeignProposalState::Deleted),
+            proto::consensus::ForeignProposalState::Invalid => Ok(ForeignProposalState::Invalid),
+            proto::consensus::ForeignProposalState::Expired => Ok(ForeignProposalState::Expired),
             proto::consensus::ForeignProposalState::UnknownState => Err(anyhow!("Foreign proposal state not provided")),
         }
     }
@@ -550,6 +703,7 @@ impl From<&QuorumCertificate> for proto::consensus::QuorumCertificate {
             signatures: source.signatures().iter().map(Into::into).collect(),
             leaf_hashes: source.leaf_hashes().iter().map(|h| h.to_vec()).collect(),
             decision: i32::from(source.decision().as_u8()),
+            genesis_hash: source.genesis_hash().map(|h| h.to_vec()).unwrap_or_default(),
         }
     }
 }
@@ -575,6 +729,12 @@ impl TryFrom<proto::consensus::QuorumCertificate> for QuorumCertificate {
                 .collect::<Result<_, _>>()?,
             QuorumDecision::from_u8(u8::try_from(value.decision)?)
                 .ok_or_else(|| anyhow!("Invalid Decision byte {}", value.decision))?,
+        )
+        .with_genesis_hash(
+            Some(value.genesis_hash)
+                .filter(|h| !h.is_empty())
+                .map(TryInto::try_into)
+                .transpose()?,
         ))
     }
 }
@@ -719,6 +879,7 @@ impl From<&SyncResponseMessage> for proto::consensus::SyncResponse {
         Self {
             epoch: value.epoch.as_u64(),
             blocks: value.blocks.iter().map(|block| block.into()).collect::<Vec<_>>(),
+            membership_proof: Some((&value.membership_proof).into()),
         }
     }
 }
@@ -734,6 +895,10 @@ impl TryFrom<proto::consensus::SyncResponse> for SyncResponseMessage {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
+            membership_proof: value
+                .membership_proof
+                .ok_or_else(|| anyhow!("Membership proof is missing"))?
+                .try_into()?,
         })
     }
 }
@@ -4,8 +4,17 @@
 use std::convert::{TryFrom, TryInto};
 
 use anyhow::anyhow;
-use tari_dan_storage::consensus_models::{SubstateCreatedProof, SubstateData, SubstateDestroyedProof, SubstateUpdate};
-use tari_engine_types::substate::{SubstateId, SubstateValue};
+use tari_dan_storage::consensus_models::{
+    CommittedBlockDiff,
+    CommittedBlockDiffCursor,
+    SubstateChange,
+    SubstateCreatedProof,
+    SubstateData,
+    SubstateDestroyedProof,
+    SubstateUpdate,
+};
+use tari_engine_types::substate::{Substate, SubstateId, SubstateValue};
+use tari_transaction::VersionedSubstateId;
 
 use crate::proto;
 
@@ -111,3 +120,103 @@ impl From<SubstateData> for proto::rpc::SubstateData {
         }
     }
 }
+
+// -------------------------------- CommittedBlockDiff -------------------------------- //
+
+impl TryFrom<proto::rpc::CommittedBlockDiff> for CommittedBlockDiff {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::rpc::CommittedBlockDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: value.sequence,
+            block: value
+                .block
+                .map(TryInto::try_into)
+                .transpose()?
+                .ok_or_else(|| anyhow!("block not provided"))?,
+            changes: value
+                .changes
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&CommittedBlockDiff> for proto::rpc::CommittedBlockDiff {
+    fn from(value: &CommittedBlockDiff) -> Self {
+        Self {
+            sequence: value.sequence,
+            block: Some((&value.block).into()),
+            changes: value.changes.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::rpc::CommittedBlockDiffCursor> for CommittedBlockDiffCursor {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::rpc::CommittedBlockDiffCursor) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: value.sequence,
+            block_id: value.block_id.try_into()?,
+        })
+    }
+}
+
+impl From<CommittedBlockDiffCursor> for proto::rpc::CommittedBlockDiffCursor {
+    fn from(value: CommittedBlockDiffCursor) -> Self {
+        Self {
+            sequence: value.sequence,
+            block_id: value.block_id.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::rpc::SubstateChange> for SubstateChange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::rpc::SubstateChange) -> Result<Self, Self::Error> {
+        let change = value.change.ok_or_else(|| anyhow!("change not provided"))?;
+        match change {
+            proto::rpc::substate_change::Change::Up(data) => {
+                let data = SubstateData::try_from(data)?;
+                Ok(Self::Up {
+                    id: VersionedSubstateId::new(data.substate_id, data.version),
+                    transaction_id: data.created_by_transaction,
+                    substate: Substate::new(data.version, data.substate_value),
+                })
+            },
+            proto::rpc::substate_change::Change::Down(destroyed) => Ok(Self::Down {
+                id: VersionedSubstateId::new(SubstateId::from_bytes(&destroyed.substate_id)?, destroyed.version),
+                transaction_id: destroyed.destroyed_by_transaction.try_into()?,
+            }),
+        }
+    }
+}
+
+impl From<&SubstateChange> for proto::rpc::SubstateChange {
+    fn from(value: &SubstateChange) -> Self {
+        let change = match value {
+            SubstateChange::Up {
+                id,
+                transaction_id,
+                substate,
+            } => proto::rpc::substate_change::Change::Up(proto::rpc::SubstateData {
+                substate_id: id.substate_id().to_bytes(),
+                version: id.version(),
+                substate_value: substate.substate_value().to_bytes(),
+                created_transaction: transaction_id.as_bytes().to_vec(),
+            }),
+            SubstateChange::Down { id, transaction_id } => {
+                proto::rpc::substate_change::Change::Down(proto::rpc::SubstateDestroyed {
+                    substate_id: id.substate_id().to_bytes(),
+                    version: id.version(),
+                    destroyed_by_transaction: transaction_id.as_bytes().to_vec(),
+                })
+            },
+        };
+
+        Self { change: Some(change) }
+    }
+}
@@ -36,6 +36,7 @@ use tari_template_lib::{
     args::Arg,
     crypto::{BalanceProofSignature, PedersonCommitmentBytes, RistrettoPublicKeyBytes},
     models::{
+        Amount,
         ConfidentialOutputStatement,
         ConfidentialStatement,
         ConfidentialWithdrawProof,
@@ -123,6 +124,7 @@ impl TryFrom<proto::transaction::Transaction> for Transaction {
             filled_inputs,
             min_epoch,
             max_epoch,
+            request.priority_fee,
         );
 
         Ok(transaction)
@@ -152,6 +154,7 @@ impl From<&Transaction> for proto::transaction::Transaction {
             filled_inputs,
             min_epoch,
             max_epoch,
+            priority_fee: transaction.priority_fee(),
         }
     }
 }
@@ -224,6 +227,23 @@ impl TryFrom<proto::transaction::Instruction> for Instruction {
                 .map_err(|e| anyhow!("claim_validator_fees_validator_public_key: {}", e))?,
             },
             InstructionType::DropAllProofsInWorkspace => Instruction::DropAllProofsInWorkspace,
+            InstructionType::DeclareRoyaltyPayment => Instruction::DeclareRoyaltyPayment {
+                resource_address: ObjectKey::try_from(request.royalty_payment_resource_address)?.into(),
+                workspace_bucket: request.royalty_payment_workspace_bucket,
+            },
+            InstructionType::MigrateComponent => Instruction::MigrateComponent {
+                component_address: ObjectKey::try_from(request.component_address)?.into(),
+                template_address: request.template_address.try_into()?,
+            },
+            InstructionType::AssertBucketContains => Instruction::AssertBucketContains {
+                workspace_bucket: request.assert_workspace_bucket,
+                resource_address: ObjectKey::try_from(request.assert_resource_address)?.into(),
+                min_amount: Amount::new(request.assert_min_amount),
+            },
+            InstructionType::AssertVaultBalanceAtLeast => Instruction::AssertVaultBalanceAtLeast {
+                vault_id: ObjectKey::try_from(request.assert_vault_id)?.into(),
+                min_amount: Amount::new(request.assert_min_amount),
+            },
             InstructionType::CreateFreeTestCoins => Instruction::CreateFreeTestCoins {
                 revealed_amount: request.create_free_test_coins_amount.try_into()?,
                 output: tari_bor::decode(&request.create_free_test_coins_output_blob)?,
@@ -295,6 +315,37 @@ impl From<Instruction> for proto::transaction::Instruction {
             Instruction::DropAllProofsInWorkspace => {
                 result.instruction_type = InstructionType::DropAllProofsInWorkspace as i32;
             },
+            Instruction::DeclareRoyaltyPayment {
+                resource_address,
+                workspace_bucket,
+            } => {
+                result.instruction_type = InstructionType::DeclareRoyaltyPayment as i32;
+                result.royalty_payment_resource_address = resource_address.as_ref().to_vec();
+                result.royalty_payment_workspace_bucket = workspace_bucket;
+            },
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => {
+                result.instruction_type = InstructionType::MigrateComponent as i32;
+                result.component_address = component_address.as_bytes().to_vec();
+                result.template_address = template_address.to_vec();
+            },
+            Instruction::AssertBucketContains {
+                workspace_bucket,
+                resource_address,
+                min_amount,
+            } => {
+                result.instruction_type = InstructionType::AssertBucketContains as i32;
+                result.assert_workspace_bucket = workspace_bucket;
+                result.assert_resource_address = resource_address.as_ref().to_vec();
+                result.assert_min_amount = min_amount.value();
+            },
+            Instruction::AssertVaultBalanceAtLeast { vault_id, min_amount } => {
+                result.instruction_type = InstructionType::AssertVaultBalanceAtLeast as i32;
+                result.assert_vault_id = vault_id.as_ref().to_vec();
+                result.assert_min_amount = min_amount.value();
+            },
             // TODO: debugging feature should not be the default. Perhaps a better way to create faucet coins is to mint
             //       a faucet vault in the genesis state for dev networks and use faucet builtin template to withdraw
             //       funds.
@@ -0,0 +1,594 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, fmt::Display, hash::Hash, mem, ops::Deref};
+
+use futures::{stream, Stream, TryStreamExt};
+use log::*;
+use tari_common::configuration::Network;
+use tari_consensus::{
+    block_validations::{check_hash_and_height, check_network, check_quorum_certificate, check_signature},
+    hotstuff::{calculate_state_merkle_diff, ProposalValidationError},
+    traits::{ConsensusSpec, LeaderStrategy, SyncProgress},
+};
+use tari_dan_common_types::{committee::Committee, optional::Optional, NodeHeight};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        BlockDiff,
+        BlockId,
+        GenesisConfig,
+        HighQc,
+        LastExecuted,
+        PendingStateTreeDiff,
+        QuorumCertificate,
+        SubstateChange,
+        SubstateUpdate,
+        SyncCheckpoint,
+        TransactionPoolRecord,
+        TransactionRecord,
+    },
+    StateStore,
+};
+use tari_engine_types::substate::hash_substate;
+use tari_epoch_manager::EpochManagerReader;
+use tari_state_tree::SubstateTreeChange;
+use tari_transaction::VersionedSubstateId;
+
+use crate::error::CommsRpcConsensusSyncError;
+
+const LOG_TARGET: &str = "tari::dan::comms_rpc_state_sync::block_sync";
+
+/// A block and the data required to process it, as received from a sync peer
+#[derive(Debug, Clone)]
+pub struct SyncedBlock {
+    pub block: Block,
+    pub qcs: Vec<QuorumCertificate>,
+    pub updates: Vec<SubstateUpdate>,
+    pub transactions: Vec<TransactionRecord>,
+}
+
+/// Validates the blocks received from a sync peer and commits them to the state store in batches.
+///
+/// Each block is checked before it is added to the current batch: the block hash, proposer signature and network must
+/// be valid, the block must link to the previous block and its justify QC must be signed by a quorum of the committee
+/// for the QC epoch. A batch is processed in a single database transaction, so a failure discards at most the current
+/// batch and every batch committed before it is kept. A [SyncCheckpoint] is stored with each committed batch.
+pub struct BlockSyncProcessor<TConsensusSpec: ConsensusSpec> {
+    network: Network,
+    epoch_manager: TConsensusSpec::EpochManager,
+    state_store: TConsensusSpec::StateStore,
+    leader_strategy: TConsensusSpec::LeaderStrategy,
+    signing_service: TConsensusSpec::SignatureService,
+    batch_size: usize,
+    batch: Vec<(SyncedBlock, Committee<TConsensusSpec::Addr>)>,
+    last_block: BlockIdAndHeight,
+    previous_high_qc: HighQc,
+    // Stores the uncommitted state updates for each block. When a block reaches a 3-chain, the updates are removed and
+    // applied.
+    pending_state_updates: HashMap<BlockId, Vec<SubstateUpdate>>,
+    num_committed: usize,
+}
+
+impl<TConsensusSpec: ConsensusSpec> BlockSyncProcessor<TConsensusSpec> {
+    /// Creates a processor for blocks that follow the given checkpoint. The current high QC is recorded so that the
+    /// synced chain can be checked against it once sync is complete.
+    pub fn new(
+        network: Network,
+        epoch_manager: TConsensusSpec::EpochManager,
+        state_store: TConsensusSpec::StateStore,
+        leader_strategy: TConsensusSpec::LeaderStrategy,
+        signing_service: TConsensusSpec::SignatureService,
+        checkpoint: &SyncCheckpoint,
+        batch_size: usize,
+    ) -> Result<Self, CommsRpcConsensusSyncError> {
+        let previous_high_qc = state_store.with_read_tx(|tx| HighQc::get(tx))?;
+        Ok(Self {
+            network,
+            epoch_manager,
+            state_store,
+            leader_strategy,
+            signing_service,
+            batch_size: batch_size.max(1),
+            batch: Vec::with_capacity(batch_size),
+            last_block: BlockIdAndHeight {
+                id: *checkpoint.block_id(),
+                height: checkpoint.height(),
+            },
+            previous_high_qc,
+            pending_state_updates: HashMap::new(),
+            num_committed: 0,
+        })
+    }
+
+    /// Validates the block and adds it to the current batch. The batch is committed once it is full, in which case the
+    /// new checkpoint is returned.
+    pub async fn add_block(
+        &mut self,
+        synced_block: SyncedBlock,
+    ) -> Result<Option<SyncCheckpoint>, CommsRpcConsensusSyncError> {
+        self.validate_block(&synced_block.block).await?;
+
+        // Note: the committee is only used for dummy block calculation, so we avoid the epoch manager call unless it
+        // is needed. Otherwise, the committee is empty.
+        let block = &synced_block.block;
+        let local_committee = if block.justifies_parent() {
+            Committee::new(vec![])
+        } else {
+            self.epoch_manager
+                .get_committees_by_shards(block.epoch(), vec![block.shard()].into_iter().collect())
+                .await?
+                .into_iter()
+                .next()
+                .map(|(_, committee)| committee)
+                .unwrap_or_else(Committee::empty)
+        };
+
+        self.last_block = BlockIdAndHeight {
+            id: *block.id(),
+            height: block.height(),
+        };
+        self.batch.push((synced_block, local_committee));
+        if self.batch.len() >= self.batch_size {
+            return self.commit_batch(false);
+        }
+
+        Ok(None)
+    }
+
+    /// Commits the remaining blocks and checks that the synced high QC extends the high QC that we had before sync.
+    /// Returns the number of blocks that were committed.
+    pub fn finish(mut self) -> Result<usize, CommsRpcConsensusSyncError> {
+        self.commit_remaining()?;
+        Ok(self.num_committed)
+    }
+
+    /// Commits the remaining blocks and checks that the synced high QC extends the high QC that we had before sync.
+    /// Returns the new checkpoint if there were blocks to commit.
+    pub fn commit_remaining(&mut self) -> Result<Option<SyncCheckpoint>, CommsRpcConsensusSyncError> {
+        self.commit_batch(true)
+    }
+
+    pub fn num_committed(&self) -> usize {
+        self.num_committed
+    }
+
+    fn progress(&self, checkpoint: SyncCheckpoint, target_height: NodeHeight) -> SyncProgress {
+        SyncProgress {
+            checkpoint,
+            blocks_synced: self.num_committed as u64,
+            estimated_remaining: target_height.saturating_sub(self.last_block.height).as_u64(),
+        }
+    }
+
+    async fn validate_block(&self, block: &Block) -> Result<(), CommsRpcConsensusSyncError> {
+        let invalid_block = |details: String| CommsRpcConsensusSyncError::InvalidBlock {
+            block_id: *block.id(),
+            details,
+        };
+
+        check_network(block, self.network).map_err(|e| invalid_block(e.to_string()))?;
+        check_hash_and_height(block).map_err(|e| invalid_block(e.to_string()))?;
+        check_signature(block).map_err(|e| invalid_block(e.to_string()))?;
+
+        if block.justifies_parent() {
+            if *block.parent() != self.last_block.id {
+                return Err(invalid_block(format!(
+                    "Parent {} does not match the previous synced block {}",
+                    block.parent(),
+                    self.last_block
+                )));
+            }
+            if block.height() != self.last_block.height + NodeHeight(1) {
+                return Err(invalid_block(format!(
+                    "Height {} does not follow the previous synced block {}",
+                    block.height(),
+                    self.last_block
+                )));
+            }
+        } else if block.height() <= self.last_block.height {
+            return Err(invalid_block(format!(
+                "Height {} is not greater than the previous synced block {}",
+                block.height(),
+                self.last_block
+            )));
+        }
+
+        check_quorum_certificate::<TConsensusSpec>(block, &self.signing_service, &self.epoch_manager)
+            .await
+            .map_err(|e| invalid_block(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn commit_batch(&mut self, is_final: bool) -> Result<Option<SyncCheckpoint>, CommsRpcConsensusSyncError> {
+        let batch = mem::take(&mut self.batch);
+        let num_blocks = batch.len();
+        // Only keep the pending updates if the batch is committed
+        let mut pending_state_updates = self.pending_state_updates.clone();
+
+        let checkpoint = self.state_store.with_write_tx(|tx| {
+            for (synced_block, local_committee) in batch {
+                self.process_block(tx, synced_block, &local_committee, &mut pending_state_updates)?;
+            }
+
+            if is_final {
+                self.check_high_qc_extends_previous(&**tx)?;
+            }
+
+            if num_blocks == 0 {
+                return Ok(None);
+            }
+
+            // The updates of blocks that are not yet committed are only held in memory, so sync must resume from the
+            // last committed block
+            let last_executed = LastExecuted::get(&**tx)?;
+            let checkpoint = Block::get(&**tx, &last_executed.block_id)?.as_sync_checkpoint();
+            checkpoint.set(tx)?;
+            Ok::<_, CommsRpcConsensusSyncError>(Some(checkpoint))
+        })?;
+
+        self.pending_state_updates = pending_state_updates;
+        self.num_committed += num_blocks;
+        if let Some(ref checkpoint) = checkpoint {
+            info!(
+                target: LOG_TARGET,
+                "🌐 Committed batch of {} synced block(s) up to {}. {}", num_blocks, self.last_block, checkpoint
+            );
+        }
+        Ok(checkpoint)
+    }
+
+    fn check_high_qc_extends_previous(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        let high_qc = HighQc::get(tx)?;
+        if high_qc.block_id == self.previous_high_qc.block_id {
+            return Ok(());
+        }
+
+        let high_qc_block = Block::get(tx, high_qc.block_id())?;
+        if !high_qc_block.extends(tx, self.previous_high_qc.block_id())? {
+            return Err(CommsRpcConsensusSyncError::HighQcDoesNotExtendPrevious {
+                high_qc: high_qc.block_id,
+                previous_high_qc: self.previous_high_qc.block_id,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn process_block(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        synced_block: SyncedBlock,
+        local_committee: &Committee<TConsensusSpec::Addr>,
+        pending_state_updates: &mut HashMap<BlockId, Vec<SubstateUpdate>>,
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        let SyncedBlock {
+            mut block,
+            qcs,
+            updates,
+            transactions,
+        } = synced_block;
+        info!(target: LOG_TARGET, "🌐 Processing block {}. {} substate update(s)", block, updates.len());
+
+        for transaction in transactions {
+            transaction.save(tx)?;
+        }
+
+        block.justify().save(tx)?;
+
+        let justify_block = block.justify().get_block(&**tx)?;
+
+        // Check if we need to calculate dummy blocks
+        if !block.justifies_parent() {
+            let mut last_dummy_block = BlockIdAndHeight {
+                id: *block.justify().block_id(),
+                height: block.justify().block_height(),
+            };
+            info!(target: LOG_TARGET, "🍼 START DUMMY BLOCK: {}. ", last_dummy_block);
+            // if the block parent is not the justify parent, then we have experienced a leader failure
+            // and should make dummy blocks to fill in the gaps.
+            while last_dummy_block.id != *block.parent() {
+                if last_dummy_block.height >= block.height() {
+                    warn!(
+                        target: LOG_TARGET,
+                        "🔥 Bad proposal, no dummy block parent hash matches between block height {} and new block \
+                         height {}.",
+                        last_dummy_block,
+                        block
+                    );
+                    return Err(ProposalValidationError::CandidateBlockDoesNotExtendJustify {
+                        justify_block_height: block.justify().block_height(),
+                        candidate_block_height: block.height(),
+                    }
+                    .into());
+                }
+
+                let next_height = last_dummy_block.height + NodeHeight(1);
+                let leader = self
+                    .leader_strategy
+                    .get_leader_public_key(local_committee, block.epoch(), next_height);
+
+                let dummy_block = Block::dummy_block(
+                    self.network,
+                    last_dummy_block.id,
+                    leader.clone(),
+                    next_height,
+                    block.justify().clone(),
+                    block.epoch(),
+                    block.shard(),
+                    *block.merkle_root(),
+                    justify_block.timestamp(),
+                    justify_block.base_layer_block_height(),
+                    *justify_block.base_layer_block_hash(),
+                );
+                dummy_block.save(tx)?;
+                last_dummy_block = BlockIdAndHeight {
+                    id: *dummy_block.id(),
+                    height: next_height,
+                };
+                info!(target: LOG_TARGET, "🍼 DUMMY BLOCK: {}. Leader: {}", last_dummy_block, leader);
+            }
+        }
+
+        if !block.is_safe(&**tx)? {
+            return Err(CommsRpcConsensusSyncError::BlockNotSafe { block_id: *block.id() });
+        }
+
+        if !block.save(tx)? {
+            // We've already seen this block. This could happen because we're syncing from high qc or resuming after a
+            // failed batch. If it has not been committed yet, its updates are still needed when it is.
+            if !updates.is_empty() && !Block::get(&**tx, block.id())?.is_committed() {
+                pending_state_updates.insert(*block.id(), updates);
+            }
+            return Ok(());
+        }
+        // Voters check the foreign indexes of the next blocks against these counters
+        block.save_foreign_send_counters(tx)?;
+
+        for qc in qcs {
+            qc.save(tx)?;
+        }
+
+        self.check_and_update_state_merkle_tree(tx, &block, &updates)?;
+
+        if !updates.is_empty() {
+            pending_state_updates.insert(*block.id(), updates);
+        }
+
+        block.update_nodes(
+            tx,
+            |_, _, _| Ok(()),
+            |tx, _last_executed, block| {
+                debug!(target: LOG_TARGET, "Sync is committing block {}", block);
+                Self::commit_block(tx, block, pending_state_updates)?;
+                block.as_last_executed().set(tx)?;
+                Ok::<_, CommsRpcConsensusSyncError>(())
+            },
+        )?;
+
+        // Ensure we don't vote on or re-process a synced block
+        block.as_last_voted().set(tx)?;
+        block.set_as_processed(tx)?;
+
+        Ok(())
+    }
+
+    fn check_and_update_state_merkle_tree(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        block: &Block,
+        updates: &[SubstateUpdate],
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        let pending_tree_updates = PendingStateTreeDiff::get_all_up_to_commit_block(&**tx, block.id())?;
+        let current_version = block.justify().block_height().as_u64();
+        let next_version = block.height().as_u64();
+
+        let changes = updates.iter().map(|update| match update {
+            SubstateUpdate::Create(create) => SubstateTreeChange::Up {
+                id: create.substate.substate_id.clone(),
+                value_hash: hash_substate(&create.substate.substate_value, create.substate.version),
+            },
+            SubstateUpdate::Destroy(destroy) => SubstateTreeChange::Down {
+                id: destroy.substate_id.clone(),
+            },
+        });
+
+        let (root_hash, tree_diff) =
+            calculate_state_merkle_diff(tx.deref(), current_version, next_version, pending_tree_updates, changes)?;
+
+        if root_hash != *block.merkle_root() {
+            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+                "Merkle root in block {} does not match the merkle root of the state tree. Block MR: {}, Calculated \
+                 MR: {}",
+                block,
+                block.merkle_root(),
+                root_hash
+            )));
+        }
+
+        // Persist pending state tree diff
+        PendingStateTreeDiff::new(*block.id(), block.height(), tree_diff).save(tx)?;
+
+        Ok(())
+    }
+
+    fn commit_block(
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        block: &Block,
+        pending_state_updates: &mut HashMap<BlockId, Vec<SubstateUpdate>>,
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        let block_diff = BlockDiff::new(
+            *block.id(),
+            pending_state_updates
+                .drain()
+                .flat_map(|(_, v)| v)
+                .map(substate_update_to_change)
+                .collect(),
+        );
+
+        block.commit_diff(tx, block_diff)?;
+
+        if block.is_dummy() {
+            return Ok(());
+        }
+
+        // Finalize any ACCEPTED transactions
+        for tx_atom in block.commands().iter().filter_map(|cmd| cmd.accept()) {
+            if let Some(mut transaction) = tx_atom.get_transaction(&**tx).optional()? {
+                transaction.final_decision = Some(tx_atom.decision);
+                if tx_atom.decision.is_abort() {
+                    transaction.abort_details = Some("Abort decision via sync".to_string());
+                }
+                // TODO: execution result - we should execute or we should get the execution result and verify state via
+                // sync
+                transaction.update(tx)?;
+            }
+        }
+
+        // Remove from pool including any pending updates
+        TransactionPoolRecord::remove_any(
+            tx,
+            block.commands().iter().filter_map(|cmd| cmd.accept()).map(|t| &t.id),
+        )?;
+
+        let diff = PendingStateTreeDiff::remove_by_block(tx, block.id())?;
+        let mut tree = tari_state_tree::SpreadPrefixStateTree::new(tx);
+        tree.commit_diff(diff.diff)?;
+
+        debug!(target: LOG_TARGET, "✅ COMMIT block {}", block);
+        Ok(())
+    }
+}
+
+/// Feeds the synced blocks to the processor and yields the progress each time a batch is committed. The remaining
+/// blocks are committed once the blocks stream ends. The number of remaining blocks is estimated from `target_height`.
+pub fn sync_progress_stream<'a, TConsensusSpec, TBlocks>(
+    processor: BlockSyncProcessor<TConsensusSpec>,
+    blocks: TBlocks,
+    target_height: NodeHeight,
+) -> impl Stream<Item = Result<SyncProgress, CommsRpcConsensusSyncError>> + Send + 'a
+where
+    TConsensusSpec: ConsensusSpec + 'a,
+    TBlocks: Stream<Item = Result<SyncedBlock, CommsRpcConsensusSyncError>> + Unpin + Send + 'a,
+{
+    stream::try_unfold((Some(processor), blocks), move |(processor, mut blocks)| async move {
+        let Some(mut processor) = processor else {
+            return Ok(None);
+        };
+
+        while let Some(synced_block) = blocks.try_next().await? {
+            if let Some(checkpoint) = processor.add_block(synced_block).await? {
+                let progress = processor.progress(checkpoint, target_height);
+                return Ok(Some((progress, (Some(processor), blocks))));
+            }
+        }
+
+        let progress = processor
+            .commit_remaining()?
+            .map(|checkpoint| processor.progress(checkpoint, target_height));
+        Ok(progress.map(|progress| (progress, (None, blocks))))
+    })
+}
+
+/// Counts the sync failures of each peer. Peers that have failed are tried after those that have not.
+#[derive(Debug, Clone)]
+pub struct SyncFailureCounter<TAddr> {
+    failures: HashMap<TAddr, usize>,
+}
+
+impl<TAddr: Eq + Hash + Clone> SyncFailureCounter<TAddr> {
+    pub fn new() -> Self {
+        Self {
+            failures: HashMap::new(),
+        }
+    }
+
+    pub fn penalize(&mut self, addr: &TAddr) -> usize {
+        let count = self.failures.entry(addr.clone()).or_default();
+        *count += 1;
+        *count
+    }
+
+    pub fn get(&self, addr: &TAddr) -> usize {
+        self.failures.get(addr).copied().unwrap_or(0)
+    }
+
+    /// Orders the candidates from the fewest to the most failures. Candidates with the same number of failures keep
+    /// their relative order.
+    pub fn order_candidates(&self, candidates: &mut [TAddr]) {
+        candidates.sort_by_key(|addr| self.get(addr));
+    }
+}
+
+impl<TAddr: Eq + Hash + Clone> Default for SyncFailureCounter<TAddr> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates the zero block and sets it as the locked, leaf and high QC block if this is a new chain
+pub fn create_zero_block_if_required<TStateStore: StateStore>(
+    state_store: &TStateStore,
+    network: Network,
+    genesis: &GenesisConfig,
+) -> Result<(), CommsRpcConsensusSyncError> {
+    state_store.with_write_tx(|tx| {
+        let zero_block = Block::zero_block_with_genesis(network, genesis);
+        if !zero_block.exists(&**tx)? {
+            debug!(target: LOG_TARGET, "Creating zero block");
+            zero_block.justify().insert(tx)?;
+            zero_block.insert(tx)?;
+            zero_block.as_locked_block().set(tx)?;
+            zero_block.as_leaf_block().set(tx)?;
+            zero_block.as_last_executed().set(tx)?;
+            zero_block.as_last_voted().set(tx)?;
+            zero_block.justify().as_high_qc().set(tx)?;
+            zero_block.commit_diff(tx, BlockDiff::empty(*zero_block.id()))?;
+        }
+        Ok::<_, CommsRpcConsensusSyncError>(())
+    })
+}
+
+/// Returns the checkpoint to resume sync from. This is the stored checkpoint, unless blocks have been committed since
+/// it was stored (e.g. by consensus), in which case it is the last committed block. The zero block must already exist.
+pub fn load_sync_checkpoint<TStateStore: StateStore>(
+    state_store: &TStateStore,
+) -> Result<SyncCheckpoint, CommsRpcConsensusSyncError> {
+    state_store.with_read_tx(|tx| {
+        let last_executed = LastExecuted::get(tx)?;
+        match SyncCheckpoint::get(tx).optional()? {
+            Some(checkpoint) if checkpoint.height() >= last_executed.height => Ok(checkpoint),
+            _ => Ok(Block::get(tx, &last_executed.block_id)?.as_sync_checkpoint()),
+        }
+    })
+}
+
+struct BlockIdAndHeight {
+    id: BlockId,
+    height: NodeHeight,
+}
+
+impl Display for BlockIdAndHeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Block: {} (#{})", self.id, self.height)
+    }
+}
+
+// TODO: these are similar structures. Clean this up.
+fn substate_update_to_change(update: SubstateUpdate) -> SubstateChange {
+    match update {
+        SubstateUpdate::Create(create) => SubstateChange::Up {
+            id: VersionedSubstateId::new(create.substate.substate_id.clone(), create.substate.version),
+            transaction_id: create.substate.created_by_transaction,
+            substate: create.substate.into_substate(),
+        },
+        SubstateUpdate::Destroy(destroy) => SubstateChange::Down {
+            id: VersionedSubstateId::new(destroy.substate_id, destroy.version),
+            transaction_id: destroy.destroyed_by_transaction,
+        },
+    }
+}
@@ -32,6 +32,13 @@ pub enum CommsRpcConsensusSyncError {
     ProposalValidationError(#[from] ProposalValidationError),
     #[error("State tree error: {0}")]
     StateTreeError(#[from] tari_state_tree::StateTreeError),
+    #[error("Peer sent invalid block {block_id}: {details}")]
+    InvalidBlock { block_id: BlockId, details: String },
+    #[error("Synced high QC for block {high_qc} does not extend the previous high QC for block {previous_high_qc}")]
+    HighQcDoesNotExtendPrevious {
+        high_qc: BlockId,
+        previous_high_qc: BlockId,
+    },
 }
 
 impl From<CommsRpcConsensusSyncError> for HotStuffError {
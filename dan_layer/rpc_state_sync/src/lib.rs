@@ -3,8 +3,17 @@
 
 //! # P2P RPC State Sync Protocol
 
+mod block_sync;
 mod error;
 mod manager;
 
+pub use block_sync::{
+    create_zero_block_if_required,
+    load_sync_checkpoint,
+    sync_progress_stream,
+    BlockSyncProcessor,
+    SyncFailureCounter,
+    SyncedBlock,
+};
 pub use error::*;
 pub use manager::*;
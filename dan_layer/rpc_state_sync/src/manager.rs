@@ -1,61 +1,62 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Display,
-    ops::Deref,
-};
+use std::collections::HashSet;
 
 use async_trait::async_trait;
-use futures::StreamExt;
+use futures::{stream, StreamExt, TryStreamExt};
 use log::*;
 use tari_common::configuration::Network;
-use tari_consensus::{
-    hotstuff::{calculate_state_merkle_diff, ProposalValidationError},
-    traits::{ConsensusSpec, LeaderStrategy, SyncManager, SyncStatus},
-};
+use tari_consensus::traits::{ConsensusSpec, SyncManager, SyncProgress, SyncProgressStream, SyncStatus};
 use tari_dan_common_types::{committee::Committee, optional::Optional, shard::Shard, Epoch, NodeHeight, PeerAddress};
-use tari_dan_p2p::proto::rpc::{GetHighQcRequest, SyncBlocksRequest};
+use tari_dan_p2p::proto::rpc::{GetHighQcRequest, SyncBlocksRequest, SyncBlocksResponse};
 use tari_dan_storage::{
     consensus_models::{
         Block,
-        BlockDiff,
-        BlockId,
+        GenesisConfig,
         HighQc,
         LeafBlock,
-        LockedBlock,
-        PendingStateTreeDiff,
         QuorumCertificate,
-        SubstateChange,
         SubstateUpdate,
-        TransactionPoolRecord,
+        SyncCheckpoint,
         TransactionRecord,
     },
     StateStore,
 };
-use tari_engine_types::substate::hash_substate;
 use tari_epoch_manager::EpochManagerReader;
-use tari_rpc_framework::RpcError;
-use tari_state_tree::SubstateTreeChange;
-use tari_transaction::{Transaction, VersionedSubstateId};
-use tari_validator_node_rpc::{
-    client::{TariValidatorNodeRpcClientFactory, ValidatorNodeClientFactory},
-    rpc_service::ValidatorNodeRpcClient,
+use tari_rpc_framework::{ClientStreaming, RpcError};
+use tari_transaction::Transaction;
+use tari_validator_node_rpc::client::{TariValidatorNodeRpcClientFactory, ValidatorNodeClientFactory};
+use tokio::sync::watch;
+
+use crate::{
+    block_sync::{
+        create_zero_block_if_required,
+        load_sync_checkpoint,
+        sync_progress_stream,
+        BlockSyncProcessor,
+        SyncFailureCounter,
+        SyncedBlock,
+    },
+    error::CommsRpcConsensusSyncError,
 };
 
-use crate::error::CommsRpcConsensusSyncError;
-
 const LOG_TARGET: &str = "tari::dan::comms_rpc_state_sync";
 
 const MAX_SUBSTATE_UPDATES: usize = 10000;
+/// The number of synced blocks that are committed to the state store in a single database transaction
+const BLOCK_SYNC_BATCH_SIZE: usize = 100;
 
 pub struct RpcStateSyncManager<TConsensusSpec: ConsensusSpec> {
     network: Network,
+    genesis: GenesisConfig,
     epoch_manager: TConsensusSpec::EpochManager,
     state_store: TConsensusSpec::StateStore,
     leader_strategy: TConsensusSpec::LeaderStrategy,
+    signing_service: TConsensusSpec::SignatureService,
     client_factory: TariValidatorNodeRpcClientFactory,
+    sync_failures: SyncFailureCounter<TConsensusSpec::Addr>,
+    tx_sync_progress: watch::Sender<Option<SyncProgress>>,
 }
 
 impl<TConsensusSpec> RpcStateSyncManager<TConsensusSpec>
@@ -63,20 +64,32 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress>
 {
     pub fn new(
         network: Network,
+        genesis: GenesisConfig,
         epoch_manager: TConsensusSpec::EpochManager,
         state_store: TConsensusSpec::StateStore,
         leader_strategy: TConsensusSpec::LeaderStrategy,
+        signing_service: TConsensusSpec::SignatureService,
         client_factory: TariValidatorNodeRpcClientFactory,
     ) -> Self {
         Self {
             network,
+            genesis,
             epoch_manager,
             state_store,
             leader_strategy,
+            signing_service,
             client_factory,
+            sync_failures: SyncFailureCounter::new(),
+            tx_sync_progress: watch::channel(None).0,
         }
     }
 
+    /// Subscribes to the progress of block sync. The value is None until the first batch of blocks has been synced
+    /// since the node started.
+    pub fn subscribe_to_progress(&self) -> watch::Receiver<Option<SyncProgress>> {
+        self.tx_sync_progress.subscribe()
+    }
+
     async fn get_sync_peers(&self) -> Result<Committee<TConsensusSpec::Addr>, CommsRpcConsensusSyncError> {
         let current_epoch = self.epoch_manager.current_epoch().await?;
         let this_vn = self.epoch_manager.get_our_validator_node(current_epoch).await?;
@@ -89,368 +102,102 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress>
     async fn sync_with_peer(
         &mut self,
         addr: &TConsensusSpec::Addr,
-        locked_block: &LockedBlock,
+        checkpoint: SyncCheckpoint,
         up_to_epoch: Option<Epoch>,
     ) -> Result<(), CommsRpcConsensusSyncError> {
-        self.create_zero_block_if_required()?;
-        let mut rpc_client = self.client_factory.create_client(addr);
-        let mut client = rpc_client.client_connection().await?;
-
-        info!(target: LOG_TARGET, "🌐 Syncing blocks from peer '{}' from Locked block {}", addr, locked_block);
-        self.sync_blocks(&mut client, locked_block, up_to_epoch).await?;
-
-        Ok(())
-    }
-
-    fn create_zero_block_if_required(&self) -> Result<(), CommsRpcConsensusSyncError> {
-        self.state_store.with_write_tx(|tx| {
-            let zero_block = Block::zero_block(self.network);
-            if !zero_block.exists(&**tx)? {
-                debug!(target: LOG_TARGET, "Creating zero block");
-                zero_block.justify().insert(tx)?;
-                zero_block.insert(tx)?;
-                zero_block.as_locked_block().set(tx)?;
-                zero_block.as_leaf_block().set(tx)?;
-                zero_block.as_last_executed().set(tx)?;
-                zero_block.as_last_voted().set(tx)?;
-                zero_block.justify().as_high_qc().set(tx)?;
-                zero_block.commit_diff(tx, BlockDiff::empty(*zero_block.id()))?;
-            }
-            Ok::<_, CommsRpcConsensusSyncError>(())
-        })?;
+        info!(target: LOG_TARGET, "🌐 Syncing blocks from peer '{}' from {}", addr, checkpoint);
+        let mut progress_stream = self.sync_blocks_from_peer(addr, checkpoint, up_to_epoch).await?;
+        let mut num_synced = 0;
+        while let Some(progress) = progress_stream.try_next().await? {
+            info!(
+                target: LOG_TARGET,
+                "🌐 Synced {} block(s) up to {}. ~{} block(s) remaining",
+                progress.blocks_synced,
+                progress.checkpoint,
+                progress.estimated_remaining
+            );
+            self.tx_sync_progress.send_replace(Some(progress));
+            num_synced = progress.blocks_synced;
+        }
+        info!(target: LOG_TARGET, "🌐 {} block(s) synced from peer '{}'", num_synced, addr);
 
         Ok(())
     }
 
-    #[allow(clippy::too_many_lines)]
-    async fn sync_blocks(
-        &mut self,
-        client: &mut ValidatorNodeRpcClient,
-        locked_block: &LockedBlock,
+    /// Requests the blocks after the checkpoint from the peer and returns a stream that processes them
+    async fn sync_blocks_from_peer(
+        &self,
+        addr: &TConsensusSpec::Addr,
+        checkpoint: SyncCheckpoint,
         up_to_epoch: Option<Epoch>,
-    ) -> Result<(), CommsRpcConsensusSyncError> {
-        let mut stream = client
+    ) -> Result<SyncProgressStream<'static, CommsRpcConsensusSyncError>, CommsRpcConsensusSyncError> {
+        let mut rpc_client = self.client_factory.create_client(addr);
+        let mut client = rpc_client.client_connection().await?;
+
+        // The peer's high QC is only used to estimate the number of blocks remaining
+        let target_height = client
+            .get_high_qc(GetHighQcRequest {})
+            .await?
+            .high_qc
+            .map(QuorumCertificate::try_from)
+            .transpose()
+            .map_err(CommsRpcConsensusSyncError::InvalidResponse)?
+            .map_or(checkpoint.height(), |qc| qc.block_height());
+
+        let stream = client
             .sync_blocks(SyncBlocksRequest {
-                start_block_id: locked_block.block_id.as_bytes().to_vec(),
+                start_block_id: checkpoint.block_id().as_bytes().to_vec(),
                 up_to_epoch: up_to_epoch.map(|epoch| epoch.into()),
             })
             .await?;
 
-        let mut counter = 0usize;
-
-        let mut expected_height = locked_block.height + NodeHeight(1);
-        // Stores the uncommitted state updates for each block. When a block reaches a 3-chain, the updates are removed
-        // and applied.
-        let mut pending_state_updates = HashMap::new();
-
-        while let Some(resp) = stream.next().await {
-            let msg = resp.map_err(RpcError::from)?;
-            let new_block = msg.into_block().ok_or_else(|| {
-                CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return a newblock",))
-            })?;
-
-            let block = Block::try_from(new_block).map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
-            if block.justifies_parent() && block.height() != expected_height {
-                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                    "Peer returned block at height {} but expected {}",
-                    block.height(),
-                    expected_height,
-                )));
-            }
-
-            let Some(resp) = stream.next().await else {
-                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                    "Peer closed session before sending QC message"
-                )));
-            };
-            let msg = resp.map_err(RpcError::from)?;
-            let qcs = msg.into_quorum_certificates().ok_or_else(|| {
-                CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return QCs"))
-            })?;
-
-            let qcs = qcs
-                .into_iter()
-                .map(QuorumCertificate::try_from)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
-
-            // TODO: Validate
-
-            let Some(resp) = stream.next().await else {
-                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                    "Peer closed session before sending substate update count message"
-                )));
-            };
-            let msg = resp.map_err(RpcError::from)?;
-            let num_substates = msg.substate_count().ok_or_else(|| {
-                CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return substate count",))
-            })? as usize;
-
-            if num_substates > MAX_SUBSTATE_UPDATES {
-                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                    "Peer returned {} substate updates, but the maximum is {}",
-                    num_substates,
-                    MAX_SUBSTATE_UPDATES,
-                )));
-            }
-
-            let mut updates = Vec::with_capacity(num_substates);
-            for _ in 0..num_substates {
-                let Some(resp) = stream.next().await else {
-                    return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                        "Peer closed session before sending substate updates message"
-                    )));
-                };
-                let msg = resp.map_err(RpcError::from)?;
-                let update = msg.into_substate_update().ok_or_else(|| {
-                    CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                        "Expected peer to return substate updates",
-                    ))
-                })?;
-
-                let update = SubstateUpdate::try_from(update).map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
-                updates.push(update);
-            }
-
-            let Some(resp) = stream.next().await else {
-                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                    "Peer closed session before sending transactions message"
-                )));
-            };
-            let msg = resp.map_err(RpcError::from)?;
-            let transactions = msg.into_transactions().ok_or_else(|| {
-                CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return QCs"))
-            })?;
-
-            debug!(target: LOG_TARGET, "🌐 Received block {}, {} transactions", block, transactions.len());
-
-            let transactions = transactions
-                .into_iter()
-                .map(Transaction::try_from)
-                .map(|r| r.map(TransactionRecord::new))
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
-
-            // TODO: Validate
-            debug!(
-                target: LOG_TARGET,
-                "🌐 Received block {}, {} qcs and {} substate updates",
-                block,
-                qcs.len(),
-                updates.len(),
-            );
-            counter += 1;
-            if counter % 100 == 0 {
-                info!(target: LOG_TARGET, "🌐 Syncing block {block}");
-            }
-            if block.justifies_parent() {
-                expected_height += NodeHeight(1);
-            } else {
-                expected_height = block.height() + NodeHeight(1);
-            }
-            self.process_block(block, qcs, updates, transactions, &mut pending_state_updates)
-                .await?;
-        }
+        let processor = BlockSyncProcessor::<TConsensusSpec>::new(
+            self.network,
+            self.epoch_manager.clone(),
+            self.state_store.clone(),
+            self.leader_strategy.clone(),
+            self.signing_service.clone(),
+            &checkpoint,
+            BLOCK_SYNC_BATCH_SIZE,
+        )?;
 
-        info!(target: LOG_TARGET, "🌐 {counter} blocks synced to height {}", expected_height - NodeHeight(1));
+        // The client is kept with the stream so that the connection stays open until sync is complete
+        let blocks = stream::try_unfold((client, stream), |(client, mut stream)| async move {
+            let synced_block = read_synced_block(&mut stream).await?;
+            Ok(synced_block.map(|synced_block| (synced_block, (client, stream))))
+        });
 
-        Ok(())
+        Ok(sync_progress_stream(processor, Box::pin(blocks), target_height).boxed())
     }
 
-    async fn process_block(
+    /// Syncs from the first peer in the local committee that we can connect to
+    async fn connect_checkpoint_sync(
         &mut self,
-        mut block: Block,
-        qcs: Vec<QuorumCertificate>,
-        updates: Vec<SubstateUpdate>,
-        transactions: Vec<TransactionRecord>,
-        pending_state_updates: &mut HashMap<BlockId, Vec<SubstateUpdate>>,
-    ) -> Result<(), CommsRpcConsensusSyncError> {
-        info!(target: LOG_TARGET, "🌐 Processing block {}. {} substate update(s)", block, updates.len());
-        // Note: this is only used for dummy block calculation, so we avoid the epoch manager call unless it is needed.
-        // Otherwise, the committee is empty.
-        let local_committee = if block.justifies_parent() {
-            Committee::new(vec![])
-        } else {
-            self.epoch_manager
-                .get_committees_by_shards(block.epoch(), vec![block.shard()].into_iter().collect())
-                .await?
-                .into_iter()
-                .next()
-                .map(|(_, committee)| committee)
-                .unwrap_or_else(Committee::empty)
-        };
-
-        // TODO: Validate before we save anything.
-        self.state_store.with_write_tx(|tx| {
-            for transaction in transactions {
-                transaction.save(tx)?;
-            }
-
-            block.justify().save(tx)?;
-
-            let justify_block = block.justify().get_block(&**tx)?;
-
-            // Check if we need to calculate dummy blocks
-            // TODO: Validate before doing this. e.g. block.height() is maliciously larger then block.justify().block_height()
-            if !block.justifies_parent() {
-                let mut last_dummy_block = BlockIdAndHeight {id: *block.justify().block_id(), height: block.justify().block_height()};
-                info!(target: LOG_TARGET, "🍼 START DUMMY BLOCK: {}. ", last_dummy_block, );
-                // if the block parent is not the justify parent, then we have experienced a leader failure
-                // and should make dummy blocks to fill in the gaps.
-                while last_dummy_block.id != *block.parent() {
-                    if last_dummy_block.height >= block.height() {
-                        warn!(target: LOG_TARGET, "🔥 Bad proposal, no dummy block parent hash matches between block height {} and new block height {}.", last_dummy_block, block);
-                        return Err( ProposalValidationError::CandidateBlockDoesNotExtendJustify {
-                            justify_block_height: block.justify().block_height(),
-                            candidate_block_height: block.height(),
-                        }.into());
-                    }
-
-                    let next_height = last_dummy_block.height + NodeHeight(1);
-                    let leader = self.leader_strategy.get_leader_public_key(&local_committee, next_height);
-
-                    let dummy_block = Block::dummy_block(
-                       self.network,
-                        last_dummy_block.id,
-                        leader.clone(),
-                        next_height,
-                        block.justify().clone(),
-                        block.epoch(),
-                        block.shard(),
-                        *block.merkle_root(),
-                        justify_block.timestamp(),
-                        justify_block.base_layer_block_height(),
-                        *justify_block.base_layer_block_hash(),
+        checkpoint: SyncCheckpoint,
+    ) -> Result<SyncProgressStream<'static, CommsRpcConsensusSyncError>, CommsRpcConsensusSyncError> {
+        create_zero_block_if_required(&self.state_store, self.network, &self.genesis)?;
+        let committee = self.get_sync_peers().await?;
+        let mut members = committee.addresses().cloned().collect::<Vec<_>>();
+        self.sync_failures.order_candidates(&mut members);
+        for member in &members {
+            match self.sync_blocks_from_peer(member, checkpoint, None).await {
+                Ok(progress_stream) => {
+                    info!(target: LOG_TARGET, "🌐 Syncing blocks from peer '{}' from {}", member, checkpoint);
+                    return Ok(progress_stream);
+                },
+                Err(err) => {
+                    let num_failures = self.sync_failures.penalize(member);
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to sync with peer {} ({} failure(s)): {}", member, num_failures, err
                     );
-                    dummy_block.save(tx)?;
-                    last_dummy_block = BlockIdAndHeight { id: *dummy_block.id(), height: next_height };
-                    info!(target: LOG_TARGET, "🍼 DUMMY BLOCK: {}. Leader: {}", last_dummy_block, leader);
-                }
-            }
-
-            if !block.is_safe(&**tx)? {
-                return Err(CommsRpcConsensusSyncError::BlockNotSafe { block_id: *block.id() });
-            }
-
-            if !block.save(tx)? {
-                // We've already seen this block. This could happen because we're syncing from high qc and we receive a
-                // block that we already have
-                return Ok(());
-            }
-
-            for qc in qcs {
-                qc.save(tx)?;
-            }
-
-            self.check_and_update_state_merkle_tree(tx, &block, &updates)?;
-
-            if !updates.is_empty() {
-                pending_state_updates.insert(*block.id(), updates);
-            }
-
-            block.update_nodes(
-                tx,
-                |_, _, _| Ok(()),
-                |tx, _last_executed, block| {
-                    debug!(target: LOG_TARGET, "Sync is committing block {}", block);
-                    Self::commit_block(tx,  block, pending_state_updates)?;
-                    block.as_last_executed().set(tx)?;
-                    Ok::<_, CommsRpcConsensusSyncError>(())
                 },
-            )?;
-
-            // Ensure we don't vote on or re-process a synced block
-            block.as_last_voted().set(tx)?;
-            block.set_as_processed(tx)?;
-
-            Ok(())
-        })
-    }
-
-    fn check_and_update_state_merkle_tree(
-        &self,
-        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
-        block: &Block,
-        updates: &[SubstateUpdate],
-    ) -> Result<(), CommsRpcConsensusSyncError> {
-        let pending_tree_updates = PendingStateTreeDiff::get_all_up_to_commit_block(&**tx, block.id())?;
-        let current_version = block.justify().block_height().as_u64();
-        let next_version = block.height().as_u64();
-
-        let changes = updates.iter().map(|update| match update {
-            SubstateUpdate::Create(create) => SubstateTreeChange::Up {
-                id: create.substate.substate_id.clone(),
-                value_hash: hash_substate(&create.substate.substate_value, create.substate.version),
-            },
-            SubstateUpdate::Destroy(destroy) => SubstateTreeChange::Down {
-                id: destroy.substate_id.clone(),
-            },
-        });
-
-        let (root_hash, tree_diff) =
-            calculate_state_merkle_diff(tx.deref(), current_version, next_version, pending_tree_updates, changes)?;
-
-        if root_hash != *block.merkle_root() {
-            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
-                "Merkle root in block {} does not match the merkle root of the state tree. Block MR: {}, Calculated \
-                 MR: {}",
-                block,
-                block.merkle_root(),
-                root_hash
-            )));
-        }
-
-        // Persist pending state tree diff
-        PendingStateTreeDiff::new(*block.id(), block.height(), tree_diff).save(tx)?;
-
-        Ok(())
-    }
-
-    fn commit_block(
-        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
-        block: &Block,
-        pending_state_updates: &mut HashMap<BlockId, Vec<SubstateUpdate>>,
-    ) -> Result<(), CommsRpcConsensusSyncError> {
-        let block_diff = BlockDiff::new(
-            *block.id(),
-            pending_state_updates
-                .drain()
-                .flat_map(|(_, v)| v)
-                .map(substate_update_to_change)
-                .collect(),
-        );
-
-        block.commit_diff(tx, block_diff)?;
-
-        if block.is_dummy() {
-            return Ok(());
-        }
-
-        // Finalize any ACCEPTED transactions
-        for tx_atom in block.commands().iter().filter_map(|cmd| cmd.accept()) {
-            if let Some(mut transaction) = tx_atom.get_transaction(&**tx).optional()? {
-                transaction.final_decision = Some(tx_atom.decision);
-                if tx_atom.decision.is_abort() {
-                    transaction.abort_details = Some("Abort decision via sync".to_string());
-                }
-                // TODO: execution result - we should execute or we should get the execution result and verify state via
-                // sync
-                transaction.update(tx)?;
             }
         }
 
-        // Remove from pool including any pending updates
-        TransactionPoolRecord::remove_any(
-            tx,
-            block.commands().iter().filter_map(|cmd| cmd.accept()).map(|t| &t.id),
-        )?;
-
-        let diff = PendingStateTreeDiff::remove_by_block(tx, block.id())?;
-        let mut tree = tari_state_tree::SpreadPrefixStateTree::new(tx);
-        tree.commit_diff(diff.diff)?;
-
-        debug!(target: LOG_TARGET, "✅ COMMIT block {}", block);
-        Ok(())
+        Err(CommsRpcConsensusSyncError::NoPeersAvailable {
+            committee_size: committee.len(),
+        })
     }
 
     async fn check_sync_from_committee(
@@ -542,23 +289,28 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress>
         this_vn_address: PeerAddress,
     ) -> Result<Option<CommsRpcConsensusSyncError>, CommsRpcConsensusSyncError> {
         let mut sync_error = None;
-        for member in committee.addresses() {
+        let mut members = committee.addresses().cloned().collect::<Vec<_>>();
+        // Peers that previously sent us invalid blocks or failed are tried last
+        self.sync_failures.order_candidates(&mut members);
+        for member in &members {
             if *member == this_vn_address {
                 continue;
             }
-            // Refresh the HighQC each time because a partial sync could have been achieved from a peer
-            let locked_block = self
-                .state_store
-                .with_read_tx(|tx| LockedBlock::get(tx).optional())?
-                .unwrap_or_else(|| Block::zero_block(self.network).as_locked_block());
+            // Reload the checkpoint each time because a partial sync could have been achieved from a peer
+            create_zero_block_if_required(&self.state_store, self.network, &self.genesis)?;
+            let checkpoint = load_sync_checkpoint(&self.state_store)?;
 
-            match self.sync_with_peer(member, &locked_block, up_to_epoch).await {
+            match self.sync_with_peer(member, checkpoint, up_to_epoch).await {
                 Ok(()) => {
                     sync_error = None;
                     break;
                 },
                 Err(err) => {
-                    warn!(target: LOG_TARGET, "Failed to sync with peer {}: {}", member, err);
+                    let num_failures = self.sync_failures.penalize(member);
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to sync with peer {} ({} failure(s)): {}", member, num_failures, err
+                    );
                     sync_error = Some(err);
                     continue;
                 },
@@ -687,30 +439,108 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress> + Send + Sync + 'static
 
         Ok(())
     }
-}
 
-struct BlockIdAndHeight {
-    id: BlockId,
-    height: NodeHeight,
+    fn sync_from_checkpoint(&mut self, checkpoint: SyncCheckpoint) -> SyncProgressStream<'_, Self::Error> {
+        stream::once(self.connect_checkpoint_sync(checkpoint))
+            .try_flatten()
+            .boxed()
+    }
 }
 
-impl Display for BlockIdAndHeight {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Block: {} (#{})", self.id, self.height)
+/// Reads the messages for the next block from the sync stream. Returns None once the peer has sent all blocks.
+async fn read_synced_block(
+    stream: &mut ClientStreaming<SyncBlocksResponse>,
+) -> Result<Option<SyncedBlock>, CommsRpcConsensusSyncError> {
+    let Some(resp) = stream.next().await else {
+        return Ok(None);
+    };
+    let msg = resp.map_err(RpcError::from)?;
+    let new_block = msg.into_block().ok_or_else(|| {
+        CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return a newblock",))
+    })?;
+
+    let block = Block::try_from(new_block).map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+    let Some(resp) = stream.next().await else {
+        return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+            "Peer closed session before sending QC message"
+        )));
+    };
+    let msg = resp.map_err(RpcError::from)?;
+    let qcs = msg
+        .into_quorum_certificates()
+        .ok_or_else(|| CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return QCs")))?;
+
+    let qcs = qcs
+        .into_iter()
+        .map(QuorumCertificate::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+
+    let Some(resp) = stream.next().await else {
+        return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+            "Peer closed session before sending substate update count message"
+        )));
+    };
+    let msg = resp.map_err(RpcError::from)?;
+    let num_substates = msg.substate_count().ok_or_else(|| {
+        CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return substate count",))
+    })? as usize;
+
+    if num_substates > MAX_SUBSTATE_UPDATES {
+        return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+            "Peer returned {} substate updates, but the maximum is {}",
+            num_substates,
+            MAX_SUBSTATE_UPDATES,
+        )));
     }
-}
 
-// TODO: these are similar structures. Clean this up.
-fn substate_update_to_change(update: SubstateUpdate) -> SubstateChange {
-    match update {
-        SubstateUpdate::Create(create) => SubstateChange::Up {
-            id: VersionedSubstateId::new(create.substate.substate_id.clone(), create.substate.version),
-            transaction_id: create.substate.created_by_transaction,
-            substate: create.substate.into_substate(),
-        },
-        SubstateUpdate::Destroy(destroy) => SubstateChange::Down {
-            id: VersionedSubstateId::new(destroy.substate_id, destroy.version),
-            transaction_id: destroy.destroyed_by_transaction,
-        },
+    let mut updates = Vec::with_capacity(num_substates);
+    for _ in 0..num_substates {
+        let Some(resp) = stream.next().await else {
+            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+                "Peer closed session before sending substate updates message"
+            )));
+        };
+        let msg = resp.map_err(RpcError::from)?;
+        let update = msg.into_substate_update().ok_or_else(|| {
+            CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return substate updates",))
+        })?;
+
+        let update = SubstateUpdate::try_from(update).map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+        updates.push(update);
     }
+
+    let Some(resp) = stream.next().await else {
+        return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!(
+            "Peer closed session before sending transactions message"
+        )));
+    };
+    let msg = resp.map_err(RpcError::from)?;
+    let transactions = msg
+        .into_transactions()
+        .ok_or_else(|| CommsRpcConsensusSyncError::InvalidResponse(anyhow::anyhow!("Expected peer to return QCs")))?;
+
+    debug!(target: LOG_TARGET, "🌐 Received block {}, {} transactions", block, transactions.len());
+
+    let transactions = transactions
+        .into_iter()
+        .map(Transaction::try_from)
+        .map(|r| r.map(TransactionRecord::new))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+
+    debug!(
+        target: LOG_TARGET,
+        "🌐 Received block {}, {} qcs and {} substate updates",
+        block,
+        qcs.len(),
+        updates.len(),
+    );
+
+    Ok(Some(SyncedBlock {
+        block,
+        qcs,
+        updates,
+        transactions,
+    }))
 }
@@ -0,0 +1,80 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tari_common_types::types::FixedHash;
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
+use tari_dan_storage::{consensus_models::Block, StateStore, StateStoreWriteTransaction};
+use tari_state_store_sqlite::SqliteStateStore;
+
+/// The number of heights between the justify block and the proposal, all of which are filled with dummy blocks
+const GAP: u64 = 500;
+
+fn create_db() -> (SqliteStateStore<String>, Vec<Block>) {
+    let db = SqliteStateStore::connect(":memory:").unwrap();
+    let zero_block = Block::zero_block(Default::default());
+    db.with_write_tx(|tx| {
+        zero_block.justify().insert(tx)?;
+        zero_block.insert(tx)
+    })
+    .unwrap();
+
+    let justify = zero_block.justify().clone();
+
+    let mut parent = *zero_block.id();
+    let dummy_blocks = (1..=GAP)
+        .map(|i| {
+            let block = Block::dummy_block(
+                Default::default(),
+                parent,
+                Default::default(),
+                NodeHeight(i),
+                justify.clone(),
+                Epoch(0),
+                Shard::from(0),
+                FixedHash::zero(),
+                0,
+                0,
+                FixedHash::zero(),
+            );
+            parent = *block.id();
+            block
+        })
+        .collect();
+    (db, dummy_blocks)
+}
+
+fn dummy_blocks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dummy_blocks");
+
+    group.bench_function("save_one_by_one", |b| {
+        b.iter_batched(
+            create_db,
+            |(db, dummy_blocks)| {
+                let mut tx = db.create_write_tx().unwrap();
+                for block in &dummy_blocks {
+                    block.save(&mut tx).unwrap();
+                }
+                tx.commit().unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("save_all", |b| {
+        b.iter_batched(
+            create_db,
+            |(db, dummy_blocks)| {
+                let mut tx = db.create_write_tx().unwrap();
+                Block::save_all(&mut tx, &dummy_blocks).unwrap();
+                tx.commit().unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, dummy_blocks);
+criterion_main!(benches);
@@ -40,6 +40,8 @@ create table blocks
 
 -- block_id must be unique. Optimise fetching by block_id
 create unique index blocks_uniq_idx_id on blocks (block_id);
+-- Optimise fetching the blocks proposed for a height when checking for proposer equivocation
+create index blocks_idx_epoch_height on blocks (epoch, height);
 
 create table parked_blocks
 (
@@ -62,6 +64,9 @@ create table parked_blocks
     timestamp               bigint    not NULL,
     base_layer_block_height bigint    not NULL,
     base_layer_block_hash   text      not NULL,
+    -- Unix timestamp (seconds) at which the missing transactions were last requested from the proposer
+    transactions_requested_at    bigint    NULL,
+    transaction_request_attempts int       not NULL DEFAULT 0,
     created_at              timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -94,6 +99,18 @@ create table block_diffs
 );
 create index block_diffs_idx_block_id on block_diffs (block_id);
 
+-- A bounded buffer of committed blocks and the substate changes that they applied, in commit order
+create table committed_block_diffs
+(
+    -- The sequence number of the commit, used as the cursor when streaming committed diffs
+    id         integer   NOT NULL primary key AUTOINCREMENT,
+    block_id   text      NOT NULL,
+    block      text      NOT NULL,
+    changes    text      NOT NULL,
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create unique index committed_block_diffs_uniq_idx_block_id on committed_block_diffs (block_id);
+
 create table substates
 (
     id                       integer   not NULL primary key AUTOINCREMENT,
@@ -123,6 +140,48 @@ create unique index substates_uniq_substate_id_and_version on substates (substat
 create index substates_idx_created_by_transaction on substates (created_by_transaction);
 create index substates_idx_destroyed_by_transaction on substates (destroyed_by_transaction) where destroyed_by_transaction is not null;
 
+-- Periodic samples of the number and size of the substates in the shard of this validator, used to monitor growth
+create table shard_growth_samples
+(
+    id             integer   not NULL primary key AUTOINCREMENT,
+    epoch          bigint    not NULL,
+    shard          integer   not NULL,
+    substate_count bigint    not NULL,
+    total_bytes    bigint    not NULL,
+    count_delta    bigint    not NULL,
+    bytes_delta    bigint    not NULL,
+    interval_secs  bigint    not NULL,
+    sampled_at     bigint    not NULL,
+    created_at     timestamp not NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+create index shard_growth_samples_idx_shard on shard_growth_samples (shard);
+
+-- The vault that holds each non-fungible token in this shard, maintained from committed substate changes
+create table nft_ownership
+(
+    id               integer   not NULL primary key AUTOINCREMENT,
+    resource_address text      not NULL,
+    nft_id           text      not NULL,
+    vault_id         text      not NULL,
+    created_at       timestamp not NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+create unique index nft_ownership_uniq_resource_address_nft_id on nft_ownership (resource_address, nft_id);
+create index nft_ownership_idx_vault_id on nft_ownership (vault_id);
+
+-- The component that owns each vault referenced by a committed component in this shard
+create table vault_owners
+(
+    id                integer   not NULL primary key AUTOINCREMENT,
+    vault_id          text      not NULL,
+    component_address text      not NULL,
+    created_at        timestamp not NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+create unique index vault_owners_uniq_vault_id on vault_owners (vault_id);
+create index vault_owners_idx_component_address on vault_owners (component_address);
+
 create table substate_locks
 (
     id             integer   NOT NULL primary key AUTOINCREMENT,
@@ -197,6 +256,30 @@ create table locked_block
     FOREIGN KEY (block_id) REFERENCES blocks (block_id)
 );
 
+-- The last block committed by block sync, used to resume an interrupted sync
+create table sync_checkpoints
+(
+    id         integer   not null primary key autoincrement,
+    block_id   text      not null,
+    height     bigint    not null,
+    epoch      bigint    not null,
+    created_at timestamp NOT NULL default current_timestamp,
+    FOREIGN KEY (block_id) REFERENCES blocks (block_id)
+);
+
+-- The checkpoint carried by the last committed block of each epoch
+create table epoch_checkpoints
+(
+    id                 integer   not null primary key autoincrement,
+    epoch              bigint    not null,
+    block_id           text      not null,
+    state_merkle_root  text      not null,
+    validator_set_hash text      not null,
+    created_at         timestamp NOT NULL default current_timestamp,
+    UNIQUE (epoch),
+    FOREIGN KEY (block_id) REFERENCES blocks (block_id)
+);
+
 create table transactions
 (
     id                integer   not null primary key AUTOINCREMENT,
@@ -215,6 +298,7 @@ create table transactions
     abort_details     text      NULL,
     min_epoch         BIGINT    NULL,
     max_epoch         BIGINT    NULL,
+    priority_fee      bigint    not NULL DEFAULT 0,
     created_at        timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -248,9 +332,15 @@ create table transaction_pool
     transaction_fee     bigint    not null,
     leader_fee          bigint    null,
     global_exhaust_burn bigint    null,
+    priority_fee        bigint    not null DEFAULT 0,
+    max_fee             bigint    not null DEFAULT 0,
     stage               text      not null,
     pending_stage       text      null,
     is_ready            boolean   not null,
+    -- The number of times that execution of a deferred transaction has failed when proposing it
+    deferred_execution_attempts integer not null DEFAULT 0,
+    -- The height of the leaf block when the transaction was added to the pool
+    first_seen_height   bigint    not null,
     updated_at          timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
     created_at          timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id)
@@ -280,12 +370,107 @@ create table votes
     hash             text      not null,
     epoch            bigint    not null,
     block_id         text      not NULL,
+    height           bigint    not null,
     decision         integer   not null,
     sender_leaf_hash text      not NULL,
     signature        text      not NULL,
     created_at       timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
+create index votes_idx_epoch_height_sender_leaf_hash on votes (epoch, height, sender_leaf_hash);
+
+-- Vote arrival times for blocks whose votes were collected by this node
+create table qc_timings
+(
+    id                integer   not null primary key AUTOINCREMENT,
+    block_id          text      not NULL,
+    epoch             bigint    not null,
+    height            bigint    not null,
+    first_vote_ms     bigint    not null,
+    quorum_reached_ms bigint    NULL,
+    last_vote_ms      bigint    not null,
+    votes             text      not NULL,
+    created_at        timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (block_id)
+);
+
+create index qc_timings_idx_epoch on qc_timings (epoch);
+
+-- Evidence of leaders that proposed two different blocks for the same height. Only the first equivocation of a
+-- proposer at a height is recorded.
+create table proposer_equivocations
+(
+    id              integer   not null primary key AUTOINCREMENT,
+    epoch           bigint    not null,
+    height          bigint    not null,
+    proposed_by     text      not NULL,
+    first_block_id  text      not NULL,
+    second_block_id text      not NULL,
+    first_block     text      not NULL,
+    second_block    text      not NULL,
+    created_at      timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (epoch, height, proposed_by)
+);
+
+-- Evidence of validators that voted for two different blocks at the same height. Only the first equivocation of a
+-- voter at a height is recorded.
+create table equivocation_evidence
+(
+    id               integer   not null primary key AUTOINCREMENT,
+    epoch            bigint    not null,
+    height           bigint    not null,
+    sender_leaf_hash text      not NULL,
+    voter            text      not NULL,
+    first_block_id   text      not NULL,
+    second_block_id  text      not NULL,
+    first_vote       text      not NULL,
+    second_vote      text      not NULL,
+    created_at       timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (epoch, height, sender_leaf_hash)
+);
+
+-- Local proposals that failed validation, so that operators can see why proposals from a peer are being rejected
+create table block_rejections
+(
+    id          integer   not null primary key AUTOINCREMENT,
+    block_id    text      not NULL,
+    epoch       bigint    not null,
+    height      bigint    not null,
+    proposed_by text      not NULL,
+    reason      text      not NULL,
+    created_at  timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+create index block_rejections_idx_epoch on block_rejections (epoch);
+
+-- The misbehavior score of peers that sent invalid consensus messages. Times are in seconds since the unix epoch.
+-- Messages from a peer are dropped until banned_until.
+create table peer_misbehaviors
+(
+    id           integer   not null primary key AUTOINCREMENT,
+    address      text      not NULL,
+    score        bigint    not null,
+    last_offence bigint    not null,
+    reason       text      not NULL,
+    banned_until bigint    NULL,
+    created_at   timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (address)
+);
+
+-- NEWVIEW messages received while this node is the leader of a view. Only the first NEWVIEW of a sender for a view is
+-- recorded.
+create table new_views
+(
+    id             integer   not null primary key AUTOINCREMENT,
+    epoch          bigint    not null,
+    new_height     bigint    not null,
+    sender         text      not NULL,
+    high_qc_id     text      not NULL,
+    high_qc_height bigint    not null,
+    created_at     timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (epoch, new_height, sender)
+);
+
 
 CREATE TABLE missing_transactions
 (
@@ -307,9 +492,39 @@ CREATE TABLE foreign_proposals
     proposed_height         bigint    NULL,
     transactions            text      not NULL,
     base_layer_block_height bigint    not NULL,
+    -- The block height and epoch at which the proposal entered a terminal (Invalid or Expired) state
+    terminal_height         bigint    NULL,
+    terminal_epoch          bigint    NULL,
     created_at              timestamp not NULL DEFAULT CURRENT_TIMESTAMP,
     UNIQUE (bucket, block_id)
 );
+CREATE INDEX foreign_proposals_idx_state on foreign_proposals (state);
+CREATE INDEX foreign_proposals_idx_terminal_epoch on foreign_proposals (terminal_epoch);
+
+-- Foreign blocks whose proposals have been applied, keyed by block hash. Unlike foreign_proposals, rows are never
+-- deleted, so a proposal that is delivered again, e.g. after a sync, is not applied twice.
+CREATE TABLE applied_foreign_proposals
+(
+    id         integer   not NULL primary key AUTOINCREMENT,
+    bucket     int       not NULL,
+    block_id   text      not NULL,
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (block_id)
+);
+CREATE INDEX applied_foreign_proposals_idx_bucket on applied_foreign_proposals (bucket);
+
+-- Foreign proposals that have not been acknowledged by the destination committee
+CREATE TABLE foreign_proposal_outbox
+(
+    id              integer   not NULL primary key AUTOINCREMENT,
+    bucket          int       not NULL,
+    block_id        text      not NULL,
+    created_at      bigint    not NULL,
+    attempts        int       not NULL DEFAULT 0,
+    last_attempt_at bigint    NULL,
+    last_error      text      NULL,
+    UNIQUE (bucket, block_id)
+);
 
 CREATE TABLE foreign_send_counters
 (
@@ -0,0 +1,26 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+const MIB: usize = 1024 * 1024;
+
+/// The maximum sizes in bytes of the JSON blobs that are deserialized when records are loaded. A blob larger than its
+/// limit is rejected with a `BlobTooLarge` error instead of being decoded, so that a corrupt row cannot exhaust memory.
+#[derive(Debug, Clone, Copy)]
+pub struct BlobLimits {
+    /// The commands of a block
+    pub max_block_commands_size: usize,
+    /// The JSON of a quorum certificate, including those that justify blocks
+    pub max_quorum_certificate_size: usize,
+    /// The evidence of a transaction pool record or state update
+    pub max_evidence_size: usize,
+}
+
+impl Default for BlobLimits {
+    fn default() -> Self {
+        Self {
+            max_block_commands_size: 64 * MIB,
+            max_quorum_certificate_size: 4 * MIB,
+            max_evidence_size: 4 * MIB,
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 use tari_dan_common_types::optional::IsNotFoundError;
 use tari_dan_storage::StorageError;
+use tari_dan_storage_sqlite::error::is_database_busy;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -39,6 +40,19 @@ pub enum SqliteStorageError {
     SubstatesWriteLocked { operation: &'static str },
     #[error("[{operation}] lock error: {details}")]
     SubstatesUnlock { operation: &'static str, details: String },
+    #[error("Database {path} is corrupt: {details}")]
+    DatabaseCorrupt { path: String, details: String },
+    #[error("Failed to recover database {path}: {details}")]
+    RecoveryFailed { path: String, details: String },
+    #[error("Database connection mutex poisoned: {details}")]
+    ConnectionPoisoned { details: String },
+}
+
+impl SqliteStorageError {
+    /// Returns true if the query failed because another connection holds a lock on the database
+    pub fn is_database_busy(&self) -> bool {
+        matches!(self, SqliteStorageError::DieselError { source, .. } if is_database_busy(source))
+    }
 }
 
 impl From<SqliteStorageError> for StorageError {
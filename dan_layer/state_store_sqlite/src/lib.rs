@@ -1,14 +1,25 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+mod blob_limits;
+pub use blob_limits::BlobLimits;
+
 mod error;
 mod reader;
+mod recovery;
+pub use recovery::{latest_backup, RecoveryPolicy, RecoveryReport};
+
 mod schema;
 mod serialization;
+#[cfg(feature = "fuzzing")]
+pub mod sql_models;
+#[cfg(not(feature = "fuzzing"))]
 mod sql_models;
 mod sqlite_transaction;
 mod store;
 mod tree_store;
+mod write_queue;
+pub use write_queue::{WriteQueueConfig, WriteQueueMonitor, WriteQueueStats};
 mod writer;
 
 pub use store::SqliteStateStore;
@@ -3,7 +3,7 @@
 
 use std::{
     borrow::Borrow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     marker::PhantomData,
     ops::RangeInclusive,
     str::FromStr,
@@ -14,7 +14,8 @@ use diesel::{
     dsl,
     query_builder::SqlQuery,
     sql_query,
-    sql_types::{BigInt, Text},
+    sql_types::{BigInt, Bool, Text},
+    sqlite::Sqlite,
     BoolExpressionMethods,
     ExpressionMethods,
     JoinOnDsl,
@@ -29,14 +30,21 @@ use indexmap::IndexMap;
 use log::*;
 use serde::{de::DeserializeOwned, Serialize};
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_common_types::{Epoch, NodeAddressable, NodeHeight, SubstateAddress};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeAddressable, NodeHeight, SubstateAddress};
 use tari_dan_storage::{
     consensus_models::{
+        select_non_conflicting_transactions,
         Block,
+        BlockCursor,
         BlockDiff,
         BlockId,
+        BlockRejection,
         Command,
+        CommittedBlockDiff,
+        EpochCheckpoint,
+        EquivocationEvidence,
         ForeignProposal,
+        ForeignProposalOutboxEntry,
         ForeignProposalState,
         ForeignReceiveCounters,
         ForeignSendCounters,
@@ -48,11 +56,25 @@ use tari_dan_storage::{
         LeafBlock,
         LockedBlock,
         LockedSubstate,
+        MissingTransactionsRequest,
+        NftOwnership,
+        PeerMisbehavior,
         PendingStateTreeDiff,
+        ProposerEquivocation,
+        PruneSafetyInfo,
         QcId,
+        QcTiming,
         QuorumCertificate,
+        RecentTransaction,
+        RecentTransactionFilter,
+        ShardGrowthSample,
+        SubstateLockFlag,
         SubstateRecord,
+        SyncCheckpoint,
+        TransactionConflictEdge,
+        TransactionCursor,
         TransactionExecution,
+        TransactionPoolOrdering,
         TransactionPoolRecord,
         TransactionPoolStage,
         TransactionRecord,
@@ -62,13 +84,23 @@ use tari_dan_storage::{
     StateStoreReadTransaction,
     StorageError,
 };
-use tari_engine_types::substate::SubstateId;
+use tari_engine_types::{fee_claim::FeeClaimAddress, substate::SubstateId};
+use tari_template_lib::models::{ComponentAddress, NonFungibleId, ResourceAddress, VaultId};
 use tari_transaction::{SubstateRequirement, TransactionId, VersionedSubstateId};
 use tari_utilities::ByteArray;
+use time::PrimitiveDateTime;
 
 use crate::{
+    blob_limits::BlobLimits,
     error::SqliteStorageError,
-    serialization::{deserialize_hex_try_from, deserialize_json, serialize_hex, serialize_json},
+    serialization::{
+        deserialize_hex,
+        deserialize_hex_try_from,
+        deserialize_json,
+        deserialize_json_bounded,
+        serialize_hex,
+        serialize_json,
+    },
     sql_models,
     sqlite_transaction::SqliteTransaction,
 };
@@ -77,13 +109,15 @@ const LOG_TARGET: &str = "tari::dan::storage::state_store_sqlite::reader";
 
 pub struct SqliteStateStoreReadTransaction<'a, TAddr> {
     transaction: SqliteTransaction<'a>,
+    blob_limits: BlobLimits,
     _addr: PhantomData<TAddr>,
 }
 
 impl<'a, TAddr> SqliteStateStoreReadTransaction<'a, TAddr> {
-    pub(crate) fn new(transaction: SqliteTransaction<'a>) -> Self {
+    pub(crate) fn new(transaction: SqliteTransaction<'a>, blob_limits: BlobLimits) -> Self {
         Self {
             transaction,
+            blob_limits,
             _addr: PhantomData,
         }
     }
@@ -92,6 +126,10 @@ impl<'a, TAddr> SqliteStateStoreReadTransaction<'a, TAddr> {
         self.transaction.connection()
     }
 
+    pub(crate) fn blob_limits(&self) -> &BlobLimits {
+        &self.blob_limits
+    }
+
     pub(crate) fn commit(self) -> Result<(), SqliteStorageError> {
         self.transaction.commit()
     }
@@ -417,6 +455,20 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         high_qc.try_into()
     }
 
+    fn sync_checkpoint_get(&self) -> Result<SyncCheckpoint, StorageError> {
+        use crate::schema::sync_checkpoints;
+
+        let checkpoint = sync_checkpoints::table
+            .order_by(sync_checkpoints::id.desc())
+            .first::<sql_models::SyncCheckpoint>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "sync_checkpoint_get",
+                source: e,
+            })?;
+
+        checkpoint.try_into()
+    }
+
     fn foreign_proposal_exists(&self, foreign_proposal: &ForeignProposal) -> Result<bool, StorageError> {
         use crate::schema::foreign_proposals;
 
@@ -425,6 +477,11 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .filter(foreign_proposals::block_id.eq(serialize_hex(foreign_proposal.block_id)))
             .filter(foreign_proposals::transactions.eq(serialize_json(&foreign_proposal.transactions)?))
             .filter(foreign_proposals::base_layer_block_height.eq(foreign_proposal.base_layer_block_height as i64))
+            // Invalid and expired proposals are only kept for inspection
+            .filter(foreign_proposals::state.ne_all([
+                ForeignProposalState::Invalid.to_string(),
+                ForeignProposalState::Expired.to_string(),
+            ]))
             .count()
             .limit(1)
             .get_result::<i64>(self.connection())
@@ -494,6 +551,86 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         foreign_proposals.into_iter().map(|p| p.try_into()).collect()
     }
 
+    fn foreign_proposal_get_all_by_state(
+        &self,
+        state: ForeignProposalState,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<ForeignProposal>, StorageError> {
+        use crate::schema::foreign_proposals;
+
+        let foreign_proposals = foreign_proposals::table
+            .filter(foreign_proposals::state.eq(state.to_string()))
+            .order_by(foreign_proposals::id.asc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .load::<sql_models::ForeignProposal>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_get_all_by_state",
+                source: e,
+            })?;
+
+        foreign_proposals.into_iter().map(|p| p.try_into()).collect()
+    }
+
+    fn foreign_proposal_outbox_get_all(&self) -> Result<Vec<ForeignProposalOutboxEntry>, StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        let entries = foreign_proposal_outbox::table
+            .order_by(foreign_proposal_outbox::id.asc())
+            .load::<sql_models::ForeignProposalOutboxEntry>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_get_all",
+                source: e,
+            })?;
+
+        entries.into_iter().map(|e| e.try_into()).collect()
+    }
+
+    fn foreign_proposal_outbox_count(&self) -> Result<u64, StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        let count = foreign_proposal_outbox::table
+            .count()
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
+    fn applied_foreign_proposals_exists(&self, block_id: &BlockId) -> Result<bool, StorageError> {
+        use crate::schema::applied_foreign_proposals;
+
+        let count = applied_foreign_proposals::table
+            .filter(applied_foreign_proposals::block_id.eq(serialize_hex(block_id)))
+            .count()
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "applied_foreign_proposals_exists",
+                source: e,
+            })?;
+
+        Ok(count > 0)
+    }
+
+    fn applied_foreign_proposals_count(&self, bucket: Shard) -> Result<u64, StorageError> {
+        use crate::schema::applied_foreign_proposals;
+
+        let count = applied_foreign_proposals::table
+            .filter(applied_foreign_proposals::bucket.eq(bucket.as_u32() as i32))
+            .count()
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "applied_foreign_proposals_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
     fn foreign_send_counters_get(&self, block_id: &BlockId) -> Result<ForeignSendCounters, StorageError> {
         use crate::schema::foreign_send_counters;
 
@@ -606,6 +743,154 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .collect()
     }
 
+    fn transactions_get_after(
+        &self,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+        ordering: Ordering,
+    ) -> Result<(Vec<TransactionRecord>, Option<TransactionCursor>), StorageError> {
+        use crate::schema::transactions;
+
+        let created_at = created_at_millis_sql("transactions");
+        let mut query = transactions::table.into_boxed();
+
+        if let Some(cursor) = cursor {
+            let op = cursor_operator(ordering);
+            query = query.filter(
+                dsl::sql::<Bool>(&format!("(({created_at}) {op} "))
+                    .bind::<BigInt, _>(cursor.created_at as i64)
+                    .sql(&format!(" OR (({created_at}) = "))
+                    .bind::<BigInt, _>(cursor.created_at as i64)
+                    .sql(&format!(" AND transactions.id {op} "))
+                    .bind::<BigInt, _>(cursor.id as i64)
+                    .sql("))"),
+            );
+        }
+
+        query = match ordering {
+            Ordering::Ascending => query
+                .order_by(dsl::sql::<BigInt>(&created_at).asc())
+                .then_order_by(transactions::id.asc()),
+            Ordering::Descending => query
+                .order_by(dsl::sql::<BigInt>(&created_at).desc())
+                .then_order_by(transactions::id.desc()),
+        };
+
+        let transactions = query
+            .limit(limit as i64)
+            .get_results::<sql_models::Transaction>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transactions_get_after",
+                source: e,
+            })?;
+
+        let next_cursor = transactions
+            .last()
+            .map(|t| TransactionCursor {
+                created_at: timestamp_millis(t.created_at),
+                id: t.id as u64,
+            })
+            .or_else(|| cursor.copied());
+
+        let transactions = transactions
+            .into_iter()
+            .map(|transaction| transaction.try_into())
+            .collect::<Result<_, _>>()?;
+
+        Ok((transactions, next_cursor))
+    }
+
+    fn transactions_get_recent_filtered(
+        &self,
+        filter: &RecentTransactionFilter,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+    ) -> Result<(Vec<RecentTransaction>, Option<TransactionCursor>), StorageError> {
+        let created_at = created_at_millis_sql("transactions");
+        // Joins the latest execution of each transaction. The fee is read from the JSON-encoded execution result.
+        let mut query = sql_query(format!(
+            r#"
+            SELECT
+                transactions.id,
+                transactions.transaction_id,
+                transactions.final_decision,
+                transactions.abort_details,
+                json_extract(transaction_executions.result, '$.finalize.fee_receipt.total_fees_paid') AS fee,
+                transaction_executions.block_id,
+                {created_at} AS created_at_millis
+            FROM transactions
+            LEFT JOIN transaction_executions ON transaction_executions.id = (
+                SELECT MAX(latest.id) FROM transaction_executions AS latest
+                WHERE latest.transaction_id = transactions.transaction_id
+            )
+            LEFT JOIN blocks ON blocks.block_id = transaction_executions.block_id
+            WHERE 1 = 1"#
+        ))
+        .into_boxed::<Sqlite>();
+
+        if let Some(decision) = filter.decision {
+            query = query
+                .sql(" AND transactions.final_decision = ")
+                .bind::<Text, _>(decision.to_string());
+        }
+        if let Some(from_epoch) = filter.from_epoch {
+            query = query
+                .sql(" AND blocks.epoch >= ")
+                .bind::<BigInt, _>(from_epoch.as_u64() as i64);
+        }
+        if let Some(to_epoch) = filter.to_epoch {
+            query = query
+                .sql(" AND blocks.epoch <= ")
+                .bind::<BigInt, _>(to_epoch.as_u64() as i64);
+        }
+        if let Some(substate_id) = &filter.involved_substate_id {
+            // Substate ids are serialized as strings in the JSON input and output columns
+            let quoted_id = format!("\"{}\"", substate_id);
+            query = query
+                .sql(" AND (instr(transactions.inputs, ")
+                .bind::<Text, _>(quoted_id.clone())
+                .sql(") > 0 OR instr(transactions.filled_inputs, ")
+                .bind::<Text, _>(quoted_id.clone())
+                .sql(") > 0 OR instr(transaction_executions.resulting_outputs, ")
+                .bind::<Text, _>(quoted_id)
+                .sql(") > 0)");
+        }
+        if let Some(cursor) = cursor {
+            query = query
+                .sql(format!(" AND (({created_at}) < "))
+                .bind::<BigInt, _>(cursor.created_at as i64)
+                .sql(format!(" OR (({created_at}) = "))
+                .bind::<BigInt, _>(cursor.created_at as i64)
+                .sql(" AND transactions.id < ")
+                .bind::<BigInt, _>(cursor.id as i64)
+                .sql("))");
+        }
+
+        let transactions = query
+            .sql(" ORDER BY created_at_millis DESC, transactions.id DESC LIMIT ")
+            .bind::<BigInt, _>(limit as i64)
+            .load::<sql_models::RecentTransaction>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transactions_get_recent_filtered",
+                source: e,
+            })?;
+
+        let next_cursor = transactions
+            .last()
+            .map(|t| TransactionCursor {
+                created_at: t.created_at_millis as u64,
+                id: t.id as u64,
+            })
+            .or_else(|| cursor.copied());
+
+        let transactions = transactions
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok((transactions, next_cursor))
+    }
+
     fn transaction_executions_get(
         &self,
         tx_id: &TransactionId,
@@ -669,7 +954,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             ),
         })?;
 
-        block.try_convert(qc)
+        block.try_convert(qc, self.blob_limits())
     }
 
     fn blocks_get_tip(&self) -> Result<Block, StorageError> {
@@ -693,7 +978,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             ),
         })?;
 
-        block.try_convert(qc)
+        block.try_convert(qc, self.blob_limits())
     }
 
     fn blocks_get_all_between(
@@ -741,7 +1026,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                     ),
                 })?;
 
-                block.try_convert(qc)
+                block.try_convert(qc, self.blob_limits())
             })
             .collect()
     }
@@ -826,7 +1111,64 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                     ),
                 })?;
 
-                block.try_convert(qc)
+                block.try_convert(qc, self.blob_limits())
+            })
+            .collect()
+    }
+
+    fn blocks_get_certified_child(&self, parent_id: &BlockId) -> Result<Block, StorageError> {
+        use crate::schema::{blocks, quorum_certificates};
+
+        let parent_id = serialize_hex(parent_id);
+        let (block, qc) = blocks::table
+            .inner_join(quorum_certificates::table.on(blocks::qc_id.eq(quorum_certificates::qc_id)))
+            .select((blocks::all_columns, quorum_certificates::all_columns))
+            .filter(blocks::parent_block_id.eq(&parent_id))
+            .filter(quorum_certificates::block_id.eq(&parent_id))
+            .filter(blocks::block_id.ne(blocks::parent_block_id)) // Exclude the genesis block
+            .filter(blocks::block_id.eq_any(quorum_certificates::table.select(quorum_certificates::block_id)))
+            .first::<(sql_models::Block, sql_models::QuorumCertificate)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_get_certified_child",
+                source: e,
+            })?;
+
+        block.try_convert(qc, self.blob_limits())
+    }
+
+    fn blocks_get_all_by_proposer_at_height(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<Vec<Block>, StorageError> {
+        use crate::schema::{blocks, quorum_certificates};
+
+        let results = blocks::table
+            .left_join(quorum_certificates::table.on(blocks::qc_id.eq(quorum_certificates::qc_id)))
+            .select((blocks::all_columns, quorum_certificates::all_columns.nullable()))
+            .filter(blocks::epoch.eq(epoch.as_u64() as i64))
+            .filter(blocks::height.eq(height.as_u64() as i64))
+            .filter(blocks::proposed_by.eq(serialize_hex(proposed_by.as_bytes())))
+            .filter(blocks::is_dummy.eq(false))
+            .get_results::<(sql_models::Block, Option<sql_models::QuorumCertificate>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_get_all_by_proposer_at_height",
+                source: e,
+            })?;
+
+        results
+            .into_iter()
+            .map(|(block, qc)| {
+                let qc = qc.ok_or_else(|| SqliteStorageError::DbInconsistency {
+                    operation: "blocks_get_all_by_proposer_at_height",
+                    details: format!(
+                        "block {} references non-existent quorum certificate {}",
+                        block.block_id, block.qc_id
+                    ),
+                })?;
+
+                block.try_convert(qc, self.blob_limits())
             })
             .collect()
     }
@@ -871,7 +1213,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                     ),
                 })?;
 
-                b.try_convert(qc)
+                b.try_convert(qc, self.blob_limits())
             })
             .collect()
     }
@@ -946,7 +1288,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                     ),
                 })?;
 
-                block.try_convert(qc)
+                block.try_convert(qc, self.blob_limits())
             })
             .collect()
     }
@@ -1048,11 +1390,81 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                     ),
                 })?;
 
-                block.try_convert(qc)
+                block.try_convert(qc, self.blob_limits())
             })
             .collect()
     }
 
+    fn blocks_get_after(
+        &self,
+        cursor: Option<&BlockCursor>,
+        limit: u64,
+        ordering: Ordering,
+    ) -> Result<(Vec<Block>, Option<BlockCursor>), StorageError> {
+        use crate::schema::{blocks, quorum_certificates};
+
+        let created_at = created_at_millis_sql("blocks");
+        let mut query = blocks::table
+            .left_join(quorum_certificates::table.on(blocks::qc_id.eq(quorum_certificates::qc_id)))
+            .select((blocks::all_columns, quorum_certificates::all_columns.nullable()))
+            .into_boxed();
+
+        if let Some(cursor) = cursor {
+            let op = cursor_operator(ordering);
+            query = query.filter(
+                dsl::sql::<Bool>(&format!("(({created_at}) {op} "))
+                    .bind::<BigInt, _>(cursor.created_at as i64)
+                    .sql(&format!(" OR (({created_at}) = "))
+                    .bind::<BigInt, _>(cursor.created_at as i64)
+                    .sql(&format!(" AND blocks.id {op} "))
+                    .bind::<BigInt, _>(cursor.id as i64)
+                    .sql("))"),
+            );
+        }
+
+        query = match ordering {
+            Ordering::Ascending => query
+                .order_by(dsl::sql::<BigInt>(&created_at).asc())
+                .then_order_by(blocks::id.asc()),
+            Ordering::Descending => query
+                .order_by(dsl::sql::<BigInt>(&created_at).desc())
+                .then_order_by(blocks::id.desc()),
+        };
+
+        let blocks = query
+            .limit(limit as i64)
+            .get_results::<(sql_models::Block, Option<sql_models::QuorumCertificate>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_get_after",
+                source: e,
+            })?;
+
+        let next_cursor = blocks
+            .last()
+            .map(|(block, _)| BlockCursor {
+                created_at: timestamp_millis(block.created_at),
+                id: block.id as u64,
+            })
+            .or_else(|| cursor.copied());
+
+        let blocks = blocks
+            .into_iter()
+            .map(|(block, qc)| {
+                let qc = qc.ok_or_else(|| SqliteStorageError::DbInconsistency {
+                    operation: "blocks_get_after",
+                    details: format!(
+                        "block {} references non-existent quorum certificate {}",
+                        block.id, block.qc_id
+                    ),
+                })?;
+
+                block.try_convert(qc, self.blob_limits())
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((blocks, next_cursor))
+    }
+
     fn blocks_get_count(&self) -> Result<i64, StorageError> {
         use crate::schema::{blocks, quorum_certificates};
         let count = blocks::table
@@ -1066,6 +1478,26 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count)
     }
 
+    fn blocks_count_dummy(&self, epoch: Option<Epoch>) -> Result<u64, StorageError> {
+        use crate::schema::blocks;
+
+        let mut query = blocks::table.filter(blocks::is_dummy.eq(true)).into_boxed();
+        if let Some(epoch) = epoch {
+            query = query.filter(blocks::epoch.eq(epoch.as_u64() as i64));
+        }
+
+        let count =
+            query
+                .count()
+                .get_result::<i64>(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "blocks_count_dummy",
+                    source: e,
+                })?;
+
+        Ok(count as u64)
+    }
+
     fn filtered_blocks_get_count(
         &self,
         filter_index: Option<usize>,
@@ -1153,6 +1585,44 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         sql_models::BlockDiff::try_load(*block_id, block_diff)
     }
 
+    fn committed_block_diffs_get(&self, sequence: u64) -> Result<CommittedBlockDiff, StorageError> {
+        use crate::schema::committed_block_diffs;
+
+        let diff = committed_block_diffs::table
+            .filter(committed_block_diffs::id.eq(sequence as i32))
+            .first::<sql_models::CommittedBlockDiff>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "committed_block_diffs_get",
+                source: e,
+            })?;
+
+        diff.try_into()
+    }
+
+    fn committed_block_diffs_get_after(
+        &self,
+        after_sequence: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<CommittedBlockDiff>, StorageError> {
+        use crate::schema::committed_block_diffs;
+
+        let mut query = committed_block_diffs::table.into_boxed();
+        if let Some(after_sequence) = after_sequence {
+            query = query.filter(committed_block_diffs::id.gt(after_sequence as i32));
+        }
+
+        let diffs = query
+            .order_by(committed_block_diffs::id.asc())
+            .limit(limit as i64)
+            .get_results::<sql_models::CommittedBlockDiff>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "committed_block_diffs_get_after",
+                source: e,
+            })?;
+
+        diffs.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn parked_blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError> {
         use crate::schema::parked_blocks;
 
@@ -1171,6 +1641,84 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count > 0)
     }
 
+    fn missing_transactions_get_due_requests(
+        &self,
+        min_height: NodeHeight,
+        requested_before: u64,
+    ) -> Result<Vec<MissingTransactionsRequest>, StorageError> {
+        use crate::schema::{missing_transactions, parked_blocks};
+
+        let parked = parked_blocks::table
+            .select((
+                parked_blocks::block_id,
+                parked_blocks::epoch,
+                parked_blocks::height,
+                parked_blocks::proposed_by,
+                parked_blocks::transactions_requested_at,
+                parked_blocks::transaction_request_attempts,
+            ))
+            .filter(parked_blocks::height.ge(min_height.as_u64() as i64))
+            .filter(
+                parked_blocks::transactions_requested_at
+                    .is_null()
+                    .or(parked_blocks::transactions_requested_at.lt(requested_before as i64)),
+            )
+            .order_by(parked_blocks::height.asc())
+            .get_results::<(String, i64, i64, String, Option<i64>, i32)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "missing_transactions_get_due_requests",
+                source: e,
+            })?;
+
+        if parked.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let missing = missing_transactions::table
+            .select((missing_transactions::block_id, missing_transactions::transaction_id))
+            .filter(missing_transactions::block_id.eq_any(parked.iter().map(|(block_id, ..)| block_id)))
+            .filter(missing_transactions::is_awaiting_execution.eq(false))
+            .get_results::<(String, String)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "missing_transactions_get_due_requests",
+                source: e,
+            })?;
+
+        let mut missing_by_block = HashMap::<_, HashSet<TransactionId>>::with_capacity(parked.len());
+        for (block_id, transaction_id) in missing {
+            missing_by_block
+                .entry(block_id)
+                .or_default()
+                .insert(deserialize_hex_try_from(&transaction_id)?);
+        }
+
+        let mut requests = Vec::with_capacity(missing_by_block.len());
+        for (block_id, epoch, height, proposed_by, requested_at, attempts) in parked {
+            // Blocks that are only awaiting execution have nothing to request
+            let Some(transaction_ids) = missing_by_block.remove(&block_id) else {
+                continue;
+            };
+            let proposed_by = PublicKey::from_canonical_bytes(&deserialize_hex(&proposed_by)?).map_err(|_| {
+                StorageError::DecodingError {
+                    operation: "missing_transactions_get_due_requests",
+                    item: "parked block",
+                    details: format!("Parked block {block_id} proposed_by is malformed"),
+                }
+            })?;
+            requests.push(MissingTransactionsRequest {
+                block_id: deserialize_hex_try_from(&block_id)?,
+                epoch: Epoch(epoch as u64),
+                height: NodeHeight(height as u64),
+                proposed_by,
+                transaction_ids,
+                requested_at: requested_at.map(|t| t as u64),
+                attempts: attempts as u32,
+            });
+        }
+
+        Ok(requests)
+    }
+
     fn quorum_certificates_get(&self, qc_id: &QcId) -> Result<QuorumCertificate, StorageError> {
         use crate::schema::quorum_certificates;
 
@@ -1183,7 +1731,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 source: e,
             })?;
 
-        deserialize_json(&qc_json)
+        deserialize_json_bounded(&qc_json, self.blob_limits().max_quorum_certificate_size)
     }
 
     fn quorum_certificates_get_all<'a, I: IntoIterator<Item = &'a QcId>>(
@@ -1216,7 +1764,10 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .into());
         }
 
-        qc_json.iter().map(|j| deserialize_json(j)).collect()
+        qc_json
+            .iter()
+            .map(|j| deserialize_json_bounded(j, self.blob_limits().max_quorum_certificate_size))
+            .collect()
     }
 
     fn quorum_certificates_get_by_block_id(&self, block_id: &BlockId) -> Result<QuorumCertificate, StorageError> {
@@ -1231,7 +1782,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 source: e,
             })?;
 
-        deserialize_json(&qc_json)
+        deserialize_json_bounded(&qc_json, self.blob_limits().max_quorum_certificate_size)
     }
 
     fn transaction_pool_get(&self, transaction_id: &TransactionId) -> Result<TransactionPoolRecord, StorageError> {
@@ -1246,7 +1797,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 source: e,
             })?;
 
-        rec.try_convert(None)
+        rec.try_convert(None, self.blob_limits())
     }
 
     fn transaction_pool_get_for_blocks(
@@ -1281,7 +1832,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 source: e,
             })?;
 
-        rec.try_convert(updates.remove(&transaction_id))
+        rec.try_convert(updates.remove(&transaction_id), self.blob_limits())
     }
 
     fn transaction_pool_exists(&self, transaction_id: &TransactionId) -> Result<bool, StorageError> {
@@ -1308,17 +1859,42 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 source: e,
             })?;
         // TODO: need to get the updates - this is just used in JRPC so it doesnt matter too much
-        txs.into_iter().map(|tx| tx.try_convert(None)).collect()
+        txs.into_iter()
+            .map(|tx| tx.try_convert(None, self.blob_limits()))
+            .collect()
     }
 
-    fn transaction_pool_get_many_ready(&self, max_txs: usize) -> Result<Vec<TransactionPoolRecord>, StorageError> {
-        use crate::schema::transaction_pool;
+    fn transaction_pool_get_many_ready(
+        &self,
+        max_txs: usize,
+        ordering: TransactionPoolOrdering,
+        exclude_conflicting: bool,
+        epoch: Epoch,
+    ) -> Result<Vec<TransactionPoolRecord>, StorageError> {
+        use crate::schema::{transaction_pool, transactions};
 
-        let ready_txs = transaction_pool::table
-            .order_by(transaction_pool::transaction_id.asc())
-            .get_results::<sql_models::TransactionPoolRecord>(self.connection())
-            .map_err(|e| SqliteStorageError::DieselError {
-                operation: "transaction_pool_get_many_ready",
+        let epoch = epoch.as_u64() as i64;
+        let mut query = transaction_pool::table
+            .filter(
+                transaction_pool::transaction_id.eq_any(
+                    transactions::table
+                        .select(transactions::transaction_id)
+                        .filter(transactions::min_epoch.is_null().or(transactions::min_epoch.le(epoch)))
+                        .filter(transactions::max_epoch.is_null().or(transactions::max_epoch.ge(epoch))),
+                ),
+            )
+            .into_boxed();
+        if ordering == TransactionPoolOrdering::FeePriority {
+            query = query
+                .order_by(transaction_pool::priority_fee.desc())
+                .then_order_by(transaction_pool::max_fee.desc());
+        }
+
+        let ready_txs = query
+            .then_order_by(transaction_pool::transaction_id.asc())
+            .get_results::<sql_models::TransactionPoolRecord>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_pool_get_many_ready",
                 source: e,
             })?;
 
@@ -1345,96 +1921,72 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             updates.len()
         );
 
-        ready_txs
+        let ready_txs = ready_txs
             .into_iter()
             .map(|rec| {
                 let maybe_update = updates.remove(&rec.transaction_id);
-                rec.try_convert(maybe_update)
+                rec.try_convert(maybe_update, self.blob_limits())
             })
             // Filter only Ok where is_ready == true (after update) or Err
-            .filter(|result| result.as_ref().map_or(true, |rec| rec.is_ready()))
+            .filter(|result| result.as_ref().map_or(true, |rec| rec.is_ready()));
+
+        if exclude_conflicting {
+            let ready_txs = ready_txs.collect::<Result<Vec<_>, _>>()?;
+            Ok(select_non_conflicting_transactions(ready_txs, max_txs))
+        } else {
+            ready_txs.take(max_txs).collect()
+        }
+    }
+
+    fn transaction_pool_get_many_expired(
+        &self,
+        max_txs: usize,
+        epoch: Epoch,
+    ) -> Result<Vec<TransactionPoolRecord>, StorageError> {
+        use crate::schema::{transaction_pool, transactions};
+
+        let expired_txs = transaction_pool::table
+            .filter(
+                transaction_pool::transaction_id.eq_any(
+                    transactions::table
+                        .select(transactions::transaction_id)
+                        .filter(transactions::max_epoch.lt(epoch.as_u64() as i64)),
+                ),
+            )
+            .order_by(transaction_pool::transaction_id.asc())
+            .get_results::<sql_models::TransactionPoolRecord>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_pool_get_many_expired",
+                source: e,
+            })?;
+
+        if expired_txs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let locked = self.locked_block_get()?;
+        let leaf = self.leaf_block_get()?;
+
+        let mut updates = self.get_transaction_atom_state_updates_between_blocks(
+            &locked.block_id,
+            &leaf.block_id,
+            expired_txs.iter().map(|s| s.transaction_id.as_str()),
+        )?;
+
+        expired_txs
+            .into_iter()
+            .map(|rec| {
+                let maybe_update = updates.remove(&rec.transaction_id);
+                rec.try_convert(maybe_update, self.blob_limits())
+            })
+            // Transactions that have been prepared are already sequenced and continue through consensus
+            .filter(|result| {
+                result
+                    .as_ref()
+                    .map_or(true, |rec| rec.is_ready() && rec.current_stage().is_new())
+            })
             .take(max_txs)
             .collect()
-        // let mut used_substates = HashMap::<SubstateAddress, SubstateLockFlag>::new();
-        // let mut processed_substates =
-        //     HashMap::<TransactionId, HashSet<(SubstateAddress, _)>>::with_capacity(updates.len());
-        // for (tx_id, update) in &updates {
-        //     if update.local_decision.as_deref() == Some(Decision::Abort.as_str()) {
-        //         // The aborted transaction don't lock any substates
-        //         continue;
-        //     }
-        //     let evidence = deserialize_json::<Evidence>(&update.evidence)?;
-        //     let evidence = evidence
-        //         .iter()
-        //         .map(|(shard, evidence)| (*shard, evidence.lock))
-        //         .collect::<HashSet<(SubstateAddress, _)>>();
-        //     processed_substates.insert(deserialize_hex_try_from(tx_id)?, evidence);
-        // }
-        //
-        // ready_txs
-        //     .into_iter()
-        //     .filter_map(|rec| {
-        //         let maybe_update = updates.remove(&rec.transaction_id);
-        //         match rec.try_convert(maybe_update) {
-        //             Ok(rec) => {
-        //                 if !rec.is_ready() {
-        //                     return None;
-        //                 }
-        //
-        //                 let tx_substates = rec
-        //                     .transaction()
-        //                     .evidence
-        //                     .iter()
-        //                     .map(|(shard, evidence)| (*shard, evidence.lock))
-        //                     .collect::<HashMap<_, _>>();
-        //
-        //                 // Are there any conflicts between the currently selected set and this transaction?
-        //                 if tx_substates.iter().any(|(shard, lock)| {
-        //                     if lock.is_write() {
-        //                         // Write lock must have no conflicts
-        //                         used_substates.contains_key(shard)
-        //                     } else {
-        //                         // If there is a Shard conflict, then it must not be a write lock
-        //                         used_substates
-        //                             .get(shard)
-        //                             .map(|tx_lock| tx_lock.is_write())
-        //                             .unwrap_or(false)
-        //                     }
-        //                 }) {
-        //                     return None;
-        //                 }
-        //
-        //                 // Are there any conflicts between this transaction and other transactions to be included in
-        // the                 // block?
-        //                 if processed_substates
-        //                     .iter()
-        //                     // Check other transactions
-        //                     .filter(|(tx_id, _)| *tx_id != rec.transaction_id())
-        //                     .all(|(_, evidence)| {
-        //                         evidence.iter().all(|(shard, lock)| {
-        //                             if lock.is_write() {
-        //                                 // Write lock must have no conflicts
-        //                                 !tx_substates.contains_key(shard)
-        //                             } else {
-        //                                 // If there is a Shard conflict, then it must be a read lock
-        //                                 tx_substates.get(shard).map(|tx_lock| tx_lock.is_read()).unwrap_or(true)
-        //                             }
-        //                         })
-        //                     })
-        //                 {
-        //                     used_substates.extend(tx_substates);
-        //                     Some(Ok(rec))
-        //                 } else {
-        //                     // TODO: If we don't switch to "no version" transaction, then we can abort these here.
-        //                     // That also requires changes to the on_ready_to_vote_on_local_block
-        //                     None
-        //                 }
-        //             },
-        //             Err(e) => Some(Err(e)),
-        //         }
-        //     })
-        //     .take(max_txs)
-        //     .collect()
     }
 
     fn transaction_pool_count(
@@ -1481,6 +2033,88 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count as usize)
     }
 
+    fn transaction_pool_get_conflict_edges(
+        &self,
+        substate_id: Option<&SubstateId>,
+        limit: usize,
+    ) -> Result<Vec<TransactionConflictEdge>, StorageError> {
+        use crate::schema::{substate_locks, transaction_pool};
+
+        let mut query = substate_locks::table
+            .filter(
+                substate_locks::transaction_id.eq_any(transaction_pool::table.select(transaction_pool::transaction_id)),
+            )
+            .into_boxed();
+
+        // Edges only ever connect locks on the same substate, so filtering the locks yields exactly the edges for the
+        // substate before the limit is applied
+        if let Some(substate_id) = substate_id {
+            query = query.filter(substate_locks::substate_id.eq(substate_id.to_string()));
+        }
+
+        let lock_recs = query
+            .order_by(substate_locks::id.asc())
+            .get_results::<sql_models::SubstateLock>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_pool_get_conflict_edges",
+                source: e,
+            })?;
+
+        // Per substate, the last transaction to acquire a write (or output) lock and the readers that have locked it
+        // since then.
+        let mut holders = HashMap::<SubstateId, (Option<(TransactionId, SubstateLockFlag)>, Vec<TransactionId>)>::new();
+        let mut seen = HashSet::with_capacity(lock_recs.len());
+        let mut edges = Vec::new();
+        for rec in lock_recs {
+            if edges.len() >= limit {
+                break;
+            }
+            // The same lock may be recorded by more than one (possibly forked) block
+            if !seen.insert((rec.substate_id.clone(), rec.transaction_id.clone())) {
+                continue;
+            }
+
+            let substate_id = SubstateId::from_str(&rec.substate_id).map_err(|e| SqliteStorageError::MalformedDbData {
+                operation: "transaction_pool_get_conflict_edges",
+                details: format!("'{}' is not a valid SubstateId: {}", rec.substate_id, e),
+            })?;
+            let lock = rec.try_into_substate_lock()?;
+            let (last_writer, readers) = holders.entry(substate_id.clone()).or_default();
+
+            let mut add_edge = |from: TransactionId, from_lock: SubstateLockFlag| {
+                if from != lock.transaction_id() && edges.len() < limit {
+                    edges.push(TransactionConflictEdge {
+                        from,
+                        to: lock.transaction_id(),
+                        substate_id: substate_id.clone(),
+                        from_lock,
+                        to_lock: lock.substate_lock(),
+                    });
+                }
+            };
+
+            if lock.is_read() {
+                if let Some((writer, writer_lock)) = *last_writer {
+                    add_edge(writer, writer_lock);
+                }
+                readers.push(lock.transaction_id());
+            } else {
+                if readers.is_empty() {
+                    if let Some((writer, writer_lock)) = *last_writer {
+                        add_edge(writer, writer_lock);
+                    }
+                } else {
+                    for reader in readers.drain(..) {
+                        add_edge(reader, SubstateLockFlag::Read);
+                    }
+                }
+                *last_writer = Some((lock.transaction_id(), lock.substate_lock()));
+            }
+        }
+
+        Ok(edges)
+    }
+
     fn transactions_fetch_involved_shards(
         &self,
         transaction_ids: HashSet<TransactionId>,
@@ -1574,6 +2208,201 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         votes.into_iter().map(Vote::try_from).collect()
     }
 
+    fn votes_get_by_sender_at_height(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<Vec<Vote>, StorageError> {
+        use crate::schema::votes;
+
+        let votes = votes::table
+            .filter(votes::epoch.eq(epoch.as_u64() as i64))
+            .filter(votes::height.eq(height.as_u64() as i64))
+            .filter(votes::sender_leaf_hash.eq(serialize_hex(sender_leaf_hash)))
+            .order_by(votes::id.asc())
+            .get_results::<sql_models::Vote>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "votes_get_by_sender_at_height",
+                source: e,
+            })?;
+
+        votes.into_iter().map(Vote::try_from).collect()
+    }
+
+    fn qc_timings_get_by_epoch(&self, epoch: Epoch) -> Result<Vec<QcTiming>, StorageError> {
+        use crate::schema::qc_timings;
+
+        let timings = qc_timings::table
+            .filter(qc_timings::epoch.eq(epoch.as_u64() as i64))
+            .order_by(qc_timings::height.asc())
+            .get_results::<sql_models::QcTiming>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "qc_timings_get_by_epoch",
+                source: e,
+            })?;
+
+        timings.into_iter().map(QcTiming::try_from).collect()
+    }
+
+    fn proposer_equivocations_get(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<ProposerEquivocation, StorageError> {
+        use crate::schema::proposer_equivocations;
+
+        let equivocation = proposer_equivocations::table
+            .filter(proposer_equivocations::epoch.eq(epoch.as_u64() as i64))
+            .filter(proposer_equivocations::height.eq(height.as_u64() as i64))
+            .filter(proposer_equivocations::proposed_by.eq(serialize_hex(proposed_by.as_bytes())))
+            .first::<sql_models::ProposerEquivocation>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposer_equivocations_get",
+                source: e,
+            })?;
+
+        equivocation.try_into()
+    }
+
+    fn proposer_equivocations_count(&self) -> Result<u64, StorageError> {
+        use crate::schema::proposer_equivocations;
+
+        let count = proposer_equivocations::table
+            .count()
+            .first::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposer_equivocations_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
+    fn equivocation_evidence_get(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<EquivocationEvidence, StorageError> {
+        use crate::schema::equivocation_evidence;
+
+        let evidence = equivocation_evidence::table
+            .filter(equivocation_evidence::epoch.eq(epoch.as_u64() as i64))
+            .filter(equivocation_evidence::height.eq(height.as_u64() as i64))
+            .filter(equivocation_evidence::sender_leaf_hash.eq(serialize_hex(sender_leaf_hash)))
+            .first::<sql_models::EquivocationEvidence>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "equivocation_evidence_get",
+                source: e,
+            })?;
+
+        evidence.try_into()
+    }
+
+    fn equivocation_evidence_get_all(&self) -> Result<Vec<EquivocationEvidence>, StorageError> {
+        use crate::schema::equivocation_evidence;
+
+        let evidence = equivocation_evidence::table
+            .order_by(equivocation_evidence::id.desc())
+            .get_results::<sql_models::EquivocationEvidence>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "equivocation_evidence_get_all",
+                source: e,
+            })?;
+
+        evidence.into_iter().map(TryInto::try_into).collect()
+    }
+
+    fn block_rejections_get_paginated(&self, limit: u64, offset: u64) -> Result<Vec<BlockRejection>, StorageError> {
+        use crate::schema::block_rejections;
+
+        let rejections = block_rejections::table
+            .order_by(block_rejections::id.desc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .get_results::<sql_models::BlockRejection>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "block_rejections_get_paginated",
+                source: e,
+            })?;
+
+        rejections.into_iter().map(BlockRejection::try_from).collect()
+    }
+
+    fn block_rejections_count(&self) -> Result<u64, StorageError> {
+        use crate::schema::block_rejections;
+
+        let count = block_rejections::table
+            .count()
+            .first::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "block_rejections_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
+    fn new_views_count(&self, epoch: Epoch, new_height: NodeHeight) -> Result<u64, StorageError> {
+        use crate::schema::new_views;
+
+        let count = new_views::table
+            .filter(new_views::epoch.eq(epoch.as_u64() as i64))
+            .filter(new_views::new_height.eq(new_height.as_u64() as i64))
+            .count()
+            .first::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "new_views_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
+    fn epoch_checkpoint_get(&self, epoch: Epoch) -> Result<EpochCheckpoint, StorageError> {
+        use crate::schema::epoch_checkpoints;
+
+        let checkpoint = epoch_checkpoints::table
+            .filter(epoch_checkpoints::epoch.eq(epoch.as_u64() as i64))
+            .first::<sql_models::EpochCheckpoint>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "epoch_checkpoint_get",
+                source: e,
+            })?;
+
+        checkpoint.try_into()
+    }
+
+    fn peer_misbehaviors_get(&self, address: &Self::Addr) -> Result<PeerMisbehavior<Self::Addr>, StorageError> {
+        use crate::schema::peer_misbehaviors;
+
+        let misbehavior = peer_misbehaviors::table
+            .filter(peer_misbehaviors::address.eq(serialize_json(address)?))
+            .first::<sql_models::PeerMisbehavior>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "peer_misbehaviors_get",
+                source: e,
+            })?;
+
+        misbehavior.try_into()
+    }
+
+    fn peer_misbehaviors_get_all(&self) -> Result<Vec<PeerMisbehavior<Self::Addr>>, StorageError> {
+        use crate::schema::peer_misbehaviors;
+
+        let misbehaviors = peer_misbehaviors::table
+            .order_by((peer_misbehaviors::score.desc(), peer_misbehaviors::id.asc()))
+            .get_results::<sql_models::PeerMisbehavior>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "peer_misbehaviors_get_all",
+                source: e,
+            })?;
+
+        misbehaviors.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn substates_get(&self, address: &SubstateAddress) -> Result<SubstateRecord, StorageError> {
         use crate::schema::substates;
 
@@ -1828,6 +2657,123 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(substates)
     }
 
+    fn substates_count_in_range(&self, start: &SubstateAddress, end: &SubstateAddress) -> Result<u64, StorageError> {
+        use crate::schema::substates;
+
+        let count = substates::table
+            .filter(substates::address.between(serialize_hex(start), serialize_hex(end)))
+            .filter(substates::destroyed_by_transaction.is_null())
+            .count()
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substates_count_in_range",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
+    fn substates_size_in_range(&self, start: &SubstateAddress, end: &SubstateAddress) -> Result<u64, StorageError> {
+        use crate::schema::substates;
+
+        let size = substates::table
+            .select(dsl::sql::<BigInt>("COALESCE(SUM(LENGTH(data)), 0)"))
+            .filter(substates::address.between(serialize_hex(start), serialize_hex(end)))
+            .filter(substates::destroyed_by_transaction.is_null())
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substates_size_in_range",
+                source: e,
+            })?;
+
+        Ok(size as u64)
+    }
+
+    fn shard_growth_samples_get_recent(
+        &self,
+        shard: Shard,
+        limit: usize,
+    ) -> Result<Vec<ShardGrowthSample>, StorageError> {
+        use crate::schema::shard_growth_samples;
+
+        let mut samples = shard_growth_samples::table
+            .filter(shard_growth_samples::shard.eq(shard.as_u32() as i32))
+            .order_by(shard_growth_samples::id.desc())
+            .limit(i64::try_from(limit).unwrap_or(i64::MAX))
+            .get_results::<sql_models::ShardGrowthSample>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "shard_growth_samples_get_recent",
+                source: e,
+            })?;
+        samples.reverse();
+
+        Ok(samples.into_iter().map(Into::into).collect())
+    }
+
+    fn nft_ownership_get(
+        &self,
+        resource_address: &ResourceAddress,
+        nft_id: &NonFungibleId,
+    ) -> Result<NftOwnership, StorageError> {
+        use crate::schema::{nft_ownership, vault_owners};
+
+        let (ownership, component_address) = nft_ownership::table
+            .left_join(vault_owners::table.on(nft_ownership::vault_id.eq(vault_owners::vault_id)))
+            .select((nft_ownership::all_columns, vault_owners::component_address.nullable()))
+            .filter(nft_ownership::resource_address.eq(resource_address.to_string()))
+            .filter(nft_ownership::nft_id.eq(nft_id.to_canonical_string()))
+            .first::<(sql_models::NftOwnership, Option<String>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "nft_ownership_get",
+                source: e,
+            })?;
+
+        ownership.try_convert(component_address)
+    }
+
+    fn nft_ownership_get_by_owner(
+        &self,
+        component_address: &ComponentAddress,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<NftOwnership>, StorageError> {
+        use crate::schema::{nft_ownership, vault_owners};
+
+        let component_address = component_address.to_string();
+        let ownerships = nft_ownership::table
+            .inner_join(vault_owners::table.on(nft_ownership::vault_id.eq(vault_owners::vault_id)))
+            .select(nft_ownership::all_columns)
+            .filter(vault_owners::component_address.eq(&component_address))
+            .order_by((nft_ownership::resource_address.asc(), nft_ownership::nft_id.asc()))
+            .limit(i64::try_from(limit).unwrap_or(i64::MAX))
+            .offset(i64::try_from(offset).unwrap_or(i64::MAX))
+            .get_results::<sql_models::NftOwnership>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "nft_ownership_get_by_owner",
+                source: e,
+            })?;
+
+        ownerships
+            .into_iter()
+            .map(|ownership| ownership.try_convert(Some(component_address.clone())))
+            .collect()
+    }
+
+    fn nft_ownership_get_ids_in_vault(&self, vault_id: &VaultId) -> Result<BTreeSet<NonFungibleId>, StorageError> {
+        use crate::schema::nft_ownership;
+
+        let nft_ids = nft_ownership::table
+            .select(nft_ownership::nft_id)
+            .filter(nft_ownership::vault_id.eq(vault_id.to_string()))
+            .get_results::<String>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "nft_ownership_get_ids_in_vault",
+                source: e,
+            })?;
+
+        nft_ids.iter().map(|id| sql_models::parse_nft_id(id)).collect()
+    }
+
     fn substate_locks_get_all_for_block(
         &self,
         block_id: BlockId,
@@ -1866,9 +2812,8 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
     fn substate_locks_get_latest_for_substate(&self, substate_id: &SubstateId) -> Result<LockedSubstate, StorageError> {
         use crate::schema::substate_locks;
 
-        // TODO: this may return an invalid lock if:
-        // 1. the proposer links the parent block to the locked block instead of the previous tip
-        // 2. if there are any inactive locks that were not removed from previous uncommitted blocks.
+        // Locks are removed when their transaction is finalized or when their block is abandoned by a fork switch, so
+        // any remaining lock was acquired by a block on the current chain.
 
         let lock = substate_locks::table
             .filter(substate_locks::substate_id.eq(substate_id.to_string()))
@@ -1925,6 +2870,107 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
 
         diffs.into_iter().map(TryInto::try_into).collect()
     }
+
+    fn get_prune_safety_info(&self) -> Result<PruneSafetyInfo, StorageError> {
+        use crate::schema::{
+            blocks,
+            foreign_proposals,
+            parked_blocks,
+            pending_state_tree_diffs,
+            substates,
+            transaction_executions,
+            transactions,
+        };
+
+        let commit_block = self.blocks_get(&self.get_commit_block_id()?)?;
+
+        let foreign_proposal_height = foreign_proposals::table
+            .select(dsl::min(foreign_proposals::proposed_height))
+            .filter(foreign_proposals::state.eq(ForeignProposalState::Proposed.to_string()))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "get_prune_safety_info",
+                source: e,
+            })?;
+
+        // Parked blocks need their parent to be kept so that they can be processed once unparked
+        let parked_parent_height = parked_blocks::table
+            .inner_join(blocks::table.on(blocks::block_id.eq(parked_blocks::parent_block_id)))
+            .select(dsl::min(blocks::height))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "get_prune_safety_info",
+                source: e,
+            })?;
+
+        let pending_diff_height = pending_state_tree_diffs::table
+            .select(dsl::min(pending_state_tree_diffs::block_height))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "get_prune_safety_info",
+                source: e,
+            })?;
+
+        let unfinalized_execution_height = transaction_executions::table
+            .inner_join(transactions::table.on(transactions::transaction_id.eq(transaction_executions::transaction_id)))
+            .inner_join(blocks::table.on(blocks::block_id.eq(transaction_executions::block_id)))
+            .select(dsl::min(blocks::height))
+            .filter(transactions::final_decision.is_null())
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "get_prune_safety_info",
+                source: e,
+            })?;
+
+        let lowest_referenced_height = [
+            foreign_proposal_height,
+            parked_parent_height,
+            pending_diff_height,
+            unfinalized_execution_height,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .map(|height| NodeHeight(height as u64));
+
+        // Leader fees are claimed per epoch and validator, which creates a fee claim substate
+        let fee_epochs = blocks::table
+            .select((blocks::epoch, blocks::proposed_by))
+            .filter(blocks::is_committed.eq(true))
+            .filter(blocks::total_leader_fee.gt(0))
+            .distinct()
+            .order_by(blocks::epoch.asc())
+            .get_results::<(i64, String)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "get_prune_safety_info",
+                source: e,
+            })?;
+
+        let mut earliest_unclaimed_fee_epoch = None;
+        for (epoch, proposed_by) in fee_epochs {
+            let public_key = deserialize_hex(&proposed_by)?;
+            let fee_claim = SubstateId::FeeClaim(FeeClaimAddress::from_addr(epoch as u64, public_key));
+            let num_claims = substates::table
+                .count()
+                .filter(substates::substate_id.eq(fee_claim.to_string()))
+                .first::<i64>(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "get_prune_safety_info",
+                    source: e,
+                })?;
+            if num_claims == 0 {
+                earliest_unclaimed_fee_epoch = Some(Epoch(epoch as u64));
+                break;
+            }
+        }
+
+        Ok(PruneSafetyInfo {
+            committed_height: commit_block.height(),
+            committed_epoch: commit_block.epoch(),
+            lowest_referenced_height,
+            earliest_unclaimed_fee_epoch,
+        })
+    }
 }
 
 #[derive(QueryableByName)]
@@ -1938,3 +2984,22 @@ struct BlockIdSqlValue {
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub bid: String,
 }
+
+/// SQL expression for the unix timestamp in milliseconds of the created_at column of a table. julianday is used so that
+/// timestamps written by sqlite (CURRENT_TIMESTAMP) and timestamps with sub-second precision compare consistently.
+fn created_at_millis_sql(table: &str) -> String {
+    format!("CAST(ROUND((julianday({table}.created_at) - 2440587.5) * 86400000) AS INTEGER)")
+}
+
+/// Returns the unix timestamp in milliseconds, rounded to match [created_at_millis_sql].
+fn timestamp_millis(timestamp: PrimitiveDateTime) -> u64 {
+    let nanos = timestamp.assume_utc().unix_timestamp_nanos();
+    ((nanos + 500_000) / 1_000_000) as u64
+}
+
+fn cursor_operator(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Ascending => ">",
+        Ordering::Descending => "<",
+    }
+}
@@ -0,0 +1,250 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use diesel::{
+    result::{DatabaseErrorKind, Error as DieselError},
+    sql_query,
+    sql_types::Text,
+    Connection,
+    ConnectionError,
+    QueryableByName,
+    RunQueryDsl,
+    SqliteConnection,
+};
+use log::*;
+use tari_dan_storage::StorageError;
+
+use crate::{error::SqliteStorageError, SqliteStateStore};
+
+const LOG_TARGET: &str = "tari::dan::storage::sqlite::recovery";
+
+const BACKUP_FILE_PREFIX: &str = "state-";
+const BACKUP_FILE_EXTENSION: &str = "db";
+
+/// What to do if the state store database is corrupt when it is opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryPolicy {
+    /// Refuse to open a corrupt database
+    Refuse,
+    /// Move the corrupt database aside and restore the most recent backup in the directory
+    RestoreFromBackup { backup_dir: PathBuf },
+}
+
+/// Describes the recovery of a corrupt database
+#[derive(Debug, Clone)]
+pub struct RecoveryReport {
+    /// The reason the database was found to be corrupt
+    pub corruption: String,
+    /// Where the corrupt database file was moved to
+    pub corrupt_file: PathBuf,
+    /// The backup that the database was restored from
+    pub restored_from: PathBuf,
+}
+
+impl<TAddr> SqliteStateStore<TAddr> {
+    /// Opens the database at `path` after checking its integrity. If the database is corrupt, it is either refused or
+    /// replaced by the most recent backup according to the policy. A restored database does not contain anything
+    /// committed after the backup was taken, so the node must sync from peers before it participates in consensus.
+    pub fn try_open_with_recovery<P: AsRef<Path>>(
+        path: P,
+        policy: &RecoveryPolicy,
+    ) -> Result<(Self, Option<RecoveryReport>), StorageError> {
+        let path = path.as_ref();
+        let Some(corruption) = check_integrity(path)? else {
+            return Ok((Self::connect(&sqlite_url(path))?, None));
+        };
+
+        error!(target: LOG_TARGET, "🚨 Database {} is corrupt: {}", path.display(), corruption);
+        let backup_dir = match policy {
+            RecoveryPolicy::Refuse => {
+                return Err(SqliteStorageError::DatabaseCorrupt {
+                    path: path.display().to_string(),
+                    details: corruption,
+                }
+                .into())
+            },
+            RecoveryPolicy::RestoreFromBackup { backup_dir } => backup_dir,
+        };
+
+        let recovery_failed = |details: String| SqliteStorageError::RecoveryFailed {
+            path: path.display().to_string(),
+            details,
+        };
+
+        // Check for a backup before touching the corrupt file
+        let backup = latest_backup(backup_dir)
+            .map_err(|err| recovery_failed(format!("failed to list backups in {}: {}", backup_dir.display(), err)))?
+            .ok_or_else(|| recovery_failed(format!("no backup found in {}", backup_dir.display())))?;
+        if let Some(details) = check_integrity(&backup)? {
+            return Err(recovery_failed(format!("backup {} is also corrupt: {}", backup.display(), details)).into());
+        }
+
+        let corrupt_file = move_aside(path)
+            .map_err(|err| recovery_failed(format!("failed to move the corrupt database aside: {}", err)))?;
+        warn!(
+            target: LOG_TARGET,
+            "Moved corrupt database {} to {}",
+            path.display(),
+            corrupt_file.display()
+        );
+
+        fs::copy(&backup, path)
+            .map_err(|err| recovery_failed(format!("failed to restore backup {}: {}", backup.display(), err)))?;
+        if let Some(details) = check_integrity(path)? {
+            return Err(recovery_failed(format!("restored database is corrupt: {}", details)).into());
+        }
+        warn!(
+            target: LOG_TARGET,
+            "Restored database {} from backup {}",
+            path.display(),
+            backup.display()
+        );
+
+        let store = Self::connect(&sqlite_url(path))?;
+        Ok((store, Some(RecoveryReport {
+            corruption,
+            corrupt_file,
+            restored_from: backup,
+        })))
+    }
+
+    /// Writes a backup of the database to `backup_dir` and removes all but the `keep` most recent backups. Returns the
+    /// path of the new backup.
+    pub fn create_backup<P: AsRef<Path>>(&self, backup_dir: P, keep: usize) -> Result<PathBuf, StorageError> {
+        let backup_dir = backup_dir.as_ref();
+        let io_error = |err: io::Error| StorageError::General {
+            details: format!("Failed to manage backups in {}: {}", backup_dir.display(), err),
+        };
+
+        fs::create_dir_all(backup_dir).map_err(io_error)?;
+        let path = backup_dir.join(format!(
+            "{}{:020}.{}",
+            BACKUP_FILE_PREFIX,
+            unix_timestamp_millis(),
+            BACKUP_FILE_EXTENSION
+        ));
+        self.backup_to(&path)?;
+
+        let backups = list_backups(backup_dir).map_err(io_error)?;
+        for old in backups.iter().take(backups.len().saturating_sub(keep.max(1))) {
+            debug!(target: LOG_TARGET, "Removing old backup {}", old.display());
+            fs::remove_file(old).map_err(io_error)?;
+        }
+
+        Ok(path)
+    }
+}
+
+/// Returns the most recent backup created by `SqliteStateStore::create_backup` in the directory, if any
+pub fn latest_backup<P: AsRef<Path>>(backup_dir: P) -> io::Result<Option<PathBuf>> {
+    Ok(list_backups(backup_dir.as_ref())?.pop())
+}
+
+/// Returns the backups in the directory, oldest first
+fn list_backups(backup_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !backup_dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut backups = fs::read_dir(backup_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.extension().is_some_and(|ext| ext == BACKUP_FILE_EXTENSION) &&
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(BACKUP_FILE_PREFIX))
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    // Backup file names contain a zero-padded timestamp, so they sort chronologically
+    backups.sort();
+    Ok(backups)
+}
+
+#[derive(QueryableByName)]
+struct IntegrityCheck {
+    #[diesel(sql_type = Text)]
+    integrity_check: String,
+}
+
+/// Runs `PRAGMA integrity_check` on the database. Returns None if the database is intact or does not exist, otherwise
+/// the problems that were found.
+fn check_integrity(path: &Path) -> Result<Option<String>, SqliteStorageError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut connection = match SqliteConnection::establish(&sqlite_url(path)) {
+        Ok(connection) => connection,
+        Err(ConnectionError::BadConnection(message)) if is_corruption_message(&message) => return Ok(Some(message)),
+        Err(err) => return Err(err.into()),
+    };
+    let rows = match sql_query("PRAGMA integrity_check;").load::<IntegrityCheck>(&mut connection) {
+        Ok(rows) => rows,
+        Err(DieselError::DatabaseError(DatabaseErrorKind::Unknown, info)) if is_corruption_message(info.message()) => {
+            return Ok(Some(info.message().to_string()));
+        },
+        Err(source) => {
+            return Err(SqliteStorageError::DieselError {
+                source,
+                operation: "integrity check",
+            })
+        },
+    };
+
+    if rows.len() == 1 && rows[0].integrity_check == "ok" {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        rows.into_iter()
+            .map(|row| row.integrity_check)
+            .take(10)
+            .collect::<Vec<_>>()
+            .join("; "),
+    ))
+}
+
+/// Returns true for the messages of SQLITE_CORRUPT and SQLITE_NOTADB
+fn is_corruption_message(message: &str) -> bool {
+    message.contains("malformed") || message.contains("not a database")
+}
+
+/// Moves the database file, and its journal files if any, to `<path>.corrupt-<timestamp>`
+fn move_aside(path: &Path) -> io::Result<PathBuf> {
+    let suffix = format!("corrupt-{}", unix_timestamp_millis());
+    let corrupt_file = append_to_file_name(path, &format!(".{}", suffix));
+    fs::rename(path, &corrupt_file)?;
+    for journal in ["-journal", "-wal", "-shm"] {
+        let journal_file = append_to_file_name(path, journal);
+        if journal_file.exists() {
+            let moved_journal_file = append_to_file_name(&journal_file, &format!(".{}", suffix));
+            fs::rename(&journal_file, moved_journal_file)?;
+        }
+    }
+    Ok(corrupt_file)
+}
+
+fn append_to_file_name(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+fn sqlite_url(path: &Path) -> String {
+    format!("sqlite://{}", path.display())
+}
+
+fn unix_timestamp_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
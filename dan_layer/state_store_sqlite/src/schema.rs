@@ -1,5 +1,14 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    applied_foreign_proposals (id) {
+        id -> Integer,
+        bucket -> Integer,
+        block_id -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     block_diffs (id) {
         id -> Integer,
@@ -13,6 +22,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    block_rejections (id) {
+        id -> Integer,
+        block_id -> Text,
+        epoch -> BigInt,
+        height -> BigInt,
+        proposed_by -> Text,
+        reason -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     blocks (id) {
         id -> Integer,
@@ -41,6 +62,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    epoch_checkpoints (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        block_id -> Text,
+        state_merkle_root -> Text,
+        validator_set_hash -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    equivocation_evidence (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        height -> BigInt,
+        sender_leaf_hash -> Text,
+        voter -> Text,
+        first_block_id -> Text,
+        second_block_id -> Text,
+        first_vote -> Text,
+        second_vote -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     foreign_proposals (id) {
         id -> Integer,
@@ -50,10 +97,34 @@ diesel::table! {
         proposed_height -> Nullable<BigInt>,
         transactions -> Text,
         base_layer_block_height -> BigInt,
+        terminal_height -> Nullable<BigInt>,
+        terminal_epoch -> Nullable<BigInt>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    committed_block_diffs (id) {
+        id -> Integer,
+        block_id -> Text,
+        block -> Text,
+        changes -> Text,
         created_at -> Timestamp,
     }
 }
 
+diesel::table! {
+    foreign_proposal_outbox (id) {
+        id -> Integer,
+        bucket -> Integer,
+        block_id -> Text,
+        created_at -> BigInt,
+        attempts -> Integer,
+        last_attempt_at -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     foreign_receive_counters (id) {
         id -> Integer,
@@ -149,6 +220,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    new_views (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        new_height -> BigInt,
+        sender -> Text,
+        high_qc_id -> Text,
+        high_qc_height -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    nft_ownership (id) {
+        id -> Integer,
+        resource_address -> Text,
+        nft_id -> Text,
+        vault_id -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     parked_blocks (id) {
         id -> Integer,
@@ -170,6 +263,20 @@ diesel::table! {
         timestamp -> BigInt,
         base_layer_block_height -> BigInt,
         base_layer_block_hash -> Text,
+        transactions_requested_at -> Nullable<BigInt>,
+        transaction_request_attempts -> Integer,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    peer_misbehaviors (id) {
+        id -> Integer,
+        address -> Text,
+        score -> BigInt,
+        last_offence -> BigInt,
+        reason -> Text,
+        banned_until -> Nullable<BigInt>,
         created_at -> Timestamp,
     }
 }
@@ -184,6 +291,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    proposer_equivocations (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        height -> BigInt,
+        proposed_by -> Text,
+        first_block_id -> Text,
+        second_block_id -> Text,
+        first_block -> Text,
+        second_block -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    qc_timings (id) {
+        id -> Integer,
+        block_id -> Text,
+        epoch -> BigInt,
+        height -> BigInt,
+        first_vote_ms -> BigInt,
+        quorum_reached_ms -> Nullable<BigInt>,
+        last_vote_ms -> BigInt,
+        votes -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     quorum_certificates (id) {
         id -> Integer,
@@ -194,6 +329,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    shard_growth_samples (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        shard -> Integer,
+        substate_count -> BigInt,
+        total_bytes -> BigInt,
+        count_delta -> BigInt,
+        bytes_delta -> BigInt,
+        interval_secs -> BigInt,
+        sampled_at -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     state_tree (id) {
         id -> Integer,
@@ -238,6 +388,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sync_checkpoints (id) {
+        id -> Integer,
+        block_id -> Text,
+        height -> BigInt,
+        epoch -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     transaction_executions (id) {
         id -> Integer,
@@ -263,9 +423,13 @@ diesel::table! {
         transaction_fee -> BigInt,
         leader_fee -> Nullable<BigInt>,
         global_exhaust_burn -> Nullable<BigInt>,
+        priority_fee -> BigInt,
+        max_fee -> BigInt,
         stage -> Text,
         pending_stage -> Nullable<Text>,
         is_ready -> Bool,
+        deferred_execution_attempts -> Integer,
+        first_seen_height -> BigInt,
         updated_at -> Timestamp,
         created_at -> Timestamp,
     }
@@ -325,6 +489,16 @@ diesel::table! {
         abort_details -> Nullable<Text>,
         min_epoch -> Nullable<BigInt>,
         max_epoch -> Nullable<BigInt>,
+        priority_fee -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    vault_owners (id) {
+        id -> Integer,
+        vault_id -> Text,
+        component_address -> Text,
         created_at -> Timestamp,
     }
 }
@@ -335,6 +509,7 @@ diesel::table! {
         hash -> Text,
         epoch -> BigInt,
         block_id -> Text,
+        height -> BigInt,
         decision -> Integer,
         sender_leaf_hash -> Text,
         signature -> Text,
@@ -343,8 +518,14 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    applied_foreign_proposals,
     block_diffs,
+    block_rejections,
     blocks,
+    committed_block_diffs,
+    epoch_checkpoints,
+    equivocation_evidence,
+    foreign_proposal_outbox,
     foreign_proposals,
     foreign_receive_counters,
     foreign_send_counters,
@@ -356,16 +537,24 @@ diesel::allow_tables_to_appear_in_same_query!(
     leaf_blocks,
     locked_block,
     missing_transactions,
+    new_views,
+    nft_ownership,
     parked_blocks,
+    peer_misbehaviors,
     pending_state_tree_diffs,
+    proposer_equivocations,
+    qc_timings,
     quorum_certificates,
+    shard_growth_samples,
     state_tree,
     substate_locks,
     substates,
+    sync_checkpoints,
     transaction_executions,
     transaction_pool,
     transaction_pool_history,
     transaction_pool_state_updates,
     transactions,
+    vault_owners,
     votes,
 );
@@ -21,6 +21,18 @@ pub fn deserialize_json<T: serde::de::DeserializeOwned>(s: &str) -> Result<T, St
     })
 }
 
+/// Deserializes a JSON blob, refusing to decode blobs larger than `max_size` bytes
+pub fn deserialize_json_bounded<T: serde::de::DeserializeOwned>(s: &str, max_size: usize) -> Result<T, StorageError> {
+    if s.len() > max_size {
+        return Err(StorageError::BlobTooLarge {
+            item: type_name::<T>(),
+            size: s.len(),
+            max_size,
+        });
+    }
+    deserialize_json(s)
+}
+
 pub fn serialize_hex<T: AsRef<[u8]>>(bytes: T) -> String {
     hex::encode(bytes.as_ref())
 }
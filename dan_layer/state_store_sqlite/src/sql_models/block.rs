@@ -9,8 +9,9 @@ use tari_utilities::byte_array::ByteArray;
 use time::PrimitiveDateTime;
 
 use crate::{
+    blob_limits::BlobLimits,
     schema::blocks,
-    serialization::{deserialize_hex, deserialize_hex_try_from, deserialize_json},
+    serialization::{deserialize_hex, deserialize_hex_try_from, deserialize_json, deserialize_json_bounded},
     sql_models,
 };
 
@@ -42,7 +43,11 @@ pub struct Block {
 }
 
 impl Block {
-    pub fn try_convert(self, qc: sql_models::QuorumCertificate) -> Result<consensus_models::Block, StorageError> {
+    pub fn try_convert(
+        self,
+        qc: sql_models::QuorumCertificate,
+        blob_limits: &BlobLimits,
+    ) -> Result<consensus_models::Block, StorageError> {
         let network = self.network.parse().map_err(|_| StorageError::DecodingError {
             operation: "try_convert",
             item: "block",
@@ -52,7 +57,7 @@ impl Block {
             deserialize_hex_try_from(&self.block_id)?,
             network,
             deserialize_hex_try_from(&self.parent_block_id)?,
-            qc.try_into()?,
+            qc.try_convert(blob_limits)?,
             NodeHeight(self.height as u64),
             Epoch(self.epoch as u64),
             Shard::from(self.shard as u32),
@@ -63,7 +68,7 @@ impl Block {
                     details: format!("Block #{} proposed_by is malformed", self.id),
                 }
             })?,
-            deserialize_json(&self.commands)?,
+            deserialize_json_bounded(&self.commands, blob_limits.max_block_commands_size)?,
             deserialize_hex_try_from(&self.merkle_root)?,
             self.total_leader_fee as u64,
             self.is_dummy,
@@ -101,46 +106,101 @@ pub struct ParkedBlock {
     pub timestamp: i64,
     pub base_layer_block_height: i64,
     pub base_layer_block_hash: String,
+    pub transactions_requested_at: Option<i64>,
+    pub transaction_request_attempts: i32,
     pub created_at: PrimitiveDateTime,
 }
 
-impl TryFrom<ParkedBlock> for consensus_models::Block {
-    type Error = StorageError;
-
-    fn try_from(value: ParkedBlock) -> Result<Self, Self::Error> {
-        let network = value.network.parse().map_err(|_| StorageError::DecodingError {
+impl ParkedBlock {
+    pub fn try_convert(self, blob_limits: &BlobLimits) -> Result<consensus_models::Block, StorageError> {
+        let network = self.network.parse().map_err(|_| StorageError::DecodingError {
             operation: "try_convert",
             item: "block",
-            details: format!("Block #{} network byte is not a valid Network", value.id),
+            details: format!("Block #{} network byte is not a valid Network", self.id),
         })?;
         Ok(consensus_models::Block::load(
-            deserialize_hex_try_from(&value.block_id)?,
+            deserialize_hex_try_from(&self.block_id)?,
             network,
-            deserialize_hex_try_from(&value.parent_block_id)?,
-            deserialize_json(&value.justify)?,
-            NodeHeight(value.height as u64),
-            Epoch(value.epoch as u64),
-            Shard::from(value.shard as u32),
-            PublicKey::from_canonical_bytes(&deserialize_hex(&value.proposed_by)?).map_err(|_| {
+            deserialize_hex_try_from(&self.parent_block_id)?,
+            deserialize_json_bounded(&self.justify, blob_limits.max_quorum_certificate_size)?,
+            NodeHeight(self.height as u64),
+            Epoch(self.epoch as u64),
+            Shard::from(self.shard as u32),
+            PublicKey::from_canonical_bytes(&deserialize_hex(&self.proposed_by)?).map_err(|_| {
                 StorageError::DecodingError {
                     operation: "try_convert",
                     item: "block",
-                    details: format!("Block #{} proposed_by is malformed", value.id),
+                    details: format!("Block #{} proposed_by is malformed", self.id),
                 }
             })?,
-            deserialize_json(&value.commands)?,
-            deserialize_hex_try_from(&value.merkle_root)?,
-            value.total_leader_fee as u64,
+            deserialize_json_bounded(&self.commands, blob_limits.max_block_commands_size)?,
+            deserialize_hex_try_from(&self.merkle_root)?,
+            self.total_leader_fee as u64,
             false,
             false,
             false,
-            deserialize_json(&value.foreign_indexes)?,
-            value.signature.map(|val| deserialize_json(&val)).transpose()?,
-            value.created_at,
-            value.block_time.map(|v| v as u64),
-            value.timestamp as u64,
-            value.base_layer_block_height as u64,
-            deserialize_hex_try_from(&value.base_layer_block_hash)?,
+            deserialize_json(&self.foreign_indexes)?,
+            self.signature.map(|val| deserialize_json(&val)).transpose()?,
+            self.created_at,
+            self.block_time.map(|v| v as u64),
+            self.timestamp as u64,
+            self.base_layer_block_height as u64,
+            deserialize_hex_try_from(&self.base_layer_block_hash)?,
         ))
     }
 }
+
+#[derive(Debug, Clone, Queryable)]
+pub struct ProposerEquivocation {
+    pub id: i32,
+    pub epoch: i64,
+    pub height: i64,
+    pub proposed_by: String,
+    pub first_block_id: String,
+    pub second_block_id: String,
+    pub first_block: String,
+    pub second_block: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<ProposerEquivocation> for consensus_models::ProposerEquivocation {
+    type Error = StorageError;
+
+    fn try_from(value: ProposerEquivocation) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            deserialize_json(&value.first_block)?,
+            deserialize_json(&value.second_block)?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct BlockRejection {
+    pub id: i32,
+    pub block_id: String,
+    pub epoch: i64,
+    pub height: i64,
+    pub proposed_by: String,
+    pub reason: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<BlockRejection> for consensus_models::BlockRejection {
+    type Error = StorageError;
+
+    fn try_from(value: BlockRejection) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_id: deserialize_hex_try_from(&value.block_id)?,
+            epoch: Epoch(value.epoch as u64),
+            height: NodeHeight(value.height as u64),
+            proposed_by: PublicKey::from_canonical_bytes(&deserialize_hex(&value.proposed_by)?).map_err(|_| {
+                StorageError::DecodingError {
+                    operation: "try_from",
+                    item: "block_rejection",
+                    details: format!("Block rejection #{} proposed_by is malformed", value.id),
+                }
+            })?,
+            reason: value.reason,
+        })
+    }
+}
@@ -53,3 +53,24 @@ impl BlockDiff {
         Ok(consensus_models::BlockDiff { block_id, changes })
     }
 }
+
+#[derive(Debug, Clone, Queryable)]
+pub struct CommittedBlockDiff {
+    pub id: i32,
+    pub block_id: String,
+    pub block: String,
+    pub changes: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<CommittedBlockDiff> for consensus_models::CommittedBlockDiff {
+    type Error = StorageError;
+
+    fn try_from(value: CommittedBlockDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: value.id as u64,
+            block: deserialize_json(&value.block)?,
+            changes: deserialize_json(&value.changes)?,
+        })
+    }
+}
@@ -44,6 +44,8 @@ pub struct ForeignProposal {
     pub mined_at: Option<i64>,
     pub transactions: String,
     pub base_layer_block_height: i64,
+    pub terminal_height: Option<i64>,
+    pub terminal_epoch: Option<i64>,
     pub created_at: PrimitiveDateTime,
 }
 
@@ -62,6 +64,32 @@ impl TryFrom<ForeignProposal> for consensus_models::ForeignProposal {
     }
 }
 
+#[derive(Debug, Clone, Queryable)]
+pub struct ForeignProposalOutboxEntry {
+    pub id: i32,
+    pub bucket: i32,
+    pub block_id: String,
+    pub created_at: i64,
+    pub attempts: i32,
+    pub last_attempt_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+impl TryFrom<ForeignProposalOutboxEntry> for consensus_models::ForeignProposalOutboxEntry {
+    type Error = StorageError;
+
+    fn try_from(value: ForeignProposalOutboxEntry) -> Result<Self, Self::Error> {
+        Ok(Self {
+            bucket: Shard::from(value.bucket as u32),
+            block_id: deserialize_hex_try_from(&value.block_id)?,
+            created_at: value.created_at as u64,
+            attempts: value.attempts as u32,
+            last_attempt_at: value.last_attempt_at.map(|t| t as u64),
+            last_error: value.last_error,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Queryable)]
 pub struct ForeignSendCounters {
     pub id: i32,
@@ -135,6 +163,49 @@ impl TryFrom<LastExecuted> for consensus_models::LastExecuted {
     }
 }
 
+#[derive(Debug, Clone, Queryable)]
+pub struct SyncCheckpoint {
+    pub id: i32,
+    pub block_id: String,
+    pub height: i64,
+    pub epoch: i64,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<SyncCheckpoint> for consensus_models::SyncCheckpoint {
+    type Error = StorageError;
+
+    fn try_from(value: SyncCheckpoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_id: deserialize_hex_try_from(&value.block_id)?,
+            height: NodeHeight(value.height as u64),
+            epoch: Epoch(value.epoch as u64),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct EpochCheckpoint {
+    pub id: i32,
+    pub epoch: i64,
+    pub block_id: String,
+    pub state_merkle_root: String,
+    pub validator_set_hash: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<EpochCheckpoint> for consensus_models::EpochCheckpoint {
+    type Error = StorageError;
+
+    fn try_from(value: EpochCheckpoint) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            Epoch(value.epoch as u64),
+            deserialize_hex_try_from(&value.state_merkle_root)?,
+            deserialize_hex_try_from(&value.validator_set_hash)?,
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Queryable)]
 pub struct LastVoted {
     pub id: i32,
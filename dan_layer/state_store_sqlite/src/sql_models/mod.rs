@@ -5,6 +5,7 @@ mod block;
 mod block_diff;
 mod bookkeeping;
 mod leaf_block;
+mod nft_ownership;
 mod pending_state_tree_diff;
 mod quorum_certificate;
 mod substate;
@@ -18,6 +19,7 @@ pub use block::*;
 pub use block_diff::*;
 pub use bookkeeping::*;
 pub use leaf_block::*;
+pub use nft_ownership::*;
 pub use pending_state_tree_diff::*;
 pub use quorum_certificate::*;
 pub use substate::*;
@@ -0,0 +1,40 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_storage::{consensus_models, StorageError};
+use tari_template_lib::models::NonFungibleId;
+use time::PrimitiveDateTime;
+
+use crate::serialization::parse_from_string;
+
+#[derive(Debug, Clone, Queryable)]
+pub struct NftOwnership {
+    pub id: i32,
+    pub resource_address: String,
+    pub nft_id: String,
+    pub vault_id: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl NftOwnership {
+    pub fn try_convert(
+        self,
+        component_address: Option<String>,
+    ) -> Result<consensus_models::NftOwnership, StorageError> {
+        Ok(consensus_models::NftOwnership {
+            resource_address: parse_from_string(&self.resource_address)?,
+            nft_id: parse_nft_id(&self.nft_id)?,
+            vault_id: parse_from_string(&self.vault_id)?,
+            component_address: component_address.as_deref().map(parse_from_string).transpose()?,
+        })
+    }
+}
+
+pub fn parse_nft_id(s: &str) -> Result<NonFungibleId, StorageError> {
+    NonFungibleId::try_from_canonical_string(s).map_err(|e| StorageError::DecodingError {
+        operation: "parse_nft_id",
+        item: "NonFungibleId",
+        details: e.to_string(),
+    })
+}
@@ -5,7 +5,7 @@ use diesel::{Queryable, QueryableByName};
 use tari_dan_storage::{consensus_models, StorageError};
 use time::PrimitiveDateTime;
 
-use crate::{schema::quorum_certificates, serialization::deserialize_json};
+use crate::{blob_limits::BlobLimits, schema::quorum_certificates, serialization::deserialize_json_bounded};
 
 #[derive(Debug, Clone, Queryable, QueryableByName)]
 pub struct QuorumCertificate {
@@ -16,10 +16,8 @@ pub struct QuorumCertificate {
     pub created_at: PrimitiveDateTime,
 }
 
-impl TryFrom<QuorumCertificate> for consensus_models::QuorumCertificate {
-    type Error = StorageError;
-
-    fn try_from(value: QuorumCertificate) -> Result<Self, Self::Error> {
-        deserialize_json(&value.json)
+impl QuorumCertificate {
+    pub fn try_convert(self, blob_limits: &BlobLimits) -> Result<consensus_models::QuorumCertificate, StorageError> {
+        deserialize_json_bounded(&self.json, blob_limits.max_quorum_certificate_size)
     }
 }
@@ -2,7 +2,7 @@
 //    SPDX-License-Identifier: BSD-3-Clause
 
 use diesel::Queryable;
-use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
 use tari_dan_storage::{consensus_models, consensus_models::SubstateDestroyed, StorageError};
 use time::PrimitiveDateTime;
 
@@ -71,3 +71,32 @@ impl TryFrom<SubstateRecord> for consensus_models::SubstateRecord {
         })
     }
 }
+
+#[derive(Debug, Clone, Queryable)]
+pub struct ShardGrowthSample {
+    pub id: i32,
+    pub epoch: i64,
+    pub shard: i32,
+    pub substate_count: i64,
+    pub total_bytes: i64,
+    pub count_delta: i64,
+    pub bytes_delta: i64,
+    pub interval_secs: i64,
+    pub sampled_at: i64,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl From<ShardGrowthSample> for consensus_models::ShardGrowthSample {
+    fn from(value: ShardGrowthSample) -> Self {
+        Self {
+            epoch: Epoch(value.epoch as u64),
+            shard: Shard::from(value.shard as u32),
+            substate_count: value.substate_count as u64,
+            total_bytes: value.total_bytes as u64,
+            count_delta: value.count_delta,
+            bytes_delta: value.bytes_delta,
+            interval_secs: value.interval_secs as u64,
+            sampled_at: value.sampled_at as u64,
+        }
+    }
+}
@@ -3,12 +3,16 @@
 
 use std::{str::FromStr, time::Duration};
 
-use diesel::Queryable;
+use diesel::{
+    sql_types::{BigInt, Integer, Nullable, Text},
+    Queryable,
+    QueryableByName,
+};
 use tari_dan_common_types::Epoch;
 use tari_dan_storage::{consensus_models, consensus_models::Decision, StorageError};
 use time::PrimitiveDateTime;
 
-use crate::serialization::deserialize_json;
+use crate::serialization::{deserialize_hex_try_from, deserialize_json};
 
 #[derive(Debug, Clone, Queryable)]
 pub struct Transaction {
@@ -28,6 +32,7 @@ pub struct Transaction {
     pub abort_details: Option<String>,
     pub min_epoch: Option<i64>,
     pub max_epoch: Option<i64>,
+    pub priority_fee: i64,
     pub created_at: PrimitiveDateTime,
 }
 
@@ -53,6 +58,7 @@ impl TryFrom<Transaction> for tari_transaction::Transaction {
             filled_inputs,
             min_epoch,
             max_epoch,
+            value.priority_fee as u64,
         ))
     }
 }
@@ -117,3 +123,52 @@ impl TryFrom<Transaction> for consensus_models::ExecutedTransaction {
         rec.try_into()
     }
 }
+
+/// A transaction joined with its latest execution, as selected by `transactions_get_recent_filtered`
+#[derive(Debug, Clone, QueryableByName)]
+pub struct RecentTransaction {
+    #[diesel(sql_type = Integer)]
+    pub id: i32,
+    #[diesel(sql_type = Text)]
+    pub transaction_id: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub final_decision: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub abort_details: Option<String>,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub fee: Option<i64>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub block_id: Option<String>,
+    /// Unix timestamp in milliseconds
+    #[diesel(sql_type = BigInt)]
+    pub created_at_millis: i64,
+}
+
+impl TryFrom<RecentTransaction> for consensus_models::RecentTransaction {
+    type Error = StorageError;
+
+    fn try_from(value: RecentTransaction) -> Result<Self, Self::Error> {
+        let decision = value
+            .final_decision
+            .as_deref()
+            .map(Decision::from_str)
+            .transpose()
+            .map_err(|_| StorageError::DecodingError {
+                operation: "TryFrom<RecentTransaction> for consensus_models::RecentTransaction",
+                item: "decision",
+                details: format!(
+                    "Failed to parse decision from string: {}",
+                    value.final_decision.as_ref().unwrap()
+                ),
+            })?;
+
+        Ok(Self {
+            transaction_id: deserialize_hex_try_from(&value.transaction_id)?,
+            decision,
+            abort_reason: value.abort_details,
+            fee: value.fee.map(|fee| fee as u64),
+            block_id: value.block_id.as_deref().map(deserialize_hex_try_from).transpose()?,
+            timestamp: value.created_at_millis as u64,
+        })
+    }
+}
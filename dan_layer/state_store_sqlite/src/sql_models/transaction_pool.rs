@@ -2,6 +2,7 @@
 //    SPDX-License-Identifier: BSD-3-Clause
 
 use diesel::{Queryable, QueryableByName};
+use tari_dan_common_types::NodeHeight;
 use tari_dan_storage::{
     consensus_models,
     consensus_models::{Decision, Evidence, LeaderFee, TransactionAtom},
@@ -9,7 +10,10 @@ use tari_dan_storage::{
 };
 use time::PrimitiveDateTime;
 
-use crate::serialization::{deserialize_hex_try_from, deserialize_json, parse_from_string};
+use crate::{
+    blob_limits::BlobLimits,
+    serialization::{deserialize_hex_try_from, deserialize_json_bounded, parse_from_string},
+};
 
 #[derive(Debug, Clone, Queryable)]
 pub struct TransactionPoolRecord {
@@ -23,12 +27,16 @@ pub struct TransactionPoolRecord {
     pub transaction_fee: i64,
     pub leader_fee: Option<i64>,
     pub global_exhaust_burn: Option<i64>,
+    pub priority_fee: i64,
+    pub max_fee: i64,
     pub stage: String,
     // TODO: This is the last stage update, but does not reflect the actual stage (which comes from the
     //       transaction_pool_state_updates table). This is kind of a hack to make transaction_pool_count work
     //       and should not given to TransactionPoolRecord::load.
     pub pending_stage: Option<String>,
     pub is_ready: bool,
+    pub deferred_execution_attempts: i32,
+    pub first_seen_height: i64,
     pub updated_at: PrimitiveDateTime,
     pub created_at: PrimitiveDateTime,
 }
@@ -37,18 +45,20 @@ impl TransactionPoolRecord {
     pub fn try_convert(
         mut self,
         update: Option<TransactionPoolStateUpdate>,
+        blob_limits: &BlobLimits,
     ) -> Result<consensus_models::TransactionPoolRecord, StorageError> {
-        let mut evidence = deserialize_json::<Evidence>(&self.evidence)?;
+        let max_evidence_size = blob_limits.max_evidence_size;
+        let mut evidence = deserialize_json_bounded::<Evidence>(&self.evidence, max_evidence_size)?;
         let mut pending_stage = None;
         if let Some(update) = update {
-            evidence.merge(deserialize_json::<Evidence>(&update.evidence)?);
+            evidence.merge(deserialize_json_bounded(&update.evidence, max_evidence_size)?);
             self.is_ready = update.is_ready;
             pending_stage = Some(parse_from_string(&update.stage)?);
             self.local_decision = update.local_decision;
         }
 
         if let Some(ref remote_evidence) = self.remote_evidence {
-            evidence.merge(deserialize_json::<Evidence>(remote_evidence)?);
+            evidence.merge(deserialize_json_bounded(remote_evidence, max_evidence_size)?);
         }
 
         let leader_fee = self
@@ -87,12 +97,15 @@ impl TransactionPoolRecord {
                 evidence,
                 transaction_fee: self.transaction_fee as u64,
                 leader_fee,
+                priority_fee: self.priority_fee as u64,
             },
             parse_from_string(&self.stage)?,
             pending_stage,
             local_decision,
             remote_decision,
             self.is_ready,
+            self.deferred_execution_attempts as u32,
+            NodeHeight(self.first_seen_height as u64),
         ))
     }
 }
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use diesel::Queryable;
-use tari_dan_common_types::Epoch;
+use tari_dan_common_types::{Epoch, NodeAddressable, NodeHeight};
 use tari_dan_storage::{consensus_models, consensus_models::QuorumDecision, StorageError};
 use time::PrimitiveDateTime;
 
@@ -17,6 +17,7 @@ pub struct Vote {
     pub hash: String,
     pub epoch: i64,
     pub block_id: String,
+    pub height: i64,
     pub decision: i32,
     pub sender: String,
     pub signature: String,
@@ -30,6 +31,7 @@ impl TryFrom<Vote> for consensus_models::Vote {
         Ok(Self {
             epoch: Epoch(value.epoch as u64),
             block_id: deserialize_hex_try_from(&value.block_id)?,
+            height: NodeHeight(value.height as u64),
             decision: QuorumDecision::from_u8(u8::try_from(value.decision).map_err(|_| {
                 SqliteStorageError::MalformedDbData {
                     operation: "TryFrom<Vote> decision",
@@ -45,3 +47,82 @@ impl TryFrom<Vote> for consensus_models::Vote {
         })
     }
 }
+
+#[derive(Debug, Clone, Queryable)]
+pub struct QcTiming {
+    pub id: i32,
+    pub block_id: String,
+    pub epoch: i64,
+    pub height: i64,
+    pub first_vote_ms: i64,
+    pub quorum_reached_ms: Option<i64>,
+    pub last_vote_ms: i64,
+    pub votes: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<QcTiming> for consensus_models::QcTiming {
+    type Error = StorageError;
+
+    fn try_from(value: QcTiming) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_id: deserialize_hex_try_from(&value.block_id)?,
+            epoch: Epoch(value.epoch as u64),
+            height: NodeHeight(value.height as u64),
+            first_vote_ms: value.first_vote_ms as u64,
+            quorum_reached_ms: value.quorum_reached_ms.map(|ms| ms as u64),
+            last_vote_ms: value.last_vote_ms as u64,
+            votes: deserialize_json(&value.votes)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct EquivocationEvidence {
+    pub id: i32,
+    pub epoch: i64,
+    pub height: i64,
+    pub sender_leaf_hash: String,
+    pub voter: String,
+    pub first_block_id: String,
+    pub second_block_id: String,
+    pub first_vote: String,
+    pub second_vote: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<EquivocationEvidence> for consensus_models::EquivocationEvidence {
+    type Error = StorageError;
+
+    fn try_from(value: EquivocationEvidence) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            deserialize_json(&value.first_vote)?,
+            deserialize_json(&value.second_vote)?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct PeerMisbehavior {
+    pub id: i32,
+    pub address: String,
+    pub score: i64,
+    pub last_offence: i64,
+    pub reason: String,
+    pub banned_until: Option<i64>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl<TAddr: NodeAddressable> TryFrom<PeerMisbehavior> for consensus_models::PeerMisbehavior<TAddr> {
+    type Error = StorageError;
+
+    fn try_from(value: PeerMisbehavior) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: deserialize_json(&value.address)?,
+            score: value.score as u64,
+            last_offence: value.last_offence as u64,
+            reason: value.reason,
+            banned_until: value.banned_until.map(|t| t as u64),
+        })
+    }
+}
@@ -4,21 +4,24 @@
 use std::{
     fmt,
     marker::PhantomData,
-    sync::{Arc, Mutex},
+    path::Path,
+    sync::{Arc, Mutex, TryLockError},
     time::{Duration, Instant},
 };
 
-use diesel::{sql_query, Connection, RunQueryDsl, SqliteConnection};
+use diesel::{sql_query, sql_types::Text, Connection, RunQueryDsl, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use log::log;
+use log::*;
 use serde::{de::DeserializeOwned, Serialize};
 use tari_dan_common_types::NodeAddressable;
 use tari_dan_storage::{StateStore, StorageError};
 
 use crate::{
+    blob_limits::BlobLimits,
     error::SqliteStorageError,
     reader::SqliteStateStoreReadTransaction,
     sqlite_transaction::SqliteTransaction,
+    write_queue::{WriteQueueConfig, WriteQueueMonitor},
     writer::SqliteStateStoreWriteTransaction,
 };
 
@@ -26,6 +29,8 @@ const LOG_TARGET: &str = "tari::dan::storage::sqlite::state_store";
 
 pub struct SqliteStateStore<TAddr> {
     connection: Arc<Mutex<SqliteConnection>>,
+    blob_limits: BlobLimits,
+    write_queue: WriteQueueMonitor,
     _addr: PhantomData<TAddr>,
 }
 
@@ -47,10 +52,29 @@ impl<TAddr> SqliteStateStore<TAddr> {
 
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
+            blob_limits: BlobLimits::default(),
+            write_queue: WriteQueueMonitor::default(),
             _addr: PhantomData,
         })
     }
 
+    /// Sets the maximum sizes of stored JSON blobs that will be deserialized by transactions created by this store
+    pub fn with_blob_limits(mut self, blob_limits: BlobLimits) -> Self {
+        self.blob_limits = blob_limits;
+        self
+    }
+
+    /// Sets the thresholds used to instrument write transactions and decide when to shed load
+    pub fn with_write_queue_config(mut self, config: WriteQueueConfig) -> Self {
+        self.write_queue = WriteQueueMonitor::new(config);
+        self
+    }
+
+    /// Returns the instrumentation of the time that write transactions wait for and hold the writer
+    pub fn write_queue(&self) -> &WriteQueueMonitor {
+        &self.write_queue
+    }
+
     pub fn foreign_keys_off(&self) -> Result<(), StorageError> {
         sql_query("PRAGMA foreign_keys = OFF;")
             .execute(&mut *self.connection.lock().unwrap())
@@ -62,6 +86,64 @@ impl<TAddr> SqliteStateStore<TAddr> {
     }
 }
 
+impl<TAddr> SqliteStateStore<TAddr> {
+    /// Runs `PRAGMA optimize`, `ANALYZE` and, if `incremental_vacuum` is true, an incremental vacuum on the database.
+    /// Maintenance is skipped and `None` is returned if a transaction is currently active or the database is locked by
+    /// another connection, otherwise the time taken is returned.
+    pub fn maintenance(&self, incremental_vacuum: bool) -> Result<Option<Duration>, StorageError> {
+        let mut connection = match self.connection.try_lock() {
+            Ok(connection) => connection,
+            Err(TryLockError::WouldBlock) => {
+                debug!(target: LOG_TARGET, "Skipping maintenance because a transaction is active");
+                return Ok(None);
+            },
+            Err(TryLockError::Poisoned(err)) => {
+                return Err(SqliteStorageError::ConnectionPoisoned {
+                    details: err.to_string(),
+                }
+                .into())
+            },
+        };
+
+        let timer = Instant::now();
+        let mut pragmas = vec![
+            ("PRAGMA optimize;", "maintenance: optimize"),
+            ("ANALYZE;", "maintenance: analyze"),
+        ];
+        if incremental_vacuum {
+            pragmas.push(("PRAGMA incremental_vacuum;", "maintenance: incremental_vacuum"));
+        }
+        for (pragma, operation) in pragmas {
+            if let Err(source) = sql_query(pragma).execute(&mut *connection) {
+                let err = SqliteStorageError::DieselError { source, operation };
+                if err.is_database_busy() {
+                    debug!(target: LOG_TARGET, "Skipping maintenance because the database is locked");
+                    return Ok(None);
+                }
+                return Err(err.into());
+            }
+        }
+        Ok(Some(timer.elapsed()))
+    }
+
+    /// Writes a consistent copy of the database to a new file at `path`. Blocks until any active transaction has
+    /// completed.
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<(), StorageError> {
+        let path = path.as_ref();
+        let path = path.to_str().ok_or_else(|| StorageError::General {
+            details: format!("Backup path {} is not valid UTF-8", path.display()),
+        })?;
+        sql_query("VACUUM INTO ?;")
+            .bind::<Text, _>(path)
+            .execute(&mut *self.connection.lock().unwrap())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "backup",
+            })?;
+        Ok(())
+    }
+}
+
 // Manually implement the Debug implementation because `SqliteConnection` does not implement the Debug trait
 impl<TAddr> fmt::Debug for SqliteStateStore<TAddr> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -76,25 +158,20 @@ impl<TAddr: NodeAddressable + Serialize + DeserializeOwned> StateStore for Sqlit
 
     fn create_read_tx(&self) -> Result<Self::ReadTransaction<'_>, StorageError> {
         let tx = SqliteTransaction::begin(self.connection.lock().unwrap())?;
-        Ok(SqliteStateStoreReadTransaction::new(tx))
+        Ok(SqliteStateStoreReadTransaction::new(tx, self.blob_limits))
     }
 
     fn create_write_tx(&self) -> Result<Self::WriteTransaction<'_>, StorageError> {
         let timer = Instant::now();
         let tx = SqliteTransaction::begin(self.connection.lock().unwrap())?;
-        let tx = SqliteStateStoreWriteTransaction::new(tx);
         let elapsed = timer.elapsed();
-        let level = if elapsed > Duration::from_secs(1) {
-            log::Level::Warn
-        } else {
-            log::Level::Trace
-        };
-        log!(
-            target: LOG_TARGET,
-            level,
-            "Write transaction obtained in {:?}", elapsed
-        );
-        Ok(tx)
+        self.write_queue.record_wait(elapsed);
+        trace!(target: LOG_TARGET, "Write transaction obtained in {:?}", elapsed);
+        Ok(SqliteStateStoreWriteTransaction::new(
+            tx,
+            self.blob_limits,
+            self.write_queue.clone(),
+        ))
     }
 }
 
@@ -102,6 +179,8 @@ impl<TAddr> Clone for SqliteStateStore<TAddr> {
     fn clone(&self) -> Self {
         Self {
             connection: self.connection.clone(),
+            blob_limits: self.blob_limits,
+            write_queue: self.write_queue.clone(),
             _addr: PhantomData,
         }
     }
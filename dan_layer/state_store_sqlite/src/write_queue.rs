@@ -0,0 +1,155 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::*;
+
+const LOG_TARGET: &str = "tari::dan::storage::sqlite::write_queue";
+
+/// Thresholds for the time that write transactions wait for the single database writer
+#[derive(Debug, Clone, Copy)]
+pub struct WriteQueueConfig {
+    /// A warning is logged when a write transaction waits longer than this
+    pub warn_wait: Duration,
+    /// Load shedding engages when a write transaction waits longer than this
+    pub shed_wait: Duration,
+    /// Load shedding disengages after `recover_count` consecutive write transactions wait less than this
+    pub recover_wait: Duration,
+    /// The number of consecutive short waits required to disengage load shedding
+    pub recover_count: usize,
+    /// Load shedding disengages once no write transaction has waited longer than `recover_wait` for this long, even if
+    /// there were too few writes to disengage it by count
+    pub max_shed_duration: Duration,
+}
+
+impl Default for WriteQueueConfig {
+    fn default() -> Self {
+        Self {
+            warn_wait: Duration::from_millis(500),
+            shed_wait: Duration::from_secs(2),
+            recover_wait: Duration::from_millis(100),
+            recover_count: 10,
+            max_shed_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A snapshot of the write queue instrumentation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WriteQueueStats {
+    /// The time the most recent write transaction waited for the writer
+    pub last_wait: Duration,
+    /// The longest time any write transaction waited for the writer
+    pub max_wait: Duration,
+    /// The time the most recently completed write transaction held the writer
+    pub last_duration: Duration,
+    /// The longest time any write transaction held the writer
+    pub max_duration: Duration,
+    /// The number of write transactions that waited longer than the warning threshold
+    pub num_slow_waits: u64,
+    /// True if the node should temporarily stop accepting new transaction submissions
+    pub is_shedding_load: bool,
+}
+
+/// Records how long write transactions wait for and hold the database writer, and decides when the node should shed
+/// load. Load shedding engages on a single long wait and disengages once waits have normalized.
+#[derive(Debug, Clone, Default)]
+pub struct WriteQueueMonitor {
+    config: WriteQueueConfig,
+    state: Arc<Mutex<WriteQueueState>>,
+}
+
+#[derive(Debug, Default)]
+struct WriteQueueState {
+    stats: WriteQueueStats,
+    last_long_wait_at: Option<Instant>,
+    num_short_waits: usize,
+}
+
+impl WriteQueueMonitor {
+    pub fn new(config: WriteQueueConfig) -> Self {
+        Self {
+            config,
+            state: Arc::default(),
+        }
+    }
+
+    pub fn config(&self) -> &WriteQueueConfig {
+        &self.config
+    }
+
+    pub fn is_shedding_load(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.expire_shedding(&mut state);
+        state.stats.is_shedding_load
+    }
+
+    pub fn stats(&self) -> WriteQueueStats {
+        let mut state = self.state.lock().unwrap();
+        self.expire_shedding(&mut state);
+        state.stats
+    }
+
+    pub(crate) fn record_wait(&self, wait: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.stats.last_wait = wait;
+        state.stats.max_wait = state.stats.max_wait.max(wait);
+        if wait > self.config.warn_wait {
+            state.stats.num_slow_waits += 1;
+            warn!(target: LOG_TARGET, "🐢 Write transaction waited {:.2?} for the writer", wait);
+        }
+
+        if wait > self.config.recover_wait {
+            state.last_long_wait_at = Some(Instant::now());
+            state.num_short_waits = 0;
+        } else {
+            state.num_short_waits += 1;
+        }
+
+        if wait > self.config.shed_wait {
+            if !state.stats.is_shedding_load {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Write transaction waited {:.2?} for the writer. New transaction submissions are refused until \
+                     the write queue recovers",
+                    wait
+                );
+                state.stats.is_shedding_load = true;
+            }
+        } else if state.stats.is_shedding_load && state.num_short_waits >= self.config.recover_count {
+            info!(
+                target: LOG_TARGET,
+                "✅ Write queue recovered after {} short waits. Accepting new transaction submissions",
+                state.num_short_waits
+            );
+            state.stats.is_shedding_load = false;
+        }
+    }
+
+    pub(crate) fn record_duration(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.stats.last_duration = duration;
+        state.stats.max_duration = state.stats.max_duration.max(duration);
+    }
+
+    fn expire_shedding(&self, state: &mut WriteQueueState) {
+        if !state.stats.is_shedding_load {
+            return;
+        }
+        let has_expired = state
+            .last_long_wait_at
+            .map_or(true, |at| at.elapsed() >= self.config.max_shed_duration);
+        if has_expired {
+            info!(
+                target: LOG_TARGET,
+                "✅ No long write queue waits for {:.2?}. Accepting new transaction submissions",
+                self.config.max_shed_duration
+            );
+            state.stats.is_shedding_load = false;
+        }
+    }
+}
@@ -1,27 +1,32 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref, time::Instant};
 
 use diesel::{
-    sql_types::Text,
+    sql_types::{BigInt, Text},
     AsChangeset,
     ExpressionMethods,
+    NullableExpressionMethods,
     OptionalExtension,
     QueryDsl,
     RunQueryDsl,
     SqliteConnection,
 };
 use log::*;
-use tari_dan_common_types::{optional::Optional, Epoch, NodeAddressable, NodeHeight, SubstateAddress};
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, NodeAddressable, NodeHeight, SubstateAddress};
 use tari_dan_storage::{
     consensus_models::{
         Block,
         BlockDiff,
         BlockId,
+        BlockRejection,
         Decision,
+        EpochCheckpoint,
+        EquivocationEvidence,
         Evidence,
         ForeignProposal,
+        ForeignProposalOutboxEntry,
         ForeignReceiveCounters,
         ForeignSendCounters,
         HighQc,
@@ -32,10 +37,17 @@ use tari_dan_storage::{
         LeafBlock,
         LockedBlock,
         LockedSubstate,
+        NewViewRecord,
+        PeerMisbehavior,
         PendingStateTreeDiff,
+        ProposerEquivocation,
+        PruneSafetyInfo,
         QcId,
+        QcTiming,
         QuorumCertificate,
+        ShardGrowthSample,
         SubstateRecord,
+        SyncCheckpoint,
         TransactionAtom,
         TransactionExecution,
         TransactionPoolStage,
@@ -47,17 +59,20 @@ use tari_dan_storage::{
     StateStoreWriteTransaction,
     StorageError,
 };
-use tari_engine_types::substate::SubstateId;
+use tari_engine_types::{commit_result::ExecuteResult, substate::SubstateId};
+use tari_template_lib::models::{ComponentAddress, NonFungibleId, ResourceAddress, VaultId};
 use tari_transaction::TransactionId;
 use tari_utilities::ByteArray;
 use time::{OffsetDateTime, PrimitiveDateTime};
 
 use crate::{
+    blob_limits::BlobLimits,
     error::SqliteStorageError,
     reader::SqliteStateStoreReadTransaction,
-    serialization::{serialize_hex, serialize_json},
+    serialization::{deserialize_json, serialize_hex, serialize_json},
     sql_models,
     sqlite_transaction::SqliteTransaction,
+    write_queue::WriteQueueMonitor,
 };
 
 const LOG_TARGET: &str = "tari::dan::storage";
@@ -65,12 +80,16 @@ const LOG_TARGET: &str = "tari::dan::storage";
 pub struct SqliteStateStoreWriteTransaction<'a, TAddr> {
     /// None indicates if the transaction has been explicitly committed/rolled back
     transaction: Option<SqliteStateStoreReadTransaction<'a, TAddr>>,
+    write_queue: WriteQueueMonitor,
+    started_at: Instant,
 }
 
 impl<'a, TAddr: NodeAddressable> SqliteStateStoreWriteTransaction<'a, TAddr> {
-    pub fn new(transaction: SqliteTransaction<'a>) -> Self {
+    pub fn new(transaction: SqliteTransaction<'a>, blob_limits: BlobLimits, write_queue: WriteQueueMonitor) -> Self {
         Self {
-            transaction: Some(SqliteStateStoreReadTransaction::new(transaction)),
+            transaction: Some(SqliteStateStoreReadTransaction::new(transaction, blob_limits)),
+            write_queue,
+            started_at: Instant::now(),
         }
     }
 
@@ -102,7 +121,7 @@ impl<'a, TAddr: NodeAddressable> SqliteStateStoreWriteTransaction<'a, TAddr> {
                 source: e,
             })?;
 
-        block.try_into()
+        block.try_convert(self.blob_limits())
     }
 
     fn parked_blocks_insert(&mut self, block: &Block) -> Result<(), StorageError> {
@@ -242,6 +261,86 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn blocks_insert_many(&mut self, blocks: &[Block]) -> Result<(), StorageError> {
+        use crate::schema::blocks;
+
+        // Each row binds 19 variables, so this stays well below the SQLite bound variable limit
+        const BATCH_SIZE: usize = 500;
+
+        for chunk in blocks.chunks(BATCH_SIZE) {
+            let values = chunk
+                .iter()
+                .map(|block| {
+                    Ok((
+                        blocks::block_id.eq(serialize_hex(block.id())),
+                        blocks::parent_block_id.eq(serialize_hex(block.parent())),
+                        blocks::merkle_root.eq(block.merkle_root().to_string()),
+                        blocks::network.eq(block.network().to_string()),
+                        blocks::height.eq(block.height().as_u64() as i64),
+                        blocks::epoch.eq(block.epoch().as_u64() as i64),
+                        blocks::shard.eq(block.shard().as_u32() as i32),
+                        blocks::proposed_by.eq(serialize_hex(block.proposed_by().as_bytes())),
+                        blocks::command_count.eq(block.commands().len() as i64),
+                        blocks::commands.eq(serialize_json(block.commands())?),
+                        blocks::total_leader_fee.eq(block.total_leader_fee() as i64),
+                        blocks::qc_id.eq(serialize_hex(block.justify().id())),
+                        blocks::is_dummy.eq(block.is_dummy()),
+                        blocks::is_processed.eq(block.is_processed()),
+                        blocks::signature.eq(block.get_signature().map(serialize_json).transpose()?),
+                        blocks::foreign_indexes.eq(serialize_json(block.foreign_indexes())?),
+                        blocks::timestamp.eq(block.timestamp() as i64),
+                        blocks::base_layer_block_height.eq(block.base_layer_block_height() as i64),
+                        blocks::base_layer_block_hash.eq(serialize_hex(block.base_layer_block_hash())),
+                    ))
+                })
+                .collect::<Result<Vec<_>, StorageError>>()?;
+
+            diesel::insert_or_ignore_into(blocks::table)
+                .values(values)
+                .execute(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "blocks_insert_many",
+                    source: e,
+                })?;
+        }
+
+        // Set the block time of all blocks that are justified by the same block in one statement
+        let mut blocks_by_justify = HashMap::<_, Vec<_>>::new();
+        for block in blocks {
+            blocks_by_justify
+                .entry(serialize_hex(block.justify().block_id()))
+                .or_default()
+                .push(serialize_hex(block.id()));
+        }
+        for (justify_block_id, block_ids) in blocks_by_justify {
+            let justify_timestamp = blocks::table
+                .select(blocks::timestamp)
+                .filter(blocks::block_id.eq(&justify_block_id))
+                .first::<i64>(self.connection())
+                .optional()
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "blocks_insert_many_set_delta_time",
+                    source: e,
+                })?;
+            let Some(justify_timestamp) = justify_timestamp else {
+                continue;
+            };
+
+            for block_ids in block_ids.chunks(BATCH_SIZE) {
+                diesel::update(blocks::table)
+                    .filter(blocks::block_id.eq_any(block_ids))
+                    .set(blocks::block_time.eq((blocks::timestamp - justify_timestamp).nullable()))
+                    .execute(self.connection())
+                    .map_err(|e| SqliteStorageError::DieselError {
+                        operation: "blocks_insert_many_set_delta_time",
+                        source: e,
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn blocks_set_flags(
         &mut self,
         block_id: &BlockId,
@@ -318,6 +417,55 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn committed_block_diffs_insert(&mut self, block: &Block, block_diff: &BlockDiff) -> Result<(), StorageError> {
+        use crate::schema::committed_block_diffs;
+
+        diesel::insert_into(committed_block_diffs::table)
+            .values((
+                committed_block_diffs::block_id.eq(serialize_hex(block.id())),
+                committed_block_diffs::block.eq(serialize_json(block)?),
+                committed_block_diffs::changes.eq(serialize_json(block_diff.changes())?),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "committed_block_diffs_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn committed_block_diffs_prune(&mut self, retain: u64) -> Result<(), StorageError> {
+        use crate::schema::committed_block_diffs;
+
+        let max_sequence = committed_block_diffs::table
+            .select(diesel::dsl::max(committed_block_diffs::id))
+            .first::<Option<i32>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "committed_block_diffs_prune",
+                source: e,
+            })?;
+        let Some(max_sequence) = max_sequence else {
+            return Ok(());
+        };
+
+        // Sequence numbers are contiguous so every diff at or below the cutoff is outside of the retention window
+        let cutoff = i64::from(max_sequence) - retain as i64;
+        if cutoff <= 0 {
+            return Ok(());
+        }
+
+        diesel::delete(committed_block_diffs::table)
+            .filter(committed_block_diffs::id.le(cutoff as i32))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "committed_block_diffs_prune",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn quorum_certificates_insert(&mut self, qc: &QuorumCertificate) -> Result<(), StorageError> {
         use crate::schema::quorum_certificates;
 
@@ -516,6 +664,26 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn sync_checkpoint_set(&mut self, checkpoint: &SyncCheckpoint) -> Result<(), StorageError> {
+        use crate::schema::sync_checkpoints;
+
+        let insert = (
+            sync_checkpoints::block_id.eq(serialize_hex(checkpoint.block_id)),
+            sync_checkpoints::height.eq(checkpoint.height.as_u64() as i64),
+            sync_checkpoints::epoch.eq(checkpoint.epoch.as_u64() as i64),
+        );
+
+        diesel::insert_into(sync_checkpoints::table)
+            .values(insert)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "sync_checkpoint_set",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn foreign_proposal_upsert(&mut self, foreign_proposal: &ForeignProposal) -> Result<(), StorageError> {
         use crate::schema::foreign_proposals;
 
@@ -556,6 +724,177 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn foreign_proposal_set_terminal_state(
+        &mut self,
+        foreign_proposal: &ForeignProposal,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<(), StorageError> {
+        use crate::schema::foreign_proposals;
+
+        let num_affected = diesel::update(foreign_proposals::table)
+            .filter(foreign_proposals::bucket.eq(foreign_proposal.bucket.as_u32() as i32))
+            .filter(foreign_proposals::block_id.eq(serialize_hex(foreign_proposal.block_id)))
+            .set((
+                foreign_proposals::state.eq(foreign_proposal.state.to_string()),
+                foreign_proposals::terminal_height.eq(height.as_u64() as i64),
+                foreign_proposals::terminal_epoch.eq(epoch.as_u64() as i64),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_set_terminal_state",
+                source: e,
+            })?;
+
+        if num_affected == 0 {
+            return Err(StorageError::NotFound {
+                item: "foreign_proposal".to_string(),
+                key: foreign_proposal.block_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn foreign_proposal_insert_terminal(
+        &mut self,
+        foreign_proposal: &ForeignProposal,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<bool, StorageError> {
+        use crate::schema::foreign_proposals;
+
+        let values = (
+            foreign_proposals::bucket.eq(foreign_proposal.bucket.as_u32() as i32),
+            foreign_proposals::block_id.eq(serialize_hex(foreign_proposal.block_id)),
+            foreign_proposals::state.eq(foreign_proposal.state.to_string()),
+            foreign_proposals::proposed_height.eq(foreign_proposal.proposed_height.map(|h| h.as_u64() as i64)),
+            foreign_proposals::transactions.eq(serialize_json(&foreign_proposal.transactions)?),
+            foreign_proposals::base_layer_block_height.eq(foreign_proposal.base_layer_block_height as i64),
+            foreign_proposals::terminal_height.eq(height.as_u64() as i64),
+            foreign_proposals::terminal_epoch.eq(epoch.as_u64() as i64),
+        );
+
+        let num_inserted = diesel::insert_into(foreign_proposals::table)
+            .values(values)
+            .on_conflict((foreign_proposals::bucket, foreign_proposals::block_id))
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_insert_terminal",
+                source: e,
+            })?;
+
+        Ok(num_inserted > 0)
+    }
+
+    fn foreign_proposal_delete_terminal_before(&mut self, epoch: Epoch) -> Result<usize, StorageError> {
+        use crate::schema::foreign_proposals;
+
+        let num_deleted = diesel::delete(foreign_proposals::table)
+            .filter(foreign_proposals::terminal_epoch.lt(epoch.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_delete_terminal_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn foreign_proposal_outbox_insert(&mut self, entry: &ForeignProposalOutboxEntry) -> Result<(), StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        let values = (
+            foreign_proposal_outbox::bucket.eq(entry.bucket.as_u32() as i32),
+            foreign_proposal_outbox::block_id.eq(serialize_hex(entry.block_id)),
+            foreign_proposal_outbox::created_at.eq(entry.created_at as i64),
+            foreign_proposal_outbox::attempts.eq(entry.attempts as i32),
+            foreign_proposal_outbox::last_attempt_at.eq(entry.last_attempt_at.map(|t| t as i64)),
+            foreign_proposal_outbox::last_error.eq(entry.last_error.as_deref()),
+        );
+
+        diesel::insert_into(foreign_proposal_outbox::table)
+            .values(values)
+            .on_conflict((foreign_proposal_outbox::bucket, foreign_proposal_outbox::block_id))
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn foreign_proposal_outbox_update(&mut self, entry: &ForeignProposalOutboxEntry) -> Result<(), StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        diesel::update(foreign_proposal_outbox::table)
+            .filter(foreign_proposal_outbox::bucket.eq(entry.bucket.as_u32() as i32))
+            .filter(foreign_proposal_outbox::block_id.eq(serialize_hex(entry.block_id)))
+            .set((
+                foreign_proposal_outbox::attempts.eq(entry.attempts as i32),
+                foreign_proposal_outbox::last_attempt_at.eq(entry.last_attempt_at.map(|t| t as i64)),
+                foreign_proposal_outbox::last_error.eq(entry.last_error.as_deref()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_update",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn foreign_proposal_outbox_delete(&mut self, bucket: Shard, block_id: &BlockId) -> Result<bool, StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        let num_deleted = diesel::delete(foreign_proposal_outbox::table)
+            .filter(foreign_proposal_outbox::bucket.eq(bucket.as_u32() as i32))
+            .filter(foreign_proposal_outbox::block_id.eq(serialize_hex(block_id)))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_delete",
+                source: e,
+            })?;
+
+        Ok(num_deleted > 0)
+    }
+
+    fn foreign_proposal_outbox_delete_created_before(&mut self, created_before: u64) -> Result<usize, StorageError> {
+        use crate::schema::foreign_proposal_outbox;
+
+        let num_deleted = diesel::delete(foreign_proposal_outbox::table)
+            .filter(foreign_proposal_outbox::created_at.lt(created_before as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposal_outbox_delete_created_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn applied_foreign_proposals_insert(&mut self, bucket: Shard, block_id: &BlockId) -> Result<bool, StorageError> {
+        use crate::schema::applied_foreign_proposals;
+
+        let num_inserted = diesel::insert_into(applied_foreign_proposals::table)
+            .values((
+                applied_foreign_proposals::bucket.eq(bucket.as_u32() as i32),
+                applied_foreign_proposals::block_id.eq(serialize_hex(block_id)),
+            ))
+            .on_conflict(applied_foreign_proposals::block_id)
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "applied_foreign_proposals_insert",
+                source: e,
+            })?;
+
+        Ok(num_inserted > 0)
+    }
+
     fn foreign_send_counters_set(
         &mut self,
         foreign_send_counter: &ForeignSendCounters,
@@ -629,6 +968,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
             transactions::abort_details.eq(tx_rec.abort_details()),
             transactions::min_epoch.eq(transaction.min_epoch().map(|e| e.as_u64() as i64)),
             transactions::max_epoch.eq(transaction.max_epoch().map(|e| e.as_u64() as i64)),
+            transactions::priority_fee.eq(transaction.priority_fee() as i64),
         );
 
         diesel::insert_into(transactions::table)
@@ -743,9 +1083,16 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
             .map(|atom| {
                 // TODO(perf): n calls, 2n queries, query is slow
                 let exec = self.transaction_executions_get_pending_for_block(&atom.id, &block_id)?;
+                // Record why the execution was rejected (e.g. because it exceeded the execution budget) so that the
+                // abort reason is available on the transaction record
+                let abort_details = if atom.decision.is_abort() {
+                    exec.result().finalize.full_reject().map(|reason| reason.to_string())
+                } else {
+                    None
+                };
 
                 Ok((
-                    transactions::transaction_id.eq(serialize_hex(atom.id())),
+                    serialize_hex(atom.id()),
                     (
                         transactions::resolved_inputs.eq(serialize_json(&exec.resolved_inputs())?),
                         transactions::resulting_outputs.eq(serialize_json(&exec.resulting_outputs())?),
@@ -754,19 +1101,32 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
                         transactions::final_decision.eq(atom.decision.to_string()),
                         transactions::finalized_at.eq(now()),
                     ),
+                    abort_details,
                 ))
             })
             .collect::<Result<Vec<_>, StorageError>>()?;
 
-        for (predicate, change) in changes {
+        for (transaction_id, change, abort_details) in changes {
             diesel::update(transactions::table)
-                .filter(predicate)
+                .filter(transactions::transaction_id.eq(&transaction_id))
                 .set(change)
                 .execute(self.connection())
                 .map_err(|e| SqliteStorageError::DieselError {
                     operation: "transactions_finalize_all",
                     source: e,
                 })?;
+
+            // Existing abort details are kept if the execution does not have a reject reason
+            if let Some(abort_details) = abort_details {
+                diesel::update(transactions::table)
+                    .filter(transactions::transaction_id.eq(&transaction_id))
+                    .set(transactions::abort_details.eq(abort_details))
+                    .execute(self.connection())
+                    .map_err(|e| SqliteStorageError::DieselError {
+                        operation: "transactions_finalize_all",
+                        source: e,
+                    })?;
+            }
         }
 
         Ok(())
@@ -776,7 +1136,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         &mut self,
         transaction_execution: &TransactionExecution,
     ) -> Result<(), StorageError> {
-        use crate::schema::transaction_executions;
+        use crate::schema::{transaction_executions, transaction_pool};
 
         let insert = (
             transaction_executions::block_id.eq(serialize_hex(transaction_execution.block_id())),
@@ -796,6 +1156,17 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
                 source: e,
             })?;
 
+        // The maximum fee is only known once the fee instructions have been executed. Update the pool record (if any)
+        // so that it is prioritised accordingly when proposing.
+        diesel::update(transaction_pool::table)
+            .filter(transaction_pool::transaction_id.eq(serialize_hex(transaction_execution.transaction_id())))
+            .set(transaction_pool::max_fee.eq(max_fee_from_result(transaction_execution.result())))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_executions_insert",
+                source: e,
+            })?;
+
         Ok(())
     }
 
@@ -804,19 +1175,50 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         transaction: TransactionAtom,
         stage: TransactionPoolStage,
         is_ready: bool,
+        first_seen_height: NodeHeight,
     ) -> Result<(), StorageError> {
-        use crate::schema::transaction_pool;
+        use crate::schema::{transaction_pool, transactions};
+
+        let transaction_id = serialize_hex(transaction.id);
+
+        // The fees are used to prioritise the transaction when proposing. The transaction record may not exist (e.g.
+        // foreign keys are disabled), in which case the transaction has no priority. The maximum fee is updated once
+        // the transaction is executed if it has not been executed yet.
+        let fees = transactions::table
+            .select((transactions::priority_fee, transactions::result))
+            .filter(transactions::transaction_id.eq(&transaction_id))
+            .first::<(i64, Option<String>)>(self.connection())
+            .optional()
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_pool_insert",
+                source: e,
+            })?;
+        let (priority_fee, max_fee) = match fees {
+            Some((priority_fee, result)) => {
+                let max_fee = result
+                    .as_deref()
+                    .map(deserialize_json::<ExecuteResult>)
+                    .transpose()?
+                    .map(|result| max_fee_from_result(&result))
+                    .unwrap_or(0);
+                (priority_fee, max_fee)
+            },
+            None => (0, 0),
+        };
 
         let insert = (
-            transaction_pool::transaction_id.eq(serialize_hex(transaction.id)),
+            transaction_pool::transaction_id.eq(transaction_id),
             transaction_pool::original_decision.eq(transaction.decision.to_string()),
             transaction_pool::transaction_fee.eq(transaction.transaction_fee as i64),
             transaction_pool::evidence.eq(serialize_json(&transaction.evidence)?),
             transaction_pool::leader_fee.eq(transaction.leader_fee.as_ref().map(|f| f.fee as i64)),
             transaction_pool::global_exhaust_burn
                 .eq(transaction.leader_fee.as_ref().map(|f| f.global_exhaust_burn as i64)),
+            transaction_pool::priority_fee.eq(priority_fee),
+            transaction_pool::max_fee.eq(max_fee),
             transaction_pool::stage.eq(stage.to_string()),
             transaction_pool::is_ready.eq(is_ready),
+            transaction_pool::first_seen_height.eq(first_seen_height.as_u64() as i64),
         );
 
         diesel::insert_into(transaction_pool::table)
@@ -976,6 +1378,36 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn transaction_pool_increment_deferred_execution_attempts(
+        &mut self,
+        transaction_id: &TransactionId,
+    ) -> Result<(), StorageError> {
+        use crate::schema::transaction_pool;
+
+        let transaction_id = serialize_hex(transaction_id);
+
+        let num_affected = diesel::update(transaction_pool::table)
+            .filter(transaction_pool::transaction_id.eq(&transaction_id))
+            .set((
+                transaction_pool::deferred_execution_attempts.eq(transaction_pool::deferred_execution_attempts + 1),
+                transaction_pool::updated_at.eq(now()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_pool_increment_deferred_execution_attempts",
+                source: e,
+            })?;
+
+        if num_affected == 0 {
+            return Err(StorageError::NotFound {
+                item: "transaction".to_string(),
+                key: transaction_id,
+            });
+        }
+
+        Ok(())
+    }
+
     fn transaction_pool_remove(&mut self, transaction_id: &TransactionId) -> Result<(), StorageError> {
         use crate::schema::{transaction_pool, transaction_pool_state_updates};
 
@@ -1044,7 +1476,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
             })?;
 
         txs.into_iter()
-            .map(|tx| tx.try_convert(None).map(|t| t.into_atom()))
+            .map(|tx| tx.try_convert(None, self.blob_limits()).map(|t| t.into_atom()))
             .collect()
     }
 
@@ -1217,6 +1649,35 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(None)
     }
 
+    fn parked_blocks_set_transactions_requested(
+        &mut self,
+        block_id: &BlockId,
+        requested_at: u64,
+    ) -> Result<(), StorageError> {
+        use crate::schema::parked_blocks;
+
+        let num_updated = diesel::update(parked_blocks::table)
+            .filter(parked_blocks::block_id.eq(serialize_hex(block_id)))
+            .set((
+                parked_blocks::transactions_requested_at.eq(requested_at as i64),
+                parked_blocks::transaction_request_attempts.eq(parked_blocks::transaction_request_attempts + 1),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "parked_blocks_set_transactions_requested",
+                source: e,
+            })?;
+
+        if num_updated == 0 {
+            return Err(StorageError::NotFound {
+                item: "parked_blocks".to_string(),
+                key: block_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     fn votes_insert(&mut self, vote: &Vote) -> Result<(), StorageError> {
         use crate::schema::votes;
 
@@ -1224,6 +1685,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
             votes::hash.eq(serialize_hex(vote.calculate_hash())),
             votes::epoch.eq(vote.epoch.as_u64() as i64),
             votes::block_id.eq(serialize_hex(vote.block_id)),
+            votes::height.eq(vote.height.as_u64() as i64),
             votes::sender_leaf_hash.eq(serialize_hex(vote.sender_leaf_hash)),
             votes::decision.eq(i32::from(vote.decision.as_u8())),
             votes::signature.eq(serialize_json(&vote.signature)?),
@@ -1240,6 +1702,228 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn qc_timings_upsert(&mut self, timing: &QcTiming) -> Result<(), StorageError> {
+        use crate::schema::qc_timings;
+
+        let values = (
+            qc_timings::block_id.eq(serialize_hex(timing.block_id)),
+            qc_timings::epoch.eq(timing.epoch.as_u64() as i64),
+            qc_timings::height.eq(timing.height.as_u64() as i64),
+            qc_timings::first_vote_ms.eq(timing.first_vote_ms as i64),
+            qc_timings::quorum_reached_ms.eq(timing.quorum_reached_ms.map(|ms| ms as i64)),
+            qc_timings::last_vote_ms.eq(timing.last_vote_ms as i64),
+            qc_timings::votes.eq(serialize_json(&timing.votes)?),
+        );
+
+        diesel::insert_into(qc_timings::table)
+            .values(values.clone())
+            .on_conflict(qc_timings::block_id)
+            .do_update()
+            .set(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "qc_timings_upsert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn proposer_equivocations_insert(&mut self, equivocation: &ProposerEquivocation) -> Result<(), StorageError> {
+        use crate::schema::proposer_equivocations;
+
+        let values = (
+            proposer_equivocations::epoch.eq(equivocation.epoch().as_u64() as i64),
+            proposer_equivocations::height.eq(equivocation.height().as_u64() as i64),
+            proposer_equivocations::proposed_by.eq(serialize_hex(equivocation.proposed_by().as_bytes())),
+            proposer_equivocations::first_block_id.eq(serialize_hex(equivocation.first_block_id())),
+            proposer_equivocations::second_block_id.eq(serialize_hex(equivocation.second_block_id())),
+            proposer_equivocations::first_block.eq(serialize_json(equivocation.first_block())?),
+            proposer_equivocations::second_block.eq(serialize_json(equivocation.second_block())?),
+        );
+
+        diesel::insert_into(proposer_equivocations::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposer_equivocations_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn equivocation_evidence_insert(&mut self, evidence: &EquivocationEvidence) -> Result<(), StorageError> {
+        use crate::schema::equivocation_evidence;
+
+        let values = (
+            equivocation_evidence::epoch.eq(evidence.epoch().as_u64() as i64),
+            equivocation_evidence::height.eq(evidence.height().as_u64() as i64),
+            equivocation_evidence::sender_leaf_hash.eq(serialize_hex(evidence.sender_leaf_hash())),
+            equivocation_evidence::voter.eq(serialize_hex(evidence.voter().as_bytes())),
+            equivocation_evidence::first_block_id.eq(serialize_hex(evidence.first_block_id())),
+            equivocation_evidence::second_block_id.eq(serialize_hex(evidence.second_block_id())),
+            equivocation_evidence::first_vote.eq(serialize_json(evidence.first_vote())?),
+            equivocation_evidence::second_vote.eq(serialize_json(evidence.second_vote())?),
+        );
+
+        diesel::insert_into(equivocation_evidence::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "equivocation_evidence_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn block_rejections_insert(&mut self, rejection: &BlockRejection) -> Result<(), StorageError> {
+        use crate::schema::block_rejections;
+
+        let values = (
+            block_rejections::block_id.eq(serialize_hex(rejection.block_id)),
+            block_rejections::epoch.eq(rejection.epoch.as_u64() as i64),
+            block_rejections::height.eq(rejection.height.as_u64() as i64),
+            block_rejections::proposed_by.eq(serialize_hex(rejection.proposed_by.as_bytes())),
+            block_rejections::reason.eq(&rejection.reason),
+        );
+
+        diesel::insert_into(block_rejections::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "block_rejections_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn block_rejections_prune_before(&mut self, epoch: Epoch) -> Result<(), StorageError> {
+        use crate::schema::block_rejections;
+
+        diesel::delete(block_rejections::table)
+            .filter(block_rejections::epoch.lt(epoch.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "block_rejections_prune_before",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn new_views_insert(&mut self, new_view: &NewViewRecord<Self::Addr>) -> Result<bool, StorageError> {
+        use crate::schema::new_views;
+
+        let values = (
+            new_views::epoch.eq(new_view.epoch.as_u64() as i64),
+            new_views::new_height.eq(new_view.new_height.as_u64() as i64),
+            new_views::sender.eq(serialize_json(&new_view.sender)?),
+            new_views::high_qc_id.eq(serialize_hex(new_view.high_qc_id)),
+            new_views::high_qc_height.eq(new_view.high_qc_height.as_u64() as i64),
+        );
+
+        let num_inserted = diesel::insert_into(new_views::table)
+            .values(values)
+            .on_conflict((new_views::epoch, new_views::new_height, new_views::sender))
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "new_views_insert",
+                source: e,
+            })?;
+
+        Ok(num_inserted > 0)
+    }
+
+    fn new_views_prune_before(&mut self, epoch: Epoch, new_height: NodeHeight) -> Result<usize, StorageError> {
+        use crate::schema::new_views;
+
+        let epoch = epoch.as_u64() as i64;
+        let new_height = new_height.as_u64() as i64;
+        let num_deleted = diesel::delete(new_views::table)
+            .filter(
+                new_views::epoch
+                    .lt(epoch)
+                    .or(new_views::epoch.eq(epoch).and(new_views::new_height.lt(new_height))),
+            )
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "new_views_prune_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn epoch_checkpoints_insert(
+        &mut self,
+        block_id: &BlockId,
+        checkpoint: &EpochCheckpoint,
+    ) -> Result<(), StorageError> {
+        use crate::schema::epoch_checkpoints;
+
+        let values = (
+            epoch_checkpoints::epoch.eq(checkpoint.epoch().as_u64() as i64),
+            epoch_checkpoints::block_id.eq(serialize_hex(block_id)),
+            epoch_checkpoints::state_merkle_root.eq(serialize_hex(checkpoint.state_merkle_root())),
+            epoch_checkpoints::validator_set_hash.eq(serialize_hex(checkpoint.validator_set_hash())),
+        );
+
+        diesel::insert_into(epoch_checkpoints::table)
+            .values(values)
+            .on_conflict(epoch_checkpoints::epoch)
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "epoch_checkpoints_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn peer_misbehaviors_upsert(&mut self, misbehavior: &PeerMisbehavior<Self::Addr>) -> Result<(), StorageError> {
+        use crate::schema::peer_misbehaviors;
+
+        let values = (
+            peer_misbehaviors::address.eq(serialize_json(&misbehavior.address)?),
+            peer_misbehaviors::score.eq(misbehavior.score as i64),
+            peer_misbehaviors::last_offence.eq(misbehavior.last_offence as i64),
+            peer_misbehaviors::reason.eq(&misbehavior.reason),
+            peer_misbehaviors::banned_until.eq(misbehavior.banned_until.map(|t| t as i64)),
+        );
+
+        diesel::insert_into(peer_misbehaviors::table)
+            .values(&values)
+            .on_conflict(peer_misbehaviors::address)
+            .do_update()
+            .set(values.clone())
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "peer_misbehaviors_upsert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn peer_misbehaviors_remove(&mut self, address: &Self::Addr) -> Result<bool, StorageError> {
+        use crate::schema::peer_misbehaviors;
+
+        let num_deleted = diesel::delete(peer_misbehaviors::table)
+            .filter(peer_misbehaviors::address.eq(serialize_json(address)?))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "peer_misbehaviors_remove",
+                source: e,
+            })?;
+
+        Ok(num_deleted > 0)
+    }
+
     fn substate_locks_insert_all<I: IntoIterator<Item = (SubstateId, Vec<LockedSubstate>)>>(
         &mut self,
         block_id: BlockId,
@@ -1291,6 +1975,23 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn substate_locks_remove_many_for_blocks<'a, I: IntoIterator<Item = &'a BlockId>>(
+        &mut self,
+        block_ids: I,
+    ) -> Result<(), StorageError> {
+        use crate::schema::substate_locks;
+
+        diesel::delete(substate_locks::table)
+            .filter(substate_locks::block_id.eq_any(block_ids.into_iter().map(serialize_hex)))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substate_locks_remove_many_for_blocks",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn substate_down_many<I: IntoIterator<Item = SubstateAddress>>(
         &mut self,
         addresses: I,
@@ -1354,6 +2055,139 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn shard_growth_samples_insert(&mut self, sample: &ShardGrowthSample) -> Result<(), StorageError> {
+        use crate::schema::shard_growth_samples;
+
+        let values = (
+            shard_growth_samples::epoch.eq(sample.epoch.as_u64() as i64),
+            shard_growth_samples::shard.eq(sample.shard.as_u32() as i32),
+            shard_growth_samples::substate_count.eq(sample.substate_count as i64),
+            shard_growth_samples::total_bytes.eq(sample.total_bytes as i64),
+            shard_growth_samples::count_delta.eq(sample.count_delta),
+            shard_growth_samples::bytes_delta.eq(sample.bytes_delta),
+            shard_growth_samples::interval_secs.eq(sample.interval_secs as i64),
+            shard_growth_samples::sampled_at.eq(sample.sampled_at as i64),
+        );
+
+        diesel::insert_into(shard_growth_samples::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "shard_growth_samples_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn shard_growth_samples_prune(&mut self, shard: Shard, keep: usize) -> Result<(), StorageError> {
+        use crate::schema::shard_growth_samples;
+
+        let shard = shard.as_u32() as i32;
+        let oldest_kept_id = shard_growth_samples::table
+            .select(shard_growth_samples::id)
+            .filter(shard_growth_samples::shard.eq(shard))
+            .order_by(shard_growth_samples::id.desc())
+            .offset(keep.saturating_sub(1) as i64)
+            .first::<i32>(self.connection())
+            .optional()
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "shard_growth_samples_prune",
+                source: e,
+            })?;
+
+        let Some(oldest_kept_id) = oldest_kept_id else {
+            return Ok(());
+        };
+
+        diesel::delete(shard_growth_samples::table)
+            .filter(shard_growth_samples::shard.eq(shard))
+            .filter(shard_growth_samples::id.lt(oldest_kept_id))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "shard_growth_samples_prune",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn vault_owners_set(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_ids: &[VaultId],
+    ) -> Result<(), StorageError> {
+        use crate::schema::vault_owners;
+
+        let component_address = component_address.to_string();
+        for vault_id in vault_ids {
+            diesel::insert_into(vault_owners::table)
+                .values((
+                    vault_owners::vault_id.eq(vault_id.to_string()),
+                    vault_owners::component_address.eq(&component_address),
+                ))
+                .on_conflict(vault_owners::vault_id)
+                .do_update()
+                .set(vault_owners::component_address.eq(&component_address))
+                .execute(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "vault_owners_set",
+                    source: e,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    fn nft_ownership_set_vault(
+        &mut self,
+        vault_id: &VaultId,
+        resource_address: &ResourceAddress,
+        nft_ids: &[NonFungibleId],
+    ) -> Result<(), StorageError> {
+        use crate::schema::nft_ownership;
+
+        let vault_id = vault_id.to_string();
+        let resource_address = resource_address.to_string();
+        for nft_id in nft_ids {
+            diesel::insert_into(nft_ownership::table)
+                .values((
+                    nft_ownership::resource_address.eq(&resource_address),
+                    nft_ownership::nft_id.eq(nft_id.to_canonical_string()),
+                    nft_ownership::vault_id.eq(&vault_id),
+                ))
+                .on_conflict((nft_ownership::resource_address, nft_ownership::nft_id))
+                .do_update()
+                .set(nft_ownership::vault_id.eq(&vault_id))
+                .execute(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "nft_ownership_set_vault",
+                    source: e,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    fn nft_ownership_remove_from_vault(
+        &mut self,
+        vault_id: &VaultId,
+        nft_ids: &[NonFungibleId],
+    ) -> Result<(), StorageError> {
+        use crate::schema::nft_ownership;
+
+        diesel::delete(nft_ownership::table)
+            .filter(nft_ownership::vault_id.eq(vault_id.to_string()))
+            .filter(nft_ownership::nft_id.eq_any(nft_ids.iter().map(|id| id.to_canonical_string())))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "nft_ownership_remove_from_vault",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn pending_state_tree_diffs_remove_by_block(
         &mut self,
         block_id: &BlockId,
@@ -1398,6 +2232,146 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
 
         Ok(())
     }
+
+    fn blocks_prune_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError> {
+        use crate::schema::blocks;
+
+        safety_info.check_height(before_height)?;
+        let max_epoch = blocks::table
+            .select(diesel::dsl::max(blocks::epoch))
+            .filter(blocks::height.lt(before_height.as_u64() as i64))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_prune_before",
+                source: e,
+            })?;
+        if let Some(epoch) = max_epoch {
+            safety_info.check_fee_epoch(Epoch(epoch as u64))?;
+        }
+
+        // Records that reference the pruned blocks are removed first so that foreign key constraints are not violated
+        for table in [
+            "block_diffs",
+            "substate_locks",
+            "transaction_pool_state_updates",
+            "pending_state_tree_diffs",
+            "foreign_send_counters",
+            "leaf_blocks",
+            "high_qcs",
+            "last_sent_vote",
+            "last_executed",
+            "locked_block",
+        ] {
+            diesel::sql_query(format!(
+                "DELETE FROM {} WHERE block_id IN (SELECT block_id FROM blocks WHERE height < ?)",
+                table
+            ))
+            .bind::<BigInt, _>(before_height.as_u64() as i64)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_prune_before",
+                source: e,
+            })?;
+        }
+
+        let num_deleted = diesel::delete(blocks::table)
+            .filter(blocks::height.lt(before_height.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_prune_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn substates_prune_destroyed_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError> {
+        use crate::schema::{blocks, substates};
+
+        safety_info.check_height(before_height)?;
+        let pruned_blocks = blocks::table
+            .select(blocks::block_id.nullable())
+            .filter(blocks::height.lt(before_height.as_u64() as i64));
+
+        let num_deleted = diesel::delete(substates::table)
+            .filter(substates::destroyed_by_block.eq_any(pruned_blocks))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substates_prune_destroyed_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn votes_prune_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_epoch: Epoch,
+    ) -> Result<usize, StorageError> {
+        use crate::schema::votes;
+
+        safety_info.check_epoch(before_epoch)?;
+        let num_deleted = diesel::delete(votes::table)
+            .filter(votes::epoch.lt(before_epoch.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "votes_prune_before",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn foreign_counters_prune_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError> {
+        use crate::schema::{blocks, foreign_receive_counters, foreign_send_counters};
+
+        safety_info.check_height(before_height)?;
+        let pruned_blocks = blocks::table
+            .select(blocks::block_id)
+            .filter(blocks::height.lt(before_height.as_u64() as i64));
+
+        let num_send_deleted = diesel::delete(foreign_send_counters::table)
+            .filter(foreign_send_counters::block_id.eq_any(pruned_blocks))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_counters_prune_before",
+                source: e,
+            })?;
+
+        // Only the latest receive counters are ever read
+        let latest_id = foreign_receive_counters::table
+            .select(diesel::dsl::max(foreign_receive_counters::id))
+            .first::<Option<i32>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_counters_prune_before",
+                source: e,
+            })?;
+        let num_receive_deleted = match latest_id {
+            Some(latest_id) => diesel::delete(foreign_receive_counters::table)
+                .filter(foreign_receive_counters::id.lt(latest_id))
+                .execute(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "foreign_counters_prune_before",
+                    source: e,
+                })?,
+            None => 0,
+        };
+
+        Ok(num_send_deleted + num_receive_deleted)
+    }
 }
 
 impl<'a, TAddr> Deref for SqliteStateStoreWriteTransaction<'a, TAddr> {
@@ -1416,6 +2390,7 @@ impl<'a, TAddr> Deref for SqliteStateStoreWriteTransaction<'a, TAddr> {
 
 impl<TAddr> Drop for SqliteStateStoreWriteTransaction<'_, TAddr> {
     fn drop(&mut self) {
+        self.write_queue.record_duration(self.started_at.elapsed());
         if self.transaction.is_some() {
             warn!(
                 target: LOG_TARGET,
@@ -1429,3 +2404,13 @@ fn now() -> PrimitiveDateTime {
     let now = time::OffsetDateTime::now_utc();
     PrimitiveDateTime::new(now.date(), now.time())
 }
+
+fn max_fee_from_result(result: &ExecuteResult) -> i64 {
+    result
+        .finalize
+        .fee_receipt
+        .total_allocated_fee_payments()
+        .as_u64_checked()
+        .and_then(|fee| i64::try_from(fee).ok())
+        .unwrap_or(0)
+}
@@ -26,6 +26,7 @@ fn create_tx_atom() -> TransactionAtom {
         evidence: Default::default(),
         transaction_fee: 0,
         leader_fee: None,
+        priority_fee: 0,
     }
 }
 
@@ -70,11 +71,11 @@ mod confirm_all_transitions {
         );
         block1.insert(&mut tx).unwrap();
 
-        tx.transaction_pool_insert(atom1.clone(), TransactionPoolStage::New, false)
+        tx.transaction_pool_insert(atom1.clone(), TransactionPoolStage::New, false, NodeHeight::zero())
             .unwrap();
-        tx.transaction_pool_insert(atom2.clone(), TransactionPoolStage::New, false)
+        tx.transaction_pool_insert(atom2.clone(), TransactionPoolStage::New, false, NodeHeight::zero())
             .unwrap();
-        tx.transaction_pool_insert(atom3.clone(), TransactionPoolStage::New, false)
+        tx.transaction_pool_insert(atom3.clone(), TransactionPoolStage::New, false, NodeHeight::zero())
             .unwrap();
         let block_id = *block1.id();
 
@@ -147,3 +148,2402 @@ mod confirm_all_transitions {
         tx.rollback().unwrap();
     }
 }
+
+mod conflict_edges {
+    use std::str::FromStr;
+
+    use tari_dan_storage::consensus_models::{BlockId, LockedSubstate, SubstateLockFlag};
+    use tari_engine_types::substate::SubstateId;
+
+    use super::*;
+
+    fn lock(atom: &TransactionAtom, lock_flag: SubstateLockFlag) -> LockedSubstate {
+        LockedSubstate::new(atom.id, 0, lock_flag, false)
+    }
+
+    #[test]
+    fn it_returns_an_edge_for_each_write_conflict_in_a_chain() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let atom1 = create_tx_atom();
+        let atom2 = create_tx_atom();
+        let atom3 = create_tx_atom();
+        for atom in [&atom1, &atom2, &atom3] {
+            tx.transaction_pool_insert(atom.clone(), TransactionPoolStage::New, true, NodeHeight::zero())
+                .unwrap();
+        }
+
+        let substate_a =
+            SubstateId::from_str("component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5").unwrap();
+        let substate_b =
+            SubstateId::from_str("component_0000e29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5").unwrap();
+        tx.substate_locks_insert_all(BlockId::genesis(), [
+            (substate_a.clone(), vec![
+                lock(&atom1, SubstateLockFlag::Write),
+                lock(&atom2, SubstateLockFlag::Write),
+            ]),
+            (substate_b.clone(), vec![
+                lock(&atom2, SubstateLockFlag::Write),
+                lock(&atom3, SubstateLockFlag::Write),
+            ]),
+        ])
+        .unwrap();
+
+        let edges = tx.transaction_pool_get_conflict_edges(None, 100).unwrap();
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].from, atom1.id);
+        assert_eq!(edges[0].to, atom2.id);
+        assert_eq!(edges[0].substate_id, substate_a);
+        assert!(edges[0].from_lock.is_write());
+        assert!(edges[0].to_lock.is_write());
+        assert_eq!(edges[1].from, atom2.id);
+        assert_eq!(edges[1].to, atom3.id);
+        assert_eq!(edges[1].substate_id, substate_b);
+
+        let edges = tx.transaction_pool_get_conflict_edges(None, 1).unwrap();
+        assert_eq!(edges.len(), 1);
+
+        // The filter is applied before the limit
+        let edges = tx.transaction_pool_get_conflict_edges(Some(&substate_b), 1).unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from, atom2.id);
+        assert_eq!(edges[0].to, atom3.id);
+        assert_eq!(edges[0].substate_id, substate_b);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_does_not_return_edges_between_readers() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let atom1 = create_tx_atom();
+        let atom2 = create_tx_atom();
+        let atom3 = create_tx_atom();
+        for atom in [&atom1, &atom2, &atom3] {
+            tx.transaction_pool_insert(atom.clone(), TransactionPoolStage::New, true, NodeHeight::zero())
+                .unwrap();
+        }
+
+        let substate_a =
+            SubstateId::from_str("component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5").unwrap();
+        tx.substate_locks_insert_all(BlockId::genesis(), [(substate_a, vec![
+            lock(&atom1, SubstateLockFlag::Read),
+            lock(&atom2, SubstateLockFlag::Read),
+            lock(&atom3, SubstateLockFlag::Write),
+        ])])
+        .unwrap();
+
+        let edges = tx.transaction_pool_get_conflict_edges(None, 100).unwrap();
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| e.to == atom3.id && e.from_lock.is_read()));
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod substate_lock_release {
+    use std::str::FromStr;
+
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{BlockId, LockedSubstate, SubstateLockFlag, SubstateRecord};
+    use tari_engine_types::substate::SubstateId;
+
+    use super::*;
+
+    fn insert_block<TTx: StateStoreWriteTransaction>(tx: &mut TTx, parent: &Block, timestamp: u64) -> Block {
+        let block = Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            parent.height() + NodeHeight(1),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            timestamp,
+            0,
+            FixedHash::zero(),
+        );
+        block.insert(tx).unwrap();
+        block
+    }
+
+    fn substate() -> SubstateId {
+        SubstateId::from_str("component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5").unwrap()
+    }
+
+    fn insert_write_lock<TTx: StateStoreWriteTransaction>(tx: &mut TTx, block_id: BlockId, atom: &TransactionAtom) {
+        let lock = LockedSubstate::new(atom.id, 0, SubstateLockFlag::Write, false);
+        tx.substate_locks_insert_all(block_id, [(substate(), vec![lock])])
+            .unwrap();
+    }
+
+    fn latest_lock_holder<TTx: StateStoreReadTransaction>(tx: &TTx) -> TransactionId {
+        tx.substate_locks_get_latest_for_substate(&substate())
+            .unwrap()
+            .transaction_id()
+    }
+
+    #[test]
+    fn it_releases_locks_held_by_finalized_transactions() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let atom1 = create_tx_atom();
+        let atom2 = create_tx_atom();
+        insert_write_lock(&mut tx, BlockId::genesis(), &atom1);
+        insert_write_lock(&mut tx, BlockId::genesis(), &atom2);
+        assert_eq!(latest_lock_holder(&*tx), atom2.id);
+
+        SubstateRecord::remove_locks_for_transactions(&mut tx, [&atom2.id]).unwrap();
+        assert_eq!(latest_lock_holder(&*tx), atom1.id);
+
+        SubstateRecord::remove_locks_for_transactions(&mut tx, [&atom1.id]).unwrap();
+        assert!(tx.substate_locks_get_latest_for_substate(&substate()).is_err());
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_releases_locks_acquired_by_blocks_on_an_abandoned_fork() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+
+        // zero <- common <- abandoned1 <- abandoned2
+        //                \- new_tip
+        let common = insert_block(&mut tx, &zero_block, 1);
+        let abandoned1 = insert_block(&mut tx, &common, 2);
+        let abandoned2 = insert_block(&mut tx, &abandoned1, 3);
+        let new_tip = insert_block(&mut tx, &common, 4);
+
+        let atom1 = create_tx_atom();
+        let atom2 = create_tx_atom();
+        let atom3 = create_tx_atom();
+        insert_write_lock(&mut tx, *common.id(), &atom1);
+        insert_write_lock(&mut tx, *abandoned1.id(), &atom2);
+        insert_write_lock(&mut tx, *abandoned2.id(), &atom3);
+
+        let abandoned =
+            SubstateRecord::remove_locks_for_abandoned_fork(&mut tx, abandoned2.id(), new_tip.id()).unwrap();
+        assert_eq!(abandoned, vec![*abandoned2.id(), *abandoned1.id()]);
+
+        assert_eq!(latest_lock_holder(&*tx), atom1.id);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod blob_limits {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::StorageError;
+    use tari_state_store_sqlite::BlobLimits;
+
+    use super::*;
+
+    fn insert_block<TTx: StateStoreWriteTransaction>(tx: &mut TTx, parent: &Block, commands: Vec<Command>) -> Block {
+        let block = Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            parent.height() + NodeHeight(1),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            commands.into_iter().collect(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            0,
+            0,
+            FixedHash::zero(),
+        );
+        block.insert(tx).unwrap();
+        block
+    }
+
+    #[test]
+    fn it_refuses_to_decode_block_commands_that_exceed_the_limit() {
+        let db = create_db().with_blob_limits(BlobLimits {
+            max_block_commands_size: 128,
+            ..Default::default()
+        });
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+
+        let small_block = insert_block(&mut tx, &zero_block, vec![]);
+        let large_block = insert_block(
+            &mut tx,
+            &small_block,
+            (0..10).map(|_| Command::Prepare(create_tx_atom())).collect(),
+        );
+
+        assert_eq!(tx.blocks_get(small_block.id()).unwrap().id(), small_block.id());
+        let err = tx.blocks_get(large_block.id()).unwrap_err();
+        assert!(
+            matches!(err, StorageError::BlobTooLarge { size, max_size: 128, .. } if size > 128),
+            "Unexpected error: {}",
+            err
+        );
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod maintenance {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn it_runs_maintenance_when_idle() {
+        let db = create_db();
+        let elapsed = db.maintenance(true).unwrap();
+        assert!(elapsed.is_some());
+
+        // The store remains usable after maintenance
+        let tx = db.create_read_tx().unwrap();
+        assert_eq!(tx.blocks_get_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn it_skips_maintenance_while_a_transaction_is_active() {
+        let db = create_db();
+        let tx = db.create_write_tx().unwrap();
+        assert!(db.maintenance(false).unwrap().is_none());
+        tx.rollback().unwrap();
+
+        assert!(db.maintenance(false).unwrap().is_some());
+    }
+
+    #[test]
+    fn it_returns_an_error_if_the_connection_is_poisoned() {
+        let db = create_db();
+        let poisoner = {
+            let db = db.clone();
+            thread::spawn(move || {
+                let _tx = db.create_write_tx().unwrap();
+                panic!("poison the connection");
+            })
+        };
+        assert!(poisoner.join().is_err());
+
+        assert!(db.maintenance(false).is_err());
+    }
+
+    #[test]
+    fn it_does_not_deadlock_with_concurrent_reads() {
+        let db = create_db();
+
+        let readers = (0..4)
+            .map(|_| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        let tx = db.create_read_tx().unwrap();
+                        tx.transaction_pool_count(None, None, None).unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Maintenance may be skipped while a reader holds the connection, but must never block or fail
+        for _ in 0..50 {
+            db.maintenance(true).unwrap();
+        }
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        // Once all readers are done, maintenance always runs
+        assert!(db.maintenance(true).unwrap().is_some());
+    }
+}
+
+mod foreign_proposal_outbox {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{BlockId, ForeignProposalOutboxEntry};
+
+    use super::*;
+
+    #[test]
+    fn it_records_attempts_until_removed() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let block_id = BlockId::from(FixedHash::from([1u8; 32]));
+
+        let mut entry = ForeignProposalOutboxEntry::new(Shard::from(1), block_id, 100);
+        entry.insert_if_not_exists(&mut tx).unwrap();
+        // Inserting again does not reset the entry
+        entry
+            .record_attempt(101, Some("unreachable".to_string()))
+            .update(&mut tx)
+            .unwrap();
+        ForeignProposalOutboxEntry::new(Shard::from(1), block_id, 200)
+            .insert_if_not_exists(&mut tx)
+            .unwrap();
+
+        let entries = ForeignProposalOutboxEntry::get_all(&*tx).unwrap();
+        assert_eq!(entries, vec![entry]);
+        assert_eq!(entries[0].attempts, 1);
+        assert_eq!(entries[0].last_error.as_deref(), Some("unreachable"));
+
+        assert!(ForeignProposalOutboxEntry::remove(&mut tx, Shard::from(1), &block_id).unwrap());
+        assert!(!ForeignProposalOutboxEntry::remove(&mut tx, Shard::from(1), &block_id).unwrap());
+        assert_eq!(ForeignProposalOutboxEntry::count(&*tx).unwrap(), 0);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_removes_expired_entries() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        ForeignProposalOutboxEntry::new(Shard::from(1), BlockId::from(FixedHash::from([1u8; 32])), 100)
+            .insert_if_not_exists(&mut tx)
+            .unwrap();
+        ForeignProposalOutboxEntry::new(Shard::from(2), BlockId::from(FixedHash::from([1u8; 32])), 200)
+            .insert_if_not_exists(&mut tx)
+            .unwrap();
+
+        let num_removed = ForeignProposalOutboxEntry::remove_created_before(&mut tx, 150).unwrap();
+        assert_eq!(num_removed, 1);
+        let entries = ForeignProposalOutboxEntry::get_all(&*tx).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].bucket, Shard::from(2));
+        tx.rollback().unwrap();
+    }
+}
+
+mod applied_foreign_proposals {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{BlockId, ForeignProposal};
+
+    use super::*;
+
+    #[test]
+    fn it_records_a_proposal_as_applied_once() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let block_id = BlockId::from(FixedHash::from([1u8; 32]));
+
+        assert!(!ForeignProposal::is_applied(&*tx, &block_id).unwrap());
+        assert!(ForeignProposal::record_applied(&mut tx, Shard::from(1), &block_id).unwrap());
+        assert!(!ForeignProposal::record_applied(&mut tx, Shard::from(1), &block_id).unwrap());
+        assert!(ForeignProposal::is_applied(&*tx, &block_id).unwrap());
+
+        // Deleting the foreign proposal once its transactions are resolved does not forget that it was applied
+        let proposal = ForeignProposal::new(Shard::from(1), block_id, vec![], 0);
+        proposal.upsert(&mut tx).unwrap();
+        proposal.delete(&mut tx).unwrap();
+        assert!(!ForeignProposal::record_applied(&mut tx, Shard::from(1), &block_id).unwrap());
+
+        ForeignProposal::record_applied(&mut tx, Shard::from(2), &BlockId::from(FixedHash::from([2u8; 32]))).unwrap();
+        assert_eq!(ForeignProposal::count_applied(&*tx, Shard::from(1)).unwrap(), 1);
+        assert_eq!(ForeignProposal::count_applied(&*tx, Shard::from(2)).unwrap(), 1);
+        assert_eq!(ForeignProposal::count_applied(&*tx, Shard::from(3)).unwrap(), 0);
+        tx.rollback().unwrap();
+    }
+}
+
+mod foreign_proposal_states {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{BlockId, ForeignProposal, ForeignProposalState};
+
+    use super::*;
+
+    fn create_proposal(seed: u8) -> ForeignProposal {
+        ForeignProposal::new(Shard::from(1), BlockId::from(FixedHash::from([seed; 32])), vec![], 0)
+    }
+
+    #[test]
+    fn it_keeps_expired_proposals_until_pruned() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let mut expired = create_proposal(1);
+        expired.set_proposed_height(NodeHeight(1));
+        expired.upsert(&mut tx).unwrap();
+        let mut proposed = create_proposal(2);
+        proposed.set_proposed_height(NodeHeight(2));
+        proposed.upsert(&mut tx).unwrap();
+
+        assert!(ForeignProposal::exists(&*tx, &expired).unwrap());
+        expired.expire(&mut tx, NodeHeight(10), Epoch(1)).unwrap();
+        // Expired proposals no longer count as existing proposals but are still returned by state
+        assert!(!ForeignProposal::exists(&*tx, &expired).unwrap());
+        assert_eq!(
+            ForeignProposal::get_all_by_state(&*tx, ForeignProposalState::Expired, 10, 0).unwrap(),
+            vec![expired.clone()]
+        );
+        assert_eq!(ForeignProposal::get_all_proposed(&*tx, NodeHeight(10)).unwrap(), vec![
+            proposed.clone()
+        ]);
+
+        assert_eq!(ForeignProposal::prune_terminal_before(&mut tx, Epoch(1)).unwrap(), 0);
+        assert_eq!(ForeignProposal::prune_terminal_before(&mut tx, Epoch(2)).unwrap(), 1);
+        assert!(
+            ForeignProposal::get_all_by_state(&*tx, ForeignProposalState::Expired, 10, 0)
+                .unwrap()
+                .is_empty()
+        );
+        // Proposals in a non-terminal state are never pruned
+        assert!(ForeignProposal::exists(&*tx, &proposed).unwrap());
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_does_not_overwrite_a_proposal_with_an_invalid_one() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let existing = create_proposal(1);
+        existing.upsert(&mut tx).unwrap();
+        assert!(!create_proposal(1)
+            .record_invalid(&mut tx, NodeHeight(5), Epoch(1))
+            .unwrap());
+        assert!(create_proposal(2)
+            .record_invalid(&mut tx, NodeHeight(5), Epoch(1))
+            .unwrap());
+
+        assert_eq!(ForeignProposal::get_all_new(&*tx).unwrap(), vec![existing]);
+        let invalid = ForeignProposal::get_all_by_state(&*tx, ForeignProposalState::Invalid, 10, 0).unwrap();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].block_id, BlockId::from(FixedHash::from([2u8; 32])));
+        assert_eq!(invalid[0].state, ForeignProposalState::Invalid);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_paginates_proposals_by_state() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        for seed in 1..=5 {
+            create_proposal(seed)
+                .record_invalid(&mut tx, NodeHeight(1), Epoch(0))
+                .unwrap();
+        }
+        create_proposal(6).upsert(&mut tx).unwrap();
+
+        let first_page = ForeignProposal::get_all_by_state(&*tx, ForeignProposalState::Invalid, 2, 0).unwrap();
+        let last_page = ForeignProposal::get_all_by_state(&*tx, ForeignProposalState::Invalid, 2, 4).unwrap();
+        assert_eq!(first_page.iter().map(|p| p.block_id).collect::<Vec<_>>(), vec![
+            BlockId::from(FixedHash::from([1u8; 32])),
+            BlockId::from(FixedHash::from([2u8; 32]))
+        ]);
+        assert_eq!(last_page.iter().map(|p| p.block_id).collect::<Vec<_>>(), vec![
+            BlockId::from(FixedHash::from([5u8; 32]))
+        ]);
+        tx.rollback().unwrap();
+    }
+}
+
+mod cursor_pagination {
+    use std::collections::HashSet;
+
+    use tari_common_types::types::PrivateKey;
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::{
+        consensus_models::{BlockId, TransactionRecord},
+        Ordering,
+    };
+    use tari_transaction::Transaction;
+    use tari_utilities::epoch_time::EpochTime;
+
+    use super::*;
+
+    fn insert_transaction<TTx: StateStoreWriteTransaction>(tx: &mut TTx) -> TransactionId {
+        let record = TransactionRecord::new(Transaction::builder().sign(&PrivateKey::default()).build());
+        record.insert(tx).unwrap();
+        *record.id()
+    }
+
+    fn insert_block<TTx: StateStoreWriteTransaction>(tx: &mut TTx, parent: &Block, height: u64) -> BlockId {
+        let block = Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            NodeHeight(height),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            EpochTime::now().as_u64(),
+            0,
+            FixedHash::zero(),
+        );
+        block.insert(tx).unwrap();
+        *block.id()
+    }
+
+    fn assert_no_duplicates<T: std::hash::Hash + Eq>(items: &[T]) {
+        let unique = items.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), items.len(), "page results contain duplicates");
+    }
+
+    #[test]
+    fn it_returns_transactions_inserted_between_pages_without_gaps() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let mut expected = (0..3).map(|_| insert_transaction(&mut tx)).collect::<Vec<_>>();
+
+        let (page, mut cursor) = tx.transactions_get_after(None, 2, Ordering::Ascending).unwrap();
+        let mut fetched = page.iter().map(|t| *t.id()).collect::<Vec<_>>();
+
+        expected.extend((0..2).map(|_| insert_transaction(&mut tx)));
+
+        loop {
+            let (page, next_cursor) = tx
+                .transactions_get_after(cursor.as_ref(), 2, Ordering::Ascending)
+                .unwrap();
+            if page.is_empty() {
+                assert_eq!(next_cursor, cursor);
+                break;
+            }
+            fetched.extend(page.iter().map(|t| *t.id()));
+            cursor = next_cursor;
+        }
+
+        assert_no_duplicates(&fetched);
+        assert_eq!(fetched, expected);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_does_not_repeat_transactions_in_descending_order_when_new_transactions_are_inserted() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let mut expected = (0..5).map(|_| insert_transaction(&mut tx)).collect::<Vec<_>>();
+        expected.reverse();
+
+        let (page, mut cursor) = tx.transactions_get_after(None, 2, Ordering::Descending).unwrap();
+        let mut fetched = page.iter().map(|t| *t.id()).collect::<Vec<_>>();
+
+        // Newer transactions come before the cursor and are not returned by subsequent pages
+        insert_transaction(&mut tx);
+        insert_transaction(&mut tx);
+
+        loop {
+            let (page, next_cursor) = tx
+                .transactions_get_after(cursor.as_ref(), 2, Ordering::Descending)
+                .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            fetched.extend(page.iter().map(|t| *t.id()));
+            cursor = next_cursor;
+        }
+
+        assert_no_duplicates(&fetched);
+        assert_eq!(fetched, expected);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_returns_blocks_inserted_between_pages_without_gaps() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+
+        let mut expected = vec![*zero_block.id()];
+        expected.extend((1..=3).map(|height| insert_block(&mut tx, &zero_block, height)));
+
+        let (page, mut cursor) = tx.blocks_get_after(None, 3, Ordering::Ascending).unwrap();
+        let mut fetched = page.iter().map(|b| *b.id()).collect::<Vec<_>>();
+
+        expected.extend((4..=6).map(|height| insert_block(&mut tx, &zero_block, height)));
+
+        loop {
+            let (page, next_cursor) = tx.blocks_get_after(cursor.as_ref(), 3, Ordering::Ascending).unwrap();
+            if page.is_empty() {
+                assert_eq!(next_cursor, cursor);
+                break;
+            }
+            fetched.extend(page.iter().map(|b| *b.id()));
+            cursor = next_cursor;
+        }
+
+        assert_no_duplicates(&fetched);
+        assert_eq!(fetched, expected);
+        tx.rollback().unwrap();
+    }
+}
+
+mod recent_transactions {
+    use tari_common_types::types::PrivateKey;
+    use tari_dan_storage::consensus_models::{RecentTransaction, RecentTransactionFilter, TransactionRecord};
+    use tari_transaction::Transaction;
+
+    use super::*;
+
+    fn insert_transaction<TTx: StateStoreWriteTransaction>(tx: &mut TTx) -> TransactionId {
+        let record = TransactionRecord::new(Transaction::builder().sign(&PrivateKey::default()).build());
+        record.insert(tx).unwrap();
+        *record.id()
+    }
+
+    fn ids(transactions: &[RecentTransaction]) -> Vec<TransactionId> {
+        transactions.iter().map(|t| t.transaction_id).collect()
+    }
+
+    #[test]
+    fn it_filters_by_abort_decision() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let first = insert_transaction(&mut tx);
+        let aborted = insert_transaction(&mut tx);
+        let last = insert_transaction(&mut tx);
+
+        let mut record = TransactionRecord::get(&*tx, &aborted).unwrap();
+        record.set_abort("Input conflict");
+        record.update(&mut tx).unwrap();
+
+        let filter = RecentTransactionFilter {
+            decision: Some(Decision::Abort),
+            ..Default::default()
+        };
+        let (transactions, _) = tx.transactions_get_recent_filtered(&filter, None, 10).unwrap();
+        assert_eq!(ids(&transactions), vec![aborted]);
+        assert_eq!(transactions[0].decision, Some(Decision::Abort));
+        assert_eq!(transactions[0].abort_reason.as_deref(), Some("Input conflict"));
+        assert_eq!(transactions[0].fee, None);
+        assert_eq!(transactions[0].block_id, None);
+
+        let filter = RecentTransactionFilter {
+            decision: Some(Decision::Commit),
+            ..Default::default()
+        };
+        let (transactions, _) = tx.transactions_get_recent_filtered(&filter, None, 10).unwrap();
+        assert!(transactions.is_empty());
+
+        let (transactions, _) = tx
+            .transactions_get_recent_filtered(&RecentTransactionFilter::default(), None, 10)
+            .unwrap();
+        assert_eq!(ids(&transactions), vec![last, aborted, first]);
+        assert_eq!(transactions[0].decision, None);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_does_not_repeat_or_skip_transactions_when_new_transactions_are_inserted_between_pages() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let filter = RecentTransactionFilter::default();
+
+        let mut expected = (0..5).map(|_| insert_transaction(&mut tx)).collect::<Vec<_>>();
+        expected.reverse();
+
+        let (page, mut cursor) = tx.transactions_get_recent_filtered(&filter, None, 2).unwrap();
+        let mut fetched = ids(&page);
+
+        // Newer transactions are ahead of the cursor and are not returned by subsequent pages
+        insert_transaction(&mut tx);
+        insert_transaction(&mut tx);
+
+        loop {
+            let (page, next_cursor) = tx
+                .transactions_get_recent_filtered(&filter, cursor.as_ref(), 2)
+                .unwrap();
+            if page.is_empty() {
+                assert_eq!(next_cursor, cursor);
+                break;
+            }
+            fetched.extend(ids(&page));
+            cursor = next_cursor;
+        }
+
+        assert_eq!(fetched, expected);
+        tx.rollback().unwrap();
+    }
+}
+
+mod transaction_pool_ordering {
+    use tari_common_types::types::PrivateKey;
+    use tari_dan_storage::consensus_models::{TransactionPoolOrdering, TransactionRecord};
+    use tari_transaction::Transaction;
+
+    use super::*;
+
+    fn insert_pool_transaction<TTx: StateStoreWriteTransaction>(tx: &mut TTx, priority_fee: u64) -> TransactionId {
+        insert_pool_transaction_with_epoch_bounds(tx, priority_fee, None, None)
+    }
+
+    fn insert_pool_transaction_with_epoch_bounds<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        priority_fee: u64,
+        min_epoch: Option<Epoch>,
+        max_epoch: Option<Epoch>,
+    ) -> TransactionId {
+        let record = TransactionRecord::new(
+            Transaction::builder()
+                .with_priority_fee(priority_fee)
+                .with_min_epoch(min_epoch)
+                .with_max_epoch(max_epoch)
+                .sign(&PrivateKey::default())
+                .build(),
+        );
+        record.insert(tx).unwrap();
+        let atom = TransactionAtom {
+            id: *record.id(),
+            priority_fee,
+            ..create_tx_atom()
+        };
+        tx.transaction_pool_insert(atom, TransactionPoolStage::New, true, NodeHeight::zero())
+            .unwrap();
+        *record.id()
+    }
+
+    #[test]
+    fn it_returns_higher_priority_transactions_first() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+        zero_block.as_locked_block().set(&mut tx).unwrap();
+        zero_block.as_leaf_block().set(&mut tx).unwrap();
+
+        let tx_ids = [0, 0, 100, 10]
+            .into_iter()
+            .map(|priority_fee| insert_pool_transaction(&mut tx, priority_fee))
+            .collect::<Vec<_>>();
+
+        // The last transaction to be submitted overtakes the earlier, lower priority transactions
+        let ready = tx
+            .transaction_pool_get_many_ready(2, TransactionPoolOrdering::FeePriority, true, Epoch(0))
+            .unwrap();
+        assert_eq!(ready[0].atom().priority_fee, 100);
+        let ready = ready.iter().map(|rec| *rec.transaction_id()).collect::<Vec<_>>();
+        assert_eq!(ready, vec![tx_ids[2], tx_ids[3]]);
+
+        // Ordering by transaction id does not take the priority fee into account
+        let mut expected = tx_ids;
+        expected.sort();
+        let ready = tx
+            .transaction_pool_get_many_ready(4, TransactionPoolOrdering::TransactionId, true, Epoch(0))
+            .unwrap()
+            .iter()
+            .map(|rec| *rec.transaction_id())
+            .collect::<Vec<_>>();
+        assert_eq!(ready, expected);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_only_returns_ready_transactions_within_their_epoch_bounds() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+        zero_block.as_locked_block().set(&mut tx).unwrap();
+        zero_block.as_leaf_block().set(&mut tx).unwrap();
+
+        let unbounded = insert_pool_transaction(&mut tx, 0);
+        let bounded = insert_pool_transaction_with_epoch_bounds(&mut tx, 0, Some(Epoch(2)), Some(Epoch(3)));
+
+        let ready_ids = |epoch| {
+            let mut ids = tx
+                .transaction_pool_get_many_ready(10, TransactionPoolOrdering::TransactionId, true, epoch)
+                .unwrap()
+                .iter()
+                .map(|rec| *rec.transaction_id())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+        let mut both = vec![unbounded, bounded];
+        both.sort();
+
+        // Not yet valid
+        assert_eq!(ready_ids(Epoch(1)), vec![unbounded]);
+        assert_eq!(ready_ids(Epoch(2)), both);
+        assert_eq!(ready_ids(Epoch(3)), both);
+        // Expired
+        assert_eq!(ready_ids(Epoch(4)), vec![unbounded]);
+
+        let expired = |epoch| {
+            tx.transaction_pool_get_many_expired(10, epoch)
+                .unwrap()
+                .iter()
+                .map(|rec| *rec.transaction_id())
+                .collect::<Vec<_>>()
+        };
+        assert!(expired(Epoch(1)).is_empty());
+        assert!(expired(Epoch(3)).is_empty());
+        assert_eq!(expired(Epoch(4)), vec![bounded]);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_counts_deferred_execution_attempts() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let tx_id = insert_pool_transaction(&mut tx, 0);
+        let rec = tx.transaction_pool_get(&tx_id).unwrap();
+        assert_eq!(rec.deferred_execution_attempts(), 0);
+        assert_eq!(rec.first_seen_height(), NodeHeight::zero());
+
+        tx.transaction_pool_increment_deferred_execution_attempts(&tx_id)
+            .unwrap();
+        tx.transaction_pool_increment_deferred_execution_attempts(&tx_id)
+            .unwrap();
+        let rec = tx.transaction_pool_get(&tx_id).unwrap();
+        assert_eq!(rec.deferred_execution_attempts(), 2);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_excludes_transactions_that_conflict_with_selected_transactions() {
+        use tari_dan_common_types::SubstateAddress;
+        use tari_dan_storage::consensus_models::{ShardEvidence, SubstateLockFlag};
+
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+        zero_block.as_locked_block().set(&mut tx).unwrap();
+        zero_block.as_leaf_block().set(&mut tx).unwrap();
+
+        let substate = SubstateAddress::new([1u8; 32]);
+        let mut tx_ids = (0..2)
+            .map(|_| {
+                let id = insert_pool_transaction(&mut tx, 0);
+                tx.transaction_pool_set_atom(TransactionAtom {
+                    id,
+                    evidence: [(
+                        substate,
+                        ShardEvidence::new(Default::default(), SubstateLockFlag::Write),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    ..create_tx_atom()
+                })
+                .unwrap();
+                id
+            })
+            .collect::<Vec<_>>();
+        tx_ids.sort();
+
+        let ready = tx
+            .transaction_pool_get_many_ready(10, TransactionPoolOrdering::TransactionId, true, Epoch(0))
+            .unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(*ready[0].transaction_id(), tx_ids[0]);
+
+        // Both are selected when conflicting transactions are not excluded
+        let ready = tx
+            .transaction_pool_get_many_ready(10, TransactionPoolOrdering::TransactionId, false, Epoch(0))
+            .unwrap();
+        assert_eq!(ready.len(), 2);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod qc_timings {
+    use tari_common_types::types::PublicKey;
+    use tari_dan_storage::consensus_models::{BlockId, QcTiming, VoteTiming};
+
+    use super::*;
+
+    fn create_timing(block: u8, epoch: Epoch, votes: &[u64]) -> QcTiming {
+        QcTiming {
+            block_id: BlockId::from(FixedHash::from([block; 32])),
+            epoch,
+            height: NodeHeight(u64::from(block)),
+            first_vote_ms: votes[0],
+            quorum_reached_ms: None,
+            last_vote_ms: votes[votes.len() - 1],
+            votes: votes
+                .iter()
+                .enumerate()
+                .map(|(rank, ms)| VoteTiming {
+                    public_key: PublicKey::default(),
+                    arrived_after_ms: *ms,
+                    rank: rank as u32,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_replaces_the_timing_for_a_block() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let mut timing = create_timing(1, Epoch(1), &[3, 7]);
+        timing.upsert(&mut tx).unwrap();
+        create_timing(2, Epoch(2), &[1]).upsert(&mut tx).unwrap();
+
+        timing.quorum_reached_ms = Some(7);
+        timing.last_vote_ms = 250;
+        timing.votes.push(VoteTiming {
+            public_key: PublicKey::default(),
+            arrived_after_ms: 250,
+            rank: 2,
+        });
+        timing.upsert(&mut tx).unwrap();
+
+        let timings = QcTiming::get_by_epoch(&*tx, Epoch(1)).unwrap();
+        assert_eq!(timings, vec![timing]);
+        assert_eq!(QcTiming::get_by_epoch(&*tx, Epoch(2)).unwrap().len(), 1);
+        assert!(QcTiming::get_by_epoch(&*tx, Epoch(3)).unwrap().is_empty());
+        tx.rollback().unwrap();
+    }
+}
+
+mod prune_safety_info {
+    use std::time::Duration;
+
+    use indexmap::IndexSet;
+    use tari_common_types::types::{PrivateKey, PublicKey};
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::{
+        consensus_models::{
+            BlockId,
+            ForeignProposal,
+            ForeignProposalState,
+            PendingStateTreeDiff,
+            PruneSafetyInfo,
+            QcId,
+            SubstateRecord,
+            TransactionExecution,
+            TransactionRecord,
+        },
+        StorageError,
+    };
+    use tari_engine_types::{
+        commit_result::{ExecuteResult, FinalizeResult, RejectReason},
+        fee_claim::{FeeClaim, FeeClaimAddress},
+        substate::{SubstateId, SubstateValue},
+    };
+    use tari_transaction::Transaction;
+    use tari_utilities::{epoch_time::EpochTime, ByteArray};
+
+    use super::*;
+
+    /// Creates a chain of blocks from the zero block to the given height where block N is in epoch N / 2. The block at
+    /// height 2 receives the given leader fee. The locked block is set to the tip, which makes its parent the last
+    /// committed block.
+    fn create_chain<TTx: StateStoreWriteTransaction>(tx: &mut TTx, tip_height: u64, leader_fee: u64) -> Vec<Block> {
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(tx).unwrap();
+        zero_block.insert(tx).unwrap();
+
+        let mut blocks = vec![zero_block];
+        for height in 1..=tip_height {
+            let parent = blocks.last().unwrap();
+            let block = create_block(parent, height, if height == 2 { leader_fee } else { 0 });
+            block.insert(tx).unwrap();
+            blocks.push(block);
+        }
+        for block in &blocks[1..blocks.len() - 1] {
+            tx.blocks_set_flags(block.id(), Some(true), None).unwrap();
+        }
+        blocks.last().unwrap().as_locked_block().set(tx).unwrap();
+        blocks
+    }
+
+    fn create_block(parent: &Block, height: u64, leader_fee: u64) -> Block {
+        Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            NodeHeight(height),
+            Epoch(height / 2),
+            Shard::from(0),
+            PublicKey::default(),
+            Default::default(),
+            Default::default(),
+            leader_fee,
+            Default::default(),
+            None,
+            EpochTime::now().as_u64(),
+            0,
+            FixedHash::zero(),
+        )
+    }
+
+    fn assert_refused<T: std::fmt::Debug>(result: Result<T, StorageError>) {
+        match result {
+            Err(StorageError::PruneFloorExceeded { .. }) => {},
+            other => panic!("Expected pruning to be refused, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_returns_the_committed_block_when_nothing_is_referenced() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        create_chain(&mut tx, 6, 0);
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info, PruneSafetyInfo {
+            committed_height: NodeHeight(5),
+            committed_epoch: Epoch(2),
+            lowest_referenced_height: None,
+            earliest_unclaimed_fee_epoch: None,
+        });
+        assert_eq!(info.height_floor(), NodeHeight(5));
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_includes_proposed_foreign_proposals() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        create_chain(&mut tx, 6, 0);
+
+        // New foreign proposals are not yet referenced by a local block
+        ForeignProposal::new(Shard::from(1), BlockId::from(FixedHash::from([1u8; 32])), vec![], 0)
+            .upsert(&mut tx)
+            .unwrap();
+        assert_eq!(tx.get_prune_safety_info().unwrap().lowest_referenced_height, None);
+
+        let mut proposal = ForeignProposal::new(Shard::from(1), BlockId::from(FixedHash::from([2u8; 32])), vec![], 0);
+        proposal.state = ForeignProposalState::Proposed;
+        proposal.proposed_height = Some(NodeHeight(3));
+        proposal.upsert(&mut tx).unwrap();
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.lowest_referenced_height, Some(NodeHeight(3)));
+        assert_eq!(info.height_floor(), NodeHeight(3));
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_includes_the_parent_of_parked_blocks() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let blocks = create_chain(&mut tx, 6, 0);
+
+        let parked = create_block(&blocks[2], 3, 0);
+        tx.missing_transactions_insert(&parked, [&create_tx_atom().id], [])
+            .unwrap();
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.lowest_referenced_height, Some(NodeHeight(2)));
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_includes_pending_state_tree_diffs() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let blocks = create_chain(&mut tx, 6, 0);
+
+        tx.pending_state_tree_diffs_insert(&PendingStateTreeDiff::new(
+            *blocks[6].id(),
+            NodeHeight(6),
+            Default::default(),
+        ))
+        .unwrap();
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.lowest_referenced_height, Some(NodeHeight(6)));
+        // The committed block is always the upper bound
+        assert_eq!(info.height_floor(), NodeHeight(5));
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_includes_executions_of_unfinalized_transactions() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let blocks = create_chain(&mut tx, 6, 0);
+
+        let record = TransactionRecord::new(Transaction::builder().sign(&PrivateKey::default()).build());
+        record.insert(&mut tx).unwrap();
+        TransactionExecution::new(
+            *blocks[1].id(),
+            *record.id(),
+            ExecuteResult {
+                finalize: FinalizeResult::new_rejected(Default::default(), RejectReason::FeeTransactionFailed),
+            },
+            IndexSet::new(),
+            vec![],
+            Duration::from_millis(1),
+        )
+        .insert_if_required(&mut tx)
+        .unwrap();
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.lowest_referenced_height, Some(NodeHeight(1)));
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_includes_the_earliest_epoch_with_unclaimed_leader_fees() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let blocks = create_chain(&mut tx, 6, 100);
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.earliest_unclaimed_fee_epoch, Some(Epoch(1)));
+        // Block 2 is in epoch 1
+        assert_refused(tx.blocks_prune_before(&info, NodeHeight(3)));
+        assert_eq!(tx.blocks_prune_before(&info, NodeHeight(2)).unwrap(), 2);
+
+        let public_key = PublicKey::default();
+        let fee_claim = SubstateRecord::new(
+            SubstateId::FeeClaim(FeeClaimAddress::from_addr(1, public_key.as_bytes())),
+            0,
+            SubstateValue::FeeClaim(FeeClaim {
+                epoch: 1,
+                validator_public_key: public_key,
+                amount: 100i64.into(),
+            }),
+            Epoch(2),
+            NodeHeight(4),
+            *blocks[4].id(),
+            create_tx_atom().id,
+            QcId::genesis(),
+        );
+        tx.substates_create(fee_claim).unwrap();
+
+        let info = tx.get_prune_safety_info().unwrap();
+        assert_eq!(info.earliest_unclaimed_fee_epoch, None);
+        assert_eq!(tx.blocks_prune_before(&info, NodeHeight(3)).unwrap(), 1);
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_refuses_to_prune_past_the_floors() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let blocks = create_chain(&mut tx, 6, 0);
+
+        let parked = create_block(&blocks[3], 4, 0);
+        tx.missing_transactions_insert(&parked, [&create_tx_atom().id], [])
+            .unwrap();
+        let info = PruneSafetyInfo::get(&*tx).unwrap();
+        assert_eq!(info.height_floor(), NodeHeight(3));
+
+        assert_refused(tx.blocks_prune_before(&info, NodeHeight(4)));
+        assert_refused(tx.substates_prune_destroyed_before(&info, NodeHeight(4)));
+        assert_refused(tx.foreign_counters_prune_before(&info, NodeHeight(4)));
+        assert_refused(tx.votes_prune_before(&info, Epoch(3)));
+
+        assert_eq!(tx.substates_prune_destroyed_before(&info, NodeHeight(3)).unwrap(), 0);
+        assert_eq!(tx.foreign_counters_prune_before(&info, NodeHeight(3)).unwrap(), 0);
+        assert_eq!(tx.votes_prune_before(&info, Epoch(2)).unwrap(), 0);
+        assert_eq!(tx.blocks_prune_before(&info, NodeHeight(3)).unwrap(), 3);
+        assert!(!tx.blocks_exists(blocks[2].id()).unwrap());
+        assert!(tx.blocks_exists(blocks[3].id()).unwrap());
+        // The store remains consistent after pruning
+        assert_eq!(tx.get_prune_safety_info().unwrap(), info);
+        tx.rollback().unwrap();
+    }
+}
+
+mod committed_block_diffs {
+    use std::str::FromStr;
+
+    use tari_common_types::types::PublicKey;
+    use tari_dan_common_types::{optional::Optional, shard::Shard};
+    use tari_dan_storage::consensus_models::{
+        BlockDiff,
+        BlockId,
+        CommittedBlockDiff,
+        CommittedBlockDiffCursor,
+        SubstateChange,
+    };
+    use tari_engine_types::{
+        fee_claim::FeeClaim,
+        substate::{Substate, SubstateId},
+    };
+    use tari_transaction::VersionedSubstateId;
+
+    use super::*;
+
+    fn create_block(parent: &Block) -> Block {
+        Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            parent.height() + NodeHeight(1),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            parent.height().as_u64() + 1,
+            0,
+            FixedHash::zero(),
+        )
+    }
+
+    fn create_diff(block: &Block) -> BlockDiff {
+        let transaction_id = create_tx_atom().id;
+        let id = SubstateId::from_str("component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5").unwrap();
+        let version = block.height().as_u64() as u32;
+        let substate = Substate::new(version + 1, FeeClaim {
+            epoch: 0,
+            validator_public_key: PublicKey::default(),
+            amount: 100.into(),
+        });
+        BlockDiff::new(*block.id(), vec![
+            SubstateChange::Down {
+                id: VersionedSubstateId::new(id.clone(), version),
+                transaction_id,
+            },
+            SubstateChange::Up {
+                id: VersionedSubstateId::new(id, version + 1),
+                transaction_id,
+                substate,
+            },
+        ])
+    }
+
+    /// Commits `count` blocks on top of `parent` and returns them in commit order
+    fn commit_blocks(db: &SqliteStateStore<String>, parent: &Block, count: usize, retain: u64) -> Vec<Block> {
+        let mut blocks = Vec::with_capacity(count);
+        let mut parent = parent.clone();
+        for _ in 0..count {
+            let block = create_block(&parent);
+            db.with_write_tx(|tx| CommittedBlockDiff::record(tx, &block, &create_diff(&block), retain))
+                .unwrap();
+            parent = block.clone();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    fn block_ids<'a, I: IntoIterator<Item = &'a Block>>(blocks: I) -> Vec<BlockId> {
+        blocks.into_iter().map(|b| *b.id()).collect()
+    }
+
+    #[test]
+    fn it_keeps_the_most_recent_diffs_in_commit_order() {
+        let db = create_db();
+        let zero_block = Block::zero_block(Default::default());
+        let blocks = commit_blocks(&db, &zero_block, 5, 3);
+
+        let diffs = db
+            .with_read_tx(|tx| CommittedBlockDiff::get_after(tx, None, 10))
+            .unwrap();
+        assert_eq!(diffs.iter().map(|d| d.sequence).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(block_ids(diffs.iter().map(|d| &d.block)), block_ids(&blocks[2..]));
+
+        let diff = &diffs[0];
+        assert_eq!(diff.changes.len(), 2);
+        assert!(diff.changes[0].is_down());
+        assert!(diff.changes[1].is_up());
+        assert_eq!(diff.changes[1].versioned_substate_id().version(), 4);
+        assert_eq!(diff.changes[1].substate().unwrap().version(), 4);
+
+        // The oldest diffs were pruned
+        assert!(db
+            .with_read_tx(|tx| CommittedBlockDiff::get(tx, 2).optional())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn it_resumes_from_a_cursor_without_missing_a_block() {
+        let db = create_db();
+        let zero_block = Block::zero_block(Default::default());
+        let mut committed = commit_blocks(&db, &zero_block, 3, 10);
+
+        // The consumer reads part of the stream and disconnects
+        let mut received = db
+            .with_read_tx(|tx| CommittedBlockDiff::get_after(tx, None, 2))
+            .unwrap();
+        let cursor = received.last().unwrap().cursor();
+        assert_eq!(cursor.sequence, 2);
+
+        // More blocks are committed while the consumer is disconnected
+        let last = committed.last().unwrap().clone();
+        committed.extend(commit_blocks(&db, &last, 4, 10));
+
+        // The consumer reconnects with its cursor
+        let resumed = db
+            .with_read_tx(|tx| {
+                assert!(CommittedBlockDiff::is_retained(tx, &cursor)?);
+                CommittedBlockDiff::get_after(tx, Some(cursor.sequence), 10)
+            })
+            .unwrap();
+        received.extend(resumed);
+
+        assert_eq!(
+            received.iter().map(|d| d.sequence).collect::<Vec<_>>(),
+            (1..=7).collect::<Vec<_>>()
+        );
+        assert_eq!(block_ids(received.iter().map(|d| &d.block)), block_ids(&committed));
+    }
+
+    #[test]
+    fn it_does_not_resume_from_a_cursor_outside_of_the_retention_window() {
+        let db = create_db();
+        let zero_block = Block::zero_block(Default::default());
+        let blocks = commit_blocks(&db, &zero_block, 2, 2);
+        let cursor = CommittedBlockDiffCursor {
+            sequence: 1,
+            block_id: *blocks[0].id(),
+        };
+        assert!(db
+            .with_read_tx(|tx| CommittedBlockDiff::is_retained(tx, &cursor))
+            .unwrap());
+
+        commit_blocks(&db, &blocks[1], 1, 2);
+        assert!(!db
+            .with_read_tx(|tx| CommittedBlockDiff::is_retained(tx, &cursor))
+            .unwrap());
+
+        // A cursor from a different chain is not resumed
+        let cursor = CommittedBlockDiffCursor {
+            sequence: 2,
+            block_id: *blocks[0].id(),
+        };
+        assert!(!db
+            .with_read_tx(|tx| CommittedBlockDiff::is_retained(tx, &cursor))
+            .unwrap());
+    }
+}
+
+mod proposer_equivocations {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::ProposerEquivocation;
+
+    use super::*;
+
+    fn create_block(parent: &Block, timestamp: u64) -> Block {
+        Block::new(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            NodeHeight(1),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            timestamp,
+            0,
+            FixedHash::zero(),
+        )
+    }
+
+    #[test]
+    fn it_records_one_equivocation_per_proposer_and_height() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+
+        let first = create_block(&zero_block, 1);
+        let second = create_block(&zero_block, 2);
+        first.insert(&mut tx).unwrap();
+        second.insert(&mut tx).unwrap();
+
+        let proposed =
+            Block::get_all_by_proposer_at_height(&*tx, Epoch(0), NodeHeight(1), first.proposed_by()).unwrap();
+        assert_eq!(proposed.len(), 2);
+
+        let equivocation = ProposerEquivocation::new(first.clone(), second.clone());
+        assert!(equivocation.is_conflicting());
+        assert!(!ProposerEquivocation::exists(&*tx, Epoch(0), NodeHeight(1), first.proposed_by()).unwrap());
+        equivocation.insert(&mut tx).unwrap();
+
+        let recorded = ProposerEquivocation::get(&*tx, Epoch(0), NodeHeight(1), first.proposed_by()).unwrap();
+        assert_eq!(recorded.first_block_id(), first.id());
+        assert_eq!(recorded.second_block_id(), second.id());
+        assert_eq!(ProposerEquivocation::count(&*tx).unwrap(), 1);
+
+        // A second record for the same proposer and height is rejected
+        ProposerEquivocation::new(second, first).insert(&mut tx).unwrap_err();
+        assert_eq!(ProposerEquivocation::count(&*tx).unwrap(), 1);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod equivocation_evidence {
+    use tari_common_types::types::PrivateKey;
+    use tari_dan_storage::consensus_models::{
+        vote_signature_challenge,
+        BlockId,
+        EquivocationEvidence,
+        QuorumDecision,
+        ValidatorSignature,
+        Vote,
+    };
+    use tari_utilities::ByteArray;
+
+    use super::*;
+
+    fn create_vote(secret_key: &PrivateKey, sender_leaf_hash: FixedHash, block_id: BlockId, height: u64) -> Vote {
+        let challenge = vote_signature_challenge(&sender_leaf_hash, &block_id, &QuorumDecision::Accept);
+        Vote {
+            epoch: Epoch(0),
+            block_id,
+            height: NodeHeight(height),
+            decision: QuorumDecision::Accept,
+            sender_leaf_hash,
+            signature: ValidatorSignature::sign(secret_key, challenge),
+        }
+    }
+
+    #[test]
+    fn it_records_a_forged_double_vote_with_both_signatures() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let secret_key = PrivateKey::from_canonical_bytes(&[1u8; 32]).unwrap();
+        let sender_leaf_hash = FixedHash::from([1u8; 32]);
+
+        let first = create_vote(&secret_key, sender_leaf_hash, BlockId::new([2u8; 32]), 5);
+        first.save(&mut tx).unwrap();
+        // A vote at a different height is not conflicting
+        create_vote(&secret_key, sender_leaf_hash, BlockId::new([3u8; 32]), 6)
+            .save(&mut tx)
+            .unwrap();
+
+        let second = create_vote(&secret_key, sender_leaf_hash, BlockId::new([4u8; 32]), 5);
+        assert!(first.get_conflicting(&*tx).unwrap().is_none());
+        let conflicting = second.get_conflicting(&*tx).unwrap().unwrap();
+        assert_eq!(conflicting.block_id, first.block_id);
+
+        assert!(!EquivocationEvidence::exists(&*tx, Epoch(0), NodeHeight(5), &sender_leaf_hash).unwrap());
+        EquivocationEvidence::new(conflicting, second.clone())
+            .insert(&mut tx)
+            .unwrap();
+        assert!(EquivocationEvidence::exists(&*tx, Epoch(0), NodeHeight(5), &sender_leaf_hash).unwrap());
+
+        let all = EquivocationEvidence::get_all(&*tx).unwrap();
+        assert_eq!(all.len(), 1);
+        let evidence = &all[0];
+        assert!(evidence.is_conflicting());
+        assert_eq!(evidence.first_block_id(), &first.block_id);
+        assert_eq!(evidence.second_block_id(), &second.block_id);
+        for vote in [evidence.first_vote(), evidence.second_vote()] {
+            let challenge = vote_signature_challenge(&vote.sender_leaf_hash, &vote.block_id, &vote.decision);
+            assert!(vote.signature().verify(challenge));
+        }
+
+        // Only the first equivocation of a sender at a height is recorded
+        EquivocationEvidence::new(second, first).insert(&mut tx).unwrap_err();
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod shard_growth_samples {
+    use tari_common_types::types::PublicKey;
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{BlockId, QcId, ShardGrowthSample, SubstateRecord};
+    use tari_engine_types::{
+        fee_claim::{FeeClaim, FeeClaimAddress},
+        substate::{SubstateId, SubstateValue},
+    };
+    use tari_utilities::ByteArray;
+
+    use super::*;
+
+    fn create_substates<TTx: StateStoreWriteTransaction>(tx: &mut TTx, range: std::ops::Range<u64>) {
+        let public_key = PublicKey::default();
+        for epoch in range {
+            let substate = SubstateRecord::new(
+                SubstateId::FeeClaim(FeeClaimAddress::from_addr(epoch, public_key.as_bytes())),
+                0,
+                SubstateValue::FeeClaim(FeeClaim {
+                    epoch,
+                    validator_public_key: public_key.clone(),
+                    amount: 100i64.into(),
+                }),
+                Epoch(epoch),
+                NodeHeight(epoch),
+                BlockId::genesis(),
+                create_tx_atom().id,
+                QcId::genesis(),
+            );
+            tx.substates_create(substate).unwrap();
+        }
+    }
+
+    #[test]
+    fn it_records_the_growth_since_the_previous_sample() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let shard = Shard::from(0);
+
+        create_substates(&mut tx, 0..3);
+        let first = ShardGrowthSample::record(&mut tx, Epoch(1), shard, 1, 1_000, 10).unwrap();
+        assert_eq!(first.substate_count, 3);
+        assert!(first.total_bytes > 0);
+        assert_eq!(first.count_delta, 0);
+        assert_eq!(first.bytes_delta, 0);
+        assert_eq!(first.bytes_per_hour(), None);
+
+        create_substates(&mut tx, 3..5);
+        let second = ShardGrowthSample::record(&mut tx, Epoch(2), shard, 1, 1_000 + 30 * 60, 10).unwrap();
+        assert_eq!(second.substate_count, 5);
+        assert_eq!(second.count_delta, 2);
+        assert!(second.bytes_delta > 0);
+        assert_eq!(second.bytes_delta, second.total_bytes as i64 - first.total_bytes as i64);
+        assert_eq!(second.interval_secs, 30 * 60);
+        assert_eq!(second.bytes_per_hour(), Some(second.bytes_delta * 2));
+
+        let samples = ShardGrowthSample::get_recent(&*tx, shard, 10).unwrap();
+        assert_eq!(samples, vec![first, second]);
+        // Samples of other shards are independent
+        let other_shard = ShardGrowthSample::get_recent(&*tx, Shard::from(1), 10).unwrap();
+        assert!(other_shard.is_empty());
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_counts_the_substates_in_each_shard() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        create_substates(&mut tx, 0..20);
+
+        let ranges = [Shard::from(0), Shard::from(1)].map(|shard| shard.to_substate_address_range(2));
+        let counts = ranges
+            .iter()
+            .map(|range| tx.substates_count_in_range(range.start(), range.end()).unwrap())
+            .collect::<Vec<_>>();
+        let sizes = ranges
+            .iter()
+            .map(|range| tx.substates_size_in_range(range.start(), range.end()).unwrap())
+            .collect::<Vec<_>>();
+        let all = Shard::from(0).to_substate_address_range(1);
+
+        assert_eq!(counts.iter().sum::<u64>(), 20);
+        assert_eq!(
+            sizes.iter().sum::<u64>(),
+            tx.substates_size_in_range(all.start(), all.end()).unwrap()
+        );
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_keeps_only_the_most_recent_samples() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let shard = Shard::from(0);
+
+        for sampled_at in 1..=5 {
+            create_substates(&mut tx, sampled_at..sampled_at + 1);
+            ShardGrowthSample::record(&mut tx, Epoch(1), shard, 1, sampled_at, 3).unwrap();
+        }
+
+        let samples = ShardGrowthSample::get_recent(&*tx, shard, 10).unwrap();
+        assert_eq!(samples.iter().map(|s| s.sampled_at).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert!(samples.iter().all(|s| s.count_delta == 1));
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod nft_ownership {
+    use std::collections::BTreeSet;
+
+    use tari_dan_common_types::optional::Optional;
+    use tari_dan_storage::consensus_models::{BlockDiff, BlockId, NftOwnership, SubstateChange};
+    use tari_engine_types::{
+        component::{ComponentBody, ComponentHeader},
+        resource_container::ResourceContainer,
+        substate::Substate,
+        vault::Vault,
+    };
+    use tari_template_lib::models::{ComponentAddress, NonFungibleId, ObjectKey, ResourceAddress, VaultId};
+    use tari_transaction::VersionedSubstateId;
+
+    use super::*;
+
+    const RESOURCE: ResourceAddress = ResourceAddress::new(ObjectKey::from_array([1u8; ObjectKey::LENGTH]));
+
+    fn component_up(address: ComponentAddress, vault_id: VaultId) -> SubstateChange {
+        let component = ComponentHeader {
+            template_address: Default::default(),
+            module_name: "".to_string(),
+            owner_key: None,
+            owner_rule: Default::default(),
+            access_rules: Default::default(),
+            entity_id: Default::default(),
+            body: ComponentBody {
+                state: tari_bor::to_value(&vec![vault_id]).unwrap(),
+            },
+        };
+        SubstateChange::Up {
+            id: VersionedSubstateId::new(address.into(), 0),
+            transaction_id: create_tx_atom().id,
+            substate: Substate::new(0, component),
+        }
+    }
+
+    fn vault_up(vault_id: VaultId, version: u32, ids: &[u32]) -> SubstateChange {
+        let token_ids = ids
+            .iter()
+            .copied()
+            .map(NonFungibleId::from_u32)
+            .collect::<BTreeSet<_>>();
+        SubstateChange::Up {
+            id: VersionedSubstateId::new(vault_id.into(), version),
+            transaction_id: create_tx_atom().id,
+            substate: Substate::new(
+                version,
+                Vault::new(ResourceContainer::non_fungible(RESOURCE, token_ids)),
+            ),
+        }
+    }
+
+    fn commit(db: &SqliteStateStore<String>, changes: Vec<SubstateChange>) {
+        let diff = BlockDiff::new(BlockId::genesis(), changes);
+        db.with_write_tx(|tx| NftOwnership::update_from_diff(tx, &diff))
+            .unwrap();
+    }
+
+    fn nfts_owned_by(db: &SqliteStateStore<String>, owner: &ComponentAddress) -> Vec<NonFungibleId> {
+        let tx = db.create_read_tx().unwrap();
+        NftOwnership::get_by_owner(&tx, owner, usize::MAX, 0)
+            .unwrap()
+            .into_iter()
+            .map(|nft| nft.nft_id)
+            .collect()
+    }
+
+    #[test]
+    fn it_follows_nfts_through_mint_transfer_and_burn() {
+        let db = create_db();
+        let alice = ComponentAddress::from_array([2u8; ObjectKey::LENGTH]);
+        let bob = ComponentAddress::from_array([3u8; ObjectKey::LENGTH]);
+        let alice_vault = VaultId::new([4u8; ObjectKey::LENGTH].into());
+        let bob_vault = VaultId::new([5u8; ObjectKey::LENGTH].into());
+
+        // Mint
+        commit(&db, vec![
+            component_up(alice, alice_vault),
+            component_up(bob, bob_vault),
+            vault_up(alice_vault, 0, &[1, 2]),
+            vault_up(bob_vault, 0, &[]),
+        ]);
+        assert_eq!(nfts_owned_by(&db, &alice), vec![
+            NonFungibleId::from_u32(1),
+            NonFungibleId::from_u32(2)
+        ]);
+        assert!(nfts_owned_by(&db, &bob).is_empty());
+
+        // Transfer, with the receiving vault changed before the sending vault
+        commit(&db, vec![vault_up(bob_vault, 1, &[2]), vault_up(alice_vault, 1, &[1])]);
+        assert_eq!(nfts_owned_by(&db, &alice), vec![NonFungibleId::from_u32(1)]);
+        assert_eq!(nfts_owned_by(&db, &bob), vec![NonFungibleId::from_u32(2)]);
+        let tx = db.create_read_tx().unwrap();
+        let ownership = NftOwnership::get(&tx, &RESOURCE, &NonFungibleId::from_u32(2)).unwrap();
+        assert_eq!(ownership.vault_id, bob_vault);
+        assert_eq!(ownership.component_address, Some(bob));
+        drop(tx);
+
+        // Burn
+        commit(&db, vec![vault_up(alice_vault, 2, &[])]);
+        assert!(nfts_owned_by(&db, &alice).is_empty());
+        let tx = db.create_read_tx().unwrap();
+        let ownership = NftOwnership::get(&tx, &RESOURCE, &NonFungibleId::from_u32(1))
+            .optional()
+            .unwrap();
+        assert_eq!(ownership, None);
+    }
+}
+
+mod recovery {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use tari_dan_common_types::optional::Optional;
+    use tari_state_store_sqlite::{latest_backup, RecoveryPolicy};
+
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("state_store_recovery_{}", OsRng.next_u64()));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ignore = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn corrupt(path: &Path) {
+        let mut bytes = fs::read(path).unwrap();
+        bytes[..100].fill(0xff);
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn it_restores_a_corrupt_database_from_the_latest_backup() {
+        let dir = TempDir::new();
+        let db_path = dir.0.join("state.db");
+        let backup_dir = dir.0.join("backups");
+        let policy = RecoveryPolicy::RestoreFromBackup {
+            backup_dir: backup_dir.clone(),
+        };
+
+        let zero_block = Block::zero_block(Default::default());
+        {
+            let (db, recovery) = SqliteStateStore::<String>::try_open_with_recovery(&db_path, &policy).unwrap();
+            assert!(recovery.is_none());
+            db.with_write_tx(|tx| {
+                zero_block.justify().insert(tx)?;
+                zero_block.insert(tx)
+            })
+            .unwrap();
+            db.create_backup(&backup_dir, 2).unwrap();
+        }
+        let backup = latest_backup(&backup_dir).unwrap().unwrap();
+
+        corrupt(&db_path);
+
+        let err = SqliteStateStore::<String>::try_open_with_recovery(&db_path, &RecoveryPolicy::Refuse).unwrap_err();
+        assert!(err.to_string().contains("corrupt"), "{}", err);
+
+        let (db, recovery) = SqliteStateStore::<String>::try_open_with_recovery(&db_path, &policy).unwrap();
+        let recovery = recovery.unwrap();
+        assert_eq!(recovery.restored_from, backup);
+        assert!(recovery.corrupt_file.exists());
+
+        let block = db
+            .with_read_tx(|tx| Block::get(tx, zero_block.id()).optional())
+            .unwrap();
+        assert!(block.is_some());
+
+        // The restored database is intact, so it opens without recovery
+        drop(db);
+        let (_db, recovery) = SqliteStateStore::<String>::try_open_with_recovery(&db_path, &policy).unwrap();
+        assert!(recovery.is_none());
+    }
+
+    #[test]
+    fn it_refuses_to_recover_without_a_backup() {
+        let dir = TempDir::new();
+        let db_path = dir.0.join("state.db");
+        drop(SqliteStateStore::<String>::try_open_with_recovery(&db_path, &RecoveryPolicy::Refuse).unwrap());
+        corrupt(&db_path);
+
+        let policy = RecoveryPolicy::RestoreFromBackup {
+            backup_dir: dir.0.join("backups"),
+        };
+        SqliteStateStore::<String>::try_open_with_recovery(&db_path, &policy).unwrap_err();
+        // The corrupt database is left in place for the operator
+        assert!(db_path.exists());
+    }
+
+    #[test]
+    fn it_keeps_the_most_recent_backups() {
+        let dir = TempDir::new();
+        let backup_dir = dir.0.join("backups");
+        let (db, _) =
+            SqliteStateStore::<String>::try_open_with_recovery(dir.0.join("state.db"), &RecoveryPolicy::Refuse)
+                .unwrap();
+
+        let mut backups = vec![];
+        for _ in 0..3 {
+            backups.push(db.create_backup(&backup_dir, 2).unwrap());
+            // Backup file names have millisecond resolution
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        assert!(!backups[0].exists());
+        assert!(backups[1].exists());
+        assert!(backups[2].exists());
+        assert_eq!(latest_backup(&backup_dir).unwrap().unwrap(), backups[2]);
+    }
+}
+
+mod write_queue {
+    use std::{sync::mpsc, thread, time::Duration};
+
+    use tari_state_store_sqlite::WriteQueueConfig;
+
+    use super::*;
+
+    fn create_db_with_config(max_shed_duration: Duration) -> SqliteStateStore<String> {
+        create_db().with_write_queue_config(WriteQueueConfig {
+            warn_wait: Duration::from_millis(50),
+            shed_wait: Duration::from_millis(100),
+            recover_wait: Duration::from_millis(50),
+            recover_count: 3,
+            max_shed_duration,
+        })
+    }
+
+    /// Holds the writer on another thread for the given time so that the next write transaction has to wait for it
+    fn hold_writer(db: &SqliteStateStore<String>, hold_for: Duration) -> thread::JoinHandle<()> {
+        let db = db.clone();
+        let (started_tx, started_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let tx = db.create_write_tx().unwrap();
+            started_tx.send(()).unwrap();
+            thread::sleep(hold_for);
+            tx.rollback().unwrap();
+        });
+        started_rx.recv().unwrap();
+        handle
+    }
+
+    #[test]
+    fn it_sheds_load_until_waits_recover() {
+        let db = create_db_with_config(Duration::from_secs(600));
+        assert!(!db.write_queue().is_shedding_load());
+
+        let handle = hold_writer(&db, Duration::from_millis(300));
+        db.create_write_tx().unwrap().rollback().unwrap();
+        handle.join().unwrap();
+
+        let stats = db.write_queue().stats();
+        assert!(stats.is_shedding_load);
+        assert!(stats.last_wait > Duration::from_millis(100));
+        assert_eq!(stats.num_slow_waits, 1);
+
+        for _ in 0..2 {
+            db.create_write_tx().unwrap().rollback().unwrap();
+            assert!(db.write_queue().is_shedding_load());
+        }
+        db.create_write_tx().unwrap().rollback().unwrap();
+        assert!(!db.write_queue().is_shedding_load());
+    }
+
+    #[test]
+    fn it_stops_shedding_load_after_the_max_shed_duration() {
+        let db = create_db_with_config(Duration::from_millis(200));
+
+        let handle = hold_writer(&db, Duration::from_millis(300));
+        db.create_write_tx().unwrap().rollback().unwrap();
+        handle.join().unwrap();
+        assert!(db.write_queue().is_shedding_load());
+
+        // No further writes are made, so load shedding only disengages because the time bound is reached
+        thread::sleep(Duration::from_millis(300));
+        assert!(!db.write_queue().is_shedding_load());
+    }
+}
+
+mod dummy_blocks {
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::{LeaderFailureStats, QuorumCertificate, QuorumDecision};
+
+    use super::*;
+
+    fn insert_justify_block<TTx: StateStoreWriteTransaction>(tx: &mut TTx, timestamp: u64) -> Block {
+        let zero_block = Block::zero_block(Default::default());
+        zero_block.justify().insert(tx).unwrap();
+        zero_block.insert(tx).unwrap();
+
+        let block = Block::new(
+            Default::default(),
+            *zero_block.id(),
+            zero_block.justify().clone(),
+            NodeHeight(1),
+            Epoch(0),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            timestamp,
+            0,
+            FixedHash::zero(),
+        );
+        block.insert(tx).unwrap();
+        block
+    }
+
+    fn create_dummy_blocks(justify_block: &Block, num: u64, timestamp: u64) -> Vec<Block> {
+        let justify = QuorumCertificate::new(
+            *justify_block.id(),
+            justify_block.height(),
+            Epoch(0),
+            Shard::from(0),
+            vec![],
+            vec![],
+            QuorumDecision::Accept,
+        );
+        let mut parent = *justify_block.id();
+        (1..=num)
+            .map(|i| {
+                let block = Block::dummy_block(
+                    Default::default(),
+                    parent,
+                    Default::default(),
+                    justify_block.height() + NodeHeight(i),
+                    justify.clone(),
+                    Epoch(0),
+                    Shard::from(0),
+                    FixedHash::zero(),
+                    timestamp,
+                    0,
+                    FixedHash::zero(),
+                );
+                parent = *block.id();
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_saves_all_blocks_in_batches() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let justify_block = insert_justify_block(&mut tx, 10);
+        // More blocks than fit in a single insert statement
+        let dummy_blocks = create_dummy_blocks(&justify_block, 1200, 25);
+        Block::save_all(&mut tx, &dummy_blocks).unwrap();
+
+        for block in &dummy_blocks {
+            let stored = Block::get(&*tx, block.id()).unwrap();
+            assert!(stored.is_dummy());
+            assert_eq!(stored.height(), block.height());
+            assert_eq!(stored.block_time(), Some(15));
+        }
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_ignores_blocks_that_already_exist() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let justify_block = insert_justify_block(&mut tx, 10);
+        let dummy_blocks = create_dummy_blocks(&justify_block, 20, 10);
+        Block::save_all(&mut tx, &dummy_blocks[..10]).unwrap();
+
+        // Consensus includes the justify block in the dummy blocks that it saves
+        let mut all_blocks = vec![justify_block.clone()];
+        all_blocks.extend(dummy_blocks.iter().cloned());
+        Block::save_all(&mut tx, &all_blocks).unwrap();
+
+        assert!(!Block::get(&*tx, justify_block.id()).unwrap().is_dummy());
+        for block in &dummy_blocks {
+            assert!(block.exists(&*tx).unwrap());
+        }
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_counts_dummy_blocks_by_epoch() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let justify_block = insert_justify_block(&mut tx, 10);
+        let dummy_blocks = create_dummy_blocks(&justify_block, 7, 10);
+        Block::save_all(&mut tx, &dummy_blocks).unwrap();
+
+        assert_eq!(tx.blocks_count_dummy(None).unwrap(), 7);
+        assert_eq!(tx.blocks_count_dummy(Some(Epoch(0))).unwrap(), 7);
+        assert_eq!(tx.blocks_count_dummy(Some(Epoch(1))).unwrap(), 0);
+
+        let stats = LeaderFailureStats::get(&*tx, Epoch(0)).unwrap();
+        assert_eq!(stats.dummy_block_count, 7);
+        assert_eq!(stats.leader_timeouts_last_epoch, 0);
+        let stats = LeaderFailureStats::get(&*tx, Epoch(1)).unwrap();
+        assert_eq!(stats.leader_timeouts_last_epoch, 7);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod missing_transaction_requests {
+    use std::collections::HashSet;
+
+    use tari_dan_common_types::shard::Shard;
+    use tari_dan_storage::consensus_models::MissingTransactionsRequest;
+
+    use super::*;
+
+    fn create_parked_block(height: u64) -> Block {
+        let zero_block = Block::zero_block(Default::default());
+        Block::new(
+            Default::default(),
+            *zero_block.id(),
+            zero_block.justify().clone(),
+            NodeHeight(height),
+            Epoch(1),
+            Shard::from(0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            0,
+            0,
+            FixedHash::zero(),
+        )
+    }
+
+    #[test]
+    fn it_requests_only_the_remaining_transactions_after_a_partial_response() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let block = create_parked_block(5);
+        let missing = (0..3).map(|_| create_tx_atom().id).collect::<Vec<_>>();
+        let awaiting = create_tx_atom().id;
+        tx.missing_transactions_insert(&block, &missing, [&awaiting]).unwrap();
+
+        let requests = MissingTransactionsRequest::get_all_due(&*tx, NodeHeight(5), 100).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].block_id, *block.id());
+        assert_eq!(requests[0].proposed_by, *block.proposed_by());
+        assert_eq!(
+            requests[0].transaction_ids,
+            missing.iter().copied().collect::<HashSet<_>>()
+        );
+        assert_eq!(requests[0].requested_at, None);
+        assert_eq!(requests[0].attempts, 0);
+
+        MissingTransactionsRequest::record_attempt(&mut tx, block.id(), 100).unwrap();
+        // Not due until the retry timeout has elapsed
+        assert!(MissingTransactionsRequest::get_all_due(&*tx, NodeHeight(5), 100)
+            .unwrap()
+            .is_empty());
+
+        // The proposer only responds with a subset of the requested transactions
+        for id in &missing[..2] {
+            assert!(tx.missing_transactions_remove(NodeHeight(5), id).unwrap().is_none());
+        }
+
+        let requests = MissingTransactionsRequest::get_all_due(&*tx, NodeHeight(5), 105).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].transaction_ids, HashSet::from([missing[2]]));
+        assert_eq!(requests[0].requested_at, Some(100));
+        assert_eq!(requests[0].attempts, 1);
+
+        // The block is only waiting for execution, so there is nothing left to request
+        assert!(tx
+            .missing_transactions_remove(NodeHeight(5), &missing[2])
+            .unwrap()
+            .is_none());
+        assert!(MissingTransactionsRequest::get_all_due(&*tx, NodeHeight(5), 105)
+            .unwrap()
+            .is_empty());
+
+        let unparked = tx
+            .missing_transactions_remove(NodeHeight(5), &awaiting)
+            .unwrap()
+            .unwrap();
+        assert_eq!(unparked.id(), block.id());
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_excludes_blocks_below_the_min_height() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let old_block = create_parked_block(3);
+        tx.missing_transactions_insert(&old_block, [&create_tx_atom().id], [])
+            .unwrap();
+        let block = create_parked_block(4);
+        tx.missing_transactions_insert(&block, [&create_tx_atom().id], [])
+            .unwrap();
+
+        let requests = MissingTransactionsRequest::get_all_due(&*tx, NodeHeight(4), 0).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].block_id, *block.id());
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_fails_to_record_an_attempt_for_a_block_that_is_not_parked() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let block = create_parked_block(1);
+        let err = MissingTransactionsRequest::record_attempt(&mut tx, block.id(), 0).unwrap_err();
+        assert!(matches!(err, tari_dan_storage::StorageError::NotFound { .. }));
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod block_rejections {
+    use tari_common_types::types::PublicKey;
+    use tari_dan_storage::consensus_models::{BlockId, BlockRejection};
+
+    use super::*;
+
+    fn rejection(epoch: u64, height: u64) -> BlockRejection {
+        BlockRejection {
+            block_id: BlockId::new(FixedHash::from([height as u8; 32])),
+            epoch: Epoch(epoch),
+            height: NodeHeight(height),
+            proposed_by: PublicKey::default(),
+            reason: format!("rejected at height {}", height),
+        }
+    }
+
+    #[test]
+    fn it_returns_the_most_recent_rejections_first() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        for height in 1..=5 {
+            rejection(1, height).insert(&mut tx).unwrap();
+        }
+
+        let page = BlockRejection::get_paginated(&*tx, 2, 1).unwrap();
+        assert_eq!(page, vec![rejection(1, 4), rejection(1, 3)]);
+        assert_eq!(BlockRejection::count(&*tx).unwrap(), 5);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_prunes_rejections_before_the_epoch() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        rejection(1, 1).insert(&mut tx).unwrap();
+        rejection(2, 2).insert(&mut tx).unwrap();
+        rejection(3, 3).insert(&mut tx).unwrap();
+
+        BlockRejection::prune_before(&mut tx, Epoch(2)).unwrap();
+
+        let remaining = BlockRejection::get_paginated(&*tx, 10, 0).unwrap();
+        assert_eq!(remaining, vec![rejection(3, 3), rejection(2, 2)]);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod sync_checkpoint {
+    use tari_dan_common_types::optional::Optional;
+    use tari_dan_storage::consensus_models::{BlockId, SyncCheckpoint};
+
+    use super::*;
+
+    fn checkpoint(height: u64) -> SyncCheckpoint {
+        SyncCheckpoint {
+            block_id: BlockId::new([height as u8; 32]),
+            height: NodeHeight(height),
+            epoch: Epoch(1),
+        }
+    }
+
+    #[test]
+    fn it_returns_the_most_recent_checkpoint() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+        assert_eq!(SyncCheckpoint::get(&*tx).optional().unwrap(), None);
+
+        checkpoint(100).set(&mut tx).unwrap();
+        checkpoint(200).set(&mut tx).unwrap();
+        assert_eq!(SyncCheckpoint::get(&*tx).unwrap(), checkpoint(200));
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod new_views {
+    use tari_dan_storage::consensus_models::{NewViewRecord, QcId};
+
+    use super::*;
+
+    fn count(tx: &impl StateStoreReadTransaction<Addr = String>, epoch: u64, new_height: u64) -> u64 {
+        NewViewRecord::count_for_view(tx, Epoch(epoch), NodeHeight(new_height)).unwrap()
+    }
+
+    fn new_view(epoch: u64, new_height: u64, sender: &str, high_qc_height: u64) -> NewViewRecord<String> {
+        NewViewRecord {
+            epoch: Epoch(epoch),
+            new_height: NodeHeight(new_height),
+            sender: sender.to_string(),
+            high_qc_id: QcId::new(FixedHash::from([high_qc_height as u8; 32])),
+            high_qc_height: NodeHeight(high_qc_height),
+        }
+    }
+
+    #[test]
+    fn it_only_counts_the_first_new_view_from_each_sender() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        assert!(new_view(1, 10, "a", 7).insert(&mut tx).unwrap());
+        assert!(new_view(1, 10, "b", 8).insert(&mut tx).unwrap());
+        assert!(!new_view(1, 10, "a", 9).insert(&mut tx).unwrap());
+        assert!(new_view(1, 11, "a", 9).insert(&mut tx).unwrap());
+
+        assert_eq!(count(&*tx, 1, 10), 2);
+        assert_eq!(count(&*tx, 1, 11), 1);
+        assert_eq!(count(&*tx, 2, 10), 0);
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_prunes_new_views_before_the_view() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        new_view(1, 20, "a", 1).insert(&mut tx).unwrap();
+        new_view(2, 9, "a", 1).insert(&mut tx).unwrap();
+        new_view(2, 10, "a", 1).insert(&mut tx).unwrap();
+        new_view(3, 1, "a", 1).insert(&mut tx).unwrap();
+
+        let num_pruned = NewViewRecord::<String>::prune_before(&mut tx, Epoch(2), NodeHeight(10)).unwrap();
+        assert_eq!(num_pruned, 2);
+
+        assert_eq!(count(&*tx, 2, 10), 1);
+        assert_eq!(count(&*tx, 3, 1), 1);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod epoch_checkpoints {
+    use tari_dan_common_types::optional::Optional;
+    use tari_dan_storage::consensus_models::{BlockId, EpochCheckpoint};
+
+    use super::*;
+
+    fn checkpoint(epoch: u64, state_merkle_root: u8) -> EpochCheckpoint {
+        EpochCheckpoint::new(
+            Epoch(epoch),
+            FixedHash::from([state_merkle_root; 32]),
+            FixedHash::from([epoch as u8; 32]),
+        )
+    }
+
+    #[test]
+    fn it_keeps_the_first_checkpoint_for_each_epoch() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+        assert_eq!(EpochCheckpoint::get(&*tx, Epoch(1)).optional().unwrap(), None);
+
+        checkpoint(1, 1).insert(&mut tx, &BlockId::new([1u8; 32])).unwrap();
+        checkpoint(2, 2).insert(&mut tx, &BlockId::new([2u8; 32])).unwrap();
+        checkpoint(1, 3).insert(&mut tx, &BlockId::new([3u8; 32])).unwrap();
+
+        assert_eq!(EpochCheckpoint::get(&*tx, Epoch(1)).unwrap(), checkpoint(1, 1));
+        assert_eq!(EpochCheckpoint::get(&*tx, Epoch(2)).unwrap(), checkpoint(2, 2));
+        assert_eq!(EpochCheckpoint::get(&*tx, Epoch(3)).optional().unwrap(), None);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod peer_misbehaviors {
+    use std::time::Duration;
+
+    use tari_dan_common_types::optional::Optional;
+    use tari_dan_storage::consensus_models::PeerMisbehavior;
+
+    use super::*;
+
+    fn offence(address: &str, score: u64, banned_until: Option<u64>) -> PeerMisbehavior<String> {
+        PeerMisbehavior {
+            address: address.to_string(),
+            score,
+            last_offence: 100,
+            reason: "invalid proposal".to_string(),
+            banned_until,
+        }
+    }
+
+    #[test]
+    fn it_upserts_and_lists_misbehaving_peers_by_score() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+
+        offence("a", 1, None).upsert(&mut tx).unwrap();
+        offence("b", 5, Some(200)).upsert(&mut tx).unwrap();
+
+        let mut record = PeerMisbehavior::get(&*tx, &"a".to_string()).unwrap();
+        record.record_offence(100, "invalid vote".to_string(), Duration::from_secs(60));
+        record.record_offence(100, "invalid vote".to_string(), Duration::from_secs(60));
+        record.upsert(&mut tx).unwrap();
+
+        let all = PeerMisbehavior::get_all(&*tx).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], offence("b", 5, Some(200)));
+        assert_eq!(all[1].address, "a");
+        assert_eq!(all[1].score, 3);
+        assert_eq!(all[1].reason, "invalid vote");
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_removes_a_misbehaving_peer() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        offence("a", 10, Some(200)).upsert(&mut tx).unwrap();
+
+        assert!(PeerMisbehavior::remove(&mut tx, &"a".to_string()).unwrap());
+        assert!(!PeerMisbehavior::remove(&mut tx, &"a".to_string()).unwrap());
+        assert!(PeerMisbehavior::get(&*tx, &"a".to_string())
+            .optional()
+            .unwrap()
+            .is_none());
+
+        tx.rollback().unwrap();
+    }
+}
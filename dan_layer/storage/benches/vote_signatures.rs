@@ -0,0 +1,43 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::rngs::OsRng;
+use tari_common_types::types::{FixedHash, PrivateKey};
+use tari_crypto::keys::SecretKey;
+use tari_dan_storage::consensus_models::{verify_validator_signatures_batch, ValidatorSignature};
+
+/// The number of signatures in a QC for a large committee
+const NUM_SIGNATURES: usize = 64;
+
+fn create_signatures() -> Vec<(FixedHash, ValidatorSignature)> {
+    (0..NUM_SIGNATURES)
+        .map(|i| {
+            let message = FixedHash::from([i as u8; 32]);
+            let secret_key = PrivateKey::random(&mut OsRng);
+            (message, ValidatorSignature::sign(&secret_key, message))
+        })
+        .collect()
+}
+
+fn vote_signatures(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vote_signatures");
+    let signatures = create_signatures();
+    let batch = signatures
+        .iter()
+        .map(|(message, signature)| (*message, signature))
+        .collect::<Vec<_>>();
+
+    group.bench_function("verify_individually", |b| {
+        b.iter(|| assert!(signatures.iter().all(|(message, signature)| signature.verify(message))))
+    });
+
+    group.bench_function("verify_batch", |b| {
+        b.iter(|| assert!(verify_validator_signatures_batch(&batch)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, vote_signatures);
+criterion_main!(benches);
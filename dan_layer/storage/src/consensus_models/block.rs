@@ -14,7 +14,6 @@ use log::*;
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::Network;
 use tari_common_types::types::{FixedHash, FixedHashSizeError, PublicKey};
-use tari_crypto::tari_utilities::epoch_time::EpochTime;
 use tari_dan_common_types::{
     hashing,
     optional::Optional,
@@ -44,6 +43,7 @@ use super::{
 use crate::{
     consensus_models::{
         Command,
+        EpochCheckpoint,
         HighQc,
         LastExecuted,
         LastProposed,
@@ -52,6 +52,7 @@ use crate::{
         LockedBlock,
         SubstateCreatedProof,
         SubstateUpdate,
+        SyncCheckpoint,
         TransactionRecord,
         Vote,
     },
@@ -205,38 +206,46 @@ impl Block {
         }
     }
 
-    pub fn genesis(network: Network) -> Self {
+    /// Returns the genesis block for the given genesis config, timestamped with the given time in seconds since the
+    /// unix epoch.
+    pub fn genesis(network: Network, genesis: &GenesisConfig, timestamp: u64) -> Self {
         Self::new(
             network,
             BlockId::genesis(),
-            QuorumCertificate::genesis(),
+            QuorumCertificate::genesis_with_config(network, genesis),
             NodeHeight(0),
-            Epoch(0),
-            Shard::from(0),
+            genesis.epoch,
+            genesis.shard,
             PublicKey::default(),
             Default::default(),
-            FixedHash::zero(),
+            genesis.initial_state_root,
             0,
             IndexMap::new(),
             None,
-            EpochTime::now().as_u64(),
-            0,
-            FixedHash::zero(),
+            timestamp,
+            genesis.base_layer_anchor.block_height,
+            genesis.base_layer_anchor.block_hash,
         )
     }
 
     /// This is the parent block for all genesis blocks. Its block ID is always zero.
     pub fn zero_block(network: Network) -> Self {
+        Self::zero_block_with_genesis(network, &GenesisConfig::default())
+    }
+
+    /// Returns the zero block for a chain that starts with the given genesis config. The block ID is always zero, the
+    /// genesis config is committed to by the justify QC.
+    pub fn zero_block_with_genesis(network: Network, genesis: &GenesisConfig) -> Self {
         Self {
             network,
             id: BlockId::genesis(),
             parent: BlockId::genesis(),
-            justify: QuorumCertificate::genesis(),
+            justify: QuorumCertificate::genesis_with_config(network, genesis),
             height: NodeHeight(0),
-            epoch: Epoch(0),
-            shard: Shard::from(0),
+            epoch: genesis.epoch,
+            shard: genesis.shard,
             proposed_by: PublicKey::default(),
-            merkle_root: FixedHash::zero(),
+            merkle_root: genesis.initial_state_root,
             commands: Default::default(),
             total_leader_fee: 0,
             is_dummy: false,
@@ -246,9 +255,10 @@ impl Block {
             stored_at: None,
             signature: None,
             block_time: None,
-            timestamp: EpochTime::now().as_u64(),
-            base_layer_block_height: 0,
-            base_layer_block_hash: FixedHash::zero(),
+            // Must be the same on all nodes and never ahead of the first proposed block
+            timestamp: 0,
+            base_layer_block_height: genesis.base_layer_anchor.block_height,
+            base_layer_block_hash: genesis.base_layer_anchor.block_hash,
         }
     }
 
@@ -349,6 +359,11 @@ impl Block {
         self.commands.iter().filter_map(|d| d.foreign_proposal())
     }
 
+    /// Returns the epoch checkpoint if this block ends the epoch
+    pub fn epoch_checkpoint(&self) -> Option<&EpochCheckpoint> {
+        self.commands.iter().find_map(|c| c.epoch_checkpoint())
+    }
+
     pub fn command_count(&self) -> usize {
         self.commands.len()
     }
@@ -367,6 +382,14 @@ impl Block {
         }
     }
 
+    pub fn as_sync_checkpoint(&self) -> SyncCheckpoint {
+        SyncCheckpoint {
+            block_id: self.id,
+            height: self.height,
+            epoch: self.epoch,
+        }
+    }
+
     pub fn as_last_voted(&self) -> LastVoted {
         LastVoted {
             height: self.height,
@@ -432,6 +455,16 @@ impl Block {
             .sum()
     }
 
+    /// The total priority fees offered by the transactions in this block. These are included in the total
+    /// transaction fee.
+    pub fn total_priority_fee(&self) -> u64 {
+        self.commands
+            .iter()
+            .filter_map(|c| c.accept().or_else(|| c.local_only()))
+            .map(|atom| atom.priority_fee)
+            .sum()
+    }
+
     pub fn proposed_by(&self) -> &PublicKey {
         &self.proposed_by
     }
@@ -562,6 +595,14 @@ impl Block {
 
     /// Inserts the block if it doesnt exist. Returns true if the block was saved and did not exist previously,
     /// otherwise false.
+    /// Saves all blocks that do not already exist in a single batch
+    pub fn save_all<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        blocks: &[Block],
+    ) -> Result<(), StorageError> {
+        tx.blocks_insert_many(blocks)
+    }
+
     pub fn save<TTx>(&self, tx: &mut TTx) -> Result<bool, StorageError>
     where
         TTx: StateStoreWriteTransaction + Deref,
@@ -672,6 +713,23 @@ impl Block {
         Block::get(tx, &self.parent)
     }
 
+    /// Returns the ids of the blocks on the fork ending at `abandoned_tip` that are not ancestors of `new_tip`, starting
+    /// at `abandoned_tip`. These blocks are abandoned when the chain switches to the fork ending at `new_tip`.
+    pub fn get_ids_abandoned_by_fork_switch<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        abandoned_tip: &BlockId,
+        new_tip: &BlockId,
+    ) -> Result<Vec<BlockId>, StorageError> {
+        let mut abandoned = Vec::new();
+        let mut current = *abandoned_tip;
+        while !current.is_genesis() && !tx.blocks_is_ancestor(new_tip, &current)? {
+            let block = Block::get(tx, &current)?;
+            abandoned.push(current);
+            current = *block.parent();
+        }
+        Ok(abandoned)
+    }
+
     pub fn get_parent_chain<TTx: StateStoreReadTransaction>(
         &self,
         tx: &TTx,
@@ -688,6 +746,67 @@ impl Block {
         tx.blocks_get_all_by_parent(self.id())
     }
 
+    /// Returns a child of this block that justifies it and has itself been certified by a QC
+    pub fn get_certified_child<TTx: StateStoreReadTransaction>(&self, tx: &TTx) -> Result<Self, StorageError> {
+        tx.blocks_get_certified_child(self.id())
+    }
+
+    /// Returns the chain of blocks that proves that this committed block is committed, together with the QC that
+    /// certifies the last block in the chain. The chain starts with this block and ends with a 3-chain b, b', b''
+    /// where b' and b'' are certified children of b and b' respectively. A block that was committed as an ancestor of
+    /// a 3-chain (e.g. one followed by a dummy block) is linked to the 3-chain by its committed descendants. At most
+    /// `max_len` blocks are returned.
+    pub fn get_commit_chain<TTx: StateStoreReadTransaction>(
+        &self,
+        tx: &TTx,
+        max_len: usize,
+    ) -> Result<(Vec<Self>, QuorumCertificate), StorageError> {
+        let not_found = || StorageError::NotFound {
+            item: "Block commit chain".to_string(),
+            key: self.id().to_string(),
+        };
+
+        if !self.is_committed() {
+            return Err(not_found());
+        }
+
+        let mut chain = vec![self.clone()];
+        loop {
+            let current = chain.last().expect("chain is never empty");
+            if let Some(child) = current.get_certified_child(tx).optional()? {
+                if let Some(grandchild) = child.get_certified_child(tx).optional()? {
+                    let commit_qc = QuorumCertificate::get_by_block_id(tx, grandchild.id())?;
+                    chain.push(child);
+                    chain.push(grandchild);
+                    return Ok((chain, commit_qc));
+                }
+            }
+
+            // The 3-chain must fit within the limit
+            if chain.len() + 2 >= max_len {
+                return Err(not_found());
+            }
+
+            let next = current
+                .get_child_blocks(tx)?
+                .into_iter()
+                .find(|b| b.is_committed())
+                .ok_or_else(not_found)?;
+            chain.push(next);
+        }
+    }
+
+    /// Returns all non-dummy blocks that the validator proposed for the given height and epoch. Honest leaders propose
+    /// at most one block per height.
+    pub fn get_all_by_proposer_at_height<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.blocks_get_all_by_proposer_at_height(epoch, height, proposed_by)
+    }
+
     pub fn get_total_due_for_epoch<TTx: StateStoreReadTransaction>(
         tx: &TTx,
         epoch: Epoch,
@@ -0,0 +1,63 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use super::BlockId;
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A local proposal that this node rejected because it failed validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct BlockRejection {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    pub epoch: Epoch,
+    pub height: NodeHeight,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub proposed_by: PublicKey,
+    pub reason: String,
+}
+
+impl BlockRejection {
+    pub fn insert<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.block_rejections_insert(self)
+    }
+
+    /// Returns the most recent rejections first
+    pub fn get_paginated<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.block_rejections_get_paginated(limit, offset)
+    }
+
+    pub fn count<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<u64, StorageError> {
+        tx.block_rejections_count()
+    }
+
+    /// Removes all rejections of blocks proposed before the given epoch
+    pub fn prune_before<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        epoch: Epoch,
+    ) -> Result<(), StorageError> {
+        tx.block_rejections_prune_before(epoch)
+    }
+}
+
+impl Display for BlockRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "BlockRejection(block: {}, epoch: {}, height: {}, proposed_by: {}, reason: {})",
+            self.block_id, self.epoch, self.height, self.proposed_by, self.reason
+        )
+    }
+}
@@ -9,12 +9,13 @@ use std::{
 
 use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
-use tari_dan_common_types::SubstateAddress;
+use tari_dan_common_types::{Epoch, SubstateAddress};
 use tari_transaction::{TransactionId, VersionedSubstateId};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
 use super::{
+    EpochCheckpoint,
     ExecutedTransaction,
     ForeignProposal,
     LeaderFee,
@@ -28,6 +29,16 @@ use crate::{
     StorageError,
 };
 
+/// Size estimates in bytes used by the `estimated_size` functions. They approximate the encoded size of each field
+/// including framing overhead and err on the side of overestimating.
+const HASH_SIZE: usize = 32;
+const FIELD_OVERHEAD: usize = 4;
+const U64_SIZE: usize = 8 + FIELD_OVERHEAD;
+const SHARD_EVIDENCE_SIZE: usize = HASH_SIZE + FIELD_OVERHEAD + U64_SIZE;
+const TRANSACTION_ATOM_SIZE: usize = HASH_SIZE + FIELD_OVERHEAD + U64_SIZE * 5;
+const FOREIGN_PROPOSAL_SIZE: usize = HASH_SIZE + FIELD_OVERHEAD + U64_SIZE * 4;
+const EPOCH_CHECKPOINT_SIZE: usize = U64_SIZE + (HASH_SIZE + FIELD_OVERHEAD) * 2;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub struct Evidence {
@@ -112,6 +123,14 @@ impl Evidence {
         self.evidence.values().flat_map(|e| e.qc_ids.iter())
     }
 
+    /// Returns a cheap estimate of the encoded size of the evidence in bytes
+    pub fn estimated_size(&self) -> usize {
+        self.evidence
+            .values()
+            .map(|e| SHARD_EVIDENCE_SIZE + e.qc_ids.len() * (HASH_SIZE + FIELD_OVERHEAD))
+            .sum()
+    }
+
     pub fn merge(&mut self, other: Evidence) -> &mut Self {
         for (substate_address, shard_evidence) in other.evidence {
             let entry = self.evidence.entry(substate_address).or_insert_with(|| ShardEvidence {
@@ -168,6 +187,10 @@ pub struct TransactionAtom {
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub transaction_fee: u64,
     pub leader_fee: Option<LeaderFee>,
+    /// The priority fee offered by the transaction. This is included in the transaction fee.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub priority_fee: u64,
 }
 
 impl TransactionAtom {
@@ -178,6 +201,7 @@ impl TransactionAtom {
             evidence: Evidence::empty(),
             transaction_fee: 0,
             leader_fee: None,
+            priority_fee: 0,
         }
     }
 
@@ -196,6 +220,12 @@ impl TransactionAtom {
         ExecutedTransaction::get(tx, &self.id)
     }
 
+    /// Returns a cheap estimate of the encoded size of the atom in bytes. The estimate assumes that a leader fee is
+    /// set, so it does not change when the leader fee is calculated.
+    pub fn estimated_size(&self) -> usize {
+        TRANSACTION_ATOM_SIZE + self.evidence.estimated_size()
+    }
+
     pub fn abort(self) -> Self {
         Self {
             decision: Decision::Abort,
@@ -231,6 +261,8 @@ pub enum Command {
     ForeignProposal(ForeignProposal),
     LocalOnly(TransactionAtom),
     EpochEvent(EpochEvent),
+    /// The checkpoint of the epoch, carried by the block that ends the epoch
+    EpochCheckpoint(EpochCheckpoint),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Ord, PartialOrd)]
@@ -245,6 +277,7 @@ pub enum CommandId {
     TransactionId(TransactionId),
     ForeignProposal(ForeignProposal),
     EpochEvent(EpochEvent),
+    EpochCheckpoint(Epoch),
 }
 
 impl Display for CommandId {
@@ -253,6 +286,7 @@ impl Display for CommandId {
             CommandId::TransactionId(id) => write!(f, "Transaction({})", id),
             CommandId::ForeignProposal(fp) => write!(f, "ForeignProposal({})", fp.block_id),
             CommandId::EpochEvent(event) => write!(f, "EpochEvent({:?})", event),
+            CommandId::EpochCheckpoint(epoch) => write!(f, "EpochCheckpoint({})", epoch),
         }
     }
 }
@@ -266,9 +300,31 @@ impl Command {
             Command::LocalOnly(tx) => Some(tx),
             Command::ForeignProposal(_) => None,
             Command::EpochEvent(_) => None,
+            Command::EpochCheckpoint(_) => None,
         }
     }
 
+    /// Returns a cheap estimate of the encoded size of the command in bytes
+    pub fn estimated_size(&self) -> usize {
+        match self {
+            Command::Prepare(tx) | Command::LocalPrepared(tx) | Command::Accept(tx) | Command::LocalOnly(tx) => {
+                Self::estimated_size_of_transaction(tx)
+            },
+            Command::ForeignProposal(foreign_proposal) => {
+                FIELD_OVERHEAD +
+                    FOREIGN_PROPOSAL_SIZE +
+                    foreign_proposal.transactions.len() * (HASH_SIZE + FIELD_OVERHEAD)
+            },
+            Command::EpochEvent(_) => FIELD_OVERHEAD + U64_SIZE,
+            Command::EpochCheckpoint(_) => FIELD_OVERHEAD + EPOCH_CHECKPOINT_SIZE,
+        }
+    }
+
+    /// Returns a cheap estimate of the encoded size in bytes of a transaction command with the given atom
+    pub fn estimated_size_of_transaction(atom: &TransactionAtom) -> usize {
+        FIELD_OVERHEAD + atom.estimated_size()
+    }
+
     pub fn id(&self) -> CommandId {
         match self {
             Command::Prepare(tx) => CommandId::TransactionId(tx.id),
@@ -277,6 +333,7 @@ impl Command {
             Command::LocalOnly(tx) => CommandId::TransactionId(tx.id),
             Command::ForeignProposal(foreign_proposal) => CommandId::ForeignProposal(foreign_proposal.clone()),
             Command::EpochEvent(event) => CommandId::EpochEvent(event.clone()),
+            Command::EpochCheckpoint(checkpoint) => CommandId::EpochCheckpoint(checkpoint.epoch()),
         }
     }
 
@@ -308,6 +365,13 @@ impl Command {
         }
     }
 
+    pub fn epoch_checkpoint(&self) -> Option<&EpochCheckpoint> {
+        match self {
+            Command::EpochCheckpoint(checkpoint) => Some(checkpoint),
+            _ => None,
+        }
+    }
+
     pub fn local_only(&self) -> Option<&TransactionAtom> {
         match self {
             Command::LocalOnly(tx) => Some(tx),
@@ -341,6 +405,7 @@ impl Command {
             Command::LocalOnly(tx) => tx.evidence.substate_addresses_iter(),
             Command::ForeignProposal(_) => panic!("ForeignProposal does not have involved shards"),
             Command::EpochEvent(_) => panic!("EpochEvent does not have involved shards"),
+            Command::EpochCheckpoint(_) => panic!("EpochCheckpoint does not have involved shards"),
         }
     }
 
@@ -352,6 +417,7 @@ impl Command {
             Command::LocalOnly(tx) => &tx.evidence,
             Command::ForeignProposal(_) => panic!("ForeignProposal does not have evidence"),
             Command::EpochEvent(_) => panic!("EpochEvent does not have evidence"),
+            Command::EpochCheckpoint(_) => panic!("EpochCheckpoint does not have evidence"),
         }
     }
 }
@@ -377,6 +443,7 @@ impl Display for Command {
             Command::LocalOnly(tx) => write!(f, "LocalOnly({}, {})", tx.id, tx.decision),
             Command::ForeignProposal(fp) => write!(f, "ForeignProposal {}", fp.block_id),
             Command::EpochEvent(event) => write!(f, "EpochEvent {:?}", event),
+            Command::EpochCheckpoint(checkpoint) => write!(f, "EpochCheckpoint {}", checkpoint.epoch()),
         }
     }
 }
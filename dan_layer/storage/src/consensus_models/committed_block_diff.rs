@@ -0,0 +1,97 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::optional::Optional;
+
+use crate::{
+    consensus_models::{Block, BlockDiff, BlockId, SubstateChange},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// A committed block together with the substate changes that it applied to local state. Committed diffs are kept in a
+/// bounded buffer in commit order so that they can be streamed to consumers that may disconnect and resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommittedBlockDiff {
+    /// The position of this diff in commit order. Sequence numbers increase by one for each committed block.
+    pub sequence: u64,
+    pub block: Block,
+    pub changes: Vec<SubstateChange>,
+}
+
+impl CommittedBlockDiff {
+    /// Returns the cursor that a consumer uses to resume streaming after this diff
+    pub fn cursor(&self) -> CommittedBlockDiffCursor {
+        CommittedBlockDiffCursor {
+            sequence: self.sequence,
+            block_id: *self.block.id(),
+        }
+    }
+}
+
+impl CommittedBlockDiff {
+    /// Appends the diff for a newly committed block and prunes the buffer so that at most `retain` diffs are kept
+    pub fn record<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        block: &Block,
+        diff: &BlockDiff,
+        retain: u64,
+    ) -> Result<(), StorageError> {
+        tx.committed_block_diffs_insert(block, diff)?;
+        tx.committed_block_diffs_prune(retain)
+    }
+
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx, sequence: u64) -> Result<Self, StorageError> {
+        tx.committed_block_diffs_get(sequence)
+    }
+
+    /// Returns up to `limit` diffs in commit order that were committed after the given sequence number, or from the
+    /// oldest retained diff if `after_sequence` is None.
+    pub fn get_after<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        after_sequence: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.committed_block_diffs_get_after(after_sequence, limit)
+    }
+
+    /// Returns true if the diff that the cursor points to is still buffered. If it is not, the consumer has fallen
+    /// behind the retention window (or the cursor is from a different chain) and cannot resume without a gap.
+    pub fn is_retained<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        cursor: &CommittedBlockDiffCursor,
+    ) -> Result<bool, StorageError> {
+        let diff = tx.committed_block_diffs_get(cursor.sequence).optional()?;
+        Ok(diff.is_some_and(|diff| *diff.block.id() == cursor.block_id))
+    }
+}
+
+impl Display for CommittedBlockDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CommittedBlockDiff(#{}, {}, {} change(s))",
+            self.sequence,
+            self.block,
+            self.changes.len()
+        )
+    }
+}
+
+/// The position of the last committed diff that a consumer received. The block id is included so that a cursor from
+/// a different chain (e.g. after the node's database was reset) is not silently resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedBlockDiffCursor {
+    pub sequence: u64,
+    pub block_id: BlockId,
+}
+
+impl Display for CommittedBlockDiffCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{} ({})", self.sequence, self.block_id)
+    }
+}
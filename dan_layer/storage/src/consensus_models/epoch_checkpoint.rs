@@ -0,0 +1,73 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::FixedHash;
+use tari_dan_common_types::Epoch;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{consensus_models::BlockId, StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// The state at the end of an epoch, carried by the last block of the epoch. A node that joins later can check the
+/// validator set hash against the validator node merkle root on the base layer and the state merkle root against the
+/// state it syncs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct EpochCheckpoint {
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub state_merkle_root: FixedHash,
+    /// The validator node merkle root of the epoch
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub validator_set_hash: FixedHash,
+}
+
+impl EpochCheckpoint {
+    pub fn new(epoch: Epoch, state_merkle_root: FixedHash, validator_set_hash: FixedHash) -> Self {
+        Self {
+            epoch,
+            state_merkle_root,
+            validator_set_hash,
+        }
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn state_merkle_root(&self) -> &FixedHash {
+        &self.state_merkle_root
+    }
+
+    pub fn validator_set_hash(&self) -> &FixedHash {
+        &self.validator_set_hash
+    }
+}
+
+impl EpochCheckpoint {
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx, epoch: Epoch) -> Result<Self, StorageError> {
+        tx.epoch_checkpoint_get(epoch)
+    }
+
+    /// Stores the checkpoint of the committed block that ended the epoch
+    pub fn insert<TTx: StateStoreWriteTransaction + ?Sized>(
+        &self,
+        tx: &mut TTx,
+        block_id: &BlockId,
+    ) -> Result<(), StorageError> {
+        tx.epoch_checkpoints_insert(block_id, self)
+    }
+}
+
+impl Display for EpochCheckpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EpochCheckpoint({}, state_merkle_root: {}, validator_set_hash: {})",
+            self.epoch, self.state_merkle_root, self.validator_set_hash
+        )
+    }
+}
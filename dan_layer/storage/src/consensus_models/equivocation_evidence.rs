@@ -0,0 +1,122 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_dan_common_types::{optional::Optional, Epoch, NodeHeight};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{BlockId, Vote},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// Evidence that a validator voted for two different blocks at the same height and epoch. Both votes are signed by the
+/// voter, so the evidence can be verified by any validator that knows the voter's leaf hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct EquivocationEvidence {
+    /// The vote that was received first
+    first_vote: Vote,
+    second_vote: Vote,
+}
+
+impl EquivocationEvidence {
+    pub fn new(first_vote: Vote, second_vote: Vote) -> Self {
+        Self {
+            first_vote,
+            second_vote,
+        }
+    }
+
+    pub fn voter(&self) -> &PublicKey {
+        self.first_vote.signature().public_key()
+    }
+
+    pub fn sender_leaf_hash(&self) -> &FixedHash {
+        &self.first_vote.sender_leaf_hash
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.first_vote.epoch
+    }
+
+    pub fn height(&self) -> NodeHeight {
+        self.first_vote.height
+    }
+
+    pub fn first_vote(&self) -> &Vote {
+        &self.first_vote
+    }
+
+    pub fn second_vote(&self) -> &Vote {
+        &self.second_vote
+    }
+
+    pub fn first_block_id(&self) -> &BlockId {
+        &self.first_vote.block_id
+    }
+
+    pub fn second_block_id(&self) -> &BlockId {
+        &self.second_vote.block_id
+    }
+
+    /// Returns true if the votes are for different blocks from the same sender at the same height and epoch. This does
+    /// not check the vote signatures.
+    pub fn is_conflicting(&self) -> bool {
+        self.first_vote.block_id != self.second_vote.block_id &&
+            self.first_vote.sender_leaf_hash == self.second_vote.sender_leaf_hash &&
+            self.first_vote.signature().public_key() == self.second_vote.signature().public_key() &&
+            self.first_vote.epoch == self.second_vote.epoch &&
+            self.first_vote.height == self.second_vote.height
+    }
+}
+
+impl EquivocationEvidence {
+    pub fn insert<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.equivocation_evidence_insert(self)
+    }
+
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<Self, StorageError> {
+        tx.equivocation_evidence_get(epoch, height, sender_leaf_hash)
+    }
+
+    /// Returns true if evidence has already been recorded for the sender at the given height and epoch
+    pub fn exists<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<bool, StorageError> {
+        Ok(Self::get(tx, epoch, height, sender_leaf_hash).optional()?.is_some())
+    }
+
+    /// Returns all recorded evidence, the most recently recorded first
+    pub fn get_all<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<Vec<Self>, StorageError> {
+        tx.equivocation_evidence_get_all()
+    }
+}
+
+impl Display for EquivocationEvidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EquivocationEvidence(voter: {}, epoch: {}, height: {}, blocks: {} and {})",
+            self.voter(),
+            self.epoch(),
+            self.height(),
+            self.first_block_id(),
+            self.second_block_id()
+        )
+    }
+}
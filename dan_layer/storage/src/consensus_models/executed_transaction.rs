@@ -230,6 +230,7 @@ impl ExecutedTransaction {
                 .unwrap_or(0),
             // We calculate the leader fee later depending on the epoch of the block
             leader_fee: None,
+            priority_fee: self.transaction().priority_fee(),
         }
     }
 }
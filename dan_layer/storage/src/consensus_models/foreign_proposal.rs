@@ -8,7 +8,7 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
-use tari_dan_common_types::{shard::Shard, NodeHeight};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeHeight};
 use tari_transaction::TransactionId;
 #[cfg(feature = "ts")]
 use ts_rs::TS;
@@ -22,6 +22,18 @@ pub enum ForeignProposalState {
     New,
     Proposed,
     Deleted,
+    /// The proposal failed validation when it was received
+    Invalid,
+    /// The proposal timed out before all of its transactions were prepared locally
+    Expired,
+}
+
+impl ForeignProposalState {
+    /// Returns true if the proposal will not be proposed or updated again. Proposals in a terminal state are kept for
+    /// inspection until they are pruned.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ForeignProposalState::Invalid | ForeignProposalState::Expired)
+    }
 }
 
 impl Display for ForeignProposalState {
@@ -30,6 +42,8 @@ impl Display for ForeignProposalState {
             ForeignProposalState::New => write!(f, "New"),
             ForeignProposalState::Proposed => write!(f, "Proposed"),
             ForeignProposalState::Deleted => write!(f, "Deleted"),
+            ForeignProposalState::Invalid => write!(f, "Invalid"),
+            ForeignProposalState::Expired => write!(f, "Expired"),
         }
     }
 }
@@ -42,6 +56,8 @@ impl FromStr for ForeignProposalState {
             "New" => Ok(ForeignProposalState::New),
             "Proposed" => Ok(ForeignProposalState::Proposed),
             "Deleted" => Ok(ForeignProposalState::Deleted),
+            "Invalid" => Ok(ForeignProposalState::Invalid),
+            "Expired" => Ok(ForeignProposalState::Expired),
             _ => Err(anyhow::anyhow!("Invalid foreign proposal state {}", s)),
         }
     }
@@ -97,6 +113,38 @@ impl ForeignProposal {
         Ok(())
     }
 
+    /// Transitions the proposal to the Expired state at the given block height and epoch. The proposal is kept until it
+    /// is pruned by [ForeignProposal::prune_terminal_before].
+    pub fn expire<TTx: StateStoreWriteTransaction + ?Sized>(
+        &mut self,
+        tx: &mut TTx,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<(), StorageError> {
+        self.state = ForeignProposalState::Expired;
+        tx.foreign_proposal_set_terminal_state(self, height, epoch)
+    }
+
+    /// Records a proposal that failed validation in the Invalid state. Nothing is recorded if a proposal for the same
+    /// block already exists, so an invalid delivery cannot overwrite a valid proposal. Returns false in that case.
+    pub fn record_invalid<TTx: StateStoreWriteTransaction + ?Sized>(
+        mut self,
+        tx: &mut TTx,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<bool, StorageError> {
+        self.state = ForeignProposalState::Invalid;
+        tx.foreign_proposal_insert_terminal(&self, height, epoch)
+    }
+
+    /// Deletes proposals that entered a terminal state before the given epoch. Returns the number of proposals deleted.
+    pub fn prune_terminal_before<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        epoch: Epoch,
+    ) -> Result<usize, StorageError> {
+        tx.foreign_proposal_delete_terminal_before(epoch)
+    }
+
     pub fn exists<TTx: StateStoreReadTransaction + ?Sized>(
         tx: &TTx,
         foreign_proposal: &Self,
@@ -104,6 +152,32 @@ impl ForeignProposal {
         tx.foreign_proposal_exists(foreign_proposal)
     }
 
+    /// Records that the proposal for the foreign block has been applied. The block id is the hash of the block
+    /// contents, so the same proposal is recorded once regardless of how often it is delivered. Returns false if it was
+    /// already applied, in which case it must not be applied again.
+    pub fn record_applied<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        bucket: Shard,
+        block_id: &BlockId,
+    ) -> Result<bool, StorageError> {
+        tx.applied_foreign_proposals_insert(bucket, block_id)
+    }
+
+    pub fn is_applied<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        block_id: &BlockId,
+    ) -> Result<bool, StorageError> {
+        tx.applied_foreign_proposals_exists(block_id)
+    }
+
+    /// Returns the number of applied proposals from the given foreign shard
+    pub fn count_applied<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        bucket: Shard,
+    ) -> Result<u64, StorageError> {
+        tx.applied_foreign_proposals_count(bucket)
+    }
+
     pub fn get_all_new<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<Vec<Self>, StorageError> {
         tx.foreign_proposal_get_all_new()
     }
@@ -122,4 +196,13 @@ impl ForeignProposal {
     ) -> Result<Vec<Self>, StorageError> {
         tx.foreign_proposal_get_all_proposed(to_height)
     }
+
+    pub fn get_all_by_state<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        state: ForeignProposalState,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.foreign_proposal_get_all_by_state(state, limit, offset)
+    }
 }
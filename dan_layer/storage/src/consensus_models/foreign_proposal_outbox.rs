@@ -0,0 +1,93 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use tari_dan_common_types::shard::Shard;
+
+use super::BlockId;
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A foreign proposal that has not yet been acknowledged by the destination committee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignProposalOutboxEntry {
+    /// The destination committee
+    pub bucket: Shard,
+    /// The locked block that is proposed to the destination committee
+    pub block_id: BlockId,
+    /// Unix timestamp (seconds) at which the entry was created
+    pub created_at: u64,
+    pub attempts: u32,
+    /// Unix timestamp (seconds) of the most recent delivery attempt
+    pub last_attempt_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl ForeignProposalOutboxEntry {
+    pub fn new(bucket: Shard, block_id: BlockId, created_at: u64) -> Self {
+        Self {
+            bucket,
+            block_id,
+            created_at,
+            attempts: 0,
+            last_attempt_at: None,
+            last_error: None,
+        }
+    }
+
+    pub fn record_attempt(&mut self, attempted_at: u64, error: Option<String>) -> &mut Self {
+        self.attempts += 1;
+        self.last_attempt_at = Some(attempted_at);
+        self.last_error = error;
+        self
+    }
+}
+
+impl ForeignProposalOutboxEntry {
+    /// Inserts the entry if there is no existing entry for the same bucket and block
+    pub fn insert_if_not_exists<TTx: StateStoreWriteTransaction + ?Sized>(
+        &self,
+        tx: &mut TTx,
+    ) -> Result<(), StorageError> {
+        tx.foreign_proposal_outbox_insert(self)
+    }
+
+    pub fn update<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.foreign_proposal_outbox_update(self)
+    }
+
+    /// Removes the entry for the bucket and block, returning true if it existed
+    pub fn remove<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        bucket: Shard,
+        block_id: &BlockId,
+    ) -> Result<bool, StorageError> {
+        tx.foreign_proposal_outbox_delete(bucket, block_id)
+    }
+
+    /// Removes all entries created before the given unix timestamp, returning the number of removed entries
+    pub fn remove_created_before<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        created_before: u64,
+    ) -> Result<usize, StorageError> {
+        tx.foreign_proposal_outbox_delete_created_before(created_before)
+    }
+
+    pub fn get_all<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<Vec<Self>, StorageError> {
+        tx.foreign_proposal_outbox_get_all()
+    }
+
+    pub fn count<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<u64, StorageError> {
+        tx.foreign_proposal_outbox_count()
+    }
+}
+
+impl Display for ForeignProposalOutboxEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ForeignProposalOutboxEntry(bucket: {}, block: {}, attempts: {})",
+            self.bucket, self.block_id, self.attempts
+        )
+    }
+}
@@ -0,0 +1,129 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_common_types::types::FixedHash;
+use tari_dan_common_types::{hashing, serde_with, shard::Shard, Epoch};
+
+/// Parameters used to construct the zero block and genesis QC that every chain builds on. All members of a committee
+/// must use the same config, otherwise proposals justified by the genesis QC will be rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenesisConfig {
+    /// The epoch that the chain starts at
+    pub epoch: Epoch,
+    /// The shard of the genesis block
+    pub shard: Shard,
+    /// The state merkle root that the first block builds on e.g. the root of a pre-seeded genesis state
+    #[serde(with = "serde_with::hex")]
+    pub initial_state_root: FixedHash,
+    /// The base layer block that the chain is anchored to
+    pub base_layer_anchor: BaseLayerAnchor,
+}
+
+impl GenesisConfig {
+    /// Returns true if this is the config used by networks that were started before genesis configuration existed
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn calculate_hash(&self, network: Network) -> FixedHash {
+        hashing::genesis_hasher()
+            .chain(&network)
+            .chain(&self.epoch)
+            .chain(&self.shard)
+            .chain(&self.initial_state_root)
+            .chain(&self.base_layer_anchor.block_height)
+            .chain(&self.base_layer_anchor.block_hash)
+            .result()
+    }
+}
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        Self {
+            epoch: Epoch(0),
+            shard: Shard::from(0),
+            initial_state_root: FixedHash::zero(),
+            base_layer_anchor: BaseLayerAnchor::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BaseLayerAnchor {
+    pub block_height: u64,
+    #[serde(with = "serde_with::hex")]
+    pub block_hash: FixedHash,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_models::{Block, QuorumCertificate};
+
+    #[test]
+    fn it_uses_the_legacy_genesis_for_the_default_config() {
+        let network = Network::LocalNet;
+        let qc = QuorumCertificate::genesis_with_config(network, &GenesisConfig::default());
+        assert_eq!(qc.id(), QuorumCertificate::genesis().id());
+        assert!(qc.genesis_hash().is_none());
+
+        let zero_block = Block::zero_block_with_genesis(network, &GenesisConfig::default());
+        assert_eq!(zero_block.justify().id(), Block::zero_block(network).justify().id());
+    }
+
+    #[test]
+    fn it_commits_to_the_genesis_config_in_the_genesis_qc() {
+        let network = Network::LocalNet;
+        let genesis = GenesisConfig {
+            epoch: Epoch(5),
+            initial_state_root: FixedHash::from([1u8; 32]),
+            ..Default::default()
+        };
+        let zero_block = Block::zero_block_with_genesis(network, &genesis);
+        assert!(zero_block.id().is_genesis());
+        assert_eq!(zero_block.epoch(), Epoch(5));
+        assert_eq!(*zero_block.merkle_root(), genesis.initial_state_root);
+        assert_eq!(
+            zero_block.justify().genesis_hash(),
+            Some(&genesis.calculate_hash(network))
+        );
+        assert!(zero_block.justify().leaf_hashes().is_empty());
+
+        let other = GenesisConfig {
+            initial_state_root: FixedHash::from([2u8; 32]),
+            ..genesis
+        };
+        assert_ne!(
+            zero_block.justify().id(),
+            QuorumCertificate::genesis_with_config(network, &other).id()
+        );
+        assert_ne!(
+            zero_block.justify().id(),
+            QuorumCertificate::genesis_with_config(Network::Esmeralda, &genesis).id()
+        );
+    }
+
+    #[test]
+    fn it_builds_the_genesis_block_from_the_genesis_config() {
+        let network = Network::LocalNet;
+        let genesis = GenesisConfig {
+            epoch: Epoch(5),
+            shard: Shard::from(2),
+            initial_state_root: FixedHash::from([1u8; 32]),
+            ..Default::default()
+        };
+        let block = Block::genesis(network, &genesis, 1_700_000_000);
+        assert_eq!(block.epoch(), Epoch(5));
+        assert_eq!(block.timestamp(), 1_700_000_000);
+        assert_eq!(block.shard(), Shard::from(2));
+        assert_eq!(*block.merkle_root(), genesis.initial_state_root);
+        assert_eq!(
+            block.justify().id(),
+            QuorumCertificate::genesis_with_config(network, &genesis).id()
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::Epoch;
+
+use crate::{StateStoreReadTransaction, StorageError};
+
+/// Aggregate leader failure statistics. Each height at which the leader failed to propose is filled by a dummy block,
+/// so the statistics are derived from the stored dummy blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeaderFailureStats {
+    /// The total number of dummy blocks in the state store
+    pub dummy_block_count: u64,
+    /// The number of leader timeouts in the epoch before the current epoch, or zero in the first epoch
+    pub leader_timeouts_last_epoch: u64,
+}
+
+impl LeaderFailureStats {
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx, current_epoch: Epoch) -> Result<Self, StorageError> {
+        let dummy_block_count = tx.blocks_count_dummy(None)?;
+        let leader_timeouts_last_epoch = match current_epoch.as_u64().checked_sub(1) {
+            Some(last_epoch) => tx.blocks_count_dummy(Some(Epoch(last_epoch)))?,
+            None => 0,
+        };
+
+        Ok(Self {
+            dummy_block_count,
+            leader_timeouts_last_epoch,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_transaction::TransactionId;
+
+use super::BlockId;
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// An outstanding request to the proposer of a parked block for the block's transactions that this node does not have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingTransactionsRequest {
+    pub block_id: BlockId,
+    pub epoch: Epoch,
+    pub height: NodeHeight,
+    pub proposed_by: PublicKey,
+    /// The transactions that are still missing. Transactions that have been received since the last request are not
+    /// included.
+    pub transaction_ids: HashSet<TransactionId>,
+    /// Unix timestamp (seconds) of the most recent request, or None if the transactions have not been requested yet
+    pub requested_at: Option<u64>,
+    pub attempts: u32,
+}
+
+impl MissingTransactionsRequest {
+    /// Returns the outstanding requests for parked blocks at or above `min_height` that have not been requested since
+    /// `requested_before`.
+    pub fn get_all_due<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        min_height: NodeHeight,
+        requested_before: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.missing_transactions_get_due_requests(min_height, requested_before)
+    }
+
+    /// Records that the missing transactions for the parked block were requested at the given unix timestamp
+    pub fn record_attempt<TTx: StateStoreWriteTransaction + ?Sized>(
+        tx: &mut TTx,
+        block_id: &BlockId,
+        requested_at: u64,
+    ) -> Result<(), StorageError> {
+        tx.parked_blocks_set_transactions_requested(block_id, requested_at)
+    }
+}
+
+impl Display for MissingTransactionsRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MissingTransactionsRequest(block: {}, missing: {}, attempts: {})",
+            self.block_id,
+            self.transaction_ids.len(),
+            self.attempts
+        )
+    }
+}
@@ -3,25 +3,44 @@
 
 mod block;
 mod block_diff;
+mod block_rejection;
 mod command;
+mod committed_block_diff;
+mod cursor;
+mod epoch_checkpoint;
+mod equivocation_evidence;
 mod executed_transaction;
 mod foreign_proposal;
+mod foreign_proposal_outbox;
 mod foreign_receive_counters;
 mod foreign_send_counters;
+mod genesis;
 mod high_qc;
 mod last_executed;
 mod last_proposed;
 mod last_sent_vote;
 mod last_voted;
+mod leader_failure_stats;
 mod leaf_block;
 mod locked_block;
+mod missing_transactions_request;
+mod new_view;
+mod nft_ownership;
+mod peer_misbehavior;
+mod proposer_equivocation;
+mod prune_safety_info;
+mod qc_timing;
 mod quorum;
 mod quorum_certificate;
+mod recent_transaction;
+mod shard_growth;
 mod state_tree_diff;
 mod substate;
 mod substate_change;
 mod substate_lock;
+mod sync_checkpoint;
 mod transaction;
+mod transaction_conflict_edge;
 mod transaction_decision;
 mod transaction_execution;
 mod transaction_pool;
@@ -32,25 +51,44 @@ mod vote_signature;
 
 pub use block::*;
 pub use block_diff::*;
+pub use block_rejection::*;
 pub use command::*;
+pub use committed_block_diff::*;
+pub use cursor::*;
+pub use epoch_checkpoint::*;
+pub use equivocation_evidence::*;
 pub use executed_transaction::*;
 pub use foreign_proposal::*;
+pub use foreign_proposal_outbox::*;
 pub use foreign_receive_counters::*;
 pub use foreign_send_counters::*;
+pub use genesis::*;
 pub use high_qc::*;
 pub use last_executed::*;
 pub use last_proposed::*;
 pub use last_sent_vote::*;
 pub use last_voted::*;
+pub use leader_failure_stats::*;
 pub use leaf_block::*;
 pub use locked_block::*;
+pub use missing_transactions_request::*;
+pub use new_view::*;
+pub use nft_ownership::*;
+pub use peer_misbehavior::*;
+pub use proposer_equivocation::*;
+pub use prune_safety_info::*;
+pub use qc_timing::*;
 pub use quorum::*;
 pub use quorum_certificate::*;
+pub use recent_transaction::*;
+pub use shard_growth::*;
 pub use state_tree_diff::*;
 pub use substate::*;
 pub use substate_change::*;
 pub use substate_lock::*;
+pub use sync_checkpoint::*;
 pub use transaction::*;
+pub use transaction_conflict_edge::*;
 pub use transaction_decision::*;
 pub use transaction_execution::*;
 pub use transaction_pool::*;
@@ -0,0 +1,51 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+
+use super::QcId;
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A NEWVIEW message received by the leader of a view. A leader proposes as soon as it has received NEWVIEWs from
+/// f + 1 distinct validators for the view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewViewRecord<TAddr> {
+    pub epoch: Epoch,
+    pub new_height: NodeHeight,
+    pub sender: TAddr,
+    pub high_qc_id: QcId,
+    pub high_qc_height: NodeHeight,
+}
+
+impl<TAddr> NewViewRecord<TAddr> {
+    /// Inserts the NEWVIEW if the sender has not already sent one for this view. Returns true if the record was
+    /// inserted.
+    pub fn insert<TTx>(&self, tx: &mut TTx) -> Result<bool, StorageError>
+    where TTx: StateStoreWriteTransaction<Addr = TAddr> + ?Sized {
+        tx.new_views_insert(self)
+    }
+
+    /// Returns the number of distinct validators that sent a NEWVIEW for the given view
+    pub fn count_for_view<TTx>(tx: &TTx, epoch: Epoch, new_height: NodeHeight) -> Result<u64, StorageError>
+    where TTx: StateStoreReadTransaction<Addr = TAddr> + ?Sized {
+        tx.new_views_count(epoch, new_height)
+    }
+
+    /// Removes all NEWVIEWs for views before the given epoch and height
+    pub fn prune_before<TTx>(tx: &mut TTx, epoch: Epoch, new_height: NodeHeight) -> Result<usize, StorageError>
+    where TTx: StateStoreWriteTransaction<Addr = TAddr> + ?Sized {
+        tx.new_views_prune_before(epoch, new_height)
+    }
+}
+
+impl<TAddr: Display> Display for NewViewRecord<TAddr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "NewView(epoch: {}, new_height: {}, sender: {}, high_qc: {} ({}))",
+            self.epoch, self.new_height, self.sender, self.high_qc_id, self.high_qc_height
+        )
+    }
+}
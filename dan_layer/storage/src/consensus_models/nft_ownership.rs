@@ -0,0 +1,102 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+use tari_engine_types::{
+    indexed_value::IndexedWellKnownTypes,
+    resource::ResourceType,
+    substate::{SubstateId, SubstateValue},
+};
+use tari_template_lib::models::{ComponentAddress, NonFungibleId, ResourceAddress, VaultId};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{BlockDiff, SubstateChange},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// The vault that holds a non-fungible token and the component that owns the vault.
+///
+/// The index is maintained from the substate changes committed to this node's shard, so it only contains the NFTs held
+/// in vaults in this shard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct NftOwnership {
+    pub resource_address: ResourceAddress,
+    pub nft_id: NonFungibleId,
+    pub vault_id: VaultId,
+    /// None if the component that owns the vault has not been committed to this node's shard
+    pub component_address: Option<ComponentAddress>,
+}
+
+impl NftOwnership {
+    /// Updates the index from the substate changes of a committed block. This must be called in the same transaction
+    /// that commits the block.
+    ///
+    /// A committed vault holds exactly the tokens that it lists, so tokens that leave a vault (transfers and burns) are
+    /// removed and tokens that enter it (transfers and mints) are moved to it. A token that moves between two vaults in
+    /// the same block is indexed against its new vault regardless of the order of the changes.
+    pub fn update_from_diff<TTx>(tx: &mut TTx, diff: &BlockDiff) -> Result<(), StorageError>
+    where
+        TTx: StateStoreWriteTransaction + Deref,
+        TTx::Target: StateStoreReadTransaction,
+    {
+        for change in &diff.changes {
+            let SubstateChange::Up { id, substate, .. } = change else {
+                continue;
+            };
+            match (id.substate_id(), substate.substate_value()) {
+                (SubstateId::Component(address), SubstateValue::Component(component)) => {
+                    let indexed = IndexedWellKnownTypes::from_value(component.state()).map_err(|e| {
+                        StorageError::DecodingError {
+                            operation: "NftOwnership::update_from_diff",
+                            item: "component state",
+                            details: e.to_string(),
+                        }
+                    })?;
+                    if !indexed.vault_ids().is_empty() {
+                        tx.vault_owners_set(address, indexed.vault_ids())?;
+                    }
+                },
+                (SubstateId::Vault(vault_id), SubstateValue::Vault(vault))
+                    if vault.resource_type() == ResourceType::NonFungible =>
+                {
+                    let previous = tx.nft_ownership_get_ids_in_vault(vault_id)?;
+                    let current = vault.get_non_fungible_ids();
+                    let removed = previous.difference(current).cloned().collect::<Vec<_>>();
+                    let added = current.difference(&previous).cloned().collect::<Vec<_>>();
+                    // Only remove the tokens that are still indexed against this vault. A token may already have been
+                    // moved to the vault that it was transferred to by an earlier change in this block.
+                    tx.nft_ownership_remove_from_vault(vault_id, &removed)?;
+                    tx.nft_ownership_set_vault(vault_id, vault.resource_address(), &added)?;
+                },
+                _ => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        resource_address: &ResourceAddress,
+        nft_id: &NonFungibleId,
+    ) -> Result<Self, StorageError> {
+        tx.nft_ownership_get(resource_address, nft_id)
+    }
+
+    /// Returns the NFTs held in vaults owned by the component, ordered by resource address and token id
+    pub fn get_by_owner<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        component_address: &ComponentAddress,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.nft_ownership_get_by_owner(component_address, limit, offset)
+    }
+}
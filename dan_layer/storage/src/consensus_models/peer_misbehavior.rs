@@ -0,0 +1,92 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// The misbehavior score of a peer that sent invalid consensus messages. The score decays while the peer behaves and
+/// the peer is banned for a period once the score reaches a threshold. Times are in seconds since the unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerMisbehavior<TAddr> {
+    pub address: TAddr,
+    pub score: u64,
+    pub last_offence: u64,
+    /// The reason for the most recent offence
+    pub reason: String,
+    /// Messages from the peer are dropped until this time
+    pub banned_until: Option<u64>,
+}
+
+impl<TAddr> PeerMisbehavior<TAddr> {
+    pub fn new(address: TAddr) -> Self {
+        Self {
+            address,
+            score: 0,
+            last_offence: 0,
+            reason: String::new(),
+            banned_until: None,
+        }
+    }
+
+    /// Returns the score at the given time. The score is reduced by one for each full decay interval since the last
+    /// offence. A zero interval disables decay.
+    pub fn decayed_score(&self, now: u64, decay_interval: Duration) -> u64 {
+        let interval_secs = decay_interval.as_secs();
+        if interval_secs == 0 {
+            return self.score;
+        }
+        let elapsed = now.saturating_sub(self.last_offence);
+        self.score.saturating_sub(elapsed / interval_secs)
+    }
+
+    /// Adds one to the decayed score and records the offence
+    pub fn record_offence(&mut self, now: u64, reason: String, decay_interval: Duration) {
+        self.score = self.decayed_score(now, decay_interval) + 1;
+        self.last_offence = now;
+        self.reason = reason;
+    }
+
+    pub fn is_banned(&self, now: u64) -> bool {
+        self.banned_until.is_some_and(|until| now < until)
+    }
+}
+
+impl<TAddr> PeerMisbehavior<TAddr> {
+    pub fn get<TTx>(tx: &TTx, address: &TAddr) -> Result<Self, StorageError>
+    where TTx: StateStoreReadTransaction<Addr = TAddr> + ?Sized {
+        tx.peer_misbehaviors_get(address)
+    }
+
+    /// Returns the misbehavior records of all peers, highest score first
+    pub fn get_all<TTx>(tx: &TTx) -> Result<Vec<Self>, StorageError>
+    where TTx: StateStoreReadTransaction<Addr = TAddr> + ?Sized {
+        tx.peer_misbehaviors_get_all()
+    }
+
+    pub fn upsert<TTx>(&self, tx: &mut TTx) -> Result<(), StorageError>
+    where TTx: StateStoreWriteTransaction<Addr = TAddr> + ?Sized {
+        tx.peer_misbehaviors_upsert(self)
+    }
+
+    /// Removes the record of the peer, which unbans it and resets its score. Returns true if a record was removed.
+    pub fn remove<TTx>(tx: &mut TTx, address: &TAddr) -> Result<bool, StorageError>
+    where TTx: StateStoreWriteTransaction<Addr = TAddr> + ?Sized {
+        tx.peer_misbehaviors_remove(address)
+    }
+}
+
+impl<TAddr: Display> Display for PeerMisbehavior<TAddr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PeerMisbehavior(address: {}, score: {}, last_offence: {}, reason: {}, banned_until: {:?})",
+            self.address, self.score, self.last_offence, self.reason, self.banned_until
+        )
+    }
+}
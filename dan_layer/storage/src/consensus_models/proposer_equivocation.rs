@@ -0,0 +1,113 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{optional::Optional, Epoch, NodeHeight};
+
+use crate::{
+    consensus_models::{Block, BlockId},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// Evidence that a leader proposed two different blocks for the same height and epoch. Both blocks are signed by the
+/// proposer, so any validator can verify the evidence without having received either proposal itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposerEquivocation {
+    /// The proposal that was received first. This is the only one of the two that this node may have voted for.
+    first_block: Block,
+    second_block: Block,
+}
+
+impl ProposerEquivocation {
+    pub fn new(first_block: Block, second_block: Block) -> Self {
+        Self {
+            first_block,
+            second_block,
+        }
+    }
+
+    pub fn proposed_by(&self) -> &PublicKey {
+        self.first_block.proposed_by()
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.first_block.epoch()
+    }
+
+    pub fn height(&self) -> NodeHeight {
+        self.first_block.height()
+    }
+
+    pub fn first_block(&self) -> &Block {
+        &self.first_block
+    }
+
+    pub fn second_block(&self) -> &Block {
+        &self.second_block
+    }
+
+    pub fn first_block_id(&self) -> &BlockId {
+        self.first_block.id()
+    }
+
+    pub fn second_block_id(&self) -> &BlockId {
+        self.second_block.id()
+    }
+
+    /// Returns true if the blocks are different proposals from the same proposer for the same height and epoch. This
+    /// does not check the block hashes or signatures.
+    pub fn is_conflicting(&self) -> bool {
+        self.first_block.id() != self.second_block.id() &&
+            self.first_block.proposed_by() == self.second_block.proposed_by() &&
+            self.first_block.epoch() == self.second_block.epoch() &&
+            self.first_block.height() == self.second_block.height()
+    }
+}
+
+impl ProposerEquivocation {
+    pub fn insert<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.proposer_equivocations_insert(self)
+    }
+
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<Self, StorageError> {
+        tx.proposer_equivocations_get(epoch, height, proposed_by)
+    }
+
+    /// Returns true if an equivocation has already been recorded for the proposer at the given height and epoch
+    pub fn exists<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<bool, StorageError> {
+        Ok(Self::get(tx, epoch, height, proposed_by).optional()?.is_some())
+    }
+
+    pub fn count<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<u64, StorageError> {
+        tx.proposer_equivocations_count()
+    }
+}
+
+impl Display for ProposerEquivocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ProposerEquivocation(proposed_by: {}, epoch: {}, height: {}, blocks: {} and {})",
+            self.proposed_by(),
+            self.epoch(),
+            self.height(),
+            self.first_block_id(),
+            self.second_block_id()
+        )
+    }
+}
@@ -0,0 +1,96 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+
+use crate::{StateStoreReadTransaction, StorageError};
+
+/// The floors below which chain data may be pruned without removing anything that consensus still depends on. All
+/// pruning methods take this struct and refuse to prune past any of its floors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneSafetyInfo {
+    /// The height of the last committed block
+    pub committed_height: NodeHeight,
+    /// The epoch of the last committed block
+    pub committed_epoch: Epoch,
+    /// The lowest height of any block still referenced by a proposed foreign proposal, a parked block, a pending state
+    /// tree diff or the execution of a transaction that has not been finalized. None if no blocks are referenced.
+    pub lowest_referenced_height: Option<NodeHeight>,
+    /// The earliest epoch containing committed blocks with leader fees that have not been claimed. None if all leader
+    /// fees have been claimed.
+    pub earliest_unclaimed_fee_epoch: Option<Epoch>,
+}
+
+impl PruneSafetyInfo {
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<Self, StorageError> {
+        tx.get_prune_safety_info()
+    }
+
+    /// Returns the lowest block height that must be retained
+    pub fn height_floor(&self) -> NodeHeight {
+        self.lowest_referenced_height
+            .map_or(self.committed_height, |height| height.min(self.committed_height))
+    }
+
+    /// Returns an error if data for blocks below the given height may not be pruned
+    pub fn check_height(&self, height: NodeHeight) -> Result<(), StorageError> {
+        let floor = self.height_floor();
+        if height > floor {
+            return Err(StorageError::PruneFloorExceeded {
+                details: format!(
+                    "cannot prune below height {} because height {} is still required",
+                    height, floor
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns an error if data for epochs before the given epoch may not be pruned
+    pub fn check_epoch(&self, epoch: Epoch) -> Result<(), StorageError> {
+        if epoch > self.committed_epoch {
+            return Err(StorageError::PruneFloorExceeded {
+                details: format!(
+                    "cannot prune before epoch {} because the last committed block is in epoch {}",
+                    epoch, self.committed_epoch
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the leader fees for the given epoch may not have been claimed yet
+    pub fn check_fee_epoch(&self, epoch: Epoch) -> Result<(), StorageError> {
+        if let Some(unclaimed) = self.earliest_unclaimed_fee_epoch {
+            if epoch >= unclaimed {
+                return Err(StorageError::PruneFloorExceeded {
+                    details: format!(
+                        "cannot prune blocks in epoch {} because leader fees in epoch {} have not been claimed",
+                        epoch, unclaimed
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for PruneSafetyInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PruneSafetyInfo(committed: {} in {}, ",
+            self.committed_height, self.committed_epoch
+        )?;
+        match self.lowest_referenced_height {
+            Some(height) => write!(f, "lowest referenced: {}, ", height)?,
+            None => write!(f, "lowest referenced: --, ")?,
+        }
+        match self.earliest_unclaimed_fee_epoch {
+            Some(epoch) => write!(f, "unclaimed fees from: {})", epoch),
+            None => write!(f, "unclaimed fees from: --)"),
+        }
+    }
+}
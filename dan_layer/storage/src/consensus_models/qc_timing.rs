@@ -0,0 +1,164 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use super::BlockId;
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A summary of when the votes for a block arrived at the validator that collected them to form a QC. All times are
+/// in milliseconds after the proposal for the block was received, or after the first vote if that arrived before the
+/// proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct QcTiming {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    pub epoch: Epoch,
+    pub height: NodeHeight,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub first_vote_ms: u64,
+    /// None if the block has not (yet) received a quorum of votes
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub quorum_reached_ms: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub last_vote_ms: u64,
+    /// The votes in the order that they arrived
+    pub votes: Vec<VoteTiming>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct VoteTiming {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub arrived_after_ms: u64,
+    /// The order in which the vote arrived, starting at 0 for the first vote
+    pub rank: u32,
+}
+
+/// The average lateness of a validator's votes over all recorded blocks in an epoch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ValidatorVoteLateness {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_votes: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub average_lateness_ms: u64,
+    pub average_rank: f64,
+}
+
+impl QcTiming {
+    /// Returns the average vote lateness of each validator in the given timings, ordered from the earliest to the
+    /// latest voter on average.
+    pub fn summarize_by_validator(timings: &[QcTiming]) -> Vec<ValidatorVoteLateness> {
+        let mut totals = HashMap::<&PublicKey, (u64, u64, u64)>::new();
+        for vote in timings.iter().flat_map(|t| &t.votes) {
+            let (num_votes, total_lateness, total_rank) = totals.entry(&vote.public_key).or_default();
+            *num_votes += 1;
+            *total_lateness += vote.arrived_after_ms;
+            *total_rank += u64::from(vote.rank);
+        }
+
+        let mut summary = totals
+            .into_iter()
+            .map(|(public_key, (num_votes, total_lateness, total_rank))| ValidatorVoteLateness {
+                public_key: public_key.clone(),
+                num_votes,
+                average_lateness_ms: total_lateness / num_votes,
+                average_rank: total_rank as f64 / num_votes as f64,
+            })
+            .collect::<Vec<_>>();
+        summary.sort_by(|a, b| {
+            a.average_rank
+                .total_cmp(&b.average_rank)
+                .then(a.average_lateness_ms.cmp(&b.average_lateness_ms))
+        });
+        summary
+    }
+}
+
+impl QcTiming {
+    /// Inserts the timing or replaces the existing timing for the same block
+    pub fn upsert<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.qc_timings_upsert(self)
+    }
+
+    pub fn get_by_epoch<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        epoch: Epoch,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.qc_timings_get_by_epoch(epoch)
+    }
+}
+
+impl Display for QcTiming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "QcTiming(block: {}, first: {}ms, ", self.block_id, self.first_vote_ms)?;
+        match self.quorum_reached_ms {
+            Some(ms) => write!(f, "quorum: {}ms, ", ms)?,
+            None => write!(f, "quorum: --, ")?,
+        }
+        write!(f, "last: {}ms, votes: {})", self.last_vote_ms, self.votes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_crypto::keys::PublicKey as _;
+
+    use super::*;
+
+    fn timing(votes: &[(&PublicKey, u64)]) -> QcTiming {
+        QcTiming {
+            block_id: BlockId::genesis(),
+            epoch: Epoch(1),
+            height: NodeHeight(1),
+            first_vote_ms: votes[0].1,
+            quorum_reached_ms: None,
+            last_vote_ms: votes[votes.len() - 1].1,
+            votes: votes
+                .iter()
+                .enumerate()
+                .map(|(rank, (public_key, arrived_after_ms))| VoteTiming {
+                    public_key: (*public_key).clone(),
+                    arrived_after_ms: *arrived_after_ms,
+                    rank: rank as u32,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_orders_validators_by_average_lateness() {
+        let (_, a) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+        let (_, b) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+        let (_, c) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+
+        let summary = QcTiming::summarize_by_validator(&[
+            timing(&[(&a, 10), (&b, 20), (&c, 500)]),
+            timing(&[(&b, 6), (&a, 15), (&c, 700)]),
+        ]);
+
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[2].public_key, c);
+        assert_eq!(summary[2].num_votes, 2);
+        assert_eq!(summary[2].average_lateness_ms, 600);
+        assert_eq!(summary[2].average_rank, 2.0);
+        assert_eq!(summary[0].average_rank, 0.5);
+        assert_eq!(summary[1].average_rank, 0.5);
+        assert_eq!(summary[0].public_key, a);
+    }
+}
@@ -5,6 +5,7 @@ use std::{fmt::Display, ops::Deref};
 
 use log::*;
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
 use tari_common_types::types::{FixedHash, FixedHashSizeError};
 use tari_dan_common_types::{
     hashing::quorum_certificate_hasher,
@@ -18,7 +19,16 @@ use tari_dan_common_types::{
 use ts_rs::TS;
 
 use crate::{
-    consensus_models::{Block, BlockId, HighQc, LastVoted, LeafBlock, QuorumDecision, ValidatorSignature},
+    consensus_models::{
+        Block,
+        BlockId,
+        GenesisConfig,
+        HighQc,
+        LastVoted,
+        LeafBlock,
+        QuorumDecision,
+        ValidatorSignature,
+    },
     StateStoreReadTransaction,
     StateStoreWriteTransaction,
     StorageError,
@@ -41,6 +51,11 @@ pub struct QuorumCertificate {
     #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     leaf_hashes: Vec<FixedHash>,
     decision: QuorumDecision,
+    /// The hash of the genesis config that a genesis QC commits to. This is only set for genesis QCs of chains that do
+    /// not use the default genesis config.
+    #[serde(default, with = "serde_with::hex::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    genesis_hash: Option<FixedHash>,
 }
 
 impl QuorumCertificate {
@@ -63,6 +78,7 @@ impl QuorumCertificate {
             signatures,
             leaf_hashes,
             decision,
+            genesis_hash: None,
         };
         qc.qc_id = qc.calculate_id();
         qc
@@ -80,17 +96,48 @@ impl QuorumCertificate {
         )
     }
 
+    /// Returns the genesis QC for a chain that starts with the given genesis config. The QC commits to the hash of the
+    /// config so that the first proposals commit to the config. The default config records no hash, so existing
+    /// networks keep the same genesis QC.
+    pub fn genesis_with_config(network: Network, genesis: &GenesisConfig) -> Self {
+        if genesis.is_default() {
+            return Self::genesis();
+        }
+
+        Self::new(
+            BlockId::genesis(),
+            NodeHeight::zero(),
+            genesis.epoch,
+            genesis.shard,
+            vec![],
+            vec![],
+            QuorumDecision::Accept,
+        )
+        .with_genesis_hash(Some(genesis.calculate_hash(network)))
+    }
+
+    /// Sets the genesis hash that this QC commits to. This is used when converting a genesis QC received from a peer.
+    pub fn with_genesis_hash(mut self, genesis_hash: Option<FixedHash>) -> Self {
+        self.genesis_hash = genesis_hash;
+        self.qc_id = self.calculate_id();
+        self
+    }
+
     pub fn calculate_id(&self) -> QcId {
-        quorum_certificate_hasher()
+        let hasher = quorum_certificate_hasher()
             .chain(&self.epoch)
             .chain(&self.shard)
             .chain(&self.block_id)
             .chain(&self.block_height)
             .chain(&self.signatures)
             .chain(&self.leaf_hashes)
-            .chain(&self.decision)
-            .result()
-            .into()
+            .chain(&self.decision);
+        // Only committed to if present, so that the IDs of existing QCs are unchanged
+        let hasher = match self.genesis_hash {
+            Some(ref genesis_hash) => hasher.chain(genesis_hash),
+            None => hasher,
+        };
+        hasher.result().into()
     }
 
     pub fn is_valid(&self) -> bool {
@@ -103,6 +150,11 @@ impl QuorumCertificate {
         self.block_id.is_genesis()
     }
 
+    /// Returns the genesis hash recorded in a genesis QC, if any
+    pub fn genesis_hash(&self) -> Option<&FixedHash> {
+        self.genesis_hash.as_ref()
+    }
+
     pub fn id(&self) -> &QcId {
         &self.qc_id
     }
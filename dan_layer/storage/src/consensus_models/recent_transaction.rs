@@ -0,0 +1,58 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::Epoch;
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{BlockId, Decision, TransactionCursor},
+    StateStoreReadTransaction,
+    StorageError,
+};
+
+/// A summary of a transaction and the outcome of its latest execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct RecentTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    /// The final decision, or None if the transaction has not been finalized
+    pub decision: Option<Decision>,
+    pub abort_reason: Option<String>,
+    /// The fees paid in the latest execution, or None if the transaction has not been executed
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub fee: Option<u64>,
+    /// The block in which the transaction was last executed
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub block_id: Option<BlockId>,
+    /// Unix timestamp in milliseconds at which the transaction was received
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub timestamp: u64,
+}
+
+impl RecentTransaction {
+    pub fn get_filtered<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        filter: &RecentTransactionFilter,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+    ) -> Result<(Vec<Self>, Option<TransactionCursor>), StorageError> {
+        tx.transactions_get_recent_filtered(filter, cursor, limit)
+    }
+}
+
+/// Filters for recent transactions. A transaction is returned if it matches all filters that are set.
+#[derive(Debug, Clone, Default)]
+pub struct RecentTransactionFilter {
+    pub decision: Option<Decision>,
+    /// The minimum (inclusive) epoch of the block in which the transaction was last executed
+    pub from_epoch: Option<Epoch>,
+    /// The maximum (inclusive) epoch of the block in which the transaction was last executed
+    pub to_epoch: Option<Epoch>,
+    /// Only return transactions that have the substate as an input or output
+    pub involved_substate_id: Option<SubstateId>,
+}
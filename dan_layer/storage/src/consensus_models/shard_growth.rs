@@ -0,0 +1,96 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{shard::Shard, Epoch};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A sample of the number and stored size of the live substates in a shard, with the change since the previous sample
+/// of the same shard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ShardGrowthSample {
+    pub epoch: Epoch,
+    pub shard: Shard,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub substate_count: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_bytes: u64,
+    /// Zero for the first sample of a shard
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub count_delta: i64,
+    /// Zero for the first sample of a shard
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_delta: i64,
+    /// The number of seconds since the previous sample of the shard, zero for the first sample
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub interval_secs: u64,
+    /// Unix timestamp in seconds
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub sampled_at: u64,
+}
+
+impl ShardGrowthSample {
+    /// Returns the growth in bytes per hour since the previous sample, or None if this is the first sample of the
+    /// shard.
+    pub fn bytes_per_hour(&self) -> Option<i64> {
+        if self.interval_secs == 0 {
+            return None;
+        }
+        Some(self.bytes_delta.saturating_mul(60 * 60) / self.interval_secs as i64)
+    }
+}
+
+impl ShardGrowthSample {
+    /// Counts the live substates in the shard, stores the sample with the change since the previous sample of the
+    /// shard and removes all but the `max_samples` most recent samples of the shard.
+    pub fn record<TTx>(
+        tx: &mut TTx,
+        epoch: Epoch,
+        shard: Shard,
+        num_committees: u32,
+        sampled_at: u64,
+        max_samples: usize,
+    ) -> Result<Self, StorageError>
+    where
+        TTx: StateStoreWriteTransaction + Deref,
+        TTx::Target: StateStoreReadTransaction,
+    {
+        let range = shard.to_substate_address_range(num_committees);
+        let substate_count = tx.substates_count_in_range(range.start(), range.end())?;
+        let total_bytes = tx.substates_size_in_range(range.start(), range.end())?;
+        let previous = tx.shard_growth_samples_get_recent(shard, 1)?.pop();
+
+        let sample = Self {
+            epoch,
+            shard,
+            substate_count,
+            total_bytes,
+            count_delta: previous
+                .as_ref()
+                .map_or(0, |p| substate_count as i64 - p.substate_count as i64),
+            bytes_delta: previous
+                .as_ref()
+                .map_or(0, |p| total_bytes as i64 - p.total_bytes as i64),
+            interval_secs: previous.as_ref().map_or(0, |p| sampled_at.saturating_sub(p.sampled_at)),
+            sampled_at,
+        };
+        tx.shard_growth_samples_insert(&sample)?;
+        tx.shard_growth_samples_prune(shard, max_samples)?;
+        Ok(sample)
+    }
+
+    /// Returns the `limit` most recent samples of the shard, oldest first
+    pub fn get_recent<TTx: StateStoreReadTransaction + ?Sized>(
+        tx: &TTx,
+        shard: Shard,
+        limit: usize,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.shard_growth_samples_get_recent(shard, limit)
+    }
+}
@@ -168,6 +168,31 @@ impl SubstateRecord {
         tx.substate_locks_insert_all(block_id, locks)
     }
 
+    /// Removes the locks held by the given transactions. This is called when the transactions are finalized.
+    pub fn remove_locks_for_transactions<'a, TTx, I>(tx: &mut TTx, transaction_ids: I) -> Result<(), StorageError>
+    where
+        TTx: StateStoreWriteTransaction,
+        I: IntoIterator<Item = &'a TransactionId>,
+    {
+        tx.substate_locks_remove_many_for_transactions(transaction_ids)
+    }
+
+    /// Removes the locks acquired by blocks on the fork ending at `abandoned_tip`, which has been abandoned in favour
+    /// of the fork ending at `new_tip`. Locks acquired by blocks that are common to both forks are kept.
+    pub fn remove_locks_for_abandoned_fork<TTx>(
+        tx: &mut TTx,
+        abandoned_tip: &BlockId,
+        new_tip: &BlockId,
+    ) -> Result<Vec<BlockId>, StorageError>
+    where
+        TTx: StateStoreWriteTransaction + Deref,
+        TTx::Target: StateStoreReadTransaction,
+    {
+        let abandoned = Block::get_ids_abandoned_by_fork_switch(&**tx, abandoned_tip, new_tip)?;
+        tx.substate_locks_remove_many_for_blocks(&abandoned)?;
+        Ok(abandoned)
+    }
+
     pub fn create<TTx: StateStoreWriteTransaction>(self, tx: &mut TTx) -> Result<(), StorageError> {
         tx.substates_create(self)?;
         Ok(())
@@ -1,6 +1,7 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::SubstateAddress;
 use tari_engine_types::substate::Substate;
 use tari_state_tree::SubstateTreeChange;
@@ -8,7 +9,7 @@ use tari_transaction::{TransactionId, VersionedSubstateId};
 
 use crate::consensus_models::SubstateRecord;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SubstateChange {
     Up {
         id: VersionedSubstateId,
@@ -0,0 +1,55 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{Epoch, NodeHeight};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{consensus_models::BlockId, StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// The last synced block that has been validated and committed. Block sync resumes from the checkpoint, so a node that
+/// is interrupted mid-sync does not request the blocks it has already committed again.
+///
+/// Synced blocks after the checkpoint may already be stored, but their substate updates are only kept in memory until
+/// the blocks are committed, so those blocks are requested again when sync resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SyncCheckpoint {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    pub height: NodeHeight,
+    pub epoch: Epoch,
+}
+
+impl SyncCheckpoint {
+    pub fn block_id(&self) -> &BlockId {
+        &self.block_id
+    }
+
+    pub fn height(&self) -> NodeHeight {
+        self.height
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+}
+
+impl SyncCheckpoint {
+    pub fn get<TTx: StateStoreReadTransaction + ?Sized>(tx: &TTx) -> Result<Self, StorageError> {
+        tx.sync_checkpoint_get()
+    }
+
+    pub fn set<TTx: StateStoreWriteTransaction + ?Sized>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.sync_checkpoint_set(self)
+    }
+}
+
+impl Display for SyncCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SyncCheckpoint({}, {}, {})", self.epoch, self.height, self.block_id)
+    }
+}
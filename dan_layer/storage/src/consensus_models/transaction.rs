@@ -9,7 +9,14 @@ use tari_engine_types::commit_result::{ExecuteResult, FinalizeResult, RejectReas
 use tari_transaction::{Transaction, TransactionId, VersionedSubstateId};
 
 use crate::{
-    consensus_models::{BlockId, Decision, ExecutedTransaction, TransactionAtom, VersionedSubstateIdLockIntent},
+    consensus_models::{
+        BlockId,
+        Decision,
+        ExecutedTransaction,
+        TransactionAtom,
+        TransactionCursor,
+        VersionedSubstateIdLockIntent,
+    },
     Ordering,
     StateStoreReadTransaction,
     StateStoreWriteTransaction,
@@ -236,6 +243,15 @@ impl TransactionRecord {
         tx.transactions_get_paginated(limit, offset, ordering)
     }
 
+    pub fn get_after<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+        ordering: Ordering,
+    ) -> Result<(Vec<Self>, Option<TransactionCursor>), StorageError> {
+        tx.transactions_get_after(cursor, limit, ordering)
+    }
+
     pub fn finalize_all<'a, TTx, I>(tx: &mut TTx, block_id: BlockId, transactions: I) -> Result<(), StorageError>
     where
         TTx: StateStoreWriteTransaction + Deref,
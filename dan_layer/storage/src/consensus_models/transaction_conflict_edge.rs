@@ -0,0 +1,45 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+
+use crate::consensus_models::SubstateLockFlag;
+
+/// A directed edge in the transaction dependency graph. The `to` transaction cannot proceed on `substate_id` until the
+/// `from` transaction has released its lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct TransactionConflictEdge {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub from: TransactionId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub to: TransactionId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub substate_id: SubstateId,
+    pub from_lock: SubstateLockFlag,
+    pub to_lock: SubstateLockFlag,
+}
+
+impl TransactionConflictEdge {
+    pub fn involves(&self, transaction_id: &TransactionId) -> bool {
+        self.from == *transaction_id || self.to == *transaction_id
+    }
+}
+
+impl Display for TransactionConflictEdge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) -> {} ({}) on {}",
+            self.from, self.from_lock, self.to, self.to_lock, self.substate_id
+        )
+    }
+}
@@ -2,6 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
     marker::PhantomData,
     num::NonZeroU64,
@@ -13,6 +14,9 @@ use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{
     committee::CommitteeInfo,
     optional::{IsNotFoundError, Optional},
+    Epoch,
+    NodeHeight,
+    SubstateAddress,
 };
 use tari_transaction::TransactionId;
 
@@ -23,6 +27,7 @@ use crate::{
         LeafBlock,
         LockedBlock,
         QcId,
+        SubstateLockFlag,
         TransactionAtom,
         TransactionPoolStatusUpdate,
         TransactionRecord,
@@ -58,6 +63,17 @@ pub enum TransactionPoolStage {
 }
 
 impl TransactionPoolStage {
+    pub const fn all() -> [Self; 6] {
+        [
+            Self::New,
+            Self::Prepared,
+            Self::LocalPrepared,
+            Self::AllPrepared,
+            Self::SomePrepared,
+            Self::LocalOnly,
+        ]
+    }
+
     pub fn is_new(&self) -> bool {
         matches!(self, Self::New)
     }
@@ -120,14 +136,42 @@ impl FromStr for TransactionPoolStage {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// The order in which ready transactions are selected from the pool when proposing a block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionPoolOrdering {
+    /// Transactions are selected in transaction id order
+    #[default]
+    TransactionId,
+    /// Transactions with the highest priority fee are selected first, then those with the highest max fee. Ties are
+    /// broken by transaction id.
+    FeePriority,
+}
+
+#[derive(Debug, Clone)]
 pub struct TransactionPool<TStateStore> {
+    ordering: TransactionPoolOrdering,
+    exclude_conflicting: bool,
     _store: PhantomData<TStateStore>,
 }
 
 impl<TStateStore: StateStore> TransactionPool<TStateStore> {
     pub fn new() -> Self {
-        Self { _store: PhantomData }
+        Self::with_ordering(TransactionPoolOrdering::default())
+    }
+
+    pub fn with_ordering(ordering: TransactionPoolOrdering) -> Self {
+        Self {
+            ordering,
+            exclude_conflicting: true,
+            _store: PhantomData,
+        }
+    }
+
+    /// When enabled (the default), a batch for the next block never contains two transactions that lock the same
+    /// substate, unless both only read it.
+    pub fn with_exclude_conflicting(mut self, exclude_conflicting: bool) -> Self {
+        self.exclude_conflicting = exclude_conflicting;
+        self
     }
 
     pub fn get(
@@ -159,12 +203,25 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
         Ok(exists)
     }
 
+    /// Adds a new transaction to the pool. `first_seen_height` is the current height of the chain and is used to
+    /// determine how long a deferred transaction has been waiting to be executed.
     pub fn insert(
         &self,
         tx: &mut TStateStore::WriteTransaction<'_>,
         transaction: TransactionAtom,
+        first_seen_height: NodeHeight,
     ) -> Result<(), TransactionPoolError> {
-        tx.transaction_pool_insert(transaction, TransactionPoolStage::New, true)?;
+        tx.transaction_pool_insert(transaction, TransactionPoolStage::New, true, first_seen_height)?;
+        Ok(())
+    }
+
+    /// Records that the execution of a deferred transaction failed when proposing it
+    pub fn record_deferred_execution_failure(
+        &self,
+        tx: &mut TStateStore::WriteTransaction<'_>,
+        id: &TransactionId,
+    ) -> Result<(), TransactionPoolError> {
+        tx.transaction_pool_increment_deferred_execution_attempts(id)?;
         Ok(())
     }
 
@@ -181,8 +238,20 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
         &self,
         tx: &TStateStore::ReadTransaction<'_>,
         max: usize,
+        epoch: Epoch,
+    ) -> Result<Vec<TransactionPoolRecord>, TransactionPoolError> {
+        let recs = tx.transaction_pool_get_many_ready(max, self.ordering, self.exclude_conflicting, epoch)?;
+        Ok(recs)
+    }
+
+    /// Returns new transactions whose max epoch has passed. These must be proposed as ABORT.
+    pub fn get_expired_for_next_block(
+        &self,
+        tx: &TStateStore::ReadTransaction<'_>,
+        max: usize,
+        epoch: Epoch,
     ) -> Result<Vec<TransactionPoolRecord>, TransactionPoolError> {
-        let recs = tx.transaction_pool_get_many_ready(max)?;
+        let recs = tx.transaction_pool_get_many_expired(max, epoch)?;
         Ok(recs)
     }
 
@@ -264,6 +333,72 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
     }
 }
 
+/// Greedily selects up to `max` of the given records, in order, so that no two selected transactions lock the same
+/// substate unless both only read it. Records that are past the New stage already hold their locks, so they are
+/// selected before any New records and are never excluded. Records that are being aborted do not lock any substates
+/// and are always selected.
+pub fn select_non_conflicting_transactions<I>(records: I, max: usize) -> Vec<TransactionPoolRecord>
+where I: IntoIterator<Item = TransactionPoolRecord> {
+    let (in_progress, new) = records
+        .into_iter()
+        .partition::<Vec<_>, _>(|rec| !rec.current_stage().is_new());
+
+    let mut locked_substates = HashMap::<SubstateAddress, SubstateLockFlag>::new();
+    let mut selected = Vec::new();
+    for rec in in_progress {
+        if selected.len() >= max {
+            return selected;
+        }
+
+        if !rec.current_decision().is_abort() {
+            locked_substates.extend(
+                rec.atom()
+                    .evidence
+                    .iter()
+                    .map(|(address, evidence)| (*address, evidence.lock)),
+            );
+        }
+        selected.push(rec);
+    }
+
+    for rec in new {
+        if selected.len() >= max {
+            break;
+        }
+
+        if rec.current_decision().is_abort() {
+            selected.push(rec);
+            continue;
+        }
+
+        let conflict = rec.atom().evidence.iter().find(|(address, evidence)| {
+            locked_substates
+                .get(*address)
+                .map_or(false, |lock| !(lock.is_read() && evidence.lock.is_read()))
+        });
+        if let Some((address, evidence)) = conflict {
+            debug!(
+                target: LOG_TARGET,
+                "Not selecting new transaction {} because its {} lock on {} conflicts with a selected transaction",
+                rec.transaction_id(),
+                evidence.lock,
+                address
+            );
+            continue;
+        }
+
+        locked_substates.extend(
+            rec.atom()
+                .evidence
+                .iter()
+                .map(|(address, evidence)| (*address, evidence.lock)),
+        );
+        selected.push(rec);
+    }
+
+    selected
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -277,6 +412,10 @@ pub struct TransactionPoolRecord {
     local_decision: Option<Decision>,
     remote_decision: Option<Decision>,
     is_ready: bool,
+    /// The number of times that execution of this deferred transaction has failed when proposing it
+    deferred_execution_attempts: u32,
+    /// The height of the chain when the transaction was added to the pool
+    first_seen_height: NodeHeight,
 }
 
 impl TransactionPoolRecord {
@@ -287,6 +426,8 @@ impl TransactionPoolRecord {
         local_decision: Option<Decision>,
         remote_decision: Option<Decision>,
         is_ready: bool,
+        deferred_execution_attempts: u32,
+        first_seen_height: NodeHeight,
     ) -> Self {
         Self {
             atom: transaction,
@@ -295,6 +436,8 @@ impl TransactionPoolRecord {
             local_decision,
             remote_decision,
             is_ready,
+            deferred_execution_attempts,
+            first_seen_height,
         }
     }
 
@@ -354,6 +497,14 @@ impl TransactionPoolRecord {
         self.is_ready
     }
 
+    pub fn deferred_execution_attempts(&self) -> u32 {
+        self.deferred_execution_attempts
+    }
+
+    pub fn first_seen_height(&self) -> NodeHeight {
+        self.first_seen_height
+    }
+
     pub fn get_final_transaction_atom(&self, leader_fee: LeaderFee) -> TransactionAtom {
         TransactionAtom {
             decision: self.current_decision(),
@@ -607,6 +758,162 @@ mod tests {
 
     use super::*;
 
+    mod select_non_conflicting_transactions {
+        use super::*;
+        use crate::consensus_models::{
+            ShardEvidence,
+            SubstateLockFlag::{Output, Read, Write},
+        };
+
+        /// Returns an address for the given substate in the given shard when there are 4 shards
+        fn address(shard: u8, substate: u8) -> SubstateAddress {
+            let mut address = [0u8; 32];
+            address[0] = shard * 0x40;
+            address[1] = substate;
+            SubstateAddress::new(address)
+        }
+
+        fn create_record(
+            seed: u8,
+            decision: Decision,
+            locks: &[(SubstateAddress, SubstateLockFlag)],
+        ) -> TransactionPoolRecord {
+            create_record_in_stage(seed, TransactionPoolStage::New, decision, locks)
+        }
+
+        fn create_record_in_stage(
+            seed: u8,
+            stage: TransactionPoolStage,
+            decision: Decision,
+            locks: &[(SubstateAddress, SubstateLockFlag)],
+        ) -> TransactionPoolRecord {
+            TransactionPoolRecord {
+                atom: TransactionAtom {
+                    id: TransactionId::new([seed; 32]),
+                    decision,
+                    evidence: locks
+                        .iter()
+                        .map(|(address, lock)| (*address, ShardEvidence::new(Default::default(), *lock)))
+                        .collect(),
+                    transaction_fee: 0,
+                    leader_fee: None,
+                    priority_fee: 0,
+                },
+                stage,
+                pending_stage: None,
+                local_decision: None,
+                remote_decision: None,
+                is_ready: true,
+                deferred_execution_attempts: 0,
+                first_seen_height: NodeHeight::zero(),
+            }
+        }
+
+        fn select(records: Vec<TransactionPoolRecord>, max: usize) -> Vec<u8> {
+            select_non_conflicting_transactions(records, max)
+                .iter()
+                .map(|rec| rec.transaction_id().as_bytes()[0])
+                .collect()
+        }
+
+        #[test]
+        fn it_excludes_write_write_conflicts() {
+            let records = vec![
+                create_record(1, Decision::Commit, &[(address(0, 1), Write), (address(1, 1), Write)]),
+                // Conflicts with 1 on the substate in shard 1
+                create_record(2, Decision::Commit, &[(address(2, 1), Write), (address(1, 1), Write)]),
+                create_record(3, Decision::Commit, &[(address(2, 1), Write), (address(3, 1), Write)]),
+                // Outputs are written
+                create_record(4, Decision::Commit, &[(address(3, 1), Output)]),
+            ];
+
+            assert_eq!(select(records, 10), vec![1, 3]);
+        }
+
+        #[test]
+        fn it_excludes_read_write_conflicts() {
+            let records = vec![
+                create_record(1, Decision::Commit, &[(address(0, 1), Read), (address(1, 1), Write)]),
+                // Writes a substate read by 1
+                create_record(2, Decision::Commit, &[(address(0, 1), Write)]),
+                // Reads a substate written by 1
+                create_record(3, Decision::Commit, &[(address(1, 1), Read), (address(2, 1), Write)]),
+                create_record(4, Decision::Commit, &[(address(2, 2), Read), (address(3, 1), Write)]),
+            ];
+
+            assert_eq!(select(records, 10), vec![1, 4]);
+        }
+
+        #[test]
+        fn it_allows_read_read_across_shards() {
+            let records = vec![
+                create_record(1, Decision::Commit, &[(address(0, 1), Read), (address(1, 1), Read)]),
+                create_record(2, Decision::Commit, &[(address(0, 1), Read), (address(2, 1), Write)]),
+                create_record(3, Decision::Commit, &[(address(1, 1), Read), (address(3, 1), Write)]),
+                // Writes a different substate in the shard written by 3
+                create_record(4, Decision::Commit, &[(address(0, 1), Read), (address(3, 2), Write)]),
+            ];
+
+            assert_eq!(select(records, 10), vec![1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn it_does_not_lock_substates_for_aborted_transactions() {
+            let records = vec![
+                create_record(1, Decision::Abort, &[(address(0, 1), Write)]),
+                create_record(2, Decision::Commit, &[(address(0, 1), Write)]),
+                create_record(3, Decision::Abort, &[(address(0, 1), Write)]),
+            ];
+
+            assert_eq!(select(records, 10), vec![1, 2, 3]);
+        }
+
+        #[test]
+        fn it_selects_at_most_max_transactions() {
+            let records = vec![
+                create_record(1, Decision::Commit, &[(address(0, 1), Write)]),
+                create_record(2, Decision::Commit, &[(address(0, 1), Write)]),
+                create_record(3, Decision::Commit, &[(address(0, 2), Write)]),
+                create_record(4, Decision::Commit, &[(address(0, 3), Write)]),
+            ];
+
+            assert_eq!(select(records, 2), vec![1, 3]);
+        }
+
+        #[test]
+        fn it_does_not_exclude_in_progress_transactions_for_new_transactions() {
+            let records = vec![
+                // Ordered before the in-progress transactions, but conflicts with both of them
+                create_record(1, Decision::Commit, &[(address(0, 1), Write), (address(1, 1), Write)]),
+                create_record_in_stage(2, TransactionPoolStage::Prepared, Decision::Commit, &[(
+                    address(0, 1),
+                    Write,
+                )]),
+                create_record_in_stage(3, TransactionPoolStage::LocalPrepared, Decision::Commit, &[(
+                    address(1, 1),
+                    Read,
+                )]),
+                create_record(4, Decision::Commit, &[(address(1, 1), Read), (address(2, 1), Write)]),
+            ];
+
+            assert_eq!(select(records, 10), vec![2, 3, 4]);
+        }
+
+        #[test]
+        fn it_selects_in_progress_transactions_first() {
+            let records = vec![
+                create_record(1, Decision::Commit, &[(address(0, 1), Write)]),
+                create_record(2, Decision::Commit, &[(address(0, 2), Write)]),
+                create_record_in_stage(3, TransactionPoolStage::AllPrepared, Decision::Commit, &[(
+                    address(0, 3),
+                    Write,
+                )]),
+            ];
+
+            assert_eq!(select(records, 2), vec![3, 1]);
+        }
+    }
+
     mod calculate_leader_fee {
         use super::*;
 
@@ -618,12 +925,15 @@ mod tests {
                     evidence: Default::default(),
                     transaction_fee: fee,
                     leader_fee: None,
+                    priority_fee: 0,
                 },
                 stage: TransactionPoolStage::New,
                 pending_stage: None,
                 local_decision: None,
                 remote_decision: None,
                 is_ready: false,
+                deferred_execution_attempts: 0,
+                first_seen_height: NodeHeight::zero(),
             }
         }
 
@@ -1,14 +1,13 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{fmt, fmt::Display, ops::Deref};
+use std::{fmt, fmt::Display};
 
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{Epoch, NodeHeight};
 
 use crate::{
     consensus_models::{Block, BlockId, QuorumCertificate},
-    StateStoreReadTransaction,
     StateStoreWriteTransaction,
     StorageError,
 };
@@ -60,15 +59,11 @@ impl ValidBlock {
 }
 
 impl ValidBlock {
-    pub fn save_all_dummy_blocks<TTx>(&self, tx: &mut TTx) -> Result<(), StorageError>
-    where
-        TTx: StateStoreWriteTransaction + Deref,
-        TTx::Target: StateStoreReadTransaction,
-    {
-        for block in &self.dummy_blocks {
-            block.save(tx)?;
-        }
-        Ok(())
+    pub fn save_all_dummy_blocks<TTx: StateStoreWriteTransaction + ?Sized>(
+        &self,
+        tx: &mut TTx,
+    ) -> Result<(), StorageError> {
+        Block::save_all(tx, &self.dummy_blocks)
     }
 }
 
@@ -5,7 +5,9 @@ use std::ops::Deref;
 
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::FixedHash;
-use tari_dan_common_types::{hashing::vote_hasher, optional::Optional, Epoch};
+use tari_dan_common_types::{hashing::vote_hasher, optional::Optional, Epoch, NodeHeight};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
 
 use crate::{
     consensus_models::{BlockId, QuorumDecision, ValidatorSignature},
@@ -15,10 +17,15 @@ use crate::{
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub struct Vote {
     pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub block_id: BlockId,
+    /// The height of the block as given by the voter. The height is not covered by the vote signature.
+    pub height: NodeHeight,
     pub decision: QuorumDecision,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub sender_leaf_hash: FixedHash,
     pub signature: ValidatorSignature,
 }
@@ -74,4 +81,13 @@ impl Vote {
     ) -> Result<Vec<Self>, StorageError> {
         tx.votes_get_for_block(block_id)
     }
+
+    /// Returns a stored vote from the same sender for a different block at the same height and epoch, if any
+    pub fn get_conflicting<TTx: StateStoreReadTransaction + ?Sized>(
+        &self,
+        tx: &TTx,
+    ) -> Result<Option<Self>, StorageError> {
+        let votes = tx.votes_get_by_sender_at_height(self.epoch, self.height, &self.sender_leaf_hash)?;
+        Ok(votes.into_iter().find(|vote| vote.block_id != self.block_id))
+    }
 }
@@ -1,14 +1,22 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use blake2::Blake2b;
+use digest::consts::U64;
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_common_types::types::{FixedHash, PrivateKey, PublicKey};
 use tari_core::transactions::transaction_components::ValidatorNodeHashDomain;
-use tari_crypto::{keys::PublicKey as _, signatures::SchnorrSignature};
+use tari_crypto::{
+    keys::{PublicKey as _, SecretKey as _},
+    signatures::SchnorrSignature,
+};
+use tari_dan_common_types::hashing::vote_signature_hasher;
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
+use crate::consensus_models::{BlockId, QuorumDecision};
+
 pub type ValidatorSchnorrSignature = SchnorrSignature<PublicKey, PrivateKey, ValidatorNodeHashDomain>;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -42,3 +50,90 @@ impl ValidatorSignature {
         self.signature.verify(&self.public_key, message)
     }
 }
+
+/// Returns the message that a validator signs when voting on a block. The leaf hash is the validator's node hash for
+/// the epoch of the vote.
+pub fn vote_signature_challenge(leaf_hash: &FixedHash, block_id: &BlockId, decision: &QuorumDecision) -> FixedHash {
+    vote_signature_hasher()
+        .chain(leaf_hash)
+        .chain(block_id)
+        .chain(decision)
+        .result()
+}
+
+/// Verifies a batch of validator signatures, each over its own message, using a single multiscalar multiplication.
+/// Returns true if all signatures are valid. Each signature equation is weighted by a random scalar so that invalid
+/// signatures cannot cancel each other out.
+pub fn verify_validator_signatures_batch<M: AsRef<[u8]>>(messages_and_signatures: &[(M, &ValidatorSignature)]) -> bool {
+    let mut weighted_signature_sum = PrivateKey::default();
+    let mut scalars = Vec::with_capacity(messages_and_signatures.len() * 2);
+    let mut points = Vec::with_capacity(messages_and_signatures.len() * 2);
+    for (message, signature) in messages_and_signatures {
+        let public_nonce = signature.signature.get_public_nonce();
+        let challenge = ValidatorSchnorrSignature::construct_domain_separated_challenge::<_, Blake2b<U64>>(
+            public_nonce,
+            &signature.public_key,
+            message,
+        );
+        let Ok(challenge) = PrivateKey::from_uniform_bytes(challenge.as_ref()) else {
+            return false;
+        };
+        let weight = PrivateKey::random(&mut OsRng);
+
+        // s.G = R + e.P for each signature, so sum(w.s).G = sum(w.R) + sum(w.e.P) for the batch
+        weighted_signature_sum = &weighted_signature_sum + &weight * signature.signature.get_signature();
+        scalars.push(&weight * &challenge);
+        points.push(signature.public_key.clone());
+        scalars.push(weight);
+        points.push(public_nonce.clone());
+    }
+
+    PublicKey::from_secret_key(&weighted_signature_sum) == PublicKey::batch_mul(&scalars, &points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_signatures(n: usize) -> Vec<(FixedHash, ValidatorSignature)> {
+        (0..n)
+            .map(|i| {
+                let message = FixedHash::from([i as u8; 32]);
+                let secret_key = PrivateKey::random(&mut OsRng);
+                (message, ValidatorSignature::sign(&secret_key, message))
+            })
+            .collect()
+    }
+
+    fn as_batch(signatures: &[(FixedHash, ValidatorSignature)]) -> Vec<(FixedHash, &ValidatorSignature)> {
+        signatures
+            .iter()
+            .map(|(message, signature)| (*message, signature))
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_a_batch_of_valid_signatures() {
+        let signatures = create_signatures(16);
+        assert!(verify_validator_signatures_batch(&as_batch(&signatures)));
+    }
+
+    #[test]
+    fn it_accepts_an_empty_batch() {
+        assert!(verify_validator_signatures_batch::<FixedHash>(&[]));
+    }
+
+    #[test]
+    fn it_rejects_a_batch_with_a_signature_over_a_different_message() {
+        let mut signatures = create_signatures(16);
+        signatures[7].0 = FixedHash::from([0xffu8; 32]);
+        assert!(!verify_validator_signatures_batch(&as_batch(&signatures)));
+    }
+
+    #[test]
+    fn it_rejects_a_batch_with_a_signature_from_a_different_key() {
+        let mut signatures = create_signatures(16);
+        signatures[3].1.public_key = signatures[4].1.public_key.clone();
+        assert!(!verify_validator_signatures_batch(&as_batch(&signatures)));
+    }
+}
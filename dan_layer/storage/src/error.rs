@@ -52,12 +52,20 @@ pub enum StorageError {
         item: &'static str,
         details: String,
     },
+    #[error("Refusing to decode {item}: size {size} bytes exceeds the maximum of {max_size} bytes")]
+    BlobTooLarge {
+        item: &'static str,
+        size: usize,
+        max_size: usize,
+    },
     #[error("Fixed hash size error: {0}")]
     FixedHashSizeError(#[from] FixedHashSizeError),
     #[error("Invalid integer cast")]
     InvalidIntegerCast,
     #[error("Data inconsistency: {details}")]
     DataInconsistency { details: String },
+    #[error("Refusing to prune: {details}")]
+    PruneFloorExceeded { details: String },
     #[error("General storage error: {details}")]
     General { details: String },
 }
@@ -25,6 +25,7 @@ use std::{
     ops::RangeInclusive,
 };
 
+use chrono::NaiveDateTime;
 use serde::{de::DeserializeOwned, Serialize};
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::{
@@ -42,7 +43,7 @@ use crate::{
     global::{
         metadata_db::MetadataKey,
         models::ValidatorNode,
-        template_db::{DbTemplate, DbTemplateUpdate},
+        template_db::{DbTemplate, DbTemplateUpdate, DbTemplateUpload, DbTemplateUploadChunk},
     },
 };
 
@@ -79,6 +80,38 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
         template: DbTemplateUpdate,
     ) -> Result<(), Self::Error>;
 
+    fn insert_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload: DbTemplateUpload,
+    ) -> Result<(), Self::Error>;
+    fn get_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<Option<DbTemplateUpload>, Self::Error>;
+    fn insert_template_upload_chunk(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+        chunk: DbTemplateUploadChunk,
+    ) -> Result<(), Self::Error>;
+    fn get_template_upload_chunks(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<Vec<DbTemplateUploadChunk>, Self::Error>;
+    fn delete_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<(), Self::Error>;
+    fn delete_template_uploads_started_before(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        time: NaiveDateTime,
+    ) -> Result<usize, Self::Error>;
+
     fn insert_validator_node(
         &self,
         tx: &mut Self::DbTransaction<'_>,
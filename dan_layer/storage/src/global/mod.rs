@@ -29,7 +29,15 @@ mod metadata_db;
 pub use metadata_db::{MetadataDb, MetadataKey};
 
 mod template_db;
-pub use template_db::{DbTemplate, DbTemplateType, DbTemplateUpdate, TemplateDb, TemplateStatus};
+pub use template_db::{
+    DbTemplate,
+    DbTemplateType,
+    DbTemplateUpdate,
+    DbTemplateUpload,
+    DbTemplateUploadChunk,
+    TemplateDb,
+    TemplateStatus,
+};
 
 mod validator_node_db;
 pub use validator_node_db::ValidatorNodeDb;
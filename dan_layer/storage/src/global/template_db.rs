@@ -60,6 +60,40 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> TemplateDb<'a, 'tx, TGlobalDbAd
     pub fn template_exists(&mut self, key: &[u8]) -> Result<bool, TGlobalDbAdapter::Error> {
         self.backend.template_exists(self.tx, key)
     }
+
+    pub fn insert_upload(&mut self, upload: DbTemplateUpload) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend.insert_template_upload(self.tx, upload)
+    }
+
+    pub fn get_upload(&mut self, upload_id: &FixedHash) -> Result<Option<DbTemplateUpload>, TGlobalDbAdapter::Error> {
+        self.backend.get_template_upload(self.tx, upload_id)
+    }
+
+    pub fn insert_upload_chunk(
+        &mut self,
+        upload_id: &FixedHash,
+        chunk: DbTemplateUploadChunk,
+    ) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend.insert_template_upload_chunk(self.tx, upload_id, chunk)
+    }
+
+    /// Returns the chunks of the upload ordered by offset
+    pub fn get_upload_chunks(
+        &mut self,
+        upload_id: &FixedHash,
+    ) -> Result<Vec<DbTemplateUploadChunk>, TGlobalDbAdapter::Error> {
+        self.backend.get_template_upload_chunks(self.tx, upload_id)
+    }
+
+    pub fn delete_upload(&mut self, upload_id: &FixedHash) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend.delete_template_upload(self.tx, upload_id)
+    }
+
+    /// Deletes all uploads (and their chunks) that were started before the given time, returning the number of uploads
+    /// deleted
+    pub fn delete_uploads_started_before(&mut self, time: NaiveDateTime) -> Result<usize, TGlobalDbAdapter::Error> {
+        self.backend.delete_template_uploads_started_before(self.tx, time)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +108,10 @@ pub struct DbTemplate {
     pub compiled_code: Option<Vec<u8>>,
     pub flow_json: Option<String>,
     pub manifest: Option<String>,
+    /// JSON encoded component state schema taken from the template ABI, if the template defines one
+    pub component_schema: Option<String>,
+    /// The address of the template that this template upgrades, as declared in the template ABI
+    pub previous_template_address: Option<FixedHash>,
     pub status: TemplateStatus,
     pub added_at: NaiveDateTime,
 }
@@ -83,9 +121,27 @@ pub struct DbTemplateUpdate {
     pub compiled_code: Option<Vec<u8>>,
     pub flow_json: Option<String>,
     pub manifest: Option<String>,
+    pub component_schema: Option<String>,
+    pub previous_template_address: Option<FixedHash>,
     pub status: Option<TemplateStatus>,
 }
 
+/// A template binary upload that is in progress
+#[derive(Debug, Clone)]
+pub struct DbTemplateUpload {
+    pub upload_id: FixedHash,
+    pub template_name: String,
+    pub total_size: u64,
+    pub expected_hash: FixedHash,
+    pub started_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbTemplateUploadChunk {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
 #[derive(Debug, Clone)]
 pub enum DbTemplateType {
     Wasm,
@@ -132,6 +188,8 @@ pub enum TemplateStatus {
     DownloadFailed,
     /// Template has been deprecated
     Deprecated,
+    /// Template binary was uploaded directly and is waiting for its base layer registration
+    Uploaded,
 }
 
 impl FromStr for TemplateStatus {
@@ -146,6 +204,7 @@ impl FromStr for TemplateStatus {
             "invalid" => Ok(TemplateStatus::Invalid),
             "downloadfailed" => Ok(TemplateStatus::DownloadFailed),
             "deprecated" => Ok(TemplateStatus::Deprecated),
+            "uploaded" => Ok(TemplateStatus::Uploaded),
             _ => Err(()),
         }
     }
@@ -160,6 +219,7 @@ impl TemplateStatus {
             TemplateStatus::Invalid => "Invalid",
             TemplateStatus::DownloadFailed => "DownloadFailed",
             TemplateStatus::Deprecated => "Deprecated",
+            TemplateStatus::Uploaded => "Uploaded",
         }
     }
 }
@@ -3,16 +3,17 @@
 
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{BTreeSet, HashSet},
     ops::{Deref, RangeInclusive},
 };
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_common_types::{Epoch, NodeAddressable, NodeHeight, SubstateAddress};
+use tari_dan_common_types::{shard::Shard, Epoch, NodeAddressable, NodeHeight, SubstateAddress};
 use tari_engine_types::substate::SubstateId;
 use tari_state_tree::{TreeStore, TreeStoreReader, Version};
+use tari_template_lib::models::{ComponentAddress, NonFungibleId, ResourceAddress, VaultId};
 use tari_transaction::{SubstateRequirement, TransactionId, VersionedSubstateId};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
@@ -20,11 +21,18 @@ use ts_rs::TS;
 use crate::{
     consensus_models::{
         Block,
+        BlockCursor,
         BlockDiff,
         BlockId,
+        BlockRejection,
+        CommittedBlockDiff,
         Decision,
+        EpochCheckpoint,
+        EquivocationEvidence,
         Evidence,
         ForeignProposal,
+        ForeignProposalOutboxEntry,
+        ForeignProposalState,
         ForeignReceiveCounters,
         ForeignSendCounters,
         HighQc,
@@ -35,12 +43,26 @@ use crate::{
         LeafBlock,
         LockedBlock,
         LockedSubstate,
+        MissingTransactionsRequest,
+        NewViewRecord,
+        NftOwnership,
+        PeerMisbehavior,
         PendingStateTreeDiff,
+        ProposerEquivocation,
+        PruneSafetyInfo,
         QcId,
+        QcTiming,
         QuorumCertificate,
+        RecentTransaction,
+        RecentTransactionFilter,
+        ShardGrowthSample,
         SubstateRecord,
+        SyncCheckpoint,
         TransactionAtom,
+        TransactionConflictEdge,
+        TransactionCursor,
         TransactionExecution,
+        TransactionPoolOrdering,
         TransactionPoolRecord,
         TransactionPoolStage,
         TransactionPoolStatusUpdate,
@@ -98,6 +120,7 @@ pub trait StateStoreReadTransaction: Sized {
     fn locked_block_get(&self) -> Result<LockedBlock, StorageError>;
     fn leaf_block_get(&self) -> Result<LeafBlock, StorageError>;
     fn high_qc_get(&self) -> Result<HighQc, StorageError>;
+    fn sync_checkpoint_get(&self) -> Result<SyncCheckpoint, StorageError>;
     fn foreign_proposal_exists(&self, foreign_proposal: &ForeignProposal) -> Result<bool, StorageError>;
     fn foreign_proposal_get_all_new(&self) -> Result<Vec<ForeignProposal>, StorageError>;
     fn foreign_proposal_get_all_pending(
@@ -106,6 +129,17 @@ pub trait StateStoreReadTransaction: Sized {
         to_block_id: &BlockId,
     ) -> Result<Vec<ForeignProposal>, StorageError>;
     fn foreign_proposal_get_all_proposed(&self, to_height: NodeHeight) -> Result<Vec<ForeignProposal>, StorageError>;
+    /// Returns up to `limit` proposals in the given state, oldest first
+    fn foreign_proposal_get_all_by_state(
+        &self,
+        state: ForeignProposalState,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<ForeignProposal>, StorageError>;
+    fn foreign_proposal_outbox_get_all(&self) -> Result<Vec<ForeignProposalOutboxEntry>, StorageError>;
+    fn foreign_proposal_outbox_count(&self) -> Result<u64, StorageError>;
+    fn applied_foreign_proposals_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
+    fn applied_foreign_proposals_count(&self, bucket: Shard) -> Result<u64, StorageError>;
     fn foreign_send_counters_get(&self, block_id: &BlockId) -> Result<ForeignSendCounters, StorageError>;
     fn foreign_receive_counters_get(&self) -> Result<ForeignReceiveCounters, StorageError>;
     fn transactions_get(&self, tx_id: &TransactionId) -> Result<TransactionRecord, StorageError>;
@@ -121,6 +155,23 @@ pub trait StateStoreReadTransaction: Sized {
         offset: u64,
         asc_desc_created_at: Option<Ordering>,
     ) -> Result<Vec<TransactionRecord>, StorageError>;
+    /// Returns up to `limit` transactions after the cursor in the given order, and the cursor of the last transaction
+    /// returned. If no transactions are returned, the given cursor is returned.
+    fn transactions_get_after(
+        &self,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+        ordering: Ordering,
+    ) -> Result<(Vec<TransactionRecord>, Option<TransactionCursor>), StorageError>;
+    /// Returns up to `limit` transactions that match the filter, newest first, joined with their latest execution.
+    /// Only transactions received before the cursor are returned, so that transactions inserted between page fetches
+    /// do not cause duplicates. The cursor of the last transaction returned is returned with the page.
+    fn transactions_get_recent_filtered(
+        &self,
+        filter: &RecentTransactionFilter,
+        cursor: Option<&TransactionCursor>,
+        limit: u64,
+    ) -> Result<(Vec<RecentTransaction>, Option<TransactionCursor>), StorageError>;
 
     fn transaction_executions_get(
         &self,
@@ -145,6 +196,16 @@ pub trait StateStoreReadTransaction: Sized {
     fn blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
     fn blocks_is_ancestor(&self, descendant: &BlockId, ancestor: &BlockId) -> Result<bool, StorageError>;
     fn blocks_get_all_by_parent(&self, parent: &BlockId) -> Result<Vec<Block>, StorageError>;
+    /// Returns a child of the given block that is justified by a QC for the given block and that has itself been
+    /// certified by a QC. Returns a NotFound error if no such child exists.
+    fn blocks_get_certified_child(&self, parent: &BlockId) -> Result<Block, StorageError>;
+    /// Returns all non-dummy blocks proposed by the validator for the given height and epoch
+    fn blocks_get_all_by_proposer_at_height(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<Vec<Block>, StorageError>;
     fn blocks_get_parent_chain(&self, block_id: &BlockId, limit: usize) -> Result<Vec<Block>, StorageError>;
     fn blocks_get_pending_transactions(&self, block_id: &BlockId) -> Result<Vec<TransactionId>, StorageError>;
     fn blocks_get_total_leader_fee_for_epoch(
@@ -166,7 +227,17 @@ pub trait StateStoreReadTransaction: Sized {
         ordering_index: Option<usize>,
         ordering: Option<Ordering>,
     ) -> Result<Vec<Block>, StorageError>;
+    /// Returns up to `limit` blocks after the cursor in the given order, and the cursor of the last block returned. If
+    /// no blocks are returned, the given cursor is returned.
+    fn blocks_get_after(
+        &self,
+        cursor: Option<&BlockCursor>,
+        limit: u64,
+        ordering: Ordering,
+    ) -> Result<(Vec<Block>, Option<BlockCursor>), StorageError>;
     fn blocks_get_count(&self) -> Result<i64, StorageError>;
+    /// Returns the number of dummy blocks, optionally only counting the dummy blocks in the given epoch
+    fn blocks_count_dummy(&self, epoch: Option<Epoch>) -> Result<u64, StorageError>;
 
     fn filtered_blocks_get_count(
         &self,
@@ -176,8 +247,19 @@ pub trait StateStoreReadTransaction: Sized {
     fn blocks_max_height(&self) -> Result<NodeHeight, StorageError>;
 
     fn block_diffs_get(&self, block_id: &BlockId) -> Result<BlockDiff, StorageError>;
+    fn committed_block_diffs_get(&self, sequence: u64) -> Result<CommittedBlockDiff, StorageError>;
+    fn committed_block_diffs_get_after(
+        &self,
+        after_sequence: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<CommittedBlockDiff>, StorageError>;
 
     fn parked_blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
+    fn missing_transactions_get_due_requests(
+        &self,
+        min_height: NodeHeight,
+        requested_before: u64,
+    ) -> Result<Vec<MissingTransactionsRequest>, StorageError>;
 
     // -------------------------------- QuorumCertificate -------------------------------- //
     fn quorum_certificates_get(&self, qc_id: &QcId) -> Result<QuorumCertificate, StorageError>;
@@ -197,13 +279,36 @@ pub trait StateStoreReadTransaction: Sized {
     ) -> Result<TransactionPoolRecord, StorageError>;
     fn transaction_pool_exists(&self, transaction_id: &TransactionId) -> Result<bool, StorageError>;
     fn transaction_pool_get_all(&self) -> Result<Vec<TransactionPoolRecord>, StorageError>;
-    fn transaction_pool_get_many_ready(&self, max_txs: usize) -> Result<Vec<TransactionPoolRecord>, StorageError>;
+    /// Returns up to `max_txs` ready transactions whose min/max epoch bounds include the given epoch. If
+    /// `exclude_conflicting` is true, transactions that lock a substate that is locked by a previously selected
+    /// transaction are skipped.
+    fn transaction_pool_get_many_ready(
+        &self,
+        max_txs: usize,
+        ordering: TransactionPoolOrdering,
+        exclude_conflicting: bool,
+        epoch: Epoch,
+    ) -> Result<Vec<TransactionPoolRecord>, StorageError>;
+    /// Returns up to `max_txs` ready transactions in the New stage whose max epoch is before the given epoch.
+    fn transaction_pool_get_many_expired(
+        &self,
+        max_txs: usize,
+        epoch: Epoch,
+    ) -> Result<Vec<TransactionPoolRecord>, StorageError>;
     fn transaction_pool_count(
         &self,
         stage: Option<TransactionPoolStage>,
         is_ready: Option<bool>,
         has_foreign_data: Option<bool>,
     ) -> Result<usize, StorageError>;
+    /// Returns up to `limit` conflict edges between transactions in the pool, derived from the substate locks held by
+    /// pooled transactions. Edges are ordered by the order in which the locks were acquired. If `substate_id` is
+    /// given, only edges over that substate are returned.
+    fn transaction_pool_get_conflict_edges(
+        &self,
+        substate_id: Option<&SubstateId>,
+        limit: usize,
+    ) -> Result<Vec<TransactionConflictEdge>, StorageError>;
 
     fn transactions_fetch_involved_shards(
         &self,
@@ -218,6 +323,40 @@ pub trait StateStoreReadTransaction: Sized {
     ) -> Result<Vote, StorageError>;
     fn votes_count_for_block(&self, block_id: &BlockId) -> Result<u64, StorageError>;
     fn votes_get_for_block(&self, block_id: &BlockId) -> Result<Vec<Vote>, StorageError>;
+    /// Returns the votes sent by the sender for any block at the given height and epoch
+    fn votes_get_by_sender_at_height(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<Vec<Vote>, StorageError>;
+    fn qc_timings_get_by_epoch(&self, epoch: Epoch) -> Result<Vec<QcTiming>, StorageError>;
+    fn proposer_equivocations_get(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: &PublicKey,
+    ) -> Result<ProposerEquivocation, StorageError>;
+    fn proposer_equivocations_count(&self) -> Result<u64, StorageError>;
+    fn equivocation_evidence_get(
+        &self,
+        epoch: Epoch,
+        height: NodeHeight,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<EquivocationEvidence, StorageError>;
+    /// Returns all recorded vote equivocation evidence, most recent first
+    fn equivocation_evidence_get_all(&self) -> Result<Vec<EquivocationEvidence>, StorageError>;
+    /// Returns the recorded block rejections, most recent first
+    fn block_rejections_get_paginated(&self, limit: u64, offset: u64) -> Result<Vec<BlockRejection>, StorageError>;
+    fn block_rejections_count(&self) -> Result<u64, StorageError>;
+    // -------------------------------- NewViews -------------------------------- //
+    fn new_views_count(&self, epoch: Epoch, new_height: NodeHeight) -> Result<u64, StorageError>;
+    // -------------------------------- EpochCheckpoints -------------------------------- //
+    fn epoch_checkpoint_get(&self, epoch: Epoch) -> Result<EpochCheckpoint, StorageError>;
+    // -------------------------------- PeerMisbehaviors -------------------------------- //
+    fn peer_misbehaviors_get(&self, address: &Self::Addr) -> Result<PeerMisbehavior<Self::Addr>, StorageError>;
+    /// Returns the misbehavior records of all peers, highest score first
+    fn peer_misbehaviors_get_all(&self) -> Result<Vec<PeerMisbehavior<Self::Addr>>, StorageError>;
     //---------------------------------- Substates --------------------------------------------//
     fn substates_get(&self, substate_id: &SubstateAddress) -> Result<SubstateRecord, StorageError>;
     fn substates_get_any(
@@ -255,6 +394,32 @@ pub trait StateStoreReadTransaction: Sized {
         &self,
         transaction_id: &TransactionId,
     ) -> Result<Vec<SubstateRecord>, StorageError>;
+    /// Returns the number of substates in the inclusive address range that have not been destroyed
+    fn substates_count_in_range(&self, start: &SubstateAddress, end: &SubstateAddress) -> Result<u64, StorageError>;
+    /// Returns the total stored size in bytes of the substate values in the inclusive address range that have not been
+    /// destroyed
+    fn substates_size_in_range(&self, start: &SubstateAddress, end: &SubstateAddress) -> Result<u64, StorageError>;
+    /// Returns the `limit` most recent growth samples of the shard, oldest first
+    fn shard_growth_samples_get_recent(
+        &self,
+        shard: Shard,
+        limit: usize,
+    ) -> Result<Vec<ShardGrowthSample>, StorageError>;
+
+    // -------------------------------- NFT ownership -------------------------------- //
+    fn nft_ownership_get(
+        &self,
+        resource_address: &ResourceAddress,
+        nft_id: &NonFungibleId,
+    ) -> Result<NftOwnership, StorageError>;
+    /// Returns the NFTs held in vaults owned by the component, ordered by resource address and token id
+    fn nft_ownership_get_by_owner(
+        &self,
+        component_address: &ComponentAddress,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<NftOwnership>, StorageError>;
+    fn nft_ownership_get_ids_in_vault(&self, vault_id: &VaultId) -> Result<BTreeSet<NonFungibleId>, StorageError>;
 
     fn substate_locks_get_all_for_block(
         &self,
@@ -268,6 +433,10 @@ pub trait StateStoreReadTransaction: Sized {
         &self,
         block_id: &BlockId,
     ) -> Result<Vec<PendingStateTreeDiff>, StorageError>;
+
+    // -------------------------------- Pruning -------------------------------- //
+    /// Returns the floors that pruning must not go past. All values are read within this transaction.
+    fn get_prune_safety_info(&self) -> Result<PruneSafetyInfo, StorageError>;
 }
 
 pub trait StateStoreWriteTransaction {
@@ -278,6 +447,8 @@ pub trait StateStoreWriteTransaction {
 
     // -------------------------------- Block -------------------------------- //
     fn blocks_insert(&mut self, block: &Block) -> Result<(), StorageError>;
+    /// Inserts the blocks in as few statements as possible. Blocks that already exist are ignored.
+    fn blocks_insert_many(&mut self, blocks: &[Block]) -> Result<(), StorageError>;
     fn blocks_set_flags(
         &mut self,
         block_id: &BlockId,
@@ -288,6 +459,9 @@ pub trait StateStoreWriteTransaction {
     // -------------------------------- BlockDiff -------------------------------- //
     fn block_diffs_insert(&mut self, block_diff: &BlockDiff) -> Result<(), StorageError>;
     fn block_diffs_remove(&mut self, block_id: &BlockId) -> Result<(), StorageError>;
+    fn committed_block_diffs_insert(&mut self, block: &Block, block_diff: &BlockDiff) -> Result<(), StorageError>;
+    /// Removes the oldest committed block diffs so that at most `retain` diffs are kept
+    fn committed_block_diffs_prune(&mut self, retain: u64) -> Result<(), StorageError>;
 
     // -------------------------------- QuorumCertificate -------------------------------- //
     fn quorum_certificates_insert(&mut self, qc: &QuorumCertificate) -> Result<(), StorageError>;
@@ -302,8 +476,29 @@ pub trait StateStoreWriteTransaction {
     fn leaf_block_set(&mut self, leaf_node: &LeafBlock) -> Result<(), StorageError>;
     fn locked_block_set(&mut self, locked_block: &LockedBlock) -> Result<(), StorageError>;
     fn high_qc_set(&mut self, high_qc: &HighQc) -> Result<(), StorageError>;
+    fn sync_checkpoint_set(&mut self, checkpoint: &SyncCheckpoint) -> Result<(), StorageError>;
     fn foreign_proposal_upsert(&mut self, foreign_proposal: &ForeignProposal) -> Result<(), StorageError>;
     fn foreign_proposal_delete(&mut self, foreign_proposal: &ForeignProposal) -> Result<(), StorageError>;
+    /// Sets the state of an existing proposal and records the block height and epoch at which it entered that state
+    fn foreign_proposal_set_terminal_state(
+        &mut self,
+        foreign_proposal: &ForeignProposal,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<(), StorageError>;
+    /// Inserts a proposal in a terminal state. Returns false and does nothing if the proposal already exists.
+    fn foreign_proposal_insert_terminal(
+        &mut self,
+        foreign_proposal: &ForeignProposal,
+        height: NodeHeight,
+        epoch: Epoch,
+    ) -> Result<bool, StorageError>;
+    fn foreign_proposal_delete_terminal_before(&mut self, epoch: Epoch) -> Result<usize, StorageError>;
+    fn foreign_proposal_outbox_insert(&mut self, entry: &ForeignProposalOutboxEntry) -> Result<(), StorageError>;
+    fn foreign_proposal_outbox_update(&mut self, entry: &ForeignProposalOutboxEntry) -> Result<(), StorageError>;
+    fn foreign_proposal_outbox_delete(&mut self, bucket: Shard, block_id: &BlockId) -> Result<bool, StorageError>;
+    fn foreign_proposal_outbox_delete_created_before(&mut self, created_before: u64) -> Result<usize, StorageError>;
+    fn applied_foreign_proposals_insert(&mut self, bucket: Shard, block_id: &BlockId) -> Result<bool, StorageError>;
     fn foreign_send_counters_set(
         &mut self,
         foreign_send_counter: &ForeignSendCounters,
@@ -339,6 +534,7 @@ pub trait StateStoreWriteTransaction {
         transaction: TransactionAtom,
         stage: TransactionPoolStage,
         is_ready: bool,
+        first_seen_height: NodeHeight,
     ) -> Result<(), StorageError>;
     fn transaction_pool_set_atom(&mut self, transaction: TransactionAtom) -> Result<(), StorageError>;
     fn transaction_pool_add_pending_update(
@@ -353,6 +549,10 @@ pub trait StateStoreWriteTransaction {
         remote_decision: Option<Decision>,
         remote_evidence: Option<&Evidence>,
     ) -> Result<(), StorageError>;
+    fn transaction_pool_increment_deferred_execution_attempts(
+        &mut self,
+        transaction_id: &TransactionId,
+    ) -> Result<(), StorageError>;
     fn transaction_pool_remove(&mut self, transaction_id: &TransactionId) -> Result<(), StorageError>;
     fn transaction_pool_remove_all<'a, I: IntoIterator<Item = &'a TransactionId>>(
         &mut self,
@@ -381,9 +581,38 @@ pub trait StateStoreWriteTransaction {
         current_height: NodeHeight,
         transaction_id: &TransactionId,
     ) -> Result<Option<Block>, StorageError>;
+    fn parked_blocks_set_transactions_requested(
+        &mut self,
+        block_id: &BlockId,
+        requested_at: u64,
+    ) -> Result<(), StorageError>;
 
     // -------------------------------- Votes -------------------------------- //
     fn votes_insert(&mut self, vote: &Vote) -> Result<(), StorageError>;
+    fn qc_timings_upsert(&mut self, timing: &QcTiming) -> Result<(), StorageError>;
+    fn proposer_equivocations_insert(&mut self, equivocation: &ProposerEquivocation) -> Result<(), StorageError>;
+    fn equivocation_evidence_insert(&mut self, evidence: &EquivocationEvidence) -> Result<(), StorageError>;
+    fn block_rejections_insert(&mut self, rejection: &BlockRejection) -> Result<(), StorageError>;
+    /// Removes all block rejections for blocks proposed before the given epoch
+    fn block_rejections_prune_before(&mut self, epoch: Epoch) -> Result<(), StorageError>;
+
+    // -------------------------------- NewViews -------------------------------- //
+    /// Inserts the NEWVIEW unless the sender has already sent one for the view. Returns true if it was inserted.
+    fn new_views_insert(&mut self, new_view: &NewViewRecord<Self::Addr>) -> Result<bool, StorageError>;
+    /// Removes all NEWVIEWs for views before the given epoch and height
+    fn new_views_prune_before(&mut self, epoch: Epoch, new_height: NodeHeight) -> Result<usize, StorageError>;
+
+    // -------------------------------- EpochCheckpoints -------------------------------- //
+    fn epoch_checkpoints_insert(
+        &mut self,
+        block_id: &BlockId,
+        checkpoint: &EpochCheckpoint,
+    ) -> Result<(), StorageError>;
+
+    // -------------------------------- PeerMisbehaviors -------------------------------- //
+    fn peer_misbehaviors_upsert(&mut self, misbehavior: &PeerMisbehavior<Self::Addr>) -> Result<(), StorageError>;
+    /// Removes the record of the peer. Returns true if a record was removed.
+    fn peer_misbehaviors_remove(&mut self, address: &Self::Addr) -> Result<bool, StorageError>;
 
     //---------------------------------- Substates --------------------------------------------//
     fn substate_locks_insert_all<I: IntoIterator<Item = (SubstateId, Vec<LockedSubstate>)>>(
@@ -397,6 +626,11 @@ pub trait StateStoreWriteTransaction {
         transaction_ids: I,
     ) -> Result<(), StorageError>;
 
+    fn substate_locks_remove_many_for_blocks<'a, I: IntoIterator<Item = &'a BlockId>>(
+        &mut self,
+        block_ids: I,
+    ) -> Result<(), StorageError>;
+
     fn substate_down_many<I: IntoIterator<Item = SubstateAddress>>(
         &mut self,
         substate_addresses: I,
@@ -406,6 +640,30 @@ pub trait StateStoreWriteTransaction {
         destroyed_qc_id: &QcId,
     ) -> Result<(), StorageError>;
     fn substates_create(&mut self, substate: SubstateRecord) -> Result<(), StorageError>;
+    fn shard_growth_samples_insert(&mut self, sample: &ShardGrowthSample) -> Result<(), StorageError>;
+    /// Removes all but the `keep` most recent growth samples of the shard
+    fn shard_growth_samples_prune(&mut self, shard: Shard, keep: usize) -> Result<(), StorageError>;
+
+    // -------------------------------- NFT ownership -------------------------------- //
+    /// Records the component as the owner of the vaults
+    fn vault_owners_set(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_ids: &[VaultId],
+    ) -> Result<(), StorageError>;
+    /// Indexes the tokens against the vault, replacing the vault that they were previously indexed against
+    fn nft_ownership_set_vault(
+        &mut self,
+        vault_id: &VaultId,
+        resource_address: &ResourceAddress,
+        nft_ids: &[NonFungibleId],
+    ) -> Result<(), StorageError>;
+    /// Removes the tokens that are indexed against the vault. Tokens indexed against another vault are not removed.
+    fn nft_ownership_remove_from_vault(
+        &mut self,
+        vault_id: &VaultId,
+        nft_ids: &[NonFungibleId],
+    ) -> Result<(), StorageError>;
 
     // -------------------------------- Pending State Tree Diffs -------------------------------- //
     fn pending_state_tree_diffs_insert(&mut self, diff: &PendingStateTreeDiff) -> Result<(), StorageError>;
@@ -413,6 +671,31 @@ pub trait StateStoreWriteTransaction {
         &mut self,
         block_id: &BlockId,
     ) -> Result<PendingStateTreeDiff, StorageError>;
+
+    // -------------------------------- Pruning -------------------------------- //
+    /// Removes all blocks below the given height along with the diffs, locks, pending pool updates and bookkeeping
+    /// records that reference them. Returns the number of blocks removed.
+    fn blocks_prune_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError>;
+    /// Removes all substates that were destroyed in blocks below the given height. Returns the number of substates
+    /// removed.
+    fn substates_prune_destroyed_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError>;
+    /// Removes all votes for epochs before the given epoch. Returns the number of votes removed.
+    fn votes_prune_before(&mut self, safety_info: &PruneSafetyInfo, before_epoch: Epoch) -> Result<usize, StorageError>;
+    /// Removes the foreign send counters for blocks below the given height and all but the latest foreign receive
+    /// counters. Returns the number of counter records removed.
+    fn foreign_counters_prune_before(
+        &mut self,
+        safety_info: &PruneSafetyInfo,
+        before_height: NodeHeight,
+    ) -> Result<usize, StorageError>;
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
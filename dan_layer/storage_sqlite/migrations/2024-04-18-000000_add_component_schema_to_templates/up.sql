@@ -0,0 +1,6 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- JSON encoded component state schema extracted from the template ABI
+alter table templates
+    add column component_schema text null;
@@ -0,0 +1,5 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+drop table template_upload_chunks;
+drop table template_uploads;
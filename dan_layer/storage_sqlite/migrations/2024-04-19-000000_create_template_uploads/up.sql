@@ -0,0 +1,26 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- Template binaries that are being uploaded in chunks. Uploads are deleted once finished or expired.
+create table template_uploads
+(
+    id            integer primary key autoincrement not null,
+    upload_id     blob                              not null,
+    template_name text                              not null,
+    total_size    bigint                            not null,
+    expected_hash blob                              not null,
+    started_at    timestamp                         not null default current_timestamp
+);
+
+create unique index template_uploads_upload_id_index on template_uploads (upload_id);
+
+create table template_upload_chunks
+(
+    id           integer primary key autoincrement not null,
+    upload_id    blob                              not null,
+    chunk_offset bigint                            not null,
+    data         blob                              not null,
+    foreign key (upload_id) references template_uploads (upload_id) on delete cascade
+);
+
+create index template_upload_chunks_upload_id_index on template_upload_chunks (upload_id);
@@ -0,0 +1,5 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+alter table templates
+    drop column previous_template_address;
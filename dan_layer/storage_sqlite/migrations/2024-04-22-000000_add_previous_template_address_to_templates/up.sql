@@ -0,0 +1,6 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- The address of the template that this template upgrades, taken from the template ABI
+alter table templates
+    add column previous_template_address blob null;
@@ -69,6 +69,24 @@ pub enum SqliteStorageError {
         item: &'static str,
         details: String,
     },
+    #[error("Database connection mutex poisoned: {details}")]
+    ConnectionPoisoned { details: String },
+}
+
+impl SqliteStorageError {
+    /// Returns true if the query failed because another connection holds a lock on the database
+    pub fn is_database_busy(&self) -> bool {
+        matches!(self, SqliteStorageError::DieselError { source, .. } if is_database_busy(source))
+    }
+}
+
+/// Returns true if the diesel error was caused by another connection holding a lock on the SQLite database
+pub fn is_database_busy(err: &diesel::result::Error) -> bool {
+    matches!(
+        err,
+        diesel::result::Error::DatabaseError(_, info)
+            if matches!(info.message(), "database is locked" | "database table is locked")
+    )
 }
 
 impl From<SqliteStorageError> for StorageError {
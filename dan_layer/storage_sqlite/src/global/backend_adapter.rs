@@ -25,7 +25,8 @@ use std::{
     fmt::{Debug, Formatter},
     marker::PhantomData,
     ops::RangeInclusive,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, TryLockError},
+    time::{Duration, Instant},
 };
 
 use diesel::{
@@ -38,6 +39,7 @@ use diesel::{
     RunQueryDsl,
     SqliteConnection,
 };
+use chrono::NaiveDateTime;
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
 use serde::{de::DeserializeOwned, Serialize};
 use tari_common_types::types::{FixedHash, PublicKey};
@@ -56,6 +58,8 @@ use tari_dan_storage::{
         DbEpoch,
         DbTemplate,
         DbTemplateUpdate,
+        DbTemplateUpload,
+        DbTemplateUploadChunk,
         GlobalDbAdapter,
         MetadataKey,
         TemplateStatus,
@@ -73,8 +77,11 @@ use crate::{
             NewBaseLayerBlockInfo,
             NewEpoch,
             NewTemplateModel,
+            NewTemplateUploadModel,
             TemplateModel,
             TemplateUpdateModel,
+            TemplateUploadChunkModel,
+            TemplateUploadModel,
         },
         schema::templates,
         serialization::serialize_json,
@@ -114,6 +121,203 @@ impl<TAddr> SqliteGlobalDbAdapter<TAddr> {
         Ok(result > 0)
     }
 
+    fn insert_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload: DbTemplateUpload,
+    ) -> Result<(), Self::Error> {
+        use crate::global::schema::template_uploads;
+
+        let new_upload = NewTemplateUploadModel {
+            upload_id: upload.upload_id.to_vec(),
+            template_name: upload.template_name,
+            total_size: upload.total_size as i64,
+            expected_hash: upload.expected_hash.to_vec(),
+            started_at: upload.started_at,
+        };
+        diesel::insert_into(template_uploads::table)
+            .values(new_upload)
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "insert_template_upload".to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    fn get_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<Option<DbTemplateUpload>, Self::Error> {
+        use crate::global::schema::template_uploads;
+
+        let upload = template_uploads::table
+            .filter(template_uploads::upload_id.eq(upload_id.as_slice()))
+            .first::<TemplateUploadModel>(tx.connection())
+            .optional()
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "get_template_upload".to_string(),
+            })?;
+
+        match upload {
+            Some(u) => Ok(Some(DbTemplateUpload {
+                upload_id: u.upload_id.try_into()?,
+                template_name: u.template_name,
+                total_size: u.total_size as u64,
+                expected_hash: u.expected_hash.try_into()?,
+                started_at: u.started_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn insert_template_upload_chunk(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+        chunk: DbTemplateUploadChunk,
+    ) -> Result<(), Self::Error> {
+        use crate::global::schema::template_upload_chunks;
+
+        diesel::insert_into(template_upload_chunks::table)
+            .values((
+                template_upload_chunks::upload_id.eq(upload_id.as_slice()),
+                template_upload_chunks::chunk_offset.eq(chunk.offset as i64),
+                template_upload_chunks::data.eq(chunk.data),
+            ))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "insert_template_upload_chunk".to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    fn get_template_upload_chunks(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<Vec<DbTemplateUploadChunk>, Self::Error> {
+        use crate::global::schema::template_upload_chunks;
+
+        let chunks = template_upload_chunks::table
+            .filter(template_upload_chunks::upload_id.eq(upload_id.as_slice()))
+            .order_by(template_upload_chunks::chunk_offset.asc())
+            .get_results::<TemplateUploadChunkModel>(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "get_template_upload_chunks".to_string(),
+            })?;
+
+        Ok(chunks
+            .into_iter()
+            .map(|c| DbTemplateUploadChunk {
+                offset: c.chunk_offset as u64,
+                data: c.data,
+            })
+            .collect())
+    }
+
+    fn delete_template_upload(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        upload_id: &FixedHash,
+    ) -> Result<(), Self::Error> {
+        use crate::global::schema::{template_upload_chunks, template_uploads};
+
+        diesel::delete(template_upload_chunks::table)
+            .filter(template_upload_chunks::upload_id.eq(upload_id.as_slice()))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_template_upload".to_string(),
+            })?;
+        diesel::delete(template_uploads::table)
+            .filter(template_uploads::upload_id.eq(upload_id.as_slice()))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_template_upload".to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    fn delete_template_uploads_started_before(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        time: NaiveDateTime,
+    ) -> Result<usize, Self::Error> {
+        use crate::global::schema::{template_upload_chunks, template_uploads};
+
+        let expired = template_uploads::table
+            .select(template_uploads::upload_id)
+            .filter(template_uploads::started_at.lt(time))
+            .get_results::<Vec<u8>>(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_template_uploads_started_before".to_string(),
+            })?;
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(template_upload_chunks::table)
+            .filter(template_upload_chunks::upload_id.eq_any(&expired))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_template_uploads_started_before".to_string(),
+            })?;
+        let num_deleted = diesel::delete(template_uploads::table)
+            .filter(template_uploads::upload_id.eq_any(&expired))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_template_uploads_started_before".to_string(),
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    /// Runs `PRAGMA optimize`, `ANALYZE` and, if `incremental_vacuum` is true, an incremental vacuum on the database.
+    /// Maintenance is skipped and `None` is returned if a transaction is currently active or the database is locked by
+    /// another connection, otherwise the time taken is returned.
+    pub fn maintenance(&self, incremental_vacuum: bool) -> Result<Option<Duration>, SqliteStorageError> {
+        let mut connection = match self.connection.try_lock() {
+            Ok(connection) => connection,
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Poisoned(err)) => {
+                return Err(SqliteStorageError::ConnectionPoisoned {
+                    details: err.to_string(),
+                })
+            },
+        };
+
+        let timer = Instant::now();
+        let mut pragmas = vec!["PRAGMA optimize;", "ANALYZE;"];
+        if incremental_vacuum {
+            pragmas.push("PRAGMA incremental_vacuum;");
+        }
+        for pragma in pragmas {
+            if let Err(source) = sql_query(pragma).execute(&mut *connection) {
+                let err = SqliteStorageError::DieselError {
+                    source,
+                    operation: format!("maintenance: {}", pragma),
+                };
+                if err.is_database_busy() {
+                    return Ok(None);
+                }
+                return Err(err);
+            }
+        }
+        Ok(Some(timer.elapsed()))
+    }
+
     pub fn migrate(&self) -> Result<(), SqliteStorageError> {
         const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
         self.connection
@@ -215,6 +419,8 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                 compiled_code: t.compiled_code,
                 flow_json: t.flow_json,
                 manifest: t.manifest,
+                component_schema: t.component_schema,
+                previous_template_address: t.previous_template_address.map(TryInto::try_into).transpose()?,
                 status: t.status.parse().expect("DB status corrupted"),
                 added_at: t.added_at,
             })),
@@ -252,6 +458,8 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                     compiled_code: t.compiled_code,
                     flow_json: t.flow_json,
                     manifest: t.manifest,
+                    component_schema: t.component_schema,
+                    previous_template_address: t.previous_template_address.map(TryInto::try_into).transpose()?,
                     status: t.status.parse().expect("DB status corrupted"),
                     added_at: t.added_at,
                 })
@@ -287,6 +495,8 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                     compiled_code: t.compiled_code,
                     flow_json: t.flow_json,
                     manifest: t.manifest,
+                    component_schema: t.component_schema,
+                    previous_template_address: t.previous_template_address.map(TryInto::try_into).transpose()?,
                     status: t.status.parse().expect("DB status corrupted"),
                     added_at: t.added_at,
                 })
@@ -307,6 +517,8 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
             status: item.status.as_str().to_string(),
             wasm_path: None,
             manifest: None,
+            component_schema: item.component_schema,
+            previous_template_address: item.previous_template_address.map(|a| a.to_vec()),
         };
         diesel::insert_into(templates::table)
             .values(new_template)
@@ -329,6 +541,8 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
             compiled_code: template.compiled_code,
             flow_json: template.flow_json,
             manifest: template.manifest,
+            component_schema: template.component_schema,
+            previous_template_address: template.previous_template_address.map(|a| a.to_vec()),
             status: template.status.map(|s| s.as_str().to_string()),
         };
         diesel::update(templates::table)
@@ -40,6 +40,8 @@ pub struct TemplateModel {
     pub wasm_path: Option<String>,
     pub manifest: Option<String>,
     pub added_at: NaiveDateTime,
+    pub component_schema: Option<String>,
+    pub previous_template_address: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Insertable)]
@@ -56,6 +58,8 @@ pub struct NewTemplateModel {
     pub status: String,
     pub wasm_path: Option<String>,
     pub manifest: Option<String>,
+    pub component_schema: Option<String>,
+    pub previous_template_address: Option<Vec<u8>>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -64,5 +68,38 @@ pub struct TemplateUpdateModel {
     pub compiled_code: Option<Vec<u8>>,
     pub flow_json: Option<String>,
     pub manifest: Option<String>,
+    pub component_schema: Option<String>,
+    pub previous_template_address: Option<Vec<u8>>,
     pub status: Option<String>,
 }
+
+#[derive(Debug, Identifiable, Queryable)]
+#[diesel(table_name = template_uploads)]
+pub struct TemplateUploadModel {
+    pub id: i32,
+    pub upload_id: Vec<u8>,
+    pub template_name: String,
+    pub total_size: i64,
+    pub expected_hash: Vec<u8>,
+    pub started_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = template_uploads)]
+pub struct NewTemplateUploadModel {
+    pub upload_id: Vec<u8>,
+    pub template_name: String,
+    pub total_size: i64,
+    pub expected_hash: Vec<u8>,
+    pub started_at: NaiveDateTime,
+}
+
+#[derive(Debug, Identifiable, Queryable)]
+#[diesel(table_name = template_upload_chunks)]
+pub struct TemplateUploadChunkModel {
+    pub id: i32,
+    pub upload_id: Vec<u8>,
+    pub chunk_offset: i64,
+    pub data: Vec<u8>,
+}
+
@@ -37,6 +37,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    template_upload_chunks (id) {
+        id -> Integer,
+        upload_id -> Binary,
+        chunk_offset -> BigInt,
+        data -> Binary,
+    }
+}
+
+diesel::table! {
+    template_uploads (id) {
+        id -> Integer,
+        upload_id -> Binary,
+        template_name -> Text,
+        total_size -> BigInt,
+        expected_hash -> Binary,
+        started_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     templates (id) {
         id -> Integer,
@@ -52,6 +72,8 @@ diesel::table! {
         wasm_path -> Nullable<Text>,
         manifest -> Nullable<Text>,
         added_at -> Timestamp,
+        component_schema -> Nullable<Text>,
+        previous_template_address -> Nullable<Binary>,
     }
 }
 
@@ -75,6 +97,8 @@ diesel::allow_tables_to_appear_in_same_query!(
     committees,
     epochs,
     metadata,
+    template_upload_chunks,
+    template_uploads,
     templates,
     validator_nodes,
 );
@@ -56,6 +56,24 @@ impl TemplateDef {
             TemplateDef::V1(def) => &def.functions,
         }
     }
+
+    pub fn component_schema(&self) -> Option<&ComponentSchema> {
+        match self {
+            TemplateDef::V1(def) => def.component_schema.as_ref(),
+        }
+    }
+
+    pub fn is_reentrant(&self) -> bool {
+        match self {
+            TemplateDef::V1(def) => def.is_reentrant,
+        }
+    }
+
+    pub fn upgrades_from(&self) -> Option<&[u8; 32]> {
+        match self {
+            TemplateDef::V1(def) => def.upgrades_from.as_ref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +82,19 @@ pub struct TemplateDefV1 {
     pub template_name: String,
     pub tari_version: String,
     pub functions: Vec<FunctionDef>,
+    /// The layout of the component state. This is `None` for templates that were built before the schema was
+    /// included in the ABI.
+    #[serde(default)]
+    pub component_schema: Option<ComponentSchema>,
+    /// If true, a component of this template may be called again while it is already in the active call stack
+    /// (e.g. A calls B which calls back into A). The engine denies reentrant calls to all other templates.
+    #[serde(default)]
+    pub is_reentrant: bool,
+    /// The address of the previous version of this template. Components of the previous version may be migrated to
+    /// this template by their owner.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "Uint8Array | null"))]
+    pub upgrades_from: Option<[u8; 32]>,
 }
 
 impl TemplateDefV1 {
@@ -88,6 +119,42 @@ pub struct ArgDef {
     pub arg_type: Type,
 }
 
+/// Describes the fields of the component state struct and of any other structs defined in the template that the state
+/// may contain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ComponentSchema {
+    pub component: StructDef,
+    pub types: Vec<StructDef>,
+}
+
+impl ComponentSchema {
+    pub fn get_type(&self, name: &str) -> Option<&StructDef> {
+        self.types.iter().find(|t| t.name.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct StructDef {
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+}
+
+impl StructDef {
+    pub fn get_field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|f| f.name.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct FieldDef {
+    /// The name of the field, or its index for tuple structs
+    pub name: String,
+    pub field_type: Type,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub enum Type {
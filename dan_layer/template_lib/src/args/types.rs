@@ -49,7 +49,7 @@ use crate::{
         VaultRef,
     },
     prelude::{ComponentAccessRules, ConfidentialOutputStatement, TemplateAddress},
-    resource::ResourceType,
+    resource::{ResourceType, RoyaltyConfig},
     template::BuiltinTemplate,
 };
 
@@ -260,6 +260,7 @@ pub struct CreateResourceArg {
     pub mint_arg: Option<MintArg>,
     pub view_key: Option<RistrettoPublicKeyBytes>,
     pub authorize_hook: Option<AuthHook>,
+    pub royalty: Option<RoyaltyConfig>,
 }
 
 /// A resource minting operation argument
@@ -325,6 +326,7 @@ pub enum VaultAction {
     GetNonFungibleIds,
     GetCommitmentCount,
     ConfidentialReveal,
+    BurnConfidential,
     PayFee,
     CreateProofByResource,
     CreateProofByFungibleAmount,
@@ -364,6 +366,13 @@ pub struct ConfidentialRevealArg {
     pub proof: ConfidentialWithdrawProof,
 }
 
+/// A confidential resource burn operation argument. The proof must not have a confidential output, the revealed output
+/// amount of the proof is the amount that is burnt.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfidentialBurnArg {
+    pub proof: ConfidentialWithdrawProof,
+}
+
 // -------------------------------- Fees -------------------------------- //
 
 /// A fee payment operation argument
@@ -425,6 +434,7 @@ pub enum BucketAction {
     TakeConfidential,
     RevealConfidential,
     Burn,
+    BurnConfidential,
     CreateProof,
     GetNonFungibleIds,
     GetNonFungibles,
@@ -514,6 +524,8 @@ pub enum CallerContextAction {
     GetCallerPublicKey,
     GetComponentAddress,
     AllocateNewComponentAddress,
+    /// Allocates a declared address in the reserved address range. Only permitted in system transactions.
+    AllocateReservedComponentAddress(ComponentAddress),
 }
 
 // -------------------------------- CallInvoke -------------------------------- //
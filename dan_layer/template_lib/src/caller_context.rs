@@ -44,4 +44,17 @@ impl CallerContext {
         resp.decode()
             .expect("Failed to decode AddressAllocation<ComponentAddress>")
     }
+
+    /// Allocates a declared component address in the reserved address range, e.g.
+    /// [`FEE_POOL_COMPONENT_ADDRESS`](crate::constants::FEE_POOL_COMPONENT_ADDRESS). This is used by system
+    /// transactions to create components at well-known addresses. The transaction fails if it is not a system
+    /// transaction or if the address is not in the reserved range.
+    pub fn allocate_reserved_component_address(address: ComponentAddress) -> AddressAllocation<ComponentAddress> {
+        let resp: InvokeResult = call_engine(EngineOp::CallerContextInvoke, &CallerContextInvokeArg {
+            action: CallerContextAction::AllocateReservedComponentAddress(address),
+        });
+
+        resp.decode()
+            .expect("Failed to decode AddressAllocation<ComponentAddress>")
+    }
 }
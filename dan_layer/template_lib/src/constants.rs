@@ -3,7 +3,7 @@
 
 //! A collection of convenient constant values
 
-use crate::models::{ObjectKey, ResourceAddress};
+use crate::models::{ComponentAddress, ObjectKey, ResourceAddress};
 
 // TODO: This is set pretty arbitrarily.
 
@@ -19,3 +19,11 @@ pub const CONFIDENTIAL_TARI_RESOURCE_ADDRESS: ResourceAddress =
 
 /// Shorthand version of the `CONFIDENTIAL_TARI_RESOURCE_ADDRESS` constant
 pub const XTR2: ResourceAddress = CONFIDENTIAL_TARI_RESOURCE_ADDRESS;
+
+/// The component that holds the network fee pool. This address is in the reserved address range (see
+/// [`EntityId::RESERVED`](crate::models::EntityId::RESERVED)) and can only be created by a system transaction.
+pub const FEE_POOL_COMPONENT_ADDRESS: ComponentAddress = ComponentAddress::new(ObjectKey::reserved(1));
+
+/// The component that holds the network configuration. This address is in the reserved address range and can only be
+/// created by a system transaction.
+pub const NETWORK_CONFIG_COMPONENT_ADDRESS: ComponentAddress = ComponentAddress::new(ObjectKey::reserved(2));
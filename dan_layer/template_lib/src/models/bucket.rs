@@ -28,7 +28,7 @@ use ts_rs::TS;
 
 use super::{NonFungible, NonFungibleId};
 use crate::{
-    args::{BucketAction, BucketInvokeArg, BucketRef, InvokeResult},
+    args::{BucketAction, BucketInvokeArg, BucketRef, ConfidentialBurnArg, InvokeResult},
     models::{Amount, BinaryTag, ConfidentialWithdrawProof, Proof, ResourceAddress},
     prelude::ResourceType,
 };
@@ -130,6 +130,20 @@ impl Bucket {
         resp.decode().expect("Bucket Burn returned invalid result")
     }
 
+    /// Destroy an amount of confidential tokens in the bucket, proving how much was destroyed. The `proof` must not
+    /// have a confidential output and its revealed output amount is the amount that is burnt. Any change is returned to
+    /// this bucket. Returns the amount that was burnt.
+    /// It will panic if the proof is invalid or the caller does not have the appropriate permissions
+    pub fn burn_confidential(&mut self, proof: ConfidentialWithdrawProof) -> Amount {
+        let resp: InvokeResult = call_engine(EngineOp::BucketInvoke, &BucketInvokeArg {
+            bucket_ref: BucketRef::Ref(self.id),
+            action: BucketAction::BurnConfidential,
+            args: invoke_args![ConfidentialBurnArg { proof }],
+        });
+
+        resp.decode().expect("Bucket BurnConfidential returned invalid amount")
+    }
+
     /// Split the current bucket, returning two new buckets, one with `amount` tokens and the other with the rest.
     /// It will panic if there are not enough tokens in the bucket
     pub fn split(mut self, amount: Amount) -> (Self, Self) {
@@ -19,6 +19,9 @@ pub struct EntityId([u8; Self::LENGTH]);
 
 impl EntityId {
     pub const LENGTH: usize = 20;
+    /// The entity id of the reserved address range. Substates in this range can only be created by system
+    /// transactions.
+    pub const RESERVED: Self = Self([0u8; Self::LENGTH]);
 
     pub const fn new(bytes: [u8; Self::LENGTH]) -> Self {
         Self(bytes)
@@ -28,6 +31,10 @@ impl EntityId {
         &self.0
     }
 
+    pub fn is_reserved(&self) -> bool {
+        *self == Self::RESERVED
+    }
+
     pub const fn from_array(bytes: [u8; Self::LENGTH]) -> Self {
         Self(bytes)
     }
@@ -155,6 +162,23 @@ impl ObjectKey {
         Self(bytes)
     }
 
+    /// Returns the object key at the given index in the reserved address range
+    pub const fn reserved(index: u64) -> Self {
+        let index = index.to_be_bytes();
+        let mut bytes = [0u8; Self::LENGTH];
+        let mut i = 0;
+        while i < ComponentKey::LENGTH {
+            bytes[EntityId::LENGTH + i] = index[i];
+            i += 1;
+        }
+        Self(bytes)
+    }
+
+    /// Returns true if the object key is in the reserved address range
+    pub fn is_reserved(&self) -> bool {
+        self.as_entity_id().is_reserved()
+    }
+
     pub fn into_array(self) -> [u8; Self::LENGTH] {
         self.0
     }
@@ -38,6 +38,7 @@ use ts_rs::TS;
 use super::{BinaryTag, EntityId, KeyParseError, NonFungible, ObjectKey, Proof, ProofAuth};
 use crate::{
     args::{
+        ConfidentialBurnArg,
         ConfidentialRevealArg,
         InvokeResult,
         PayFeeArg,
@@ -328,6 +329,20 @@ impl Vault {
         Bucket::from_id(resp.decode().expect("reveal_confidential returned invalid bucket"))
     }
 
+    /// Destroy an amount of confidential tokens in the vault, proving how much was destroyed. The `proof` must not
+    /// have a confidential output and its revealed output amount is the amount that is burnt. Any change is returned to
+    /// the vault. Returns the amount that was burnt.
+    /// It will panic if the proof is invalid or the caller does not have the appropriate permissions
+    pub fn burn_confidential(&self, proof: ConfidentialWithdrawProof) -> Amount {
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::BurnConfidential,
+            args: invoke_args![ConfidentialBurnArg { proof }],
+        });
+
+        resp.decode().expect("burn_confidential returned invalid amount")
+    }
+
     /// Pay a transaction fee with the funds present in the vault.
     /// Note that the vault must hold native Tari tokens to perform this operation
     pub fn pay_fee(&self, amount: Amount) {
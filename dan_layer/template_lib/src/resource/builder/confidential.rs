@@ -202,6 +202,7 @@ impl ConfidentialResourceBuilder {
             resource,
             view_key,
             authorize_hook,
+            None,
         )
     }
 }
@@ -184,6 +184,7 @@ impl FungibleResourceBuilder {
             mint_arg,
             None,
             authorize_hook,
+            None,
         )
     }
 }
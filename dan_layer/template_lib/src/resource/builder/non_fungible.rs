@@ -11,7 +11,7 @@ use crate::{
     args::MintArg,
     auth::{AccessRule, AuthHook, OwnerRule, ResourceAccessRules},
     models::{Bucket, ComponentAddress, Metadata, NonFungibleId, ResourceAddress},
-    resource::{ResourceManager, ResourceType},
+    resource::{ResourceManager, ResourceType, RoyaltyConfig},
 };
 
 /// Utility for building non-fungible resources inside templates
@@ -22,6 +22,7 @@ pub struct NonFungibleResourceBuilder {
     tokens_ids: BTreeMap<NonFungibleId, (tari_bor::Value, tari_bor::Value)>,
     token_symbol: Option<String>,
     authorize_hook: Option<AuthHook>,
+    royalty: Option<RoyaltyConfig>,
 }
 
 impl NonFungibleResourceBuilder {
@@ -34,6 +35,7 @@ impl NonFungibleResourceBuilder {
             tokens_ids: BTreeMap::new(),
             token_symbol: None,
             authorize_hook: None,
+            royalty: None,
         }
     }
 
@@ -178,6 +180,25 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    /// Sets up a royalty that is charged whenever a token of the resource is transferred between different components.
+    /// The transaction that transfers the token must declare a payment bucket, from which `basis_points` (1/100th of a
+    /// percent) of the payment is deposited into the `recipient_component`. Transfers without a declared payment will
+    /// fail. Moving tokens within the same component and minting are exempt.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use tari_template_lib::{caller_context::CallerContext, prelude::ResourceBuilder};
+    /// // 2.5% royalty paid to the creating component
+    /// ResourceBuilder::non_fungible()
+    ///     .with_royalty(CallerContext::current_component_address(), 250)
+    ///     .build();
+    /// ```
+    pub fn with_royalty(mut self, recipient_component: ComponentAddress, basis_points: u16) -> Self {
+        self.royalty = Some(RoyaltyConfig::new(recipient_component, basis_points));
+        self
+    }
+
     /// Build the resource, returning the address
     pub fn build(self) -> ResourceAddress {
         // TODO: Improve API
@@ -189,6 +210,7 @@ impl NonFungibleResourceBuilder {
             None,
             self.token_symbol,
             self.authorize_hook,
+            self.royalty,
         );
         address
     }
@@ -206,6 +228,7 @@ impl NonFungibleResourceBuilder {
             Some(resource),
             self.token_symbol,
             self.authorize_hook,
+            self.royalty,
         );
         bucket.expect("[build_bucket] Bucket not returned from system")
     }
@@ -217,6 +240,7 @@ impl NonFungibleResourceBuilder {
         resource: Option<MintArg>,
         token_symbol: Option<String>,
         authorize_hook: Option<AuthHook>,
+        royalty: Option<RoyaltyConfig>,
     ) -> (ResourceAddress, Option<Bucket>) {
         if let Some(symbol) = token_symbol {
             metadata.insert(TOKEN_SYMBOL, symbol);
@@ -230,6 +254,7 @@ impl NonFungibleResourceBuilder {
             resource,
             None,
             authorize_hook,
+            royalty,
         )
     }
 }
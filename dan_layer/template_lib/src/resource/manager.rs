@@ -53,6 +53,7 @@ use crate::{
         VaultId,
     },
     prelude::{AuthHook, ResourceType},
+    resource::RoyaltyConfig,
 };
 
 /// Utility for managing resources inside templates
@@ -104,6 +105,8 @@ impl ResourceManager {
     /// * `access_rules` - Rules that will govern access to the resource
     /// * `metadata` - Collection of information used to describe the resource
     /// * `mint_arg` - Specification of the initial tokens that will be minted on resource creation
+    /// * `royalty` - Royalty charged when tokens are transferred between components (non-fungible resources only)
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &self,
         resource_type: ResourceType,
@@ -113,6 +116,7 @@ impl ResourceManager {
         mint_arg: Option<MintArg>,
         view_key: Option<RistrettoPublicKeyBytes>,
         authorize_hook: Option<AuthHook>,
+        royalty: Option<RoyaltyConfig>,
     ) -> (ResourceAddress, Option<Bucket>) {
         let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
             resource_ref: ResourceRef::Resource,
@@ -125,6 +129,7 @@ impl ResourceManager {
                 mint_arg,
                 view_key,
                 authorize_hook,
+                royalty,
             }],
         });
 
@@ -0,0 +1,40 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::models::{Amount, ComponentAddress};
+
+/// The maximum royalty that can be charged, in basis points (100%)
+pub const MAX_ROYALTY_BASIS_POINTS: u16 = 10_000;
+
+/// Royalty configuration for a non-fungible resource. When a token of the resource is transferred between different
+/// components, `basis_points` of the declared payment are routed to the `recipient` component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct RoyaltyConfig {
+    pub recipient: ComponentAddress,
+    pub basis_points: u16,
+}
+
+impl RoyaltyConfig {
+    pub fn new(recipient: ComponentAddress, basis_points: u16) -> Self {
+        Self {
+            recipient,
+            basis_points,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.basis_points > 0 && self.basis_points <= MAX_ROYALTY_BASIS_POINTS
+    }
+
+    /// Calculates the royalty due on the given payment amount, rounded down. Returns None on overflow.
+    pub fn calculate_royalty(&self, payment: Amount) -> Option<Amount> {
+        payment
+            .checked_mul(&Amount::from(u32::from(self.basis_points)))?
+            .checked_div(&Amount::from(u32::from(MAX_ROYALTY_BASIS_POINTS)))
+    }
+}
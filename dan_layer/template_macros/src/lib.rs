@@ -25,9 +25,17 @@ mod template;
 use proc_macro::TokenStream;
 
 /// Generates Tari template definition and dispatcher code from annotated template code.
+///
+/// Options:
+/// - `reentrant`: allows a component of this template to be called while it is already in the active call stack e.g.
+///   `#[template(reentrant)]`. By default, the engine rejects reentrant calls.
+/// - `upgrades_from = "<template address>"`: declares that this template is a new version of the template at the given
+///   hex address, allowing components of the previous version to be migrated to it. If the template defines an
+///   `on_migrate` function, the engine calls it with the previous component state and uses its return value as the new
+///   state.
 #[proc_macro_attribute]
-pub fn template(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    template::generate_template(proc_macro2::TokenStream::from(item))
+pub fn template(attr: TokenStream, item: TokenStream) -> TokenStream {
+    template::generate_template(attr.into(), item.into())
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
@@ -35,8 +43,8 @@ pub fn template(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Returns the template code without the wasm ABI code. This allows the code to compile for non-WASM targets and allows
 /// "intellisense" to work in IDEs.
 #[proc_macro_attribute]
-pub fn template_non_wasm(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    template::generate_template_non_wasm(proc_macro2::TokenStream::from(item))
+pub fn template_non_wasm(attr: TokenStream, item: TokenStream) -> TokenStream {
+    template::generate_template_non_wasm(attr.into(), item.into())
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
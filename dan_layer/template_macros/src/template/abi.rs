@@ -21,22 +21,35 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{AngleBracketedGenericArguments, GenericArgument, PathArguments, PathSegment, Result, Type, TypeTuple};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    AngleBracketedGenericArguments,
+    Fields,
+    GenericArgument,
+    ItemStruct,
+    PathArguments,
+    PathSegment,
+    Result,
+    Type,
+    TypeTuple,
+};
 use tari_template_abi::{
     ArgDef,
+    ComponentSchema,
+    FieldDef,
     FunctionDef,
+    StructDef,
     TemplateDef,
     TemplateDefV1,
     Type as ArgType,
     ABI_TEMPLATE_DEF_GLOBAL_NAME,
 };
 
-use crate::template::ast::{TemplateAst, TypeAst};
+use crate::template::ast::{TemplateAst, TemplateOptions, TypeAst};
 
 pub const TARI_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub fn generate_abi(ast: &TemplateAst) -> Result<TokenStream> {
+pub fn generate_abi(ast: &TemplateAst, options: &TemplateOptions) -> Result<TokenStream> {
     let template_name_as_str = ast.template_name.to_string();
 
     let template_def = TemplateDef::V1(TemplateDefV1 {
@@ -62,6 +75,9 @@ pub fn generate_abi(ast: &TemplateAst) -> Result<TokenStream> {
                 })
             })
             .collect::<Result<_>>()?,
+        component_schema: generate_component_schema(ast),
+        is_reentrant: options.is_reentrant,
+        upgrades_from: options.upgrades_from,
     });
 
     let template_def_data = tari_bor::encode_with_len(&template_def);
@@ -76,6 +92,78 @@ pub fn generate_abi(ast: &TemplateAst) -> Result<TokenStream> {
     Ok(output)
 }
 
+fn generate_component_schema(ast: &TemplateAst) -> Option<ComponentSchema> {
+    let template_name = ast.template_name.to_string();
+    let mut structs = ast.get_structs().map(|item| struct_to_def(&template_name, item));
+    let component = structs.next()?;
+    Some(ComponentSchema {
+        component,
+        types: structs.collect(),
+    })
+}
+
+fn struct_to_def(template_name: &str, item: &ItemStruct) -> StructDef {
+    let fields = match &item.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| FieldDef {
+                name: field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default(),
+                field_type: field_type_to_arg_type(template_name, &field.ty),
+            })
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| FieldDef {
+                name: i.to_string(),
+                field_type: field_type_to_arg_type(template_name, &field.ty),
+            })
+            .collect(),
+        Fields::Unit => vec![],
+    };
+
+    StructDef {
+        name: item.ident.to_string(),
+        fields,
+    }
+}
+
+/// Converts the type of a struct field. Unlike function arguments, any valid rust type may be used in a struct so
+/// unsupported types are represented by their source tokens rather than rejected.
+fn field_type_to_arg_type(template_name: &str, ty: &Type) -> ArgType {
+    match ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "Vec" => match &segment.arguments {
+                PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) => match args.first() {
+                    Some(GenericArgument::Type(ty)) => {
+                        ArgType::Vec(Box::new(field_type_to_arg_type(template_name, ty)))
+                    },
+                    _ => tokens_to_arg_type(ty),
+                },
+                _ => tokens_to_arg_type(ty),
+            },
+            Some(segment) => path_segment_to_arg_type(template_name, segment),
+            None => tokens_to_arg_type(ty),
+        },
+        Type::Tuple(tuple) => ArgType::Tuple(
+            tuple
+                .elems
+                .iter()
+                .map(|t| field_type_to_arg_type(template_name, t))
+                .collect(),
+        ),
+        ty => tokens_to_arg_type(ty),
+    }
+}
+
+fn tokens_to_arg_type<T: ToTokens>(ty: &T) -> ArgType {
+    ArgType::Other {
+        name: ty.to_token_stream().to_string(),
+    }
+}
+
 fn convert_to_arg_type(template_name: &str, ty: &TypeAst) -> ArgType {
     match ty {
         TypeAst::Receiver { mutability: true } => ArgType::Other {
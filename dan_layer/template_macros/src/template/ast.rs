@@ -33,11 +33,14 @@ use syn::{
     ImplItemMethod,
     Item,
     ItemMod,
+    ItemStruct,
     ItemUse,
+    LitStr,
     Result,
     ReturnType,
     Signature,
     Stmt,
+    Token,
     TypePath,
     TypeTuple,
     UseTree,
@@ -118,7 +121,61 @@ impl Parse for TemplateAst {
     }
 }
 
+/// Options given to the template attribute e.g. `#[template(reentrant)]`
+#[derive(Debug, Default)]
+pub struct TemplateOptions {
+    pub is_reentrant: bool,
+    pub upgrades_from: Option<[u8; 32]>,
+}
+
+impl Parse for TemplateOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = Self::default();
+        while !input.is_empty() {
+            let option = input.parse::<Ident>()?;
+            if option == "reentrant" {
+                options.is_reentrant = true;
+            } else if option == "upgrades_from" {
+                input.parse::<Token![=]>()?;
+                options.upgrades_from = Some(parse_template_address(&input.parse::<LitStr>()?)?);
+            } else {
+                return Err(Error::new(
+                    option.span(),
+                    format!(
+                        "unknown template option `{}`, expected `reentrant` or `upgrades_from`",
+                        option
+                    ),
+                ));
+            }
+            if !input.is_empty() {
+                input.parse::<Comma>()?;
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn parse_template_address(lit: &LitStr) -> Result<[u8; 32]> {
+    hex::decode(lit.value())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            Error::new(
+                lit.span(),
+                "`upgrades_from` must be a hex-encoded 32-byte template address",
+            )
+        })
+}
+
 impl TemplateAst {
+    /// Returns all structs defined in the template module. The first struct is the component struct.
+    pub fn get_structs(&self) -> impl Iterator<Item = &ItemStruct> + '_ {
+        self.module_content.iter().filter_map(|i| match i {
+            Item::Struct(item) => Some(item),
+            _ => None,
+        })
+    }
+
     pub fn get_functions(&self) -> impl Iterator<Item = FunctionAst> + '_ {
         self.module_content
             .iter()
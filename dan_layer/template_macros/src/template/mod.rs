@@ -29,13 +29,19 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse2, Result};
 
-use self::{abi::generate_abi, ast::TemplateAst, definition::generate_definition, dispatcher::generate_dispatcher};
+use self::{
+    abi::generate_abi,
+    ast::{TemplateAst, TemplateOptions},
+    definition::generate_definition,
+    dispatcher::generate_dispatcher,
+};
 
-pub fn generate_template(input: TokenStream) -> Result<TokenStream> {
+pub fn generate_template(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    let options = parse2::<TemplateOptions>(attr)?;
     let ast = parse2::<TemplateAst>(input).unwrap();
 
     let definition = generate_definition(&ast);
-    let abi = generate_abi(&ast)?;
+    let abi = generate_abi(&ast, &options)?;
     let dispatcher = generate_dispatcher(&ast)?;
 
     let output = quote! {
@@ -51,7 +57,9 @@ pub fn generate_template(input: TokenStream) -> Result<TokenStream> {
     Ok(output)
 }
 
-pub fn generate_template_non_wasm(input: TokenStream) -> Result<TokenStream> {
+pub fn generate_template_non_wasm(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    // The options only affect the ABI but are parsed so that invalid options are reported for non-WASM targets too
+    parse2::<TemplateOptions>(attr)?;
     let ast = parse2::<TemplateAst>(input).unwrap();
 
     let definition = generate_definition(&ast);
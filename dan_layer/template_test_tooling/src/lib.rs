@@ -3,10 +3,12 @@
 
 mod package_builder;
 mod read_only_state_store;
+mod sharded_template_test;
 pub mod support;
 mod template_test;
 mod track_calls;
 pub use package_builder::Package;
+pub use sharded_template_test::{ShardDistribution, ShardedTemplateTest};
 pub use template_test::{test_faucet_component, SubstateType, TemplateTest};
 
 pub mod crypto {
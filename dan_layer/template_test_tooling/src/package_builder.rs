@@ -67,6 +67,13 @@ impl PackageBuilder {
         self
     }
 
+    /// Adds the template at the given address rather than the hash of its code. This allows a template to refer to the
+    /// address of another template at compile time e.g. `#[template(upgrades_from = "...")]`.
+    pub fn add_template_at_address<P: AsRef<Path>>(&mut self, path: P, address: TemplateAddress) -> &mut Self {
+        let wasm = compile_template(path, &[]).unwrap().load_template().unwrap();
+        self.add_loaded_template(address, wasm)
+    }
+
     pub fn add_loaded_template(&mut self, address: TemplateAddress, template: LoadedTemplate) -> &mut Self {
         self.templates.insert(address, template);
         self
@@ -0,0 +1,224 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use tari_dan_common_types::{shard::Shard, SubstateAddress};
+use tari_engine_types::{commit_result::ExecuteResult, substate::SubstateId};
+use tari_template_lib::models::NonFungibleAddress;
+use tari_transaction::Transaction;
+
+use crate::TemplateTest;
+
+/// A [TemplateTest] that partitions the substate address space into a number of committees and tracks which committee
+/// each substate created by a transaction falls into.
+///
+/// All substates are still stored in a single flat state store, so transactions execute exactly as they would with
+/// [TemplateTest]. This allows tests to assert on shard assignment without running a network.
+pub struct ShardedTemplateTest {
+    test: TemplateTest,
+    num_committees: u32,
+    substate_shards: HashMap<SubstateId, Shard>,
+}
+
+impl ShardedTemplateTest {
+    pub fn new<I: IntoIterator<Item = P>, P: AsRef<Path>>(template_paths: I, num_committees: u32) -> Self {
+        Self::from_template_test(TemplateTest::new(template_paths), num_committees)
+    }
+
+    pub fn from_template_test(test: TemplateTest, num_committees: u32) -> Self {
+        assert!(num_committees > 0, "num_committees must be greater than zero");
+        Self {
+            test,
+            num_committees,
+            substate_shards: HashMap::new(),
+        }
+    }
+
+    pub fn num_committees(&self) -> u32 {
+        self.num_committees
+    }
+
+    pub fn into_template_test(self) -> TemplateTest {
+        self.test
+    }
+
+    /// Returns the shard that the given substate version is assigned to.
+    pub fn shard_for(&self, substate_id: &SubstateId, version: u32) -> Shard {
+        SubstateAddress::from_substate_id(substate_id, version).to_shard(self.num_committees)
+    }
+
+    /// Returns the shard of the latest version of the substate. Substates that have not been output by a transaction
+    /// executed through this instance are looked up in the state store.
+    pub fn shard_of(&self, substate_id: &SubstateId) -> Shard {
+        if let Some(shard) = self.substate_shards.get(substate_id) {
+            return *shard;
+        }
+
+        let substate = self
+            .test
+            .read_only_state_store()
+            .get_substate(substate_id)
+            .unwrap_or_else(|e| panic!("Substate {} not found: {}", substate_id, e));
+        self.shard_for(substate_id, substate.version())
+    }
+
+    /// Returns the tracked shard assignment of all substates output by transactions executed through this instance.
+    pub fn tracked_substates(&self) -> impl Iterator<Item = (&SubstateId, Shard)> + '_ {
+        self.substate_shards.iter().map(|(id, shard)| (id, *shard))
+    }
+
+    pub fn assert_same_shard<A: Into<SubstateId>, B: Into<SubstateId>>(&self, a: A, b: B) {
+        let a = a.into();
+        let b = b.into();
+        let shard_a = self.shard_of(&a);
+        let shard_b = self.shard_of(&b);
+        assert_eq!(
+            shard_a, shard_b,
+            "Expected {} (shard {}) and {} (shard {}) to be in the same shard of {}",
+            a, shard_a, b, shard_b, self.num_committees
+        );
+    }
+
+    pub fn assert_different_shard<A: Into<SubstateId>, B: Into<SubstateId>>(&self, a: A, b: B) {
+        let a = a.into();
+        let b = b.into();
+        let shard = self.shard_of(&a);
+        assert_ne!(
+            shard,
+            self.shard_of(&b),
+            "Expected {} and {} to be in different shards, both are in shard {} of {}",
+            a,
+            b,
+            shard,
+            self.num_committees
+        );
+    }
+
+    pub fn assert_in_shard<A: Into<SubstateId>>(&self, substate_id: A, expected: Shard) {
+        let substate_id = substate_id.into();
+        let shard = self.shard_of(&substate_id);
+        assert_eq!(
+            shard, expected,
+            "Expected {} to be in shard {} but it is in shard {} of {}",
+            substate_id, expected, shard, self.num_committees
+        );
+    }
+
+    /// Returns the distribution of the outputs of the given result across the committees.
+    pub fn output_distribution(&self, result: &ExecuteResult) -> ShardDistribution {
+        let mut outputs = BTreeMap::<_, Vec<_>>::new();
+        if let Some(diff) = result.finalize.result.accept() {
+            for (id, substate) in diff.up_iter() {
+                outputs
+                    .entry(self.shard_for(id, substate.version()))
+                    .or_default()
+                    .push(id.clone());
+            }
+        }
+
+        ShardDistribution {
+            num_committees: self.num_committees,
+            outputs,
+        }
+    }
+
+    pub fn execute_expect_success(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> ExecuteResult {
+        let result = self.test.execute_expect_success(transaction, proofs);
+        self.track_outputs(&result);
+        result
+    }
+
+    pub fn execute_expect_commit(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> ExecuteResult {
+        let result = self.test.execute_expect_commit(transaction, proofs);
+        self.track_outputs(&result);
+        result
+    }
+
+    pub fn execute_and_commit_on_success(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> ExecuteResult {
+        let result = self.test.execute_and_commit_on_success(transaction, proofs);
+        self.track_outputs(&result);
+        result
+    }
+
+    /// Records the shard of each output of the result. This is called by the execute functions of this type and should
+    /// be called for results that are committed by calling the inner [TemplateTest] directly.
+    pub fn track_outputs(&mut self, result: &ExecuteResult) {
+        let Some(diff) = result.finalize.result.accept() else {
+            return;
+        };
+
+        for (id, substate) in diff.up_iter() {
+            let shard = self.shard_for(id, substate.version());
+            self.substate_shards.insert(id.clone(), shard);
+        }
+    }
+}
+
+impl Deref for ShardedTemplateTest {
+    type Target = TemplateTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.test
+    }
+}
+
+impl DerefMut for ShardedTemplateTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.test
+    }
+}
+
+/// The outputs of a transaction grouped by the shard they are assigned to.
+#[derive(Debug, Clone)]
+pub struct ShardDistribution {
+    num_committees: u32,
+    outputs: BTreeMap<Shard, Vec<SubstateId>>,
+}
+
+impl ShardDistribution {
+    pub fn num_committees(&self) -> u32 {
+        self.num_committees
+    }
+
+    /// Returns the number of shards that contain at least one output
+    pub fn num_shards_involved(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn is_cross_shard(&self) -> bool {
+        self.num_shards_involved() > 1
+    }
+
+    pub fn total_outputs(&self) -> usize {
+        self.outputs.values().map(Vec::len).sum()
+    }
+
+    pub fn count_in(&self, shard: Shard) -> usize {
+        self.outputs.get(&shard).map_or(0, Vec::len)
+    }
+
+    pub fn outputs_in(&self, shard: Shard) -> &[SubstateId] {
+        self.outputs.get(&shard).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Shard, &[SubstateId])> + '_ {
+        self.outputs.iter().map(|(shard, ids)| (*shard, ids.as_slice()))
+    }
+}
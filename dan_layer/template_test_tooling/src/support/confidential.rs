@@ -125,6 +125,20 @@ pub fn generate_withdraw_proof_with_view_key(
     )
 }
 
+/// Generates a withdraw proof that burns `burn_amount` of the input commitment. The proof has no output commitment and
+/// the remainder of the input, if any, is returned as change.
+pub fn generate_burn_proof(input_mask: &PrivateKey, input_amount: Amount, burn_amount: Amount) -> WithdrawProofOutput {
+    let change_amount = input_amount - burn_amount;
+    generate_withdraw_proof_internal(
+        &[(input_mask.clone(), input_amount)],
+        Amount::zero(),
+        Amount::zero(),
+        Some(change_amount).filter(|amount| amount.is_positive()),
+        burn_amount,
+        None,
+    )
+}
+
 fn generate_withdraw_proof_internal(
     inputs: &[(PrivateKey, Amount)],
     input_revealed_amount: Amount,
@@ -22,6 +22,7 @@ use tari_dan_common_types::crypto::create_key_pair_from_seed;
 use tari_dan_engine::{
     bootstrap_state,
     fees::{FeeModule, FeeTable},
+    limits::{SubstateSizeLimitModule, SubstateSizeLimits},
     runtime::{AuthParams, RuntimeModule},
     state_store::{
         memory::{MemoryStateStore, MemoryWriteTransaction},
@@ -54,7 +55,7 @@ use tari_template_lib::{
     Hash,
 };
 use tari_transaction::{Transaction, VersionedSubstateId};
-use tari_transaction_manifest::{parse_manifest, ManifestValue};
+use tari_transaction_manifest::{check_manifest, parse_manifest, ManifestValue};
 
 use crate::{read_only_state_store::ReadOnlyStateStore, track_calls::TrackCallsModule, Package};
 
@@ -72,8 +73,12 @@ pub struct TemplateTest {
     state_store: MemoryStateStore,
     enable_fees: bool,
     fee_table: FeeTable,
+    substate_size_limits: Option<SubstateSizeLimits>,
     virtual_substates: VirtualSubstates,
     key_seed: u8,
+    id_seed: Option<u64>,
+    num_executed: u64,
+    allocated_addresses: Vec<Vec<SubstateId>>,
 }
 
 impl TemplateTest {
@@ -136,6 +141,7 @@ impl TemplateTest {
             state_store,
             virtual_substates,
             enable_fees: false,
+            substate_size_limits: None,
             fee_table: FeeTable {
                 per_module_call_cost: 1,
                 per_byte_storage_cost: 1,
@@ -143,9 +149,19 @@ impl TemplateTest {
                 per_log_cost: 1,
             },
             key_seed: 1,
+            id_seed: None,
+            num_executed: 0,
+            allocated_addresses: Vec::new(),
         }
     }
 
+    /// Derives the addresses of new components, resources and vaults from the seed instead of the transaction hash, so
+    /// that running the same sequence of transactions allocates the same addresses every time.
+    pub fn with_deterministic_ids(mut self, seed: u64) -> Self {
+        self.id_seed = Some(seed);
+        self
+    }
+
     pub fn bootstrap_faucet(&self, amount: Amount) {
         let mut tx = self.state_store.write_access().unwrap();
         Self::initial_tari_faucet_supply(
@@ -218,6 +234,12 @@ impl TemplateTest {
         self
     }
 
+    /// Enables the substate size limits. The limits are disabled by default.
+    pub fn set_substate_size_limits(&mut self, limits: SubstateSizeLimits) -> &mut Self {
+        self.substate_size_limits = Some(limits);
+        self
+    }
+
     pub fn set_virtual_substate(&mut self, address: VirtualSubstateId, value: VirtualSubstate) -> &mut Self {
         self.virtual_substates.insert(address, value);
         self
@@ -257,6 +279,42 @@ impl TemplateTest {
             .unwrap_or_else(|| panic!("No output of type {:?}", ty))
     }
 
+    /// Returns the substates created by each executed transaction, in execution order. Transaction receipts are
+    /// excluded because their address is always derived from the transaction hash.
+    pub fn allocated_addresses(&self) -> &[Vec<SubstateId>] {
+        &self.allocated_addresses
+    }
+
+    /// Returns the substates created by the most recently executed transaction
+    pub fn last_allocated_addresses(&self) -> &[SubstateId] {
+        self.allocated_addresses.last().map(|a| a.as_slice()).unwrap_or_default()
+    }
+
+    fn record_allocated_addresses(&mut self, result: &ExecuteResult) {
+        let allocated = result
+            .finalize
+            .result
+            .accept()
+            .map(|diff| {
+                diff.up_iter()
+                    .map(|(id, _)| id)
+                    .filter(|id| !id.is_transaction_receipt() && !diff.down_iter().any(|(down, _)| down == *id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.allocated_addresses.push(allocated);
+    }
+
+    /// Returns the seed for the ids allocated by the next transaction, if deterministic ids are enabled
+    fn next_id_seed(&mut self) -> Option<Hash> {
+        let seed = self.id_seed?;
+        let mut bytes = [0u8; Hash::LENGTH];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.num_executed.to_le_bytes());
+        Some(Hash::from_array(bytes))
+    }
+
     fn commit_diff(&mut self, diff: &SubstateDiff) {
         self.last_outputs.clear();
         let mut tx = self.state_store.write_access().unwrap();
@@ -456,27 +514,56 @@ impl TemplateTest {
     }
 
     pub fn try_execute(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> Result<ExecuteResult, TransactionError> {
+        self.try_execute_inner(transaction, proofs, false)
+    }
+
+    /// Executes a system (e.g. genesis) transaction, which may create substates in the reserved address range
+    pub fn try_execute_system(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> Result<ExecuteResult, TransactionError> {
+        self.try_execute_inner(transaction, proofs, true)
+    }
+
+    fn try_execute_inner(
         &mut self,
         mut transaction: Transaction,
         proofs: Vec<NonFungibleAddress>,
+        is_system_transaction: bool,
     ) -> Result<ExecuteResult, TransactionError> {
         let mut modules: Vec<Arc<dyn RuntimeModule>> = vec![Arc::new(self.track_calls.clone())];
 
+        if let Some(limits) = self.substate_size_limits {
+            modules.push(Arc::new(SubstateSizeLimitModule::new(limits)));
+        }
+
         if self.enable_fees {
-            modules.push(Arc::new(FeeModule::new(0, self.fee_table.clone())));
+            modules.push(Arc::new(
+                FeeModule::new(0, self.fee_table.clone()).with_priority_fee(transaction.priority_fee()),
+            ));
         }
 
         let auth_params = AuthParams {
             initial_ownership_proofs: proofs,
         };
-        let processor = TransactionProcessor::new(
+        let mut processor = TransactionProcessor::new(
             self.package.clone(),
             self.state_store.clone(),
             auth_params,
             self.virtual_substates.clone(),
             modules,
             Network::LocalNet,
-        );
+        )
+        .with_system_transaction(is_system_transaction);
+        if let Some(id_seed) = self.next_id_seed() {
+            processor = processor.with_id_seed(id_seed);
+        }
+        self.num_executed += 1;
 
         {
             let access = self.state_store.read_access().unwrap();
@@ -493,6 +580,7 @@ impl TemplateTest {
         eprintln!("START Transaction id = \"{}\"", tx_id);
 
         let result = processor.execute(transaction)?;
+        self.record_allocated_addresses(&result);
 
         if self.enable_fees {
             let fee = &result.finalize.fee_receipt;
@@ -540,6 +628,19 @@ impl TemplateTest {
         result
     }
 
+    /// Executes a system transaction and commits the result. Panics if the transaction fails.
+    pub fn execute_system_expect_success(
+        &mut self,
+        transaction: Transaction,
+        proofs: Vec<NonFungibleAddress>,
+    ) -> ExecuteResult {
+        let result = self.try_execute_system(transaction, proofs).unwrap();
+        let diff = result.expect_finalization_success();
+        self.commit_diff(diff);
+        result.expect_success();
+        result
+    }
+
     /// Executes a transaction. Panics if the transaction fails.
     pub fn execute_expect_success(
         &mut self,
@@ -607,12 +708,17 @@ impl TemplateTest {
             .collect::<Vec<_>>()
             .join("\n");
         let manifest = format!("{} fn main() {{ {} }}", template_imports, manifest);
-        let instructions = parse_manifest(
-            &manifest,
-            variables.into_iter().map(|(a, b)| (a.to_string(), b)).collect(),
-            Default::default(),
-        )
-        .unwrap();
+        let globals = variables
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b))
+            .collect::<HashMap<_, _>>();
+        let diagnostics = check_manifest(&manifest, &globals).unwrap();
+        assert!(
+            diagnostics.is_empty(),
+            "Manifest failed static checks: {}",
+            diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ")
+        );
+        let instructions = parse_manifest(&manifest, globals, Default::default()).unwrap();
         self.execute_and_commit(instructions.instructions, proofs)
     }
 
@@ -1,13 +1,14 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use rand::{CryptoRng, RngCore};
 use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_dan_common_types::Epoch;
 use tari_engine_types::{confidential::ConfidentialClaim, instruction::Instruction, TemplateAddress};
 use tari_template_lib::{
     args,
     args::Arg,
-    models::{Amount, ComponentAddress, ConfidentialWithdrawProof, ResourceAddress},
+    models::{Amount, ComponentAddress, ConfidentialWithdrawProof, ResourceAddress, VaultId},
 };
 
 use crate::{unsigned_transaction::UnsignedTransaction, SubstateRequirement, Transaction, TransactionSignature};
@@ -100,6 +101,45 @@ impl TransactionBuilder {
         })
     }
 
+    pub fn declare_royalty_payment<T: Into<String>>(
+        self,
+        resource_address: ResourceAddress,
+        workspace_bucket: T,
+    ) -> Self {
+        self.add_instruction(Instruction::DeclareRoyaltyPayment {
+            resource_address,
+            workspace_bucket: workspace_bucket.into(),
+        })
+    }
+
+    pub fn migrate_component(self, component_address: ComponentAddress, template_address: TemplateAddress) -> Self {
+        self.add_instruction(Instruction::MigrateComponent {
+            component_address,
+            template_address,
+        })
+    }
+
+    /// Aborts the transaction unless the bucket on the workspace contains at least `min_amount` of the resource when
+    /// this instruction is executed
+    pub fn assert_bucket_contains<T: Into<String>>(
+        self,
+        workspace_bucket: T,
+        resource_address: ResourceAddress,
+        min_amount: Amount,
+    ) -> Self {
+        self.add_instruction(Instruction::AssertBucketContains {
+            workspace_bucket: workspace_bucket.into(),
+            resource_address,
+            min_amount,
+        })
+    }
+
+    /// Aborts the transaction unless the balance of the vault is at least `min_amount` when this instruction is
+    /// executed. The vault must be an input of the transaction.
+    pub fn assert_vault_balance_at_least(self, vault_id: VaultId, min_amount: Amount) -> Self {
+        self.add_instruction(Instruction::AssertVaultBalanceAtLeast { vault_id, min_amount })
+    }
+
     pub fn claim_burn(self, claim: ConfidentialClaim) -> Self {
         self.add_instruction(Instruction::ClaimBurn { claim: Box::new(claim) })
     }
@@ -159,6 +199,15 @@ impl TransactionBuilder {
         self
     }
 
+    pub fn sign_with_rng<R: RngCore + CryptoRng>(mut self, secret_key: &PrivateKey, rng: &mut R) -> Self {
+        self.signature = Some(TransactionSignature::sign_with_rng(
+            secret_key,
+            &self.unsigned_transaction,
+            rng,
+        ));
+        self
+    }
+
     /// Add an input to use in the transaction
     pub fn add_input<I: Into<SubstateRequirement>>(mut self, input_object: I) -> Self {
         self.unsigned_transaction.inputs.insert(input_object.into());
@@ -188,6 +237,15 @@ impl TransactionBuilder {
         self
     }
 
+    /// Sets an additional fee paid to the validators on top of the fees charged for execution. Transactions with a
+    /// higher priority fee are included in blocks before other transactions.
+    pub fn with_priority_fee(mut self, priority_fee: u64) -> Self {
+        self.unsigned_transaction.priority_fee = priority_fee;
+        // Reset the signature as it is no longer valid
+        self.signature = None;
+        self
+    }
+
     pub fn build_unsigned_transaction(self) -> UnsignedTransaction {
         self.unsigned_transaction
     }
@@ -200,6 +258,7 @@ impl TransactionBuilder {
             filled_inputs,
             min_epoch,
             max_epoch,
+            priority_fee,
         } = self.unsigned_transaction;
 
         Transaction::new(
@@ -210,6 +269,7 @@ impl TransactionBuilder {
             filled_inputs,
             min_epoch,
             max_epoch,
+            priority_fee,
         )
     }
 }
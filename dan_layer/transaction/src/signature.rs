@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use indexmap::IndexSet;
-use rand::rngs::OsRng;
+use rand::{rngs::OsRng, CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{PublicKey, Signature};
 use tari_crypto::{
@@ -34,11 +34,21 @@ impl TransactionSignature {
     }
 
     pub fn sign(secret_key: &RistrettoSecretKey, transaction: &UnsignedTransaction) -> Self {
+        Self::sign_with_rng(secret_key, transaction, &mut OsRng)
+    }
+
+    /// Signs the transaction using a nonce from the given RNG. A seeded RNG produces the same signature (and therefore
+    /// the same transaction id) every time, which is useful for reproducible test transactions.
+    pub fn sign_with_rng<R: RngCore + CryptoRng>(
+        secret_key: &RistrettoSecretKey,
+        transaction: &UnsignedTransaction,
+        rng: &mut R,
+    ) -> Self {
         let public_key = RistrettoPublicKey::from_secret_key(secret_key);
         let challenge = Self::create_challenge(transaction);
 
         Self {
-            signature: Signature::sign(secret_key, challenge, &mut OsRng).unwrap(),
+            signature: Signature::sign(secret_key, challenge, rng).unwrap(),
             public_key,
         }
     }
@@ -56,7 +66,8 @@ impl TransactionSignature {
         &self.public_key
     }
 
-    fn create_challenge(transaction: &UnsignedTransaction) -> [u8; 64] {
+    /// Returns the challenge that is signed to produce a valid signature for the transaction
+    pub fn create_challenge(transaction: &UnsignedTransaction) -> [u8; 64] {
         let signature_fields = TransactionSignatureFields::from(transaction);
         hasher64(EngineHashDomainLabel::TransactionSignature)
             .chain(&signature_fields)
@@ -71,6 +82,7 @@ struct TransactionSignatureFields<'a> {
     inputs: &'a IndexSet<SubstateRequirement>,
     min_epoch: Option<Epoch>,
     max_epoch: Option<Epoch>,
+    priority_fee: u64,
 }
 
 impl<'a> From<&'a UnsignedTransaction> for TransactionSignatureFields<'a> {
@@ -81,6 +93,7 @@ impl<'a> From<&'a UnsignedTransaction> for TransactionSignatureFields<'a> {
             inputs: &transaction.inputs,
             min_epoch: transaction.min_epoch,
             max_epoch: transaction.max_epoch,
+            priority_fee: transaction.priority_fee,
         }
     }
 }
@@ -37,6 +37,11 @@ pub struct Transaction {
     filled_inputs: IndexSet<VersionedSubstateId>,
     min_epoch: Option<Epoch>,
     max_epoch: Option<Epoch>,
+    /// An additional fee paid to the validators on top of the fees charged for execution. Proposers include
+    /// transactions with a higher priority fee first.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    priority_fee: u64,
 }
 
 impl Transaction {
@@ -52,6 +57,7 @@ impl Transaction {
         filled_inputs: IndexSet<VersionedSubstateId>,
         min_epoch: Option<Epoch>,
         max_epoch: Option<Epoch>,
+        priority_fee: u64,
     ) -> Self {
         let mut tx = Self {
             id: TransactionId::default(),
@@ -62,6 +68,7 @@ impl Transaction {
             filled_inputs,
             min_epoch,
             max_epoch,
+            priority_fee,
         };
         tx.id = tx.calculate_hash();
         tx
@@ -75,6 +82,7 @@ impl Transaction {
             .chain(&self.inputs)
             .chain(&self.min_epoch)
             .chain(&self.max_epoch)
+            .chain(&self.priority_fee)
             .result()
             .into_array()
             .into()
@@ -191,6 +199,20 @@ impl Transaction {
         self.max_epoch
     }
 
+    /// Returns true if the given epoch is within the min/max epoch bounds of this transaction
+    pub fn is_valid_in_epoch(&self, epoch: Epoch) -> bool {
+        self.min_epoch.map_or(true, |min_epoch| epoch >= min_epoch) && !self.is_expired_in_epoch(epoch)
+    }
+
+    /// Returns true if the max epoch of this transaction has passed at the given epoch
+    pub fn is_expired_in_epoch(&self, epoch: Epoch) -> bool {
+        self.max_epoch.is_some_and(|max_epoch| epoch > max_epoch)
+    }
+
+    pub fn priority_fee(&self) -> u64 {
+        self.priority_fee
+    }
+
     pub fn as_referenced_components(&self) -> impl Iterator<Item = &ComponentAddress> + '_ {
         self.instructions()
             .iter()
@@ -231,6 +253,9 @@ impl Transaction {
                 Instruction::ClaimBurn { claim } => {
                     substates.insert(SubstateId::UnclaimedConfidentialOutput(claim.output_address));
                 },
+                Instruction::AssertVaultBalanceAtLeast { vault_id, .. } => {
+                    substates.insert(SubstateId::Vault(*vault_id));
+                },
                 _ => {},
             }
         }
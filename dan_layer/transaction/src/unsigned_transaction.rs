@@ -23,6 +23,10 @@ pub struct UnsignedTransaction {
     pub filled_inputs: IndexSet<VersionedSubstateId>,
     pub min_epoch: Option<Epoch>,
     pub max_epoch: Option<Epoch>,
+    /// An additional fee paid to the validators on top of the fees charged for execution
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub priority_fee: u64,
 }
 
 impl From<&Transaction> for UnsignedTransaction {
@@ -34,6 +38,7 @@ impl From<&Transaction> for UnsignedTransaction {
             filled_inputs: tx.filled_inputs().clone(),
             min_epoch: tx.min_epoch(),
             max_epoch: tx.max_epoch(),
+            priority_fee: tx.priority_fee(),
         }
     }
 }
@@ -0,0 +1,522 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Static checks for a parsed manifest.
+//!
+//! The checker walks the intents of each block in order and keeps a symbol table of the variables in scope: global
+//! aliases assigned by `global!`, `var!` or `arg!`, and workspace values output by calls. Each symbol has a
+//! [ValueKind] so that misuse, such as calling a method on a bucket, can be reported with the position of the
+//! offending identifier before the manifest is submitted. Templates are not known to the checker, so the kind of a
+//! call output is inferred from the names of the builtin account and faucet methods and is otherwise
+//! [ValueKind::Value].
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+};
+
+use proc_macro2::Ident;
+use tari_engine_types::substate::SubstateId;
+
+use crate::{
+    parser::{AssertIntent, InvokeIntent, ManifestIntent, ManifestLiteral, ParsedManifest},
+    ManifestValue,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Component,
+    Resource,
+    Bucket,
+    Proof,
+    Amount,
+    /// Any other value, or a value whose kind cannot be determined statically
+    Value,
+}
+
+impl ValueKind {
+    fn of_global(value: &ManifestValue) -> Self {
+        match value {
+            ManifestValue::SubstateId(SubstateId::Component(_)) => ValueKind::Component,
+            ManifestValue::SubstateId(SubstateId::Resource(_)) => ValueKind::Resource,
+            _ => ValueKind::Value,
+        }
+    }
+
+    fn of_method_output(method: &str) -> Self {
+        match method {
+            "balance" => ValueKind::Amount,
+            "reveal_confidential" => ValueKind::Bucket,
+            m if m.starts_with("withdraw") || m.starts_with("take_") => ValueKind::Bucket,
+            m if m.starts_with("create_proof") => ValueKind::Proof,
+            _ => ValueKind::Value,
+        }
+    }
+}
+
+impl Display for ValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueKind::Component => write!(f, "component"),
+            ValueKind::Resource => write!(f, "resource"),
+            ValueKind::Bucket => write!(f, "bucket"),
+            ValueKind::Proof => write!(f, "proof"),
+            ValueKind::Amount => write!(f, "amount"),
+            ValueKind::Value => write!(f, "value"),
+        }
+    }
+}
+
+/// A line (1-based) and column (1-based) in the manifest source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    fn of(ident: &Ident) -> Self {
+        let start = ident.span().start();
+        Self {
+            line: start.line,
+            column: start.column + 1,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    UndefinedVariable {
+        name: String,
+    },
+    UndefinedGlobal {
+        name: String,
+    },
+    TemplateNotImported {
+        name: String,
+    },
+    /// A bucket was used after a call that deposited it
+    BucketAlreadyDeposited {
+        name: String,
+        deposited_at: Position,
+    },
+    /// A method was called on a value that is not a component input
+    NotAComponent {
+        name: String,
+        kind: ValueKind,
+        is_workspace_value: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestDiagnostic {
+    pub position: Position,
+    pub kind: DiagnosticKind,
+}
+
+impl Display for ManifestDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.position)?;
+        match &self.kind {
+            DiagnosticKind::UndefinedVariable { name } => write!(f, "Variable '{}' is not defined", name),
+            DiagnosticKind::UndefinedGlobal { name } => write!(f, "Global '{}' is not defined", name),
+            DiagnosticKind::TemplateNotImported { name } => write!(f, "Template '{}' is not imported", name),
+            DiagnosticKind::BucketAlreadyDeposited { name, deposited_at } => write!(
+                f,
+                "Bucket '{}' is used after it was deposited at {}",
+                name, deposited_at
+            ),
+            DiagnosticKind::NotAComponent {
+                name,
+                kind,
+                is_workspace_value: true,
+            } => write!(
+                f,
+                "Cannot call a method on '{}': it is a {} output by a previous call, methods can only be called on \
+                 component inputs",
+                name, kind
+            ),
+            DiagnosticKind::NotAComponent { name, kind, .. } => write!(
+                f,
+                "Cannot call a method on '{}': expected a component but it is a {}",
+                name, kind
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Symbol {
+    kind: ValueKind,
+    is_workspace_value: bool,
+    deposited_at: Option<Position>,
+}
+
+pub struct ManifestChecker<'a> {
+    globals: &'a HashMap<String, ManifestValue>,
+    imported_templates: HashSet<String>,
+    symbols: HashMap<String, Symbol>,
+    diagnostics: Vec<ManifestDiagnostic>,
+}
+
+impl<'a> ManifestChecker<'a> {
+    pub fn new(globals: &'a HashMap<String, ManifestValue>) -> Self {
+        Self {
+            globals,
+            imported_templates: HashSet::new(),
+            symbols: HashMap::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn check(mut self, manifest: &ParsedManifest) -> Vec<ManifestDiagnostic> {
+        self.imported_templates = manifest.defines.iter().map(|d| d.alias.to_string()).collect();
+
+        // The fee instructions are executed separately, so variables defined in main are not in scope in fee_main
+        for intents in [&manifest.fee_instruction_intents, &manifest.instruction_intents] {
+            self.symbols.clear();
+            for intent in intents {
+                self.check_intent(intent);
+            }
+        }
+
+        self.diagnostics.sort_by_key(|d| (d.position.line, d.position.column));
+        self.diagnostics
+    }
+
+    fn check_intent(&mut self, intent: &ManifestIntent) {
+        match intent {
+            ManifestIntent::InvokeTemplate(invoke) => {
+                if let Some(template) = &invoke.template_variable {
+                    if !self.imported_templates.contains(&template.to_string()) {
+                        self.report(template, DiagnosticKind::TemplateNotImported {
+                            name: template.to_string(),
+                        });
+                    }
+                }
+                self.check_arguments(invoke);
+                self.define_output(invoke, ValueKind::Value);
+            },
+            ManifestIntent::InvokeComponent(invoke) => {
+                if let Some(component) = &invoke.component_variable {
+                    self.check_receiver(component);
+                }
+                self.check_arguments(invoke);
+                self.define_output(invoke, ValueKind::of_method_output(&invoke.function_name.to_string()));
+            },
+            ManifestIntent::AssignInput(assign) => {
+                let global_name = assign.global_variable_name.value();
+                let kind = match self.globals.get(&global_name) {
+                    Some(value) => ValueKind::of_global(value),
+                    None => {
+                        self.diagnostics.push(ManifestDiagnostic {
+                            position: Position::of(&assign.variable_name),
+                            kind: DiagnosticKind::UndefinedGlobal { name: global_name },
+                        });
+                        ValueKind::Value
+                    },
+                };
+                self.symbols.insert(assign.variable_name.to_string(), Symbol {
+                    kind,
+                    is_workspace_value: false,
+                    deposited_at: None,
+                });
+            },
+            ManifestIntent::Log(_) => {},
+            ManifestIntent::Assert(AssertIntent::BucketContains { bucket, resource, .. }) => {
+                self.check_variable(bucket, false);
+                self.check_variable(resource, false);
+            },
+            ManifestIntent::Assert(AssertIntent::VaultBalanceAtLeast { vault, .. }) => {
+                self.check_variable(vault, false);
+            },
+        }
+    }
+
+    fn check_receiver(&mut self, component: &Ident) {
+        let Some(symbol) = self.symbols.get(&component.to_string()).cloned() else {
+            self.report(component, DiagnosticKind::UndefinedVariable {
+                name: component.to_string(),
+            });
+            return;
+        };
+        if symbol.is_workspace_value || symbol.kind != ValueKind::Component {
+            let kind = DiagnosticKind::NotAComponent {
+                name: component.to_string(),
+                kind: symbol.kind,
+                is_workspace_value: symbol.is_workspace_value,
+            };
+            self.report(component, kind);
+        }
+    }
+
+    fn check_arguments(&mut self, invoke: &InvokeIntent) {
+        let is_deposit = invoke.function_name.to_string().starts_with("deposit");
+        for arg in &invoke.arguments {
+            if let ManifestLiteral::Variable(ident) = arg {
+                self.check_variable(ident, is_deposit);
+            }
+        }
+    }
+
+    fn check_variable(&mut self, ident: &Ident, is_deposit: bool) {
+        let name = ident.to_string();
+        match self.symbols.get_mut(&name) {
+            Some(symbol) => {
+                if let Some(deposited_at) = symbol.deposited_at {
+                    self.diagnostics.push(ManifestDiagnostic {
+                        position: Position::of(ident),
+                        kind: DiagnosticKind::BucketAlreadyDeposited { name, deposited_at },
+                    });
+                } else if is_deposit && symbol.kind == ValueKind::Bucket {
+                    symbol.deposited_at = Some(Position::of(ident));
+                }
+            },
+            // Globals may be passed as arguments without first assigning them to a variable
+            None if self.globals.contains_key(&name) => {},
+            None => self.diagnostics.push(ManifestDiagnostic {
+                position: Position::of(ident),
+                kind: DiagnosticKind::UndefinedVariable { name },
+            }),
+        }
+    }
+
+    fn define_output(&mut self, invoke: &InvokeIntent, kind: ValueKind) {
+        if let Some(output) = &invoke.output_variable {
+            // Redefining a variable shadows the previous value
+            self.symbols.insert(output.to_string(), Symbol {
+                kind,
+                is_workspace_value: true,
+                deposited_at: None,
+            });
+        }
+    }
+
+    fn report(&mut self, ident: &Ident, kind: DiagnosticKind) {
+        self.diagnostics.push(ManifestDiagnostic {
+            position: Position::of(ident),
+            kind,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+    use syn::parse2;
+    use tari_template_lib::models::{ComponentAddress, ObjectKey, ResourceAddress};
+
+    use super::*;
+    use crate::ast::ManifestAst;
+
+    fn check(input: &str) -> Vec<ManifestDiagnostic> {
+        let globals = HashMap::from([
+            (
+                "account".to_string(),
+                SubstateId::Component(ComponentAddress::new([0u8; ObjectKey::LENGTH].into())).into(),
+            ),
+            (
+                "resource".to_string(),
+                SubstateId::Resource(ResourceAddress::from([1u8; ObjectKey::LENGTH])).into(),
+            ),
+        ]);
+        let ast = parse2::<ManifestAst>(TokenStream::from_str(input).unwrap()).unwrap();
+        ManifestChecker::new(&globals).check(&ast.parsed)
+    }
+
+    fn position(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    #[test]
+    fn it_accepts_a_valid_manifest() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    let resource = global!["resource"];
+    let bucket = account.withdraw(resource, Amount(10));
+    let balance = account.balance(resource);
+    account.deposit(bucket);
+    let bucket = account.withdraw(resource, balance);
+    account.deposit(bucket);
+}"#,
+        );
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn it_reports_undefined_variables() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    account.deposit(bucket);
+    other.deposit(account);
+}"#,
+        );
+        assert_eq!(diagnostics, vec![
+            ManifestDiagnostic {
+                position: position(4, 21),
+                kind: DiagnosticKind::UndefinedVariable {
+                    name: "bucket".to_string()
+                },
+            },
+            ManifestDiagnostic {
+                position: position(5, 5),
+                kind: DiagnosticKind::UndefinedVariable {
+                    name: "other".to_string()
+                },
+            },
+        ]);
+    }
+
+    #[test]
+    fn it_reports_undefined_globals() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let faucet = global!["faucet"];
+}"#,
+        );
+        assert_eq!(diagnostics, vec![ManifestDiagnostic {
+            position: position(3, 9),
+            kind: DiagnosticKind::UndefinedGlobal {
+                name: "faucet".to_string()
+            },
+        }]);
+    }
+
+    #[test]
+    fn it_reports_templates_that_are_not_imported() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let component = Faucet::mint(Amount(10));
+}"#,
+        );
+        assert_eq!(diagnostics, vec![ManifestDiagnostic {
+            position: position(3, 21),
+            kind: DiagnosticKind::TemplateNotImported {
+                name: "Faucet".to_string()
+            },
+        }]);
+    }
+
+    #[test]
+    fn it_reports_buckets_used_after_deposit() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    let bucket = account.withdraw(resource, Amount(10));
+    account.deposit(bucket);
+    account.deposit(bucket);
+}"#,
+        );
+        assert_eq!(diagnostics, vec![ManifestDiagnostic {
+            position: position(6, 21),
+            kind: DiagnosticKind::BucketAlreadyDeposited {
+                name: "bucket".to_string(),
+                deposited_at: position(5, 21),
+            },
+        }]);
+    }
+
+    #[test]
+    fn it_reports_assertions_on_buckets_after_deposit() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    let bucket = account.withdraw(resource, Amount(10));
+    account.deposit(bucket);
+    assert_bucket_contains!(bucket, resource, Amount(10));
+}"#,
+        );
+        assert_eq!(diagnostics, vec![ManifestDiagnostic {
+            position: position(6, 29),
+            kind: DiagnosticKind::BucketAlreadyDeposited {
+                name: "bucket".to_string(),
+                deposited_at: position(5, 21),
+            },
+        }]);
+    }
+
+    #[test]
+    fn it_allows_buckets_to_be_passed_to_other_calls_before_deposit() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    let bucket = account.withdraw(resource, Amount(10));
+    let proof = account.create_proof_for_resource(resource);
+    let other = account.split(bucket, proof);
+    account.deposit(bucket);
+    account.deposit(other);
+}"#,
+        );
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn it_reports_method_calls_on_values_that_are_not_components() {
+        let diagnostics = check(
+            r#"
+fn main() {
+    let mut account = global!["account"];
+    let resource = global!["resource"];
+    let bucket = account.withdraw(resource, Amount(10));
+    bucket.amount();
+    resource.total_supply();
+}"#,
+        );
+        assert_eq!(diagnostics, vec![
+            ManifestDiagnostic {
+                position: position(6, 5),
+                kind: DiagnosticKind::NotAComponent {
+                    name: "bucket".to_string(),
+                    kind: ValueKind::Bucket,
+                    is_workspace_value: true,
+                },
+            },
+            ManifestDiagnostic {
+                position: position(7, 5),
+                kind: DiagnosticKind::NotAComponent {
+                    name: "resource".to_string(),
+                    kind: ValueKind::Resource,
+                    is_workspace_value: false,
+                },
+            },
+        ]);
+    }
+
+    #[test]
+    fn it_checks_fee_main_in_its_own_scope() {
+        let diagnostics = check(
+            r#"
+fn fee_main() {
+    account.pay_fee(Amount(1000));
+}
+
+fn main() {
+    let mut account = global!["account"];
+    account.pay_fee(Amount(1000));
+}"#,
+        );
+        assert_eq!(diagnostics, vec![ManifestDiagnostic {
+            position: position(3, 5),
+            kind: DiagnosticKind::UndefinedVariable {
+                name: "account".to_string()
+            },
+        }]);
+    }
+}
@@ -15,7 +15,7 @@ use tari_template_lib::{
 use crate::{
     ast::ManifestAst,
     error::ManifestError,
-    parser::{InvokeIntent, ManifestIntent, ManifestLiteral, SpecialLiteral},
+    parser::{AssertIntent, InvokeIntent, ManifestIntent, ManifestLiteral, SpecialLiteral},
     ManifestInstructions,
     ManifestValue,
 };
@@ -143,9 +143,50 @@ impl ManifestInstructionGenerator {
                 level: log.level,
                 message: log.message,
             }]),
+            ManifestIntent::Assert(AssertIntent::BucketContains {
+                bucket,
+                resource,
+                min_amount,
+            }) => {
+                let workspace_bucket = bucket.to_string();
+                if !self.variables.contains(&workspace_bucket) {
+                    return Err(ManifestError::UndefinedVariable { name: workspace_bucket });
+                }
+                let resource_address = self
+                    .get_input_address(&resource)?
+                    .as_resource_address()
+                    .ok_or_else(|| {
+                        ManifestError::InvalidVariableType(format!("Expected resource variable but got {}", resource))
+                    })?;
+                Ok(vec![Instruction::AssertBucketContains {
+                    workspace_bucket,
+                    resource_address,
+                    min_amount: Amount(min_amount),
+                }])
+            },
+            ManifestIntent::Assert(AssertIntent::VaultBalanceAtLeast { vault, min_amount }) => {
+                let vault_id = self.get_input_address(&vault)?.as_vault_id().ok_or_else(|| {
+                    ManifestError::InvalidVariableType(format!("Expected vault variable but got {}", vault))
+                })?;
+                Ok(vec![Instruction::AssertVaultBalanceAtLeast {
+                    vault_id,
+                    min_amount: Amount(min_amount),
+                }])
+            },
         }
     }
 
+    /// Returns the address of a global or a variable assigned from a global
+    fn get_input_address(&self, ident: &Ident) -> Result<&SubstateId, ManifestError> {
+        let name = ident.to_string();
+        self.globals
+            .get(&name)
+            .or_else(|| self.global_aliases.get(&name))
+            .ok_or(ManifestError::UndefinedVariable { name })?
+            .as_address()
+            .ok_or_else(|| ManifestError::InvalidVariableType(format!("Expected an address but got {}", ident)))
+    }
+
     fn process_args(&self, args: Vec<ManifestLiteral>) -> Result<Vec<Arg>, ManifestError> {
         args.into_iter()
             .map(|arg| match arg {
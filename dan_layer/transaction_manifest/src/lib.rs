@@ -27,10 +27,14 @@ use syn::parse2;
 use tari_engine_types::{instruction::Instruction, TemplateAddress};
 
 use self::ast::ManifestAst;
-pub use crate::value::ManifestValue;
-use crate::{error::ManifestError, generator::ManifestInstructionGenerator};
+pub use crate::{
+    checker::{DiagnosticKind, ManifestDiagnostic, Position, ValueKind},
+    value::ManifestValue,
+};
+use crate::{checker::ManifestChecker, error::ManifestError, generator::ManifestInstructionGenerator};
 
 mod ast;
+mod checker;
 mod error;
 mod generator;
 mod parser;
@@ -47,6 +51,19 @@ pub fn parse_manifest(
     ManifestInstructionGenerator::new(globals, templates).generate_instructions(ast)
 }
 
+/// Statically checks the manifest without generating instructions. Returns a diagnostic, with its line and column, for
+/// each problem found. An empty result means the manifest passed all checks. Lex and syntax errors are returned as an
+/// error.
+pub fn check_manifest(
+    input: &str,
+    globals: &HashMap<String, ManifestValue>,
+) -> Result<Vec<ManifestDiagnostic>, ManifestError> {
+    let tokens = TokenStream::from_str(input).map_err(|e| ManifestError::LexError(e.to_string()))?;
+    let ast = parse2::<ManifestAst>(tokens)?;
+
+    Ok(ManifestChecker::new(globals).check(&ast.parsed))
+}
+
 pub struct ManifestInstructions {
     pub instructions: Vec<Instruction>,
     pub fee_instructions: Vec<Instruction>,
@@ -3,7 +3,7 @@
 
 use proc_macro2::{Ident, TokenStream};
 use syn::{
-    parse::ParseStream,
+    parse::{ParseStream, Parser},
     parse2,
     punctuated::Punctuated,
     token::Comma,
@@ -38,6 +38,7 @@ pub enum ManifestIntent {
     InvokeComponent(InvokeIntent),
     AssignInput(AssignInputStmt),
     Log(LogIntent),
+    Assert(AssertIntent),
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +68,19 @@ pub struct LogIntent {
     pub message: String,
 }
 
+/// A post-condition that aborts the transaction if it does not hold
+#[derive(Debug, Clone)]
+pub enum AssertIntent {
+    /// `assert_bucket_contains!(bucket, resource, Amount(n));`
+    BucketContains {
+        bucket: Ident,
+        resource: Ident,
+        min_amount: i64,
+    },
+    /// `assert_vault_balance!(vault, Amount(n));`
+    VaultBalanceAtLeast { vault: Ident, min_amount: i64 },
+}
+
 #[derive(Debug, Clone)]
 pub enum ManifestLiteral {
     Lit(Lit),
@@ -348,10 +362,56 @@ fn macro_call(mac: &Ident, tokens: TokenStream) -> Result<ManifestIntent, syn::E
             level: LogLevel::Error,
             message: parse2::<LitStr>(tokens)?.value(),
         })),
+        "assert_bucket_contains" => {
+            let args = assert_arguments(mac, tokens, 3)?;
+            Ok(ManifestIntent::Assert(AssertIntent::BucketContains {
+                bucket: expect_variable(mac, &args[0])?,
+                resource: expect_variable(mac, &args[1])?,
+                min_amount: expect_amount(mac, &args[2])?,
+            }))
+        },
+        "assert_vault_balance" => {
+            let args = assert_arguments(mac, tokens, 2)?;
+            Ok(ManifestIntent::Assert(AssertIntent::VaultBalanceAtLeast {
+                vault: expect_variable(mac, &args[0])?,
+                min_amount: expect_amount(mac, &args[1])?,
+            }))
+        },
         _ => Err(syn::Error::new_spanned(mac, "Invalid macro name")),
     }
 }
 
+fn assert_arguments(mac: &Ident, tokens: TokenStream, expected: usize) -> Result<Vec<ManifestLiteral>, syn::Error> {
+    let args = build_arguments(Punctuated::<Expr, Comma>::parse_terminated.parse2(tokens)?)?;
+    if args.len() != expected {
+        return Err(syn::Error::new_spanned(
+            mac,
+            format!("{} expects {} arguments but got {}", mac, expected, args.len()),
+        ));
+    }
+    Ok(args)
+}
+
+fn expect_variable(mac: &Ident, arg: &ManifestLiteral) -> Result<Ident, syn::Error> {
+    match arg {
+        ManifestLiteral::Variable(ident) => Ok(ident.clone()),
+        _ => Err(syn::Error::new_spanned(
+            mac,
+            format!("Invalid argument to {}, expected a variable", mac),
+        )),
+    }
+}
+
+fn expect_amount(mac: &Ident, arg: &ManifestLiteral) -> Result<i64, syn::Error> {
+    match arg {
+        ManifestLiteral::Special(SpecialLiteral::Amount(amount)) => Ok(*amount),
+        _ => Err(syn::Error::new_spanned(
+            mac,
+            format!("Invalid argument to {}, expected Amount(n)", mac),
+        )),
+    }
+}
+
 fn build_arguments(args: Punctuated<Expr, Comma>) -> Result<Vec<ManifestLiteral>, syn::Error> {
     args.into_iter()
         .map(|arg| match arg {
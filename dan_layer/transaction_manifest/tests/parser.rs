@@ -25,9 +25,9 @@ use std::{collections::HashMap, fs};
 use tari_engine_types::{instruction::Instruction, substate::SubstateId};
 use tari_template_lib::{
     args,
-    models::{Amount, ComponentAddress, ObjectKey, ResourceAddress, TemplateAddress},
+    models::{Amount, ComponentAddress, ObjectKey, ResourceAddress, TemplateAddress, VaultId},
 };
-use tari_transaction_manifest::{parse_manifest, ManifestInstructions};
+use tari_transaction_manifest::{check_manifest, parse_manifest, ManifestInstructions};
 
 #[test]
 #[allow(clippy::too_many_lines)]
@@ -103,3 +103,65 @@ fn manifest_smoke_test() {
     assert_eq!(instructions, expected);
     assert_eq!(fee_instructions, vec![]);
 }
+
+#[test]
+fn example_manifest_passes_checks() {
+    let input = fs::read_to_string("tests/examples/picture_seller.rs").unwrap();
+    let globals = HashMap::from([
+        (
+            "account".to_string(),
+            SubstateId::Component(ComponentAddress::new([0u8; ObjectKey::LENGTH].into())).into(),
+        ),
+        (
+            "picture_seller_addr".to_string(),
+            SubstateId::Component(ComponentAddress::new([1u8; ObjectKey::LENGTH].into())).into(),
+        ),
+        (
+            "test_faucet".to_string(),
+            SubstateId::Component(ComponentAddress::new([2u8; ObjectKey::LENGTH].into())).into(),
+        ),
+        (
+            "xtr_resource".to_string(),
+            SubstateId::Resource(ResourceAddress::from([3u8; ObjectKey::LENGTH])).into(),
+        ),
+    ]);
+
+    let diagnostics = check_manifest(&input, &globals).unwrap();
+    assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+}
+
+#[test]
+fn it_generates_assertion_instructions() {
+    let input = r#"
+        fn main() {
+            let account = global!["account"];
+            let xtr = global!["xtr_resource"];
+            let bucket = account.withdraw(xtr, Amount(100));
+            assert_bucket_contains!(bucket, xtr, Amount(100));
+            account.deposit(bucket);
+            assert_vault_balance!(account_vault, Amount(50));
+        }
+    "#;
+    let account_component = ComponentAddress::new([0u8; ObjectKey::LENGTH].into());
+    let xtr_resource = ResourceAddress::from([3u8; ObjectKey::LENGTH]);
+    let account_vault = VaultId::from(ObjectKey::from([4u8; ObjectKey::LENGTH]));
+    let globals = HashMap::from([
+        ("account".to_string(), SubstateId::Component(account_component).into()),
+        ("xtr_resource".to_string(), SubstateId::Resource(xtr_resource).into()),
+        ("account_vault".to_string(), SubstateId::Vault(account_vault).into()),
+    ]);
+
+    let diagnostics = check_manifest(input, &globals).unwrap();
+    assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+
+    let ManifestInstructions { instructions, .. } = parse_manifest(input, globals, Default::default()).unwrap();
+    assert_eq!(instructions[2], Instruction::AssertBucketContains {
+        workspace_bucket: "bucket".to_string(),
+        resource_address: xtr_resource,
+        min_amount: Amount(100),
+    });
+    assert_eq!(instructions[4], Instruction::AssertVaultBalanceAtLeast {
+        vault_id: account_vault,
+        min_amount: Amount(50),
+    });
+}
@@ -42,4 +42,13 @@ pub trait ValidatorNodeRpcService: Send + Sync + 'static {
         &self,
         request: Request<proto::GetHighQcRequest>,
     ) -> Result<Response<proto::GetHighQcResponse>, RpcStatus>;
+
+    /// Streams the diffs of committed blocks in commit order, starting after the given cursor, and then follows new
+    /// commits. The stream ends if no block is committed within the client deadline, after which the client resumes
+    /// from the cursor of the last diff that it received.
+    #[rpc(method = 7)]
+    async fn stream_committed_block_diffs(
+        &self,
+        request: Request<proto::StreamCommittedBlockDiffsRequest>,
+    ) -> Result<Streaming<proto::CommittedBlockDiff>, RpcStatus>;
 }
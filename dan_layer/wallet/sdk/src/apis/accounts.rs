@@ -1,15 +1,18 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::collections::HashMap;
+
 use tari_dan_common_types::optional::{IsNotFoundError, Optional};
-use tari_engine_types::substate::SubstateId;
+use tari_engine_types::{substate::SubstateId, vault::Vault};
 use tari_template_lib::{
     models::{Amount, ResourceAddress},
     prelude::ResourceType,
 };
+use tari_transaction::TransactionId;
 
 use crate::{
-    models::{Account, VaultBalance, VaultModel},
+    models::{Account, AccountVaultInfo, VaultBalance, VaultModel},
     storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
 };
 
@@ -192,6 +195,48 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
         let vaults = tx.vaults_get_by_account(account)?;
         Ok(vaults)
     }
+
+    /// Returns a breakdown of every vault in the account. `latest_vaults` contains the latest vault substates (and
+    /// the transaction that produced them) as scanned from the network. Vaults that were not scanned fall back to the
+    /// balance and last transaction recorded in the wallet.
+    pub fn get_vault_breakdown(
+        &self,
+        account: &SubstateId,
+        latest_vaults: &HashMap<SubstateId, (Vault, TransactionId)>,
+    ) -> Result<Vec<AccountVaultInfo>, AccountsApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let vaults = tx.vaults_get_by_account(account)?;
+        let mut breakdown = Vec::with_capacity(vaults.len());
+        for vault in vaults {
+            let info = match latest_vaults.get(&vault.address) {
+                Some((latest, transaction_id)) => AccountVaultInfo {
+                    vault_address: vault.address,
+                    resource_address: *latest.resource_address(),
+                    resource_type: latest.resource_type(),
+                    revealed_balance: latest.balance(),
+                    confidential_commitment_count: latest.get_commitment_count(),
+                    last_updated_transaction: Some(*transaction_id),
+                },
+                None => {
+                    let last_updated_transaction = tx
+                        .substates_get(&vault.address)
+                        .optional()?
+                        .map(|s| s.transaction_hash.into_array().into());
+                    AccountVaultInfo {
+                        vault_address: vault.address,
+                        resource_address: vault.resource_address,
+                        resource_type: vault.resource_type,
+                        revealed_balance: vault.revealed_balance,
+                        // Commitments are only known from the vault substate
+                        confidential_commitment_count: 0,
+                        last_updated_transaction,
+                    }
+                },
+            };
+            breakdown.push(info);
+        }
+        Ok(breakdown)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
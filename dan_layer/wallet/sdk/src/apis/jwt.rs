@@ -166,7 +166,7 @@ pub struct AuthClaims {
 }
 
 impl<'a, TStore: WalletStore> JwtApi<'a, TStore> {
-    pub(crate) fn new(store: &'a TStore, default_expiry: Duration, secret_key: String) -> Self {
+    pub fn new(store: &'a TStore, default_expiry: Duration, secret_key: String) -> Self {
         Self {
             store,
             default_expiry,
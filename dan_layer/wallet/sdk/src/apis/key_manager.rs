@@ -23,11 +23,16 @@ pub const VIEW_KEY_BRANCH: &str = "view_key";
 pub struct KeyManagerApi<'a, TStore> {
     store: &'a TStore,
     cipher_seed: &'a CipherSeed,
+    key_domain: Option<&'a str>,
 }
 
 impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
-    pub(crate) fn new(store: &'a TStore, cipher_seed: &'a CipherSeed) -> Self {
-        Self { store, cipher_seed }
+    pub(crate) fn new(store: &'a TStore, cipher_seed: &'a CipherSeed, key_domain: Option<&'a str>) -> Self {
+        Self {
+            store,
+            cipher_seed,
+            key_domain,
+        }
     }
 
     pub fn get_or_create_initial(&self, branch: &str) -> Result<(), KeyManagerApiError> {
@@ -68,12 +73,12 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
     pub fn next_key(&self, branch: &str) -> Result<DerivedKey<RistrettoPublicKey>, KeyManagerApiError> {
         let mut tx = self.store.create_write_tx()?;
         let index = tx.key_manager_get_last_index(branch).optional()?.unwrap_or(0);
-        let mut key_manager = WalletKeyManager::from(self.cipher_seed.clone(), branch.to_string(), index);
+        let mut key_manager = self.get_key_manager(branch, index);
         let key = key_manager
             .next_key()
             // TODO: Key manager shouldn't return other errors
             .map_err(tari_key_manager::error::KeyManagerError::from)?;
-        tx.key_manager_insert(&key_manager.branch_seed, key_manager.key_index())?;
+        tx.key_manager_insert(branch, key_manager.key_index())?;
         tx.commit()?;
         Ok(key)
     }
@@ -145,7 +150,12 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
     }
 
     fn get_key_manager(&self, branch: &str, index: u64) -> WalletKeyManager {
-        KeyManager::from(self.cipher_seed.clone(), branch.to_string(), index)
+        // The branch is stored untagged, the domain tag only affects derivation
+        let branch_seed = match self.key_domain {
+            Some(domain) => format!("{}.{}", domain, branch),
+            None => branch.to_string(),
+        };
+        KeyManager::from(self.cipher_seed.clone(), branch_seed, index)
     }
 }
 
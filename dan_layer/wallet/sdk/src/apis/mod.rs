@@ -9,5 +9,7 @@ pub mod config;
 pub mod jwt;
 pub mod key_manager;
 pub mod non_fungible_tokens;
+pub mod scheduled_transaction;
 pub mod substate;
 pub mod transaction;
+pub mod transaction_queue;
@@ -0,0 +1,189 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    Epoch,
+};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, UnsignedTransaction};
+
+use crate::{
+    apis::key_manager::{KeyManagerApi, KeyManagerApiError, TRANSACTION_BRANCH},
+    models::{ScheduleTrigger, ScheduledTransaction, ScheduledTransactionStatus},
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::scheduled_transaction";
+
+/// Maintains transactions that are scheduled to be submitted once a trigger (a wall-clock time or an epoch) fires.
+/// Scheduled transactions are stored unsigned. When released, the input versions are re-resolved against the current
+/// substates known to the wallet and the transaction is signed.
+pub struct ScheduledTransactionApi<'a, TStore> {
+    store: &'a TStore,
+    key_manager_api: KeyManagerApi<'a, TStore>,
+}
+
+impl<'a, TStore> ScheduledTransactionApi<'a, TStore>
+where TStore: WalletStore
+{
+    pub fn new(store: &'a TStore, key_manager_api: KeyManagerApi<'a, TStore>) -> Self {
+        Self { store, key_manager_api }
+    }
+
+    pub fn schedule(
+        &self,
+        transaction: &UnsignedTransaction,
+        required_substates: &[SubstateRequirement],
+        signing_key_index: u64,
+        trigger: ScheduleTrigger,
+    ) -> Result<u64, ScheduledTransactionApiError> {
+        let id = self.store.with_write_tx(|tx| {
+            tx.scheduled_transactions_insert(transaction, required_substates, signing_key_index, trigger)
+        })?;
+        info!(target: LOG_TARGET, "Scheduled transaction {} to be released {}", id, trigger);
+        Ok(id)
+    }
+
+    pub fn get(&self, id: u64) -> Result<ScheduledTransaction, ScheduledTransactionApiError> {
+        let scheduled = self.store.with_read_tx(|tx| tx.scheduled_transactions_get(id))?;
+        Ok(scheduled)
+    }
+
+    pub fn list(
+        &self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, ScheduledTransactionApiError> {
+        let scheduled = self
+            .store
+            .with_read_tx(|tx| tx.scheduled_transactions_fetch_all(status))?;
+        Ok(scheduled)
+    }
+
+    /// Cancels a scheduled transaction. A transaction can only be cancelled before its trigger has fired.
+    pub fn cancel(&self, id: u64) -> Result<(), ScheduledTransactionApiError> {
+        self.store.with_write_tx(|tx| {
+            let scheduled = tx.scheduled_transactions_get(id)?;
+            if scheduled.status != ScheduledTransactionStatus::Scheduled {
+                return Err(ScheduledTransactionApiError::NotScheduled {
+                    id,
+                    status: scheduled.status,
+                });
+            }
+            tx.scheduled_transactions_set_status(id, ScheduledTransactionStatus::Cancelled, None, None)?;
+            Ok(())
+        })?;
+        info!(target: LOG_TARGET, "Cancelled scheduled transaction {}", id);
+        Ok(())
+    }
+
+    /// Returns the scheduled transactions whose trigger has fired, in the order that they were scheduled
+    pub fn get_due(
+        &self,
+        now_timestamp: u64,
+        current_epoch: Option<Epoch>,
+    ) -> Result<Vec<ScheduledTransaction>, ScheduledTransactionApiError> {
+        let due = self
+            .list(Some(ScheduledTransactionStatus::Scheduled))?
+            .into_iter()
+            .filter(|scheduled| scheduled.trigger.is_due(now_timestamp, current_epoch))
+            .collect();
+        Ok(due)
+    }
+
+    /// Prepares a scheduled transaction for submission. The versions of the inputs and required substates are updated
+    /// to the latest versions known to the wallet, since the substates may have changed since the transaction was
+    /// scheduled, and the transaction is signed. The signed transaction and required substates are returned.
+    pub fn prepare_release(
+        &self,
+        scheduled: &ScheduledTransaction,
+    ) -> Result<(Transaction, Vec<SubstateRequirement>), ScheduledTransactionApiError> {
+        if scheduled.status != ScheduledTransactionStatus::Scheduled {
+            return Err(ScheduledTransactionApiError::NotScheduled {
+                id: scheduled.id,
+                status: scheduled.status,
+            });
+        }
+
+        let (transaction, required_substates) = self.store.with_read_tx(|tx| {
+            let mut transaction = scheduled.transaction.clone();
+            transaction.inputs = transaction
+                .inputs
+                .into_iter()
+                .map(|input| resolve_current_version(tx, input))
+                .collect::<Result<_, _>>()?;
+            let required_substates = scheduled
+                .required_substates
+                .iter()
+                .map(|requirement| resolve_current_version(tx, requirement.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, WalletStorageError>((transaction, required_substates))
+        })?;
+
+        let key = self
+            .key_manager_api
+            .derive_key(TRANSACTION_BRANCH, scheduled.signing_key_index)?;
+        let transaction = Transaction::builder()
+            .with_unsigned_transaction(transaction)
+            .sign(&key.key)
+            .build();
+
+        Ok((transaction, required_substates))
+    }
+
+    pub fn mark_submitted(&self, id: u64, transaction_id: TransactionId) -> Result<(), ScheduledTransactionApiError> {
+        self.store.with_write_tx(|tx| {
+            tx.scheduled_transactions_set_status(id, ScheduledTransactionStatus::Submitted, Some(transaction_id), None)
+        })?;
+        info!(
+            target: LOG_TARGET,
+            "Scheduled transaction {} released as transaction {}", id, transaction_id
+        );
+        Ok(())
+    }
+
+    pub fn mark_failed(&self, id: u64, reason: &str) -> Result<(), ScheduledTransactionApiError> {
+        self.store.with_write_tx(|tx| {
+            tx.scheduled_transactions_set_status(id, ScheduledTransactionStatus::Failed, None, Some(reason))
+        })?;
+        warn!(target: LOG_TARGET, "Scheduled transaction {} failed: {}", id, reason);
+        Ok(())
+    }
+}
+
+/// Returns the requirement with the version of the substate currently known to the wallet. Requirements without a
+/// version and substates that the wallet does not know about are returned unchanged.
+fn resolve_current_version<TTx: WalletStoreReader + ?Sized>(
+    tx: &mut TTx,
+    requirement: SubstateRequirement,
+) -> Result<SubstateRequirement, WalletStorageError> {
+    if requirement.version().is_none() {
+        return Ok(requirement);
+    }
+    let Some(substate) = tx.substates_get(requirement.substate_id()).optional()? else {
+        return Ok(requirement);
+    };
+    Ok(SubstateRequirement::with_version(
+        substate.address.substate_id,
+        substate.address.version,
+    ))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduledTransactionApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("Key manager error: {0}")]
+    KeyManagerError(#[from] KeyManagerApiError),
+    #[error("Scheduled transaction {id} cannot be changed because it is {status}")]
+    NotScheduled {
+        id: u64,
+        status: ScheduledTransactionStatus,
+    },
+}
+
+impl IsNotFoundError for ScheduledTransactionApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
@@ -4,13 +4,20 @@
 use std::collections::HashMap;
 
 use log::*;
+use tari_common_types::types::{PublicKey, Signature};
 use tari_dan_common_types::optional::{IsNotFoundError, Optional};
 use tari_engine_types::{
     indexed_value::{IndexedValueError, IndexedWellKnownTypes},
     substate::SubstateDiff,
 };
 use tari_template_lib::prelude::ComponentAddress;
-use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
+use tari_transaction::{
+    SubstateRequirement,
+    Transaction,
+    TransactionId,
+    TransactionSignature,
+    UnsignedTransaction,
+};
 
 use crate::{
     models::{NewAccountInfo, TransactionStatus, VersionedSubstateId, WalletTransaction},
@@ -59,6 +66,98 @@ where
         Ok(tx_id)
     }
 
+    /// Stores a transaction that is waiting for a signature from an external signer. The returned id is provisional
+    /// and is replaced by the id of the signed transaction once `apply_signature` is called.
+    pub fn insert_pending_signature(
+        &self,
+        transaction: UnsignedTransaction,
+        signer_public_key: PublicKey,
+        required_substates: Vec<SubstateRequirement>,
+        new_account_info: Option<NewAccountInfo>,
+        is_dry_run: bool,
+    ) -> Result<TransactionId, TransactionApiError> {
+        let transaction = Transaction::builder()
+            .with_unsigned_transaction(transaction)
+            .with_signature(TransactionSignature::new(signer_public_key, Signature::default()))
+            .build();
+        let tx_id = *transaction.id();
+        self.store.with_write_tx(|tx| {
+            tx.transactions_insert(&transaction, &required_substates, new_account_info.as_ref(), is_dry_run)?;
+            tx.transactions_set_result_and_status(
+                tx_id,
+                None,
+                None,
+                None,
+                TransactionStatus::PendingSignature,
+                None,
+                None,
+            )
+        })?;
+
+        Ok(tx_id)
+    }
+
+    /// Applies the signature to a transaction that is pending a signature. The signed transaction is returned and is
+    /// stored with the New status, ready to be submitted.
+    pub fn apply_signature(
+        &self,
+        pending_transaction_id: TransactionId,
+        signature: TransactionSignature,
+    ) -> Result<Transaction, TransactionApiError> {
+        let pending = self.store.with_read_tx(|tx| tx.transactions_get(pending_transaction_id))?;
+        if pending.status != TransactionStatus::PendingSignature {
+            return Err(TransactionApiError::NotPendingSignature {
+                transaction_id: pending_transaction_id,
+                status: pending.status,
+            });
+        }
+
+        let unsigned_transaction = UnsignedTransaction::from(&pending.transaction);
+        let is_valid = signature.public_key() == pending.transaction.signer_public_key() &&
+            signature.verify(&unsigned_transaction);
+        if !is_valid {
+            return Err(TransactionApiError::InvalidSignature {
+                transaction_id: pending_transaction_id,
+            });
+        }
+
+        let transaction = Transaction::builder()
+            .with_unsigned_transaction(unsigned_transaction)
+            .with_signature(signature)
+            .build();
+        self.store
+            .with_write_tx(|tx| tx.transactions_set_signed(pending_transaction_id, &transaction))?;
+
+        Ok(transaction)
+    }
+
+    /// Marks a transaction that did not receive a signature as invalid
+    pub fn abandon_pending_signature(&self, pending_transaction_id: TransactionId) -> Result<(), TransactionApiError> {
+        self.store.with_write_tx(|tx| {
+            tx.transactions_set_result_and_status(
+                pending_transaction_id,
+                None,
+                None,
+                None,
+                TransactionStatus::InvalidTransaction,
+                None,
+                None,
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Marks all transactions that are pending a signature as invalid, returning their ids. This is used at startup
+    /// because the signing requests of these transactions did not survive the restart.
+    pub fn abandon_all_pending_signatures(&self) -> Result<Vec<TransactionId>, TransactionApiError> {
+        let pending = self.fetch_all(Some(TransactionStatus::PendingSignature), None)?;
+        let transaction_ids = pending.iter().map(|t| *t.transaction.id()).collect::<Vec<_>>();
+        for transaction_id in &transaction_ids {
+            self.abandon_pending_signature(*transaction_id)?;
+        }
+        Ok(transaction_ids)
+    }
+
     pub async fn submit_transaction(&self, transaction_id: TransactionId) -> Result<(), TransactionApiError> {
         let transaction = self.store.with_read_tx(|tx| tx.transactions_get(transaction_id))?;
 
@@ -359,6 +458,13 @@ pub enum TransactionApiError {
     IndexedValueError(#[from] IndexedValueError),
     #[error("Invalid transaction query response: {details}")]
     InvalidTransactionQueryResponse { details: String },
+    #[error("Transaction {transaction_id} is not pending a signature (status: {status})")]
+    NotPendingSignature {
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+    },
+    #[error("Invalid signature for transaction {transaction_id}")]
+    InvalidSignature { transaction_id: TransactionId },
 }
 
 impl IsNotFoundError for TransactionApiError {
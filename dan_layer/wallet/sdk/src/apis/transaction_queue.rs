@@ -0,0 +1,235 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_engine_types::{instruction::Instruction, substate::SubstateId};
+use tari_transaction::{Transaction, TransactionId};
+
+use crate::{
+    models::{AccountTransactionQueue, QueuedTransactionStatus, TransactionStatus},
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::transaction_queue";
+
+/// Maintains the opt-in ordered transaction queues of accounts. Transactions that are added to a queue are held in the
+/// Queued status and are released one at a time, in the order that they were added, once the previous transaction has
+/// been finalized successfully or skipped.
+pub struct TransactionQueueApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore> TransactionQueueApi<'a, TStore>
+where TStore: WalletStore
+{
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    pub fn enable(&self, account: &SubstateId) -> Result<(), TransactionQueueApiError> {
+        self.store.with_write_tx(|tx| {
+            if tx.transaction_queue_get(account).optional()?.is_none() {
+                tx.transaction_queue_enable(account)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Disables ordered transactions for the account. This fails if there are transactions in the queue that have not
+    /// been finalized or skipped.
+    pub fn disable(&self, account: &SubstateId) -> Result<(), TransactionQueueApiError> {
+        self.store.with_write_tx(|tx| {
+            let Some(queue) = tx.transaction_queue_get(account).optional()? else {
+                return Ok(());
+            };
+            if !queue.entries.is_empty() {
+                return Err(TransactionQueueApiError::QueueNotEmpty {
+                    account: account.clone(),
+                    num_entries: queue.entries.len(),
+                });
+            }
+            tx.transaction_queue_disable(account)?;
+            Ok(())
+        })
+    }
+
+    pub fn get(&self, account: &SubstateId) -> Result<AccountTransactionQueue, TransactionQueueApiError> {
+        let queue = self.store.with_read_tx(|tx| tx.transaction_queue_get(account))?;
+        Ok(queue)
+    }
+
+    pub fn get_enabled_accounts(&self) -> Result<Vec<SubstateId>, TransactionQueueApiError> {
+        let accounts = self
+            .store
+            .with_read_tx(|tx| tx.transaction_queue_get_enabled_accounts())?;
+        Ok(accounts)
+    }
+
+    /// Returns the account with ordered transactions enabled that the transaction belongs to, if any. This is the
+    /// first such account that the transaction calls, starting with the fee instructions.
+    pub fn find_queue_account(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Option<SubstateId>, TransactionQueueApiError> {
+        let enabled_accounts = self.get_enabled_accounts()?;
+        if enabled_accounts.is_empty() {
+            return Ok(None);
+        }
+
+        let account = transaction
+            .fee_instructions()
+            .iter()
+            .chain(transaction.instructions())
+            .filter_map(|instruction| match instruction {
+                Instruction::CallMethod { component_address, .. } => Some(SubstateId::Component(*component_address)),
+                _ => None,
+            })
+            .find(|address| enabled_accounts.contains(address));
+        Ok(account)
+    }
+
+    /// Adds a new transaction to the back of the account queue and holds it in the Queued status. The sequence number
+    /// of the transaction is returned.
+    pub fn enqueue(
+        &self,
+        account: &SubstateId,
+        transaction_id: TransactionId,
+    ) -> Result<u64, TransactionQueueApiError> {
+        let sequence = self.store.with_write_tx(|tx| {
+            let sequence = tx.transaction_queue_push(account, transaction_id)?;
+            tx.transactions_set_result_and_status(
+                transaction_id,
+                None,
+                None,
+                None,
+                TransactionStatus::Queued,
+                None,
+                None,
+            )?;
+            Ok::<_, TransactionQueueApiError>(sequence)
+        })?;
+        debug!(
+            target: LOG_TARGET,
+            "Transaction {} queued for account {} with sequence {}", transaction_id, account, sequence
+        );
+        Ok(sequence)
+    }
+
+    /// Releases the transaction at the front of the queue if it is waiting to be submitted and the queue is not
+    /// paused. The released transaction is set to the New status so that it can be submitted.
+    pub fn release_next(&self, account: &SubstateId) -> Result<Option<TransactionId>, TransactionQueueApiError> {
+        self.store.with_write_tx(|tx| {
+            let queue = tx.transaction_queue_get(account)?;
+            if queue.is_paused() {
+                return Ok(None);
+            }
+            let Some(head) = queue.head().filter(|head| head.status == QueuedTransactionStatus::Queued) else {
+                return Ok(None);
+            };
+
+            tx.transaction_queue_set_status(head.transaction_id, QueuedTransactionStatus::Submitted)?;
+            tx.transactions_set_result_and_status(
+                head.transaction_id,
+                None,
+                None,
+                None,
+                TransactionStatus::New,
+                None,
+                None,
+            )?;
+            Ok(Some(head.transaction_id))
+        })
+    }
+
+    /// Updates the queue containing the transaction once the transaction has been finalized. If the transaction was
+    /// not accepted, the queue is paused. The updated queue is returned, or None if the transaction is not queued.
+    pub fn on_transaction_finalized(
+        &self,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+    ) -> Result<Option<AccountTransactionQueue>, TransactionQueueApiError> {
+        self.store.with_write_tx(|tx| {
+            let Some(account) = tx
+                .transaction_queue_get_account_by_transaction(transaction_id)
+                .optional()?
+            else {
+                return Ok(None);
+            };
+
+            if status == TransactionStatus::Accepted {
+                tx.transaction_queue_set_status(transaction_id, QueuedTransactionStatus::Finalized)?;
+            } else {
+                tx.transaction_queue_set_status(transaction_id, QueuedTransactionStatus::Failed)?;
+                let reason = format!("Transaction {} finalized with status {}", transaction_id, status);
+                warn!(target: LOG_TARGET, "Pausing transaction queue for account {}: {}", account, reason);
+                tx.transaction_queue_set_paused(&account, Some(&reason))?;
+            }
+
+            let queue = tx.transaction_queue_get(&account)?;
+            Ok(Some(queue))
+        })
+    }
+
+    /// Skips the transaction at the front of the queue and resumes the queue if it was paused. A transaction that has
+    /// been submitted and is waiting to be finalized cannot be skipped. A skipped transaction that was never submitted
+    /// is marked as invalid. The id of the skipped transaction is returned.
+    pub fn skip(&self, account: &SubstateId) -> Result<TransactionId, TransactionQueueApiError> {
+        self.store.with_write_tx(|tx| {
+            let queue = tx.transaction_queue_get(account)?;
+            let head = queue
+                .head()
+                .ok_or_else(|| TransactionQueueApiError::QueueEmpty {
+                    account: account.clone(),
+                })?;
+
+            match head.status {
+                QueuedTransactionStatus::Submitted => {
+                    return Err(TransactionQueueApiError::CannotSkipSubmittedTransaction {
+                        transaction_id: head.transaction_id,
+                    });
+                },
+                QueuedTransactionStatus::Queued => {
+                    tx.transactions_set_result_and_status(
+                        head.transaction_id,
+                        None,
+                        None,
+                        None,
+                        TransactionStatus::InvalidTransaction,
+                        None,
+                        None,
+                    )?;
+                },
+                QueuedTransactionStatus::Failed |
+                QueuedTransactionStatus::Finalized |
+                QueuedTransactionStatus::Skipped => {},
+            }
+
+            tx.transaction_queue_set_status(head.transaction_id, QueuedTransactionStatus::Skipped)?;
+            tx.transaction_queue_set_paused(account, None)?;
+            info!(
+                target: LOG_TARGET,
+                "Skipped transaction {} in the queue for account {}", head.transaction_id, account
+            );
+            Ok(head.transaction_id)
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionQueueApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("The transaction queue for account {account} has {num_entries} unresolved transaction(s)")]
+    QueueNotEmpty { account: SubstateId, num_entries: usize },
+    #[error("The transaction queue for account {account} is empty")]
+    QueueEmpty { account: SubstateId },
+    #[error("Transaction {transaction_id} has been submitted and cannot be skipped")]
+    CannotSkipSubmittedTransaction { transaction_id: TransactionId },
+}
+
+impl IsNotFoundError for TransactionQueueApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
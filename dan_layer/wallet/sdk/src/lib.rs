@@ -9,6 +9,7 @@ mod sdk;
 
 pub use sdk::{DanWalletSdk, WalletSdkConfig};
 pub mod network;
+pub mod signer;
 
 pub use tari_key_manager::cipher_seed::CipherSeed;
 
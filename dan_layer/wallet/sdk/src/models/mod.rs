@@ -24,3 +24,9 @@ pub use vault::*;
 
 mod non_fungible_tokens;
 pub use non_fungible_tokens::*;
+
+mod transaction_queue;
+pub use transaction_queue::*;
+
+mod scheduled_transaction;
+pub use scheduled_transaction::*;
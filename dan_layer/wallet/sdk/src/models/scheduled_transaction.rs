@@ -0,0 +1,107 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::Epoch;
+use tari_transaction::{SubstateRequirement, TransactionId, UnsignedTransaction};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+/// A transaction that is held by the wallet until its trigger fires. The transaction is signed and its input versions
+/// are resolved when it is released.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ScheduledTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    pub trigger: ScheduleTrigger,
+    pub transaction: UnsignedTransaction,
+    pub required_substates: Vec<SubstateRequirement>,
+    /// The index of the transaction key used to sign the transaction on release
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub signing_key_index: u64,
+    pub status: ScheduledTransactionStatus,
+    /// The id of the transaction that was submitted when the trigger fired
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub transaction_id: Option<TransactionId>,
+    /// Set if the transaction could not be released
+    pub failure_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum ScheduleTrigger {
+    /// Fires at or after the given unix timestamp in seconds
+    Time {
+        #[cfg_attr(feature = "ts", ts(type = "number"))]
+        timestamp: u64,
+    },
+    /// Fires once the network has reached the given epoch
+    Epoch { epoch: Epoch },
+}
+
+impl ScheduleTrigger {
+    /// Returns true if the trigger has fired. Epoch triggers cannot fire until the current epoch is known.
+    pub fn is_due(&self, now_timestamp: u64, current_epoch: Option<Epoch>) -> bool {
+        match self {
+            Self::Time { timestamp } => now_timestamp >= *timestamp,
+            Self::Epoch { epoch } => current_epoch.is_some_and(|current| current >= *epoch),
+        }
+    }
+}
+
+impl Display for ScheduleTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Time { timestamp } => write!(f, "at time {}", timestamp),
+            Self::Epoch { epoch } => write!(f, "at {}", epoch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum ScheduledTransactionStatus {
+    /// Waiting for the trigger to fire
+    Scheduled,
+    /// The trigger fired and the transaction was submitted
+    Submitted,
+    /// The trigger fired but the transaction could not be signed or submitted
+    Failed,
+    /// Removed from the schedule by the user before the trigger fired
+    Cancelled,
+}
+
+impl ScheduledTransactionStatus {
+    pub fn as_key_str(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "Scheduled",
+            Self::Submitted => "Submitted",
+            Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl FromStr for ScheduledTransactionStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Scheduled" => Ok(Self::Scheduled),
+            "Submitted" => Ok(Self::Submitted),
+            "Failed" => Ok(Self::Failed),
+            "Cancelled" => Ok(Self::Cancelled),
+            _ => Err(anyhow!("Invalid ScheduledTransactionStatus: {}", s)),
+        }
+    }
+}
+
+impl Display for ScheduledTransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_key_str())
+    }
+}
@@ -0,0 +1,101 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+/// The ordered transaction queue of an account. Transactions in the queue are released to the network one at a time
+/// in sequence order, each only after the previous transaction has been finalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct AccountTransactionQueue {
+    pub account_address: SubstateId,
+    /// The sequence number that will be assigned to the next transaction added to the queue
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub next_sequence: u64,
+    /// Set if the queue was paused because a transaction failed. The queue resumes once the failed transaction is
+    /// skipped.
+    pub paused_reason: Option<String>,
+    /// The transactions that have not been finalized successfully or skipped, ordered by sequence number
+    pub entries: Vec<QueuedTransaction>,
+}
+
+impl AccountTransactionQueue {
+    pub fn is_paused(&self) -> bool {
+        self.paused_reason.is_some()
+    }
+
+    /// Returns the transaction at the front of the queue
+    pub fn head(&self) -> Option<&QueuedTransaction> {
+        self.entries.first()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct QueuedTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub sequence: u64,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub status: QueuedTransactionStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum QueuedTransactionStatus {
+    /// Waiting for the previous transactions in the queue to be finalized
+    Queued,
+    /// Released to the network and waiting to be finalized
+    Submitted,
+    /// Finalized and accepted
+    Finalized,
+    /// Finalized but not accepted. The queue is paused until this transaction is skipped.
+    Failed,
+    /// Removed from the queue by the user
+    Skipped,
+}
+
+impl QueuedTransactionStatus {
+    /// Returns true if the transaction no longer holds up the queue
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, Self::Finalized | Self::Skipped)
+    }
+
+    pub fn as_key_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "Queued",
+            Self::Submitted => "Submitted",
+            Self::Finalized => "Finalized",
+            Self::Failed => "Failed",
+            Self::Skipped => "Skipped",
+        }
+    }
+}
+
+impl FromStr for QueuedTransactionStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Queued" => Ok(Self::Queued),
+            "Submitted" => Ok(Self::Submitted),
+            "Finalized" => Ok(Self::Finalized),
+            "Failed" => Ok(Self::Failed),
+            "Skipped" => Ok(Self::Skipped),
+            _ => Err(anyhow!("Invalid QueuedTransactionStatus: {}", s)),
+        }
+    }
+}
+
+impl Display for QueuedTransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_key_str())
+    }
+}
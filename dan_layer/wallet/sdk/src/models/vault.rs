@@ -6,6 +6,7 @@ use tari_template_lib::{
     models::{Amount, ResourceAddress},
     resource::ResourceType,
 };
+use tari_transaction::TransactionId;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct VaultModel {
@@ -31,3 +32,14 @@ pub struct VaultBalance {
     pub confidential: Amount,
     pub revealed: Amount,
 }
+
+/// Per-resource breakdown of a single account vault
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AccountVaultInfo {
+    pub vault_address: SubstateId,
+    pub resource_address: ResourceAddress,
+    pub resource_type: ResourceType,
+    pub revealed_balance: Amount,
+    pub confidential_commitment_count: u32,
+    pub last_updated_transaction: Option<TransactionId>,
+}
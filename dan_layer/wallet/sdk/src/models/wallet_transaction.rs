@@ -35,6 +35,10 @@ pub struct WalletTransaction {
 pub enum TransactionStatus {
     #[default]
     New,
+    /// The transaction has been built but is waiting for a signature from an external signer
+    PendingSignature,
+    /// The transaction is waiting in the ordered transaction queue of its account
+    Queued,
     DryRun,
     Pending,
     Accepted,
@@ -47,6 +51,8 @@ impl TransactionStatus {
     pub fn as_key_str(&self) -> &'static str {
         match self {
             TransactionStatus::New => "New",
+            TransactionStatus::PendingSignature => "PendingSignature",
+            TransactionStatus::Queued => "Queued",
             TransactionStatus::DryRun => "DryRun",
             TransactionStatus::Pending => "Pending",
             TransactionStatus::Accepted => "Accepted",
@@ -63,6 +69,8 @@ impl FromStr for TransactionStatus {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "New" => Ok(TransactionStatus::New),
+            "PendingSignature" => Ok(TransactionStatus::PendingSignature),
+            "Queued" => Ok(TransactionStatus::Queued),
             "DryRun" => Ok(TransactionStatus::DryRun),
             "Pending" => Ok(TransactionStatus::Pending),
             "Accepted" => Ok(TransactionStatus::Accepted),
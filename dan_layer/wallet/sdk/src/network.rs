@@ -6,6 +6,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tari_dan_common_types::Epoch;
 use tari_dan_storage::consensus_models::Decision;
 use tari_engine_types::{
     commit_result::ExecuteResult,
@@ -44,6 +45,8 @@ pub trait WalletNetworkInterface {
     ) -> Result<TransactionQueryResult, Self::Error>;
 
     async fn fetch_template_definition(&self, template_address: TemplateAddress) -> Result<TemplateDef, Self::Error>;
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error>;
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -17,8 +17,10 @@ use crate::{
         jwt::JwtApi,
         key_manager::KeyManagerApi,
         non_fungible_tokens::NonFungibleTokensApi,
+        scheduled_transaction::ScheduledTransactionApi,
         substate::SubstatesApi,
         transaction::TransactionApi,
+        transaction_queue::TransactionQueueApi,
     },
     network::WalletNetworkInterface,
     storage::{WalletStorageError, WalletStore},
@@ -34,6 +36,9 @@ pub struct WalletSdkConfig {
     // outside       of the SDK in the JWT handler.
     pub jwt_expiry: Duration,
     pub jwt_secret_key: String,
+    /// Domain tag included in the derivation of every wallet key, so that wallets for different networks derive
+    /// different keys. None derives keys without a tag.
+    pub key_domain: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -82,13 +87,21 @@ where
     }
 
     pub fn key_manager_api(&self) -> KeyManagerApi<'_, TStore> {
-        KeyManagerApi::new(&self.store, &self.cipher_seed)
+        KeyManagerApi::new(&self.store, &self.cipher_seed, self.config.key_domain.as_deref())
     }
 
     pub fn transaction_api(&self) -> TransactionApi<'_, TStore, TNetworkInterface> {
         TransactionApi::new(&self.store, &self.network_interface)
     }
 
+    pub fn transaction_queue_api(&self) -> TransactionQueueApi<'_, TStore> {
+        TransactionQueueApi::new(&self.store)
+    }
+
+    pub fn scheduled_transaction_api(&self) -> ScheduledTransactionApi<'_, TStore> {
+        ScheduledTransactionApi::new(&self.store, self.key_manager_api())
+    }
+
     pub fn substate_api(&self) -> SubstatesApi<'_, TStore, TNetworkInterface> {
         SubstatesApi::new(&self.store, &self.network_interface)
     }
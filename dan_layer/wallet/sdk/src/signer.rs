@@ -0,0 +1,171 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
+use tari_crypto::keys::PublicKey as PublicKeyTrait;
+use tari_transaction::{TransactionSignature, UnsignedTransaction};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time,
+};
+
+/// Signs transactions on behalf of the wallet. The signing key may be held in memory or by an external device.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// An identifier for the signing key, for example a key manager index or an HSM key label
+    fn key_id(&self) -> &str;
+    fn public_key(&self) -> &PublicKey;
+    async fn sign_transaction(
+        &self,
+        transaction: &UnsignedTransaction,
+    ) -> Result<TransactionSignature, TransactionSignerError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct InMemoryTransactionSigner {
+    key_id: String,
+    secret_key: PrivateKey,
+    public_key: PublicKey,
+}
+
+impl InMemoryTransactionSigner {
+    pub fn new<T: Into<String>>(key_id: T, secret_key: PrivateKey) -> Self {
+        Self {
+            key_id: key_id.into(),
+            public_key: PublicKey::from_secret_key(&secret_key),
+            secret_key,
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for InMemoryTransactionSigner {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign_transaction(
+        &self,
+        transaction: &UnsignedTransaction,
+    ) -> Result<TransactionSignature, TransactionSignerError> {
+        Ok(TransactionSignature::sign(&self.secret_key, transaction))
+    }
+}
+
+/// The data an external signer needs to produce a transaction signature
+#[derive(Debug, Clone)]
+pub struct SigningPayload {
+    pub key_id: String,
+    pub public_key: PublicKey,
+    /// The challenge that must be signed with the key identified by `key_id`
+    pub challenge: [u8; 64],
+    /// The transaction being signed, so that the signer can display or check it before signing
+    pub transaction: UnsignedTransaction,
+}
+
+/// A request sent to an external signer. The signature is returned by calling `respond`.
+#[derive(Debug)]
+pub struct SigningRequest {
+    payload: SigningPayload,
+    reply: oneshot::Sender<Signature>,
+}
+
+impl SigningRequest {
+    pub fn payload(&self) -> &SigningPayload {
+        &self.payload
+    }
+
+    /// Returns the signature to the waiting signer. This has no effect if the signer has already timed out.
+    pub fn respond(self, signature: Signature) {
+        let _ignore = self.reply.send(signature);
+    }
+}
+
+/// Delegates signing to an external party (e.g. an HSM) by sending a `SigningRequest` on a channel and waiting for
+/// the signature to be returned.
+#[derive(Debug, Clone)]
+pub struct ExternalTransactionSigner {
+    key_id: String,
+    public_key: PublicKey,
+    tx_requests: mpsc::Sender<SigningRequest>,
+    timeout: Duration,
+}
+
+impl ExternalTransactionSigner {
+    pub fn new<T: Into<String>>(
+        key_id: T,
+        public_key: PublicKey,
+        tx_requests: mpsc::Sender<SigningRequest>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            key_id: key_id.into(),
+            public_key,
+            tx_requests,
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for ExternalTransactionSigner {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign_transaction(
+        &self,
+        transaction: &UnsignedTransaction,
+    ) -> Result<TransactionSignature, TransactionSignerError> {
+        let (reply, rx_reply) = oneshot::channel();
+        let request = SigningRequest {
+            payload: SigningPayload {
+                key_id: self.key_id.clone(),
+                public_key: self.public_key.clone(),
+                challenge: TransactionSignature::create_challenge(transaction),
+                transaction: transaction.clone(),
+            },
+            reply,
+        };
+        self.tx_requests
+            .send(request)
+            .await
+            .map_err(|_| TransactionSignerError::SignerUnavailable)?;
+
+        let signature = time::timeout(self.timeout, rx_reply)
+            .await
+            .map_err(|_| TransactionSignerError::Timeout { timeout: self.timeout })?
+            .map_err(|_| TransactionSignerError::RequestDropped)?;
+
+        let signature = TransactionSignature::new(self.public_key.clone(), signature);
+        if !signature.verify(transaction) {
+            return Err(TransactionSignerError::InvalidSignature {
+                key_id: self.key_id.clone(),
+            });
+        }
+        Ok(signature)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionSignerError {
+    #[error("External signer is not available")]
+    SignerUnavailable,
+    #[error("External signer did not respond within {timeout:.2?}")]
+    Timeout { timeout: Duration },
+    #[error("External signer dropped the signing request")]
+    RequestDropped,
+    #[error("External signer returned an invalid signature for key {key_id}")]
+    InvalidSignature { key_id: String },
+}
@@ -14,16 +14,21 @@ use tari_template_lib::{
     models::Amount,
     prelude::{ComponentAddress, NonFungibleId, ResourceAddress},
 };
-use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, UnsignedTransaction};
 
 use crate::models::{
     Account,
+    AccountTransactionQueue,
     ConfidentialOutputModel,
     ConfidentialProofId,
     Config,
     NewAccountInfo,
     NonFungibleToken,
     OutputStatus,
+    QueuedTransactionStatus,
+    ScheduleTrigger,
+    ScheduledTransaction,
+    ScheduledTransactionStatus,
     SubstateModel,
     SubstateType,
     TransactionStatus,
@@ -129,6 +134,23 @@ pub trait WalletStoreReader {
         status: Option<TransactionStatus>,
         component: Option<ComponentAddress>,
     ) -> Result<Vec<WalletTransaction>, WalletStorageError>;
+    // Transaction queues
+    /// Returns the transaction queue of the account. A NotFound error is returned if the account does not have
+    /// ordered transactions enabled.
+    fn transaction_queue_get(&mut self, account: &SubstateId) -> Result<AccountTransactionQueue, WalletStorageError>;
+    fn transaction_queue_get_enabled_accounts(&mut self) -> Result<Vec<SubstateId>, WalletStorageError>;
+    /// Returns the account of the queue that contains the transaction
+    fn transaction_queue_get_account_by_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<SubstateId, WalletStorageError>;
+    // Scheduled transactions
+    fn scheduled_transactions_get(&mut self, id: u64) -> Result<ScheduledTransaction, WalletStorageError>;
+    /// Returns the scheduled transactions with the given status, or all scheduled transactions if None, ordered by id
+    fn scheduled_transactions_fetch_all(
+        &mut self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, WalletStorageError>;
     // Substates
     fn substates_get(&mut self, address: &SubstateId) -> Result<SubstateModel, WalletStorageError>;
     fn substates_get_all(
@@ -239,6 +261,50 @@ pub trait WalletStoreWriter {
         execution_time: Option<Duration>,
         finalized_time: Option<Duration>,
     ) -> Result<(), WalletStorageError>;
+    /// Replaces a transaction that is pending a signature with the signed transaction and sets its status to New
+    fn transactions_set_signed(
+        &mut self,
+        pending_transaction_id: TransactionId,
+        signed_transaction: &Transaction,
+    ) -> Result<(), WalletStorageError>;
+
+    // Transaction queues
+    fn transaction_queue_enable(&mut self, account: &SubstateId) -> Result<(), WalletStorageError>;
+    /// Removes the transaction queue of the account and all of its entries
+    fn transaction_queue_disable(&mut self, account: &SubstateId) -> Result<(), WalletStorageError>;
+    /// Adds the transaction to the back of the queue, returning its sequence number
+    fn transaction_queue_push(
+        &mut self,
+        account: &SubstateId,
+        transaction_id: TransactionId,
+    ) -> Result<u64, WalletStorageError>;
+    fn transaction_queue_set_status(
+        &mut self,
+        transaction_id: TransactionId,
+        status: QueuedTransactionStatus,
+    ) -> Result<(), WalletStorageError>;
+    fn transaction_queue_set_paused(
+        &mut self,
+        account: &SubstateId,
+        paused_reason: Option<&str>,
+    ) -> Result<(), WalletStorageError>;
+
+    // Scheduled transactions
+    /// Inserts a transaction in the Scheduled status, returning its id
+    fn scheduled_transactions_insert(
+        &mut self,
+        transaction: &UnsignedTransaction,
+        required_substates: &[SubstateRequirement],
+        signing_key_index: u64,
+        trigger: ScheduleTrigger,
+    ) -> Result<u64, WalletStorageError>;
+    fn scheduled_transactions_set_status(
+        &mut self,
+        id: u64,
+        status: ScheduledTransactionStatus,
+        transaction_id: Option<TransactionId>,
+        failure_reason: Option<&str>,
+    ) -> Result<(), WalletStorageError>;
 
     // Substates
     fn substates_upsert_root(
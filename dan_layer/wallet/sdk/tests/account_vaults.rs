@@ -0,0 +1,258 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+use tari_common_types::types::PublicKey;
+use tari_crypto::commitment::HomomorphicCommitmentFactory;
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    models::VersionedSubstateId,
+    network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
+    DanWalletSdk,
+    WalletSdkConfig,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::{
+    confidential::{get_commitment_factory, ConfidentialOutput},
+    resource_container::ResourceContainer,
+    substate::SubstateId,
+    vault::Vault,
+};
+use tari_template_abi::TemplateDef;
+use tari_template_lib::{
+    constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+    models::{Amount, EncryptedData, NonFungibleId, ResourceAddress, TemplateAddress},
+    resource::ResourceType,
+};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
+
+#[test]
+fn it_returns_a_breakdown_for_each_resource_type() {
+    let test = Test::new();
+    let fungible_vault = test.add_vault(1, test.fungible_resource, ResourceType::Fungible);
+    let nft_vault = test.add_vault(2, test.nft_resource, ResourceType::NonFungible);
+    let confidential_vault = test.add_vault(3, CONFIDENTIAL_TARI_RESOURCE_ADDRESS, ResourceType::Confidential);
+
+    let mut latest = HashMap::new();
+    latest.insert(
+        fungible_vault.clone(),
+        (
+            Vault::new(ResourceContainer::fungible(test.fungible_resource, Amount(100))),
+            TransactionId::new([1u8; 32]),
+        ),
+    );
+    latest.insert(
+        nft_vault.clone(),
+        (
+            Vault::new(ResourceContainer::non_fungible(
+                test.nft_resource,
+                [NonFungibleId::from_u32(1), NonFungibleId::from_u32(2)]
+                    .into_iter()
+                    .collect(),
+            )),
+            TransactionId::new([2u8; 32]),
+        ),
+    );
+    latest.insert(
+        confidential_vault.clone(),
+        (
+            Vault::new(ResourceContainer::confidential(
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                [confidential_output(5), confidential_output(7)],
+                Amount(10),
+            )),
+            TransactionId::new([3u8; 32]),
+        ),
+    );
+
+    let breakdown = test
+        .sdk
+        .accounts_api()
+        .get_vault_breakdown(&Test::account_address(), &latest)
+        .unwrap();
+    assert_eq!(breakdown.len(), 3);
+
+    let fungible = breakdown.iter().find(|v| v.vault_address == fungible_vault).unwrap();
+    assert_eq!(fungible.resource_address, test.fungible_resource);
+    assert_eq!(fungible.resource_type, ResourceType::Fungible);
+    assert_eq!(fungible.revealed_balance, Amount(100));
+    assert_eq!(fungible.confidential_commitment_count, 0);
+    assert_eq!(fungible.last_updated_transaction, Some(TransactionId::new([1u8; 32])));
+
+    let nft = breakdown.iter().find(|v| v.vault_address == nft_vault).unwrap();
+    assert_eq!(nft.resource_type, ResourceType::NonFungible);
+    assert_eq!(nft.revealed_balance, Amount(2));
+    assert_eq!(nft.confidential_commitment_count, 0);
+    assert_eq!(nft.last_updated_transaction, Some(TransactionId::new([2u8; 32])));
+
+    let confidential = breakdown.iter().find(|v| v.vault_address == confidential_vault).unwrap();
+    assert_eq!(confidential.resource_address, CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
+    assert_eq!(confidential.resource_type, ResourceType::Confidential);
+    assert_eq!(confidential.revealed_balance, Amount(10));
+    assert_eq!(confidential.confidential_commitment_count, 2);
+    assert_eq!(
+        confidential.last_updated_transaction,
+        Some(TransactionId::new([3u8; 32]))
+    );
+}
+
+#[test]
+fn it_falls_back_to_the_wallet_cache_for_unscanned_vaults() {
+    let test = Test::new();
+    let fungible_vault = test.add_vault(1, test.fungible_resource, ResourceType::Fungible);
+    let nft_vault = test.add_vault(2, test.nft_resource, ResourceType::NonFungible);
+
+    let substate_api = test.sdk.substate_api();
+    substate_api
+        .save_root(TransactionId::new([9u8; 32]), VersionedSubstateId {
+            substate_id: Test::account_address(),
+            version: 0,
+        })
+        .unwrap();
+    substate_api
+        .save_child(TransactionId::new([9u8; 32]), Test::account_address(), VersionedSubstateId {
+            substate_id: fungible_vault.clone(),
+            version: 0,
+        })
+        .unwrap();
+
+    let breakdown = test
+        .sdk
+        .accounts_api()
+        .get_vault_breakdown(&Test::account_address(), &HashMap::new())
+        .unwrap();
+    assert_eq!(breakdown.len(), 2);
+
+    let fungible = breakdown.iter().find(|v| v.vault_address == fungible_vault).unwrap();
+    assert_eq!(fungible.resource_type, ResourceType::Fungible);
+    assert_eq!(fungible.revealed_balance, Amount(0));
+    assert_eq!(fungible.last_updated_transaction, Some(TransactionId::new([9u8; 32])));
+
+    // Not in the substate cache
+    let nft = breakdown.iter().find(|v| v.vault_address == nft_vault).unwrap();
+    assert_eq!(nft.last_updated_transaction, None);
+}
+
+fn confidential_output(value: u64) -> (tari_common_types::types::Commitment, ConfidentialOutput) {
+    let commitment = get_commitment_factory().commit_value(&Default::default(), value);
+    let output = ConfidentialOutput {
+        commitment: commitment.clone(),
+        stealth_public_nonce: PublicKey::default(),
+        encrypted_data: EncryptedData([0; EncryptedData::size()]),
+        minimum_value_promise: 0,
+        viewable_balance: None,
+    };
+    (commitment, output)
+}
+
+struct Test {
+    sdk: DanWalletSdk<SqliteWalletStore, PanicIndexer>,
+    fungible_resource: ResourceAddress,
+    nft_resource: ResourceAddress,
+    _temp: tempfile::TempDir,
+}
+
+impl Test {
+    pub fn new() -> Self {
+        let temp = tempfile::tempdir().unwrap();
+        let store = SqliteWalletStore::try_open(temp.path().join("data/wallet.sqlite")).unwrap();
+        store.run_migrations().unwrap();
+
+        let sdk = DanWalletSdk::initialize(store, PanicIndexer, WalletSdkConfig {
+            password: None,
+            jwt_expiry: Duration::from_secs(60),
+            jwt_secret_key: "secret_key".to_string(),
+            key_domain: None,
+        })
+        .unwrap();
+        sdk.accounts_api()
+            .add_account(Some("test"), &Test::account_address(), 0, true)
+            .unwrap();
+
+        Self {
+            sdk,
+            fungible_resource: "resource_01010101010101010101010101010101010101010101010101010101"
+                .parse()
+                .unwrap(),
+            nft_resource: "resource_02020202020202020202020202020202020202020202020202020202"
+                .parse()
+                .unwrap(),
+            _temp: temp,
+        }
+    }
+
+    pub fn account_address() -> SubstateId {
+        "component_0dc41b5cc74b36d696c7b140323a40a2f98b71df5d60e5a6bf4c1a07"
+            .parse()
+            .unwrap()
+    }
+
+    pub fn add_vault(&self, n: u8, resource_address: ResourceAddress, resource_type: ResourceType) -> SubstateId {
+        let vault_address: SubstateId = format!("vault_{}", format!("{:02x}", n).repeat(28)).parse().unwrap();
+        self.sdk
+            .accounts_api()
+            .add_vault(
+                Test::account_address(),
+                vault_address.clone(),
+                resource_address,
+                resource_type,
+                None,
+            )
+            .unwrap();
+        vault_address
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PanicIndexer;
+
+#[async_trait]
+impl WalletNetworkInterface for PanicIndexer {
+    type Error = Infallible;
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_substate(
+        &self,
+        _address: &SubstateId,
+        _version: Option<u32>,
+        _local_search_only: bool,
+    ) -> Result<SubstateQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionId, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_dry_run_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_transaction_result(
+        &self,
+        _transaction_id: TransactionId,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    async fn fetch_template_definition(&self, _template_address: TemplateAddress) -> Result<TemplateDef, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+}
@@ -6,7 +6,7 @@ use std::{convert::Infallible, time::Duration};
 use async_trait::async_trait;
 use tari_common_types::types::Commitment;
 use tari_crypto::commitment::HomomorphicCommitmentFactory;
-use tari_dan_common_types::optional::Optional;
+use tari_dan_common_types::{optional::Optional, Epoch};
 use tari_dan_wallet_sdk::{
     models::{ConfidentialOutputModel, ConfidentialProofId, OutputStatus},
     network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
@@ -144,6 +144,7 @@ impl Test {
             password: None,
             jwt_expiry: Duration::from_secs(60),
             jwt_secret_key: "secret_key".to_string(),
+            key_domain: None,
         })
         .unwrap();
         let accounts_api = sdk.accounts_api();
@@ -269,4 +270,8 @@ impl WalletNetworkInterface for PanicIndexer {
     async fn fetch_template_definition(&self, _template_address: TemplateAddress) -> Result<TemplateDef, Self::Error> {
         panic!("PanicIndexer called")
     }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("PanicIndexer called")
+    }
 }
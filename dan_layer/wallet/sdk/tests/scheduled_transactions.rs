@@ -0,0 +1,253 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tari_common_types::types::PublicKey;
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    apis::{key_manager::TRANSACTION_BRANCH, scheduled_transaction::ScheduledTransactionApiError},
+    models::{ScheduleTrigger, ScheduledTransactionStatus, VersionedSubstateId},
+    network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
+    DanWalletSdk,
+    WalletSdkConfig,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::substate::SubstateId;
+use tari_template_abi::TemplateDef;
+use tari_template_lib::models::{ComponentAddress, TemplateAddress};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, UnsignedTransaction};
+
+const NOW: u64 = 1_700_000_000;
+
+#[tokio::test]
+async fn it_releases_a_time_triggered_transaction_with_current_input_versions() {
+    let test = Test::new();
+    let scheduled_api = test.sdk.scheduled_transaction_api();
+    test.set_account_version(0);
+    let id = test.schedule(ScheduleTrigger::Time { timestamp: NOW + 60 });
+
+    assert!(scheduled_api.get_due(NOW, None).unwrap().is_empty());
+    assert!(scheduled_api.get_due(NOW + 59, Some(Epoch(100))).unwrap().is_empty());
+
+    // The account is changed by another transaction before the trigger fires
+    test.set_account_version(1);
+
+    let due = scheduled_api.get_due(NOW + 60, None).unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, id);
+    let transaction_id = test.release(id).await;
+
+    let submitted = test.submitted();
+    assert_eq!(submitted.len(), 1);
+    let (transaction, required_substates) = &submitted[0];
+    assert_eq!(*transaction.id(), transaction_id);
+    let expected = SubstateRequirement::with_version(test.account.clone(), 1);
+    assert_eq!(transaction.inputs().iter().collect::<Vec<_>>(), vec![&expected]);
+    assert_eq!(*required_substates, vec![expected]);
+    // Signed on release with the scheduled key
+    assert_eq!(*transaction.signer_public_key(), test.public_key);
+    assert!(transaction.signature().verify(&UnsignedTransaction::from(transaction)));
+
+    let scheduled = scheduled_api.get(id).unwrap();
+    assert_eq!(scheduled.status, ScheduledTransactionStatus::Submitted);
+    assert_eq!(scheduled.transaction_id, Some(transaction_id));
+    assert!(scheduled_api.get_due(NOW + 60, None).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn it_releases_an_epoch_triggered_transaction_once_the_epoch_is_reached() {
+    let test = Test::new();
+    let scheduled_api = test.sdk.scheduled_transaction_api();
+    test.set_account_version(3);
+    let id = test.schedule(ScheduleTrigger::Epoch { epoch: Epoch(5) });
+
+    // Epoch triggers do not fire until the current epoch is known
+    assert!(scheduled_api.get_due(u64::MAX, None).unwrap().is_empty());
+    assert!(scheduled_api.get_due(NOW, Some(Epoch(4))).unwrap().is_empty());
+
+    test.set_account_version(4);
+    let due = scheduled_api.get_due(NOW, Some(Epoch(5))).unwrap();
+    assert_eq!(due.len(), 1);
+    test.release(id).await;
+
+    let submitted = test.submitted();
+    let (transaction, _) = &submitted[0];
+    let expected = SubstateRequirement::with_version(test.account.clone(), 4);
+    assert_eq!(transaction.inputs().iter().collect::<Vec<_>>(), vec![&expected]);
+    assert_eq!(
+        scheduled_api.list(Some(ScheduledTransactionStatus::Submitted)).unwrap()[0].id,
+        id
+    );
+}
+
+#[tokio::test]
+async fn it_only_cancels_transactions_that_have_not_been_released() {
+    let test = Test::new();
+    let scheduled_api = test.sdk.scheduled_transaction_api();
+    test.set_account_version(0);
+    let cancelled = test.schedule(ScheduleTrigger::Time { timestamp: NOW });
+    let released = test.schedule(ScheduleTrigger::Time { timestamp: NOW });
+
+    scheduled_api.cancel(cancelled).unwrap();
+    assert_eq!(
+        scheduled_api.get(cancelled).unwrap().status,
+        ScheduledTransactionStatus::Cancelled
+    );
+    let due = scheduled_api.get_due(NOW, None).unwrap();
+    assert_eq!(due.iter().map(|s| s.id).collect::<Vec<_>>(), vec![released]);
+
+    test.release(released).await;
+    let err = scheduled_api.cancel(released).unwrap_err();
+    assert!(matches!(err, ScheduledTransactionApiError::NotScheduled {
+        status: ScheduledTransactionStatus::Submitted,
+        ..
+    }));
+    assert_eq!(scheduled_api.list(None).unwrap().len(), 2);
+    assert_eq!(test.submitted().len(), 1);
+}
+
+struct Test {
+    sdk: DanWalletSdk<SqliteWalletStore, RecordingIndexer>,
+    account: SubstateId,
+    public_key: PublicKey,
+    submitted: Arc<Mutex<Vec<(Transaction, Vec<SubstateRequirement>)>>>,
+    _temp: tempfile::TempDir,
+}
+
+impl Test {
+    pub fn new() -> Self {
+        let temp = tempfile::tempdir().unwrap();
+        let store = SqliteWalletStore::try_open(temp.path().join("data/wallet.sqlite")).unwrap();
+        store.run_migrations().unwrap();
+
+        let submitted = Arc::new(Mutex::new(Vec::new()));
+        let indexer = RecordingIndexer {
+            submitted: submitted.clone(),
+        };
+        let sdk = DanWalletSdk::initialize(store, indexer, WalletSdkConfig {
+            password: None,
+            jwt_expiry: Duration::from_secs(60),
+            jwt_secret_key: "secret_key".to_string(),
+            key_domain: None,
+        })
+        .unwrap();
+        let key = sdk.key_manager_api().derive_key(TRANSACTION_BRANCH, 1).unwrap();
+
+        Self {
+            sdk,
+            account: SubstateId::Component(ComponentAddress::from_array([1u8; 32])),
+            public_key: PublicKey::from_secret_key(&key.key),
+            submitted,
+            _temp: temp,
+        }
+    }
+
+    /// Records the account substate at the given version, as the account monitor does when the account changes
+    fn set_account_version(&self, version: u32) {
+        self.sdk
+            .substate_api()
+            .save_root(
+                TransactionId::new([version as u8; 32]),
+                VersionedSubstateId {
+                    substate_id: self.account.clone(),
+                    version,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Schedules a withdrawal from the account with inputs at the versions currently known to the wallet
+    fn schedule(&self, trigger: ScheduleTrigger) -> u64 {
+        let version = self.sdk.substate_api().get_substate(&self.account).unwrap().address.version;
+        let requirement = SubstateRequirement::with_version(self.account.clone(), version);
+        let transaction = Transaction::builder()
+            .call_method(self.account.as_component_address().unwrap(), "withdraw", vec![])
+            .with_inputs([requirement.clone()])
+            .build_unsigned_transaction();
+        self.sdk
+            .scheduled_transaction_api()
+            .schedule(&transaction, &[requirement], 1, trigger)
+            .unwrap()
+    }
+
+    /// Signs and submits a scheduled transaction, as the wallet daemon does when the trigger fires
+    async fn release(&self, id: u64) -> TransactionId {
+        let scheduled_api = self.sdk.scheduled_transaction_api();
+        let transaction_api = self.sdk.transaction_api();
+        let scheduled = scheduled_api.get(id).unwrap();
+        let (transaction, required_substates) = scheduled_api.prepare_release(&scheduled).unwrap();
+        let transaction_id = transaction_api
+            .insert_new_transaction(transaction, required_substates, None, false)
+            .await
+            .unwrap();
+        transaction_api.submit_transaction(transaction_id).await.unwrap();
+        scheduled_api.mark_submitted(id, transaction_id).unwrap();
+        transaction_id
+    }
+
+    fn submitted(&self) -> Vec<(Transaction, Vec<SubstateRequirement>)> {
+        self.submitted.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RecordingIndexer {
+    submitted: Arc<Mutex<Vec<(Transaction, Vec<SubstateRequirement>)>>>,
+}
+
+#[async_trait]
+impl WalletNetworkInterface for RecordingIndexer {
+    type Error = Infallible;
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_substate(
+        &self,
+        _address: &SubstateId,
+        _version: Option<u32>,
+        _local_search_only: bool,
+    ) -> Result<SubstateQueryResult, Self::Error> {
+        panic!("RecordingIndexer::query_substate called")
+    }
+
+    async fn submit_transaction(
+        &self,
+        transaction: Transaction,
+        required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionId, Self::Error> {
+        let id = *transaction.id();
+        self.submitted.lock().unwrap().push((transaction, required_substates));
+        Ok(id)
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_dry_run_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("RecordingIndexer::submit_dry_run_transaction called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_transaction_result(
+        &self,
+        _transaction_id: TransactionId,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("RecordingIndexer::query_transaction_result called")
+    }
+
+    async fn fetch_template_definition(&self, _template_address: TemplateAddress) -> Result<TemplateDef, Self::Error> {
+        panic!("RecordingIndexer::fetch_template_definition called")
+    }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("RecordingIndexer::get_current_epoch called")
+    }
+}
@@ -0,0 +1,257 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tari_common_types::types::PrivateKey;
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    apis::{key_manager::TRANSACTION_BRANCH, transaction_queue::TransactionQueueApiError},
+    models::{QueuedTransactionStatus, TransactionStatus},
+    network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
+    DanWalletSdk,
+    WalletSdkConfig,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::substate::SubstateId;
+use tari_template_abi::TemplateDef;
+use tari_template_lib::models::{ComponentAddress, TemplateAddress};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
+
+#[tokio::test]
+async fn it_releases_queued_transactions_in_order() {
+    let test = Test::new();
+    let queue_api = test.sdk.transaction_queue_api();
+    let transfers = test.queue_transfers(3).await;
+
+    let queue = queue_api.get(&test.account).unwrap();
+    assert_eq!(queue.next_sequence, 3);
+    assert_eq!(
+        queue.entries.iter().map(|e| e.transaction_id).collect::<Vec<_>>(),
+        transfers
+    );
+    for id in &transfers {
+        assert_eq!(test.transaction_status(*id), TransactionStatus::Queued);
+    }
+
+    for (i, id) in transfers.iter().enumerate() {
+        assert_eq!(test.release_next().await, Some(*id));
+        assert_eq!(test.transaction_status(*id), TransactionStatus::Pending);
+        // The next transaction is held until this one is finalized
+        assert_eq!(test.release_next().await, None);
+        assert_eq!(test.submitted(), transfers[..=i]);
+
+        let queue = queue_api
+            .on_transaction_finalized(*id, TransactionStatus::Accepted)
+            .unwrap()
+            .unwrap();
+        assert!(!queue.is_paused());
+        assert_eq!(queue.entries.len(), transfers.len() - i - 1);
+    }
+
+    assert_eq!(test.submitted(), transfers);
+    assert!(queue_api.get(&test.account).unwrap().entries.is_empty());
+}
+
+#[tokio::test]
+async fn it_pauses_the_queue_when_a_transaction_fails() {
+    let test = Test::new();
+    let queue_api = test.sdk.transaction_queue_api();
+    let transfers = test.queue_transfers(3).await;
+
+    assert_eq!(test.release_next().await, Some(transfers[0]));
+    queue_api
+        .on_transaction_finalized(transfers[0], TransactionStatus::Accepted)
+        .unwrap();
+    assert_eq!(test.release_next().await, Some(transfers[1]));
+
+    let queue = queue_api
+        .on_transaction_finalized(transfers[1], TransactionStatus::Rejected)
+        .unwrap()
+        .unwrap();
+    assert!(queue.is_paused());
+    assert_eq!(queue.head().unwrap().transaction_id, transfers[1]);
+    assert_eq!(queue.head().unwrap().status, QueuedTransactionStatus::Failed);
+    assert_eq!(test.release_next().await, None);
+    assert_eq!(test.submitted(), transfers[..2]);
+
+    // Skipping the failed transaction resumes the queue
+    assert_eq!(queue_api.skip(&test.account).unwrap(), transfers[1]);
+    assert!(!queue_api.get(&test.account).unwrap().is_paused());
+    assert_eq!(test.release_next().await, Some(transfers[2]));
+    assert_eq!(test.submitted(), transfers);
+
+    let err = queue_api.skip(&test.account).unwrap_err();
+    assert!(matches!(
+        err,
+        TransactionQueueApiError::CannotSkipSubmittedTransaction { .. }
+    ));
+}
+
+#[tokio::test]
+async fn it_only_disables_an_empty_queue() {
+    let test = Test::new();
+    let queue_api = test.sdk.transaction_queue_api();
+    let transfers = test.queue_transfers(1).await;
+
+    let err = queue_api.disable(&test.account).unwrap_err();
+    assert!(matches!(err, TransactionQueueApiError::QueueNotEmpty { .. }));
+
+    // A transaction that was never submitted can be skipped
+    assert_eq!(queue_api.skip(&test.account).unwrap(), transfers[0]);
+    assert_eq!(
+        test.transaction_status(transfers[0]),
+        TransactionStatus::InvalidTransaction
+    );
+    queue_api.disable(&test.account).unwrap();
+    assert!(queue_api.get_enabled_accounts().unwrap().is_empty());
+
+    // Transactions for the account are no longer queued
+    let transaction = test.transfer();
+    assert_eq!(queue_api.find_queue_account(&transaction).unwrap(), None);
+}
+
+struct Test {
+    sdk: DanWalletSdk<SqliteWalletStore, RecordingIndexer>,
+    account: SubstateId,
+    secret_key: PrivateKey,
+    submitted: Arc<Mutex<Vec<TransactionId>>>,
+    _temp: tempfile::TempDir,
+}
+
+impl Test {
+    pub fn new() -> Self {
+        let temp = tempfile::tempdir().unwrap();
+        let store = SqliteWalletStore::try_open(temp.path().join("data/wallet.sqlite")).unwrap();
+        store.run_migrations().unwrap();
+
+        let submitted = Arc::new(Mutex::new(Vec::new()));
+        let indexer = RecordingIndexer {
+            submitted: submitted.clone(),
+        };
+        let sdk = DanWalletSdk::initialize(store, indexer, WalletSdkConfig {
+            password: None,
+            jwt_expiry: Duration::from_secs(60),
+            jwt_secret_key: "secret_key".to_string(),
+            key_domain: None,
+        })
+        .unwrap();
+        let secret_key = sdk.key_manager_api().derive_key(TRANSACTION_BRANCH, 0).unwrap().key;
+
+        let account = SubstateId::Component(ComponentAddress::from_array([1u8; 32]));
+        sdk.accounts_api()
+            .add_account(Some("exchange"), &account, 0, true)
+            .unwrap();
+        sdk.transaction_queue_api().enable(&account).unwrap();
+
+        Self {
+            sdk,
+            account,
+            secret_key,
+            submitted,
+            _temp: temp,
+        }
+    }
+
+    fn transfer(&self) -> Transaction {
+        let component_address = self.account.as_component_address().unwrap();
+        Transaction::builder()
+            .call_method(component_address, "withdraw", vec![])
+            .sign(&self.secret_key)
+            .build()
+    }
+
+    /// Inserts the given number of transfers from the queued account, returning the transaction ids in queue order
+    async fn queue_transfers(&self, n: usize) -> Vec<TransactionId> {
+        let transaction_api = self.sdk.transaction_api();
+        let queue_api = self.sdk.transaction_queue_api();
+        let mut ids = Vec::with_capacity(n);
+        for i in 0..n {
+            let transaction = self.transfer();
+            let account = queue_api.find_queue_account(&transaction).unwrap().unwrap();
+            assert_eq!(account, self.account);
+            let id = transaction_api
+                .insert_new_transaction(transaction, vec![], None, false)
+                .await
+                .unwrap();
+            let sequence = queue_api.enqueue(&account, id).unwrap();
+            assert_eq!(sequence, i as u64);
+            ids.push(id);
+        }
+        ids
+    }
+
+    /// Releases and submits the next transaction in the queue, as the wallet daemon does
+    async fn release_next(&self) -> Option<TransactionId> {
+        let id = self.sdk.transaction_queue_api().release_next(&self.account).unwrap()?;
+        self.sdk.transaction_api().submit_transaction(id).await.unwrap();
+        Some(id)
+    }
+
+    fn transaction_status(&self, id: TransactionId) -> TransactionStatus {
+        self.sdk.transaction_api().get(id).unwrap().status
+    }
+
+    fn submitted(&self) -> Vec<TransactionId> {
+        self.submitted.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RecordingIndexer {
+    submitted: Arc<Mutex<Vec<TransactionId>>>,
+}
+
+#[async_trait]
+impl WalletNetworkInterface for RecordingIndexer {
+    type Error = Infallible;
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_substate(
+        &self,
+        _address: &SubstateId,
+        _version: Option<u32>,
+        _local_search_only: bool,
+    ) -> Result<SubstateQueryResult, Self::Error> {
+        panic!("RecordingIndexer::query_substate called")
+    }
+
+    async fn submit_transaction(
+        &self,
+        transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionId, Self::Error> {
+        self.submitted.lock().unwrap().push(*transaction.id());
+        Ok(*transaction.id())
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_dry_run_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("RecordingIndexer::submit_dry_run_transaction called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_transaction_result(
+        &self,
+        _transaction_id: TransactionId,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("RecordingIndexer::query_transaction_result called")
+    }
+
+    async fn fetch_template_definition(&self, _template_address: TemplateAddress) -> Result<TemplateDef, Self::Error> {
+        panic!("RecordingIndexer::fetch_template_definition called")
+    }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("RecordingIndexer::get_current_epoch called")
+    }
+}
@@ -0,0 +1,259 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+use rand::rngs::OsRng;
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    apis::{key_manager::TRANSACTION_BRANCH, transaction::TransactionApiError},
+    models::TransactionStatus,
+    network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
+    signer::{
+        ExternalTransactionSigner,
+        InMemoryTransactionSigner,
+        SigningRequest,
+        TransactionSigner,
+        TransactionSignerError,
+    },
+    DanWalletSdk,
+    WalletSdkConfig,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::{instruction::Instruction, substate::SubstateId};
+use tari_template_abi::TemplateDef;
+use tari_template_lib::models::TemplateAddress;
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, UnsignedTransaction};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn it_signs_with_the_in_memory_signer() {
+    let test = Test::new();
+    let signer = InMemoryTransactionSigner::new("transactions/0", test.secret_key.clone());
+    assert_eq!(*signer.public_key(), PublicKey::from_secret_key(&test.secret_key));
+
+    let unsigned_transaction = unsigned_transaction();
+    let signature = signer.sign_transaction(&unsigned_transaction).await.unwrap();
+    let transaction = Transaction::builder()
+        .with_unsigned_transaction(unsigned_transaction)
+        .with_signature(signature)
+        .build();
+    assert!(transaction.signature().verify(&UnsignedTransaction::from(&transaction)));
+}
+
+#[tokio::test]
+async fn it_submits_a_transaction_signed_by_an_external_signer() {
+    let test = Test::new();
+    let (tx_requests, rx_requests) = mpsc::channel(1);
+    let signer = ExternalTransactionSigner::new(
+        "hsm-key-1",
+        PublicKey::from_secret_key(&test.secret_key),
+        tx_requests,
+        Duration::from_secs(10),
+    );
+    spawn_software_signer(test.secret_key.clone(), rx_requests);
+
+    let unsigned_transaction = unsigned_transaction();
+    let transaction_api = test.sdk.transaction_api();
+    let pending_id = transaction_api
+        .insert_pending_signature(
+            unsigned_transaction.clone(),
+            signer.public_key().clone(),
+            vec![],
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        transaction_api.get(pending_id).unwrap().status,
+        TransactionStatus::PendingSignature
+    );
+
+    let signature = signer.sign_transaction(&unsigned_transaction).await.unwrap();
+    let transaction = transaction_api.apply_signature(pending_id, signature).unwrap();
+    assert_ne!(*transaction.id(), pending_id);
+    assert!(transaction.check_id());
+    assert!(transaction.signature().verify(&UnsignedTransaction::from(&transaction)));
+
+    let stored = transaction_api.get(*transaction.id()).unwrap();
+    assert_eq!(stored.status, TransactionStatus::New);
+    assert_eq!(stored.transaction.id(), transaction.id());
+    assert!(stored
+        .transaction
+        .signature()
+        .verify(&UnsignedTransaction::from(&stored.transaction)));
+    assert!(transaction_api.get(pending_id).is_err());
+}
+
+#[tokio::test]
+async fn it_rejects_a_signature_for_a_different_key() {
+    let test = Test::new();
+    let unsigned_transaction = unsigned_transaction();
+    let transaction_api = test.sdk.transaction_api();
+    let pending_id = transaction_api
+        .insert_pending_signature(
+            unsigned_transaction.clone(),
+            PublicKey::from_secret_key(&test.secret_key),
+            vec![],
+            None,
+            false,
+        )
+        .unwrap();
+
+    let other_key = test.sdk.key_manager_api().derive_key(TRANSACTION_BRANCH, 1).unwrap().key;
+    let signature = InMemoryTransactionSigner::new("transactions/1", other_key)
+        .sign_transaction(&unsigned_transaction)
+        .await
+        .unwrap();
+    let err = transaction_api.apply_signature(pending_id, signature).unwrap_err();
+    assert!(matches!(err, TransactionApiError::InvalidSignature { .. }));
+    assert_eq!(
+        transaction_api.get(pending_id).unwrap().status,
+        TransactionStatus::PendingSignature
+    );
+}
+
+#[tokio::test]
+async fn it_times_out_if_the_external_signer_does_not_respond() {
+    let test = Test::new();
+    let (tx_requests, mut rx_requests) = mpsc::channel(1);
+    let signer = ExternalTransactionSigner::new(
+        "hsm-key-1",
+        PublicKey::from_secret_key(&test.secret_key),
+        tx_requests,
+        Duration::from_millis(100),
+    );
+
+    let (result, request) = tokio::join!(
+        signer.sign_transaction(&unsigned_transaction()),
+        rx_requests.recv()
+    );
+    assert!(matches!(result, Err(TransactionSignerError::Timeout { .. })));
+    // The request is still held by the caller but the signature is no longer accepted
+    request.unwrap().respond(Signature::default());
+}
+
+#[tokio::test]
+async fn it_abandons_all_transactions_pending_a_signature() {
+    let test = Test::new();
+    let transaction_api = test.sdk.transaction_api();
+    let pending_id = transaction_api
+        .insert_pending_signature(
+            unsigned_transaction(),
+            PublicKey::from_secret_key(&test.secret_key),
+            vec![],
+            None,
+            false,
+        )
+        .unwrap();
+
+    let abandoned = transaction_api.abandon_all_pending_signatures().unwrap();
+    assert_eq!(abandoned, vec![pending_id]);
+    assert_eq!(
+        transaction_api.get(pending_id).unwrap().status,
+        TransactionStatus::InvalidTransaction
+    );
+    assert!(transaction_api.abandon_all_pending_signatures().unwrap().is_empty());
+}
+
+/// Stands in for an HSM by signing the challenge with a key it holds
+fn spawn_software_signer(secret_key: PrivateKey, mut rx_requests: mpsc::Receiver<SigningRequest>) {
+    tokio::spawn(async move {
+        while let Some(request) = rx_requests.recv().await {
+            let signature = Signature::sign(&secret_key, request.payload().challenge, &mut OsRng).unwrap();
+            request.respond(signature);
+        }
+    });
+}
+
+fn unsigned_transaction() -> UnsignedTransaction {
+    Transaction::builder()
+        .add_instruction(Instruction::DropAllProofsInWorkspace)
+        .with_min_epoch(Some(Epoch(1)))
+        .with_max_epoch(Some(Epoch(10)))
+        .build_unsigned_transaction()
+}
+
+struct Test {
+    sdk: DanWalletSdk<SqliteWalletStore, PanicIndexer>,
+    secret_key: PrivateKey,
+    _temp: tempfile::TempDir,
+}
+
+impl Test {
+    pub fn new() -> Self {
+        let temp = tempfile::tempdir().unwrap();
+        let store = SqliteWalletStore::try_open(temp.path().join("data/wallet.sqlite")).unwrap();
+        store.run_migrations().unwrap();
+
+        let sdk = DanWalletSdk::initialize(store, PanicIndexer, WalletSdkConfig {
+            password: None,
+            jwt_expiry: Duration::from_secs(60),
+            jwt_secret_key: "secret_key".to_string(),
+            key_domain: None,
+        })
+        .unwrap();
+        let secret_key = sdk.key_manager_api().derive_key(TRANSACTION_BRANCH, 0).unwrap().key;
+
+        Self {
+            sdk,
+            secret_key,
+            _temp: temp,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PanicIndexer;
+
+#[async_trait]
+impl WalletNetworkInterface for PanicIndexer {
+    type Error = Infallible;
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_substate(
+        &self,
+        _address: &SubstateId,
+        _version: Option<u32>,
+        _local_search_only: bool,
+    ) -> Result<SubstateQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionId, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn submit_dry_run_transaction(
+        &self,
+        _transaction: Transaction,
+        _required_substates: Vec<SubstateRequirement>,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    #[allow(clippy::diverging_sub_expression)]
+    async fn query_transaction_result(
+        &self,
+        _transaction_id: TransactionId,
+    ) -> Result<TransactionQueryResult, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    async fn fetch_template_definition(&self, _template_address: TemplateAddress) -> Result<TemplateDef, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("PanicIndexer called")
+    }
+}
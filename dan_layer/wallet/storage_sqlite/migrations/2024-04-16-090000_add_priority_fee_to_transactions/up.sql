@@ -0,0 +1,2 @@
+ALTER TABLE transactions
+    ADD COLUMN priority_fee bigint NOT NULL default 0;
@@ -0,0 +1,2 @@
+DROP TABLE queued_transactions;
+DROP TABLE transaction_queues;
@@ -0,0 +1,25 @@
+CREATE TABLE transaction_queues
+(
+    id            INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account_id    INTEGER  NOT NULL REFERENCES accounts (id),
+    next_sequence BIGINT   NOT NULL DEFAULT 0,
+    paused_reason TEXT     NULL,
+    created_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX transaction_queues_uniq_account_id ON transaction_queues (account_id);
+
+CREATE TABLE queued_transactions
+(
+    id               INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account_id       INTEGER  NOT NULL REFERENCES accounts (id),
+    sequence         BIGINT   NOT NULL,
+    transaction_hash TEXT     NOT NULL,
+    status           TEXT     NOT NULL,
+    created_at       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX queued_transactions_uniq_account_id_sequence ON queued_transactions (account_id, sequence);
+CREATE UNIQUE INDEX queued_transactions_uniq_transaction_hash ON queued_transactions (transaction_hash);
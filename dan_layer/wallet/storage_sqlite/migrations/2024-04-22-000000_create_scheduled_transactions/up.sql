@@ -0,0 +1,16 @@
+CREATE TABLE scheduled_transactions
+(
+    id                   INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    unsigned_transaction TEXT     NOT NULL,
+    required_substates   TEXT     NOT NULL,
+    signing_key_index    BIGINT   NOT NULL,
+    trigger_timestamp    BIGINT   NULL,
+    trigger_epoch        BIGINT   NULL,
+    status               TEXT     NOT NULL,
+    transaction_hash     TEXT     NULL,
+    failure_reason       TEXT     NULL,
+    created_at           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX scheduled_transactions_idx_status ON scheduled_transactions (status);
@@ -26,3 +26,9 @@ pub use non_fungible_tokens::NonFungibleToken;
 mod proof;
 // Currently only used internally
 pub(crate) use proof::Proof;
+
+mod transaction_queue;
+pub(crate) use transaction_queue::{QueuedTransaction, TransactionQueue};
+
+mod scheduled_transaction;
+pub(crate) use scheduled_transaction::ScheduledTransaction;
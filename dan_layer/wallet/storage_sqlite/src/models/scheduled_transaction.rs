@@ -0,0 +1,77 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    models::{ScheduleTrigger, ScheduledTransaction as ScheduledTransactionModel, ScheduledTransactionStatus},
+    storage::WalletStorageError,
+};
+use tari_transaction::TransactionId;
+
+use crate::{schema::scheduled_transactions, serialization::deserialize_json};
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = scheduled_transactions)]
+pub struct ScheduledTransaction {
+    pub id: i32,
+    pub unsigned_transaction: String,
+    pub required_substates: String,
+    pub signing_key_index: i64,
+    pub trigger_timestamp: Option<i64>,
+    pub trigger_epoch: Option<i64>,
+    pub status: String,
+    pub transaction_hash: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ScheduledTransaction {
+    pub fn try_into_model(self) -> Result<ScheduledTransactionModel, WalletStorageError> {
+        let trigger = match (self.trigger_timestamp, self.trigger_epoch) {
+            (Some(timestamp), None) => ScheduleTrigger::Time {
+                timestamp: timestamp as u64,
+            },
+            (None, Some(epoch)) => ScheduleTrigger::Epoch {
+                epoch: Epoch(epoch as u64),
+            },
+            _ => {
+                return Err(WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "scheduled transaction trigger",
+                    details: format!("Exactly one trigger must be set for scheduled transaction {}", self.id),
+                })
+            },
+        };
+
+        Ok(ScheduledTransactionModel {
+            id: self.id as u64,
+            trigger,
+            transaction: deserialize_json(&self.unsigned_transaction)?,
+            required_substates: deserialize_json(&self.required_substates)?,
+            signing_key_index: self.signing_key_index as u64,
+            status: ScheduledTransactionStatus::from_str(&self.status).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "scheduled transaction status",
+                    details: e.to_string(),
+                }
+            })?,
+            transaction_id: self
+                .transaction_hash
+                .as_deref()
+                .map(TransactionId::from_hex)
+                .transpose()
+                .map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "scheduled transaction hash",
+                    details: e.to_string(),
+                })?,
+            failure_reason: self.failure_reason,
+        })
+    }
+}
@@ -37,6 +37,7 @@ pub struct Transaction {
     pub finalized_time_ms: Option<i64>,
     pub required_substates: String,
     pub new_account_info: Option<String>,
+    pub priority_fee: i64,
 }
 
 impl Transaction {
@@ -61,6 +62,7 @@ impl Transaction {
                 Default::default(),
                 self.min_epoch.map(|epoch| Epoch(epoch as u64)),
                 self.max_epoch.map(|epoch| Epoch(epoch as u64)),
+                self.priority_fee as u64,
             ),
             status: TransactionStatus::from_str(&self.status).map_err(|e| WalletStorageError::DecodingError {
                 operation: "transaction_get",
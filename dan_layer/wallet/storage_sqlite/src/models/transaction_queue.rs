@@ -0,0 +1,57 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{
+    models::{QueuedTransaction as QueuedTransactionModel, QueuedTransactionStatus},
+    storage::WalletStorageError,
+};
+use tari_transaction::TransactionId;
+
+use crate::schema::{queued_transactions, transaction_queues};
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = transaction_queues)]
+pub struct TransactionQueue {
+    pub id: i32,
+    pub account_id: i32,
+    pub next_sequence: i64,
+    pub paused_reason: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = queued_transactions)]
+pub struct QueuedTransaction {
+    pub id: i32,
+    pub account_id: i32,
+    pub sequence: i64,
+    pub transaction_hash: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl QueuedTransaction {
+    pub fn try_into_model(self) -> Result<QueuedTransactionModel, WalletStorageError> {
+        Ok(QueuedTransactionModel {
+            sequence: self.sequence as u64,
+            transaction_id: TransactionId::from_hex(&self.transaction_hash).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "queued transaction hash",
+                    details: e.to_string(),
+                }
+            })?,
+            status: QueuedTransactionStatus::from_str(&self.status).map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_into_model",
+                item: "queued transaction status",
+                details: e.to_string(),
+            })?,
+        })
+    }
+}
@@ -21,11 +21,15 @@ use tari_common_types::types::Commitment;
 use tari_dan_wallet_sdk::{
     models::{
         Account,
+        AccountTransactionQueue,
         ConfidentialOutputModel,
         ConfidentialProofId,
         Config,
         NonFungibleToken,
         OutputStatus,
+        QueuedTransactionStatus,
+        ScheduledTransaction,
+        ScheduledTransactionStatus,
         SubstateModel,
         SubstateType,
         TransactionStatus,
@@ -229,6 +233,127 @@ impl WalletStoreReader for ReadTransaction<'_> {
         rows.into_iter().map(|row| row.try_into_wallet_transaction()).collect()
     }
 
+    // -------------------------------- Transaction queues -------------------------------- //
+    fn transaction_queue_get(&mut self, account: &SubstateId) -> Result<AccountTransactionQueue, WalletStorageError> {
+        use crate::schema::{accounts, queued_transactions, transaction_queues};
+
+        let queue = transaction_queues::table
+            .inner_join(accounts::table)
+            .select(transaction_queues::all_columns)
+            .filter(accounts::address.eq(account.to_string()))
+            .first::<models::TransactionQueue>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("transaction_queue_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "transaction_queue_get",
+                entity: "transaction queue".to_string(),
+                key: account.to_string(),
+            })?;
+
+        let entries = queued_transactions::table
+            .filter(queued_transactions::account_id.eq(queue.account_id))
+            .filter(queued_transactions::status.ne_all([
+                QueuedTransactionStatus::Finalized.as_key_str(),
+                QueuedTransactionStatus::Skipped.as_key_str(),
+            ]))
+            .order_by(queued_transactions::sequence.asc())
+            .get_results::<models::QueuedTransaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_get", e))?;
+
+        Ok(AccountTransactionQueue {
+            account_address: account.clone(),
+            next_sequence: queue.next_sequence as u64,
+            paused_reason: queue.paused_reason,
+            entries: entries
+                .into_iter()
+                .map(|entry| entry.try_into_model())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn transaction_queue_get_enabled_accounts(&mut self) -> Result<Vec<SubstateId>, WalletStorageError> {
+        use crate::schema::{accounts, transaction_queues};
+
+        let addresses = transaction_queues::table
+            .inner_join(accounts::table)
+            .select(accounts::address)
+            .get_results::<String>(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_get_enabled_accounts", e))?;
+
+        addresses
+            .iter()
+            .map(|address| {
+                address.parse().map_err(|e: InvalidSubstateIdFormat| WalletStorageError::DecodingError {
+                    operation: "transaction_queue_get_enabled_accounts",
+                    item: "account address",
+                    details: e.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn transaction_queue_get_account_by_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<SubstateId, WalletStorageError> {
+        use crate::schema::{accounts, queued_transactions};
+
+        let address = queued_transactions::table
+            .inner_join(accounts::table)
+            .select(accounts::address)
+            .filter(queued_transactions::transaction_hash.eq(transaction_id.to_string()))
+            .first::<String>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("transaction_queue_get_account_by_transaction", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "transaction_queue_get_account_by_transaction",
+                entity: "queued transaction".to_string(),
+                key: transaction_id.to_string(),
+            })?;
+
+        address.parse().map_err(|e: InvalidSubstateIdFormat| WalletStorageError::DecodingError {
+            operation: "transaction_queue_get_account_by_transaction",
+            item: "account address",
+            details: e.to_string(),
+        })
+    }
+
+    // -------------------------------- Scheduled transactions -------------------------------- //
+    fn scheduled_transactions_get(&mut self, id: u64) -> Result<ScheduledTransaction, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let row = scheduled_transactions::table
+            .filter(scheduled_transactions::id.eq(id as i32))
+            .first::<models::ScheduledTransaction>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "scheduled_transactions_get",
+                entity: "scheduled transaction".to_string(),
+                key: id.to_string(),
+            })?;
+
+        row.try_into_model()
+    }
+
+    fn scheduled_transactions_fetch_all(
+        &mut self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let mut rows = scheduled_transactions::table.into_boxed();
+        if let Some(status) = status {
+            rows = rows.filter(scheduled_transactions::status.eq(status.as_key_str()));
+        }
+        let rows = rows
+            .order_by(scheduled_transactions::id.asc())
+            .get_results::<models::ScheduledTransaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_fetch_all", e))?;
+
+        rows.into_iter().map(|row| row.try_into_model()).collect()
+    }
+
     // -------------------------------- Substates -------------------------------- //
     fn substates_get(&mut self, address: &SubstateId) -> Result<SubstateModel, WalletStorageError> {
         use crate::schema::substates;
@@ -87,6 +87,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    queued_transactions (id) {
+        id -> Integer,
+        account_id -> Integer,
+        sequence -> BigInt,
+        transaction_hash -> Text,
+        status -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scheduled_transactions (id) {
+        id -> Integer,
+        unsigned_transaction -> Text,
+        required_substates -> Text,
+        signing_key_index -> BigInt,
+        trigger_timestamp -> Nullable<BigInt>,
+        trigger_epoch -> Nullable<BigInt>,
+        status -> Text,
+        transaction_hash -> Nullable<Text>,
+        failure_reason -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     substates (id) {
         id -> Integer,
@@ -100,6 +128,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transaction_queues (id) {
+        id -> Integer,
+        account_id -> Integer,
+        next_sequence -> BigInt,
+        paused_reason -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     transactions (id) {
         id -> Integer,
@@ -122,6 +161,7 @@ diesel::table! {
         finalized_time_ms -> Nullable<BigInt>,
         required_substates -> Text,
         new_account_info -> Nullable<Text>,
+        priority_fee -> BigInt,
     }
 }
 
@@ -146,6 +186,8 @@ diesel::joinable!(outputs -> accounts (account_id));
 diesel::joinable!(outputs -> vaults (vault_id));
 diesel::joinable!(proofs -> accounts (account_id));
 diesel::joinable!(proofs -> vaults (vault_id));
+diesel::joinable!(queued_transactions -> accounts (account_id));
+diesel::joinable!(transaction_queues -> accounts (account_id));
 diesel::joinable!(vaults -> accounts (account_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
@@ -156,7 +198,10 @@ diesel::allow_tables_to_appear_in_same_query!(
     non_fungible_tokens,
     outputs,
     proofs,
+    queued_transactions,
+    scheduled_transactions,
     substates,
+    transaction_queues,
     transactions,
     vaults,
 );
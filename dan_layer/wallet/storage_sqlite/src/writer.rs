@@ -22,6 +22,9 @@ use tari_dan_wallet_sdk::{
         NewAccountInfo,
         NonFungibleToken,
         OutputStatus,
+        QueuedTransactionStatus,
+        ScheduleTrigger,
+        ScheduledTransactionStatus,
         SubstateModel,
         TransactionStatus,
         VaultModel,
@@ -31,7 +34,7 @@ use tari_dan_wallet_sdk::{
 };
 use tari_engine_types::{commit_result::FinalizeResult, substate::SubstateId, TemplateAddress};
 use tari_template_lib::models::{Amount, EncryptedData};
-use tari_transaction::{SubstateRequirement, Transaction, TransactionId};
+use tari_transaction::{SubstateRequirement, Transaction, TransactionId, UnsignedTransaction};
 use tari_utilities::hex::Hex;
 
 use crate::{
@@ -56,6 +59,22 @@ impl<'a> WriteTransaction<'a> {
         }
     }
 
+    fn get_account_id(&mut self, account: &SubstateId, operation: &'static str) -> Result<i32, WalletStorageError> {
+        use crate::schema::accounts;
+
+        accounts::table
+            .select(accounts::id)
+            .filter(accounts::address.eq(account.to_string()))
+            .first::<i32>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general(operation, e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation,
+                entity: "account".to_string(),
+                key: account.to_string(),
+            })
+    }
+
     fn get_proof(&mut self, proof_id: ConfidentialProofId) -> Result<models::Proof, WalletStorageError> {
         use crate::schema::proofs;
 
@@ -283,6 +302,9 @@ impl WalletStoreWriter for WriteTransaction<'_> {
                 transactions::required_substates.eq(serialize_json(&required_substates)?),
                 transactions::new_account_info.eq(new_account_info.map(serialize_json).transpose()?),
                 transactions::dry_run.eq(is_dry_run),
+                transactions::min_epoch.eq(transaction.min_epoch().map(|epoch| epoch.as_u64() as i64)),
+                transactions::max_epoch.eq(transaction.max_epoch().map(|epoch| epoch.as_u64() as i64)),
+                transactions::priority_fee.eq(transaction.priority_fee() as i64),
             ))
             .execute(self.connection())
             .map_err(|e| WalletStorageError::general("transactions_insert", e))?;
@@ -329,6 +351,229 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         Ok(())
     }
 
+    fn transactions_set_signed(
+        &mut self,
+        pending_transaction_id: TransactionId,
+        signed_transaction: &Transaction,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::transactions;
+
+        let num_rows = diesel::update(transactions::table)
+            .set((
+                transactions::hash.eq(signed_transaction.id().to_string()),
+                transactions::sender_public_key.eq(signed_transaction.signer_public_key().to_hex()),
+                transactions::signature.eq(serialize_json(signed_transaction.signature().signature())?),
+                transactions::status.eq(TransactionStatus::New.as_key_str()),
+                transactions::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(transactions::hash.eq(pending_transaction_id.to_string()))
+            .filter(transactions::status.eq(TransactionStatus::PendingSignature.as_key_str()))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transactions_set_signed", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "transactions_set_signed",
+                entity: "transaction pending signature".to_string(),
+                key: pending_transaction_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------- Transaction queues -------------------------------- //
+    fn transaction_queue_enable(&mut self, account: &SubstateId) -> Result<(), WalletStorageError> {
+        use crate::schema::transaction_queues;
+
+        let account_id = self.get_account_id(account, "transaction_queue_enable")?;
+        diesel::insert_into(transaction_queues::table)
+            .values(transaction_queues::account_id.eq(account_id))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_enable", e))?;
+
+        Ok(())
+    }
+
+    fn transaction_queue_disable(&mut self, account: &SubstateId) -> Result<(), WalletStorageError> {
+        use crate::schema::{queued_transactions, transaction_queues};
+
+        let account_id = self.get_account_id(account, "transaction_queue_disable")?;
+        diesel::delete(queued_transactions::table.filter(queued_transactions::account_id.eq(account_id)))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_disable", e))?;
+        diesel::delete(transaction_queues::table.filter(transaction_queues::account_id.eq(account_id)))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_disable", e))?;
+
+        Ok(())
+    }
+
+    fn transaction_queue_push(
+        &mut self,
+        account: &SubstateId,
+        transaction_id: TransactionId,
+    ) -> Result<u64, WalletStorageError> {
+        use crate::schema::{queued_transactions, transaction_queues};
+
+        let account_id = self.get_account_id(account, "transaction_queue_push")?;
+        let sequence = transaction_queues::table
+            .select(transaction_queues::next_sequence)
+            .filter(transaction_queues::account_id.eq(account_id))
+            .first::<i64>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("transaction_queue_push", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "transaction_queue_push",
+                entity: "transaction queue".to_string(),
+                key: account.to_string(),
+            })?;
+
+        diesel::insert_into(queued_transactions::table)
+            .values((
+                queued_transactions::account_id.eq(account_id),
+                queued_transactions::sequence.eq(sequence),
+                queued_transactions::transaction_hash.eq(transaction_id.to_string()),
+                queued_transactions::status.eq(QueuedTransactionStatus::Queued.as_key_str()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_push", e))?;
+
+        diesel::update(transaction_queues::table)
+            .set((
+                transaction_queues::next_sequence.eq(sequence + 1),
+                transaction_queues::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(transaction_queues::account_id.eq(account_id))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_push", e))?;
+
+        Ok(sequence as u64)
+    }
+
+    fn transaction_queue_set_status(
+        &mut self,
+        transaction_id: TransactionId,
+        status: QueuedTransactionStatus,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::queued_transactions;
+
+        let num_rows = diesel::update(queued_transactions::table)
+            .set((
+                queued_transactions::status.eq(status.as_key_str()),
+                queued_transactions::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(queued_transactions::transaction_hash.eq(transaction_id.to_string()))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_set_status", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "transaction_queue_set_status",
+                entity: "queued transaction".to_string(),
+                key: transaction_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn transaction_queue_set_paused(
+        &mut self,
+        account: &SubstateId,
+        paused_reason: Option<&str>,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::transaction_queues;
+
+        let account_id = self.get_account_id(account, "transaction_queue_set_paused")?;
+        let num_rows = diesel::update(transaction_queues::table)
+            .set((
+                transaction_queues::paused_reason.eq(paused_reason),
+                transaction_queues::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(transaction_queues::account_id.eq(account_id))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_queue_set_paused", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "transaction_queue_set_paused",
+                entity: "transaction queue".to_string(),
+                key: account.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------- Scheduled transactions -------------------------------- //
+    fn scheduled_transactions_insert(
+        &mut self,
+        transaction: &UnsignedTransaction,
+        required_substates: &[SubstateRequirement],
+        signing_key_index: u64,
+        trigger: ScheduleTrigger,
+    ) -> Result<u64, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let (trigger_timestamp, trigger_epoch) = match trigger {
+            ScheduleTrigger::Time { timestamp } => (Some(timestamp as i64), None),
+            ScheduleTrigger::Epoch { epoch } => (None, Some(epoch.as_u64() as i64)),
+        };
+
+        diesel::insert_into(scheduled_transactions::table)
+            .values((
+                scheduled_transactions::unsigned_transaction.eq(serialize_json(transaction)?),
+                scheduled_transactions::required_substates.eq(serialize_json(required_substates)?),
+                scheduled_transactions::signing_key_index.eq(signing_key_index as i64),
+                scheduled_transactions::trigger_timestamp.eq(trigger_timestamp),
+                scheduled_transactions::trigger_epoch.eq(trigger_epoch),
+                scheduled_transactions::status.eq(ScheduledTransactionStatus::Scheduled.as_key_str()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_insert", e))?;
+
+        // RETURNING only available from SQLite 3.35 https://www.sqlite.org/lang_returning.html
+        let id = scheduled_transactions::table
+            .select(scheduled_transactions::id)
+            .order_by(scheduled_transactions::id.desc())
+            .first::<i32>(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_insert", e))?;
+
+        Ok(id as u64)
+    }
+
+    fn scheduled_transactions_set_status(
+        &mut self,
+        id: u64,
+        status: ScheduledTransactionStatus,
+        transaction_id: Option<TransactionId>,
+        failure_reason: Option<&str>,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let num_rows = diesel::update(scheduled_transactions::table)
+            .set((
+                scheduled_transactions::status.eq(status.as_key_str()),
+                scheduled_transactions::transaction_hash.eq(transaction_id.map(|id| id.to_string())),
+                scheduled_transactions::failure_reason.eq(failure_reason),
+                scheduled_transactions::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(scheduled_transactions::id.eq(id as i32))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_set_status", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "scheduled_transactions_set_status",
+                entity: "scheduled transaction".to_string(),
+                key: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     // -------------------------------- Substates -------------------------------- //
     fn substates_upsert_root(
         &mut self,
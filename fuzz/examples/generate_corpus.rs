@@ -0,0 +1,75 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Writes seed inputs for the fuzz targets to `corpus/<target>`. The seeds are valid encodings of the same genesis and
+//! dummy blocks that the consensus tests start from, so that the fuzzer begins with inputs that pass decoding.
+
+use std::{fs, io, path::Path};
+
+use prost::Message;
+use tari_common::configuration::Network;
+use tari_consensus::messages::{HotstuffMessage, NewViewMessage, ProposalMessage, SyncRequestMessage};
+use tari_dan_common_types::NodeHeight;
+use tari_dan_p2p::proto;
+use tari_dan_storage::consensus_models::{Block, GenesisConfig, QuorumCertificate};
+
+fn main() -> io::Result<()> {
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus");
+    let network = Network::LocalNet;
+
+    let genesis = Block::genesis(network, &GenesisConfig::default(), 0);
+    let zero_block = Block::zero_block(network);
+    let dummy = Block::dummy_block(
+        network,
+        *genesis.id(),
+        genesis.proposed_by().clone(),
+        genesis.height() + NodeHeight(1),
+        genesis.justify().clone(),
+        genesis.epoch(),
+        genesis.shard(),
+        *genesis.merkle_root(),
+        genesis.timestamp(),
+        genesis.base_layer_block_height(),
+        *genesis.base_layer_block_hash(),
+    );
+    let blocks = [("genesis", genesis), ("zero_block", zero_block), ("dummy", dummy)];
+
+    for (name, block) in &blocks {
+        write_seed(&corpus_dir.join("block"), name, proto::consensus::Block::from(block))?;
+        write_seed(
+            &corpus_dir.join("hotstuff_message"),
+            &format!("proposal_{}", name),
+            proto::consensus::HotStuffMessage::from(&HotstuffMessage::Proposal(ProposalMessage {
+                block: block.clone(),
+            })),
+        )?;
+    }
+
+    let high_qc = QuorumCertificate::genesis();
+    write_seed(
+        &corpus_dir.join("hotstuff_message"),
+        "new_view",
+        proto::consensus::HotStuffMessage::from(&HotstuffMessage::NewView(NewViewMessage {
+            high_qc: high_qc.clone(),
+            epoch: high_qc.epoch(),
+            new_height: high_qc.block_height() + NodeHeight(1),
+            last_vote: None,
+        })),
+    )?;
+    write_seed(
+        &corpus_dir.join("hotstuff_message"),
+        "sync_request",
+        proto::consensus::HotStuffMessage::from(&HotstuffMessage::SyncRequest(SyncRequestMessage {
+            epoch: high_qc.epoch(),
+            high_qc: high_qc.as_high_qc(),
+        })),
+    )?;
+
+    println!("Wrote seed corpus to {}", corpus_dir.display());
+    Ok(())
+}
+
+fn write_seed<M: Message>(dir: &Path, name: &str, msg: M) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(name), msg.encode_to_vec())
+}
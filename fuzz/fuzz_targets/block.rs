@@ -0,0 +1,14 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tari_dan_fuzz::check_decode_round_trip;
+use tari_dan_p2p::proto;
+use tari_dan_storage::consensus_models::Block;
+
+// Blocks are the largest part of proposals and sync responses, so they are also fuzzed on their own
+fuzz_target!(|data: &[u8]| {
+    check_decode_round_trip::<proto::consensus::Block, Block>(data);
+});
@@ -0,0 +1,14 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tari_consensus::messages::HotstuffMessage;
+use tari_dan_fuzz::check_decode_round_trip;
+use tari_dan_p2p::proto;
+
+// Every consensus message type is received as a HotStuffMessage
+fuzz_target!(|data: &[u8]| {
+    check_decode_round_trip::<proto::consensus::HotStuffMessage, HotstuffMessage>(data);
+});
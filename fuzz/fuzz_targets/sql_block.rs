@@ -0,0 +1,72 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tari_state_store_sqlite::{sql_models, BlobLimits};
+use time::{Date, PrimitiveDateTime, Time};
+
+/// The columns of a block row and its justify QC row that are decoded when a block is loaded
+#[derive(Debug, Arbitrary)]
+struct BlockRow {
+    block_id: String,
+    parent_block_id: String,
+    merkle_root: String,
+    network: String,
+    height: i64,
+    epoch: i64,
+    shard: i32,
+    proposed_by: String,
+    commands: String,
+    total_leader_fee: i64,
+    is_committed: bool,
+    is_processed: bool,
+    is_dummy: bool,
+    foreign_indexes: String,
+    signature: Option<String>,
+    block_time: Option<i64>,
+    timestamp: i64,
+    base_layer_block_height: i64,
+    base_layer_block_hash: String,
+    qc_json: String,
+}
+
+// A corrupt or tampered database row must be rejected with an error
+fuzz_target!(|row: BlockRow| {
+    let created_at = PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT);
+    let qc = sql_models::QuorumCertificate {
+        id: 1,
+        qc_id: String::new(),
+        block_id: row.block_id.clone(),
+        json: row.qc_json,
+        created_at,
+    };
+    let block = sql_models::Block {
+        id: 1,
+        block_id: row.block_id,
+        parent_block_id: row.parent_block_id,
+        merkle_root: row.merkle_root,
+        network: row.network,
+        height: row.height,
+        epoch: row.epoch,
+        shard: row.shard,
+        proposed_by: row.proposed_by,
+        qc_id: String::new(),
+        command_count: 0,
+        commands: row.commands,
+        total_leader_fee: row.total_leader_fee,
+        is_committed: row.is_committed,
+        is_processed: row.is_processed,
+        is_dummy: row.is_dummy,
+        foreign_indexes: row.foreign_indexes,
+        signature: row.signature,
+        block_time: row.block_time,
+        timestamp: row.timestamp,
+        base_layer_block_height: row.base_layer_block_height,
+        base_layer_block_hash: row.base_layer_block_hash,
+        created_at,
+    };
+    let _result = block.try_convert(qc, &BlobLimits::default());
+});
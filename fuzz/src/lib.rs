@@ -0,0 +1,33 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::Debug;
+
+use prost::Message;
+
+/// Decodes `data` as the protobuf message `P` and converts it to the domain type `T`. Neither step may panic. If both
+/// succeed, the value is re-encoded, decoded and converted again, which must succeed and produce the same bytes. The
+/// first re-encoding may differ from `data` because protobuf has many encodings for the same message, but re-encoding a
+/// decoded value must be canonical.
+pub fn check_decode_round_trip<P, T>(data: &[u8])
+where
+    P: Message + Default + for<'a> From<&'a T>,
+    T: TryFrom<P>,
+    T::Error: Debug,
+{
+    let Ok(msg) = P::decode(data) else {
+        return;
+    };
+    let Ok(value) = T::try_from(msg) else {
+        return;
+    };
+
+    let encoded = P::from(&value).encode_to_vec();
+    let msg = P::decode(encoded.as_slice()).expect("re-encoded message failed to decode");
+    let value = T::try_from(msg).expect("re-encoded message failed to convert");
+    assert_eq!(
+        P::from(&value).encode_to_vec(),
+        encoded,
+        "re-encoding a decoded value is not canonical"
+    );
+}
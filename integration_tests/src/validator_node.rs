@@ -149,7 +149,7 @@ pub async fn spawn_validator_node(
 
         // Add all other VNs as peer seeds
         config.peer_seeds.peer_seeds = StringList::from(peer_seeds);
-        run_validator_node(&config, shutdown_signal).await
+        run_validator_node(&config, None, shutdown_signal).await
     });
 
     // Wait for node to start up
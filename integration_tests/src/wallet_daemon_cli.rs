@@ -223,6 +223,7 @@ pub async fn transfer_confidential(
         inputs: vec![source_account_addr, dest_account_addr],
         min_epoch,
         max_epoch,
+        deferred_signing: false,
     };
 
     let submit_resp = client.submit_transaction(submit_req).await.unwrap();
@@ -453,6 +454,7 @@ pub async fn submit_manifest_with_signing_keys(
         inputs,
         min_epoch,
         max_epoch,
+        deferred_signing: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -526,6 +528,7 @@ pub async fn submit_manifest(
         inputs,
         min_epoch,
         max_epoch,
+        deferred_signing: false,
     };
 
     let mut client = get_auth_wallet_daemon_client(world, &wallet_daemon_name).await;
@@ -574,6 +577,7 @@ pub async fn submit_transaction(
         proof_ids: vec![],
         min_epoch,
         max_epoch,
+        deferred_signing: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -626,6 +630,7 @@ pub async fn create_component(
         inputs: vec![],
         min_epoch,
         max_epoch,
+        deferred_signing: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -672,6 +677,8 @@ pub async fn transfer(
         max_fee,
         proof_from_badge_resource: None,
         dry_run: false,
+        priority_fee: 0,
+        assert_balances: false,
     };
 
     let resp = client.accounts_transfer(request).await.unwrap();
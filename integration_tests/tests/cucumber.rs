@@ -501,14 +501,14 @@ async fn successful_transaction(world: &mut TariWorld) {
     for vn_ps in world.validator_nodes.values() {
         let mut client = vn_ps.create_client();
 
-        let request = GetRecentTransactionsRequest {};
+        let request = GetRecentTransactionsRequest::default();
         let recent_transactions_res = client.get_recent_transactions(request).await.unwrap();
 
         let recent_transactions = recent_transactions_res.transactions;
         // check that all transactions have succeeded
         for tx in &recent_transactions {
             let get_transaction_req = GetTransactionResultRequest {
-                transaction_id: *tx.id(),
+                transaction_id: tx.transaction_id,
             };
             let get_transaction_res = client
                 .get_transaction_result(get_transaction_req)
@@ -516,15 +516,13 @@ async fn successful_transaction(world: &mut TariWorld) {
                 .unwrap_or_else(|_| {
                     panic!(
                         "Failed to get transaction with hash {} for vn = {}",
-                        tx.id(),
-                        vn_ps.name
+                        tx.transaction_id, vn_ps.name
                     )
                 });
             let finalized_tx = get_transaction_res.result.unwrap_or_else(|| {
                 panic!(
                     "Transaction result was rejected for tx hash {} and vn = {}",
-                    tx.id(),
-                    vn_ps.name
+                    tx.transaction_id, vn_ps.name
                 )
             });
             finalized_tx.expect_success();
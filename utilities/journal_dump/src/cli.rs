@@ -0,0 +1,26 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Pretty-prints the records in a validator node consensus journal
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Cli {
+    /// The journal directory or a single journal file
+    pub path: PathBuf,
+    /// Only print records that refer to this block id
+    #[clap(long, short = 'b')]
+    pub block: Option<String>,
+    /// Only print records that refer to this transaction id
+    #[clap(long, short = 't')]
+    pub transaction: Option<String>,
+}
+
+impl Cli {
+    pub fn init() -> Self {
+        Self::parse()
+    }
+}
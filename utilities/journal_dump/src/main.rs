@@ -0,0 +1,75 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+mod cli;
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use cli::Cli;
+use tari_consensus::journal::{journal_files, JournalError, JournalReader, JournalRecord};
+use tari_crypto::tari_utilities::hex::from_hex;
+use tari_dan_storage::consensus_models::BlockId;
+use tari_transaction::TransactionId;
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::init();
+    let block_id = cli.block.as_deref().map(parse_block_id).transpose()?;
+    let transaction_id = cli
+        .transaction
+        .as_deref()
+        .map(|s| TransactionId::from_hex(s).map_err(|_| anyhow!("Invalid transaction id {}", s)))
+        .transpose()?;
+
+    let files = if cli.path.is_dir() {
+        journal_files(&cli.path)
+    } else {
+        vec![cli.path.clone()]
+    };
+    if files.is_empty() {
+        anyhow::bail!("No journal files found in {}", cli.path.display());
+    }
+
+    let mut num_records = 0;
+    let mut num_printed = 0;
+    for path in files {
+        for result in JournalReader::open(&path)? {
+            let record = match result {
+                Ok(record) => record,
+                // A node that crashed while writing leaves a truncated record at the end of the file
+                Err(err @ JournalError::TruncatedRecord { .. }) => {
+                    eprintln!("⚠️ {}: {}", path.display(), err);
+                    break;
+                },
+                Err(err) => return Err(err.into()),
+            };
+            num_records += 1;
+
+            if block_id.is_some_and(|id| !record.entry.involves_block(&id)) {
+                continue;
+            }
+            if transaction_id.is_some_and(|id| !record.entry.involves_transaction(&id)) {
+                continue;
+            }
+            print_record(&record);
+            num_printed += 1;
+        }
+    }
+
+    eprintln!("{} of {} record(s) shown", num_printed, num_records);
+    Ok(())
+}
+
+fn parse_block_id(s: &str) -> anyhow::Result<BlockId> {
+    let bytes = from_hex(s).map_err(|_| anyhow!("Invalid block id {}", s))?;
+    BlockId::try_from(bytes).map_err(|_| anyhow!("Invalid block id {}", s))
+}
+
+fn print_record(record: &JournalRecord) {
+    match i64::try_from(record.timestamp)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+    {
+        Some(timestamp) => println!("{} {}", timestamp.format("%Y-%m-%d %H:%M:%S%.3f"), record.entry),
+        None => println!("{} {}", record.timestamp, record.entry),
+    }
+}
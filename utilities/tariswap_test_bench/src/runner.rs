@@ -116,6 +116,7 @@ fn initialize_wallet_sdk<P: AsRef<Path>>(db_path: P, indexer_url: Url) -> Result
         password: None,
         jwt_expiry: Duration::from_secs(100_000),
         jwt_secret_key: "secret".to_string(),
+        key_domain: None,
     };
     let indexer = IndexerJsonRpcNetworkInterface::new(indexer_url);
     let wallet = DanWalletSdk::initialize(store, indexer, sdk_config)?;
@@ -4,6 +4,7 @@
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand};
+use transaction_generator::transaction_builders::synthetic::TransactionGeneratorConfig;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -41,7 +42,38 @@ pub struct WriteArgs {
     pub manifest_args_file: Option<PathBuf>,
     #[clap(long, short = 'k', alias = "signer")]
     pub signer_secret_key: Option<String>,
+    /// Generate synthetic transactions over a deterministic account set instead of free coin transactions
+    #[clap(long, conflicts_with = "manifest")]
+    pub synthetic: bool,
+    #[clap(long, default_value_t = 0)]
+    pub seed: u64,
+    #[clap(long, default_value_t = 10_000)]
+    pub num_accounts: usize,
+    /// The probability that a synthetic transaction shares an input with a previous transaction
+    #[clap(long, default_value_t = 0.0)]
+    pub conflict_ratio: f64,
+    #[clap(long, default_value_t = 1)]
+    pub min_instructions: usize,
+    #[clap(long, default_value_t = 1)]
+    pub max_instructions: usize,
+    #[clap(long, default_value_t = 1000)]
+    pub min_fee: u64,
+    #[clap(long, default_value_t = 1000)]
+    pub max_fee: u64,
 }
+
+impl WriteArgs {
+    pub fn synthetic_config(&self) -> TransactionGeneratorConfig {
+        TransactionGeneratorConfig {
+            seed: self.seed,
+            num_accounts: self.num_accounts,
+            conflict_ratio: self.conflict_ratio,
+            num_instructions: self.min_instructions..=self.max_instructions,
+            fee: self.min_fee..=self.max_fee,
+        }
+    }
+}
+
 #[derive(Args, Debug)]
 pub struct ReadArgs {
     #[clap(long, short = 'f')]
@@ -19,7 +19,7 @@ use tari_transaction_manifest::ManifestValue;
 use transaction_generator::{
     read_number_of_transactions,
     read_transactions,
-    transaction_builders::{free_coins, manifest},
+    transaction_builders::{free_coins, manifest, synthetic::TransactionGenerator},
     BoxedTransactionBuilder,
 };
 
@@ -41,7 +41,14 @@ fn main() -> anyhow::Result<()> {
 
             let mut file = std::fs::File::create(&args.output_file)?;
 
-            let builder = get_transaction_builder(&args)?;
+            let generator = args
+                .synthetic
+                .then(|| TransactionGenerator::new(args.synthetic_config()))
+                .transpose()?;
+            let builder = match generator.clone() {
+                Some(generator) => generator.into_builder(),
+                None => get_transaction_builder(&args)?,
+            };
             write_transactions(
                 args.num_transactions,
                 builder,
@@ -60,6 +67,9 @@ fn main() -> anyhow::Result<()> {
                 size,
                 timer.elapsed()
             );
+            if let Some(generator) = generator {
+                println!("Generated {}", generator.stats(args.num_transactions));
+            }
         },
         SubCommand::Read(args) => {
             let mut file = fs::File::open(args.input_file)?;
@@ -3,3 +3,4 @@
 
 pub mod free_coins;
 pub mod manifest;
+pub mod synthetic;
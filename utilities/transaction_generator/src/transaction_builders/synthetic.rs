@@ -0,0 +1,228 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashSet, fmt, ops::RangeInclusive};
+
+use anyhow::ensure;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tari_crypto::{
+    keys::{PublicKey, SecretKey},
+    ristretto::{RistrettoPublicKey, RistrettoSecretKey},
+};
+use tari_engine_types::component::new_account_address_from_parts;
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress},
+};
+use tari_transaction::Transaction;
+
+use crate::BoxedTransactionBuilder;
+
+const ACCOUNT_DOMAIN: u64 = 0;
+const TRANSACTION_DOMAIN: u64 = 1;
+const SIGNATURE_DOMAIN: u64 = 2;
+
+#[derive(Debug, Clone)]
+pub struct TransactionGeneratorConfig {
+    /// All accounts and transactions are derived from this seed
+    pub seed: u64,
+    /// The number of synthetic accounts. Transaction `i` pays its fee from account `i % num_accounts`, so transactions
+    /// start sharing accounts (and conflicting) once more than `num_accounts` transactions are generated.
+    pub num_accounts: usize,
+    /// The probability that a transaction also uses the account of a previously generated transaction as an input
+    pub conflict_ratio: f64,
+    /// The number of instructions in each transaction, not including the fee instruction
+    pub num_instructions: RangeInclusive<usize>,
+    /// The fee paid by each transaction
+    pub fee: RangeInclusive<u64>,
+}
+
+impl Default for TransactionGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            num_accounts: 10_000,
+            conflict_ratio: 0.0,
+            num_instructions: 1..=1,
+            fee: 1000..=1000,
+        }
+    }
+}
+
+/// A synthetic account that generated transactions use as inputs. The account is not created by the generator and
+/// must exist on the network (or in the test state store) for the generated transactions to succeed.
+#[derive(Debug, Clone)]
+pub struct SyntheticAccount {
+    pub owner_secret_key: RistrettoSecretKey,
+    pub owner_public_key: RistrettoPublicKey,
+    pub address: ComponentAddress,
+}
+
+/// Deterministically generates signed transactions over a set of synthetic accounts. Transaction `i` is a pure function
+/// of the seed and `i`, so transactions can be generated in parallel and in any order.
+#[derive(Debug, Clone)]
+pub struct TransactionGenerator {
+    config: TransactionGeneratorConfig,
+}
+
+impl TransactionGenerator {
+    pub fn new(config: TransactionGeneratorConfig) -> anyhow::Result<Self> {
+        ensure!(config.num_accounts > 0, "num_accounts must be greater than zero");
+        ensure!(
+            (0.0..=1.0).contains(&config.conflict_ratio),
+            "conflict_ratio must be between 0 and 1"
+        );
+        ensure!(!config.num_instructions.is_empty(), "num_instructions range is empty");
+        ensure!(!config.fee.is_empty(), "fee range is empty");
+        Ok(Self { config })
+    }
+
+    pub fn config(&self) -> &TransactionGeneratorConfig {
+        &self.config
+    }
+
+    pub fn account(&self, index: usize) -> SyntheticAccount {
+        let mut rng = self.rng(ACCOUNT_DOMAIN, index as u64);
+        let owner_secret_key = RistrettoSecretKey::random(&mut rng);
+        let owner_public_key = RistrettoPublicKey::from_secret_key(&owner_secret_key);
+        let address = new_account_address_from_parts(&ACCOUNT_TEMPLATE_ADDRESS, &owner_public_key);
+        SyntheticAccount {
+            owner_secret_key,
+            owner_public_key,
+            address,
+        }
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = SyntheticAccount> + '_ {
+        (0..self.config.num_accounts).map(|i| self.account(i))
+    }
+
+    pub fn generate(&self, index: u64) -> Transaction {
+        let plan = self.plan(index);
+        let account = self.account(plan.account);
+        let conflicting_account = plan.conflicting_account.map(|i| self.account(i));
+
+        let mut builder = Transaction::builder()
+            .add_input(account.address)
+            .fee_transaction_pay_from_component(account.address, Amount::try_from(plan.fee).unwrap());
+        if let Some(conflicting_account) = &conflicting_account {
+            builder = builder.add_input(conflicting_account.address).call_method(
+                conflicting_account.address,
+                "get_balances",
+                args![],
+            );
+        }
+        let num_instructions = plan.num_instructions - usize::from(conflicting_account.is_some());
+        for _ in 0..num_instructions {
+            builder = builder.call_method(account.address, "get_balances", args![]);
+        }
+
+        builder
+            .sign_with_rng(&account.owner_secret_key, &mut self.rng(SIGNATURE_DOMAIN, index))
+            .build()
+    }
+
+    /// Returns statistics for the first `num_transactions` generated transactions. A transaction is counted as
+    /// conflicting if it shares an input with any transaction generated before it.
+    pub fn stats(&self, num_transactions: u64) -> GeneratorStats {
+        let mut used_accounts = HashSet::new();
+        let mut stats = GeneratorStats::default();
+        for index in 0..num_transactions {
+            let plan = self.plan(index);
+            let mut is_conflicting = !used_accounts.insert(plan.account);
+            if let Some(account) = plan.conflicting_account {
+                is_conflicting |= !used_accounts.insert(account);
+            }
+
+            stats.num_transactions += 1;
+            stats.num_conflicting += u64::from(is_conflicting);
+            stats.total_instructions += plan.num_instructions as u64;
+            stats.total_fees += plan.fee;
+        }
+        stats
+    }
+
+    pub fn into_builder(self) -> BoxedTransactionBuilder {
+        Box::new(move |index| self.generate(index))
+    }
+
+    fn plan(&self, index: u64) -> TransactionPlan {
+        let mut rng = self.rng(TRANSACTION_DOMAIN, index);
+        let num_accounts = self.config.num_accounts as u64;
+        // The first transaction has no previous transaction to conflict with
+        let conflicting_account = (index > 0 && rng.gen_bool(self.config.conflict_ratio))
+            .then(|| (rng.gen_range(0..index) % num_accounts) as usize)
+            .filter(|account| *account != (index % num_accounts) as usize);
+        let num_instructions = rng
+            .gen_range(self.config.num_instructions.clone())
+            .max(usize::from(conflicting_account.is_some()));
+
+        TransactionPlan {
+            account: (index % num_accounts) as usize,
+            conflicting_account,
+            num_instructions,
+            fee: rng.gen_range(self.config.fee.clone()),
+        }
+    }
+
+    fn rng(&self, domain: u64, index: u64) -> StdRng {
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&self.config.seed.to_le_bytes());
+        seed[8..16].copy_from_slice(&domain.to_le_bytes());
+        seed[16..24].copy_from_slice(&index.to_le_bytes());
+        StdRng::from_seed(seed)
+    }
+}
+
+struct TransactionPlan {
+    account: usize,
+    conflicting_account: Option<usize>,
+    num_instructions: usize,
+    fee: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeneratorStats {
+    pub num_transactions: u64,
+    pub num_conflicting: u64,
+    pub total_instructions: u64,
+    pub total_fees: u64,
+}
+
+impl GeneratorStats {
+    pub fn conflict_ratio(&self) -> f64 {
+        if self.num_transactions == 0 {
+            return 0.0;
+        }
+        self.num_conflicting as f64 / self.num_transactions as f64
+    }
+
+    pub fn mean_instructions(&self) -> f64 {
+        if self.num_transactions == 0 {
+            return 0.0;
+        }
+        self.total_instructions as f64 / self.num_transactions as f64
+    }
+
+    pub fn mean_fee(&self) -> f64 {
+        if self.num_transactions == 0 {
+            return 0.0;
+        }
+        self.total_fees as f64 / self.num_transactions as f64
+    }
+}
+
+impl fmt::Display for GeneratorStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} transactions, {} conflicting ({:.2}%), {:.2} instructions/tx, {:.2} fee/tx",
+            self.num_transactions,
+            self.num_conflicting,
+            self.conflict_ratio() * 100.0,
+            self.mean_instructions(),
+            self.mean_fee()
+        )
+    }
+}
@@ -0,0 +1,81 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_transaction::UnsignedTransaction;
+use transaction_generator::transaction_builders::synthetic::{TransactionGenerator, TransactionGeneratorConfig};
+
+fn create_generator(seed: u64, conflict_ratio: f64) -> TransactionGenerator {
+    TransactionGenerator::new(TransactionGeneratorConfig {
+        seed,
+        conflict_ratio,
+        num_instructions: 1..=5,
+        fee: 100..=2000,
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn it_generates_the_same_transactions_for_the_same_seed() {
+    let generator1 = create_generator(123, 0.5);
+    let generator2 = create_generator(123, 0.5);
+    let other_seed = create_generator(124, 0.5);
+
+    // Generation order does not matter
+    for index in (0..50).rev() {
+        let tx1 = generator1.generate(index);
+        let tx2 = generator2.generate(index);
+        assert_eq!(tx1.id(), tx2.id());
+        assert_ne!(tx1.id(), other_seed.generate(index).id());
+    }
+    assert_eq!(generator1.stats(1000), generator2.stats(1000));
+}
+
+#[test]
+fn it_generates_valid_signed_transactions() {
+    let generator = create_generator(1, 0.5);
+    for index in 0..20 {
+        let tx = generator.generate(index);
+        assert!(tx.check_id());
+        assert!(tx.signature().verify(&UnsignedTransaction::from(&tx)));
+        assert!(!tx.inputs().is_empty());
+        assert!((1..=5).contains(&tx.instructions().len()));
+    }
+}
+
+#[test]
+fn it_generates_roughly_the_requested_conflict_ratio() {
+    const NUM_TRANSACTIONS: u64 = 5000;
+    for conflict_ratio in [0.0, 0.1, 0.5, 0.9, 1.0] {
+        let stats = create_generator(42, conflict_ratio).stats(NUM_TRANSACTIONS);
+        assert_eq!(stats.num_transactions, NUM_TRANSACTIONS);
+        assert!(
+            (stats.conflict_ratio() - conflict_ratio).abs() < 0.05,
+            "requested conflict ratio {} but generated {}",
+            conflict_ratio,
+            stats.conflict_ratio()
+        );
+        assert!((100.0..=2000.0).contains(&stats.mean_fee()));
+        assert!((1.0..=5.0).contains(&stats.mean_instructions()));
+    }
+}
+
+#[test]
+fn it_reports_conflicts_once_accounts_are_reused() {
+    let generator = TransactionGenerator::new(TransactionGeneratorConfig {
+        num_accounts: 10,
+        ..Default::default()
+    })
+    .unwrap();
+    let stats = generator.stats(25);
+    assert_eq!(stats.num_conflicting, 15);
+}
+
+#[test]
+fn it_rejects_an_invalid_config() {
+    let result = TransactionGenerator::new(TransactionGeneratorConfig {
+        conflict_ratio: 1.5,
+        ..Default::default()
+    });
+    assert!(result.is_err());
+}